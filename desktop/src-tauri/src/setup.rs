@@ -166,6 +166,8 @@ struct YamlOutput {
     #[serde(skip_serializing_if = "Option::is_none")]
     nats: Option<NatsSection>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    ipc: Option<IpcSection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     chat: Option<ChatSection>,
     #[serde(skip_serializing_if = "Option::is_none")]
     embeddings: Option<EmbeddingsSection>,
@@ -179,6 +181,12 @@ struct NatsSection {
     url: Option<String>,
 }
 
+/// Local IPC event sync, written instead of the NATS section when NATS is disabled.
+#[derive(Debug, Serialize)]
+struct IpcSection {
+    enabled: bool,
+}
+
 #[derive(Debug, Serialize)]
 struct ServerSection {
     port: u16,
@@ -666,6 +674,9 @@ pub fn generate_config(config: SetupConfig) -> Result<String, String> {
         })
     };
 
+    // Without NATS, keep the desktop processes in sync over a local socket
+    let ipc = nats.is_none().then_some(IpcSection { enabled: true });
+
     // Build public_url for YAML (None if empty)
     let public_url = if config.public_url.trim().is_empty() {
         None
@@ -693,6 +704,7 @@ pub fn generate_config(config: SetupConfig) -> Result<String, String> {
             key: meilisearch_key,
         },
         nats,
+        ipc,
         chat: Some(ChatSection {
             default_model: config.chat_model.clone(),
             max_sessions: config.chat_max_sessions,
@@ -1227,6 +1239,7 @@ pub fn generate_default_config() -> Result<PathBuf, String> {
             key: random_secret(32),
         },
         nats: None,
+        ipc: None,
         chat: None,
         embeddings: None,
         auth: None, // no-auth mode — wizard can load freely
@@ -647,10 +647,7 @@ pub async fn get_me(
     let is_root = state
         .auth_config
         .as_ref()
-        .and_then(|c| c.root_account.as_ref())
-        .is_some_and(|root| {
-            Uuid::new_v5(&Uuid::NAMESPACE_URL, root.email.as_bytes()) == user.user_id
-        });
+        .is_some_and(|c| c.is_root_user(user.user_id));
 
     // Try to fetch full user from Neo4j (may have updated picture, etc.)
     match state
@@ -1009,7 +1006,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
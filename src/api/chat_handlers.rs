@@ -2,13 +2,16 @@
 
 use crate::api::handlers::{AppError, OrchestratorState};
 use crate::api::query::{PaginatedResponse, PaginationParams};
+use crate::api::validation::ValidPath;
+use crate::chat::manager::ActiveSessionInfo;
 use crate::chat::types::{
     ChatLinkedPlan, ChatLinkedRfc, ChatLinkedTask, ChatRequest, ChatSession, CreateSessionResponse,
     MessageSearchResult,
 };
 use crate::events::{CrudAction, CrudEvent, EntityType, EventEmitter};
 use axum::{
-    extract::{Path, Query, State},
+    extract::{Query, State},
+    http::StatusCode,
     Json,
 };
 use serde::{Deserialize, Serialize};
@@ -35,6 +38,29 @@ pub async fn create_session(
         .as_ref()
         .ok_or_else(|| AppError::Internal(anyhow::anyhow!("Chat manager not initialized")))?;
 
+    // Start from a prompt template: it becomes the first message
+    if let Some(template) = request.template.take() {
+        request.message = super::prompt_template_handlers::render_for_session(
+            state.orchestrator.neo4j(),
+            &template,
+            &request.message,
+        )
+        .await?;
+    }
+
+    let resuming_active = match &request.session_id {
+        Some(id) => chat_manager.is_session_active(id).await,
+        None => false,
+    };
+    if !resuming_active {
+        super::quota_handlers::check_chat_quota(
+            &state,
+            request.project_slug.as_deref(),
+            request.workspace_slug.as_deref(),
+        )
+        .await?;
+    }
+
     let response = chat_manager
         .create_session(&request)
         .await
@@ -81,7 +107,7 @@ fn default_messages_limit() -> usize {
 /// metadata. The frontend reconstructs the ChatMessage UI model from these events.
 pub async fn list_messages(
     State(state): State<OrchestratorState>,
-    Path(session_id): Path<Uuid>,
+    ValidPath(session_id): ValidPath<Uuid>,
     Query(query): Query<MessagesQuery>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let chat_manager = state
@@ -184,6 +210,8 @@ fn session_node_to_response(s: crate::neo4j::models::ChatSessionNode) -> ChatSes
         preview: s.preview,
         permission_mode: s.permission_mode,
         add_dirs: s.add_dirs,
+        sandbox: s.sandbox.and_then(|sb| serde_json::from_str(&sb).ok()),
+        limits: s.limits.and_then(|l| serde_json::from_str(&l).ok()),
         spawned_by: s.spawned_by.and_then(|sb| serde_json::from_str(&sb).ok()),
         linked_plans: Vec::new(),
         linked_tasks: Vec::new(),
@@ -331,7 +359,7 @@ pub async fn list_sessions(
 /// GET /api/chat/sessions/{id} — Get session details (enriched with linked plans/tasks/RFCs)
 pub async fn get_session(
     State(state): State<OrchestratorState>,
-    Path(session_id): Path<Uuid>,
+    ValidPath(session_id): ValidPath<Uuid>,
 ) -> Result<Json<ChatSession>, AppError> {
     let neo4j = state.orchestrator.neo4j();
 
@@ -354,7 +382,7 @@ pub async fn get_session(
 /// GET /api/chat/sessions/{id}/children — Get child sessions spawned by this session
 pub async fn get_session_children(
     State(state): State<OrchestratorState>,
-    Path(session_id): Path<Uuid>,
+    ValidPath(session_id): ValidPath<Uuid>,
 ) -> Result<Json<Vec<ChatSession>>, AppError> {
     let children = state
         .orchestrator
@@ -371,7 +399,7 @@ pub async fn get_session_children(
 /// GET /api/chat/sessions/{id}/tree — Get the full session tree rooted at this session
 pub async fn get_session_tree(
     State(state): State<OrchestratorState>,
-    Path(session_id): Path<Uuid>,
+    ValidPath(session_id): ValidPath<Uuid>,
 ) -> Result<Json<Vec<crate::neo4j::models::SessionTreeNode>>, AppError> {
     let tree = state
         .orchestrator
@@ -385,7 +413,7 @@ pub async fn get_session_tree(
 /// GET /api/chat/runs/{run_id}/sessions — Get all sessions for a PlanRun
 pub async fn get_run_sessions(
     State(state): State<OrchestratorState>,
-    Path(run_id): Path<Uuid>,
+    ValidPath(run_id): ValidPath<Uuid>,
 ) -> Result<Json<Vec<crate::neo4j::models::SessionInfo>>, AppError> {
     let sessions = state
         .orchestrator
@@ -399,12 +427,14 @@ pub async fn get_run_sessions(
 /// DELETE /api/chat/sessions/{id} — Delete a session
 pub async fn delete_session(
     State(state): State<OrchestratorState>,
-    Path(session_id): Path<Uuid>,
+    ValidPath(session_id): ValidPath<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     // Close active session if running
     if let Some(chat_manager) = &state.chat_manager {
         let _ = chat_manager.close_session(&session_id.to_string()).await;
     }
+    crate::chat::attachments::remove_session_dir(&session_id.to_string()).await;
+    crate::chat::sandbox::release_resource_limits(&session_id.to_string());
 
     // Delete from Neo4j
     let deleted = state
@@ -475,7 +505,7 @@ pub async fn delete_session(
 /// the SIGINT happens remotely.
 pub async fn cancel_tools(
     State(state): State<OrchestratorState>,
-    Path(session_id): Path<Uuid>,
+    ValidPath(session_id): ValidPath<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let chat_manager = state.chat_manager.as_ref().ok_or_else(|| {
         AppError::NotFound("chat_manager not configured on this server".to_string())
@@ -489,6 +519,102 @@ pub async fn cancel_tools(
     Ok(Json(serde_json::to_value(&result).unwrap_or_default()))
 }
 
+// ============================================================================
+// Active sessions
+// ============================================================================
+
+/// GET /api/chat/sessions/active — Sessions running on this instance.
+///
+/// Unlike `GET /api/chat/sessions`, which lists what is stored in Neo4j,
+/// this reads the chat manager: one entry per live CLI subprocess, with its
+/// streaming state and usage (`ActiveSessionInfo`), most recently active
+/// first. Used by `orchestrator tui`.
+///
+/// ## Response codes
+///
+/// **200** — Always. An empty array means no session is running.
+///
+/// **404** — `chat_manager` not configured.
+pub async fn list_active_sessions(
+    State(state): State<OrchestratorState>,
+) -> Result<Json<Vec<ActiveSessionInfo>>, AppError> {
+    let chat_manager = state.chat_manager.as_ref().ok_or_else(|| {
+        AppError::NotFound("chat_manager not configured on this server".to_string())
+    })?;
+
+    Ok(Json(chat_manager.active_sessions_info().await))
+}
+
+// ============================================================================
+// Kill switch
+// ============================================================================
+
+/// POST /api/chat/sessions/{id}/kill — Kill a runaway session outright.
+///
+/// Sends `SIGKILL` to the CLI subprocess and every descendant, then closes
+/// the session (see `ChatManager::kill_session`). Connected clients get a
+/// `session_error` event with reason `"session_killed"`. The session stays
+/// in Neo4j but refuses new messages until `POST .../reset-usage`.
+///
+/// ## Response codes
+///
+/// **200** with `KillSessionResult { cli_pid, killed_pids }`.
+///
+/// **404** — `chat_manager` not configured, or the session is not active
+/// on this instance.
+pub async fn kill_session(
+    State(state): State<OrchestratorState>,
+    ValidPath(session_id): ValidPath<Uuid>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let chat_manager = state.chat_manager.as_ref().ok_or_else(|| {
+        AppError::NotFound("chat_manager not configured on this server".to_string())
+    })?;
+
+    let result = chat_manager
+        .kill_session(&session_id.to_string(), None)
+        .await
+        .map_err(AppError::Internal)?
+        .ok_or_else(|| AppError::NotFound(format!("Session {} is not active", session_id)))?;
+
+    Ok(Json(serde_json::to_value(&result).unwrap_or_default()))
+}
+
+/// POST /api/chat/sessions/{id}/reset-usage — Clear a session's usage.
+///
+/// The session resumes with fresh usage counters, and a killed session
+/// accepts messages again (see `ChatManager::reset_session_usage`).
+///
+/// ## Response codes
+///
+/// **200** with the `ChatSessionUsage` cleared.
+///
+/// **404** — `chat_manager` not configured, or the session does not exist.
+///
+/// **409** — the session is active.
+pub async fn reset_session_usage(
+    State(state): State<OrchestratorState>,
+    ValidPath(session_id): ValidPath<Uuid>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let chat_manager = state.chat_manager.as_ref().ok_or_else(|| {
+        AppError::NotFound("chat_manager not configured on this server".to_string())
+    })?;
+    let session_id = session_id.to_string();
+    if chat_manager.is_session_active(&session_id).await {
+        return Err(AppError::Conflict(format!(
+            "Session {} is active — kill or close it first",
+            session_id
+        )));
+    }
+
+    let usage = chat_manager
+        .reset_session_usage(&session_id)
+        .await
+        .map_err(AppError::Internal)?
+        .ok_or_else(|| AppError::NotFound(format!("Session {} not found", session_id)))?;
+
+    Ok(Json(serde_json::to_value(&usage).unwrap_or_default()))
+}
+
 // ============================================================================
 // Background tasks (T6 + T8 of plan 754a1379)
 // ============================================================================
@@ -512,7 +638,7 @@ pub async fn cancel_tools(
 /// chat support).
 pub async fn get_background_tasks(
     State(state): State<OrchestratorState>,
-    Path(session_id): Path<Uuid>,
+    ValidPath(session_id): ValidPath<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let chat_manager = state.chat_manager.as_ref().ok_or_else(|| {
         AppError::NotFound("chat_manager not configured on this server".to_string())
@@ -554,7 +680,7 @@ pub async fn get_background_tasks(
 /// **404** — `chat_manager` not configured.
 pub async fn cancel_task(
     State(state): State<OrchestratorState>,
-    Path((session_id, task_id)): Path<(Uuid, String)>,
+    ValidPath((session_id, task_id)): ValidPath<(Uuid, String)>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let chat_manager = state.chat_manager.as_ref().ok_or_else(|| {
         AppError::NotFound("chat_manager not configured on this server".to_string())
@@ -580,7 +706,7 @@ pub struct UpdateSessionRequest {
 /// PATCH /api/chat/sessions/{id} — Update a session (currently: rename)
 pub async fn update_session(
     State(state): State<OrchestratorState>,
-    Path(session_id): Path<Uuid>,
+    ValidPath(session_id): ValidPath<Uuid>,
     Json(request): Json<UpdateSessionRequest>,
 ) -> Result<Json<ChatSession>, AppError> {
     let updated = state
@@ -619,6 +745,10 @@ pub async fn update_session(
         preview: updated.preview,
         permission_mode: updated.permission_mode,
         add_dirs: updated.add_dirs,
+        sandbox: updated
+            .sandbox
+            .and_then(|sb| serde_json::from_str(&sb).ok()),
+        limits: updated.limits.and_then(|l| serde_json::from_str(&l).ok()),
         spawned_by: updated
             .spawned_by
             .and_then(|sb| serde_json::from_str(&sb).ok()),
@@ -950,8 +1080,41 @@ pub async fn get_cli_auth_status() -> Json<crate::chat::cli_auth::CliAuthStatus>
 /// npm version, whether an update is available, and whether it's a local build.
 /// The npm version check has a 10s timeout built-in — will return `latest_version: null`
 /// if npm is unreachable.
-pub async fn get_cli_status() -> Json<crate::chat::cli_version::CliVersionStatus> {
-    Json(crate::chat::cli_version::check_cli_status().await)
+pub async fn get_cli_status(
+    State(state): State<OrchestratorState>,
+) -> Json<crate::chat::cli_version::CliVersionStatus> {
+    let pinned = pinned_cli_path(&state).await;
+    Json(crate::chat::cli_version::check_cli_status(pinned.as_deref()).await)
+}
+
+/// The CLI path pinned in the chat config, if any.
+async fn pinned_cli_path(state: &OrchestratorState) -> Option<String> {
+    match state.chat_manager {
+        Some(ref chat_manager) => chat_manager.get_env_config().await.claude_cli_path,
+        None => None,
+    }
+}
+
+/// GET /api/chat/health — Whether chat sessions can spawn the Claude CLI.
+///
+/// Checks the pinned binary (or the one found on PATH) runs and is at least
+/// the minimum supported version. Returns 503 with the reason otherwise, and
+/// when the chat manager is not initialized.
+pub async fn get_chat_health(
+    State(state): State<OrchestratorState>,
+) -> (StatusCode, Json<crate::chat::cli_version::CliHealth>) {
+    let pinned = pinned_cli_path(&state).await;
+    let mut health = crate::chat::cli_version::check_cli_health(pinned.as_deref()).await;
+    if state.chat_manager.is_none() {
+        health.available = false;
+        health.error = Some("Chat manager not initialized".to_string());
+    }
+    let status = if health.available {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(health))
 }
 
 /// Request body for POST /api/chat/cli/install
@@ -992,7 +1155,7 @@ pub struct DiscussedEntityInput {
 /// POST /api/chat/sessions/{id}/discussed — Add DISCUSSED relations
 pub async fn add_discussed(
     State(state): State<OrchestratorState>,
-    Path(session_id): Path<Uuid>,
+    ValidPath(session_id): ValidPath<Uuid>,
     Json(body): Json<AddDiscussedRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     if body.entities.is_empty() {
@@ -1018,7 +1181,7 @@ pub async fn add_discussed(
 /// GET /api/chat/sessions/{id}/discussed — Get entities discussed in a session
 pub async fn get_session_entities(
     State(state): State<OrchestratorState>,
-    Path(session_id): Path<Uuid>,
+    ValidPath(session_id): ValidPath<Uuid>,
     Query(params): Query<SessionEntitiesQuery>,
 ) -> Result<Json<Vec<crate::neo4j::models::DiscussedEntity>>, AppError> {
     let project_id = params
@@ -1043,6 +1206,194 @@ pub struct SessionEntitiesQuery {
     pub project_id: Option<String>,
 }
 
+// ============================================================================
+// Context feedback (thumbs up/down on surfaced context items)
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct ContextFeedbackRequest {
+    pub item_type: crate::neo4j::models::ContextItemType,
+    /// Note UUID or symbol name
+    pub item_id: String,
+    pub helpful: bool,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ContextFeedbackResponse {
+    pub feedback: crate::neo4j::models::ContextFeedbackNode,
+    /// Learned weight of the note across all sessions (notes only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relevance_weight: Option<f64>,
+    /// Whether the note is no longer surfaced (notes only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suppressed: Option<bool>,
+}
+
+/// POST /api/chat/sessions/{id}/context-feedback — Rate a context item
+pub async fn submit_context_feedback(
+    State(state): State<OrchestratorState>,
+    ValidPath(session_id): ValidPath<Uuid>,
+    Json(body): Json<ContextFeedbackRequest>,
+) -> Result<Json<ContextFeedbackResponse>, AppError> {
+    use crate::feedback::relevance::RelevanceWeights;
+    use crate::neo4j::models::{ContextFeedbackNode, ContextItemType};
+
+    let item_id = body.item_id.trim().to_string();
+    let note_id = match body.item_type {
+        ContextItemType::Note => Some(item_id.parse::<Uuid>().map_err(|_| {
+            AppError::BadRequest(format!("item_id `{}` is not a note id", item_id))
+        })?),
+        ContextItemType::Symbol if item_id.is_empty() => {
+            return Err(AppError::BadRequest("item_id is required".to_string()))
+        }
+        ContextItemType::Symbol => None,
+    };
+    let feedback = ContextFeedbackNode {
+        session_id,
+        item_type: body.item_type,
+        item_id,
+        helpful: body.helpful,
+        comment: body.comment.filter(|c| !c.trim().is_empty()),
+        created_at: chrono::Utc::now(),
+    };
+
+    let graph = state.orchestrator.neo4j();
+    if !graph
+        .upsert_context_feedback(&feedback)
+        .await
+        .map_err(AppError::Internal)?
+    {
+        return Err(AppError::NotFound(format!(
+            "Session {} not found",
+            session_id
+        )));
+    }
+
+    let (relevance_weight, suppressed) = match note_id {
+        Some(id) => {
+            let weights = RelevanceWeights::load(graph, &[id]).await;
+            (Some(weights.weight(id)), Some(weights.is_suppressed(id)))
+        }
+        None => (None, None),
+    };
+    Ok(Json(ContextFeedbackResponse {
+        feedback,
+        relevance_weight,
+        suppressed,
+    }))
+}
+
+/// GET /api/chat/sessions/{id}/context-feedback — Votes recorded in a session
+pub async fn list_context_feedback(
+    State(state): State<OrchestratorState>,
+    ValidPath(session_id): ValidPath<Uuid>,
+) -> Result<Json<Vec<crate::neo4j::models::ContextFeedbackNode>>, AppError> {
+    let feedback = state
+        .orchestrator
+        .neo4j()
+        .list_context_feedback(session_id)
+        .await
+        .map_err(AppError::Internal)?;
+    Ok(Json(feedback))
+}
+
+// ============================================================================
+// Undo log (graph mutations made by the session's agent)
+// ============================================================================
+
+/// An undo log entry that could not be undone
+#[derive(Debug, Serialize)]
+pub struct FailedUndo {
+    pub entry: crate::neo4j::models::UndoEntry,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RollbackResponse {
+    /// Entries undone, newest first
+    pub undone: Vec<crate::neo4j::models::UndoEntry>,
+    pub failed: Vec<FailedUndo>,
+}
+
+/// Undo log of a session, 404 when the session does not exist
+async fn session_undo_log(
+    state: &OrchestratorState,
+    session_id: Uuid,
+) -> Result<Vec<crate::neo4j::models::UndoEntry>, AppError> {
+    let graph = state.orchestrator.neo4j();
+    if graph
+        .get_chat_session(session_id)
+        .await
+        .map_err(AppError::Internal)?
+        .is_none()
+    {
+        return Err(AppError::NotFound(format!(
+            "Session {} not found",
+            session_id
+        )));
+    }
+    graph
+        .list_undo_entries(session_id)
+        .await
+        .map_err(AppError::Internal)
+}
+
+/// GET /api/chat/sessions/{id}/undo-log — Mutations recorded for a session
+pub async fn get_undo_log(
+    State(state): State<OrchestratorState>,
+    ValidPath(session_id): ValidPath<Uuid>,
+) -> Result<Json<Vec<crate::neo4j::models::UndoEntry>>, AppError> {
+    Ok(Json(session_undo_log(&state, session_id).await?))
+}
+
+/// POST /api/chat/sessions/{id}/undo-last — Undo the latest mutation not
+/// undone yet
+pub async fn undo_last(
+    State(state): State<OrchestratorState>,
+    ValidPath(session_id): ValidPath<Uuid>,
+) -> Result<Json<crate::neo4j::models::UndoEntry>, AppError> {
+    let mut entry = session_undo_log(&state, session_id)
+        .await?
+        .into_iter()
+        .find(|e| e.undone_at.is_none())
+        .ok_or_else(|| AppError::NotFound("Nothing to undo in this session".to_string()))?;
+    crate::chat::undo::undo(&state.orchestrator, &entry)
+        .await
+        .map_err(|e| AppError::Conflict(format!("Cannot undo '{}': {}", entry.summary, e)))?;
+    entry.undone_at = Some(chrono::Utc::now());
+    Ok(Json(entry))
+}
+
+/// POST /api/chat/sessions/{id}/rollback — Undo every mutation of a session,
+/// newest first. Entries that cannot be undone are reported and skipped.
+pub async fn rollback_session(
+    State(state): State<OrchestratorState>,
+    ValidPath(session_id): ValidPath<Uuid>,
+) -> Result<Json<RollbackResponse>, AppError> {
+    let mut response = RollbackResponse {
+        undone: Vec::new(),
+        failed: Vec::new(),
+    };
+    for mut entry in session_undo_log(&state, session_id).await? {
+        if entry.undone_at.is_some() {
+            continue;
+        }
+        match crate::chat::undo::undo(&state.orchestrator, &entry).await {
+            Ok(()) => {
+                entry.undone_at = Some(chrono::Utc::now());
+                response.undone.push(entry);
+            }
+            Err(e) => response.failed.push(FailedUndo {
+                entry,
+                error: e.to_string(),
+            }),
+        }
+    }
+    Ok(Json(response))
+}
+
 // ============================================================================
 // Plan ↔ Session linking
 // ============================================================================
@@ -1050,7 +1401,7 @@ pub struct SessionEntitiesQuery {
 /// GET /api/plans/{id}/sessions — Get all chat sessions linked to a plan
 pub async fn get_plan_sessions(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
+    ValidPath(plan_id): ValidPath<Uuid>,
 ) -> Result<Json<Vec<crate::neo4j::models::SessionWithLinks>>, AppError> {
     let sessions = state
         .orchestrator
@@ -1064,7 +1415,7 @@ pub async fn get_plan_sessions(
 /// GET /api/tasks/{id}/sessions — Get all chat sessions linked to a task
 pub async fn get_task_sessions(
     State(state): State<OrchestratorState>,
-    Path(task_id): Path<Uuid>,
+    ValidPath(task_id): ValidPath<Uuid>,
 ) -> Result<Json<Vec<crate::neo4j::models::SessionWithLinks>>, AppError> {
     let sessions = state
         .orchestrator
@@ -1090,7 +1441,7 @@ fn default_associate_source() -> String {
 /// POST /api/chat/sessions/{id}/associate — Create ASSOCIATED_WITH relation
 pub async fn associate_session(
     State(state): State<OrchestratorState>,
-    Path(session_id): Path<Uuid>,
+    ValidPath(session_id): ValidPath<Uuid>,
     Json(body): Json<AssociateSessionRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     // Validate entity_type
@@ -1183,7 +1534,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -1230,7 +1584,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -1264,6 +1621,25 @@ mod tests {
         assert_eq!(query.project_slug.as_deref(), Some("my-project"));
     }
 
+    #[tokio::test]
+    async fn test_chat_health_without_chat_manager() {
+        let app = test_app().await;
+        let resp = app.oneshot(auth_get("/api/chat/health")).await.unwrap();
+
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["available"], false);
+        assert_eq!(json["pinned"], false);
+        assert_eq!(
+            json["min_supported_version"],
+            crate::chat::cli_version::MIN_SUPPORTED_CLI_VERSION
+        );
+        assert!(json["error"].is_string());
+    }
+
     // ====================================================================
     // GET /api/chat/sessions — list
     // ====================================================================
@@ -1319,6 +1695,146 @@ mod tests {
         assert_eq!(json["total"], 2);
     }
 
+    // ====================================================================
+    // /api/chat/sessions/{id}/context-feedback
+    // ====================================================================
+
+    #[tokio::test]
+    async fn test_context_feedback() {
+        let session = test_chat_session(Some("my-proj"));
+        let session_id = session.id;
+        let app = test_app_with_sessions(&[session]).await;
+        let uri = format!("/api/chat/sessions/{}/context-feedback", session_id);
+        let note_id = Uuid::new_v4();
+        let vote = |helpful: bool| {
+            format!(
+                r#"{{"item_type":"note","item_id":"{}","helpful":{}}}"#,
+                note_id, helpful
+            )
+        };
+
+        let resp = app
+            .clone()
+            .oneshot(auth_post(&uri, &vote(true)))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        // Voting again on the same item replaces the vote
+        let resp = app
+            .clone()
+            .oneshot(auth_post(&uri, &vote(false)))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(json["relevance_weight"].as_f64().unwrap() < 1.0);
+        assert_eq!(json["suppressed"], false);
+
+        let symbol = r#"{"item_type":"symbol","item_id":"build_prompt","helpful":true}"#;
+        let resp = app.clone().oneshot(auth_post(&uri, symbol)).await.unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(json.get("relevance_weight").is_none());
+
+        let resp = app.clone().oneshot(auth_get(&uri)).await.unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 2);
+
+        let bad = r#"{"item_type":"note","item_id":"nope","helpful":true}"#;
+        let resp = app.clone().oneshot(auth_post(&uri, bad)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let unknown = format!("/api/chat/sessions/{}/context-feedback", Uuid::new_v4());
+        let resp = app.oneshot(auth_post(&unknown, &vote(true))).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    // ====================================================================
+    // /api/chat/sessions/{id}/undo-last, /rollback, /undo-log
+    // ====================================================================
+
+    #[tokio::test]
+    async fn test_undo_session_mutations() {
+        let session = test_chat_session(Some("my-proj"));
+        let session_id = session.id;
+        let app = test_app_with_sessions(&[session]).await;
+        let agent_request = |method: &str, uri: &str, body: &str| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .header("authorization", test_bearer_token())
+                .header("x-session-id", session_id.to_string())
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+        let json_of = |resp: axum::response::Response| async move {
+            let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+
+        // The agent creates a note, then rewrites it
+        let resp = app
+            .clone()
+            .oneshot(agent_request(
+                "POST",
+                "/api/notes",
+                r#"{"note_type":"tip","content":"Use the builder"}"#,
+            ))
+            .await
+            .unwrap();
+        let note_id = json_of(resp).await["id"].as_str().unwrap().to_string();
+        let note_uri = format!("/api/notes/{}", note_id);
+        let resp = app
+            .clone()
+            .oneshot(agent_request(
+                "PATCH",
+                &note_uri,
+                r#"{"content":"Rewritten"}"#,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let log_uri = format!("/api/chat/sessions/{}/undo-log", session_id);
+        let log = json_of(app.clone().oneshot(auth_get(&log_uri)).await.unwrap()).await;
+        assert_eq!(log[0]["action"], "note.update");
+        assert_eq!(log[1]["action"], "note.create");
+
+        // Undoing the last mutation restores the content
+        let undo_uri = format!("/api/chat/sessions/{}/undo-last", session_id);
+        let resp = app.clone().oneshot(auth_post(&undo_uri, "")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(json_of(resp).await["action"], "note.update");
+        let note = json_of(app.clone().oneshot(auth_get(&note_uri)).await.unwrap()).await;
+        assert_eq!(note["content"], "Use the builder");
+
+        // Rolling back the session undoes what is left: the creation
+        let rollback_uri = format!("/api/chat/sessions/{}/rollback", session_id);
+        let resp = app
+            .clone()
+            .oneshot(auth_post(&rollback_uri, ""))
+            .await
+            .unwrap();
+        let json = json_of(resp).await;
+        assert_eq!(json["undone"].as_array().unwrap().len(), 1);
+        assert!(json["failed"].as_array().unwrap().is_empty());
+        let resp = app.clone().oneshot(auth_get(&note_uri)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let resp = app.oneshot(auth_post(&undo_uri, "")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
     // ====================================================================
     // GET /api/chat/sessions/{id} — get
     // ====================================================================
@@ -1437,6 +1953,46 @@ mod tests {
         assert_eq!(default_messages_limit(), 50);
     }
 
+    // ====================================================================
+    // GET /api/chat/sessions/active — no chat_manager
+    // ====================================================================
+
+    #[tokio::test]
+    async fn test_list_active_sessions_no_chat_manager() {
+        let app = test_app().await;
+
+        let resp = app
+            .oneshot(auth_get("/api/chat/sessions/active"))
+            .await
+            .unwrap();
+
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    // ====================================================================
+    // POST /api/chat/sessions/{id}/kill — no chat_manager
+    // ====================================================================
+
+    #[tokio::test]
+    async fn test_reset_session_usage_no_chat_manager() {
+        let app = test_app().await;
+        let uri = format!("/api/chat/sessions/{}/reset-usage", Uuid::new_v4());
+
+        let resp = app.oneshot(auth_post(&uri, "")).await.unwrap();
+
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_kill_session_no_chat_manager() {
+        let app = test_app().await;
+        let uri = format!("/api/chat/sessions/{}/kill", Uuid::new_v4());
+
+        let resp = app.oneshot(auth_post(&uri, "")).await.unwrap();
+
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
     // ====================================================================
     // GET /api/chat/sessions/{id}/messages — no chat_manager
     // ====================================================================
@@ -1467,8 +2023,8 @@ mod tests {
             .await
             .unwrap();
 
-        // Invalid UUID in path → 400 Bad Request
-        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        // Invalid UUID in path → 422 naming the parameter
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
     }
 
     // ====================================================================
@@ -1661,7 +2217,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
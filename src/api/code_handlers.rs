@@ -5,17 +5,23 @@
 
 use axum::{
     extract::{Path, Query, State},
+    http::HeaderMap,
     Json,
 };
 use serde::{Deserialize, Serialize};
 
 use super::handlers::{AppError, OrchestratorState};
+use super::search_analytics_handlers::record_search;
+use super::validation::{
+    validate_file_path, ValidJson, ValidQuery, Validate, Validator, MAX_QUERY_LEN, MAX_TITLE_LEN,
+};
 use crate::analytics::distribution::{adaptive_threshold, analyze_distribution};
 use crate::analytics::hypothesis::test_community_homogeneity;
 use crate::events::EventEmitter;
 use crate::graph::algorithms::into_ranked;
 use crate::graph::models::{FusionWeights, MultiSignalImpact, MultiSignalScore, RankedList};
 use crate::neo4j::models::{ConnectedFileNode, DecisionNode};
+use crate::parser::clones::{find_clone_groups, CloneGroup, DEFAULT_CLONE_THRESHOLD};
 
 // ============================================================================
 // Code Search (Meilisearch)
@@ -33,6 +39,19 @@ pub struct CodeSearchQuery {
     pub project_slug: Option<String>,
     /// Filter by workspace slug (searches all projects in the workspace)
     pub workspace_slug: Option<String>,
+    /// Also search archived projects (unless `project_slug` names one, they
+    /// are left out)
+    #[serde(default)]
+    pub include_archived: bool,
+}
+
+impl Validate for CodeSearchQuery {
+    fn validate(&self, v: &mut Validator) {
+        v.max_len("query", &self.query, MAX_QUERY_LEN);
+        if let Some(limit) = self.limit {
+            v.range("limit", limit, 1, 100);
+        }
+    }
 }
 
 /// Search response with both legacy hits and ranked view (Plan 10).
@@ -66,13 +85,14 @@ fn build_search_result(
 /// projects in the workspace, merges results by score, and truncates to `limit`.
 pub async fn search_code(
     State(state): State<OrchestratorState>,
-    Query(params): Query<CodeSearchQuery>,
+    headers: HeaderMap,
+    ValidQuery(params): ValidQuery<CodeSearchQuery>,
 ) -> Result<Json<CodeSearchResult>, AppError> {
     let limit = params.limit.unwrap_or(10);
 
-    // If project_slug is given, use it directly (backward compat)
-    if params.project_slug.is_some() {
-        let hits = state
+    let mut hits = if params.project_slug.is_some() {
+        // If project_slug is given, use it directly (backward compat)
+        state
             .orchestrator
             .meili()
             .search_code_with_scores(
@@ -82,12 +102,9 @@ pub async fn search_code(
                 params.project_slug.as_deref(),
                 None,
             )
-            .await?;
-        return Ok(Json(build_search_result(hits)));
-    }
-
-    // If workspace_slug is given, resolve to project slugs and merge results
-    if let Some(ref ws_slug) = params.workspace_slug {
+            .await?
+    } else if let Some(ref ws_slug) = params.workspace_slug {
+        // If workspace_slug is given, resolve to project slugs and merge results
         let workspace = state
             .orchestrator
             .neo4j()
@@ -102,7 +119,10 @@ pub async fn search_code(
             .await?;
 
         let mut all_hits = Vec::new();
-        for project in &projects {
+        for project in projects
+            .iter()
+            .filter(|p| params.include_archived || !p.archived)
+        {
             let hits = state
                 .orchestrator
                 .meili()
@@ -125,18 +145,165 @@ pub async fn search_code(
                 .unwrap_or(std::cmp::Ordering::Equal)
         });
         all_hits.truncate(limit);
+        all_hits
+    } else {
+        // No filter — global search
+        let archived = if params.include_archived {
+            Default::default()
+        } else {
+            crate::orchestrator::archived_project_slugs(state.orchestrator.neo4j()).await?
+        };
+        crate::orchestrator::search_unarchived_code(
+            state.orchestrator.meili(),
+            &archived,
+            &params.query,
+            limit,
+            params.language.as_deref(),
+        )
+        .await?
+    };
+
+    for hit in hits.iter_mut().filter(|h| !h.highlights.is_empty()) {
+        if let Ok(symbols) = state
+            .orchestrator
+            .neo4j()
+            .list_file_symbols(&hit.document.path)
+            .await
+        {
+            estimate_code_lines(&mut hit.highlights, &symbols);
+        }
+    }
+
+    record_search(
+        &state,
+        &headers,
+        "code",
+        &params.query,
+        params.project_slug.as_deref(),
+        hits.len(),
+    );
+    Ok(Json(build_search_result(hits)))
+}
+
+/// Estimate the line of each highlight of a code hit from the symbols of its
+/// file: the first symbol named after a highlighted word, else the first
+/// whose docstring mentions one.
+fn estimate_code_lines(
+    highlights: &mut [crate::meilisearch::indexes::SearchHighlight],
+    symbols: &[crate::neo4j::models::SymbolLocationNode],
+) {
+    for highlight in highlights {
+        let terms = highlight.terms();
+        let mentions = |text: &str| {
+            let text = text.to_lowercase();
+            terms.iter().any(|t| text.contains(t.as_str()))
+        };
+        highlight.line = symbols
+            .iter()
+            .find(|s| mentions(&s.name))
+            .or_else(|| {
+                symbols
+                    .iter()
+                    .find(|s| s.docstring.as_deref().is_some_and(mentions))
+            })
+            .map(|s| s.line_start as usize);
+    }
+}
+
+// ============================================================================
+// Documentation Search (Meilisearch)
+// ============================================================================
+
+#[derive(Deserialize)]
+pub struct DocsSearchQuery {
+    /// Search query (headings, section text and document titles)
+    pub query: String,
+    /// Max results (default 10)
+    pub limit: Option<usize>,
+    /// Filter by project slug
+    pub project_slug: Option<String>,
+}
 
-        return Ok(Json(build_search_result(all_hits)));
+impl Validate for DocsSearchQuery {
+    fn validate(&self, v: &mut Validator) {
+        v.max_len("query", &self.query, MAX_QUERY_LEN);
+        if let Some(limit) = self.limit {
+            v.range("limit", limit, 1, 100);
+        }
     }
+}
 
-    // No filter — global search
+/// Search the Markdown documentation indexed at sync time.
+///
+/// Each hit is one section, with its document path, anchor and start line so
+/// it can be cited as `path#anchor`.
+pub async fn search_docs(
+    State(state): State<OrchestratorState>,
+    headers: HeaderMap,
+    ValidQuery(params): ValidQuery<DocsSearchQuery>,
+) -> Result<
+    Json<
+        Vec<
+            crate::meilisearch::indexes::SearchHit<crate::meilisearch::indexes::DocSectionDocument>,
+        >,
+    >,
+    AppError,
+> {
     let hits = state
         .orchestrator
         .meili()
-        .search_code_with_scores(&params.query, limit, params.language.as_deref(), None, None)
+        .search_docs_with_scores(
+            &params.query,
+            params.limit.unwrap_or(10),
+            params.project_slug.as_deref(),
+        )
         .await?;
+    record_search(
+        &state,
+        &headers,
+        "docs",
+        &params.query,
+        params.project_slug.as_deref(),
+        hits.len(),
+    );
+    Ok(Json(hits))
+}
 
-    Ok(Json(build_search_result(hits)))
+// ============================================================================
+// Configuration Keys
+// ============================================================================
+
+#[derive(Deserialize)]
+pub struct ConfigKeysQuery {
+    /// Project slug (required)
+    pub project_slug: String,
+    /// Keep keys containing this text, case-insensitive (`MEILISEARCH_URL`,
+    /// `meilisearch`, `server.port`)
+    pub key: Option<String>,
+    /// Max keys (default 20)
+    pub limit: Option<usize>,
+}
+
+/// Find where configuration keys are defined and consumed.
+///
+/// Keys are cross-referenced at sync time between the `.env`, YAML, TOML and
+/// JSON files defining them and the files naming them (string literals,
+/// `process.env.NAME`, `${NAME}` interpolations).
+pub async fn find_config_usages(
+    State(state): State<OrchestratorState>,
+    Query(params): Query<ConfigKeysQuery>,
+) -> Result<Json<Vec<crate::neo4j::models::ConfigKeyNode>>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let project = neo4j
+        .get_project_by_slug(&params.project_slug)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project not found: {}", params.project_slug)))?;
+
+    let mut keys = neo4j
+        .list_project_config_keys(project.id, params.key.as_deref())
+        .await?;
+    keys.truncate(params.limit.unwrap_or(20));
+    Ok(Json(keys))
 }
 
 // ============================================================================
@@ -154,6 +321,8 @@ pub struct FileSymbols {
 
 #[derive(Serialize)]
 pub struct FunctionSummary {
+    /// Graph id, accepted by `/api/code/snippet`
+    pub id: String,
     pub name: String,
     pub signature: String,
     pub line: u32,
@@ -165,10 +334,14 @@ pub struct FunctionSummary {
 
 #[derive(Serialize)]
 pub struct StructSummary {
+    /// Graph id, accepted by `/api/code/snippet`
+    pub id: String,
     pub name: String,
     pub line: u32,
     pub is_public: bool,
     pub docstring: Option<String>,
+    /// Declared fields (Rust structs)
+    pub fields: Vec<crate::neo4j::models::FieldNode>,
 }
 
 /// Get all symbols defined in a file without reading the entire file
@@ -179,6 +352,7 @@ pub async fn get_file_symbols(
     let file_path = urlencoding::decode(&file_path)
         .map_err(|e| AppError::BadRequest(e.to_string()))?
         .to_string();
+    validate_file_path("file_path", &file_path)?;
 
     let language = state
         .orchestrator
@@ -196,6 +370,7 @@ pub async fn get_file_symbols(
     let functions: Vec<FunctionSummary> = func_nodes
         .into_iter()
         .map(|f| FunctionSummary {
+            id: format!("{}:{}:{}", file_path, f.name, f.line),
             name: f.name,
             signature: f.signature,
             line: f.line,
@@ -215,10 +390,12 @@ pub async fn get_file_symbols(
     let structs: Vec<StructSummary> = struct_nodes
         .into_iter()
         .map(|s| StructSummary {
+            id: format!("{}:{}", file_path, s.name),
             name: s.name,
             line: s.line,
             is_public: s.is_public,
             docstring: s.docstring,
+            fields: s.fields,
         })
         .collect();
 
@@ -237,6 +414,792 @@ pub async fn get_file_symbols(
     }))
 }
 
+// ============================================================================
+// Code Snippet
+// ============================================================================
+
+/// Surrounding lines returned at most on each side
+const MAX_CONTEXT_LINES: usize = 100;
+
+#[derive(Deserialize)]
+pub struct SnippetQuery {
+    /// Function/Struct/Trait/Enum id (`path:name:line_start` for functions,
+    /// `path:name` for types)
+    pub symbol_id: String,
+    /// Lines of context before and after the symbol (default 0, max 100)
+    pub context_lines: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct CodeSnippet {
+    pub symbol_id: String,
+    pub name: String,
+    /// `function`, `struct`, `trait` or `enum`
+    pub kind: String,
+    pub language: Option<String>,
+    pub file_path: String,
+    /// Lines of the symbol itself (1-based, inclusive)
+    pub line_start: u32,
+    pub line_end: u32,
+    /// Source of the symbol, exactly as on disk
+    pub source: String,
+    /// Context lines before and after the symbol
+    pub before: String,
+    pub after: String,
+    /// First and last line of `before` + `source` + `after`
+    pub snippet_start: u32,
+    pub snippet_end: u32,
+    pub total_lines: u32,
+    pub docstring: Option<String>,
+    /// The file changed since the last sync: line numbers may be off
+    pub stale: bool,
+}
+
+/// Split `content` into the lines before, within and after
+/// `line_start..=line_end` (1-based), with up to `context` lines around.
+/// Returns `(before, source, after, snippet_start, snippet_end)`, or `None`
+/// when the range is outside the file.
+fn slice_lines(
+    content: &str,
+    line_start: u32,
+    line_end: u32,
+    context: usize,
+) -> Option<(String, String, String, u32, u32)> {
+    let lines: Vec<&str> = content.lines().collect();
+    let start = (line_start.max(1) - 1) as usize;
+    let end = (line_end as usize).max(start + 1).min(lines.len());
+    if start >= lines.len() {
+        return None;
+    }
+    let first = start.saturating_sub(context);
+    let last = (end + context).min(lines.len());
+    Some((
+        lines[first..start].join("\n"),
+        lines[start..end].join("\n"),
+        lines[end..last].join("\n"),
+        first as u32 + 1,
+        last as u32,
+    ))
+}
+
+/// Read the file defining a symbol, if it is under an allowed project root
+/// (see `get_code_snippet`)
+async fn read_symbol_file(
+    neo4j: &dyn crate::neo4j::traits::GraphStore,
+    location: &crate::neo4j::models::SymbolLocationNode,
+) -> Result<String, AppError> {
+    let roots: Vec<String> = match &location.project_root {
+        Some(root) => vec![root.clone()],
+        None => neo4j
+            .list_projects()
+            .await?
+            .into_iter()
+            .map(|p| p.root_path)
+            .collect(),
+    };
+    let file = tokio::fs::canonicalize(&location.file_path)
+        .await
+        .map_err(|_| AppError::NotFound(format!("File not found: {}", location.file_path)))?;
+    let mut allowed = false;
+    for root in &roots {
+        if let Ok(root) = tokio::fs::canonicalize(crate::expand_tilde(root)).await {
+            if file.starts_with(&root) {
+                allowed = true;
+                break;
+            }
+        }
+    }
+    if !allowed {
+        return Err(AppError::Forbidden(format!(
+            "{} is outside the project roots",
+            location.file_path
+        )));
+    }
+
+    tokio::fs::read_to_string(&file)
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Cannot read {}: {}", location.file_path, e)))
+}
+
+/// Return the source of one symbol, read from disk, with optional context
+/// lines — instead of fetching the whole file.
+///
+/// Only files under a registered project root are read: the root of the
+/// project containing the file, or of any project when the file is not linked
+/// to one.
+pub async fn get_code_snippet(
+    State(state): State<OrchestratorState>,
+    Query(params): Query<SnippetQuery>,
+) -> Result<Json<CodeSnippet>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let location = neo4j
+        .get_symbol_location(&params.symbol_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Symbol not found: {}", params.symbol_id)))?;
+
+    let content = read_symbol_file(neo4j, &location).await?;
+    let context = params.context_lines.unwrap_or(0).min(MAX_CONTEXT_LINES);
+    let (before, source, after, snippet_start, snippet_end) =
+        slice_lines(&content, location.line_start, location.line_end, context).ok_or_else(
+            || {
+                AppError::Conflict(format!(
+                    "{} has fewer than {} lines, re-sync the project",
+                    location.file_path, location.line_start
+                ))
+            },
+        )?;
+
+    let stale = location.file_hash.as_deref().is_some_and(|hash| {
+        use sha2::{Digest, Sha256};
+        hex::encode(Sha256::digest(content.as_bytes())) != hash
+    });
+
+    Ok(Json(CodeSnippet {
+        symbol_id: location.id,
+        name: location.name,
+        kind: location.kind,
+        language: location.language,
+        file_path: location.file_path,
+        line_start: location.line_start,
+        line_end: location.line_end,
+        source,
+        before,
+        after,
+        snippet_start,
+        snippet_end,
+        total_lines: content.lines().count() as u32,
+        docstring: location.docstring,
+        stale,
+    }))
+}
+
+// ============================================================================
+// Rename Proposal
+// ============================================================================
+
+/// Edits listed at most; a rename touching more is better done by tooling
+const MAX_RENAME_EDITS: usize = 2000;
+/// Larger files are generated code, not worth scanning
+const MAX_RENAME_FILE_BYTES: u64 = 1024 * 1024;
+
+#[derive(Deserialize)]
+pub struct RenameQuery {
+    /// Function/Struct/Trait/Enum id, as for `/api/code/snippet`
+    pub symbol_id: String,
+    pub new_name: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RenameEdit {
+    pub file_path: String,
+    /// 1-based
+    pub line: u32,
+    /// 1-based, in characters
+    pub column: u32,
+    pub line_text: String,
+    /// `definition`; `graph` in a file the graph links to the symbol
+    /// (CALLS, USES_TYPE, IMPORTS_SYMBOL); `text` for a textual match only,
+    /// which may be another symbol with the same name
+    pub source: String,
+}
+
+#[derive(Serialize)]
+pub struct RenameProposal {
+    pub symbol_id: String,
+    pub kind: String,
+    pub old_name: String,
+    pub new_name: String,
+    /// Ordered by file, line and column
+    pub edits: Vec<RenameEdit>,
+    pub file_count: usize,
+    /// Lines of the edited files already using `new_name`: the rename may
+    /// clash or shadow
+    pub conflicts: Vec<RenameEdit>,
+    /// Graph references in files with no textual occurrence of the name
+    /// (macros, aliased imports): check them by hand
+    pub unmatched_references: Vec<crate::neo4j::models::SymbolReferenceNode>,
+    /// More than 2000 edits; the list is cut
+    pub truncated: bool,
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// 1-based character columns where `word` appears in `line` as a whole
+/// identifier
+fn word_columns(line: &str, word: &str) -> Vec<u32> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    line.match_indices(word)
+        .filter(|(i, _)| {
+            let before = line[..*i].chars().next_back();
+            let after = line[i + word.len()..].chars().next();
+            !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+        })
+        .map(|(i, _)| line[..i].chars().count() as u32 + 1)
+        .collect()
+}
+
+/// List every place a rename of one symbol has to edit, without editing.
+///
+/// Files are the symbol's own, those the graph links to it (callers, type
+/// users, importers) and, as a textual search, every indexed file of its
+/// project containing the name as a whole word. Occurrences in comments and
+/// strings are listed too.
+pub async fn propose_rename(
+    State(state): State<OrchestratorState>,
+    Query(params): Query<RenameQuery>,
+) -> Result<Json<RenameProposal>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let location = neo4j
+        .get_symbol_location(&params.symbol_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Symbol not found: {}", params.symbol_id)))?;
+    let old_name = location.name.clone();
+    let new_name = params.new_name.trim().to_string();
+    if !is_identifier(&new_name) {
+        return Err(AppError::BadRequest(format!(
+            "{:?} is not a valid identifier",
+            new_name
+        )));
+    }
+    if new_name == old_name {
+        return Err(AppError::BadRequest(format!(
+            "{} is already named {}",
+            params.symbol_id, new_name
+        )));
+    }
+
+    let root = location
+        .project_root
+        .as_deref()
+        .map(|r| crate::expand_tilde(r).trim_end_matches('/').to_string());
+    let in_project = |path: &str| {
+        root.as_deref()
+            .is_none_or(|r| path.starts_with(&format!("{}/", r)))
+    };
+
+    let references: Vec<_> = neo4j
+        .find_symbol_dependents(&params.symbol_id, MAX_RENAME_EDITS)
+        .await?
+        .into_iter()
+        .filter(|r| in_project(&r.file_path))
+        .collect();
+    let graph_files: std::collections::BTreeSet<String> = references
+        .iter()
+        .map(|r| r.file_path.clone())
+        .chain(std::iter::once(location.file_path.clone()))
+        .collect();
+
+    let mut files: std::collections::BTreeSet<String> = graph_files.clone();
+    if let Some(root) = &root {
+        files.extend(
+            crate::orchestrator::runner::scan_files(std::path::Path::new(root))
+                .into_iter()
+                .filter(|e| e.size <= MAX_RENAME_FILE_BYTES)
+                .map(|e| e.path),
+        );
+    }
+
+    let mut edits = Vec::new();
+    let mut conflicts = Vec::new();
+    let mut definition_found = false;
+    for path in &files {
+        let Ok(content) = tokio::fs::read_to_string(path).await else {
+            continue;
+        };
+        if !content.contains(old_name.as_str()) {
+            continue;
+        }
+        let file_source = if graph_files.contains(path) {
+            "graph"
+        } else {
+            "text"
+        };
+        let mut file_edits = Vec::new();
+        for (i, line) in content.lines().enumerate() {
+            let line_no = i as u32 + 1;
+            for column in word_columns(line, &old_name) {
+                let is_definition = !definition_found
+                    && *path == location.file_path
+                    && (location.line_start..=location.line_end.max(location.line_start))
+                        .contains(&line_no);
+                definition_found |= is_definition;
+                file_edits.push(RenameEdit {
+                    file_path: path.clone(),
+                    line: line_no,
+                    column,
+                    line_text: line.to_string(),
+                    source: if is_definition {
+                        "definition"
+                    } else {
+                        file_source
+                    }
+                    .to_string(),
+                });
+            }
+            if let Some(&column) = word_columns(line, &new_name).first() {
+                conflicts.push(RenameEdit {
+                    file_path: path.clone(),
+                    line: line_no,
+                    column,
+                    line_text: line.to_string(),
+                    source: file_source.to_string(),
+                });
+            }
+        }
+        // Conflicts only matter in files the rename touches
+        if file_edits.is_empty() {
+            conflicts.retain(|c| c.file_path != *path);
+        }
+        edits.extend(file_edits);
+    }
+
+    let edited: std::collections::BTreeSet<&str> =
+        edits.iter().map(|e| e.file_path.as_str()).collect();
+    let unmatched_references = references
+        .iter()
+        .filter(|r| !edited.contains(r.file_path.as_str()))
+        .cloned()
+        .collect();
+    let file_count = edited.len();
+    let truncated = edits.len() > MAX_RENAME_EDITS;
+    edits.truncate(MAX_RENAME_EDITS);
+
+    Ok(Json(RenameProposal {
+        symbol_id: params.symbol_id,
+        kind: location.kind,
+        old_name,
+        new_name,
+        edits,
+        file_count,
+        conflicts,
+        unmatched_references,
+        truncated,
+    }))
+}
+
+// ============================================================================
+// Symbol Explanation
+// ============================================================================
+
+/// Lines of the definition returned at most
+const MAX_EXPLAIN_SOURCE_LINES: u32 = 60;
+/// Callers, callees, notes and same-name candidates listed at most
+const MAX_EXPLAIN_ITEMS: usize = 20;
+/// Commits of the defining file listed
+const EXPLAIN_COMMIT_COUNT: i64 = 5;
+
+#[derive(Deserialize)]
+pub struct ExplainQuery {
+    /// Graph id (as for `/api/code/snippet`) or exact symbol name
+    pub symbol: String,
+    /// Resolve names within this project only
+    pub project_slug: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ExplainedNote {
+    pub id: uuid::Uuid,
+    pub note_type: crate::notes::NoteType,
+    pub status: crate::notes::NoteStatus,
+    pub importance: crate::notes::NoteImportance,
+    pub content: String,
+    /// `symbol` when attached to the symbol itself, `file` for its file
+    pub linked_to: String,
+}
+
+#[derive(Serialize)]
+pub struct SymbolExplanation {
+    pub symbol: crate::neo4j::models::SymbolLocationNode,
+    /// Functions only
+    pub signature: Option<String>,
+    pub complexity: Option<u32>,
+    /// Source of the definition, cut after 60 lines; `None` when the file
+    /// cannot be read
+    pub source: Option<String>,
+    pub source_truncated: bool,
+    /// Direct callers and callees (functions only)
+    pub callers: Vec<CallGraphEdge>,
+    pub caller_count: i64,
+    pub callees: Vec<CallGraphEdge>,
+    /// Traits implemented, for a struct or enum
+    pub implements: Vec<crate::neo4j::models::TypeTraitInfoNode>,
+    /// Implementing types, for a trait
+    pub implementors: Vec<crate::neo4j::models::TraitImplementorNode>,
+    /// Notes of the symbol and of its file, except obsolete and archived ones
+    pub notes: Vec<ExplainedNote>,
+    /// Latest commits touching the defining file
+    pub recent_commits: Vec<crate::neo4j::models::FileHistoryEntry>,
+    /// GDS analytics of the function, or of the file for types
+    pub analytics: Option<crate::neo4j::models::NodeAnalyticsRow>,
+    /// Other symbols with the same name, when `symbol` was a name
+    pub other_matches: Vec<String>,
+}
+
+/// Notes attached to a symbol or to its file, except obsolete and archived
+/// ones (best-effort).
+async fn symbol_notes(
+    neo4j: &dyn crate::neo4j::traits::GraphStore,
+    location: &crate::neo4j::models::SymbolLocationNode,
+) -> Vec<ExplainedNote> {
+    let entity_type = match location.kind.as_str() {
+        "function" => crate::notes::EntityType::Function,
+        "struct" => crate::notes::EntityType::Struct,
+        "trait" => crate::notes::EntityType::Trait,
+        _ => crate::notes::EntityType::Enum,
+    };
+    let mut notes: Vec<ExplainedNote> = Vec::new();
+    for (entity_type, entity_id, linked_to) in [
+        (&entity_type, &location.id, "symbol"),
+        (&crate::notes::EntityType::File, &location.file_path, "file"),
+    ] {
+        for note in neo4j
+            .get_notes_for_entity(entity_type, entity_id)
+            .await
+            .unwrap_or_default()
+        {
+            let retired = matches!(
+                note.status,
+                crate::notes::NoteStatus::Obsolete | crate::notes::NoteStatus::Archived
+            );
+            if !retired && !notes.iter().any(|n| n.id == note.id) {
+                notes.push(ExplainedNote {
+                    id: note.id,
+                    note_type: note.note_type,
+                    status: note.status,
+                    importance: note.importance,
+                    content: note.content,
+                    linked_to: linked_to.to_string(),
+                });
+            }
+        }
+    }
+    notes.truncate(MAX_EXPLAIN_ITEMS);
+    notes
+}
+
+fn call_edges(rows: Vec<(String, String, f64, String)>) -> Vec<CallGraphEdge> {
+    rows.into_iter()
+        .take(MAX_EXPLAIN_ITEMS)
+        .map(|(name, file_path, confidence, reason)| CallGraphEdge {
+            name,
+            file_path,
+            confidence: Some(confidence),
+            reason: Some(reason),
+        })
+        .collect()
+}
+
+/// Everything worth knowing about one symbol in a single response:
+/// definition, docstring, fields or variant payloads, direct callers and
+/// callees, trait relations, attached notes, recent commits of its file and
+/// graph analytics.
+///
+/// `symbol` is tried as a graph id first, then as an exact name. A name
+/// matching several symbols explains the first by file and line, and lists
+/// the ids of the others in `other_matches`. The parts beyond the definition
+/// are best-effort: a failing lookup leaves its field empty.
+pub async fn explain_symbol(
+    State(state): State<OrchestratorState>,
+    Query(params): Query<ExplainQuery>,
+) -> Result<Json<SymbolExplanation>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let project_id = match &params.project_slug {
+        Some(slug) => Some(
+            neo4j
+                .get_project_by_slug(slug)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Project not found: {}", slug)))?
+                .id,
+        ),
+        None => None,
+    };
+
+    let (location, other_matches) = match neo4j.get_symbol_location(&params.symbol).await? {
+        Some(location) => (location, vec![]),
+        None => {
+            let mut matches = neo4j
+                .find_symbols_by_name(&params.symbol, project_id, MAX_EXPLAIN_ITEMS + 1)
+                .await?
+                .into_iter();
+            let location = matches.next().ok_or_else(|| {
+                AppError::NotFound(format!("Symbol not found: {}", params.symbol))
+            })?;
+            (location, matches.map(|m| m.id).collect())
+        }
+    };
+    let project_id = project_id.or(location.project_id);
+    let is_function = location.kind == "function";
+
+    let (signature, complexity) = if is_function {
+        neo4j
+            .get_file_functions_summary(&location.file_path)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .find(|f| f.name == location.name && f.line == location.line_start)
+            .map(|f| (Some(f.signature), Some(f.complexity)))
+            .unwrap_or_default()
+    } else {
+        (None, None)
+    };
+
+    let last_line = location
+        .line_end
+        .min(location.line_start + MAX_EXPLAIN_SOURCE_LINES - 1);
+    let source = read_symbol_file(neo4j, &location)
+        .await
+        .ok()
+        .and_then(|content| slice_lines(&content, location.line_start, last_line, 0))
+        .map(|(_, source, _, _, _)| source);
+    let source_truncated = source.is_some() && last_line < location.line_end;
+
+    let (callers, caller_count, callees) = if is_function {
+        (
+            call_edges(
+                neo4j
+                    .get_callers_with_confidence(&location.name, project_id)
+                    .await
+                    .unwrap_or_default(),
+            ),
+            neo4j
+                .get_function_caller_count(&location.name, project_id)
+                .await
+                .unwrap_or(0),
+            call_edges(
+                neo4j
+                    .get_callees_with_confidence(&location.name, project_id)
+                    .await
+                    .unwrap_or_default(),
+            ),
+        )
+    } else {
+        (vec![], 0, vec![])
+    };
+
+    let implements = match location.kind.as_str() {
+        "struct" | "enum" => neo4j
+            .get_type_trait_implementations(&location.name)
+            .await
+            .unwrap_or_default(),
+        _ => vec![],
+    };
+    let implementors = if location.kind == "trait" {
+        neo4j
+            .get_trait_implementors_detailed(&location.name)
+            .await
+            .unwrap_or_default()
+    } else {
+        vec![]
+    };
+
+    let notes = symbol_notes(neo4j, &location).await;
+
+    let recent_commits = neo4j
+        .get_file_history(&location.file_path, Some(EXPLAIN_COMMIT_COUNT))
+        .await
+        .unwrap_or_default();
+    let analytics = if is_function {
+        neo4j.get_node_analytics(&location.name, "function").await
+    } else {
+        neo4j.get_node_analytics(&location.file_path, "file").await
+    }
+    .unwrap_or(None);
+
+    Ok(Json(SymbolExplanation {
+        symbol: location,
+        signature,
+        complexity,
+        source,
+        source_truncated,
+        callers,
+        caller_count,
+        callees,
+        implements,
+        implementors,
+        notes,
+        recent_commits,
+        analytics,
+        other_matches,
+    }))
+}
+
+// ============================================================================
+// Stack Trace Resolution
+// ============================================================================
+
+/// Largest trace accepted
+const MAX_STACK_TRACE_BYTES: usize = 256 * 1024;
+/// Functions of a trace given their context
+const MAX_TRACE_SYMBOLS: usize = 10;
+/// Source lines shown before and after a frame's line
+const TRACE_EXCERPT_CONTEXT: usize = 3;
+/// Callers listed per function
+const MAX_TRACE_CALLERS: usize = 5;
+/// Commits of a function's file listed
+const TRACE_COMMIT_COUNT: i64 = 3;
+
+#[derive(Deserialize)]
+pub struct StackTraceRequest {
+    /// Stack trace or log excerpt, as printed
+    pub trace: String,
+    /// Project the trace comes from
+    pub project_slug: String,
+}
+
+#[derive(Serialize)]
+pub struct TraceSymbol {
+    pub symbol: crate::neo4j::models::SymbolLocationNode,
+    pub signature: Option<String>,
+    /// Lines of the trace's frames inside the function
+    pub frame_lines: Vec<u32>,
+    /// Source around the first frame line, marked with `>`; `None` when the
+    /// file cannot be read
+    pub excerpt: Option<String>,
+    /// Direct callers, 5 at most
+    pub callers: Vec<CallGraphEdge>,
+    pub notes: Vec<ExplainedNote>,
+    pub recent_commits: Vec<crate::neo4j::models::FileHistoryEntry>,
+}
+
+#[derive(Serialize)]
+pub struct StackTraceResolution {
+    pub project_id: uuid::Uuid,
+    pub language: crate::orchestrator::stack_trace::TraceLanguage,
+    pub message: Option<String>,
+    /// Every frame, in the trace's order
+    pub frames: Vec<crate::orchestrator::stack_trace::StackFrame>,
+    /// Frames resolved to a function of the project
+    pub resolved_frames: usize,
+    /// The functions of the resolved frames, in order of first appearance
+    pub symbols: Vec<TraceSymbol>,
+}
+
+/// Source lines around `line` (1-based), the line itself marked with `>`.
+fn frame_excerpt(content: &str, line: u32, context: usize) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let index = (line as usize)
+        .checked_sub(1)
+        .filter(|i| *i < lines.len())?;
+    let first = index.saturating_sub(context);
+    let last = (index + context + 1).min(lines.len());
+    Some(
+        (first..last)
+            .map(|i| {
+                let marker = if i == index { '>' } else { ' ' };
+                format!("{} {:>5} | {}", marker, i + 1, lines[i])
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+/// Resolve a pasted stack trace (Rust panic, Python traceback, JavaScript
+/// stack) onto the project graph: every frame pointing into a synced file is
+/// matched to the innermost function containing its line, and the first 10
+/// functions come back with their signature, the source around the frame,
+/// direct callers, notes and the latest commits of their file.
+///
+/// Frames in the standard library or dependencies stay unresolved. The
+/// context of each function is best-effort, as for `explain_symbol`.
+pub async fn resolve_stack_trace(
+    State(state): State<OrchestratorState>,
+    Json(body): Json<StackTraceRequest>,
+) -> Result<Json<StackTraceResolution>, AppError> {
+    use crate::orchestrator::stack_trace::{parse_stack_trace, resolve_frames};
+
+    if body.trace.len() > MAX_STACK_TRACE_BYTES {
+        return Err(AppError::BadRequest(format!(
+            "trace is larger than {} bytes",
+            MAX_STACK_TRACE_BYTES
+        )));
+    }
+    let neo4j = state.orchestrator.neo4j();
+    let project = neo4j
+        .get_project_by_slug(&body.project_slug)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project not found: {}", body.project_slug)))?;
+
+    let mut trace = parse_stack_trace(&body.trace);
+    if trace.frames.is_empty() {
+        return Err(AppError::BadRequest(
+            "No stack frame found in the trace".to_string(),
+        ));
+    }
+    resolve_frames(neo4j, &project, &mut trace.frames).await?;
+
+    let mut function_ids: Vec<&str> = Vec::new();
+    for frame in &trace.frames {
+        if let Some(id) = frame.function_id.as_deref() {
+            if !function_ids.contains(&id) {
+                function_ids.push(id);
+            }
+        }
+    }
+    let resolved_frames = trace
+        .frames
+        .iter()
+        .filter(|f| f.function_id.is_some())
+        .count();
+
+    let mut symbols = Vec::new();
+    for id in function_ids.into_iter().take(MAX_TRACE_SYMBOLS) {
+        let Some(location) = neo4j.get_symbol_location(id).await.unwrap_or(None) else {
+            continue;
+        };
+        let frame_lines: Vec<u32> = trace
+            .frames
+            .iter()
+            .filter(|f| f.function_id.as_deref() == Some(id))
+            .map(|f| f.line)
+            .collect();
+        let signature = neo4j
+            .get_file_functions_summary(&location.file_path)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .find(|f| f.name == location.name && f.line == location.line_start)
+            .map(|f| f.signature);
+        let excerpt = read_symbol_file(neo4j, &location)
+            .await
+            .ok()
+            .and_then(|content| frame_excerpt(&content, frame_lines[0], TRACE_EXCERPT_CONTEXT));
+        let mut callers = call_edges(
+            neo4j
+                .get_callers_with_confidence(&location.name, Some(project.id))
+                .await
+                .unwrap_or_default(),
+        );
+        callers.truncate(MAX_TRACE_CALLERS);
+        let notes = symbol_notes(neo4j, &location).await;
+        let recent_commits = neo4j
+            .get_file_history(&location.file_path, Some(TRACE_COMMIT_COUNT))
+            .await
+            .unwrap_or_default();
+        symbols.push(TraceSymbol {
+            symbol: location,
+            signature,
+            frame_lines,
+            excerpt,
+            callers,
+            notes,
+            recent_commits,
+        });
+    }
+
+    Ok(Json(StackTraceResolution {
+        project_id: project.id,
+        language: trace.language,
+        message: trace.message,
+        frames: trace.frames,
+        resolved_frames,
+        symbols,
+    }))
+}
+
 // ============================================================================
 // Find References
 // ============================================================================
@@ -354,6 +1317,7 @@ pub async fn get_file_dependencies(
     let file_path = urlencoding::decode(&file_path)
         .map_err(|e| AppError::BadRequest(e.to_string()))?
         .to_string();
+    validate_file_path("file_path", &file_path)?;
 
     let dependents = state
         .orchestrator
@@ -404,6 +1368,8 @@ pub struct CallGraphQuery {
     pub direction: Option<String>,
     /// Filter by project slug
     pub project_slug: Option<String>,
+    /// Ignore CALLS edges scored below this confidence, 0.0-1.0 (default 0.0)
+    pub min_confidence: Option<f64>,
 }
 
 #[derive(Serialize)]
@@ -438,6 +1404,12 @@ pub async fn get_call_graph(
 ) -> Result<Json<CallGraphNode>, AppError> {
     let depth = query.depth.unwrap_or(2).clamp(1, 20);
     let direction = query.direction.as_deref().unwrap_or("both");
+    let min_confidence = query.min_confidence.unwrap_or(0.0);
+    if !(0.0..=1.0).contains(&min_confidence) {
+        return Err(AppError::BadRequest(
+            "min_confidence must be between 0.0 and 1.0".to_string(),
+        ));
+    }
 
     let project_id = if let Some(ref slug) = query.project_slug {
         Some(
@@ -462,7 +1434,7 @@ pub async fn get_call_graph(
         callers = state
             .orchestrator
             .neo4j()
-            .get_function_callers_by_name(&query.function, depth, project_id)
+            .get_function_callers_by_name(&query.function, depth, project_id, min_confidence)
             .await?;
         // Also get direct callers with confidence (depth 1)
         if let Ok(details) = state
@@ -473,6 +1445,7 @@ pub async fn get_call_graph(
         {
             caller_details = details
                 .into_iter()
+                .filter(|(_, _, conf, _)| *conf >= min_confidence)
                 .map(|(name, file, conf, reason)| CallGraphEdge {
                     name,
                     file_path: file,
@@ -487,7 +1460,7 @@ pub async fn get_call_graph(
         callees = state
             .orchestrator
             .neo4j()
-            .get_function_callees_by_name(&query.function, depth, project_id)
+            .get_function_callees_by_name(&query.function, depth, project_id, min_confidence)
             .await?;
         if let Ok(details) = state
             .orchestrator
@@ -497,6 +1470,7 @@ pub async fn get_call_graph(
         {
             callee_details = details
                 .into_iter()
+                .filter(|(_, _, conf, _)| *conf >= min_confidence)
                 .map(|(name, file, conf, reason)| CallGraphEdge {
                     name,
                     file_path: file,
@@ -587,6 +1561,10 @@ pub struct ImpactAnalysis {
     /// - ELL (2025) — "Experience-driven Lifelong Learning" — 4th pillar: self-evaluation
     #[serde(skip_serializing_if = "Option::is_none")]
     pub confidence: Option<crate::graph::confidence::ConfidenceScore>,
+    /// CODEOWNERS owners of the target and its directly affected files, who
+    /// should review the change (most files owned first)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub reviewers: Vec<crate::orchestrator::ownership::OwnerShare>,
 }
 
 /// Analyze impact of changing a file or function
@@ -876,6 +1854,25 @@ pub async fn analyze_impact(
         .await
         .unwrap_or_default();
 
+    // CODEOWNERS owners of the target and its direct dependents (best-effort)
+    let reviewers = match (project_id, project_root.as_deref()) {
+        (Some(pid), Some(root)) => {
+            let rules = state
+                .orchestrator
+                .neo4j()
+                .get_code_owner_rules(pid)
+                .await
+                .unwrap_or_default();
+            let ownership = crate::orchestrator::ownership::Ownership::new(rules, root);
+            let mut files: Vec<&str> = directly_affected.iter().map(String::as_str).collect();
+            if target_type == "file" && !directly_affected.contains(&target) {
+                files.push(&target);
+            }
+            ownership.owner_shares(files)
+        }
+        _ => Vec::new(),
+    };
+
     // Build ranked view: direct files score 1.0, transitive-only score 0.33
     let ranked_affected = {
         let mut scored: Vec<(AffectedFile, f64)> = Vec::new();
@@ -931,6 +1928,7 @@ pub async fn analyze_impact(
         context_cards,
         transitive_co_changers,
         confidence,
+        reviewers,
     }))
 }
 
@@ -2104,6 +3102,33 @@ pub async fn get_code_health(
         (dist, p95)
     };
 
+    // Near-duplicate functions (best-effort — empty until fingerprints are stored)
+    let duplication = match state
+        .orchestrator
+        .neo4j()
+        .get_project_function_fingerprints(project.id)
+        .await
+    {
+        Ok(rows) => {
+            let groups = find_clone_groups(&rows, DEFAULT_CLONE_THRESHOLD);
+            clone_report(rows.len(), &groups, HEALTH_DUPLICATE_GROUPS)
+        }
+        Err(_) => serde_json::json!(null),
+    };
+
+    // Most central functions left untested (best-effort — empty until a
+    // coverage report is ingested)
+    let untested_functions = state
+        .orchestrator
+        .neo4j()
+        .get_untested_functions(
+            project.id,
+            crate::orchestrator::coverage::UNTESTED_COVERAGE_PCT,
+            5,
+        )
+        .await
+        .unwrap_or_default();
+
     // Community risk ANOVA — detect structurally fragile communities
     let community_risk_anova = {
         let groups = state
@@ -2131,6 +3156,8 @@ pub async fn get_code_health(
         "avg_impact_score": avg_impact_score,
         "topology_violations": topology_violations,
         "homeostasis": homeostasis_json,
+        "duplication": duplication,
+        "untested_functions": untested_functions,
         // Statistical analytics (rs-stats engine)
         "pagerank_distribution": pagerank_distribution,
         "risk_score_distribution": risk_score_distribution,
@@ -2139,6 +3166,83 @@ pub async fn get_code_health(
     })))
 }
 
+// ============================================================================
+// Duplicate Code
+// ============================================================================
+
+/// Clone groups returned by `/api/code/duplicates` unless `limit` is given
+const DEFAULT_DUPLICATE_GROUPS: usize = 50;
+const MAX_DUPLICATE_GROUPS: usize = 500;
+
+/// Clone groups listed in the health report
+const HEALTH_DUPLICATE_GROUPS: usize = 5;
+
+/// Lowest accepted `threshold`: below it, LSH banding misses most pairs
+const MIN_CLONE_THRESHOLD: f64 = 0.5;
+
+#[derive(Deserialize)]
+pub struct DuplicatesQuery {
+    pub project_slug: String,
+    /// Minimum estimated similarity (0.5–1.0, default 0.85)
+    pub threshold: Option<f64>,
+    pub limit: Option<usize>,
+}
+
+/// Totals over all clone groups, with the `limit` largest listed.
+fn clone_report(fingerprinted: usize, groups: &[CloneGroup], limit: usize) -> serde_json::Value {
+    serde_json::json!({
+        "fingerprinted_functions": fingerprinted,
+        "group_count": groups.len(),
+        "duplicated_function_count": groups.iter().map(|g| g.members.len()).sum::<usize>(),
+        "duplicated_tokens": groups.iter().map(|g| g.duplicated_tokens as u64).sum::<u64>(),
+        "groups": &groups[..groups.len().min(limit)],
+        "truncated": groups.len() > limit,
+    })
+}
+
+/// GET /api/code/duplicates — Groups of near-duplicate functions in a project
+///
+/// Compares the MinHash fingerprints computed when files are parsed, so
+/// functions synced before fingerprinting existed only show up after a
+/// re-sync. Groups are ordered by the tokens a merge would save.
+pub async fn get_code_duplicates(
+    State(state): State<OrchestratorState>,
+    Query(params): Query<DuplicatesQuery>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let project = state
+        .orchestrator
+        .neo4j()
+        .get_project_by_slug(&params.project_slug)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(format!("Project '{}' not found", params.project_slug))
+        })?;
+
+    let threshold = params.threshold.unwrap_or(DEFAULT_CLONE_THRESHOLD);
+    if !(MIN_CLONE_THRESHOLD..=1.0).contains(&threshold) {
+        return Err(AppError::BadRequest(format!(
+            "threshold must be between {} and 1.0",
+            MIN_CLONE_THRESHOLD
+        )));
+    }
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_DUPLICATE_GROUPS)
+        .clamp(1, MAX_DUPLICATE_GROUPS);
+
+    let rows = state
+        .orchestrator
+        .neo4j()
+        .get_project_function_fingerprints(project.id)
+        .await?;
+    let groups = find_clone_groups(&rows, threshold);
+
+    let mut report = clone_report(rows.len(), &groups, limit);
+    report["project_slug"] = serde_json::json!(params.project_slug);
+    report["threshold"] = serde_json::json!(threshold);
+    Ok(Json(report))
+}
+
 // ============================================================================
 // Node Importance (GDS Analytics)
 // ============================================================================
@@ -3151,6 +4255,13 @@ pub struct CheckFileTopologyBody {
     pub new_imports: Vec<String>,
 }
 
+impl Validate for CheckFileTopologyBody {
+    fn validate(&self, v: &mut Validator) {
+        v.text("project_slug", &self.project_slug, MAX_TITLE_LEN);
+        v.file_path("file_path", &self.file_path);
+    }
+}
+
 /// POST /api/code/topology/check-file
 ///
 /// Real-time pre-write validation: checks if the given `new_imports` for
@@ -3158,7 +4269,7 @@ pub struct CheckFileTopologyBody {
 /// Designed for <50ms response time (in-memory regex matching after 1 Neo4j query).
 pub async fn check_file_topology(
     State(state): State<OrchestratorState>,
-    Json(body): Json<CheckFileTopologyBody>,
+    ValidJson(body): ValidJson<CheckFileTopologyBody>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let project = state
         .orchestrator
@@ -3194,6 +4305,13 @@ pub struct StructuralDnaBody {
     pub top_n: Option<usize>,
 }
 
+impl Validate for StructuralDnaBody {
+    fn validate(&self, v: &mut Validator) {
+        v.text("project_slug", &self.project_slug, MAX_TITLE_LEN);
+        v.file_path("file_path", &self.file_path);
+    }
+}
+
 /// POST /api/code/structural-profile
 ///
 /// Returns the structural DNA vector for a single file within a project.
@@ -3201,7 +4319,7 @@ pub struct StructuralDnaBody {
 /// nodes (highest PageRank), normalized to [0,1]. Requires prior analytics run.
 pub async fn get_structural_profile(
     State(state): State<OrchestratorState>,
-    Json(body): Json<StructuralDnaBody>,
+    ValidJson(body): ValidJson<StructuralDnaBody>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let project = state
         .orchestrator
@@ -3255,7 +4373,7 @@ pub async fn get_structural_profile(
 /// Returns ranked results sorted by descending similarity.
 pub async fn find_structural_twins(
     State(state): State<OrchestratorState>,
-    Json(body): Json<StructuralDnaBody>,
+    ValidJson(body): ValidJson<StructuralDnaBody>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     use crate::graph::algorithms::{compute_multi_signal_similarity, FileSignals};
 
@@ -3484,6 +4602,18 @@ pub struct CrossProjectTwinsBody {
     pub top_n: Option<usize>,
 }
 
+impl Validate for CrossProjectTwinsBody {
+    fn validate(&self, v: &mut Validator) {
+        v.text("workspace_slug", &self.workspace_slug, MAX_TITLE_LEN);
+        v.text(
+            "source_project_slug",
+            &self.source_project_slug,
+            MAX_TITLE_LEN,
+        );
+        v.file_path("file_path", &self.file_path);
+    }
+}
+
 /// POST /api/code/structural-twins/cross-project
 ///
 /// Finds structurally similar files across other projects in the same workspace.
@@ -3494,7 +4624,7 @@ pub struct CrossProjectTwinsBody {
 /// suggested for the source file in project A.
 pub async fn find_cross_project_twins(
     State(state): State<OrchestratorState>,
-    Json(body): Json<CrossProjectTwinsBody>,
+    ValidJson(body): ValidJson<CrossProjectTwinsBody>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     use crate::graph::algorithms::{compute_multi_signal_similarity, FileSignals};
 
@@ -3960,13 +5090,20 @@ pub struct ContextCardQuery {
     pub project_slug: String,
 }
 
+impl Validate for ContextCardQuery {
+    fn validate(&self, v: &mut Validator) {
+        v.file_path("path", &self.path);
+        v.text("project_slug", &self.project_slug, MAX_TITLE_LEN);
+    }
+}
+
 /// Get pre-computed context card for a single file.
 ///
 /// Returns the cached cc_* properties from Neo4j. If `cc_version == -1`,
 /// the card is stale and should be refreshed via analytics re-run.
 pub async fn get_context_card(
     State(state): State<OrchestratorState>,
-    Query(query): Query<ContextCardQuery>,
+    ValidQuery(query): ValidQuery<ContextCardQuery>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let project = state
         .orchestrator
@@ -4042,13 +5179,20 @@ pub struct FingerprintQuery {
     pub project_slug: String,
 }
 
+impl Validate for FingerprintQuery {
+    fn validate(&self, v: &mut Validator) {
+        v.file_path("path", &self.path);
+        v.text("project_slug", &self.project_slug, MAX_TITLE_LEN);
+    }
+}
+
 /// Get the structural fingerprint for a single file.
 ///
 /// Returns the 17-dim universal fingerprint vector with dimension labels,
 /// plus the WL hash and legacy structural DNA for backward compatibility.
 pub async fn get_fingerprint(
     State(state): State<OrchestratorState>,
-    Query(query): Query<FingerprintQuery>,
+    ValidQuery(query): ValidQuery<FingerprintQuery>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     use crate::graph::models::{FINGERPRINT_DIMS, FINGERPRINT_LABELS};
 
@@ -4373,7 +5517,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -4406,21 +5553,42 @@ mod tests {
             project_slug: "test-project".to_string(),
         };
         app_state.meili.index_code(&doc).await.unwrap();
+        app_state
+            .meili
+            .index_doc_sections(&[crate::meilisearch::indexes::DocSectionDocument {
+                id: "readme-install".to_string(),
+                path: "README.md".to_string(),
+                title: "Test Project".to_string(),
+                heading: "Installation".to_string(),
+                anchor: "installation".to_string(),
+                level: 2,
+                line_start: 5,
+                content: "Run cargo install to build the binary".to_string(),
+                code_languages: vec!["sh".to_string()],
+                project_id: "proj-1".to_string(),
+                project_slug: "test-project".to_string(),
+            }])
+            .await
+            .unwrap();
 
         // Seed files in the mock graph store for architecture endpoint
         let file1 = FileNode {
             path: "src/main.rs".to_string(),
             language: "rust".to_string(),
             hash: "abc123".to_string(),
+            line_count: 0,
             last_parsed: chrono::Utc::now(),
             project_id: None,
+            encoding: None,
         };
         let file2 = FileNode {
             path: "src/lib.rs".to_string(),
             language: "rust".to_string(),
             hash: "def456".to_string(),
+            line_count: 0,
             last_parsed: chrono::Utc::now(),
             project_id: None,
+            encoding: None,
         };
         app_state.neo4j.upsert_file(&file1).await.unwrap();
         app_state.neo4j.upsert_file(&file2).await.unwrap();
@@ -4443,7 +5611,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -4459,6 +5630,43 @@ mod tests {
         create_router(state)
     }
 
+    // ====================================================================
+    // GET /api/code/docs/search
+    // ====================================================================
+
+    #[tokio::test]
+    async fn test_search_docs() {
+        let app = test_app_with_code().await;
+        let resp = app
+            .clone()
+            .oneshot(auth_get(
+                "/api/code/docs/search?query=installation&project_slug=test-project",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let hits = json.as_array().unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0]["document"]["path"], "README.md");
+        assert_eq!(hits[0]["document"]["anchor"], "installation");
+
+        let resp = app
+            .oneshot(auth_get(
+                "/api/code/docs/search?query=installation&project_slug=other",
+            ))
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(json.as_array().unwrap().is_empty());
+    }
+
     // ====================================================================
     // GET /api/code/search
     // ====================================================================
@@ -4554,8 +5762,14 @@ mod tests {
             .await
             .unwrap();
 
-        // Missing required 'query' param → 400
-        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        // Missing required 'query' param → 422 naming the field
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["code"], "validation_failed");
+        assert_eq!(json["errors"][0]["field"], "query");
     }
 
     // ====================================================================
@@ -4678,6 +5892,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         app_state.neo4j.create_project(&project).await.unwrap();
         app_state
@@ -4705,11 +5920,35 @@ mod tests {
             path: "src/main.rs".to_string(),
             language: "rust".to_string(),
             hash: "abc123".to_string(),
+            line_count: 0,
             last_parsed: chrono::Utc::now(),
             project_id: Some(proj_id),
+            encoding: None,
         };
         app_state.neo4j.upsert_file(&file).await.unwrap();
 
+        // Seed a config key read by the file
+        app_state
+            .neo4j
+            .replace_project_config_keys(
+                proj_id,
+                &[crate::neo4j::models::ConfigKeyNode {
+                    key: "MEILISEARCH_URL".to_string(),
+                    project_id: proj_id,
+                    kind: crate::neo4j::models::ConfigKeyKind::Env,
+                    defined_in: vec![crate::neo4j::models::ConfigKeyLocation {
+                        file_path: ".env".to_string(),
+                        line: 1,
+                    }],
+                    referenced_by: vec![crate::neo4j::models::ConfigKeyLocation {
+                        file_path: "src/main.rs".to_string(),
+                        line: 3,
+                    }],
+                }],
+            )
+            .await
+            .unwrap();
+
         let orchestrator = Arc::new(Orchestrator::new(app_state).await.unwrap());
         let watcher = Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
             orchestrator.clone(),
@@ -4728,7 +5967,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -4744,6 +5986,52 @@ mod tests {
         create_router(state)
     }
 
+    // ====================================================================
+    // GET /api/code/config-keys
+    // ====================================================================
+
+    #[tokio::test]
+    async fn test_find_config_usages() {
+        let app = test_app_with_workspace().await;
+        let resp = app
+            .clone()
+            .oneshot(auth_get(
+                "/api/code/config-keys?project_slug=test-project&key=meilisearch",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let keys = json.as_array().unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0]["key"], "MEILISEARCH_URL");
+        assert_eq!(keys[0]["kind"], "env");
+        assert_eq!(keys[0]["referenced_by"][0]["file_path"], "src/main.rs");
+        assert_eq!(keys[0]["referenced_by"][0]["line"], 3);
+
+        let resp = app
+            .clone()
+            .oneshot(auth_get(
+                "/api/code/config-keys?project_slug=test-project&key=neo4j",
+            ))
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(json.as_array().unwrap().is_empty());
+
+        let resp = app
+            .oneshot(auth_get("/api/code/config-keys?project_slug=nonexistent"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn test_search_code_with_workspace_slug_filter() {
         let app = test_app_with_workspace().await;
@@ -4839,6 +6127,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         graph.create_project(&project).await.unwrap();
 
@@ -4854,8 +6143,10 @@ mod tests {
                 path: path.to_string(),
                 language: "rust".to_string(),
                 hash: "h".to_string(),
+                line_count: 0,
                 last_parsed: chrono::Utc::now(),
                 project_id: Some(project.id),
+                encoding: None,
             };
             graph.upsert_file(&file).await.unwrap();
             graph
@@ -4898,6 +6189,16 @@ mod tests {
             .await
             .unwrap();
 
+        let codeowners = "* @acme/core\n/tests/ @acme/qa\n";
+        graph
+            .replace_code_owners(
+                project.id,
+                &crate::orchestrator::ownership::parse_codeowners(codeowners).rules,
+                &[],
+            )
+            .await
+            .unwrap();
+
         let app_state = mock_app_state_with(graph, MockSearchStore::new());
         let orchestrator = Arc::new(Orchestrator::new(app_state).await.unwrap());
         let watcher = Arc::new(tokio::sync::RwLock::new(
@@ -4917,7 +6218,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -5004,6 +6308,30 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_rest_analyze_impact_reviewers() {
+        let app = test_app_with_imports().await;
+
+        let resp = app
+            .oneshot(auth_get(
+                "/api/code/impact?target=src/lib.rs&project_slug=test-proj",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        // The target and its direct dependents, by the last matching rule
+        let reviewers = json["reviewers"].as_array().unwrap();
+        assert_eq!(reviewers.len(), 2);
+        assert_eq!(reviewers[0]["owner"], "@acme/core");
+        assert_eq!(reviewers[0]["files"], 3);
+        assert_eq!(reviewers[1]["owner"], "@acme/qa");
+    }
+
     // ====================================================================
     // GET /api/code/architecture — GDS enrichment tests (Task 3.1)
     // ====================================================================
@@ -5035,6 +6363,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         graph.create_project(&project).await.unwrap();
 
@@ -5050,8 +6379,10 @@ mod tests {
                 path: path.to_string(),
                 language: "rust".to_string(),
                 hash: "h".to_string(),
+                line_count: 0,
                 last_parsed: chrono::Utc::now(),
                 project_id: Some(project_id),
+                encoding: None,
             };
             graph.upsert_file(&file).await.unwrap();
         }
@@ -5132,7 +6463,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -5248,6 +6582,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         graph.create_project(&project).await.unwrap();
 
@@ -5264,8 +6599,10 @@ mod tests {
                 path: path.to_string(),
                 language: "rust".to_string(),
                 hash: "h".to_string(),
+                line_count: 0,
                 last_parsed: chrono::Utc::now(),
                 project_id: Some(project_id),
+                encoding: None,
             };
             graph.upsert_file(&file).await.unwrap();
         }
@@ -5332,7 +6669,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -5401,6 +6741,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         graph.create_project(&project).await.unwrap();
 
@@ -5418,8 +6759,10 @@ mod tests {
                 path: path.to_string(),
                 language: "rust".to_string(),
                 hash: "h".to_string(),
+                line_count: 0,
                 last_parsed: chrono::Utc::now(),
                 project_id: Some(project_id),
+                encoding: None,
             };
             graph.upsert_file(&file).await.unwrap();
             graph
@@ -5536,7 +6879,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -5714,8 +7060,10 @@ mod tests {
             path: "src/handler.rs".to_string(),
             language: "rust".to_string(),
             hash: "abc".to_string(),
+            line_count: 0,
             last_parsed: chrono::Utc::now(),
             project_id: None,
+            encoding: None,
         };
         graph.upsert_file(&file).await.unwrap();
 
@@ -5750,6 +7098,7 @@ mod tests {
             docstring: Some("App config".to_string()),
             parent_class: None,
             interfaces: vec![],
+            fields: vec![],
         };
         graph.upsert_struct(&s).await.unwrap();
 
@@ -5761,7 +7110,179 @@ mod tests {
             file_path: "src/handler.rs".to_string(),
             line: 1,
         };
-        graph.upsert_import(&imp).await.unwrap();
+        graph.upsert_import(&imp).await.unwrap();
+
+        let app_state = mock_app_state_with(graph, MockSearchStore::new());
+        let orchestrator = Arc::new(Orchestrator::new(app_state).await.unwrap());
+        let watcher = Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
+            orchestrator.clone(),
+        )));
+        let state = Arc::new(ServerState {
+            orchestrator,
+            watcher,
+            chat_manager: None,
+            event_bus: Arc::new(crate::events::HybridEmitter::new(Arc::new(
+                crate::events::EventBus::default(),
+            ))),
+            nats_emitter: None,
+            auth_config: Some(crate::test_helpers::test_auth_config()),
+            serve_frontend: false,
+            frontend_path: "./dist".to_string(),
+            setup_completed: true,
+            server_port: 6600,
+            public_url: None,
+            read_only: false,
+            ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
+            registry_remote_url: None,
+            oidc_client: None,
+            neural_router: crate::test_helpers::mock_neural_router(),
+            trajectory_collector: std::sync::RwLock::new(None),
+            trajectory_store_neo4j: None,
+            trajectory_store: None,
+            identity: None,
+            reactor_counters: std::sync::OnceLock::new(),
+            confidence_tracker: Arc::new(crate::graph::confidence::ConfidenceTracker::default()),
+            mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
+            model_catalog: crate::chat::model_catalog::ModelCatalogCache::new(None),
+        });
+        create_router(state)
+    }
+
+    #[tokio::test]
+    async fn test_get_file_symbols_with_data() {
+        let app = test_app_with_symbols().await;
+        let resp = app
+            .oneshot(auth_get("/api/code/symbols/src/handler.rs"))
+            .await
+            .unwrap();
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(json["path"], "src/handler.rs");
+        assert_eq!(json["language"], "rust");
+
+        // Check function
+        let functions = json["functions"].as_array().unwrap();
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0]["name"], "handle_request");
+        assert_eq!(functions[0]["id"], "src/handler.rs:handle_request:10");
+        assert!(functions[0]["is_async"].as_bool().unwrap());
+        assert!(functions[0]["is_public"].as_bool().unwrap());
+        assert_eq!(functions[0]["line"], 10);
+        assert_eq!(functions[0]["complexity"], 3);
+        assert_eq!(functions[0]["docstring"], "Handle incoming request");
+        let sig = functions[0]["signature"].as_str().unwrap();
+        assert!(sig.contains("handle_request"), "signature: {}", sig);
+        assert!(sig.contains("req: Request"), "signature: {}", sig);
+        assert!(sig.contains("-> Response"), "signature: {}", sig);
+
+        // Check struct
+        let structs = json["structs"].as_array().unwrap();
+        assert_eq!(structs.len(), 1);
+        assert_eq!(structs[0]["name"], "Config");
+        assert!(structs[0]["is_public"].as_bool().unwrap());
+        assert_eq!(structs[0]["line"], 5);
+
+        // Check imports
+        let imports = json["imports"].as_array().unwrap();
+        assert_eq!(imports.len(), 1);
+        assert_eq!(imports[0], "std::io");
+    }
+
+    // ====================================================================
+    // GET /api/code/snippet — get_code_snippet
+    // ====================================================================
+
+    /// Build a test router with a project rooted at `root` holding
+    /// `src/lib.rs`, and a function in a file outside any project root
+    async fn test_app_with_snippet(
+        root: &std::path::Path,
+        outside: &std::path::Path,
+    ) -> axum::Router {
+        use crate::meilisearch::mock::MockSearchStore;
+        use crate::neo4j::mock::MockGraphStore;
+        use crate::neo4j::models::{FieldNode, FunctionNode, StructNode, Visibility};
+        use crate::neo4j::traits::GraphStore;
+        use crate::test_helpers::{mock_app_state_with, test_project_named};
+        use sha2::{Digest, Sha256};
+
+        let graph = MockGraphStore::new();
+        let mut project = test_project_named("snippets");
+        project.root_path = root.to_string_lossy().to_string();
+        graph.create_project(&project).await.unwrap();
+
+        let content = "use std::io;\n\n/// Settings\npub struct Config {\n    pub port: u16,\n}\n\npub fn load() -> Config {\n    Config { port: 80 }\n}\n";
+        let path = root.join("src/lib.rs");
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(&path, content).unwrap();
+        let path = path.to_string_lossy().to_string();
+        graph.link_file_to_project(&path, project.id).await.unwrap();
+        graph
+            .upsert_file(&FileNode {
+                path: path.clone(),
+                language: "rust".to_string(),
+                hash: hex::encode(Sha256::digest(content.as_bytes())),
+                line_count: 0,
+                last_parsed: chrono::Utc::now(),
+                project_id: Some(project.id),
+                encoding: None,
+            })
+            .await
+            .unwrap();
+        graph
+            .upsert_struct(&StructNode {
+                name: "Config".to_string(),
+                visibility: Visibility::Public,
+                generics: vec![],
+                file_path: path.clone(),
+                line_start: 4,
+                line_end: 6,
+                docstring: Some("Settings".to_string()),
+                parent_class: None,
+                interfaces: vec![],
+                fields: vec![FieldNode {
+                    name: "port".to_string(),
+                    type_name: "u16".to_string(),
+                    visibility: Visibility::Public,
+                    default_value: None,
+                }],
+            })
+            .await
+            .unwrap();
+        let load = FunctionNode {
+            name: "load".to_string(),
+            visibility: Visibility::Public,
+            params: vec![],
+            return_type: Some("Config".to_string()),
+            generics: vec![],
+            is_async: false,
+            is_unsafe: false,
+            complexity: 1,
+            file_path: path,
+            line_start: 8,
+            line_end: 10,
+            docstring: None,
+        };
+        graph.upsert_function(&load).await.unwrap();
+
+        let secret = outside.join("secret.rs");
+        std::fs::write(&secret, "fn leak() {}\n").unwrap();
+        graph
+            .upsert_function(&FunctionNode {
+                name: "leak".to_string(),
+                file_path: secret.to_string_lossy().to_string(),
+                line_start: 1,
+                line_end: 1,
+                ..load
+            })
+            .await
+            .unwrap();
 
         let app_state = mock_app_state_with(graph, MockSearchStore::new());
         let orchestrator = Arc::new(Orchestrator::new(app_state).await.unwrap());
@@ -5782,7 +7303,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -5798,54 +7322,337 @@ mod tests {
         create_router(state)
     }
 
+    #[test]
+    fn test_slice_lines() {
+        let content = "a\nb\nc\nd\ne";
+        assert_eq!(
+            slice_lines(content, 2, 3, 1),
+            Some(("a".to_string(), "b\nc".to_string(), "d".to_string(), 1, 4))
+        );
+        // Context is clamped to the file
+        assert_eq!(
+            slice_lines(content, 4, 9, 5),
+            Some((
+                "a\nb\nc".to_string(),
+                "d\ne".to_string(),
+                String::new(),
+                1,
+                5
+            ))
+        );
+        assert_eq!(slice_lines(content, 6, 7, 0), None);
+    }
+
     #[tokio::test]
-    async fn test_get_file_symbols_with_data() {
-        let app = test_app_with_symbols().await;
+    async fn test_get_code_snippet() {
+        let root = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        let app = test_app_with_snippet(root.path(), outside.path()).await;
+        let file = root.path().join("src/lib.rs").to_string_lossy().to_string();
+
+        let id = format!("{}:load:8", file);
         let resp = app
-            .oneshot(auth_get("/api/code/symbols/src/handler.rs"))
+            .clone()
+            .oneshot(auth_get(&format!(
+                "/api/code/snippet?symbol_id={}&context_lines=1",
+                urlencoding::encode(&id)
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["kind"], "function");
+        assert_eq!(json["language"], "rust");
+        assert_eq!(
+            json["source"],
+            "pub fn load() -> Config {\n    Config { port: 80 }\n}"
+        );
+        assert_eq!(json["before"], "");
+        assert_eq!(json["snippet_start"], 7);
+        assert_eq!(json["snippet_end"], 10);
+        assert_eq!(json["total_lines"], 10);
+        assert_eq!(json["stale"], false);
+
+        let resp = app
+            .clone()
+            .oneshot(auth_get(&format!(
+                "/api/code/snippet?symbol_id={}",
+                urlencoding::encode(&format!("{}:Config", file))
+            )))
             .await
             .unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["kind"], "struct");
+        assert_eq!(json["source"], "pub struct Config {\n    pub port: u16,\n}");
+        assert_eq!(json["docstring"], "Settings");
+
+        // Files outside every project root are never read
+        let secret = outside
+            .path()
+            .join("secret.rs")
+            .to_string_lossy()
+            .to_string();
+        let resp = app
+            .clone()
+            .oneshot(auth_get(&format!(
+                "/api/code/snippet?symbol_id={}",
+                urlencoding::encode(&format!("{}:leak:1", secret))
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let resp = app
+            .oneshot(auth_get("/api/code/snippet?symbol_id=src%2Fnope.rs%3Anope"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    // ====================================================================
+    // GET /api/code/rename-proposal — propose_rename
+    // ====================================================================
 
+    #[test]
+    fn test_word_columns() {
+        assert_eq!(
+            word_columns("let c: Config = Config::new();", "Config"),
+            vec![8, 17]
+        );
+        assert!(word_columns("ConfigLoader, MyConfig", "Config").is_empty());
+        assert_eq!(word_columns("é Config", "Config"), vec![3]);
+        assert!(is_identifier("Settings"));
+        assert!(!is_identifier("9lives"));
+        assert!(!is_identifier("two words"));
+    }
+
+    #[tokio::test]
+    async fn test_propose_rename() {
+        let root = tempfile::tempdir().unwrap();
+        let root_path = root.path().canonicalize().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        let app = test_app_with_snippet(&root_path, outside.path()).await;
+        std::fs::write(
+            root_path.join("src/main.rs"),
+            "use crate::Config;\nfn main() { let c: Config = load(); }\n",
+        )
+        .unwrap();
+        let lib = root_path.join("src/lib.rs").to_string_lossy().to_string();
+        let main = root_path.join("src/main.rs").to_string_lossy().to_string();
+
+        let resp = app
+            .clone()
+            .oneshot(auth_get(&format!(
+                "/api/code/rename-proposal?symbol_id={}&new_name=Settings",
+                urlencoding::encode(&format!("{}:Config", lib))
+            )))
+            .await
+            .unwrap();
         assert_eq!(resp.status(), StatusCode::OK);
         let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
             .await
             .unwrap();
         let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["old_name"], "Config");
+        assert_eq!(json["file_count"], 2);
+        let edits: Vec<(String, u64, u64, String)> = json["edits"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| {
+                (
+                    e["file_path"].as_str().unwrap().to_string(),
+                    e["line"].as_u64().unwrap(),
+                    e["column"].as_u64().unwrap(),
+                    e["source"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            edits,
+            vec![
+                (lib.clone(), 4, 12, "definition".to_string()),
+                (lib.clone(), 8, 18, "graph".to_string()),
+                (lib.clone(), 9, 5, "graph".to_string()),
+                (main.clone(), 1, 12, "text".to_string()),
+                (main, 2, 20, "text".to_string()),
+            ]
+        );
+        // `/// Settings` already uses the new name
+        let conflicts = json["conflicts"].as_array().unwrap();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0]["file_path"], lib.as_str());
+        assert_eq!(conflicts[0]["line"], 3);
 
-        assert_eq!(json["path"], "src/handler.rs");
-        assert_eq!(json["language"], "rust");
+        let resp = app
+            .oneshot(auth_get(&format!(
+                "/api/code/rename-proposal?symbol_id={}&new_name=9lives",
+                urlencoding::encode(&format!("{}:Config", lib))
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
 
-        // Check function
-        let functions = json["functions"].as_array().unwrap();
-        assert_eq!(functions.len(), 1);
-        assert_eq!(functions[0]["name"], "handle_request");
-        assert!(functions[0]["is_async"].as_bool().unwrap());
-        assert!(functions[0]["is_public"].as_bool().unwrap());
-        assert_eq!(functions[0]["line"], 10);
-        assert_eq!(functions[0]["complexity"], 3);
-        assert_eq!(functions[0]["docstring"], "Handle incoming request");
-        let sig = functions[0]["signature"].as_str().unwrap();
-        assert!(sig.contains("handle_request"), "signature: {}", sig);
-        assert!(sig.contains("req: Request"), "signature: {}", sig);
-        assert!(sig.contains("-> Response"), "signature: {}", sig);
+    // ====================================================================
+    // GET /api/code/explain — explain_symbol
+    // ====================================================================
 
-        // Check struct
-        let structs = json["structs"].as_array().unwrap();
-        assert_eq!(structs.len(), 1);
-        assert_eq!(structs[0]["name"], "Config");
-        assert!(structs[0]["is_public"].as_bool().unwrap());
-        assert_eq!(structs[0]["line"], 5);
+    #[tokio::test]
+    async fn test_explain_symbol() {
+        let root = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        let app = test_app_with_snippet(root.path(), outside.path()).await;
+        let file = root.path().join("src/lib.rs").to_string_lossy().to_string();
 
-        // Check imports
-        let imports = json["imports"].as_array().unwrap();
-        assert_eq!(imports.len(), 1);
-        assert_eq!(imports[0], "std::io");
+        // By name
+        let resp = app
+            .clone()
+            .oneshot(auth_get("/api/code/explain?symbol=load"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["symbol"]["id"], format!("{}:load:8", file));
+        assert_eq!(json["symbol"]["kind"], "function");
+        assert_eq!(json["signature"], "fn load() -> Config");
+        assert_eq!(json["complexity"], 1);
+        assert_eq!(
+            json["source"],
+            "pub fn load() -> Config {\n    Config { port: 80 }\n}"
+        );
+        assert_eq!(json["source_truncated"], false);
+        assert_eq!(json["other_matches"].as_array().unwrap().len(), 0);
+
+        // By id
+        let resp = app
+            .clone()
+            .oneshot(auth_get(&format!(
+                "/api/code/explain?symbol={}",
+                urlencoding::encode(&format!("{}:Config", file))
+            )))
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["symbol"]["kind"], "struct");
+        assert_eq!(json["symbol"]["docstring"], "Settings");
+        assert!(json["signature"].is_null());
+        assert_eq!(json["source"], "pub struct Config {\n    pub port: u16,\n}");
+        assert_eq!(json["symbol"]["fields"][0]["name"], "port");
+        assert_eq!(json["symbol"]["fields"][0]["type_name"], "u16");
+
+        // The definition of a file outside the project roots is left out
+        let resp = app
+            .clone()
+            .oneshot(auth_get("/api/code/explain?symbol=leak"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(json["source"].is_null());
+
+        let resp = app
+            .clone()
+            .oneshot(auth_get("/api/code/explain?symbol=nope"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        // Names resolve within the given project only
+        let resp = app
+            .oneshot(auth_get(
+                "/api/code/explain?symbol=leak&project_slug=snippets",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
     }
 
     // ====================================================================
     // GET /api/code/references — find_references
     // ====================================================================
 
+    #[tokio::test]
+    async fn test_resolve_stack_trace() {
+        let root = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        let app = test_app_with_snippet(root.path(), outside.path()).await;
+        let file = root.path().join("src/lib.rs").to_string_lossy().to_string();
+        let post = |body: serde_json::Value| {
+            Request::builder()
+                .method("POST")
+                .uri("/api/code/stack-trace")
+                .header("authorization", test_bearer_token())
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let trace = "thread 'main' panicked at src/lib.rs:9:5:\nport missing\n\
+                     stack backtrace:\n   0: std::panicking::begin_panic\n             \
+                     at /rustc/abc/library/std/src/panicking.rs:616:12\n   1: snippets::load\n             \
+                     at ./src/lib.rs:9:5\n";
+        let resp = app
+            .clone()
+            .oneshot(post(serde_json::json!({
+                "trace": trace,
+                "project_slug": "snippets",
+            })))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["language"], "rust");
+        assert_eq!(json["message"], "port missing");
+        assert_eq!(json["resolved_frames"], 2);
+        assert!(json["frames"][1]["function_id"].is_null());
+        let symbols = json["symbols"].as_array().unwrap();
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0]["symbol"]["id"], format!("{}:load:8", file));
+        assert_eq!(symbols[0]["signature"], "fn load() -> Config");
+        assert_eq!(symbols[0]["frame_lines"], serde_json::json!([9, 9]));
+        assert!(symbols[0]["excerpt"]
+            .as_str()
+            .unwrap()
+            .contains(">     9 |     Config { port: 80 }"));
+
+        let resp = app
+            .clone()
+            .oneshot(post(serde_json::json!({
+                "trace": "nothing to see here",
+                "project_slug": "snippets",
+            })))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let resp = app
+            .oneshot(post(serde_json::json!({
+                "trace": trace,
+                "project_slug": "nope",
+            })))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
     #[tokio::test]
     async fn test_find_references_empty() {
         // No references for a symbol that doesn't exist
@@ -5921,7 +7728,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -6134,7 +7944,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -6300,7 +8113,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -6773,4 +8589,196 @@ mod tests {
             "Handler should succeed even with max_depth=100 (clamped to 20)"
         );
     }
+
+    // ====================================================================
+    // GET /api/code/duplicates — get_code_duplicates
+    // ====================================================================
+
+    /// Build a test router with a project holding two copies of one
+    /// function and an unrelated one, all fingerprinted
+    async fn test_app_with_clones() -> axum::Router {
+        use crate::meilisearch::mock::MockSearchStore;
+        use crate::neo4j::mock::MockGraphStore;
+        use crate::neo4j::models::{FunctionFingerprint, FunctionNode, Visibility};
+        use crate::neo4j::traits::GraphStore;
+        use crate::parser::clones::body_signature;
+        use crate::test_helpers::{mock_app_state_with, test_project_named};
+
+        let graph = MockGraphStore::new();
+        let project = test_project_named("dups");
+        graph.create_project(&project).await.unwrap();
+
+        let copied = "fn total(items: &[Item]) -> u64 {\n    let mut sum = 0;\n    for item in items {\n        if item.active && item.price > 0 {\n            sum += item.price * item.quantity;\n        }\n    }\n    tracing::debug!(\"total {}\", sum);\n    sum\n}";
+        let other = "fn render(rows: &[Row]) -> String {\n    let mut out = String::new();\n    for row in rows {\n        out.push_str(&row.label);\n        out.push(':');\n        out.push_str(&row.value.to_string());\n        out.push_str(\"\\n\");\n    }\n    out\n}";
+        let mut paths = Vec::new();
+        let mut fingerprints = Vec::new();
+        for (path, name, body) in [
+            ("/repo/src/cart.rs", "cart_total", copied),
+            ("/repo/src/invoice.rs", "invoice_total", copied),
+            ("/repo/src/report.rs", "render", other),
+        ] {
+            graph
+                .upsert_file(&FileNode {
+                    path: path.to_string(),
+                    language: "rust".to_string(),
+                    hash: "h".to_string(),
+                    line_count: 0,
+                    last_parsed: chrono::Utc::now(),
+                    project_id: Some(project.id),
+                    encoding: None,
+                })
+                .await
+                .unwrap();
+            graph.link_file_to_project(path, project.id).await.unwrap();
+            graph
+                .upsert_function(&FunctionNode {
+                    name: name.to_string(),
+                    visibility: Visibility::Public,
+                    params: vec![],
+                    return_type: None,
+                    generics: vec![],
+                    is_async: false,
+                    is_unsafe: false,
+                    complexity: 2,
+                    file_path: path.to_string(),
+                    line_start: 1,
+                    line_end: 10,
+                    docstring: None,
+                })
+                .await
+                .unwrap();
+            let (token_count, minhash) = body_signature(body).unwrap();
+            paths.push(path.to_string());
+            fingerprints.push(FunctionFingerprint {
+                function_id: format!("{}:{}:1", path, name),
+                token_count,
+                minhash,
+            });
+        }
+        graph
+            .batch_set_function_fingerprints(&paths, &fingerprints)
+            .await
+            .unwrap();
+
+        let app_state = mock_app_state_with(graph, MockSearchStore::new());
+        let orchestrator = Arc::new(Orchestrator::new(app_state).await.unwrap());
+        let watcher = Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
+            orchestrator.clone(),
+        )));
+        let state = Arc::new(ServerState {
+            orchestrator,
+            watcher,
+            chat_manager: None,
+            event_bus: Arc::new(crate::events::HybridEmitter::new(Arc::new(
+                crate::events::EventBus::default(),
+            ))),
+            nats_emitter: None,
+            auth_config: Some(crate::test_helpers::test_auth_config()),
+            serve_frontend: false,
+            frontend_path: "./dist".to_string(),
+            setup_completed: true,
+            server_port: 6600,
+            public_url: None,
+            read_only: false,
+            ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
+            registry_remote_url: None,
+            oidc_client: None,
+            neural_router: crate::test_helpers::mock_neural_router(),
+            trajectory_collector: std::sync::RwLock::new(None),
+            trajectory_store_neo4j: None,
+            trajectory_store: None,
+            identity: None,
+            reactor_counters: std::sync::OnceLock::new(),
+            confidence_tracker: Arc::new(crate::graph::confidence::ConfidenceTracker::default()),
+            mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
+            model_catalog: crate::chat::model_catalog::ModelCatalogCache::new(None),
+        });
+        create_router(state)
+    }
+
+    #[tokio::test]
+    async fn test_get_code_duplicates() {
+        let app = test_app_with_clones().await;
+
+        let resp = app
+            .clone()
+            .oneshot(auth_get("/api/code/duplicates?project_slug=dups"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["fingerprinted_functions"], 3);
+        assert_eq!(json["group_count"], 1);
+        assert_eq!(json["duplicated_function_count"], 2);
+        assert_eq!(json["truncated"], false);
+        let group = &json["groups"][0];
+        assert_eq!(group["similarity"], 1.0);
+        assert_eq!(group["members"][0]["name"], "cart_total");
+        assert_eq!(group["members"][1]["name"], "invoice_total");
+        assert_eq!(
+            json["duplicated_tokens"],
+            group["members"][0]["token_count"]
+        );
+
+        let resp = app
+            .clone()
+            .oneshot(auth_get(
+                "/api/code/duplicates?project_slug=dups&threshold=0.2",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let resp = app
+            .oneshot(auth_get("/api/code/duplicates?project_slug=missing"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_estimate_code_lines() {
+        use crate::meilisearch::indexes::SearchHighlight;
+        use crate::neo4j::models::SymbolLocationNode;
+
+        let symbol = |name: &str, line_start: u32, docstring: Option<&str>| SymbolLocationNode {
+            id: format!("src/lib.rs:{}:{}", name, line_start),
+            name: name.to_string(),
+            kind: "function".to_string(),
+            file_path: "src/lib.rs".to_string(),
+            line_start,
+            line_end: line_start + 3,
+            language: Some("rust".to_string()),
+            docstring: docstring.map(str::to_string),
+            file_hash: None,
+            project_id: None,
+            project_root: None,
+            fields: vec![],
+            variant_payloads: vec![],
+        };
+        let symbols = vec![
+            symbol("load", 8, Some("Read the config from disk")),
+            symbol("parse_config", 20, None),
+        ];
+        let highlight = |field: &str, snippet: &str| SearchHighlight {
+            field: field.to_string(),
+            snippet: snippet.to_string(),
+            line: None,
+        };
+        let mut highlights = vec![
+            highlight("symbols", "parse_<em>config</em>"),
+            highlight("docstrings", "…from <em>disk</em>"),
+            highlight("signatures", "fn <em>save</em>()"),
+        ];
+        estimate_code_lines(&mut highlights, &symbols);
+        // Names win over docstrings
+        assert_eq!(highlights[0].line, Some(20));
+        assert_eq!(highlights[1].line, Some(8));
+        assert_eq!(highlights[2].line, None);
+    }
 }
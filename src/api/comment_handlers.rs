@@ -0,0 +1,516 @@
+//! Comment API handlers
+//!
+//! Discussion threads on tasks, plans and notes. A comment can reply to
+//! another comment of the same entity and mention people with `@handle`;
+//! the mentions are stored with the comment, listed by
+//! `GET /api/comments?mentioned=handle` and sent in the `comment` events, so
+//! notification rules can match them. Only the author of a comment can edit
+//! or delete it.
+//!
+//! The comments of a task and of its plan are part of the agent context of
+//! the task (see `orchestrator::context`).
+
+use super::handlers::{AppError, OrchestratorState};
+use super::validation::ValidPath;
+use crate::auth::jwt::Claims;
+use crate::events::{EntityType, EventEmitter};
+use crate::neo4j::models::{CommentNode, CommentTarget};
+use crate::neo4j::traits::GraphStore;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// Longest comment accepted, in characters
+const MAX_COMMENT_CHARS: usize = 10_000;
+/// Page size of the comments mentioning a handle when none is requested
+const DEFAULT_MENTION_LIMIT: usize = 50;
+/// Largest page of comments mentioning a handle
+const MAX_MENTION_LIMIT: usize = 200;
+
+#[derive(Debug, Deserialize)]
+pub struct CommentsQuery {
+    pub entity_type: Option<CommentTarget>,
+    pub entity_id: Option<Uuid>,
+    /// List the comments mentioning this handle instead
+    pub mentioned: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateCommentRequest {
+    pub entity_type: CommentTarget,
+    pub entity_id: Uuid,
+    pub content: String,
+    /// Comment of the same entity this one replies to
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateCommentRequest {
+    pub content: String,
+}
+
+/// Lowercased `@handles` of a comment, in order of first appearance.
+///
+/// A handle starts with a letter or digit and goes on with letters, digits,
+/// `_`, `-` and `.` (trailing dots and dashes excepted). An `@` preceded by
+/// a letter or digit, as in an email address, is not a mention.
+pub fn extract_mentions(content: &str) -> Vec<String> {
+    let chars: Vec<char> = content.chars().collect();
+    let mut mentions: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let starts_mention = chars[i] == '@'
+            && (i == 0 || !chars[i - 1].is_alphanumeric())
+            && chars.get(i + 1).is_some_and(|c| c.is_alphanumeric());
+        if !starts_mention {
+            i += 1;
+            continue;
+        }
+        let start = i + 1;
+        let mut end = start;
+        while end < chars.len()
+            && (chars[end].is_alphanumeric() || matches!(chars[end], '_' | '-' | '.'))
+        {
+            end += 1;
+        }
+        let handle: String = chars[start..end].iter().collect();
+        let handle = handle.trim_end_matches(['.', '-']).to_lowercase();
+        if !mentions.contains(&handle) {
+            mentions.push(handle);
+        }
+        i = end;
+    }
+    mentions
+}
+
+fn current_user(claims: Option<Extension<Claims>>) -> Claims {
+    claims
+        .map(|Extension(c)| c)
+        .unwrap_or_else(Claims::anonymous)
+}
+
+/// Content of a comment, trimmed; rejects empty or oversized content
+fn check_content(content: &str) -> Result<String, AppError> {
+    let content = content.trim();
+    if content.is_empty() {
+        return Err(AppError::BadRequest(
+            "Comment content is required".to_string(),
+        ));
+    }
+    if content.chars().count() > MAX_COMMENT_CHARS {
+        return Err(AppError::BadRequest(format!(
+            "Comment content exceeds {} characters",
+            MAX_COMMENT_CHARS
+        )));
+    }
+    Ok(content.to_string())
+}
+
+async fn load_comment(neo4j: &dyn GraphStore, id: Uuid) -> Result<CommentNode, AppError> {
+    neo4j
+        .get_comment(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Comment {} not found", id)))
+}
+
+/// Load a comment of the current user; comments of others are read-only
+async fn load_own_comment(
+    neo4j: &dyn GraphStore,
+    id: Uuid,
+    user: &Claims,
+) -> Result<CommentNode, AppError> {
+    let comment = load_comment(neo4j, id).await?;
+    if comment.author_id != user.sub {
+        return Err(AppError::Forbidden(
+            "Only the author can change a comment".to_string(),
+        ));
+    }
+    Ok(comment)
+}
+
+/// Project of the entity a comment is attached to, for the events
+async fn project_of(neo4j: &dyn GraphStore, comment: &CommentNode) -> Option<String> {
+    let project_id = match comment.entity_type {
+        CommentTarget::Task => neo4j
+            .get_project_for_task(comment.entity_id)
+            .await
+            .ok()
+            .flatten()
+            .map(|p| p.id),
+        CommentTarget::Plan => neo4j
+            .get_plan(comment.entity_id)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|p| p.project_id),
+        CommentTarget::Note => neo4j
+            .get_note(comment.entity_id)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|n| n.project_id),
+    };
+    project_id.map(|id| id.to_string())
+}
+
+/// GET /api/comments — Comments of a task, plan or note (oldest first), or
+/// the comments mentioning a handle (most recent first)
+pub async fn list_comments(
+    State(state): State<OrchestratorState>,
+    Query(query): Query<CommentsQuery>,
+) -> Result<Json<Vec<CommentNode>>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    if let Some(handle) = query.mentioned {
+        let handle = handle.trim().trim_start_matches('@');
+        if handle.is_empty() {
+            return Err(AppError::BadRequest("mentioned is empty".to_string()));
+        }
+        let limit = query
+            .limit
+            .unwrap_or(DEFAULT_MENTION_LIMIT)
+            .min(MAX_MENTION_LIMIT);
+        return Ok(Json(neo4j.list_comments_mentioning(handle, limit).await?));
+    }
+    let (Some(entity_type), Some(entity_id)) = (query.entity_type, query.entity_id) else {
+        return Err(AppError::BadRequest(
+            "entity_type and entity_id (or mentioned) are required".to_string(),
+        ));
+    };
+    Ok(Json(neo4j.list_comments(entity_type, entity_id).await?))
+}
+
+/// POST /api/comments — Comment on a task, plan or note, or reply to a comment
+pub async fn create_comment(
+    State(state): State<OrchestratorState>,
+    claims: Option<Extension<Claims>>,
+    Json(body): Json<CreateCommentRequest>,
+) -> Result<(StatusCode, Json<CommentNode>), AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let user = current_user(claims);
+    let content = check_content(&body.content)?;
+    if let Some(parent_id) = body.parent_id {
+        let parent = load_comment(neo4j, parent_id).await?;
+        if parent.entity_type != body.entity_type || parent.entity_id != body.entity_id {
+            return Err(AppError::BadRequest(
+                "A reply must be on the entity of its parent comment".to_string(),
+            ));
+        }
+    }
+    let now = chrono::Utc::now();
+    let comment = CommentNode {
+        id: Uuid::new_v4(),
+        entity_type: body.entity_type,
+        entity_id: body.entity_id,
+        author: if user.name.is_empty() {
+            user.email.clone()
+        } else {
+            user.name.clone()
+        },
+        author_id: user.sub,
+        mentions: extract_mentions(&content),
+        content,
+        parent_id: body.parent_id,
+        created_at: now,
+        updated_at: now,
+    };
+    if !neo4j.create_comment(&comment).await? {
+        return Err(AppError::NotFound(format!(
+            "{} {} not found",
+            comment.entity_type.label(),
+            comment.entity_id
+        )));
+    }
+    state.event_bus.emit_created(
+        EntityType::Comment,
+        &comment.id.to_string(),
+        serde_json::to_value(&comment).unwrap_or_default(),
+        project_of(neo4j, &comment).await,
+    );
+    Ok((StatusCode::CREATED, Json(comment)))
+}
+
+/// GET /api/comments/{id} — Get a comment
+pub async fn get_comment(
+    State(state): State<OrchestratorState>,
+    ValidPath(id): ValidPath<Uuid>,
+) -> Result<Json<CommentNode>, AppError> {
+    Ok(Json(load_comment(state.orchestrator.neo4j(), id).await?))
+}
+
+/// PATCH /api/comments/{id} — Edit a comment (author only)
+pub async fn update_comment(
+    State(state): State<OrchestratorState>,
+    claims: Option<Extension<Claims>>,
+    ValidPath(id): ValidPath<Uuid>,
+    Json(body): Json<UpdateCommentRequest>,
+) -> Result<Json<CommentNode>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let user = current_user(claims);
+    let mut comment = load_own_comment(neo4j, id, &user).await?;
+    comment.content = check_content(&body.content)?;
+    comment.mentions = extract_mentions(&comment.content);
+    comment.updated_at = chrono::Utc::now();
+    neo4j.update_comment(&comment).await?;
+    state.event_bus.emit_updated(
+        EntityType::Comment,
+        &comment.id.to_string(),
+        serde_json::to_value(&comment).unwrap_or_default(),
+        project_of(neo4j, &comment).await,
+    );
+    Ok(Json(comment))
+}
+
+/// DELETE /api/comments/{id} — Delete a comment and its replies (author only)
+pub async fn delete_comment(
+    State(state): State<OrchestratorState>,
+    claims: Option<Extension<Claims>>,
+    ValidPath(id): ValidPath<Uuid>,
+) -> Result<StatusCode, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let user = current_user(claims);
+    let comment = load_own_comment(neo4j, id, &user).await?;
+    let project_id = project_of(neo4j, &comment).await;
+    neo4j.delete_comment(id).await?;
+    state
+        .event_bus
+        .emit_deleted(EntityType::Comment, &id.to_string(), project_id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::handlers::ServerState;
+    use crate::api::routes::create_router;
+    use crate::orchestrator::watcher::FileWatcher;
+    use crate::orchestrator::Orchestrator;
+    use crate::test_helpers::{
+        mock_app_state, test_auth_config, test_bearer_token, test_plan, test_task,
+    };
+    use axum::{body::Body, http::Request};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    async fn mock_server_state() -> OrchestratorState {
+        let app_state = mock_app_state();
+        let orchestrator = Arc::new(Orchestrator::new(app_state).await.unwrap());
+        let watcher = Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
+            orchestrator.clone(),
+        )));
+        Arc::new(ServerState {
+            orchestrator,
+            watcher,
+            chat_manager: None,
+            event_bus: Arc::new(crate::events::HybridEmitter::new(Arc::new(
+                crate::events::EventBus::default(),
+            ))),
+            nats_emitter: None,
+            auth_config: Some(test_auth_config()),
+            serve_frontend: false,
+            frontend_path: "./dist".to_string(),
+            setup_completed: true,
+            server_port: 6600,
+            public_url: None,
+            read_only: false,
+            ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
+            registry_remote_url: None,
+            oidc_client: None,
+            neural_router: crate::test_helpers::mock_neural_router(),
+            trajectory_collector: std::sync::RwLock::new(None),
+            trajectory_store_neo4j: None,
+            trajectory_store: None,
+            identity: None,
+            reactor_counters: std::sync::OnceLock::new(),
+            confidence_tracker: Arc::new(crate::graph::confidence::ConfidenceTracker::default()),
+            mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
+            model_catalog: crate::chat::model_catalog::ModelCatalogCache::new(None),
+        })
+    }
+
+    /// Bearer token of a fixed user, so that several requests share an author
+    fn author_token(user_id: Uuid) -> String {
+        let token = crate::auth::jwt::encode_jwt(
+            user_id,
+            "alice@ffs.holdings",
+            "Alice",
+            &test_auth_config().jwt_secret,
+            3600,
+        )
+        .unwrap();
+        format!("Bearer {}", token)
+    }
+
+    fn request(method: &str, uri: &str, token: &str, body: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("authorization", token)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    async fn json_body(resp: axum::response::Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[test]
+    fn test_extract_mentions() {
+        assert_eq!(
+            extract_mentions("@Bob can you check this with @alice.smith? cc @bob."),
+            vec!["bob", "alice.smith"]
+        );
+        // Email addresses and lone @ are not mentions
+        assert!(extract_mentions("mail bob@example.com @ noon").is_empty());
+        assert_eq!(extract_mentions("(@ops-team)"), vec!["ops-team"]);
+    }
+
+    #[tokio::test]
+    async fn test_comment_thread() {
+        let state = mock_server_state().await;
+        let neo4j = state.orchestrator.neo4j();
+        let plan = test_plan();
+        neo4j.create_plan(&plan).await.unwrap();
+        let task = test_task();
+        neo4j.create_task(plan.id, &task).await.unwrap();
+        let alice = author_token(Uuid::new_v4());
+        let app = create_router(state.clone());
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/api/comments",
+                &alice,
+                serde_json::json!({
+                    "entity_type": "task",
+                    "entity_id": Uuid::new_v4(),
+                    "content": "Lost?"
+                }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/api/comments",
+                &alice,
+                serde_json::json!({
+                    "entity_type": "task",
+                    "entity_id": task.id,
+                    "content": "@bob the API changed, see the plan"
+                }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let root = json_body(resp).await;
+        assert_eq!(root["author"], "Alice");
+        assert_eq!(root["mentions"], serde_json::json!(["bob"]));
+        let root_id = root["id"].as_str().unwrap().to_string();
+
+        let bob = test_bearer_token();
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/api/comments",
+                &bob,
+                serde_json::json!({
+                    "entity_type": "task",
+                    "entity_id": task.id,
+                    "content": "Thanks, on it",
+                    "parent_id": root_id
+                }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "GET",
+                &format!("/api/comments?entity_type=task&entity_id={}", task.id),
+                &alice,
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        let thread = json_body(resp).await;
+        assert_eq!(thread.as_array().unwrap().len(), 2);
+        assert_eq!(thread[1]["parent_id"], root_id.as_str());
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "GET",
+                "/api/comments?mentioned=@Bob",
+                &alice,
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(json_body(resp).await[0]["id"], root_id.as_str());
+
+        // Only the author edits or deletes a comment
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "PATCH",
+                &format!("/api/comments/{}", root_id),
+                &bob,
+                serde_json::json!({"content": "hijacked"}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "PATCH",
+                &format!("/api/comments/{}", root_id),
+                &alice,
+                serde_json::json!({"content": "@carol the API changed"}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            json_body(resp).await["mentions"],
+            serde_json::json!(["carol"])
+        );
+
+        // Deleting the root deletes the thread
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "DELETE",
+                &format!("/api/comments/{}", root_id),
+                &alice,
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        assert!(neo4j
+            .list_comments(CommentTarget::Task, task.id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,413 @@
+//! API error type
+//!
+//! Every handler error is an [`AppError`]. It is rendered as an RFC 7807
+//! `application/problem+json` body with a stable `code` that clients can
+//! match on instead of parsing messages:
+//!
+//! ```json
+//! {
+//!   "type": "urn:project-orchestrator:error:not_found",
+//!   "title": "Not Found",
+//!   "status": 404,
+//!   "detail": "Project 'po' not found",
+//!   "code": "not_found",
+//!   "error": "Project 'po' not found"
+//! }
+//! ```
+//!
+//! `error` repeats `detail` for clients written against the former
+//! `{"error": "..."}` bodies.
+
+use axum::{
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use serde::Serialize;
+
+use super::validation::FieldError;
+
+/// Content type of error bodies
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// Prefix of the `type` URI of error bodies, followed by the error code
+const PROBLEM_TYPE_PREFIX: &str = "urn:project-orchestrator:error:";
+
+/// Storage backend whose failure made a request fail
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// Neo4j
+    Graph,
+    /// Meilisearch
+    Search,
+}
+
+/// Application error type
+#[derive(Debug)]
+pub enum AppError {
+    Internal(anyhow::Error),
+    NotFound(String),
+    BadRequest(String),
+    Unauthorized(String),
+    Forbidden(String),
+    Conflict(String),
+    TooManyRequests(String),
+    Locked(String),
+    /// The request has invalid fields
+    Validation(Vec<FieldError>),
+    /// The graph or search store failed (unreachable, query rejected, ...)
+    Unavailable(Backend, anyhow::Error),
+    /// The server can't take the request right now, retry later
+    ServiceUnavailable(String),
+    /// `If-Match` named another version than the entity's current one
+    VersionConflict {
+        expected: i64,
+        current_version: i64,
+        /// The entity as currently stored
+        current: serde_json::Value,
+    },
+}
+
+impl AppError {
+    /// HTTP status of the error
+    pub fn status(&self) -> StatusCode {
+        match self {
+            AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+            AppError::Conflict(_) | AppError::VersionConflict { .. } => StatusCode::CONFLICT,
+            AppError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            AppError::Locked(_) => StatusCode::LOCKED,
+            AppError::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::Unavailable(..) | AppError::ServiceUnavailable(_) => {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+        }
+    }
+
+    /// Stable machine-readable code of the error
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Internal(_) => "internal",
+            AppError::NotFound(_) => "not_found",
+            AppError::BadRequest(_) => "bad_request",
+            AppError::Unauthorized(_) => "unauthorized",
+            AppError::Forbidden(_) => "forbidden",
+            AppError::Conflict(_) => "conflict",
+            AppError::TooManyRequests(_) => "too_many_requests",
+            AppError::Locked(_) => "locked",
+            AppError::Validation(_) => "validation_failed",
+            AppError::Unavailable(Backend::Graph, _) => "graph_store_unavailable",
+            AppError::Unavailable(Backend::Search, _) => "search_store_unavailable",
+            AppError::ServiceUnavailable(_) => "service_unavailable",
+            AppError::VersionConflict { .. } => "version_conflict",
+        }
+    }
+
+    fn detail(&self) -> String {
+        match self {
+            AppError::Internal(e) | AppError::Unavailable(_, e) => e.to_string(),
+            AppError::NotFound(msg)
+            | AppError::BadRequest(msg)
+            | AppError::Unauthorized(msg)
+            | AppError::Forbidden(msg)
+            | AppError::Conflict(msg)
+            | AppError::TooManyRequests(msg)
+            | AppError::Locked(msg)
+            | AppError::ServiceUnavailable(msg) => msg.clone(),
+            AppError::Validation(errors) => format!(
+                "Invalid request: {}",
+                errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            ),
+            AppError::VersionConflict {
+                expected,
+                current_version,
+                ..
+            } => format!(
+                "Expected version {} but the current version is {}: reload and retry",
+                expected, current_version
+            ),
+        }
+    }
+
+    /// The RFC 7807 body of the error
+    pub fn problem(&self) -> ProblemDetails {
+        let status = self.status();
+        let detail = self.detail();
+        ProblemDetails {
+            problem_type: format!("{}{}", PROBLEM_TYPE_PREFIX, self.code()),
+            title: status.canonical_reason().unwrap_or("Error"),
+            status: status.as_u16(),
+            code: self.code(),
+            error: detail.clone(),
+            detail,
+            errors: match self {
+                AppError::Validation(errors) => errors.clone(),
+                _ => Vec::new(),
+            },
+            current_version: match self {
+                AppError::VersionConflict {
+                    current_version, ..
+                } => Some(*current_version),
+                _ => None,
+            },
+            current: match self {
+                AppError::VersionConflict { current, .. } => Some(current.clone()),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// An RFC 7807 problem details object
+#[derive(Debug, Serialize)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: &'static str,
+    pub status: u16,
+    pub detail: String,
+    pub code: &'static str,
+    /// Same as `detail`
+    pub error: String,
+    /// Invalid fields of `validation_failed` errors
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+    /// Current version of the entity of `version_conflict` errors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_version: Option<i64>,
+    /// Current state of the entity of `version_conflict` errors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current: Option<serde_json::Value>,
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> axum::response::Response {
+        if matches!(self, AppError::Internal(_) | AppError::Unavailable(..)) {
+            tracing::warn!(code = self.code(), "Request failed: {}", self.detail());
+        }
+        (
+            self.status(),
+            [(header::CONTENT_TYPE, PROBLEM_JSON)],
+            Json(self.problem()),
+        )
+            .into_response()
+    }
+}
+
+/// Neo4j status code of a uniqueness or existence constraint violation
+const NEO4J_CONSTRAINT_VIOLATION: &str = "Neo.ClientError.Schema.ConstraintValidationFailed";
+
+/// What a store error says about a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StoreFailure {
+    /// The store can't be reached or timed out
+    Unavailable,
+    /// The write broke a constraint
+    Conflict,
+    /// The document asked for does not exist
+    NotFound,
+    /// Anything else: a bug or a misconfiguration
+    Other,
+}
+
+fn graph_failure(err: &neo4rs::Error) -> StoreFailure {
+    match err {
+        neo4rs::Error::IOError { .. } | neo4rs::Error::ConnectionError => StoreFailure::Unavailable,
+        neo4rs::Error::Neo4j(e) => neo4j_failure(e.code()),
+        _ => StoreFailure::Other,
+    }
+}
+
+fn neo4j_failure(code: &str) -> StoreFailure {
+    if code == NEO4J_CONSTRAINT_VIOLATION {
+        StoreFailure::Conflict
+    } else {
+        StoreFailure::Other
+    }
+}
+
+fn search_failure(err: &meilisearch_sdk::errors::Error) -> StoreFailure {
+    use meilisearch_sdk::errors::{Error, ErrorCode};
+    match err {
+        Error::Timeout => StoreFailure::Unavailable,
+        Error::HttpError(e) if e.is_connect() || e.is_timeout() => StoreFailure::Unavailable,
+        // Proxy or server failure in front of the API
+        Error::MeilisearchCommunication(e) if e.status_code >= 500 => StoreFailure::Unavailable,
+        Error::Meilisearch(e) if e.error_code == ErrorCode::DocumentNotFound => {
+            StoreFailure::NotFound
+        }
+        _ => StoreFailure::Other,
+    }
+}
+
+impl From<anyhow::Error> for AppError {
+    /// The first store error in the chain decides: connection and pool
+    /// failures and timeouts become `Unavailable`, a Neo4j constraint
+    /// violation `Conflict`, a missing Meilisearch document `NotFound`.
+    /// Everything else — a rejected query, a missing index — is `Internal`.
+    fn from(err: anyhow::Error) -> Self {
+        let failure = err.chain().find_map(|e| {
+            if let Some(e) = e.downcast_ref::<neo4rs::Error>() {
+                Some((Backend::Graph, graph_failure(e)))
+            } else if e.is::<crate::neo4j::QueryTimeout>() {
+                Some((Backend::Graph, StoreFailure::Unavailable))
+            } else if let Some(e) = e.downcast_ref::<meilisearch_sdk::errors::Error>() {
+                Some((Backend::Search, search_failure(e)))
+            } else if e.is::<crate::meilisearch::resilient::SearchUnavailable>() {
+                Some((Backend::Search, StoreFailure::Unavailable))
+            } else {
+                None
+            }
+        });
+        match failure {
+            Some((backend, StoreFailure::Unavailable)) => AppError::Unavailable(backend, err),
+            Some((_, StoreFailure::Conflict)) => AppError::Conflict(format!("{:#}", err)),
+            Some((_, StoreFailure::NotFound)) => AppError::NotFound(format!("{:#}", err)),
+            Some((_, StoreFailure::Other)) | None => AppError::Internal(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn body(resp: axum::response::Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_problem_json_response() {
+        let resp = AppError::NotFound("Project 'po' not found".to_string()).into_response();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        assert_eq!(resp.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+        let json = body(resp).await;
+        assert_eq!(json["type"], "urn:project-orchestrator:error:not_found");
+        assert_eq!(json["title"], "Not Found");
+        assert_eq!(json["status"], 404);
+        assert_eq!(json["code"], "not_found");
+        assert_eq!(json["detail"], "Project 'po' not found");
+        assert_eq!(json["error"], "Project 'po' not found");
+        assert!(json.get("errors").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_validation_problem_lists_fields() {
+        let err = AppError::Validation(vec![
+            FieldError::new("title", "must not be empty"),
+            FieldError::new("tags[0]", "must be at most 100 characters, got 101"),
+        ]);
+        let resp = err.into_response();
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let json = body(resp).await;
+        assert_eq!(json["code"], "validation_failed");
+        assert_eq!(
+            json["detail"],
+            "Invalid request: title: must not be empty; tags[0]: must be at most 100 characters, got 101"
+        );
+        assert_eq!(json["errors"][0]["field"], "title");
+        assert_eq!(
+            json["errors"][1]["message"],
+            "must be at most 100 characters, got 101"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_version_conflict_carries_current_state() {
+        let err = AppError::VersionConflict {
+            expected: 2,
+            current_version: 3,
+            current: serde_json::json!({"title": "Theirs", "version": 3}),
+        };
+        let resp = err.into_response();
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+        let json = body(resp).await;
+        assert_eq!(json["code"], "version_conflict");
+        assert_eq!(json["current_version"], 3);
+        assert_eq!(json["current"]["title"], "Theirs");
+    }
+
+    #[tokio::test]
+    async fn test_store_errors_are_unavailable() {
+        let err: anyhow::Error = meilisearch_sdk::errors::Error::Timeout.into();
+        let err = AppError::from(err.context("Failed to search code"));
+        assert!(matches!(err, AppError::Unavailable(Backend::Search, _)));
+        let resp = err.into_response();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body(resp).await["code"], "search_store_unavailable");
+
+        let err: anyhow::Error = crate::meilisearch::resilient::SearchUnavailable.into();
+        assert_eq!(AppError::from(err).code(), "search_store_unavailable");
+
+        let err: anyhow::Error = crate::neo4j::QueryTimeout {
+            caller: "src/neo4j/note.rs:10:5".to_string(),
+            timeout: std::time::Duration::from_secs(60),
+        }
+        .into();
+        let err = AppError::from(err.context("Failed to list notes"));
+        assert_eq!(err.code(), "graph_store_unavailable");
+
+        // Plain errors stay internal, even when they mention a store
+        let err = AppError::from(anyhow::anyhow!("Neo4j connection refused"));
+        assert!(matches!(err, AppError::Internal(_)));
+        assert_eq!(err.code(), "internal");
+    }
+
+    #[test]
+    fn test_store_errors_are_told_apart() {
+        let err: anyhow::Error = neo4rs::Error::ConnectionError.into();
+        let err = AppError::from(err.context("Failed to get project"));
+        assert_eq!(err.code(), "graph_store_unavailable");
+        let io = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset");
+        let err: anyhow::Error = neo4rs::Error::from(io).into();
+        assert_eq!(AppError::from(err).code(), "graph_store_unavailable");
+
+        // A query the driver or Neo4j rejects is a bug, not an outage
+        let err: anyhow::Error = neo4rs::Error::ConversionError.into();
+        assert_eq!(AppError::from(err).code(), "internal");
+        assert_eq!(
+            neo4j_failure(NEO4J_CONSTRAINT_VIOLATION),
+            StoreFailure::Conflict
+        );
+        assert_eq!(
+            neo4j_failure("Neo.ClientError.Statement.SyntaxError"),
+            StoreFailure::Other
+        );
+
+        let meili = |code: &str| -> anyhow::Error {
+            let error: meilisearch_sdk::errors::MeilisearchError =
+                serde_json::from_value(serde_json::json!({
+                    "message": "not found",
+                    "code": code,
+                    "type": "invalid_request",
+                    "link": "https://docs.meilisearch.com/errors",
+                }))
+                .unwrap();
+            meilisearch_sdk::errors::Error::Meilisearch(error).into()
+        };
+        let err = AppError::from(meili("document_not_found").context("Failed to get document"));
+        assert_eq!(err.status(), StatusCode::NOT_FOUND);
+        assert!(err.detail().starts_with("Failed to get document: "));
+        assert_eq!(AppError::from(meili("index_not_found")).code(), "internal");
+
+        let err: anyhow::Error = meilisearch_sdk::errors::Error::MeilisearchCommunication(
+            meilisearch_sdk::errors::MeilisearchCommunicationError {
+                status_code: 502,
+                message: None,
+                url: "http://meilisearch:7700/indexes/code/search".to_string(),
+            },
+        )
+        .into();
+        assert_eq!(AppError::from(err).code(), "search_store_unavailable");
+    }
+}
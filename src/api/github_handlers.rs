@@ -0,0 +1,361 @@
+//! GitHub App API handlers
+//!
+//! The App webhook (public, authenticated by its HMAC signature) and the
+//! per-project check run settings.
+
+use super::handlers::{AppError, OrchestratorState};
+use super::validation::ValidPath;
+use crate::github::app::GitHubAppConfig;
+use crate::github::checks::{parse_pull_request_event, run_pull_request_check};
+use crate::neo4j::models::GitHubCheckSettings;
+use axum::{extract::State, http::StatusCode, Json};
+use serde_json::json;
+use uuid::Uuid;
+
+// ============================================================================
+// Webhook
+// ============================================================================
+
+/// POST /api/github/webhook — Receive a GitHub App webhook.
+///
+/// Pull requests opened or pushed to on a repository a project checks are
+/// queued for a check run (202); other events are acknowledged and ignored.
+pub async fn receive_github_webhook(
+    State(state): State<OrchestratorState>,
+    headers: axum::http::HeaderMap,
+    body: axum::body::Bytes,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    let config = GitHubAppConfig::from_env().ok_or_else(|| {
+        AppError::NotFound("GitHub App integration is not configured".to_string())
+    })?;
+
+    let signature = headers
+        .get("x-hub-signature-256")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    if !crate::runner::providers::webhook::validate_github_signature(
+        &config.webhook_secret,
+        &body,
+        signature,
+    ) {
+        return Err(AppError::Unauthorized(
+            "Invalid webhook signature".to_string(),
+        ));
+    }
+
+    let ignored = |reason: String| -> Result<_, AppError> {
+        Ok((
+            StatusCode::OK,
+            Json(json!({ "status": "ignored", "reason": reason })),
+        ))
+    };
+    let github_event = headers
+        .get("x-github-event")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    match github_event {
+        "ping" => return Ok((StatusCode::OK, Json(json!({ "status": "pong" })))),
+        "pull_request" => {}
+        other => return ignored(format!("Event '{}' is not handled", other)),
+    }
+
+    let payload: serde_json::Value = serde_json::from_slice(&body)
+        .map_err(|e| AppError::BadRequest(format!("Invalid JSON payload: {}", e)))?;
+    let Some(event) = parse_pull_request_event(&payload) else {
+        return ignored("Pull request action does not run the check".to_string());
+    };
+
+    let neo4j = state.orchestrator.neo4j();
+    let Some(project) = neo4j
+        .find_project_by_github_repository(&event.repository)
+        .await?
+    else {
+        return ignored(format!("No project checks {}", event.repository));
+    };
+    let Some(settings) = neo4j
+        .get_project_github_checks(project.id)
+        .await?
+        .filter(|s| s.enabled)
+    else {
+        return ignored(format!("Checks are disabled for {}", event.repository));
+    };
+
+    let response = json!({
+        "status": "queued",
+        "project_id": project.id,
+        "pull_request": event.number,
+        "head_sha": event.head_sha,
+    });
+    let orchestrator = state.orchestrator.clone();
+    tokio::spawn(async move {
+        let (repository, number) = (event.repository.clone(), event.number);
+        if let Err(e) = run_pull_request_check(orchestrator, config, project, settings, event).await
+        {
+            tracing::warn!(
+                "GitHub check run for {}#{} failed: {}",
+                repository,
+                number,
+                e
+            );
+        }
+    });
+    Ok((StatusCode::ACCEPTED, Json(response)))
+}
+
+// ============================================================================
+// Project check settings
+// ============================================================================
+
+/// Whether `repository` has the `owner/name` form GitHub uses
+fn is_repository_name(repository: &str) -> bool {
+    let valid = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+    repository
+        .split_once('/')
+        .is_some_and(|(owner, name)| valid(owner) && valid(name))
+}
+
+/// Get the GitHub check run settings of a project
+pub async fn get_github_checks(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+) -> Result<Json<GitHubCheckSettings>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    neo4j
+        .get_project_github_checks(project_id)
+        .await?
+        .map(Json)
+        .ok_or_else(|| {
+            AppError::NotFound(format!(
+                "GitHub checks are not configured for project {}",
+                project_id
+            ))
+        })
+}
+
+/// Replace the GitHub check run settings of a project
+pub async fn set_github_checks(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+    Json(mut settings): Json<GitHubCheckSettings>,
+) -> Result<Json<GitHubCheckSettings>, AppError> {
+    settings.repository = settings.repository.trim().to_string();
+    settings.check_name = settings.check_name.trim().to_string();
+    if !is_repository_name(&settings.repository) {
+        return Err(AppError::BadRequest(format!(
+            "repository must be owner/name, got '{}'",
+            settings.repository
+        )));
+    }
+    if settings.check_name.is_empty() {
+        return Err(AppError::BadRequest(
+            "check_name must not be empty".to_string(),
+        ));
+    }
+
+    let neo4j = state.orchestrator.neo4j();
+    neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+    if let Some(other) = neo4j
+        .find_project_by_github_repository(&settings.repository)
+        .await?
+        .filter(|p| p.id != project_id)
+    {
+        return Err(AppError::Conflict(format!(
+            "{} is already checked by project {}",
+            settings.repository, other.slug
+        )));
+    }
+
+    neo4j
+        .update_project_github_checks(project_id, Some(&settings))
+        .await?;
+    Ok(Json(settings))
+}
+
+/// Remove the GitHub check run settings of a project
+pub async fn delete_github_checks(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+) -> Result<StatusCode, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    neo4j.update_project_github_checks(project_id, None).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::handlers::ServerState;
+    use crate::api::routes::create_router;
+    use crate::orchestrator::watcher::FileWatcher;
+    use crate::orchestrator::Orchestrator;
+    use crate::test_helpers::{
+        mock_app_state, test_auth_config, test_bearer_token, test_project_named,
+    };
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode as AxumStatus},
+    };
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    async fn mock_server_state() -> OrchestratorState {
+        let app_state = mock_app_state();
+        let orchestrator = Arc::new(Orchestrator::new(app_state).await.unwrap());
+        let watcher = Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
+            orchestrator.clone(),
+        )));
+        Arc::new(ServerState {
+            orchestrator,
+            watcher,
+            chat_manager: None,
+            event_bus: Arc::new(crate::events::HybridEmitter::new(Arc::new(
+                crate::events::EventBus::default(),
+            ))),
+            nats_emitter: None,
+            auth_config: Some(test_auth_config()),
+            serve_frontend: false,
+            frontend_path: "./dist".to_string(),
+            setup_completed: true,
+            server_port: 6600,
+            public_url: None,
+            read_only: false,
+            ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
+            registry_remote_url: None,
+            oidc_client: None,
+            neural_router: crate::test_helpers::mock_neural_router(),
+            trajectory_collector: std::sync::RwLock::new(None),
+            trajectory_store_neo4j: None,
+            trajectory_store: None,
+            identity: None,
+            reactor_counters: std::sync::OnceLock::new(),
+            confidence_tracker: Arc::new(crate::graph::confidence::ConfidenceTracker::default()),
+            mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
+            model_catalog: crate::chat::model_catalog::ModelCatalogCache::new(None),
+        })
+    }
+
+    fn authed(method: &str, uri: &str, body: Option<serde_json::Value>) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("authorization", test_bearer_token())
+            .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_is_repository_name() {
+        assert!(is_repository_name("acme/widgets"));
+        assert!(is_repository_name("this-rs/project.orchestrator_2"));
+        assert!(!is_repository_name("widgets"));
+        assert!(!is_repository_name("acme/"));
+        assert!(!is_repository_name("acme/widgets/extra"));
+        assert!(!is_repository_name("acme/wid gets"));
+    }
+
+    #[tokio::test]
+    async fn test_github_check_settings() {
+        let state = mock_server_state().await;
+        let project = test_project_named("checked");
+        let other = test_project_named("other");
+        let neo4j = state.orchestrator.neo4j();
+        neo4j.create_project(&project).await.unwrap();
+        neo4j.create_project(&other).await.unwrap();
+        let app = create_router(state.clone());
+        let uri = format!("/api/projects/{}/github-checks", project.id);
+
+        let resp = app
+            .clone()
+            .oneshot(authed("GET", &uri, None))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::NOT_FOUND);
+
+        let resp = app
+            .clone()
+            .oneshot(authed(
+                "PUT",
+                &uri,
+                Some(json!({ "repository": "not-a-repo" })),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::BAD_REQUEST);
+
+        let resp = app
+            .clone()
+            .oneshot(authed(
+                "PUT",
+                &uri,
+                Some(json!({ "repository": "Acme/Widgets", "fail_on_rules": false })),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+
+        let resp = app
+            .clone()
+            .oneshot(authed("GET", &uri, None))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let settings: GitHubCheckSettings = serde_json::from_slice(&body).unwrap();
+        assert!(settings.enabled && settings.fail_on_budgets && !settings.fail_on_rules);
+        assert_eq!(settings.check_name, "Project Orchestrator");
+
+        // Repository lookup ignores case
+        let found = neo4j
+            .find_project_by_github_repository("acme/widgets")
+            .await
+            .unwrap();
+        assert_eq!(found.map(|p| p.id), Some(project.id));
+
+        // One project per repository
+        let resp = app
+            .clone()
+            .oneshot(authed(
+                "PUT",
+                &format!("/api/projects/{}/github-checks", other.id),
+                Some(json!({ "repository": "acme/widgets" })),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::CONFLICT);
+
+        let resp = app
+            .clone()
+            .oneshot(authed("DELETE", &uri, None))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::NO_CONTENT);
+        assert!(neo4j
+            .find_project_by_github_repository("acme/widgets")
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
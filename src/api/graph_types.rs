@@ -390,7 +390,7 @@ pub async fn build_project_graph_data(
 
         // CALLS edges between functions
         let call_edges = neo4j
-            .get_project_call_edges(project.id)
+            .get_project_call_edges(project.id, 0.0)
             .await
             .unwrap_or_default();
 
@@ -1,9 +1,15 @@
 //! API request handlers
 
+pub use super::error::AppError;
+use super::validation::{
+    ValidJson, ValidPath, ValidQuery, Validate, Validator, MAX_TEXT_LEN, MAX_TITLE_LEN,
+};
+use super::versioning::{claim_version, current_version, etag};
 use crate::api::{
     workspace_handlers::{MilestonePlanSummary, MilestoneStepSummary, MilestoneTaskSummary},
     PaginatedResponse, PaginationParams, PriorityFilter, SearchFilter, StatusFilter, TagsFilter,
 };
+use crate::chat::undo::{self, UndoOperation};
 use crate::chat::ChatManager;
 use crate::events::{EventEmitter, HybridEmitter, NatsEmitter};
 use crate::graph::algorithms::add_thermal_noise;
@@ -11,7 +17,7 @@ use crate::identity::InstanceIdentity;
 use crate::neo4j::models::{
     AffectsRelation, CommitNode, ConstraintNode, DecisionNode, DecisionStatus,
     DecisionTimelineEntry, MilestoneNode, MilestoneStatus, PlanNode, PlanStatus, ReleaseNode,
-    ReleaseStatus, StepNode, TaskNode, TaskWithPlan,
+    ReleaseStatus, StepNode, TaskNode, TaskWithPlan, VersionedNode,
 };
 use crate::neo4j::plan::{compute_file_conflicts, WaveComputationResult};
 use crate::orchestrator::{FileWatcher, Orchestrator};
@@ -20,7 +26,6 @@ use crate::AuthConfig;
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    response::IntoResponse,
     Json,
 };
 use serde::{Deserialize, Serialize};
@@ -51,9 +56,17 @@ pub struct ServerState {
     /// Public URL for reverse-proxy setups (e.g. https://ffs.dev).
     /// Used for CORS and OAuth origin whitelist when both desktop + web access is needed.
     pub public_url: Option<String>,
+    /// Read-only mode: mutating routes are rejected with 423 Locked
+    /// (see [`super::read_only`]).
+    pub read_only: bool,
     /// In-memory store for ephemeral WebSocket auth tickets.
     /// Used as a fallback when cookies are not sent on WS upgrades (WKWebView).
     pub ws_ticket_store: Arc<super::ws_auth::WsTicketStore>,
+    /// Who is viewing or editing which plan or note (see [`super::presence`]).
+    pub presence: Arc<super::presence::PresenceTracker>,
+    /// Kept responses of POSTs with an `Idempotency-Key` (see
+    /// [`super::idempotency`]).
+    pub idempotency: Arc<super::idempotency::IdempotencyStore>,
     /// Remote skill registry URL (optional — enables cross-instance skill search).
     /// When set, registry search merges local + remote results.
     pub registry_remote_url: Option<String>,
@@ -190,20 +203,29 @@ pub struct ServiceHealthStatus {
     pub nats: String,
 }
 
+/// `service` of the health responses, by which `orchestrator doctor` tells
+/// a running orchestrator from another process on its port
+pub const SERVICE_NAME: &str = "project-orchestrator";
+
 /// Health check response
 #[derive(Serialize)]
 pub struct HealthResponse {
+    pub service: String,
     pub status: String,
     pub version: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub services: Option<ServiceHealthStatus>,
+    /// Meilisearch circuit breaker, when the search store has one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_circuit: Option<crate::meilisearch::SearchCircuitStatus>,
 }
 
 /// Health check handler — verifies actual connectivity to Neo4j, Meilisearch, and NATS.
 ///
 /// Returns:
 /// - 200 + `"ok"` if both Neo4j and Meilisearch are connected
-/// - 200 + `"degraded"` if Neo4j is connected but Meilisearch is not
+/// - 200 + `"degraded"` if Neo4j is connected but Meilisearch is not (or its
+///   circuit breaker still has writes to replay)
 /// - 503 + `"unhealthy"` if Neo4j is disconnected (critical dependency)
 pub async fn health(State(state): State<OrchestratorState>) -> (StatusCode, Json<HealthResponse>) {
     let neo4j_ok = state
@@ -218,6 +240,10 @@ pub async fn health(State(state): State<OrchestratorState>) -> (StatusCode, Json
         .health_check()
         .await
         .unwrap_or(false);
+    let search_circuit = state.orchestrator.meili().circuit_status();
+    let search_degraded = search_circuit
+        .as_ref()
+        .is_some_and(|circuit| circuit.is_degraded());
 
     // NATS: check connection state if configured, otherwise "disabled"
     let nats_status = match &state.nats_emitter {
@@ -232,7 +258,7 @@ pub async fn health(State(state): State<OrchestratorState>) -> (StatusCode, Json
         None => "disabled",
     };
 
-    let status = if neo4j_ok && meili_ok {
+    let status = if neo4j_ok && meili_ok && !search_degraded {
         "ok"
     } else if neo4j_ok {
         "degraded"
@@ -249,6 +275,7 @@ pub async fn health(State(state): State<OrchestratorState>) -> (StatusCode, Json
     (
         http_status,
         Json(HealthResponse {
+            service: SERVICE_NAME.to_string(),
             status: status.to_string(),
             version: env!("CARGO_PKG_VERSION").to_string(),
             services: Some(ServiceHealthStatus {
@@ -264,6 +291,80 @@ pub async fn health(State(state): State<OrchestratorState>) -> (StatusCode, Json
                 },
                 nats: nats_status.to_string(),
             }),
+            search_circuit,
+        }),
+    )
+}
+
+/// Liveness probe (`/health/live`) — the process is up and serving HTTP.
+///
+/// Deliberately checks no dependency: a Neo4j outage must not make
+/// Kubernetes restart the pod, only take it out of rotation (see [`readiness`]).
+pub async fn liveness() -> Json<HealthResponse> {
+    Json(HealthResponse {
+        service: SERVICE_NAME.to_string(),
+        status: "alive".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        services: None,
+        search_circuit: None,
+    })
+}
+
+/// Per-dependency timeout for the readiness probe, kept under the default
+/// Kubernetes `timeoutSeconds` so a hung backend reports "not ready" instead
+/// of timing the probe out.
+const READINESS_CHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(800);
+
+/// Readiness probe (`/health/ready`) — the instance can serve traffic.
+///
+/// Returns 200 + `"ready"` when Neo4j is reachable, 503 + `"not_ready"`
+/// otherwise. Meilisearch is reported but, as in [`health`], not required.
+pub async fn readiness(
+    State(state): State<OrchestratorState>,
+) -> (StatusCode, Json<HealthResponse>) {
+    let neo4j_ok = tokio::time::timeout(
+        READINESS_CHECK_TIMEOUT,
+        state.orchestrator.neo4j().health_check(),
+    )
+    .await
+    .ok()
+    .and_then(|r| r.ok())
+    .unwrap_or(false);
+    let meili_ok = tokio::time::timeout(
+        READINESS_CHECK_TIMEOUT,
+        state.orchestrator.meili().health_check(),
+    )
+    .await
+    .ok()
+    .and_then(|r| r.ok())
+    .unwrap_or(false);
+    let nats_status = match &state.nats_emitter {
+        Some(emitter)
+            if emitter.client().connection_state() == async_nats::connection::State::Connected =>
+        {
+            "connected"
+        }
+        Some(_) => "disconnected",
+        None => "disabled",
+    };
+    let connected = |ok: bool| if ok { "connected" } else { "disconnected" }.to_string();
+
+    (
+        if neo4j_ok {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        },
+        Json(HealthResponse {
+            service: SERVICE_NAME.to_string(),
+            status: if neo4j_ok { "ready" } else { "not_ready" }.to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            services: Some(ServiceHealthStatus {
+                neo4j: connected(neo4j_ok),
+                meilisearch: connected(meili_ok),
+                nats: nats_status.to_string(),
+            }),
+            search_circuit: None,
         }),
     )
 }
@@ -277,6 +378,8 @@ pub async fn health(State(state): State<OrchestratorState>) -> (StatusCode, Json
 pub struct VersionFeatures {
     pub embedded_frontend: bool,
     pub serve_frontend: bool,
+    /// Mutating routes are rejected (server.read_only)
+    pub read_only: bool,
 }
 
 /// Build metadata exposed in the version endpoint
@@ -301,6 +404,7 @@ pub async fn get_version(State(state): State<OrchestratorState>) -> Json<Version
         features: VersionFeatures {
             embedded_frontend: cfg!(feature = "embedded-frontend"),
             serve_frontend: state.serve_frontend,
+            read_only: state.read_only,
         },
         build: VersionBuild {
             target: format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS),
@@ -397,13 +501,24 @@ pub async fn list_plans(
 /// Create a new plan
 pub async fn create_plan(
     State(state): State<OrchestratorState>,
-    Json(req): Json<CreatePlanRequest>,
+    headers: axum::http::HeaderMap,
+    ValidJson(req): ValidJson<CreatePlanRequest>,
 ) -> Result<Json<PlanNode>, AppError> {
     let plan = state
         .orchestrator
         .plan_manager()
         .create_plan(req, "orchestrator")
         .await?;
+    if let Some(session_id) = undo::session_from_headers(&headers) {
+        undo::record(
+            state.orchestrator.neo4j(),
+            session_id,
+            "plan.create",
+            format!("Created plan '{}'", plan.title),
+            UndoOperation::DeletePlan { plan_id: plan.id },
+        )
+        .await;
+    }
     state.event_bus.emit_created(
         crate::events::EntityType::Plan,
         &plan.id.to_string(),
@@ -416,15 +531,15 @@ pub async fn create_plan(
 /// Get plan details
 pub async fn get_plan(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
-) -> Result<Json<PlanDetails>, AppError> {
+    ValidPath(plan_id): ValidPath<Uuid>,
+) -> Result<(axum::http::HeaderMap, Json<PlanDetails>), AppError> {
     let details = state
         .orchestrator
         .plan_manager()
         .get_plan_details(plan_id)
         .await?
         .ok_or(AppError::NotFound("Plan not found".into()))?;
-    Ok(Json(details))
+    Ok((etag(Some(details.plan.version)), Json(details)))
 }
 
 /// Update plan status (kept for backwards compatibility with status-only requests)
@@ -446,6 +561,17 @@ pub struct UpdatePlanFullRequest {
     pub status: Option<PlanStatus>,
 }
 
+impl Validate for UpdatePlanFullRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(ref title) = self.title {
+            v.text("title", title, MAX_TITLE_LEN);
+        }
+        if let Some(ref description) = self.description {
+            v.max_len("description", description, MAX_TEXT_LEN);
+        }
+    }
+}
+
 /// Request to link a plan to a project
 #[derive(Deserialize)]
 pub struct LinkPlanToProjectRequest {
@@ -455,7 +581,7 @@ pub struct LinkPlanToProjectRequest {
 /// Link a plan to a project
 pub async fn link_plan_to_project(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
+    ValidPath(plan_id): ValidPath<Uuid>,
     Json(req): Json<LinkPlanToProjectRequest>,
 ) -> Result<StatusCode, AppError> {
     state
@@ -468,7 +594,7 @@ pub async fn link_plan_to_project(
 /// Unlink a plan from its project
 pub async fn unlink_plan_from_project(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
+    ValidPath(plan_id): ValidPath<Uuid>,
 ) -> Result<StatusCode, AppError> {
     state.orchestrator.unlink_plan_from_project(plan_id).await?;
     Ok(StatusCode::NO_CONTENT)
@@ -476,65 +602,98 @@ pub async fn unlink_plan_from_project(
 
 pub async fn update_plan_status(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
-    Json(req): Json<UpdatePlanFullRequest>,
-) -> Result<StatusCode, AppError> {
-    // Handle status change if provided
-    if let Some(status) = req.status {
-        // Get old status before mutation for StatusChanged event
-        let old_status = state
-            .orchestrator
-            .neo4j()
-            .get_plan(plan_id)
-            .await
-            .ok()
-            .flatten()
-            .map(|p| format!("{:?}", p.status))
-            .unwrap_or_default();
-        state
-            .orchestrator
-            .plan_manager()
-            .update_plan_status(plan_id, status.clone())
-            .await?;
-        state.event_bus.emit_status_changed(
-            crate::events::EntityType::Plan,
-            &plan_id.to_string(),
-            &old_status,
-            &format!("{:?}", status),
-            None,
-        );
-    }
+    headers: axum::http::HeaderMap,
+    ValidPath(plan_id): ValidPath<Uuid>,
+    ValidJson(req): ValidJson<UpdatePlanFullRequest>,
+) -> Result<(StatusCode, axum::http::HeaderMap), AppError> {
+    let claim = claim_version(
+        state.orchestrator.neo4j(),
+        &headers,
+        VersionedNode::Plan,
+        plan_id,
+    )
+    .await?;
+    let result: Result<_, AppError> = async {
+        let undo_session = undo::session_from_headers(&headers);
+        let snapshot = match undo_session {
+            Some(_) => state.orchestrator.neo4j().get_plan(plan_id).await?,
+            None => None,
+        };
 
-    // Handle field updates (title, description, priority)
-    let plan_update = UpdatePlanRequest {
-        title: req.title,
-        description: req.description,
-        priority: req.priority,
-    };
-    if plan_update.title.is_some()
-        || plan_update.description.is_some()
-        || plan_update.priority.is_some()
-    {
-        state
-            .orchestrator
-            .plan_manager()
-            .update_plan(plan_id, plan_update)
-            .await?;
-        state.event_bus.emit_updated(
-            crate::events::EntityType::Plan,
-            &plan_id.to_string(),
-            serde_json::json!({}),
-            None,
-        );
-    }
+        // Handle status change if provided
+        if let Some(status) = req.status {
+            // Get old status before mutation for StatusChanged event
+            let old_status = state
+                .orchestrator
+                .neo4j()
+                .get_plan(plan_id)
+                .await
+                .ok()
+                .flatten()
+                .map(|p| format!("{:?}", p.status))
+                .unwrap_or_default();
+            state
+                .orchestrator
+                .plan_manager()
+                .update_plan_status(plan_id, status.clone())
+                .await?;
+            state.event_bus.emit_status_changed(
+                crate::events::EntityType::Plan,
+                &plan_id.to_string(),
+                &old_status,
+                &format!("{:?}", status),
+                None,
+            );
+        }
 
-    Ok(StatusCode::NO_CONTENT)
+        // Handle field updates (title, description, priority)
+        let plan_update = UpdatePlanRequest {
+            title: req.title,
+            description: req.description,
+            priority: req.priority,
+        };
+        if plan_update.title.is_some()
+            || plan_update.description.is_some()
+            || plan_update.priority.is_some()
+        {
+            state
+                .orchestrator
+                .plan_manager()
+                .update_plan(plan_id, plan_update)
+                .await?;
+            state.event_bus.emit_updated(
+                crate::events::EntityType::Plan,
+                &plan_id.to_string(),
+                serde_json::json!({}),
+                None,
+            );
+        }
+
+        if let (Some(session_id), Some(snapshot)) = (undo_session, snapshot) {
+            undo::record(
+                state.orchestrator.neo4j(),
+                session_id,
+                "plan.update",
+                format!("Updated plan '{}'", snapshot.title),
+                UndoOperation::RestorePlan {
+                    plan: Box::new(snapshot),
+                },
+            )
+            .await;
+        }
+
+        Ok(())
+    }
+    .await;
+    claim.settle(state.orchestrator.neo4j(), result).await?;
+    let version = current_version(state.orchestrator.neo4j(), VersionedNode::Plan, plan_id).await?;
+    Ok((StatusCode::NO_CONTENT, etag(version)))
 }
 
 /// Delete a plan and all its related data
 pub async fn delete_plan(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
+    ValidPath(plan_id): ValidPath<Uuid>,
 ) -> Result<StatusCode, AppError> {
     state
         .orchestrator
@@ -554,14 +713,25 @@ pub async fn delete_plan(
 /// Add a task to a plan
 pub async fn add_task(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
-    Json(req): Json<CreateTaskRequest>,
+    headers: axum::http::HeaderMap,
+    ValidPath(plan_id): ValidPath<Uuid>,
+    ValidJson(req): ValidJson<CreateTaskRequest>,
 ) -> Result<Json<TaskNode>, AppError> {
     let task = state
         .orchestrator
         .plan_manager()
         .add_task(plan_id, req)
         .await?;
+    if let Some(session_id) = undo::session_from_headers(&headers) {
+        undo::record(
+            state.orchestrator.neo4j(),
+            session_id,
+            "task.create",
+            format!("Created task {}", task.id),
+            UndoOperation::DeleteTask { task_id: task.id },
+        )
+        .await;
+    }
     state.event_bus.emit_created(
         crate::events::EntityType::Task,
         &task.id.to_string(),
@@ -574,21 +744,21 @@ pub async fn add_task(
 /// Get task details
 pub async fn get_task(
     State(state): State<OrchestratorState>,
-    Path(task_id): Path<Uuid>,
-) -> Result<Json<TaskDetails>, AppError> {
+    ValidPath(task_id): ValidPath<Uuid>,
+) -> Result<(axum::http::HeaderMap, Json<TaskDetails>), AppError> {
     let details = state
         .orchestrator
         .plan_manager()
         .get_task_details(task_id)
         .await?
         .ok_or(AppError::NotFound("Task not found".into()))?;
-    Ok(Json(details))
+    Ok((etag(Some(details.task.version)), Json(details)))
 }
 
 /// Delete a task and all its related data
 pub async fn delete_task(
     State(state): State<OrchestratorState>,
-    Path(task_id): Path<Uuid>,
+    ValidPath(task_id): ValidPath<Uuid>,
 ) -> Result<StatusCode, AppError> {
     state
         .orchestrator
@@ -605,9 +775,9 @@ pub async fn delete_task(
 pub async fn update_task(
     State(state): State<OrchestratorState>,
     headers: axum::http::HeaderMap,
-    Path(task_id): Path<Uuid>,
-    Json(req): Json<UpdateTaskRequest>,
-) -> Result<StatusCode, AppError> {
+    ValidPath(task_id): ValidPath<Uuid>,
+    ValidJson(req): ValidJson<UpdateTaskRequest>,
+) -> Result<(StatusCode, axum::http::HeaderMap), AppError> {
     // Extract auto-linking fields before moving req.
     // Priority: explicit session_id in body > X-Session-Id header (injected by MCP proxy)
     let session_id_for_linking = req.session_id.clone().or_else(|| {
@@ -616,89 +786,232 @@ pub async fn update_task(
             .and_then(|v| v.to_str().ok())
             .map(|s| s.to_string())
     });
-    let new_status_str = req.status.as_ref().map(|s| format!("{:?}", s));
-    let is_transition_to_in_progress = new_status_str.as_deref() == Some("InProgress");
+    let claim = claim_version(
+        state.orchestrator.neo4j(),
+        &headers,
+        VersionedNode::Task,
+        task_id,
+    )
+    .await?;
+    let result: Result<_, AppError> = async {
+        let undo_session = undo::session_from_headers(&headers);
+        let snapshot = match undo_session {
+            Some(_) => state.orchestrator.neo4j().get_task(task_id).await?,
+            None => None,
+        };
 
-    let status_change = if req.status.is_some() {
-        let old_status = state
+        let new_status_str = req.status.as_ref().map(|s| format!("{:?}", s));
+        let is_transition_to_in_progress = new_status_str.as_deref() == Some("InProgress");
+
+        let status_change = if req.status.is_some() {
+            let old_status = state
+                .orchestrator
+                .neo4j()
+                .get_task(task_id)
+                .await
+                .ok()
+                .flatten()
+                .map(|t| format!("{:?}", t.status))
+                .unwrap_or_default();
+            Some((old_status, new_status_str.clone().unwrap_or_default()))
+        } else {
+            None
+        };
+
+        state
             .orchestrator
-            .neo4j()
-            .get_task(task_id)
-            .await
-            .ok()
-            .flatten()
-            .map(|t| format!("{:?}", t.status))
-            .unwrap_or_default();
-        Some((old_status, new_status_str.clone().unwrap_or_default()))
-    } else {
-        None
-    };
+            .plan_manager()
+            .update_task(task_id, req)
+            .await?;
 
-    state
-        .orchestrator
-        .plan_manager()
-        .update_task(task_id, req)
-        .await?;
+        if let (Some(session_id), Some(snapshot)) = (undo_session, snapshot) {
+            undo::record(
+                state.orchestrator.neo4j(),
+                session_id,
+                "task.update",
+                format!("Updated task {}", task_id),
+                UndoOperation::RestoreTask {
+                    task: Box::new(snapshot),
+                },
+            )
+            .await;
+        }
 
-    // Auto-link session to task + plan when transitioning to in_progress
-    if is_transition_to_in_progress {
-        if let Some(sid_str) = session_id_for_linking {
-            if let Ok(session_uuid) = sid_str.parse::<uuid::Uuid>() {
-                let neo4j = state.orchestrator.neo4j_arc();
-                tokio::spawn(async move {
-                    if let Err(e) = neo4j
-                        .create_associated_with(session_uuid, "Task", task_id, "auto")
-                        .await
-                    {
-                        tracing::warn!("Auto-link session→task failed: {}", e);
-                    }
-                    match neo4j.get_task_plan_id(task_id).await {
-                        Ok(Some(plan_id)) => {
-                            if let Err(e) = neo4j
-                                .create_associated_with(session_uuid, "Plan", plan_id, "auto")
-                                .await
-                            {
-                                tracing::warn!("Auto-link session→plan failed: {}", e);
-                            }
-                        }
-                        Ok(None) => {
-                            tracing::debug!(
-                                "Task {} has no parent plan, skipping plan link",
-                                task_id
-                            );
+        // Auto-link session to task + plan when transitioning to in_progress
+        if is_transition_to_in_progress {
+            if let Some(sid_str) = session_id_for_linking {
+                if let Ok(session_uuid) = sid_str.parse::<uuid::Uuid>() {
+                    let neo4j = state.orchestrator.neo4j_arc();
+                    tokio::spawn(async move {
+                        if let Err(e) = neo4j
+                            .create_associated_with(session_uuid, "Task", task_id, "auto")
+                            .await
+                        {
+                            tracing::warn!("Auto-link session→task failed: {}", e);
                         }
-                        Err(e) => {
-                            tracing::warn!("Failed to resolve plan_id for task {}: {}", task_id, e);
+                        match neo4j.get_task_plan_id(task_id).await {
+                            Ok(Some(plan_id)) => {
+                                if let Err(e) = neo4j
+                                    .create_associated_with(session_uuid, "Plan", plan_id, "auto")
+                                    .await
+                                {
+                                    tracing::warn!("Auto-link session→plan failed: {}", e);
+                                }
+                            }
+                            Ok(None) => {
+                                tracing::debug!(
+                                    "Task {} has no parent plan, skipping plan link",
+                                    task_id
+                                );
+                            }
+                            Err(e) => {
+                                tracing::warn!(
+                                    "Failed to resolve plan_id for task {}: {}",
+                                    task_id,
+                                    e
+                                );
+                            }
                         }
-                    }
-                });
+                    });
+                }
             }
         }
+
+        if let Some((old_status, new_status)) = status_change {
+            state.event_bus.emit_status_changed(
+                crate::events::EntityType::Task,
+                &task_id.to_string(),
+                &old_status,
+                &new_status,
+                None,
+            );
+        } else {
+            state.event_bus.emit_updated(
+                crate::events::EntityType::Task,
+                &task_id.to_string(),
+                serde_json::json!({}),
+                None,
+            );
+        }
+        Ok(())
     }
+    .await;
+    claim.settle(state.orchestrator.neo4j(), result).await?;
+    let version = current_version(state.orchestrator.neo4j(), VersionedNode::Task, task_id).await?;
+    Ok((StatusCode::NO_CONTENT, etag(version)))
+}
 
-    if let Some((old_status, new_status)) = status_change {
-        state.event_bus.emit_status_changed(
-            crate::events::EntityType::Task,
-            &task_id.to_string(),
-            &old_status,
-            &new_status,
-            None,
-        );
-    } else {
-        state.event_bus.emit_updated(
-            crate::events::EntityType::Task,
-            &task_id.to_string(),
-            serde_json::json!({}),
-            None,
-        );
+/// Request to move a task within its status column of the board
+#[derive(Deserialize)]
+pub struct TaskPositionRequest {
+    /// Task the moved card lands right after (none: top of the column)
+    pub after_task_id: Option<Uuid>,
+    /// Task the moved card lands right before (none: bottom of the column)
+    pub before_task_id: Option<Uuid>,
+}
+
+/// Response for a board move
+#[derive(Serialize)]
+pub struct TaskPositionResponse {
+    pub task_id: Uuid,
+    pub board_position: String,
+}
+
+/// A neighbour of a card being moved: an existing task of the same column
+async fn board_neighbor(
+    neo4j: &dyn crate::neo4j::GraphStore,
+    task: &TaskNode,
+    neighbor_id: Option<Uuid>,
+) -> Result<Option<TaskNode>, AppError> {
+    let Some(neighbor_id) = neighbor_id else {
+        return Ok(None);
+    };
+    if neighbor_id == task.id {
+        return Err(AppError::BadRequest(
+            "A task cannot be positioned relative to itself".into(),
+        ));
     }
-    Ok(StatusCode::NO_CONTENT)
+    let neighbor = neo4j
+        .get_task(neighbor_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Task {} not found", neighbor_id)))?;
+    if neighbor.status != task.status {
+        return Err(AppError::BadRequest(format!(
+            "Task {} is not in the same status column",
+            neighbor_id
+        )));
+    }
+    Ok(Some(neighbor))
+}
+
+/// Move a task between two cards of its status column.
+///
+/// Only the moved card gets a new rank (see [`crate::plan::board`]), so the
+/// order set by others is kept. Unranked neighbours are ranked first. Returns
+/// 409 when the neighbours are no longer in that order (stale board).
+pub async fn update_task_position(
+    State(state): State<OrchestratorState>,
+    ValidPath(task_id): ValidPath<Uuid>,
+    Json(req): Json<TaskPositionRequest>,
+) -> Result<Json<TaskPositionResponse>, AppError> {
+    use crate::plan::board::rank_between;
+
+    let neo4j = state.orchestrator.neo4j();
+    let task = neo4j
+        .get_task(task_id)
+        .await?
+        .ok_or(AppError::NotFound("Task not found".into()))?;
+    let after = board_neighbor(neo4j, &task, req.after_task_id).await?;
+    let before = board_neighbor(neo4j, &task, req.before_task_id).await?;
+    if let (Some(a), Some(b)) = (&after, &before) {
+        if a.id == b.id {
+            return Err(AppError::BadRequest(
+                "after_task_id and before_task_id must differ".into(),
+            ));
+        }
+    }
+
+    let mut lower = after.as_ref().and_then(|t| t.board_position.clone());
+    let mut upper = before.as_ref().and_then(|t| t.board_position.clone());
+    if let (Some(l), Some(u)) = (&lower, &upper) {
+        if l >= u {
+            return Err(AppError::Conflict(
+                "after_task_id is not ranked above before_task_id; reload the board".into(),
+            ));
+        }
+    }
+    if let Some(a) = after.as_ref().filter(|t| t.board_position.is_none()) {
+        let rank = rank_between(None, upper.as_deref());
+        neo4j.set_task_board_position(a.id, &rank).await?;
+        lower = Some(rank);
+    }
+    if let Some(b) = before.as_ref().filter(|t| t.board_position.is_none()) {
+        let rank = rank_between(lower.as_deref(), None);
+        neo4j.set_task_board_position(b.id, &rank).await?;
+        upper = Some(rank);
+    }
+
+    let board_position = rank_between(lower.as_deref(), upper.as_deref());
+    neo4j
+        .set_task_board_position(task_id, &board_position)
+        .await?;
+
+    state.event_bus.emit_updated(
+        crate::events::EntityType::Task,
+        &task_id.to_string(),
+        serde_json::json!({"board_position": &board_position}),
+        None,
+    );
+    Ok(Json(TaskPositionResponse {
+        task_id,
+        board_position,
+    }))
 }
 
 /// Get next available task
 pub async fn get_next_task(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
+    ValidPath(plan_id): ValidPath<Uuid>,
 ) -> Result<Json<Option<TaskNode>>, AppError> {
     let task = state
         .orchestrator
@@ -770,7 +1083,7 @@ pub async fn list_all_tasks(
 /// Get context for a task
 pub async fn get_task_context(
     State(state): State<OrchestratorState>,
-    Path((plan_id, task_id)): Path<(Uuid, Uuid)>,
+    ValidPath((plan_id, task_id)): ValidPath<(Uuid, Uuid)>,
 ) -> Result<Json<AgentContext>, AppError> {
     let context = state
         .orchestrator
@@ -788,7 +1101,7 @@ pub struct PromptResponse {
 
 pub async fn get_task_prompt(
     State(state): State<OrchestratorState>,
-    Path((plan_id, task_id)): Path<(Uuid, Uuid)>,
+    ValidPath((plan_id, task_id)): ValidPath<(Uuid, Uuid)>,
 ) -> Result<Json<PromptResponse>, AppError> {
     let context = state
         .orchestrator
@@ -816,7 +1129,7 @@ pub struct BuildPromptRequest {
 /// the fully rendered prompt string.
 pub async fn build_task_prompt(
     State(state): State<OrchestratorState>,
-    Path((plan_id, task_id)): Path<(Uuid, Uuid)>,
+    ValidPath((plan_id, task_id)): ValidPath<(Uuid, Uuid)>,
     Json(req): Json<BuildPromptRequest>,
 ) -> Result<Json<crate::runner::prompt::StructuredPrompt>, AppError> {
     let structured = state
@@ -893,7 +1206,7 @@ pub struct DelegateTaskResponse {
 /// Retrieve results from the AgentExecution node after completion.
 pub async fn delegate_task(
     State(state): State<OrchestratorState>,
-    Path((plan_id, task_id)): Path<(Uuid, Uuid)>,
+    ValidPath((plan_id, task_id)): ValidPath<(Uuid, Uuid)>,
     Json(req): Json<DelegateTaskRequest>,
 ) -> Result<(StatusCode, Json<DelegateTaskResponse>), AppError> {
     let chat_manager = state
@@ -977,6 +1290,9 @@ pub async fn delegate_task(
         permission_mode: Some("bypassPermissions".to_string()),
         add_dirs: None,
         workspace_slug: None,
+        sandbox: None,
+        limits: None,
+        template: None,
         user_claims: Some(crate::auth::jwt::Claims::service_account(&format!(
             "delegate-agent:{}",
             task_id
@@ -1184,7 +1500,7 @@ async fn listen_delegation_result(
 /// Enrich all tasks in a plan (pre-build context, profile persona, cache prompts).
 pub async fn enrich_plan(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
+    ValidPath(plan_id): ValidPath<Uuid>,
 ) -> Result<Json<crate::orchestrator::context::PlanEnrichmentResult>, AppError> {
     let result = state
         .orchestrator
@@ -1197,7 +1513,7 @@ pub async fn enrich_plan(
 /// Enrich a single task (pre-build context, profile persona, cache prompt).
 pub async fn enrich_task(
     State(state): State<OrchestratorState>,
-    Path((plan_id, task_id)): Path<(Uuid, Uuid)>,
+    ValidPath((plan_id, task_id)): ValidPath<(Uuid, Uuid)>,
 ) -> Result<Json<crate::orchestrator::context::EnrichmentResult>, AppError> {
     let result = state
         .orchestrator
@@ -1214,8 +1530,8 @@ pub async fn enrich_task(
 /// Add a decision to a task
 pub async fn add_decision(
     State(state): State<OrchestratorState>,
-    Path(task_id): Path<Uuid>,
-    Json(req): Json<CreateDecisionRequest>,
+    ValidPath(task_id): ValidPath<Uuid>,
+    ValidJson(req): ValidJson<CreateDecisionRequest>,
 ) -> Result<Json<DecisionNode>, AppError> {
     let explicit_run_id = req.run_id;
     let decision = state
@@ -1318,6 +1634,30 @@ pub async fn add_decision(
         });
     }
 
+    // ADR notebook: projects keeping a docs/adr directory get the new record
+    if let Ok(Some(project)) = state
+        .orchestrator
+        .neo4j()
+        .get_project_for_task(task_id)
+        .await
+    {
+        if crate::orchestrator::adr::has_adr_dir(&project) {
+            if let Err(e) = crate::orchestrator::adr::export_adrs(
+                state.orchestrator.neo4j(),
+                &project,
+                Some(decision.id),
+            )
+            .await
+            {
+                tracing::warn!(
+                    decision_id = %decision.id,
+                    error = %e,
+                    "Failed to export ADR for decision (non-fatal)"
+                );
+            }
+        }
+    }
+
     state.event_bus.emit_created(
         crate::events::EntityType::Decision,
         &decision.id.to_string(),
@@ -1331,7 +1671,7 @@ pub async fn add_decision(
 /// Get a single decision by ID
 pub async fn get_decision(
     State(state): State<OrchestratorState>,
-    Path(decision_id): Path<Uuid>,
+    ValidPath(decision_id): ValidPath<Uuid>,
 ) -> Result<Json<DecisionNode>, AppError> {
     let decision = state
         .orchestrator
@@ -1354,7 +1694,7 @@ pub struct UpdateDecisionRequest {
 /// Update a decision
 pub async fn update_decision(
     State(state): State<OrchestratorState>,
-    Path(decision_id): Path<Uuid>,
+    ValidPath(decision_id): ValidPath<Uuid>,
     Json(req): Json<UpdateDecisionRequest>,
 ) -> Result<StatusCode, AppError> {
     state
@@ -1373,7 +1713,7 @@ pub async fn update_decision(
 /// Delete a decision
 pub async fn delete_decision(
     State(state): State<OrchestratorState>,
-    Path(decision_id): Path<Uuid>,
+    ValidPath(decision_id): ValidPath<Uuid>,
 ) -> Result<StatusCode, AppError> {
     state.orchestrator.delete_decision(decision_id).await?;
     Ok(StatusCode::NO_CONTENT)
@@ -1391,22 +1731,20 @@ pub struct SearchQuery {
 
 pub async fn search_decisions(
     State(state): State<OrchestratorState>,
+    headers: axum::http::HeaderMap,
     axum::extract::Query(query): axum::extract::Query<SearchQuery>,
 ) -> Result<Json<Vec<DecisionNode>>, AppError> {
     let limit = query.limit.unwrap_or(10);
 
-    // If project_slug is given, use it directly (takes precedence)
-    if query.project_slug.is_some() {
-        let decisions = state
+    let decisions = if query.project_slug.is_some() {
+        // If project_slug is given, use it directly (takes precedence)
+        state
             .orchestrator
             .plan_manager()
             .search_decisions(&query.q, limit, query.project_slug.as_deref())
-            .await?;
-        return Ok(Json(decisions));
-    }
-
-    // If workspace_slug is given, resolve to project slugs and filter
-    if let Some(ref ws_slug) = query.workspace_slug {
+            .await?
+    } else if let Some(ref ws_slug) = query.workspace_slug {
+        // If workspace_slug is given, resolve to project slugs and filter
         let workspace = state
             .orchestrator
             .neo4j()
@@ -1422,20 +1760,28 @@ pub async fn search_decisions(
 
         let project_slugs: Vec<String> = projects.iter().map(|p| p.slug.clone()).collect();
 
-        let decisions = state
+        state
             .orchestrator
             .plan_manager()
             .search_decisions_in_workspace(&query.q, limit, &project_slugs)
-            .await?;
-        return Ok(Json(decisions));
-    }
+            .await?
+    } else {
+        // No filter — global search
+        state
+            .orchestrator
+            .plan_manager()
+            .search_decisions(&query.q, limit, None)
+            .await?
+    };
 
-    // No filter — global search
-    let decisions = state
-        .orchestrator
-        .plan_manager()
-        .search_decisions(&query.q, limit, None)
-        .await?;
+    crate::api::search_analytics_handlers::record_search(
+        &state,
+        &headers,
+        "decisions",
+        &query.q,
+        query.project_slug.as_deref(),
+        decisions.len(),
+    );
     Ok(Json(decisions))
 }
 
@@ -1455,6 +1801,7 @@ pub struct SearchDecisionsSemanticQuery {
 
 pub async fn search_decisions_semantic(
     State(state): State<OrchestratorState>,
+    headers: axum::http::HeaderMap,
     axum::extract::Query(params): axum::extract::Query<SearchDecisionsSemanticQuery>,
 ) -> Result<Json<Vec<DecisionSearchHit>>, AppError> {
     let mut results = state
@@ -1488,6 +1835,14 @@ pub async fn search_decisions_semantic(
         }
     }
 
+    crate::api::search_analytics_handlers::record_search(
+        &state,
+        &headers,
+        "decisions_semantic",
+        &params.query,
+        None,
+        results.len(),
+    );
     Ok(Json(results))
 }
 
@@ -1531,7 +1886,7 @@ pub struct AddAffectsRequest {
 /// Add an AFFECTS relation from a decision to an entity
 pub async fn add_decision_affects(
     State(state): State<OrchestratorState>,
-    Path(decision_id): Path<Uuid>,
+    ValidPath(decision_id): ValidPath<Uuid>,
     Json(req): Json<AddAffectsRequest>,
 ) -> Result<StatusCode, AppError> {
     let neo4j = state.orchestrator.neo4j();
@@ -1566,7 +1921,7 @@ pub async fn add_decision_affects(
 /// - `DELETE /api/decisions/{id}/affects?entity_type=File&entity_id=/path/to/file.rs` (paths with slashes)
 pub async fn remove_decision_affects(
     State(state): State<OrchestratorState>,
-    Path((decision_id, entity_type, entity_id)): Path<(Uuid, String, String)>,
+    ValidPath((decision_id, entity_type, entity_id)): ValidPath<(Uuid, String, String)>,
 ) -> Result<StatusCode, AppError> {
     let neo4j = state.orchestrator.neo4j();
     neo4j
@@ -1600,7 +1955,7 @@ pub struct RemoveAffectsQuery {
 /// Alternative to the path-based route, for entity_ids that contain slashes (e.g. file paths).
 pub async fn remove_decision_affects_query(
     State(state): State<OrchestratorState>,
-    Path(decision_id): Path<Uuid>,
+    ValidPath(decision_id): ValidPath<Uuid>,
     Query(query): Query<RemoveAffectsQuery>,
 ) -> Result<StatusCode, AppError> {
     let neo4j = state.orchestrator.neo4j();
@@ -1626,7 +1981,7 @@ pub async fn remove_decision_affects_query(
 /// List all entities affected by a decision
 pub async fn list_decision_affects(
     State(state): State<OrchestratorState>,
-    Path(decision_id): Path<Uuid>,
+    ValidPath(decision_id): ValidPath<Uuid>,
 ) -> Result<Json<Vec<AffectsRelation>>, AppError> {
     let affects = state
         .orchestrator
@@ -1639,7 +1994,7 @@ pub async fn list_decision_affects(
 /// Mark a decision as superseded by a newer decision
 pub async fn supersede_decision(
     State(state): State<OrchestratorState>,
-    Path((new_id, old_id)): Path<(Uuid, Uuid)>,
+    ValidPath((new_id, old_id)): ValidPath<(Uuid, Uuid)>,
 ) -> Result<StatusCode, AppError> {
     state
         .orchestrator
@@ -1693,6 +2048,12 @@ pub struct SyncResponse {
     pub files_deleted: usize,
     pub symbols_deleted: usize,
     pub errors: usize,
+    pub documents_synced: usize,
+    pub documents_deleted: usize,
+    /// Files left unparsed by the project's sync limits
+    pub skipped_files: Vec<crate::orchestrator::sync_limits::SkippedFile>,
+    /// Unchanged files whose stale search document was re-indexed
+    pub files_reindexed: usize,
 }
 
 /// Sync a directory to the knowledge base
@@ -1786,6 +2147,24 @@ pub async fn sync_directory(
             }
         });
 
+        // Link imports to workspace resources owned by other projects
+        state.orchestrator.spawn_link_resource_symbols(pid);
+
+        // Record locked third-party packages for the vulnerability audit
+        state.orchestrator.spawn_ingest_packages(pid);
+
+        // Link the files to their CODEOWNERS owners
+        state.orchestrator.spawn_ingest_code_owners(pid);
+
+        // Summarize the directories whose files changed
+        state.orchestrator.spawn_refresh_module_summaries(pid);
+
+        // Check the complexity and size budgets
+        state.orchestrator.spawn_check_budgets(pid);
+
+        // Snapshot the public API surface when it changed
+        state.orchestrator.spawn_record_api_surface(pid);
+
         // Spawn event-triggered protocol runs (post_sync)
         crate::protocol::hooks::spawn_event_triggered_protocols(
             state.orchestrator.neo4j_arc(),
@@ -1801,6 +2180,10 @@ pub async fn sync_directory(
         files_deleted: result.files_deleted,
         symbols_deleted: result.symbols_deleted,
         errors: result.errors,
+        documents_synced: result.documents_synced,
+        documents_deleted: result.documents_deleted,
+        skipped_files: result.skipped_files,
+        files_reindexed: result.files_reindexed,
     }))
 }
 
@@ -1894,6 +2277,12 @@ pub async fn start_watch(
     if let Some(ref pid_str) = req.project_id {
         if let Ok(pid) = uuid::Uuid::parse_str(pid_str) {
             if let Some(project) = state.orchestrator.neo4j().get_project(pid).await? {
+                if project.archived {
+                    return Err(AppError::Conflict(format!(
+                        "Project '{}' is archived",
+                        project.slug
+                    )));
+                }
                 watcher
                     .register_project(path, project.id, project.slug)
                     .await
@@ -2029,8 +2418,8 @@ pub async fn reactor_status(State(state): State<OrchestratorState>) -> Json<serd
 /// Add a step to a task
 pub async fn add_step(
     State(state): State<OrchestratorState>,
-    Path(task_id): Path<Uuid>,
-    Json(req): Json<AddStepRequest>,
+    ValidPath(task_id): ValidPath<Uuid>,
+    ValidJson(req): ValidJson<AddStepRequest>,
 ) -> Result<Json<StepNode>, AppError> {
     // Get current step count to determine order
     let steps = state.orchestrator.neo4j().get_task_steps(task_id).await?;
@@ -2049,7 +2438,7 @@ pub async fn add_step(
 /// Get steps for a task
 pub async fn get_task_steps(
     State(state): State<OrchestratorState>,
-    Path(task_id): Path<Uuid>,
+    ValidPath(task_id): ValidPath<Uuid>,
 ) -> Result<Json<Vec<StepNode>>, AppError> {
     let steps = state.orchestrator.neo4j().get_task_steps(task_id).await?;
     Ok(Json(steps))
@@ -2058,7 +2447,7 @@ pub async fn get_task_steps(
 /// Get a single step by ID
 pub async fn get_step(
     State(state): State<OrchestratorState>,
-    Path(step_id): Path<Uuid>,
+    ValidPath(step_id): ValidPath<Uuid>,
 ) -> Result<Json<StepNode>, AppError> {
     let step = state
         .orchestrator
@@ -2072,7 +2461,7 @@ pub async fn get_step(
 /// Delete a step
 pub async fn delete_step(
     State(state): State<OrchestratorState>,
-    Path(step_id): Path<Uuid>,
+    ValidPath(step_id): ValidPath<Uuid>,
 ) -> Result<StatusCode, AppError> {
     state.orchestrator.delete_step(step_id).await?;
     state
@@ -2084,9 +2473,15 @@ pub async fn delete_step(
 /// Update a step
 pub async fn update_step(
     State(state): State<OrchestratorState>,
-    Path(step_id): Path<Uuid>,
-    Json(req): Json<UpdateStepRequest>,
+    headers: axum::http::HeaderMap,
+    ValidPath(step_id): ValidPath<Uuid>,
+    ValidJson(req): ValidJson<UpdateStepRequest>,
 ) -> Result<StatusCode, AppError> {
+    let undo_session = undo::session_from_headers(&headers);
+    let snapshot = match undo_session {
+        Some(_) => state.orchestrator.neo4j().get_step(step_id).await?,
+        None => None,
+    };
     if let Some(status) = req.status.clone() {
         state
             .orchestrator
@@ -2118,6 +2513,18 @@ pub async fn update_step(
             .update_step(step_id, &req)
             .await?;
     }
+    if let (Some(session_id), Some(snapshot)) = (undo_session, snapshot) {
+        undo::record(
+            state.orchestrator.neo4j(),
+            session_id,
+            "step.update",
+            format!("Updated step {}", step_id),
+            UndoOperation::RestoreStep {
+                step: Box::new(snapshot),
+            },
+        )
+        .await;
+    }
     state.event_bus.emit_updated(
         crate::events::EntityType::Step,
         &step_id.to_string(),
@@ -2138,7 +2545,7 @@ pub struct StepProgressResponse {
 /// Get step progress for a task
 pub async fn get_step_progress(
     State(state): State<OrchestratorState>,
-    Path(task_id): Path<Uuid>,
+    ValidPath(task_id): ValidPath<Uuid>,
 ) -> Result<Json<StepProgressResponse>, AppError> {
     let (completed, total) = state
         .orchestrator
@@ -2166,7 +2573,7 @@ pub async fn get_step_progress(
 /// Add a constraint to a plan
 pub async fn add_constraint(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
+    ValidPath(plan_id): ValidPath<Uuid>,
     Json(req): Json<AddConstraintRequest>,
 ) -> Result<Json<ConstraintNode>, AppError> {
     let constraint = ConstraintNode::new(req.constraint_type, req.description, req.enforced_by);
@@ -2189,7 +2596,7 @@ pub async fn add_constraint(
 /// Get constraints for a plan
 pub async fn get_plan_constraints(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
+    ValidPath(plan_id): ValidPath<Uuid>,
 ) -> Result<Json<Vec<ConstraintNode>>, AppError> {
     let constraints = state
         .orchestrator
@@ -2202,7 +2609,7 @@ pub async fn get_plan_constraints(
 /// Get a single constraint by ID
 pub async fn get_constraint(
     State(state): State<OrchestratorState>,
-    Path(constraint_id): Path<Uuid>,
+    ValidPath(constraint_id): ValidPath<Uuid>,
 ) -> Result<Json<ConstraintNode>, AppError> {
     let constraint = state
         .orchestrator
@@ -2224,7 +2631,7 @@ pub struct UpdateConstraintRequest {
 /// Update a constraint
 pub async fn update_constraint(
     State(state): State<OrchestratorState>,
-    Path(constraint_id): Path<Uuid>,
+    ValidPath(constraint_id): ValidPath<Uuid>,
     Json(req): Json<UpdateConstraintRequest>,
 ) -> Result<StatusCode, AppError> {
     state
@@ -2250,7 +2657,7 @@ pub async fn update_constraint(
 /// Delete a constraint
 pub async fn delete_constraint(
     State(state): State<OrchestratorState>,
-    Path(constraint_id): Path<Uuid>,
+    ValidPath(constraint_id): ValidPath<Uuid>,
 ) -> Result<StatusCode, AppError> {
     state.orchestrator.delete_constraint(constraint_id).await?;
 
@@ -2324,7 +2731,7 @@ pub async fn create_lifecycle_hook(
 /// GET /api/lifecycle-hooks/:id — Get a lifecycle hook by ID
 pub async fn get_lifecycle_hook(
     State(state): State<OrchestratorState>,
-    Path(hook_id): Path<Uuid>,
+    ValidPath(hook_id): ValidPath<Uuid>,
 ) -> Result<Json<crate::lifecycle::LifecycleHook>, AppError> {
     let hook = state
         .orchestrator
@@ -2339,7 +2746,7 @@ pub async fn get_lifecycle_hook(
 /// PATCH /api/lifecycle-hooks/:id — Update a lifecycle hook
 pub async fn update_lifecycle_hook(
     State(state): State<OrchestratorState>,
-    Path(hook_id): Path<Uuid>,
+    ValidPath(hook_id): ValidPath<Uuid>,
     Json(req): Json<crate::lifecycle::UpdateLifecycleHookRequest>,
 ) -> Result<StatusCode, AppError> {
     state
@@ -2362,7 +2769,7 @@ pub async fn update_lifecycle_hook(
 /// DELETE /api/lifecycle-hooks/:id — Delete a lifecycle hook (builtin hooks cannot be deleted)
 pub async fn delete_lifecycle_hook(
     State(state): State<OrchestratorState>,
-    Path(hook_id): Path<Uuid>,
+    ValidPath(hook_id): ValidPath<Uuid>,
 ) -> Result<StatusCode, AppError> {
     state
         .orchestrator
@@ -2452,6 +2859,68 @@ pub async fn cleanup_builtin_calls(
     Ok(Json(serde_json::json!({ "deleted_count": deleted })))
 }
 
+/// Body of `PUT /api/admin/log-level` — exactly one form is used, checked in
+/// this order: `reset`, `filter`, `level` (with optional `target`).
+#[derive(Debug, Deserialize)]
+pub struct LogLevelRequest {
+    /// Restore the filter the server started with.
+    #[serde(default)]
+    pub reset: bool,
+    /// Replace the whole filter (`RUST_LOG` syntax).
+    pub filter: Option<String>,
+    /// Module path to adjust (e.g. `project_orchestrator::chat`); omitted
+    /// means the default level.
+    pub target: Option<String>,
+    /// New level: trace, debug, info, warn, error or off.
+    pub level: Option<String>,
+}
+
+fn log_level_error(e: crate::logging::LogLevelError) -> AppError {
+    match e {
+        crate::logging::LogLevelError::Unavailable => AppError::Conflict(e.to_string()),
+        crate::logging::LogLevelError::Invalid(_) => AppError::BadRequest(e.to_string()),
+    }
+}
+
+/// GET /api/admin/log-level — Current log filter directives
+pub async fn get_log_level() -> Result<Json<serde_json::Value>, AppError> {
+    let filter = crate::logging::current_filter()
+        .ok_or_else(|| log_level_error(crate::logging::LogLevelError::Unavailable))?;
+    Ok(Json(serde_json::json!({ "filter": filter })))
+}
+
+/// PUT /api/admin/log-level — Adjust the log filter at runtime (no restart)
+pub async fn set_log_level(
+    Json(body): Json<LogLevelRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let filter = if body.reset {
+        crate::logging::reset_filter()
+    } else if let Some(ref filter) = body.filter {
+        crate::logging::set_filter(filter)
+    } else if let Some(ref level) = body.level {
+        crate::logging::set_level(body.target.as_deref(), level)
+    } else {
+        return Err(AppError::BadRequest(
+            "expected one of `reset`, `filter` or `level`".to_string(),
+        ));
+    }
+    .map_err(log_level_error)?;
+    Ok(Json(serde_json::json!({ "filter": filter })))
+}
+
+/// GET /api/admin/metrics — Query counters of the graph store with its
+/// latest slow queries (`graph` is null for a store that does not measure
+/// them), and the write-behind indexing queue of the search store
+/// (`search.index_queue` is null when code is indexed synchronously)
+pub async fn get_metrics(State(state): State<OrchestratorState>) -> Json<serde_json::Value> {
+    Json(serde_json::json!({
+        "graph": state.orchestrator.neo4j().query_metrics(),
+        "search": {
+            "index_queue": state.orchestrator.meili().index_queue_status(),
+        },
+    }))
+}
+
 /// POST /api/admin/migrate-calls-confidence — Add confidence/reason to existing CALLS relationships
 pub async fn migrate_calls_confidence(
     State(state): State<OrchestratorState>,
@@ -2713,7 +3182,7 @@ pub struct LinkCommitRequest {
 /// Link a commit to a task
 pub async fn link_commit_to_task(
     State(state): State<OrchestratorState>,
-    Path(task_id): Path<Uuid>,
+    ValidPath(task_id): ValidPath<Uuid>,
     Json(req): Json<LinkCommitRequest>,
 ) -> Result<StatusCode, AppError> {
     state
@@ -2733,7 +3202,7 @@ pub async fn link_commit_to_task(
 /// Get commits for a task
 pub async fn get_task_commits(
     State(state): State<OrchestratorState>,
-    Path(task_id): Path<Uuid>,
+    ValidPath(task_id): ValidPath<Uuid>,
 ) -> Result<Json<Vec<CommitNode>>, AppError> {
     let commits = state.orchestrator.neo4j().get_task_commits(task_id).await?;
     Ok(Json(commits))
@@ -2742,7 +3211,7 @@ pub async fn get_task_commits(
 /// Link a commit to a plan
 pub async fn link_commit_to_plan(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
+    ValidPath(plan_id): ValidPath<Uuid>,
     Json(req): Json<LinkCommitRequest>,
 ) -> Result<StatusCode, AppError> {
     state
@@ -2762,7 +3231,7 @@ pub async fn link_commit_to_plan(
 /// Get commits for a plan
 pub async fn get_plan_commits(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
+    ValidPath(plan_id): ValidPath<Uuid>,
 ) -> Result<Json<Vec<CommitNode>>, AppError> {
     let commits = state.orchestrator.neo4j().get_plan_commits(plan_id).await?;
     Ok(Json(commits))
@@ -2792,10 +3261,16 @@ pub struct FileHistoryQuery {
     pub limit: Option<i64>,
 }
 
+impl Validate for FileHistoryQuery {
+    fn validate(&self, v: &mut Validator) {
+        v.file_path("path", &self.path);
+    }
+}
+
 /// Get commit history for a file (via TOUCHES relations)
 pub async fn get_file_history(
     State(state): State<OrchestratorState>,
-    Query(query): Query<FileHistoryQuery>,
+    ValidQuery(query): ValidQuery<FileHistoryQuery>,
 ) -> Result<Json<Vec<crate::neo4j::models::FileHistoryEntry>>, AppError> {
     let history = state
         .orchestrator
@@ -2815,7 +3290,7 @@ pub struct CoChangeGraphQuery {
 /// Get the co-change graph for a project
 pub async fn get_co_change_graph(
     State(state): State<OrchestratorState>,
-    Path(project_id): Path<Uuid>,
+    ValidPath(project_id): ValidPath<Uuid>,
     Query(query): Query<CoChangeGraphQuery>,
 ) -> Result<Json<Vec<crate::neo4j::models::CoChangePair>>, AppError> {
     let pairs = state
@@ -2838,10 +3313,16 @@ pub struct FileCoChangersQuery {
     pub limit: Option<i64>,
 }
 
+impl Validate for FileCoChangersQuery {
+    fn validate(&self, v: &mut Validator) {
+        v.file_path("path", &self.path);
+    }
+}
+
 /// Get files that co-change with a given file
 pub async fn get_file_co_changers(
     State(state): State<OrchestratorState>,
-    Query(query): Query<FileCoChangersQuery>,
+    ValidQuery(query): ValidQuery<FileCoChangersQuery>,
 ) -> Result<Json<Vec<crate::neo4j::models::CoChanger>>, AppError> {
     let changers = state
         .orchestrator
@@ -3992,7 +4473,7 @@ pub async fn skill_maintenance(
 /// Detect global stagnation across a project.
 pub async fn detect_stagnation(
     State(state): State<OrchestratorState>,
-    Path(project_id): Path<Uuid>,
+    ValidPath(project_id): ValidPath<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     state
         .orchestrator
@@ -4015,7 +4496,7 @@ pub async fn detect_stagnation(
 /// Run deep maintenance (aggressive cleanup for stagnating projects).
 pub async fn run_deep_maintenance(
     State(state): State<OrchestratorState>,
-    Path(project_id): Path<Uuid>,
+    ValidPath(project_id): ValidPath<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     state
         .orchestrator
@@ -4049,7 +4530,7 @@ pub async fn run_deep_maintenance(
 /// episode_to_skill_ratio, etc.
 pub async fn get_learning_metrics(
     State(state): State<OrchestratorState>,
-    Path(project_id): Path<Uuid>,
+    ValidPath(project_id): ValidPath<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     state
         .orchestrator
@@ -4073,7 +4554,7 @@ pub async fn get_learning_metrics(
 /// on existing protocol states. Idempotent — safe to run multiple times.
 pub async fn seed_prompt_fragments(
     State(state): State<OrchestratorState>,
-    Path(project_id): Path<Uuid>,
+    ValidPath(project_id): ValidPath<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     state
         .orchestrator
@@ -4139,7 +4620,7 @@ pub struct AcknowledgeAlertRequest {
 /// Acknowledge (dismiss) an alert.
 pub async fn acknowledge_alert(
     State(state): State<OrchestratorState>,
-    Path(alert_id): Path<Uuid>,
+    ValidPath(alert_id): ValidPath<Uuid>,
     Json(req): Json<AcknowledgeAlertRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     // Verify the alert exists
@@ -4181,7 +4662,7 @@ pub struct CreateReleaseRequest {
 /// Create a release for a project
 pub async fn create_release(
     State(state): State<OrchestratorState>,
-    Path(project_id): Path<Uuid>,
+    ValidPath(project_id): ValidPath<Uuid>,
     Json(req): Json<CreateReleaseRequest>,
 ) -> Result<Json<ReleaseNode>, AppError> {
     let release = ReleaseNode {
@@ -4218,7 +4699,7 @@ pub struct ReleasesListQuery {
 
 pub async fn list_releases(
     State(state): State<OrchestratorState>,
-    Path(project_id): Path<Uuid>,
+    ValidPath(project_id): ValidPath<Uuid>,
     Query(query): Query<ReleasesListQuery>,
 ) -> Result<Json<PaginatedResponse<ReleaseNode>>, AppError> {
     query.pagination.validate().map_err(AppError::BadRequest)?;
@@ -4257,7 +4738,7 @@ pub struct UpdateReleaseRequest {
 /// Update a release
 pub async fn update_release(
     State(state): State<OrchestratorState>,
-    Path(release_id): Path<Uuid>,
+    ValidPath(release_id): ValidPath<Uuid>,
     Json(req): Json<UpdateReleaseRequest>,
 ) -> Result<StatusCode, AppError> {
     let status_change = if let Some(ref new_status_val) = req.status {
@@ -4312,7 +4793,7 @@ pub async fn update_release(
 /// Delete a release
 pub async fn delete_release(
     State(state): State<OrchestratorState>,
-    Path(release_id): Path<Uuid>,
+    ValidPath(release_id): ValidPath<Uuid>,
 ) -> Result<StatusCode, AppError> {
     state.orchestrator.delete_release(release_id).await?;
     state.event_bus.emit_deleted(
@@ -4332,7 +4813,7 @@ pub struct AddTaskToReleaseRequest {
 /// Add a task to a release
 pub async fn add_task_to_release(
     State(state): State<OrchestratorState>,
-    Path(release_id): Path<Uuid>,
+    ValidPath(release_id): ValidPath<Uuid>,
     Json(req): Json<AddTaskToReleaseRequest>,
 ) -> Result<StatusCode, AppError> {
     state
@@ -4358,7 +4839,7 @@ pub struct AddCommitToReleaseRequest {
 /// Add a commit to a release
 pub async fn add_commit_to_release(
     State(state): State<OrchestratorState>,
-    Path(release_id): Path<Uuid>,
+    ValidPath(release_id): ValidPath<Uuid>,
     Json(req): Json<AddCommitToReleaseRequest>,
 ) -> Result<StatusCode, AppError> {
     state
@@ -4371,7 +4852,7 @@ pub async fn add_commit_to_release(
 /// Remove a commit from a release
 pub async fn remove_commit_from_release(
     State(state): State<OrchestratorState>,
-    Path((release_id, commit_sha)): Path<(Uuid, String)>,
+    ValidPath((release_id, commit_sha)): ValidPath<(Uuid, String)>,
 ) -> Result<StatusCode, AppError> {
     state
         .orchestrator
@@ -4391,7 +4872,7 @@ pub struct ReleaseDetailsResponse {
 /// Get release details
 pub async fn get_release(
     State(state): State<OrchestratorState>,
-    Path(release_id): Path<Uuid>,
+    ValidPath(release_id): ValidPath<Uuid>,
 ) -> Result<Json<ReleaseDetailsResponse>, AppError> {
     let details = state
         .orchestrator
@@ -4422,7 +4903,7 @@ pub struct CreateMilestoneRequest {
 /// Create a milestone for a project
 pub async fn create_milestone(
     State(state): State<OrchestratorState>,
-    Path(project_id): Path<Uuid>,
+    ValidPath(project_id): ValidPath<Uuid>,
     Json(req): Json<CreateMilestoneRequest>,
 ) -> Result<Json<MilestoneNode>, AppError> {
     let milestone = MilestoneNode {
@@ -4458,7 +4939,7 @@ pub struct MilestonesListQuery {
 
 pub async fn list_milestones(
     State(state): State<OrchestratorState>,
-    Path(project_id): Path<Uuid>,
+    ValidPath(project_id): ValidPath<Uuid>,
     Query(query): Query<MilestonesListQuery>,
 ) -> Result<Json<PaginatedResponse<MilestoneNode>>, AppError> {
     query.pagination.validate().map_err(AppError::BadRequest)?;
@@ -4497,7 +4978,7 @@ pub struct UpdateMilestoneRequest {
 /// Update a milestone
 pub async fn update_milestone(
     State(state): State<OrchestratorState>,
-    Path(milestone_id): Path<Uuid>,
+    ValidPath(milestone_id): ValidPath<Uuid>,
     Json(req): Json<UpdateMilestoneRequest>,
 ) -> Result<StatusCode, AppError> {
     let status_change = if let Some(ref new_status_val) = req.status {
@@ -4552,7 +5033,7 @@ pub async fn update_milestone(
 /// Delete a milestone
 pub async fn delete_milestone(
     State(state): State<OrchestratorState>,
-    Path(milestone_id): Path<Uuid>,
+    ValidPath(milestone_id): ValidPath<Uuid>,
 ) -> Result<StatusCode, AppError> {
     state.orchestrator.delete_milestone(milestone_id).await?;
     state.event_bus.emit_deleted(
@@ -4572,7 +5053,7 @@ pub struct AddTaskToMilestoneRequest {
 /// Add a task to a milestone
 pub async fn add_task_to_milestone(
     State(state): State<OrchestratorState>,
-    Path(milestone_id): Path<Uuid>,
+    ValidPath(milestone_id): ValidPath<Uuid>,
     Json(req): Json<AddTaskToMilestoneRequest>,
 ) -> Result<StatusCode, AppError> {
     state
@@ -4598,7 +5079,7 @@ pub struct LinkPlanToMilestoneRequest {
 /// Link a plan to a project milestone
 pub async fn link_plan_to_milestone(
     State(state): State<OrchestratorState>,
-    Path(milestone_id): Path<Uuid>,
+    ValidPath(milestone_id): ValidPath<Uuid>,
     Json(req): Json<LinkPlanToMilestoneRequest>,
 ) -> Result<StatusCode, AppError> {
     state
@@ -4618,7 +5099,7 @@ pub async fn link_plan_to_milestone(
 /// Unlink a plan from a project milestone
 pub async fn unlink_plan_from_milestone(
     State(state): State<OrchestratorState>,
-    Path((milestone_id, plan_id)): Path<(Uuid, Uuid)>,
+    ValidPath((milestone_id, plan_id)): ValidPath<(Uuid, Uuid)>,
 ) -> Result<StatusCode, AppError> {
     state
         .orchestrator
@@ -4647,7 +5128,7 @@ pub struct MilestoneGetQuery {
 /// Get milestone details
 pub async fn get_milestone(
     State(state): State<OrchestratorState>,
-    Path(milestone_id): Path<Uuid>,
+    ValidPath(milestone_id): ValidPath<Uuid>,
     Query(query): Query<MilestoneGetQuery>,
 ) -> Result<Json<MilestoneDetailsResponse>, AppError> {
     let neo4j = state.orchestrator.neo4j();
@@ -4769,7 +5250,7 @@ pub struct MilestoneProgressResponse {
 /// Get milestone progress
 pub async fn get_milestone_progress(
     State(state): State<OrchestratorState>,
-    Path(milestone_id): Path<Uuid>,
+    ValidPath(milestone_id): ValidPath<Uuid>,
 ) -> Result<Json<MilestoneProgressResponse>, AppError> {
     let (total, completed, in_progress, pending) = state
         .orchestrator
@@ -4805,7 +5286,7 @@ pub struct AddDependenciesRequest {
 /// Add dependencies to a task
 pub async fn add_task_dependencies(
     State(state): State<OrchestratorState>,
-    Path(task_id): Path<Uuid>,
+    ValidPath(task_id): ValidPath<Uuid>,
     Json(req): Json<AddDependenciesRequest>,
 ) -> Result<StatusCode, AppError> {
     for dep_id in req.depends_on {
@@ -4820,7 +5301,7 @@ pub async fn add_task_dependencies(
 /// Remove a dependency from a task
 pub async fn remove_task_dependency(
     State(state): State<OrchestratorState>,
-    Path((task_id, dep_id)): Path<(Uuid, Uuid)>,
+    ValidPath((task_id, dep_id)): ValidPath<(Uuid, Uuid)>,
 ) -> Result<StatusCode, AppError> {
     state
         .orchestrator
@@ -4832,7 +5313,7 @@ pub async fn remove_task_dependency(
 /// Get tasks that block this task (uncompleted dependencies)
 pub async fn get_task_blockers(
     State(state): State<OrchestratorState>,
-    Path(task_id): Path<Uuid>,
+    ValidPath(task_id): ValidPath<Uuid>,
 ) -> Result<Json<Vec<TaskNode>>, AppError> {
     let blockers = state
         .orchestrator
@@ -4845,7 +5326,7 @@ pub async fn get_task_blockers(
 /// Get tasks blocked by this task
 pub async fn get_tasks_blocked_by(
     State(state): State<OrchestratorState>,
-    Path(task_id): Path<Uuid>,
+    ValidPath(task_id): ValidPath<Uuid>,
 ) -> Result<Json<Vec<TaskNode>>, AppError> {
     let blocked = state
         .orchestrator
@@ -4954,7 +5435,7 @@ pub struct DependencyGraphResponse {
 /// Get dependency graph for a plan (enriched with steps, notes, decisions counts + conflicts)
 pub async fn get_plan_dependency_graph(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
+    ValidPath(plan_id): ValidPath<Uuid>,
 ) -> Result<Json<DependencyGraphResponse>, AppError> {
     let neo4j = state.orchestrator.neo4j();
     let (tasks, edges) = neo4j.get_plan_dependency_graph(plan_id).await?;
@@ -5064,7 +5545,7 @@ pub struct CriticalPathResponse {
 /// Get critical path for a plan
 pub async fn get_plan_critical_path(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
+    ValidPath(plan_id): ValidPath<Uuid>,
 ) -> Result<Json<CriticalPathResponse>, AppError> {
     let tasks = state
         .orchestrator
@@ -5079,7 +5560,7 @@ pub async fn get_plan_critical_path(
 /// Get execution waves for a plan (topological sort + level grouping)
 pub async fn get_plan_waves(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
+    ValidPath(plan_id): ValidPath<Uuid>,
 ) -> Result<Json<WaveComputationResult>, AppError> {
     let result = state.orchestrator.neo4j().compute_waves(plan_id).await?;
 
@@ -5121,7 +5602,7 @@ pub struct RunPlanResponse {
 /// Returns 409 Conflict if the plan already has an active run.
 pub async fn run_plan(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
+    ValidPath(plan_id): ValidPath<Uuid>,
     axum::Extension(caller_claims): axum::Extension<crate::auth::jwt::Claims>,
     Json(req): Json<RunPlanRequest>,
 ) -> Result<(StatusCode, Json<RunPlanResponse>), AppError> {
@@ -5192,7 +5673,7 @@ pub async fn run_plan(
 /// GET /api/plans/:id/run/status — Get current runner status.
 pub async fn get_run_status(
     State(_state): State<OrchestratorState>,
-    Path(_plan_id): Path<Uuid>,
+    ValidPath(_plan_id): ValidPath<Uuid>,
 ) -> Result<Json<crate::runner::RunStatus>, AppError> {
     let status = crate::runner::PlanRunner::status().await;
     Ok(Json(status))
@@ -5201,7 +5682,7 @@ pub async fn get_run_status(
 /// POST /api/plans/:id/run/cancel — Cancel an active plan run.
 pub async fn cancel_run(
     State(state): State<OrchestratorState>,
-    Path(_plan_id): Path<Uuid>,
+    ValidPath(_plan_id): ValidPath<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     // Get the current run_id to cancel
     let status = crate::runner::PlanRunner::status().await;
@@ -5226,7 +5707,7 @@ pub async fn cancel_run(
 /// to the graceful cancel flag.
 pub async fn force_cancel_run(
     State(state): State<OrchestratorState>,
-    Path(_plan_id): Path<Uuid>,
+    ValidPath(_plan_id): ValidPath<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let status = crate::runner::PlanRunner::status().await;
     let run_id = status
@@ -5250,7 +5731,7 @@ pub async fn force_cancel_run(
 /// PATCH /api/plans/{plan_id}/run/budget — Update the budget of a running execution.
 pub async fn update_run_budget(
     State(_state): State<OrchestratorState>,
-    Path(_plan_id): Path<Uuid>,
+    ValidPath(_plan_id): ValidPath<Uuid>,
     Json(body): Json<serde_json::Value>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let max_cost_usd = body
@@ -5291,7 +5772,7 @@ pub async fn update_run_budget(
 /// and creates the PR via `gh pr create`.
 pub async fn create_auto_pr(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
+    ValidPath(plan_id): ValidPath<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let graph = state.orchestrator.neo4j_arc();
 
@@ -5393,7 +5874,7 @@ pub async fn create_auto_pr(
 /// POST /api/plans/:id/triggers — Create a trigger for a plan.
 pub async fn create_trigger(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
+    ValidPath(plan_id): ValidPath<Uuid>,
     Json(body): Json<serde_json::Value>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     use crate::runner::{Trigger, TriggerType};
@@ -5451,7 +5932,7 @@ pub async fn create_trigger(
 /// GET /api/plans/:id/triggers — List triggers for a plan.
 pub async fn list_triggers(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
+    ValidPath(plan_id): ValidPath<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let graph = state.orchestrator.neo4j_arc();
     let triggers = graph
@@ -5465,7 +5946,7 @@ pub async fn list_triggers(
 /// DELETE /api/triggers/:id — Delete a trigger.
 pub async fn delete_trigger(
     State(state): State<OrchestratorState>,
-    Path(trigger_id): Path<Uuid>,
+    ValidPath(trigger_id): ValidPath<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let graph = state.orchestrator.neo4j_arc();
     graph
@@ -5487,7 +5968,7 @@ pub async fn delete_trigger(
 /// PATCH /api/triggers/:id/enable — Enable a trigger.
 pub async fn enable_trigger(
     State(state): State<OrchestratorState>,
-    Path(trigger_id): Path<Uuid>,
+    ValidPath(trigger_id): ValidPath<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let graph = state.orchestrator.neo4j_arc();
     let trigger = graph
@@ -5509,7 +5990,7 @@ pub async fn enable_trigger(
 /// PATCH /api/triggers/:id/disable — Disable a trigger.
 pub async fn disable_trigger(
     State(state): State<OrchestratorState>,
-    Path(trigger_id): Path<Uuid>,
+    ValidPath(trigger_id): ValidPath<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let graph = state.orchestrator.neo4j_arc();
     let trigger = graph
@@ -5531,7 +6012,7 @@ pub async fn disable_trigger(
 /// GET /api/triggers/:id/firings — Get trigger firing history.
 pub async fn list_trigger_firings(
     State(state): State<OrchestratorState>,
-    Path(trigger_id): Path<Uuid>,
+    ValidPath(trigger_id): ValidPath<Uuid>,
     Query(params): Query<std::collections::HashMap<String, String>>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let limit = params
@@ -5557,7 +6038,7 @@ pub async fn list_trigger_firings(
 /// and branch pattern, then evaluates the trigger.
 pub async fn receive_webhook(
     State(state): State<OrchestratorState>,
-    Path(trigger_id): Path<Uuid>,
+    ValidPath(trigger_id): ValidPath<Uuid>,
     headers: axum::http::HeaderMap,
     body: axum::body::Bytes,
 ) -> Result<Json<serde_json::Value>, AppError> {
@@ -5729,7 +6210,7 @@ pub async fn list_all_plan_runs(
 /// GET /api/plans/:plan_id/runs — List historical plan runs.
 pub async fn list_plan_runs(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
+    ValidPath(plan_id): ValidPath<Uuid>,
     Query(params): Query<std::collections::HashMap<String, String>>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let limit = params
@@ -5747,7 +6228,7 @@ pub async fn list_plan_runs(
 /// GET /api/runs/:run_id/agent-executions — Get agent execution records for a plan run.
 pub async fn get_run_agent_executions(
     State(state): State<OrchestratorState>,
-    Path(run_id): Path<Uuid>,
+    ValidPath(run_id): ValidPath<Uuid>,
 ) -> Result<Json<Vec<crate::neo4j::agent_execution::AgentExecutionNode>>, AppError> {
     let graph = state.orchestrator.neo4j_arc();
     let executions = graph
@@ -5760,7 +6241,7 @@ pub async fn get_run_agent_executions(
 /// GET /api/runs/:run_id — Get a specific plan run.
 pub async fn get_plan_run(
     State(state): State<OrchestratorState>,
-    Path(run_id): Path<Uuid>,
+    ValidPath(run_id): ValidPath<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let graph = state.orchestrator.neo4j_arc();
     let run = graph
@@ -5801,6 +6282,18 @@ pub async fn get_plan_run(
         }
     }
 
+    // Finished runs carry their report (tasks, files, notes, transcripts)
+    let report = graph
+        .get_run_report(run_id)
+        .await
+        .map_err(AppError::Internal)?;
+    if let Some(obj) = result.as_object_mut() {
+        obj.insert(
+            "report".to_string(),
+            serde_json::to_value(report).unwrap_or_default(),
+        );
+    }
+
     Ok(Json(result))
 }
 
@@ -5810,7 +6303,7 @@ pub async fn get_plan_run(
 /// Returns dimension-level comparison with trend analysis.
 pub async fn compare_plan_runs(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
+    ValidPath(plan_id): ValidPath<Uuid>,
     Json(body): Json<serde_json::Value>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let run_ids: Vec<Uuid> = body
@@ -5860,7 +6353,7 @@ pub async fn compare_plan_runs(
 /// Uses exponential weighted average on historical runs.
 pub async fn predict_plan_run(
     State(state): State<OrchestratorState>,
-    Path(plan_id): Path<Uuid>,
+    ValidPath(plan_id): ValidPath<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let graph = state.orchestrator.neo4j_arc();
 
@@ -5923,7 +6416,7 @@ pub struct RoadmapResponse {
 /// Get project roadmap
 pub async fn get_project_roadmap(
     State(state): State<OrchestratorState>,
-    Path(project_id): Path<Uuid>,
+    ValidPath(project_id): ValidPath<Uuid>,
 ) -> Result<Json<RoadmapResponse>, AppError> {
     let neo4j = state.orchestrator.neo4j();
 
@@ -6038,7 +6531,7 @@ pub async fn get_project_roadmap(
 /// when no gates have been persisted yet (pipeline V1 placeholder).
 pub async fn get_run_gates(
     State(_state): State<OrchestratorState>,
-    Path(run_id): Path<Uuid>,
+    ValidPath(run_id): ValidPath<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     // V1: return empty gate results. The pipeline runner will persist these
     // in a future iteration and we'll query them from Neo4j here.
@@ -6055,7 +6548,7 @@ pub async fn get_run_gates(
 /// recorded any checkpoints yet (V1 placeholder).
 pub async fn get_run_progress(
     State(_state): State<OrchestratorState>,
-    Path(run_id): Path<Uuid>,
+    ValidPath(run_id): ValidPath<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     // V1: return a default progress summary. Real data will come from
     // ProgressOracle checkpoints once the pipeline runner persists them.
@@ -6077,50 +6570,22 @@ pub async fn get_run_progress(
     Ok(Json(response))
 }
 
-// ============================================================================
-// Error handling
-// ============================================================================
-
-/// Application error type
-#[derive(Debug)]
-pub enum AppError {
-    Internal(anyhow::Error),
-    NotFound(String),
-    BadRequest(String),
-    Unauthorized(String),
-    Forbidden(String),
-    Conflict(String),
-}
-
-impl IntoResponse for AppError {
-    fn into_response(self) -> axum::response::Response {
-        let (status, message) = match self {
-            AppError::Internal(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
-            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
-            AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
-            AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg),
-            AppError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg),
-            AppError::Conflict(msg) => (StatusCode::CONFLICT, msg),
-        };
-
-        let body = Json(serde_json::json!({
-            "error": message
-        }));
-
-        (status, body).into_response()
-    }
-}
-
-impl From<anyhow::Error> for AppError {
-    fn from(err: anyhow::Error) -> Self {
-        AppError::Internal(err)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_set_log_level_requires_an_action() {
+        let body = LogLevelRequest {
+            reset: false,
+            filter: None,
+            target: Some("project_orchestrator".to_string()),
+            level: None,
+        };
+        let err = set_log_level(Json(body)).await.unwrap_err();
+        assert!(matches!(err, AppError::BadRequest(_)));
+    }
+
     #[test]
     fn test_update_decision_request_all_fields() {
         let json = r#"{"description":"new desc","rationale":"new reason","chosen_option":"B"}"#;
@@ -6339,7 +6804,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -6425,7 +6893,12 @@ mod tests {
             .await
             .unwrap();
 
-        assert_eq!(resp.status(), HttpStatus::BAD_REQUEST);
+        assert_eq!(resp.status(), HttpStatus::UNPROCESSABLE_ENTITY);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["errors"][0]["field"], "milestone_id");
     }
 
     #[tokio::test]
@@ -6553,7 +7026,10 @@ mod tests {
             setup_completed: true,
             server_port,
             public_url: public_url.map(|s| s.to_string()),
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -6714,7 +7190,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -6751,6 +7230,99 @@ mod tests {
             .unwrap()
     }
 
+    #[tokio::test]
+    async fn test_create_plan_rejects_invalid_fields() {
+        let app = test_app().await;
+        let resp = app
+            .clone()
+            .oneshot(auth_post_json(
+                "/api/plans",
+                serde_json::json!({"title": "  ", "description": "x".repeat(100_001)}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::UNPROCESSABLE_ENTITY);
+        let json = resp_json(resp).await;
+        assert_eq!(json["code"], "validation_failed");
+        assert_eq!(json["errors"][0]["field"], "title");
+        assert_eq!(json["errors"][1]["field"], "description");
+
+        // Type errors name the field too
+        let resp = app
+            .oneshot(auth_post_json(
+                "/api/plans",
+                serde_json::json!({"title": "Plan", "description": "", "priority": "high"}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::UNPROCESSABLE_ENTITY);
+        assert_eq!(resp_json(resp).await["errors"][0]["field"], "priority");
+    }
+
+    #[tokio::test]
+    async fn test_update_task_with_stale_version_conflicts() {
+        let app = test_app().await;
+        let resp = app
+            .clone()
+            .oneshot(auth_post_json(
+                "/api/plans",
+                serde_json::json!({"title": "Plan", "description": "Versioned"}),
+            ))
+            .await
+            .unwrap();
+        let plan_id = resp_json(resp).await["id"].as_str().unwrap().to_string();
+        let resp = app
+            .clone()
+            .oneshot(auth_post_json(
+                &format!("/api/plans/{}/tasks", plan_id),
+                serde_json::json!({"description": "Shared task"}),
+            ))
+            .await
+            .unwrap();
+        let task = resp_json(resp).await;
+        assert_eq!(task["version"], 0);
+        let uri = format!("/api/tasks/{}", task["id"].as_str().unwrap());
+        let patch = |version: &str, title: &str| {
+            Request::builder()
+                .method("PATCH")
+                .uri(&uri)
+                .header("authorization", test_bearer_token())
+                .header("content-type", "application/json")
+                .header("if-match", version)
+                .body(Body::from(serde_json::json!({"title": title}).to_string()))
+                .unwrap()
+        };
+
+        // Both agents read version 0: the first update wins
+        let resp = app.clone().oneshot(patch("\"0\"", "Mine")).await.unwrap();
+        assert_eq!(resp.status(), HttpStatus::NO_CONTENT);
+        let etag = resp.headers()["etag"].to_str().unwrap().to_string();
+        let resp = app.clone().oneshot(patch("\"0\"", "Theirs")).await.unwrap();
+        assert_eq!(resp.status(), HttpStatus::CONFLICT);
+        let json = resp_json(resp).await;
+        assert_eq!(json["code"], "version_conflict");
+        assert_eq!(
+            json["current_version"],
+            etag.trim_matches('"').parse::<i64>().unwrap()
+        );
+        assert_eq!(json["current"]["title"], "Mine");
+
+        let resp = app.clone().oneshot(auth_get(&uri)).await.unwrap();
+        assert_eq!(resp.headers()["etag"], etag.as_str());
+        let json = resp_json(resp).await;
+        assert_eq!(json["task"]["title"], "Mine");
+
+        // The ETag of the update is the version to send next
+        let resp = app
+            .clone()
+            .oneshot(patch(&etag, "Mine again"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::NO_CONTENT);
+        let resp = app.oneshot(patch(&etag, "Stale")).await.unwrap();
+        assert_eq!(resp.status(), HttpStatus::CONFLICT);
+    }
+
     // ----------------------------------------------------------------
     // Decision semantic search
     // ----------------------------------------------------------------
@@ -7509,7 +8081,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -7619,7 +8194,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -7796,7 +8374,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -7847,7 +8428,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -7937,7 +8521,10 @@ mod tests {
             setup_completed: true,
             server_port: 0,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -8113,7 +8700,10 @@ mod tests {
             setup_completed: true,
             server_port: 0,
             public_url: None,
+            read_only: false,
             ws_ticket_store: std::sync::Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: std::sync::Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: std::sync::Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -8388,4 +8978,76 @@ mod tests {
         let json = resp_json(resp).await;
         assert_eq!(json["running"], false);
     }
+
+    #[tokio::test]
+    async fn test_update_task_position() {
+        let state = mock_server_state().await;
+        let neo4j = state.orchestrator.neo4j_arc();
+        let plan = crate::test_helpers::test_plan();
+        neo4j.create_plan(&plan).await.unwrap();
+        let (a, b, c) = (
+            crate::test_helpers::test_task_titled("A"),
+            crate::test_helpers::test_task_titled("B"),
+            crate::test_helpers::test_task_titled("C"),
+        );
+        for task in [&a, &b, &c] {
+            neo4j.create_task(plan.id, task).await.unwrap();
+        }
+        let position = |task: Uuid, after: Option<Uuid>, before: Option<Uuid>| {
+            update_task_position(
+                State(state.clone()),
+                ValidPath(task),
+                Json(TaskPositionRequest {
+                    after_task_id: after,
+                    before_task_id: before,
+                }),
+            )
+        };
+        let rank = |id: Uuid| {
+            let neo4j = neo4j.clone();
+            async move {
+                neo4j
+                    .get_task(id)
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .board_position
+                    .unwrap()
+            }
+        };
+
+        // C between unranked A and B: A and B get ranked around it
+        let moved = position(c.id, Some(a.id), Some(b.id)).await.unwrap();
+        let (ra, rb, rc) = (rank(a.id).await, rank(b.id).await, rank(c.id).await);
+        assert_eq!(moved.0.board_position, rc);
+        assert!(ra < rc && rc < rb);
+
+        // B to the top, only B moves
+        position(b.id, None, Some(a.id)).await.unwrap();
+        assert!(rank(b.id).await < ra);
+        assert_eq!(rank(a.id).await, ra);
+        assert_eq!(rank(c.id).await, rc);
+
+        // Neighbours in the wrong order: stale board
+        let err = position(a.id, Some(c.id), Some(b.id)).await.unwrap_err();
+        assert!(matches!(err, AppError::Conflict(_)));
+        let err = position(a.id, Some(a.id), None).await.unwrap_err();
+        assert!(matches!(err, AppError::BadRequest(_)));
+
+        // Neighbours must share the status column
+        neo4j
+            .update_task(
+                b.id,
+                &UpdateTaskRequest {
+                    status: Some(crate::neo4j::models::TaskStatus::Completed),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let err = position(a.id, Some(b.id), None).await.unwrap_err();
+        assert!(matches!(err, AppError::BadRequest(_)));
+        let err = position(Uuid::new_v4(), None, None).await.unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
 }
@@ -136,11 +136,8 @@ pub async fn activate_hook(
     // --- Rate limiting ---
     let client_ip = extract_client_ip(&headers);
     if !HOOK_RATE_LIMITER.check(client_ip) {
-        return Ok((
-            StatusCode::TOO_MANY_REQUESTS,
-            Json(serde_json::json!({
-                "error": "Rate limit exceeded. Max 500 requests per minute."
-            })),
+        return Err(AppError::TooManyRequests(
+            "Rate limit exceeded. Max 500 requests per minute.".to_string(),
         ));
     }
 
@@ -0,0 +1,477 @@
+//! Idempotency keys (`Idempotency-Key` header).
+//!
+//! Agents retry POSTs on timeouts, which used to create duplicate plans,
+//! tasks and notes. A POST carrying an `Idempotency-Key` header is executed
+//! once per user, path and key: its successful response is kept for
+//! [`IDEMPOTENCY_TTL`] and replayed (with `Idempotent-Replayed: true`) to
+//! retries instead of running the handler again.
+//!
+//! - The handler runs in its own task, so a client that gives up before the
+//!   response arrives does not abort it: its retry gets the result.
+//! - A retry arriving while the first request still runs gets `409`.
+//! - Reusing a key with a different body is rejected with `422`.
+//! - Failed responses are not kept: the request can be retried with the key.
+//! - The results are kept in the [`IdempotencyStore`] of the server state.
+//!   When it is full, the oldest completed keys are dropped; when every kept
+//!   key is still running, new keys get `503`.
+
+use super::error::AppError;
+use super::handlers::OrchestratorState;
+use super::validation::FieldError;
+use crate::auth::jwt::Claims;
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Header carrying the idempotency key of a request
+pub const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// Header set on replayed responses
+pub const IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+/// How long a result is replayed for its key
+pub const IDEMPOTENCY_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Max length of an idempotency key
+const MAX_KEY_LEN: usize = 255;
+
+/// Max number of kept results; the oldest completed ones are dropped first
+const MAX_ENTRIES: usize = 10_000;
+
+/// Max size of a request body buffered to fingerprint it
+const MAX_REQUEST_BODY: usize = 16 * 1024 * 1024;
+
+/// Responses larger than this are returned but not kept
+const MAX_KEPT_BODY: usize = 1024 * 1024;
+
+/// A kept response
+#[derive(Debug, Clone)]
+pub struct KeptResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+impl KeptResponse {
+    fn replay(&self) -> Response {
+        let mut resp = Response::new(Body::from(self.body.clone()));
+        *resp.status_mut() = self.status;
+        *resp.headers_mut() = self.headers.clone();
+        resp.headers_mut()
+            .insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+        resp
+    }
+}
+
+#[derive(Debug)]
+enum EntryState {
+    InFlight,
+    Done(KeptResponse),
+}
+
+#[derive(Debug)]
+struct Entry {
+    /// SHA-256 of the request body
+    fingerprint: String,
+    state: EntryState,
+    created_at: Instant,
+}
+
+/// What to do with a request carrying a key
+#[derive(Debug)]
+pub enum Begin {
+    /// First use of the key: run the handler
+    Run,
+    /// The key already completed: replay its response
+    Replay(KeptResponse),
+    /// The first request with the key is still running
+    InFlight,
+    /// The key was used with another body
+    Mismatch,
+    /// Every kept key is still running: no room for a new one
+    Full,
+}
+
+/// Results of the requests with a key, by user, path and key
+#[derive(Debug)]
+pub struct IdempotencyCache {
+    entries: HashMap<String, Entry>,
+    capacity: usize,
+}
+
+impl Default for IdempotencyCache {
+    fn default() -> Self {
+        Self::with_capacity(MAX_ENTRIES)
+    }
+}
+
+impl IdempotencyCache {
+    /// Create a cache keeping at most `capacity` keys.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            capacity,
+        }
+    }
+
+    /// Register a request, or tell how to answer it when its key is known.
+    pub fn begin(&mut self, key: &str, fingerprint: &str, now: Instant) -> Begin {
+        self.entries
+            .retain(|_, e| now.duration_since(e.created_at) < IDEMPOTENCY_TTL);
+        if let Some(entry) = self.entries.get(key) {
+            if entry.fingerprint != fingerprint {
+                return Begin::Mismatch;
+            }
+            return match entry.state {
+                EntryState::InFlight => Begin::InFlight,
+                EntryState::Done(ref kept) => Begin::Replay(kept.clone()),
+            };
+        }
+        if self.entries.len() >= self.capacity {
+            // Dropping a running key would run its retry a second time
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .filter(|(_, e)| matches!(e.state, EntryState::Done(_)))
+                .min_by_key(|(_, e)| e.created_at)
+                .map(|(k, _)| k.clone())
+            else {
+                return Begin::Full;
+            };
+            self.entries.remove(&oldest);
+        }
+        self.entries.insert(
+            key.to_string(),
+            Entry {
+                fingerprint: fingerprint.to_string(),
+                state: EntryState::InFlight,
+                created_at: now,
+            },
+        );
+        Begin::Run
+    }
+
+    /// Keep the response of a request that ran.
+    pub fn complete(&mut self, key: &str, kept: KeptResponse) {
+        if let Some(entry) = self.entries.get_mut(key) {
+            entry.state = EntryState::Done(kept);
+        }
+    }
+
+    /// Forget a request whose response is not kept, so that it can be retried.
+    pub fn abandon(&mut self, key: &str) {
+        self.entries.remove(key);
+    }
+}
+
+/// The idempotency cache of a server (see [`OrchestratorState`]).
+#[derive(Debug, Default)]
+pub struct IdempotencyStore {
+    cache: Mutex<IdempotencyCache>,
+}
+
+impl IdempotencyStore {
+    /// Create an empty store keeping up to 10,000 keys.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty store keeping at most `capacity` keys.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            cache: Mutex::new(IdempotencyCache::with_capacity(capacity)),
+        }
+    }
+
+    /// Lock the cache.
+    pub fn lock(&self) -> MutexGuard<'_, IdempotencyCache> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn key_error(message: &str) -> AppError {
+    AppError::Validation(vec![FieldError::new("Idempotency-Key", message)])
+}
+
+/// Middleware replaying the response of POSTs retried with the same
+/// `Idempotency-Key`. Runs after `require_auth`, so keys are per user.
+pub async fn replay_idempotent(
+    State(state): State<OrchestratorState>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    if req.method() != Method::POST {
+        return Ok(next.run(req).await);
+    }
+    let Some(key) = req.headers().get(&IDEMPOTENCY_KEY) else {
+        return Ok(next.run(req).await);
+    };
+    let key = key
+        .to_str()
+        .map_err(|_| key_error("must be visible ASCII"))?
+        .trim()
+        .to_string();
+    if key.is_empty() || key.len() > MAX_KEY_LEN {
+        return Err(key_error(&format!(
+            "must be between 1 and {} characters",
+            MAX_KEY_LEN
+        )));
+    }
+    let user = req
+        .extensions()
+        .get::<Claims>()
+        .map(|c| c.sub.clone())
+        .unwrap_or_default();
+    let cache_key = format!("{}\n{}\n{}", user, req.uri().path(), key);
+
+    let (parts, body) = req.into_parts();
+    let body = to_bytes(body, MAX_REQUEST_BODY)
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Failed to read request body: {}", e)))?;
+    let fingerprint = hex::encode(Sha256::digest(&body));
+
+    let store = state.idempotency.clone();
+    let begin = store.lock().begin(&cache_key, &fingerprint, Instant::now());
+    match begin {
+        Begin::Run => {}
+        Begin::Replay(kept) => return Ok(kept.replay()),
+        Begin::InFlight => {
+            return Err(AppError::Conflict(
+                "A request with this Idempotency-Key is still in progress, retry later".to_string(),
+            ))
+        }
+        Begin::Mismatch => {
+            return Err(key_error("was already used with a different request body"));
+        }
+        Begin::Full => {
+            return Err(AppError::ServiceUnavailable(
+                "Too many requests with an Idempotency-Key are in progress, retry later"
+                    .to_string(),
+            ))
+        }
+    }
+
+    let req = Request::from_parts(parts, Body::from(body));
+    let task_key = cache_key.clone();
+    let task_store = store.clone();
+    let task = tokio::spawn(async move {
+        let resp = next.run(req).await;
+        let streaming = resp
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("text/event-stream"));
+        if !resp.status().is_success() || streaming {
+            task_store.lock().abandon(&task_key);
+            return resp;
+        }
+        let (parts, body) = resp.into_parts();
+        match to_bytes(body, usize::MAX).await {
+            Ok(bytes) => {
+                if bytes.len() <= MAX_KEPT_BODY {
+                    task_store.lock().complete(
+                        &task_key,
+                        KeptResponse {
+                            status: parts.status,
+                            headers: parts.headers.clone(),
+                            body: bytes.clone(),
+                        },
+                    );
+                } else {
+                    task_store.lock().abandon(&task_key);
+                }
+                Response::from_parts(parts, Body::from(bytes))
+            }
+            Err(e) => {
+                task_store.lock().abandon(&task_key);
+                AppError::Internal(anyhow::anyhow!("Failed to read response body: {}", e))
+                    .into_response()
+            }
+        }
+    });
+    match task.await {
+        Ok(resp) => Ok(resp),
+        Err(e) => {
+            store.lock().abandon(&cache_key);
+            Err(AppError::Internal(anyhow::anyhow!(
+                "Request handler failed: {}",
+                e
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::handlers::ServerState;
+    use crate::api::routes::create_router;
+    use crate::orchestrator::watcher::FileWatcher;
+    use crate::orchestrator::Orchestrator;
+    use crate::test_helpers::{mock_app_state, test_auth_config, test_bearer_token};
+    use axum::http::Request as HttpRequest;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn kept(body: &'static str) -> KeptResponse {
+        KeptResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: Bytes::from_static(body.as_bytes()),
+        }
+    }
+
+    #[test]
+    fn test_cache_lifecycle() {
+        let mut cache = IdempotencyCache::default();
+        let now = Instant::now();
+        assert!(matches!(cache.begin("k", "a", now), Begin::Run));
+        assert!(matches!(cache.begin("k", "a", now), Begin::InFlight));
+        assert!(matches!(cache.begin("k", "b", now), Begin::Mismatch));
+
+        cache.complete("k", kept("{}"));
+        let Begin::Replay(resp) = cache.begin("k", "a", now) else {
+            panic!("expected a replay");
+        };
+        assert_eq!(resp.body, Bytes::from_static(b"{}"));
+
+        // Expired keys run again
+        let later = now + IDEMPOTENCY_TTL;
+        assert!(matches!(cache.begin("k", "a", later), Begin::Run));
+
+        // Abandoned keys too
+        cache.abandon("k");
+        assert!(matches!(cache.begin("k", "a", later), Begin::Run));
+    }
+
+    #[test]
+    fn test_full_cache_drops_only_completed_keys() {
+        let mut cache = IdempotencyCache::with_capacity(2);
+        let now = Instant::now();
+        assert!(matches!(cache.begin("old", "a", now), Begin::Run));
+        let later = now + Duration::from_secs(1);
+        assert!(matches!(cache.begin("running", "a", later), Begin::Run));
+        cache.complete("old", kept("{}"));
+
+        // The completed key makes room, the running one is kept
+        assert!(matches!(cache.begin("new", "a", later), Begin::Run));
+        assert!(matches!(
+            cache.begin("running", "a", later),
+            Begin::InFlight
+        ));
+        assert!(matches!(cache.begin("old", "a", later), Begin::Full));
+
+        // Once one of them completes, a new key fits again
+        cache.complete("new", kept("{}"));
+        assert!(matches!(cache.begin("old", "a", later), Begin::Run));
+    }
+
+    async fn test_app(idempotency: IdempotencyStore) -> axum::Router {
+        let app_state = mock_app_state();
+        let orchestrator = Arc::new(Orchestrator::new(app_state).await.unwrap());
+        let watcher = Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
+            orchestrator.clone(),
+        )));
+        create_router(Arc::new(ServerState {
+            orchestrator,
+            watcher,
+            chat_manager: None,
+            event_bus: Arc::new(crate::events::HybridEmitter::new(Arc::new(
+                crate::events::EventBus::default(),
+            ))),
+            nats_emitter: None,
+            auth_config: Some(test_auth_config()),
+            serve_frontend: false,
+            frontend_path: "./dist".to_string(),
+            setup_completed: true,
+            server_port: 6600,
+            public_url: None,
+            read_only: false,
+            ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(idempotency),
+            registry_remote_url: None,
+            oidc_client: None,
+            neural_router: crate::test_helpers::mock_neural_router(),
+            trajectory_collector: std::sync::RwLock::new(None),
+            trajectory_store_neo4j: None,
+            trajectory_store: None,
+            identity: None,
+            reactor_counters: std::sync::OnceLock::new(),
+            confidence_tracker: Arc::new(crate::graph::confidence::ConfidenceTracker::default()),
+            mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
+            model_catalog: crate::chat::model_catalog::ModelCatalogCache::new(None),
+        }))
+    }
+
+    fn create_plan(key: Option<&str>, title: &str) -> HttpRequest<Body> {
+        let mut builder = HttpRequest::builder()
+            .method("POST")
+            .uri("/api/plans")
+            .header("authorization", test_bearer_token())
+            .header("content-type", "application/json");
+        if let Some(key) = key {
+            builder = builder.header(IDEMPOTENCY_KEY, key);
+        }
+        builder
+            .body(Body::from(
+                serde_json::json!({"title": title, "description": "Retried"}).to_string(),
+            ))
+            .unwrap()
+    }
+
+    async fn send(app: &axum::Router, req: HttpRequest<Body>) -> (Response, serde_json::Value) {
+        let resp = app.clone().oneshot(req).await.unwrap();
+        let (parts, body) = resp.into_parts();
+        let bytes = to_bytes(body, usize::MAX).await.unwrap();
+        let json = serde_json::from_slice(&bytes).unwrap_or_default();
+        (Response::from_parts(parts, Body::empty()), json)
+    }
+
+    #[tokio::test]
+    async fn test_retried_post_is_replayed() {
+        let app = test_app(IdempotencyStore::new()).await;
+        let key = uuid::Uuid::new_v4().to_string();
+
+        let (first, plan) = send(&app, create_plan(Some(&key), "Plan")).await;
+        assert_eq!(first.status(), StatusCode::OK);
+        assert!(first.headers().get(IDEMPOTENT_REPLAYED).is_none());
+
+        let (retry, replayed) = send(&app, create_plan(Some(&key), "Plan")).await;
+        assert_eq!(retry.status(), StatusCode::OK);
+        assert_eq!(retry.headers()[IDEMPOTENT_REPLAYED], "true");
+        assert_eq!(replayed["id"], plan["id"]);
+
+        let (reused, problem) = send(&app, create_plan(Some(&key), "Other plan")).await;
+        assert_eq!(reused.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(problem["errors"][0]["field"], "Idempotency-Key");
+
+        // Without a key, every request runs
+        let (_, a) = send(&app, create_plan(None, "Plan")).await;
+        let (_, b) = send(&app, create_plan(None, "Plan")).await;
+        assert_ne!(a["id"], b["id"]);
+    }
+
+    #[tokio::test]
+    async fn test_keys_rejected_while_all_kept_keys_run() {
+        let store = IdempotencyStore::with_capacity(1);
+        store.lock().begin("running", "a", Instant::now());
+        let app = test_app(store).await;
+
+        let (resp, problem) = send(&app, create_plan(Some("new"), "Plan")).await;
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(problem["code"], "service_unavailable");
+
+        // Requests without a key don't need room in the cache
+        let (resp, _) = send(&app, create_plan(None, "Plan")).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+}
@@ -3,31 +3,49 @@
 pub mod auth_handlers;
 pub mod chat_handlers;
 pub mod code_handlers;
+pub mod comment_handlers;
 pub mod episode_handlers;
+pub mod error;
 pub mod feedback_handlers;
+pub mod github_handlers;
 pub mod graph_types;
 pub mod handlers;
 pub mod hook_handlers;
+pub mod idempotency;
 pub mod mcp_federation_handlers;
 pub mod neural_routing_handlers;
 pub mod note_handlers;
+pub mod notification_handlers;
+pub mod ownership_handlers;
 pub mod persona_handlers;
+pub mod presence;
 pub mod profile_handlers;
 pub mod project_handlers;
+pub mod prompt_template_handlers;
 pub mod protocol_handlers;
 pub mod query;
+pub mod quota_handlers;
+pub mod read_only;
 pub mod reason_handlers;
 pub mod registry_handlers;
+pub mod retention_handlers;
 pub mod rfc_handlers;
 pub mod routes;
+pub mod search_analytics_handlers;
+pub mod search_settings_handlers;
 pub mod sharing_handlers;
 pub mod skill_handlers;
 pub mod trajectory_handlers;
 pub mod trigger_handlers;
+pub mod usage_handlers;
+pub mod validation;
+pub mod versioning;
+pub mod view_handlers;
 pub mod workspace_handlers;
 pub mod ws_auth;
 pub mod ws_chat_handler;
 pub mod ws_handlers;
+pub mod ws_presence_handler;
 pub mod ws_run_handler;
 
 #[cfg(feature = "embedded-frontend")]
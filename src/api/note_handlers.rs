@@ -1,9 +1,16 @@
 //! API handlers for Knowledge Notes
 
 use super::handlers::{AppError, OrchestratorState};
+use super::search_analytics_handlers::record_search;
+use super::validation::{validate, ValidPath};
+use super::versioning::{claim_version, etag};
 use super::{PaginatedResponse, PaginationParams, SearchFilter};
+use crate::auth::jwt::Claims;
+use crate::chat::undo::{self, UndoOperation};
 use crate::events::graph::GraphEvent;
 use crate::graph::algorithms::add_thermal_noise;
+use crate::neo4j::models::{EndorsementKind, VersionedNode};
+use crate::notes::endorsement::EndorsementSummary;
 use crate::notes::{
     BackfillProgress, CreateAnchorRequest, CreateNoteRequest, EntityType, LinkNoteRequest, Note,
     NoteContextResponse, NoteFilters, NoteImportance, NoteScope, NoteSearchHit, NoteStatus,
@@ -12,7 +19,7 @@ use crate::notes::{
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    Json,
+    Extension, Json,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -178,7 +185,7 @@ pub async fn list_notes(
 /// List notes for a specific project
 pub async fn list_project_notes(
     State(state): State<OrchestratorState>,
-    Path(project_id): Path<Uuid>,
+    ValidPath(project_id): ValidPath<Uuid>,
     Query(query): Query<NotesListQuery>,
 ) -> Result<Json<PaginatedResponse<Note>>, AppError> {
     query.pagination.validate().map_err(AppError::BadRequest)?;
@@ -201,6 +208,7 @@ pub async fn list_project_notes(
 /// Create a new note
 pub async fn create_note(
     State(state): State<OrchestratorState>,
+    headers: axum::http::HeaderMap,
     Json(body): Json<CreateNoteBody>,
 ) -> Result<(StatusCode, Json<Note>), AppError> {
     let explicit_run_id = body.run_id;
@@ -216,6 +224,7 @@ pub async fn create_note(
         assertion_rule: body.assertion_rule,
         run_id: None, // run_id is handled at the handler level, not passed down
     };
+    validate(&request)?;
 
     let note = state
         .orchestrator
@@ -223,6 +232,17 @@ pub async fn create_note(
         .create_note(request, "api")
         .await?;
 
+    if let Some(session_id) = undo::session_from_headers(&headers) {
+        undo::record(
+            state.orchestrator.neo4j(),
+            session_id,
+            "note.create",
+            format!("Created {} note {}", note.note_type, note.id),
+            UndoOperation::DeleteNote { note_id: note.id },
+        )
+        .await;
+    }
+
     // Resolve run_id: explicit > auto-detect from active run
     let run_id = match explicit_run_id {
         Some(rid) => Some(rid),
@@ -356,8 +376,8 @@ pub async fn create_note(
 /// Get a note by ID
 pub async fn get_note(
     State(state): State<OrchestratorState>,
-    Path(note_id): Path<Uuid>,
-) -> Result<Json<Note>, AppError> {
+    ValidPath(note_id): ValidPath<Uuid>,
+) -> Result<(axum::http::HeaderMap, Json<Note>), AppError> {
     let note = state
         .orchestrator
         .note_manager()
@@ -365,37 +385,93 @@ pub async fn get_note(
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Note {} not found", note_id)))?;
 
-    Ok(Json(note))
+    Ok((etag(Some(note.version)), Json(note)))
+}
+
+/// Federated notes mirror another instance: they are edited there and
+/// re-synced, so local writes are rejected.
+async fn ensure_not_federated(state: &OrchestratorState, note_id: Uuid) -> Result<(), AppError> {
+    let note = state.orchestrator.neo4j().get_note(note_id).await?;
+    if let Some(source) = note
+        .as_ref()
+        .and_then(|n| crate::notes::federation::source_of(n))
+    {
+        return Err(AppError::Forbidden(format!(
+            "Note {} is federated from '{}' and is read-only",
+            note_id, source
+        )));
+    }
+    Ok(())
 }
 
 /// Update a note
 pub async fn update_note(
     State(state): State<OrchestratorState>,
-    Path(note_id): Path<Uuid>,
+    headers: axum::http::HeaderMap,
+    ValidPath(note_id): ValidPath<Uuid>,
     Json(body): Json<UpdateNoteBody>,
-) -> Result<Json<Note>, AppError> {
+) -> Result<(axum::http::HeaderMap, Json<Note>), AppError> {
+    ensure_not_federated(&state, note_id).await?;
     let request = UpdateNoteRequest {
         content: body.content,
         importance: body.importance,
         status: body.status,
         tags: body.tags,
     };
+    validate(&request)?;
+    let claim = claim_version(
+        state.orchestrator.neo4j(),
+        &headers,
+        VersionedNode::Note,
+        note_id,
+    )
+    .await?;
+    let result: Result<_, AppError> = async {
+        let undo_session = undo::session_from_headers(&headers);
+        let snapshot = match undo_session {
+            Some(_) => state.orchestrator.note_manager().get_note(note_id).await?,
+            None => None,
+        };
 
-    let note = state
-        .orchestrator
-        .note_manager()
-        .update_note(note_id, request)
-        .await?
-        .ok_or_else(|| AppError::NotFound(format!("Note {} not found", note_id)))?;
+        let note = state
+            .orchestrator
+            .note_manager()
+            .update_note(note_id, request)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Note {} not found", note_id)))?;
+
+        if let (Some(session_id), Some(snapshot)) = (undo_session, snapshot) {
+            undo::record(
+                state.orchestrator.neo4j(),
+                session_id,
+                "note.update",
+                format!("Updated note {}", note_id),
+                UndoOperation::RestoreNote {
+                    note: Box::new(snapshot),
+                },
+            )
+            .await;
+        }
 
-    Ok(Json(note))
+        Ok(note)
+    }
+    .await;
+    let note = claim.settle(state.orchestrator.neo4j(), result).await?;
+    Ok((etag(Some(note.version)), Json(note)))
 }
 
 /// Delete a note
 pub async fn delete_note(
     State(state): State<OrchestratorState>,
-    Path(note_id): Path<Uuid>,
+    headers: axum::http::HeaderMap,
+    ValidPath(note_id): ValidPath<Uuid>,
 ) -> Result<StatusCode, AppError> {
+    ensure_not_federated(&state, note_id).await?;
+    let undo_session = undo::session_from_headers(&headers);
+    let snapshot = match undo_session {
+        Some(_) => state.orchestrator.note_manager().get_note(note_id).await?,
+        None => None,
+    };
     let deleted = state
         .orchestrator
         .note_manager()
@@ -403,6 +479,18 @@ pub async fn delete_note(
         .await?;
 
     if deleted {
+        if let (Some(session_id), Some(snapshot)) = (undo_session, snapshot) {
+            undo::record(
+                state.orchestrator.neo4j(),
+                session_id,
+                "note.delete",
+                format!("Deleted {} note {}", snapshot.note_type, note_id),
+                UndoOperation::RestoreNote {
+                    note: Box::new(snapshot),
+                },
+            )
+            .await;
+        }
         Ok(StatusCode::NO_CONTENT)
     } else {
         Err(AppError::NotFound(format!("Note {} not found", note_id)))
@@ -412,6 +500,7 @@ pub async fn delete_note(
 /// Search notes
 pub async fn search_notes(
     State(state): State<OrchestratorState>,
+    headers: axum::http::HeaderMap,
     Query(query): Query<NotesSearchQuery>,
 ) -> Result<Json<Vec<NoteSearchHit>>, AppError> {
     let filters = NoteFilters {
@@ -441,13 +530,21 @@ pub async fn search_notes(
         .search_notes(&query.q, &filters)
         .await?;
 
+    record_search(
+        &state,
+        &headers,
+        "notes",
+        &query.q,
+        query.project_slug.as_deref(),
+        hits.len(),
+    );
     Ok(Json(hits))
 }
 
 /// Link a note to an entity
 pub async fn link_note_to_entity(
     State(state): State<OrchestratorState>,
-    Path(note_id): Path<Uuid>,
+    ValidPath(note_id): ValidPath<Uuid>,
     Json(body): Json<LinkNoteBody>,
 ) -> Result<StatusCode, AppError> {
     // For File entities, normalize absolute paths to relative using the project root.
@@ -489,7 +586,7 @@ pub async fn link_note_to_entity(
 /// Unlink a note from an entity
 pub async fn unlink_note_from_entity(
     State(state): State<OrchestratorState>,
-    Path((note_id, entity_type, entity_id)): Path<(Uuid, String, String)>,
+    ValidPath((note_id, entity_type, entity_id)): ValidPath<(Uuid, String, String)>,
 ) -> Result<StatusCode, AppError> {
     let entity_type = entity_type
         .parse::<EntityType>()
@@ -507,7 +604,7 @@ pub async fn unlink_note_from_entity(
 /// Confirm a note is still valid
 pub async fn confirm_note(
     State(state): State<OrchestratorState>,
-    Path(note_id): Path<Uuid>,
+    ValidPath(note_id): ValidPath<Uuid>,
 ) -> Result<Json<Note>, AppError> {
     let note = state
         .orchestrator
@@ -527,7 +624,7 @@ pub struct InvalidateNoteBody {
 
 pub async fn invalidate_note(
     State(state): State<OrchestratorState>,
-    Path(note_id): Path<Uuid>,
+    ValidPath(note_id): ValidPath<Uuid>,
     Json(body): Json<InvalidateNoteBody>,
 ) -> Result<Json<Note>, AppError> {
     let note = state
@@ -543,7 +640,7 @@ pub async fn invalidate_note(
 /// Supersede a note with a new one
 pub async fn supersede_note(
     State(state): State<OrchestratorState>,
-    Path(old_note_id): Path<Uuid>,
+    ValidPath(old_note_id): ValidPath<Uuid>,
     Json(body): Json<CreateNoteBody>,
 ) -> Result<(StatusCode, Json<Note>), AppError> {
     let request = CreateNoteRequest {
@@ -567,6 +664,79 @@ pub async fn supersede_note(
     Ok((StatusCode::CREATED, Json(new_note)))
 }
 
+// ============================================================================
+// Endorsements
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct EndorseNoteBody {
+    pub kind: EndorsementKind,
+}
+
+fn endorsing_user(claims: Option<Extension<Claims>>) -> Claims {
+    claims
+        .map(|Extension(c)| c)
+        .unwrap_or_else(Claims::anonymous)
+}
+
+/// Endorsements of a note (acks and upvotes per user)
+pub async fn get_note_endorsements(
+    State(state): State<OrchestratorState>,
+    ValidPath(note_id): ValidPath<Uuid>,
+) -> Result<Json<EndorsementSummary>, AppError> {
+    let summary = state
+        .orchestrator
+        .note_manager()
+        .get_endorsements(note_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Note {} not found", note_id)))?;
+
+    Ok(Json(summary))
+}
+
+/// Endorse a note as the current user
+pub async fn endorse_note(
+    State(state): State<OrchestratorState>,
+    claims: Option<Extension<Claims>>,
+    ValidPath(note_id): ValidPath<Uuid>,
+    Json(body): Json<EndorseNoteBody>,
+) -> Result<Json<EndorsementSummary>, AppError> {
+    let user = endorsing_user(claims);
+    let user_name = if user.name.is_empty() {
+        &user.email
+    } else {
+        &user.name
+    };
+    let summary = state
+        .orchestrator
+        .note_manager()
+        .endorse_note(note_id, body.kind, &user.sub, user_name)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Note {} not found", note_id)))?;
+
+    Ok(Json(summary))
+}
+
+/// Withdraw the current user's endorsement of a note
+pub async fn withdraw_note_endorsement(
+    State(state): State<OrchestratorState>,
+    claims: Option<Extension<Claims>>,
+    ValidPath((note_id, kind)): ValidPath<(Uuid, String)>,
+) -> Result<Json<EndorsementSummary>, AppError> {
+    let kind = kind
+        .parse::<EndorsementKind>()
+        .map_err(AppError::BadRequest)?;
+    let user = endorsing_user(claims);
+    let summary = state
+        .orchestrator
+        .note_manager()
+        .withdraw_endorsement(note_id, kind, &user.sub)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Note {} not found", note_id)))?;
+
+    Ok(Json(summary))
+}
+
 /// Get notes needing review
 pub async fn get_notes_needing_review(
     State(state): State<OrchestratorState>,
@@ -1291,6 +1461,7 @@ pub struct SemanticSearchQuery {
 /// GET /api/notes/search-semantic — Vector-based semantic search
 pub async fn search_notes_semantic(
     State(state): State<OrchestratorState>,
+    headers: axum::http::HeaderMap,
     Query(query): Query<SemanticSearchQuery>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let project_id = if let Some(ref slug) = query.project_slug {
@@ -1341,6 +1512,14 @@ pub async fn search_notes_semantic(
         }
     }
 
+    record_search(
+        &state,
+        &headers,
+        "notes_semantic",
+        &query.query,
+        query.project_slug.as_deref(),
+        hits.len(),
+    );
     Ok(Json(serde_json::to_value(hits).unwrap_or_default()))
 }
 
@@ -1636,6 +1815,10 @@ mod tests {
     async fn test_app() -> axum::Router {
         let app_state = mock_app_state();
         let orchestrator = Arc::new(Orchestrator::new(app_state).await.unwrap());
+        app_for(orchestrator)
+    }
+
+    fn app_for(orchestrator: Arc<Orchestrator>) -> axum::Router {
         let watcher = Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
             orchestrator.clone(),
         )));
@@ -1653,7 +1836,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -1756,6 +1942,44 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_note_versions_in_etag() {
+        let app = test_app().await;
+        let resp = app
+            .clone()
+            .oneshot(auth_post(
+                "/api/notes",
+                serde_json::json!({"note_type": "gotcha", "content": "Original"}),
+            ))
+            .await
+            .unwrap();
+        let uri = format!(
+            "/api/notes/{}",
+            body_json(resp).await["id"].as_str().unwrap()
+        );
+
+        let resp = app.clone().oneshot(auth_get(&uri)).await.unwrap();
+        assert_eq!(resp.headers()["etag"], "\"0\"");
+
+        let patch = Request::builder()
+            .method("PATCH")
+            .uri(&uri)
+            .header("authorization", test_bearer_token())
+            .header("content-type", "application/json")
+            .header("if-match", "\"0\"")
+            .body(Body::from(
+                serde_json::json!({"content": "Edited"}).to_string(),
+            ))
+            .unwrap();
+        let resp = app.oneshot(patch).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        // Claimed by If-Match, then incremented by the update itself
+        assert_eq!(resp.headers()["etag"], "\"2\"");
+        let json = body_json(resp).await;
+        assert_eq!(json["content"], "Edited");
+        assert_eq!(json["version"], 2);
+    }
+
     // ====================================================================
     // GET /api/notes/{id} — not found
     // ====================================================================
@@ -1770,6 +1994,50 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::NOT_FOUND);
     }
 
+    // ====================================================================
+    // PATCH/DELETE /api/notes/{id} — federated notes are read-only
+    // ====================================================================
+
+    #[tokio::test]
+    async fn test_federated_notes_are_read_only() {
+        let orchestrator = Arc::new(Orchestrator::new(mock_app_state()).await.unwrap());
+        let note = crate::notes::Note::new(
+            None,
+            crate::notes::NoteType::Guideline,
+            "Prefer thiserror in libraries".to_string(),
+            "federation:central".to_string(),
+        );
+        orchestrator.neo4j().create_note(&note).await.unwrap();
+        let uri = format!("/api/notes/{}", note.id);
+
+        let patch = Request::builder()
+            .method("PATCH")
+            .uri(&uri)
+            .header("authorization", test_bearer_token())
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"content":"edited"}"#))
+            .unwrap();
+        let resp = app_for(orchestrator.clone()).oneshot(patch).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let delete = Request::builder()
+            .method("DELETE")
+            .uri(&uri)
+            .header("authorization", test_bearer_token())
+            .body(Body::empty())
+            .unwrap();
+        let resp = app_for(orchestrator.clone()).oneshot(delete).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let stored = orchestrator
+            .neo4j()
+            .get_note(note.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.content, "Prefer thiserror in libraries");
+    }
+
     // ====================================================================
     // GET /api/entities/{entity_type}/{entity_id}/notes — invalid type
     // ====================================================================
@@ -1972,6 +2240,56 @@ mod tests {
         );
     }
 
+    // ====================================================================
+    // /api/notes/{id}/endorsements
+    // ====================================================================
+
+    #[tokio::test]
+    async fn test_endorse_note() {
+        let app = test_app().await;
+        let create_body = serde_json::json!({
+            "note_type": "tip",
+            "content": "Run the migrations before the integration tests.",
+            "importance": "low"
+        });
+        let resp = app
+            .clone()
+            .oneshot(auth_post("/api/notes", create_body))
+            .await
+            .unwrap();
+        let note_id = body_json(resp).await["id"].as_str().unwrap().to_string();
+        let uri = format!("/api/notes/{}/endorsements", note_id);
+
+        // Each test token is a different user
+        for _ in 0..3 {
+            let resp = app
+                .clone()
+                .oneshot(auth_post(&uri, serde_json::json!({"kind": "upvote"})))
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+        }
+        let resp = app.clone().oneshot(auth_get(&uri)).await.unwrap();
+        let summary = body_json(resp).await;
+        assert_eq!(summary["upvotes"], 3);
+        assert_eq!(summary["endorsers"], 3);
+        assert_eq!(summary["importance"], "high");
+
+        let resp = app
+            .clone()
+            .oneshot(auth_delete(&format!("{}/thanks", uri)))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let missing = format!("/api/notes/{}/endorsements", uuid::Uuid::new_v4());
+        let resp = app
+            .oneshot(auth_post(&missing, serde_json::json!({"kind": "ack"})))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
     // ================================================================
     // Knowledge Fabric — Handler Tests
     // ================================================================
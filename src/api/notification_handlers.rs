@@ -0,0 +1,208 @@
+//! Notification preference API handlers
+//!
+//! Every user chooses whether the notification rules of the server email
+//! them, and which rules they mute. Slack notifications go to a shared
+//! channel and are not per-user.
+
+use super::handlers::{AppError, OrchestratorState};
+use crate::auth::extractor::AuthUser;
+use crate::neo4j::models::NotificationPreferences;
+use axum::{extract::State, Json};
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct NotificationPreferencesResponse {
+    pub preferences: NotificationPreferences,
+    /// Whether the server sends emails (SMTP configured)
+    pub email_available: bool,
+    /// Rules notifying by email, which can be muted
+    pub rules: Vec<String>,
+}
+
+/// GET /api/notifications/preferences — Notification preferences of the current user
+pub async fn get_notification_preferences(
+    State(state): State<OrchestratorState>,
+    user: AuthUser,
+) -> Result<Json<NotificationPreferencesResponse>, AppError> {
+    let config = &state.orchestrator.config().notifications;
+    let preferences = state
+        .orchestrator
+        .neo4j()
+        .get_notification_preferences(user.user_id)
+        .await?
+        .unwrap_or_default();
+    Ok(Json(NotificationPreferencesResponse {
+        preferences,
+        email_available: config.smtp.is_some(),
+        rules: config.email_rules(),
+    }))
+}
+
+/// PUT /api/notifications/preferences — Replace the notification preferences
+/// of the current user. Emails go to the address of the account.
+pub async fn set_notification_preferences(
+    State(state): State<OrchestratorState>,
+    user: AuthUser,
+    Json(preferences): Json<NotificationPreferences>,
+) -> Result<Json<NotificationPreferences>, AppError> {
+    let config = &state.orchestrator.config().notifications;
+    if preferences.email_enabled && config.smtp.is_none() {
+        return Err(AppError::BadRequest(
+            "Email notifications are not configured on this server".to_string(),
+        ));
+    }
+    if let Some(unknown) = preferences
+        .muted_rules
+        .iter()
+        .find(|name| !config.rules.iter().any(|r| &r.name == *name))
+    {
+        return Err(AppError::BadRequest(format!(
+            "Unknown notification rule '{}'",
+            unknown
+        )));
+    }
+    state
+        .orchestrator
+        .neo4j()
+        .update_notification_preferences(user.user_id, &user.email, &preferences)
+        .await?;
+    Ok(Json(preferences))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::handlers::ServerState;
+    use crate::api::routes::create_router;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::traits::GraphStore;
+    use crate::orchestrator::watcher::FileWatcher;
+    use crate::orchestrator::Orchestrator;
+    use crate::test_helpers::{mock_app_state_with_stores, test_auth_config};
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode as AxumStatus},
+    };
+    use std::sync::Arc;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    async fn mock_server_state() -> (OrchestratorState, Arc<MockGraphStore>) {
+        let (mut app_state, graph, _) = mock_app_state_with_stores();
+        let mut config = (*app_state.config).clone();
+        config.notifications = serde_yaml::from_str(
+            r#"
+smtp: {host: smtp.example.com, from: po@example.com}
+slack: {webhook_url: https://hooks.slack.com/services/x}
+rules:
+  - {name: approved, event: plan_approved}
+  - {name: advisories, event: advisory_found, channels: [slack]}
+"#,
+        )
+        .unwrap();
+        app_state.config = Arc::new(config);
+        let orchestrator = Arc::new(Orchestrator::new(app_state).await.unwrap());
+        let watcher = Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
+            orchestrator.clone(),
+        )));
+        let state = Arc::new(ServerState {
+            orchestrator,
+            watcher,
+            chat_manager: None,
+            event_bus: Arc::new(crate::events::HybridEmitter::new(Arc::new(
+                crate::events::EventBus::default(),
+            ))),
+            nats_emitter: None,
+            auth_config: Some(test_auth_config()),
+            serve_frontend: false,
+            frontend_path: "./dist".to_string(),
+            setup_completed: true,
+            server_port: 6600,
+            public_url: None,
+            read_only: false,
+            ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
+            registry_remote_url: None,
+            oidc_client: None,
+            neural_router: crate::test_helpers::mock_neural_router(),
+            trajectory_collector: std::sync::RwLock::new(None),
+            trajectory_store_neo4j: None,
+            trajectory_store: None,
+            identity: None,
+            reactor_counters: std::sync::OnceLock::new(),
+            confidence_tracker: Arc::new(crate::graph::confidence::ConfidenceTracker::default()),
+            mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
+            model_catalog: crate::chat::model_catalog::ModelCatalogCache::new(None),
+        });
+        (state, graph)
+    }
+
+    fn request(method: &str, token: &str, body: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri("/api/notifications/preferences")
+            .header("content-type", "application/json")
+            .header("authorization", token)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_notification_preferences() {
+        let (state, graph) = mock_server_state().await;
+        let app = create_router(state);
+        let user_id = Uuid::new_v4();
+        let token = format!(
+            "Bearer {}",
+            crate::auth::jwt::encode_jwt(
+                user_id,
+                "alice@example.com",
+                "Alice",
+                "test-secret-key-minimum-32-chars!!",
+                28800,
+            )
+            .unwrap()
+        );
+
+        let resp = app
+            .clone()
+            .oneshot(request("GET", &token, serde_json::json!({})))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["preferences"]["email_enabled"], false);
+        assert_eq!(body["email_available"], true);
+        assert_eq!(body["rules"], serde_json::json!(["approved"]));
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "PUT",
+                &token,
+                serde_json::json!({"email_enabled": true, "muted_rules": ["typo"]}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::BAD_REQUEST);
+
+        let resp = app
+            .oneshot(request(
+                "PUT",
+                &token,
+                serde_json::json!({"email_enabled": true, "muted_rules": ["approved"]}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let subscribers = graph.list_notification_subscribers().await.unwrap();
+        assert_eq!(subscribers.len(), 1);
+        assert_eq!(subscribers[0].user_id, user_id);
+        assert_eq!(subscribers[0].email, "alice@example.com");
+        assert_eq!(subscribers[0].preferences.muted_rules, vec!["approved"]);
+    }
+}
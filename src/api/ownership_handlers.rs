@@ -0,0 +1,521 @@
+//! Code ownership API handlers
+//!
+//! CODEOWNERS ingestion and lookups per project, and the routing of plan
+//! tasks to the owners of the files they touch.
+
+use super::handlers::{AppError, OrchestratorState};
+use super::validation::ValidPath;
+use crate::neo4j::models::{CodeOwnerNode, CodeOwnerRule, ProjectNode};
+use crate::orchestrator::ownership::{
+    ingest_code_owners, sync_code_owners, OwnerShare, Ownership, OwnershipIngestion,
+};
+use crate::plan::models::UpdateTaskRequest;
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+async fn project_or_404(
+    state: &OrchestratorState,
+    project_id: Uuid,
+) -> Result<ProjectNode, AppError> {
+    state
+        .orchestrator
+        .neo4j()
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))
+}
+
+/// Stored rules of a project, 404 before any CODEOWNERS ingestion
+async fn ownership_or_404(
+    state: &OrchestratorState,
+    project: &ProjectNode,
+) -> Result<Ownership, AppError> {
+    let ownership = Ownership::load(state.orchestrator.neo4j(), project).await?;
+    if ownership.is_empty() {
+        return Err(AppError::NotFound(format!(
+            "No CODEOWNERS rules for project {}",
+            project.slug
+        )));
+    }
+    Ok(ownership)
+}
+
+// ============================================================================
+// CODEOWNERS
+// ============================================================================
+
+#[derive(Serialize)]
+pub struct CodeOwnersResponse {
+    pub project_id: Uuid,
+    #[serde(flatten)]
+    pub ingestion: OwnershipIngestion,
+}
+
+/// Ingest a CODEOWNERS file (raw request body), or the one of the project
+/// root when the body is empty, and link the synced files to their owners.
+pub async fn ingest_project_code_owners(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+    body: axum::body::Bytes,
+) -> Result<Json<CodeOwnersResponse>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let project = project_or_404(&state, project_id).await?;
+
+    let content = String::from_utf8_lossy(&body);
+    let ingestion = if content.trim().is_empty() {
+        sync_code_owners(neo4j, &project).await?.ok_or_else(|| {
+            AppError::NotFound(format!(
+                "No CODEOWNERS file under {} (looked in {})",
+                project.root_path,
+                crate::orchestrator::ownership::CODEOWNERS_LOCATIONS.join(", ")
+            ))
+        })?
+    } else {
+        ingest_code_owners(neo4j, &project, "request", &content).await?
+    };
+    Ok(Json(CodeOwnersResponse {
+        project_id,
+        ingestion,
+    }))
+}
+
+#[derive(Serialize)]
+pub struct CodeOwnerRulesResponse {
+    pub project_id: Uuid,
+    pub rules: Vec<CodeOwnerRule>,
+}
+
+/// Get the CODEOWNERS rules of a project, in file order
+pub async fn get_project_code_owners(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+) -> Result<Json<CodeOwnerRulesResponse>, AppError> {
+    project_or_404(&state, project_id).await?;
+    let rules = state
+        .orchestrator
+        .neo4j()
+        .get_code_owner_rules(project_id)
+        .await?;
+    Ok(Json(CodeOwnerRulesResponse { project_id, rules }))
+}
+
+#[derive(Serialize)]
+pub struct OwnersResponse {
+    pub project_id: Uuid,
+    pub owners: Vec<CodeOwnerNode>,
+}
+
+/// List the owners of a project with the number of files they own
+pub async fn list_project_owners(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+) -> Result<Json<OwnersResponse>, AppError> {
+    project_or_404(&state, project_id).await?;
+    let owners = state
+        .orchestrator
+        .neo4j()
+        .list_code_owners(project_id)
+        .await?;
+    Ok(Json(OwnersResponse { project_id, owners }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OwnershipQuery {
+    /// File path (absolute or relative to the project root), or directory
+    /// path ending with `/`
+    pub path: String,
+}
+
+#[derive(Serialize)]
+pub struct OwnershipResponse {
+    pub path: String,
+    /// Owners of the file, or of the files below the directory
+    pub owners: Vec<String>,
+    /// Rule deciding the owners of the file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule: Option<CodeOwnerRule>,
+    /// Synced files below the directory
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files: Option<usize>,
+    /// Files below the directory per owner, most files first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub shares: Vec<OwnerShare>,
+}
+
+/// Who owns a file or directory of a project
+pub async fn get_path_ownership(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+    Query(query): Query<OwnershipQuery>,
+) -> Result<Json<OwnershipResponse>, AppError> {
+    let project = project_or_404(&state, project_id).await?;
+    let ownership = ownership_or_404(&state, &project).await?;
+
+    if !query.path.ends_with('/') {
+        let rule = ownership.rule_for(&query.path).cloned();
+        return Ok(Json(OwnershipResponse {
+            owners: rule.as_ref().map(|r| r.owners.clone()).unwrap_or_default(),
+            path: query.path,
+            rule,
+            files: None,
+            shares: Vec::new(),
+        }));
+    }
+
+    let dir = ownership.relative(&query.path).to_string();
+    let paths = state
+        .orchestrator
+        .neo4j()
+        .get_project_file_paths(project_id)
+        .await?;
+    let below: Vec<&str> = paths
+        .iter()
+        .map(String::as_str)
+        .filter(|p| ownership.relative(p).starts_with(&dir))
+        .collect();
+    let shares = ownership.owner_shares(below.iter().copied());
+    Ok(Json(OwnershipResponse {
+        path: query.path,
+        owners: shares.iter().map(|s| s.owner.clone()).collect(),
+        rule: None,
+        files: Some(below.len()),
+        shares,
+    }))
+}
+
+// ============================================================================
+// Plan task routing
+// ============================================================================
+
+#[derive(Serialize)]
+pub struct TaskRouting {
+    pub task_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assigned_to: Option<String>,
+    /// Affected files of the task
+    pub files: usize,
+    /// Owners of the affected files, most files first
+    pub owners: Vec<OwnerShare>,
+    /// Owner of the most affected files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_owner: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct PlanRoutingResponse {
+    pub plan_id: Uuid,
+    pub project_id: Uuid,
+    pub tasks: Vec<TaskRouting>,
+    /// Tasks assigned to their suggested owner (POST only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assigned: Option<usize>,
+}
+
+async fn plan_routing(
+    state: &OrchestratorState,
+    plan_id: Uuid,
+) -> Result<PlanRoutingResponse, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let plan = neo4j
+        .get_plan(plan_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Plan {} not found", plan_id)))?;
+    let project_id = plan.project_id.ok_or_else(|| {
+        AppError::BadRequest(format!("Plan {} is not linked to a project", plan_id))
+    })?;
+    let project = project_or_404(state, project_id).await?;
+    let ownership = ownership_or_404(state, &project).await?;
+
+    let tasks = neo4j
+        .get_plan_tasks(plan_id)
+        .await?
+        .into_iter()
+        .map(|task| {
+            let owners = ownership.owner_shares(task.affected_files.iter().map(String::as_str));
+            TaskRouting {
+                task_id: task.id,
+                title: task.title,
+                assigned_to: task.assigned_to,
+                files: task.affected_files.len(),
+                suggested_owner: owners.first().map(|s| s.owner.clone()),
+                owners,
+            }
+        })
+        .collect();
+    Ok(PlanRoutingResponse {
+        plan_id,
+        project_id,
+        tasks,
+        assigned: None,
+    })
+}
+
+/// Owners of the files of each task of a plan, and the suggested assignee
+pub async fn get_plan_routing(
+    State(state): State<OrchestratorState>,
+    ValidPath(plan_id): ValidPath<Uuid>,
+) -> Result<Json<PlanRoutingResponse>, AppError> {
+    Ok(Json(plan_routing(&state, plan_id).await?))
+}
+
+/// Assign the unassigned tasks of a plan to their suggested owner
+pub async fn apply_plan_routing(
+    State(state): State<OrchestratorState>,
+    ValidPath(plan_id): ValidPath<Uuid>,
+) -> Result<Json<PlanRoutingResponse>, AppError> {
+    let mut routing = plan_routing(&state, plan_id).await?;
+    let mut assigned = 0;
+    for task in &mut routing.tasks {
+        let Some(owner) = task.suggested_owner.clone() else {
+            continue;
+        };
+        if task.assigned_to.as_deref().is_some_and(|a| !a.is_empty()) {
+            continue;
+        }
+        state
+            .orchestrator
+            .plan_manager()
+            .update_task(
+                task.task_id,
+                UpdateTaskRequest {
+                    assigned_to: Some(owner.clone()),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        task.assigned_to = Some(owner);
+        assigned += 1;
+    }
+    routing.assigned = Some(assigned);
+    Ok(Json(routing))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::handlers::ServerState;
+    use crate::api::routes::create_router;
+    use crate::orchestrator::watcher::FileWatcher;
+    use crate::orchestrator::Orchestrator;
+    use crate::test_helpers::{
+        mock_app_state, test_auth_config, test_bearer_token, test_plan_for_project,
+        test_project_named, test_task_titled,
+    };
+    use axum::{body::Body, http::Request, http::StatusCode};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    async fn mock_server_state() -> OrchestratorState {
+        let app_state = mock_app_state();
+        let orchestrator = Arc::new(Orchestrator::new(app_state).await.unwrap());
+        let watcher = Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
+            orchestrator.clone(),
+        )));
+        Arc::new(ServerState {
+            orchestrator,
+            watcher,
+            chat_manager: None,
+            event_bus: Arc::new(crate::events::HybridEmitter::new(Arc::new(
+                crate::events::EventBus::default(),
+            ))),
+            nats_emitter: None,
+            auth_config: Some(test_auth_config()),
+            serve_frontend: false,
+            frontend_path: "./dist".to_string(),
+            setup_completed: true,
+            server_port: 6600,
+            public_url: None,
+            read_only: false,
+            ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
+            registry_remote_url: None,
+            oidc_client: None,
+            neural_router: crate::test_helpers::mock_neural_router(),
+            trajectory_collector: std::sync::RwLock::new(None),
+            trajectory_store_neo4j: None,
+            trajectory_store: None,
+            identity: None,
+            reactor_counters: std::sync::OnceLock::new(),
+            confidence_tracker: Arc::new(crate::graph::confidence::ConfidenceTracker::default()),
+            mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
+            model_catalog: crate::chat::model_catalog::ModelCatalogCache::new(None),
+        })
+    }
+
+    fn authed(method: &str, uri: &str, body: &str) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("authorization", test_bearer_token())
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    async fn json_body(resp: axum::response::Response) -> serde_json::Value {
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    const CODEOWNERS: &str = "* @acme/core\n/src/api/ @acme/api\n*.md @writer\n";
+
+    #[tokio::test]
+    async fn test_code_owners_endpoints() {
+        let state = mock_server_state().await;
+        let project = test_project_named("owned");
+        let neo4j = state.orchestrator.neo4j();
+        neo4j.create_project(&project).await.unwrap();
+        for path in ["src/api/users.rs", "src/api/orders.rs", "src/main.rs"] {
+            neo4j
+                .link_file_to_project(&format!("/tmp/owned/{}", path), project.id)
+                .await
+                .unwrap();
+        }
+        let app = create_router(state.clone());
+        let base = format!("/api/projects/{}", project.id);
+
+        // Nothing ingested yet
+        let resp = app
+            .clone()
+            .oneshot(authed(
+                "GET",
+                &format!("{}/ownership?path=src/main.rs", base),
+                "",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let resp = app
+            .clone()
+            .oneshot(authed("POST", &format!("{}/codeowners", base), CODEOWNERS))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let json = json_body(resp).await;
+        assert_eq!(json["source"], "request");
+        assert_eq!(
+            (json["rules"].as_u64(), json["owned_files"].as_u64()),
+            (Some(3), Some(3))
+        );
+
+        let resp = app
+            .clone()
+            .oneshot(authed(
+                "GET",
+                &format!("{}/ownership?path=/tmp/owned/src/api/users.rs", base),
+                "",
+            ))
+            .await
+            .unwrap();
+        let json = json_body(resp).await;
+        assert_eq!(json["owners"], serde_json::json!(["@acme/api"]));
+        assert_eq!(json["rule"]["line"], 2);
+
+        let resp = app
+            .clone()
+            .oneshot(authed("GET", &format!("{}/ownership?path=src/", base), ""))
+            .await
+            .unwrap();
+        let json = json_body(resp).await;
+        assert_eq!(json["files"], 3);
+        assert_eq!(
+            json["owners"],
+            serde_json::json!(["@acme/api", "@acme/core"])
+        );
+        assert_eq!(json["shares"][0]["files"], 2);
+
+        let resp = app
+            .clone()
+            .oneshot(authed("GET", &format!("{}/owners", base), ""))
+            .await
+            .unwrap();
+        let json = json_body(resp).await;
+        assert_eq!(json["owners"].as_array().unwrap().len(), 3);
+        assert_eq!(json["owners"][0]["name"], "@acme/api");
+        assert_eq!(json["owners"][0]["kind"], "team");
+
+        let resp = app
+            .clone()
+            .oneshot(authed("GET", &format!("{}/codeowners", base), ""))
+            .await
+            .unwrap();
+        let json = json_body(resp).await;
+        assert_eq!(json["rules"][2]["pattern"], "*.md");
+
+        // No CODEOWNERS file under the project root
+        let resp = app
+            .clone()
+            .oneshot(authed("POST", &format!("{}/codeowners", base), ""))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_plan_routing() {
+        let state = mock_server_state().await;
+        let project = test_project_named("routed");
+        let neo4j = state.orchestrator.neo4j();
+        neo4j.create_project(&project).await.unwrap();
+        neo4j
+            .replace_code_owners(
+                project.id,
+                &crate::orchestrator::ownership::parse_codeowners(CODEOWNERS).rules,
+                &[],
+            )
+            .await
+            .unwrap();
+        let plan = test_plan_for_project(project.id);
+        neo4j.create_plan(&plan).await.unwrap();
+        let mut api = test_task_titled("API");
+        api.affected_files = vec![
+            "/tmp/routed/src/api/users.rs".to_string(),
+            "/tmp/routed/src/api/orders.rs".to_string(),
+            "/tmp/routed/README.md".to_string(),
+        ];
+        let mut taken = test_task_titled("Taken");
+        taken.affected_files = vec!["src/main.rs".to_string()];
+        taken.assigned_to = Some("bob".to_string());
+        let none = test_task_titled("No files");
+        for task in [&api, &taken, &none] {
+            neo4j.create_task(plan.id, task).await.unwrap();
+        }
+        let app = create_router(state.clone());
+        let uri = format!("/api/plans/{}/routing", plan.id);
+
+        let resp = app.clone().oneshot(authed("GET", &uri, "")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let json = json_body(resp).await;
+        let routed = |json: &serde_json::Value, title: &str| {
+            json["tasks"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|t| t["title"] == title)
+                .cloned()
+                .unwrap()
+        };
+        let api_routing = routed(&json, "API");
+        assert_eq!(api_routing["suggested_owner"], "@acme/api");
+        assert_eq!(api_routing["owners"][1]["owner"], "@writer");
+        assert!(json.get("assigned").is_none());
+
+        let resp = app.clone().oneshot(authed("POST", &uri, "")).await.unwrap();
+        let json = json_body(resp).await;
+        assert_eq!(json["assigned"], 1);
+        let stored = neo4j.get_task(api.id).await.unwrap().unwrap();
+        assert_eq!(stored.assigned_to.as_deref(), Some("@acme/api"));
+        let stored = neo4j.get_task(taken.id).await.unwrap().unwrap();
+        assert_eq!(stored.assigned_to.as_deref(), Some("bob"));
+        assert!(routed(&json, "No files")["suggested_owner"].is_null());
+    }
+}
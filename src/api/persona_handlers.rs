@@ -1,6 +1,7 @@
 //! API handlers for Living Personas
 
 use super::handlers::{AppError, OrchestratorState};
+use super::validation::ValidPath;
 use super::{PaginatedResponse, PaginationParams};
 use crate::events::EventEmitter;
 use crate::neo4j::models::{
@@ -9,7 +10,7 @@ use crate::neo4j::models::{
     PortablePersonaNote,
 };
 use axum::{
-    extract::{Path, Query, State},
+    extract::{Query, State},
     http::StatusCode,
     Json,
 };
@@ -248,7 +249,7 @@ pub async fn create_persona(
 /// GET /api/personas/:id
 pub async fn get_persona(
     State(state): State<OrchestratorState>,
-    Path(persona_id): Path<Uuid>,
+    ValidPath(persona_id): ValidPath<Uuid>,
 ) -> Result<Json<PersonaNode>, AppError> {
     let persona = state
         .orchestrator
@@ -266,7 +267,7 @@ pub async fn get_persona(
 /// PUT /api/personas/:id
 pub async fn update_persona(
     State(state): State<OrchestratorState>,
-    Path(persona_id): Path<Uuid>,
+    ValidPath(persona_id): ValidPath<Uuid>,
     Json(body): Json<UpdatePersonaBody>,
 ) -> Result<Json<PersonaNode>, AppError> {
     let mut persona = state
@@ -374,7 +375,7 @@ pub async fn update_persona(
 /// DELETE /api/personas/:id
 pub async fn delete_persona(
     State(state): State<OrchestratorState>,
-    Path(persona_id): Path<Uuid>,
+    ValidPath(persona_id): ValidPath<Uuid>,
 ) -> Result<StatusCode, AppError> {
     let deleted = state
         .orchestrator
@@ -405,7 +406,7 @@ pub async fn delete_persona(
 /// POST /api/personas/:persona_id/skills/:skill_id
 pub async fn add_skill(
     State(state): State<OrchestratorState>,
-    Path((persona_id, skill_id)): Path<(Uuid, Uuid)>,
+    ValidPath((persona_id, skill_id)): ValidPath<(Uuid, Uuid)>,
 ) -> Result<StatusCode, AppError> {
     state
         .orchestrator
@@ -428,7 +429,7 @@ pub async fn add_skill(
 /// DELETE /api/personas/:persona_id/skills/:skill_id
 pub async fn remove_skill(
     State(state): State<OrchestratorState>,
-    Path((persona_id, skill_id)): Path<(Uuid, Uuid)>,
+    ValidPath((persona_id, skill_id)): ValidPath<(Uuid, Uuid)>,
 ) -> Result<StatusCode, AppError> {
     state
         .orchestrator
@@ -455,7 +456,7 @@ pub async fn remove_skill(
 /// POST /api/personas/:persona_id/protocols/:protocol_id
 pub async fn add_protocol(
     State(state): State<OrchestratorState>,
-    Path((persona_id, protocol_id)): Path<(Uuid, Uuid)>,
+    ValidPath((persona_id, protocol_id)): ValidPath<(Uuid, Uuid)>,
 ) -> Result<StatusCode, AppError> {
     state
         .orchestrator
@@ -478,7 +479,7 @@ pub async fn add_protocol(
 /// DELETE /api/personas/:persona_id/protocols/:protocol_id
 pub async fn remove_protocol(
     State(state): State<OrchestratorState>,
-    Path((persona_id, protocol_id)): Path<(Uuid, Uuid)>,
+    ValidPath((persona_id, protocol_id)): ValidPath<(Uuid, Uuid)>,
 ) -> Result<StatusCode, AppError> {
     state
         .orchestrator
@@ -505,7 +506,7 @@ pub async fn remove_protocol(
 /// POST /api/personas/:persona_id/feature-graphs/:feature_graph_id
 pub async fn scope_to_feature_graph(
     State(state): State<OrchestratorState>,
-    Path((persona_id, feature_graph_id)): Path<(Uuid, Uuid)>,
+    ValidPath((persona_id, feature_graph_id)): ValidPath<(Uuid, Uuid)>,
 ) -> Result<StatusCode, AppError> {
     state
         .orchestrator
@@ -519,7 +520,7 @@ pub async fn scope_to_feature_graph(
 /// DELETE /api/personas/:persona_id/feature-graphs/:feature_graph_id
 pub async fn unscope_feature_graph(
     State(state): State<OrchestratorState>,
-    Path((persona_id, _feature_graph_id)): Path<(Uuid, Uuid)>,
+    ValidPath((persona_id, _feature_graph_id)): ValidPath<(Uuid, Uuid)>,
 ) -> Result<StatusCode, AppError> {
     state
         .orchestrator
@@ -537,7 +538,7 @@ pub async fn unscope_feature_graph(
 /// POST /api/personas/:persona_id/files
 pub async fn add_file(
     State(state): State<OrchestratorState>,
-    Path(persona_id): Path<Uuid>,
+    ValidPath(persona_id): ValidPath<Uuid>,
     Json(body): Json<AddFileBody>,
 ) -> Result<StatusCode, AppError> {
     if body.file_path.trim().is_empty() {
@@ -567,7 +568,7 @@ pub async fn add_file(
 /// DELETE /api/personas/:persona_id/files
 pub async fn remove_file(
     State(state): State<OrchestratorState>,
-    Path(persona_id): Path<Uuid>,
+    ValidPath(persona_id): ValidPath<Uuid>,
     Json(body): Json<RemoveFileBody>,
 ) -> Result<StatusCode, AppError> {
     state
@@ -595,7 +596,7 @@ pub async fn remove_file(
 /// POST /api/personas/:persona_id/functions
 pub async fn add_function(
     State(state): State<OrchestratorState>,
-    Path(persona_id): Path<Uuid>,
+    ValidPath(persona_id): ValidPath<Uuid>,
     Json(body): Json<AddFunctionBody>,
 ) -> Result<StatusCode, AppError> {
     if body.function_name.trim().is_empty() {
@@ -625,7 +626,7 @@ pub async fn add_function(
 /// DELETE /api/personas/:persona_id/functions
 pub async fn remove_function(
     State(state): State<OrchestratorState>,
-    Path(persona_id): Path<Uuid>,
+    ValidPath(persona_id): ValidPath<Uuid>,
     Json(body): Json<RemoveFunctionBody>,
 ) -> Result<StatusCode, AppError> {
     state
@@ -653,7 +654,7 @@ pub async fn remove_function(
 /// POST /api/personas/:persona_id/notes/:note_id
 pub async fn add_note(
     State(state): State<OrchestratorState>,
-    Path((persona_id, note_id)): Path<(Uuid, Uuid)>,
+    ValidPath((persona_id, note_id)): ValidPath<(Uuid, Uuid)>,
     Json(body): Json<AddWeightedBody>,
 ) -> Result<StatusCode, AppError> {
     let weight = body.weight.clamp(0.0, 1.0);
@@ -678,7 +679,7 @@ pub async fn add_note(
 /// DELETE /api/personas/:persona_id/notes/:note_id
 pub async fn remove_note(
     State(state): State<OrchestratorState>,
-    Path((persona_id, note_id)): Path<(Uuid, Uuid)>,
+    ValidPath((persona_id, note_id)): ValidPath<(Uuid, Uuid)>,
 ) -> Result<StatusCode, AppError> {
     state
         .orchestrator
@@ -705,7 +706,7 @@ pub async fn remove_note(
 /// POST /api/personas/:persona_id/decisions/:decision_id
 pub async fn add_decision(
     State(state): State<OrchestratorState>,
-    Path((persona_id, decision_id)): Path<(Uuid, Uuid)>,
+    ValidPath((persona_id, decision_id)): ValidPath<(Uuid, Uuid)>,
     Json(body): Json<AddWeightedBody>,
 ) -> Result<StatusCode, AppError> {
     let weight = body.weight.clamp(0.0, 1.0);
@@ -730,7 +731,7 @@ pub async fn add_decision(
 /// DELETE /api/personas/:persona_id/decisions/:decision_id
 pub async fn remove_decision(
     State(state): State<OrchestratorState>,
-    Path((persona_id, decision_id)): Path<(Uuid, Uuid)>,
+    ValidPath((persona_id, decision_id)): ValidPath<(Uuid, Uuid)>,
 ) -> Result<StatusCode, AppError> {
     state
         .orchestrator
@@ -757,7 +758,7 @@ pub async fn remove_decision(
 /// POST /api/personas/:persona_id/extends/:parent_persona_id
 pub async fn add_extends(
     State(state): State<OrchestratorState>,
-    Path((persona_id, parent_persona_id)): Path<(Uuid, Uuid)>,
+    ValidPath((persona_id, parent_persona_id)): ValidPath<(Uuid, Uuid)>,
 ) -> Result<StatusCode, AppError> {
     if persona_id == parent_persona_id {
         return Err(AppError::BadRequest(
@@ -785,7 +786,7 @@ pub async fn add_extends(
 /// DELETE /api/personas/:persona_id/extends/:parent_persona_id
 pub async fn remove_extends(
     State(state): State<OrchestratorState>,
-    Path((persona_id, parent_persona_id)): Path<(Uuid, Uuid)>,
+    ValidPath((persona_id, parent_persona_id)): ValidPath<(Uuid, Uuid)>,
 ) -> Result<StatusCode, AppError> {
     state
         .orchestrator
@@ -814,7 +815,7 @@ pub async fn remove_extends(
 /// GET /api/personas/:id/subgraph
 pub async fn get_subgraph(
     State(state): State<OrchestratorState>,
-    Path(persona_id): Path<Uuid>,
+    ValidPath(persona_id): ValidPath<Uuid>,
 ) -> Result<Json<PersonaSubgraph>, AppError> {
     let subgraph = state
         .orchestrator
@@ -949,7 +950,7 @@ pub struct AutoBuildPersonaBody {
 /// into a portable package. Files/functions are project-specific and NOT included.
 pub async fn export_persona(
     State(state): State<OrchestratorState>,
-    Path(persona_id): Path<Uuid>,
+    ValidPath(persona_id): ValidPath<Uuid>,
     Query(query): Query<ExportPersonaQuery>,
 ) -> Result<Json<PersonaPackage>, AppError> {
     let neo4j = state.orchestrator.neo4j();
@@ -1230,7 +1231,7 @@ pub struct PersonaActivation {
 /// plus metadata about the persona's scope.
 pub async fn activate_persona(
     State(state): State<OrchestratorState>,
-    Path(persona_id): Path<Uuid>,
+    ValidPath(persona_id): ValidPath<Uuid>,
 ) -> Result<Json<PersonaActivation>, AppError> {
     let neo4j = state.orchestrator.neo4j();
 
@@ -1995,7 +1996,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -2034,7 +2038,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -2102,7 +2109,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -3190,7 +3200,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -0,0 +1,309 @@
+//! Presence — who is viewing or editing which plan or note.
+//!
+//! Browser clients report where they are over `/ws/presence` (see
+//! [`super::ws_presence_handler`]); every presence connection receives the
+//! changes of all participants. Agents of the active plan run appear as
+//! participants editing the plan, with the task they work on as cursor.
+//!
+//! Presence is kept in memory by each server instance and is lost on restart.
+
+use super::handlers::{AppError, OrchestratorState};
+use crate::runner::RunnerState;
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::{broadcast, RwLock};
+use uuid::Uuid;
+
+/// Capacity of the presence change channel
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// Kind of entity a participant can be on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PresenceEntityType {
+    Plan,
+    Note,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PresenceMode {
+    Viewing,
+    Editing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParticipantKind {
+    User,
+    Agent,
+}
+
+/// Where a participant is
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PresenceEntry {
+    /// Presence connection (agents: their chat session, or task without one)
+    pub connection_id: Uuid,
+    pub user_id: String,
+    pub name: String,
+    pub kind: ParticipantKind,
+    pub entity_type: PresenceEntityType,
+    pub entity_id: Uuid,
+    pub mode: PresenceMode,
+    /// Position within the entity, set by the client (e.g. the task and field
+    /// being edited). Opaque to the server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<serde_json::Value>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PresenceEntry {
+    /// Same participant at the same place (ignores `updated_at`)
+    fn same_place(&self, other: &PresenceEntry) -> bool {
+        self.entity_type == other.entity_type
+            && self.entity_id == other.entity_id
+            && self.mode == other.mode
+            && self.cursor == other.cursor
+            && self.name == other.name
+    }
+}
+
+/// Change of presence, broadcast to the presence connections
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PresenceEvent {
+    /// A participant arrived somewhere or moved
+    Update { entry: PresenceEntry },
+    /// A participant left
+    Left { connection_id: Uuid },
+}
+
+/// In-memory presence of the participants connected to this instance.
+pub struct PresenceTracker {
+    entries: RwLock<HashMap<Uuid, PresenceEntry>>,
+    events: broadcast::Sender<PresenceEvent>,
+}
+
+impl Default for PresenceTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PresenceTracker {
+    pub fn new() -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            events: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+        }
+    }
+
+    /// Receive the presence changes
+    pub fn subscribe(&self) -> broadcast::Receiver<PresenceEvent> {
+        self.events.subscribe()
+    }
+
+    /// Set where a connection is, replacing its previous place.
+    pub async fn set(&self, entry: PresenceEntry) {
+        self.entries
+            .write()
+            .await
+            .insert(entry.connection_id, entry.clone());
+        // No receiver is not an error: nobody is watching
+        let _ = self.events.send(PresenceEvent::Update { entry });
+    }
+
+    /// Remove a connection's presence (disconnected, or no longer on a plan
+    /// or note).
+    pub async fn remove(&self, connection_id: Uuid) {
+        if self.entries.write().await.remove(&connection_id).is_some() {
+            let _ = self.events.send(PresenceEvent::Left { connection_id });
+        }
+    }
+
+    /// All participants, least recently updated first
+    pub async fn list(&self) -> Vec<PresenceEntry> {
+        let mut entries: Vec<PresenceEntry> = self.entries.read().await.values().cloned().collect();
+        entries.sort_by(|a, b| a.updated_at.cmp(&b.updated_at));
+        entries
+    }
+
+    /// Replace the agent participants, broadcasting only what changed.
+    pub async fn sync_agents(&self, agents: Vec<PresenceEntry>) {
+        let mut entries = self.entries.write().await;
+        let current: HashMap<Uuid, PresenceEntry> =
+            agents.into_iter().map(|a| (a.connection_id, a)).collect();
+
+        let gone: Vec<Uuid> = entries
+            .values()
+            .filter(|e| e.kind == ParticipantKind::Agent && !current.contains_key(&e.connection_id))
+            .map(|e| e.connection_id)
+            .collect();
+        for connection_id in gone {
+            entries.remove(&connection_id);
+            let _ = self.events.send(PresenceEvent::Left { connection_id });
+        }
+
+        for (connection_id, agent) in current {
+            if entries
+                .get(&connection_id)
+                .is_some_and(|known| known.same_place(&agent))
+            {
+                continue;
+            }
+            entries.insert(connection_id, agent.clone());
+            let _ = self.events.send(PresenceEvent::Update { entry: agent });
+        }
+    }
+}
+
+/// Agents of the active plan run, as participants editing the plan.
+pub fn agent_presence(state: Option<&RunnerState>) -> Vec<PresenceEntry> {
+    let Some(state) = state.filter(|s| s.completed_at.is_none()) else {
+        return vec![];
+    };
+    state
+        .active_agents
+        .iter()
+        .map(|agent| PresenceEntry {
+            connection_id: agent.session_id.unwrap_or(agent.task_id),
+            user_id: agent
+                .session_id
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            name: agent.task_title.clone(),
+            kind: ParticipantKind::Agent,
+            entity_type: PresenceEntityType::Plan,
+            entity_id: state.plan_id,
+            mode: PresenceMode::Editing,
+            cursor: Some(serde_json::json!({ "task_id": agent.task_id })),
+            updated_at: agent.started_at,
+        })
+        .collect()
+}
+
+/// Refresh the agent participants from the runner state.
+pub async fn refresh_agents(tracker: &PresenceTracker) {
+    let agents = agent_presence(crate::runner::RUNNER_STATE.read().await.as_ref());
+    tracker.sync_agents(agents).await;
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct PresenceQuery {
+    pub entity_type: Option<PresenceEntityType>,
+    pub entity_id: Option<Uuid>,
+}
+
+/// GET /api/presence — Participants, optionally only those on one plan or note
+pub async fn list_presence(
+    State(state): State<OrchestratorState>,
+    Query(query): Query<PresenceQuery>,
+) -> Result<Json<Vec<PresenceEntry>>, AppError> {
+    if query.entity_id.is_some() && query.entity_type.is_none() {
+        return Err(AppError::BadRequest(
+            "entity_type is required with entity_id".to_string(),
+        ));
+    }
+    refresh_agents(&state.presence).await;
+    let entries = state
+        .presence
+        .list()
+        .await
+        .into_iter()
+        .filter(|e| query.entity_type.is_none_or(|t| e.entity_type == t))
+        .filter(|e| query.entity_id.is_none_or(|id| e.entity_id == id))
+        .collect();
+    Ok(Json(entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(connection_id: Uuid, kind: ParticipantKind, plan_id: Uuid) -> PresenceEntry {
+        PresenceEntry {
+            connection_id,
+            user_id: "user-1".to_string(),
+            name: "Alice".to_string(),
+            kind,
+            entity_type: PresenceEntityType::Plan,
+            entity_id: plan_id,
+            mode: PresenceMode::Viewing,
+            cursor: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_and_remove_broadcast() {
+        let tracker = PresenceTracker::new();
+        let mut rx = tracker.subscribe();
+        let connection_id = Uuid::new_v4();
+        let alice = entry(connection_id, ParticipantKind::User, Uuid::new_v4());
+
+        tracker.set(alice.clone()).await;
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            PresenceEvent::Update {
+                entry: alice.clone()
+            }
+        );
+        assert_eq!(tracker.list().await, vec![alice]);
+
+        tracker.remove(connection_id).await;
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            PresenceEvent::Left { connection_id }
+        );
+        // Removing again broadcasts nothing
+        tracker.remove(connection_id).await;
+        assert!(rx.try_recv().is_err());
+        assert!(tracker.list().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sync_agents_broadcasts_changes_only() {
+        let tracker = PresenceTracker::new();
+        let plan_id = Uuid::new_v4();
+        let user = entry(Uuid::new_v4(), ParticipantKind::User, plan_id);
+        tracker.set(user.clone()).await;
+        let mut rx = tracker.subscribe();
+
+        let agent = entry(Uuid::new_v4(), ParticipantKind::Agent, plan_id);
+        tracker.sync_agents(vec![agent.clone()]).await;
+        assert!(matches!(rx.try_recv(), Ok(PresenceEvent::Update { .. })));
+
+        // Unchanged agents are not re-broadcast
+        let mut same = agent.clone();
+        same.updated_at = Utc::now();
+        tracker.sync_agents(vec![same]).await;
+        assert!(rx.try_recv().is_err());
+
+        // Finished agents leave; users are untouched
+        tracker.sync_agents(vec![]).await;
+        assert_eq!(
+            rx.try_recv().unwrap(),
+            PresenceEvent::Left {
+                connection_id: agent.connection_id
+            }
+        );
+        assert_eq!(tracker.list().await, vec![user]);
+    }
+
+    #[test]
+    fn test_event_serialization() {
+        let connection_id = Uuid::new_v4();
+        let json = serde_json::to_value(PresenceEvent::Left { connection_id }).unwrap();
+        assert_eq!(json["type"], "left");
+        assert_eq!(json["connection_id"], connection_id.to_string());
+    }
+}
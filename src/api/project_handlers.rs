@@ -2,7 +2,14 @@
 
 use crate::api::{PaginatedResponse, PaginationParams, SearchFilter};
 use crate::events::{EntityType, EventEmitter};
-use crate::neo4j::models::ProjectNode;
+use crate::neo4j::models::{
+    BudgetCheckReport, LanguageStats, ProjectBudgets, ProjectNode, SyncLimits,
+};
+use crate::orchestrator::adr::{self, AdrExportReport};
+use crate::orchestrator::commit_message::{self, CommitSuggestion};
+use crate::orchestrator::context_bundle;
+use crate::orchestrator::diff_sync::{self, DiffSyncReport};
+use crate::orchestrator::review::{self, ReviewReport};
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
@@ -18,6 +25,11 @@ use super::graph_types::{
     parse_layers, GraphQuery, IntelligenceSummaryResponse, ProjectGraphResponse,
 };
 use super::handlers::{AppError, OrchestratorState};
+use super::search_analytics_handlers::record_search;
+use super::validation::{
+    ValidJson, ValidPath, ValidQuery, Validate, Validator, MAX_PATH_LEN, MAX_TEXT_LEN,
+    MAX_TITLE_LEN,
+};
 
 // ============================================================================
 // Request/Response types
@@ -31,6 +43,19 @@ pub struct CreateProjectRequest {
     pub description: Option<String>,
 }
 
+impl Validate for CreateProjectRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.text("name", &self.name, MAX_TITLE_LEN);
+        if let Some(ref slug) = self.slug {
+            v.text("slug", slug, MAX_TITLE_LEN);
+        }
+        v.text("root_path", &self.root_path, MAX_PATH_LEN);
+        if let Some(ref description) = self.description {
+            v.max_len("description", description, MAX_TEXT_LEN);
+        }
+    }
+}
+
 #[derive(Serialize)]
 pub struct ProjectResponse {
     pub id: String,
@@ -42,6 +67,15 @@ pub struct ProjectResponse {
     pub last_synced: Option<String>,
     pub file_count: usize,
     pub plan_count: usize,
+    pub archived: bool,
+    /// Files and lines per language as of the last sync, most lines first.
+    /// Only returned by the project detail endpoint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub languages: Option<Vec<LanguageStats>>,
+    /// Total lines of the project's files as of the last sync.
+    /// Only returned by the project detail endpoint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_count: Option<u64>,
 }
 
 #[derive(Serialize)]
@@ -61,6 +95,9 @@ pub struct ProjectsListQuery {
     pub pagination: PaginationParams,
     #[serde(flatten)]
     pub search_filter: SearchFilter,
+    /// Also list archived projects
+    #[serde(default, deserialize_with = "super::query::deserialize_from_str")]
+    pub include_archived: bool,
 }
 
 /// List all projects with optional pagination and search
@@ -79,6 +116,7 @@ pub async fn list_projects(
             query.pagination.offset,
             query.pagination.sort_by.as_deref(),
             &query.pagination.sort_order,
+            query.include_archived,
         )
         .await?;
 
@@ -107,6 +145,9 @@ pub async fn list_projects(
             last_synced: project.last_synced.map(|dt| dt.to_rfc3339()),
             file_count: file_count as usize,
             plan_count: plan_count as usize,
+            archived: project.archived,
+            languages: None,
+            line_count: None,
         });
     }
 
@@ -121,7 +162,7 @@ pub async fn list_projects(
 /// Create a new project
 pub async fn create_project(
     State(state): State<OrchestratorState>,
-    Json(req): Json<CreateProjectRequest>,
+    ValidJson(req): ValidJson<CreateProjectRequest>,
 ) -> Result<Json<ProjectResponse>, AppError> {
     // Generate slug from name if not provided
     let slug = req.slug.unwrap_or_else(|| slugify(&req.name));
@@ -139,6 +180,7 @@ pub async fn create_project(
             slug
         )));
     }
+    super::quota_handlers::check_project_quota(&state, None).await?;
 
     let project = ProjectNode {
         id: Uuid::new_v4(),
@@ -154,6 +196,7 @@ pub async fn create_project(
         scaffolding_override: None,
         sharing_policy: None,
         watch_enabled: true,
+        archived: false,
     };
 
     state.orchestrator.create_project(&project).await?;
@@ -186,6 +229,9 @@ pub async fn create_project(
         last_synced: None,
         file_count: 0,
         plan_count: 0,
+        archived: false,
+        languages: None,
+        line_count: None,
     }))
 }
 
@@ -213,6 +259,12 @@ pub async fn get_project(
         .count_project_plans(project.id)
         .await
         .unwrap_or(0);
+    let languages = state
+        .orchestrator
+        .neo4j()
+        .get_project_language_stats(project.id)
+        .await?;
+    let line_count = languages.iter().map(|l| l.lines).sum();
 
     Ok(Json(ProjectResponse {
         id: project.id.to_string(),
@@ -224,6 +276,9 @@ pub async fn get_project(
         last_synced: project.last_synced.map(|dt| dt.to_rfc3339()),
         file_count: file_count as usize,
         plan_count: plan_count as usize,
+        archived: project.archived,
+        languages: Some(languages),
+        line_count: Some(line_count),
     }))
 }
 
@@ -235,11 +290,25 @@ pub struct UpdateProjectRequest {
     pub root_path: Option<String>,
 }
 
+impl Validate for UpdateProjectRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(ref name) = self.name {
+            v.text("name", name, MAX_TITLE_LEN);
+        }
+        if let Some(Some(ref description)) = self.description {
+            v.max_len("description", description, MAX_TEXT_LEN);
+        }
+        if let Some(ref root_path) = self.root_path {
+            v.text("root_path", root_path, MAX_PATH_LEN);
+        }
+    }
+}
+
 /// Update a project
 pub async fn update_project(
     State(state): State<OrchestratorState>,
     Path(slug): Path<String>,
-    Json(req): Json<UpdateProjectRequest>,
+    ValidJson(req): ValidJson<UpdateProjectRequest>,
 ) -> Result<StatusCode, AppError> {
     let project = state
         .orchestrator
@@ -278,6 +347,47 @@ pub async fn delete_project(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Archive a project: it is no longer watched, analyzed by the periodic
+/// checks, searched by default or listed by default
+pub async fn archive_project(
+    State(state): State<OrchestratorState>,
+    Path(slug): Path<String>,
+) -> Result<StatusCode, AppError> {
+    set_archived(&state, &slug, true).await
+}
+
+/// Unarchive a project (watched again if watching was enabled)
+pub async fn unarchive_project(
+    State(state): State<OrchestratorState>,
+    Path(slug): Path<String>,
+) -> Result<StatusCode, AppError> {
+    set_archived(&state, &slug, false).await
+}
+
+async fn set_archived(
+    state: &OrchestratorState,
+    slug: &str,
+    archived: bool,
+) -> Result<StatusCode, AppError> {
+    let project = state
+        .orchestrator
+        .neo4j()
+        .get_project_by_slug(slug)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project '{}' not found", slug)))?;
+
+    // The ProjectWatcherBridge (un)registers the project on the file watcher
+    // on the CrudEvent::Updated emitted by the orchestrator.
+    if project.archived != archived {
+        state
+            .orchestrator
+            .set_project_archived(project.id, archived)
+            .await?;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// Sync a project's codebase
 #[derive(Serialize)]
 pub struct SyncProjectResponse {
@@ -286,6 +396,12 @@ pub struct SyncProjectResponse {
     pub files_deleted: usize,
     pub symbols_deleted: usize,
     pub errors: usize,
+    pub documents_synced: usize,
+    pub documents_deleted: usize,
+    /// Files left unparsed by the project's sync limits
+    pub skipped_files: Vec<crate::orchestrator::sync_limits::SkippedFile>,
+    /// Unchanged files whose stale search document was re-indexed
+    pub files_reindexed: usize,
 }
 
 /// Query parameters for sync_project
@@ -305,6 +421,7 @@ pub async fn sync_project(
         .get_project_by_slug(&slug)
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Project '{}' not found", slug)))?;
+    super::quota_handlers::check_index_quota(&state, &project).await?;
 
     let is_first_sync = project.last_synced.is_none();
     let force = query.force.unwrap_or(false);
@@ -347,6 +464,26 @@ pub async fn sync_project(
     // Refresh auto-built feature graphs in background (best-effort)
     state.orchestrator.spawn_refresh_feature_graphs(project.id);
 
+    // Link imports to workspace resources owned by other projects (best-effort)
+    state.orchestrator.spawn_link_resource_symbols(project.id);
+
+    // Record locked third-party packages for the vulnerability audit (best-effort)
+    state.orchestrator.spawn_ingest_packages(project.id);
+
+    // Link the files to their CODEOWNERS owners (best-effort)
+    state.orchestrator.spawn_ingest_code_owners(project.id);
+
+    // Summarize the directories whose files changed (best-effort)
+    state
+        .orchestrator
+        .spawn_refresh_module_summaries(project.id);
+
+    // Check the complexity and size budgets (best-effort)
+    state.orchestrator.spawn_check_budgets(project.id);
+
+    // Snapshot the public API surface when it changed (best-effort)
+    state.orchestrator.spawn_record_api_surface(project.id);
+
     // Spawn event-triggered protocol runs (post_sync)
     crate::protocol::hooks::spawn_event_triggered_protocols(
         state.orchestrator.neo4j_arc(),
@@ -361,6 +498,10 @@ pub async fn sync_project(
         files_deleted: result.files_deleted,
         symbols_deleted: result.symbols_deleted,
         errors: result.errors,
+        documents_synced: result.documents_synced,
+        documents_deleted: result.documents_deleted,
+        skipped_files: result.skipped_files,
+        files_reindexed: result.files_reindexed,
     }))
 }
 
@@ -410,6 +551,7 @@ pub struct ProjectCodeSearchQuery {
 
 pub async fn search_project_code(
     State(state): State<OrchestratorState>,
+    headers: axum::http::HeaderMap,
     Path(slug): Path<String>,
     axum::extract::Query(query): axum::extract::Query<ProjectCodeSearchQuery>,
 ) -> Result<Json<Vec<crate::meilisearch::indexes::CodeDocument>>, AppError> {
@@ -432,6 +574,14 @@ pub async fn search_project_code(
         )
         .await?;
 
+    record_search(
+        &state,
+        &headers,
+        "code",
+        &query.q,
+        Some(&slug),
+        results.len(),
+    );
     Ok(Json(results))
 }
 
@@ -764,195 +914,3175 @@ pub async fn get_embeddings_projection(
 }
 
 // ============================================================================
-// Utilities
+// Vulnerability audit
 // ============================================================================
 
-/// Convert a name to a URL-safe slug
-fn slugify(name: &str) -> String {
-    name.to_lowercase()
-        .chars()
-        .map(|c| if c.is_alphanumeric() { c } else { '-' })
-        .collect::<String>()
-        .split('-')
-        .filter(|s| !s.is_empty())
-        .collect::<Vec<_>>()
-        .join("-")
+/// Query parameters for the advisories endpoint
+#[derive(Debug, Deserialize)]
+pub struct ProjectAdvisoriesQuery {
+    /// Only advisories at or above this severity (`low`, `medium`, `high`, `critical`)
+    pub min_severity: Option<String>,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::sync::Arc;
-
-    #[test]
-    fn test_slugify() {
-        assert_eq!(slugify("My Project"), "my-project");
-        assert_eq!(slugify("Test  Project!"), "test-project");
-        assert_eq!(slugify("embryon-neural"), "embryon-neural");
-        assert_eq!(slugify("Project 123"), "project-123");
-    }
+/// Security advisories affecting the packages locked by a project
+#[derive(Serialize)]
+pub struct ProjectAdvisoriesResponse {
+    pub project_id: Uuid,
+    /// Locked packages recorded at the last sync
+    pub packages_count: usize,
+    /// Most severe first
+    pub advisories: Vec<crate::neo4j::models::ProjectAdvisory>,
+}
 
-    #[test]
-    fn test_update_project_request_all_fields() {
-        let json = r#"{"name":"New Name","description":"New desc","root_path":"/new"}"#;
-        let req: UpdateProjectRequest = serde_json::from_str(json).unwrap();
-        assert_eq!(req.name, Some("New Name".to_string()));
-        assert_eq!(req.description, Some(Some("New desc".to_string())));
-        assert_eq!(req.root_path, Some("/new".to_string()));
-    }
+/// List the advisories found by the last vulnerability audit of a project
+pub async fn list_project_advisories(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+    Query(query): Query<ProjectAdvisoriesQuery>,
+) -> Result<Json<ProjectAdvisoriesResponse>, AppError> {
+    let min_severity = query
+        .min_severity
+        .as_deref()
+        .map(str::parse::<crate::neo4j::models::AdvisorySeverity>)
+        .transpose()
+        .map_err(AppError::BadRequest)?;
 
-    #[test]
-    fn test_update_project_request_empty() {
-        let json = r#"{}"#;
-        let req: UpdateProjectRequest = serde_json::from_str(json).unwrap();
-        assert_eq!(req.name, None);
-        assert_eq!(req.description, None);
-        assert_eq!(req.root_path, None);
-    }
+    let neo4j = state.orchestrator.neo4j();
+    neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
 
-    #[test]
-    fn test_update_project_request_null_description() {
-        // With default serde, null on Option<Option<String>> deserializes to None (absent)
-        // To distinguish "field absent" from "field = null", a custom deserializer is needed
-        let json = r#"{"description":null}"#;
-        let req: UpdateProjectRequest = serde_json::from_str(json).unwrap();
-        assert_eq!(req.description, None);
+    let packages_count = neo4j.list_project_packages(project_id).await?.len();
+    let mut advisories = neo4j.list_project_advisories(project_id).await?;
+    if let Some(min) = min_severity {
+        advisories.retain(|a| a.advisory.severity >= min);
     }
 
-    #[test]
-    fn test_update_project_request_explicit_description() {
-        // Explicit string value -> Some(Some("..."))
-        let json = r#"{"description":"hello"}"#;
-        let req: UpdateProjectRequest = serde_json::from_str(json).unwrap();
-        assert_eq!(req.description, Some(Some("hello".to_string())));
-    }
+    Ok(Json(ProjectAdvisoriesResponse {
+        project_id,
+        packages_count,
+        advisories,
+    }))
+}
 
-    #[test]
-    fn test_update_project_request_only_name() {
-        let json = r#"{"name":"Renamed"}"#;
-        let req: UpdateProjectRequest = serde_json::from_str(json).unwrap();
-        assert_eq!(req.name, Some("Renamed".to_string()));
-        assert_eq!(req.description, None);
-        assert_eq!(req.root_path, None);
-    }
+// ============================================================================
+// Documentation
+// ============================================================================
 
-    // ====================================================================
-    // Handler integration tests (axum + mock backends)
-    // ====================================================================
+/// Markdown documents indexed for a project
+#[derive(Serialize)]
+pub struct ProjectDocumentsResponse {
+    pub project_id: Uuid,
+    /// Ordered by path
+    pub documents: Vec<crate::neo4j::models::DocumentNode>,
+}
 
-    use axum::{
-        body::Body,
-        http::{Request, StatusCode as AxumStatus},
-    };
-    use tower::ServiceExt;
+/// List the Markdown documents indexed at the last sync of a project
+pub async fn list_project_documents(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+) -> Result<Json<ProjectDocumentsResponse>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
 
-    use crate::api::handlers::ServerState;
-    use crate::api::routes::create_router;
-    use crate::neo4j::models::FileNode;
-    use crate::orchestrator::watcher::FileWatcher;
-    use crate::orchestrator::Orchestrator;
-    use crate::test_helpers::{
-        mock_app_state, test_auth_config, test_bearer_token, test_project_named,
-    };
+    let documents = neo4j.list_project_documents(project_id).await?;
+    Ok(Json(ProjectDocumentsResponse {
+        project_id,
+        documents,
+    }))
+}
 
-    /// Build a mock OrchestratorState for project handler tests
-    async fn mock_server_state() -> super::OrchestratorState {
-        let app_state = mock_app_state();
-        let orchestrator = std::sync::Arc::new(Orchestrator::new(app_state).await.unwrap());
-        let watcher = std::sync::Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
-            orchestrator.clone(),
-        )));
-        std::sync::Arc::new(ServerState {
-            orchestrator,
-            watcher,
-            chat_manager: None,
-            event_bus: std::sync::Arc::new(crate::events::HybridEmitter::new(std::sync::Arc::new(
-                crate::events::EventBus::default(),
-            ))),
-            nats_emitter: None,
-            auth_config: Some(test_auth_config()),
-            serve_frontend: false,
-            frontend_path: "./dist".to_string(),
-            setup_completed: true,
-            server_port: 6600,
-            public_url: None,
-            ws_ticket_store: std::sync::Arc::new(crate::api::ws_auth::WsTicketStore::new()),
-            registry_remote_url: None,
-            oidc_client: None,
-            neural_router: crate::test_helpers::mock_neural_router(),
-            trajectory_collector: std::sync::RwLock::new(None),
-            trajectory_store_neo4j: None,
-            trajectory_store: None,
-            identity: None,
-            reactor_counters: std::sync::OnceLock::new(),
-            confidence_tracker: Arc::new(crate::graph::confidence::ConfidenceTracker::default()),
-            mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
-            model_catalog: crate::chat::model_catalog::ModelCatalogCache::new(None),
-        })
-    }
+// ============================================================================
+// Database schema
+// ============================================================================
 
-    fn authed_get(uri: &str) -> Request<Body> {
-        Request::builder()
-            .uri(uri)
-            .header("authorization", test_bearer_token())
-            .body(Body::empty())
-            .unwrap()
-    }
+/// SQL schema reconstructed from a project's schema and migration files
+#[derive(Serialize)]
+pub struct ProjectSchemaResponse {
+    pub project_id: Uuid,
+    /// Ordered by name, with columns in declaration order
+    pub tables: Vec<crate::neo4j::models::TableNode>,
+    /// In apply order
+    pub migrations: Vec<crate::neo4j::models::MigrationNode>,
+}
 
-    fn authed_post(uri: &str, body: serde_json::Value) -> Request<Body> {
-        Request::builder()
-            .method("POST")
-            .uri(uri)
-            .header("content-type", "application/json")
-            .header("authorization", test_bearer_token())
-            .body(Body::from(body.to_string()))
-            .unwrap()
-    }
+/// Get the tables and migrations parsed at the last sync of a project
+pub async fn get_project_schema(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+) -> Result<Json<ProjectSchemaResponse>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    let tables = neo4j.list_project_tables(project_id).await?;
+    let migrations = neo4j.list_project_migrations(project_id).await?;
+    Ok(Json(ProjectSchemaResponse {
+        project_id,
+        tables,
+        migrations,
+    }))
+}
 
-    #[tokio::test]
-    async fn test_list_projects_empty() {
-        let state = mock_server_state().await;
-        let app = create_router(state);
+// ============================================================================
+// Module summaries
+// ============================================================================
 
-        let resp = app.oneshot(authed_get("/api/projects")).await.unwrap();
-        assert_eq!(resp.status(), AxumStatus::OK);
+#[derive(Serialize)]
+pub struct ModuleSummariesResponse {
+    pub project_id: Uuid,
+    /// Ordered by path
+    pub modules: Vec<crate::neo4j::models::ModuleSummaryNode>,
+}
 
-        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
-        assert_eq!(json["total"], 0);
-        assert_eq!(json["items"].as_array().unwrap().len(), 0);
-    }
+/// List the cached per-directory summaries of a project
+pub async fn list_module_summaries(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+) -> Result<Json<ModuleSummariesResponse>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
 
-    #[tokio::test]
-    async fn test_list_projects_with_counts() {
-        let app_state = mock_app_state();
+    let modules = neo4j.list_module_summaries(project_id).await?;
+    Ok(Json(ModuleSummariesResponse {
+        project_id,
+        modules,
+    }))
+}
 
-        // Seed a project with files and plans
-        let project = test_project_named("my-proj");
-        app_state.neo4j.create_project(&project).await.unwrap();
+// ============================================================================
+// Onboarding overview
+// ============================================================================
 
-        // Add 2 files
-        for i in 0..2 {
-            let path = format!("/tmp/my-proj/file_{}.rs", i);
-            let file = FileNode {
-                path: path.clone(),
-                language: "rust".to_string(),
-                hash: format!("h{}", i),
-                last_parsed: chrono::Utc::now(),
-                project_id: Some(project.id),
-            };
-            app_state.neo4j.upsert_file(&file).await.unwrap();
-            app_state
-                .neo4j
-                .link_file_to_project(&path, project.id)
-                .await
-                .unwrap();
-        }
+/// Entries listed at most in each section of the overview
+const OVERVIEW_LIMIT: usize = 10;
 
-        // Add 1 plan (Draft)
-        let plan = crate::test_helpers::test_plan_for_project(project.id);
+#[derive(Serialize)]
+pub struct OverviewSymbol {
+    pub id: String,
+    pub name: String,
+    pub file_path: String,
+    pub pagerank: f64,
+    pub community_id: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct OverviewResource {
+    pub id: Uuid,
+    pub name: String,
+    pub resource_type: crate::neo4j::models::ResourceType,
+    pub file_path: String,
+    /// `owns`, `implements` or `uses`
+    pub role: String,
+}
+
+#[derive(Serialize)]
+pub struct OverviewPlan {
+    pub id: Uuid,
+    pub title: String,
+    pub status: crate::neo4j::models::PlanStatus,
+    pub priority: i32,
+    pub tasks_total: usize,
+    pub tasks_completed: usize,
+}
+
+#[derive(Serialize)]
+pub struct ProjectOverview {
+    pub project_id: Uuid,
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub root_path: String,
+    pub last_synced: Option<chrono::DateTime<chrono::Utc>>,
+    /// Files per language, most used first
+    pub languages: Vec<crate::neo4j::models::LanguageStatsNode>,
+    /// Largest communities first
+    pub communities: Vec<crate::neo4j::models::CommunityRow>,
+    /// Functions with the highest PageRank
+    pub top_symbols: Vec<OverviewSymbol>,
+    /// Summaries of the largest directories
+    pub modules: Vec<crate::neo4j::models::ModuleSummaryNode>,
+    /// Workspace resources the project owns, implements or uses
+    pub resources: Vec<OverviewResource>,
+    /// Plans not completed or cancelled, highest priority first
+    pub open_plans: Vec<OverviewPlan>,
+}
+
+/// Orient a fresh session in one call: languages, communities, the most
+/// central functions, module summaries, resources and open plans of a
+/// project, ten entries at most per section.
+///
+/// Communities, PageRank and module summaries come from the last sync and
+/// analytics run; they are empty for a project never synced.
+pub async fn get_project_overview(
+    State(state): State<OrchestratorState>,
+    Path(slug): Path<String>,
+) -> Result<Json<ProjectOverview>, AppError> {
+    use crate::neo4j::models::PlanStatus;
+
+    let neo4j = state.orchestrator.neo4j();
+    let project = neo4j
+        .get_project_by_slug(&slug)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project not found: {}", slug)))?;
+
+    let mut languages = neo4j.get_language_stats_for_project(project.id).await?;
+    languages.sort_by(|a, b| {
+        b.file_count
+            .cmp(&a.file_count)
+            .then_with(|| a.language.cmp(&b.language))
+    });
+
+    let mut communities = neo4j.get_project_communities(project.id).await?;
+    communities.sort_by(|a, b| {
+        b.file_count
+            .cmp(&a.file_count)
+            .then_with(|| a.community_id.cmp(&b.community_id))
+    });
+    communities.truncate(OVERVIEW_LIMIT);
+
+    let mut top_symbols: Vec<OverviewSymbol> = neo4j
+        .get_project_call_graph_functions(project.id)
+        .await?
+        .into_iter()
+        .filter_map(|f| {
+            Some(OverviewSymbol {
+                pagerank: f.pagerank?,
+                id: f.id,
+                name: f.name,
+                file_path: f.file_path,
+                community_id: f.community_id,
+            })
+        })
+        .collect();
+    top_symbols.sort_by(|a, b| {
+        b.pagerank
+            .total_cmp(&a.pagerank)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    top_symbols.truncate(OVERVIEW_LIMIT);
+
+    let mut modules = neo4j.list_module_summaries(project.id).await?;
+    modules.sort_by(|a, b| {
+        b.file_count
+            .cmp(&a.file_count)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    modules.truncate(OVERVIEW_LIMIT);
+
+    let mut resources = Vec::new();
+    if let Some(workspace) = neo4j.get_project_workspace(project.id).await? {
+        for resource in neo4j.list_workspace_resources(workspace.id).await? {
+            let role = if resource.project_id == Some(project.id) {
+                "owns"
+            } else if neo4j
+                .get_resource_implementers(resource.id)
+                .await?
+                .iter()
+                .any(|p| p.id == project.id)
+            {
+                "implements"
+            } else if neo4j
+                .get_resource_consumers(resource.id)
+                .await?
+                .iter()
+                .any(|p| p.id == project.id)
+            {
+                "uses"
+            } else {
+                continue;
+            };
+            resources.push(OverviewResource {
+                id: resource.id,
+                name: resource.name,
+                resource_type: resource.resource_type,
+                file_path: resource.file_path,
+                role: role.to_string(),
+            });
+            if resources.len() == OVERVIEW_LIMIT {
+                break;
+            }
+        }
+    }
+
+    let mut plans: Vec<_> = neo4j
+        .list_project_plans(project.id)
+        .await?
+        .into_iter()
+        .filter(|p| !matches!(p.status, PlanStatus::Completed | PlanStatus::Cancelled))
+        .collect();
+    plans.sort_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then_with(|| a.created_at.cmp(&b.created_at))
+    });
+    plans.truncate(OVERVIEW_LIMIT);
+    let mut open_plans = Vec::with_capacity(plans.len());
+    for plan in plans {
+        let tasks = neo4j.get_plan_tasks(plan.id).await?;
+        open_plans.push(OverviewPlan {
+            tasks_total: tasks.len(),
+            tasks_completed: tasks
+                .iter()
+                .filter(|t| t.status == crate::neo4j::models::TaskStatus::Completed)
+                .count(),
+            id: plan.id,
+            title: plan.title,
+            status: plan.status,
+            priority: plan.priority,
+        });
+    }
+
+    Ok(Json(ProjectOverview {
+        project_id: project.id,
+        name: project.name,
+        slug: project.slug,
+        description: project.description,
+        root_path: project.root_path,
+        last_synced: project.last_synced,
+        languages,
+        communities,
+        top_symbols,
+        modules,
+        resources,
+        open_plans,
+    }))
+}
+
+// ============================================================================
+// Sync limits
+// ============================================================================
+
+/// Get the sync limits of a project (the defaults when never set)
+pub async fn get_project_sync_limits(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+) -> Result<Json<SyncLimits>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    Ok(Json(
+        neo4j
+            .get_project_sync_limits(project_id)
+            .await?
+            .unwrap_or_default(),
+    ))
+}
+
+/// Replace the sync limits of a project. They apply from the next sync.
+pub async fn set_project_sync_limits(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+    Json(mut limits): Json<SyncLimits>,
+) -> Result<Json<SyncLimits>, AppError> {
+    if limits.max_file_bytes == 0 {
+        return Err(AppError::BadRequest(
+            "max_file_bytes must be at least 1".to_string(),
+        ));
+    }
+    let mut extensions = Vec::new();
+    for ext in &limits.excluded_extensions {
+        let ext = ext.trim().trim_start_matches('.').to_lowercase();
+        if ext.is_empty() {
+            return Err(AppError::BadRequest(
+                "excluded_extensions cannot contain empty extensions".to_string(),
+            ));
+        }
+        if !extensions.contains(&ext) {
+            extensions.push(ext);
+        }
+    }
+    limits.excluded_extensions = extensions;
+
+    let neo4j = state.orchestrator.neo4j();
+    neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    neo4j
+        .update_project_sync_limits(project_id, &limits)
+        .await?;
+    Ok(Json(limits))
+}
+
+// ============================================================================
+// Budgets
+// ============================================================================
+
+/// Get the complexity and size budgets of a project (all unset by default)
+pub async fn get_project_budgets(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+) -> Result<Json<ProjectBudgets>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    Ok(Json(
+        neo4j
+            .get_project_budgets(project_id)
+            .await?
+            .unwrap_or_default(),
+    ))
+}
+
+/// Replace the budgets of a project, then check them in the background
+pub async fn set_project_budgets(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+    Json(budgets): Json<ProjectBudgets>,
+) -> Result<Json<ProjectBudgets>, AppError> {
+    if budgets.max_function_complexity == Some(0) || budgets.max_file_lines == Some(0) {
+        return Err(AppError::BadRequest(
+            "max_function_complexity and max_file_lines must be at least 1".to_string(),
+        ));
+    }
+
+    let neo4j = state.orchestrator.neo4j();
+    neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    neo4j.update_project_budgets(project_id, &budgets).await?;
+    state.orchestrator.spawn_check_budgets(project_id);
+    Ok(Json(budgets))
+}
+
+/// Query parameters for the budget check
+#[derive(Debug, Default, Deserialize)]
+pub struct BudgetCheckQuery {
+    /// Return the report of the last sync instead of checking now
+    /// (falls back to checking when there is none)
+    #[serde(default)]
+    pub cached: bool,
+    /// Answer 422 instead of 200 when a budget is exceeded, for CI gates
+    #[serde(default)]
+    pub fail_on_violation: bool,
+}
+
+/// Check a project against its budgets: pass/fail with every violation.
+///
+/// A project without budgets always passes.
+pub async fn check_project_budgets(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+    Query(query): Query<BudgetCheckQuery>,
+) -> Result<(StatusCode, Json<BudgetCheckReport>), AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let project = neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    let cached = if query.cached {
+        neo4j.get_budget_report(project_id).await?
+    } else {
+        None
+    };
+    let report = match cached {
+        Some(report) => report,
+        None => {
+            let budgets = neo4j
+                .get_project_budgets(project_id)
+                .await?
+                .unwrap_or_default();
+            let report =
+                crate::orchestrator::budgets::check_budgets(neo4j, &project, &budgets).await?;
+            if !budgets.is_empty() {
+                neo4j.save_budget_report(&report).await?;
+            }
+            report
+        }
+    };
+
+    let status = if query.fail_on_violation && !report.passed {
+        StatusCode::UNPROCESSABLE_ENTITY
+    } else {
+        StatusCode::OK
+    };
+    Ok((status, Json(report)))
+}
+
+// ============================================================================
+// Public API surface
+// ============================================================================
+
+#[derive(Serialize)]
+pub struct ApiSurfaceResponse {
+    pub project_id: Uuid,
+    pub symbol_count: usize,
+    /// Ordered by module, then by symbol name
+    pub modules: Vec<crate::orchestrator::api_surface::ApiModule>,
+}
+
+/// The public symbols of a project as currently synced, grouped by module
+pub async fn get_project_api_surface(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+) -> Result<Json<ApiSurfaceResponse>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let project = neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    let symbols = crate::orchestrator::api_surface::current_surface(neo4j, &project).await?;
+    Ok(Json(ApiSurfaceResponse {
+        project_id,
+        symbol_count: symbols.len(),
+        modules: crate::orchestrator::api_surface::group_by_module(symbols),
+    }))
+}
+
+#[derive(Serialize)]
+pub struct ApiSurfaceSnapshotsResponse {
+    pub project_id: Uuid,
+    /// Newest first, without their symbols
+    pub snapshots: Vec<crate::neo4j::models::ApiSurfaceSnapshotNode>,
+}
+
+/// List the API surface snapshots recorded after syncs
+pub async fn list_api_surface_snapshots(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+) -> Result<Json<ApiSurfaceSnapshotsResponse>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    let snapshots = neo4j.list_api_surface_snapshots(project_id).await?;
+    Ok(Json(ApiSurfaceSnapshotsResponse {
+        project_id,
+        snapshots,
+    }))
+}
+
+/// Query parameters for an API surface diff
+#[derive(Debug, Deserialize)]
+pub struct ApiSurfaceDiffQuery {
+    /// Snapshot id or commit SHA of the older surface
+    pub from: String,
+    /// Snapshot id or commit SHA of the newer surface (default: the current
+    /// graph)
+    pub to: Option<String>,
+    /// Answer 422 instead of 200 when the change is breaking, for CI gates
+    #[serde(default)]
+    pub fail_on_breaking: bool,
+}
+
+#[derive(Serialize)]
+pub struct ApiSurfaceDiffResponse {
+    pub project_id: Uuid,
+    pub from: crate::neo4j::models::ApiSurfaceSnapshotNode,
+    /// None when compared with the current graph
+    pub to: Option<crate::neo4j::models::ApiSurfaceSnapshotNode>,
+    #[serde(flatten)]
+    pub diff: crate::orchestrator::api_surface::ApiSurfaceDiff,
+}
+
+/// Diff the surface of snapshot `from` with snapshot `to`, or with the
+/// current graph. Unknown snapshots are a 404.
+async fn compare_api_surfaces(
+    neo4j: &dyn crate::neo4j::GraphStore,
+    project: &crate::neo4j::models::ProjectNode,
+    from: &str,
+    to: Option<&str>,
+) -> Result<
+    (
+        crate::neo4j::models::ApiSurfaceSnapshotNode,
+        Option<crate::neo4j::models::ApiSurfaceSnapshotNode>,
+        crate::orchestrator::api_surface::ApiSurfaceDiff,
+    ),
+    AppError,
+> {
+    use crate::orchestrator::api_surface::{current_surface, diff_surfaces, find_snapshot};
+
+    let snapshot = |reference: &str| {
+        let reference = reference.to_string();
+        async move {
+            find_snapshot(neo4j, project, &reference)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("API surface snapshot {} not found", reference))
+                })
+        }
+    };
+    let mut from = snapshot(from).await?;
+    let (to, after) = match to {
+        Some(reference) => {
+            let mut to = snapshot(reference).await?;
+            let symbols = std::mem::take(&mut to.symbols);
+            (Some(to), symbols)
+        }
+        None => (None, current_surface(neo4j, project).await?),
+    };
+    let before = std::mem::take(&mut from.symbols);
+    Ok((from, to, diff_surfaces(&before, &after)))
+}
+
+/// Compare the public API surface between two sync points: added and
+/// removed symbols and changed signatures.
+pub async fn diff_api_surface(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+    Query(query): Query<ApiSurfaceDiffQuery>,
+) -> Result<(StatusCode, Json<ApiSurfaceDiffResponse>), AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let project = neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    let (from, to, diff) =
+        compare_api_surfaces(neo4j, &project, &query.from, query.to.as_deref()).await?;
+    let status = if query.fail_on_breaking && diff.breaking {
+        StatusCode::UNPROCESSABLE_ENTITY
+    } else {
+        StatusCode::OK
+    };
+    Ok((
+        status,
+        Json(ApiSurfaceDiffResponse {
+            project_id,
+            from,
+            to,
+            diff,
+        }),
+    ))
+}
+
+/// Query parameters for semver advice
+#[derive(Debug, Deserialize)]
+pub struct SemverAdviceQuery {
+    /// Snapshot id, commit SHA, branch or tag of the last release
+    pub from: String,
+    /// Snapshot id, commit SHA, branch or tag of the release candidate
+    /// (default: the current graph)
+    pub to: Option<String>,
+    /// Version to bump (default: the version of the project manifest)
+    pub version: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct SemverAdviceResponse {
+    pub project_id: Uuid,
+    pub from: crate::neo4j::models::ApiSurfaceSnapshotNode,
+    pub to: Option<crate::neo4j::models::ApiSurfaceSnapshotNode>,
+    #[serde(flatten)]
+    pub advice: crate::orchestrator::semver_advice::SemverAdvice,
+    #[serde(flatten)]
+    pub diff: crate::orchestrator::api_surface::ApiSurfaceDiff,
+}
+
+/// Classify the public API changes between two sync points as a major,
+/// minor or patch release, and suggest the next version.
+pub async fn get_semver_advice(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+    Query(query): Query<SemverAdviceQuery>,
+) -> Result<Json<SemverAdviceResponse>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let project = neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    let (from, to, diff) =
+        compare_api_surfaces(neo4j, &project, &query.from, query.to.as_deref()).await?;
+    let root = crate::orchestrator::project_roots(&project.root_path)
+        .into_iter()
+        .next();
+    let advice = crate::orchestrator::semver_advice::advise(
+        &diff,
+        root.as_deref().map(std::path::Path::new),
+        query.version.as_deref(),
+    );
+    Ok(Json(SemverAdviceResponse {
+        project_id,
+        from,
+        to,
+        advice,
+        diff,
+    }))
+}
+
+// ============================================================================
+// Test coverage
+// ============================================================================
+
+/// Largest coverage report accepted
+pub const MAX_COVERAGE_REPORT_BYTES: usize = 32 * 1024 * 1024;
+
+/// Query parameters for a coverage upload
+#[derive(Debug, Default, Deserialize)]
+pub struct CoverageQuery {
+    /// `lcov` or `cobertura` (default: detected from the report)
+    pub format: Option<crate::orchestrator::coverage::CoverageFormat>,
+}
+
+#[derive(Serialize)]
+pub struct CoverageResponse {
+    pub project_id: Uuid,
+    #[serde(flatten)]
+    pub ingestion: crate::orchestrator::coverage::CoverageIngestion,
+}
+
+/// Ingest an LCOV or Cobertura report (raw request body): the coverage of
+/// every function of the files it covers is stored as `coverage_pct`.
+pub async fn ingest_project_coverage(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+    Query(query): Query<CoverageQuery>,
+    body: axum::body::Bytes,
+) -> Result<Json<CoverageResponse>, AppError> {
+    use crate::orchestrator::coverage::{ingest_coverage, parse_report};
+
+    let neo4j = state.orchestrator.neo4j();
+    let project = neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    let report = String::from_utf8_lossy(&body);
+    let (format, parsed) = parse_report(&report, query.format);
+    if parsed.files.is_empty() {
+        return Err(AppError::BadRequest(format!(
+            "No line coverage found in the report (read as {})",
+            format.as_str()
+        )));
+    }
+
+    let ingestion = ingest_coverage(neo4j, &project, format, &parsed).await?;
+    Ok(Json(CoverageResponse {
+        project_id,
+        ingestion,
+    }))
+}
+
+// ============================================================================
+// CI test results
+// ============================================================================
+
+/// Largest JUnit report accepted
+pub const MAX_TEST_REPORT_BYTES: usize = 32 * 1024 * 1024;
+
+/// Query parameters for a test results upload
+#[derive(Debug, Default, Deserialize)]
+pub struct TestResultsQuery {
+    /// Commit the tests ran on; a rerun of a commit that failed and now
+    /// passes counts as flaky
+    pub commit: Option<String>,
+    pub branch: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct TestResultsResponse {
+    pub project_id: Uuid,
+    #[serde(flatten)]
+    pub ingestion: crate::orchestrator::test_results::TestRunIngestion,
+}
+
+/// Ingest a JUnit XML report (raw request body) as a CI test run: each test
+/// case's outcome is added to its history and linked to its test function.
+pub async fn ingest_project_test_results(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+    Query(query): Query<TestResultsQuery>,
+    body: axum::body::Bytes,
+) -> Result<Json<TestResultsResponse>, AppError> {
+    use crate::orchestrator::test_results::{ingest_test_results, parse_junit};
+
+    let neo4j = state.orchestrator.neo4j();
+    let project = neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    let cases = parse_junit(&String::from_utf8_lossy(&body));
+    if cases.is_empty() {
+        return Err(AppError::BadRequest(
+            "No <testcase> found in the JUnit report".to_string(),
+        ));
+    }
+
+    let commit = query
+        .commit
+        .as_deref()
+        .map(str::trim)
+        .filter(|c| !c.is_empty());
+    let branch = query
+        .branch
+        .as_deref()
+        .map(str::trim)
+        .filter(|b| !b.is_empty());
+    let ingestion = ingest_test_results(neo4j, &project, &cases, commit, branch).await?;
+    Ok(Json(TestResultsResponse {
+        project_id,
+        ingestion,
+    }))
+}
+
+/// Query parameters for the flaky test report
+#[derive(Debug, Default, Deserialize)]
+pub struct FlakyTestsQuery {
+    /// Most flaky tests returned (default 50)
+    pub limit: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct FlakyTestsResponse {
+    pub project_id: Uuid,
+    #[serde(flatten)]
+    pub report: crate::orchestrator::test_results::FlakyTestReport,
+}
+
+/// Flaky tests of a project, most flaky first, with a suggested gotcha note
+/// for the chronically flaky ones.
+pub async fn get_flaky_tests(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+    Query(query): Query<FlakyTestsQuery>,
+) -> Result<Json<FlakyTestsResponse>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    let limit = query.limit.unwrap_or(50).clamp(1, 500);
+    let report =
+        crate::orchestrator::test_results::flaky_test_report(neo4j, project_id, limit).await?;
+    Ok(Json(FlakyTestsResponse { project_id, report }))
+}
+
+/// Response of `GET /api/projects/{id}/production-errors`
+#[derive(Debug, Serialize)]
+pub struct ProductionErrorsResponse {
+    pub project_id: Uuid,
+    /// Most recently seen first
+    pub issues: Vec<crate::neo4j::models::SentryIssueNode>,
+}
+
+/// Open Sentry issues of a project, as last polled, with the functions on
+/// their stack.
+pub async fn get_production_errors(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+) -> Result<Json<ProductionErrorsResponse>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    let issues = neo4j.list_sentry_issues(project_id).await?;
+    Ok(Json(ProductionErrorsResponse { project_id, issues }))
+}
+
+// ============================================================================
+// Pull request review
+// ============================================================================
+
+/// Request body for a diff review
+#[derive(Debug, Deserialize)]
+pub struct ReviewRequest {
+    /// Unified diff (`git diff` output) of the change to review
+    pub diff: String,
+    /// Also ask the prompt builder model for a review comment
+    #[serde(default)]
+    pub commentary: bool,
+}
+
+/// Review a unified diff against the project graph: touched symbols and
+/// their dependents, related notes and decisions, rule and budget violations.
+pub async fn review_project_diff(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+    Json(body): Json<ReviewRequest>,
+) -> Result<Json<ReviewReport>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let project = neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    let files = review::parse_unified_diff(&body.diff);
+    if files.is_empty() {
+        return Err(AppError::BadRequest(
+            "diff contains no file changes".to_string(),
+        ));
+    }
+
+    let mut report = review::review_diff(neo4j, &project, &files).await?;
+    if body.commentary {
+        let model =
+            std::env::var("PROMPT_BUILDER_MODEL").unwrap_or_else(|_| "claude-opus-4-6".into());
+        if !model.is_empty() {
+            report.commentary = review::write_commentary(&report, &body.diff, &model).await;
+            report.commentary_model = report.commentary.is_some().then_some(model);
+        }
+    }
+    Ok(Json(report))
+}
+
+// ============================================================================
+// Commit message suggestion
+// ============================================================================
+
+/// Request body for a commit message suggestion
+#[derive(Debug, Default, Deserialize)]
+pub struct CommitMessageRequest {
+    /// Unified diff to describe (default: the working diff of the project)
+    #[serde(default)]
+    pub diff: Option<String>,
+    /// Without a diff, describe only the staged changes
+    #[serde(default)]
+    pub staged: bool,
+}
+
+/// Propose a conventional commit message for a diff of the project: the
+/// touched symbols, and references to the open tasks expecting the files.
+pub async fn suggest_commit_message(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+    body: Option<Json<CommitMessageRequest>>,
+) -> Result<Json<CommitSuggestion>, AppError> {
+    let body = body.map(|Json(b)| b).unwrap_or_default();
+    let neo4j = state.orchestrator.neo4j();
+    let project = neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    let diff = match body.diff {
+        Some(diff) => diff,
+        None => commit_message::working_diff(&expand_tilde(&project.root_path), body.staged)
+            .await
+            .map_err(|e| AppError::BadRequest(e.to_string()))?,
+    };
+    let files = review::parse_unified_diff(&diff);
+    if files.is_empty() {
+        return Err(AppError::BadRequest("no changes to describe".to_string()));
+    }
+
+    let suggestion = commit_message::suggest_commit_message(neo4j, &project, &files).await?;
+    Ok(Json(suggestion))
+}
+
+// ============================================================================
+// ADR export
+// ============================================================================
+
+/// Request body for an ADR export
+#[derive(Debug, Default, Deserialize)]
+pub struct AdrExportRequest {
+    /// Export this decision only (default: every decision of the project)
+    #[serde(default)]
+    pub decision_id: Option<Uuid>,
+}
+
+/// Write the decisions of the project as ADR files under `docs/adr/` of
+/// its repository.
+pub async fn export_adrs(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+    body: Option<Json<AdrExportRequest>>,
+) -> Result<Json<AdrExportReport>, AppError> {
+    let body = body.map(|Json(b)| b).unwrap_or_default();
+    let neo4j = state.orchestrator.neo4j();
+    let project = neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    if let Some(decision_id) = body.decision_id {
+        let decisions = neo4j.list_project_decisions(project_id).await?;
+        if !decisions.iter().any(|d| d.decision.id == decision_id) {
+            return Err(AppError::NotFound(format!(
+                "Decision {} not found in project {}",
+                decision_id, project.slug
+            )));
+        }
+    }
+
+    let report = adr::export_adrs(neo4j, &project, body.decision_id).await?;
+    Ok(Json(report))
+}
+
+// ============================================================================
+// Diff-scoped sync
+// ============================================================================
+
+fn default_head_ref() -> String {
+    "HEAD".to_string()
+}
+
+/// Request body for a diff-scoped sync
+#[derive(Debug, Deserialize)]
+pub struct DiffSyncRequest {
+    /// Base git ref (branch, tag or commit), typically the PR target
+    pub base: String,
+    /// Head git ref, the PR branch (default: HEAD)
+    #[serde(default = "default_head_ref")]
+    pub head: String,
+}
+
+/// Analyze the files changed between two git refs of a project without
+/// touching its graph: they are parsed at both refs into a throwaway overlay.
+pub async fn sync_project_diff(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+    Json(body): Json<DiffSyncRequest>,
+) -> Result<Json<DiffSyncReport>, AppError> {
+    let project = state
+        .orchestrator
+        .neo4j()
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    let cwd = expand_tilde(&project.root_path);
+    let mut commits = Vec::new();
+    for rev in [&body.base, &body.head] {
+        let commit = diff_sync::resolve_commit(&cwd, rev)
+            .await?
+            .ok_or_else(|| AppError::BadRequest(format!("Unknown git ref: {}", rev)))?;
+        commits.push(commit);
+    }
+
+    let report = state
+        .orchestrator
+        .sync_diff(&project, &commits[0], &commits[1])
+        .await?;
+    Ok(Json(report))
+}
+
+// ============================================================================
+// Context bundle
+// ============================================================================
+
+/// Output format of a context bundle
+#[derive(Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleFormat {
+    #[default]
+    Json,
+    Zip,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Request body for a context bundle
+#[derive(Debug, Deserialize)]
+pub struct ContextBundleRequest {
+    /// Task the bundle is built for; must belong to a plan of the project
+    pub task_id: Uuid,
+    #[serde(default)]
+    pub format: BundleFormat,
+    /// Embed the content of the files the task modifies (default: true)
+    #[serde(default = "default_true")]
+    pub include_file_contents: bool,
+}
+
+/// Export everything an agent needs for a task — plan state, task context,
+/// the files it modifies and the prompt — as one JSON document or zip archive.
+pub async fn create_context_bundle(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+    Json(body): Json<ContextBundleRequest>,
+) -> Result<axum::response::Response, AppError> {
+    use axum::response::IntoResponse;
+
+    let neo4j = state.orchestrator.neo4j();
+    let project = neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+    let task_not_found =
+        || AppError::NotFound(format!("Task {} not found in project", body.task_id));
+    let plan_id = neo4j
+        .get_plan_id_for_task(body.task_id)
+        .await?
+        .ok_or_else(task_not_found)?;
+    let plan = neo4j.get_plan(plan_id).await?.ok_or_else(task_not_found)?;
+    if plan.project_id != Some(project_id) {
+        return Err(task_not_found());
+    }
+
+    let bundle = state
+        .orchestrator
+        .context_builder()
+        .build_context_bundle(&project, body.task_id, plan_id, body.include_file_contents)
+        .await?;
+    if body.format == BundleFormat::Json {
+        return Ok(Json(bundle).into_response());
+    }
+
+    let archive = context_bundle::write_zip(&bundle)?;
+    let disposition = format!(
+        "attachment; filename=\"{}\"",
+        context_bundle::bundle_file_name(&bundle, "zip")
+    );
+    Ok((
+        [
+            (
+                axum::http::header::CONTENT_TYPE,
+                "application/zip".to_string(),
+            ),
+            (axum::http::header::CONTENT_DISPOSITION, disposition),
+        ],
+        archive,
+    )
+        .into_response())
+}
+
+// ============================================================================
+// Call graph view
+// ============================================================================
+
+const DEFAULT_CALLGRAPH_DEPTH: u32 = 2;
+const MAX_CALLGRAPH_DEPTH: u32 = 10;
+const DEFAULT_CALLGRAPH_NODES: usize = 300;
+const MAX_CALLGRAPH_NODES: usize = 2000;
+const MIN_NODE_SIZE: f64 = 4.0;
+const MAX_NODE_SIZE: f64 = 24.0;
+/// Color for functions the last analytics run did not assign to a community
+const UNCLUSTERED_COLOR: &str = "#9ca3af";
+const COMMUNITY_PALETTE: [&str; 12] = [
+    "#4e79a7", "#f28e2b", "#e15759", "#76b7b2", "#59a14f", "#edc948", "#b07aa1", "#ff9da7",
+    "#9c755f", "#bab0ac", "#86bcb6", "#d37295",
+];
+
+/// Query parameters for the call graph view
+#[derive(Debug, Deserialize)]
+pub struct ProjectCallGraphQuery {
+    /// Function id or name to center the graph on; whole project when absent
+    pub root: Option<String>,
+    /// Hops (callers and callees) from the root (default: 2, max: 10)
+    pub depth: Option<u32>,
+    /// Node budget after pruning (default: 300, max: 2000)
+    pub max_nodes: Option<usize>,
+    /// Ignore CALLS edges scored below this confidence, 0.0-1.0 (default: 0.0)
+    pub min_confidence: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CallGraphViewNode {
+    pub id: String,
+    pub name: String,
+    pub file_path: String,
+    pub line_start: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub community_id: Option<i64>,
+    pub color: String,
+    /// Radius scaled from PageRank, between 4 and 24
+    pub size: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pagerank: Option<f64>,
+    /// Hops from the root (0 for every node when there is no root)
+    pub distance: u32,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct CallGraphViewEdge {
+    /// Caller function id
+    pub source: String,
+    /// Callee function id
+    pub target: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CallGraphCommunity {
+    pub community_id: i64,
+    pub color: String,
+    pub node_count: usize,
+}
+
+/// Call graph ready to hand to a force layout
+#[derive(Debug, Serialize)]
+pub struct ProjectCallGraphResponse {
+    pub project_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root: Option<String>,
+    pub nodes: Vec<CallGraphViewNode>,
+    pub edges: Vec<CallGraphViewEdge>,
+    /// Communities present in `nodes`, largest first
+    pub communities: Vec<CallGraphCommunity>,
+    /// Functions reachable within `depth` before pruning
+    pub total_nodes: usize,
+    /// True when `max_nodes` dropped part of the reachable graph
+    pub truncated: bool,
+}
+
+fn community_color(community_id: Option<i64>) -> String {
+    match community_id {
+        Some(id) => COMMUNITY_PALETTE[id.rem_euclid(COMMUNITY_PALETTE.len() as i64) as usize],
+        None => UNCLUSTERED_COLOR,
+    }
+    .to_string()
+}
+
+/// Select the functions within `depth` hops of `root` (every function when
+/// there is no root), keep the closest and highest-PageRank ones up to
+/// `max_nodes`, and attach colors, sizes and the edges between kept nodes.
+fn layout_call_graph(
+    project_id: Uuid,
+    functions: Vec<crate::neo4j::models::CallGraphFunctionNode>,
+    edges: &[(String, String)],
+    root: Option<&str>,
+    depth: u32,
+    max_nodes: usize,
+) -> ProjectCallGraphResponse {
+    let mut distances: HashMap<&str, u32> = HashMap::new();
+    match root {
+        Some(root) => {
+            let mut neighbours: HashMap<&str, Vec<&str>> = HashMap::new();
+            for (source, target) in edges {
+                neighbours.entry(source.as_str()).or_default().push(target);
+                neighbours.entry(target.as_str()).or_default().push(source);
+            }
+            distances.insert(root, 0);
+            let mut frontier = vec![root];
+            for hop in 1..=depth {
+                let mut next = Vec::new();
+                for id in frontier {
+                    for &neighbour in neighbours.get(id).into_iter().flatten() {
+                        if !distances.contains_key(neighbour) {
+                            distances.insert(neighbour, hop);
+                            next.push(neighbour);
+                        }
+                    }
+                }
+                frontier = next;
+            }
+        }
+        None => distances.extend(functions.iter().map(|f| (f.id.as_str(), 0))),
+    }
+
+    let mut candidates: Vec<(u32, crate::neo4j::models::CallGraphFunctionNode)> = functions
+        .iter()
+        .filter_map(|f| distances.get(f.id.as_str()).map(|d| (*d, f.clone())))
+        .collect();
+    candidates.sort_by(|(da, a), (db, b)| {
+        da.cmp(db)
+            .then_with(|| {
+                b.pagerank
+                    .unwrap_or(0.0)
+                    .total_cmp(&a.pagerank.unwrap_or(0.0))
+            })
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    let total_nodes = candidates.len();
+    candidates.truncate(max_nodes);
+
+    let max_pagerank = candidates
+        .iter()
+        .filter_map(|(_, f)| f.pagerank)
+        .fold(0.0_f64, f64::max);
+    let nodes: Vec<CallGraphViewNode> = candidates
+        .into_iter()
+        .map(|(distance, f)| {
+            // sqrt so that the drawn area, not the radius, tracks PageRank
+            let size = match f.pagerank {
+                Some(pr) if max_pagerank > 0.0 => {
+                    MIN_NODE_SIZE + (MAX_NODE_SIZE - MIN_NODE_SIZE) * (pr / max_pagerank).sqrt()
+                }
+                _ => MIN_NODE_SIZE,
+            };
+            CallGraphViewNode {
+                color: community_color(f.community_id),
+                size,
+                distance,
+                id: f.id,
+                name: f.name,
+                file_path: f.file_path,
+                line_start: f.line_start,
+                community_id: f.community_id,
+                pagerank: f.pagerank,
+            }
+        })
+        .collect();
+
+    let kept: HashSet<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+    let mut seen = HashSet::new();
+    let view_edges = edges
+        .iter()
+        .filter(|(source, target)| kept.contains(source.as_str()) && kept.contains(target.as_str()))
+        .filter(|edge| seen.insert(*edge))
+        .map(|(source, target)| CallGraphViewEdge {
+            source: source.clone(),
+            target: target.clone(),
+        })
+        .collect();
+
+    let mut community_counts: HashMap<i64, usize> = HashMap::new();
+    for id in nodes.iter().filter_map(|n| n.community_id) {
+        *community_counts.entry(id).or_default() += 1;
+    }
+    let mut communities: Vec<CallGraphCommunity> = community_counts
+        .into_iter()
+        .map(|(community_id, node_count)| CallGraphCommunity {
+            community_id,
+            color: community_color(Some(community_id)),
+            node_count,
+        })
+        .collect();
+    communities.sort_by(|a, b| {
+        b.node_count
+            .cmp(&a.node_count)
+            .then(a.community_id.cmp(&b.community_id))
+    });
+
+    ProjectCallGraphResponse {
+        project_id,
+        root: root.map(str::to_string),
+        truncated: total_nodes > nodes.len(),
+        nodes,
+        edges: view_edges,
+        communities,
+        total_nodes,
+    }
+}
+
+/// Get a pruned, colored and sized call graph for the project's graph view
+pub async fn get_project_call_graph(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+    Query(query): Query<ProjectCallGraphQuery>,
+) -> Result<Json<ProjectCallGraphResponse>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    let depth = query
+        .depth
+        .unwrap_or(DEFAULT_CALLGRAPH_DEPTH)
+        .clamp(1, MAX_CALLGRAPH_DEPTH);
+    let max_nodes = query
+        .max_nodes
+        .unwrap_or(DEFAULT_CALLGRAPH_NODES)
+        .clamp(1, MAX_CALLGRAPH_NODES);
+    let min_confidence = query.min_confidence.unwrap_or(0.0);
+    if !(0.0..=1.0).contains(&min_confidence) {
+        return Err(AppError::BadRequest(
+            "min_confidence must be between 0.0 and 1.0".to_string(),
+        ));
+    }
+
+    let functions = neo4j.get_project_call_graph_functions(project_id).await?;
+    let edges = neo4j
+        .get_project_call_edges(project_id, min_confidence)
+        .await?;
+
+    // Accept an exact function id, or a name (the highest-PageRank homonym wins)
+    let root = match query.root.as_deref().filter(|r| !r.is_empty()) {
+        Some(root) => Some(
+            functions
+                .iter()
+                .find(|f| f.id == root)
+                .or_else(|| {
+                    functions.iter().filter(|f| f.name == root).max_by(|a, b| {
+                        a.pagerank
+                            .unwrap_or(0.0)
+                            .total_cmp(&b.pagerank.unwrap_or(0.0))
+                    })
+                })
+                .map(|f| f.id.clone())
+                .ok_or_else(|| AppError::NotFound(format!("Function not found: {}", root)))?,
+        ),
+        None => None,
+    };
+
+    Ok(Json(layout_call_graph(
+        project_id,
+        functions,
+        &edges,
+        root.as_deref(),
+        depth,
+        max_nodes,
+    )))
+}
+
+// ============================================================================
+// File tree
+// ============================================================================
+
+/// Query parameters for the file tree
+#[derive(Debug, Deserialize)]
+pub struct FileTreeQuery {
+    /// Directory to return, relative to the project root (default: the root)
+    pub path: Option<String>,
+    /// Levels of children to include below `path`; deeper entries still count
+    /// in the aggregates (default: unlimited)
+    pub depth: Option<usize>,
+}
+
+impl Validate for FileTreeQuery {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(path) = self
+            .path
+            .as_deref()
+            .filter(|p| !p.trim_matches('/').is_empty())
+        {
+            v.file_path("path", path);
+        }
+    }
+}
+
+/// A directory or file of the project tree with aggregates over its subtree
+#[derive(Debug, Serialize)]
+pub struct FileTreeNode {
+    pub name: String,
+    /// Relative to the project root ("" for the root)
+    pub path: String,
+    /// "directory" or "file"
+    pub kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    pub file_count: usize,
+    pub function_count: u32,
+    pub type_count: u32,
+    /// Mean cyclomatic complexity of the functions in the subtree
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_complexity: Option<f64>,
+    pub max_complexity: u32,
+    #[serde(skip)]
+    complexity_total: u64,
+    /// Most recent parse in the subtree
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_parsed: Option<chrono::DateTime<chrono::Utc>>,
+    /// Files whose analytics scores predate their last parse
+    pub stale_files: usize,
+    /// Files graph analytics have not scored yet
+    pub unanalyzed_files: usize,
+    /// Files with a "high" or "critical" risk level
+    pub high_risk_files: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub risk_level: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<FileTreeNode>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProjectTreeResponse {
+    pub project_id: Uuid,
+    pub tree: FileTreeNode,
+}
+
+#[derive(Default)]
+struct TreeDir {
+    dirs: std::collections::BTreeMap<String, TreeDir>,
+    files: std::collections::BTreeMap<String, crate::neo4j::models::FileStatsRow>,
+}
+
+impl FileTreeNode {
+    fn file(path: String, name: String, row: crate::neo4j::models::FileStatsRow) -> Self {
+        let high_risk = matches!(row.risk_level.as_deref(), Some("high" | "critical"));
+        Self {
+            name,
+            path,
+            kind: "file",
+            language: Some(row.language),
+            file_count: 1,
+            function_count: row.function_count,
+            type_count: row.type_count,
+            avg_complexity: (row.function_count > 0)
+                .then(|| row.complexity_total as f64 / row.function_count as f64),
+            max_complexity: row.max_complexity,
+            complexity_total: row.complexity_total,
+            last_parsed: Some(row.last_parsed),
+            stale_files: row.analytics_stale as usize,
+            unanalyzed_files: !row.analyzed as usize,
+            high_risk_files: high_risk as usize,
+            risk_level: row.risk_level,
+            children: vec![],
+        }
+    }
+
+    /// Roll up the directory's subdirectories and files, in that order.
+    fn directory(path: String, name: String, dir: TreeDir) -> Self {
+        let join = |child: &str| {
+            if path.is_empty() {
+                child.to_string()
+            } else {
+                format!("{}/{}", path, child)
+            }
+        };
+        let mut children: Vec<FileTreeNode> = dir
+            .dirs
+            .into_iter()
+            .map(|(child, sub)| Self::directory(join(&child), child, sub))
+            .collect();
+        children.extend(
+            dir.files
+                .into_iter()
+                .map(|(child, row)| Self::file(join(&child), child, row)),
+        );
+
+        let function_count = children.iter().map(|c| c.function_count).sum();
+        let complexity_total = children.iter().map(|c| c.complexity_total).sum();
+        Self {
+            name,
+            path: path.clone(),
+            kind: "directory",
+            language: None,
+            file_count: children.iter().map(|c| c.file_count).sum(),
+            function_count,
+            type_count: children.iter().map(|c| c.type_count).sum(),
+            avg_complexity: (function_count > 0)
+                .then(|| complexity_total as f64 / function_count as f64),
+            max_complexity: children.iter().map(|c| c.max_complexity).max().unwrap_or(0),
+            complexity_total,
+            last_parsed: children.iter().filter_map(|c| c.last_parsed).max(),
+            stale_files: children.iter().map(|c| c.stale_files).sum(),
+            unanalyzed_files: children.iter().map(|c| c.unanalyzed_files).sum(),
+            high_risk_files: children.iter().map(|c| c.high_risk_files).sum(),
+            risk_level: None,
+            children,
+        }
+    }
+}
+
+/// Build the directory hierarchy of `files`, with paths made relative to
+/// the project `roots`.
+fn build_file_tree(
+    roots: &[String],
+    files: Vec<crate::neo4j::models::FileStatsRow>,
+) -> FileTreeNode {
+    let mut root = TreeDir::default();
+    for row in files {
+        let relative = crate::orchestrator::relative_to_roots(&row.path, roots).to_string();
+        let mut segments: Vec<&str> = relative.split('/').filter(|s| !s.is_empty()).collect();
+        let Some(file_name) = segments.pop() else {
+            continue;
+        };
+        let dir = segments.into_iter().fold(&mut root, |dir, segment| {
+            dir.dirs.entry(segment.to_string()).or_default()
+        });
+        dir.files.insert(file_name.to_string(), row);
+    }
+    FileTreeNode::directory(String::new(), String::new(), root)
+}
+
+/// Get the project's directory hierarchy annotated with symbol counts,
+/// complexity, staleness and analytics flags per folder
+pub async fn get_project_tree(
+    State(state): State<OrchestratorState>,
+    ValidPath(project_id): ValidPath<Uuid>,
+    ValidQuery(query): ValidQuery<FileTreeQuery>,
+) -> Result<Json<ProjectTreeResponse>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let project = neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Project {} not found", project_id)))?;
+
+    let roots = crate::orchestrator::project_roots(&project.root_path);
+    let files = neo4j.get_project_file_stats(project_id).await?;
+    let subtree = query
+        .path
+        .as_deref()
+        .map(|p| p.trim_matches('/'))
+        .filter(|p| !p.is_empty());
+    let mut tree = build_file_tree(&roots, files);
+    if let Some(subtree) = subtree {
+        for segment in subtree.split('/') {
+            tree = tree
+                .children
+                .into_iter()
+                .find(|c| c.kind == "directory" && c.name == segment)
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Directory not found in project: {}", subtree))
+                })?;
+        }
+    }
+    if let Some(depth) = query.depth {
+        prune_tree(&mut tree, depth);
+    }
+
+    Ok(Json(ProjectTreeResponse { project_id, tree }))
+}
+
+/// Drop the children below `depth` levels, keeping the aggregates
+fn prune_tree(node: &mut FileTreeNode, depth: usize) {
+    if depth == 0 {
+        node.children.clear();
+    } else {
+        for child in &mut node.children {
+            prune_tree(child, depth - 1);
+        }
+    }
+}
+
+// ============================================================================
+// Utilities
+// ============================================================================
+
+/// Convert a name to a URL-safe slug
+pub(crate) fn slugify(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("My Project"), "my-project");
+        assert_eq!(slugify("Test  Project!"), "test-project");
+        assert_eq!(slugify("embryon-neural"), "embryon-neural");
+        assert_eq!(slugify("Project 123"), "project-123");
+    }
+
+    #[test]
+    fn test_update_project_request_all_fields() {
+        let json = r#"{"name":"New Name","description":"New desc","root_path":"/new"}"#;
+        let req: UpdateProjectRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.name, Some("New Name".to_string()));
+        assert_eq!(req.description, Some(Some("New desc".to_string())));
+        assert_eq!(req.root_path, Some("/new".to_string()));
+    }
+
+    #[test]
+    fn test_update_project_request_empty() {
+        let json = r#"{}"#;
+        let req: UpdateProjectRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.name, None);
+        assert_eq!(req.description, None);
+        assert_eq!(req.root_path, None);
+    }
+
+    #[test]
+    fn test_update_project_request_null_description() {
+        // With default serde, null on Option<Option<String>> deserializes to None (absent)
+        // To distinguish "field absent" from "field = null", a custom deserializer is needed
+        let json = r#"{"description":null}"#;
+        let req: UpdateProjectRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.description, None);
+    }
+
+    #[test]
+    fn test_update_project_request_explicit_description() {
+        // Explicit string value -> Some(Some("..."))
+        let json = r#"{"description":"hello"}"#;
+        let req: UpdateProjectRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.description, Some(Some("hello".to_string())));
+    }
+
+    #[test]
+    fn test_update_project_request_only_name() {
+        let json = r#"{"name":"Renamed"}"#;
+        let req: UpdateProjectRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.name, Some("Renamed".to_string()));
+        assert_eq!(req.description, None);
+        assert_eq!(req.root_path, None);
+    }
+
+    // ====================================================================
+    // Handler integration tests (axum + mock backends)
+    // ====================================================================
+
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode as AxumStatus},
+    };
+    use tower::ServiceExt;
+
+    use crate::api::handlers::ServerState;
+    use crate::api::routes::create_router;
+    use crate::neo4j::models::FileNode;
+    use crate::orchestrator::watcher::FileWatcher;
+    use crate::orchestrator::Orchestrator;
+    use crate::test_helpers::{
+        mock_app_state, test_auth_config, test_bearer_token, test_project_named,
+    };
+
+    /// Build a mock OrchestratorState for project handler tests
+    async fn mock_server_state() -> super::OrchestratorState {
+        let app_state = mock_app_state();
+        let orchestrator = std::sync::Arc::new(Orchestrator::new(app_state).await.unwrap());
+        let watcher = std::sync::Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
+            orchestrator.clone(),
+        )));
+        std::sync::Arc::new(ServerState {
+            orchestrator,
+            watcher,
+            chat_manager: None,
+            event_bus: std::sync::Arc::new(crate::events::HybridEmitter::new(std::sync::Arc::new(
+                crate::events::EventBus::default(),
+            ))),
+            nats_emitter: None,
+            auth_config: Some(test_auth_config()),
+            serve_frontend: false,
+            frontend_path: "./dist".to_string(),
+            setup_completed: true,
+            server_port: 6600,
+            public_url: None,
+            read_only: false,
+            ws_ticket_store: std::sync::Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: std::sync::Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: std::sync::Arc::new(crate::api::idempotency::IdempotencyStore::new()),
+            registry_remote_url: None,
+            oidc_client: None,
+            neural_router: crate::test_helpers::mock_neural_router(),
+            trajectory_collector: std::sync::RwLock::new(None),
+            trajectory_store_neo4j: None,
+            trajectory_store: None,
+            identity: None,
+            reactor_counters: std::sync::OnceLock::new(),
+            confidence_tracker: Arc::new(crate::graph::confidence::ConfidenceTracker::default()),
+            mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
+            model_catalog: crate::chat::model_catalog::ModelCatalogCache::new(None),
+        })
+    }
+
+    fn authed_get(uri: &str) -> Request<Body> {
+        Request::builder()
+            .uri(uri)
+            .header("authorization", test_bearer_token())
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    fn authed_post(uri: &str, body: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("authorization", test_bearer_token())
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_list_project_advisories() {
+        use crate::neo4j::models::{
+            AdvisoryNode, AdvisorySeverity, PackageEcosystem, PackageNode, ProjectAdvisory,
+        };
+
+        let state = mock_server_state().await;
+        let project = test_project_named("audited");
+        let neo4j = state.orchestrator.neo4j();
+        neo4j.create_project(&project).await.unwrap();
+
+        let package = |name: &str| PackageNode {
+            ecosystem: PackageEcosystem::CratesIo,
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            lockfile: "Cargo.lock".to_string(),
+            license: None,
+        };
+        neo4j
+            .replace_project_packages(project.id, &[package("a"), package("b")])
+            .await
+            .unwrap();
+        let advisory = |id: &str, severity, pkg: &str| ProjectAdvisory {
+            advisory: AdvisoryNode {
+                id: id.to_string(),
+                summary: "bug".to_string(),
+                aliases: vec![],
+                severity,
+                cvss: None,
+                url: None,
+                published: None,
+                modified: None,
+            },
+            ecosystem: PackageEcosystem::CratesIo,
+            package: pkg.to_string(),
+            version: "1.0.0".to_string(),
+            fixed_versions: vec!["1.0.1".to_string()],
+        };
+        neo4j
+            .replace_project_advisories(
+                project.id,
+                &[
+                    advisory("RUSTSEC-2024-0002", AdvisorySeverity::Low, "b"),
+                    advisory("RUSTSEC-2024-0001", AdvisorySeverity::Critical, "a"),
+                ],
+            )
+            .await
+            .unwrap();
+        let app = create_router(state);
+
+        let uri = format!("/api/projects/{}/advisories", project.id);
+        let resp = app.clone().oneshot(authed_get(&uri)).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["packages_count"], 2);
+        assert_eq!(json["advisories"][0]["id"], "RUSTSEC-2024-0001");
+        assert_eq!(json["advisories"][0]["severity"], "critical");
+        assert_eq!(json["advisories"].as_array().unwrap().len(), 2);
+
+        let resp = app
+            .clone()
+            .oneshot(authed_get(&format!("{}?min_severity=high", uri)))
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["advisories"].as_array().unwrap().len(), 1);
+
+        let resp = app
+            .clone()
+            .oneshot(authed_get(&format!("{}?min_severity=severe", uri)))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::BAD_REQUEST);
+
+        let resp = app
+            .oneshot(authed_get(&format!(
+                "/api/projects/{}/advisories",
+                Uuid::new_v4()
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_list_project_documents() {
+        use crate::neo4j::models::DocumentNode;
+
+        let state = mock_server_state().await;
+        let project = test_project_named("documented");
+        let neo4j = state.orchestrator.neo4j();
+        neo4j.create_project(&project).await.unwrap();
+        for path in ["/repo/docs/guide.md", "/repo/README.md"] {
+            let document = DocumentNode {
+                path: path.to_string(),
+                project_id: Some(project.id),
+                title: Some("Docs".to_string()),
+                hash: "abc".to_string(),
+                section_count: 1,
+                indexed_at: chrono::Utc::now(),
+            };
+            neo4j.upsert_document(&document, &[]).await.unwrap();
+        }
+        let app = create_router(state);
+
+        let uri = format!("/api/projects/{}/documents", project.id);
+        let resp = app.clone().oneshot(authed_get(&uri)).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let documents = json["documents"].as_array().unwrap();
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[0]["path"], "/repo/README.md");
+
+        let resp = app
+            .oneshot(authed_get(&format!(
+                "/api/projects/{}/documents",
+                Uuid::new_v4()
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_get_project_schema() {
+        use crate::neo4j::models::{ColumnNode, MigrationAlter, MigrationNode, TableNode};
+
+        let state = mock_server_state().await;
+        let project = test_project_named("persisted");
+        let neo4j = state.orchestrator.neo4j();
+        neo4j.create_project(&project).await.unwrap();
+        let table = TableNode {
+            name: "users".to_string(),
+            project_id: project.id,
+            defined_in: Some("/repo/migrations/0001_init.sql".to_string()),
+            altered_by: vec![],
+            columns: vec![ColumnNode {
+                name: "id".to_string(),
+                data_type: "BIGINT".to_string(),
+                nullable: false,
+                primary_key: true,
+                default_value: None,
+                references: None,
+            }],
+        };
+        let migration = MigrationNode {
+            path: "/repo/migrations/0001_init.sql".to_string(),
+            version: "0001".to_string(),
+            ordinal: 0,
+            project_id: project.id,
+            alters: vec![MigrationAlter {
+                table: "users".to_string(),
+                operations: vec!["create_table".to_string()],
+            }],
+        };
+        neo4j
+            .replace_project_schema(project.id, &[table], &[migration])
+            .await
+            .unwrap();
+        let app = create_router(state);
+
+        let uri = format!("/api/projects/{}/schema", project.id);
+        let resp = app.clone().oneshot(authed_get(&uri)).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["tables"][0]["name"], "users");
+        assert_eq!(json["tables"][0]["columns"][0]["primary_key"], true);
+        assert_eq!(json["migrations"][0]["alters"][0]["table"], "users");
+
+        let resp = app
+            .oneshot(authed_get(&format!(
+                "/api/projects/{}/schema",
+                Uuid::new_v4()
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::NOT_FOUND);
+    }
+
+    fn file_stats(
+        path: &str,
+        functions: u32,
+        complexity: u64,
+    ) -> crate::neo4j::models::FileStatsRow {
+        crate::neo4j::models::FileStatsRow {
+            path: path.to_string(),
+            language: "rust".to_string(),
+            last_parsed: chrono::Utc::now(),
+            function_count: functions,
+            type_count: 1,
+            complexity_total: complexity,
+            max_complexity: complexity as u32,
+            analyzed: functions > 0,
+            analytics_stale: false,
+            risk_level: None,
+        }
+    }
+
+    #[test]
+    fn test_build_file_tree() {
+        let mut risky = file_stats("/repo/src/api/routes.rs", 2, 8);
+        risky.risk_level = Some("critical".to_string());
+        risky.analytics_stale = true;
+        let files = vec![
+            file_stats("/repo/src/main.rs", 1, 2),
+            risky,
+            file_stats("/repo/Cargo.toml", 0, 0),
+            file_stats("/elsewhere/gen.rs", 1, 1),
+        ];
+        let tree = build_file_tree(&["/repo/".to_string()], files);
+
+        assert_eq!(tree.file_count, 4);
+        assert_eq!(tree.function_count, 4);
+        assert_eq!(tree.unanalyzed_files, 1);
+        let names: Vec<&str> = tree.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["elsewhere", "src", "Cargo.toml"]);
+
+        let src = &tree.children[1];
+        assert_eq!(src.kind, "directory");
+        assert_eq!(src.file_count, 2);
+        assert_eq!(src.avg_complexity, Some(10.0 / 3.0));
+        assert_eq!(src.max_complexity, 8);
+        assert_eq!(src.stale_files, 1);
+        assert_eq!(src.high_risk_files, 1);
+        let api = &src.children[0];
+        assert_eq!(api.path, "src/api");
+        assert_eq!(api.children[0].path, "src/api/routes.rs");
+        assert_eq!(api.children[0].risk_level.as_deref(), Some("critical"));
+
+        let mut tree = tree;
+        prune_tree(&mut tree, 1);
+        assert!(tree.children[1].children.is_empty());
+        assert_eq!(tree.children[1].file_count, 2);
+    }
+
+    fn call_graph_function(
+        id: &str,
+        pagerank: Option<f64>,
+        community_id: Option<i64>,
+    ) -> crate::neo4j::models::CallGraphFunctionNode {
+        crate::neo4j::models::CallGraphFunctionNode {
+            id: id.to_string(),
+            name: id.to_string(),
+            file_path: "/repo/src/lib.rs".to_string(),
+            line_start: 1,
+            pagerank,
+            community_id,
+        }
+    }
+
+    #[test]
+    fn test_layout_call_graph() {
+        let functions = vec![
+            call_graph_function("main", Some(0.4), Some(0)),
+            call_graph_function("parse", Some(0.2), Some(1)),
+            call_graph_function("lex", Some(0.1), Some(1)),
+            call_graph_function("emit", None, None),
+            call_graph_function("island", Some(0.9), Some(0)),
+        ];
+        let edges = vec![
+            ("main".to_string(), "parse".to_string()),
+            ("main".to_string(), "parse".to_string()),
+            ("parse".to_string(), "lex".to_string()),
+            ("emit".to_string(), "main".to_string()),
+        ];
+        let project_id = Uuid::new_v4();
+
+        let graph = layout_call_graph(project_id, functions.clone(), &edges, Some("parse"), 1, 10);
+        let ids: Vec<&str> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, ["parse", "main", "lex"]);
+        assert_eq!(graph.nodes[1].distance, 1);
+        assert_eq!(graph.nodes[1].size, MAX_NODE_SIZE);
+        assert_eq!(graph.nodes[0].color, graph.nodes[2].color);
+        assert_eq!(graph.edges.len(), 2);
+        assert_eq!(graph.communities[0].community_id, 1);
+        assert_eq!(graph.communities[0].node_count, 2);
+        assert!(!graph.truncated);
+
+        let graph = layout_call_graph(project_id, functions.clone(), &edges, Some("parse"), 2, 3);
+        assert_eq!(graph.total_nodes, 4);
+        assert!(graph.truncated);
+        assert!(graph.nodes.iter().all(|n| n.id != "emit"));
+
+        let graph = layout_call_graph(project_id, functions, &edges, None, 2, 2);
+        let ids: Vec<&str> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, ["island", "main"]);
+        assert!(graph.edges.is_empty());
+        assert_eq!(community_color(None), UNCLUSTERED_COLOR);
+    }
+
+    #[tokio::test]
+    async fn test_get_project_tree() {
+        let state = mock_server_state().await;
+        let mut project = test_project_named("treed");
+        project.root_path = "/repo".to_string();
+        let neo4j = state.orchestrator.neo4j();
+        neo4j.create_project(&project).await.unwrap();
+        for path in ["/repo/src/lib.rs", "/repo/src/api/mod.rs", "/repo/build.rs"] {
+            neo4j
+                .upsert_file(&FileNode {
+                    path: path.to_string(),
+                    language: "rust".to_string(),
+                    hash: "h".to_string(),
+                    line_count: 0,
+                    last_parsed: chrono::Utc::now(),
+                    project_id: Some(project.id),
+                    encoding: None,
+                })
+                .await
+                .unwrap();
+            neo4j.link_file_to_project(path, project.id).await.unwrap();
+        }
+        let app = create_router(state);
+
+        let uri = format!("/api/projects/{}/tree?depth=1", project.id);
+        let resp = app.clone().oneshot(authed_get(&uri)).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["tree"]["file_count"], 3);
+        assert_eq!(json["tree"]["unanalyzed_files"], 3);
+        let children = json["tree"]["children"].as_array().unwrap();
+        assert_eq!(children[0]["path"], "src");
+        assert_eq!(children[0]["file_count"], 2);
+        assert!(children[0].get("children").is_none());
+        assert_eq!(children[1]["kind"], "file");
+
+        let uri = format!("/api/projects/{}/tree?path=src/api", project.id);
+        let resp = app.clone().oneshot(authed_get(&uri)).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["tree"]["children"][0]["path"], "src/api/mod.rs");
+
+        let uri = format!("/api/projects/{}/tree?path=src/missing", project.id);
+        let resp = app.clone().oneshot(authed_get(&uri)).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::NOT_FOUND);
+
+        let resp = app
+            .oneshot(authed_get(&format!(
+                "/api/projects/{}/tree",
+                Uuid::new_v4()
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_get_project_call_graph() {
+        use crate::graph::models::FunctionAnalyticsUpdate;
+        use crate::neo4j::models::{FunctionNode, Visibility};
+
+        let state = mock_server_state().await;
+        let project = test_project_named("graphed");
+        let neo4j = state.orchestrator.neo4j();
+        neo4j.create_project(&project).await.unwrap();
+        let path = "/repo/src/main.rs".to_string();
+        neo4j
+            .upsert_file(&FileNode {
+                path: path.clone(),
+                language: "rust".to_string(),
+                hash: "h".to_string(),
+                line_count: 0,
+                last_parsed: chrono::Utc::now(),
+                project_id: Some(project.id),
+                encoding: None,
+            })
+            .await
+            .unwrap();
+        neo4j.link_file_to_project(&path, project.id).await.unwrap();
+        for (line, name) in [(1, "main"), (10, "run"), (20, "helper")] {
+            neo4j
+                .upsert_function(&FunctionNode {
+                    name: name.to_string(),
+                    visibility: Visibility::Public,
+                    params: vec![],
+                    return_type: None,
+                    generics: vec![],
+                    is_async: false,
+                    is_unsafe: false,
+                    complexity: 1,
+                    file_path: path.clone(),
+                    line_start: line,
+                    line_end: line + 5,
+                    docstring: None,
+                })
+                .await
+                .unwrap();
+        }
+        for (caller, callee) in [("main", "run"), ("run", "helper")] {
+            neo4j
+                .create_call_relationship(
+                    &format!("{}::{}", path, caller),
+                    callee,
+                    Some(project.id),
+                    1.0,
+                    "same-file",
+                )
+                .await
+                .unwrap();
+        }
+        neo4j
+            .batch_update_function_analytics(&[FunctionAnalyticsUpdate {
+                id: format!("{}:run:10", path),
+                pagerank: 0.5,
+                betweenness: 0.0,
+                community_id: 3,
+                clustering_coefficient: 0.0,
+                component_id: 0,
+            }])
+            .await
+            .unwrap();
+        let app = create_router(state);
+
+        let uri = format!("/api/projects/{}/callgraph?root=main&depth=1", project.id);
+        let resp = app.clone().oneshot(authed_get(&uri)).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["root"], format!("{}:main:1", path));
+        let nodes = json["nodes"].as_array().unwrap();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[1]["name"], "run");
+        assert_eq!(nodes[1]["community_id"], 3);
+        assert_eq!(json["edges"].as_array().unwrap().len(), 1);
+
+        let resp = app
+            .clone()
+            .oneshot(authed_get(&format!(
+                "/api/projects/{}/callgraph?root=missing",
+                project.id
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::NOT_FOUND);
+
+        let resp = app
+            .oneshot(authed_get(&format!(
+                "/api/projects/{}/callgraph",
+                Uuid::new_v4()
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_list_module_summaries() {
+        use crate::neo4j::models::ModuleSummaryNode;
+
+        let state = mock_server_state().await;
+        let project = test_project_named("summarized");
+        let neo4j = state.orchestrator.neo4j();
+        neo4j.create_project(&project).await.unwrap();
+        neo4j
+            .upsert_module_summaries(&[ModuleSummaryNode {
+                project_id: project.id,
+                path: "src/api".to_string(),
+                summary: "HTTP layer.".to_string(),
+                source_hash: "abc".to_string(),
+                file_count: 3,
+                generated_by: "heuristic".to_string(),
+                updated_at: chrono::Utc::now(),
+            }])
+            .await
+            .unwrap();
+        let app = create_router(state);
+
+        let uri = format!("/api/projects/{}/modules", project.id);
+        let resp = app.clone().oneshot(authed_get(&uri)).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["modules"][0]["path"], "src/api");
+        assert_eq!(json["modules"][0]["summary"], "HTTP layer.");
+
+        let resp = app
+            .oneshot(authed_get(&format!(
+                "/api/projects/{}/modules",
+                Uuid::new_v4()
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_project_budgets() {
+        use crate::neo4j::models::{FunctionNode, Visibility};
+
+        let state = mock_server_state().await;
+        let project = test_project_named("budgeted");
+        let neo4j = state.orchestrator.neo4j();
+        neo4j.create_project(&project).await.unwrap();
+        neo4j
+            .upsert_function(&FunctionNode {
+                name: "tangled".to_string(),
+                visibility: Visibility::Public,
+                params: vec![],
+                return_type: None,
+                generics: vec![],
+                is_async: false,
+                is_unsafe: false,
+                complexity: 25,
+                file_path: "/tmp/budgeted/src/lib.rs".to_string(),
+                line_start: 1,
+                line_end: 40,
+                docstring: None,
+            })
+            .await
+            .unwrap();
+        neo4j
+            .link_file_to_project("/tmp/budgeted/src/lib.rs", project.id)
+            .await
+            .unwrap();
+        let app = create_router(state);
+        let put = |body: serde_json::Value| {
+            Request::builder()
+                .method("PUT")
+                .uri(format!("/api/projects/{}/budgets", project.id))
+                .header("content-type", "application/json")
+                .header("authorization", test_bearer_token())
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        // No budgets: always passes
+        let check = format!(
+            "/api/projects/{}/budget-check?fail_on_violation=true",
+            project.id
+        );
+        let resp = app.clone().oneshot(authed_get(&check)).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+
+        let resp = app
+            .clone()
+            .oneshot(put(serde_json::json!({"max_file_lines": 0})))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::BAD_REQUEST);
+
+        let resp = app
+            .clone()
+            .oneshot(put(serde_json::json!({"max_function_complexity": 15})))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+
+        let uri = format!("/api/projects/{}/budgets", project.id);
+        let resp = app.clone().oneshot(authed_get(&uri)).await.unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["max_function_complexity"], 15);
+        assert!(json.get("max_file_lines").is_none());
+
+        let resp = app.clone().oneshot(authed_get(&check)).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::UNPROCESSABLE_ENTITY);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["passed"], false);
+        assert_eq!(json["violation_count"], 1);
+        assert_eq!(json["violations"][0]["budget"], "max_function_complexity");
+        assert_eq!(json["violations"][0]["actual"], 25);
+
+        // Without the gate flag the failed report is still a 200
+        let uri = format!("/api/projects/{}/budget-check?cached=true", project.id);
+        let resp = app.clone().oneshot(authed_get(&uri)).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+
+        let resp = app
+            .oneshot(authed_get(&format!(
+                "/api/projects/{}/budget-check",
+                Uuid::new_v4()
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_project_api_surface() {
+        use crate::neo4j::models::{FunctionNode, Visibility};
+
+        let state = mock_server_state().await;
+        let project = test_project_named("surfaced");
+        let neo4j = state.orchestrator.neo4j();
+        neo4j.create_project(&project).await.unwrap();
+        let function = |name: &str, visibility: Visibility| FunctionNode {
+            name: name.to_string(),
+            visibility,
+            params: vec![],
+            return_type: Some("u32".to_string()),
+            generics: vec![],
+            is_async: false,
+            is_unsafe: false,
+            complexity: 1,
+            file_path: format!("{}/src/lib.rs", project.root_path),
+            line_start: 1,
+            line_end: 3,
+            docstring: None,
+        };
+        neo4j
+            .link_file_to_project(&format!("{}/src/lib.rs", project.root_path), project.id)
+            .await
+            .unwrap();
+        neo4j
+            .upsert_function(&function("count", Visibility::Public))
+            .await
+            .unwrap();
+        neo4j
+            .upsert_function(&function("helper", Visibility::Private))
+            .await
+            .unwrap();
+        let snapshot = crate::orchestrator::api_surface::record_snapshot(neo4j, project.id)
+            .await
+            .unwrap()
+            .unwrap();
+        let app = create_router(state.clone());
+
+        let uri = format!("/api/projects/{}/api-surface", project.id);
+        let resp = app.clone().oneshot(authed_get(&uri)).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["symbol_count"], 1);
+        assert_eq!(json["modules"][0]["module"], "src/lib.rs");
+        assert_eq!(
+            json["modules"][0]["symbols"][0]["signature"],
+            "fn count() -> u32"
+        );
+
+        // Make `count` private: removing it from the surface is breaking
+        state
+            .orchestrator
+            .neo4j()
+            .upsert_function(&function("count", Visibility::Private))
+            .await
+            .unwrap();
+        let diff = format!(
+            "/api/projects/{}/api-surface/diff?from={}&fail_on_breaking=true",
+            project.id, snapshot.id
+        );
+        let resp = app.clone().oneshot(authed_get(&diff)).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::UNPROCESSABLE_ENTITY);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["breaking"], true);
+        assert_eq!(json["removed"][0]["name"], "count");
+        assert!(json["to"].is_null());
+
+        let uri = format!("/api/projects/{}/api-surface/snapshots", project.id);
+        let resp = app.clone().oneshot(authed_get(&uri)).await.unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["snapshots"][0]["id"], snapshot.id.to_string());
+
+        let unknown = format!(
+            "/api/projects/{}/api-surface/diff?from={}",
+            project.id,
+            Uuid::new_v4()
+        );
+        let resp = app.oneshot(authed_get(&unknown)).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_ingest_project_coverage() {
+        use crate::neo4j::models::{FunctionNode, Visibility};
+
+        let state = mock_server_state().await;
+        let project = test_project_named("covered");
+        let neo4j = state.orchestrator.neo4j();
+        neo4j.create_project(&project).await.unwrap();
+        let file_path = format!("{}/src/lib.rs", project.root_path);
+        neo4j
+            .link_file_to_project(&file_path, project.id)
+            .await
+            .unwrap();
+        neo4j
+            .upsert_function(&FunctionNode {
+                name: "parse".to_string(),
+                visibility: Visibility::Public,
+                params: vec![],
+                return_type: None,
+                generics: vec![],
+                is_async: false,
+                is_unsafe: false,
+                complexity: 3,
+                file_path: file_path.clone(),
+                line_start: 1,
+                line_end: 4,
+                docstring: None,
+            })
+            .await
+            .unwrap();
+        let app = create_router(state);
+        let post = |query: &str, body: &str| {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/api/projects/{}/coverage{}", project.id, query))
+                .header("authorization", test_bearer_token())
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let lcov = "SF:src/lib.rs\nDA:1,1\nDA:2,0\nDA:3,0\nDA:4,0\nend_of_record\n";
+        let resp = app.clone().oneshot(post("", lcov)).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["format"], "lcov");
+        assert_eq!(json["matched_files"], 1);
+        assert_eq!(json["functions_covered"], 1);
+        assert_eq!(json["coverage_pct"], 25.0);
+
+        // An LCOV body read as Cobertura holds no line data
+        let resp = app
+            .clone()
+            .oneshot(post("?format=cobertura", lcov))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::BAD_REQUEST);
+
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/api/projects/{}/coverage", Uuid::new_v4()))
+                    .header("authorization", test_bearer_token())
+                    .body(Body::from(lcov))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_project_test_results_and_flaky_tests() {
+        use crate::neo4j::models::{FunctionNode, Visibility};
+
+        let state = mock_server_state().await;
+        let project = test_project_named("ci-results");
+        let neo4j = state.orchestrator.neo4j();
+        neo4j.create_project(&project).await.unwrap();
+        let file_path = format!("{}/src/net.rs", project.root_path);
+        neo4j
+            .link_file_to_project(&file_path, project.id)
+            .await
+            .unwrap();
+        neo4j
+            .upsert_function(&FunctionNode {
+                name: "test_retry".to_string(),
+                visibility: Visibility::Private,
+                params: vec![],
+                return_type: None,
+                generics: vec![],
+                is_async: false,
+                is_unsafe: false,
+                complexity: 1,
+                file_path: file_path.clone(),
+                line_start: 40,
+                line_end: 52,
+                docstring: None,
+            })
+            .await
+            .unwrap();
+        let app = create_router(state);
+        let post = |query: &str, body: &str| {
+            Request::builder()
+                .method("POST")
+                .uri(format!(
+                    "/api/projects/{}/test-results{}",
+                    project.id, query
+                ))
+                .header("authorization", test_bearer_token())
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let failing = r#"<testsuite name="demo">
+  <testcase classname="demo" name="net::tests::test_retry" time="0.2">
+    <failure message="connection reset"/>
+  </testcase>
+  <testcase classname="demo" name="net::tests::test_ok"/>
+</testsuite>"#;
+        let resp = app
+            .clone()
+            .oneshot(post("?commit=abc1234&branch=main", failing))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["run"]["total"], 2);
+        assert_eq!(json["run"]["failed"], 1);
+        assert_eq!(json["linked_tests"], 1);
+        assert_eq!(
+            json["failed_tests"][0]["function_id"],
+            format!("{}:test_retry:40", file_path)
+        );
+
+        // Rerunning the failed commit passes: flaky
+        let passing = failing.replace(r#"<failure message="connection reset"/>"#, "");
+        let resp = app
+            .clone()
+            .oneshot(post("?commit=abc1234", &passing))
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["newly_flaky"][0], "demo::net::tests::test_retry");
+
+        let resp = app
+            .clone()
+            .oneshot(authed_get(&format!(
+                "/api/projects/{}/flaky-tests",
+                project.id
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["tests_tracked"], 2);
+        assert_eq!(json["flaky_tests"].as_array().unwrap().len(), 1);
+        assert_eq!(json["flaky_tests"][0]["history"], "FR");
+        assert_eq!(json["recent_runs"].as_array().unwrap().len(), 2);
+
+        let resp = app.oneshot(post("", "<testsuite/>")).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_get_production_errors() {
+        use crate::neo4j::models::SentryIssueNode;
+
+        let state = mock_server_state().await;
+        let project = test_project_named("sentry-errors");
+        let neo4j = state.orchestrator.neo4j();
+        neo4j.create_project(&project).await.unwrap();
+        let issue = |id: &str, last_seen: &str| SentryIssueNode {
+            project_id: project.id,
+            id: id.to_string(),
+            short_id: format!("SHOP-{}", id),
+            title: "TypeError: boom".to_string(),
+            culprit: None,
+            level: "error".to_string(),
+            events: 12,
+            users: 3,
+            first_seen: "2026-10-01T08:00:00Z".parse().unwrap(),
+            last_seen: last_seen.parse().unwrap(),
+            permalink: None,
+            function_ids: vec![],
+        };
+        neo4j
+            .replace_sentry_issues(
+                project.id,
+                &[
+                    issue("1", "2026-10-12T09:00:00Z"),
+                    issue("2", "2026-10-13T09:00:00Z"),
+                ],
+            )
+            .await
+            .unwrap();
+        let app = create_router(state);
+
+        let resp = app
+            .clone()
+            .oneshot(authed_get(&format!(
+                "/api/projects/{}/production-errors",
+                project.id
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["issues"][0]["short_id"], "SHOP-2");
+        assert_eq!(json["issues"][1]["short_id"], "SHOP-1");
+
+        let resp = app
+            .oneshot(authed_get(&format!(
+                "/api/projects/{}/production-errors",
+                uuid::Uuid::new_v4()
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_semver_advice() {
+        use crate::neo4j::models::{FunctionNode, Visibility};
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nversion = \"1.2.3\"\n",
+        )
+        .unwrap();
+        let state = mock_server_state().await;
+        let mut project = test_project_named("released");
+        project.root_path = dir.path().to_string_lossy().to_string();
+        let neo4j = state.orchestrator.neo4j();
+        neo4j.create_project(&project).await.unwrap();
+        let file_path = format!("{}/src/lib.rs", project.root_path);
+        neo4j
+            .link_file_to_project(&file_path, project.id)
+            .await
+            .unwrap();
+        let function = |name: &str| FunctionNode {
+            name: name.to_string(),
+            visibility: Visibility::Public,
+            params: vec![],
+            return_type: None,
+            generics: vec![],
+            is_async: false,
+            is_unsafe: false,
+            complexity: 1,
+            file_path: file_path.clone(),
+            line_start: 1,
+            line_end: 3,
+            docstring: None,
+        };
+        neo4j.upsert_function(&function("load")).await.unwrap();
+        let snapshot = crate::orchestrator::api_surface::record_snapshot(neo4j, project.id)
+            .await
+            .unwrap()
+            .unwrap();
+        neo4j.upsert_function(&function("reload")).await.unwrap();
+        let app = create_router(state);
+
+        let uri = format!(
+            "/api/projects/{}/semver-advice?from={}",
+            project.id, snapshot.id
+        );
+        let resp = app.clone().oneshot(authed_get(&uri)).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["bump"], "minor");
+        assert_eq!(json["current_version"], "1.2.3");
+        assert_eq!(json["version_source"], "Cargo.toml");
+        assert_eq!(json["next_version"], "1.3.0");
+        assert_eq!(json["added"][0]["name"], "reload");
+
+        let uri = format!(
+            "/api/projects/{}/semver-advice?from={}&version=0.4.1",
+            project.id, snapshot.id
+        );
+        let resp = app.oneshot(authed_get(&uri)).await.unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["next_version"], "0.4.2");
+    }
+
+    #[tokio::test]
+    async fn test_project_sync_limits() {
+        let state = mock_server_state().await;
+        let project = test_project_named("limited");
+        state
+            .orchestrator
+            .neo4j()
+            .create_project(&project)
+            .await
+            .unwrap();
+        let app = create_router(state);
+        let uri = format!("/api/projects/{}/sync-limits", project.id);
+        let put = |body: serde_json::Value| {
+            Request::builder()
+                .method("PUT")
+                .uri(&uri)
+                .header("content-type", "application/json")
+                .header("authorization", test_bearer_token())
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let resp = app.clone().oneshot(authed_get(&uri)).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["max_file_bytes"], 1024 * 1024);
+        assert_eq!(json["skip_binary"], true);
+
+        let resp = app
+            .clone()
+            .oneshot(put(serde_json::json!({"max_file_bytes": 0})))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::BAD_REQUEST);
+
+        let resp = app
+            .clone()
+            .oneshot(put(serde_json::json!({
+                "max_file_bytes": 50000,
+                "excluded_extensions": [".MIN.js", "min.js", "lock"],
+            })))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+
+        let resp = app.oneshot(authed_get(&uri)).await.unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["max_file_bytes"], 50000);
+        assert_eq!(json["skip_binary"], true);
+        assert_eq!(
+            json["excluded_extensions"],
+            serde_json::json!(["min.js", "lock"])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_review_project_diff() {
+        use crate::neo4j::models::{FileNode, FunctionNode, Visibility};
+
+        let state = mock_server_state().await;
+        let mut project = test_project_named("reviewed");
+        project.root_path = "/repo".to_string();
+        let neo4j = state.orchestrator.neo4j();
+        neo4j.create_project(&project).await.unwrap();
+        neo4j
+            .upsert_file(&FileNode {
+                path: "/repo/src/lib.rs".to_string(),
+                language: "rust".to_string(),
+                hash: "h".to_string(),
+                line_count: 0,
+                last_parsed: chrono::Utc::now(),
+                project_id: Some(project.id),
+                encoding: None,
+            })
+            .await
+            .unwrap();
+        neo4j
+            .link_file_to_project("/repo/src/lib.rs", project.id)
+            .await
+            .unwrap();
+        neo4j
+            .upsert_function(&FunctionNode {
+                name: "parse".to_string(),
+                visibility: Visibility::Public,
+                params: vec![],
+                return_type: None,
+                generics: vec![],
+                is_async: false,
+                is_unsafe: false,
+                complexity: 2,
+                file_path: "/repo/src/lib.rs".to_string(),
+                line_start: 1,
+                line_end: 6,
+                docstring: None,
+            })
+            .await
+            .unwrap();
+        let app = create_router(state);
+        let uri = format!("/api/projects/{}/review", project.id);
+
+        let diff = "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -2,2 +2,2 @@\n-    old();\n+    new();\n     done();\n";
+        let resp = app
+            .clone()
+            .oneshot(authed_post(&uri, serde_json::json!({"diff": diff})))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["files_changed"], 1);
+        assert_eq!(json["files"][0]["path"], "src/lib.rs");
+        assert_eq!(json["files"][0]["change"], "modified");
+        assert_eq!(json["files"][0]["in_graph"], true);
+        assert_eq!(json["files"][0]["symbols"][0]["name"], "parse");
+        assert_eq!(json["risk_level"], "low");
+        assert!(json.get("commentary").is_none());
+
+        let resp = app
+            .clone()
+            .oneshot(authed_post(&uri, serde_json::json!({"diff": "not a diff"})))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::BAD_REQUEST);
+
+        let resp = app
+            .oneshot(authed_post(
+                &format!("/api/projects/{}/review", Uuid::new_v4()),
+                serde_json::json!({"diff": diff}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_suggest_commit_message() {
+        let state = mock_server_state().await;
+        let project = test_project_named("committed");
+        state
+            .orchestrator
+            .neo4j()
+            .create_project(&project)
+            .await
+            .unwrap();
+        let app = create_router(state);
+        let uri = format!("/api/projects/{}/commit-message", project.id);
+
+        let diff = "--- /dev/null\n+++ b/docs/usage.md\n@@ -0,0 +1 @@\n+# Usage\n";
+        let resp = app
+            .clone()
+            .oneshot(authed_post(&uri, serde_json::json!({"diff": diff})))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["commit_type"], "docs");
+        assert_eq!(
+            json["message"],
+            "docs: add usage\n\n- docs/usage.md: new file"
+        );
+
+        let resp = app
+            .clone()
+            .oneshot(authed_post(&uri, serde_json::json!({"diff": ""})))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::BAD_REQUEST);
+
+        let resp = app
+            .oneshot(authed_post(
+                &format!("/api/projects/{}/commit-message", Uuid::new_v4()),
+                serde_json::json!({"diff": diff}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_export_adrs() {
+        use crate::test_helpers::{test_decision, test_plan_for_project, test_task};
+
+        let dir = tempfile::tempdir().unwrap();
+        let state = mock_server_state().await;
+        let neo4j = state.orchestrator.neo4j();
+        let mut project = test_project_named("decided");
+        project.root_path = dir.path().to_string_lossy().to_string();
+        neo4j.create_project(&project).await.unwrap();
+        let plan = test_plan_for_project(project.id);
+        neo4j.create_plan(&plan).await.unwrap();
+        let task = test_task();
+        neo4j.create_task(plan.id, &task).await.unwrap();
+        let decision = test_decision("Use Neo4j", "Relationships are first-class.");
+        neo4j.create_decision(task.id, &decision).await.unwrap();
+        let app = create_router(state);
+        let uri = format!("/api/projects/{}/adrs/export", project.id);
+
+        let resp = app
+            .clone()
+            .oneshot(authed_post(&uri, serde_json::json!({})))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            json["written"],
+            serde_json::json!(["docs/adr/001-use-neo4j.md"])
+        );
+        assert!(dir.path().join("docs/adr/001-use-neo4j.md").is_file());
+
+        let resp = app
+            .oneshot(authed_post(
+                &uri,
+                serde_json::json!({"decision_id": Uuid::new_v4()}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_sync_project_diff_rejects_bad_requests() {
+        let state = mock_server_state().await;
+        let project = test_project_named("overlaid");
+        state
+            .orchestrator
+            .neo4j()
+            .create_project(&project)
+            .await
+            .unwrap();
+        let app = create_router(state);
+
+        let uri = format!("/api/projects/{}/sync/diff", project.id);
+        let resp = app
+            .clone()
+            .oneshot(authed_post(
+                &uri,
+                serde_json::json!({"base": "--output=/tmp/x"}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::BAD_REQUEST);
+
+        let resp = app
+            .oneshot(authed_post(
+                &format!("/api/projects/{}/sync/diff", Uuid::new_v4()),
+                serde_json::json!({"base": "main"}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_create_context_bundle() {
+        use crate::test_helpers::{test_plan_for_project, test_task};
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "pub fn parse() {}\n").unwrap();
+        let file_path = dir.path().join("src/lib.rs").to_string_lossy().into_owned();
+
+        let state = mock_server_state().await;
+        let mut project = test_project_named("bundled");
+        project.root_path = dir.path().to_string_lossy().into_owned();
+        let other = test_project_named("elsewhere");
+        let plan = test_plan_for_project(project.id);
+        let task = test_task();
+        let neo4j = state.orchestrator.neo4j();
+        neo4j.create_project(&project).await.unwrap();
+        neo4j.create_project(&other).await.unwrap();
+        neo4j.create_plan(&plan).await.unwrap();
+        neo4j.create_task(plan.id, &task).await.unwrap();
+        neo4j
+            .upsert_file(&FileNode {
+                path: file_path.clone(),
+                language: "rust".to_string(),
+                hash: "h".to_string(),
+                line_count: 0,
+                last_parsed: chrono::Utc::now(),
+                project_id: Some(project.id),
+                encoding: None,
+            })
+            .await
+            .unwrap();
+        neo4j
+            .link_task_to_files(task.id, &[file_path])
+            .await
+            .unwrap();
+        let app = create_router(state);
+        let uri = format!("/api/projects/{}/context-bundle", project.id);
+
+        let resp = app
+            .clone()
+            .oneshot(authed_post(&uri, serde_json::json!({"task_id": task.id})))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["project"]["slug"], "bundled");
+        assert_eq!(json["plan"]["id"], plan.id.to_string());
+        assert_eq!(json["plan"]["tasks"][0]["id"], task.id.to_string());
+        assert_eq!(json["context"]["task"]["id"], task.id.to_string());
+        assert_eq!(json["files"][0]["path"], "src/lib.rs");
+        assert_eq!(json["files"][0]["content"], "pub fn parse() {}\n");
+        assert!(json["prompt"].as_str().unwrap().starts_with("# Task"));
+
+        let resp = app
+            .clone()
+            .oneshot(authed_post(
+                &uri,
+                serde_json::json!({"task_id": task.id, "format": "zip"}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        assert_eq!(resp.headers()["content-type"], "application/zip");
+        assert!(resp.headers()["content-disposition"]
+            .to_str()
+            .unwrap()
+            .contains("bundled-task-"));
+
+        // The task's plan belongs to another project
+        let resp = app
+            .oneshot(authed_post(
+                &format!("/api/projects/{}/context-bundle", other.id),
+                serde_json::json!({"task_id": task.id}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_list_projects_empty() {
+        let state = mock_server_state().await;
+        let app = create_router(state);
+
+        let resp = app.oneshot(authed_get("/api/projects")).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["total"], 0);
+        assert_eq!(json["items"].as_array().unwrap().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_list_projects_with_counts() {
+        let app_state = mock_app_state();
+
+        // Seed a project with files and plans
+        let project = test_project_named("my-proj");
+        app_state.neo4j.create_project(&project).await.unwrap();
+
+        // Add 2 files
+        for i in 0..2 {
+            let path = format!("/tmp/my-proj/file_{}.rs", i);
+            let file = FileNode {
+                path: path.clone(),
+                language: "rust".to_string(),
+                hash: format!("h{}", i),
+                line_count: 0,
+                last_parsed: chrono::Utc::now(),
+                project_id: Some(project.id),
+                encoding: None,
+            };
+            app_state.neo4j.upsert_file(&file).await.unwrap();
+            app_state
+                .neo4j
+                .link_file_to_project(&path, project.id)
+                .await
+                .unwrap();
+        }
+
+        // Add 1 plan (Draft)
+        let plan = crate::test_helpers::test_plan_for_project(project.id);
         app_state.neo4j.create_plan(&plan).await.unwrap();
         app_state
             .neo4j
@@ -978,7 +4108,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: std::sync::Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: std::sync::Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: std::sync::Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -1006,6 +4139,90 @@ mod tests {
         assert_eq!(proj["plan_count"], 1);
     }
 
+    #[tokio::test]
+    async fn test_archive_project() {
+        let state = mock_server_state().await;
+        let project = test_project_named("old-repo");
+        state
+            .orchestrator
+            .neo4j()
+            .create_project(&project)
+            .await
+            .unwrap();
+        let app = create_router(state.clone());
+        let slugs = |json: serde_json::Value| -> Vec<(String, bool)> {
+            json["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|p| {
+                    (
+                        p["slug"].as_str().unwrap().to_string(),
+                        p["archived"].as_bool().unwrap(),
+                    )
+                })
+                .collect()
+        };
+        let list = |uri: &'static str| {
+            let app = app.clone();
+            async move {
+                let resp = app.oneshot(authed_get(uri)).await.unwrap();
+                let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+            }
+        };
+
+        let resp = app
+            .clone()
+            .oneshot(authed_post(
+                "/api/projects/old-repo/archive",
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::NO_CONTENT);
+        let stored = state
+            .orchestrator
+            .neo4j()
+            .get_project(project.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(stored.archived);
+
+        // Hidden by default, listed on request
+        assert!(slugs(list("/api/projects").await).is_empty());
+        assert_eq!(
+            slugs(list("/api/projects?include_archived=true").await),
+            vec![("old-repo".to_string(), true)]
+        );
+
+        let resp = app
+            .clone()
+            .oneshot(authed_post(
+                "/api/projects/old-repo/unarchive",
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::NO_CONTENT);
+        assert_eq!(
+            slugs(list("/api/projects").await),
+            vec![("old-repo".to_string(), false)]
+        );
+
+        let resp = app
+            .oneshot(authed_post(
+                "/api/projects/missing/archive",
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn test_get_project_with_counts() {
         let app_state = mock_app_state();
@@ -1020,8 +4237,10 @@ mod tests {
                 path: path.clone(),
                 language: "rust".to_string(),
                 hash: format!("d{}", i),
+                line_count: 10 * (i + 1),
                 last_parsed: chrono::Utc::now(),
                 project_id: Some(project.id),
+                encoding: None,
             };
             app_state.neo4j.upsert_file(&file).await.unwrap();
             app_state
@@ -1030,6 +4249,11 @@ mod tests {
                 .await
                 .unwrap();
         }
+        app_state
+            .neo4j
+            .update_project_synced(project.id)
+            .await
+            .unwrap();
 
         let orchestrator = std::sync::Arc::new(Orchestrator::new(app_state).await.unwrap());
         let watcher = std::sync::Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
@@ -1049,7 +4273,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: std::sync::Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: std::sync::Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: std::sync::Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -1077,6 +4304,10 @@ mod tests {
         assert_eq!(json["file_count"], 3);
         assert_eq!(json["plan_count"], 0);
         assert_eq!(json["slug"], "detail-proj");
+        assert_eq!(json["line_count"], 60);
+        assert_eq!(json["languages"][0]["language"], "rust");
+        assert_eq!(json["languages"][0]["files"], 3);
+        assert_eq!(json["languages"][0]["lines"], 60);
     }
 
     #[tokio::test]
@@ -1140,8 +4371,10 @@ mod tests {
                 path: path.to_string(),
                 language: "rust".to_string(),
                 hash: format!("gh{}", i),
+                line_count: 0,
                 last_parsed: chrono::Utc::now(),
                 project_id: Some(project.id),
+                encoding: None,
             };
             app_state.neo4j.upsert_file(&file).await.unwrap();
             app_state
@@ -1222,7 +4455,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: std::sync::Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: std::sync::Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: std::sync::Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -1484,6 +4720,99 @@ mod tests {
         assert_eq!(resp.status(), AxumStatus::NOT_FOUND);
     }
 
+    #[tokio::test]
+    async fn test_project_overview() {
+        use crate::graph::models::FunctionAnalyticsUpdate;
+        use crate::neo4j::models::{FunctionNode, PlanStatus, TaskStatus, Visibility};
+        use crate::test_helpers::{test_plan_for_project, test_task};
+
+        let app_state = mock_app_state();
+        let project = seed_graph_project(&app_state).await;
+        let neo4j = &app_state.neo4j;
+        for (line, name, pagerank) in [(1, "main", 0.2), (10, "run", 0.7), (20, "idle", 0.0)] {
+            neo4j
+                .upsert_function(&FunctionNode {
+                    name: name.to_string(),
+                    visibility: Visibility::Public,
+                    params: vec![],
+                    return_type: None,
+                    generics: vec![],
+                    is_async: false,
+                    is_unsafe: false,
+                    complexity: 1,
+                    file_path: "/tmp/graph-proj/src/main.rs".to_string(),
+                    line_start: line,
+                    line_end: line + 5,
+                    docstring: None,
+                })
+                .await
+                .unwrap();
+            if pagerank > 0.0 {
+                neo4j
+                    .batch_update_function_analytics(&[FunctionAnalyticsUpdate {
+                        id: format!("/tmp/graph-proj/src/main.rs:{}:{}", name, line),
+                        pagerank,
+                        betweenness: 0.0,
+                        community_id: 0,
+                        clustering_coefficient: 0.0,
+                        component_id: 0,
+                    }])
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let open = test_plan_for_project(project.id);
+        neo4j.create_plan(&open).await.unwrap();
+        let mut done = test_task();
+        done.status = TaskStatus::Completed;
+        neo4j.create_task(open.id, &done).await.unwrap();
+        neo4j.create_task(open.id, &test_task()).await.unwrap();
+        let mut finished = test_plan_for_project(project.id);
+        finished.status = PlanStatus::Completed;
+        neo4j.create_plan(&finished).await.unwrap();
+
+        let app = create_router(state_from_app(app_state).await);
+        let resp = app
+            .clone()
+            .oneshot(authed_get(&format!(
+                "/api/projects/{}/overview",
+                project.slug
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["slug"], "graph-proj");
+        assert_eq!(json["languages"][0]["language"], "rust");
+        // "core" holds two files, "utilities" one
+        assert_eq!(json["communities"].as_array().unwrap().len(), 2);
+        assert_eq!(json["communities"][0]["community_label"], "core");
+        // Functions without analytics are left out
+        let names: Vec<&str> = json["top_symbols"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| s["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["run", "main"]);
+        let plans = json["open_plans"].as_array().unwrap();
+        assert_eq!(plans.len(), 1);
+        assert_eq!(plans[0]["id"], open.id.to_string());
+        assert_eq!(plans[0]["tasks_total"], 2);
+        assert_eq!(plans[0]["tasks_completed"], 1);
+        assert!(json["resources"].as_array().unwrap().is_empty());
+
+        let resp = app
+            .oneshot(authed_get("/api/projects/nonexistent/overview"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::NOT_FOUND);
+    }
+
     // ====================================================================
     // Embeddings projection tests
     // ====================================================================
@@ -0,0 +1,438 @@
+//! Prompt template API handlers
+//!
+//! User-managed chat prompts with `{{variable}}` placeholders, stored in the
+//! graph. Updating the content of a template creates a new version; earlier
+//! versions stay renderable. `POST /api/chat/sessions` accepts a `template`
+//! to start a session from one (see [`render_for_session`]).
+
+use super::handlers::{AppError, OrchestratorState};
+use super::validation::ValidPath;
+use crate::chat::templates::{self, TemplateVariable};
+use crate::chat::types::ChatTemplateRequest;
+use crate::neo4j::models::{PromptTemplateNode, PromptTemplateVersion};
+use crate::neo4j::traits::GraphStore;
+use axum::{extract::State, http::StatusCode, Json};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// A template with the variables of its content
+#[derive(Debug, Serialize)]
+pub struct PromptTemplateResponse {
+    #[serde(flatten)]
+    pub template: PromptTemplateNode,
+    pub variables: Vec<TemplateVariable>,
+}
+
+impl From<PromptTemplateNode> for PromptTemplateResponse {
+    fn from(template: PromptTemplateNode) -> Self {
+        let variables = templates::variables(&template.content);
+        Self {
+            template,
+            variables,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreatePromptTemplateRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub content: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdatePromptTemplateRequest {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// New content — creates a new version when it differs
+    #[serde(default)]
+    pub content: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RenderPromptTemplateRequest {
+    /// Version to render (default: current)
+    #[serde(default)]
+    pub version: Option<i64>,
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RenderPromptTemplateResponse {
+    pub version: i64,
+    pub content: String,
+}
+
+async fn load_template(neo4j: &dyn GraphStore, id: Uuid) -> Result<PromptTemplateNode, AppError> {
+    neo4j
+        .get_prompt_template(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Prompt template {} not found", id)))
+}
+
+/// Reject a name used by another template
+async fn check_name(
+    neo4j: &dyn GraphStore,
+    name: &str,
+    id: Option<Uuid>,
+) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::BadRequest(
+            "Template name is required".to_string(),
+        ));
+    }
+    let taken = neo4j
+        .list_prompt_templates()
+        .await?
+        .iter()
+        .any(|t| t.name == name && Some(t.id) != id);
+    if taken {
+        return Err(AppError::Conflict(format!(
+            "A prompt template named '{}' already exists",
+            name
+        )));
+    }
+    Ok(name.to_string())
+}
+
+/// Render `template` at `version` (default: current).
+async fn render_template(
+    neo4j: &dyn GraphStore,
+    template: &PromptTemplateNode,
+    version: Option<i64>,
+    variables: &HashMap<String, String>,
+) -> Result<RenderPromptTemplateResponse, AppError> {
+    let (version, content) = match version {
+        None => (template.version, template.content.clone()),
+        Some(v) if v == template.version => (v, template.content.clone()),
+        Some(v) => {
+            let found = neo4j
+                .list_prompt_template_versions(template.id)
+                .await?
+                .into_iter()
+                .find(|tv| tv.version == v)
+                .ok_or_else(|| {
+                    AppError::NotFound(format!(
+                        "Prompt template {} has no version {}",
+                        template.id, v
+                    ))
+                })?;
+            (v, found.content)
+        }
+    };
+    let content = templates::render(&content, variables).map_err(|missing| {
+        AppError::BadRequest(format!(
+            "Missing template variable(s): {}",
+            missing.join(", ")
+        ))
+    })?;
+    Ok(RenderPromptTemplateResponse { version, content })
+}
+
+/// First message of a session started from a template: the rendered
+/// template, followed by the user's message if there is one.
+pub(crate) async fn render_for_session(
+    neo4j: &dyn GraphStore,
+    request: &ChatTemplateRequest,
+    message: &str,
+) -> Result<String, AppError> {
+    let template = load_template(neo4j, request.id).await?;
+    let rendered = render_template(neo4j, &template, request.version, &request.variables).await?;
+    Ok(if message.trim().is_empty() {
+        rendered.content
+    } else {
+        format!("{}\n\n{}", rendered.content, message)
+    })
+}
+
+/// GET /api/prompt-templates — List prompt templates
+pub async fn list_prompt_templates(
+    State(state): State<OrchestratorState>,
+) -> Result<Json<Vec<PromptTemplateResponse>>, AppError> {
+    let templates = state.orchestrator.neo4j().list_prompt_templates().await?;
+    Ok(Json(templates.into_iter().map(Into::into).collect()))
+}
+
+/// POST /api/prompt-templates — Create a prompt template (version 1)
+pub async fn create_prompt_template(
+    State(state): State<OrchestratorState>,
+    Json(body): Json<CreatePromptTemplateRequest>,
+) -> Result<(StatusCode, Json<PromptTemplateResponse>), AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let name = check_name(neo4j, &body.name, None).await?;
+    let now = chrono::Utc::now();
+    let template = PromptTemplateNode {
+        id: Uuid::new_v4(),
+        name,
+        description: body.description.filter(|d| !d.trim().is_empty()),
+        content: body.content,
+        version: 1,
+        created_at: now,
+        updated_at: now,
+    };
+    neo4j.create_prompt_template(&template).await?;
+    Ok((StatusCode::CREATED, Json(template.into())))
+}
+
+/// GET /api/prompt-templates/{id} — Get a prompt template
+pub async fn get_prompt_template(
+    State(state): State<OrchestratorState>,
+    ValidPath(id): ValidPath<Uuid>,
+) -> Result<Json<PromptTemplateResponse>, AppError> {
+    let template = load_template(state.orchestrator.neo4j(), id).await?;
+    Ok(Json(template.into()))
+}
+
+/// PATCH /api/prompt-templates/{id} — Update a prompt template. A new
+/// content bumps its version.
+pub async fn update_prompt_template(
+    State(state): State<OrchestratorState>,
+    ValidPath(id): ValidPath<Uuid>,
+    Json(body): Json<UpdatePromptTemplateRequest>,
+) -> Result<Json<PromptTemplateResponse>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let mut template = load_template(neo4j, id).await?;
+    if let Some(ref name) = body.name {
+        template.name = check_name(neo4j, name, Some(id)).await?;
+    }
+    if let Some(description) = body.description {
+        template.description = Some(description).filter(|d| !d.trim().is_empty());
+    }
+    if let Some(content) = body.content {
+        if content != template.content {
+            template.content = content;
+            template.version += 1;
+        }
+    }
+    template.updated_at = chrono::Utc::now();
+    neo4j.update_prompt_template(&template).await?;
+    Ok(Json(template.into()))
+}
+
+/// DELETE /api/prompt-templates/{id} — Delete a prompt template and its versions
+pub async fn delete_prompt_template(
+    State(state): State<OrchestratorState>,
+    ValidPath(id): ValidPath<Uuid>,
+) -> Result<StatusCode, AppError> {
+    if state
+        .orchestrator
+        .neo4j()
+        .delete_prompt_template(id)
+        .await?
+    {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(AppError::NotFound(format!(
+            "Prompt template {} not found",
+            id
+        )))
+    }
+}
+
+/// GET /api/prompt-templates/{id}/versions — Versions of a template, newest first
+pub async fn list_prompt_template_versions(
+    State(state): State<OrchestratorState>,
+    ValidPath(id): ValidPath<Uuid>,
+) -> Result<Json<Vec<PromptTemplateVersion>>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    load_template(neo4j, id).await?;
+    Ok(Json(neo4j.list_prompt_template_versions(id).await?))
+}
+
+/// POST /api/prompt-templates/{id}/render — Render a template with variables
+pub async fn render_prompt_template(
+    State(state): State<OrchestratorState>,
+    ValidPath(id): ValidPath<Uuid>,
+    body: Option<Json<RenderPromptTemplateRequest>>,
+) -> Result<Json<RenderPromptTemplateResponse>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let body = body.map(|Json(b)| b).unwrap_or_default();
+    let template = load_template(neo4j, id).await?;
+    Ok(Json(
+        render_template(neo4j, &template, body.version, &body.variables).await?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::handlers::ServerState;
+    use crate::api::routes::create_router;
+    use crate::orchestrator::watcher::FileWatcher;
+    use crate::orchestrator::Orchestrator;
+    use crate::test_helpers::{mock_app_state, test_auth_config, test_bearer_token};
+    use axum::{body::Body, http::Request};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    async fn mock_server_state() -> OrchestratorState {
+        let app_state = mock_app_state();
+        let orchestrator = Arc::new(Orchestrator::new(app_state).await.unwrap());
+        let watcher = Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
+            orchestrator.clone(),
+        )));
+        Arc::new(ServerState {
+            orchestrator,
+            watcher,
+            chat_manager: None,
+            event_bus: Arc::new(crate::events::HybridEmitter::new(Arc::new(
+                crate::events::EventBus::default(),
+            ))),
+            nats_emitter: None,
+            auth_config: Some(test_auth_config()),
+            serve_frontend: false,
+            frontend_path: "./dist".to_string(),
+            setup_completed: true,
+            server_port: 6600,
+            public_url: None,
+            read_only: false,
+            ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
+            registry_remote_url: None,
+            oidc_client: None,
+            neural_router: crate::test_helpers::mock_neural_router(),
+            trajectory_collector: std::sync::RwLock::new(None),
+            trajectory_store_neo4j: None,
+            trajectory_store: None,
+            identity: None,
+            reactor_counters: std::sync::OnceLock::new(),
+            confidence_tracker: Arc::new(crate::graph::confidence::ConfidenceTracker::default()),
+            mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
+            model_catalog: crate::chat::model_catalog::ModelCatalogCache::new(None),
+        })
+    }
+
+    fn request(method: &str, uri: &str, body: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("authorization", test_bearer_token())
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    async fn json_body(resp: axum::response::Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_prompt_template_versions_and_render() {
+        let state = mock_server_state().await;
+        let app = create_router(state.clone());
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/api/prompt-templates",
+                serde_json::json!({"name": "review", "content": "Review {{file}}"}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let created = json_body(resp).await;
+        assert_eq!(created["version"], 1);
+        assert_eq!(created["variables"][0]["name"], "file");
+        let id = created["id"].as_str().unwrap().to_string();
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/api/prompt-templates",
+                serde_json::json!({"name": "review", "content": "x"}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "PATCH",
+                &format!("/api/prompt-templates/{}", id),
+                serde_json::json!({"content": "Review {{file}} for {{focus|bugs}}"}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(json_body(resp).await["version"], 2);
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "GET",
+                &format!("/api/prompt-templates/{}/versions", id),
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        let versions = json_body(resp).await;
+        assert_eq!(versions.as_array().unwrap().len(), 2);
+        assert_eq!(versions[0]["version"], 2);
+
+        let render = |body: serde_json::Value| {
+            request(
+                "POST",
+                &format!("/api/prompt-templates/{}/render", id),
+                body,
+            )
+        };
+        let resp = app
+            .clone()
+            .oneshot(render(serde_json::json!({"variables": {"file": "a.rs"}})))
+            .await
+            .unwrap();
+        assert_eq!(json_body(resp).await["content"], "Review a.rs for bugs");
+
+        let resp = app
+            .clone()
+            .oneshot(render(
+                serde_json::json!({"version": 1, "variables": {"file": "a.rs"}}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(json_body(resp).await["content"], "Review a.rs");
+
+        let resp = app
+            .clone()
+            .oneshot(render(serde_json::json!({})))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let message = render_for_session(
+            state.orchestrator.neo4j(),
+            &ChatTemplateRequest {
+                id: id.parse().unwrap(),
+                version: None,
+                variables: HashMap::from([("file".to_string(), "b.rs".to_string())]),
+            },
+            "Focus on the error paths.",
+        )
+        .await
+        .unwrap();
+        assert_eq!(message, "Review b.rs for bugs\n\nFocus on the error paths.");
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "DELETE",
+                &format!("/api/prompt-templates/{}", id),
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    }
+}
@@ -1,6 +1,7 @@
 //! API handlers for Protocol (Pattern Federation)
 
 use super::handlers::{AppError, OrchestratorState};
+use super::validation::ValidPath;
 use super::{PaginatedResponse, PaginationParams};
 use crate::events::EventEmitter;
 use crate::protocol::{
@@ -8,7 +9,7 @@ use crate::protocol::{
     TransitionResult,
 };
 use axum::{
-    extract::{Path, Query, State},
+    extract::{Query, State},
     http::StatusCode,
     Json,
 };
@@ -61,7 +62,8 @@ fn validate_transition_state_types(
 ///
 /// - Messages containing "not found" → `AppError::NotFound`
 /// - Messages containing "OptimisticLockError" → `AppError::Conflict`
-/// - Everything else → `AppError::Internal`
+/// - Everything else → `AppError::from` (`Unavailable` for store failures,
+///   `Internal` otherwise)
 fn map_fire_transition_error(e: anyhow::Error) -> AppError {
     let msg = e.to_string();
     if msg.contains("not found") {
@@ -69,7 +71,7 @@ fn map_fire_transition_error(e: anyhow::Error) -> AppError {
     } else if msg.contains("OptimisticLockError") {
         AppError::Conflict(msg)
     } else {
-        AppError::Internal(e)
+        AppError::from(e)
     }
 }
 
@@ -602,7 +604,7 @@ pub async fn create_protocol(
 /// GET /api/protocols/:id
 pub async fn get_protocol(
     State(state): State<OrchestratorState>,
-    Path(protocol_id): Path<Uuid>,
+    ValidPath(protocol_id): ValidPath<Uuid>,
 ) -> Result<Json<ProtocolDetail>, AppError> {
     let protocol = state
         .orchestrator
@@ -638,7 +640,7 @@ pub async fn get_protocol(
 /// PUT /api/protocols/:id
 pub async fn update_protocol(
     State(state): State<OrchestratorState>,
-    Path(protocol_id): Path<Uuid>,
+    ValidPath(protocol_id): ValidPath<Uuid>,
     Json(body): Json<UpdateProtocolBody>,
 ) -> Result<Json<Protocol>, AppError> {
     let mut protocol = state
@@ -716,7 +718,7 @@ pub async fn update_protocol(
 /// DELETE /api/protocols/:id
 pub async fn delete_protocol(
     State(state): State<OrchestratorState>,
-    Path(protocol_id): Path<Uuid>,
+    ValidPath(protocol_id): ValidPath<Uuid>,
 ) -> Result<StatusCode, AppError> {
     let deleted = state
         .orchestrator
@@ -749,7 +751,7 @@ pub async fn delete_protocol(
 /// POST /api/protocols/:id/states
 pub async fn add_state(
     State(state): State<OrchestratorState>,
-    Path(protocol_id): Path<Uuid>,
+    ValidPath(protocol_id): ValidPath<Uuid>,
     Json(body): Json<AddStateBody>,
 ) -> Result<(StatusCode, Json<ProtocolState>), AppError> {
     // Verify protocol exists
@@ -841,7 +843,7 @@ pub async fn add_state(
 /// GET /api/protocols/:id/states
 pub async fn list_states(
     State(state): State<OrchestratorState>,
-    Path(protocol_id): Path<Uuid>,
+    ValidPath(protocol_id): ValidPath<Uuid>,
 ) -> Result<Json<Vec<ProtocolState>>, AppError> {
     let states = state
         .orchestrator
@@ -858,7 +860,7 @@ pub async fn list_states(
 /// DELETE /api/protocols/:protocol_id/states/:state_id
 pub async fn delete_state(
     State(state): State<OrchestratorState>,
-    Path((protocol_id, state_id)): Path<(Uuid, Uuid)>,
+    ValidPath((protocol_id, state_id)): ValidPath<(Uuid, Uuid)>,
 ) -> Result<StatusCode, AppError> {
     let deleted = state
         .orchestrator
@@ -894,7 +896,7 @@ pub async fn delete_state(
 /// POST /api/protocols/:id/transitions
 pub async fn add_transition(
     State(state): State<OrchestratorState>,
-    Path(protocol_id): Path<Uuid>,
+    ValidPath(protocol_id): ValidPath<Uuid>,
     Json(body): Json<AddTransitionBody>,
 ) -> Result<(StatusCode, Json<ProtocolTransition>), AppError> {
     // Verify protocol exists
@@ -975,7 +977,7 @@ pub async fn add_transition(
 /// GET /api/protocols/:id/transitions
 pub async fn list_transitions(
     State(state): State<OrchestratorState>,
-    Path(protocol_id): Path<Uuid>,
+    ValidPath(protocol_id): ValidPath<Uuid>,
 ) -> Result<Json<Vec<ProtocolTransition>>, AppError> {
     let transitions = state
         .orchestrator
@@ -992,7 +994,7 @@ pub async fn list_transitions(
 /// DELETE /api/protocols/:protocol_id/transitions/:transition_id
 pub async fn delete_transition(
     State(state): State<OrchestratorState>,
-    Path((protocol_id, transition_id)): Path<(Uuid, Uuid)>,
+    ValidPath((protocol_id, transition_id)): ValidPath<(Uuid, Uuid)>,
 ) -> Result<StatusCode, AppError> {
     let deleted = state
         .orchestrator
@@ -1028,7 +1030,7 @@ pub async fn delete_transition(
 /// POST /api/protocols/:id/link-skill
 pub async fn link_to_skill(
     State(state): State<OrchestratorState>,
-    Path(protocol_id): Path<Uuid>,
+    ValidPath(protocol_id): ValidPath<Uuid>,
     Json(body): Json<LinkToSkillBody>,
 ) -> Result<Json<Protocol>, AppError> {
     let mut protocol = state
@@ -1070,7 +1072,7 @@ pub async fn link_to_skill(
 /// POST /api/protocols/:id/runs
 pub async fn start_run(
     State(state): State<OrchestratorState>,
-    Path(protocol_id): Path<Uuid>,
+    ValidPath(protocol_id): ValidPath<Uuid>,
     Json(body): Json<StartRunBody>,
 ) -> Result<(StatusCode, Json<ProtocolRun>), AppError> {
     let run = protocol::engine::start_run(
@@ -1088,7 +1090,7 @@ pub async fn start_run(
         } else if msg.contains("concurrent run") {
             AppError::Conflict(msg)
         } else {
-            AppError::Internal(e)
+            AppError::from(e)
         }
     })?;
 
@@ -1112,7 +1114,7 @@ pub async fn start_run(
 /// POST /api/protocols/runs/:run_id/transition
 pub async fn fire_transition(
     State(state): State<OrchestratorState>,
-    Path(run_id): Path<Uuid>,
+    ValidPath(run_id): ValidPath<Uuid>,
     Json(body): Json<FireTransitionBody>,
 ) -> Result<Json<TransitionResult>, AppError> {
     let result =
@@ -1211,7 +1213,7 @@ pub struct EnrichedProtocolRun {
 /// GET /api/protocols/runs/:run_id
 pub async fn get_run(
     State(state): State<OrchestratorState>,
-    Path(run_id): Path<Uuid>,
+    ValidPath(run_id): ValidPath<Uuid>,
 ) -> Result<Json<EnrichedProtocolRun>, AppError> {
     let neo4j = state.orchestrator.neo4j();
     let run = neo4j
@@ -1236,7 +1238,7 @@ pub async fn get_run(
 /// GET /api/protocols/runs/:run_id/children
 pub async fn get_run_children(
     State(state): State<OrchestratorState>,
-    Path(run_id): Path<Uuid>,
+    ValidPath(run_id): ValidPath<Uuid>,
 ) -> Result<Json<Vec<ProtocolRun>>, AppError> {
     // Verify parent exists
     let neo4j = state.orchestrator.neo4j();
@@ -1267,7 +1269,7 @@ pub struct RunTreeNode {
 /// GET /api/protocols/runs/:run_id/tree
 pub async fn get_run_tree(
     State(state): State<OrchestratorState>,
-    Path(run_id): Path<Uuid>,
+    ValidPath(run_id): ValidPath<Uuid>,
 ) -> Result<Json<RunTreeNode>, AppError> {
     let neo4j = state.orchestrator.neo4j();
     let root = neo4j
@@ -1308,7 +1310,7 @@ pub async fn get_run_tree(
 /// GET /api/protocols/:id/runs
 pub async fn list_runs(
     State(state): State<OrchestratorState>,
-    Path(protocol_id): Path<Uuid>,
+    ValidPath(protocol_id): ValidPath<Uuid>,
     Query(query): Query<RunsListQuery>,
 ) -> Result<Json<PaginatedResponse<ProtocolRun>>, AppError> {
     let status_filter: Option<RunStatus> = query.status.as_deref().and_then(|s| s.parse().ok());
@@ -1337,7 +1339,7 @@ pub async fn list_runs(
 /// POST /api/protocols/runs/:run_id/cancel
 pub async fn cancel_run(
     State(state): State<OrchestratorState>,
-    Path(run_id): Path<Uuid>,
+    ValidPath(run_id): ValidPath<Uuid>,
 ) -> Result<Json<ProtocolRun>, AppError> {
     let run = protocol::engine::cancel_run(state.orchestrator.neo4j(), run_id)
         .await
@@ -1366,7 +1368,7 @@ pub async fn cancel_run(
 /// POST /api/protocols/runs/:run_id/fail
 pub async fn fail_run(
     State(state): State<OrchestratorState>,
-    Path(run_id): Path<Uuid>,
+    ValidPath(run_id): ValidPath<Uuid>,
     Json(body): Json<serde_json::Value>,
 ) -> Result<Json<ProtocolRun>, AppError> {
     let error_msg = body
@@ -1421,7 +1423,7 @@ pub async fn fail_run(
 /// Only accepted for runs with status `running`.
 pub async fn report_progress(
     State(state): State<OrchestratorState>,
-    Path(run_id): Path<Uuid>,
+    ValidPath(run_id): ValidPath<Uuid>,
     Json(body): Json<ReportProgressBody>,
 ) -> Result<StatusCode, AppError> {
     // Verify the run exists and is active
@@ -1480,7 +1482,7 @@ pub struct ReportProgressBody {
 /// DELETE /api/protocols/runs/:run_id
 pub async fn delete_run(
     State(state): State<OrchestratorState>,
-    Path(run_id): Path<Uuid>,
+    ValidPath(run_id): ValidPath<Uuid>,
 ) -> Result<StatusCode, AppError> {
     let deleted = state
         .orchestrator
@@ -2124,7 +2126,10 @@ mod tests {
             frontend_path: String::new(),
             server_port: 0,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             identity: None,
             reactor_counters: std::sync::OnceLock::new(),
@@ -2258,7 +2263,7 @@ mod tests {
             trigger: "go_back".to_string(),
             guard: None,
         };
-        let result = add_transition(State(state.clone()), Path(proto_id), Json(body)).await;
+        let result = add_transition(State(state.clone()), ValidPath(proto_id), Json(body)).await;
         assert!(result.is_err());
         match result.unwrap_err() {
             AppError::BadRequest(msg) => {
@@ -2287,7 +2292,7 @@ mod tests {
             trigger: "restart".to_string(),
             guard: None,
         };
-        let result = add_transition(State(state.clone()), Path(proto_id), Json(body)).await;
+        let result = add_transition(State(state.clone()), ValidPath(proto_id), Json(body)).await;
         assert!(result.is_err());
         match result.unwrap_err() {
             AppError::BadRequest(msg) => {
@@ -2316,7 +2321,7 @@ mod tests {
             trigger: "begin".to_string(),
             guard: None,
         };
-        let result = add_transition(State(state.clone()), Path(proto_id), Json(body)).await;
+        let result = add_transition(State(state.clone()), ValidPath(proto_id), Json(body)).await;
         assert!(result.is_ok(), "Should succeed: {:?}", result.err());
     }
 
@@ -2328,7 +2333,7 @@ mod tests {
         let body = FireTransitionBody {
             trigger: "go".to_string(),
         };
-        let result = fire_transition(State(state), Path(fake_run_id), Json(body)).await;
+        let result = fire_transition(State(state), ValidPath(fake_run_id), Json(body)).await;
         assert!(result.is_err());
         match result.unwrap_err() {
             AppError::NotFound(msg) => {
@@ -2382,7 +2387,7 @@ mod tests {
         let body = FireTransitionBody {
             trigger: "begin".to_string(),
         };
-        let result = fire_transition(State(state.clone()), Path(run.id), Json(body)).await;
+        let result = fire_transition(State(state.clone()), ValidPath(run.id), Json(body)).await;
         assert!(
             result.is_ok(),
             "Fire transition should succeed: {:?}",
@@ -2413,17 +2418,20 @@ mod tests {
     #[tokio::test]
     async fn test_handler_get_protocol_ok_and_not_found() {
         let (state, _project_id, proto_id) = make_state_with_protocol().await;
-        assert!(get_protocol(State(state.clone()), Path(proto_id))
+        assert!(get_protocol(State(state.clone()), ValidPath(proto_id))
             .await
             .is_ok());
-        let missing = get_protocol(State(state), Path(Uuid::new_v4())).await;
+        let missing = get_protocol(State(state), ValidPath(Uuid::new_v4())).await;
         assert!(matches!(missing, Err(AppError::NotFound(_))));
     }
 
     #[tokio::test]
     async fn test_handler_list_states_returns_seeded_three() {
         let (state, _project_id, proto_id) = make_state_with_protocol().await;
-        let states = list_states(State(state), Path(proto_id)).await.unwrap().0;
+        let states = list_states(State(state), ValidPath(proto_id))
+            .await
+            .unwrap()
+            .0;
         assert_eq!(states.len(), 3);
         assert!(states.iter().any(|s| s.name == "start"));
         assert!(states.iter().any(|s| s.name == "done"));
@@ -2432,7 +2440,7 @@ mod tests {
     #[tokio::test]
     async fn test_handler_list_transitions_ok_empty() {
         let (state, _project_id, proto_id) = make_state_with_protocol().await;
-        let transitions = list_transitions(State(state), Path(proto_id))
+        let transitions = list_transitions(State(state), ValidPath(proto_id))
             .await
             .unwrap()
             .0;
@@ -2451,9 +2459,12 @@ mod tests {
             available_tools: None,
             forbidden_actions: None,
         };
-        let added = add_state(State(state.clone()), Path(proto_id), Json(body)).await;
+        let added = add_state(State(state.clone()), ValidPath(proto_id), Json(body)).await;
         assert!(added.is_ok(), "add_state failed: {:?}", added.err());
-        let states = list_states(State(state), Path(proto_id)).await.unwrap().0;
+        let states = list_states(State(state), ValidPath(proto_id))
+            .await
+            .unwrap()
+            .0;
         assert_eq!(states.len(), 4);
         assert!(states.iter().any(|s| s.name == "review"));
     }
@@ -2461,14 +2472,17 @@ mod tests {
     #[tokio::test]
     async fn test_handler_delete_state_ok() {
         let (state, _project_id, proto_id) = make_state_with_protocol().await;
-        let states = list_states(State(state.clone()), Path(proto_id))
+        let states = list_states(State(state.clone()), ValidPath(proto_id))
             .await
             .unwrap()
             .0;
         let target = states.iter().find(|s| s.name == "work").unwrap().id;
-        let res = delete_state(State(state.clone()), Path((proto_id, target))).await;
+        let res = delete_state(State(state.clone()), ValidPath((proto_id, target))).await;
         assert!(res.is_ok(), "delete_state failed: {:?}", res.err());
-        let after = list_states(State(state), Path(proto_id)).await.unwrap().0;
+        let after = list_states(State(state), ValidPath(proto_id))
+            .await
+            .unwrap()
+            .0;
         assert_eq!(after.len(), 2);
     }
 
@@ -2483,7 +2497,7 @@ mod tests {
             trigger_config: None,
             relevance_vector: None,
         };
-        let res = update_protocol(State(state), Path(proto_id), Json(body)).await;
+        let res = update_protocol(State(state), ValidPath(proto_id), Json(body)).await;
         assert!(res.is_ok(), "update_protocol failed: {:?}", res.err());
         assert_eq!(res.unwrap().0.name, "renamed-proto");
     }
@@ -2491,17 +2505,17 @@ mod tests {
     #[tokio::test]
     async fn test_handler_delete_protocol_ok() {
         let (state, _project_id, proto_id) = make_state_with_protocol().await;
-        let res = delete_protocol(State(state.clone()), Path(proto_id)).await;
+        let res = delete_protocol(State(state.clone()), ValidPath(proto_id)).await;
         assert!(res.is_ok(), "delete_protocol failed: {:?}", res.err());
         // After deletion the protocol is gone.
-        let gone = get_protocol(State(state), Path(proto_id)).await;
+        let gone = get_protocol(State(state), ValidPath(proto_id)).await;
         assert!(matches!(gone, Err(AppError::NotFound(_))));
     }
 
     #[tokio::test]
     async fn test_handler_get_run_not_found() {
         let (state, _project_id, _proto_id) = make_state_with_protocol().await;
-        let res = get_run(State(state), Path(Uuid::new_v4())).await;
+        let res = get_run(State(state), ValidPath(Uuid::new_v4())).await;
         assert!(matches!(res, Err(AppError::NotFound(_))));
     }
 
@@ -2512,7 +2526,7 @@ mod tests {
             pagination: PaginationParams::default(),
             status: None,
         };
-        let res = list_runs(State(state), Path(proto_id), axum::extract::Query(q)).await;
+        let res = list_runs(State(state), ValidPath(proto_id), axum::extract::Query(q)).await;
         assert!(res.is_ok(), "list_runs failed: {:?}", res.err());
     }
 }
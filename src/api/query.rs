@@ -3,8 +3,9 @@
 use serde::{Deserialize, Deserializer, Serialize};
 use std::str::FromStr;
 
-/// Helper to deserialize numbers from query string (which are always strings)
-fn deserialize_from_str<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+/// Helper to deserialize numbers and booleans from query string (which are
+/// always strings, even through `#[serde(flatten)]`)
+pub(crate) fn deserialize_from_str<'de, D, T>(deserializer: D) -> Result<T, D::Error>
 where
     D: Deserializer<'de>,
     T: FromStr + Default,
@@ -0,0 +1,713 @@
+//! Quota API handlers and enforcement
+//!
+//! Quotas cap what tenants of a shared deployment use: projects, indexed
+//! files, active chat sessions and the estimated storage of the indexed
+//! sources. They apply to the deployment as a whole and to each workspace
+//! (the server-wide default or the workspace's own limits). The `check_*`
+//! helpers are called by the handlers creating the resources: counted
+//! resources are refused once the limit is reached (403, 429 for chat
+//! sessions, which free up), measured ones once a sync left them above it.
+//!
+//! Adjusting quotas is reserved to the root account (anyone in no-auth mode).
+
+use super::handlers::{AppError, OrchestratorState};
+use crate::auth::extractor::AuthUser;
+use crate::neo4j::models::{
+    ChatSessionNode, ProjectNode, QuotaLimits, QuotaSettings, QuotaUsage, WorkspaceNode,
+};
+use crate::neo4j::traits::GraphStore;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Serialize;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+// ============================================================================
+// Usage and limits
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Resource {
+    Projects,
+    IndexedFiles,
+    ChatSessions,
+    Storage,
+}
+
+const MIB: u64 = 1024 * 1024;
+
+impl Resource {
+    fn limit(self, limits: &QuotaLimits) -> Option<u64> {
+        match self {
+            Resource::Projects => limits.max_projects,
+            Resource::IndexedFiles => limits.max_indexed_files,
+            Resource::ChatSessions => limits.max_chat_sessions,
+            Resource::Storage => limits.max_storage_mb.map(|mb| mb.saturating_mul(MIB)),
+        }
+    }
+
+    fn used(self, usage: &QuotaUsage) -> u64 {
+        match self {
+            Resource::Projects => usage.projects,
+            Resource::IndexedFiles => usage.indexed_files,
+            Resource::ChatSessions => usage.chat_sessions,
+            Resource::Storage => usage.storage_bytes,
+        }
+    }
+
+    /// Counted resources are checked before one is added, measured ones
+    /// (a sync may add any number of files) once they exceed the limit.
+    fn blocks(self, used: u64, limit: u64) -> bool {
+        match self {
+            Resource::Projects | Resource::ChatSessions => used >= limit,
+            Resource::IndexedFiles | Resource::Storage => used > limit,
+        }
+    }
+
+    fn describe(self, limit: u64) -> String {
+        match self {
+            Resource::Projects => format!("{} project(s)", limit),
+            Resource::IndexedFiles => format!("{} indexed file(s)", limit),
+            Resource::ChatSessions => format!("{} active chat session(s)", limit),
+            Resource::Storage => format!("{} MiB of indexed sources", limit / MIB),
+        }
+    }
+}
+
+/// Message for `resource` when `usage` is at its limit in `limits`
+fn quota_error(
+    tenant: &str,
+    limits: &QuotaLimits,
+    usage: &QuotaUsage,
+    resource: Resource,
+) -> Option<String> {
+    let limit = resource.limit(limits)?;
+    resource.blocks(resource.used(usage), limit).then(|| {
+        format!(
+            "{} has reached its quota of {}",
+            tenant,
+            resource.describe(limit)
+        )
+    })
+}
+
+fn workspace_tenant(workspace: &WorkspaceNode) -> String {
+    format!("Workspace '{}'", workspace.slug)
+}
+
+const DEPLOYMENT: &str = "The deployment";
+
+async fn quota_settings(neo4j: &dyn GraphStore) -> Result<QuotaSettings, AppError> {
+    Ok(neo4j.get_quota_settings().await?.unwrap_or_default())
+}
+
+/// Limits of `workspace`, and whether they are its own
+async fn workspace_limits(
+    neo4j: &dyn GraphStore,
+    settings: &QuotaSettings,
+    workspace: &WorkspaceNode,
+) -> Result<(QuotaLimits, bool), AppError> {
+    Ok(match neo4j.get_workspace_quotas(workspace.id).await? {
+        Some(limits) => (limits, true),
+        None => (settings.workspace_default.clone(), false),
+    })
+}
+
+/// Stored records of the active chat sessions
+async fn active_chat_sessions(state: &OrchestratorState) -> Result<Vec<ChatSessionNode>, AppError> {
+    let Some(chat_manager) = state.chat_manager.as_ref() else {
+        return Ok(Vec::new());
+    };
+    let neo4j = state.orchestrator.neo4j();
+    let mut sessions = Vec::new();
+    for id in chat_manager.active_session_ids().await {
+        let Ok(id) = id.parse::<Uuid>() else {
+            continue;
+        };
+        if let Some(session) = neo4j.get_chat_session(id).await? {
+            sessions.push(session);
+        }
+    }
+    Ok(sessions)
+}
+
+/// Active sessions started in `workspace` or one of its `projects`
+fn workspace_sessions(
+    sessions: &[ChatSessionNode],
+    workspace: &WorkspaceNode,
+    projects: &[ProjectNode],
+) -> u64 {
+    let slugs: HashSet<&str> = projects.iter().map(|p| p.slug.as_str()).collect();
+    sessions
+        .iter()
+        .filter(|s| {
+            s.workspace_slug.as_deref() == Some(workspace.slug.as_str())
+                || s.project_slug.as_deref().is_some_and(|p| slugs.contains(p))
+        })
+        .count() as u64
+}
+
+/// Indexed files and their size on disk (files no longer on disk count
+/// for nothing).
+async fn indexed_usage(
+    neo4j: &dyn GraphStore,
+    projects: &[ProjectNode],
+    with_storage: bool,
+) -> Result<(u64, u64), AppError> {
+    let (mut files, mut bytes) = (0, 0);
+    for project in projects {
+        if with_storage {
+            let indexed = neo4j.list_project_files(project.id).await?;
+            files += indexed.len() as u64;
+            for file in indexed {
+                if let Ok(metadata) = tokio::fs::metadata(&file.path).await {
+                    bytes += metadata.len();
+                }
+            }
+        } else {
+            files += neo4j.count_project_files(project.id).await?.max(0) as u64;
+        }
+    }
+    Ok((files, bytes))
+}
+
+async fn usage_of(
+    neo4j: &dyn GraphStore,
+    projects: &[ProjectNode],
+    chat_sessions: u64,
+) -> Result<QuotaUsage, AppError> {
+    let (indexed_files, storage_bytes) = indexed_usage(neo4j, projects, true).await?;
+    Ok(QuotaUsage {
+        projects: projects.len() as u64,
+        indexed_files,
+        chat_sessions,
+        storage_bytes,
+    })
+}
+
+// ============================================================================
+// Enforcement
+// ============================================================================
+
+/// Refuse a new project in the deployment, or in `workspace` (403).
+pub(crate) async fn check_project_quota(
+    state: &OrchestratorState,
+    workspace: Option<&WorkspaceNode>,
+) -> Result<(), AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let settings = quota_settings(neo4j).await?;
+
+    if settings.deployment.max_projects.is_some() {
+        let usage = QuotaUsage {
+            projects: neo4j.list_projects().await?.len() as u64,
+            ..Default::default()
+        };
+        if let Some(msg) = quota_error(DEPLOYMENT, &settings.deployment, &usage, Resource::Projects)
+        {
+            return Err(AppError::Forbidden(msg));
+        }
+    }
+    if let Some(workspace) = workspace {
+        let (limits, _) = workspace_limits(neo4j, &settings, workspace).await?;
+        if limits.max_projects.is_some() {
+            let usage = QuotaUsage {
+                projects: neo4j.list_workspace_projects(workspace.id).await?.len() as u64,
+                ..Default::default()
+            };
+            let tenant = workspace_tenant(workspace);
+            if let Some(msg) = quota_error(&tenant, &limits, &usage, Resource::Projects) {
+                return Err(AppError::Forbidden(msg));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// First indexing quota (files, storage) `projects` exceed in `limits`
+async fn index_quota_error(
+    neo4j: &dyn GraphStore,
+    tenant: &str,
+    limits: &QuotaLimits,
+    projects: &[ProjectNode],
+) -> Result<Option<String>, AppError> {
+    if limits.max_indexed_files.is_none() && limits.max_storage_mb.is_none() {
+        return Ok(None);
+    }
+    let (indexed_files, storage_bytes) =
+        indexed_usage(neo4j, projects, limits.max_storage_mb.is_some()).await?;
+    let usage = QuotaUsage {
+        indexed_files,
+        storage_bytes,
+        ..Default::default()
+    };
+    Ok(quota_error(tenant, limits, &usage, Resource::IndexedFiles)
+        .or_else(|| quota_error(tenant, limits, &usage, Resource::Storage)))
+}
+
+/// Refuse syncing `project` once its deployment or workspace indexes more
+/// files or storage than allowed (403).
+pub(crate) async fn check_index_quota(
+    state: &OrchestratorState,
+    project: &ProjectNode,
+) -> Result<(), AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let settings = quota_settings(neo4j).await?;
+
+    let deployment = &settings.deployment;
+    if deployment.max_indexed_files.is_some() || deployment.max_storage_mb.is_some() {
+        let projects = neo4j.list_projects().await?;
+        if let Some(msg) = index_quota_error(neo4j, DEPLOYMENT, deployment, &projects).await? {
+            return Err(AppError::Forbidden(msg));
+        }
+    }
+    if let Some(workspace) = neo4j.get_project_workspace(project.id).await? {
+        let (limits, _) = workspace_limits(neo4j, &settings, &workspace).await?;
+        let projects = neo4j.list_workspace_projects(workspace.id).await?;
+        let tenant = workspace_tenant(&workspace);
+        if let Some(msg) = index_quota_error(neo4j, &tenant, &limits, &projects).await? {
+            return Err(AppError::Forbidden(msg));
+        }
+    }
+    Ok(())
+}
+
+/// Refuse a new chat session while the deployment, or the workspace of
+/// the session (given directly or through its project), has as many active
+/// sessions as allowed (429).
+pub(crate) async fn check_chat_quota(
+    state: &OrchestratorState,
+    project_slug: Option<&str>,
+    workspace_slug: Option<&str>,
+) -> Result<(), AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let settings = quota_settings(neo4j).await?;
+
+    let workspace = match workspace_slug {
+        Some(slug) => neo4j.get_workspace_by_slug(slug).await?,
+        None => match project_slug {
+            Some(slug) => match neo4j.get_project_by_slug(slug).await? {
+                Some(project) => neo4j.get_project_workspace(project.id).await?,
+                None => None,
+            },
+            None => None,
+        },
+    };
+    let workspace_limits = match &workspace {
+        Some(workspace) => Some(workspace_limits(neo4j, &settings, workspace).await?.0),
+        None => None,
+    };
+    let workspace_limited = workspace_limits
+        .as_ref()
+        .is_some_and(|l| l.max_chat_sessions.is_some());
+    if settings.deployment.max_chat_sessions.is_none() && !workspace_limited {
+        return Ok(());
+    }
+
+    let sessions = active_chat_sessions(state).await?;
+    let usage = QuotaUsage {
+        chat_sessions: sessions.len() as u64,
+        ..Default::default()
+    };
+    if let Some(msg) = quota_error(
+        DEPLOYMENT,
+        &settings.deployment,
+        &usage,
+        Resource::ChatSessions,
+    ) {
+        return Err(AppError::TooManyRequests(msg));
+    }
+    if let (Some(workspace), Some(limits)) = (&workspace, &workspace_limits) {
+        let projects = neo4j.list_workspace_projects(workspace.id).await?;
+        let usage = QuotaUsage {
+            chat_sessions: workspace_sessions(&sessions, workspace, &projects),
+            ..Default::default()
+        };
+        let tenant = workspace_tenant(workspace);
+        if let Some(msg) = quota_error(&tenant, limits, &usage, Resource::ChatSessions) {
+            return Err(AppError::TooManyRequests(msg));
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Admin endpoints
+// ============================================================================
+
+fn require_admin(state: &OrchestratorState, user: &AuthUser) -> Result<(), AppError> {
+    match state.auth_config.as_ref() {
+        Some(auth) if !auth.is_root_user(user.user_id) => Err(AppError::Forbidden(
+            "Quota administration is reserved to the root account".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct TenantQuota {
+    pub limits: QuotaLimits,
+    pub usage: QuotaUsage,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WorkspaceQuota {
+    pub workspace_id: Uuid,
+    pub slug: String,
+    pub name: String,
+    /// The workspace's own limits rather than the default
+    pub custom: bool,
+    pub limits: QuotaLimits,
+    pub usage: QuotaUsage,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QuotaReport {
+    pub deployment: TenantQuota,
+    pub workspace_default: QuotaLimits,
+    pub workspaces: Vec<WorkspaceQuota>,
+}
+
+/// GET /api/admin/quotas — Limits and usage of the deployment and of each
+/// workspace.
+pub async fn get_quotas(
+    State(state): State<OrchestratorState>,
+    user: AuthUser,
+) -> Result<Json<QuotaReport>, AppError> {
+    require_admin(&state, &user)?;
+    let neo4j = state.orchestrator.neo4j();
+    let settings = quota_settings(neo4j).await?;
+    let sessions = active_chat_sessions(&state).await?;
+
+    let projects = neo4j.list_projects().await?;
+    let deployment = TenantQuota {
+        usage: usage_of(neo4j, &projects, sessions.len() as u64).await?,
+        limits: settings.deployment.clone(),
+    };
+
+    let mut workspaces = Vec::new();
+    for workspace in neo4j.list_workspaces().await? {
+        let (limits, custom) = workspace_limits(neo4j, &settings, &workspace).await?;
+        let projects = neo4j.list_workspace_projects(workspace.id).await?;
+        let chat_sessions = workspace_sessions(&sessions, &workspace, &projects);
+        workspaces.push(WorkspaceQuota {
+            workspace_id: workspace.id,
+            usage: usage_of(neo4j, &projects, chat_sessions).await?,
+            slug: workspace.slug,
+            name: workspace.name,
+            custom,
+            limits,
+        });
+    }
+
+    Ok(Json(QuotaReport {
+        deployment,
+        workspace_default: settings.workspace_default,
+        workspaces,
+    }))
+}
+
+/// PUT /api/admin/quotas — Replace the deployment and default workspace
+/// limits.
+pub async fn set_quotas(
+    State(state): State<OrchestratorState>,
+    user: AuthUser,
+    Json(settings): Json<QuotaSettings>,
+) -> Result<Json<QuotaSettings>, AppError> {
+    require_admin(&state, &user)?;
+    state
+        .orchestrator
+        .neo4j()
+        .update_quota_settings(&settings)
+        .await?;
+    Ok(Json(settings))
+}
+
+/// PUT /api/admin/quotas/workspaces/{slug} — Give a workspace its own limits.
+pub async fn set_workspace_quotas(
+    State(state): State<OrchestratorState>,
+    user: AuthUser,
+    Path(slug): Path<String>,
+    Json(limits): Json<QuotaLimits>,
+) -> Result<Json<QuotaLimits>, AppError> {
+    require_admin(&state, &user)?;
+    let neo4j = state.orchestrator.neo4j();
+    let workspace = neo4j
+        .get_workspace_by_slug(&slug)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Workspace '{}' not found", slug)))?;
+
+    neo4j
+        .update_workspace_quotas(workspace.id, Some(&limits))
+        .await?;
+    Ok(Json(limits))
+}
+
+/// DELETE /api/admin/quotas/workspaces/{slug} — Put a workspace back on the
+/// default limits.
+pub async fn delete_workspace_quotas(
+    State(state): State<OrchestratorState>,
+    user: AuthUser,
+    Path(slug): Path<String>,
+) -> Result<StatusCode, AppError> {
+    require_admin(&state, &user)?;
+    let neo4j = state.orchestrator.neo4j();
+    let workspace = neo4j
+        .get_workspace_by_slug(&slug)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Workspace '{}' not found", slug)))?;
+
+    neo4j.update_workspace_quotas(workspace.id, None).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::handlers::ServerState;
+    use crate::api::routes::create_router;
+    use crate::neo4j::models::FileNode;
+    use crate::orchestrator::watcher::FileWatcher;
+    use crate::orchestrator::Orchestrator;
+    use crate::test_helpers::{
+        mock_app_state, test_auth_config, test_bearer_token, test_project_named, test_workspace,
+    };
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode as AxumStatus},
+    };
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    const ROOT_EMAIL: &str = "admin@ffs.holdings";
+
+    async fn mock_server_state() -> OrchestratorState {
+        let app_state = mock_app_state();
+        let orchestrator = Arc::new(Orchestrator::new(app_state).await.unwrap());
+        let watcher = Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
+            orchestrator.clone(),
+        )));
+        let mut auth_config = test_auth_config();
+        auth_config.root_account = Some(crate::RootAccountConfig {
+            email: ROOT_EMAIL.to_string(),
+            name: "Admin".to_string(),
+            password_hash: String::new(),
+        });
+        Arc::new(ServerState {
+            orchestrator,
+            watcher,
+            chat_manager: None,
+            event_bus: Arc::new(crate::events::HybridEmitter::new(Arc::new(
+                crate::events::EventBus::default(),
+            ))),
+            nats_emitter: None,
+            auth_config: Some(auth_config),
+            serve_frontend: false,
+            frontend_path: "./dist".to_string(),
+            setup_completed: true,
+            server_port: 6600,
+            public_url: None,
+            read_only: false,
+            ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
+            registry_remote_url: None,
+            oidc_client: None,
+            neural_router: crate::test_helpers::mock_neural_router(),
+            trajectory_collector: std::sync::RwLock::new(None),
+            trajectory_store_neo4j: None,
+            trajectory_store: None,
+            identity: None,
+            reactor_counters: std::sync::OnceLock::new(),
+            confidence_tracker: Arc::new(crate::graph::confidence::ConfidenceTracker::default()),
+            mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
+            model_catalog: crate::chat::model_catalog::ModelCatalogCache::new(None),
+        })
+    }
+
+    fn root_bearer_token() -> String {
+        let token = crate::auth::jwt::encode_jwt(
+            Uuid::new_v5(&Uuid::NAMESPACE_URL, ROOT_EMAIL.as_bytes()),
+            ROOT_EMAIL,
+            "Admin",
+            "test-secret-key-minimum-32-chars!!",
+            28800,
+        )
+        .unwrap();
+        format!("Bearer {}", token)
+    }
+
+    fn request(method: &str, uri: &str, token: String, body: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("authorization", token)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_quota_error() {
+        let limits = QuotaLimits {
+            max_projects: Some(2),
+            max_indexed_files: Some(100),
+            max_storage_mb: Some(1),
+            ..Default::default()
+        };
+        let usage = QuotaUsage {
+            projects: 2,
+            indexed_files: 100,
+            chat_sessions: 5,
+            storage_bytes: MIB + 1,
+        };
+        // Counted resources block at the limit, measured ones above it
+        assert_eq!(
+            quota_error("Workspace 'w'", &limits, &usage, Resource::Projects).as_deref(),
+            Some("Workspace 'w' has reached its quota of 2 project(s)")
+        );
+        assert!(quota_error("w", &limits, &usage, Resource::IndexedFiles).is_none());
+        assert!(quota_error("w", &limits, &usage, Resource::Storage).is_some());
+        // Unset limits never block
+        assert!(quota_error("w", &limits, &usage, Resource::ChatSessions).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_quota_admin_requires_root() {
+        let app = create_router(mock_server_state().await);
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "GET",
+                "/api/admin/quotas",
+                test_bearer_token(),
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::FORBIDDEN);
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "PUT",
+                "/api/admin/quotas",
+                root_bearer_token(),
+                serde_json::json!({"deployment": {"max_projects": 10}}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "GET",
+                "/api/admin/quotas",
+                root_bearer_token(),
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let report: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(report["deployment"]["limits"]["max_projects"], 10);
+        assert_eq!(report["deployment"]["usage"]["projects"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_project_quotas_enforced() {
+        let state = mock_server_state().await;
+        let neo4j = state.orchestrator.neo4j();
+        let workspace = test_workspace();
+        neo4j.create_workspace(&workspace).await.unwrap();
+        let app = create_router(state.clone());
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "PUT",
+                "/api/admin/quotas",
+                root_bearer_token(),
+                serde_json::json!({
+                    "deployment": {"max_projects": 1},
+                    "workspace_default": {"max_projects": 0},
+                }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+
+        let create = |name: &str| {
+            request(
+                "POST",
+                "/api/projects",
+                test_bearer_token(),
+                serde_json::json!({"name": name, "root_path": format!("/tmp/{}", name)}),
+            )
+        };
+        let resp = app.clone().oneshot(create("first")).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let resp = app.clone().oneshot(create("second")).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::FORBIDDEN);
+
+        // The default workspace quota allows no project...
+        let project = neo4j.get_project_by_slug("first").await.unwrap().unwrap();
+        let add = || {
+            request(
+                "POST",
+                &format!("/api/workspaces/{}/projects", workspace.slug),
+                test_bearer_token(),
+                serde_json::json!({"project_id": project.id.to_string()}),
+            )
+        };
+        let resp = app.clone().oneshot(add()).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::FORBIDDEN);
+
+        // ...unless the workspace has limits of its own
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "PUT",
+                &format!("/api/admin/quotas/workspaces/{}", workspace.slug),
+                root_bearer_token(),
+                serde_json::json!({"max_projects": 1, "max_indexed_files": 1}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let resp = app.clone().oneshot(add()).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::CREATED);
+
+        // Indexing is refused once the workspace holds more files than allowed
+        assert!(check_index_quota(&state, &project).await.is_ok());
+        for name in ["a.rs", "b.rs"] {
+            let path = format!("/tmp/first/{}", name);
+            neo4j
+                .upsert_file(&FileNode {
+                    path: path.clone(),
+                    language: "rust".to_string(),
+                    hash: "h".to_string(),
+                    line_count: 0,
+                    last_parsed: chrono::Utc::now(),
+                    project_id: Some(project.id),
+                    encoding: None,
+                })
+                .await
+                .unwrap();
+            neo4j.link_file_to_project(&path, project.id).await.unwrap();
+        }
+        let err = check_index_quota(&state, &project).await.unwrap_err();
+        assert!(matches!(err, AppError::Forbidden(msg) if msg.contains("1 indexed file(s)")));
+
+        let other = test_project_named("other");
+        neo4j.create_project(&other).await.unwrap();
+        assert!(check_index_quota(&state, &other).await.is_ok());
+    }
+}
@@ -0,0 +1,287 @@
+//! Read-only mode (`server.read_only: true`).
+//!
+//! Every request that could change the knowledge graph is rejected with
+//! `423 Locked` before reaching its handler. MCP tools and the tools of chat
+//! sessions call the REST API, so their writes are rejected the same way.
+//!
+//! "Mutating" means any method other than GET/HEAD/OPTIONS, except for
+//! authentication and the POST routes listed in [`READ_ONLY_POSTS`]: starting
+//! chat sessions and stopping their tools (the tools are still checked), and
+//! queries that take a JSON body but write nothing. Session routes that write
+//! to the graph (`undo-last`, `rollback`, `associate`, `discussed`…) and the
+//! webhooks are rejected like any other write.
+
+use super::handlers::{AppError, OrchestratorState};
+use axum::{
+    extract::{Request, State},
+    http::Method,
+    middleware::Next,
+    response::Response,
+};
+
+/// Path prefixes whose POST/PUT/DELETE are allowed in read-only mode.
+const READ_ONLY_PREFIXES: &[&str] = &["/auth/"];
+
+/// POST routes that only read (`{}` matches one path segment).
+pub const READ_ONLY_POSTS: &[&str] = &[
+    // Chat sessions: messages go over /ws/chat, tool calls hit this API
+    "/api/chat/sessions",
+    "/api/chat/sessions/{}/cancel-tools",
+    "/api/chat/sessions/{}/cancel-task/{}",
+    "/api/chat/sessions/{}/kill",
+    "/api/trajectories/similar",
+    "/api/projects/{}/review",
+    "/api/projects/{}/commit-message",
+    "/api/projects/{}/sync/diff",
+    "/api/projects/{}/context-bundle",
+    "/api/plans/{}/runs/compare",
+    "/api/plans/{}/runs/predict",
+    "/api/plans/{}/tasks/{}/build_prompt",
+    "/api/code/similar",
+    "/api/code/topology/check-file",
+    "/api/code/structural-profile",
+    "/api/code/structural-twins",
+    "/api/code/structural-twins/cross-project",
+    "/api/code/structural-clusters",
+    "/api/code/predict-links",
+    "/api/code/link-plausibility",
+    "/api/code/stress-test-node",
+    "/api/code/stress-test-edge",
+    "/api/code/stress-test-cascade",
+    "/api/code/find-bridges",
+    "/api/protocols/simulate",
+    "/api/prompt-templates/{}/render",
+];
+
+/// Whether `path` matches `pattern`, segment by segment.
+fn matches_route(pattern: &str, path: &str) -> bool {
+    let mut pattern = pattern.trim_end_matches('/').split('/');
+    let mut path = path.trim_end_matches('/').split('/');
+    loop {
+        match (pattern.next(), path.next()) {
+            (None, None) => return true,
+            (Some("{}"), Some(segment)) if !segment.is_empty() => {}
+            (Some(expected), Some(segment)) if expected == segment => {}
+            _ => return false,
+        }
+    }
+}
+
+/// Whether a request is allowed while the server is read-only.
+pub fn is_read_only_request(method: &Method, path: &str) -> bool {
+    if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        return true;
+    }
+    if READ_ONLY_PREFIXES.iter().any(|p| path.starts_with(p)) {
+        return true;
+    }
+    *method == Method::POST && READ_ONLY_POSTS.iter().any(|p| matches_route(p, path))
+}
+
+/// Middleware rejecting mutating requests with 423 when `read_only` is set.
+pub async fn reject_writes(
+    State(state): State<OrchestratorState>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    if state.read_only && !is_read_only_request(req.method(), req.uri().path()) {
+        return Err(AppError::Locked(format!(
+            "Server is read-only: {} {} is not allowed",
+            req.method(),
+            req.uri().path()
+        )));
+    }
+    Ok(next.run(req).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::handlers::ServerState;
+    use crate::api::routes::create_router;
+    use crate::orchestrator::watcher::FileWatcher;
+    use crate::orchestrator::Orchestrator;
+    use crate::test_helpers::mock_app_state;
+    use axum::body::Body;
+    use axum::http::{Request as HttpRequest, StatusCode};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    async fn mock_server_state(read_only: bool) -> OrchestratorState {
+        let app_state = mock_app_state();
+        let orchestrator = Arc::new(Orchestrator::new(app_state).await.unwrap());
+        let watcher = Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
+            orchestrator.clone(),
+        )));
+        Arc::new(ServerState {
+            orchestrator,
+            watcher,
+            chat_manager: None,
+            event_bus: Arc::new(crate::events::HybridEmitter::new(Arc::new(
+                crate::events::EventBus::default(),
+            ))),
+            nats_emitter: None,
+            auth_config: None,
+            serve_frontend: false,
+            frontend_path: "./dist".to_string(),
+            setup_completed: true,
+            server_port: 6600,
+            public_url: None,
+            read_only,
+            ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
+            registry_remote_url: None,
+            oidc_client: None,
+            neural_router: crate::test_helpers::mock_neural_router(),
+            trajectory_collector: std::sync::RwLock::new(None),
+            trajectory_store_neo4j: None,
+            trajectory_store: None,
+            identity: None,
+            reactor_counters: std::sync::OnceLock::new(),
+            confidence_tracker: Arc::new(crate::graph::confidence::ConfidenceTracker::default()),
+            mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
+            model_catalog: crate::chat::model_catalog::ModelCatalogCache::new(None),
+        })
+    }
+
+    fn request(method: &str, uri: &str) -> HttpRequest<Body> {
+        HttpRequest::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(
+                r#"{"title":"Plan","description":"","priority":1}"#,
+            ))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_read_only_router_rejects_writes() {
+        let app = create_router(mock_server_state(true).await);
+
+        let resp = app
+            .clone()
+            .oneshot(request("POST", "/api/plans"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::LOCKED);
+
+        let resp = app
+            .clone()
+            .oneshot(request("GET", "/api/plans"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let resp = app
+            .clone()
+            .oneshot(request("GET", "/api/version"))
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let version: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(version["features"]["read_only"], true);
+    }
+
+    #[tokio::test]
+    async fn test_writable_router_accepts_writes() {
+        let app = create_router(mock_server_state(false).await);
+        let resp = app.oneshot(request("POST", "/api/plans")).await.unwrap();
+        assert_ne!(resp.status(), StatusCode::LOCKED);
+    }
+
+    #[test]
+    fn test_matches_route() {
+        assert!(matches_route(
+            "/api/projects/{}/review",
+            "/api/projects/abc/review"
+        ));
+        assert!(matches_route("/api/code/similar", "/api/code/similar/"));
+        assert!(!matches_route(
+            "/api/projects/{}/review",
+            "/api/projects//review"
+        ));
+        assert!(!matches_route(
+            "/api/projects/{}/review",
+            "/api/projects/abc"
+        ));
+        assert!(!matches_route(
+            "/api/code/similar",
+            "/api/code/similar/extra"
+        ));
+    }
+
+    #[test]
+    fn test_is_read_only_request() {
+        assert!(is_read_only_request(&Method::GET, "/api/plans"));
+        assert!(is_read_only_request(&Method::POST, "/auth/login"));
+        assert!(is_read_only_request(&Method::POST, "/api/chat/sessions"));
+        assert!(is_read_only_request(
+            &Method::POST,
+            "/api/plans/1/tasks/2/build_prompt"
+        ));
+        assert!(!is_read_only_request(&Method::POST, "/api/plans"));
+        assert!(!is_read_only_request(&Method::PATCH, "/api/tasks/1"));
+        assert!(!is_read_only_request(&Method::DELETE, "/api/notes/1"));
+        // Only POST queries are allowed, not other methods on the same path
+        assert!(!is_read_only_request(&Method::PUT, "/api/code/similar"));
+        assert!(!is_read_only_request(
+            &Method::POST,
+            "/api/chat/cli/install"
+        ));
+    }
+
+    #[test]
+    fn test_chat_session_writes_are_not_read_only() {
+        assert!(is_read_only_request(
+            &Method::POST,
+            "/api/chat/sessions/1/cancel-tools"
+        ));
+        assert!(is_read_only_request(
+            &Method::POST,
+            "/api/chat/sessions/1/cancel-task/tool-1"
+        ));
+        assert!(is_read_only_request(
+            &Method::POST,
+            "/api/chat/sessions/1/kill"
+        ));
+        for path in [
+            "/api/chat/sessions/1/undo-last",
+            "/api/chat/sessions/1/rollback",
+            "/api/chat/sessions/1/associate",
+            "/api/chat/sessions/1/discussed",
+            "/api/chat/sessions/1/context-feedback",
+            "/api/chat/sessions/1/reset-usage",
+            "/api/chat/sessions/backfill-previews",
+            "/api/github/webhook",
+            "/internal/events",
+        ] {
+            assert!(!is_read_only_request(&Method::POST, path), "{}", path);
+        }
+        assert!(!is_read_only_request(
+            &Method::DELETE,
+            "/api/chat/sessions/1"
+        ));
+        assert!(!is_read_only_request(
+            &Method::PATCH,
+            "/api/chat/sessions/1"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_read_only_router_rejects_undo_and_webhooks() {
+        let app = create_router(mock_server_state(true).await);
+        let session = uuid::Uuid::new_v4();
+        for uri in [
+            format!("/api/chat/sessions/{}/undo-last", session),
+            format!("/api/chat/sessions/{}/rollback", session),
+            "/api/github/webhook".to_string(),
+        ] {
+            let resp = app.clone().oneshot(request("POST", &uri)).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::LOCKED, "{}", uri);
+        }
+    }
+}
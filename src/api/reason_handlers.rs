@@ -5,11 +5,9 @@
 //! - `POST /api/reason/{tree_id}/feedback` — Provide feedback on a reasoning tree
 
 use crate::api::handlers::{AppError, OrchestratorState};
+use crate::api::validation::ValidPath;
 use crate::reasoning::models::ReasoningTreeConfig;
-use axum::{
-    extract::{Path, State},
-    Json,
-};
+use axum::{extract::State, Json};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -184,7 +182,7 @@ pub async fn reason(
 /// The cached tree is invalidated to ensure re-computation with updated scores.
 pub async fn reason_feedback(
     State(state): State<OrchestratorState>,
-    Path(tree_id): Path<Uuid>,
+    ValidPath(tree_id): ValidPath<Uuid>,
     Json(body): Json<ReasonFeedbackRequest>,
 ) -> Result<Json<ReasonFeedbackResponse>, AppError> {
     // Validate
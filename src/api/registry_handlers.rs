@@ -7,10 +7,11 @@
 
 use super::handlers::{AppError, OrchestratorState};
 use super::hook_handlers::skill_cache;
+use super::validation::ValidPath;
 use super::{PaginatedResponse, PaginationParams};
 use crate::skills::registry::{build_published_skill, PublishedSkillSummary};
 use axum::{
-    extract::{Path, Query, State},
+    extract::{Query, State},
     http::StatusCode,
     Json,
 };
@@ -206,7 +207,7 @@ pub async fn search_registry(
 /// GET /api/registry/:id
 pub async fn get_published_skill(
     State(state): State<OrchestratorState>,
-    Path(id): Path<Uuid>,
+    ValidPath(id): ValidPath<Uuid>,
 ) -> Result<Json<crate::skills::registry::PublishedSkill>, AppError> {
     let neo4j = state.orchestrator.neo4j();
 
@@ -227,7 +228,7 @@ pub async fn get_published_skill(
 /// and increments the import count.
 pub async fn import_from_registry(
     State(state): State<OrchestratorState>,
-    Path(id): Path<Uuid>,
+    ValidPath(id): ValidPath<Uuid>,
     Json(body): Json<ImportFromRegistryBody>,
 ) -> Result<(StatusCode, Json<crate::skills::ImportResult>), AppError> {
     let neo4j = state.orchestrator.neo4j();
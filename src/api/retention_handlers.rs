@@ -0,0 +1,256 @@
+//! Retention API handlers
+//!
+//! Retention windows of chat events, sharing events, search analytics,
+//! acknowledged alerts, undo logs, chat session usage, test runs and run
+//! reports, and the purge deleting the records older than them.
+//! The purge runs daily; these endpoints inspect the policy and the last
+//! purge, change the policy and purge right away. Reserved to the root
+//! account when auth is enabled.
+
+use super::handlers::{AppError, OrchestratorState};
+use crate::auth::extractor::AuthUser;
+use crate::neo4j::models::{RetentionPolicy, RetentionReport};
+use crate::orchestrator::retention;
+use axum::{extract::State, Json};
+use serde::Serialize;
+
+fn require_admin(state: &OrchestratorState, user: &AuthUser) -> Result<(), AppError> {
+    match state.auth_config.as_ref() {
+        Some(auth) if !auth.is_root_user(user.user_id) => Err(AppError::Forbidden(
+            "Retention administration is reserved to the root account".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct RetentionResponse {
+    pub policy: RetentionPolicy,
+    /// The last purge, `null` until the first one
+    pub last_report: Option<RetentionReport>,
+}
+
+/// GET /api/admin/retention — Retention policy and last purge
+pub async fn get_retention(
+    State(state): State<OrchestratorState>,
+    user: AuthUser,
+) -> Result<Json<RetentionResponse>, AppError> {
+    require_admin(&state, &user)?;
+    let store = state.orchestrator.neo4j();
+    Ok(Json(RetentionResponse {
+        policy: store.get_retention_policy().await?.unwrap_or_default(),
+        last_report: store.get_retention_report().await?,
+    }))
+}
+
+/// PUT /api/admin/retention — Replace the retention policy. Omitted windows
+/// take their default, `null` keeps the records forever.
+pub async fn set_retention_policy(
+    State(state): State<OrchestratorState>,
+    user: AuthUser,
+    Json(policy): Json<RetentionPolicy>,
+) -> Result<Json<RetentionPolicy>, AppError> {
+    require_admin(&state, &user)?;
+    retention::validate_policy(&policy).map_err(AppError::BadRequest)?;
+    state
+        .orchestrator
+        .neo4j()
+        .update_retention_policy(&policy)
+        .await?;
+    Ok(Json(policy))
+}
+
+/// POST /api/admin/retention/purge — Purge the expired records now
+pub async fn purge_retention(
+    State(state): State<OrchestratorState>,
+    user: AuthUser,
+) -> Result<Json<RetentionReport>, AppError> {
+    require_admin(&state, &user)?;
+    let report = retention::purge(state.orchestrator.neo4j(), true).await?;
+    Ok(Json(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::handlers::ServerState;
+    use crate::api::routes::create_router;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::ChatEventRecord;
+    use crate::orchestrator::watcher::FileWatcher;
+    use crate::orchestrator::Orchestrator;
+    use crate::test_helpers::{mock_app_state_with_stores, test_auth_config, test_bearer_token};
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode as AxumStatus},
+    };
+    use std::sync::Arc;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    const ROOT_EMAIL: &str = "admin@example.com";
+
+    async fn mock_server_state() -> (OrchestratorState, Arc<MockGraphStore>) {
+        let (app_state, graph, _) = mock_app_state_with_stores();
+        let orchestrator = Arc::new(Orchestrator::new(app_state).await.unwrap());
+        let watcher = Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
+            orchestrator.clone(),
+        )));
+        let mut auth_config = test_auth_config();
+        auth_config.root_account = Some(crate::RootAccountConfig {
+            email: ROOT_EMAIL.to_string(),
+            name: "Admin".to_string(),
+            password_hash: String::new(),
+        });
+        let state = Arc::new(ServerState {
+            orchestrator,
+            watcher,
+            chat_manager: None,
+            event_bus: Arc::new(crate::events::HybridEmitter::new(Arc::new(
+                crate::events::EventBus::default(),
+            ))),
+            nats_emitter: None,
+            auth_config: Some(auth_config),
+            serve_frontend: false,
+            frontend_path: "./dist".to_string(),
+            setup_completed: true,
+            server_port: 6600,
+            public_url: None,
+            read_only: false,
+            ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
+            registry_remote_url: None,
+            oidc_client: None,
+            neural_router: crate::test_helpers::mock_neural_router(),
+            trajectory_collector: std::sync::RwLock::new(None),
+            trajectory_store_neo4j: None,
+            trajectory_store: None,
+            identity: None,
+            reactor_counters: std::sync::OnceLock::new(),
+            confidence_tracker: Arc::new(crate::graph::confidence::ConfidenceTracker::default()),
+            mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
+            model_catalog: crate::chat::model_catalog::ModelCatalogCache::new(None),
+        });
+        (state, graph)
+    }
+
+    fn root_bearer_token() -> String {
+        let token = crate::auth::jwt::encode_jwt(
+            Uuid::new_v5(&Uuid::NAMESPACE_URL, ROOT_EMAIL.as_bytes()),
+            ROOT_EMAIL,
+            "Admin",
+            "test-secret-key-minimum-32-chars!!",
+            28800,
+        )
+        .unwrap();
+        format!("Bearer {}", token)
+    }
+
+    fn request(method: &str, uri: &str, token: String, body: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("authorization", token)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    async fn json(resp: axum::response::Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_retention_admin() {
+        let (state, graph) = mock_server_state().await;
+        let app = create_router(state);
+        let session_id = Uuid::new_v4();
+        graph.chat_events.write().await.insert(
+            session_id,
+            vec![ChatEventRecord {
+                id: Uuid::new_v4(),
+                session_id,
+                seq: 1,
+                event_type: "user_message".to_string(),
+                data: "{}".to_string(),
+                created_at: chrono::Utc::now() - chrono::Duration::days(10),
+            }],
+        );
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/api/admin/retention/purge",
+                test_bearer_token(),
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::FORBIDDEN);
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "PUT",
+                "/api/admin/retention",
+                root_bearer_token(),
+                serde_json::json!({"chat_events_days": 0}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::BAD_REQUEST);
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "PUT",
+                "/api/admin/retention",
+                root_bearer_token(),
+                serde_json::json!({"chat_events_days": 7, "sharing_events_days": null}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let policy = graph.retention_policy.read().await.clone().unwrap();
+        assert_eq!(policy.chat_events_days, Some(7));
+        assert_eq!(policy.sharing_events_days, None);
+        assert_eq!(policy.alerts_days, Some(30));
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/api/admin/retention/purge",
+                root_bearer_token(),
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let report = json(resp).await;
+        assert_eq!(report["manual"], true);
+        assert_eq!(report["purges"][0]["kind"], "chat_events");
+        assert_eq!(report["purges"][0]["deleted"], 1);
+        assert!(graph.chat_events.read().await[&session_id].is_empty());
+
+        let resp = app
+            .oneshot(request(
+                "GET",
+                "/api/admin/retention",
+                root_bearer_token(),
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let body = json(resp).await;
+        assert_eq!(body["policy"]["chat_events_days"], 7);
+        // Sharing events are kept forever, so they were not purged
+        assert_eq!(body["last_report"]["purges"].as_array().unwrap().len(), 3);
+    }
+}
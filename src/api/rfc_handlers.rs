@@ -9,6 +9,7 @@
 //! The protocol run link is stored as a tag `rfc-run:<uuid>` on the note.
 
 use super::handlers::{AppError, OrchestratorState};
+use super::validation::ValidPath;
 use super::PaginatedResponse;
 use crate::neo4j::GraphStore;
 use crate::notes::{
@@ -18,7 +19,7 @@ use crate::protocol::{
     self, Protocol, ProtocolCategory, ProtocolState, ProtocolTransition, StateType,
 };
 use axum::{
-    extract::{Path, Query, State},
+    extract::{Query, State},
     http::StatusCode,
     Json,
 };
@@ -429,7 +430,7 @@ pub async fn list_rfcs(
 /// GET /api/rfcs/:rfc_id
 pub async fn get_rfc(
     State(state): State<OrchestratorState>,
-    Path(rfc_id): Path<Uuid>,
+    ValidPath(rfc_id): ValidPath<Uuid>,
 ) -> Result<Json<RfcResponse>, AppError> {
     let note = state
         .orchestrator
@@ -561,7 +562,7 @@ pub async fn create_rfc(
 /// PATCH /api/rfcs/:rfc_id
 pub async fn update_rfc(
     State(state): State<OrchestratorState>,
-    Path(rfc_id): Path<Uuid>,
+    ValidPath(rfc_id): ValidPath<Uuid>,
     Json(body): Json<UpdateRfcBody>,
 ) -> Result<Json<RfcResponse>, AppError> {
     // Get existing note first to merge content
@@ -632,7 +633,7 @@ pub async fn update_rfc(
 /// DELETE /api/rfcs/:rfc_id
 pub async fn delete_rfc(
     State(state): State<OrchestratorState>,
-    Path(rfc_id): Path<Uuid>,
+    ValidPath(rfc_id): ValidPath<Uuid>,
 ) -> Result<StatusCode, AppError> {
     // Verify it's actually an RFC before deleting
     let note = state
@@ -669,7 +670,7 @@ pub async fn delete_rfc(
 /// transition on it, and updates the `rfc-status:<status>` tag accordingly.
 pub async fn transition_rfc(
     State(state): State<OrchestratorState>,
-    Path(rfc_id): Path<Uuid>,
+    ValidPath(rfc_id): ValidPath<Uuid>,
     Json(body): Json<TransitionRfcBody>,
 ) -> Result<Json<RfcResponse>, AppError> {
     // 1. Get the note
@@ -886,7 +887,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: std::sync::Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: std::sync::Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: std::sync::Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -926,7 +930,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: std::sync::Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: std::sync::Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: std::sync::Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -1,32 +1,51 @@
 //! API route definitions
 //!
 //! Routes are split into public (no auth) and protected (require valid JWT).
-//! The `require_auth` middleware is applied only to protected routes.
+//! The `require_auth` middleware is applied only to protected routes;
+//! `reject_writes` covers both when the server is read-only, `count_usage`
+//! counts the routes served when usage telemetry is enabled, and
+//! `replay_idempotent` replays protected POSTs retried with an
+//! `Idempotency-Key`.
 
 use super::auth_handlers;
 use super::chat_handlers;
 use super::code_handlers;
+use super::comment_handlers;
 use super::episode_handlers;
 use super::feedback_handlers;
+use super::github_handlers;
 use super::handlers::{self, OrchestratorState};
 use super::hook_handlers;
+use super::idempotency::{replay_idempotent, IDEMPOTENCY_KEY};
 use super::mcp_federation_handlers;
 use super::neural_routing_handlers;
 use super::note_handlers;
+use super::notification_handlers;
+use super::ownership_handlers;
 use super::persona_handlers;
+use super::presence;
 use super::profile_handlers;
 use super::project_handlers;
+use super::prompt_template_handlers;
 use super::protocol_handlers;
+use super::quota_handlers;
+use super::read_only::reject_writes;
 use super::reason_handlers;
 use super::registry_handlers;
+use super::retention_handlers;
 use super::rfc_handlers;
+use super::search_analytics_handlers;
+use super::search_settings_handlers;
 use super::sharing_handlers;
 use super::skill_handlers;
 use super::trajectory_handlers;
 use super::trigger_handlers;
+use super::usage_handlers::{self, count_usage};
+use super::view_handlers;
 use super::workspace_handlers;
 use super::ws_chat_handler;
 use super::ws_handlers;
+use super::ws_presence_handler;
 use super::ws_run_handler;
 use crate::auth::middleware::require_auth;
 use axum::http::{header, Method};
@@ -54,10 +73,15 @@ pub fn create_router(state: OrchestratorState) -> Router {
     let cors = build_cors(&state);
 
     let public = public_routes();
-    let protected = protected_routes().layer(from_fn_with_state(state.clone(), require_auth));
+    // Layers run outermost first: auth, then idempotency (keys are per user)
+    let protected = protected_routes()
+        .layer(from_fn_with_state(state.clone(), replay_idempotent))
+        .layer(from_fn_with_state(state.clone(), require_auth));
 
     let router = public
         .merge(protected)
+        .layer(from_fn_with_state(state.clone(), count_usage))
+        .layer(from_fn_with_state(state.clone(), reject_writes))
         .layer(TraceLayer::new_for_http())
         .layer(cors)
         .with_state(state.clone());
@@ -134,6 +158,8 @@ fn build_cors(state: &OrchestratorState) -> CorsLayer {
             header::CONTENT_TYPE,
             header::ACCEPT,
             header::COOKIE,
+            header::IF_MATCH,
+            IDEMPOTENCY_KEY,
         ])
         .allow_credentials(true)
 }
@@ -149,6 +175,8 @@ fn public_routes() -> Router<OrchestratorState> {
     Router::new()
         // Health check, version & setup status
         .route("/health", get(handlers::health))
+        .route("/health/live", get(handlers::liveness))
+        .route("/health/ready", get(handlers::readiness))
         .route("/api/version", get(handlers::get_version))
         .route("/api/setup-status", get(handlers::setup_status))
         // ================================================================
@@ -180,12 +208,18 @@ fn public_routes() -> Router<OrchestratorState> {
         .route("/ws/events", get(ws_handlers::ws_events))
         .route("/ws/chat/{session_id}", get(ws_chat_handler::ws_chat))
         .route("/ws/run/{run_id}", get(ws_run_handler::ws_run))
+        .route("/ws/presence", get(ws_presence_handler::ws_presence))
         // ================================================================
         // Webhooks & Internal
         // ================================================================
         .route("/hooks/wake", post(handlers::wake))
         // DEPRECATED: Use NATS for inter-process events. Kept for backward compatibility.
         .route("/internal/events", post(handlers::receive_event))
+        // GitHub App webhook (authenticated by its HMAC signature)
+        .route(
+            "/api/github/webhook",
+            post(github_handlers::receive_github_webhook),
+        )
         // ================================================================
         // Hook activation (public — called from Claude Code hooks, rate limited)
         // ================================================================
@@ -227,6 +261,14 @@ fn protected_routes() -> Router<OrchestratorState> {
                 .patch(project_handlers::update_project)
                 .delete(project_handlers::delete_project),
         )
+        .route(
+            "/api/projects/{slug}/archive",
+            post(project_handlers::archive_project),
+        )
+        .route(
+            "/api/projects/{slug}/unarchive",
+            post(project_handlers::unarchive_project),
+        )
         .route(
             "/api/projects/{slug}/sync",
             post(project_handlers::sync_project),
@@ -247,6 +289,11 @@ fn protected_routes() -> Router<OrchestratorState> {
             "/api/projects/{slug}/intelligence/summary",
             get(project_handlers::get_intelligence_summary),
         )
+        // Onboarding overview: architecture, central symbols, open plans
+        .route(
+            "/api/projects/{slug}/overview",
+            get(project_handlers::get_project_overview),
+        )
         .route(
             "/api/projects/{slug}/embeddings/projection",
             get(project_handlers::get_embeddings_projection),
@@ -368,6 +415,137 @@ fn protected_routes() -> Router<OrchestratorState> {
             "/api/projects/{project_id}/roadmap",
             get(handlers::get_project_roadmap),
         )
+        // Vulnerability audit (advisories from the last OSV check)
+        .route(
+            "/api/projects/{project_id}/advisories",
+            get(project_handlers::list_project_advisories),
+        )
+        // Markdown documentation indexed at sync time
+        .route(
+            "/api/projects/{project_id}/documents",
+            get(project_handlers::list_project_documents),
+        )
+        // SQL schema reconstructed from schema & migration files
+        .route(
+            "/api/projects/{project_id}/schema",
+            get(project_handlers::get_project_schema),
+        )
+        // Directory hierarchy with per-folder symbol & health aggregates
+        .route(
+            "/api/projects/{project_id}/tree",
+            get(project_handlers::get_project_tree),
+        )
+        // Cached per-directory summaries, refreshed after sync
+        .route(
+            "/api/projects/{project_id}/modules",
+            get(project_handlers::list_module_summaries),
+        )
+        // Files a sync leaves unparsed (size, binary, excluded extensions)
+        .route(
+            "/api/projects/{project_id}/sync-limits",
+            get(project_handlers::get_project_sync_limits)
+                .put(project_handlers::set_project_sync_limits),
+        )
+        // Complexity and size budgets, and their pass/fail check for CI gates
+        .route(
+            "/api/projects/{project_id}/budgets",
+            get(project_handlers::get_project_budgets).put(project_handlers::set_project_budgets),
+        )
+        .route(
+            "/api/projects/{project_id}/budget-check",
+            get(project_handlers::check_project_budgets),
+        )
+        // Public API surface, its per-sync snapshots and breaking-change diffs
+        .route(
+            "/api/projects/{project_id}/api-surface",
+            get(project_handlers::get_project_api_surface),
+        )
+        .route(
+            "/api/projects/{project_id}/api-surface/snapshots",
+            get(project_handlers::list_api_surface_snapshots),
+        )
+        .route(
+            "/api/projects/{project_id}/api-surface/diff",
+            get(project_handlers::diff_api_surface),
+        )
+        // Major/minor/patch release advice from the API surface diff
+        .route(
+            "/api/projects/{project_id}/semver-advice",
+            get(project_handlers::get_semver_advice),
+        )
+        // Test coverage upload (LCOV or Cobertura), mapped onto functions
+        .route(
+            "/api/projects/{project_id}/coverage",
+            post(project_handlers::ingest_project_coverage).layer(
+                axum::extract::DefaultBodyLimit::max(project_handlers::MAX_COVERAGE_REPORT_BYTES),
+            ),
+        )
+        // CI test results (JUnit XML) and the flaky tests they reveal
+        .route(
+            "/api/projects/{project_id}/test-results",
+            post(project_handlers::ingest_project_test_results).layer(
+                axum::extract::DefaultBodyLimit::max(project_handlers::MAX_TEST_REPORT_BYTES),
+            ),
+        )
+        .route(
+            "/api/projects/{project_id}/flaky-tests",
+            get(project_handlers::get_flaky_tests),
+        )
+        .route(
+            "/api/projects/{project_id}/production-errors",
+            get(project_handlers::get_production_errors),
+        )
+        // CODEOWNERS ingestion and lookups
+        .route(
+            "/api/projects/{project_id}/codeowners",
+            get(ownership_handlers::get_project_code_owners)
+                .post(ownership_handlers::ingest_project_code_owners),
+        )
+        .route(
+            "/api/projects/{project_id}/owners",
+            get(ownership_handlers::list_project_owners),
+        )
+        .route(
+            "/api/projects/{project_id}/ownership",
+            get(ownership_handlers::get_path_ownership),
+        )
+        // Pull request review: a unified diff mapped onto the graph, for CI bots
+        .route(
+            "/api/projects/{project_id}/review",
+            post(project_handlers::review_project_diff),
+        )
+        // Conventional commit message proposal for the working (or a given) diff
+        .route(
+            "/api/projects/{project_id}/commit-message",
+            post(project_handlers::suggest_commit_message),
+        )
+        // Decisions of the project written as docs/adr/NNN-title.md files
+        .route(
+            "/api/projects/{project_id}/adrs/export",
+            post(project_handlers::export_adrs),
+        )
+        // Diff-scoped sync: analyze a PR branch in an overlay, leaving the graph as is
+        .route(
+            "/api/projects/{project_id}/sync/diff",
+            post(project_handlers::sync_project_diff),
+        )
+        // Portable context bundle of a task, for external agents and issues
+        .route(
+            "/api/projects/{project_id}/context-bundle",
+            post(project_handlers::create_context_bundle),
+        )
+        // GitHub App check runs on the project's pull requests
+        .route(
+            "/api/projects/{project_id}/github-checks",
+            get(github_handlers::get_github_checks)
+                .put(github_handlers::set_github_checks)
+                .delete(github_handlers::delete_github_checks),
+        )
+        // Pruned call graph with community colors for the graph view
+        .route(
+            "/api/projects/{project_id}/callgraph",
+            get(project_handlers::get_project_call_graph),
+        )
         // ================================================================
         // Plans (global or legacy)
         // ================================================================
@@ -399,6 +577,11 @@ fn protected_routes() -> Router<OrchestratorState> {
             get(handlers::get_plan_critical_path),
         )
         .route("/api/plans/{plan_id}/waves", get(handlers::get_plan_waves))
+        // Owners of the files of each task, and their assignment
+        .route(
+            "/api/plans/{plan_id}/routing",
+            get(ownership_handlers::get_plan_routing).post(ownership_handlers::apply_plan_routing),
+        )
         // Runner
         .route("/api/plans/{plan_id}/run", post(handlers::run_plan))
         .route(
@@ -506,6 +689,10 @@ fn protected_routes() -> Router<OrchestratorState> {
                 .patch(handlers::update_task)
                 .delete(handlers::delete_task),
         )
+        .route(
+            "/api/tasks/{task_id}/position",
+            axum::routing::patch(handlers::update_task_position),
+        )
         // Task ↔ Session linking
         .route(
             "/api/tasks/{task_id}/sessions",
@@ -699,6 +886,33 @@ fn protected_routes() -> Router<OrchestratorState> {
         // ================================================================
         // Search code semantically (Meilisearch)
         .route("/api/code/search", get(code_handlers::search_code))
+        // Search indexed Markdown documentation (Meilisearch)
+        .route("/api/code/docs/search", get(code_handlers::search_docs))
+        // Search query analytics (most searched and zero-result queries)
+        .route(
+            "/api/search/analytics",
+            get(search_analytics_handlers::get_search_analytics),
+        )
+        // Search settings (synonyms, stop words, ranking rules of every index)
+        .route(
+            "/api/search/settings",
+            get(search_settings_handlers::get_search_settings)
+                .put(search_settings_handlers::set_search_settings),
+        )
+        .route("/api/code/snippet", get(code_handlers::get_code_snippet))
+        .route(
+            "/api/code/rename-proposal",
+            get(code_handlers::propose_rename),
+        )
+        .route("/api/code/explain", get(code_handlers::explain_symbol))
+        .route(
+            "/api/code/stack-trace",
+            post(code_handlers::resolve_stack_trace),
+        )
+        .route(
+            "/api/code/config-keys",
+            get(code_handlers::find_config_usages),
+        )
         // Get symbols in a file (Neo4j)
         .route(
             "/api/code/symbols/{*file_path}",
@@ -776,6 +990,10 @@ fn protected_routes() -> Router<OrchestratorState> {
             get(code_handlers::get_code_communities),
         )
         .route("/api/code/health", get(code_handlers::get_code_health))
+        .route(
+            "/api/code/duplicates",
+            get(code_handlers::get_code_duplicates),
+        )
         .route(
             "/api/code/node-importance",
             get(code_handlers::get_node_importance),
@@ -1026,6 +1244,15 @@ fn protected_routes() -> Router<OrchestratorState> {
             "/api/notes/{note_id}/supersede",
             post(note_handlers::supersede_note),
         )
+        // Note endorsements (per-user acks and upvotes)
+        .route(
+            "/api/notes/{note_id}/endorsements",
+            get(note_handlers::get_note_endorsements).post(note_handlers::endorse_note),
+        )
+        .route(
+            "/api/notes/{note_id}/endorsements/{kind}",
+            axum::routing::delete(note_handlers::withdraw_note_endorsement),
+        )
         // Note linking
         .route(
             "/api/notes/{note_id}/links",
@@ -1385,6 +1612,42 @@ fn protected_routes() -> Router<OrchestratorState> {
             post(handlers::backfill_discussed),
         )
         // ================================================================
+        // Admin — Quotas
+        // ================================================================
+        .route(
+            "/api/admin/quotas",
+            get(quota_handlers::get_quotas).put(quota_handlers::set_quotas),
+        )
+        .route(
+            "/api/admin/quotas/workspaces/{slug}",
+            axum::routing::put(quota_handlers::set_workspace_quotas)
+                .delete(quota_handlers::delete_workspace_quotas),
+        )
+        // ================================================================
+        // Admin — Retention
+        // ================================================================
+        .route(
+            "/api/admin/retention",
+            get(retention_handlers::get_retention).put(retention_handlers::set_retention_policy),
+        )
+        .route(
+            "/api/admin/retention/purge",
+            post(retention_handlers::purge_retention),
+        )
+        // ================================================================
+        // Admin — Usage telemetry
+        // ================================================================
+        .route("/api/admin/usage", get(usage_handlers::get_usage))
+        .route("/api/admin/usage/export", get(usage_handlers::export_usage))
+        // ================================================================
+        // Notification preferences (current user)
+        // ================================================================
+        .route(
+            "/api/notifications/preferences",
+            get(notification_handlers::get_notification_preferences)
+                .put(notification_handlers::set_notification_preferences),
+        )
+        // ================================================================
         // Meilisearch Maintenance
         // ================================================================
         .route(
@@ -1396,6 +1659,17 @@ fn protected_routes() -> Router<OrchestratorState> {
             axum::routing::delete(handlers::delete_meilisearch_orphans),
         )
         // ================================================================
+        // Admin — Runtime log level
+        // ================================================================
+        .route(
+            "/api/admin/log-level",
+            get(handlers::get_log_level).put(handlers::set_log_level),
+        )
+        // ================================================================
+        // Admin — Metrics
+        // ================================================================
+        .route("/api/admin/metrics", get(handlers::get_metrics))
+        // ================================================================
         // Admin Cleanup
         // ================================================================
         .route(
@@ -1524,6 +1798,10 @@ fn protected_routes() -> Router<OrchestratorState> {
             "/api/workspaces/{slug}/overview",
             get(workspace_handlers::get_workspace_overview),
         )
+        .route(
+            "/api/workspaces/{slug}/stats",
+            get(workspace_handlers::get_workspace_stats),
+        )
         .route(
             "/api/workspaces/{slug}/projects",
             get(workspace_handlers::list_workspace_projects)
@@ -1566,6 +1844,10 @@ fn protected_routes() -> Router<OrchestratorState> {
             "/api/workspace-milestones/{id}/progress",
             get(workspace_handlers::get_workspace_milestone_progress),
         )
+        .route(
+            "/api/workspace-milestones/{id}/rollup",
+            get(workspace_handlers::get_workspace_milestone_rollup),
+        )
         // Resources
         .route(
             "/api/workspaces/{slug}/resources",
@@ -1581,6 +1863,22 @@ fn protected_routes() -> Router<OrchestratorState> {
             "/api/resources/{id}/projects",
             post(workspace_handlers::link_resource_to_project),
         )
+        .route(
+            "/api/resources/{id}/usages",
+            get(workspace_handlers::get_resource_usages),
+        )
+        .route(
+            "/api/resources/{id}/parse",
+            post(workspace_handlers::parse_resource),
+        )
+        .route(
+            "/api/resources/{id}/elements",
+            get(workspace_handlers::list_resource_elements),
+        )
+        .route(
+            "/api/resources/{id}/diff",
+            get(workspace_handlers::diff_resources),
+        )
         // Components
         .route(
             "/api/workspaces/{slug}/components",
@@ -1608,10 +1906,31 @@ fn protected_routes() -> Router<OrchestratorState> {
             "/api/workspaces/{slug}/topology",
             get(workspace_handlers::get_workspace_topology),
         )
+        .route(
+            "/api/workspaces/{slug}/topology/drift",
+            get(workspace_handlers::get_workspace_topology_drift),
+        )
+        .route(
+            "/api/workspaces/{slug}/license-policy",
+            get(workspace_handlers::get_workspace_license_policy)
+                .put(workspace_handlers::set_workspace_license_policy),
+        )
+        .route(
+            "/api/workspaces/{slug}/license-violations",
+            get(workspace_handlers::get_workspace_license_violations),
+        )
         .route(
             "/api/workspaces/{slug}/coupling-matrix",
             get(workspace_handlers::get_coupling_matrix),
         )
+        .route(
+            "/api/workspaces/{slug}/search",
+            get(workspace_handlers::search_workspace),
+        )
+        .route(
+            "/api/workspaces/{slug}/context",
+            get(workspace_handlers::get_workspace_context),
+        )
         // Workspace Intelligence (aggregated graph + summary)
         .route(
             "/api/workspaces/{slug}/graph",
@@ -1633,6 +1952,11 @@ fn protected_routes() -> Router<OrchestratorState> {
             "/api/chat/sessions/backfill-previews",
             post(chat_handlers::backfill_previews),
         )
+        // Sessions with a live CLI subprocess on this instance
+        .route(
+            "/api/chat/sessions/active",
+            get(chat_handlers::list_active_sessions),
+        )
         .route(
             "/api/chat/sessions/{id}",
             get(chat_handlers::get_session)
@@ -1660,6 +1984,24 @@ fn protected_routes() -> Router<OrchestratorState> {
             "/api/chat/sessions/{id}/discussed",
             post(chat_handlers::add_discussed).get(chat_handlers::get_session_entities),
         )
+        // Thumbs up/down on the notes and symbols surfaced in a session
+        .route(
+            "/api/chat/sessions/{id}/context-feedback",
+            post(chat_handlers::submit_context_feedback).get(chat_handlers::list_context_feedback),
+        )
+        // Undo log of the graph mutations made by the session's agent
+        .route(
+            "/api/chat/sessions/{id}/undo-log",
+            get(chat_handlers::get_undo_log),
+        )
+        .route(
+            "/api/chat/sessions/{id}/undo-last",
+            post(chat_handlers::undo_last),
+        )
+        .route(
+            "/api/chat/sessions/{id}/rollback",
+            post(chat_handlers::rollback_session),
+        )
         // ASSOCIATED_WITH relation (ChatSession → Plan/Task)
         .route(
             "/api/chat/sessions/{id}/associate",
@@ -1672,6 +2014,17 @@ fn protected_routes() -> Router<OrchestratorState> {
             "/api/chat/sessions/{id}/cancel-tools",
             post(chat_handlers::cancel_tools),
         )
+        // Kill switch — SIGKILL the whole CLI process tree and close the
+        // session. Also triggered by the session limits (chat::limits).
+        .route(
+            "/api/chat/sessions/{id}/kill",
+            post(chat_handlers::kill_session),
+        )
+        // Clear the stored usage, so a killed session can resume
+        .route(
+            "/api/chat/sessions/{id}/reset-usage",
+            post(chat_handlers::reset_session_usage),
+        )
         // Background tasks (T6 + T8 of plan 754a1379) — snapshot
         // of the tracked Monitor / Bash bg subprocesses, plus the
         // granular per-task cancel that targets a single tool_use_id
@@ -1684,6 +2037,54 @@ fn protected_routes() -> Router<OrchestratorState> {
             "/api/chat/sessions/{id}/cancel-task/{task_id}",
             post(chat_handlers::cancel_task),
         )
+        // Prompt templates (versioned, `{{variable}}` substitution)
+        .route(
+            "/api/prompt-templates",
+            get(prompt_template_handlers::list_prompt_templates)
+                .post(prompt_template_handlers::create_prompt_template),
+        )
+        .route(
+            "/api/prompt-templates/{id}",
+            get(prompt_template_handlers::get_prompt_template)
+                .patch(prompt_template_handlers::update_prompt_template)
+                .delete(prompt_template_handlers::delete_prompt_template),
+        )
+        .route(
+            "/api/prompt-templates/{id}/versions",
+            get(prompt_template_handlers::list_prompt_template_versions),
+        )
+        .route(
+            "/api/prompt-templates/{id}/render",
+            post(prompt_template_handlers::render_prompt_template),
+        )
+        // Presence (who is on which plan or note; live updates via /ws/presence)
+        .route("/api/presence", get(presence::list_presence))
+        // Comments (discussion threads on tasks, plans and notes)
+        .route(
+            "/api/comments",
+            get(comment_handlers::list_comments).post(comment_handlers::create_comment),
+        )
+        .route(
+            "/api/comments/{id}",
+            get(comment_handlers::get_comment)
+                .patch(comment_handlers::update_comment)
+                .delete(comment_handlers::delete_comment),
+        )
+        // Saved views (per-user queries over tasks, notes and symbols)
+        .route(
+            "/api/views",
+            get(view_handlers::list_saved_views).post(view_handlers::create_saved_view),
+        )
+        .route(
+            "/api/views/{id}",
+            get(view_handlers::get_saved_view)
+                .patch(view_handlers::update_saved_view)
+                .delete(view_handlers::delete_saved_view),
+        )
+        .route(
+            "/api/views/{id}/results",
+            get(view_handlers::get_saved_view_results),
+        )
         // Chat permission config (runtime GET/PUT)
         .route(
             "/api/chat/config/permissions",
@@ -1699,6 +2100,7 @@ fn protected_routes() -> Router<OrchestratorState> {
         // Detect user PATH from login shell
         .route("/api/chat/detect-path", get(chat_handlers::detect_path))
         // CLI version management (check + install/upgrade)
+        .route("/api/chat/health", get(chat_handlers::get_chat_health))
         .route("/api/chat/cli/status", get(chat_handlers::get_cli_status))
         .route("/api/chat/cli/install", post(chat_handlers::install_cli))
         // CLI auth status
@@ -1768,7 +2170,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -1803,7 +2208,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -1927,9 +2335,36 @@ mod tests {
             text.contains("\"status\""),
             "Expected JSON health response, got: {text}"
         );
+        assert!(text.contains("\"service\":\"project-orchestrator\""));
         assert!(!text.contains("SPA"), "Health should NOT return index.html");
     }
 
+    // ====================================================================
+    // Kubernetes probes: /health/live and /health/ready are public
+    // ====================================================================
+
+    #[tokio::test]
+    async fn test_liveness_and_readiness_probes() {
+        let app = test_app_no_frontend().await;
+
+        for (path, expected) in [
+            ("/health/live", "\"alive\""),
+            ("/health/ready", "\"ready\""),
+        ] {
+            let resp = app
+                .clone()
+                .oneshot(Request::get(path).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::OK, "{path}");
+            let body = axum::body::to_bytes(resp.into_body(), 10_000)
+                .await
+                .unwrap();
+            let text = String::from_utf8(body.to_vec()).unwrap();
+            assert!(text.contains(expected), "{path}: {text}");
+        }
+    }
+
     // ====================================================================
     // serve_frontend=false: SPA routes return 404
     // ====================================================================
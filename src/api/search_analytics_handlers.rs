@@ -0,0 +1,317 @@
+//! Search query analytics API handlers
+//!
+//! The search endpoints (code, docs, notes and decisions, keyword and
+//! semantic) record every query they serve with its hit count into
+//! aggregated `SearchQuery` counters. `GET /api/search/analytics` summarizes
+//! them: what is searched most, and what keeps returning nothing — the
+//! queries worth a synonym, an index setting or a note.
+
+use super::handlers::{AppError, OrchestratorState};
+use crate::neo4j::models::{SearchQueryEvent, SearchQueryStats};
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Header set by the MCP server on the requests it proxies to the REST API
+pub const CLIENT_HEADER: &str = "x-po-client";
+
+const DEFAULT_ANALYTICS_LIMIT: usize = 20;
+const MAX_ANALYTICS_LIMIT: usize = 100;
+
+/// Lowercase a query, trim it and collapse its whitespace so that
+/// "Auth  Token" and "auth token" count as the same query.
+pub fn normalize_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Record a served search in the background, so that recording never slows
+/// down or fails the search itself. Empty queries are not recorded.
+pub fn record_search(
+    state: &OrchestratorState,
+    headers: &HeaderMap,
+    endpoint: &str,
+    query: &str,
+    project_slug: Option<&str>,
+    hits: usize,
+) {
+    let query = normalize_query(query);
+    if query.is_empty() {
+        return;
+    }
+    let event = SearchQueryEvent {
+        endpoint: endpoint.to_string(),
+        query,
+        project_slug: project_slug.map(str::to_string),
+        hits,
+        from_mcp: headers
+            .get(CLIENT_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.eq_ignore_ascii_case("mcp")),
+        at: Utc::now(),
+    };
+    let graph = state.orchestrator.neo4j_arc();
+    tokio::spawn(async move {
+        if let Err(e) = graph.record_search_query(&event).await {
+            tracing::warn!("Failed to record search query: {}", e);
+        }
+    });
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SearchAnalyticsQuery {
+    /// Restrict to one endpoint ("code", "docs", "notes", "notes_semantic",
+    /// "decisions", "decisions_semantic")
+    pub endpoint: Option<String>,
+    pub project_slug: Option<String>,
+    /// Only queries searched since this date (their counters still cover
+    /// all time)
+    pub since: Option<DateTime<Utc>>,
+    /// Length of the top lists (default 20, max 100)
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EndpointSearchSummary {
+    pub endpoint: String,
+    pub searches: u64,
+    pub zero_result_searches: u64,
+    pub distinct_queries: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchAnalyticsResponse {
+    pub total_searches: u64,
+    pub zero_result_searches: u64,
+    /// zero_result_searches / total_searches (0 when nothing was searched)
+    pub zero_result_rate: f64,
+    pub mcp_searches: u64,
+    pub distinct_queries: usize,
+    pub by_endpoint: Vec<EndpointSearchSummary>,
+    /// Most searched queries
+    pub top_queries: Vec<SearchQueryStats>,
+    /// Queries that most often returned nothing
+    pub top_zero_result: Vec<SearchQueryStats>,
+}
+
+/// Summarize search counters into totals and top lists of `limit` queries.
+pub fn summarize(mut stats: Vec<SearchQueryStats>, limit: usize) -> SearchAnalyticsResponse {
+    let total_searches: u64 = stats.iter().map(|s| s.count).sum();
+    let zero_result_searches: u64 = stats.iter().map(|s| s.zero_result_count).sum();
+    let mcp_searches: u64 = stats.iter().map(|s| s.mcp_count).sum();
+
+    let mut endpoints: BTreeMap<&str, EndpointSearchSummary> = BTreeMap::new();
+    for s in &stats {
+        let summary =
+            endpoints
+                .entry(s.endpoint.as_str())
+                .or_insert_with(|| EndpointSearchSummary {
+                    endpoint: s.endpoint.clone(),
+                    searches: 0,
+                    zero_result_searches: 0,
+                    distinct_queries: 0,
+                });
+        summary.searches += s.count;
+        summary.zero_result_searches += s.zero_result_count;
+        summary.distinct_queries += 1;
+    }
+    let by_endpoint = endpoints.into_values().collect();
+
+    let mut top_zero_result: Vec<SearchQueryStats> = stats
+        .iter()
+        .filter(|s| s.zero_result_count > 0)
+        .cloned()
+        .collect();
+    top_zero_result.sort_by(|a, b| {
+        b.zero_result_count
+            .cmp(&a.zero_result_count)
+            .then(b.last_seen_at.cmp(&a.last_seen_at))
+    });
+    top_zero_result.truncate(limit);
+
+    let distinct_queries = stats.len();
+    stats.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then(b.last_seen_at.cmp(&a.last_seen_at))
+    });
+    stats.truncate(limit);
+
+    SearchAnalyticsResponse {
+        total_searches,
+        zero_result_searches,
+        zero_result_rate: if total_searches == 0 {
+            0.0
+        } else {
+            zero_result_searches as f64 / total_searches as f64
+        },
+        mcp_searches,
+        distinct_queries,
+        by_endpoint,
+        top_queries: stats,
+        top_zero_result,
+    }
+}
+
+/// GET /api/search/analytics — Most searched and zero-result queries
+pub async fn get_search_analytics(
+    State(state): State<OrchestratorState>,
+    Query(query): Query<SearchAnalyticsQuery>,
+) -> Result<Json<SearchAnalyticsResponse>, AppError> {
+    let limit = query.limit.unwrap_or(DEFAULT_ANALYTICS_LIMIT);
+    if limit == 0 || limit > MAX_ANALYTICS_LIMIT {
+        return Err(AppError::BadRequest(format!(
+            "limit must be between 1 and {}",
+            MAX_ANALYTICS_LIMIT
+        )));
+    }
+    let stats = state
+        .orchestrator
+        .neo4j()
+        .list_search_query_stats(
+            query.endpoint.as_deref(),
+            query.project_slug.as_deref(),
+            query.since,
+        )
+        .await?;
+    Ok(Json(summarize(stats, limit)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::handlers::ServerState;
+    use crate::api::routes::create_router;
+    use crate::orchestrator::watcher::FileWatcher;
+    use crate::orchestrator::Orchestrator;
+    use crate::test_helpers::{mock_app_state, test_auth_config, test_bearer_token};
+    use axum::{body::Body, http::Request};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    async fn mock_server_state() -> OrchestratorState {
+        let app_state = mock_app_state();
+        let orchestrator = Arc::new(Orchestrator::new(app_state).await.unwrap());
+        let watcher = Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
+            orchestrator.clone(),
+        )));
+        Arc::new(ServerState {
+            orchestrator,
+            watcher,
+            chat_manager: None,
+            event_bus: Arc::new(crate::events::HybridEmitter::new(Arc::new(
+                crate::events::EventBus::default(),
+            ))),
+            nats_emitter: None,
+            auth_config: Some(test_auth_config()),
+            serve_frontend: false,
+            frontend_path: "./dist".to_string(),
+            setup_completed: true,
+            server_port: 6600,
+            public_url: None,
+            read_only: false,
+            ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
+            registry_remote_url: None,
+            oidc_client: None,
+            neural_router: crate::test_helpers::mock_neural_router(),
+            trajectory_collector: std::sync::RwLock::new(None),
+            trajectory_store_neo4j: None,
+            trajectory_store: None,
+            identity: None,
+            reactor_counters: std::sync::OnceLock::new(),
+            confidence_tracker: Arc::new(crate::graph::confidence::ConfidenceTracker::default()),
+            mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
+            model_catalog: crate::chat::model_catalog::ModelCatalogCache::new(None),
+        })
+    }
+
+    fn event(endpoint: &str, query: &str, hits: usize, from_mcp: bool) -> SearchQueryEvent {
+        SearchQueryEvent {
+            endpoint: endpoint.to_string(),
+            query: query.to_string(),
+            project_slug: Some("po".to_string()),
+            hits,
+            from_mcp,
+            at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_query() {
+        assert_eq!(normalize_query("  Auth\t Token \n"), "auth token");
+        assert_eq!(normalize_query("   "), "");
+    }
+
+    #[tokio::test]
+    async fn test_search_analytics() {
+        let state = mock_server_state().await;
+        let neo4j = state.orchestrator.neo4j();
+        for e in [
+            event("code", "auth token", 4, false),
+            event("code", "auth token", 0, true),
+            event("code", "auth token", 3, true),
+            event("notes", "retry policy", 0, true),
+            event("notes", "retry policy", 0, true),
+            event("docs", "setup", 2, false),
+        ] {
+            neo4j.record_search_query(&e).await.unwrap();
+        }
+
+        let app = create_router(state);
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/search/analytics")
+                    .header("authorization", test_bearer_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), axum::http::StatusCode::OK);
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["total_searches"], 6);
+        assert_eq!(json["zero_result_searches"], 3);
+        assert_eq!(json["zero_result_rate"], 0.5);
+        assert_eq!(json["mcp_searches"], 4);
+        assert_eq!(json["distinct_queries"], 3);
+        assert_eq!(json["by_endpoint"].as_array().unwrap().len(), 3);
+        assert_eq!(json["top_queries"][0]["query"], "auth token");
+        assert_eq!(json["top_queries"][0]["count"], 3);
+        assert_eq!(json["top_queries"][0]["last_hits"], 3);
+        assert_eq!(json["top_zero_result"][0]["query"], "retry policy");
+        assert_eq!(json["top_zero_result"].as_array().unwrap().len(), 2);
+
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/search/analytics?endpoint=notes&limit=1")
+                    .header("authorization", test_bearer_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["total_searches"], 2);
+        assert_eq!(json["distinct_queries"], 1);
+        assert_eq!(json["top_queries"].as_array().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,281 @@
+//! Search settings API handlers
+//!
+//! Synonyms, stop words and ranking rules shared by every Meilisearch index
+//! (code, decisions, notes, docs). They are stored once per deployment on the
+//! `SearchSettings` node and re-applied to Meilisearch at startup, so domain
+//! terms ("repo" = "project", "fn" = "function") improve recall without
+//! editing Meilisearch directly.
+
+use super::handlers::{AppError, OrchestratorState};
+use crate::auth::extractor::AuthUser;
+use crate::meilisearch::indexes::SearchSettings;
+use axum::{extract::State, Json};
+use std::collections::BTreeMap;
+
+/// Built-in Meilisearch ranking rules. Custom rules are `attribute:asc` or
+/// `attribute:desc`.
+const BUILTIN_RANKING_RULES: &[&str] = &[
+    "words",
+    "typo",
+    "proximity",
+    "attribute",
+    "sort",
+    "exactness",
+];
+
+fn normalize_word(word: &str) -> String {
+    word.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Lowercase and deduplicate synonyms and stop words, and check the ranking
+/// rules.
+pub fn normalize_settings(settings: SearchSettings) -> Result<SearchSettings, AppError> {
+    let mut synonyms: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (word, words) in settings.synonyms {
+        let word = normalize_word(&word);
+        if word.is_empty() {
+            return Err(AppError::BadRequest(
+                "Synonyms cannot have an empty word".to_string(),
+            ));
+        }
+        let entry = synonyms.entry(word.clone()).or_default();
+        for synonym in words.iter().map(|w| normalize_word(w)) {
+            if !synonym.is_empty() && synonym != word && !entry.contains(&synonym) {
+                entry.push(synonym);
+            }
+        }
+        if entry.is_empty() {
+            return Err(AppError::BadRequest(format!(
+                "Synonyms of '{}' are empty",
+                word
+            )));
+        }
+    }
+
+    let mut stop_words: Vec<String> = settings
+        .stop_words
+        .iter()
+        .map(|w| normalize_word(w))
+        .filter(|w| !w.is_empty())
+        .collect();
+    stop_words.sort();
+    stop_words.dedup();
+
+    if let Some(ref rules) = settings.ranking_rules {
+        if rules.is_empty() {
+            return Err(AppError::BadRequest(
+                "ranking_rules cannot be empty: omit it to keep the defaults".to_string(),
+            ));
+        }
+        for rule in rules {
+            let custom = rule
+                .rsplit_once(':')
+                .is_some_and(|(attr, order)| !attr.is_empty() && matches!(order, "asc" | "desc"));
+            if !custom && !BUILTIN_RANKING_RULES.contains(&rule.as_str()) {
+                return Err(AppError::BadRequest(format!(
+                    "Unknown ranking rule '{}': expected one of {} or attribute:asc|desc",
+                    rule,
+                    BUILTIN_RANKING_RULES.join(", ")
+                )));
+            }
+        }
+    }
+
+    Ok(SearchSettings {
+        synonyms,
+        stop_words,
+        ranking_rules: settings.ranking_rules,
+    })
+}
+
+/// GET /api/search/settings — Synonyms, stop words and ranking rules
+pub async fn get_search_settings(
+    State(state): State<OrchestratorState>,
+) -> Result<Json<SearchSettings>, AppError> {
+    let settings = state
+        .orchestrator
+        .neo4j()
+        .get_search_settings()
+        .await?
+        .unwrap_or_default();
+    Ok(Json(settings))
+}
+
+/// PUT /api/search/settings — Replace the search settings and apply them to
+/// every index. Reserved to the root account when auth is enabled.
+pub async fn set_search_settings(
+    State(state): State<OrchestratorState>,
+    user: AuthUser,
+    Json(settings): Json<SearchSettings>,
+) -> Result<Json<SearchSettings>, AppError> {
+    if let Some(auth) = state.auth_config.as_ref() {
+        if !auth.is_root_user(user.user_id) {
+            return Err(AppError::Forbidden(
+                "Search settings are reserved to the root account".to_string(),
+            ));
+        }
+    }
+    let settings = normalize_settings(settings)?;
+    // Apply first: settings Meilisearch rejects are not stored
+    state
+        .orchestrator
+        .meili()
+        .apply_search_settings(&settings)
+        .await?;
+    state
+        .orchestrator
+        .neo4j()
+        .update_search_settings(&settings)
+        .await?;
+    Ok(Json(settings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::handlers::ServerState;
+    use crate::api::routes::create_router;
+    use crate::meilisearch::mock::MockSearchStore;
+    use crate::orchestrator::watcher::FileWatcher;
+    use crate::orchestrator::Orchestrator;
+    use crate::test_helpers::{mock_app_state_with_stores, test_auth_config, test_bearer_token};
+    use axum::{body::Body, http::Request};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    const ROOT_EMAIL: &str = "admin@example.com";
+
+    async fn mock_server_state() -> (OrchestratorState, Arc<MockSearchStore>) {
+        let (app_state, _, meili) = mock_app_state_with_stores();
+        let orchestrator = Arc::new(Orchestrator::new(app_state).await.unwrap());
+        let watcher = Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
+            orchestrator.clone(),
+        )));
+        let mut auth_config = test_auth_config();
+        auth_config.root_account = Some(crate::RootAccountConfig {
+            email: ROOT_EMAIL.to_string(),
+            name: "Admin".to_string(),
+            password_hash: String::new(),
+        });
+        let state = Arc::new(ServerState {
+            orchestrator,
+            watcher,
+            chat_manager: None,
+            event_bus: Arc::new(crate::events::HybridEmitter::new(Arc::new(
+                crate::events::EventBus::default(),
+            ))),
+            nats_emitter: None,
+            auth_config: Some(auth_config),
+            serve_frontend: false,
+            frontend_path: "./dist".to_string(),
+            setup_completed: true,
+            server_port: 6600,
+            public_url: None,
+            read_only: false,
+            ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
+            registry_remote_url: None,
+            oidc_client: None,
+            neural_router: crate::test_helpers::mock_neural_router(),
+            trajectory_collector: std::sync::RwLock::new(None),
+            trajectory_store_neo4j: None,
+            trajectory_store: None,
+            identity: None,
+            reactor_counters: std::sync::OnceLock::new(),
+            confidence_tracker: Arc::new(crate::graph::confidence::ConfidenceTracker::default()),
+            mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
+            model_catalog: crate::chat::model_catalog::ModelCatalogCache::new(None),
+        });
+        (state, meili)
+    }
+
+    fn root_bearer_token() -> String {
+        let token = crate::auth::jwt::encode_jwt(
+            Uuid::new_v5(&Uuid::NAMESPACE_URL, ROOT_EMAIL.as_bytes()),
+            ROOT_EMAIL,
+            "Admin",
+            "test-secret-key-minimum-32-chars!!",
+            28800,
+        )
+        .unwrap();
+        format!("Bearer {}", token)
+    }
+
+    fn request(method: &str, token: String, body: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri("/api/search/settings")
+            .header("content-type", "application/json")
+            .header("authorization", token)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_normalize_settings() {
+        let settings: SearchSettings = serde_json::from_value(serde_json::json!({
+            "synonyms": {"Repo": ["project", " Project ", "repo"]},
+            "stop_words": ["The", "a", "the", " "],
+            "ranking_rules": ["words", "typo", "created_at:desc"],
+        }))
+        .unwrap();
+        let settings = normalize_settings(settings).unwrap();
+        assert_eq!(settings.synonyms["repo"], vec!["project".to_string()]);
+        assert_eq!(settings.stop_words, vec!["a", "the"]);
+
+        let bad_rule = SearchSettings {
+            ranking_rules: Some(vec!["relevance".to_string()]),
+            ..Default::default()
+        };
+        assert!(normalize_settings(bad_rule).is_err());
+        let empty_synonyms = SearchSettings {
+            synonyms: BTreeMap::from([("fn".to_string(), vec!["fn".to_string()])]),
+            ..Default::default()
+        };
+        assert!(normalize_settings(empty_synonyms).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_settings() {
+        let (state, meili) = mock_server_state().await;
+        let app = create_router(state);
+        let body = serde_json::json!({
+            "synonyms": {"repo": ["project"], "project": ["repo"], "fn": ["function"]},
+            "stop_words": ["the"],
+        });
+
+        let resp = app
+            .clone()
+            .oneshot(request("PUT", test_bearer_token(), body.clone()))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), axum::http::StatusCode::FORBIDDEN);
+
+        let resp = app
+            .clone()
+            .oneshot(request("PUT", root_bearer_token(), body))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), axum::http::StatusCode::OK);
+        let applied = meili.search_settings.read().await.clone().unwrap();
+        assert_eq!(applied.synonyms["fn"], vec!["function".to_string()]);
+        assert!(applied.ranking_rules.is_none());
+
+        let resp = app
+            .oneshot(request("GET", test_bearer_token(), serde_json::json!({})))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), axum::http::StatusCode::OK);
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["synonyms"]["repo"][0], "project");
+        assert_eq!(json["stop_words"][0], "the");
+    }
+}
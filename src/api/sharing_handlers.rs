@@ -4,6 +4,7 @@
 //! the REST surface consumed by the frontend Sharing page.
 
 use super::handlers::{AppError, OrchestratorState};
+use super::validation::ValidPath;
 use crate::episodes::distill_models::{
     ConsentStats, PrivacyMode, SharingConsent, SharingEvent, SharingMode, SharingPolicy,
 };
@@ -260,7 +261,7 @@ pub async fn set_sharing_policy(
 /// PUT /api/notes/{note_id}/sharing/consent
 pub async fn set_sharing_consent(
     State(state): State<OrchestratorState>,
-    Path(note_id): Path<Uuid>,
+    ValidPath(note_id): ValidPath<Uuid>,
     Json(body): Json<ConsentUpdateRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     state
@@ -521,7 +522,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -561,7 +565,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -2,6 +2,7 @@
 
 use super::handlers::{AppError, OrchestratorState};
 use super::hook_handlers::skill_cache;
+use super::validation::ValidPath;
 use super::{PaginatedResponse, PaginationParams};
 use crate::events::EventEmitter;
 use crate::skills::{
@@ -9,7 +10,7 @@ use crate::skills::{
     TriggerType, MAX_TRIGGER_PATTERN_LEN, REGEX_DFA_SIZE_LIMIT, REGEX_SIZE_LIMIT,
 };
 use axum::{
-    extract::{Path, Query, State},
+    extract::{Query, State},
     http::StatusCode,
     Json,
 };
@@ -320,7 +321,7 @@ pub async fn create_skill(
 /// GET /api/skills/:id
 pub async fn get_skill(
     State(state): State<OrchestratorState>,
-    Path(skill_id): Path<Uuid>,
+    ValidPath(skill_id): ValidPath<Uuid>,
 ) -> Result<Json<SkillNode>, AppError> {
     let skill = state
         .orchestrator
@@ -338,7 +339,7 @@ pub async fn get_skill(
 /// PUT /api/skills/:id
 pub async fn update_skill(
     State(state): State<OrchestratorState>,
-    Path(skill_id): Path<Uuid>,
+    ValidPath(skill_id): ValidPath<Uuid>,
     Json(body): Json<UpdateSkillBody>,
 ) -> Result<Json<SkillNode>, AppError> {
     let mut skill = state
@@ -441,7 +442,7 @@ pub async fn update_skill(
 /// DELETE /api/skills/:id
 pub async fn delete_skill(
     State(state): State<OrchestratorState>,
-    Path(skill_id): Path<Uuid>,
+    ValidPath(skill_id): ValidPath<Uuid>,
 ) -> Result<StatusCode, AppError> {
     // Get project_id before deletion for cache invalidation
     let skill = state
@@ -487,7 +488,7 @@ pub async fn delete_skill(
 /// GET /api/skills/:id/members
 pub async fn get_skill_members(
     State(state): State<OrchestratorState>,
-    Path(skill_id): Path<Uuid>,
+    ValidPath(skill_id): ValidPath<Uuid>,
 ) -> Result<Json<SkillMembersResponse>, AppError> {
     let (notes, decisions) = state
         .orchestrator
@@ -504,7 +505,7 @@ pub async fn get_skill_members(
 /// POST /api/skills/:id/members
 pub async fn add_skill_member(
     State(state): State<OrchestratorState>,
-    Path(skill_id): Path<Uuid>,
+    ValidPath(skill_id): ValidPath<Uuid>,
     Json(body): Json<AddMemberBody>,
 ) -> Result<StatusCode, AppError> {
     if !["note", "decision"].contains(&body.entity_type.as_str()) {
@@ -554,7 +555,7 @@ pub async fn add_skill_member(
 /// DELETE /api/skills/:id/members/:entity_type/:entity_id
 pub async fn remove_skill_member(
     State(state): State<OrchestratorState>,
-    Path((skill_id, entity_type, entity_id)): Path<(Uuid, String, Uuid)>,
+    ValidPath((skill_id, entity_type, entity_id)): ValidPath<(Uuid, String, Uuid)>,
 ) -> Result<StatusCode, AppError> {
     if !["note", "decision"].contains(&entity_type.as_str()) {
         return Err(AppError::BadRequest(
@@ -616,7 +617,7 @@ pub async fn remove_skill_member(
 /// POST /api/skills/:id/activate
 pub async fn activate_skill(
     State(state): State<OrchestratorState>,
-    Path(skill_id): Path<Uuid>,
+    ValidPath(skill_id): ValidPath<Uuid>,
     Json(body): Json<ActivateSkillBody>,
 ) -> Result<Json<ActivatedSkillContext>, AppError> {
     if body.query.trim().is_empty() {
@@ -651,7 +652,7 @@ pub async fn activate_skill(
 /// GET /api/skills/:id/export?source_project_name=...
 pub async fn export_skill(
     State(state): State<OrchestratorState>,
-    Path(skill_id): Path<Uuid>,
+    ValidPath(skill_id): ValidPath<Uuid>,
     Query(query): Query<ExportQuery>,
 ) -> Result<Json<crate::skills::SkillPackage>, AppError> {
     let identity_ref = state.identity.as_deref();
@@ -730,7 +731,7 @@ pub async fn import_skill(
 /// GET /api/skills/:id/health
 pub async fn get_skill_health(
     State(state): State<OrchestratorState>,
-    Path(skill_id): Path<Uuid>,
+    ValidPath(skill_id): ValidPath<Uuid>,
 ) -> Result<Json<crate::skills::SkillHealthMetrics>, AppError> {
     let neo4j = state.orchestrator.neo4j();
 
@@ -778,7 +779,7 @@ pub struct MergeSkillsRequest {
 /// Archives the original skill and creates N new skills.
 pub async fn split_skill(
     State(state): State<OrchestratorState>,
-    Path(skill_id): Path<Uuid>,
+    ValidPath(skill_id): ValidPath<Uuid>,
     Json(body): Json<SplitSkillRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     use crate::skills::evolution::{execute_evolution, SkillEvolution, MIN_MEMBERS_FOR_SPLIT};
@@ -1235,7 +1236,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -1274,7 +1278,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -1367,7 +1374,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -1428,7 +1438,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -1476,7 +1489,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -1527,7 +1543,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -1583,7 +1602,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -7,8 +7,9 @@
 //! - POST /api/trajectories/similar  — vector similarity search
 
 use super::handlers::{AppError, OrchestratorState};
+use super::validation::ValidPath;
 use axum::{
-    extract::{Path, Query, State},
+    extract::{Query, State},
     Json,
 };
 use neural_routing_runtime::{Trajectory, TrajectoryFilter, TrajectoryStats};
@@ -151,7 +152,7 @@ pub async fn get_stats(
 /// Get a single trajectory with all its nodes.
 pub async fn get_trajectory(
     State(state): State<OrchestratorState>,
-    Path(id): Path<Uuid>,
+    ValidPath(id): ValidPath<Uuid>,
 ) -> Result<Json<TrajectoryDetailResponse>, AppError> {
     let store = get_store(&state)?;
 
@@ -236,7 +237,10 @@ mod tests {
             setup_completed: true,
             server_port: 0,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: mock_neural_router(),
@@ -272,7 +276,10 @@ mod tests {
             setup_completed: true,
             server_port: 0,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: mock_neural_router(),
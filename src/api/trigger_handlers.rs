@@ -1,10 +1,11 @@
 //! API handlers for EventTriggers
 
 use super::handlers::{AppError, OrchestratorState};
+use super::validation::ValidPath;
 use crate::events::trigger::EventTrigger;
 use crate::events::{CrudAction, CrudEvent, EntityType, EventEmitter};
 use axum::{
-    extract::{Path, Query, State},
+    extract::{Query, State},
     Json,
 };
 use chrono::Utc;
@@ -134,7 +135,7 @@ pub async fn create_trigger(
 /// GET /api/triggers/:id — get a trigger by ID
 pub async fn get_trigger(
     State(state): State<OrchestratorState>,
-    Path(id): Path<Uuid>,
+    ValidPath(id): ValidPath<Uuid>,
 ) -> Result<Json<EventTrigger>, AppError> {
     let trigger = state
         .orchestrator
@@ -148,7 +149,7 @@ pub async fn get_trigger(
 /// PUT /api/triggers/:id — update a trigger (enable/disable, modify patterns)
 pub async fn update_trigger(
     State(state): State<OrchestratorState>,
-    Path(id): Path<Uuid>,
+    ValidPath(id): ValidPath<Uuid>,
     Json(body): Json<UpdateTriggerRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let updated = state
@@ -183,7 +184,7 @@ pub async fn update_trigger(
 /// DELETE /api/triggers/:id — delete a trigger
 pub async fn delete_trigger(
     State(state): State<OrchestratorState>,
-    Path(id): Path<Uuid>,
+    ValidPath(id): ValidPath<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let deleted = state.orchestrator.neo4j().delete_event_trigger(id).await?;
 
@@ -204,7 +205,7 @@ pub async fn delete_trigger(
 /// POST /api/triggers/:id/enable — enable a trigger
 pub async fn enable_trigger(
     State(state): State<OrchestratorState>,
-    Path(id): Path<Uuid>,
+    ValidPath(id): ValidPath<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let updated = state
         .orchestrator
@@ -227,7 +228,7 @@ pub async fn enable_trigger(
 /// POST /api/triggers/:id/disable — disable a trigger
 pub async fn disable_trigger(
     State(state): State<OrchestratorState>,
-    Path(id): Path<Uuid>,
+    ValidPath(id): ValidPath<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let updated = state
         .orchestrator
@@ -316,7 +317,10 @@ mod tests {
             setup_completed: true,
             server_port: 0,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -575,7 +579,7 @@ mod tests {
         // MockGraphStore.get_event_trigger always returns None
         let state = test_state().await;
         let id = Uuid::new_v4();
-        let result = get_trigger(State(state), Path(id)).await;
+        let result = get_trigger(State(state), ValidPath(id)).await;
         assert!(result.is_err());
         match result.unwrap_err() {
             AppError::NotFound(msg) => {
@@ -599,7 +603,7 @@ mod tests {
             cooldown_secs: None,
             project_scope: None,
         };
-        let result = update_trigger(State(state), Path(id), Json(body)).await;
+        let result = update_trigger(State(state), ValidPath(id), Json(body)).await;
         assert!(result.is_err());
         match result.unwrap_err() {
             AppError::NotFound(msg) => {
@@ -614,7 +618,7 @@ mod tests {
         // MockGraphStore.delete_event_trigger always returns Ok(false)
         let state = test_state().await;
         let id = Uuid::new_v4();
-        let result = delete_trigger(State(state), Path(id)).await;
+        let result = delete_trigger(State(state), ValidPath(id)).await;
         assert!(result.is_err());
         match result.unwrap_err() {
             AppError::NotFound(msg) => {
@@ -629,7 +633,7 @@ mod tests {
         // MockGraphStore.update_event_trigger always returns Ok(false)
         let state = test_state().await;
         let id = Uuid::new_v4();
-        let result = enable_trigger(State(state), Path(id)).await;
+        let result = enable_trigger(State(state), ValidPath(id)).await;
         assert!(result.is_err());
         match result.unwrap_err() {
             AppError::NotFound(msg) => {
@@ -644,7 +648,7 @@ mod tests {
         // MockGraphStore.update_event_trigger always returns Ok(false)
         let state = test_state().await;
         let id = Uuid::new_v4();
-        let result = disable_trigger(State(state), Path(id)).await;
+        let result = disable_trigger(State(state), ValidPath(id)).await;
         assert!(result.is_err());
         match result.unwrap_err() {
             AppError::NotFound(msg) => {
@@ -0,0 +1,477 @@
+//! Usage telemetry API handlers
+//!
+//! Opt-in (`telemetry.enabled`), anonymous counters of the API routes served,
+//! per day and client (REST or MCP), so that self-hosting admins can see
+//! which subsystems their team actually uses. Only the route pattern is
+//! counted (`POST /api/projects/{slug}/sync`): never path parameters, query
+//! strings, bodies or users. Counts are buffered in memory, flushed to
+//! `UsageCounter` nodes every minute and never leave the instance.
+//! `GET /api/admin/usage` summarizes them, `GET /api/admin/usage/export`
+//! returns the raw counters.
+
+use super::handlers::{AppError, OrchestratorState};
+use super::search_analytics_handlers::CLIENT_HEADER;
+use crate::auth::extractor::AuthUser;
+use crate::neo4j::models::UsageCount;
+use crate::neo4j::traits::GraphStore;
+use axum::{
+    extract::{MatchedPath, Query, Request, State},
+    http::header,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, LazyLock, Mutex};
+
+/// Interval between two flushes of the buffered counts
+const FLUSH_INTERVAL_SECS: u64 = 60;
+
+const DEFAULT_USAGE_DAYS: i64 = 30;
+const MAX_USAGE_DAYS: i64 = 366;
+
+/// Routes listed per subsystem in the dashboard
+const TOP_ROUTES: usize = 10;
+
+/// Counts not flushed yet, keyed by (day, route, client)
+static PENDING: LazyLock<Mutex<HashMap<(NaiveDate, String, String), u64>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn add_pending(day: NaiveDate, route: String, client: String, count: u64) {
+    *PENDING
+        .lock()
+        .unwrap()
+        .entry((day, route, client))
+        .or_insert(0) += count;
+}
+
+/// Write the buffered counts to the graph. Counts that fail to be written
+/// are kept for the next flush.
+pub async fn flush(store: &dyn GraphStore) {
+    let counts: Vec<UsageCount> = PENDING
+        .lock()
+        .unwrap()
+        .drain()
+        .map(|((day, route, client), count)| UsageCount {
+            day,
+            route,
+            client,
+            count,
+        })
+        .collect();
+    if counts.is_empty() {
+        return;
+    }
+    if let Err(e) = store.record_usage(&counts).await {
+        tracing::warn!("Failed to flush usage counters: {}", e);
+        for c in counts {
+            add_pending(c.day, c.route, c.client, c.count);
+        }
+    }
+}
+
+/// Spawn the periodic flush of the buffered counts. Every instance flushes
+/// the requests it served.
+///
+/// This task runs for the lifetime of the server and never returns.
+pub fn spawn_usage_flusher(store: Arc<dyn GraphStore>) {
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(FLUSH_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            flush(&*store).await;
+        }
+    });
+}
+
+/// Middleware counting the API routes served when usage telemetry is enabled.
+pub async fn count_usage(
+    State(state): State<OrchestratorState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if state.orchestrator.config().telemetry_enabled {
+        // Unmatched paths have no MatchedPath: they are not counted, so that
+        // arbitrary URLs never end up in the counters
+        if let Some(path) = req.extensions().get::<MatchedPath>() {
+            if path.as_str().starts_with("/api/") {
+                let client = match req
+                    .headers()
+                    .get(CLIENT_HEADER)
+                    .and_then(|v| v.to_str().ok())
+                {
+                    Some(v) if v.eq_ignore_ascii_case("mcp") => "mcp",
+                    _ => "rest",
+                };
+                add_pending(
+                    Utc::now().date_naive(),
+                    format!("{} {}", req.method(), path.as_str()),
+                    client.to_string(),
+                    1,
+                );
+            }
+        }
+    }
+    next.run(req).await
+}
+
+/// Subsystem of a counted route: its first path segment after `/api/`
+/// ("POST /api/projects/{slug}/sync" is "projects").
+pub fn subsystem_of(route: &str) -> &str {
+    let path = route.split_once(' ').map_or(route, |(_, path)| path);
+    path.trim_start_matches("/api/")
+        .split('/')
+        .next()
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Serialize)]
+pub struct RouteUsage {
+    pub route: String,
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SubsystemUsage {
+    pub subsystem: String,
+    pub count: u64,
+    pub mcp_count: u64,
+    /// Most used routes of the subsystem
+    pub top_routes: Vec<RouteUsage>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DailyUsage {
+    pub day: NaiveDate,
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UsageDashboard {
+    /// Whether requests are being counted (counters recorded while it was
+    /// enabled are still reported)
+    pub enabled: bool,
+    pub since: NaiveDate,
+    pub total: u64,
+    pub mcp_count: u64,
+    /// Most used first
+    pub subsystems: Vec<SubsystemUsage>,
+    /// Days with at least one request, oldest first
+    pub daily: Vec<DailyUsage>,
+}
+
+/// Summarize usage counters by subsystem and by day.
+pub fn summarize(counts: &[UsageCount], enabled: bool, since: NaiveDate) -> UsageDashboard {
+    let mut subsystems: BTreeMap<&str, (u64, u64, BTreeMap<&str, u64>)> = BTreeMap::new();
+    let mut daily: BTreeMap<NaiveDate, u64> = BTreeMap::new();
+    for c in counts {
+        let (count, mcp_count, routes) = subsystems.entry(subsystem_of(&c.route)).or_default();
+        *count += c.count;
+        if c.client == "mcp" {
+            *mcp_count += c.count;
+        }
+        *routes.entry(c.route.as_str()).or_insert(0) += c.count;
+        *daily.entry(c.day).or_insert(0) += c.count;
+    }
+
+    let mut subsystems: Vec<SubsystemUsage> = subsystems
+        .into_iter()
+        .map(|(subsystem, (count, mcp_count, routes))| {
+            let mut top_routes: Vec<RouteUsage> = routes
+                .into_iter()
+                .map(|(route, count)| RouteUsage {
+                    route: route.to_string(),
+                    count,
+                })
+                .collect();
+            top_routes.sort_by(|a, b| b.count.cmp(&a.count));
+            top_routes.truncate(TOP_ROUTES);
+            SubsystemUsage {
+                subsystem: subsystem.to_string(),
+                count,
+                mcp_count,
+                top_routes,
+            }
+        })
+        .collect();
+    subsystems.sort_by(|a, b| b.count.cmp(&a.count));
+
+    UsageDashboard {
+        enabled,
+        since,
+        total: counts.iter().map(|c| c.count).sum(),
+        mcp_count: subsystems.iter().map(|s| s.mcp_count).sum(),
+        subsystems,
+        daily: daily
+            .into_iter()
+            .map(|(day, count)| DailyUsage { day, count })
+            .collect(),
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct UsageQuery {
+    /// Days covered, today included (default 30, max 366)
+    pub days: Option<i64>,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct UsageExportQuery {
+    pub days: Option<i64>,
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+fn require_admin(state: &OrchestratorState, user: &AuthUser) -> Result<(), AppError> {
+    match state.auth_config.as_ref() {
+        Some(auth) if !auth.is_root_user(user.user_id) => Err(AppError::Forbidden(
+            "Usage telemetry is reserved to the root account".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Flush the buffered counts and read the counters of the last `days` days.
+async fn usage_since(
+    state: &OrchestratorState,
+    days: Option<i64>,
+) -> Result<(NaiveDate, Vec<UsageCount>), AppError> {
+    let days = days.unwrap_or(DEFAULT_USAGE_DAYS);
+    if !(1..=MAX_USAGE_DAYS).contains(&days) {
+        return Err(AppError::BadRequest(format!(
+            "days must be between 1 and {}",
+            MAX_USAGE_DAYS
+        )));
+    }
+    let since = Utc::now().date_naive() - Duration::days(days - 1);
+    let store = state.orchestrator.neo4j();
+    flush(store).await;
+    Ok((since, store.list_usage(since).await?))
+}
+
+/// GET /api/admin/usage — Requests per subsystem and per day
+pub async fn get_usage(
+    State(state): State<OrchestratorState>,
+    user: AuthUser,
+    Query(query): Query<UsageQuery>,
+) -> Result<Json<UsageDashboard>, AppError> {
+    require_admin(&state, &user)?;
+    let (since, counts) = usage_since(&state, query.days).await?;
+    let enabled = state.orchestrator.config().telemetry_enabled;
+    Ok(Json(summarize(&counts, enabled, since)))
+}
+
+/// GET /api/admin/usage/export — Raw daily counters, as JSON or CSV
+pub async fn export_usage(
+    State(state): State<OrchestratorState>,
+    user: AuthUser,
+    Query(query): Query<UsageExportQuery>,
+) -> Result<Response, AppError> {
+    require_admin(&state, &user)?;
+    let (_, counts) = usage_since(&state, query.days).await?;
+    if query.format == ExportFormat::Json {
+        return Ok(Json(counts).into_response());
+    }
+
+    // Route patterns contain no comma or quote, so no field needs quoting
+    let mut csv = String::from("day,subsystem,route,client,count\n");
+    for c in &counts {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            c.day,
+            subsystem_of(&c.route),
+            c.route,
+            c.client,
+            c.count
+        ));
+    }
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"usage.csv\"",
+            ),
+        ],
+        csv,
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::handlers::ServerState;
+    use crate::api::routes::create_router;
+    use crate::orchestrator::watcher::FileWatcher;
+    use crate::orchestrator::Orchestrator;
+    use crate::test_helpers::mock_app_state;
+    use axum::{
+        body::Body,
+        http::{Request as HttpRequest, StatusCode as AxumStatus},
+    };
+    use tower::ServiceExt;
+
+    async fn mock_server_state(telemetry_enabled: bool) -> OrchestratorState {
+        let mut app_state = mock_app_state();
+        let mut config = (*app_state.config).clone();
+        config.telemetry_enabled = telemetry_enabled;
+        app_state.config = Arc::new(config);
+        let orchestrator = Arc::new(Orchestrator::new(app_state).await.unwrap());
+        let watcher = Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
+            orchestrator.clone(),
+        )));
+        Arc::new(ServerState {
+            orchestrator,
+            watcher,
+            chat_manager: None,
+            event_bus: Arc::new(crate::events::HybridEmitter::new(Arc::new(
+                crate::events::EventBus::default(),
+            ))),
+            nats_emitter: None,
+            auth_config: None,
+            serve_frontend: false,
+            frontend_path: "./dist".to_string(),
+            setup_completed: true,
+            server_port: 6600,
+            public_url: None,
+            read_only: false,
+            ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
+            registry_remote_url: None,
+            oidc_client: None,
+            neural_router: crate::test_helpers::mock_neural_router(),
+            trajectory_collector: std::sync::RwLock::new(None),
+            trajectory_store_neo4j: None,
+            trajectory_store: None,
+            identity: None,
+            reactor_counters: std::sync::OnceLock::new(),
+            confidence_tracker: Arc::new(crate::graph::confidence::ConfidenceTracker::default()),
+            mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
+            model_catalog: crate::chat::model_catalog::ModelCatalogCache::new(None),
+        })
+    }
+
+    fn get(uri: &str, client: Option<&str>) -> HttpRequest<Body> {
+        let mut req = HttpRequest::builder().uri(uri);
+        if let Some(client) = client {
+            req = req.header(CLIENT_HEADER, client);
+        }
+        req.body(Body::empty()).unwrap()
+    }
+
+    async fn body(resp: Response) -> Vec<u8> {
+        axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap()
+            .to_vec()
+    }
+
+    #[test]
+    fn test_summarize() {
+        let day = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let count = |day, route: &str, client: &str, count| UsageCount {
+            day,
+            route: route.to_string(),
+            client: client.to_string(),
+            count,
+        };
+        let counts = vec![
+            count(day, "GET /api/projects", "rest", 3),
+            count(day, "POST /api/projects/{slug}/sync", "mcp", 2),
+            count(day.succ_opt().unwrap(), "GET /api/notes", "rest", 1),
+        ];
+        let dashboard = summarize(&counts, true, day);
+        assert_eq!(dashboard.total, 6);
+        assert_eq!(dashboard.mcp_count, 2);
+        assert_eq!(dashboard.subsystems[0].subsystem, "projects");
+        assert_eq!(dashboard.subsystems[0].count, 5);
+        assert_eq!(
+            dashboard.subsystems[0].top_routes[0].route,
+            "GET /api/projects"
+        );
+        assert_eq!(dashboard.daily.len(), 2);
+        assert_eq!(
+            subsystem_of("DELETE /api/admin/quotas/workspaces/{slug}"),
+            "admin"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_usage_is_counted_only_when_enabled() {
+        let app = create_router(mock_server_state(false).await);
+        app.clone()
+            .oneshot(get("/api/projects", None))
+            .await
+            .unwrap();
+        let resp = app.oneshot(get("/api/admin/usage", None)).await.unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let json: serde_json::Value = serde_json::from_slice(&body(resp).await).unwrap();
+        assert_eq!(json["enabled"], false);
+        assert_eq!(json["total"], 0);
+
+        let app = create_router(mock_server_state(true).await);
+        for client in [None, Some("mcp")] {
+            app.clone()
+                .oneshot(get("/api/projects", client))
+                .await
+                .unwrap();
+        }
+        // Unmatched paths are not counted
+        app.clone()
+            .oneshot(get("/api/secret-project-name", None))
+            .await
+            .unwrap();
+
+        let resp = app
+            .clone()
+            .oneshot(get("/api/admin/usage?days=7", None))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        let json: serde_json::Value = serde_json::from_slice(&body(resp).await).unwrap();
+        assert_eq!(json["enabled"], true);
+        // The dashboard request itself is counted too
+        assert_eq!(json["total"], 3);
+        assert_eq!(json["mcp_count"], 1);
+        assert_eq!(json["subsystems"][0]["subsystem"], "projects");
+        assert_eq!(json["subsystems"][0]["count"], 2);
+        assert_eq!(json["subsystems"][1]["subsystem"], "admin");
+
+        let resp = app
+            .clone()
+            .oneshot(get("/api/admin/usage/export?format=csv", None))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::OK);
+        assert_eq!(
+            resp.headers()[header::CONTENT_TYPE],
+            "text/csv; charset=utf-8"
+        );
+        let csv = String::from_utf8(body(resp).await).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "day,subsystem,route,client,count");
+        assert!(lines
+            .iter()
+            .any(|l| l.ends_with(",projects,GET /api/projects,mcp,1")));
+        assert!(!csv.contains("secret-project-name"));
+
+        let resp = app
+            .oneshot(get("/api/admin/usage?days=0", None))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), AxumStatus::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,580 @@
+//! Request validation
+//!
+//! [`ValidJson`], [`ValidQuery`] and [`ValidPath`] replace axum's `Json`,
+//! `Query` and `Path` extractors in handlers. Malformed input (wrong types,
+//! unknown enum values, malformed UUIDs) and input breaking the [`Validate`]
+//! rules of the request (length limits, path traversal in file paths, ...)
+//! is rejected before the handler runs, with a 422 listing every invalid
+//! field:
+//!
+//! ```json
+//! {
+//!   "code": "validation_failed",
+//!   "status": 422,
+//!   "errors": [
+//!     {"field": "title", "message": "must not be empty"},
+//!     {"field": "affected_files[1]", "message": "must not contain '..' segments"}
+//!   ]
+//! }
+//! ```
+
+use super::error::AppError;
+use crate::notes::models::{CreateNoteRequest, EntityType, UpdateNoteRequest};
+use crate::plan::models::{
+    AddStepRequest, CreateDecisionRequest, CreatePlanRequest, CreateTaskRequest, UpdateStepRequest,
+    UpdateTaskRequest,
+};
+use axum::{
+    extract::{
+        path::ErrorKind, rejection::JsonRejection, rejection::PathRejection,
+        rejection::QueryRejection, FromRequest, FromRequestParts, Path, Query, RawPathParams,
+        Request,
+    },
+    http::request::Parts,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Display;
+
+/// Max length of titles and names
+pub const MAX_TITLE_LEN: usize = 500;
+/// Max length of descriptions, rationales and note contents
+pub const MAX_TEXT_LEN: usize = 100_000;
+/// Max length of search queries
+pub const MAX_QUERY_LEN: usize = 1_000;
+/// Max length of a file path
+pub const MAX_PATH_LEN: usize = 4_096;
+/// Max length of an item of a list field
+pub const MAX_ITEM_LEN: usize = 5_000;
+/// Max number of items of a list field (tags, acceptance criteria, files, ...)
+pub const MAX_LIST_LEN: usize = 200;
+/// Max number of tags
+pub const MAX_TAGS: usize = 50;
+/// Max length of a tag
+pub const MAX_TAG_LEN: usize = 100;
+
+/// An invalid request field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldError {
+    /// Path of the field in the request (`title`, `steps[0].description`, ...)
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Collects the field errors of a request
+#[derive(Debug, Default)]
+pub struct Validator {
+    errors: Vec<FieldError>,
+}
+
+impl Validator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn error(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.errors.push(FieldError::new(field, message));
+    }
+
+    pub fn not_blank(&mut self, field: &str, value: &str) {
+        if value.trim().is_empty() {
+            self.error(field, "must not be empty");
+        }
+    }
+
+    pub fn max_len(&mut self, field: &str, value: &str, max: usize) {
+        let len = value.chars().count();
+        if len > max {
+            self.error(
+                field,
+                format!("must be at most {} characters, got {}", max, len),
+            );
+        }
+    }
+
+    /// A required text field: not blank and at most `max` characters
+    pub fn text(&mut self, field: &str, value: &str, max: usize) {
+        self.not_blank(field, value);
+        self.max_len(field, value, max);
+    }
+
+    pub fn range<T: PartialOrd + Display>(&mut self, field: &str, value: T, min: T, max: T) {
+        if value < min || value > max {
+            self.error(
+                field,
+                format!("must be between {} and {}, got {}", min, max, value),
+            );
+        }
+    }
+
+    /// A list of short strings, such as acceptance criteria
+    pub fn list(&mut self, field: &str, items: &[String], max_item_len: usize) {
+        if items.len() > MAX_LIST_LEN {
+            self.error(
+                field,
+                format!(
+                    "must have at most {} items, got {}",
+                    MAX_LIST_LEN,
+                    items.len()
+                ),
+            );
+        }
+        for (i, item) in items.iter().enumerate() {
+            self.max_len(&format!("{}[{}]", field, i), item, max_item_len);
+        }
+    }
+
+    pub fn tags(&mut self, field: &str, tags: &[String]) {
+        if tags.len() > MAX_TAGS {
+            self.error(
+                field,
+                format!("must have at most {} tags, got {}", MAX_TAGS, tags.len()),
+            );
+        }
+        for (i, tag) in tags.iter().enumerate() {
+            self.text(&format!("{}[{}]", field, i), tag, MAX_TAG_LEN);
+        }
+    }
+
+    pub fn uuid(&mut self, field: &str, value: &str) {
+        if uuid::Uuid::parse_str(value).is_err() {
+            self.error(field, format!("'{}' is not a valid UUID", value));
+        }
+    }
+
+    pub fn one_of(&mut self, field: &str, value: &str, allowed: &[&str]) {
+        if !allowed.contains(&value) {
+            self.error(
+                field,
+                format!(
+                    "unknown value '{}': expected one of {}",
+                    value,
+                    allowed.join(", ")
+                ),
+            );
+        }
+    }
+
+    /// A file path: not blank, no NUL byte and no `..` segment, so that it
+    /// cannot escape the project root it is resolved against.
+    pub fn file_path(&mut self, field: &str, value: &str) {
+        self.text(field, value, MAX_PATH_LEN);
+        if value.contains('\0') {
+            self.error(field, "must not contain NUL bytes");
+        }
+        if value.split(['/', '\\']).any(|segment| segment == "..") {
+            self.error(field, "must not contain '..' segments");
+        }
+    }
+
+    pub fn finish(self) -> Result<(), AppError> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::Validation(self.errors))
+        }
+    }
+}
+
+/// Validation rules of a request
+pub trait Validate {
+    fn validate(&self, v: &mut Validator);
+}
+
+/// Check `value` against its rules
+pub fn validate<T: Validate>(value: &T) -> Result<(), AppError> {
+    let mut v = Validator::new();
+    value.validate(&mut v);
+    v.finish()
+}
+
+/// Check a single file path parameter
+pub fn validate_file_path(field: &str, value: &str) -> Result<(), AppError> {
+    let mut v = Validator::new();
+    v.file_path(field, value);
+    v.finish()
+}
+
+/// Split a serde error message into the field it is about and the message.
+///
+/// Handles `field.path: message` (as produced by axum's extractors) and
+/// `missing field `name``, and drops the ` at line L column C` suffix.
+fn split_serde_message(message: &str) -> FieldError {
+    let message = match message.rfind(" at line ") {
+        Some(i) if message[i..].contains(" column ") => &message[..i],
+        _ => message,
+    };
+    if let Some((path, inner)) = message.split_once(": ") {
+        if !path.is_empty() && !path.contains(' ') && path != "." {
+            return FieldError::new(path, inner);
+        }
+    }
+    if let Some(name) = message
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.split('`').next())
+    {
+        return FieldError::new(name, "is required");
+    }
+    FieldError::new("body", message)
+}
+
+/// Field error of an axum rejection message `"Failed to ...: <serde error>"`
+fn rejection_field_error(body_text: &str) -> FieldError {
+    let serde_message = body_text
+        .split_once(": ")
+        .map(|(_, rest)| rest)
+        .unwrap_or(body_text);
+    split_serde_message(serde_message)
+}
+
+/// `Json` that rejects invalid bodies with field-level 422s
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidJson<T>(pub T);
+
+impl<S, T> FromRequest<S> for ValidJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state)
+            .await
+            .map_err(|rejection| match rejection {
+                JsonRejection::JsonDataError(e) => {
+                    AppError::Validation(vec![rejection_field_error(&e.body_text())])
+                        .into_response()
+                }
+                JsonRejection::JsonSyntaxError(e) => {
+                    AppError::BadRequest(e.body_text()).into_response()
+                }
+                other => other.into_response(),
+            })?;
+        validate(&value).map_err(IntoResponse::into_response)?;
+        Ok(Self(value))
+    }
+}
+
+/// `Query` that rejects invalid query strings with field-level 422s
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidQuery<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for ValidQuery<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state).await.map_err(
+            |rejection| match rejection {
+                QueryRejection::FailedToDeserializeQueryString(e) => {
+                    AppError::Validation(vec![rejection_field_error(&e.body_text())])
+                }
+                other => AppError::BadRequest(other.body_text()),
+            },
+        )?;
+        validate(&value)?;
+        Ok(Self(value))
+    }
+}
+
+/// `Path` that rejects malformed parameters (UUIDs, numbers, ...) with
+/// field-level 422s naming the parameter
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidPath<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for ValidPath<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match Path::<T>::from_request_parts(parts, state).await {
+            Ok(Path(value)) => Ok(Self(value)),
+            Err(PathRejection::FailedToDeserializePathParams(e)) => {
+                let raw = RawPathParams::from_request_parts(parts, state).await.ok();
+                let key_at = |index: usize| {
+                    raw.as_ref()
+                        .and_then(|raw| raw.iter().nth(index).map(|(key, _)| key.to_string()))
+                };
+                // Errors without a key come from single-parameter paths
+                let single_key = || {
+                    raw.as_ref()
+                        .filter(|raw| raw.iter().count() == 1)
+                        .and_then(|_| key_at(0))
+                        .unwrap_or_else(|| "path".to_string())
+                };
+                let invalid = |value: &str, expected: &str| {
+                    format!("invalid value '{}': expected {}", value, expected)
+                };
+                let error = match e.kind() {
+                    ErrorKind::ParseErrorAtKey {
+                        key,
+                        value,
+                        expected_type,
+                    } => FieldError::new(key.as_str(), invalid(value, expected_type)),
+                    ErrorKind::ParseErrorAtIndex {
+                        index,
+                        value,
+                        expected_type,
+                    } => FieldError::new(
+                        key_at(*index).unwrap_or_else(|| format!("path[{}]", index)),
+                        invalid(value, expected_type),
+                    ),
+                    ErrorKind::ParseError {
+                        value,
+                        expected_type,
+                    } => FieldError::new(single_key(), invalid(value, expected_type)),
+                    ErrorKind::DeserializeError {
+                        key,
+                        value,
+                        message,
+                    } => FieldError::new(
+                        key.as_str(),
+                        format!("invalid value '{}': {}", value, message),
+                    ),
+                    ErrorKind::Message(message) => FieldError::new(single_key(), message.as_str()),
+                    _ => FieldError::new(single_key(), e.body_text()),
+                };
+                Err(AppError::Validation(vec![error]))
+            }
+            Err(other) => Err(AppError::BadRequest(other.body_text())),
+        }
+    }
+}
+
+// ============================================================================
+// Rules of the plan and note requests
+// ============================================================================
+
+impl Validate for CreatePlanRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.text("title", &self.title, MAX_TITLE_LEN);
+        v.max_len("description", &self.description, MAX_TEXT_LEN);
+        for (i, constraint) in self.constraints.iter().flatten().enumerate() {
+            v.text(
+                &format!("constraints[{}].description", i),
+                &constraint.description,
+                MAX_TEXT_LEN,
+            );
+        }
+    }
+}
+
+impl Validate for CreateTaskRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(ref title) = self.title {
+            v.max_len("title", title, MAX_TITLE_LEN);
+        }
+        v.max_len("description", &self.description, MAX_TEXT_LEN);
+        if let Some(ref tags) = self.tags {
+            v.tags("tags", tags);
+        }
+        if let Some(ref criteria) = self.acceptance_criteria {
+            v.list("acceptance_criteria", criteria, MAX_ITEM_LEN);
+        }
+        if let Some(ref files) = self.affected_files {
+            validate_file_list(v, "affected_files", files);
+        }
+        for (i, step) in self.steps.iter().flatten().enumerate() {
+            v.text(
+                &format!("steps[{}].description", i),
+                &step.description,
+                MAX_TEXT_LEN,
+            );
+        }
+        if let Some(complexity) = self.estimated_complexity {
+            v.range("estimated_complexity", complexity, 1, 10);
+        }
+    }
+}
+
+impl Validate for UpdateTaskRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(ref title) = self.title {
+            v.max_len("title", title, MAX_TITLE_LEN);
+        }
+        if let Some(ref description) = self.description {
+            v.max_len("description", description, MAX_TEXT_LEN);
+        }
+        if let Some(ref tags) = self.tags {
+            v.tags("tags", tags);
+        }
+        if let Some(ref criteria) = self.acceptance_criteria {
+            v.list("acceptance_criteria", criteria, MAX_ITEM_LEN);
+        }
+        if let Some(ref files) = self.affected_files {
+            validate_file_list(v, "affected_files", files);
+        }
+        if let Some(complexity) = self.estimated_complexity {
+            v.range("estimated_complexity", complexity, 1, 10);
+        }
+        if let Some(complexity) = self.actual_complexity {
+            v.range("actual_complexity", complexity, 1, 10);
+        }
+    }
+}
+
+impl Validate for AddStepRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.text("description", &self.description, MAX_TEXT_LEN);
+    }
+}
+
+impl Validate for UpdateStepRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(ref description) = self.description {
+            v.text("description", description, MAX_TEXT_LEN);
+        }
+    }
+}
+
+impl Validate for CreateDecisionRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.text("description", &self.description, MAX_TEXT_LEN);
+        v.max_len("rationale", &self.rationale, MAX_TEXT_LEN);
+        if let Some(ref alternatives) = self.alternatives {
+            v.list("alternatives", alternatives, MAX_TEXT_LEN);
+        }
+    }
+}
+
+impl Validate for CreateNoteRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.text("content", &self.content, MAX_TEXT_LEN);
+        if let Some(ref tags) = self.tags {
+            v.tags("tags", tags);
+        }
+        for (i, anchor) in self.anchors.iter().flatten().enumerate() {
+            let field = format!("anchors[{}].entity_id", i);
+            if anchor.entity_type == EntityType::File {
+                v.file_path(&field, &anchor.entity_id);
+            } else {
+                v.text(&field, &anchor.entity_id, MAX_PATH_LEN);
+            }
+        }
+    }
+}
+
+impl Validate for UpdateNoteRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(ref content) = self.content {
+            v.text("content", content, MAX_TEXT_LEN);
+        }
+        if let Some(ref tags) = self.tags {
+            v.tags("tags", tags);
+        }
+    }
+}
+
+fn validate_file_list(v: &mut Validator, field: &str, files: &[String]) {
+    if files.len() > MAX_LIST_LEN {
+        v.error(
+            field,
+            format!(
+                "must have at most {} items, got {}",
+                MAX_LIST_LEN,
+                files.len()
+            ),
+        );
+    }
+    for (i, file) in files.iter().enumerate() {
+        v.file_path(&format!("{}[{}]", field, i), file);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_serde_message() {
+        assert_eq!(
+            split_serde_message(
+                "title: invalid type: integer `1`, expected a string at line 1 column 10"
+            ),
+            FieldError::new("title", "invalid type: integer `1`, expected a string")
+        );
+        assert_eq!(
+            split_serde_message(
+                "steps[0].status: unknown variant `doing`, expected one of `pending`, `completed`"
+            ),
+            FieldError::new(
+                "steps[0].status",
+                "unknown variant `doing`, expected one of `pending`, `completed`"
+            )
+        );
+        assert_eq!(
+            split_serde_message("missing field `description` at line 1 column 17"),
+            FieldError::new("description", "is required")
+        );
+        assert_eq!(
+            split_serde_message("invalid type: map, expected a string"),
+            FieldError::new("body", "invalid type: map, expected a string")
+        );
+    }
+
+    #[test]
+    fn test_file_path_rules() {
+        for path in ["src/main.rs", "/repo/src/a..b.rs", "C:\\repo\\lib.rs"] {
+            assert!(validate_file_path("path", path).is_ok(), "{}", path);
+        }
+        for path in [
+            "",
+            "../etc/passwd",
+            "/repo/src/../../etc",
+            "a\\..\\b",
+            "a\0b",
+        ] {
+            assert!(validate_file_path("path", path).is_err(), "{:?}", path);
+        }
+    }
+
+    #[test]
+    fn test_validate_collects_every_field() {
+        let req: CreateTaskRequest = serde_json::from_value(serde_json::json!({
+            "title": "x".repeat(MAX_TITLE_LEN + 1),
+            "description": "Add retries",
+            "tags": ["backend", " "],
+            "affected_files": ["src/lib.rs", "../secrets"],
+            "steps": [{"description": ""}],
+            "estimated_complexity": 11,
+        }))
+        .unwrap();
+        let Err(AppError::Validation(errors)) = validate(&req) else {
+            panic!("expected validation errors");
+        };
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "title",
+                "tags[1]",
+                "affected_files[1]",
+                "steps[0].description",
+                "estimated_complexity"
+            ]
+        );
+    }
+}
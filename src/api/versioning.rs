@@ -0,0 +1,292 @@
+//! Optimistic concurrency control
+//!
+//! Plans, tasks and notes carry a `version` incremented by every update of
+//! the store (`update_plan`, `update_task_status`, `update_note`…), whoever
+//! makes it: PATCHes, runners, undo. A client that sends back the version it
+//! read in `If-Match` only updates an entity nobody changed in the meantime;
+//! otherwise it gets a 409 `version_conflict` with the current state, so two
+//! agents editing the same task cannot silently overwrite each other. Without
+//! `If-Match` the update is applied unconditionally, as before.
+//!
+//! With `If-Match`, the version is claimed (checked and incremented in one
+//! query) before the update runs, which may take several queries, so a
+//! concurrent update with the same `If-Match` gets the 409. If the update
+//! fails before writing anything, the claim is given back; once a write went
+//! through it has moved the version on and the claim is kept. GETs and
+//! successful PATCHes return the version as `ETag: "<version>"`.
+
+use super::error::AppError;
+use crate::neo4j::models::{VersionBump, VersionedNode};
+use crate::neo4j::GraphStore;
+use axum::http::{header, HeaderMap, HeaderValue};
+use uuid::Uuid;
+
+/// Version named by the `If-Match` header: `3`, `"3"` or `W/"3"`
+pub fn if_match_version(headers: &HeaderMap) -> Result<Option<i64>, AppError> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let value = value.to_str().unwrap_or_default().trim();
+    let tag = value.strip_prefix("W/").unwrap_or(value).trim_matches('"');
+    tag.parse::<i64>()
+        .ok()
+        .filter(|v| *v >= 0)
+        .map(Some)
+        .ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "If-Match must be the version of the entity, got '{}'",
+                value
+            ))
+        })
+}
+
+/// `ETag` header naming `version`, none without one
+pub fn etag(version: Option<i64>) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Some(version) = version {
+        let value = HeaderValue::from_str(&format!("\"{}\"", version))
+            .expect("a quoted number is a valid header value");
+        headers.insert(header::ETAG, value);
+    }
+    headers
+}
+
+/// Version claimed by [`claim_version`] for an update
+#[derive(Debug, Clone, Copy)]
+pub struct VersionClaim {
+    node: VersionedNode,
+    id: Uuid,
+    /// None without `If-Match`, or when the node does not exist
+    version: Option<i64>,
+}
+
+impl VersionClaim {
+    /// The claimed version
+    pub fn version(&self) -> Option<i64> {
+        self.version
+    }
+
+    /// Pass the outcome of the update through, giving the version back if
+    /// the update failed. Only a version nothing moved on since is given
+    /// back, so a failure after some of the writes keeps it.
+    pub async fn settle<T>(
+        self,
+        store: &dyn GraphStore,
+        result: Result<T, AppError>,
+    ) -> Result<T, AppError> {
+        let Some(version) = self.version.filter(|_| result.is_err()) else {
+            return result;
+        };
+        if let Err(e) = store.restore_version(self.node, self.id, version).await {
+            tracing::warn!(
+                node = self.node.label(),
+                id = %self.id,
+                "Failed to give back version {} after a failed update: {}",
+                version,
+                e
+            );
+        }
+        result
+    }
+}
+
+/// Claim the next version of a node before updating it, if `If-Match` is
+/// set.
+///
+/// Fails with `version_conflict` when `If-Match` names another version than
+/// the current one. A missing node is left to the update itself to report.
+pub async fn claim_version(
+    store: &dyn GraphStore,
+    headers: &HeaderMap,
+    node: VersionedNode,
+    id: Uuid,
+) -> Result<VersionClaim, AppError> {
+    let Some(expected) = if_match_version(headers)? else {
+        return Ok(VersionClaim {
+            node,
+            id,
+            version: None,
+        });
+    };
+    let current_version = match store.bump_version(node, id, Some(expected)).await? {
+        VersionBump::Bumped(version) => {
+            return Ok(VersionClaim {
+                node,
+                id,
+                version: Some(version),
+            })
+        }
+        VersionBump::NotFound => {
+            return Ok(VersionClaim {
+                node,
+                id,
+                version: None,
+            })
+        }
+        VersionBump::Conflict(current_version) => current_version,
+    };
+    let current = match node {
+        VersionedNode::Plan => serde_json::to_value(store.get_plan(id).await?),
+        VersionedNode::Task => serde_json::to_value(store.get_task(id).await?),
+        VersionedNode::Note => serde_json::to_value(store.get_note(id).await?),
+    }
+    .map_err(anyhow::Error::from)?;
+    Err(AppError::VersionConflict {
+        expected,
+        current_version,
+        current,
+    })
+}
+
+/// Current version of a node, for the `ETag` of an update
+pub async fn current_version(
+    store: &dyn GraphStore,
+    node: VersionedNode,
+    id: Uuid,
+) -> Result<Option<i64>, AppError> {
+    Ok(match node {
+        VersionedNode::Plan => store.get_plan(id).await?.map(|p| p.version),
+        VersionedNode::Task => store.get_task(id).await?.map(|t| t.version),
+        VersionedNode::Note => store.get_note(id).await?.map(|n| n.version),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::{PlanStatus, TaskStatus};
+    use crate::test_helpers::{test_plan, test_task};
+
+    fn if_match(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_if_match_version() {
+        assert_eq!(if_match_version(&HeaderMap::new()).unwrap(), None);
+        assert_eq!(if_match_version(&if_match("3")).unwrap(), Some(3));
+        assert_eq!(if_match_version(&if_match("\"3\"")).unwrap(), Some(3));
+        assert_eq!(if_match_version(&if_match("W/\"3\"")).unwrap(), Some(3));
+        assert!(if_match_version(&if_match("*")).is_err());
+        assert!(if_match_version(&if_match("-1")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_claim_version() {
+        let store = MockGraphStore::new();
+        let plan = test_plan();
+        store.create_plan(&plan).await.unwrap();
+
+        // Without If-Match nothing is claimed
+        let claim = claim_version(&store, &HeaderMap::new(), VersionedNode::Plan, plan.id)
+            .await
+            .unwrap();
+        assert_eq!(claim.version(), None);
+        assert_eq!(store.get_plan(plan.id).await.unwrap().unwrap().version, 0);
+
+        let claim = claim_version(&store, &if_match("0"), VersionedNode::Plan, plan.id)
+            .await
+            .unwrap();
+        assert_eq!(claim.version(), Some(1));
+
+        // A concurrent update with the same If-Match loses
+        let err = claim_version(&store, &if_match("0"), VersionedNode::Plan, plan.id)
+            .await
+            .unwrap_err();
+        match err {
+            AppError::VersionConflict {
+                expected,
+                current_version,
+                current,
+            } => {
+                assert_eq!((expected, current_version), (0, 1));
+                assert_eq!(current["id"], plan.id.to_string());
+            }
+            other => panic!("expected a version conflict, got {:?}", other),
+        }
+        assert_eq!(store.get_plan(plan.id).await.unwrap().unwrap().version, 1);
+
+        // Unknown nodes are left to the update
+        let claim = claim_version(&store, &if_match("0"), VersionedNode::Task, Uuid::new_v4())
+            .await
+            .unwrap();
+        assert_eq!(claim.version(), None);
+    }
+
+    #[tokio::test]
+    async fn test_store_updates_bump_version() {
+        let store = MockGraphStore::new();
+        let plan = test_plan();
+        store.create_plan(&plan).await.unwrap();
+        let task = test_task();
+        store.create_task(plan.id, &task).await.unwrap();
+
+        // A write made outside any PATCH, e.g. by a runner
+        store
+            .update_task_status(task.id, TaskStatus::InProgress)
+            .await
+            .unwrap();
+        store
+            .update_plan_status(plan.id, PlanStatus::InProgress)
+            .await
+            .unwrap();
+        assert_eq!(
+            current_version(&store, VersionedNode::Task, task.id)
+                .await
+                .unwrap(),
+            Some(1)
+        );
+        assert_eq!(
+            current_version(&store, VersionedNode::Plan, plan.id)
+                .await
+                .unwrap(),
+            Some(1)
+        );
+
+        // so a client that read the task before it cannot overwrite it
+        let err = claim_version(&store, &if_match("0"), VersionedNode::Task, task.id)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::VersionConflict { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_failed_update_gives_version_back() {
+        let store = MockGraphStore::new();
+        let plan = test_plan();
+        store.create_plan(&plan).await.unwrap();
+
+        // Nothing written: the version is given back
+        let claim = claim_version(&store, &if_match("0"), VersionedNode::Plan, plan.id)
+            .await
+            .unwrap();
+        let result: Result<(), AppError> = claim
+            .settle(&store, Err(AppError::BadRequest("rejected".into())))
+            .await;
+        assert!(result.is_err());
+        assert_eq!(store.get_plan(plan.id).await.unwrap().unwrap().version, 0);
+
+        // A first write went through before the failure: the version is kept
+        let claim = claim_version(&store, &if_match("0"), VersionedNode::Plan, plan.id)
+            .await
+            .unwrap();
+        store
+            .update_plan_status(plan.id, PlanStatus::InProgress)
+            .await
+            .unwrap();
+        let result: Result<(), AppError> = claim
+            .settle(&store, Err(AppError::BadRequest("rejected".into())))
+            .await;
+        assert!(result.is_err());
+        assert_eq!(store.get_plan(plan.id).await.unwrap().unwrap().version, 2);
+    }
+
+    #[test]
+    fn test_etag() {
+        assert_eq!(etag(Some(3))[header::ETAG], "\"3\"");
+        assert!(etag(None).is_empty());
+    }
+}
@@ -0,0 +1,663 @@
+//! Saved view API handlers
+//!
+//! A saved view is a named set of filters over tasks, notes or the functions
+//! of a project, stored in the graph for the user who created it. Dashboards
+//! define a view once ("stale critical notes", "complex untested public
+//! functions", ...) and fetch its current matches from
+//! `GET /api/views/{id}/results`.
+
+use super::handlers::{AppError, OrchestratorState};
+use super::validation::ValidPath;
+use super::PaginatedResponse;
+use crate::auth::jwt::Claims;
+use crate::neo4j::models::{
+    SavedViewFilters, SavedViewNode, SavedViewTarget, TaskStatus, ViewFunctionRow,
+};
+use crate::neo4j::traits::GraphStore;
+use crate::notes::{NoteFilters, NoteStatus};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Page size of the results when neither the request nor the view sets one
+const DEFAULT_RESULTS_LIMIT: usize = 50;
+/// Largest page of results
+const MAX_RESULTS_LIMIT: usize = 100;
+
+const VISIBILITIES: &[&str] = &["public", "private", "crate", "super"];
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSavedViewRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub target: SavedViewTarget,
+    #[serde(default)]
+    pub filters: SavedViewFilters,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateSavedViewRequest {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub target: Option<SavedViewTarget>,
+    /// Replaces all the filters of the view
+    #[serde(default)]
+    pub filters: Option<SavedViewFilters>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ViewResultsQuery {
+    /// Overrides the `limit` of the view
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub offset: usize,
+}
+
+/// A page of the current matches of a view. `items` are tasks (with their
+/// plan), notes or functions depending on `target`.
+#[derive(Debug, Serialize)]
+pub struct ViewResultsResponse {
+    pub view_id: Uuid,
+    pub target: SavedViewTarget,
+    #[serde(flatten)]
+    pub results: PaginatedResponse<serde_json::Value>,
+}
+
+fn owner_id(claims: Option<Extension<Claims>>) -> String {
+    claims
+        .map(|Extension(c)| c)
+        .unwrap_or_else(Claims::anonymous)
+        .sub
+}
+
+/// Load a view of `owner`; views of other users are reported as missing
+async fn load_view(
+    neo4j: &dyn GraphStore,
+    id: Uuid,
+    owner: &str,
+) -> Result<SavedViewNode, AppError> {
+    neo4j
+        .get_saved_view(id)
+        .await?
+        .filter(|v| v.owner_id == owner)
+        .ok_or_else(|| AppError::NotFound(format!("Saved view {} not found", id)))
+}
+
+/// Reject an empty name or one used by another view of the same owner
+async fn check_name(
+    neo4j: &dyn GraphStore,
+    owner: &str,
+    name: &str,
+    id: Option<Uuid>,
+) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::BadRequest("View name is required".to_string()));
+    }
+    let taken = neo4j
+        .list_saved_views(owner)
+        .await?
+        .iter()
+        .any(|v| v.name == name && Some(v.id) != id);
+    if taken {
+        return Err(AppError::Conflict(format!(
+            "A saved view named '{}' already exists",
+            name
+        )));
+    }
+    Ok(name.to_string())
+}
+
+fn note_statuses(filters: &SavedViewFilters) -> Result<Option<Vec<NoteStatus>>, AppError> {
+    filters
+        .status
+        .as_ref()
+        .map(|statuses| {
+            statuses
+                .iter()
+                .map(|s| NoteStatus::from_str(s).map_err(AppError::BadRequest))
+                .collect()
+        })
+        .transpose()
+}
+
+/// Check that `filters` can be run for `target`
+fn validate_filters(target: SavedViewTarget, filters: &SavedViewFilters) -> Result<(), AppError> {
+    if let Some(order) = filters.sort_order.as_deref() {
+        if !["asc", "desc"].contains(&order) {
+            return Err(AppError::BadRequest(
+                "sort_order must be 'asc' or 'desc'".to_string(),
+            ));
+        }
+    }
+    if filters
+        .limit
+        .is_some_and(|l| l == 0 || l > MAX_RESULTS_LIMIT)
+    {
+        return Err(AppError::BadRequest(format!(
+            "limit must be between 1 and {}",
+            MAX_RESULTS_LIMIT
+        )));
+    }
+    match target {
+        SavedViewTarget::Tasks => {
+            for status in filters.status.iter().flatten() {
+                serde_json::from_value::<TaskStatus>(serde_json::json!(status)).map_err(|_| {
+                    AppError::BadRequest(format!("Invalid task status: {}", status))
+                })?;
+            }
+        }
+        SavedViewTarget::Notes => {
+            note_statuses(filters)?;
+        }
+        SavedViewTarget::Symbols => {
+            if filters.project_id.is_none() {
+                return Err(AppError::BadRequest(
+                    "A symbols view requires a project_id".to_string(),
+                ));
+            }
+            if let Some(ref visibility) = filters.visibility {
+                if !VISIBILITIES.contains(&visibility.to_lowercase().as_str()) {
+                    return Err(AppError::BadRequest(format!(
+                        "visibility must be one of: {}",
+                        VISIBILITIES.join(", ")
+                    )));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Order functions of a symbols view: by complexity (default, most complex
+/// first), name or file path.
+fn sort_functions(rows: &mut [ViewFunctionRow], filters: &SavedViewFilters) {
+    let default_desc = !matches!(filters.sort_by.as_deref(), Some("name" | "file_path"));
+    let desc = filters
+        .sort_order
+        .as_deref()
+        .map_or(default_desc, |o| o == "desc");
+    rows.sort_by(|a, b| {
+        let ordering = match filters.sort_by.as_deref() {
+            Some("name") => a.name.cmp(&b.name),
+            Some("file_path") => a
+                .file_path
+                .cmp(&b.file_path)
+                .then(a.line_start.cmp(&b.line_start)),
+            _ => a.complexity.cmp(&b.complexity),
+        };
+        if desc {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+}
+
+fn to_values<T: Serialize>(items: Vec<T>) -> Result<Vec<serde_json::Value>, AppError> {
+    items
+        .into_iter()
+        .map(|item| serde_json::to_value(item).map_err(|e| AppError::Internal(e.into())))
+        .collect()
+}
+
+/// Run a view and return one page of its matches
+async fn run_view(
+    state: &OrchestratorState,
+    view: &SavedViewNode,
+    limit: usize,
+    offset: usize,
+) -> Result<PaginatedResponse<serde_json::Value>, AppError> {
+    let filters = &view.filters;
+    let (items, total) = match view.target {
+        SavedViewTarget::Tasks => {
+            let (tasks, total) = state
+                .orchestrator
+                .neo4j()
+                .list_all_tasks_filtered(
+                    filters.plan_id,
+                    filters.project_id,
+                    filters.workspace_slug.as_deref(),
+                    filters.status.clone(),
+                    filters.priority_min,
+                    filters.priority_max,
+                    filters.tags.clone(),
+                    filters.assigned_to.as_deref(),
+                    limit,
+                    offset,
+                    filters.sort_by.as_deref(),
+                    filters.sort_order.as_deref().unwrap_or("desc"),
+                )
+                .await?;
+            (to_values(tasks)?, total)
+        }
+        SavedViewTarget::Notes => {
+            let note_filters = NoteFilters {
+                status: note_statuses(filters)?,
+                note_type: filters.note_type.clone(),
+                importance: filters.importance.clone(),
+                tags: filters.tags.clone(),
+                min_staleness: filters.min_staleness,
+                limit: Some(limit as i64),
+                offset: Some(offset as i64),
+                sort_by: filters.sort_by.clone(),
+                sort_order: filters.sort_order.clone(),
+                ..Default::default()
+            };
+            let (notes, total) = state
+                .orchestrator
+                .note_manager()
+                .list_notes(
+                    filters.project_id,
+                    filters.workspace_slug.as_deref(),
+                    &note_filters,
+                )
+                .await?;
+            (to_values(notes)?, total)
+        }
+        SavedViewTarget::Symbols => {
+            let project_id = filters.project_id.ok_or_else(|| {
+                AppError::BadRequest("A symbols view requires a project_id".to_string())
+            })?;
+            let mut rows = state
+                .orchestrator
+                .neo4j()
+                .list_view_functions(project_id, filters)
+                .await?;
+            sort_functions(&mut rows, filters);
+            let total = rows.len();
+            let page = rows.into_iter().skip(offset).take(limit).collect();
+            (to_values(page)?, total)
+        }
+    };
+    Ok(PaginatedResponse::new(items, total, limit, offset))
+}
+
+/// GET /api/views — List the saved views of the current user
+pub async fn list_saved_views(
+    State(state): State<OrchestratorState>,
+    claims: Option<Extension<Claims>>,
+) -> Result<Json<Vec<SavedViewNode>>, AppError> {
+    let owner = owner_id(claims);
+    Ok(Json(
+        state.orchestrator.neo4j().list_saved_views(&owner).await?,
+    ))
+}
+
+/// POST /api/views — Create a saved view for the current user
+pub async fn create_saved_view(
+    State(state): State<OrchestratorState>,
+    claims: Option<Extension<Claims>>,
+    Json(body): Json<CreateSavedViewRequest>,
+) -> Result<(StatusCode, Json<SavedViewNode>), AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let owner = owner_id(claims);
+    let name = check_name(neo4j, &owner, &body.name, None).await?;
+    validate_filters(body.target, &body.filters)?;
+    let now = chrono::Utc::now();
+    let view = SavedViewNode {
+        id: Uuid::new_v4(),
+        owner_id: owner,
+        name,
+        description: body.description.filter(|d| !d.trim().is_empty()),
+        target: body.target,
+        filters: body.filters,
+        created_at: now,
+        updated_at: now,
+    };
+    neo4j.create_saved_view(&view).await?;
+    Ok((StatusCode::CREATED, Json(view)))
+}
+
+/// GET /api/views/{id} — Get a saved view
+pub async fn get_saved_view(
+    State(state): State<OrchestratorState>,
+    claims: Option<Extension<Claims>>,
+    ValidPath(id): ValidPath<Uuid>,
+) -> Result<Json<SavedViewNode>, AppError> {
+    let owner = owner_id(claims);
+    Ok(Json(
+        load_view(state.orchestrator.neo4j(), id, &owner).await?,
+    ))
+}
+
+/// PATCH /api/views/{id} — Update a saved view
+pub async fn update_saved_view(
+    State(state): State<OrchestratorState>,
+    claims: Option<Extension<Claims>>,
+    ValidPath(id): ValidPath<Uuid>,
+    Json(body): Json<UpdateSavedViewRequest>,
+) -> Result<Json<SavedViewNode>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let owner = owner_id(claims);
+    let mut view = load_view(neo4j, id, &owner).await?;
+    if let Some(ref name) = body.name {
+        view.name = check_name(neo4j, &owner, name, Some(id)).await?;
+    }
+    if let Some(description) = body.description {
+        view.description = Some(description).filter(|d| !d.trim().is_empty());
+    }
+    if let Some(target) = body.target {
+        view.target = target;
+    }
+    if let Some(filters) = body.filters {
+        view.filters = filters;
+    }
+    validate_filters(view.target, &view.filters)?;
+    view.updated_at = chrono::Utc::now();
+    neo4j.update_saved_view(&view).await?;
+    Ok(Json(view))
+}
+
+/// DELETE /api/views/{id} — Delete a saved view
+pub async fn delete_saved_view(
+    State(state): State<OrchestratorState>,
+    claims: Option<Extension<Claims>>,
+    ValidPath(id): ValidPath<Uuid>,
+) -> Result<StatusCode, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let owner = owner_id(claims);
+    load_view(neo4j, id, &owner).await?;
+    neo4j.delete_saved_view(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /api/views/{id}/results — Current matches of a saved view
+pub async fn get_saved_view_results(
+    State(state): State<OrchestratorState>,
+    claims: Option<Extension<Claims>>,
+    ValidPath(id): ValidPath<Uuid>,
+    Query(query): Query<ViewResultsQuery>,
+) -> Result<Json<ViewResultsResponse>, AppError> {
+    let owner = owner_id(claims);
+    let view = load_view(state.orchestrator.neo4j(), id, &owner).await?;
+    let limit = query
+        .limit
+        .or(view.filters.limit)
+        .unwrap_or(DEFAULT_RESULTS_LIMIT)
+        .min(MAX_RESULTS_LIMIT);
+    let results = run_view(&state, &view, limit, query.offset).await?;
+    Ok(Json(ViewResultsResponse {
+        view_id: view.id,
+        target: view.target,
+        results,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::handlers::ServerState;
+    use crate::api::routes::create_router;
+    use crate::neo4j::models::{FileNode, FunctionNode, Visibility};
+    use crate::orchestrator::watcher::FileWatcher;
+    use crate::orchestrator::Orchestrator;
+    use crate::test_helpers::{
+        mock_app_state, test_auth_config, test_bearer_token, test_project_named,
+    };
+    use axum::{body::Body, http::Request};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    async fn mock_server_state() -> OrchestratorState {
+        let app_state = mock_app_state();
+        let orchestrator = Arc::new(Orchestrator::new(app_state).await.unwrap());
+        let watcher = Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
+            orchestrator.clone(),
+        )));
+        Arc::new(ServerState {
+            orchestrator,
+            watcher,
+            chat_manager: None,
+            event_bus: Arc::new(crate::events::HybridEmitter::new(Arc::new(
+                crate::events::EventBus::default(),
+            ))),
+            nats_emitter: None,
+            auth_config: Some(test_auth_config()),
+            serve_frontend: false,
+            frontend_path: "./dist".to_string(),
+            setup_completed: true,
+            server_port: 6600,
+            public_url: None,
+            read_only: false,
+            ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
+            registry_remote_url: None,
+            oidc_client: None,
+            neural_router: crate::test_helpers::mock_neural_router(),
+            trajectory_collector: std::sync::RwLock::new(None),
+            trajectory_store_neo4j: None,
+            trajectory_store: None,
+            identity: None,
+            reactor_counters: std::sync::OnceLock::new(),
+            confidence_tracker: Arc::new(crate::graph::confidence::ConfidenceTracker::default()),
+            mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
+            model_catalog: crate::chat::model_catalog::ModelCatalogCache::new(None),
+        })
+    }
+
+    fn request(method: &str, uri: &str, body: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("authorization", test_bearer_token())
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    async fn json_body(resp: axum::response::Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    fn function(
+        name: &str,
+        file_path: &str,
+        visibility: Visibility,
+        complexity: u32,
+    ) -> FunctionNode {
+        FunctionNode {
+            name: name.to_string(),
+            visibility,
+            params: vec![],
+            return_type: None,
+            generics: vec![],
+            is_async: false,
+            is_unsafe: false,
+            complexity,
+            file_path: file_path.to_string(),
+            line_start: 1,
+            line_end: 10,
+            docstring: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_filters() {
+        let filters = SavedViewFilters {
+            status: Some(vec!["in_progress".to_string()]),
+            ..Default::default()
+        };
+        assert!(validate_filters(SavedViewTarget::Tasks, &filters).is_ok());
+        // "in_progress" is not a note status
+        assert!(validate_filters(SavedViewTarget::Notes, &filters).is_err());
+        // Symbols need a project
+        assert!(validate_filters(SavedViewTarget::Symbols, &filters).is_err());
+        let filters = SavedViewFilters {
+            project_id: Some(Uuid::new_v4()),
+            visibility: Some("exported".to_string()),
+            ..Default::default()
+        };
+        assert!(validate_filters(SavedViewTarget::Symbols, &filters).is_err());
+        let filters = SavedViewFilters {
+            limit: Some(500),
+            ..Default::default()
+        };
+        assert!(validate_filters(SavedViewTarget::Tasks, &filters).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_saved_view_results() {
+        let state = mock_server_state().await;
+        let neo4j = state.orchestrator.neo4j();
+        let project = test_project_named("views");
+        neo4j.create_project(&project).await.unwrap();
+        for path in ["/repo/src/lib.rs", "/repo/tests/lib_test.rs"] {
+            neo4j
+                .upsert_file(&FileNode {
+                    path: path.to_string(),
+                    language: "rust".to_string(),
+                    hash: "h".to_string(),
+                    line_count: 0,
+                    last_parsed: chrono::Utc::now(),
+                    project_id: Some(project.id),
+                    encoding: None,
+                })
+                .await
+                .unwrap();
+            neo4j.link_file_to_project(path, project.id).await.unwrap();
+        }
+        for f in [
+            function("parse", "/repo/src/lib.rs", Visibility::Public, 18),
+            function("render", "/repo/src/lib.rs", Visibility::Public, 12),
+            function("helper", "/repo/src/lib.rs", Visibility::Private, 20),
+            function("simple", "/repo/src/lib.rs", Visibility::Public, 2),
+            function(
+                "test_parse",
+                "/repo/tests/lib_test.rs",
+                Visibility::Private,
+                1,
+            ),
+        ] {
+            neo4j.upsert_function(&f).await.unwrap();
+        }
+        neo4j
+            .create_call_relationship(
+                "/repo/tests/lib_test.rs:test_parse:1",
+                "parse",
+                None,
+                1.0,
+                "",
+            )
+            .await
+            .unwrap();
+
+        let app = create_router(state.clone());
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/api/views",
+                serde_json::json!({"name": "untested", "target": "symbols"}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/api/views",
+                serde_json::json!({
+                    "name": "untested",
+                    "target": "symbols",
+                    "filters": {
+                        "project_id": project.id,
+                        "visibility": "public",
+                        "min_complexity": 10,
+                        "untested": true
+                    }
+                }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let id = json_body(resp).await["id"].as_str().unwrap().to_string();
+
+        let resp = app
+            .clone()
+            .oneshot(request("GET", "/api/views", serde_json::json!({})))
+            .await
+            .unwrap();
+        assert_eq!(json_body(resp).await.as_array().unwrap().len(), 1);
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "GET",
+                &format!("/api/views/{}/results", id),
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        let results = json_body(resp).await;
+        assert_eq!(results["target"], "symbols");
+        assert_eq!(results["total"], 1);
+        assert_eq!(results["items"][0]["name"], "render");
+        assert_eq!(results["items"][0]["visibility"], "public");
+
+        // Dropping the "untested" filter brings back the tested function
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "PATCH",
+                &format!("/api/views/{}", id),
+                serde_json::json!({"filters": {
+                    "project_id": project.id,
+                    "visibility": "public",
+                    "min_complexity": 10
+                }}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "GET",
+                &format!("/api/views/{}/results?limit=1", id),
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        let results = json_body(resp).await;
+        assert_eq!(results["total"], 2);
+        assert_eq!(results["items"][0]["name"], "parse");
+        assert_eq!(results["has_more"], true);
+
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "DELETE",
+                &format!("/api/views/{}", id),
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        let resp = app
+            .clone()
+            .oneshot(request(
+                "GET",
+                &format!("/api/views/{}/results", id),
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -1,7 +1,11 @@
 //! Workspace API handlers
 
 use crate::api::{PaginatedResponse, PaginationParams, StatusFilter};
+use crate::audit::license::{LicensePolicy, LicenseReport};
 use crate::neo4j::models::*;
+use crate::orchestrator::runner::ResourceParseResult;
+use crate::orchestrator::topology_drift::TopologyDriftReport;
+use crate::parser::contracts::{self, ContractFormat};
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
@@ -215,6 +219,63 @@ pub struct MilestoneStepSummary {
     pub verification: Option<String>,
 }
 
+/// Milestone rollup: progress per plan, velocity and forecast
+#[derive(Serialize)]
+pub struct MilestoneRollupResponse {
+    #[serde(flatten)]
+    pub milestone: WorkspaceMilestoneResponse,
+    pub progress: MilestoneProgressResponse,
+    pub plans: Vec<MilestonePlanRollup>,
+    pub velocity: MilestoneVelocity,
+    pub forecast: MilestoneForecast,
+}
+
+/// Task counts of one plan within a milestone rollup
+#[derive(Serialize)]
+pub struct MilestonePlanRollup {
+    pub id: String,
+    pub title: String,
+    pub status: Option<String>,
+    pub total: u32,
+    pub completed: u32,
+    pub in_progress: u32,
+    pub pending: u32,
+    pub blocked: u32,
+    pub failed: u32,
+    pub percentage: f64,
+}
+
+/// Task completions of a milestone over the last weeks
+#[derive(Serialize)]
+pub struct MilestoneVelocity {
+    pub window_days: u32,
+    pub completed_in_window: u32,
+    pub tasks_per_week: f64,
+    /// Completions per week, oldest first; the last week ends now
+    pub weekly: Vec<WeeklyCompletions>,
+}
+
+/// Tasks completed in the week starting at `week_start`
+#[derive(Serialize)]
+pub struct WeeklyCompletions {
+    pub week_start: String,
+    pub completed: u32,
+}
+
+/// Forecast completion of a milestone at its current velocity
+#[derive(Serialize)]
+pub struct MilestoneForecast {
+    /// Tasks not completed yet
+    pub remaining: u32,
+    pub estimated_completion: Option<String>,
+    pub target_date: Option<String>,
+    /// Whether the estimate meets the target date (None without either)
+    pub on_track: Option<bool>,
+    /// How the estimate was made: `velocity`, `completed` (every task is
+    /// done), `no_history` (nothing completed in the window) or `empty`
+    pub basis: String,
+}
+
 // ============================================================================
 // Request/Response types - Resource
 // ============================================================================
@@ -579,6 +640,20 @@ pub async fn get_workspace_overview(
     }))
 }
 
+/// GET /api/workspaces/{slug}/stats — Knowledge graph statistics aggregated
+/// across the workspace projects (dashboard)
+pub async fn get_workspace_stats(
+    State(state): State<OrchestratorState>,
+    Path(slug): Path<String>,
+) -> Result<Json<WorkspaceStats>, AppError> {
+    let neo4j = state.orchestrator.neo4j();
+    let workspace = neo4j
+        .get_workspace_by_slug(&slug)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Workspace '{}' not found", slug)))?;
+    Ok(Json(neo4j.get_workspace_stats(workspace.id).await?))
+}
+
 // ============================================================================
 // Workspace Project Handlers
 // ============================================================================
@@ -627,6 +702,15 @@ pub async fn add_project_to_workspace(
         .parse()
         .map_err(|_| AppError::BadRequest("Invalid project ID".to_string()))?;
 
+    let current = state
+        .orchestrator
+        .neo4j()
+        .get_project_workspace(project_id)
+        .await?;
+    if current.is_none_or(|w| w.id != workspace.id) {
+        super::quota_handlers::check_project_quota(&state, Some(&workspace)).await?;
+    }
+
     state
         .orchestrator
         .add_project_to_workspace(workspace.id, project_id)
@@ -1046,6 +1130,171 @@ pub async fn get_workspace_milestone_progress(
     }))
 }
 
+/// Query params for the milestone rollup
+#[derive(Debug, Deserialize, Default)]
+pub struct MilestoneRollupQuery {
+    /// Days of completion history the velocity is computed over (default 28,
+    /// rounded up to whole weeks, at most 364)
+    pub window_days: Option<u32>,
+}
+
+/// Default velocity window of the milestone rollup, in days
+const ROLLUP_WINDOW_DAYS: u32 = 28;
+
+fn percentage(completed: u32, total: u32) -> f64 {
+    if total > 0 {
+        (completed as f64 / total as f64) * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// Build the rollup of a milestone from its tasks, as of `now`
+fn milestone_rollup(
+    milestone: WorkspaceMilestoneNode,
+    tasks: &[TaskWithPlan],
+    window_days: u32,
+    now: chrono::DateTime<chrono::Utc>,
+) -> MilestoneRollupResponse {
+    let count = |tasks: &[&TaskWithPlan], status: TaskStatus| {
+        tasks.iter().filter(|t| t.task.status == status).count() as u32
+    };
+
+    // Per-plan breakdown, in order of first task
+    let mut plan_order: Vec<Uuid> = Vec::new();
+    let mut by_plan: std::collections::HashMap<Uuid, Vec<&TaskWithPlan>> =
+        std::collections::HashMap::new();
+    for twp in tasks {
+        by_plan
+            .entry(twp.plan_id)
+            .or_insert_with(|| {
+                plan_order.push(twp.plan_id);
+                Vec::new()
+            })
+            .push(twp);
+    }
+    let plans = plan_order
+        .iter()
+        .map(|pid| {
+            let plan_tasks = &by_plan[pid];
+            let total = plan_tasks.len() as u32;
+            let completed = count(plan_tasks, TaskStatus::Completed);
+            MilestonePlanRollup {
+                id: pid.to_string(),
+                title: plan_tasks[0].plan_title.clone(),
+                status: plan_tasks[0].plan_status.clone(),
+                total,
+                completed,
+                in_progress: count(plan_tasks, TaskStatus::InProgress),
+                pending: count(plan_tasks, TaskStatus::Pending),
+                blocked: count(plan_tasks, TaskStatus::Blocked),
+                failed: count(plan_tasks, TaskStatus::Failed),
+                percentage: percentage(completed, total),
+            }
+        })
+        .collect();
+
+    let all: Vec<&TaskWithPlan> = tasks.iter().collect();
+    let total = all.len() as u32;
+    let completed = count(&all, TaskStatus::Completed);
+    let progress = MilestoneProgressResponse {
+        total,
+        completed,
+        in_progress: count(&all, TaskStatus::InProgress),
+        pending: count(&all, TaskStatus::Pending),
+        percentage: percentage(completed, total),
+    };
+
+    // Velocity over whole weeks ending now
+    let weeks = window_days.div_ceil(7).clamp(1, 52);
+    let completions: Vec<chrono::DateTime<chrono::Utc>> = tasks
+        .iter()
+        .filter(|t| t.task.status == TaskStatus::Completed)
+        .filter_map(|t| t.task.completed_at)
+        .collect();
+    let weekly: Vec<WeeklyCompletions> = (0..weeks)
+        .map(|i| {
+            let start = now - chrono::Duration::weeks((weeks - i) as i64);
+            let end = start + chrono::Duration::weeks(1);
+            WeeklyCompletions {
+                week_start: start.to_rfc3339(),
+                completed: completions
+                    .iter()
+                    .filter(|at| **at >= start && **at < end)
+                    .count() as u32,
+            }
+        })
+        .collect();
+    let completed_in_window: u32 = weekly.iter().map(|w| w.completed).sum();
+    let tasks_per_week = completed_in_window as f64 / weeks as f64;
+
+    // Forecast at that velocity
+    let remaining = total - completed;
+    let (estimated_completion, basis) = if total == 0 {
+        (None, "empty")
+    } else if remaining == 0 {
+        (completions.iter().max().copied(), "completed")
+    } else if tasks_per_week > 0.0 {
+        let days = remaining as f64 / tasks_per_week * 7.0;
+        let estimate = now + chrono::Duration::seconds((days * 86_400.0).round() as i64);
+        (Some(estimate), "velocity")
+    } else {
+        (None, "no_history")
+    };
+    let on_track = match (estimated_completion, milestone.target_date) {
+        (Some(estimate), Some(target)) => Some(estimate <= target),
+        // Work left, no velocity and the target already passed
+        (None, Some(target)) if remaining > 0 && target < now => Some(false),
+        _ => None,
+    };
+    let forecast = MilestoneForecast {
+        remaining,
+        estimated_completion: estimated_completion.map(|dt| dt.to_rfc3339()),
+        target_date: milestone.target_date.map(|dt| dt.to_rfc3339()),
+        on_track,
+        basis: basis.to_string(),
+    };
+
+    MilestoneRollupResponse {
+        milestone: WorkspaceMilestoneResponse::from(milestone),
+        progress,
+        plans,
+        velocity: MilestoneVelocity {
+            window_days: weeks * 7,
+            completed_in_window,
+            tasks_per_week,
+            weekly,
+        },
+        forecast,
+    }
+}
+
+/// Get the rollup of a workspace milestone: progress per plan, velocity from
+/// task completion history and a forecast completion date
+pub async fn get_workspace_milestone_rollup(
+    State(state): State<OrchestratorState>,
+    Path(id): Path<String>,
+    Query(query): Query<MilestoneRollupQuery>,
+) -> Result<Json<MilestoneRollupResponse>, AppError> {
+    let id: Uuid = id
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid milestone ID".to_string()))?;
+
+    let neo4j = state.orchestrator.neo4j();
+    let milestone = neo4j
+        .get_workspace_milestone(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Milestone not found".to_string()))?;
+    let tasks = neo4j.get_workspace_milestone_tasks(id).await?;
+
+    Ok(Json(milestone_rollup(
+        milestone,
+        &tasks,
+        query.window_days.unwrap_or(ROLLUP_WINDOW_DAYS),
+        chrono::Utc::now(),
+    )))
+}
+
 // ============================================================================
 // Global Workspace Milestones
 // ============================================================================
@@ -1303,6 +1552,176 @@ pub async fn link_resource_to_project(
     Ok(StatusCode::CREATED)
 }
 
+/// Files of other projects importing a resource
+#[derive(Serialize)]
+pub struct ResourceUsagesResponse {
+    pub resource_id: Uuid,
+    pub resource_name: String,
+    /// Slugs of the consuming projects, sorted
+    pub project_slugs: Vec<String>,
+    pub usages: Vec<ResourceSymbolLink>,
+}
+
+/// Get cross-project usages of a resource (USES_RESOURCE_SYMBOL edges),
+/// for impact analysis before changing a shared contract
+pub async fn get_resource_usages(
+    State(state): State<OrchestratorState>,
+    Path(id): Path<String>,
+) -> Result<Json<ResourceUsagesResponse>, AppError> {
+    let id: Uuid = id
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid resource ID".to_string()))?;
+
+    let neo4j = state.orchestrator.neo4j();
+    let resource = neo4j
+        .get_resource(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Resource not found".to_string()))?;
+
+    let usages = neo4j.get_resource_symbol_links(id).await?;
+
+    let mut project_ids: Vec<Uuid> = usages.iter().map(|u| u.project_id).collect();
+    project_ids.sort();
+    project_ids.dedup();
+    let mut project_slugs = Vec::with_capacity(project_ids.len());
+    for project_id in project_ids {
+        if let Some(project) = neo4j.get_project(project_id).await? {
+            project_slugs.push(project.slug);
+        }
+    }
+    project_slugs.sort();
+
+    Ok(Json(ResourceUsagesResponse {
+        resource_id: resource.id,
+        resource_name: resource.name,
+        project_slugs,
+        usages,
+    }))
+}
+
+fn parse_resource_id(id: &str) -> Result<Uuid, AppError> {
+    id.parse()
+        .map_err(|_| AppError::BadRequest("Invalid resource ID".to_string()))
+}
+
+/// Parse a resource's OpenAPI/protobuf spec into ResourceElement nodes,
+/// replacing the previous parse. The response includes the diff against it.
+pub async fn parse_resource(
+    State(state): State<OrchestratorState>,
+    Path(id): Path<String>,
+) -> Result<Json<ResourceParseResult>, AppError> {
+    let id = parse_resource_id(&id)?;
+    let resource = state
+        .orchestrator
+        .neo4j()
+        .get_resource(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Resource not found".to_string()))?;
+
+    let format = ContractFormat::detect(
+        &resource.resource_type,
+        resource.format.as_deref(),
+        &resource.file_path,
+    )
+    .ok_or_else(|| {
+        AppError::BadRequest(format!(
+            "'{}' is not an OpenAPI or protobuf spec",
+            resource.file_path
+        ))
+    })?;
+    let path = state
+        .orchestrator
+        .resolve_resource_path(&resource)
+        .await?
+        .ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "Spec file '{}' not found (relative paths resolve against the owning or implementing project)",
+                resource.file_path
+            ))
+        })?;
+    let content = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Failed to read {}: {}", path.display(), e)))?;
+    let elements = contracts::parse_contract(resource.id, format, &content)
+        .map_err(|e| AppError::BadRequest(format!("{:#}", e)))?;
+
+    let result = state
+        .orchestrator
+        .store_resource_elements(&resource, format, elements)
+        .await?;
+    Ok(Json(result))
+}
+
+/// Query parameters for listing resource elements
+#[derive(Debug, Deserialize)]
+pub struct ResourceElementsQuery {
+    /// operation, schema, service, rpc, message, enum
+    pub kind: Option<String>,
+}
+
+/// List the elements (endpoints, messages, …) a resource defines
+pub async fn list_resource_elements(
+    State(state): State<OrchestratorState>,
+    Path(id): Path<String>,
+    Query(query): Query<ResourceElementsQuery>,
+) -> Result<Json<Vec<ResourceElementNode>>, AppError> {
+    let id = parse_resource_id(&id)?;
+    let kind = query
+        .kind
+        .as_deref()
+        .map(str::parse::<ResourceElementKind>)
+        .transpose()
+        .map_err(AppError::BadRequest)?;
+
+    let neo4j = state.orchestrator.neo4j();
+    if neo4j.get_resource(id).await?.is_none() {
+        return Err(AppError::NotFound("Resource not found".to_string()));
+    }
+    Ok(Json(neo4j.list_resource_elements(id, kind).await?))
+}
+
+/// Query parameters for diffing two resources
+#[derive(Debug, Deserialize)]
+pub struct ResourceDiffQuery {
+    /// Resource holding the older version of the contract
+    pub against: String,
+}
+
+/// Element diff between two versions of a contract
+#[derive(Serialize)]
+pub struct ResourceDiffResponse {
+    pub from_resource_id: Uuid,
+    pub to_resource_id: Uuid,
+    #[serde(flatten)]
+    pub diff: ResourceElementDiff,
+}
+
+/// Diff the parsed elements of `against` (old) and `id` (new), e.g. the
+/// `v1` and `v2` resources of one API
+pub async fn diff_resources(
+    State(state): State<OrchestratorState>,
+    Path(id): Path<String>,
+    Query(query): Query<ResourceDiffQuery>,
+) -> Result<Json<ResourceDiffResponse>, AppError> {
+    let to = parse_resource_id(&id)?;
+    let from = parse_resource_id(&query.against)?;
+
+    let neo4j = state.orchestrator.neo4j();
+    for rid in [from, to] {
+        if neo4j.get_resource(rid).await?.is_none() {
+            return Err(AppError::NotFound(format!("Resource {} not found", rid)));
+        }
+    }
+    let old = neo4j.list_resource_elements(from, None).await?;
+    let new = neo4j.list_resource_elements(to, None).await?;
+
+    Ok(Json(ResourceDiffResponse {
+        from_resource_id: from,
+        to_resource_id: to,
+        diff: contracts::diff_elements(&old, &new),
+    }))
+}
+
 // ============================================================================
 // Component Handlers
 // ============================================================================
@@ -1550,6 +1969,125 @@ pub async fn get_workspace_topology(
     Ok(Json(TopologyResponse { components }))
 }
 
+/// Declared component dependencies vs dependencies observed in code
+#[derive(Serialize)]
+pub struct TopologyDriftResponse {
+    pub workspace_slug: String,
+    #[serde(flatten)]
+    pub report: TopologyDriftReport,
+}
+
+/// Get the topology drift report: undeclared and stale component dependencies
+pub async fn get_workspace_topology_drift(
+    State(state): State<OrchestratorState>,
+    Path(slug): Path<String>,
+) -> Result<Json<TopologyDriftResponse>, AppError> {
+    let workspace = state
+        .orchestrator
+        .neo4j()
+        .get_workspace_by_slug(&slug)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Workspace '{}' not found", slug)))?;
+
+    let report = crate::orchestrator::topology_drift::compute_topology_drift(
+        state.orchestrator.neo4j(),
+        workspace.id,
+    )
+    .await?;
+
+    Ok(Json(TopologyDriftResponse {
+        workspace_slug: workspace.slug,
+        report,
+    }))
+}
+
+// ============================================================================
+// License policy
+// ============================================================================
+
+/// Get the license policy of a workspace (empty policy when none is set)
+pub async fn get_workspace_license_policy(
+    State(state): State<OrchestratorState>,
+    Path(slug): Path<String>,
+) -> Result<Json<LicensePolicy>, AppError> {
+    let workspace = state
+        .orchestrator
+        .neo4j()
+        .get_workspace_by_slug(&slug)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Workspace '{}' not found", slug)))?;
+
+    Ok(Json(LicensePolicy::from_metadata(&workspace.metadata)))
+}
+
+/// Replace the license policy of a workspace (stored in its metadata)
+pub async fn set_workspace_license_policy(
+    State(state): State<OrchestratorState>,
+    Path(slug): Path<String>,
+    Json(policy): Json<LicensePolicy>,
+) -> Result<Json<LicensePolicy>, AppError> {
+    policy.validate().map_err(AppError::BadRequest)?;
+
+    let workspace = state
+        .orchestrator
+        .neo4j()
+        .get_workspace_by_slug(&slug)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Workspace '{}' not found", slug)))?;
+
+    let mut metadata = match workspace.metadata {
+        serde_json::Value::Object(map) => map,
+        _ => serde_json::Map::new(),
+    };
+    metadata.insert(
+        LicensePolicy::METADATA_KEY.to_string(),
+        serde_json::to_value(&policy).map_err(anyhow::Error::from)?,
+    );
+
+    state
+        .orchestrator
+        .update_workspace(
+            workspace.id,
+            None,
+            None,
+            Some(serde_json::Value::Object(metadata)),
+            None,
+        )
+        .await?;
+
+    Ok(Json(policy))
+}
+
+/// Packages of the workspace's projects breaking its license policy
+#[derive(Serialize)]
+pub struct LicenseViolationsResponse {
+    pub workspace_slug: String,
+    #[serde(flatten)]
+    pub report: LicenseReport,
+}
+
+/// Evaluate the workspace license policy against every project's packages
+pub async fn get_workspace_license_violations(
+    State(state): State<OrchestratorState>,
+    Path(slug): Path<String>,
+) -> Result<Json<LicenseViolationsResponse>, AppError> {
+    let workspace = state
+        .orchestrator
+        .neo4j()
+        .get_workspace_by_slug(&slug)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Workspace '{}' not found", slug)))?;
+
+    let report =
+        crate::audit::license::workspace_license_report(state.orchestrator.neo4j(), &workspace)
+            .await?;
+
+    Ok(Json(LicenseViolationsResponse {
+        workspace_slug: workspace.slug,
+        report,
+    }))
+}
+
 // ============================================================================
 // P2P Coupling Matrix (Biomimicry — inter-project influence field)
 // ============================================================================
@@ -1587,26 +2125,409 @@ pub async fn get_coupling_matrix(
 }
 
 // ============================================================================
-// Workspace Intelligence — Graph & Summary (aggregated across all projects)
+// Workspace Search (federated across member projects)
 // ============================================================================
 
-use super::graph_types::{
-    parse_layers, GraphQuery, IntelligenceSummaryResponse, ProjectGraphMeta,
-    ProjectIntelligenceSummary, WorkspaceGraphResponse, WorkspaceIntelligenceSummaryResponse,
-};
+use crate::meilisearch::indexes::{CodeDocument, DecisionDocument, NoteDocument, SearchHit};
 
-/// GET /api/workspaces/{slug}/graph — Multi-layer graph aggregated across all workspace projects
-pub async fn get_workspace_graph(
-    State(state): State<OrchestratorState>,
-    Path(slug): Path<String>,
-    Query(params): Query<GraphQuery>,
-) -> Result<Json<WorkspaceGraphResponse>, AppError> {
-    let neo4j = state.orchestrator.neo4j_arc();
+#[derive(Deserialize)]
+pub struct WorkspaceSearchQuery {
+    pub q: String,
+    /// Comma-separated result kinds: code, notes, decisions (default: all)
+    pub kinds: Option<String>,
+    pub language: Option<String>,
+    /// Maximum results per kind (default 10)
+    pub limit: Option<usize>,
+    /// Also search the archived member projects
+    #[serde(default)]
+    pub include_archived: bool,
+}
 
-    let workspace = neo4j
-        .get_workspace_by_slug(&slug)
-        .await?
-        .ok_or_else(|| AppError::NotFound(format!("Workspace '{}' not found", slug)))?;
+#[derive(Serialize)]
+pub struct WorkspaceSearchResponse {
+    pub workspace_slug: String,
+    /// Member projects that were searched
+    pub projects: Vec<String>,
+    pub code: Vec<WorkspaceCodeHit>,
+    pub notes: Vec<WorkspaceNoteHit>,
+    pub decisions: Vec<DecisionDocument>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WorkspaceCodeHit {
+    pub path: String,
+    pub language: String,
+    pub symbols: Vec<String>,
+    pub signatures: Vec<String>,
+    pub score: f64,
+    /// Every member project the file was found in, best match first
+    pub project_slugs: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WorkspaceNoteHit {
+    #[serde(flatten)]
+    pub note: NoteDocument,
+    pub score: f64,
+    /// Every member project holding this note (or an identical copy)
+    pub project_slugs: Vec<String>,
+}
+
+/// Merge per-project code hits, keeping one entry per file path (projects
+/// with overlapping roots index the same files) ranked by best score.
+fn merge_code_hits(mut hits: Vec<SearchHit<CodeDocument>>, limit: usize) -> Vec<WorkspaceCodeHit> {
+    hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+    let mut merged: Vec<WorkspaceCodeHit> = Vec::new();
+    for hit in hits {
+        let doc = hit.document;
+        if let Some(existing) = merged.iter_mut().find(|m| m.path == doc.path) {
+            if !existing.project_slugs.contains(&doc.project_slug) {
+                existing.project_slugs.push(doc.project_slug);
+            }
+            continue;
+        }
+        merged.push(WorkspaceCodeHit {
+            path: doc.path,
+            language: doc.language,
+            symbols: doc.symbols,
+            signatures: doc.signatures,
+            score: hit.score,
+            project_slugs: vec![doc.project_slug],
+        });
+    }
+    merged.truncate(limit);
+    merged
+}
+
+/// Identity of a note's knowledge: its type and whitespace- and
+/// case-normalized content, so copies propagated to sibling projects match.
+fn note_content_key(note_type: &str, content: &str) -> String {
+    let content = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    format!("{}\u{0}{}", note_type, content.to_lowercase())
+}
+
+/// Merge per-project note hits. Notes are the same when they share an id,
+/// or when a copy has the same type and content (knowledge propagated to
+/// sibling projects); the best-scoring copy is kept.
+fn merge_note_hits(mut hits: Vec<SearchHit<NoteDocument>>, limit: usize) -> Vec<WorkspaceNoteHit> {
+    hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+    let mut merged: Vec<(String, WorkspaceNoteHit)> = Vec::new();
+    for hit in hits {
+        let key = note_content_key(&hit.document.note_type, &hit.document.content);
+        let note = hit.document;
+        if let Some((_, existing)) = merged
+            .iter_mut()
+            .find(|(k, m)| m.note.id == note.id || *k == key)
+        {
+            if !existing.project_slugs.contains(&note.project_slug) {
+                existing.project_slugs.push(note.project_slug);
+            }
+            continue;
+        }
+        merged.push((
+            key,
+            WorkspaceNoteHit {
+                project_slugs: vec![note.project_slug.clone()],
+                score: hit.score,
+                note,
+            },
+        ));
+    }
+    merged.truncate(limit);
+    merged.into_iter().map(|(_, hit)| hit).collect()
+}
+
+/// GET /api/workspaces/{slug}/search?q=...&kinds=code,notes,decisions
+///
+/// Search code, notes and decisions across every project in the workspace.
+/// Each project is queried separately and the results are merged, so a
+/// file or note shared between members appears once with all its projects.
+/// A project whose search fails is skipped rather than failing the request.
+pub async fn search_workspace(
+    State(state): State<OrchestratorState>,
+    Path(slug): Path<String>,
+    Query(params): Query<WorkspaceSearchQuery>,
+) -> Result<Json<WorkspaceSearchResponse>, AppError> {
+    if params.q.trim().is_empty() {
+        return Err(AppError::BadRequest(
+            "Query 'q' must not be empty".to_string(),
+        ));
+    }
+
+    let kinds: Vec<String> = match params.kinds.as_deref() {
+        Some(kinds) => kinds
+            .split(',')
+            .map(|k| k.trim().to_lowercase())
+            .filter(|k| !k.is_empty())
+            .collect(),
+        None => vec!["code".into(), "notes".into(), "decisions".into()],
+    };
+    if let Some(bad) = kinds
+        .iter()
+        .find(|k| !matches!(k.as_str(), "code" | "notes" | "decisions"))
+    {
+        return Err(AppError::BadRequest(format!(
+            "Unknown kind '{}' (expected code, notes, decisions)",
+            bad
+        )));
+    }
+    let wants = |kind: &str| kinds.iter().any(|k| k == kind);
+    let limit = params.limit.unwrap_or(10).clamp(1, 100);
+
+    let workspace = state
+        .orchestrator
+        .neo4j()
+        .get_workspace_by_slug(&slug)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Workspace '{}' not found", slug)))?;
+    let projects = state
+        .orchestrator
+        .neo4j()
+        .list_workspace_projects(workspace.id)
+        .await?;
+    let project_slugs: Vec<String> = projects
+        .iter()
+        .filter(|p| params.include_archived || !p.archived)
+        .map(|p| p.slug.clone())
+        .collect();
+    let meili = state.orchestrator.meili();
+    let query = params.q.as_str();
+    let language = params.language.as_deref();
+
+    let code = if wants("code") {
+        let searches = project_slugs.iter().map(|project_slug| async move {
+            meili
+                .search_code_with_scores(query, limit, language, Some(project_slug), None)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!(project_slug = %project_slug, error = ?e, "Workspace code search failed for project, skipping");
+                    Vec::new()
+                })
+        });
+        let hits = futures::future::join_all(searches).await;
+        merge_code_hits(hits.into_iter().flatten().collect(), limit)
+    } else {
+        Vec::new()
+    };
+
+    let notes = if wants("notes") {
+        let searches = project_slugs.iter().map(|project_slug| async move {
+            meili
+                .search_notes_with_scores(query, limit, Some(project_slug), None, None, None)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!(project_slug = %project_slug, error = ?e, "Workspace note search failed for project, skipping");
+                    Vec::new()
+                })
+        });
+        let hits = futures::future::join_all(searches).await;
+        merge_note_hits(hits.into_iter().flatten().collect(), limit)
+    } else {
+        Vec::new()
+    };
+
+    let decisions = if wants("decisions") && !project_slugs.is_empty() {
+        let mut decisions = meili
+            .search_decisions_in_projects(query, limit, &project_slugs)
+            .await?;
+        let mut seen = std::collections::HashSet::new();
+        decisions.retain(|d| seen.insert(d.id.clone()));
+        decisions
+    } else {
+        Vec::new()
+    };
+
+    Ok(Json(WorkspaceSearchResponse {
+        workspace_slug: workspace.slug,
+        projects: project_slugs,
+        code,
+        notes,
+        decisions,
+    }))
+}
+
+// ============================================================================
+// Workspace Context (knowledge of all member projects)
+// ============================================================================
+
+use crate::notes::{EntityType as NoteEntityType, Note, NoteFilters, NoteImportance, NoteStatus};
+
+#[derive(Deserialize)]
+pub struct WorkspaceContextQuery {
+    /// Maximum notes and decisions returned, each (default 20)
+    pub limit: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct WorkspaceContextResponse {
+    pub workspace_slug: String,
+    /// Member projects the context was built from
+    pub projects: Vec<String>,
+    /// Active notes linked to the workspace itself
+    pub workspace_notes: Vec<Note>,
+    /// Active critical and high importance notes of the member projects
+    pub notes: Vec<WorkspaceContextNote>,
+    /// Proposed and accepted decisions of the member projects, newest first
+    pub decisions: Vec<WorkspaceContextDecision>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WorkspaceContextNote {
+    #[serde(flatten)]
+    pub note: Note,
+    /// Every member project holding this note (or an identical copy)
+    pub project_slugs: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WorkspaceContextDecision {
+    #[serde(flatten)]
+    pub decision: ProjectDecision,
+    /// Every member project the decision belongs to
+    pub project_slugs: Vec<String>,
+}
+
+/// Merge the notes of the member projects, most important then newest
+/// first, keeping one entry per note (same id, or same type and content).
+fn merge_context_notes(mut notes: Vec<(String, Note)>, limit: usize) -> Vec<WorkspaceContextNote> {
+    notes.sort_by(|(_, a), (_, b)| {
+        b.importance
+            .weight()
+            .total_cmp(&a.importance.weight())
+            .then(b.created_at.cmp(&a.created_at))
+    });
+    let mut merged: Vec<(String, WorkspaceContextNote)> = Vec::new();
+    for (project_slug, note) in notes {
+        let key = note_content_key(&note.note_type.to_string(), &note.content);
+        if let Some((_, existing)) = merged
+            .iter_mut()
+            .find(|(k, m)| m.note.id == note.id || *k == key)
+        {
+            if !existing.project_slugs.contains(&project_slug) {
+                existing.project_slugs.push(project_slug);
+            }
+            continue;
+        }
+        merged.push((
+            key,
+            WorkspaceContextNote {
+                note,
+                project_slugs: vec![project_slug],
+            },
+        ));
+    }
+    merged.truncate(limit);
+    merged.into_iter().map(|(_, note)| note).collect()
+}
+
+/// Merge the decisions of the member projects, newest first, leaving out
+/// deprecated and superseded ones.
+fn merge_context_decisions(
+    mut decisions: Vec<(String, ProjectDecision)>,
+    limit: usize,
+) -> Vec<WorkspaceContextDecision> {
+    decisions.retain(|(_, d)| {
+        matches!(
+            d.decision.status,
+            DecisionStatus::Proposed | DecisionStatus::Accepted
+        )
+    });
+    decisions.sort_by(|(_, a), (_, b)| b.decision.decided_at.cmp(&a.decision.decided_at));
+    let mut merged: Vec<WorkspaceContextDecision> = Vec::new();
+    for (project_slug, decision) in decisions {
+        if let Some(existing) = merged
+            .iter_mut()
+            .find(|m| m.decision.decision.id == decision.decision.id)
+        {
+            if !existing.project_slugs.contains(&project_slug) {
+                existing.project_slugs.push(project_slug);
+            }
+            continue;
+        }
+        merged.push(WorkspaceContextDecision {
+            decision,
+            project_slugs: vec![project_slug],
+        });
+    }
+    merged.truncate(limit);
+    merged
+}
+
+/// GET /api/workspaces/{slug}/context
+///
+/// Knowledge to load before working in any project of the workspace: the
+/// notes linked to the workspace, the critical and high importance notes of
+/// every member project and their live decisions. Knowledge shared between
+/// members appears once with all its projects.
+pub async fn get_workspace_context(
+    State(state): State<OrchestratorState>,
+    Path(slug): Path<String>,
+    Query(params): Query<WorkspaceContextQuery>,
+) -> Result<Json<WorkspaceContextResponse>, AppError> {
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
+    let neo4j = state.orchestrator.neo4j();
+
+    let workspace = neo4j
+        .get_workspace_by_slug(&slug)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Workspace '{}' not found", slug)))?;
+    let projects = neo4j.list_workspace_projects(workspace.id).await?;
+
+    let mut workspace_notes = neo4j
+        .get_notes_for_entity(&NoteEntityType::Workspace, &workspace.id.to_string())
+        .await?;
+    workspace_notes.retain(|n| n.status == NoteStatus::Active);
+    workspace_notes.truncate(limit);
+
+    let filters = NoteFilters {
+        status: Some(vec![NoteStatus::Active]),
+        importance: Some(vec![NoteImportance::Critical, NoteImportance::High]),
+        limit: Some(limit as i64),
+        ..Default::default()
+    };
+    let mut notes = Vec::new();
+    let mut decisions = Vec::new();
+    for project in &projects {
+        let (project_notes, _) = neo4j.list_notes(Some(project.id), None, &filters).await?;
+        notes.extend(project_notes.into_iter().map(|n| (project.slug.clone(), n)));
+        let project_decisions = neo4j.list_project_decisions(project.id).await?;
+        decisions.extend(
+            project_decisions
+                .into_iter()
+                .map(|d| (project.slug.clone(), d)),
+        );
+    }
+
+    Ok(Json(WorkspaceContextResponse {
+        workspace_slug: workspace.slug,
+        projects: projects.into_iter().map(|p| p.slug).collect(),
+        workspace_notes,
+        notes: merge_context_notes(notes, limit),
+        decisions: merge_context_decisions(decisions, limit),
+    }))
+}
+
+// ============================================================================
+// Workspace Intelligence — Graph & Summary (aggregated across all projects)
+// ============================================================================
+
+use super::graph_types::{
+    parse_layers, GraphQuery, IntelligenceSummaryResponse, ProjectGraphMeta,
+    ProjectIntelligenceSummary, WorkspaceGraphResponse, WorkspaceIntelligenceSummaryResponse,
+};
+
+/// GET /api/workspaces/{slug}/graph — Multi-layer graph aggregated across all workspace projects
+pub async fn get_workspace_graph(
+    State(state): State<OrchestratorState>,
+    Path(slug): Path<String>,
+    Query(params): Query<GraphQuery>,
+) -> Result<Json<WorkspaceGraphResponse>, AppError> {
+    let neo4j = state.orchestrator.neo4j_arc();
+
+    let workspace = neo4j
+        .get_workspace_by_slug(&slug)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Workspace '{}' not found", slug)))?;
 
     let projects = neo4j.list_workspace_projects(workspace.id).await?;
 
@@ -1996,7 +2917,49 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
+            ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
+            registry_remote_url: None,
+            oidc_client: None,
+            neural_router: crate::test_helpers::mock_neural_router(),
+            trajectory_collector: std::sync::RwLock::new(None),
+            trajectory_store_neo4j: None,
+            trajectory_store: None,
+            identity: None,
+            reactor_counters: std::sync::OnceLock::new(),
+            confidence_tracker: Arc::new(crate::graph::confidence::ConfidenceTracker::default()),
+            mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
+            model_catalog: crate::chat::model_catalog::ModelCatalogCache::new(None),
+        });
+        create_router(state)
+    }
+
+    /// Build a test router over an already seeded state
+    async fn test_app_with_state(app_state: crate::AppState) -> axum::Router {
+        let orchestrator = Arc::new(Orchestrator::new(app_state).await.unwrap());
+        let watcher = Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
+            orchestrator.clone(),
+        )));
+        let state = Arc::new(ServerState {
+            orchestrator,
+            watcher,
+            chat_manager: None,
+            event_bus: Arc::new(crate::events::HybridEmitter::new(Arc::new(
+                crate::events::EventBus::default(),
+            ))),
+            nats_emitter: None,
+            auth_config: Some(crate::test_helpers::test_auth_config()),
+            serve_frontend: false,
+            frontend_path: "./dist".to_string(),
+            setup_completed: true,
+            server_port: 6600,
+            public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -2081,7 +3044,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -2174,6 +3140,120 @@ mod tests {
         }
     }
 
+    // ====================================================================
+    // GET /api/workspace-milestones/{id}/rollup
+    // ====================================================================
+
+    fn rollup_task(
+        plan_id: Uuid,
+        status: TaskStatus,
+        completed_days_ago: Option<i64>,
+    ) -> TaskWithPlan {
+        let now = chrono::Utc::now();
+        let mut task = test_task_titled("Rollup task");
+        task.status = status;
+        task.completed_at = completed_days_ago.map(|d| now - chrono::Duration::days(d));
+        TaskWithPlan {
+            task,
+            plan_id,
+            plan_title: format!("Plan {}", plan_id),
+            plan_status: Some("in_progress".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_milestone_rollup_forecast() {
+        let now = chrono::Utc::now();
+        let (plan_a, plan_b) = (Uuid::new_v4(), Uuid::new_v4());
+        let tasks = vec![
+            rollup_task(plan_a, TaskStatus::Completed, Some(2)),
+            rollup_task(plan_a, TaskStatus::Completed, Some(10)),
+            rollup_task(plan_a, TaskStatus::InProgress, None),
+            rollup_task(plan_b, TaskStatus::Completed, Some(60)),
+            rollup_task(plan_b, TaskStatus::Pending, None),
+            rollup_task(plan_b, TaskStatus::Blocked, None),
+        ];
+        let mut milestone = WorkspaceMilestoneNode {
+            id: Uuid::new_v4(),
+            workspace_id: Uuid::new_v4(),
+            title: "Launch".to_string(),
+            description: None,
+            status: MilestoneStatus::Open,
+            target_date: Some(now + chrono::Duration::weeks(4)),
+            closed_at: None,
+            created_at: now,
+            tags: vec![],
+        };
+
+        let rollup = milestone_rollup(milestone.clone(), &tasks, 28, now);
+        assert_eq!(rollup.progress.total, 6);
+        assert_eq!(rollup.progress.completed, 3);
+        assert_eq!(rollup.plans.len(), 2);
+        assert_eq!(rollup.plans[0].id, plan_a.to_string());
+        assert_eq!(rollup.plans[0].completed, 2);
+        assert_eq!(rollup.plans[1].blocked, 1);
+
+        // Two completions in the last 4 weeks (the 60-day-old one is outside)
+        assert_eq!(rollup.velocity.weekly.len(), 4);
+        assert_eq!(rollup.velocity.completed_in_window, 2);
+        assert_eq!(rollup.velocity.weekly[3].completed, 1);
+        assert!((rollup.velocity.tasks_per_week - 0.5).abs() < f64::EPSILON);
+
+        // 3 remaining at 0.5/week → 6 weeks, past the 4-week target
+        assert_eq!(rollup.forecast.remaining, 3);
+        assert_eq!(rollup.forecast.basis, "velocity");
+        assert_eq!(rollup.forecast.on_track, Some(false));
+        let estimate: chrono::DateTime<chrono::Utc> = rollup
+            .forecast
+            .estimated_completion
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!((estimate - now).num_days(), 42);
+
+        milestone.target_date = Some(now + chrono::Duration::weeks(8));
+        let rollup = milestone_rollup(milestone.clone(), &tasks, 28, now);
+        assert_eq!(rollup.forecast.on_track, Some(true));
+
+        // Nothing completed recently → no estimate
+        let stale = vec![
+            rollup_task(plan_b, TaskStatus::Completed, Some(60)),
+            rollup_task(plan_b, TaskStatus::Pending, None),
+        ];
+        let rollup = milestone_rollup(milestone, &stale, 7, now);
+        assert_eq!(rollup.velocity.window_days, 7);
+        assert_eq!(rollup.forecast.basis, "no_history");
+        assert!(rollup.forecast.estimated_completion.is_none());
+        assert_eq!(rollup.forecast.on_track, None);
+    }
+
+    #[tokio::test]
+    async fn test_get_workspace_milestone_rollup() {
+        let (app, milestone_id, _, _) = test_app_with_milestone_tasks().await;
+        let uri = format!(
+            "/api/workspace-milestones/{}/rollup?window_days=14",
+            milestone_id
+        );
+        let resp = app.clone().oneshot(auth_get(&uri)).await.unwrap();
+
+        assert_eq!(resp.status(), HttpStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["title"], "Test Milestone");
+        assert_eq!(json["progress"]["total"], 2);
+        assert_eq!(json["plans"].as_array().unwrap().len(), 1);
+        assert_eq!(json["plans"][0]["total"], 2);
+        assert_eq!(json["velocity"]["window_days"], 14);
+        assert_eq!(json["forecast"]["remaining"], 2);
+        assert_eq!(json["forecast"]["basis"], "no_history");
+
+        let uri = format!("/api/workspace-milestones/{}/rollup", Uuid::new_v4());
+        let resp = app.oneshot(auth_get(&uri)).await.unwrap();
+        assert_eq!(resp.status(), HttpStatus::NOT_FOUND);
+    }
+
     // ====================================================================
     // Existing serialization tests
     // ====================================================================
@@ -2410,6 +3490,16 @@ mod tests {
             .unwrap()
     }
 
+    fn auth_put_json(uri: &str, body: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .method("PUT")
+            .uri(uri)
+            .header("authorization", test_bearer_token())
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_string(&body).unwrap()))
+            .unwrap()
+    }
+
     fn auth_delete(uri: &str) -> Request<Body> {
         Request::builder()
             .method("DELETE")
@@ -2478,7 +3568,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -2536,7 +3629,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -2590,7 +3686,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -2620,6 +3719,726 @@ mod tests {
         assert!(projects[0].get("root_path").is_some());
     }
 
+    #[tokio::test]
+    async fn test_get_workspace_stats() {
+        let app_state = mock_app_state();
+        let ws = test_workspace();
+        app_state.neo4j.create_workspace(&ws).await.unwrap();
+        let project = crate::test_helpers::test_project();
+        app_state.neo4j.create_project(&project).await.unwrap();
+        app_state
+            .neo4j
+            .add_project_to_workspace(ws.id, project.id)
+            .await
+            .unwrap();
+        let plan = crate::test_helpers::test_plan_for_project(project.id);
+        app_state.neo4j.create_plan(&plan).await.unwrap();
+        app_state
+            .neo4j
+            .create_task(plan.id, &crate::test_helpers::test_task())
+            .await
+            .unwrap();
+        let app = test_app_with_state(app_state).await;
+
+        let resp = app
+            .clone()
+            .oneshot(auth_get(&format!("/api/workspaces/{}/stats", ws.slug)))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["project_count"], 1);
+        assert_eq!(json["open_plans"], 1);
+        assert_eq!(json["open_tasks"], 1);
+        assert_eq!(json["projects_without_analytics"], 1);
+
+        let resp = app
+            .oneshot(auth_get("/api/workspaces/nonexistent/stats"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::NOT_FOUND);
+    }
+
+    fn code_doc(path: &str, project_slug: &str) -> CodeDocument {
+        CodeDocument {
+            id: path.to_string(),
+            path: path.to_string(),
+            language: "rust".to_string(),
+            symbols: vec!["authenticate".to_string()],
+            docstrings: String::new(),
+            signatures: vec![],
+            imports: vec![],
+            project_id: String::new(),
+            project_slug: project_slug.to_string(),
+        }
+    }
+
+    fn note_doc(id: &str, project_slug: &str, content: &str) -> NoteDocument {
+        NoteDocument {
+            id: id.to_string(),
+            project_id: String::new(),
+            project_slug: project_slug.to_string(),
+            note_type: "gotcha".to_string(),
+            status: "active".to_string(),
+            importance: "high".to_string(),
+            scope_type: "project".to_string(),
+            scope_path: String::new(),
+            content: content.to_string(),
+            tags: vec![],
+            anchor_entities: vec![],
+            created_at: 0,
+            created_by: "test".to_string(),
+            staleness_score: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_merge_code_hits_dedups_shared_paths() {
+        let hits = vec![
+            SearchHit {
+                document: code_doc("/ws/shared/auth.rs", "api"),
+                score: 0.4,
+                highlights: vec![],
+            },
+            SearchHit {
+                document: code_doc("/ws/shared/auth.rs", "monorepo"),
+                score: 0.9,
+                highlights: vec![],
+            },
+            SearchHit {
+                document: code_doc("/ws/web/login.rs", "web"),
+                score: 0.6,
+                highlights: vec![],
+            },
+        ];
+        let merged = merge_code_hits(hits, 10);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].path, "/ws/shared/auth.rs");
+        assert_eq!(merged[0].score, 0.9);
+        assert_eq!(merged[0].project_slugs, vec!["monorepo", "api"]);
+        assert_eq!(merged[1].path, "/ws/web/login.rs");
+
+        assert_eq!(
+            merge_code_hits(
+                vec![SearchHit {
+                    document: code_doc("/a.rs", "api"),
+                    score: 1.0,
+                    highlights: vec![],
+                }],
+                0
+            )
+            .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_merge_note_hits_dedups_by_id_and_content() {
+        let hits = vec![
+            SearchHit {
+                document: note_doc("n1", "api", "Tokens expire after 15 minutes"),
+                score: 0.7,
+                highlights: vec![],
+            },
+            SearchHit {
+                document: note_doc("n2", "web", "tokens  expire after 15 minutes\n"),
+                score: 0.8,
+                highlights: vec![],
+            },
+            SearchHit {
+                document: note_doc("n1", "api", "Tokens expire after 15 minutes"),
+                score: 0.7,
+                highlights: vec![],
+            },
+            SearchHit {
+                document: note_doc("n3", "web", "Refresh tokens are rotated"),
+                score: 0.5,
+                highlights: vec![],
+            },
+        ];
+        let merged = merge_note_hits(hits, 10);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].note.id, "n2");
+        assert_eq!(merged[0].project_slugs, vec!["web", "api"]);
+        assert_eq!(merged[1].note.id, "n3");
+    }
+
+    #[tokio::test]
+    async fn test_search_workspace_federates_member_projects() {
+        let app_state = mock_app_state();
+        let ws = test_workspace();
+        app_state.neo4j.create_workspace(&ws).await.unwrap();
+        for name in ["api", "web"] {
+            let project = crate::test_helpers::test_project_named(name);
+            app_state.neo4j.create_project(&project).await.unwrap();
+            app_state
+                .neo4j
+                .add_project_to_workspace(ws.id, project.id)
+                .await
+                .unwrap();
+        }
+        for (path, slug) in [
+            ("/ws/api/auth.rs", "api"),
+            ("/ws/web/auth.rs", "web"),
+            ("/other/auth.rs", "outsider"),
+        ] {
+            app_state
+                .meili
+                .index_code(&code_doc(path, slug))
+                .await
+                .unwrap();
+        }
+        app_state
+            .meili
+            .index_note(&note_doc(
+                "n1",
+                "api",
+                "authenticate calls the web session API",
+            ))
+            .await
+            .unwrap();
+        app_state
+            .meili
+            .index_note(&note_doc(
+                "n2",
+                "web",
+                "authenticate calls the web session API",
+            ))
+            .await
+            .unwrap();
+        let app = test_app_with_state(app_state).await;
+
+        let resp = app
+            .clone()
+            .oneshot(auth_get(&format!(
+                "/api/workspaces/{}/search?q=authenticate",
+                ws.slug
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["projects"].as_array().unwrap().len(), 2);
+        let paths: Vec<&str> = json["code"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|h| h["path"].as_str().unwrap())
+            .collect();
+        assert_eq!(paths.len(), 2, "{:?}", paths);
+        assert!(!paths.contains(&"/other/auth.rs"));
+        let notes = json["notes"].as_array().unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0]["project_slugs"].as_array().unwrap().len(), 2);
+
+        let resp = app
+            .oneshot(auth_get(&format!(
+                "/api/workspaces/{}/search?q=authenticate&kinds=code,bogus",
+                ws.slug
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::BAD_REQUEST);
+    }
+
+    fn test_resource(file_path: &str, project_id: Option<Uuid>) -> ResourceNode {
+        ResourceNode {
+            id: Uuid::new_v4(),
+            workspace_id: None,
+            project_id,
+            name: "Billing".to_string(),
+            resource_type: ResourceType::Protobuf,
+            file_path: file_path.to_string(),
+            url: None,
+            format: None,
+            version: None,
+            description: None,
+            created_at: chrono::Utc::now(),
+            updated_at: None,
+            metadata: serde_json::Value::Null,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parse_resource_and_list_elements() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("proto")).unwrap();
+        let spec = tmp.path().join("proto/billing.proto");
+        std::fs::write(
+            &spec,
+            "package billing;\nservice Billing { rpc Charge (Req) returns (Res); }\nmessage Req { string id = 1; }\n",
+        )
+        .unwrap();
+
+        let app_state = mock_app_state();
+        let mut project = crate::test_helpers::test_project_named("api");
+        project.root_path = tmp.path().to_string_lossy().to_string();
+        app_state.neo4j.create_project(&project).await.unwrap();
+        let resource = test_resource("proto/billing.proto", Some(project.id));
+        app_state.neo4j.create_resource(&resource).await.unwrap();
+        let app = test_app_with_state(app_state).await;
+
+        let parse_uri = format!("/api/resources/{}/parse", resource.id);
+        let parse = |app: axum::Router| {
+            let uri = parse_uri.clone();
+            async move {
+                let resp = app
+                    .oneshot(auth_post_json(&uri, serde_json::json!({})))
+                    .await
+                    .unwrap();
+                assert_eq!(resp.status(), HttpStatus::OK);
+                let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+            }
+        };
+
+        let first = parse(app.clone()).await;
+        assert_eq!(first["format"], "protobuf");
+        assert_eq!(first["elements"], 3);
+        assert_eq!(first["diff"]["added"].as_array().unwrap().len(), 3);
+
+        // New version: rpc added, message field added
+        std::fs::write(
+            &spec,
+            "package billing;\nservice Billing {\n  rpc Charge (Req) returns (Res);\n  rpc Refund (Req) returns (Res);\n}\nmessage Req { string id = 1; int64 cents = 2; }\n",
+        )
+        .unwrap();
+        let second = parse(app.clone()).await;
+        assert_eq!(second["diff"]["added"][0]["name"], "billing.Billing/Refund");
+        let changed: Vec<&str> = second["diff"]["changed"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(changed, vec!["billing.Billing", "billing.Req"]);
+
+        let resp = app
+            .clone()
+            .oneshot(auth_get(&format!(
+                "/api/resources/{}/elements?kind=rpc",
+                resource.id
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let rpcs: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(rpcs.as_array().unwrap().len(), 2);
+
+        let resp = app
+            .oneshot(auth_get(&format!(
+                "/api/resources/{}/elements?kind=bogus",
+                resource.id
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_parse_resource_missing_file_400() {
+        let app_state = mock_app_state();
+        let resource = test_resource("/nonexistent/billing.proto", None);
+        app_state.neo4j.create_resource(&resource).await.unwrap();
+        let app = test_app_with_state(app_state).await;
+
+        let resp = app
+            .oneshot(auth_post_json(
+                &format!("/api/resources/{}/parse", resource.id),
+                serde_json::json!({}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_diff_resources() {
+        let app_state = mock_app_state();
+        let v1 = test_resource("v1/billing.proto", None);
+        let v2 = test_resource("v2/billing.proto", None);
+        let element = |resource_id: Uuid, name: &str, signature: &str| ResourceElementNode {
+            resource_id,
+            kind: ResourceElementKind::Message,
+            name: name.to_string(),
+            summary: None,
+            signature: signature.to_string(),
+            line: None,
+        };
+        for (resource, elements) in [
+            (
+                &v1,
+                vec![
+                    element(v1.id, "billing.Invoice", "1 string id"),
+                    element(v1.id, "billing.Legacy", ""),
+                ],
+            ),
+            (
+                &v2,
+                vec![element(
+                    v2.id,
+                    "billing.Invoice",
+                    "1 string id; 2 int64 cents",
+                )],
+            ),
+        ] {
+            app_state.neo4j.create_resource(resource).await.unwrap();
+            app_state
+                .neo4j
+                .replace_resource_elements(resource.id, &elements)
+                .await
+                .unwrap();
+        }
+        let app = test_app_with_state(app_state).await;
+
+        let resp = app
+            .oneshot(auth_get(&format!(
+                "/api/resources/{}/diff?against={}",
+                v2.id, v1.id
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["removed"][0]["name"], "billing.Legacy");
+        assert_eq!(json["changed"][0]["before"], "1 string id");
+        assert!(json["added"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_resource_usages() {
+        let app_state = mock_app_state();
+        let web = crate::test_helpers::test_project_named("web");
+        app_state.neo4j.create_project(&web).await.unwrap();
+        let resource = test_resource("billing.proto", None);
+        app_state.neo4j.create_resource(&resource).await.unwrap();
+        app_state
+            .neo4j
+            .replace_resource_symbol_links(
+                web.id,
+                &[ResourceSymbolLink {
+                    resource_id: resource.id,
+                    project_id: web.id,
+                    file_path: "/web/src/pay.ts".to_string(),
+                    import_path: "gen/billing_pb".to_string(),
+                    symbol: "billing_pb".to_string(),
+                    line: 2,
+                }],
+            )
+            .await
+            .unwrap();
+        let app = test_app_with_state(app_state).await;
+
+        let resp = app
+            .clone()
+            .oneshot(auth_get(&format!("/api/resources/{}/usages", resource.id)))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["project_slugs"], serde_json::json!([web.slug]));
+        assert_eq!(json["usages"][0]["file_path"], "/web/src/pay.ts");
+
+        let resp = app
+            .oneshot(auth_get(&format!(
+                "/api/resources/{}/usages",
+                Uuid::new_v4()
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_get_workspace_topology_drift() {
+        let app_state = mock_app_state();
+        let ws = test_workspace();
+        app_state.neo4j.create_workspace(&ws).await.unwrap();
+        let app = test_app_with_state(app_state).await;
+
+        let resp = app
+            .clone()
+            .oneshot(auth_get(&format!(
+                "/api/workspaces/{}/topology/drift",
+                ws.slug
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["workspace_slug"], ws.slug);
+        assert!(json["undeclared"].as_array().unwrap().is_empty());
+        assert!(json["stale"].as_array().unwrap().is_empty());
+
+        let resp = app
+            .oneshot(auth_get("/api/workspaces/nope/topology/drift"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_workspace_license_policy_and_violations() {
+        let app_state = mock_app_state();
+        let ws = test_workspace();
+        app_state.neo4j.create_workspace(&ws).await.unwrap();
+        let project = crate::test_helpers::test_project();
+        app_state.neo4j.create_project(&project).await.unwrap();
+        app_state
+            .neo4j
+            .add_project_to_workspace(ws.id, project.id)
+            .await
+            .unwrap();
+        app_state
+            .neo4j
+            .replace_project_packages(
+                project.id,
+                &[PackageNode {
+                    ecosystem: PackageEcosystem::CratesIo,
+                    name: "gpl-crate".to_string(),
+                    version: "1.0.0".to_string(),
+                    lockfile: "Cargo.lock".to_string(),
+                    license: Some("GPL-3.0".to_string()),
+                }],
+            )
+            .await
+            .unwrap();
+        let app = test_app_with_state(app_state).await;
+        let policy_uri = format!("/api/workspaces/{}/license-policy", ws.slug);
+
+        let resp = app.clone().oneshot(auth_get(&policy_uri)).await.unwrap();
+        assert_eq!(resp.status(), HttpStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["denied"], serde_json::json!([]));
+
+        // Overlapping allow/deny entries are rejected
+        let resp = app
+            .clone()
+            .oneshot(auth_put_json(
+                &policy_uri,
+                serde_json::json!({"allowed": ["MIT"], "denied": ["mit"]}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::BAD_REQUEST);
+
+        let resp = app
+            .clone()
+            .oneshot(auth_put_json(
+                &policy_uri,
+                serde_json::json!({"denied": ["GPL-*"]}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::OK);
+
+        let resp = app
+            .clone()
+            .oneshot(auth_get(&format!(
+                "/api/workspaces/{}/license-violations",
+                ws.slug
+            )))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["workspace_slug"], ws.slug);
+        assert_eq!(json["policy"]["denied"], serde_json::json!(["GPL-*"]));
+        assert_eq!(json["packages_scanned"], 1);
+        assert_eq!(json["violations"][0]["name"], "gpl-crate");
+        assert_eq!(json["violations"][0]["kind"], "denied");
+
+        let resp = app
+            .oneshot(auth_get("/api/workspaces/nope/license-violations"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_search_workspace_not_found() {
+        let app = test_app().await;
+        let resp = app
+            .oneshot(auth_get("/api/workspaces/nope/search?q=auth"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::NOT_FOUND);
+    }
+
+    fn context_note(importance: NoteImportance, content: &str) -> Note {
+        let mut note =
+            crate::test_helpers::test_note(Uuid::new_v4(), crate::notes::NoteType::Gotcha, content);
+        note.importance = importance;
+        note
+    }
+
+    #[test]
+    fn test_merge_context_notes_dedups_and_ranks() {
+        let shared = context_note(NoteImportance::High, "Tokens expire after 15 minutes");
+        let mut copy = context_note(NoteImportance::High, "tokens expire after  15 minutes");
+        copy.created_at = shared.created_at - chrono::Duration::hours(1);
+        let critical = context_note(NoteImportance::Critical, "Never log card numbers");
+        let notes = vec![
+            ("api".to_string(), shared.clone()),
+            ("web".to_string(), copy),
+            ("web".to_string(), critical.clone()),
+            ("web".to_string(), shared.clone()),
+        ];
+
+        let merged = merge_context_notes(notes, 10);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].note.id, critical.id);
+        assert_eq!(merged[1].note.id, shared.id);
+        assert_eq!(merged[1].project_slugs, vec!["api", "web"]);
+        assert_eq!(
+            merge_context_notes(vec![("api".to_string(), critical)], 0).len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_merge_context_decisions_dedups_live_ones() {
+        let decision = |description: &str, status: DecisionStatus| ProjectDecision {
+            decision: DecisionNode {
+                status,
+                ..crate::test_helpers::test_decision(description, "because")
+            },
+            task_id: None,
+            adr: None,
+        };
+        let mut older = decision("Use REST", DecisionStatus::Proposed);
+        older.decision.decided_at -= chrono::Duration::days(1);
+        let shared = decision("Use JWT", DecisionStatus::Accepted);
+        let decisions = vec![
+            ("api".to_string(), older.clone()),
+            ("api".to_string(), shared.clone()),
+            ("web".to_string(), shared.clone()),
+            (
+                "web".to_string(),
+                decision("Use SOAP", DecisionStatus::Deprecated),
+            ),
+        ];
+
+        let merged = merge_context_decisions(decisions, 10);
+        let ids: Vec<Uuid> = merged.iter().map(|d| d.decision.decision.id).collect();
+        assert_eq!(ids, vec![shared.decision.id, older.decision.id]);
+        assert_eq!(merged[0].project_slugs, vec!["api", "web"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_workspace_context() {
+        let app_state = mock_app_state();
+        let neo4j = app_state.neo4j.clone();
+        let ws = test_workspace();
+        neo4j.create_workspace(&ws).await.unwrap();
+        let mut projects = Vec::new();
+        for name in ["api", "web"] {
+            let project = crate::test_helpers::test_project_named(name);
+            neo4j.create_project(&project).await.unwrap();
+            neo4j
+                .add_project_to_workspace(ws.id, project.id)
+                .await
+                .unwrap();
+            projects.push(project);
+        }
+        let outsider = crate::test_helpers::test_project_named("outsider");
+        neo4j.create_project(&outsider).await.unwrap();
+
+        for (project, importance, content) in [
+            (
+                &projects[0],
+                NoteImportance::Critical,
+                "Never log card numbers",
+            ),
+            (
+                &projects[1],
+                NoteImportance::Critical,
+                "Never log card numbers",
+            ),
+            (&projects[0], NoteImportance::Medium, "Prefer small modules"),
+            (&outsider, NoteImportance::Critical, "Outsider knowledge"),
+        ] {
+            let mut note = context_note(importance, content);
+            note.project_id = Some(project.id);
+            neo4j.create_note(&note).await.unwrap();
+        }
+        let mut ws_note = context_note(NoteImportance::Medium, "All services speak JSON");
+        ws_note.project_id = None;
+        neo4j.create_note(&ws_note).await.unwrap();
+        neo4j
+            .link_note_to_entity(
+                ws_note.id,
+                &NoteEntityType::Workspace,
+                &ws.id.to_string(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let mut plan = test_plan();
+        plan.project_id = Some(projects[0].id);
+        neo4j.create_plan(&plan).await.unwrap();
+        let task = test_task_titled("Auth");
+        neo4j.create_task(plan.id, &task).await.unwrap();
+        let decision = crate::test_helpers::test_decision("Use JWT", "Stateless");
+        neo4j.create_decision(task.id, &decision).await.unwrap();
+
+        let app = test_app_with_state(app_state).await;
+        let resp = app
+            .clone()
+            .oneshot(auth_get(&format!("/api/workspaces/{}/context", ws.slug)))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["projects"], serde_json::json!(["api", "web"]));
+        assert_eq!(json["workspace_notes"][0]["id"], ws_note.id.to_string());
+        let notes = json["notes"].as_array().unwrap();
+        assert_eq!(notes.len(), 1, "{:?}", notes);
+        assert_eq!(notes[0]["content"], "Never log card numbers");
+        assert_eq!(notes[0]["project_slugs"].as_array().unwrap().len(), 2);
+        let decisions = json["decisions"].as_array().unwrap();
+        assert_eq!(decisions.len(), 1);
+        assert_eq!(decisions[0]["decision"]["id"], decision.id.to_string());
+        assert_eq!(decisions[0]["project_slugs"], serde_json::json!(["api"]));
+
+        let resp = app
+            .oneshot(auth_get("/api/workspaces/nope/context"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), HttpStatus::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn test_list_resources_empty() {
         let app_state = mock_app_state();
@@ -2644,7 +4463,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -2692,7 +4514,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -2740,7 +4565,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -2786,7 +4614,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -2877,7 +4708,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -2930,7 +4764,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -2981,7 +4818,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -3032,7 +4872,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -3090,7 +4933,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -3141,7 +4987,10 @@ mod tests {
             setup_completed: true,
             server_port: 6600,
             public_url: None,
+            read_only: false,
             ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
             registry_remote_url: None,
             oidc_client: None,
             neural_router: crate::test_helpers::mock_neural_router(),
@@ -23,7 +23,9 @@ use crate::auth::jwt::Claims;
 use crate::auth::refresh;
 use crate::neo4j::GraphStore;
 use crate::AuthConfig;
+use async_trait::async_trait;
 use axum::extract::ws::{Message, WebSocket};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -38,36 +40,100 @@ use uuid::Uuid;
 /// How long a WS ticket remains valid (seconds).
 const TICKET_TTL_SECS: u64 = 30;
 
+/// JetStream KV bucket holding tickets shared across replicas.
+pub const TICKET_BUCKET: &str = "ws_tickets";
+
 /// A pending WebSocket ticket — single-use, short-lived.
 struct WsTicket {
     claims: Claims,
     created_at: Instant,
 }
 
-/// In-memory store for ephemeral WebSocket auth tickets.
+/// A ticket as stored in a [`TicketBackend`] shared by several instances.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SharedTicket {
+    pub claims: Claims,
+    /// Unix timestamp (seconds) of creation.
+    pub created_at: i64,
+}
+
+impl SharedTicket {
+    fn is_expired(&self) -> bool {
+        chrono::Utc::now().timestamp() - self.created_at > TICKET_TTL_SECS as i64
+    }
+}
+
+/// Storage shared by every server replica, so a ticket issued by one
+/// instance can be consumed by another behind a load balancer.
+#[async_trait]
+pub trait TicketBackend: Send + Sync {
+    /// Store a ticket; the backend should drop it after [`TICKET_TTL_SECS`].
+    async fn put(&self, ticket: &str, entry: &SharedTicket) -> anyhow::Result<()>;
+
+    /// Remove and return a ticket. Must be atomic: when two instances race
+    /// for the same ticket, at most one gets it.
+    async fn take(&self, ticket: &str) -> anyhow::Result<Option<SharedTicket>>;
+}
+
+/// Store for ephemeral WebSocket auth tickets.
 ///
 /// Tickets are generated by `POST /auth/ws-ticket` (which validates the
 /// HttpOnly cookie via `fetch()`) and consumed during the WS upgrade
 /// handshake. Each ticket is single-use and expires after [`TICKET_TTL_SECS`].
 ///
+/// Tickets are kept in memory unless a shared [`TicketBackend`] is set
+/// (NATS KV when NATS is configured); if the backend fails, the store falls
+/// back to memory so a single instance keeps working.
+///
 /// This works around WKWebView (macOS/iOS) not sending cookies on WebSocket
 /// upgrade requests — a known WebKit limitation since macOS Monterey.
 #[derive(Default)]
 pub struct WsTicketStore {
     tickets: RwLock<HashMap<String, WsTicket>>,
+    shared: Option<Arc<dyn TicketBackend>>,
 }
 
 impl WsTicketStore {
-    /// Create an empty ticket store.
+    /// Create an empty in-memory ticket store.
     pub fn new() -> Self {
         Self {
             tickets: RwLock::new(HashMap::new()),
+            shared: None,
+        }
+    }
+
+    /// Create a ticket store backed by storage shared across instances.
+    pub fn with_backend(backend: Arc<dyn TicketBackend>) -> Self {
+        Self {
+            tickets: RwLock::new(HashMap::new()),
+            shared: Some(backend),
         }
     }
 
+    /// Whether tickets are shared across instances.
+    pub fn is_shared(&self) -> bool {
+        self.shared.is_some()
+    }
+
     /// Generate a new ticket for the given claims. Returns the ticket string.
     pub async fn create_ticket(&self, claims: Claims) -> String {
         let ticket = Uuid::new_v4().to_string();
+        let claims = match self.shared {
+            Some(ref backend) => {
+                let entry = SharedTicket {
+                    claims,
+                    created_at: chrono::Utc::now().timestamp(),
+                };
+                match backend.put(&ticket, &entry).await {
+                    Ok(()) => return ticket,
+                    Err(e) => {
+                        warn!("Shared WS ticket store unavailable, keeping ticket local: {e}");
+                        entry.claims
+                    }
+                }
+            }
+            None => claims,
+        };
         let entry = WsTicket {
             claims,
             created_at: Instant::now(),
@@ -80,6 +146,18 @@ impl WsTicketStore {
     ///
     /// The ticket is removed from the store regardless (single-use).
     pub async fn consume_ticket(&self, ticket: &str) -> Option<Claims> {
+        if let Some(ref backend) = self.shared {
+            match backend.take(ticket).await {
+                Ok(Some(entry)) if entry.is_expired() => {
+                    debug!("WS ticket expired (shared store)");
+                    return None;
+                }
+                Ok(Some(entry)) => return Some(entry.claims),
+                // Not shared — it may have been kept local after a backend error
+                Ok(None) => {}
+                Err(e) => warn!("Shared WS ticket store unavailable: {e}"),
+            }
+        }
         let entry = self.tickets.write().await.remove(ticket)?;
         if entry.created_at.elapsed().as_secs() > TICKET_TTL_SECS {
             debug!(
@@ -92,6 +170,8 @@ impl WsTicketStore {
     }
 
     /// Remove all expired tickets. Called periodically by a background task.
+    ///
+    /// Only the in-memory tickets: shared backends expire their own.
     pub async fn cleanup_expired(&self) {
         let mut map = self.tickets.write().await;
         let before = map.len();
@@ -103,6 +183,73 @@ impl WsTicketStore {
     }
 }
 
+/// [`TicketBackend`] on a NATS JetStream key-value bucket ([`TICKET_BUCKET`]).
+///
+/// Entries expire through the bucket's `max_age`; a ticket is taken by
+/// overwriting it with an empty value at the revision that was read, so only
+/// one instance wins a race.
+pub struct NatsTicketBackend {
+    kv: async_nats::jetstream::kv::Store,
+}
+
+impl NatsTicketBackend {
+    /// Open the ticket bucket, creating it if needed. Fails when the NATS
+    /// server has JetStream disabled.
+    pub async fn connect(client: &async_nats::Client) -> anyhow::Result<Self> {
+        let jetstream = async_nats::jetstream::new(client.clone());
+        let kv = match jetstream.get_key_value(TICKET_BUCKET).await {
+            Ok(kv) => kv,
+            Err(_) => jetstream
+                .create_key_value(async_nats::jetstream::kv::Config {
+                    bucket: TICKET_BUCKET.to_string(),
+                    history: 1,
+                    max_age: std::time::Duration::from_secs(TICKET_TTL_SECS * 2),
+                    ..Default::default()
+                })
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to create KV bucket {TICKET_BUCKET}: {e}"))?,
+        };
+        Ok(Self { kv })
+    }
+}
+
+#[async_trait]
+impl TicketBackend for NatsTicketBackend {
+    async fn put(&self, ticket: &str, entry: &SharedTicket) -> anyhow::Result<()> {
+        let value = serde_json::to_vec(entry)?;
+        self.kv
+            .put(ticket, value.into())
+            .await
+            .map_err(|e| anyhow::anyhow!("KV put failed: {e}"))?;
+        Ok(())
+    }
+
+    async fn take(&self, ticket: &str) -> anyhow::Result<Option<SharedTicket>> {
+        let Some(entry) = self
+            .kv
+            .entry(ticket)
+            .await
+            .map_err(|e| anyhow::anyhow!("KV get failed: {e}"))?
+        else {
+            return Ok(None);
+        };
+        if entry.value.is_empty() {
+            return Ok(None);
+        }
+        // Another instance consumed it first if the revision moved on
+        if self
+            .kv
+            .update(ticket, Vec::new().into(), entry.revision)
+            .await
+            .is_err()
+        {
+            return Ok(None);
+        }
+        let _ = self.kv.purge(ticket).await;
+        Ok(Some(serde_json::from_slice(&entry.value)?))
+    }
+}
+
 // ============================================================================
 // Cookie auth result
 // ============================================================================
@@ -450,6 +597,77 @@ mod tests {
         assert_eq!(store.tickets.read().await.len(), 0);
     }
 
+    /// Shared backend standing in for NATS KV across replicas.
+    #[derive(Default)]
+    struct FakeBackend {
+        entries: std::sync::Mutex<HashMap<String, String>>,
+        failing: bool,
+    }
+
+    #[async_trait]
+    impl TicketBackend for FakeBackend {
+        async fn put(&self, ticket: &str, entry: &SharedTicket) -> anyhow::Result<()> {
+            if self.failing {
+                anyhow::bail!("backend down");
+            }
+            self.entries
+                .lock()
+                .unwrap()
+                .insert(ticket.to_string(), serde_json::to_string(entry)?);
+            Ok(())
+        }
+
+        async fn take(&self, ticket: &str) -> anyhow::Result<Option<SharedTicket>> {
+            if self.failing {
+                anyhow::bail!("backend down");
+            }
+            let value = self.entries.lock().unwrap().remove(ticket);
+            Ok(value.map(|v| serde_json::from_str(&v)).transpose()?)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shared_ticket_consumed_by_other_replica() {
+        let backend = Arc::new(FakeBackend::default());
+        let replica_a = WsTicketStore::with_backend(backend.clone());
+        let replica_b = WsTicketStore::with_backend(backend.clone());
+        assert!(replica_a.is_shared());
+
+        let ticket = replica_a.create_ticket(Claims::anonymous()).await;
+        assert!(replica_a.tickets.read().await.is_empty());
+
+        assert!(replica_b.consume_ticket(&ticket).await.is_some());
+        // Single use across replicas
+        assert!(replica_a.consume_ticket(&ticket).await.is_none());
+        assert!(replica_b.consume_ticket(&ticket).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_shared_ticket_expired() {
+        let backend = Arc::new(FakeBackend::default());
+        let store = WsTicketStore::with_backend(backend.clone());
+        let entry = SharedTicket {
+            claims: Claims::anonymous(),
+            created_at: chrono::Utc::now().timestamp() - TICKET_TTL_SECS as i64 - 10,
+        };
+        backend.put("old-ticket", &entry).await.unwrap();
+        assert!(store.consume_ticket("old-ticket").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_shared_backend_failure_falls_back_to_memory() {
+        let backend = Arc::new(FakeBackend {
+            failing: true,
+            ..Default::default()
+        });
+        let store = WsTicketStore::with_backend(backend);
+
+        let ticket = store.create_ticket(Claims::anonymous()).await;
+        assert_eq!(store.tickets.read().await.len(), 1);
+        assert!(store.consume_ticket(&ticket).await.is_some());
+        assert!(store.consume_ticket(&ticket).await.is_none());
+    }
+
     // ========================================================================
     // ws_authenticate — cookie path tests (regression)
     // ========================================================================
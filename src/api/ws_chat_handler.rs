@@ -39,8 +39,12 @@ pub struct WsChatQuery {
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum WsChatClientMessage {
-    /// Send a new user message
-    UserMessage { content: String },
+    /// Send a new user message, optionally with files (see [`crate::chat::attachments`])
+    UserMessage {
+        content: String,
+        #[serde(default)]
+        attachments: Vec<crate::chat::types::ChatAttachment>,
+    },
     /// Interrupt the current operation
     Interrupt,
     /// Response to a permission request
@@ -716,12 +720,31 @@ async fn handle_ws_chat_loop(
                         match serde_json::from_str::<WsChatClientMessage>(text_str) {
                             Ok(client_msg) => {
                                 match client_msg {
-                                    WsChatClientMessage::UserMessage { content } => {
-                                        debug!(session_id = %session_id, "WS: Received user_message");
+                                    WsChatClientMessage::UserMessage { content, attachments } => {
+                                        debug!(session_id = %session_id, attachments = attachments.len(), "WS: Received user_message");
 
                                         // T4.3: Extract code entities and create DISCUSSED relations (non-blocking)
                                         spawn_entity_extraction(&state, &session_id, &content);
 
+                                        // Store attachments in the session scratch dir and reference
+                                        // them at the end of the message given to the CLI
+                                        let content = if attachments.is_empty() {
+                                            content
+                                        } else {
+                                            match crate::chat::attachments::store(&session_id, &attachments).await {
+                                                Ok(stored) => crate::chat::attachments::append_to_message(&content, &stored),
+                                                Err(e) => {
+                                                    warn!(session_id = %session_id, error = %e, "Rejected message attachments");
+                                                    let err = serde_json::json!({
+                                                        "type": "error",
+                                                        "message": format!("Invalid attachments: {:#}", e),
+                                                    });
+                                                    let _ = ws_sender.send(Message::Text(err.to_string().into())).await;
+                                                    continue;
+                                                }
+                                            }
+                                        };
+
                                         // 3-branch routing: local → remote (NATS RPC) → fallback resume
                                         // Track which branch was used so the error handler knows
                                         // whether a resume_session fallback makes sense.
@@ -1417,7 +1440,17 @@ mod tests {
         // user_message — the main one that triggers resume_session
         let msg: WsChatClientMessage =
             serde_json::from_str(r#"{"type":"user_message","content":"hello"}"#).unwrap();
-        assert!(matches!(msg, WsChatClientMessage::UserMessage { content } if content == "hello"));
+        assert!(
+            matches!(msg, WsChatClientMessage::UserMessage { content, attachments } if content == "hello" && attachments.is_empty())
+        );
+
+        let msg: WsChatClientMessage = serde_json::from_str(
+            r#"{"type":"user_message","content":"see log","attachments":[{"name":"app.log","mime_type":"text/plain","data":"aGk="}]}"#,
+        )
+        .unwrap();
+        assert!(
+            matches!(msg, WsChatClientMessage::UserMessage { attachments, .. } if attachments.len() == 1 && attachments[0].name == "app.log")
+        );
 
         // interrupt — should work even if dormant (no-op since no stream)
         let msg: WsChatClientMessage = serde_json::from_str(r#"{"type":"interrupt"}"#).unwrap();
@@ -0,0 +1,274 @@
+//! WebSocket handler for presence (`/ws/presence`)
+//!
+//! Each connection reports where its user is and receives the presence
+//! changes of everyone (see [`super::presence`]):
+//!
+//! - client → server: `{"type":"here","entity_type":"plan","entity_id":"…","mode":"editing","cursor":{…}}`
+//!   when opening or moving within a plan or note, `{"type":"away"}` when
+//!   leaving it
+//! - server → client: a `snapshot` of all participants on connect (and after
+//!   falling behind), then `update` / `left` events
+
+use super::handlers::OrchestratorState;
+use super::presence::{
+    refresh_agents, ParticipantKind, PresenceEntityType, PresenceEntry, PresenceMode,
+};
+use super::ws_auth::CookieAuthResult;
+use crate::auth::jwt::Claims;
+use axum::{
+    extract::{
+        ws::{Message, WebSocket},
+        Query, State, WebSocketUpgrade,
+    },
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use futures::{SinkExt, StreamExt};
+use serde::Deserialize;
+use tokio::sync::broadcast;
+use tokio::time::{interval, Duration};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Largest cursor accepted from a client, serialized
+const MAX_CURSOR_BYTES: usize = 2048;
+/// How often the agents of the active run are refreshed
+const AGENT_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Query parameters for the presence WebSocket
+#[derive(Debug, Deserialize, Default)]
+pub struct WsPresenceQuery {
+    /// One-time ticket for auth (fallback when cookies aren't sent on WS upgrade)
+    pub ticket: Option<String>,
+}
+
+/// Message from a presence client
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientPresenceMessage {
+    /// The user is on a plan or note
+    Here {
+        entity_type: PresenceEntityType,
+        entity_id: Uuid,
+        mode: PresenceMode,
+        #[serde(default)]
+        cursor: Option<serde_json::Value>,
+    },
+    /// The user left the plan or note
+    Away,
+}
+
+/// WebSocket upgrade handler for `/ws/presence`
+///
+/// Authentication: validates cookie or ticket BEFORE upgrade.
+pub async fn ws_presence(
+    ws: WebSocketUpgrade,
+    State(state): State<OrchestratorState>,
+    Query(query): Query<WsPresenceQuery>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, StatusCode> {
+    let neo4j = state.orchestrator.neo4j_arc();
+    let auth_result = super::ws_auth::ws_authenticate(
+        &headers,
+        &state.auth_config,
+        &neo4j,
+        query.ticket.as_deref(),
+        &state.ws_ticket_store,
+    )
+    .await;
+
+    match auth_result {
+        CookieAuthResult::Authenticated(claims) => {
+            info!(email = %claims.email, "WS /ws/presence: authenticated, upgrading");
+            Ok(ws.on_upgrade(move |socket| handle_presence_ws(socket, state, claims)))
+        }
+        CookieAuthResult::Invalid(reason) => {
+            warn!(reason = %reason, "WS /ws/presence: auth REJECTED (401)");
+            Err(StatusCode::UNAUTHORIZED)
+        }
+    }
+}
+
+/// Main WebSocket loop of a presence connection
+async fn handle_presence_ws(socket: WebSocket, state: OrchestratorState, claims: Claims) {
+    let mut socket = socket;
+    super::ws_auth::wait_ready_then_auth_ok(&mut socket, &claims).await;
+    let (mut ws_sender, mut ws_receiver) = socket.split();
+
+    let connection_id = Uuid::new_v4();
+    let presence = state.presence.clone();
+    let mut events_rx = presence.subscribe();
+
+    refresh_agents(&presence).await;
+    if !send_snapshot(&mut ws_sender, &state, connection_id).await {
+        return;
+    }
+
+    let mut ping_interval = interval(Duration::from_secs(30));
+    ping_interval.tick().await; // skip first tick
+    let mut agent_interval = interval(AGENT_REFRESH_INTERVAL);
+    agent_interval.tick().await; // skip first tick
+
+    loop {
+        tokio::select! {
+            result = events_rx.recv() => {
+                match result {
+                    Ok(event) => {
+                        if !send_json(&mut ws_sender, &event).await {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!("WS presence {}: lagged by {} events, resending snapshot", connection_id, n);
+                        if !send_snapshot(&mut ws_sender, &state, connection_id).await {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+
+            msg = ws_receiver.next() => {
+                match msg {
+                    Some(Ok(Message::Text(text))) => {
+                        match parse_client_message(&text, connection_id, &claims) {
+                            Ok(Some(entry)) => presence.set(entry).await,
+                            Ok(None) => presence.remove(connection_id).await,
+                            Err(e) => debug!("WS presence {}: ignored message: {}", connection_id, e),
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | None => {
+                        debug!("WS presence {}: client disconnected", connection_id);
+                        break;
+                    }
+                    Some(Ok(_)) => {} // Pong, binary
+                    Some(Err(e)) => {
+                        debug!("WS presence {}: error: {}", connection_id, e);
+                        break;
+                    }
+                }
+            }
+
+            _ = agent_interval.tick() => refresh_agents(&presence).await,
+
+            _ = ping_interval.tick() => {
+                if ws_sender.send(Message::Ping(vec![].into())).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    presence.remove(connection_id).await;
+    debug!(
+        "WS presence {} disconnected ({})",
+        connection_id, claims.email
+    );
+}
+
+/// Presence of the connection after a client message: `None` when the user
+/// went away.
+fn parse_client_message(
+    text: &str,
+    connection_id: Uuid,
+    claims: &Claims,
+) -> Result<Option<PresenceEntry>, String> {
+    let message: ClientPresenceMessage = serde_json::from_str(text).map_err(|e| e.to_string())?;
+    match message {
+        ClientPresenceMessage::Here {
+            entity_type,
+            entity_id,
+            mode,
+            cursor,
+        } => {
+            if let Some(ref cursor) = cursor {
+                if cursor.to_string().len() > MAX_CURSOR_BYTES {
+                    return Err(format!("cursor exceeds {} bytes", MAX_CURSOR_BYTES));
+                }
+            }
+            let name = if claims.name.is_empty() {
+                claims.email.clone()
+            } else {
+                claims.name.clone()
+            };
+            Ok(Some(PresenceEntry {
+                connection_id,
+                user_id: claims.sub.clone(),
+                name,
+                kind: ParticipantKind::User,
+                entity_type,
+                entity_id,
+                mode,
+                cursor,
+                updated_at: chrono::Utc::now(),
+            }))
+        }
+        ClientPresenceMessage::Away => Ok(None),
+    }
+}
+
+/// Send all participants, with the connection's own ID
+async fn send_snapshot(
+    ws_sender: &mut futures::stream::SplitSink<WebSocket, Message>,
+    state: &OrchestratorState,
+    connection_id: Uuid,
+) -> bool {
+    let snapshot = serde_json::json!({
+        "type": "snapshot",
+        "connection_id": connection_id,
+        "entries": state.presence.list().await,
+    });
+    send_json(ws_sender, &snapshot).await
+}
+
+/// Send a JSON message over the WebSocket. Returns false if send failed.
+async fn send_json<T: serde::Serialize>(
+    ws_sender: &mut futures::stream::SplitSink<WebSocket, Message>,
+    value: &T,
+) -> bool {
+    match serde_json::to_string(value) {
+        Ok(json) => ws_sender.send(Message::Text(json.into())).await.is_ok(),
+        Err(e) => {
+            warn!("Failed to serialize presence message: {}", e);
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_client_message() {
+        let claims = Claims::anonymous();
+        let connection_id = Uuid::new_v4();
+        let plan_id = Uuid::new_v4();
+        let here = format!(
+            r#"{{"type":"here","entity_type":"plan","entity_id":"{}","mode":"editing","cursor":{{"field":"title"}}}}"#,
+            plan_id
+        );
+        let entry = parse_client_message(&here, connection_id, &claims)
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.connection_id, connection_id);
+        assert_eq!(entry.entity_id, plan_id);
+        assert_eq!(entry.mode, PresenceMode::Editing);
+        assert_eq!(entry.kind, ParticipantKind::User);
+        assert_eq!(entry.name, "Anonymous");
+
+        assert!(
+            parse_client_message(r#"{"type":"away"}"#, connection_id, &claims)
+                .unwrap()
+                .is_none()
+        );
+        assert!(parse_client_message("garbage", connection_id, &claims).is_err());
+
+        let huge = format!(
+            r#"{{"type":"here","entity_type":"note","entity_id":"{}","mode":"viewing","cursor":"{}"}}"#,
+            plan_id,
+            "x".repeat(MAX_CURSOR_BYTES)
+        );
+        assert!(parse_client_message(&huge, connection_id, &claims).is_err());
+    }
+}
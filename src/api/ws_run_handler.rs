@@ -9,6 +9,7 @@
 //! automatically on each RunnerEvent::TaskStarted.
 
 use super::handlers::OrchestratorState;
+use super::validation::ValidPath;
 use super::ws_auth::CookieAuthResult;
 use crate::auth::jwt::Claims;
 use crate::chat::types::ChatEvent;
@@ -16,7 +17,7 @@ use crate::runner::{RunnerEvent, RUNNER_STATE};
 use axum::{
     extract::{
         ws::{Message, WebSocket},
-        Path, Query, State, WebSocketUpgrade,
+        Query, State, WebSocketUpgrade,
     },
     http::{HeaderMap, StatusCode},
     response::IntoResponse,
@@ -42,7 +43,7 @@ pub struct WsRunQuery {
 pub async fn ws_run(
     ws: WebSocketUpgrade,
     State(state): State<OrchestratorState>,
-    Path(run_id): Path<Uuid>,
+    ValidPath(run_id): ValidPath<Uuid>,
     Query(query): Query<WsRunQuery>,
     headers: HeaderMap,
 ) -> Result<impl IntoResponse, StatusCode> {
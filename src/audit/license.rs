@@ -0,0 +1,770 @@
+//! License scanning and workspace license policies.
+//!
+//! Package licenses come from the lockfile when it records them (npm v2/v3),
+//! otherwise from a local copy of the package's own manifest:
+//! - npm: `node_modules/<name>/package.json` (`license`, legacy `licenses`)
+//! - crates.io: the `license` of `Cargo.toml` under the project's `vendor/`
+//!   or the cargo registry cache (`$CARGO_HOME/registry/src/*/<name>-<version>`)
+//! - Go: the LICENSE file of the module cache copy
+//!   (`$GOMODCACHE/<module>@v<version>`), identified from its text
+//!
+//! Packages without a local copy keep `license: None`.
+//!
+//! A workspace policy (stored under `metadata.license_policy`) lists allowed
+//! and denied SPDX identifiers; [`workspace_license_report`] evaluates every
+//! package of the workspace's projects against it.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::neo4j::models::{PackageEcosystem, PackageNode, WorkspaceNode};
+use crate::neo4j::GraphStore;
+
+/// License files looked for next to a manifest, in order.
+const LICENSE_FILES: &[&str] = &[
+    "LICENSE",
+    "LICENSE.md",
+    "LICENSE.txt",
+    "LICENCE",
+    "COPYING",
+    "LICENSE-MIT",
+];
+
+// ============================================================================
+// License resolution
+// ============================================================================
+
+/// Directories searched for local package copies.
+#[derive(Debug, Clone, Default)]
+pub struct LicenseSources {
+    pub project_root: PathBuf,
+    /// `$CARGO_HOME/registry/src/<index>` directories
+    pub cargo_registry_src: Vec<PathBuf>,
+    /// Go module cache (`$GOMODCACHE`)
+    pub go_mod_cache: Option<PathBuf>,
+}
+
+impl LicenseSources {
+    /// Sources for a project: its own tree plus the user's cargo and Go caches.
+    pub fn for_project(root: &Path) -> Self {
+        let home = dirs::home_dir();
+
+        let cargo_home = std::env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home.as_ref().map(|h| h.join(".cargo")));
+        let cargo_registry_src = cargo_home
+            .and_then(|c| std::fs::read_dir(c.join("registry").join("src")).ok())
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.path())
+                    .filter(|p| p.is_dir())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let go_mod_cache = std::env::var_os("GOMODCACHE")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("GOPATH").map(|g| PathBuf::from(g).join("pkg/mod")))
+            .or_else(|| home.as_ref().map(|h| h.join("go/pkg/mod")))
+            .filter(|p| p.is_dir());
+
+        Self {
+            project_root: root.to_path_buf(),
+            cargo_registry_src,
+            go_mod_cache,
+        }
+    }
+}
+
+/// Fill in the license of packages whose lockfile did not record one.
+pub fn resolve_licenses(root: &Path, packages: &mut [PackageNode]) {
+    resolve_licenses_with(&LicenseSources::for_project(root), packages);
+}
+
+pub fn resolve_licenses_with(sources: &LicenseSources, packages: &mut [PackageNode]) {
+    for pkg in packages.iter_mut().filter(|p| p.license.is_none()) {
+        pkg.license = match pkg.ecosystem {
+            PackageEcosystem::Npm => npm_license(sources, pkg),
+            PackageEcosystem::CratesIo => cargo_license(sources, pkg),
+            PackageEcosystem::Go => go_license(sources, pkg),
+        };
+    }
+}
+
+fn npm_license(sources: &LicenseSources, pkg: &PackageNode) -> Option<String> {
+    let manifest = sources
+        .project_root
+        .join("node_modules")
+        .join(&pkg.name)
+        .join("package.json");
+    let json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(manifest).ok()?).ok()?;
+    // A hoisted copy of another version says nothing about this one
+    if json.get("version").and_then(|v| v.as_str()) != Some(pkg.version.as_str()) {
+        return None;
+    }
+    match json.get("license") {
+        Some(serde_json::Value::String(s)) => Some(s.clone()),
+        Some(obj) => obj.get("type").and_then(|t| t.as_str()).map(str::to_string),
+        None => {
+            let types: Vec<&str> = json
+                .get("licenses")?
+                .as_array()?
+                .iter()
+                .filter_map(|l| l.get("type").and_then(|t| t.as_str()))
+                .collect();
+            (!types.is_empty()).then(|| types.join(" OR "))
+        }
+    }
+}
+
+fn cargo_license(sources: &LicenseSources, pkg: &PackageNode) -> Option<String> {
+    let dir_name = format!("{}-{}", pkg.name, pkg.version);
+    let vendor = sources.project_root.join("vendor");
+    let candidates = [vendor.join(&dir_name), vendor.join(&pkg.name)]
+        .into_iter()
+        .chain(sources.cargo_registry_src.iter().map(|r| r.join(&dir_name)));
+
+    for dir in candidates {
+        let Ok(manifest) = std::fs::read_to_string(dir.join("Cargo.toml")) else {
+            continue;
+        };
+        if let Some(license) = cargo_manifest_license(&manifest) {
+            return Some(license);
+        }
+        // `license-file` crates: identify the bundled text
+        if let Some(license) = license_file_in(&dir) {
+            return Some(license);
+        }
+    }
+    None
+}
+
+fn go_license(sources: &LicenseSources, pkg: &PackageNode) -> Option<String> {
+    let cache = sources.go_mod_cache.as_ref()?;
+    let module = escape_go_module_path(&pkg.name);
+    [
+        format!("{}@v{}", module, pkg.version),
+        format!("{}@v{}+incompatible", module, pkg.version),
+    ]
+    .iter()
+    .find_map(|dir| license_file_in(&cache.join(dir)))
+}
+
+/// Go module cache escaping: uppercase letters become `!` + lowercase.
+fn escape_go_module_path(module: &str) -> String {
+    let mut out = String::with_capacity(module.len());
+    for c in module.chars() {
+        if c.is_ascii_uppercase() {
+            out.push('!');
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// `license = "..."` of the `[package]` table of a Cargo.toml.
+fn cargo_manifest_license(manifest: &str) -> Option<String> {
+    let mut in_package = false;
+    for line in manifest.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+            continue;
+        }
+        if !in_package {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if key.trim() == "license" {
+            let value = value.trim().trim_matches('"').trim();
+            return (!value.is_empty()).then(|| value.to_string());
+        }
+    }
+    None
+}
+
+fn license_file_in(dir: &Path) -> Option<String> {
+    LICENSE_FILES.iter().find_map(|name| {
+        let text = std::fs::read_to_string(dir.join(name)).ok()?;
+        detect_license_text(&text).map(str::to_string)
+    })
+}
+
+/// Identify a license from the text of a LICENSE file (common licenses only).
+pub fn detect_license_text(text: &str) -> Option<&'static str> {
+    let t = text.to_lowercase();
+    let has = |s: &str| t.contains(s);
+
+    if has("gnu affero general public license") {
+        return Some("AGPL-3.0");
+    }
+    if has("gnu lesser general public license") {
+        return Some(if has("version 3") {
+            "LGPL-3.0"
+        } else {
+            "LGPL-2.1"
+        });
+    }
+    if has("gnu general public license") {
+        return Some(if has("version 3") {
+            "GPL-3.0"
+        } else {
+            "GPL-2.0"
+        });
+    }
+    if has("mozilla public license") && has("2.0") {
+        return Some("MPL-2.0");
+    }
+    if has("apache license") && has("version 2.0") {
+        return Some("Apache-2.0");
+    }
+    if has("permission is hereby granted, free of charge") {
+        return Some("MIT");
+    }
+    if has("permission to use, copy, modify, and/or distribute this software") {
+        return Some("ISC");
+    }
+    if has("redistribution and use in source and binary forms") {
+        return Some(
+            if has("neither the name") || has("names of its contributors") {
+                "BSD-3-Clause"
+            } else {
+                "BSD-2-Clause"
+            },
+        );
+    }
+    if has("this is free and unencumbered software released into the public domain") {
+        return Some("Unlicense");
+    }
+    None
+}
+
+// ============================================================================
+// Policy
+// ============================================================================
+
+/// Allowed/denied licenses of a workspace, stored under
+/// `metadata.license_policy`.
+///
+/// Entries are SPDX identifiers, compared case-insensitively; a trailing `*`
+/// matches a prefix (`GPL-*`). With an empty `allowed` list every license not
+/// denied is acceptable.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LicensePolicy {
+    #[serde(default)]
+    pub allowed: Vec<String>,
+    #[serde(default)]
+    pub denied: Vec<String>,
+    /// Also report packages whose license could not be determined
+    #[serde(default)]
+    pub flag_unknown: bool,
+}
+
+/// Why a package breaks the license policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LicenseViolationKind {
+    /// Every alternative of the license expression is denied
+    Denied,
+    /// The license is not on the allow list
+    NotAllowed,
+    /// No license found (only with `flag_unknown`)
+    Unknown,
+}
+
+impl LicensePolicy {
+    /// Key of the policy in the workspace metadata.
+    pub const METADATA_KEY: &'static str = "license_policy";
+
+    /// Policy stored in workspace metadata (empty policy when absent or invalid).
+    pub fn from_metadata(metadata: &serde_json::Value) -> Self {
+        metadata
+            .get(Self::METADATA_KEY)
+            .and_then(|p| serde_json::from_value(p.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.allowed.is_empty() && self.denied.is_empty() && !self.flag_unknown
+    }
+
+    /// Reject blank entries and entries both allowed and denied.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(blank) = self
+            .allowed
+            .iter()
+            .chain(&self.denied)
+            .find(|e| e.trim().is_empty())
+        {
+            return Err(format!("Invalid empty license entry '{}'", blank));
+        }
+        if let Some(both) = self
+            .allowed
+            .iter()
+            .find(|a| self.denied.iter().any(|d| d.eq_ignore_ascii_case(a)))
+        {
+            return Err(format!("License '{}' is both allowed and denied", both));
+        }
+        Ok(())
+    }
+
+    /// Evaluate a package license. `None` means compliant.
+    pub fn evaluate(&self, license: Option<&str>) -> Option<LicenseViolationKind> {
+        let Some(license) = license.map(str::trim).filter(|l| !l.is_empty()) else {
+            return self.flag_unknown.then_some(LicenseViolationKind::Unknown);
+        };
+        let expr = parse_expression(license);
+        match self.badness(&expr) {
+            0 => None,
+            1 => Some(LicenseViolationKind::NotAllowed),
+            _ => Some(LicenseViolationKind::Denied),
+        }
+    }
+
+    /// 0 = acceptable, 1 = not allowed, 2 = denied.
+    /// `OR` takes the best alternative, `AND` the worst requirement.
+    fn badness(&self, expr: &LicenseExpr) -> u8 {
+        match expr {
+            LicenseExpr::Id(id) => {
+                if self.denied.iter().any(|p| pattern_matches(p, id)) {
+                    2
+                } else if !self.allowed.is_empty()
+                    && !self.allowed.iter().any(|p| pattern_matches(p, id))
+                {
+                    1
+                } else {
+                    0
+                }
+            }
+            LicenseExpr::Or(alts) => alts.iter().map(|e| self.badness(e)).min().unwrap_or(0),
+            LicenseExpr::And(reqs) => reqs.iter().map(|e| self.badness(e)).max().unwrap_or(0),
+        }
+    }
+}
+
+fn pattern_matches(pattern: &str, id: &str) -> bool {
+    let pattern = pattern.trim().to_lowercase();
+    let id = id.to_lowercase();
+    match pattern.strip_suffix('*') {
+        Some(prefix) => id.starts_with(prefix),
+        None => {
+            // `GPL-3.0` also covers `GPL-3.0-only` / `GPL-3.0-or-later`
+            id == pattern
+                || id
+                    .strip_prefix(&pattern)
+                    .is_some_and(|rest| rest == "-only" || rest == "-or-later" || rest == "+")
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum LicenseExpr {
+    Id(String),
+    Or(Vec<LicenseExpr>),
+    And(Vec<LicenseExpr>),
+}
+
+/// Parse an SPDX expression (`MIT OR (Apache-2.0 AND BSD-3-Clause)`).
+/// The legacy `/` separator (`MIT/Apache-2.0`) means OR and `WITH`
+/// exceptions are ignored. Malformed input degrades to a single identifier.
+fn parse_expression(license: &str) -> LicenseExpr {
+    let spaced = license
+        .replace('(', " ( ")
+        .replace(')', " ) ")
+        .replace('/', " OR ");
+    let tokens: Vec<&str> = spaced.split_whitespace().collect();
+    let mut pos = 0;
+    match parse_or(&tokens, &mut pos) {
+        Some(expr) if pos == tokens.len() => expr,
+        _ => LicenseExpr::Id(license.to_string()),
+    }
+}
+
+fn parse_or(tokens: &[&str], pos: &mut usize) -> Option<LicenseExpr> {
+    let mut alts = vec![parse_and(tokens, pos)?];
+    while tokens
+        .get(*pos)
+        .is_some_and(|t| t.eq_ignore_ascii_case("or"))
+    {
+        *pos += 1;
+        alts.push(parse_and(tokens, pos)?);
+    }
+    Some(if alts.len() == 1 {
+        alts.remove(0)
+    } else {
+        LicenseExpr::Or(alts)
+    })
+}
+
+fn parse_and(tokens: &[&str], pos: &mut usize) -> Option<LicenseExpr> {
+    let mut reqs = vec![parse_atom(tokens, pos)?];
+    while tokens
+        .get(*pos)
+        .is_some_and(|t| t.eq_ignore_ascii_case("and"))
+    {
+        *pos += 1;
+        reqs.push(parse_atom(tokens, pos)?);
+    }
+    Some(if reqs.len() == 1 {
+        reqs.remove(0)
+    } else {
+        LicenseExpr::And(reqs)
+    })
+}
+
+fn parse_atom(tokens: &[&str], pos: &mut usize) -> Option<LicenseExpr> {
+    let token = *tokens.get(*pos)?;
+    *pos += 1;
+    if token == "(" {
+        let expr = parse_or(tokens, pos)?;
+        if tokens.get(*pos) != Some(&")") {
+            return None;
+        }
+        *pos += 1;
+        return Some(expr);
+    }
+    if token == ")" || ["and", "or", "with"].contains(&token.to_lowercase().as_str()) {
+        return None;
+    }
+    if tokens
+        .get(*pos)
+        .is_some_and(|t| t.eq_ignore_ascii_case("with"))
+    {
+        *pos += 2;
+    }
+    Some(LicenseExpr::Id(token.to_string()))
+}
+
+// ============================================================================
+// Workspace report
+// ============================================================================
+
+/// A package of a workspace project breaking the license policy.
+#[derive(Debug, Clone, Serialize)]
+pub struct LicenseViolation {
+    pub project_id: Uuid,
+    pub project_slug: String,
+    #[serde(flatten)]
+    pub package: PackageNode,
+    pub kind: LicenseViolationKind,
+}
+
+/// License policy evaluation over every project of a workspace.
+#[derive(Debug, Clone, Serialize)]
+pub struct LicenseReport {
+    pub policy: LicensePolicy,
+    pub projects_scanned: usize,
+    pub packages_scanned: usize,
+    pub violations: Vec<LicenseViolation>,
+    /// Package count per license expression (`unknown` when missing)
+    pub licenses: BTreeMap<String, usize>,
+}
+
+/// Evaluate the workspace's license policy against the packages recorded at
+/// the last sync of each of its projects.
+pub async fn workspace_license_report(
+    neo4j: &dyn GraphStore,
+    workspace: &WorkspaceNode,
+) -> anyhow::Result<LicenseReport> {
+    let policy = LicensePolicy::from_metadata(&workspace.metadata);
+    let projects = neo4j.list_workspace_projects(workspace.id).await?;
+
+    let mut report = LicenseReport {
+        policy: policy.clone(),
+        projects_scanned: projects.len(),
+        packages_scanned: 0,
+        violations: Vec::new(),
+        licenses: BTreeMap::new(),
+    };
+
+    for project in &projects {
+        for package in neo4j.list_project_packages(project.id).await? {
+            report.packages_scanned += 1;
+            *report
+                .licenses
+                .entry(
+                    package
+                        .license
+                        .clone()
+                        .unwrap_or_else(|| "unknown".to_string()),
+                )
+                .or_default() += 1;
+            if let Some(kind) = policy.evaluate(package.license.as_deref()) {
+                report.violations.push(LicenseViolation {
+                    project_id: project.id,
+                    project_slug: project.slug.clone(),
+                    package,
+                    kind,
+                });
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(allowed: &[&str], denied: &[&str]) -> LicensePolicy {
+        LicensePolicy {
+            allowed: allowed.iter().map(|s| s.to_string()).collect(),
+            denied: denied.iter().map(|s| s.to_string()).collect(),
+            flag_unknown: false,
+        }
+    }
+
+    fn package(ecosystem: PackageEcosystem, name: &str, version: &str) -> PackageNode {
+        PackageNode {
+            ecosystem,
+            name: name.to_string(),
+            version: version.to_string(),
+            lockfile: "lock".to_string(),
+            license: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_expression() {
+        assert_eq!(parse_expression("MIT"), LicenseExpr::Id("MIT".into()));
+        assert_eq!(
+            parse_expression("MIT/Apache-2.0"),
+            LicenseExpr::Or(vec![
+                LicenseExpr::Id("MIT".into()),
+                LicenseExpr::Id("Apache-2.0".into())
+            ])
+        );
+        assert_eq!(
+            parse_expression("(MIT OR Apache-2.0) AND Unicode-DFS-2016"),
+            LicenseExpr::And(vec![
+                LicenseExpr::Or(vec![
+                    LicenseExpr::Id("MIT".into()),
+                    LicenseExpr::Id("Apache-2.0".into())
+                ]),
+                LicenseExpr::Id("Unicode-DFS-2016".into())
+            ])
+        );
+        assert_eq!(
+            parse_expression("Apache-2.0 WITH LLVM-exception"),
+            LicenseExpr::Id("Apache-2.0".into())
+        );
+        // Malformed: kept whole
+        assert_eq!(parse_expression("MIT OR"), LicenseExpr::Id("MIT OR".into()));
+    }
+
+    #[test]
+    fn test_policy_evaluate() {
+        let p = policy(&["MIT", "Apache-2.0", "BSD-*"], &["GPL-3.0", "AGPL-*"]);
+        assert_eq!(p.evaluate(Some("MIT")), None);
+        assert_eq!(p.evaluate(Some("bsd-3-clause")), None);
+        assert_eq!(p.evaluate(Some("MIT OR GPL-3.0")), None);
+        assert_eq!(
+            p.evaluate(Some("GPL-3.0-or-later")),
+            Some(LicenseViolationKind::Denied)
+        );
+        assert_eq!(
+            p.evaluate(Some("MIT AND AGPL-3.0")),
+            Some(LicenseViolationKind::Denied)
+        );
+        assert_eq!(
+            p.evaluate(Some("MPL-2.0")),
+            Some(LicenseViolationKind::NotAllowed)
+        );
+        assert_eq!(p.evaluate(None), None);
+
+        let deny_only = LicensePolicy {
+            flag_unknown: true,
+            ..policy(&[], &["GPL-*"])
+        };
+        assert_eq!(deny_only.evaluate(Some("MPL-2.0")), None);
+        assert_eq!(
+            deny_only.evaluate(Some("GPL-2.0")),
+            Some(LicenseViolationKind::Denied)
+        );
+        assert_eq!(
+            deny_only.evaluate(Some("  ")),
+            Some(LicenseViolationKind::Unknown)
+        );
+    }
+
+    #[test]
+    fn test_policy_validate_and_metadata() {
+        assert!(policy(&["MIT"], &["GPL-3.0"]).validate().is_ok());
+        assert!(policy(&["MIT"], &["mit"]).validate().is_err());
+        assert!(policy(&[" "], &[]).validate().is_err());
+
+        let metadata = serde_json::json!({
+            "license_policy": {"denied": ["GPL-3.0"], "flag_unknown": true},
+            "other": 1
+        });
+        let p = LicensePolicy::from_metadata(&metadata);
+        assert_eq!(p.denied, vec!["GPL-3.0"]);
+        assert!(p.flag_unknown);
+        assert!(LicensePolicy::from_metadata(&serde_json::json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_detect_license_text() {
+        assert_eq!(
+            detect_license_text(
+                "MIT License\n\nPermission is hereby granted, free of charge, to any person"
+            ),
+            Some("MIT")
+        );
+        assert_eq!(
+            detect_license_text("Apache License\nVersion 2.0, January 2004"),
+            Some("Apache-2.0")
+        );
+        assert_eq!(
+            detect_license_text(
+                "Redistribution and use in source and binary forms ... Neither the name of Google"
+            ),
+            Some("BSD-3-Clause")
+        );
+        assert_eq!(
+            detect_license_text("GNU LESSER GENERAL PUBLIC LICENSE\nVersion 3, 29 June 2007"),
+            Some("LGPL-3.0")
+        );
+        assert_eq!(detect_license_text("All rights reserved."), None);
+    }
+
+    #[test]
+    fn test_resolve_licenses_from_local_copies() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("project");
+        let registry = dir.path().join("registry/index.crates.io-xxxx");
+        let gocache = dir.path().join("gomod");
+
+        // npm: matching version only
+        let npm_dir = root.join("node_modules/left-pad");
+        std::fs::create_dir_all(&npm_dir).unwrap();
+        std::fs::write(
+            npm_dir.join("package.json"),
+            r#"{"name": "left-pad", "version": "1.3.0", "license": "WTFPL"}"#,
+        )
+        .unwrap();
+
+        // crates.io: license field, and license-file fallback
+        let serde_dir = registry.join("serde-1.0.0");
+        std::fs::create_dir_all(&serde_dir).unwrap();
+        std::fs::write(
+            serde_dir.join("Cargo.toml"),
+            "[package]\nname = \"serde\"\nlicense = \"MIT OR Apache-2.0\"\n\n[dependencies]\nlicense = \"x\"\n",
+        )
+        .unwrap();
+        let ring_dir = registry.join("ring-0.17.0");
+        std::fs::create_dir_all(&ring_dir).unwrap();
+        std::fs::write(
+            ring_dir.join("Cargo.toml"),
+            "[package]\nname = \"ring\"\nlicense-file = \"LICENSE\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            ring_dir.join("LICENSE"),
+            "Permission to use, copy, modify, and/or distribute this software for any purpose",
+        )
+        .unwrap();
+
+        // Go: escaped module path
+        let go_dir = gocache.join("github.com/!burnt!sushi/toml@v1.2.0");
+        std::fs::create_dir_all(&go_dir).unwrap();
+        std::fs::write(
+            go_dir.join("LICENSE"),
+            "The MIT License\nPermission is hereby granted, free of charge",
+        )
+        .unwrap();
+
+        let sources = LicenseSources {
+            project_root: root,
+            cargo_registry_src: vec![registry],
+            go_mod_cache: Some(gocache),
+        };
+        let mut packages = vec![
+            package(PackageEcosystem::Npm, "left-pad", "1.3.0"),
+            package(PackageEcosystem::Npm, "left-pad", "1.0.0"),
+            package(PackageEcosystem::CratesIo, "serde", "1.0.0"),
+            package(PackageEcosystem::CratesIo, "ring", "0.17.0"),
+            package(PackageEcosystem::Go, "github.com/BurntSushi/toml", "1.2.0"),
+            PackageNode {
+                license: Some("ISC".to_string()),
+                ..package(PackageEcosystem::Npm, "kept", "1.0.0")
+            },
+        ];
+        resolve_licenses_with(&sources, &mut packages);
+
+        let licenses: Vec<Option<&str>> = packages.iter().map(|p| p.license.as_deref()).collect();
+        assert_eq!(
+            licenses,
+            vec![
+                Some("WTFPL"),
+                None,
+                Some("MIT OR Apache-2.0"),
+                Some("ISC"),
+                Some("MIT"),
+                Some("ISC"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_workspace_license_report() {
+        use crate::neo4j::mock::MockGraphStore;
+
+        let store = MockGraphStore::new();
+        let mut workspace = crate::test_helpers::test_workspace();
+        workspace.metadata = serde_json::json!({
+            "license_policy": {"denied": ["GPL-*"], "flag_unknown": true}
+        });
+        store.create_workspace(&workspace).await.unwrap();
+        let project = crate::test_helpers::test_project();
+        store.create_project(&project).await.unwrap();
+        store
+            .add_project_to_workspace(workspace.id, project.id)
+            .await
+            .unwrap();
+
+        let with_license = |name: &str, license: Option<&str>| PackageNode {
+            license: license.map(str::to_string),
+            ..package(PackageEcosystem::CratesIo, name, "1.0.0")
+        };
+        store
+            .replace_project_packages(
+                project.id,
+                &[
+                    with_license("a", Some("MIT")),
+                    with_license("b", Some("GPL-3.0-only")),
+                    with_license("c", None),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let report = workspace_license_report(&store, &workspace).await.unwrap();
+        assert_eq!(report.projects_scanned, 1);
+        assert_eq!(report.packages_scanned, 3);
+        let kinds: Vec<(&str, LicenseViolationKind)> = report
+            .violations
+            .iter()
+            .map(|v| (v.package.name.as_str(), v.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("b", LicenseViolationKind::Denied),
+                ("c", LicenseViolationKind::Unknown)
+            ]
+        );
+        assert_eq!(report.licenses.get("unknown"), Some(&1));
+        assert_eq!(report.violations[0].project_slug, project.slug);
+    }
+}
@@ -0,0 +1,381 @@
+//! Lockfile parsing — the exact third-party versions a project builds with.
+//!
+//! Supported lockfiles:
+//! - `Cargo.lock`: crates.io packages only (workspace members have no
+//!   `source`, git and private-registry crates are not in OSV)
+//! - `package-lock.json`: v2/v3 `packages` map and the v1 `dependencies` tree
+//! - `go.sum`: modules whose content was downloaded (`/go.mod`-only entries
+//!   are skipped)
+//!
+//! Only npm lockfiles (v2/v3) carry licenses; the others are completed from
+//! the packages' own manifests by [`super::license::resolve_licenses`].
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::neo4j::models::{PackageEcosystem, PackageNode};
+
+/// Lockfile names recognized by [`parse_lockfile`].
+pub const LOCKFILE_NAMES: &[&str] = &["Cargo.lock", "package-lock.json", "go.sum"];
+
+/// Maximum directory depth searched for lockfiles (monorepo sub-projects).
+const MAX_DEPTH: usize = 4;
+
+/// Directories never searched (vendored or generated dependencies).
+const SKIP_DIRS: &[&str] = &["node_modules", "target", "vendor", ".git", "dist", "build"];
+
+/// Find the lockfiles of a project, shallowest first.
+pub fn discover_lockfiles(root: &Path) -> Vec<PathBuf> {
+    let mut found: Vec<PathBuf> = walkdir::WalkDir::new(root)
+        .max_depth(MAX_DEPTH)
+        .into_iter()
+        .filter_entry(|e| {
+            e.depth() == 0
+                || !e.file_type().is_dir()
+                || !SKIP_DIRS.contains(&e.file_name().to_string_lossy().as_ref())
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            e.file_type().is_file()
+                && LOCKFILE_NAMES.contains(&e.file_name().to_string_lossy().as_ref())
+        })
+        .map(|e| e.into_path())
+        .collect();
+    found.sort_by_key(|p| (p.components().count(), p.clone()));
+    found
+}
+
+/// Read every lockfile of a project and resolve package licenses.
+/// A package version locked by several lockfiles is reported once, for the
+/// shallowest one.
+pub fn read_project_packages(root: &Path) -> Vec<PackageNode> {
+    let mut seen = HashSet::new();
+    let mut packages = Vec::new();
+
+    for path in discover_lockfiles(root) {
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let rel = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .to_string();
+        for pkg in parse_lockfile(&rel, &content) {
+            if seen.insert((pkg.ecosystem, pkg.name.clone(), pkg.version.clone())) {
+                packages.push(pkg);
+            }
+        }
+    }
+
+    super::license::resolve_licenses(root, &mut packages);
+    packages
+}
+
+/// Parse a lockfile, dispatching on its file name. `lockfile` is recorded on
+/// every returned package. Unknown or malformed files yield no packages.
+pub fn parse_lockfile(lockfile: &str, content: &str) -> Vec<PackageNode> {
+    let file_name = Path::new(lockfile)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let entries = match file_name.as_str() {
+        "Cargo.lock" => parse_cargo_lock(content),
+        "package-lock.json" => parse_package_lock(content),
+        "go.sum" => parse_go_sum(content),
+        _ => Vec::new(),
+    };
+
+    let mut seen = HashSet::new();
+    entries
+        .into_iter()
+        .filter(|(eco, name, version, _)| seen.insert((*eco, name.clone(), version.clone())))
+        .map(|(ecosystem, name, version, license)| PackageNode {
+            ecosystem,
+            name,
+            version,
+            lockfile: lockfile.to_string(),
+            license,
+        })
+        .collect()
+}
+
+/// (ecosystem, name, version, license)
+type Entry = (PackageEcosystem, String, String, Option<String>);
+
+/// `[[package]]` tables of a Cargo.lock. Only the simple `key = "value"`
+/// lines are needed, so no TOML parser is involved.
+fn parse_cargo_lock(content: &str) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    let mut current: Option<(Option<String>, Option<String>, Option<String>)> = None;
+
+    let mut flush = |pkg: Option<(Option<String>, Option<String>, Option<String>)>| {
+        if let Some((Some(name), Some(version), Some(source))) = pkg {
+            let registry = source.starts_with("registry+") || source.starts_with("sparse+");
+            if registry && source.contains("crates.io") {
+                entries.push((PackageEcosystem::CratesIo, name, version, None));
+            }
+        }
+    };
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            flush(current.take());
+            if line == "[[package]]" {
+                current = Some((None, None, None));
+            }
+            continue;
+        }
+        let Some(pkg) = current.as_mut() else {
+            continue;
+        };
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        let Some(value) = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .map(str::to_string)
+        else {
+            continue;
+        };
+        match key.trim() {
+            "name" => pkg.0 = Some(value),
+            "version" => pkg.1 = Some(value),
+            "source" => pkg.2 = Some(value),
+            _ => {}
+        }
+    }
+    flush(current.take());
+
+    entries
+}
+
+fn parse_package_lock(content: &str) -> Vec<Entry> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    let mut entries = Vec::new();
+
+    // lockfileVersion 2/3: flat map keyed by install path
+    if let Some(packages) = json.get("packages").and_then(|p| p.as_object()) {
+        for (path, pkg) in packages {
+            // "" is the root project; paths outside node_modules are workspace members
+            let Some(idx) = path.rfind("node_modules/") else {
+                continue;
+            };
+            if pkg.get("link").and_then(|l| l.as_bool()).unwrap_or(false) {
+                continue;
+            }
+            let name = pkg
+                .get("name")
+                .and_then(|n| n.as_str())
+                .unwrap_or(&path[idx + "node_modules/".len()..]);
+            let license = pkg.get("license").and_then(|l| l.as_str());
+            if let Some(version) = pkg.get("version").and_then(|v| v.as_str()) {
+                push_npm(&mut entries, name, version, license);
+            }
+        }
+        return entries;
+    }
+
+    // lockfileVersion 1: nested dependency tree
+    fn walk(deps: &serde_json::Map<String, serde_json::Value>, entries: &mut Vec<Entry>) {
+        for (name, dep) in deps {
+            if let Some(version) = dep.get("version").and_then(|v| v.as_str()) {
+                push_npm(entries, name, version, None);
+            }
+            if let Some(nested) = dep.get("dependencies").and_then(|d| d.as_object()) {
+                walk(nested, entries);
+            }
+        }
+    }
+    if let Some(deps) = json.get("dependencies").and_then(|d| d.as_object()) {
+        walk(deps, &mut entries);
+    }
+
+    entries
+}
+
+/// Keep registry versions only (`file:`, `git+…` and tarball URLs are skipped).
+fn push_npm(entries: &mut Vec<Entry>, name: &str, version: &str, license: Option<&str>) {
+    if !name.is_empty() && version.starts_with(|c: char| c.is_ascii_digit()) {
+        entries.push((
+            PackageEcosystem::Npm,
+            name.to_string(),
+            version.to_string(),
+            license.map(str::to_string),
+        ));
+    }
+}
+
+fn parse_go_sum(content: &str) -> Vec<Entry> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let module = parts.next()?;
+            let version = parts.next()?;
+            if version.ends_with("/go.mod") {
+                return None;
+            }
+            // OSV records Go versions without the `v` prefix
+            let version = version.strip_prefix('v').unwrap_or(version);
+            let version = version.strip_suffix("+incompatible").unwrap_or(version);
+            Some((
+                PackageEcosystem::Go,
+                module.to_string(),
+                version.to_string(),
+                None,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cargo_lock() {
+        let content = r#"
+version = 3
+
+[[package]]
+name = "my-app"
+version = "0.1.0"
+dependencies = [
+ "serde",
+]
+
+[[package]]
+name = "serde"
+version = "1.0.100"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "abc"
+
+[[package]]
+name = "forked"
+version = "0.2.0"
+source = "git+https://github.com/me/forked#deadbeef"
+
+[[package]]
+name = "smallvec"
+version = "1.6.0"
+source = "sparse+https://index.crates.io/"
+
+[metadata]
+"#;
+        let pkgs = parse_lockfile("Cargo.lock", content);
+        let names: Vec<_> = pkgs
+            .iter()
+            .map(|p| (p.name.as_str(), p.version.as_str()))
+            .collect();
+        assert_eq!(names, vec![("serde", "1.0.100"), ("smallvec", "1.6.0")]);
+        assert!(pkgs
+            .iter()
+            .all(|p| p.ecosystem == PackageEcosystem::CratesIo));
+        assert_eq!(pkgs[0].lockfile, "Cargo.lock");
+    }
+
+    #[test]
+    fn test_parse_package_lock_v3() {
+        let content = r#"{
+            "name": "web",
+            "lockfileVersion": 3,
+            "packages": {
+                "": {"name": "web", "version": "1.0.0"},
+                "node_modules/lodash": {"version": "4.17.20", "license": "MIT"},
+                "node_modules/@babel/core": {"version": "7.0.0"},
+                "node_modules/a/node_modules/lodash": {"version": "3.10.1"},
+                "node_modules/local": {"resolved": "packages/local", "link": true},
+                "node_modules/tarball": {"version": "file:../tarball.tgz"},
+                "packages/local": {"name": "local", "version": "0.0.1"}
+            }
+        }"#;
+        let pkgs = parse_lockfile("web/package-lock.json", content);
+        let mut names: Vec<_> = pkgs
+            .iter()
+            .map(|p| format!("{}@{}", p.name, p.version))
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec!["@babel/core@7.0.0", "lodash@3.10.1", "lodash@4.17.20"]
+        );
+        assert!(pkgs.iter().all(|p| p.ecosystem == PackageEcosystem::Npm));
+        let lodash = pkgs.iter().find(|p| p.version == "4.17.20").unwrap();
+        assert_eq!(lodash.license.as_deref(), Some("MIT"));
+    }
+
+    #[test]
+    fn test_parse_package_lock_v1() {
+        let content = r#"{
+            "lockfileVersion": 1,
+            "dependencies": {
+                "minimist": {"version": "1.2.0"},
+                "mkdirp": {
+                    "version": "0.5.1",
+                    "dependencies": {"minimist": {"version": "0.0.8"}}
+                }
+            }
+        }"#;
+        let pkgs = parse_lockfile("package-lock.json", content);
+        assert_eq!(pkgs.len(), 3);
+        assert!(pkgs
+            .iter()
+            .any(|p| p.name == "minimist" && p.version == "0.0.8"));
+    }
+
+    #[test]
+    fn test_parse_go_sum() {
+        let content = "\
+golang.org/x/text v0.3.7 h1:abc=
+golang.org/x/text v0.3.7/go.mod h1:def=
+golang.org/x/net v0.0.0-20210405180319-a5a99cb37ef4/go.mod h1:ghi=
+github.com/old/lib v2.0.0+incompatible h1:jkl=
+";
+        let pkgs = parse_lockfile("go.sum", content);
+        let names: Vec<_> = pkgs
+            .iter()
+            .map(|p| (p.name.as_str(), p.version.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("golang.org/x/text", "0.3.7"),
+                ("github.com/old/lib", "2.0.0")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_lockfile_unknown_or_malformed() {
+        assert!(parse_lockfile("yarn.lock", "lodash@4: ...").is_empty());
+        assert!(parse_lockfile("package-lock.json", "{not json").is_empty());
+    }
+
+    #[test]
+    fn test_read_project_packages_skips_vendored_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let cargo_lock = "[[package]]\nname = \"serde\"\nversion = \"1.0.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n";
+        std::fs::write(root.join("Cargo.lock"), cargo_lock).unwrap();
+        std::fs::create_dir_all(root.join("tools/gen")).unwrap();
+        std::fs::write(root.join("tools/gen/Cargo.lock"), cargo_lock).unwrap();
+        std::fs::create_dir_all(root.join("node_modules/dep")).unwrap();
+        std::fs::write(
+            root.join("node_modules/dep/package-lock.json"),
+            r#"{"packages": {"node_modules/x": {"version": "1.0.0"}}}"#,
+        )
+        .unwrap();
+
+        let pkgs = read_project_packages(root);
+        assert_eq!(pkgs.len(), 1);
+        assert_eq!(pkgs[0].name, "serde");
+        assert_eq!(pkgs[0].lockfile, "Cargo.lock");
+    }
+}
@@ -0,0 +1,183 @@
+//! Vulnerability audit of locked third-party dependencies.
+//!
+//! Sync records the package versions pinned by a project's lockfiles as
+//! `(:Package)` nodes ([`ingest_project_packages`]). The
+//! `vulnerability_audit` heartbeat check then periodically matches them
+//! against OSV — RustSec, GitHub advisories, Go vulndb — and records the hits
+//! as `(:Advisory)` nodes linked to the affected projects ([`audit_project`]).
+//! Package licenses are resolved at ingestion and checked against workspace
+//! license policies ([`license`]).
+
+pub mod license;
+pub mod lockfile;
+pub mod osv;
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::neo4j::models::ProjectAdvisory;
+use crate::neo4j::GraphStore;
+
+use osv::OsvClient;
+
+/// Read the lockfiles under `root` and replace the project's Package edges.
+/// Returns the number of packages recorded.
+pub async fn ingest_project_packages(
+    neo4j: &dyn GraphStore,
+    project_id: Uuid,
+    root: &Path,
+) -> Result<usize> {
+    let root = root.to_path_buf();
+    let packages =
+        tokio::task::spawn_blocking(move || lockfile::read_project_packages(&root)).await?;
+    neo4j.replace_project_packages(project_id, &packages).await
+}
+
+/// Outcome of auditing one project.
+#[derive(Debug, Default, Serialize)]
+pub struct AuditReport {
+    pub packages_checked: usize,
+    /// Every advisory currently affecting the project, most severe first
+    pub advisories: Vec<ProjectAdvisory>,
+    /// Advisories not recorded by the previous audit
+    pub new_advisories: Vec<ProjectAdvisory>,
+}
+
+/// Match the project's packages against OSV and replace its advisories.
+pub async fn audit_project(
+    neo4j: &dyn GraphStore,
+    osv: &OsvClient,
+    project_id: Uuid,
+) -> Result<AuditReport> {
+    let packages = neo4j.list_project_packages(project_id).await?;
+    let vulns = if packages.is_empty() {
+        Vec::new()
+    } else {
+        osv.query_packages(&packages).await?
+    };
+
+    let mut advisories = Vec::new();
+    for (pkg, vulns) in packages.iter().zip(vulns) {
+        for vuln in vulns {
+            advisories.push(ProjectAdvisory {
+                advisory: vuln.to_advisory(),
+                ecosystem: pkg.ecosystem,
+                package: pkg.name.clone(),
+                version: pkg.version.clone(),
+                fixed_versions: vuln.fixed_versions(pkg.ecosystem, &pkg.name),
+            });
+        }
+    }
+
+    let key = |a: &ProjectAdvisory| (a.advisory.id.clone(), a.package.clone(), a.version.clone());
+    let previous: HashSet<_> = neo4j
+        .list_project_advisories(project_id)
+        .await?
+        .iter()
+        .map(key)
+        .collect();
+    let new_advisories: Vec<ProjectAdvisory> = advisories
+        .iter()
+        .filter(|a| !previous.contains(&key(a)))
+        .cloned()
+        .collect();
+
+    neo4j
+        .replace_project_advisories(project_id, &advisories)
+        .await?;
+
+    Ok(AuditReport {
+        packages_checked: packages.len(),
+        advisories: neo4j.list_project_advisories(project_id).await?,
+        new_advisories,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::AdvisorySeverity;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn mount_osv(server: &MockServer) {
+        Mock::given(method("POST"))
+            .and(path("/v1/querybatch"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "results": [
+                    {},
+                    {"vulns": [{"id": "GHSA-aaaa-bbbb-cccc", "modified": "2024-01-01T00:00:00Z"}]}
+                ]
+            })))
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/vulns/GHSA-aaaa-bbbb-cccc"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "GHSA-aaaa-bbbb-cccc",
+                "modified": "2024-01-01T00:00:00Z",
+                "summary": "Prototype pollution",
+                "database_specific": {"severity": "HIGH"},
+                "affected": [{
+                    "package": {"ecosystem": "npm", "name": "lodash"},
+                    "ranges": [{"type": "SEMVER", "events": [{"introduced": "0"}, {"fixed": "4.17.21"}]}]
+                }]
+            })))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_ingest_and_audit_project() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("package-lock.json"),
+            r#"{"packages": {
+                "node_modules/express": {"version": "4.18.0"},
+                "node_modules/lodash": {"version": "4.17.20"}
+            }}"#,
+        )
+        .unwrap();
+
+        let store = MockGraphStore::new();
+        let project_id = Uuid::new_v4();
+        let count = ingest_project_packages(&store, project_id, dir.path())
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+
+        let server = MockServer::start().await;
+        mount_osv(&server).await;
+        let osv = OsvClient::new(server.uri());
+
+        let report = audit_project(&store, &osv, project_id).await.unwrap();
+        assert_eq!(report.packages_checked, 2);
+        assert_eq!(report.advisories.len(), 1);
+        assert_eq!(report.new_advisories.len(), 1);
+        let adv = &report.advisories[0];
+        assert_eq!(adv.package, "lodash");
+        assert_eq!(adv.version, "4.17.20");
+        assert_eq!(adv.advisory.severity, AdvisorySeverity::High);
+        assert_eq!(adv.fixed_versions, vec!["4.17.21"]);
+
+        // Second audit: same advisory, nothing new
+        let report = audit_project(&store, &osv, project_id).await.unwrap();
+        assert_eq!(report.advisories.len(), 1);
+        assert!(report.new_advisories.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_audit_project_without_packages() {
+        let store = MockGraphStore::new();
+        // No request may reach OSV
+        let osv = OsvClient::new("http://127.0.0.1:9");
+        let report = audit_project(&store, &osv, Uuid::new_v4()).await.unwrap();
+        assert_eq!(report.packages_checked, 0);
+        assert!(report.advisories.is_empty());
+    }
+}
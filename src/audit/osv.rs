@@ -0,0 +1,515 @@
+//! Minimal client for the OSV vulnerability database (<https://osv.dev>).
+//!
+//! OSV aggregates RustSec, the GitHub advisory database and the Go
+//! vulnerability database behind a single API: `POST /v1/querybatch`
+//! returns the advisory ids affecting each (package, version), and
+//! `GET /v1/vulns/{id}` the advisory itself.
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use tokio::sync::Mutex;
+
+use crate::neo4j::models::{AdvisoryNode, AdvisorySeverity, PackageEcosystem, PackageNode};
+
+/// Public OSV API. Override with the `OSV_API_URL` environment variable
+/// (mirror or air-gapped proxy).
+pub const DEFAULT_OSV_URL: &str = "https://api.osv.dev";
+
+/// Maximum queries per `querybatch` request accepted by OSV.
+const BATCH_SIZE: usize = 1000;
+
+/// Concurrent `GET /v1/vulns/{id}` requests.
+const FETCH_CONCURRENCY: usize = 8;
+
+/// OSV API client. Advisory details are cached per id and only re-fetched
+/// when OSV reports a newer `modified` timestamp.
+pub struct OsvClient {
+    http: reqwest::Client,
+    base_url: String,
+    cache: Mutex<HashMap<String, OsvVuln>>,
+}
+
+impl OsvClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap_or_else(|_| reqwest::Client::new()),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Client for `OSV_API_URL`, or the public API.
+    pub fn from_env() -> Self {
+        Self::new(std::env::var("OSV_API_URL").unwrap_or_else(|_| DEFAULT_OSV_URL.to_string()))
+    }
+
+    /// Advisories affecting each package, in the order of `packages`.
+    pub async fn query_packages(&self, packages: &[PackageNode]) -> Result<Vec<Vec<OsvVuln>>> {
+        let mut refs: Vec<Vec<OsvVulnRef>> = Vec::with_capacity(packages.len());
+        for chunk in packages.chunks(BATCH_SIZE) {
+            refs.extend(self.query_batch(chunk).await?);
+        }
+
+        // Fetch each distinct advisory once, skipping unchanged cached ones
+        let mut to_fetch: Vec<String> = Vec::new();
+        {
+            let cache = self.cache.lock().await;
+            for r in refs.iter().flatten() {
+                let fresh = cache
+                    .get(&r.id)
+                    .is_some_and(|v| r.modified.is_none() || v.modified == r.modified);
+                if !fresh && !to_fetch.contains(&r.id) {
+                    to_fetch.push(r.id.clone());
+                }
+            }
+        }
+
+        use futures::StreamExt;
+        let fetched: Vec<Result<OsvVuln>> = futures::stream::iter(to_fetch)
+            .map(|id| async move { self.get_vuln(&id).await })
+            .buffer_unordered(FETCH_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut cache = self.cache.lock().await;
+        for vuln in fetched {
+            let vuln = vuln?;
+            cache.insert(vuln.id.clone(), vuln);
+        }
+
+        Ok(refs
+            .into_iter()
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(|r| cache.get(&r.id).cloned())
+                    .collect()
+            })
+            .collect())
+    }
+
+    async fn query_batch(&self, packages: &[PackageNode]) -> Result<Vec<Vec<OsvVulnRef>>> {
+        let queries: Vec<serde_json::Value> = packages
+            .iter()
+            .map(|p| {
+                serde_json::json!({
+                    "version": p.version,
+                    "package": {"name": p.name, "ecosystem": p.ecosystem.to_string()},
+                })
+            })
+            .collect();
+
+        let resp = self
+            .http
+            .post(format!("{}/v1/querybatch", self.base_url))
+            .json(&serde_json::json!({ "queries": queries }))
+            .send()
+            .await
+            .context("OSV querybatch request failed")?
+            .error_for_status()
+            .context("OSV querybatch returned an error")?;
+        let body: QueryBatchResponse = resp
+            .json()
+            .await
+            .context("Invalid OSV querybatch response")?;
+
+        if body.results.len() != packages.len() {
+            anyhow::bail!(
+                "OSV querybatch returned {} results for {} queries",
+                body.results.len(),
+                packages.len()
+            );
+        }
+        Ok(body.results.into_iter().map(|r| r.vulns).collect())
+    }
+
+    async fn get_vuln(&self, id: &str) -> Result<OsvVuln> {
+        let resp = self
+            .http
+            .get(format!(
+                "{}/v1/vulns/{}",
+                self.base_url,
+                urlencoding::encode(id)
+            ))
+            .send()
+            .await
+            .with_context(|| format!("OSV request for {} failed", id))?
+            .error_for_status()
+            .with_context(|| format!("OSV returned an error for {}", id))?;
+        resp.json()
+            .await
+            .with_context(|| format!("Invalid OSV advisory {}", id))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct QueryBatchResponse {
+    #[serde(default)]
+    results: Vec<QueryBatchResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct QueryBatchResult {
+    #[serde(default)]
+    vulns: Vec<OsvVulnRef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvVulnRef {
+    id: String,
+    modified: Option<String>,
+}
+
+/// An OSV advisory (subset of the OSV schema).
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsvVuln {
+    pub id: String,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub details: Option<String>,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub published: Option<String>,
+    #[serde(default)]
+    pub modified: Option<String>,
+    #[serde(default)]
+    pub severity: Vec<OsvSeverity>,
+    #[serde(default)]
+    pub affected: Vec<OsvAffected>,
+    #[serde(default)]
+    pub references: Vec<OsvReference>,
+    #[serde(default)]
+    pub database_specific: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsvSeverity {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub score: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsvAffected {
+    pub package: Option<OsvPackage>,
+    #[serde(default)]
+    pub ranges: Vec<OsvRange>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsvPackage {
+    pub ecosystem: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsvRange {
+    #[serde(default)]
+    pub events: Vec<HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsvReference {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub url: String,
+}
+
+impl OsvVuln {
+    pub fn to_advisory(&self) -> AdvisoryNode {
+        let cvss = self
+            .severity
+            .iter()
+            .find(|s| s.kind == "CVSS_V3")
+            .or_else(|| self.severity.iter().find(|s| s.kind.starts_with("CVSS")))
+            .map(|s| s.score.clone());
+
+        // GHSA curates a textual severity; otherwise derive it from CVSS v3
+        let severity = self
+            .database_specific
+            .as_ref()
+            .and_then(|d| d.get("severity"))
+            .and_then(|s| s.as_str())
+            .and_then(|s| s.parse().ok())
+            .or_else(|| {
+                cvss.as_deref()
+                    .and_then(cvss3_base_score)
+                    .map(severity_from_score)
+            })
+            .unwrap_or(AdvisorySeverity::Unknown);
+
+        let url = ["ADVISORY", "WEB"]
+            .iter()
+            .find_map(|kind| self.references.iter().find(|r| r.kind == *kind))
+            .map(|r| r.url.clone());
+
+        let summary = self
+            .summary
+            .clone()
+            .filter(|s| !s.is_empty())
+            .or_else(|| {
+                self.details
+                    .as_deref()
+                    .and_then(|d| d.lines().find(|l| !l.trim().is_empty()))
+                    .map(|l| l.trim().to_string())
+            })
+            .unwrap_or_default();
+
+        AdvisoryNode {
+            id: self.id.clone(),
+            summary,
+            aliases: self.aliases.clone(),
+            severity,
+            cvss,
+            url,
+            published: self.published.as_deref().and_then(parse_date),
+            modified: self.modified.as_deref().and_then(parse_date),
+        }
+    }
+
+    /// `fixed` events of the ranges affecting `name` in `ecosystem`.
+    pub fn fixed_versions(&self, ecosystem: PackageEcosystem, name: &str) -> Vec<String> {
+        let ecosystem = ecosystem.to_string();
+        let mut fixed: Vec<String> = Vec::new();
+        for affected in &self.affected {
+            let Some(pkg) = &affected.package else {
+                continue;
+            };
+            if pkg.ecosystem != ecosystem || pkg.name != name {
+                continue;
+            }
+            for event in affected.ranges.iter().flat_map(|r| &r.events) {
+                if let Some(v) = event.get("fixed").filter(|v| !fixed.contains(v)) {
+                    fixed.push(v.clone());
+                }
+            }
+        }
+        fixed
+    }
+}
+
+fn parse_date(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .ok()
+        .map(|d| d.with_timezone(&Utc))
+}
+
+/// CVSS v3.x qualitative rating of a base score.
+pub fn severity_from_score(score: f64) -> AdvisorySeverity {
+    match score {
+        s if s >= 9.0 => AdvisorySeverity::Critical,
+        s if s >= 7.0 => AdvisorySeverity::High,
+        s if s >= 4.0 => AdvisorySeverity::Medium,
+        s if s > 0.0 => AdvisorySeverity::Low,
+        _ => AdvisorySeverity::Unknown,
+    }
+}
+
+/// Base score of a CVSS v3.0/v3.1 vector (`CVSS:3.1/AV:N/AC:L/...`).
+/// Returns `None` for other versions or incomplete vectors.
+pub fn cvss3_base_score(vector: &str) -> Option<f64> {
+    let mut parts = vector.split('/');
+    if !parts.next()?.starts_with("CVSS:3") {
+        return None;
+    }
+    let metrics: HashMap<&str, &str> = parts.filter_map(|p| p.split_once(':')).collect();
+    let changed = match *metrics.get("S")? {
+        "U" => false,
+        "C" => true,
+        _ => return None,
+    };
+
+    let av = match *metrics.get("AV")? {
+        "N" => 0.85,
+        "A" => 0.62,
+        "L" => 0.55,
+        "P" => 0.2,
+        _ => return None,
+    };
+    let ac = match *metrics.get("AC")? {
+        "L" => 0.77,
+        "H" => 0.44,
+        _ => return None,
+    };
+    let pr = match (*metrics.get("PR")?, changed) {
+        ("N", _) => 0.85,
+        ("L", false) => 0.62,
+        ("L", true) => 0.68,
+        ("H", false) => 0.27,
+        ("H", true) => 0.5,
+        _ => return None,
+    };
+    let ui = match *metrics.get("UI")? {
+        "N" => 0.85,
+        "R" => 0.62,
+        _ => return None,
+    };
+    let cia = |key: &str| -> Option<f64> {
+        match *metrics.get(key)? {
+            "H" => Some(0.56),
+            "L" => Some(0.22),
+            "N" => Some(0.0),
+            _ => None,
+        }
+    };
+    let (c, i, a) = (cia("C")?, cia("I")?, cia("A")?);
+
+    let iss = 1.0 - (1.0 - c) * (1.0 - i) * (1.0 - a);
+    let impact = if changed {
+        7.52 * (iss - 0.029) - 3.25 * (iss - 0.02_f64).powi(15)
+    } else {
+        6.42 * iss
+    };
+    if impact <= 0.0 {
+        return Some(0.0);
+    }
+    let exploitability = 8.22 * av * ac * pr * ui;
+    let raw = if changed {
+        1.08 * (impact + exploitability)
+    } else {
+        impact + exploitability
+    };
+    Some(round_up(raw.min(10.0)))
+}
+
+/// CVSS v3.1 "Roundup": smallest one-decimal number >= `x`, robust to
+/// floating-point noise.
+fn round_up(x: f64) -> f64 {
+    let int_input = (x * 100_000.0).round() as i64;
+    if int_input % 10_000 == 0 {
+        int_input as f64 / 100_000.0
+    } else {
+        ((int_input / 10_000) + 1) as f64 / 10.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn vuln_json() -> serde_json::Value {
+        serde_json::json!({
+            "id": "RUSTSEC-2021-0001",
+            "modified": "2021-06-01T00:00:00Z",
+            "published": "2021-01-05T00:00:00Z",
+            "aliases": ["CVE-2021-1234"],
+            "summary": "Memory corruption in decoder",
+            "severity": [{"type": "CVSS_V3", "score": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"}],
+            "affected": [{
+                "package": {"ecosystem": "crates.io", "name": "decoder"},
+                "ranges": [{"type": "SEMVER", "events": [
+                    {"introduced": "0"}, {"fixed": "1.2.3"},
+                    {"introduced": "2.0.0"}, {"fixed": "2.0.1"}
+                ]}]
+            }],
+            "references": [
+                {"type": "PACKAGE", "url": "https://crates.io/crates/decoder"},
+                {"type": "ADVISORY", "url": "https://rustsec.org/advisories/RUSTSEC-2021-0001.html"}
+            ]
+        })
+    }
+
+    #[test]
+    fn test_cvss3_base_score() {
+        let score = |v| cvss3_base_score(v).unwrap();
+        assert_eq!(score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"), 9.8);
+        assert_eq!(score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H"), 10.0);
+        assert_eq!(score("CVSS:3.0/AV:N/AC:H/PR:N/UI:N/S:U/C:H/I:N/A:N"), 5.9);
+        assert_eq!(score("CVSS:3.1/AV:L/AC:L/PR:L/UI:N/S:U/C:N/I:N/A:N"), 0.0);
+        assert!(cvss3_base_score("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N").is_none());
+        assert!(cvss3_base_score("CVSS:3.1/AV:N/AC:L").is_none());
+    }
+
+    #[test]
+    fn test_to_advisory() {
+        let vuln: OsvVuln = serde_json::from_value(vuln_json()).unwrap();
+        let adv = vuln.to_advisory();
+        assert_eq!(adv.id, "RUSTSEC-2021-0001");
+        assert_eq!(adv.severity, AdvisorySeverity::Critical);
+        assert_eq!(
+            adv.url.as_deref(),
+            Some("https://rustsec.org/advisories/RUSTSEC-2021-0001.html")
+        );
+        assert_eq!(adv.aliases, vec!["CVE-2021-1234"]);
+        assert!(adv.published.is_some());
+        assert_eq!(
+            vuln.fixed_versions(PackageEcosystem::CratesIo, "decoder"),
+            vec!["1.2.3", "2.0.1"]
+        );
+        assert!(vuln
+            .fixed_versions(PackageEcosystem::Npm, "decoder")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_to_advisory_database_severity_wins() {
+        let mut json = vuln_json();
+        json["database_specific"] = serde_json::json!({"severity": "MODERATE"});
+        json["severity"] = serde_json::json!([]);
+        json["summary"] = serde_json::Value::Null;
+        json["details"] = serde_json::json!("\nFirst line.\nMore details.");
+        let adv = serde_json::from_value::<OsvVuln>(json)
+            .unwrap()
+            .to_advisory();
+        assert_eq!(adv.severity, AdvisorySeverity::Medium);
+        assert_eq!(adv.summary, "First line.");
+        assert!(adv.cvss.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_query_packages() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/querybatch"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "results": [
+                    {"vulns": [{"id": "RUSTSEC-2021-0001", "modified": "2021-06-01T00:00:00Z"}]},
+                    {}
+                ]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/vulns/RUSTSEC-2021-0001"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vuln_json()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = OsvClient::new(server.uri());
+        let packages = vec![
+            PackageNode {
+                ecosystem: PackageEcosystem::CratesIo,
+                name: "decoder".to_string(),
+                version: "1.0.0".to_string(),
+                lockfile: "Cargo.lock".to_string(),
+                license: None,
+            },
+            PackageNode {
+                ecosystem: PackageEcosystem::CratesIo,
+                name: "serde".to_string(),
+                version: "1.0.0".to_string(),
+                lockfile: "Cargo.lock".to_string(),
+                license: None,
+            },
+        ];
+
+        let results = client.query_packages(&packages).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0][0].id, "RUSTSEC-2021-0001");
+        assert!(results[1].is_empty());
+
+        // Unchanged advisory is served from the cache (GET expected once)
+        let again = client.query_packages(&packages).await.unwrap();
+        assert_eq!(again[0].len(), 1);
+    }
+}
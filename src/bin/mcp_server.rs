@@ -8,6 +8,10 @@
 //!
 //! - `PO_SERVER_URL` (required): REST API base URL (e.g. `http://127.0.0.1:8080`)
 //! - `PO_AUTH_TOKEN` (optional): JWT session token for authenticated requests
+//! - `PO_MCP_MAX_RESULT_BYTES` (optional): max rendered tool result size (default 100000)
+//! - `PO_MCP_PAGE_SIZE` (optional): default page size when a `cursor` is passed
+//! - `PO_MCP_HEALTH_SOCKET` (optional, Unix): socket answering the liveness
+//!   pings of the chat supervisor
 //!
 //! # Architecture
 //!
@@ -71,6 +75,14 @@ async fn main() -> Result<()> {
 
     let mut server = McpServer::new(http_client);
 
+    #[cfg(unix)]
+    if let Some(path) = std::env::var_os(project_orchestrator::mcp::health::HEALTH_SOCKET_ENV) {
+        match project_orchestrator::mcp::health::listen(std::path::Path::new(&path)) {
+            Ok(probes) => server = server.with_health_probes(probes),
+            Err(e) => error!("Failed to listen on the health socket: {}", e),
+        }
+    }
+
     if let Err(e) = server.run().await {
         error!("MCP server error: {}", e);
         return Err(e);
@@ -0,0 +1,267 @@
+//! File and image attachments of chat messages.
+//!
+//! The WebSocket client sends attachments inline (base64) with a
+//! `user_message`. They are written to a per-session scratch directory,
+//! which is passed to the Claude CLI with `--add-dir`. The message given to
+//! the CLI then ends with an `<attachments>` block listing each file's path,
+//! so the agent opens them with its Read tool (which also reads images).
+//! [`split_message`] takes that block back out when the message is turned
+//! into a [`ChatEvent::UserMessage`](super::types::ChatEvent), so clients
+//! see the text and a structured attachment list.
+
+use super::types::{ChatAttachment, ChatAttachmentRef};
+use anyhow::{bail, Context, Result};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Attachments accepted per message
+pub const MAX_ATTACHMENTS: usize = 10;
+/// Largest attachment, decoded
+pub const MAX_ATTACHMENT_BYTES: usize = 10 * 1024 * 1024;
+/// Total decoded size of the attachments of one message
+pub const MAX_MESSAGE_ATTACHMENT_BYTES: usize = 20 * 1024 * 1024;
+
+const BLOCK_START: &str = "\n\n<attachments>\n";
+const BLOCK_END: &str = "</attachments>";
+
+/// Scratch directory holding the attachments of a session.
+pub fn session_dir(session_id: &str) -> Result<PathBuf> {
+    let id = Uuid::parse_str(session_id).context("Invalid session ID")?;
+    Ok(std::env::temp_dir()
+        .join("project-orchestrator")
+        .join("chat")
+        .join(id.to_string()))
+}
+
+/// Remove the scratch directory of a session, if any.
+pub async fn remove_session_dir(session_id: &str) {
+    if let Ok(dir) = session_dir(session_id) {
+        let _ = tokio::fs::remove_dir_all(dir).await;
+    }
+}
+
+/// File name safe to store and to quote in the attachment block.
+fn sanitize_name(name: &str) -> String {
+    let base = name.rsplit(['/', '\\']).next().unwrap_or_default();
+    let clean: String = base
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '.' | '-' | '_' | ' ') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let clean = clean.trim().trim_start_matches('.').to_string();
+    if clean.is_empty() {
+        "attachment".to_string()
+    } else {
+        clean.chars().take(100).collect()
+    }
+}
+
+/// Decode base64 data, with or without padding or a `data:` URL prefix.
+fn decode_data(data: &str) -> Result<Vec<u8>> {
+    let data = match data.split_once(";base64,") {
+        Some((prefix, rest)) if prefix.starts_with("data:") => rest,
+        _ => data,
+    };
+    let data: String = data.chars().filter(|c| !c.is_whitespace()).collect();
+    let base = if data.ends_with('=') || data.len() % 4 == 0 {
+        'M'
+    } else {
+        'm'
+    };
+    multibase::decode(format!("{}{}", base, data))
+        .map(|(_, bytes)| bytes)
+        .map_err(|e| anyhow::anyhow!("invalid base64: {}", e))
+}
+
+/// Validate and write a message's attachments to the session scratch dir.
+pub async fn store(
+    session_id: &str,
+    attachments: &[ChatAttachment],
+) -> Result<Vec<ChatAttachmentRef>> {
+    if attachments.len() > MAX_ATTACHMENTS {
+        bail!("At most {} attachments per message", MAX_ATTACHMENTS);
+    }
+    let mut decoded = Vec::with_capacity(attachments.len());
+    let mut total = 0usize;
+    for attachment in attachments {
+        let bytes = decode_data(&attachment.data)
+            .with_context(|| format!("Attachment '{}'", attachment.name))?;
+        if bytes.len() > MAX_ATTACHMENT_BYTES {
+            bail!(
+                "Attachment '{}' is larger than {} bytes",
+                attachment.name,
+                MAX_ATTACHMENT_BYTES
+            );
+        }
+        total += bytes.len();
+        if total > MAX_MESSAGE_ATTACHMENT_BYTES {
+            bail!(
+                "Attachments are larger than {} bytes in total",
+                MAX_MESSAGE_ATTACHMENT_BYTES
+            );
+        }
+        decoded.push(bytes);
+    }
+
+    let dir = session_dir(session_id)?;
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .with_context(|| format!("Failed to create {}", dir.display()))?;
+    let mut stored = Vec::with_capacity(attachments.len());
+    for (attachment, bytes) in attachments.iter().zip(decoded) {
+        let name = sanitize_name(&attachment.name);
+        let id = Uuid::new_v4().simple().to_string();
+        let path = dir.join(format!("{}-{}", &id[..8], name));
+        tokio::fs::write(&path, &bytes)
+            .await
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        stored.push(ChatAttachmentRef {
+            name,
+            mime_type: sanitize_mime(&attachment.mime_type),
+            size: bytes.len() as u64,
+            path: path.to_string_lossy().into_owned(),
+        });
+    }
+    Ok(stored)
+}
+
+fn sanitize_mime(mime: &str) -> String {
+    let mime: String = mime
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '.' | '+' | '-'))
+        .collect();
+    if mime.is_empty() {
+        "application/octet-stream".to_string()
+    } else {
+        mime
+    }
+}
+
+/// Append the attachment block given to the CLI to a message.
+pub fn append_to_message(content: &str, attachments: &[ChatAttachmentRef]) -> String {
+    if attachments.is_empty() {
+        return content.to_string();
+    }
+    let mut message = format!("{}{}", content, BLOCK_START);
+    for a in attachments {
+        message.push_str(&format!(
+            "<attachment name=\"{}\" type=\"{}\" size=\"{}\" path=\"{}\" />\n",
+            a.name, a.mime_type, a.size, a.path
+        ));
+    }
+    message.push_str(BLOCK_END);
+    message
+}
+
+fn attribute(line: &str, key: &str) -> Option<String> {
+    let start = line.find(&format!(" {}=\"", key))? + key.len() + 3;
+    let len = line[start..].find('"')?;
+    Some(line[start..start + len].to_string())
+}
+
+/// Split a message built by [`append_to_message`] back into its text and
+/// attachments. Messages without a block are returned as is.
+pub fn split_message(message: &str) -> (String, Vec<ChatAttachmentRef>) {
+    let Some(start) = message.rfind(BLOCK_START) else {
+        return (message.to_string(), Vec::new());
+    };
+    let block = &message[start + BLOCK_START.len()..];
+    let Some(lines) = block.strip_suffix(BLOCK_END) else {
+        return (message.to_string(), Vec::new());
+    };
+    let mut attachments = Vec::new();
+    for line in lines.lines() {
+        let parsed = (|| {
+            Some(ChatAttachmentRef {
+                name: attribute(line, "name")?,
+                mime_type: attribute(line, "type")?,
+                size: attribute(line, "size")?.parse().ok()?,
+                path: attribute(line, "path")?,
+            })
+        })();
+        match parsed {
+            Some(attachment) => attachments.push(attachment),
+            None => return (message.to_string(), Vec::new()),
+        }
+    }
+    (message[..start].to_string(), attachments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attachment(name: &str, data: &str) -> ChatAttachment {
+        ChatAttachment {
+            name: name.to_string(),
+            mime_type: "text/plain".to_string(),
+            data: data.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_sanitize_name() {
+        assert_eq!(sanitize_name("../../etc/passwd"), "passwd");
+        assert_eq!(sanitize_name("C:\\logs\\app.log"), "app.log");
+        assert_eq!(sanitize_name("a\"b<c>.png"), "a_b_c_.png");
+        assert_eq!(sanitize_name(".."), "attachment");
+    }
+
+    #[test]
+    fn test_decode_data() {
+        assert_eq!(decode_data("aGVsbG8=").unwrap(), b"hello");
+        assert_eq!(decode_data("aGVsbG8").unwrap(), b"hello");
+        assert_eq!(
+            decode_data("data:text/plain;base64,aGVsbG8=").unwrap(),
+            b"hello"
+        );
+        assert!(decode_data("not base64!").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_store_and_round_trip() {
+        let session_id = Uuid::new_v4().to_string();
+        let stored = store(&session_id, &[attachment("error.log", "aGVsbG8=")])
+            .await
+            .unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].size, 5);
+        assert!(stored[0].name == "error.log" && stored[0].path.ends_with("-error.log"));
+        assert_eq!(std::fs::read(&stored[0].path).unwrap(), b"hello");
+
+        let message = append_to_message("What does this mean?", &stored);
+        assert!(message.contains(&stored[0].path));
+        let (text, parsed) = split_message(&message);
+        assert_eq!(text, "What does this mean?");
+        assert_eq!(parsed, stored);
+
+        remove_session_dir(&session_id).await;
+        assert!(!session_dir(&session_id).unwrap().exists());
+    }
+
+    #[tokio::test]
+    async fn test_store_rejects_invalid() {
+        let session_id = Uuid::new_v4().to_string();
+        let too_many: Vec<_> = (0..=MAX_ATTACHMENTS)
+            .map(|i| attachment(&format!("{}.txt", i), "aGk="))
+            .collect();
+        assert!(store(&session_id, &too_many).await.is_err());
+        assert!(store(&session_id, &[attachment("x", "%%%")]).await.is_err());
+        assert!(store("../escape", &[attachment("x", "aGk=")])
+            .await
+            .is_err());
+        assert!(!session_dir(&session_id).unwrap().exists());
+    }
+
+    #[test]
+    fn test_split_message_without_block() {
+        let (text, attachments) = split_message("plain <attachments> text");
+        assert_eq!(text, "plain <attachments> text");
+        assert!(attachments.is_empty());
+    }
+}
@@ -2,13 +2,18 @@
 //!
 //! Uses the Nexus SDK's `find_claude_cli`, `get_cli_version`, `check_latest_npm_version`,
 //! and `download_cli` functions to provide a unified CLI management service.
+//!
+//! When `claude_cli_path` is configured (env `CLAUDE_CLI_PATH` or `chat.claude_cli_path`
+//! in config.yaml), that binary is pinned: it is the one checked and spawned, with no
+//! fallback to PATH. [`check_cli_health`] reports whether it runs and is at least
+//! [`MIN_SUPPORTED_CLI_VERSION`].
 
 use nexus_claude::{
     check_latest_npm_version, download_cli, find_claude_cli, get_cached_cli_path, get_cli_version,
     SemVer,
 };
 use serde::Serialize;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
 
 /// Run `<cli_path> --version` and parse the raw output ourselves.
@@ -50,6 +55,93 @@ async fn get_cli_version_robust(cli_path: &Path) -> Option<SemVer> {
     get_cli_version(cli_path).await
 }
 
+/// Oldest CLI version the chat bridge is tested against (stream-json control
+/// protocol, hooks and `--add-dir`).
+pub const MIN_SUPPORTED_CLI_VERSION: &str = "2.0.0";
+
+/// Whether `version` is at least [`MIN_SUPPORTED_CLI_VERSION`].
+pub fn is_supported_version(version: &SemVer) -> bool {
+    SemVer::parse(MIN_SUPPORTED_CLI_VERSION).is_none_or(|min| *version >= min)
+}
+
+/// The CLI binary to use: the pinned path when configured, otherwise the one
+/// found on PATH or in the cc-sdk cache.
+pub fn resolve_cli_path(pinned: Option<&str>) -> Result<PathBuf, String> {
+    match pinned {
+        Some(path) => {
+            let path = PathBuf::from(crate::expand_tilde(path));
+            if path.is_file() {
+                Ok(path)
+            } else {
+                Err(format!(
+                    "Pinned Claude CLI path {} does not exist",
+                    path.display()
+                ))
+            }
+        }
+        None => find_claude_cli().map_err(|e| format!("Claude CLI not found: {}", e)),
+    }
+}
+
+/// CLI availability — returned by `check_cli_health()`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CliHealth {
+    /// Whether the CLI was found, runs, and is a supported version
+    pub available: bool,
+    /// Path of the binary checked
+    pub cli_path: Option<String>,
+    /// Whether the path is pinned by configuration
+    pub pinned: bool,
+    /// Version reported by `--version`
+    pub version: Option<String>,
+    pub min_supported_version: &'static str,
+    /// Why the CLI is not available
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Check that the CLI chat sessions will spawn is usable. Unlike
+/// `check_cli_status()`, this makes no network call.
+pub async fn check_cli_health(pinned: Option<&str>) -> CliHealth {
+    let mut health = CliHealth {
+        available: false,
+        cli_path: None,
+        pinned: pinned.is_some(),
+        version: None,
+        min_supported_version: MIN_SUPPORTED_CLI_VERSION,
+        error: None,
+    };
+    let path = match resolve_cli_path(pinned) {
+        Ok(path) => path,
+        Err(e) => {
+            health.error = Some(e);
+            return health;
+        }
+    };
+    health.cli_path = Some(path.to_string_lossy().to_string());
+
+    match get_cli_version_robust(&path).await {
+        Some(version) if is_supported_version(&version) => {
+            health.available = true;
+            health.version = Some(version.to_string());
+        }
+        Some(version) => {
+            health.error = Some(format!(
+                "Claude CLI {} is older than the minimum supported version {}",
+                version, MIN_SUPPORTED_CLI_VERSION
+            ));
+            health.version = Some(version.to_string());
+        }
+        None => {
+            health.error = Some(format!(
+                "{} --version failed; the binary does not run",
+                path.display()
+            ));
+        }
+    }
+    health
+}
+
 /// Full CLI version status — returned by `check_cli_status()`.
 #[derive(Debug, Clone, Serialize)]
 pub struct CliVersionStatus {
@@ -83,14 +175,14 @@ pub struct CliInstallResult {
 /// Check the current CLI installation status.
 ///
 /// This function:
-/// 1. Finds the CLI binary via `find_claude_cli()` (PATH + cache search)
+/// 1. Finds the CLI binary: the `pinned` path, or `find_claude_cli()` (PATH + cache search)
 /// 2. Gets the installed version via `get_cli_version()`
 /// 3. Checks npm for the latest version (10s timeout, returns None on failure)
 /// 4. Detects whether the binary is a "local build" (not from npm/cc-sdk cache)
 /// 5. Compares versions to determine if an update is available
-pub async fn check_cli_status() -> CliVersionStatus {
+pub async fn check_cli_status(pinned: Option<&str>) -> CliVersionStatus {
     // 1. Find the CLI binary
-    let cli_path = find_claude_cli().ok();
+    let cli_path = resolve_cli_path(pinned).ok();
     let cli_path_str = cli_path.as_ref().map(|p| p.to_string_lossy().to_string());
 
     if cli_path.is_none() {
@@ -442,13 +534,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_is_supported_version() {
+        assert!(is_supported_version(&SemVer::parse("2.1.50").unwrap()));
+        assert!(is_supported_version(
+            &SemVer::parse(MIN_SUPPORTED_CLI_VERSION).unwrap()
+        ));
+        assert!(!is_supported_version(&SemVer::parse("1.0.128").unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_check_cli_health_missing_pinned_path() {
+        let health = check_cli_health(Some("/nonexistent/bin/claude")).await;
+        assert!(!health.available);
+        assert!(health.pinned);
+        assert!(health.cli_path.is_none());
+        assert!(health.error.unwrap().contains("/nonexistent/bin/claude"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_check_cli_health_pinned_binary() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let script = |name: &str, version: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(
+                &path,
+                format!("#!/bin/sh\necho '{} (Claude Code)'\n", version),
+            )
+            .unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+            path.to_string_lossy().to_string()
+        };
+
+        let current = script("claude", "2.1.50");
+        let health = check_cli_health(Some(&current)).await;
+        assert!(health.available, "{:?}", health.error);
+        assert_eq!(health.version.as_deref(), Some("2.1.50"));
+        assert_eq!(health.cli_path.as_deref(), Some(current.as_str()));
+
+        let old = script("claude-old", "1.0.3");
+        let health = check_cli_health(Some(&old)).await;
+        assert!(!health.available);
+        assert_eq!(health.version.as_deref(), Some("1.0.3"));
+        assert!(health.error.unwrap().contains(MIN_SUPPORTED_CLI_VERSION));
+    }
+
     /// Integration test: check_cli_status returns a valid struct.
     /// Marked #[ignore] because it requires network access for npm check
     /// and a real Claude CLI installation.
     #[tokio::test]
     #[ignore]
     async fn test_check_cli_status_integration() {
-        let status = check_cli_status().await;
+        let status = check_cli_status(None).await;
         // On a machine with Claude CLI installed, this should be true
         if status.installed {
             assert!(
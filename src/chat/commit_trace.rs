@@ -0,0 +1,252 @@
+//! Task-to-commit traceability for chat sessions.
+//!
+//! A session spawned for a task (runner, pipeline, gate or trigger — see
+//! [`SpawnedBy::task_id`]) commits its work with plain `git commit`. After each
+//! turn, the commits made in the session's repository since it started are
+//! read from `git log`; those touching a file the session modified are
+//! registered as `Commit` nodes (with their TOUCHES relations) and linked with
+//! `(:Task)-[:IMPLEMENTED_BY]->(:Commit)`.
+
+use super::types::SpawnedBy;
+use crate::neo4j::models::{CommitNode, FileChangedInfo};
+use crate::neo4j::GraphStore;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use std::path::Path;
+use tokio::process::Command;
+use uuid::Uuid;
+
+/// Record separator between commits of the `git log` output
+const RECORD_SEPARATOR: char = '\u{1e}';
+/// Field separator within a commit header
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+/// A commit read from `git log`, with the files it changed as absolute paths
+#[derive(Debug, Clone)]
+pub(crate) struct TracedCommit {
+    pub hash: String,
+    pub author: String,
+    pub timestamp: DateTime<Utc>,
+    pub message: String,
+    pub files: Vec<FileChangedInfo>,
+}
+
+/// Parse `git log --numstat --format=%x1e%H%x1f%an%x1f%aI%x1f%s` output.
+/// File paths are made absolute against the repository `root`.
+pub(crate) fn parse_git_log(output: &str, root: &Path) -> Vec<TracedCommit> {
+    output
+        .split(RECORD_SEPARATOR)
+        .filter_map(|record| {
+            let mut lines = record.lines();
+            let header = lines.next()?;
+            let mut fields = header.split(FIELD_SEPARATOR);
+            let hash = fields.next()?.trim().to_string();
+            let author = fields.next()?.to_string();
+            let timestamp = fields.next()?.parse().ok()?;
+            let message = fields.next().unwrap_or_default().to_string();
+            if hash.is_empty() {
+                return None;
+            }
+            let files = lines
+                .filter_map(|line| {
+                    let mut cols = line.splitn(3, '\t');
+                    let additions = cols.next()?.parse().ok();
+                    let deletions = cols.next()?.parse().ok();
+                    let path = cols.next()?;
+                    Some(FileChangedInfo {
+                        path: root.join(path).to_string_lossy().to_string(),
+                        additions,
+                        deletions,
+                    })
+                })
+                .collect();
+            Some(TracedCommit {
+                hash,
+                author,
+                timestamp,
+                message,
+                files,
+            })
+        })
+        .collect()
+}
+
+/// Task a session works on, from its `spawned_by` JSON
+pub(crate) fn session_task(spawned_by: Option<&str>) -> Option<Uuid> {
+    SpawnedBy::from_json_str(spawned_by?)?.task_id()
+}
+
+async fn git(cwd: &str, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .await
+        .with_context(|| format!("Failed to run git {}", args.first().unwrap_or(&"")))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Commits made in the repository at `cwd` since `since`, oldest first
+pub(crate) async fn commits_since(cwd: &str, since: DateTime<Utc>) -> Result<Vec<TracedCommit>> {
+    let root = git(cwd, &["rev-parse", "--show-toplevel"]).await?;
+    let since = format!("--since={}", since.to_rfc3339());
+    let log = git(
+        cwd,
+        &[
+            "log",
+            "--reverse",
+            "--no-color",
+            "--numstat",
+            "--format=%x1e%H%x1f%an%x1f%aI%x1f%s",
+            &since,
+        ],
+    )
+    .await?;
+    Ok(parse_git_log(&log, Path::new(root.trim())))
+}
+
+/// Link the commits of `commits` touching one of `files_modified` to `task_id`,
+/// creating the missing `Commit` nodes. Returns the linked hashes.
+pub(crate) async fn link_task_commits(
+    graph: &dyn GraphStore,
+    task_id: Uuid,
+    session_id: Option<Uuid>,
+    cwd: &str,
+    files_modified: &HashSet<String>,
+    commits: &[TracedCommit],
+) -> Result<Vec<String>> {
+    let modified: HashSet<String> = files_modified
+        .iter()
+        .map(|f| Path::new(cwd).join(f).to_string_lossy().to_string())
+        .collect();
+    let mut linked = Vec::new();
+    for commit in commits {
+        if !commit.files.iter().any(|f| modified.contains(&f.path)) {
+            continue;
+        }
+        if graph.get_commit(&commit.hash).await?.is_none() {
+            graph
+                .create_commit(&CommitNode {
+                    hash: commit.hash.clone(),
+                    message: commit.message.clone(),
+                    author: commit.author.clone(),
+                    timestamp: commit.timestamp,
+                })
+                .await?;
+            graph
+                .create_commit_touches(&commit.hash, &commit.files)
+                .await?;
+        }
+        graph
+            .link_commit_implements_task(&commit.hash, task_id, session_id)
+            .await?;
+        linked.push(commit.hash.clone());
+    }
+    Ok(linked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::test_helpers::test_task;
+
+    fn log(records: &[(&str, &str, &[&str])]) -> String {
+        records
+            .iter()
+            .map(|(hash, subject, files)| {
+                let mut record = format!(
+                    "\u{1e}{}\u{1f}Ada\u{1f}2026-10-01T10:00:00+02:00\u{1f}{}\n\n",
+                    hash, subject
+                );
+                for file in files.iter() {
+                    record.push_str(&format!("3\t1\t{}\n", file));
+                }
+                record
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_git_log() {
+        let output = log(&[
+            ("aaa", "feat: add login", &["src/auth.rs", "src/lib.rs"]),
+            ("bbb", "docs: readme", &["README.md"]),
+        ]);
+        let commits = parse_git_log(&output, Path::new("/repo"));
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].hash, "aaa");
+        assert_eq!(commits[0].author, "Ada");
+        assert_eq!(commits[0].message, "feat: add login");
+        assert_eq!(
+            commits[0].timestamp,
+            "2026-10-01T08:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(commits[0].files[0].path, "/repo/src/auth.rs");
+        assert_eq!(commits[0].files[0].additions, Some(3));
+        assert_eq!(commits[1].files.len(), 1);
+        // Binary files have no line counts
+        let binary = parse_git_log(
+            "\u{1e}ccc\u{1f}Ada\u{1f}2026-10-01T10:00:00Z\u{1f}img\n\n-\t-\tlogo.png\n",
+            Path::new("/repo"),
+        );
+        assert_eq!(binary[0].files[0].additions, None);
+        assert!(parse_git_log("", Path::new("/repo")).is_empty());
+    }
+
+    #[test]
+    fn test_session_task() {
+        let task_id = Uuid::new_v4();
+        let spawned_by = SpawnedBy::Runner {
+            run_id: Uuid::new_v4(),
+            task_id,
+            parent_session_id: None,
+        }
+        .to_json_string();
+        assert_eq!(session_task(Some(&spawned_by)), Some(task_id));
+        assert_eq!(session_task(None), None);
+        assert_eq!(session_task(Some("")), None);
+    }
+
+    #[tokio::test]
+    async fn test_link_task_commits() {
+        let graph = MockGraphStore::new();
+        let task = test_task();
+        graph.create_task(Uuid::new_v4(), &task).await.unwrap();
+        let commits = parse_git_log(
+            &log(&[
+                ("aaa", "feat: add login", &["src/auth.rs"]),
+                ("bbb", "chore: unrelated", &["other.rs"]),
+            ]),
+            Path::new("/repo"),
+        );
+        let files: HashSet<String> = ["src/auth.rs".to_string()].into();
+
+        let linked = link_task_commits(&graph, task.id, None, "/repo", &files, &commits)
+            .await
+            .unwrap();
+        assert_eq!(linked, vec!["aaa".to_string()]);
+        assert!(graph.get_commit("aaa").await.unwrap().is_some());
+        assert!(graph.get_commit("bbb").await.unwrap().is_none());
+
+        // Linking again is idempotent
+        link_task_commits(&graph, task.id, None, "/repo", &files, &commits)
+            .await
+            .unwrap();
+        let details = graph
+            .get_task_with_full_details(task.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(details.implemented_by.len(), 1);
+        assert_eq!(details.implemented_by[0].hash, "aaa");
+    }
+}
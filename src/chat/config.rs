@@ -1,5 +1,8 @@
 //! Chat configuration
 
+use super::limits::SessionLimits;
+use super::model_routing::ModelRoutingConfig;
+use super::sandbox::SandboxConfig;
 use nexus_claude::PermissionMode;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -182,6 +185,15 @@ pub struct ChatConfig {
     pub server_port: u16,
     /// Expiration duration for MCP session tokens in seconds (default: 24h = 86400s).
     pub session_token_expiry_secs: u64,
+    /// Execution sandbox applied to every session (default: disabled).
+    /// Sessions may opt in or tighten it, see [`SandboxConfig::for_session`].
+    pub sandbox: SandboxConfig,
+    /// Resource limits applied to every session (default: none).
+    /// Sessions may lower them, see [`SessionLimits::for_session`].
+    pub limits: SessionLimits,
+    /// Model routing rules by task type (default: none, every session runs
+    /// on `default_model`).
+    pub model_routing: ModelRoutingConfig,
 }
 
 impl ChatConfig {
@@ -259,6 +271,9 @@ impl ChatConfig {
                 .ok()
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(86400), // 24 hours
+            sandbox: SandboxConfig::default(),
+            limits: SessionLimits::default(),
+            model_routing: ModelRoutingConfig::default(),
         }
     }
 
@@ -349,6 +364,9 @@ mod tests {
             jwt_secret: None,
             server_port: 8080,
             session_token_expiry_secs: 86400,
+            sandbox: SandboxConfig::default(),
+            limits: SessionLimits::default(),
+            model_routing: ModelRoutingConfig::default(),
         };
 
         assert_eq!(config.default_model, "claude-sonnet-4-6");
@@ -504,6 +522,9 @@ mod tests {
             jwt_secret: None,
             server_port: 8080,
             session_token_expiry_secs: 86400,
+            sandbox: SandboxConfig::default(),
+            limits: SessionLimits::default(),
+            model_routing: ModelRoutingConfig::default(),
         };
 
         let json = config.mcp_server_config();
@@ -537,6 +558,9 @@ mod tests {
             jwt_secret: None,
             server_port: 8080,
             session_token_expiry_secs: 86400,
+            sandbox: SandboxConfig::default(),
+            limits: SessionLimits::default(),
+            model_routing: ModelRoutingConfig::default(),
         };
 
         let json = config.mcp_server_config();
@@ -109,10 +109,8 @@ pub(crate) async fn drain_pending_messages(
                     ),
                     PendingMessageKind::User => (
                         "user_message".to_string(),
-                        serde_json::to_string(&ChatEvent::UserMessage {
-                            content: msg_content.clone(),
-                        })
-                        .unwrap_or_default(),
+                        serde_json::to_string(&ChatEvent::user_message(msg_content.clone()))
+                            .unwrap_or_default(),
                     ),
                     PendingMessageKind::BackgroundOutput => unreachable!(
                         "BackgroundOutput is filtered out by needs_persist_and_broadcast"
@@ -134,9 +132,7 @@ pub(crate) async fn drain_pending_messages(
                 PendingMessageKind::SystemHint => ChatEvent::SystemHint {
                     content: msg_content.clone(),
                 },
-                PendingMessageKind::User => ChatEvent::UserMessage {
-                    content: msg_content.clone(),
-                },
+                PendingMessageKind::User => ChatEvent::user_message(msg_content.clone()),
                 PendingMessageKind::BackgroundOutput => unreachable!(),
             };
             let _ = events_tx.send(chat_event.clone());
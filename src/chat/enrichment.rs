@@ -51,6 +51,12 @@ pub struct EnrichmentConfig {
     /// Enable the MCP federation stage (inject external tool availability into prompt).
     #[serde(default)]
     pub mcp_federation: bool,
+    /// Enable the documentation stage (cite matching Markdown doc sections).
+    #[serde(default)]
+    pub documentation: bool,
+    /// Enable the schema stage (inject SQL tables mentioned in the message).
+    #[serde(default)]
+    pub schema: bool,
     /// Enable the reasoning tree injection stage.
     pub reasoning_tree: bool,
     /// Enable debug mode (logs timing and content of each stage).
@@ -70,6 +76,8 @@ impl Default for EnrichmentConfig {
             user_profile: false, // Disabled by default — opt-in via ENRICHMENT_USER_PROFILE=true
             persona: true,     // Enabled by default — auto-skips when no personas match
             mcp_federation: true, // Enabled by default — auto-skips when no MCP servers connected
+            documentation: true, // Enabled by default — auto-skips when no doc section matches
+            schema: true,      // Enabled by default — auto-skips when no table is mentioned
             reasoning_tree: true,
             debug: false,
             max_pipeline_ms: 500,
@@ -113,6 +121,12 @@ impl EnrichmentConfig {
             mcp_federation: std::env::var("ENRICHMENT_MCP_FEDERATION")
                 .map(|v| v != "false" && v != "0")
                 .unwrap_or(true),
+            documentation: std::env::var("ENRICHMENT_DOCUMENTATION")
+                .map(|v| v != "false" && v != "0")
+                .unwrap_or(true),
+            schema: std::env::var("ENRICHMENT_SCHEMA")
+                .map(|v| v != "false" && v != "0")
+                .unwrap_or(true),
             reasoning_tree: std::env::var("ENRICHMENT_REASONING_TREE")
                 .map(|v| v != "false" && v != "0")
                 .unwrap_or(true),
@@ -179,6 +193,9 @@ pub struct EnrichmentContext {
     /// - `scaffolding_level`: 0-4 (from project maturity)
     /// - `detected_files`: comma-separated file paths mentioned in the message
     /// - `detected_functions`: comma-separated function names mentioned
+    /// - `context_note_ids` / `context_symbols`: comma-separated notes and
+    ///   symbols surfaced by knowledge injection (listed in a `context_items`
+    ///   chat event for context feedback)
     pub hints: HashMap<String, String>,
     /// Timing information per stage (name, duration in ms).
     pub stage_timings: Vec<(String, u64)>,
@@ -210,6 +227,10 @@ pub enum EnrichmentSource {
     UserProfile,
     /// MCP Federation stage (external tool availability).
     McpFederation,
+    /// Documentation stage (Markdown doc sections).
+    Documentation,
+    /// Schema stage (SQL tables and columns).
+    Schema,
     /// Fallback for stages without a specific source type.
     #[default]
     Other,
@@ -304,6 +325,8 @@ impl EnrichmentContext {
                 | EnrichmentSource::Biomimicry
                 | EnrichmentSource::UserProfile
                 | EnrichmentSource::McpFederation
+                | EnrichmentSource::Documentation
+                | EnrichmentSource::Schema
                 | EnrichmentSource::Other => PromptSection::Enrichment(section.content.clone()),
             })
             .collect()
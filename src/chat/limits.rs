@@ -0,0 +1,436 @@
+//! Resource limits of chat sessions — the runaway-agent kill switch.
+//!
+//! An agent stuck in a loop keeps calling tools, rewriting files and
+//! spending tokens until someone notices. [`SessionLimits`] caps a session,
+//! server-wide (`chat.limits` in config.yaml) or per session:
+//!
+//! - **Tool calls** and **file writes** (Write, Edit, MultiEdit,
+//!   NotebookEdit) are counted by [`LimitsHook`], an in-process PreToolUse
+//!   hook. The call that would go over the limit is denied, so the agent
+//!   can't touch the workspace past it.
+//! - **Wall-clock** time and **spend** (the `total_cost_usd` reported by the
+//!   CLI) are checked by `ChatManager::start_limits_task`.
+//!
+//! Whichever limit trips first, the manager kills the session
+//! (`ChatManager::kill_session`): the CLI process tree gets `SIGKILL` and
+//! the session is closed with a `SessionError` of reason
+//! `"session_limit_exceeded"`.
+//!
+//! Usage covers the whole session, not one CLI process: it is stored on the
+//! session node ([`ChatSessionUsage`]) after each turn and when the session
+//! closes, and restored when it resumes. A killed session is marked as such
+//! and refuses new messages until its usage is reset
+//! (`ChatManager::reset_session_usage`).
+
+use crate::neo4j::models::ChatSessionUsage;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// Tools counted as file writes
+const FILE_WRITE_TOOLS: &[&str] = &["Write", "Edit", "MultiEdit", "NotebookEdit"];
+
+/// Limits of a session, server-wide (`chat.limits` in config.yaml) or per session
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionLimits {
+    /// Maximum number of tool calls
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tool_calls: Option<u64>,
+    /// Maximum time the session's CLI processes ran, in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_wall_clock_secs: Option<u64>,
+    /// Maximum number of file writes (Write, Edit, MultiEdit, NotebookEdit)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_file_writes: Option<u64>,
+    /// Maximum spend, in USD
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_cost_usd: Option<f64>,
+}
+
+fn min_limit<T: PartialOrd + Copy>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if b < a { b } else { a }),
+        (a, b) => a.or(b),
+    }
+}
+
+impl SessionLimits {
+    /// Combine the server default with a session's limits. A session can
+    /// only tighten the server's: each limit takes the lowest value.
+    pub fn for_session(&self, session: Option<&SessionLimits>) -> SessionLimits {
+        let Some(session) = session else {
+            return self.clone();
+        };
+        SessionLimits {
+            max_tool_calls: min_limit(self.max_tool_calls, session.max_tool_calls),
+            max_wall_clock_secs: min_limit(self.max_wall_clock_secs, session.max_wall_clock_secs),
+            max_file_writes: min_limit(self.max_file_writes, session.max_file_writes),
+            max_cost_usd: min_limit(self.max_cost_usd, session.max_cost_usd),
+        }
+    }
+
+    /// Whether any limit is set
+    pub fn is_limited(&self) -> bool {
+        self.max_tool_calls.is_some()
+            || self.max_wall_clock_secs.is_some()
+            || self.max_file_writes.is_some()
+            || self.max_cost_usd.is_some()
+    }
+}
+
+/// A limit a session went over
+#[derive(Debug, Clone, PartialEq)]
+pub enum LimitExceeded {
+    ToolCalls(u64),
+    WallClock(u64),
+    FileWrites(u64),
+    Cost(f64),
+}
+
+impl std::fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ToolCalls(max) => write!(f, "tool call limit reached ({} calls)", max),
+            Self::WallClock(max) => write!(f, "wall-clock limit reached ({}s)", max),
+            Self::FileWrites(max) => write!(f, "file write limit reached ({} writes)", max),
+            Self::Cost(max) => write!(f, "spend limit reached (${:.2})", max),
+        }
+    }
+}
+
+/// What a session consumed so far, shared by its [`LimitsHook`], the stream
+/// loop and the limits task
+#[derive(Debug)]
+pub struct SessionUsage {
+    started_at: Instant,
+    /// Usage of the session's previous CLI processes
+    previous: ChatSessionUsage,
+    tool_calls: AtomicU64,
+    file_writes: AtomicU64,
+    /// `f64` bits of the spend so far, the previous processes' included
+    cost_usd_bits: AtomicU64,
+    /// Limit a tool call was denied for, until the session is killed
+    tripped: Mutex<Option<LimitExceeded>>,
+}
+
+impl Default for SessionUsage {
+    fn default() -> Self {
+        Self::resumed(ChatSessionUsage::default())
+    }
+}
+
+impl SessionUsage {
+    /// Usage of a resumed session, counting on from what its previous CLI
+    /// processes used
+    pub fn resumed(previous: ChatSessionUsage) -> Self {
+        Self {
+            started_at: Instant::now(),
+            tool_calls: AtomicU64::new(previous.tool_calls),
+            file_writes: AtomicU64::new(previous.file_writes),
+            cost_usd_bits: AtomicU64::new(previous.cost_usd.to_bits()),
+            previous,
+            tripped: Mutex::new(None),
+        }
+    }
+
+    /// What the session used so far, to store on the session node
+    pub fn snapshot(&self) -> ChatSessionUsage {
+        ChatSessionUsage {
+            tool_calls: self.tool_calls(),
+            file_writes: self.file_writes(),
+            cost_usd: self.cost_usd(),
+            elapsed_secs: self.elapsed().as_secs(),
+            killed: None,
+        }
+    }
+
+    pub fn tool_calls(&self) -> u64 {
+        self.tool_calls.load(Ordering::Relaxed)
+    }
+
+    pub fn file_writes(&self) -> u64 {
+        self.file_writes.load(Ordering::Relaxed)
+    }
+
+    pub fn cost_usd(&self) -> f64 {
+        f64::from_bits(self.cost_usd_bits.load(Ordering::Relaxed))
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_secs(self.previous.elapsed_secs) + self.started_at.elapsed()
+    }
+
+    /// Record the `total_cost_usd` of a CLI `result` message — the CLI
+    /// reports the running total of its own process, added to what the
+    /// previous processes spent.
+    pub fn record_cost(&self, total_cost_usd: f64) {
+        self.cost_usd_bits.store(
+            (self.previous.cost_usd + total_cost_usd).to_bits(),
+            Ordering::Relaxed,
+        );
+    }
+
+    /// Count a tool call about to run. Returns the limit it would exceed,
+    /// in which case it is not counted and must be denied.
+    pub fn record_tool_call(
+        &self,
+        tool_name: &str,
+        limits: &SessionLimits,
+    ) -> Option<LimitExceeded> {
+        if let Some(max) = limits.max_tool_calls {
+            if self.tool_calls() >= max {
+                return Some(self.trip(LimitExceeded::ToolCalls(max)));
+            }
+        }
+        let is_write = FILE_WRITE_TOOLS.contains(&tool_name);
+        if let (true, Some(max)) = (is_write, limits.max_file_writes) {
+            if self.file_writes() >= max {
+                return Some(self.trip(LimitExceeded::FileWrites(max)));
+            }
+        }
+        self.tool_calls.fetch_add(1, Ordering::Relaxed);
+        if is_write {
+            self.file_writes.fetch_add(1, Ordering::Relaxed);
+        }
+        None
+    }
+
+    fn trip(&self, exceeded: LimitExceeded) -> LimitExceeded {
+        let mut tripped = self.tripped.lock().unwrap_or_else(|e| e.into_inner());
+        tripped.get_or_insert_with(|| exceeded.clone());
+        exceeded
+    }
+
+    /// The limit the session went over, if any
+    pub fn exceeded(&self, limits: &SessionLimits) -> Option<LimitExceeded> {
+        let tripped = self
+            .tripped
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        if tripped.is_some() {
+            return tripped;
+        }
+        if let Some(max) = limits.max_wall_clock_secs {
+            if self.elapsed() >= Duration::from_secs(max) {
+                return Some(LimitExceeded::WallClock(max));
+            }
+        }
+        if let Some(max) = limits.max_cost_usd {
+            if self.cost_usd() >= max {
+                return Some(LimitExceeded::Cost(max));
+            }
+        }
+        None
+    }
+}
+
+// ============================================================================
+// LimitsHook — PreToolUse enforcement
+// ============================================================================
+
+/// In-process PreToolUse hook counting a session's tool calls and denying
+/// the ones over its [`SessionLimits`].
+///
+/// Registered in `create_session()` and `resume_session()` ahead of the other
+/// PreToolUse hooks, for runner sessions too.
+pub(crate) struct LimitsHook {
+    limits: SessionLimits,
+    usage: std::sync::Arc<SessionUsage>,
+}
+
+impl LimitsHook {
+    pub fn new(limits: SessionLimits, usage: std::sync::Arc<SessionUsage>) -> Self {
+        Self { limits, usage }
+    }
+}
+
+#[async_trait::async_trait]
+impl nexus_claude::HookCallback for LimitsHook {
+    async fn execute(
+        &self,
+        input: &nexus_claude::HookInput,
+        _tool_use_id: Option<&str>,
+        _context: &nexus_claude::HookContext,
+    ) -> std::result::Result<nexus_claude::HookJSONOutput, nexus_claude::SdkError> {
+        let denied = match input {
+            nexus_claude::HookInput::PreToolUse(pre_tool) => self
+                .usage
+                .record_tool_call(&pre_tool.tool_name, &self.limits)
+                .inspect(|exceeded| {
+                    info!(tool = %pre_tool.tool_name, %exceeded, "Session limit denied tool call");
+                }),
+            _ => None,
+        };
+        let hook_specific_output = denied.map(|exceeded| {
+            nexus_claude::HookSpecificOutput::PreToolUse(
+                nexus_claude::PreToolUseHookSpecificOutput {
+                    permission_decision: Some("deny".to_string()),
+                    permission_decision_reason: Some(format!(
+                        "Session {}: the session is being stopped",
+                        exceeded
+                    )),
+                    updated_input: None,
+                    additional_context: None,
+                },
+            )
+        });
+        Ok(nexus_claude::HookJSONOutput::Sync(
+            nexus_claude::SyncHookJSONOutput {
+                continue_: Some(true),
+                hook_specific_output,
+                ..Default::default()
+            },
+        ))
+    }
+}
+
+/// Add the hook of a session's limits to its hooks, ahead of the other
+/// PreToolUse hooks. No-op when the session is not limited.
+pub(crate) fn register_hook(
+    hooks: &mut std::collections::HashMap<String, Vec<nexus_claude::HookMatcher>>,
+    limits: &SessionLimits,
+    usage: std::sync::Arc<SessionUsage>,
+) {
+    if !limits.is_limited() {
+        return;
+    }
+    hooks.entry("PreToolUse".to_string()).or_default().insert(
+        0,
+        nexus_claude::HookMatcher {
+            matcher: None,
+            hooks: vec![std::sync::Arc::new(LimitsHook::new(limits.clone(), usage))],
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_session_only_tightens() {
+        let server = SessionLimits {
+            max_tool_calls: Some(500),
+            max_cost_usd: Some(5.0),
+            ..Default::default()
+        };
+        let session = SessionLimits {
+            max_tool_calls: Some(1000),
+            max_file_writes: Some(20),
+            max_cost_usd: Some(1.5),
+            ..Default::default()
+        };
+        let merged = server.for_session(Some(&session));
+        assert_eq!(merged.max_tool_calls, Some(500));
+        assert_eq!(merged.max_file_writes, Some(20));
+        assert_eq!(merged.max_wall_clock_secs, None);
+        assert_eq!(merged.max_cost_usd, Some(1.5));
+        assert_eq!(server.for_session(None), server);
+        assert!(!SessionLimits::default().is_limited());
+    }
+
+    #[test]
+    fn test_tool_call_over_limit_is_denied_and_trips() {
+        let limits = SessionLimits {
+            max_tool_calls: Some(2),
+            ..Default::default()
+        };
+        let usage = SessionUsage::default();
+        assert_eq!(usage.record_tool_call("Read", &limits), None);
+        assert_eq!(usage.record_tool_call("Bash", &limits), None);
+        assert_eq!(usage.exceeded(&limits), None);
+
+        assert_eq!(
+            usage.record_tool_call("Read", &limits),
+            Some(LimitExceeded::ToolCalls(2))
+        );
+        assert_eq!(usage.tool_calls(), 2);
+        assert_eq!(usage.exceeded(&limits), Some(LimitExceeded::ToolCalls(2)));
+    }
+
+    #[test]
+    fn test_only_write_tools_count_as_file_writes() {
+        let limits = SessionLimits {
+            max_file_writes: Some(1),
+            ..Default::default()
+        };
+        let usage = SessionUsage::default();
+        assert_eq!(usage.record_tool_call("Edit", &limits), None);
+        assert_eq!(usage.record_tool_call("Read", &limits), None);
+        assert_eq!(usage.record_tool_call("Grep", &limits), None);
+        assert_eq!(
+            usage.record_tool_call("Write", &limits),
+            Some(LimitExceeded::FileWrites(1))
+        );
+        assert_eq!(usage.file_writes(), 1);
+        assert_eq!(usage.tool_calls(), 3);
+    }
+
+    #[test]
+    fn test_cost_and_wall_clock_limits() {
+        let usage = SessionUsage::default();
+        let cost = SessionLimits {
+            max_cost_usd: Some(1.0),
+            ..Default::default()
+        };
+        usage.record_cost(0.4);
+        assert_eq!(usage.exceeded(&cost), None);
+        usage.record_cost(1.2);
+        assert_eq!(usage.exceeded(&cost), Some(LimitExceeded::Cost(1.0)));
+
+        let wall_clock = SessionLimits {
+            max_wall_clock_secs: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(
+            usage.exceeded(&wall_clock),
+            Some(LimitExceeded::WallClock(0))
+        );
+        assert_eq!(usage.exceeded(&SessionLimits::default()), None);
+    }
+
+    #[test]
+    fn test_resumed_usage_counts_on() {
+        let limits = SessionLimits {
+            max_tool_calls: Some(3),
+            max_cost_usd: Some(1.0),
+            ..Default::default()
+        };
+        let usage = SessionUsage::resumed(ChatSessionUsage {
+            tool_calls: 2,
+            file_writes: 1,
+            cost_usd: 0.8,
+            elapsed_secs: 600,
+            killed: None,
+        });
+        assert_eq!(usage.record_tool_call("Write", &limits), None);
+        assert_eq!(
+            usage.record_tool_call("Read", &limits),
+            Some(LimitExceeded::ToolCalls(3))
+        );
+
+        // The new process reports its own total
+        usage.record_cost(0.1);
+        assert!((usage.cost_usd() - 0.9).abs() < 1e-9);
+        usage.record_cost(0.3);
+        let snapshot = usage.snapshot();
+        assert_eq!((snapshot.tool_calls, snapshot.file_writes), (3, 2));
+        assert!((snapshot.cost_usd - 1.1).abs() < 1e-9);
+        assert!(snapshot.elapsed_secs >= 600);
+        assert_eq!(snapshot.killed, None);
+    }
+
+    #[test]
+    fn test_limits_yaml_roundtrip() {
+        let limits: SessionLimits =
+            serde_yaml::from_str("max_tool_calls: 200\nmax_cost_usd: 2.5\n").unwrap();
+        assert_eq!(limits.max_tool_calls, Some(200));
+        assert_eq!(limits.max_cost_usd, Some(2.5));
+        let json = serde_json::to_string(&limits).unwrap();
+        assert_eq!(json, r#"{"max_tool_calls":200,"max_cost_usd":2.5}"#);
+    }
+}
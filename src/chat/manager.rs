@@ -10,7 +10,10 @@
 //! - A cleanup task periodically closes timed-out sessions
 
 use super::config::ChatConfig;
+use super::limits::{LimitExceeded, SessionLimits, SessionUsage};
+use super::model_routing::{self, RouteInput};
 use super::post_tool_hook;
+use super::sandbox::{Sandbox, SandboxConfig};
 use super::skill_hook;
 use super::types::{
     classify_api_error, truncate_snippet, BackgroundTaskInfo, BackgroundTaskKind, ChatEvent,
@@ -20,6 +23,7 @@ use super::types::{
 use crate::meilisearch::SearchStore;
 use crate::neo4j::models::ChatEventRecord;
 use crate::neo4j::models::ChatSessionNode;
+use crate::neo4j::models::ChatSessionUsage;
 use crate::neo4j::GraphStore;
 use anyhow::{anyhow, bail, Context, Result};
 use futures::StreamExt;
@@ -74,6 +78,11 @@ pub(crate) const CANCEL_TASK_CAP: u32 = 30;
 /// Rolling window for the cancel-task rate cap (300s = 5 minutes).
 pub(crate) const CANCEL_TASK_WINDOW_SECS: u64 = 300;
 
+/// Interval at which `start_limits_task` checks every active session
+/// against its `SessionLimits`. Bounds how long a session can stay over
+/// its wall-clock or spend limit before being killed.
+pub(crate) const LIMITS_CHECK_INTERVAL_SECS: u64 = 2;
+
 /// Grace period applied between marking a `BackgroundTaskInfo` for removal
 /// (`pending_removal_at`) and physically purging it from the
 /// `active_background_tasks` map. 5 seconds absorbs in-flight
@@ -250,6 +259,15 @@ pub struct ActiveSession {
     pub cancel_task_cap: u32,
     /// Rolling window for the cancel_task rate cap. Defaults to 300s (5 min).
     pub cancel_task_window: Duration,
+    /// Resource limits of the session: the server's `chat.limits` combined
+    /// with the session's own. Enforced by `LimitsHook` (tool calls, file
+    /// writes) and `start_limits_task` (wall-clock, spend).
+    pub limits: SessionLimits,
+    /// Usage counted against `limits` since the CLI subprocess started.
+    pub usage: Arc<SessionUsage>,
+    /// Model routed by `chat.model_routing` for summarizing the session
+    /// (None = `PROMPT_BUILDER_MODEL`).
+    pub summarization_model: Option<String>,
 }
 
 /// Result of `ChatManager::cancel_running_tools`. Surfaced to REST/WS
@@ -299,6 +317,34 @@ pub struct CancelTaskResult {
     pub capped: bool,
 }
 
+/// Result of `ChatManager::kill_session`, returned by
+/// `POST /api/chat/sessions/{id}/kill`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct KillSessionResult {
+    /// PID of the Claude Code CLI subprocess that was killed. `None` when
+    /// the platform doesn't support PID capture.
+    pub cli_pid: Option<u32>,
+    /// PIDs (descendants first, then the CLI) that received `SIGKILL`.
+    pub killed_pids: Vec<u32>,
+}
+
+/// A session running on this instance (live CLI subprocess), returned by
+/// `GET /api/chat/sessions/active`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ActiveSessionInfo {
+    pub session_id: String,
+    /// Whether a response is being streamed right now
+    pub is_streaming: bool,
+    pub model: Option<String>,
+    /// Seconds since the session was last active
+    pub idle_secs: u64,
+    /// Usage counted by the session limits (see `chat::limits`)
+    pub elapsed_secs: u64,
+    pub tool_calls: u64,
+    pub file_writes: u64,
+    pub cost_usd: f64,
+}
+
 /// Runtime-mutable environment config for Claude CLI subprocess.
 ///
 /// These fields can be changed at runtime via the REST API and are
@@ -665,6 +711,14 @@ impl ChatManager {
         pipeline.add_parallel_stage(Box::new(super::stages::FileContextStage::new(
             graph.clone(),
         )));
+        // Documentation stage: cites matching sections of the project's Markdown docs.
+        // Controlled by ENRICHMENT_DOCUMENTATION env var (default: true).
+        pipeline.add_parallel_stage(Box::new(super::stages::DocumentationStage::new(
+            search.clone(),
+        )));
+        // Schema stage: injects the columns of SQL tables mentioned in the message.
+        // Controlled by ENRICHMENT_SCHEMA env var (default: true).
+        pipeline.add_parallel_stage(Box::new(super::stages::SchemaStage::new(graph.clone())));
         // Reflex stage: injects scar warnings, episode recall, co-change reminders
         // from the autonomous learning loop (T1→T2→T3 materialized knowledge).
         // Controlled by ENRICHMENT_REFLEX env var (default: true).
@@ -1619,9 +1673,9 @@ impl ChatManager {
                                     session_id: uuid,
                                     seq: next_seq.fetch_add(1, Ordering::SeqCst),
                                     event_type: "user_message".to_string(),
-                                    data: serde_json::to_string(&ChatEvent::UserMessage {
-                                        content: message.to_string(),
-                                    })
+                                    data: serde_json::to_string(&ChatEvent::user_message(
+                                        message.to_string(),
+                                    ))
                                     .unwrap_or_default(),
                                     created_at: chrono::Utc::now(),
                                 };
@@ -1629,9 +1683,7 @@ impl ChatManager {
                             }
 
                             // Broadcast user_message locally + NATS
-                            let user_msg_event = ChatEvent::UserMessage {
-                                content: message.clone(),
-                            };
+                            let user_msg_event = ChatEvent::user_message(message.clone());
                             let _ = events_tx.send(user_msg_event.clone());
                             nats.publish_chat_event(&session_id, user_msg_event);
 
@@ -1714,6 +1766,46 @@ impl ChatManager {
             .unwrap_or_else(|| self.config.default_model.clone())
     }
 
+    /// Route the model of a new session: request > routing rules > config default.
+    ///
+    /// The task kind is classified from the session's task metadata (runner
+    /// task tags, protocol state) and its first message, see
+    /// [`model_routing::classify`].
+    pub fn route_model(&self, request: &ChatRequest) -> String {
+        if let Some(ref model) = request.model {
+            return model.clone();
+        }
+        let tags = request
+            .runner_context
+            .as_ref()
+            .map(|ctx| ctx.task_tags.as_slice())
+            .unwrap_or_default();
+        let protocol_state = request
+            .spawned_by
+            .as_deref()
+            .and_then(parse_spawned_by)
+            .and_then(|ctx| ctx.protocol_state);
+        let task = model_routing::classify(
+            &request.message,
+            tags,
+            protocol_state.as_deref(),
+            request.runner_context.is_some(),
+        );
+        let input = RouteInput {
+            task,
+            tags,
+            message_chars: request.message.chars().count(),
+            project_slug: request.project_slug.as_deref(),
+        };
+        match self.config.model_routing.route(&input) {
+            Some(model) => {
+                info!("Routed {:?} session to model {}", task, model);
+                model.to_string()
+            }
+            None => self.config.default_model.clone(),
+        }
+    }
+
     /// Build the system prompt with project context.
     ///
     /// Modular architecture via FsmPromptComposer:
@@ -2027,10 +2119,50 @@ impl ChatManager {
         self.active_sessions.read().await.contains_key(session_id)
     }
 
+    /// IDs of the sessions currently active
+    pub async fn active_session_ids(&self) -> Vec<String> {
+        self.active_sessions.read().await.keys().cloned().collect()
+    }
+
+    /// Snapshot of the sessions currently active, most recently active first
+    pub async fn active_sessions_info(&self) -> Vec<ActiveSessionInfo> {
+        let mut sessions: Vec<ActiveSessionInfo> = self
+            .active_sessions
+            .read()
+            .await
+            .iter()
+            .map(|(id, s)| ActiveSessionInfo {
+                session_id: id.clone(),
+                is_streaming: s.is_streaming.load(Ordering::SeqCst),
+                model: s.model.clone(),
+                idle_secs: s.last_activity.elapsed().as_secs(),
+                elapsed_secs: s.usage.elapsed().as_secs(),
+                tool_calls: s.usage.tool_calls(),
+                file_writes: s.usage.file_writes(),
+                cost_usd: s.usage.cost_usd(),
+            })
+            .collect();
+        sessions.sort_by_key(|s| s.idle_secs);
+        sessions
+    }
+
     // ========================================================================
     // ClaudeCodeOptions builder
     // ========================================================================
 
+    /// Sandbox of a session: the server's `chat.sandbox` combined with the
+    /// options the session was created with. None when neither enables it.
+    fn session_sandbox(
+        &self,
+        requested: Option<&SandboxConfig>,
+        cwd: &str,
+        add_dirs: &[String],
+        session_id: &str,
+    ) -> Option<Sandbox> {
+        let config = self.config.sandbox.for_session(requested);
+        Sandbox::new(config, cwd, add_dirs, Some(session_id))
+    }
+
     /// Resolve additional directories for the Claude CLI `--add-dir` flag.
     ///
     /// Priority:
@@ -2165,6 +2297,13 @@ impl ChatManager {
         // to tasks/plans without the agent needing to pass session_id explicitly.
         if let Some(sid) = session_id {
             env.insert("PO_SESSION_ID".into(), sid.to_string());
+            // Socket answering the pings of the session's MCP supervisor
+            env.insert(
+                crate::mcp::health::HEALTH_SOCKET_ENV.into(),
+                super::mcp_supervisor::health_socket_path(sid)
+                    .to_string_lossy()
+                    .into_owned(),
+            );
         }
 
         let mcp_config = McpServerConfig::Stdio {
@@ -2197,7 +2336,7 @@ impl ChatManager {
             .include_partial_messages(true)
             .permission_prompt_tool_name("stdio")
             .cli_channel_buffer_size(8192)
-            .add_mcp_server("project-orchestrator", mcp_config);
+            .add_mcp_server(super::mcp_supervisor::MCP_SERVER_NAME, mcp_config);
 
         // Wire allowed/disallowed tool patterns from config
         if !perm_config.allowed_tools.is_empty() {
@@ -2220,6 +2359,15 @@ impl ChatManager {
             builder = builder.add_dir(expand_tilde(dir));
         }
 
+        // Scratch dir of the session's attachments, so the CLI can read files
+        // attached to any later message
+        if let Some(dir) = session_id.and_then(|sid| super::attachments::session_dir(sid).ok()) {
+            match tokio::fs::create_dir_all(&dir).await {
+                Ok(()) => builder = builder.add_dir(dir.to_string_lossy().into_owned()),
+                Err(e) => warn!("Failed to create attachment dir {}: {}", dir.display(), e),
+            }
+        }
+
         // Inject custom PATH and CLI path from runtime-mutable env config
         {
             let env = self.env_config.read().await;
@@ -2471,7 +2619,7 @@ impl ChatManager {
         }
 
         let session_id = Uuid::new_v4();
-        let model = self.resolve_model(request.model.as_deref());
+        let model = self.route_model(request);
 
         // Resolve scaffolding override: explicit field takes priority,
         // fallback to spawned_by JSON if present (for MCP callers)
@@ -2544,6 +2692,14 @@ impl ChatManager {
             } else {
                 Some(resolved_add_dirs.clone())
             },
+            sandbox: request
+                .sandbox
+                .as_ref()
+                .and_then(|sb| serde_json::to_string(sb).ok()),
+            limits: request
+                .limits
+                .as_ref()
+                .and_then(|l| serde_json::to_string(l).ok()),
             spawned_by: request.spawned_by.clone(),
         };
         self.graph
@@ -2577,6 +2733,15 @@ impl ChatManager {
             None => (None, None),
         };
 
+        let sandbox = self.session_sandbox(
+            request.sandbox.as_ref(),
+            &request.cwd,
+            &resolved_add_dirs,
+            &session_id.to_string(),
+        );
+        let limits = self.config.limits.for_session(request.limits.as_ref());
+        let usage = Arc::new(SessionUsage::default());
+
         // Create broadcast channel early so CompactionNotifier can use the sender
         let (events_tx, _) = broadcast::channel(BROADCAST_BUFFER);
 
@@ -2639,6 +2804,15 @@ impl ChatManager {
                 );
             }
 
+            // PreToolUse → SandboxHook confines tools (runner sessions included)
+            if let Some(sandbox) = sandbox.clone() {
+                super::sandbox::register_hook(&mut hooks, sandbox);
+            }
+
+            // PreToolUse → LimitsHook counts tool calls against the session
+            // limits and denies the ones over them (runner sessions included)
+            super::limits::register_hook(&mut hooks, &limits, usage.clone());
+
             hooks
         };
 
@@ -2722,6 +2896,9 @@ impl ChatManager {
         // (Plan 28e9afe3 — without this, kill_descendants always sees
         // None and the per-tool Stop button is a no-op.)
         let child_pid: Option<u32> = client.child_pid().await;
+        if let (Some(sandbox), Some(pid)) = (&sandbox, child_pid) {
+            super::sandbox::apply_resource_limits(&session_id.to_string(), pid, sandbox.config());
+        }
 
         info!(
             session_id = %session_id,
@@ -2819,6 +2996,12 @@ impl ChatManager {
                     cancel_task_history: Arc::new(Mutex::new(VecDeque::new())),
                     cancel_task_cap: CANCEL_TASK_CAP,
                     cancel_task_window: Duration::from_secs(CANCEL_TASK_WINDOW_SECS),
+                    limits: limits.clone(),
+                    usage: usage.clone(),
+                    summarization_model: self
+                        .config
+                        .model_routing
+                        .summarization_model(request.project_slug.as_deref()),
                 },
             );
             interrupt_flag
@@ -2871,6 +3054,19 @@ impl ChatManager {
             nats_cancel.clone(),
         );
 
+        // Supervise the MCP server the CLI spawned: if it crashes, the
+        // session would silently lose every orchestrator tool. Restarts it
+        // with backoff through the CLI and tells the user. Tied to
+        // `nats_cancel` like the poller above.
+        super::mcp_supervisor::spawn_mcp_supervisor(
+            session_id.to_string(),
+            self.config.mcp_server_path.to_string_lossy().to_string(),
+            self.active_sessions.clone(),
+            events_tx.clone(),
+            self.nats.clone(),
+            nats_cancel.clone(),
+        );
+
         // Spawn the permanent out-of-band SDK message listener (T4+T5 of
         // plan 9a1684b2). Captures Messages emitted by the CLI subprocess
         // between turns (background tool notifications, etc.) and
@@ -2905,10 +3101,8 @@ impl ChatManager {
             session_id,
             seq: next_seq.fetch_add(1, Ordering::SeqCst),
             event_type: "user_message".to_string(),
-            data: serde_json::to_string(&ChatEvent::UserMessage {
-                content: request.message.clone(),
-            })
-            .unwrap_or_default(),
+            data: serde_json::to_string(&ChatEvent::user_message(request.message.clone()))
+                .unwrap_or_default(),
             created_at: chrono::Utc::now(),
         };
         let _ = self
@@ -2917,9 +3111,7 @@ impl ChatManager {
             .await;
 
         // Emit user_message on local broadcast + NATS (so all clients see it)
-        let user_msg_event = ChatEvent::UserMessage {
-            content: request.message.clone(),
-        };
+        let user_msg_event = ChatEvent::user_message(request.message.clone());
         let _ = events_tx.send(user_msg_event.clone());
         if let Some(ref nats) = self.nats {
             nats.publish_chat_event(&session_id.to_string(), user_msg_event);
@@ -3616,6 +3808,9 @@ impl ChatManager {
         // Track whether this stream ended with error_max_turns (for auto-continue)
         let mut hit_error_max_turns = false;
 
+        // Context size of the last model calls, from the Result usage (for summarization)
+        let mut context_tokens: u64 = 0;
+
         // Track whether any *productive* tool_use occurred in this stream turn.
         // "Conclusive" tools (git commit/push/status, git tag) don't count — the agent
         // may be wrapping up without actually finishing the task.
@@ -3678,6 +3873,37 @@ impl ChatManager {
 
             if let Some(input) = enrichment_input {
                 let ctx = enrichment_pipeline.execute(&input).await;
+
+                // List the injected notes and symbols so the frontend can
+                // collect context feedback on them
+                let hint_list = |key: &str| -> Vec<String> {
+                    ctx.get_hint(key)
+                        .map(|v| {
+                            v.split(',')
+                                .filter(|s| !s.is_empty())
+                                .map(str::to_string)
+                                .collect()
+                        })
+                        .unwrap_or_default()
+                };
+                let note_ids = hint_list("context_note_ids");
+                let symbols = hint_list("context_symbols");
+                if !note_ids.is_empty() || !symbols.is_empty() {
+                    let event = ChatEvent::ContextItems { note_ids, symbols };
+                    emit_chat(event.clone(), &events_tx, &nats, &session_id);
+                    let record = ChatEventRecord {
+                        id: Uuid::new_v4(),
+                        session_id: input.session_id,
+                        seq: next_seq.fetch_add(1, Ordering::SeqCst),
+                        event_type: event.event_type().to_string(),
+                        data: serde_json::to_string(&event).unwrap_or_default(),
+                        created_at: chrono::Utc::now(),
+                    };
+                    let _ = graph
+                        .store_chat_events(input.session_id, vec![record])
+                        .await;
+                }
+
                 if ctx.has_content() {
                     debug!(
                         "[enrichment] Prompt enriched: {} sections, {}ms (hints: {:?})",
@@ -4096,9 +4322,18 @@ impl ChatManager {
                                 if let Message::Result {
                                     session_id: ref cli_sid,
                                     total_cost_usd: ref cost,
+                                    ref usage,
+                                    num_turns,
                                     ..
                                 } = msg
                                 {
+                                    if let Some(ref usage) = usage {
+                                        context_tokens = super::summarization::context_tokens(
+                                            usage,
+                                            num_turns as u64,
+                                        );
+                                    }
+
                                     // Update Neo4j with cli_session_id and cost
                                     if let Some(uuid) = session_uuid {
                                         let _ = graph
@@ -4115,10 +4350,28 @@ impl ChatManager {
                                     }
 
                                     // Update active session's cli_session_id
-                                    let mut sessions = active_sessions.write().await;
-                                    if let Some(active) = sessions.get_mut(&session_id) {
-                                        active.cli_session_id = Some(cli_sid.clone());
-                                        active.last_activity = Instant::now();
+                                    let usage = {
+                                        let mut sessions = active_sessions.write().await;
+                                        sessions.get_mut(&session_id).map(|active| {
+                                            active.cli_session_id = Some(cli_sid.clone());
+                                            active.last_activity = Instant::now();
+                                            // Checked against `max_cost_usd` by the limits task
+                                            if let Some(cost) = *cost {
+                                                active.usage.record_cost(cost);
+                                            }
+                                            active.usage.snapshot()
+                                        })
+                                    };
+                                    // Stored after every turn, restored on resume
+                                    if let (Some(uuid), Some(usage)) = (session_uuid, usage) {
+                                        if let Err(e) =
+                                            graph.set_chat_session_usage(uuid, Some(&usage)).await
+                                        {
+                                            warn!(
+                                                "Failed to store usage of session {}: {}",
+                                                session_id, e
+                                            );
+                                        }
                                     }
                                 }
 
@@ -4539,7 +4792,15 @@ impl ChatManager {
             .handle_feedback(&assistant_text_parts, &memory_manager, &context_injector)
             .await;
 
-        // 8. Drain pending messages queue
+        // 8. Rolling summarization once the context grows too large
+        post_handler.handle_summarization(context_tokens).await;
+
+        // 8b. Link the commits of a task-bound session to its task
+        post_handler
+            .handle_commit_tracing(had_conclusive_tool_use)
+            .await;
+
+        // 9. Drain pending messages queue
         super::drain::drain_pending_messages(
             has_pending,
             client,
@@ -4722,19 +4983,15 @@ impl ChatManager {
                 session_id: uuid,
                 seq: next_seq.fetch_add(1, Ordering::SeqCst),
                 event_type: "user_message".to_string(),
-                data: serde_json::to_string(&ChatEvent::UserMessage {
-                    content: message.to_string(),
-                })
-                .unwrap_or_default(),
+                data: serde_json::to_string(&ChatEvent::user_message(message.to_string()))
+                    .unwrap_or_default(),
                 created_at: chrono::Utc::now(),
             };
             let _ = self.graph.store_chat_events(uuid, vec![user_event]).await;
         }
 
         // Emit user_message on local broadcast + NATS (visible to all clients)
-        let user_msg_event = ChatEvent::UserMessage {
-            content: message.to_string(),
-        };
+        let user_msg_event = ChatEvent::user_message(message.to_string());
         let _ = events_tx.send(user_msg_event.clone());
         if let Some(ref nats) = self.nats {
             nats.publish_chat_event(session_id, user_msg_event);
@@ -5280,7 +5537,7 @@ impl ChatManager {
         }
 
         // Build options - with resume flag only if we have a cli_session_id
-        let (system_prompt, _included_note_ids) = self
+        let (mut system_prompt, _included_note_ids) = self
             .build_system_prompt(
                 session_node.project_slug.as_deref(),
                 message,
@@ -5290,6 +5547,58 @@ impl ChatManager {
             )
             .await;
 
+        // Seed the summary of a summarized session (and, for a fresh CLI session,
+        // the messages since) — see summarization.rs
+        if let Some(section) = super::summarization::memory_prompt_section(
+            self.graph.as_ref(),
+            uuid,
+            cli_session_id.is_none(),
+        )
+        .await
+        {
+            system_prompt.push_str(&section);
+        }
+
+        let resume_add_dirs = session_node.add_dirs.clone().unwrap_or_default();
+        let requested_sandbox: Option<SandboxConfig> = session_node
+            .sandbox
+            .as_deref()
+            .and_then(|sb| serde_json::from_str(sb).ok());
+        let sandbox = self.session_sandbox(
+            requested_sandbox.as_ref(),
+            &session_node.cwd,
+            &resume_add_dirs,
+            session_id,
+        );
+        let requested_limits: Option<SessionLimits> = session_node
+            .limits
+            .as_deref()
+            .and_then(|l| serde_json::from_str(l).ok());
+        let limits = self.config.limits.for_session(requested_limits.as_ref());
+        // Limits cover the whole session: count on from the previous CLI
+        // processes, and keep a killed session down until it is reset
+        let previous_usage = self
+            .graph
+            .get_chat_session_usage(uuid)
+            .await
+            .context("Failed to fetch session usage")?
+            .unwrap_or_default();
+        if let Some(ref killed) = previous_usage.killed {
+            bail!(
+                "Session {} was killed ({}); reset its usage to resume it",
+                session_id,
+                killed
+            );
+        }
+        let usage = Arc::new(SessionUsage::resumed(previous_usage));
+        if let Some(exceeded) = usage.exceeded(&limits) {
+            bail!(
+                "Session {} is over its limits ({}); reset its usage to resume it",
+                session_id,
+                exceeded
+            );
+        }
+
         // Create broadcast channel early so CompactionNotifier can use the sender
         let (events_tx, _) = broadcast::channel(BROADCAST_BUFFER);
 
@@ -5341,10 +5650,17 @@ impl ChatManager {
                 }],
             );
 
+            // PreToolUse → SandboxHook confines tools
+            if let Some(sandbox) = sandbox.clone() {
+                super::sandbox::register_hook(&mut hooks, sandbox);
+            }
+
+            // PreToolUse → LimitsHook enforces the session limits
+            super::limits::register_hook(&mut hooks, &limits, usage.clone());
+
             hooks
         };
 
-        let resume_add_dirs = session_node.add_dirs.clone().unwrap_or_default();
         let options = self
             .build_options(
                 &session_node.cwd,
@@ -5389,6 +5705,9 @@ impl ChatManager {
         // CLI subprocess PID for descendant SIGINT cascade (T1+T2 of
         // plan 28e9afe3). Same as create_session.
         let child_pid: Option<u32> = client.child_pid().await;
+        if let (Some(sandbox), Some(pid)) = (&sandbox, child_pid) {
+            super::sandbox::apply_resource_limits(session_id, pid, sandbox.config());
+        }
 
         let client = Arc::new(Mutex::new(client));
 
@@ -5506,6 +5825,12 @@ impl ChatManager {
                     cancel_task_history: Arc::new(Mutex::new(VecDeque::new())),
                     cancel_task_cap: CANCEL_TASK_CAP,
                     cancel_task_window: Duration::from_secs(CANCEL_TASK_WINDOW_SECS),
+                    limits: limits.clone(),
+                    usage: usage.clone(),
+                    summarization_model: self
+                        .config
+                        .model_routing
+                        .summarization_model(session_node.project_slug.as_deref()),
                 },
             );
             interrupt_flag
@@ -5558,6 +5883,17 @@ impl ChatManager {
             nats_cancel.clone(),
         );
 
+        // MCP server supervisor — same as `create_session`, watching the
+        // MCP server of the new CLI subprocess.
+        super::mcp_supervisor::spawn_mcp_supervisor(
+            session_id.to_string(),
+            self.config.mcp_server_path.to_string_lossy().to_string(),
+            self.active_sessions.clone(),
+            events_tx.clone(),
+            self.nats.clone(),
+            nats_cancel.clone(),
+        );
+
         // Spawn the permanent out-of-band SDK message listener (T4+T5 of
         // plan 9a1684b2). Same as `create_session` — the resumed session
         // needs its own listener bound to the new InteractiveClient. The
@@ -5588,18 +5924,14 @@ impl ChatManager {
             session_id: uuid,
             seq: next_seq.fetch_add(1, Ordering::SeqCst),
             event_type: "user_message".to_string(),
-            data: serde_json::to_string(&ChatEvent::UserMessage {
-                content: message.to_string(),
-            })
-            .unwrap_or_default(),
+            data: serde_json::to_string(&ChatEvent::user_message(message.to_string()))
+                .unwrap_or_default(),
             created_at: chrono::Utc::now(),
         };
         let _ = self.graph.store_chat_events(uuid, vec![user_event]).await;
 
         // Emit user_message on local broadcast + NATS
-        let user_msg_event = ChatEvent::UserMessage {
-            content: message.to_string(),
-        };
+        let user_msg_event = ChatEvent::user_message(message.to_string());
         let _ = events_tx.send(user_msg_event.clone());
         if let Some(ref nats) = self.nats {
             nats.publish_chat_event(session_id, user_msg_event);
@@ -5732,9 +6064,7 @@ impl ChatManager {
                         "assistant_text"
                     };
                     let chat_event = if msg.role == "user" {
-                        ChatEvent::UserMessage {
-                            content: msg.content.clone(),
-                        }
+                        ChatEvent::user_message(msg.content.clone())
                     } else {
                         ChatEvent::AssistantText {
                             content: msg.content.clone(),
@@ -6569,7 +6899,7 @@ impl ChatManager {
     /// This is used by `interrupt()` to kill tool subprocesses (find, sleep, etc.)
     /// without killing the CLI itself (which would break the session).
     #[cfg(unix)]
-    fn get_descendant_pids(pid: u32) -> Vec<u32> {
+    pub(crate) fn get_descendant_pids(pid: u32) -> Vec<u32> {
         fn get_children(pid: u32) -> Vec<u32> {
             std::process::Command::new("pgrep")
                 .args(["-P", &pid.to_string()])
@@ -6596,7 +6926,7 @@ impl ChatManager {
     /// is currently Unix-only, so we no-op cleanly on Windows: callers
     /// receive an empty Vec and skip the descendant-aware code paths.
     #[cfg(windows)]
-    fn get_descendant_pids(_pid: u32) -> Vec<u32> {
+    pub(crate) fn get_descendant_pids(_pid: u32) -> Vec<u32> {
         Vec::new()
     }
 
@@ -6807,6 +7137,125 @@ impl ChatManager {
         }
     }
 
+    /// Send `SIGKILL` to every descendant of `root_pid`, then to `root_pid`
+    /// itself. Returns the PIDs that received the signal.
+    ///
+    /// The hard counterpart of `kill_subtree`, for `kill_session`: the tree
+    /// is snapshotted before any signal so descendants can't escape by being
+    /// reparented when their parent dies first. No-op on non-unix platforms.
+    fn kill_tree_hard(root_pid: u32) -> Vec<u32> {
+        #[cfg(unix)]
+        {
+            let mut all_pids = Self::get_descendant_pids(root_pid);
+            all_pids.push(root_pid);
+
+            let mut killed = Vec::with_capacity(all_pids.len());
+            for &pid in &all_pids {
+                // SAFETY: libc::kill is FFI; ESRCH (already exited) is ignored.
+                let rc = unsafe { libc::kill(pid as i32, libc::SIGKILL) };
+                if rc == 0 {
+                    killed.push(pid);
+                } else {
+                    let errno = std::io::Error::last_os_error();
+                    if errno.raw_os_error() != Some(libc::ESRCH) {
+                        warn!(pid = pid, error = ?errno, "kill_tree_hard: SIGKILL failed");
+                    }
+                }
+            }
+            killed
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = root_pid;
+            Vec::new()
+        }
+    }
+
+    /// Kill a session outright — the runaway-agent kill switch.
+    ///
+    /// Unlike `interrupt()` or `cancel_running_tools()`, nothing is asked to
+    /// wind down: the CLI subprocess and all its descendants get `SIGKILL`
+    /// and the session is closed. Emits a `SessionError` with reason
+    /// `"session_killed"`, or `"session_limit_exceeded"` when `exceeded`
+    /// is the limit that triggered it. The session is marked as killed in
+    /// its stored usage: it refuses new messages until
+    /// `reset_session_usage()`.
+    ///
+    /// Returns `None` when the session is not active on this instance.
+    pub async fn kill_session(
+        &self,
+        session_id: &str,
+        exceeded: Option<LimitExceeded>,
+    ) -> Result<Option<KillSessionResult>> {
+        let Some((child_pid, events_tx, nats_cancel, interrupt_flag, interrupt_token, usage)) = ({
+            let sessions = self.active_sessions.read().await;
+            sessions.get(session_id).map(|s| {
+                (
+                    s.child_pid,
+                    s.events_tx.clone(),
+                    s.nats_cancel.clone(),
+                    s.interrupt_flag.clone(),
+                    s.interrupt_token.clone(),
+                    s.usage.clone(),
+                )
+            })
+        }) else {
+            return Ok(None);
+        };
+
+        // Break the stream loop, and stop the OOB listener and the other
+        // per-session tasks first so the dying CLI is not reported as a crash
+        interrupt_flag.store(true, Ordering::SeqCst);
+        interrupt_token.cancel();
+        nats_cancel.cancel();
+
+        let killed_pids = child_pid.map(Self::kill_tree_hard).unwrap_or_default();
+        warn!(
+            session_id = %session_id,
+            cli_pid = ?child_pid,
+            killed_pids = ?killed_pids,
+            exceeded = ?exceeded,
+            "Killed session"
+        );
+
+        let killed = match exceeded {
+            Some(ref exceeded) => exceeded.to_string(),
+            None => "killed".to_string(),
+        };
+        let event = match exceeded {
+            Some(exceeded) => ChatEvent::SessionError {
+                reason: "session_limit_exceeded".to_string(),
+                message: format!("Session stopped: {}.", exceeded),
+                received_at: chrono::Utc::now(),
+            },
+            None => ChatEvent::SessionError {
+                reason: "session_killed".to_string(),
+                message: "Session killed.".to_string(),
+                received_at: chrono::Utc::now(),
+            },
+        };
+        let _ = events_tx.send(event.clone());
+        if let Some(ref nats) = self.nats {
+            nats.publish_chat_event(session_id, event);
+        }
+
+        self.close_session(session_id).await?;
+        if let Ok(uuid) = Uuid::parse_str(session_id) {
+            let usage = ChatSessionUsage {
+                killed: Some(killed),
+                ..usage.snapshot()
+            };
+            self.graph
+                .set_chat_session_usage(uuid, Some(&usage))
+                .await
+                .context("Failed to mark the session as killed")?;
+        }
+        Ok(Some(KillSessionResult {
+            cli_pid: child_pid,
+            killed_pids,
+        }))
+    }
+
     /// Close an active session: interrupt first, then disconnect and remove.
     ///
     /// T3 fix (Gap 5): call interrupt() BEFORE removing the session from
@@ -6821,7 +7270,7 @@ impl ChatManager {
         tokio::time::sleep(std::time::Duration::from_millis(50)).await;
 
         // 3. Remove session from active map
-        let (client, protocol_run_id, protocol_state) = {
+        let (client, protocol_run_id, protocol_state, usage) = {
             let mut sessions = self.active_sessions.write().await;
             let session = sessions
                 .remove(session_id)
@@ -6830,9 +7279,21 @@ impl ChatManager {
                 session.client,
                 session.protocol_run_id,
                 session.protocol_state,
+                session.usage,
             )
         };
 
+        // Store the usage since the last turn, for the next resume
+        if let Ok(uuid) = Uuid::parse_str(session_id) {
+            if let Err(e) = self
+                .graph
+                .set_chat_session_usage(uuid, Some(&usage.snapshot()))
+                .await
+            {
+                warn!("Failed to store usage of session {}: {}", session_id, e);
+            }
+        }
+
         // 4. Finalize trajectory — fire-and-forget (non-blocking)
         //    Uses end_session_auto() so the collector computes the reward from
         //    actual buffered DecisionRecords (tool success rate, confidence, duration).
@@ -6906,6 +7367,55 @@ impl ChatManager {
         });
     }
 
+    /// Clear the stored usage of an inactive session: it resumes with fresh
+    /// counters, and accepts messages again if it was killed.
+    ///
+    /// Returns the usage cleared, `None` when the session does not exist.
+    pub async fn reset_session_usage(&self, session_id: &str) -> Result<Option<ChatSessionUsage>> {
+        let uuid = Uuid::parse_str(session_id).context("Invalid session ID")?;
+        if self.is_session_active(session_id).await {
+            bail!("Session {} is active", session_id);
+        }
+        if self.graph.get_chat_session(uuid).await?.is_none() {
+            return Ok(None);
+        }
+        let usage = self.graph.get_chat_session_usage(uuid).await?;
+        self.graph.set_chat_session_usage(uuid, None).await?;
+        info!("Reset the usage of session {}", session_id);
+        Ok(Some(usage.unwrap_or_default()))
+    }
+
+    /// Start a background task that kills sessions over their limits.
+    ///
+    /// `LimitsHook` denies the tool calls and file writes over a session's
+    /// `SessionLimits` as they happen, and this task kills the session. It
+    /// also checks the wall-clock and spend limits, which no hook sees.
+    pub fn start_limits_task(self: &Arc<Self>) {
+        let manager = Arc::clone(self);
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(Duration::from_secs(LIMITS_CHECK_INTERVAL_SECS));
+            loop {
+                ticker.tick().await;
+
+                let exceeded: Vec<(String, LimitExceeded)> = {
+                    let sessions = manager.active_sessions.read().await;
+                    sessions
+                        .iter()
+                        .filter_map(|(id, s)| Some((id.clone(), s.usage.exceeded(&s.limits)?)))
+                        .collect()
+                };
+
+                for (id, limit) in exceeded {
+                    info!("Session {} is over its limits ({}), killing it", id, limit);
+                    if let Err(e) = manager.kill_session(&id, Some(limit)).await {
+                        warn!("Failed to kill session {}: {}", id, e);
+                    }
+                }
+            }
+        });
+    }
+
     /// Get the number of currently active sessions
     pub async fn active_session_count(&self) -> usize {
         self.active_sessions.read().await.len()
@@ -7037,6 +7547,9 @@ mod tests {
             jwt_secret: None,
             server_port: 8080,
             session_token_expiry_secs: 86400,
+            sandbox: Default::default(),
+            limits: Default::default(),
+            model_routing: Default::default(),
         }
     }
 
@@ -7059,6 +7572,62 @@ mod tests {
         assert_eq!(manager.resolve_model(None), "claude-sonnet-4-6");
     }
 
+    #[test]
+    fn test_route_model() {
+        use crate::chat::model_routing::{ModelRule, TaskKind};
+
+        let rule = |task, model: &str| ModelRule {
+            task: Some(task),
+            tags: Vec::new(),
+            min_message_chars: None,
+            max_message_chars: None,
+            model: model.to_string(),
+        };
+        let mut config = test_config();
+        config.model_routing.rules = vec![
+            rule(TaskKind::Planning, "claude-opus-4-6"),
+            rule(TaskKind::QuickEdit, "claude-haiku-4-5"),
+        ];
+        config
+            .model_routing
+            .projects
+            .insert("my-api".into(), vec![rule(TaskKind::QuickEdit, "local")]);
+        let state = mock_app_state();
+        let manager = ChatManager::new_without_memory(state.neo4j, state.meili, config);
+
+        let request = |message: &str| -> ChatRequest {
+            serde_json::from_value(serde_json::json!({ "message": message, "cwd": "/tmp" }))
+                .unwrap()
+        };
+        assert_eq!(
+            manager.route_model(&request("Let's plan the v2 API")),
+            "claude-opus-4-6"
+        );
+        assert_eq!(
+            manager.route_model(&request("Rename foo")),
+            "claude-haiku-4-5"
+        );
+        // No rule for plain chat → config default
+        let long = "word ".repeat(100);
+        assert_eq!(manager.route_model(&request(&long)), "claude-sonnet-4-6");
+
+        // Project rules come first
+        let mut quick = request("Rename foo");
+        quick.project_slug = Some("my-api".into());
+        assert_eq!(manager.route_model(&quick), "local");
+
+        // Protocol state takes precedence over the message
+        let mut planning = request("Rename foo");
+        planning.spawned_by =
+            Some(r#"{"type":"protocol","protocol_state":"planning"}"#.to_string());
+        assert_eq!(manager.route_model(&planning), "claude-opus-4-6");
+
+        // An explicit model always wins
+        let mut explicit = request("Let's plan the v2 API");
+        explicit.model = Some("claude-sonnet-4-6".into());
+        assert_eq!(manager.route_model(&explicit), "claude-sonnet-4-6");
+    }
+
     #[tokio::test]
     async fn test_build_options_uses_config_permission_default() {
         // Default config uses "default" permission mode (safe-by-default)
@@ -7508,6 +8077,33 @@ mod tests {
         assert!(result.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_session_sandbox() {
+        let state = mock_app_state();
+        let mut config = test_config();
+        let session_id = Uuid::new_v4().to_string();
+        let manager = ChatManager::new_without_memory(
+            state.neo4j.clone(),
+            state.meili.clone(),
+            config.clone(),
+        );
+        assert!(manager
+            .session_sandbox(None, "/tmp", &[], &session_id)
+            .is_none());
+
+        config.sandbox.enabled = true;
+        let manager = ChatManager::new_without_memory(state.neo4j, state.meili, config);
+        let requested = SandboxConfig {
+            no_network: true,
+            ..Default::default()
+        };
+        let sandbox = manager
+            .session_sandbox(Some(&requested), "/tmp", &[], &session_id)
+            .unwrap();
+        assert!(sandbox.config().enabled && sandbox.config().no_network);
+        assert!(sandbox.roots().iter().any(|r| r.ends_with(&session_id)));
+    }
+
     #[tokio::test]
     async fn test_build_options_with_add_dirs() {
         let state = mock_app_state();
@@ -7572,6 +8168,9 @@ mod tests {
             Some("abc-def-123"),
             "PO_SESSION_ID should be injected into MCP server env when session_id is provided"
         );
+        assert!(mcp_env
+            .get("PO_MCP_HEALTH_SOCKET")
+            .is_some_and(|path| path.ends_with("po-mcp-abc-def-123.sock")));
     }
 
     #[tokio::test]
@@ -8245,6 +8844,52 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_killed_session_refuses_resume_until_reset() {
+        let state = mock_app_state();
+        let session = test_chat_session(None);
+        state.neo4j.create_chat_session(&session).await.unwrap();
+        let usage = ChatSessionUsage {
+            tool_calls: 500,
+            killed: Some("tool call limit reached (500 calls)".to_string()),
+            ..Default::default()
+        };
+        state
+            .neo4j
+            .set_chat_session_usage(session.id, Some(&usage))
+            .await
+            .unwrap();
+
+        let manager =
+            ChatManager::new_without_memory(state.neo4j.clone(), state.meili, test_config());
+        let id = session.id.to_string();
+
+        let err = manager
+            .resume_session(&id, "hello", None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("was killed"), "{}", err);
+
+        assert_eq!(manager.reset_session_usage(&id).await.unwrap(), Some(usage));
+        assert_eq!(
+            state
+                .neo4j
+                .get_chat_session_usage(session.id)
+                .await
+                .unwrap(),
+            None
+        );
+        // Fails further on without a CLI, but no longer for being killed
+        let err = manager
+            .resume_session(&id, "hello", None)
+            .await
+            .unwrap_err();
+        assert!(!err.to_string().contains("was killed"), "{}", err);
+
+        let unknown = Uuid::new_v4().to_string();
+        assert_eq!(manager.reset_session_usage(&unknown).await.unwrap(), None);
+    }
+
     // ====================================================================
     // ChatSession CRUD via GraphStore (mock)
     // ====================================================================
@@ -8402,6 +9047,8 @@ mod tests {
             preview: Some("Hello, can you help me with this?".into()),
             permission_mode: None,
             add_dirs: None,
+            sandbox: None,
+            limits: None,
             spawned_by: None,
         };
 
@@ -8484,6 +9131,7 @@ mod tests {
                 event_type: "user_message".into(),
                 data: serde_json::to_string(&ChatEvent::UserMessage {
                     content: "List my plans".into(),
+                    attachments: vec![],
                 })
                 .unwrap(),
                 created_at: chrono::Utc::now(),
@@ -8761,6 +9409,8 @@ mod tests {
             preview: None,
             permission_mode: None,
             add_dirs: None,
+            sandbox: None,
+            limits: None,
             spawned_by: None,
         };
 
@@ -8792,6 +9442,8 @@ mod tests {
             preview: None,
             permission_mode: None,
             add_dirs: None,
+            sandbox: None,
+            limits: None,
             spawned_by: None,
         };
 
@@ -8881,6 +9533,9 @@ mod tests {
             cancel_task_history: Arc::new(Mutex::new(VecDeque::new())),
             cancel_task_cap: CANCEL_TASK_CAP,
             cancel_task_window: Duration::from_secs(CANCEL_TASK_WINDOW_SECS),
+            limits: Default::default(),
+            usage: Default::default(),
+            summarization_model: None,
         };
 
         Some((session, pending_messages))
@@ -9097,6 +9752,7 @@ mod tests {
             },
             ChatEvent::UserMessage {
                 content: "Hello".into(),
+                attachments: vec![],
             },
         ];
 
@@ -9164,6 +9820,7 @@ mod tests {
     fn test_chat_event_user_message_type() {
         let event = ChatEvent::UserMessage {
             content: "Hello".into(),
+            attachments: vec![],
         };
         assert_eq!(event.event_type(), "user_message");
     }
@@ -9186,13 +9843,14 @@ mod tests {
     fn test_chat_event_user_message_serde_roundtrip() {
         let event = ChatEvent::UserMessage {
             content: "Hello world".into(),
+            attachments: vec![],
         };
         let json = serde_json::to_string(&event).unwrap();
         assert!(json.contains("\"user_message\""));
 
         let deserialized: ChatEvent = serde_json::from_str(&json).unwrap();
         assert!(
-            matches!(deserialized, ChatEvent::UserMessage { ref content } if content == "Hello world")
+            matches!(deserialized, ChatEvent::UserMessage { ref content, .. } if content == "Hello world")
         );
     }
 
@@ -9788,6 +10446,9 @@ mod tests {
             cancel_task_history: Arc::new(Mutex::new(VecDeque::new())),
             cancel_task_cap: CANCEL_TASK_CAP,
             cancel_task_window: Duration::from_secs(CANCEL_TASK_WINDOW_SECS),
+            limits: Default::default(),
+            usage: Default::default(),
+            summarization_model: None,
         };
 
         (session, handle)
@@ -12098,6 +12759,69 @@ mod tests {
         assert!(result.killed_pids.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_kill_session_unknown_session_returns_none() {
+        let state = mock_app_state();
+        let manager = ChatManager::new_without_memory(state.neo4j, state.meili, test_config());
+
+        let result = manager.kill_session("ghost-session", None).await.unwrap();
+        assert!(result.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_kill_session_sigkills_cli_and_closes_session() {
+        let mut child = match std::process::Command::new("sleep").arg("30").spawn() {
+            Ok(c) => c,
+            Err(_) => {
+                eprintln!("Skipping test: sleep not available");
+                return;
+            }
+        };
+        let real_pid = child.id();
+
+        let Some((mut session, _)) = try_create_dummy_session(false, "", vec![]) else {
+            eprintln!("Skipping test: Claude CLI not installed");
+            let _ = child.kill();
+            let _ = child.wait();
+            return;
+        };
+        // Stand-in for the CLI subprocess
+        session.child_pid = Some(real_pid);
+        let mut rx = session.events_tx.subscribe();
+        let nats_cancel = session.nats_cancel.clone();
+
+        let state = mock_app_state();
+        let manager = ChatManager::new_without_memory(state.neo4j, state.meili, test_config());
+        manager
+            .active_sessions
+            .write()
+            .await
+            .insert("s-kill".into(), session);
+
+        let result = manager
+            .kill_session("s-kill", Some(LimitExceeded::ToolCalls(100)))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.cli_pid, Some(real_pid));
+        assert!(result.killed_pids.contains(&real_pid));
+        assert!(nats_cancel.is_cancelled());
+        assert!(!manager.active_sessions.read().await.contains_key("s-kill"));
+
+        match rx.recv().await.unwrap() {
+            ChatEvent::SessionError {
+                reason, message, ..
+            } => {
+                assert_eq!(reason, "session_limit_exceeded");
+                assert!(message.contains("tool call limit"));
+            }
+            other => panic!("expected SessionError, got {:?}", other),
+        }
+
+        let exit = child.wait().expect("waitpid");
+        assert!(!exit.success(), "subprocess should have been SIGKILLed");
+    }
+
     #[tokio::test]
     async fn test_cancel_task_rate_cap_enforced() {
         let Some((mut session, _)) = try_create_dummy_session(false, "", vec![]) else {
@@ -0,0 +1,423 @@
+//! MCP server supervision — restarts a crashed `mcp_server` of a session.
+//!
+//! The CLI spawns the `mcp_server` binary configured in
+//! `ChatManager::build_options` and, when that process dies, silently drops
+//! its tools for the rest of the session. Each session gets a supervisor
+//! task that checks every [`MCP_HEALTH_INTERVAL`] that the server still runs
+//! under the CLI and still answers an MCP `ping` on its health socket
+//! ([`crate::mcp::health`]) within [`MCP_PING_TIMEOUT`]. When it is gone or
+//! hung, the supervisor asks the CLI to reconnect it with an `mcp_reconnect`
+//! control request: the CLI respawns the server and redoes the MCP
+//! initialize handshake.
+//!
+//! Restarts back off exponentially, and each one emits a
+//! `ChatEvent::McpServerRestarted` so the user knows why tool calls failed.
+//! After [`MCP_MAX_RESTARTS`] attempts without the server staying up, the
+//! supervisor gives up with a `SessionError` (`"mcp_server_unavailable"`).
+//!
+//! Unix only: the process checks rely on `pgrep` / `ps` and the ping on a
+//! Unix socket. Elsewhere the health check is inconclusive and the
+//! supervisor never restarts anything.
+
+use super::manager::ActiveSession;
+use super::types::ChatEvent;
+use crate::mcp::health::{ping, PingOutcome};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, RwLock};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Name under which the orchestrator MCP server is registered with the CLI.
+pub(crate) const MCP_SERVER_NAME: &str = "project-orchestrator";
+
+/// Delay between two health checks of a session's MCP server.
+const MCP_HEALTH_INTERVAL: Duration = Duration::from_secs(15);
+
+/// How long the server has to answer a ping. It answers between two tool
+/// calls, so this is longer than the REST client timeout of a tool call
+/// (120s): a server busy with a slow one is not mistaken for a hung one.
+const MCP_PING_TIMEOUT: Duration = Duration::from_secs(150);
+
+/// Delay before the first restart; doubles on each further attempt.
+const MCP_RESTART_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Upper bound of the restart delay.
+const MCP_RESTART_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Restart attempts before giving up on the session's MCP server.
+pub(crate) const MCP_MAX_RESTARTS: u32 = 5;
+
+/// How long the server must stay up for the restart budget to refill.
+const MCP_HEALTHY_RESET: Duration = Duration::from_secs(300);
+
+/// Restart budget and backoff of one session's MCP server.
+#[derive(Debug, Default)]
+pub(crate) struct RestartBackoff {
+    attempts: u32,
+    healthy_since: Option<Instant>,
+}
+
+impl RestartBackoff {
+    /// Book the next restart: its attempt number and the delay to wait
+    /// before it. `None` once the budget is exhausted.
+    pub(crate) fn next_restart(&mut self) -> Option<(u32, Duration)> {
+        if self.attempts >= MCP_MAX_RESTARTS {
+            return None;
+        }
+        let delay = MCP_RESTART_BASE_DELAY
+            .saturating_mul(2u32.saturating_pow(self.attempts))
+            .min(MCP_RESTART_MAX_DELAY);
+        self.attempts += 1;
+        self.healthy_since = None;
+        Some((self.attempts, delay))
+    }
+
+    /// Record a health check that found the server running. The budget
+    /// refills once the server has been up for [`MCP_HEALTHY_RESET`].
+    pub(crate) fn record_healthy(&mut self, now: Instant) {
+        let since = *self.healthy_since.get_or_insert(now);
+        if now.saturating_duration_since(since) >= MCP_HEALTHY_RESET {
+            self.attempts = 0;
+        }
+    }
+}
+
+/// State of a session's MCP server found by a health check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Liveness {
+    Alive,
+    /// The process is gone.
+    Gone,
+    /// The process runs but did not answer the ping.
+    Hung,
+    /// Can't be told (see [`mcp_server_running`]).
+    Unknown,
+}
+
+/// Health socket of the MCP server of `session_id`, passed to the server in
+/// `PO_MCP_HEALTH_SOCKET`.
+pub(crate) fn health_socket_path(session_id: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("po-mcp-{}.sock", session_id))
+}
+
+/// Check that the MCP server still runs under the CLI process `cli_pid`,
+/// then that it answers a ping on `socket` within `ping_timeout`.
+///
+/// A server without a health socket (an older `mcp_server` build) is only
+/// checked for being there.
+pub(crate) async fn check_liveness(
+    cli_pid: u32,
+    mcp_path: &str,
+    socket: &Path,
+    ping_timeout: Duration,
+) -> Liveness {
+    let path = mcp_path.to_string();
+    let running = tokio::task::spawn_blocking(move || mcp_server_running(cli_pid, &path))
+        .await
+        .ok()
+        .flatten();
+    match running {
+        Some(true) => {}
+        Some(false) => return Liveness::Gone,
+        None => return Liveness::Unknown,
+    }
+    match ping(socket, ping_timeout).await {
+        PingOutcome::Alive | PingOutcome::NoSocket => Liveness::Alive,
+        PingOutcome::Unresponsive => Liveness::Hung,
+    }
+}
+
+/// Control request asking the CLI to respawn and re-handshake `server`.
+pub(crate) fn mcp_reconnect_request(server: &str) -> String {
+    serde_json::json!({
+        "type": "control_request",
+        "request_id": Uuid::new_v4().to_string(),
+        "request": {
+            "subtype": "mcp_reconnect",
+            "serverName": server
+        }
+    })
+    .to_string()
+}
+
+/// Whether the CLI process `cli_pid` still runs `mcp_path` as a descendant.
+///
+/// `None` when it can't be told: the CLI itself is gone (reported by the
+/// OOB listener, not here) or `ps` is unavailable.
+#[cfg(unix)]
+pub(crate) fn mcp_server_running(cli_pid: u32, mcp_path: &str) -> Option<bool> {
+    process_args(cli_pid)?;
+    let running = super::manager::ChatManager::get_descendant_pids(cli_pid)
+        .into_iter()
+        .filter_map(process_args)
+        // The CLI spawns the configured path as is, so it prefixes the command line
+        .any(|args| args.starts_with(mcp_path));
+    Some(running)
+}
+
+/// Non-Unix stub: descendant discovery is not implemented (see
+/// `ChatManager::get_descendant_pids`), so the check is inconclusive.
+#[cfg(not(unix))]
+pub(crate) fn mcp_server_running(_cli_pid: u32, _mcp_path: &str) -> Option<bool> {
+    None
+}
+
+/// Command line of a process, `None` if it doesn't exist.
+#[cfg(unix)]
+fn process_args(pid: u32) -> Option<String> {
+    let output = std::process::Command::new("ps")
+        .args(["-p", &pid.to_string(), "-o", "args="])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let args = String::from_utf8(output.stdout).ok()?;
+    Some(args.trim().to_string())
+}
+
+/// Spawn the MCP server supervisor of a session.
+///
+/// Terminates when `cancel_token` fires (the session's `nats_cancel`, so
+/// `resume_session` replaces it along with the CLI), when the session is
+/// no longer active, or after giving up on the server.
+pub(crate) fn spawn_mcp_supervisor(
+    session_id: String,
+    mcp_path: String,
+    active_sessions: Arc<RwLock<HashMap<String, ActiveSession>>>,
+    events_tx: broadcast::Sender<ChatEvent>,
+    nats: Option<Arc<crate::events::NatsEmitter>>,
+    cancel_token: CancellationToken,
+) {
+    tokio::spawn(async move {
+        let socket = health_socket_path(&session_id);
+        let mut interval = tokio::time::interval(MCP_HEALTH_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        // Skip the immediate first tick: the CLI is still spawning the server
+        interval.tick().await;
+        let mut backoff = RestartBackoff::default();
+
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    debug!(session_id = %session_id, "mcp_supervisor: cancelled, exiting");
+                    return;
+                }
+                _ = interval.tick() => {}
+            }
+
+            let (cli_pid, stdin_tx) = {
+                let sessions = active_sessions.read().await;
+                match sessions
+                    .get(&session_id)
+                    .and_then(|s| Some((s.child_pid?, s.stdin_tx.clone()?)))
+                {
+                    Some(handles) => handles,
+                    None => return,
+                }
+            };
+
+            let liveness = tokio::select! {
+                _ = cancel_token.cancelled() => return,
+                liveness = check_liveness(cli_pid, &mcp_path, &socket, MCP_PING_TIMEOUT) => liveness,
+            };
+            match liveness {
+                Liveness::Alive => {
+                    backoff.record_healthy(Instant::now());
+                    continue;
+                }
+                Liveness::Gone | Liveness::Hung => {}
+                Liveness::Unknown => continue,
+            }
+
+            let Some((attempt, delay)) = backoff.next_restart() else {
+                warn!(
+                    session_id = %session_id,
+                    attempts = MCP_MAX_RESTARTS,
+                    "mcp_supervisor: MCP server keeps dying or hanging, giving up"
+                );
+                emit(
+                    &session_id,
+                    &events_tx,
+                    &nats,
+                    ChatEvent::SessionError {
+                        reason: "mcp_server_unavailable".to_string(),
+                        message: "The orchestrator tools of this session stopped working \
+                                  and could not be restarted. Start a new session to get \
+                                  them back."
+                            .into(),
+                        received_at: chrono::Utc::now(),
+                    },
+                );
+                return;
+            };
+
+            warn!(
+                session_id = %session_id,
+                cli_pid,
+                attempt,
+                delay_ms = delay.as_millis() as u64,
+                hung = liveness == Liveness::Hung,
+                "mcp_supervisor: MCP server is gone or hung, restarting it"
+            );
+            tokio::select! {
+                _ = cancel_token.cancelled() => return,
+                _ = tokio::time::sleep(delay) => {}
+            }
+
+            // Lock-free like set_session_permission_mode: stream_response may hold the client
+            if let Err(e) = stdin_tx.send(mcp_reconnect_request(MCP_SERVER_NAME)).await {
+                // CLI stdin closed: the subprocess is gone, the OOB listener reports it
+                debug!(session_id = %session_id, "mcp_supervisor: CLI stdin closed: {}", e);
+                return;
+            }
+            info!(
+                session_id = %session_id,
+                attempt,
+                "mcp_supervisor: requested MCP server reconnect"
+            );
+            emit(
+                &session_id,
+                &events_tx,
+                &nats,
+                ChatEvent::McpServerRestarted {
+                    server: MCP_SERVER_NAME.to_string(),
+                    attempt,
+                    max_attempts: MCP_MAX_RESTARTS,
+                    received_at: chrono::Utc::now(),
+                },
+            );
+        }
+    });
+}
+
+/// Emit a ChatEvent to the local broadcast and NATS (if configured).
+fn emit(
+    session_id: &str,
+    events_tx: &broadcast::Sender<ChatEvent>,
+    nats: &Option<Arc<crate::events::NatsEmitter>>,
+    event: ChatEvent,
+) {
+    let _ = events_tx.send(event.clone());
+    if let Some(nats) = nats {
+        nats.publish_chat_event(session_id, event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_then_gives_up() {
+        let mut backoff = RestartBackoff::default();
+        let delays: Vec<(u32, Duration)> = std::iter::from_fn(|| backoff.next_restart()).collect();
+        assert_eq!(delays.len(), MCP_MAX_RESTARTS as usize);
+        assert_eq!(delays[0], (1, Duration::from_secs(1)));
+        assert_eq!(delays[1], (2, Duration::from_secs(2)));
+        assert_eq!(delays[4], (5, Duration::from_secs(16)));
+        assert!(backoff.next_restart().is_none());
+    }
+
+    #[test]
+    fn test_backoff_refills_after_staying_healthy() {
+        let mut backoff = RestartBackoff::default();
+        for _ in 0..MCP_MAX_RESTARTS {
+            backoff.next_restart();
+        }
+        let start = Instant::now();
+        backoff.record_healthy(start);
+        // Up, but not for long enough: still exhausted
+        backoff.record_healthy(start + Duration::from_secs(10));
+        assert!(backoff.next_restart().is_none());
+
+        backoff.record_healthy(start);
+        backoff.record_healthy(start + MCP_HEALTHY_RESET);
+        assert_eq!(backoff.next_restart(), Some((1, MCP_RESTART_BASE_DELAY)));
+    }
+
+    #[test]
+    fn test_mcp_reconnect_request_shape() {
+        let request: serde_json::Value =
+            serde_json::from_str(&mcp_reconnect_request(MCP_SERVER_NAME)).unwrap();
+        assert_eq!(request["type"], "control_request");
+        assert_eq!(request["request"]["subtype"], "mcp_reconnect");
+        assert_eq!(request["request"]["serverName"], "project-orchestrator");
+        assert!(request["request_id"]
+            .as_str()
+            .is_some_and(|id| !id.is_empty()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_mcp_server_running_finds_descendant() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let me = std::process::id();
+
+        assert_eq!(mcp_server_running(me, "sleep 30"), Some(true));
+        assert_eq!(
+            mcp_server_running(me, "/nonexistent/mcp_server"),
+            Some(false)
+        );
+
+        child.kill().unwrap();
+        let _ = child.wait();
+        // A process that doesn't exist can't be checked
+        assert_eq!(mcp_server_running(child.id(), "sleep"), None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_check_liveness_detects_hung_server() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("31")
+            .spawn()
+            .unwrap();
+        let me = std::process::id();
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("mcp.sock");
+        let timeout = Duration::from_millis(200);
+
+        // Running, without a health socket: only the process is checked
+        assert_eq!(
+            check_liveness(me, "sleep 31", &socket, timeout).await,
+            Liveness::Alive
+        );
+
+        // Running, but its loop never answers the ping
+        let probes = crate::mcp::health::listen(&socket).unwrap();
+        assert_eq!(
+            check_liveness(me, "sleep 31", &socket, timeout).await,
+            Liveness::Hung
+        );
+
+        // Answering again
+        tokio::spawn(async move {
+            let mut probes = probes;
+            while let Some(probe) = probes.recv().await {
+                let _ = probe.reply.send(Some(crate::mcp::JsonRpcResponse::success(
+                    serde_json::Value::Null,
+                    serde_json::json!({}),
+                )));
+            }
+        });
+        assert_eq!(
+            check_liveness(me, "sleep 31", &socket, timeout).await,
+            Liveness::Alive
+        );
+
+        child.kill().unwrap();
+        let _ = child.wait();
+        assert_eq!(
+            check_liveness(me, "sleep 31", &socket, timeout).await,
+            Liveness::Gone
+        );
+    }
+}
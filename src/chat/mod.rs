@@ -3,8 +3,10 @@
 //! Provides WebSocket streaming chat with bidirectional communication,
 //! event persistence with replay, session management, and auto-resume capabilities.
 
+pub mod attachments;
 pub mod cli_auth;
 pub mod cli_version;
+pub(crate) mod commit_trace;
 pub mod compaction_context;
 pub mod composer;
 pub mod config;
@@ -14,8 +16,11 @@ pub(crate) mod drain;
 pub mod enrichment;
 pub mod entity_extractor;
 pub mod feedback;
+pub mod limits;
 pub mod manager;
+pub(crate) mod mcp_supervisor;
 pub mod model_catalog;
+pub mod model_routing;
 pub mod observation_detector;
 pub(crate) mod oob_listener;
 pub mod path_detect;
@@ -24,9 +29,13 @@ pub(crate) mod post_tool_hook;
 pub mod prompt;
 pub mod prompt_sections;
 pub mod routing;
+pub mod sandbox;
 pub(crate) mod skill_hook;
 pub mod stages;
+pub mod summarization;
+pub mod templates;
 pub mod types;
+pub mod undo;
 pub mod viz;
 pub mod viz_builder;
 
@@ -0,0 +1,337 @@
+//! Model routing — picks a session's model from the kind of work it does.
+//!
+//! Without routing every session runs on `chat.default_model` unless the
+//! caller names a model, so a one-line question costs as much as a planning
+//! session. `chat.model_routing` maps kinds of work to models:
+//!
+//! ```yaml
+//! chat:
+//!   model_routing:
+//!     rules:
+//!       - task: planning
+//!         model: claude-opus-4-6
+//!       - task: quick_edit
+//!         model: claude-haiku-4-5
+//!       - task: summarization
+//!         model: claude-haiku-4-5
+//!     projects:
+//!       my-api:
+//!         - tags: [security]
+//!           model: claude-opus-4-6
+//! ```
+//!
+//! [`classify`] derives the [`TaskKind`] of a new session from its task
+//! metadata (protocol state, runner task tags) and its first message. The
+//! first matching rule wins, the project's rules before the global ones. A
+//! model named in the request always wins, and the model of a session is
+//! kept once routed (resuming it doesn't route again).
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Messages up to this many characters are quick edits, unless classified
+/// otherwise
+pub const QUICK_EDIT_MAX_CHARS: usize = 280;
+
+/// Words of a first message marking a planning session
+const PLANNING_WORDS: &[&str] = &[
+    "plan",
+    "planning",
+    "design",
+    "architecture",
+    "roadmap",
+    "rfc",
+];
+
+/// Words of a first message marking a review session
+const REVIEW_WORDS: &[&str] = &["review", "audit"];
+
+/// Kind of work a model is routed for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskKind {
+    /// Planning, design and architecture discussions
+    Planning,
+    /// Task execution by the plan runner
+    Implementation,
+    /// Code review and audits
+    Review,
+    /// Short requests — a quick edit or question
+    QuickEdit,
+    /// Any other conversation
+    Chat,
+    /// Summarization of long sessions (see `chat::summarization`)
+    Summarization,
+}
+
+/// One routing rule: the model to use for the sessions it matches.
+/// Conditions left out match anything.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelRule {
+    /// Kind of work
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<TaskKind>,
+    /// Task tags, any of which must be on the task (runner sessions)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Minimum size of the first message, in characters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_message_chars: Option<usize>,
+    /// Maximum size of the first message, in characters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_message_chars: Option<usize>,
+    /// Model to use
+    pub model: String,
+}
+
+impl ModelRule {
+    fn matches(&self, input: &RouteInput<'_>) -> bool {
+        self.task.is_none_or(|task| task == input.task)
+            && (self.tags.is_empty() || self.tags.iter().any(|t| input.tags.contains(t)))
+            && self
+                .min_message_chars
+                .is_none_or(|min| input.message_chars >= min)
+            && self
+                .max_message_chars
+                .is_none_or(|max| input.message_chars <= max)
+    }
+}
+
+/// Model routing rules (`chat.model_routing` in config.yaml)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModelRoutingConfig {
+    /// Rules of every project, in order
+    pub rules: Vec<ModelRule>,
+    /// Rules of a project, by slug, tried before `rules`
+    pub projects: HashMap<String, Vec<ModelRule>>,
+}
+
+/// What a session is routed on
+#[derive(Debug, Clone)]
+pub struct RouteInput<'a> {
+    pub task: TaskKind,
+    pub tags: &'a [String],
+    pub message_chars: usize,
+    pub project_slug: Option<&'a str>,
+}
+
+impl ModelRoutingConfig {
+    /// Model of the first rule matching `input`, if any
+    pub fn route(&self, input: &RouteInput<'_>) -> Option<&str> {
+        let project_rules = input
+            .project_slug
+            .and_then(|slug| self.projects.get(slug))
+            .into_iter()
+            .flatten();
+        project_rules
+            .chain(&self.rules)
+            .find(|rule| rule.matches(input))
+            .map(|rule| rule.model.as_str())
+    }
+
+    /// Model routed for summarizing a session of `project_slug`, if any
+    pub fn summarization_model(&self, project_slug: Option<&str>) -> Option<String> {
+        self.route(&RouteInput {
+            task: TaskKind::Summarization,
+            tags: &[],
+            message_chars: 0,
+            project_slug,
+        })
+        .map(str::to_string)
+    }
+}
+
+/// Kind of work of a new session.
+///
+/// In order: the protocol state it was spawned in (`plan…`, `review…`), the
+/// tags of its runner task (`planning`, `review`), any other runner session
+/// (implementation), then the first message — planning or review words,
+/// else its size.
+pub fn classify(
+    message: &str,
+    tags: &[String],
+    protocol_state: Option<&str>,
+    is_runner: bool,
+) -> TaskKind {
+    if let Some(state) = protocol_state.map(str::to_lowercase) {
+        if state.starts_with("plan") {
+            return TaskKind::Planning;
+        }
+        if state.starts_with("review") {
+            return TaskKind::Review;
+        }
+    }
+    let has_tag = |names: &[&str]| tags.iter().any(|t| names.contains(&t.as_str()));
+    if has_tag(&["planning", "plan"]) {
+        return TaskKind::Planning;
+    }
+    if has_tag(&["review"]) {
+        return TaskKind::Review;
+    }
+    if is_runner {
+        return TaskKind::Implementation;
+    }
+
+    let message = message.to_lowercase();
+    let has_word = |words: &[&str]| {
+        message
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|w| words.contains(&w))
+    };
+    if has_word(PLANNING_WORDS) {
+        TaskKind::Planning
+    } else if has_word(REVIEW_WORDS) {
+        TaskKind::Review
+    } else if message.chars().count() <= QUICK_EDIT_MAX_CHARS {
+        TaskKind::QuickEdit
+    } else {
+        TaskKind::Chat
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(task: Option<TaskKind>, model: &str) -> ModelRule {
+        ModelRule {
+            task,
+            tags: Vec::new(),
+            min_message_chars: None,
+            max_message_chars: None,
+            model: model.to_string(),
+        }
+    }
+
+    fn input(task: TaskKind, message_chars: usize, project_slug: Option<&str>) -> RouteInput<'_> {
+        RouteInput {
+            task,
+            tags: &[],
+            message_chars,
+            project_slug,
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        let long = "x ".repeat(QUICK_EDIT_MAX_CHARS);
+        assert_eq!(
+            classify("Let's design the auth module", &[], None, false),
+            TaskKind::Planning
+        );
+        assert_eq!(
+            classify("Please review PR 12", &[], None, false),
+            TaskKind::Review
+        );
+        assert_eq!(
+            classify("Rename foo to bar", &[], None, false),
+            TaskKind::QuickEdit
+        );
+        assert_eq!(classify(&long, &[], None, false), TaskKind::Chat);
+        // "planet" is not a planning word
+        assert_eq!(
+            classify("Fix the planet renderer", &[], None, false),
+            TaskKind::QuickEdit
+        );
+
+        // Task metadata comes before the message
+        assert_eq!(
+            classify("Rename foo", &[], Some("Planning"), false),
+            TaskKind::Planning
+        );
+        assert_eq!(
+            classify("Rename foo", &["review".to_string()], None, true),
+            TaskKind::Review
+        );
+        assert_eq!(
+            classify("Let's design it", &[], None, true),
+            TaskKind::Implementation
+        );
+    }
+
+    #[test]
+    fn test_route_first_match_project_rules_first() {
+        let mut config = ModelRoutingConfig {
+            rules: vec![
+                rule(Some(TaskKind::Planning), "opus"),
+                ModelRule {
+                    max_message_chars: Some(100),
+                    ..rule(None, "haiku")
+                },
+                rule(None, "sonnet"),
+            ],
+            ..Default::default()
+        };
+        config.projects.insert(
+            "my-api".into(),
+            vec![rule(Some(TaskKind::Planning), "sonnet")],
+        );
+
+        assert_eq!(
+            config.route(&input(TaskKind::Planning, 10, None)),
+            Some("opus")
+        );
+        assert_eq!(
+            config.route(&input(TaskKind::Planning, 10, Some("my-api"))),
+            Some("sonnet")
+        );
+        assert_eq!(
+            config.route(&input(TaskKind::Chat, 50, Some("my-api"))),
+            Some("haiku")
+        );
+        assert_eq!(
+            config.route(&input(TaskKind::Chat, 500, None)),
+            Some("sonnet")
+        );
+        assert_eq!(
+            ModelRoutingConfig::default().route(&input(TaskKind::Chat, 500, None)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_route_by_tags() {
+        let config = ModelRoutingConfig {
+            rules: vec![ModelRule {
+                tags: vec!["security".into()],
+                ..rule(None, "opus")
+            }],
+            ..Default::default()
+        };
+        let tags = vec!["backend".to_string(), "security".to_string()];
+        let tagged = RouteInput {
+            tags: &tags,
+            ..input(TaskKind::Implementation, 0, None)
+        };
+        assert_eq!(config.route(&tagged), Some("opus"));
+        assert_eq!(
+            config.route(&input(TaskKind::Implementation, 0, None)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_model_routing_yaml() {
+        let yaml = r#"
+rules:
+  - task: quick_edit
+    model: claude-haiku-4-5
+  - task: summarization
+    model: local-summarizer
+projects:
+  my-api:
+    - tags: [security]
+      min_message_chars: 10
+      model: claude-opus-4-6
+"#;
+        let config: ModelRoutingConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.rules.len(), 2);
+        assert_eq!(config.rules[0].task, Some(TaskKind::QuickEdit));
+        assert_eq!(config.projects["my-api"][0].min_message_chars, Some(10));
+        assert_eq!(
+            config.summarization_model(Some("my-api")).as_deref(),
+            Some("local-summarizer")
+        );
+    }
+}
@@ -7,6 +7,8 @@
 //! - Objective tracking reminder injection
 //! - Streaming status updates
 //! - Event persistence to Neo4j
+//! - Rolling summarization of long sessions
+//! - Task-to-commit traceability
 //! - Memory/feedback/RFC recording
 
 use super::manager::ActiveSession;
@@ -33,6 +35,16 @@ use std::collections::VecDeque;
 pub(crate) struct PostStreamContext {
     pub project_slug: Option<String>,
     pub project_id: Option<Uuid>,
+    /// Task the session works on (from `spawned_by`), with the session's
+    /// cwd and creation time — used to trace its commits
+    pub task: Option<SessionTask>,
+}
+
+/// Task-bound session details needed by commit tracing
+pub(crate) struct SessionTask {
+    pub task_id: Uuid,
+    pub cwd: String,
+    pub started_at: chrono::DateTime<chrono::Utc>,
 }
 
 impl PostStreamContext {
@@ -54,20 +66,30 @@ impl PostStreamContext {
                     } else {
                         None
                     };
+                    let task = super::commit_trace::session_task(node.spawned_by.as_deref()).map(
+                        |task_id| SessionTask {
+                            task_id,
+                            cwd: node.cwd.clone(),
+                            started_at: node.created_at,
+                        },
+                    );
                     PostStreamContext {
                         project_slug: node.project_slug,
                         project_id: pid,
+                        task,
                     }
                 }
                 _ => PostStreamContext {
                     project_slug: None,
                     project_id: None,
+                    task: None,
                 },
             }
         } else {
             PostStreamContext {
                 project_slug: None,
                 project_id: None,
+                task: None,
             }
         }
     }
@@ -538,6 +560,113 @@ impl PostStreamHandler {
         }
     }
 
+    // ── Rolling summarization ─────────────────────────────────────────────
+
+    /// Summarize the earlier turns in the background once the context of the
+    /// last model calls crosses the threshold, then stop the CLI if the
+    /// session is still idle so the next message resumes it seeded with the
+    /// summary (see [`super::summarization`]). Runs on the model routed for
+    /// summarization, if any.
+    pub async fn handle_summarization(&self, context_tokens: u64) {
+        let threshold = super::summarization::threshold_tokens();
+        if threshold == 0 || context_tokens < threshold {
+            return;
+        }
+        let Some(uuid) = self.session_uuid else {
+            return;
+        };
+        let routed = self
+            .active_sessions
+            .read()
+            .await
+            .get(&self.session_id)
+            .and_then(|s| s.summarization_model.clone());
+        let model = routed.unwrap_or_else(|| {
+            std::env::var("PROMPT_BUILDER_MODEL").unwrap_or_else(|_| "claude-opus-4-6".into())
+        });
+        if model.is_empty() || !super::summarization::begin(&self.session_id) {
+            return;
+        }
+        info!(
+            "Session {} reached {} context tokens, summarizing earlier turns",
+            self.session_id, context_tokens
+        );
+
+        let graph = self.graph.clone();
+        let active_sessions = self.active_sessions.clone();
+        let session_id = self.session_id.clone();
+        tokio::spawn(async move {
+            match super::summarization::summarize_session(graph.as_ref(), uuid, &model).await {
+                Ok(Some(memory)) => {
+                    if let Err(e) = graph.set_chat_session_memory(uuid, &memory).await {
+                        warn!("Failed to store summary of session {}: {}", session_id, e);
+                    } else {
+                        restart_if_idle(&active_sessions, &session_id).await;
+                    }
+                }
+                Ok(None) => debug!("Nothing summarized for session {}", session_id),
+                Err(e) => warn!("Failed to summarize session {}: {}", session_id, e),
+            }
+            super::summarization::finish(&session_id);
+        });
+    }
+
+    // ── Task-to-commit traceability ───────────────────────────────────────
+
+    /// After a turn that ran a git finalization command in a task-bound
+    /// session, link the commits it made to the task in the background
+    /// (see [`super::commit_trace`]).
+    pub async fn handle_commit_tracing(&self, had_conclusive_tool_use: bool) {
+        if !had_conclusive_tool_use {
+            return;
+        }
+        let Some(ref task) = self.ctx.task else {
+            return;
+        };
+        let files_modified = self.work_log.lock().await.files_modified.clone();
+        if files_modified.is_empty() {
+            return;
+        }
+
+        let graph = self.graph.clone();
+        let session_id = self.session_id.clone();
+        let session_uuid = self.session_uuid;
+        let task_id = task.task_id;
+        let cwd = task.cwd.clone();
+        let started_at = task.started_at;
+        tokio::spawn(async move {
+            let commits = match super::commit_trace::commits_since(&cwd, started_at).await {
+                Ok(commits) => commits,
+                Err(e) => {
+                    debug!("No commits traced for session {}: {}", session_id, e);
+                    return;
+                }
+            };
+            match super::commit_trace::link_task_commits(
+                graph.as_ref(),
+                task_id,
+                session_uuid,
+                &cwd,
+                &files_modified,
+                &commits,
+            )
+            .await
+            {
+                Ok(linked) if !linked.is_empty() => info!(
+                    "Session {} linked {} commit(s) to task {}",
+                    session_id,
+                    linked.len(),
+                    task_id
+                ),
+                Ok(_) => {}
+                Err(e) => warn!(
+                    "Failed to link commits of session {} to task {}: {}",
+                    session_id, task_id, e
+                ),
+            }
+        });
+    }
+
     // ── Memory / feedback / RFC ───────────────────────────────────────────
 
     /// Record assistant response in memory, spawn feedback extraction and RFC detection.
@@ -606,6 +735,37 @@ impl PostStreamHandler {
     }
 }
 
+/// Stop the CLI of a session that is neither streaming nor has queued
+/// messages. Its subscribers go dormant, and the next message resumes the
+/// session through `resume_session()`.
+async fn restart_if_idle(
+    active_sessions: &RwLock<HashMap<String, ActiveSession>>,
+    session_id: &str,
+) {
+    let client = {
+        let mut sessions = active_sessions.write().await;
+        let idle = sessions.get(session_id).is_some_and(|s| {
+            !s.is_streaming.load(Ordering::SeqCst)
+                && s.pending_messages.try_lock().is_ok_and(|p| p.is_empty())
+        });
+        if !idle {
+            debug!("Session {} is busy, keeping its CLI running", session_id);
+            return;
+        }
+        sessions.remove(session_id).map(|s| s.client)
+    };
+    if let Some(client) = client {
+        let disconnect = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            client.lock().await.disconnect().await
+        })
+        .await;
+        if !matches!(disconnect, Ok(Ok(()))) {
+            warn!("Unclean CLI shutdown of summarized session {}", session_id);
+        }
+        info!("Restarting session {} from its summary", session_id);
+    }
+}
+
 // ── Pure logic for objective tracking (testable without Graph) ─────────
 
 /// Cooldown threshold for objective reminders (same as PostStreamHandler).
@@ -1110,6 +1270,9 @@ mod integration_tests {
                 cancel_task_window: std::time::Duration::from_secs(
                     crate::chat::manager::CANCEL_TASK_WINDOW_SECS,
                 ),
+                limits: Default::default(),
+                usage: Default::default(),
+                summarization_model: None,
             };
             active_sessions
                 .write()
@@ -1131,6 +1294,7 @@ mod integration_tests {
             ctx: PostStreamContext {
                 project_slug,
                 project_id: None,
+                task: None,
             },
             is_streaming: Arc::new(AtomicBool::new(false)),
             streaming_text: Arc::new(Mutex::new(String::new())),
@@ -107,7 +107,14 @@ Business processes: `code(action: "list_processes")`, `code(action: "get_process
 - **Required before any code exploration**: if `last_synced` is absent, run `project(action: "sync")` first; otherwise, commit sync maintains freshness automatically
 - Exploration tools available after sync:
   - `code(action: "search", query)` / `code(action: "search_project", project_slug, query)` — semantic search
+  - `code(action: "search_docs", query, project_slug)` — sections of the project's Markdown docs (README, docs/), cite as `path#anchor`
+  - `code(action: "find_config_usages", project_slug, key)` — where a config key or env var (`MEILISEARCH_URL`, `server.port`) is defined and read
+  - `project(action: "get_overview", slug)` — one-call orientation on an unfamiliar project: communities, most central functions, module summaries, resources, open plans
+  - `project(action: "get_schema", project_id)` — SQL tables (columns, PK/FK) and migrations parsed from the project's `.sql` files
   - `code(action: "get_file_symbols", file_path)` — functions, structs, traits in a file
+  - `code(action: "get_snippet", symbol_id, context_lines)` — source of one symbol (ids from `get_file_symbols`) instead of the whole file
+  - `code(action: "propose_rename", symbol_id, new_name)` — before renaming across files: every edit location, `text`-only matches to review, clashes with the new name
+  - `code(action: "explain_symbol", symbol)` — one call for a symbol (name or id): definition, docstring, struct fields / enum variant payloads, callers/callees, traits, notes, recent commits, analytics
   - `code(action: "find_references", symbol)` — all usages of a symbol
   - `code(action: "get_file_dependencies", file_path)` — imports and dependents
   - `code(action: "get_call_graph", function)` — call graph
@@ -281,6 +288,7 @@ When GDS (Graph Data Science) data is available on the project:
    - **Knowledge gaps**: files with low knowledge_density (under-documented, few linked notes/decisions)
    - **Risk assessment**: composite score (pagerank × churn × knowledge_gap × betweenness) with levels critical/high/medium/low
    - **Neural metrics**: active synapses, average energy, weak synapse ratio, dead notes
+   - **Duplication**: near-duplicate function groups (full list via `code(action: "find_duplicates", project_slug)`)
    - **Use this**: at project start, code review, or technical debt prioritization
 
 3. **Evaluate node importance** → `code(action: "get_node_importance", project_slug, node_path, node_type)`
@@ -478,8 +486,15 @@ The Knowledge Fabric connects all graph entities via semantic relations:
 
 - `workspace(action: "get_overview", slug)` — workspace overview
 - `workspace_milestone(action: "create", slug, title)` — cross-project milestones
-- `workspace(action: "get_topology", slug)` — components and service dependencies
+- `workspace(action: "get_topology", slug)` — components and service dependencies (auto-populated from Dockerfiles and docker-compose files at project sync)
+- `workspace(action: "get_topology_drift", slug)` — declared dependencies missing from code (stale) and code dependencies never declared
+- `workspace(action: "get_license_violations", slug)` — before adding a dependency, check it against the workspace license policy (`set_license_policy`)
+- `workspace(action: "search", slug, query)` — code, notes and decisions across all member projects (de-duplicated)
+- `workspace(action: "get_context", slug)` — workspace notes, key notes and live decisions of all member projects
 - `resource(action: "list", workspace_slug)` — API contracts, shared schemas
+- `resource(action: "get_usages", id)` — files in other projects importing a contract, before changing it
+- `resource(action: "list_elements", id, kind?)` — endpoints/messages a contract defines (`parse` refreshes them from the spec file)
+- `resource(action: "diff", id, against)` — added/removed/changed elements between two contract versions
 
 ### Inheritance strategy (EXTENDS / IMPLEMENTS)
 
@@ -668,7 +683,7 @@ pub const TOOL_REFERENCE: &str = r#"# MCP Mega-Tools Reference
 All tools require `action` (string). UUIDs are strings. Dates are ISO 8601.
 
 ## project
-Manage projects. Actions: list, create, get, update, delete, sync, get_roadmap, list_plans, get_graph, get_intelligence_summary, get_embeddings_projection, get_scaffolding_level, set_scaffolding_override, get_health_dashboard, get_auto_roadmap
+Manage projects. Actions: list, create, get, update, delete, sync, get_roadmap, list_plans, get_graph, get_intelligence_summary, get_embeddings_projection, get_scaffolding_level, set_scaffolding_override, get_health_dashboard, get_auto_roadmap, list_advisories, list_documents, get_schema, get_overview
 
 | Action | Key Parameters | Description |
 |--------|---------------|-------------|
@@ -682,11 +697,15 @@ Manage projects. Actions: list, create, get, update, delete, sync, get_roadmap,
 | list_plans | `slug` (req) | List plans for project |
 | get_graph | `slug` (req), `layers` (code/knowledge/fabric/neural/skills/behavioral), `community`, `limit` | Export multi-layer graph (filterable by layer and community) |
 | get_intelligence_summary | `slug` (req) | Get project intelligence summary (key metrics, health, active plans) |
+| get_overview | `slug` (req) | Onboarding summary: languages, communities, top PageRank functions, module summaries, resources, open plans |
 | get_embeddings_projection | `slug` (req) | Get 2D UMAP projection of code embeddings |
 | get_scaffolding_level | `slug` (req) | Get current scaffolding level (0-4, auto-computed from maturity) |
 | set_scaffolding_override | `slug` (req), `level` (0-4 or null to clear) | Override scaffolding level (null = auto) |
 | get_health_dashboard | `slug` (req) | Get consolidated health dashboard (health + gaps + risk) |
 | get_auto_roadmap | `slug` (req) | Get auto-generated roadmap from knowledge graph signals |
+| list_advisories | `project_id` (req), `min_severity` (low/medium/high/critical) | Security advisories (OSV/RustSec) affecting locked dependencies |
+| list_documents | `project_id` (req) | Markdown documents indexed at sync (path, title, section count) |
+| get_schema | `project_id` (req) | SQL tables (columns, PK/FK, defining & altering files) and migrations in apply order |
 
 ## plan
 Manage plans. Actions: list, create, get, update, update_status, delete, link_to_project, unlink_from_project, get_dependency_graph, get_critical_path, get_waves, run, run_status, cancel_run, auto_pr, add_trigger, list_triggers, remove_trigger, enable_trigger, disable_trigger, list_runs, get_run, compare_runs, predict_run, enrich, delegate_task
@@ -753,7 +772,7 @@ Manage steps within tasks. Actions: list, create, update, get, delete, get_progr
 | get_progress | `task_id` (req) | Get step completion progress |
 
 ## decision
-Manage architectural decisions. Actions: add, get, update, delete, search, search_semantic, add_affects, remove_affects, list_affects, get_affecting, supersede, get_timeline
+Manage architectural decisions. Actions: add, get, update, delete, search, search_semantic, add_affects, remove_affects, list_affects, get_affecting, supersede, get_timeline, export_adrs
 
 | Action | Key Parameters | Description |
 |--------|---------------|-------------|
@@ -769,6 +788,7 @@ Manage architectural decisions. Actions: add, get, update, delete, search, searc
 | get_affecting | `entity_type` (req), `entity_id` (req) | Get decisions affecting entity |
 | supersede | `decision_id` (req), `superseded_by_id` (req) | Mark decision as superseded |
 | get_timeline | `task_id` (req), `from`, `to` | Get decision timeline |
+| export_adrs | `project_id` (req), `decision_id` | Write decisions as docs/adr/NNN-title.md files |
 
 ## constraint
 Manage plan constraints. Actions: list, add, get, update, delete
@@ -811,7 +831,7 @@ Manage project milestones. Actions: list, create, get, update, delete, get_progr
 | unlink_plan | `milestone_id` (req), `plan_id` (req) | Unlink plan from milestone |
 
 ## commit
-Register and link git commits. Actions: create, link_to_task, link_to_plan, get_task_commits, get_plan_commits, get_commit_files, get_file_history
+Register and link git commits. Actions: create, link_to_task, link_to_plan, get_task_commits, get_plan_commits, get_commit_files, get_file_history, suggest_message
 
 | Action | Key Parameters | Description |
 |--------|---------------|-------------|
@@ -822,6 +842,7 @@ Register and link git commits. Actions: create, link_to_task, link_to_plan, get_
 | get_plan_commits | `plan_id` (req) | Get commits for plan |
 | get_commit_files | `sha` (req) | Get files changed in commit |
 | get_file_history | `file_path` (req), `limit` | Get commit history for file |
+| suggest_message | `project_id` (req), `diff`, `staged` | Conventional commit message for the working diff, with task references |
 
 ## note
 Manage knowledge notes. Actions: list, create, get, update, delete, search, search_semantic, confirm, invalidate, supersede, link_to_entity, unlink_from_entity, get_context, get_needing_review, list_project, get_propagated, get_entity, get_context_knowledge, get_propagated_knowledge, list_rfcs, advance_rfc, get_rfc_status
@@ -852,7 +873,7 @@ Manage knowledge notes. Actions: list, create, get, update, delete, search, sear
 | get_rfc_status | `note_id` (req) | Get RFC lifecycle status and protocol run details |
 
 ## workspace
-Manage workspaces. Actions: list, create, get, update, delete, get_overview, list_projects, add_project, remove_project, get_topology
+Manage workspaces. Actions: list, create, get, update, delete, get_overview, list_projects, add_project, remove_project, get_topology, get_topology_drift, get_license_policy, set_license_policy, get_license_violations, search, get_context
 
 | Action | Key Parameters | Description |
 |--------|---------------|-------------|
@@ -866,9 +887,15 @@ Manage workspaces. Actions: list, create, get, update, delete, get_overview, lis
 | add_project | `slug` (req), `project_id` (req), `role` | Add project to workspace |
 | remove_project | `slug` (req), `project_id` (req) | Remove project from workspace |
 | get_topology | `slug` (req) | Get component topology |
+| get_topology_drift | `slug` (req) | Compare DEPENDS_ON_COMPONENT edges with resource imports and service URLs found in code |
+| get_license_policy | `slug` (req) | Get the allowed/denied license lists |
+| set_license_policy | `slug` (req), `allowed`, `denied`, `flag_unknown` | Replace the license policy (SPDX ids, trailing `*` for prefixes) |
+| get_license_violations | `slug` (req) | Locked packages of member projects breaking the policy, plus license counts |
+| search | `slug` (req), `query` (req), `kinds`, `language`, `limit` | Search code, notes and decisions across member projects |
+| get_context | `slug` (req), `limit` | Workspace notes, critical/high notes and live decisions of member projects |
 
 ## workspace_milestone
-Manage workspace milestones. Actions: list_all, list, create, get, update, delete, add_task, link_plan, unlink_plan, get_progress
+Manage workspace milestones. Actions: list_all, list, create, get, update, delete, add_task, link_plan, unlink_plan, get_progress, get_rollup
 
 | Action | Key Parameters | Description |
 |--------|---------------|-------------|
@@ -882,9 +909,10 @@ Manage workspace milestones. Actions: list_all, list, create, get, update, delet
 | link_plan | `milestone_id` (req), `plan_id` (req) | Link plan |
 | unlink_plan | `milestone_id` (req), `plan_id` (req) | Unlink plan |
 | get_progress | `milestone_id` (req) | Get completion progress |
+| get_rollup | `milestone_id` (req), `window_days` | Progress per plan, velocity and forecast completion date |
 
 ## resource
-Manage workspace resources (API contracts, schemas). Actions: list, create, get, update, delete, link_to_project
+Manage workspace resources (API contracts, schemas). Actions: list, create, get, update, delete, link_to_project, get_usages, parse, list_elements, diff
 
 | Action | Key Parameters | Description |
 |--------|---------------|-------------|
@@ -894,6 +922,10 @@ Manage workspace resources (API contracts, schemas). Actions: list, create, get,
 | update | `id` (req), `name`, `description`, `file_path`, `url`, `version` | Update resource |
 | delete | `id` (req) | Delete resource |
 | link_to_project | `resource_id` (req), `project_id` (req) | Link resource to project |
+| get_usages | `id` (req) | Files in other projects importing the resource |
+| parse | `id` (req) | Parse the OpenAPI/proto spec into elements; returns the diff vs the previous parse |
+| list_elements | `id` (req), `kind` (operation/schema/service/rpc/message/enum) | Endpoints, schemas, messages… the contract defines |
+| diff | `id` (req), `against` (req: older resource UUID) | Added/removed/changed elements between two versions |
 
 ## component
 Manage workspace components (services, modules). Actions: list, create, get, update, delete, add_dependency, remove_dependency, map_to_project
@@ -941,14 +973,19 @@ Manage feature graphs. Actions: create, get, list, add_entity, auto_build, delet
 | find_overlapping | `id` (req) | Find other feature graphs that overlap with this one |
 
 ## code
-Explore and analyze code. Actions: search, search_project, search_workspace, get_file_symbols, find_references, get_file_dependencies, get_call_graph, analyze_impact, get_architecture, find_similar, find_trait_implementations, find_type_traits, get_impl_blocks, get_communities, get_health, get_node_importance, plan_implementation, get_co_change_graph, get_file_co_changers, detect_processes, get_class_hierarchy, find_subclasses, find_interface_implementors, list_processes, get_process, get_entry_points, enrich_communities, get_hotspots, get_knowledge_gaps, get_risk_assessment, get_homeostasis, get_structural_drift, get_structural_profile, find_structural_twins, cluster_dna, find_cross_project_twins, predict_missing_links, check_link_plausibility, stress_test_node, stress_test_edge, stress_test_cascade, find_bridges, get_context_card, refresh_context_cards, get_fingerprint, find_isomorphic, suggest_structural_templates, get_bridge, check_topology, list_topology_rules, create_topology_rule, delete_topology_rule, check_file_topology
+Explore and analyze code. Actions: search, search_project, search_workspace, search_docs, find_config_usages, get_file_symbols, get_snippet, propose_rename, explain_symbol, find_references, get_file_dependencies, get_call_graph, analyze_impact, get_architecture, find_similar, find_trait_implementations, find_type_traits, get_impl_blocks, get_communities, get_health, find_duplicates, get_node_importance, plan_implementation, get_co_change_graph, get_file_co_changers, detect_processes, get_class_hierarchy, find_subclasses, find_interface_implementors, list_processes, get_process, get_entry_points, enrich_communities, get_hotspots, get_knowledge_gaps, get_risk_assessment, get_homeostasis, get_structural_drift, get_structural_profile, find_structural_twins, cluster_dna, find_cross_project_twins, predict_missing_links, check_link_plausibility, stress_test_node, stress_test_edge, stress_test_cascade, find_bridges, get_context_card, refresh_context_cards, get_fingerprint, find_isomorphic, suggest_structural_templates, get_bridge, check_topology, list_topology_rules, create_topology_rule, delete_topology_rule, check_file_topology
 
 | Action | Key Parameters | Description |
 |--------|---------------|-------------|
 | search | `query` (req), `path_prefix`, `limit` | Search code globally |
 | search_project | `query` (req), `project_slug` (req), `limit` | Search within project |
 | search_workspace | `query` (req), `workspace_slug` (req), `limit` | Search within workspace |
+| search_docs | `query` (req), `project_slug`, `limit` | Search Markdown documentation sections (heading, anchor, line) |
+| find_config_usages | `project_slug` (req), `key`, `limit` | Config keys / env vars with the files and lines defining and reading them |
 | get_file_symbols | `file_path` (req) | Get symbols in file |
+| get_snippet | `symbol_id` (req), `context_lines` | Source of one function/struct/trait/enum, read from disk, with context lines |
+| propose_rename | `symbol_id` (req), `new_name` (req) | Every line/column to edit for a rename (graph + textual), with name clashes |
+| explain_symbol | `symbol` (req, name or id), `project_slug` | Definition, docstring, fields or variant payloads, callers/callees, traits, notes, recent commits and analytics of one symbol |
 | find_references | `symbol` (req) | Find references to symbol |
 | get_file_dependencies | `file_path` (req) | Get file imports/dependents |
 | get_call_graph | `function` (req), `limit` (depth) | Get call graph for function |
@@ -960,6 +997,7 @@ Explore and analyze code. Actions: search, search_project, search_workspace, get
 | get_impl_blocks | `type_name` (req) | Get impl blocks for type |
 | get_communities | `project_slug` (req), `min_size` | Get code communities (Louvain) |
 | get_health | `project_slug` (req) | Get codebase health metrics |
+| find_duplicates | `project_slug` (req), `min_similarity`, `limit` | Groups of near-duplicate functions (MinHash clones), largest duplication first — refactor candidates |
 | get_node_importance | `project_slug` (req), `node_path` (req), `node_type` (req) | Get node importance score |
 | plan_implementation | `project_slug` (req), `description` (req), `entry_points`, `scope`, `auto_create_plan` | Plan implementation from code graph |
 | get_co_change_graph | `project_slug` (req) | Get co-change graph |
@@ -1000,7 +1038,7 @@ Explore and analyze code. Actions: search, search_project, search_workspace, get
 | check_file_topology | `project_slug` (req), `file_path` (req), `new_imports` (req, array) | Check if new imports would violate rules |
 
 ## admin
-Admin operations. Actions: sync_directory, start_watch, stop_watch, watch_status, meilisearch_stats, delete_meilisearch_orphans, cleanup_cross_project_calls, cleanup_builtin_calls, migrate_calls_confidence, cleanup_sync_data, update_staleness_scores, update_energy_scores, search_neurons, reinforce_neurons, decay_synapses, backfill_synapses, reindex_decisions, backfill_decision_embeddings, backfill_touches, backfill_discussed, update_fabric_scores, bootstrap_knowledge_fabric, reinforce_isomorphic, detect_skills, detect_skill_fission, detect_skill_fusion, maintain_skills, auto_anchor_notes, reconstruct_knowledge, heal_scars, consolidate_memory, detect_stagnation, deep_maintenance, audit_gaps, persist_health_report, install_hooks
+Admin operations. Actions: sync_directory, start_watch, stop_watch, watch_status, meilisearch_stats, search_analytics, delete_meilisearch_orphans, cleanup_cross_project_calls, cleanup_builtin_calls, migrate_calls_confidence, cleanup_sync_data, update_staleness_scores, update_energy_scores, search_neurons, reinforce_neurons, decay_synapses, backfill_synapses, reindex_decisions, backfill_decision_embeddings, backfill_touches, backfill_discussed, update_fabric_scores, bootstrap_knowledge_fabric, reinforce_isomorphic, detect_skills, detect_skill_fission, detect_skill_fusion, maintain_skills, auto_anchor_notes, reconstruct_knowledge, heal_scars, consolidate_memory, detect_stagnation, deep_maintenance, audit_gaps, persist_health_report, install_hooks
 
 | Action | Key Parameters | Description |
 |--------|---------------|-------------|
@@ -1009,6 +1047,7 @@ Admin operations. Actions: sync_directory, start_watch, stop_watch, watch_status
 | stop_watch | `project_id` (req) | Stop watching |
 | watch_status | | Get watch status |
 | meilisearch_stats | | Get search index stats |
+| search_analytics | `endpoint`, `project_slug`, `since`, `limit` | Most searched and zero-result queries |
 | delete_meilisearch_orphans | | Clean orphaned search docs |
 | cleanup_cross_project_calls | | Remove cross-project calls |
 | cleanup_builtin_calls | | Remove builtin calls |
@@ -1081,6 +1120,31 @@ Manage analysis profiles (edge/fusion weight presets). Actions: list, create, ge
 | get | `id` (req) | Get analysis profile by UUID |
 | delete | `id` (req) | Delete analysis profile |
 
+## view
+Manage saved views (your persisted queries over tasks, notes or project symbols). Actions: list, create, get, update, delete, get_results
+
+| Action | Key Parameters | Description |
+|--------|---------------|-------------|
+| list | — | List your saved views |
+| create | `name` (req), `target` (req: tasks/notes/symbols), `description`, `filters` (object) | Create a saved view |
+| get | `id` (req) | Get a saved view |
+| update | `id` (req), `name`, `description`, `target`, `filters` (replaces all filters) | Update a saved view |
+| delete | `id` (req) | Delete a saved view |
+| get_results | `id` (req), `limit`, `offset` | Current matches of the view |
+
+**Filters:** `project_id` (required for symbols), `sort_by`, `sort_order`, `limit`; tasks/notes: `workspace_slug`, `status`, `tags`; tasks: `plan_id`, `priority_min`, `priority_max`, `assigned_to`; notes: `note_type`, `importance`, `min_staleness`; symbols: `visibility`, `min_complexity`, `untested`, `name_contains`.
+
+## comment
+Discuss tasks, plans and notes: comment, reply, mention people with @handle. Actions: list, create, get, update, delete
+
+| Action | Key Parameters | Description |
+|--------|---------------|-------------|
+| list | `entity_type` + `entity_id`, or `mentioned` (+ `limit`) | Comments of a task/plan/note (oldest first), or those mentioning a handle |
+| create | `entity_type` (req: task/plan/note), `entity_id` (req), `content` (req), `parent_id` | Comment, or reply to a comment of the same entity |
+| get | `id` (req) | Get a comment |
+| update | `id` (req), `content` (req) | Edit your comment |
+| delete | `id` (req) | Delete your comment and its replies |
+
 ## protocol
 Manage Protocol FSMs (Pattern Federation). Actions: list, create, get, update, delete, add_state, delete_state, list_states, add_transition, delete_transition, list_transitions, link_to_skill, start_run, transition, get_run, list_runs, cancel_run, fail_run, report_progress, delete_run, route, compose, simulate, get_run_tree, get_run_children
 
@@ -1354,11 +1418,18 @@ pub static TOOL_GROUPS: &[ToolGroup] = &[
         name: "project_management",
         description: "CRUD projects, sync, roadmap, intelligence, scaffolding, health dashboard",
         keywords: &["project", "projet", "codebase", "sync", "roadmap", "create project", "créer projet",
-            "scaffolding", "intelligence", "health dashboard", "auto roadmap", "graph export", "embeddings"],
-        tools: &[ToolRef {
-            name: "project",
-            description: "Manage projects (list/create/get/update/delete/sync/get_roadmap/list_plans/get_graph/get_intelligence_summary/get_embeddings_projection/get_scaffolding_level/set_scaffolding_override/get_health_dashboard/get_auto_roadmap)",
-        }],
+            "scaffolding", "intelligence", "health dashboard", "auto roadmap", "graph export", "embeddings",
+            "saved view", "smart view"],
+        tools: &[
+            ToolRef {
+                name: "project",
+                description: "Manage projects (list/create/get/update/delete/sync/get_roadmap/list_plans/get_graph/get_intelligence_summary/get_embeddings_projection/get_scaffolding_level/set_scaffolding_override/get_health_dashboard/get_auto_roadmap/list_advisories/list_documents/get_schema/get_overview)",
+            },
+            ToolRef {
+                name: "view",
+                description: "Manage saved views (list/create/get/update/delete/get_results) — persisted queries over tasks, notes or symbols for dashboards",
+            },
+        ],
     },
     // ── Planning ────────────────────────────────────────────────────
     ToolGroup {
@@ -1371,6 +1442,7 @@ pub static TOOL_GROUPS: &[ToolGroup] = &[
             "run", "execute", "exécuter", "trigger", "auto PR", "delegate", "déléguer",
             "predict", "compare", "enrich", "enrichir",
             "lifecycle", "hook", "cascade", "on_status", "automation",
+            "comments", "commentaire", "commentaires", "discussion", "mention", "reply",
         ],
         tools: &[
             ToolRef {
@@ -1385,6 +1457,10 @@ pub static TOOL_GROUPS: &[ToolGroup] = &[
                 name: "step",
                 description: "Manage steps (list/create/update/get/delete/get_progress)",
             },
+            ToolRef {
+                name: "comment",
+                description: "Discuss tasks, plans and notes (list/create/get/update/delete) — threads with @mentions, shown to agents working the task",
+            },
             ToolRef {
                 name: "lifecycle_hook",
                 description: "Manage lifecycle hooks (list/create/get/update/delete) — automatic actions triggered on entity status changes (cascade_children, create_note, emit_alert, start_protocol, mcp_call)",
@@ -1403,7 +1479,7 @@ pub static TOOL_GROUPS: &[ToolGroup] = &[
         tools: &[
             ToolRef {
                 name: "decision",
-                description: "Manage decisions (add/get/update/delete/search/search_semantic/add_affects/remove_affects/list_affects/get_affecting/supersede/get_timeline)",
+                description: "Manage decisions (add/get/update/delete/search/search_semantic/add_affects/remove_affects/list_affects/get_affecting/supersede/get_timeline/export_adrs)",
             },
             ToolRef {
                 name: "constraint",
@@ -1435,7 +1511,7 @@ pub static TOOL_GROUPS: &[ToolGroup] = &[
         tools: &[
             ToolRef {
                 name: "code",
-                description: "Explore code (search/search_project/search_workspace/get_file_symbols/find_references/get_file_dependencies/get_call_graph/analyze_impact/get_architecture/find_similar/find_trait_implementations/find_type_traits/get_impl_blocks/get_communities/get_health/get_node_importance/plan_implementation/get_co_change_graph/get_file_co_changers/detect_processes/get_class_hierarchy/find_subclasses/find_interface_implementors/list_processes/get_process/get_entry_points/enrich_communities/get_hotspots/get_knowledge_gaps/get_risk_assessment/get_homeostasis/get_structural_drift/get_structural_profile/find_structural_twins/cluster_dna/find_cross_project_twins/predict_missing_links/check_link_plausibility/stress_test_node/stress_test_edge/stress_test_cascade/find_bridges/get_context_card/refresh_context_cards/get_fingerprint/find_isomorphic/suggest_structural_templates/get_bridge/check_topology/list_topology_rules/create_topology_rule/delete_topology_rule/check_file_topology)",
+                description: "Explore code (search/search_project/search_workspace/search_docs/find_config_usages/get_file_symbols/get_snippet/propose_rename/explain_symbol/find_references/get_file_dependencies/get_call_graph/analyze_impact/get_architecture/find_similar/find_trait_implementations/find_type_traits/get_impl_blocks/get_communities/get_health/find_duplicates/get_node_importance/plan_implementation/get_co_change_graph/get_file_co_changers/detect_processes/get_class_hierarchy/find_subclasses/find_interface_implementors/list_processes/get_process/get_entry_points/enrich_communities/get_hotspots/get_knowledge_gaps/get_risk_assessment/get_homeostasis/get_structural_drift/get_structural_profile/find_structural_twins/cluster_dna/find_cross_project_twins/predict_missing_links/check_link_plausibility/stress_test_node/stress_test_edge/stress_test_cascade/find_bridges/get_context_card/refresh_context_cards/get_fingerprint/find_isomorphic/suggest_structural_templates/get_bridge/check_topology/list_topology_rules/create_topology_rule/delete_topology_rule/check_file_topology)",
             },
             ToolRef {
                 name: "analysis_profile",
@@ -1471,7 +1547,7 @@ pub static TOOL_GROUPS: &[ToolGroup] = &[
         keywords: &["commit", "git", "branch", "branche", "sha", "push", "history", "historique", "co-change"],
         tools: &[ToolRef {
             name: "commit",
-            description: "Register and link commits (create/link_to_task/link_to_plan/get_task_commits/get_plan_commits/get_commit_files/get_file_history) — create with files_changed triggers TOUCHES relations + incremental sync",
+            description: "Register and link commits (create/link_to_task/link_to_plan/get_task_commits/get_plan_commits/get_commit_files/get_file_history/suggest_message) — create with files_changed triggers TOUCHES relations + incremental sync",
         }],
     },
     // ── Releases & Milestones ───────────────────────────────────────
@@ -1502,15 +1578,15 @@ pub static TOOL_GROUPS: &[ToolGroup] = &[
         tools: &[
             ToolRef {
                 name: "workspace",
-                description: "Manage workspaces (list/create/get/update/delete/get_overview/list_projects/add_project/remove_project/get_topology)",
+                description: "Manage workspaces (list/create/get/update/delete/get_overview/list_projects/add_project/remove_project/get_topology/get_topology_drift/get_license_policy/set_license_policy/get_license_violations/search/get_context)",
             },
             ToolRef {
                 name: "workspace_milestone",
-                description: "Manage workspace milestones (list_all/list/create/get/update/delete/add_task/link_plan/unlink_plan/get_progress)",
+                description: "Manage workspace milestones (list_all/list/create/get/update/delete/add_task/link_plan/unlink_plan/get_progress/get_rollup)",
             },
             ToolRef {
                 name: "resource",
-                description: "Manage resources (list/create/get/update/delete/link_to_project)",
+                description: "Manage resources (list/create/get/update/delete/link_to_project/get_usages/parse/list_elements/diff)",
             },
             ToolRef {
                 name: "component",
@@ -1624,7 +1700,7 @@ pub static TOOL_GROUPS: &[ToolGroup] = &[
         tools: &[
             ToolRef {
                 name: "admin",
-                description: "Admin ops (sync_directory/start_watch/stop_watch/watch_status/meilisearch_stats/delete_meilisearch_orphans/cleanup_cross_project_calls/cleanup_builtin_calls/migrate_calls_confidence/cleanup_sync_data/update_staleness_scores/update_energy_scores/search_neurons/reinforce_neurons/decay_synapses/backfill_synapses/reindex_decisions/backfill_decision_embeddings/backfill_touches/backfill_discussed/update_fabric_scores/bootstrap_knowledge_fabric/reinforce_isomorphic/detect_skills/detect_skill_fission/detect_skill_fusion/maintain_skills/auto_anchor_notes/reconstruct_knowledge/heal_scars/consolidate_memory/detect_stagnation/deep_maintenance/audit_gaps/persist_health_report/install_hooks)",
+                description: "Admin ops (sync_directory/start_watch/stop_watch/watch_status/meilisearch_stats/search_analytics/delete_meilisearch_orphans/cleanup_cross_project_calls/cleanup_builtin_calls/migrate_calls_confidence/cleanup_sync_data/update_staleness_scores/update_energy_scores/search_neurons/reinforce_neurons/decay_synapses/backfill_synapses/reindex_decisions/backfill_decision_embeddings/backfill_touches/backfill_discussed/update_fabric_scores/bootstrap_knowledge_fabric/reinforce_isomorphic/detect_skills/detect_skill_fission/detect_skill_fusion/maintain_skills/auto_anchor_notes/reconstruct_knowledge/heal_scars/consolidate_memory/detect_stagnation/deep_maintenance/audit_gaps/persist_health_report/install_hooks)",
             },
             ToolRef {
                 name: "neural_routing",
@@ -1643,7 +1719,7 @@ pub static TOOL_GROUPS: &[ToolGroup] = &[
 ];
 
 /// Total number of unique tools across all groups.
-/// Must match the MCP tools.rs count (currently 29 mega-tools).
+/// Must match the MCP tools.rs count (currently 31 mega-tools).
 pub fn tool_catalog_tool_count() -> usize {
     let mut names: Vec<&str> = TOOL_GROUPS
         .iter()
@@ -1702,7 +1778,7 @@ pub fn select_tool_groups_by_keywords(user_message: &str) -> Vec<&'static ToolGr
 
 use crate::neo4j::models::{
     ConnectedFileNode, ConstraintNode, FeatureGraphNode, LanguageStatsNode, MilestoneNode,
-    PlanNode, ProjectNode, ReleaseNode, WorkspaceNode,
+    ModuleSummaryNode, PlanNode, ProjectNode, ReleaseNode, WorkspaceNode,
 };
 use crate::neo4j::GraphStore;
 use crate::notes::models::{Note, NoteFilters, NoteImportance, NoteStatus, NoteType};
@@ -1711,6 +1787,9 @@ use crate::notes::models::{Note, NoteFilters, NoteImportance, NoteStatus, NoteTy
 // ProjectContext — all dynamic data fetched from Neo4j
 // ============================================================================
 
+/// Module summaries listed in the dynamic context
+const MAX_PROMPT_MODULES: usize = 15;
+
 /// Contextual data fetched from Neo4j for the current project.
 /// Used to build the dynamic section of the system prompt.
 #[derive(Default)]
@@ -1730,6 +1809,8 @@ pub struct ProjectContext {
     pub releases: Vec<ReleaseNode>,
     pub language_stats: Vec<LanguageStatsNode>,
     pub key_files: Vec<ConnectedFileNode>,
+    /// Cached per-directory summaries, largest directories first
+    pub module_summaries: Vec<ModuleSummaryNode>,
     pub feature_graphs: Vec<FeatureGraphNode>,
     pub last_synced: Option<DateTime<Utc>>,
     /// Pre-built GDS topology section (communities, bridges, health alerts)
@@ -1888,6 +1969,15 @@ pub async fn fetch_project_context(
         .await
         .unwrap_or_default();
 
+    // 10b. Module summaries (cached per directory, refreshed after sync)
+    let mut module_summaries = graph
+        .list_module_summaries(project_id)
+        .await
+        .unwrap_or_default();
+    module_summaries.sort_by(|a, b| b.file_count.cmp(&a.file_count).then(a.path.cmp(&b.path)));
+    module_summaries.truncate(MAX_PROMPT_MODULES);
+    ctx.module_summaries = module_summaries;
+
     // 11. Feature graphs (lightweight catalogue for the prompt)
     ctx.feature_graphs = graph
         .list_feature_graphs(Some(project_id))
@@ -2103,6 +2193,13 @@ async fn build_gds_topology_section(
 // context_to_json was removed along with the oneshot Opus refinement pipeline.
 // The FsmPromptComposer with modular sections supersedes the oneshot approach.
 
+/// " (from <source>)" for notes federated from another instance.
+fn provenance(note: &Note) -> String {
+    crate::notes::federation::source_of(note)
+        .map(|source| format!(" (from {})", source))
+        .unwrap_or_default()
+}
+
 /// Format ProjectContext as markdown for the system prompt dynamic section.
 /// Only includes sections that have data.
 /// When `user_message` is provided, appends a "Recommended Tools" section
@@ -2174,11 +2271,16 @@ pub fn context_to_markdown(ctx: &ProjectContext, user_message: Option<&str>) ->
         md.push('\n');
     }
 
-    // Global notes (cross-project knowledge)
+    // Global notes (cross-project knowledge, possibly federated)
     if !ctx.global_guidelines.is_empty() {
         md.push_str("## Global Guidelines\n");
         for g in &ctx.global_guidelines {
-            md.push_str(&format!("- [{:?}] {}\n", g.importance, g.content));
+            md.push_str(&format!(
+                "- [{:?}] {}{}\n",
+                g.importance,
+                g.content,
+                provenance(g)
+            ));
         }
         md.push('\n');
     }
@@ -2186,7 +2288,7 @@ pub fn context_to_markdown(ctx: &ProjectContext, user_message: Option<&str>) ->
     if !ctx.global_gotchas.is_empty() {
         md.push_str("## Global Gotchas\n");
         for g in &ctx.global_gotchas {
-            md.push_str(&format!("- {}\n", g.content));
+            md.push_str(&format!("- {}{}\n", g.content, provenance(g)));
         }
         md.push('\n');
     }
@@ -2240,6 +2342,19 @@ pub fn context_to_markdown(ctx: &ProjectContext, user_message: Option<&str>) ->
         md.push('\n');
     }
 
+    if !ctx.module_summaries.is_empty() {
+        md.push_str("## Modules\n");
+        for m in &ctx.module_summaries {
+            let summary = if m.summary.len() > 200 {
+                format!("{}…", &m.summary[..floor_char_boundary(&m.summary, 200)])
+            } else {
+                m.summary.clone()
+            };
+            md.push_str(&format!("- `{}` — {}\n", m.path, summary));
+        }
+        md.push('\n');
+    }
+
     if let Some(ref topo) = ctx.structural_topology {
         md.push_str("## Structural Topology\n");
         md.push_str(topo);
@@ -2605,6 +2720,7 @@ mod tests {
                 scaffolding_override: None,
                 sharing_policy: None,
                 watch_enabled: true,
+                archived: false,
             }),
             ..Default::default()
         };
@@ -2631,6 +2747,7 @@ mod tests {
                 scaffolding_override: None,
                 sharing_policy: None,
                 watch_enabled: true,
+                archived: false,
             }),
             language_stats: vec![LanguageStatsNode {
                 language: "Rust".into(),
@@ -2786,6 +2903,7 @@ mod tests {
                 scaffolding_override: None,
                 sharing_policy: None,
                 watch_enabled: true,
+                archived: false,
             }),
             workspace: Some(WorkspaceNode {
                 id: uuid::Uuid::new_v4(),
@@ -2811,6 +2929,7 @@ mod tests {
                     scaffolding_override: None,
                     sharing_policy: None,
                     watch_enabled: true,
+                    archived: false,
                 },
                 ProjectNode {
                     id: uuid::Uuid::new_v4(),
@@ -2826,6 +2945,7 @@ mod tests {
                     scaffolding_override: None,
                     sharing_policy: None,
                     watch_enabled: true,
+                    archived: false,
                 },
             ],
             ..Default::default()
@@ -2907,6 +3027,7 @@ mod tests {
                 scaffolding_override: None,
                 sharing_policy: None,
                 watch_enabled: true,
+                archived: false,
             }),
             global_guidelines: vec![{
                 let mut n = crate::notes::Note::new(
@@ -2918,19 +3039,28 @@ mod tests {
                 n.importance = crate::notes::NoteImportance::Critical;
                 n
             }],
-            global_gotchas: vec![crate::notes::Note::new(
-                None,
-                crate::notes::NoteType::Gotcha,
-                "Beware of circular deps".to_string(),
-                "test".to_string(),
-            )],
+            global_gotchas: vec![
+                crate::notes::Note::new(
+                    None,
+                    crate::notes::NoteType::Gotcha,
+                    "Beware of circular deps".to_string(),
+                    "test".to_string(),
+                ),
+                crate::notes::Note::new(
+                    None,
+                    crate::notes::NoteType::Gotcha,
+                    "Staging shares the prod database".to_string(),
+                    "federation:central".to_string(),
+                ),
+            ],
             ..Default::default()
         };
         let md = context_to_markdown(&ctx, None);
         assert!(md.contains("Global Guidelines"));
-        assert!(md.contains("Always write tests"));
+        assert!(md.contains("- [Critical] Always write tests\n"));
         assert!(md.contains("Global Gotchas"));
-        assert!(md.contains("Beware of circular deps"));
+        assert!(md.contains("- Beware of circular deps\n"));
+        assert!(md.contains("- Staging shares the prod database (from central)\n"));
     }
 
     // ================================================================
@@ -2938,11 +3068,11 @@ mod tests {
     // ================================================================
 
     #[test]
-    fn test_tool_groups_cover_all_29_mega_tools() {
+    fn test_tool_groups_cover_all_31_mega_tools() {
         let count = tool_catalog_tool_count();
         assert_eq!(
-            count, 29,
-            "TOOL_GROUPS must cover exactly 29 unique mega-tools (got {}). \
+            count, 31,
+            "TOOL_GROUPS must cover exactly 31 unique mega-tools (got {}). \
              Update the catalog when adding/removing MCP tools.",
             count
         );
@@ -3199,6 +3329,7 @@ mod tests {
                 scaffolding_override: None,
                 sharing_policy: None,
                 watch_enabled: true,
+                archived: false,
             }),
             ..Default::default()
         };
@@ -3242,6 +3373,7 @@ mod tests {
                 scaffolding_override: None,
                 sharing_policy: None,
                 watch_enabled: true,
+                archived: false,
             }),
             feature_graphs: make_feature_graphs(3),
             ..Default::default()
@@ -3257,6 +3389,25 @@ mod tests {
         assert!(md.contains("get_feature_graph(id)"));
     }
 
+    #[test]
+    fn test_context_to_markdown_with_module_summaries() {
+        let ctx = ProjectContext {
+            module_summaries: vec![ModuleSummaryNode {
+                project_id: uuid::Uuid::new_v4(),
+                path: "src/api".into(),
+                summary: "HTTP layer: routes and handlers.".into(),
+                source_hash: "abc".into(),
+                file_count: 12,
+                generated_by: "heuristic".into(),
+                updated_at: Utc::now(),
+            }],
+            ..Default::default()
+        };
+        let md = context_to_markdown(&ctx, None);
+        assert!(md.contains("## Modules"));
+        assert!(md.contains("- `src/api` — HTTP layer: routes and handlers."));
+    }
+
     #[test]
     fn test_context_to_markdown_empty_feature_graphs() {
         let ctx = ProjectContext {
@@ -3274,6 +3425,7 @@ mod tests {
                 scaffolding_override: None,
                 sharing_policy: None,
                 watch_enabled: true,
+                archived: false,
             }),
             ..Default::default()
         };
@@ -3314,6 +3466,7 @@ mod tests {
                 scaffolding_override: None,
                 sharing_policy: None,
                 watch_enabled: true,
+                archived: false,
             }),
             feature_graphs: fgs,
             ..Default::default()
@@ -3374,8 +3527,10 @@ mod tests {
                 path: path.to_string(),
                 language: "rust".to_string(),
                 hash: "test".to_string(),
+                line_count: 0,
                 last_parsed: chrono::Utc::now(),
                 project_id: Some(project.id),
+                encoding: None,
             };
             graph.upsert_file(&file).await.unwrap();
         }
@@ -3506,8 +3661,10 @@ mod tests {
             path: "src/main.rs".to_string(),
             language: "rust".to_string(),
             hash: "test".to_string(),
+            line_count: 0,
             last_parsed: chrono::Utc::now(),
             project_id: Some(project.id),
+            encoding: None,
         };
         graph.upsert_file(&file).await.unwrap();
 
@@ -0,0 +1,765 @@
+//! Execution sandbox for chat sessions.
+//!
+//! Without a sandbox the Claude CLI can reach everything the server can,
+//! subject only to the permission patterns. A [`SandboxConfig`] confines a
+//! session's tools:
+//!
+//! - **Directory allowlist** — file tools (Read, Write, Edit, Glob, Grep, ...)
+//!   may only touch the session cwd, its `add_dirs`, its attachment dir and
+//!   the configured `allowed_dirs`.
+//! - **No network** — WebFetch/WebSearch are denied.
+//! - **Bash** — when `bwrap` (bubblewrap) is installed, every command is
+//!   rewritten to run in a mount namespace where only the allowed dirs are
+//!   writable (and, with `no_network`, without a network namespace). Without
+//!   bubblewrap, Bash is denied unless `allow_unisolated_bash` opts into a
+//!   best-effort check: paths outside the allowlist (relative ones included),
+//!   inline interpreter code and network clients are denied.
+//! - **Resource limits** — memory, CPU and process count are applied to the
+//!   CLI process tree through a cgroup v2 child group when the server's
+//!   cgroup is writable (see [`apply_resource_limits`]).
+//!
+//! Enforcement happens in [`SandboxHook`], an in-process PreToolUse hook, so
+//! it runs before the CLI spawns any tool. MCP tools are not restricted: they
+//! go through the orchestrator API and its own authorization.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+use tracing::{debug, info, warn};
+
+/// Sandbox options, server-wide (`chat.sandbox` in config.yaml) or per session
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SandboxConfig {
+    /// Enforce the sandbox
+    pub enabled: bool,
+    /// Directories tools may use besides the session cwd and add_dirs
+    pub allowed_dirs: Vec<String>,
+    /// Deny network access to tools
+    pub no_network: bool,
+    /// Without bubblewrap, run Bash with a best-effort command check instead
+    /// of denying it
+    pub allow_unisolated_bash: bool,
+    /// Memory limit of the CLI process tree, in MiB
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_max_mb: Option<u64>,
+    /// CPU limit of the CLI process tree, in percent of one core
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_max_percent: Option<u32>,
+    /// Maximum number of processes in the CLI process tree
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pids_max: Option<u64>,
+}
+
+fn min_limit<T: Ord + Copy>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+impl SandboxConfig {
+    /// Combine the server default with a session's options.
+    ///
+    /// A session can opt into a sandbox or tighten the server's, never relax
+    /// it: flags are OR-ed and limits take the lowest value. Extra
+    /// `allowed_dirs` and `allow_unisolated_bash` of the session are only
+    /// honored when the server does not enforce a sandbox itself.
+    pub fn for_session(&self, session: Option<&SandboxConfig>) -> SandboxConfig {
+        let Some(session) = session else {
+            return self.clone();
+        };
+        let (allowed_dirs, allow_unisolated_bash) = if self.enabled {
+            (self.allowed_dirs.clone(), self.allow_unisolated_bash)
+        } else {
+            (session.allowed_dirs.clone(), session.allow_unisolated_bash)
+        };
+        SandboxConfig {
+            enabled: self.enabled || session.enabled,
+            allowed_dirs,
+            no_network: self.no_network || session.no_network,
+            allow_unisolated_bash,
+            memory_max_mb: min_limit(self.memory_max_mb, session.memory_max_mb),
+            cpu_max_percent: min_limit(self.cpu_max_percent, session.cpu_max_percent),
+            pids_max: min_limit(self.pids_max, session.pids_max),
+        }
+    }
+
+    /// Whether any resource limit is set
+    pub fn has_resource_limits(&self) -> bool {
+        self.memory_max_mb.is_some() || self.cpu_max_percent.is_some() || self.pids_max.is_some()
+    }
+}
+
+/// What the sandbox decided for a tool call
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Allow,
+    /// Refuse the call, with the reason shown to the agent
+    Deny(String),
+    /// Run the call with this input instead
+    Rewrite(serde_json::Value),
+}
+
+/// Tools reading or writing the file given in one of these input fields
+const FILE_TOOLS: &[&str] = &[
+    "Read",
+    "Write",
+    "Edit",
+    "MultiEdit",
+    "NotebookEdit",
+    "NotebookRead",
+    "Glob",
+    "Grep",
+    "LS",
+];
+const PATH_FIELDS: &[&str] = &["file_path", "notebook_path", "path"];
+const NETWORK_TOOLS: &[&str] = &["WebFetch", "WebSearch"];
+/// Commands denied under `no_network` when Bash is not isolated
+const NETWORK_COMMANDS: &[&str] = &[
+    "curl", "wget", "ssh", "scp", "sftp", "rsync", "nc", "ncat", "netcat", "telnet", "ftp", "socat",
+];
+/// Network subcommands of package managers and VCS, under `no_network`
+const NETWORK_SUBCOMMANDS: &[(&str, &[&str])] = &[
+    ("git", &["clone", "fetch", "pull", "push", "ls-remote"]),
+    ("npm", &["install", "i", "ci", "publish"]),
+    ("pip", &["install", "download"]),
+    ("cargo", &["install", "publish"]),
+];
+/// Interpreters and the flags running code given inline, which the
+/// unisolated check cannot see into
+const INLINE_CODE_FLAGS: &[(&str, &[&str])] = &[
+    ("python", &["-c"]),
+    ("python3", &["-c"]),
+    ("node", &["-e", "--eval", "-p", "--print"]),
+    ("deno", &["eval"]),
+    ("bun", &["-e", "--eval"]),
+    ("perl", &["-e", "-E"]),
+    ("ruby", &["-e"]),
+    ("php", &["-r"]),
+];
+/// System directories a command may name without being in the allowlist
+const SYSTEM_DIRS: &[&str] = &["/usr", "/bin", "/sbin", "/lib", "/lib64", "/opt", "/dev"];
+
+/// Make a path absolute (relative to `cwd`) and drop `.` and `..` lexically.
+fn normalize(path: &str, cwd: &Path) -> PathBuf {
+    let expanded = crate::expand_tilde(path);
+    let path = Path::new(&expanded);
+    let joined = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        cwd.join(path)
+    };
+    let mut out = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::ParentDir => {
+                out.pop();
+            }
+            Component::CurDir => {}
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Resolve symlinks of the longest existing prefix of `path`.
+fn resolve(path: &Path) -> PathBuf {
+    let mut existing = path.to_path_buf();
+    let mut rest = Vec::new();
+    while !existing.exists() {
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                rest.push(name.to_os_string());
+                existing = parent.to_path_buf();
+            }
+            _ => return path.to_path_buf(),
+        }
+    }
+    let mut resolved = existing.canonicalize().unwrap_or(existing);
+    for name in rest.into_iter().rev() {
+        resolved.push(name);
+    }
+    resolved
+}
+
+/// The `bwrap` binary, looked up once on PATH.
+fn bwrap_path() -> Option<&'static PathBuf> {
+    static BWRAP: OnceLock<Option<PathBuf>> = OnceLock::new();
+    BWRAP
+        .get_or_init(|| {
+            let path = std::env::var_os("PATH")?;
+            std::env::split_paths(&path)
+                .map(|dir| dir.join("bwrap"))
+                .find(|candidate| candidate.is_file())
+        })
+        .as_ref()
+}
+
+/// Warn (once) that Bash cannot be isolated by an enabled sandbox.
+pub fn warn_if_unisolated(config: &SandboxConfig) {
+    static WARNED: std::sync::Once = std::sync::Once::new();
+    if !config.enabled || bwrap_path().is_some() {
+        return;
+    }
+    WARNED.call_once(|| {
+        if config.allow_unisolated_bash {
+            warn!(
+                "Chat sandbox is enabled but bubblewrap (bwrap) is not installed: \
+                 Bash commands only get a best-effort check (allow_unisolated_bash)"
+            );
+        } else {
+            warn!(
+                "Chat sandbox is enabled but bubblewrap (bwrap) is not installed: \
+                 Bash is denied in sandboxed sessions (install bwrap, or set \
+                 chat.sandbox.allow_unisolated_bash for a best-effort check)"
+            );
+        }
+    });
+}
+
+/// Quote a string for a POSIX shell.
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Tokens of a shell command, with quotes and redirections stripped.
+fn command_words(command: &str) -> Vec<Vec<String>> {
+    command
+        .split(['|', ';', '&', '\n', '(', ')', '`'])
+        .map(|segment| {
+            segment
+                .split_whitespace()
+                .map(|w| {
+                    w.trim_start_matches(['<', '>', '0', '1', '2'])
+                        .trim_matches(['"', '\''])
+                        .to_string()
+                })
+                .filter(|w| !w.is_empty())
+                .collect()
+        })
+        .collect()
+}
+
+/// The sandbox of one session: its config and resolved directory allowlist
+#[derive(Debug, Clone)]
+pub struct Sandbox {
+    config: SandboxConfig,
+    roots: Vec<PathBuf>,
+}
+
+impl Sandbox {
+    /// Build the sandbox of a session from its cwd, add_dirs and attachment
+    /// dir. Returns None when the config does not enable it.
+    pub fn new(
+        config: SandboxConfig,
+        cwd: &str,
+        add_dirs: &[String],
+        session_id: Option<&str>,
+    ) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        let base = PathBuf::from(crate::expand_tilde(cwd));
+        let mut dirs: Vec<String> = vec![cwd.to_string()];
+        dirs.extend(add_dirs.iter().cloned());
+        dirs.extend(config.allowed_dirs.iter().cloned());
+        let mut roots: Vec<PathBuf> = dirs.iter().map(|d| resolve(&normalize(d, &base))).collect();
+        if let Some(dir) = session_id.and_then(|id| super::attachments::session_dir(id).ok()) {
+            roots.push(resolve(&dir));
+        }
+        roots.dedup();
+        warn_if_unisolated(&config);
+        Some(Self { config, roots })
+    }
+
+    pub fn config(&self) -> &SandboxConfig {
+        &self.config
+    }
+
+    /// Directories tools may use
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    /// Whether `path` (relative to `cwd`) is inside the allowlist
+    pub fn allows_path(&self, path: &str, cwd: &Path) -> bool {
+        let path = resolve(&normalize(path, cwd));
+        self.roots.iter().any(|root| path.starts_with(root))
+    }
+
+    /// Decide on a tool call.
+    pub fn check(&self, tool_name: &str, input: &serde_json::Value, cwd: &str) -> Verdict {
+        let cwd = PathBuf::from(crate::expand_tilde(cwd));
+        if self.config.no_network && NETWORK_TOOLS.contains(&tool_name) {
+            return Verdict::Deny(format!(
+                "{} is disabled: this session's sandbox has no network access",
+                tool_name
+            ));
+        }
+        if FILE_TOOLS.contains(&tool_name) {
+            for field in PATH_FIELDS {
+                if let Some(path) = input.get(*field).and_then(|v| v.as_str()) {
+                    if !self.allows_path(path, &cwd) {
+                        return Verdict::Deny(self.outside_message(path));
+                    }
+                }
+            }
+            return Verdict::Allow;
+        }
+        if tool_name == "Bash" {
+            return self.check_bash(input, &cwd, bwrap_path().map(PathBuf::as_path));
+        }
+        Verdict::Allow
+    }
+
+    /// Decide on a Bash call: wrap it under bubblewrap when available,
+    /// otherwise deny it unless unisolated Bash is allowed.
+    fn check_bash(&self, input: &serde_json::Value, cwd: &Path, bwrap: Option<&Path>) -> Verdict {
+        let Some(command) = input.get("command").and_then(|v| v.as_str()) else {
+            return Verdict::Allow;
+        };
+        if let Some(bwrap) = bwrap {
+            let mut input = input.clone();
+            input["command"] = serde_json::Value::String(self.wrap_command(bwrap, command));
+            return Verdict::Rewrite(input);
+        }
+        if !self.config.allow_unisolated_bash {
+            return Verdict::Deny(
+                "Bash is disabled: this session's sandbox cannot isolate commands \
+                 (bubblewrap is not installed on the server)"
+                    .to_string(),
+            );
+        }
+        self.check_command(command, cwd)
+    }
+
+    fn outside_message(&self, path: &str) -> String {
+        let roots: Vec<String> = self.roots.iter().map(|r| r.display().to_string()).collect();
+        format!(
+            "'{}' is outside this session's sandbox. Allowed directories: {}",
+            path,
+            roots.join(", ")
+        )
+    }
+
+    /// Best-effort check of a Bash command when it cannot be isolated.
+    ///
+    /// Every argument naming a path is resolved, against the directory of
+    /// the last `cd` — `cd ..` and `cat ../other/.env` escape as surely as
+    /// absolute paths.
+    fn check_command(&self, command: &str, cwd: &Path) -> Verdict {
+        let mut cwd = cwd.to_path_buf();
+        for words in command_words(command) {
+            let Some(program) = words.first() else {
+                continue;
+            };
+            let program = program.rsplit('/').next().unwrap_or(program);
+            let inline_code = INLINE_CODE_FLAGS.iter().any(|(p, flags)| {
+                *p == program && words[1..].iter().any(|w| flags.contains(&w.as_str()))
+            });
+            if inline_code {
+                return Verdict::Deny(format!(
+                    "Inline `{}` code cannot be checked by this session's sandbox; \
+                     write it to a file in the project instead",
+                    program
+                ));
+            }
+            if self.config.no_network {
+                let subcommand = words.get(1).map(String::as_str).unwrap_or_default();
+                let network = NETWORK_COMMANDS.contains(&program)
+                    || NETWORK_SUBCOMMANDS
+                        .iter()
+                        .any(|(p, subs)| *p == program && subs.contains(&subcommand));
+                if network {
+                    return Verdict::Deny(format!(
+                        "`{}` needs network access, which this session's sandbox denies",
+                        program
+                    ));
+                }
+            }
+            for word in &words {
+                // `--output=../x` names a path too
+                let path = word.rsplit_once('=').map_or(word.as_str(), |(_, v)| v);
+                let path_like = path.contains('/') || path.starts_with('~') || path == "..";
+                if !path_like || self.allows_path(path, &cwd) {
+                    continue;
+                }
+                let normalized = normalize(path, &cwd);
+                if SYSTEM_DIRS.iter().any(|dir| normalized.starts_with(dir)) {
+                    continue;
+                }
+                return Verdict::Deny(self.outside_message(path));
+            }
+            if program == "cd" {
+                if let Some(dir) = words.get(1) {
+                    cwd = normalize(dir, &cwd);
+                }
+            }
+        }
+        Verdict::Allow
+    }
+
+    /// Wrap a command so it runs under bubblewrap: the filesystem is
+    /// read-only except the allowed dirs, /tmp is private, and the network
+    /// namespace is unshared under `no_network`.
+    fn wrap_command(&self, bwrap: &Path, command: &str) -> String {
+        let mut args = vec![
+            shell_quote(&bwrap.to_string_lossy()),
+            "--die-with-parent".to_string(),
+            "--ro-bind / /".to_string(),
+            "--dev /dev".to_string(),
+            "--proc /proc".to_string(),
+            "--tmpfs /tmp".to_string(),
+        ];
+        for root in &self.roots {
+            if root.exists() {
+                let root = shell_quote(&root.to_string_lossy());
+                args.push(format!("--bind {} {}", root, root));
+            }
+        }
+        if self.config.no_network {
+            args.push("--unshare-net".to_string());
+        }
+        args.push(format!("-- /bin/sh -c {}", shell_quote(command)));
+        args.join(" ")
+    }
+}
+
+// ============================================================================
+// SandboxHook — PreToolUse enforcement
+// ============================================================================
+
+/// In-process PreToolUse hook enforcing a session's [`Sandbox`].
+///
+/// Registered in `create_session()` and `resume_session()` ahead of the other
+/// PreToolUse hooks, for runner sessions too.
+pub(crate) struct SandboxHook {
+    sandbox: Sandbox,
+}
+
+impl SandboxHook {
+    pub fn new(sandbox: Sandbox) -> Self {
+        Self { sandbox }
+    }
+
+    fn output(
+        decision: Option<&str>,
+        reason: Option<String>,
+        updated_input: Option<serde_json::Value>,
+    ) -> nexus_claude::HookJSONOutput {
+        nexus_claude::HookJSONOutput::Sync(nexus_claude::SyncHookJSONOutput {
+            continue_: Some(true),
+            hook_specific_output: Some(nexus_claude::HookSpecificOutput::PreToolUse(
+                nexus_claude::PreToolUseHookSpecificOutput {
+                    permission_decision: decision.map(str::to_string),
+                    permission_decision_reason: reason,
+                    updated_input,
+                    additional_context: None,
+                },
+            )),
+            ..Default::default()
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl nexus_claude::HookCallback for SandboxHook {
+    async fn execute(
+        &self,
+        input: &nexus_claude::HookInput,
+        _tool_use_id: Option<&str>,
+        _context: &nexus_claude::HookContext,
+    ) -> std::result::Result<nexus_claude::HookJSONOutput, nexus_claude::SdkError> {
+        let nexus_claude::HookInput::PreToolUse(pre_tool) = input else {
+            return Ok(nexus_claude::HookJSONOutput::Sync(
+                nexus_claude::SyncHookJSONOutput {
+                    continue_: Some(true),
+                    ..Default::default()
+                },
+            ));
+        };
+        match self
+            .sandbox
+            .check(&pre_tool.tool_name, &pre_tool.tool_input, &pre_tool.cwd)
+        {
+            Verdict::Allow => Ok(Self::output(None, None, None)),
+            Verdict::Deny(reason) => {
+                info!(tool = %pre_tool.tool_name, %reason, "Sandbox denied tool call");
+                Ok(Self::output(Some("deny"), Some(reason), None))
+            }
+            Verdict::Rewrite(updated) => {
+                // The rewritten input only takes effect with an explicit
+                // decision: keep bypass sessions unprompted, ask otherwise.
+                let decision = match pre_tool.permission_mode.as_deref() {
+                    Some("bypassPermissions") => "allow",
+                    _ => "ask",
+                };
+                debug!(tool = %pre_tool.tool_name, "Sandbox wrapped tool call");
+                Ok(Self::output(
+                    Some(decision),
+                    Some("Runs inside the session sandbox".to_string()),
+                    Some(updated),
+                ))
+            }
+        }
+    }
+}
+
+/// Add the hook of a sandbox to a session's hooks, ahead of the other
+/// PreToolUse hooks.
+pub(crate) fn register_hook(
+    hooks: &mut std::collections::HashMap<String, Vec<nexus_claude::HookMatcher>>,
+    sandbox: Sandbox,
+) {
+    hooks.entry("PreToolUse".to_string()).or_default().insert(
+        0,
+        nexus_claude::HookMatcher {
+            matcher: None,
+            hooks: vec![std::sync::Arc::new(SandboxHook::new(sandbox))],
+        },
+    );
+}
+
+// ============================================================================
+// Resource limits (cgroup v2)
+// ============================================================================
+
+/// cgroup v2 directory of this process, from /proc/self/cgroup.
+fn own_cgroup_dir() -> Option<PathBuf> {
+    let content = std::fs::read_to_string("/proc/self/cgroup").ok()?;
+    let path = content.lines().find_map(|l| l.strip_prefix("0::"))?;
+    Some(Path::new("/sys/fs/cgroup").join(path.trim_start_matches('/')))
+}
+
+fn session_cgroup_dir(session_id: &str) -> Option<PathBuf> {
+    Some(own_cgroup_dir()?.join(format!("po-chat-{}", session_id)))
+}
+
+/// Move the CLI process of a session into a cgroup with the configured
+/// memory, CPU and process limits. Descendants (the tools) inherit it.
+///
+/// Only works where the server's cgroup is delegated to it (e.g. a systemd
+/// unit with `Delegate=yes`); returns false and logs otherwise.
+pub fn apply_resource_limits(session_id: &str, pid: u32, config: &SandboxConfig) -> bool {
+    if !config.enabled || !config.has_resource_limits() {
+        return false;
+    }
+    let Some(dir) = session_cgroup_dir(session_id) else {
+        warn!(
+            session_id,
+            "Sandbox resource limits need cgroup v2, not applied"
+        );
+        return false;
+    };
+    let result = (|| -> std::io::Result<()> {
+        std::fs::create_dir_all(&dir)?;
+        if let Some(mb) = config.memory_max_mb {
+            std::fs::write(dir.join("memory.max"), (mb * 1024 * 1024).to_string())?;
+        }
+        if let Some(percent) = config.cpu_max_percent {
+            let quota = u64::from(percent.max(1)) * 1000;
+            std::fs::write(dir.join("cpu.max"), format!("{} 100000", quota))?;
+        }
+        if let Some(pids) = config.pids_max {
+            std::fs::write(dir.join("pids.max"), pids.to_string())?;
+        }
+        std::fs::write(dir.join("cgroup.procs"), pid.to_string())
+    })();
+    match result {
+        Ok(()) => {
+            info!(session_id, pid, cgroup = %dir.display(), "Applied sandbox resource limits");
+            true
+        }
+        Err(e) => {
+            warn!(
+                session_id,
+                cgroup = %dir.display(),
+                "Sandbox resource limits not applied (cgroup not writable): {}",
+                e
+            );
+            let _ = std::fs::remove_dir(&dir);
+            false
+        }
+    }
+}
+
+/// Remove the cgroup of a session once its processes have exited.
+pub fn release_resource_limits(session_id: &str) {
+    if let Some(dir) = session_cgroup_dir(session_id) {
+        let _ = std::fs::remove_dir(dir);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sandbox(root: &Path, no_network: bool) -> Sandbox {
+        let config = SandboxConfig {
+            enabled: true,
+            no_network,
+            ..Default::default()
+        };
+        Sandbox::new(config, &root.to_string_lossy(), &[], None).unwrap()
+    }
+
+    #[test]
+    fn test_for_session_only_tightens() {
+        let server = SandboxConfig {
+            enabled: true,
+            allowed_dirs: vec!["/srv/shared".into()],
+            memory_max_mb: Some(2048),
+            ..Default::default()
+        };
+        let session = SandboxConfig {
+            enabled: false,
+            allowed_dirs: vec!["/".into()],
+            no_network: true,
+            memory_max_mb: Some(4096),
+            pids_max: Some(64),
+            ..Default::default()
+        };
+        let merged = server.for_session(Some(&session));
+        assert!(merged.enabled && merged.no_network);
+        assert_eq!(merged.allowed_dirs, vec!["/srv/shared".to_string()]);
+        assert_eq!(merged.memory_max_mb, Some(2048));
+        assert_eq!(merged.pids_max, Some(64));
+
+        let opt_in = SandboxConfig::default().for_session(Some(&session));
+        assert!(!opt_in.enabled);
+        assert_eq!(opt_in.allowed_dirs, vec!["/".to_string()]);
+        assert!(Sandbox::new(SandboxConfig::default(), "/tmp", &[], None).is_none());
+    }
+
+    #[test]
+    fn test_file_tools_stay_in_allowlist() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("repo");
+        std::fs::create_dir_all(&root).unwrap();
+        let sb = sandbox(&root, false);
+        let cwd = root.to_string_lossy();
+
+        let inside = json!({"file_path": root.join("src/new.rs")});
+        assert_eq!(sb.check("Write", &inside, &cwd), Verdict::Allow);
+        assert_eq!(
+            sb.check("Read", &json!({"file_path": "src/lib.rs"}), &cwd),
+            Verdict::Allow
+        );
+        for escape in ["../secret.txt", "/etc/passwd"] {
+            let verdict = sb.check("Read", &json!({ "file_path": escape }), &cwd);
+            assert!(matches!(verdict, Verdict::Deny(_)), "{}", escape);
+        }
+        let glob = sb.check("Glob", &json!({"pattern": "*", "path": "/"}), &cwd);
+        assert!(matches!(glob, Verdict::Deny(_)));
+        assert_eq!(
+            sb.check("mcp__project-orchestrator__task", &json!({}), &cwd),
+            Verdict::Allow
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_out_of_allowlist_is_denied() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("repo");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(dir.path().join("secret.txt"), "nope").unwrap();
+        std::os::unix::fs::symlink(dir.path().join("secret.txt"), root.join("link")).unwrap();
+        let sb = sandbox(&root, false);
+        assert!(!sb.allows_path("link", &root));
+    }
+
+    #[test]
+    fn test_no_network() {
+        let dir = tempfile::tempdir().unwrap();
+        let sb = sandbox(dir.path(), true);
+        let cwd = dir.path().to_string_lossy();
+        let verdict = sb.check("WebFetch", &json!({"url": "https://example.com"}), &cwd);
+        assert!(matches!(verdict, Verdict::Deny(_)));
+
+        let deny =
+            |command: &str| matches!(sb.check_command(command, dir.path()), Verdict::Deny(_));
+        assert!(deny("curl https://example.com"));
+        assert!(deny("cargo build && git push origin main"));
+        assert!(deny("/usr/bin/wget -q x"));
+        assert!(!deny("git status && cargo test"));
+    }
+
+    #[test]
+    fn test_bash_fails_closed_without_bubblewrap() {
+        let dir = tempfile::tempdir().unwrap();
+        let cwd = dir.path();
+        let input = json!({"command": "ls"});
+
+        let strict = sandbox(cwd, false);
+        assert!(matches!(
+            strict.check_bash(&input, cwd, None),
+            Verdict::Deny(_)
+        ));
+        assert!(matches!(
+            strict.check_bash(&input, cwd, Some(Path::new("/usr/bin/bwrap"))),
+            Verdict::Rewrite(_)
+        ));
+
+        let config = SandboxConfig {
+            enabled: true,
+            allow_unisolated_bash: true,
+            ..Default::default()
+        };
+        let lenient = Sandbox::new(config, &cwd.to_string_lossy(), &[], None).unwrap();
+        assert_eq!(lenient.check_bash(&input, cwd, None), Verdict::Allow);
+
+        // A session cannot opt out of the server's fail-closed default
+        let session = SandboxConfig {
+            allow_unisolated_bash: true,
+            ..Default::default()
+        };
+        let merged = strict.config().for_session(Some(&session));
+        assert!(!merged.allow_unisolated_bash);
+    }
+
+    #[test]
+    fn test_check_command_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("repo");
+        std::fs::create_dir_all(&root).unwrap();
+        let sb = sandbox(&root, false);
+        let deny = |command: &str| matches!(sb.check_command(command, &root), Verdict::Deny(_));
+        assert!(!deny("ls -la && /usr/bin/env cargo test 2>/dev/null"));
+        assert!(!deny(&format!("cat {}/README.md", root.display())));
+        assert!(deny("cat /root/.ssh/id_rsa"));
+        assert!(deny("echo x > ~/.bashrc"));
+        assert!(deny("cd /var/lib && ls"));
+        assert!(deny("cat /etc/shadow"));
+
+        // Relative escapes
+        assert!(!deny("cat src/../README.md && ls ./target"));
+        assert!(deny("cat ../../other-repo/.env"));
+        assert!(deny("cd ..; ls"));
+        assert!(deny("cp x.txt --target-directory=../out"));
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        assert!(!deny("cd src && cat ../Cargo.toml"));
+        assert!(deny("cd src && cat ../../secret"));
+
+        // Inline code is opaque to the check
+        assert!(deny(
+            "python3 -c 'import os; print(open(\"/root/x\").read())'"
+        ));
+        assert!(deny("node -e \"require('https').get('x')\""));
+        assert!(!deny("python3 scripts/gen.py"));
+    }
+
+    #[test]
+    fn test_wrap_command() {
+        let dir = tempfile::tempdir().unwrap();
+        let sb = sandbox(dir.path(), true);
+        let wrapped = sb.wrap_command(Path::new("/usr/bin/bwrap"), "echo 'hi' | wc -c");
+        assert!(wrapped.starts_with("'/usr/bin/bwrap' --die-with-parent --ro-bind / /"));
+        assert!(wrapped.contains("--unshare-net"));
+        let root = shell_quote(&sb.roots()[0].to_string_lossy());
+        assert!(wrapped.contains(&format!("--bind {} {}", root, root)));
+        assert!(wrapped.ends_with(r"-- /bin/sh -c 'echo '\''hi'\'' | wc -c'"));
+    }
+}
@@ -0,0 +1,211 @@
+//! Documentation Enrichment Stage for the Chat Pipeline.
+//!
+//! Searches the project's Markdown documentation (README, docs/…) indexed at
+//! sync time and injects the best-matching sections, each cited as
+//! `path#anchor` with its start line, so the agent can ground answers in the
+//! project's own docs and not only in code.
+//!
+//! Skips when the session is not scoped to a project or the search is slow.
+//!
+//! Controlled by `ENRICHMENT_DOCUMENTATION` env var (default: true).
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::time::timeout;
+use tracing::debug;
+
+use crate::chat::enrichment::{
+    EnrichmentConfig, EnrichmentInput, EnrichmentSource, ParallelEnrichmentStage, StageOutput,
+};
+use crate::meilisearch::traits::SearchStore;
+
+/// Sections injected at most
+const MAX_SECTIONS: usize = 3;
+/// Hits below this ranking score are too weak to cite
+const MIN_SCORE: f64 = 0.3;
+/// Characters of section text quoted per hit
+const EXCERPT_CHARS: usize = 400;
+/// Characters of the user message used as query
+const MAX_QUERY_CHARS: usize = 200;
+
+/// Enrichment stage that cites relevant documentation sections.
+pub struct DocumentationStage {
+    search: Arc<dyn SearchStore>,
+}
+
+impl DocumentationStage {
+    /// Create a new documentation stage.
+    pub fn new(search: Arc<dyn SearchStore>) -> Self {
+        Self { search }
+    }
+}
+
+#[async_trait::async_trait]
+impl ParallelEnrichmentStage for DocumentationStage {
+    async fn execute(&self, input: &EnrichmentInput) -> Result<StageOutput> {
+        let mut output = StageOutput::new(self.name());
+
+        let Some(slug) = input.project_slug.as_deref() else {
+            return Ok(output);
+        };
+        let query: String = input.message.trim().chars().take(MAX_QUERY_CHARS).collect();
+        if query.len() < 3 {
+            return Ok(output);
+        }
+
+        let hits = match timeout(
+            Duration::from_millis(200),
+            self.search
+                .search_docs_with_scores(&query, MAX_SECTIONS, Some(slug)),
+        )
+        .await
+        {
+            Ok(Ok(hits)) => hits,
+            Ok(Err(e)) => {
+                debug!("[documentation] Docs search failed: {}", e);
+                return Ok(output);
+            }
+            Err(_) => {
+                debug!("[documentation] Docs search timed out");
+                return Ok(output);
+            }
+        };
+
+        let entries: Vec<String> = hits
+            .iter()
+            .filter(|h| h.score >= MIN_SCORE)
+            .map(|h| {
+                let doc = &h.document;
+                let heading = if doc.heading.is_empty() {
+                    doc.title.as_str()
+                } else {
+                    doc.heading.as_str()
+                };
+                let mut excerpt: String = doc.content.chars().take(EXCERPT_CHARS).collect();
+                if excerpt.len() < doc.content.len() {
+                    excerpt.push('…');
+                }
+                let quoted = excerpt
+                    .lines()
+                    .map(|l| format!("> {}", l))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                format!(
+                    "- **{}** — `{}#{}` (line {})\n{}",
+                    heading, doc.path, doc.anchor, doc.line_start, quoted
+                )
+            })
+            .collect();
+
+        if entries.is_empty() {
+            return Ok(output);
+        }
+
+        debug!("[documentation] Citing {} section(s)", entries.len());
+        output.add_section(
+            "Project Documentation",
+            format!(
+                "## Project Documentation\nRelevant sections of the project's docs — cite them by `path#anchor`.\n\n{}",
+                entries.join("\n\n")
+            ),
+            self.name(),
+            EnrichmentSource::Documentation,
+        );
+
+        Ok(output)
+    }
+
+    fn name(&self) -> &str {
+        "documentation"
+    }
+
+    fn is_enabled(&self, config: &EnrichmentConfig) -> bool {
+        config.documentation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meilisearch::indexes::DocSectionDocument;
+    use crate::meilisearch::mock::MockSearchStore;
+    use uuid::Uuid;
+
+    fn test_input(message: &str, project_slug: Option<&str>) -> EnrichmentInput {
+        EnrichmentInput {
+            message: message.to_string(),
+            session_id: Uuid::new_v4(),
+            project_slug: project_slug.map(str::to_string),
+            project_id: None,
+            cwd: None,
+            protocol_run_id: None,
+            protocol_state: None,
+            excluded_note_ids: Default::default(),
+            reasoning_path_tracker: None,
+        }
+    }
+
+    async fn seeded_store() -> Arc<MockSearchStore> {
+        let store = Arc::new(MockSearchStore::new());
+        store
+            .index_doc_sections(&[DocSectionDocument {
+                id: "1".to_string(),
+                path: "/repo/README.md".to_string(),
+                title: "Demo".to_string(),
+                heading: "Deployment".to_string(),
+                anchor: "deployment".to_string(),
+                level: 2,
+                line_start: 12,
+                content: "Run `make deploy` from the repository root.".to_string(),
+                code_languages: vec![],
+                project_id: "p".to_string(),
+                project_slug: "demo".to_string(),
+            }])
+            .await
+            .unwrap();
+        store
+    }
+
+    #[tokio::test]
+    async fn test_cites_matching_sections() {
+        let stage = DocumentationStage::new(seeded_store().await);
+        let output = stage
+            .execute(&test_input("deployment", Some("demo")))
+            .await
+            .unwrap();
+
+        assert_eq!(output.sections.len(), 1);
+        let content = &output.sections[0].content;
+        assert!(content.contains("`/repo/README.md#deployment` (line 12)"));
+        assert!(content.contains("> Run `make deploy`"));
+        assert_eq!(
+            output.sections[0].enrichment_source,
+            EnrichmentSource::Documentation
+        );
+    }
+
+    #[tokio::test]
+    async fn test_skips_without_project_or_match() {
+        let stage = DocumentationStage::new(seeded_store().await);
+
+        let output = stage
+            .execute(&test_input("deployment", None))
+            .await
+            .unwrap();
+        assert!(output.sections.is_empty());
+
+        let output = stage
+            .execute(&test_input("deployment", Some("other")))
+            .await
+            .unwrap();
+        assert!(output.sections.is_empty());
+
+        let output = stage
+            .execute(&test_input("unrelated", Some("demo")))
+            .await
+            .unwrap();
+        assert!(output.sections.is_empty());
+    }
+}
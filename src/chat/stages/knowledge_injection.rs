@@ -33,6 +33,7 @@ use crate::chat::enrichment::{
 };
 use crate::chat::entity_extractor::{self, EntityType as ChatEntityType, ExtractedEntity};
 use crate::chat::stages::intent_weights::IntentWeightMap;
+use crate::feedback::relevance::RelevanceWeights;
 use crate::meilisearch::SearchStore;
 use crate::neo4j::traits::GraphStore;
 use crate::neurons::intent::{IntentDetector, QueryIntentMode};
@@ -878,6 +879,34 @@ fn truncate_content(content: &str, max_chars: usize) -> String {
     }
 }
 
+/// Scale notes by their learned relevance weight and drop the ones users
+/// repeatedly voted irrelevant (see [`crate::feedback::relevance`]).
+async fn apply_relevance_weights(
+    graph: &dyn GraphStore,
+    mut notes: Vec<ScoredNote>,
+    mut workspace_notes: Vec<ScoredNote>,
+) -> (Vec<ScoredNote>, Vec<ScoredNote>) {
+    let ids: Vec<Uuid> = notes
+        .iter()
+        .chain(workspace_notes.iter())
+        .filter_map(|n| n.id.parse().ok())
+        .collect();
+    let weights = RelevanceWeights::load(graph, &ids).await;
+    for list in [&mut notes, &mut workspace_notes] {
+        list.retain(|n| {
+            n.id.parse::<Uuid>()
+                .map(|id| !weights.is_suppressed(id))
+                .unwrap_or(true)
+        });
+        for note in list.iter_mut() {
+            if let Ok(id) = note.id.parse::<Uuid>() {
+                note.score *= weights.weight(id);
+            }
+        }
+    }
+    (notes, workspace_notes)
+}
+
 /// Map an [`IntentDetector`] result to the enrichment hint string
 /// used by [`IntentWeightMap`].
 ///
@@ -1009,6 +1038,10 @@ impl ParallelEnrichmentStage for KnowledgeInjectionStage {
         for note in &mut notes {
             note.score *= weight_map.get(&note.note_type);
         }
+
+        // ── Learned relevance (chat context feedback) ───────────────────
+        let (mut notes, workspace_notes) =
+            apply_relevance_weights(self.graph.as_ref(), notes, workspace_notes).await;
         notes.sort_by(|a, b| {
             b.score
                 .partial_cmp(&a.score)
@@ -1078,6 +1111,25 @@ impl ParallelEnrichmentStage for KnowledgeInjectionStage {
             });
         }
 
+        // Surfaced items, for the context feedback the frontend offers on them
+        let surfaced_notes: Vec<&str> = workspace_notes
+            .iter()
+            .chain(notes.iter())
+            .map(|n| n.id.as_str())
+            .collect();
+        if !surfaced_notes.is_empty() {
+            output.set_hint("context_note_ids", surfaced_notes.join(","));
+        }
+        let surfaced_symbols: Vec<&str> = entities
+            .iter()
+            .filter(|e| e.entity_type != ChatEntityType::File)
+            .take(effective_config.max_entity_queries)
+            .map(|e| e.identifier.as_str())
+            .collect();
+        if !surfaced_symbols.is_empty() {
+            output.set_hint("context_symbols", surfaced_symbols.join(","));
+        }
+
         // Render and inject into output
         if let Some(content) = self.render_knowledge(
             &notes,
@@ -1456,6 +1508,51 @@ mod tests {
         assert_eq!(result.content, "high score version");
     }
 
+    #[tokio::test]
+    async fn test_apply_relevance_weights() {
+        use crate::neo4j::mock::MockGraphStore;
+        use crate::neo4j::models::{ContextFeedbackNode, ContextItemType};
+
+        let graph = MockGraphStore::new();
+        let (noisy, useful) = (Uuid::new_v4(), Uuid::new_v4());
+        for _ in 0..3 {
+            let session = crate::test_helpers::test_chat_session(Some("proj"));
+            graph.create_chat_session(&session).await.unwrap();
+            for (note, helpful) in [(noisy, false), (useful, true)] {
+                graph
+                    .upsert_context_feedback(&ContextFeedbackNode {
+                        session_id: session.id,
+                        item_type: ContextItemType::Note,
+                        item_id: note.to_string(),
+                        helpful,
+                        comment: None,
+                        created_at: chrono::Utc::now(),
+                    })
+                    .await
+                    .unwrap();
+            }
+        }
+        let scored = |id: String| ScoredNote {
+            id,
+            note_type: "Gotcha".to_string(),
+            importance: "High".to_string(),
+            content: "content".to_string(),
+            score: 0.5,
+            source: "bm25_search",
+        };
+
+        let (notes, workspace_notes) = apply_relevance_weights(
+            &graph,
+            vec![scored(noisy.to_string()), scored(useful.to_string())],
+            vec![scored("not-a-uuid".to_string())],
+        )
+        .await;
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].id, useful.to_string());
+        assert!(notes[0].score > 0.5);
+        assert_eq!(workspace_notes[0].score, 0.5);
+    }
+
     // ── UUID extraction tests ─────────────────────────────────────────
 
     #[test]
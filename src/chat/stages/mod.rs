@@ -6,6 +6,7 @@
 //! the pipeline merges in registration order.
 
 pub mod biomimicry;
+pub mod documentation;
 pub mod file_context;
 pub mod intent_weights;
 pub mod knowledge_injection;
@@ -13,15 +14,18 @@ pub mod mcp_federation_stage;
 pub mod persona;
 #[cfg(test)]
 mod pipeline_e2e_tests;
+pub mod schema;
 pub mod skill_activation;
 pub mod status_injection;
 pub mod user_profile;
 
 pub use biomimicry::BiomimicryStage;
+pub use documentation::DocumentationStage;
 pub use file_context::FileContextStage;
 pub use knowledge_injection::KnowledgeInjectionStage;
 pub use mcp_federation_stage::McpFederationStage;
 pub use persona::PersonaStage;
+pub use schema::SchemaStage;
 pub use skill_activation::SkillActivationStage;
 pub use status_injection::{GraphProtocolProvider, StatusInjectionConfig, StatusInjectionStage};
 pub use user_profile::UserProfileStage;
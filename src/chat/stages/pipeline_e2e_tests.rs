@@ -71,6 +71,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         graph.projects.write().await.insert(project_id, project);
         (project_id, slug)
@@ -128,6 +129,7 @@ mod tests {
             memory_horizon: crate::notes::MemoryHorizon::Operational,
             scar_intensity: 0.0,
             sharing_consent: Default::default(),
+            version: 0,
         };
         graph.notes.write().await.insert(note_id, note);
 
@@ -198,6 +200,7 @@ mod tests {
             project_id: Some(project_id),
             execution_context: None,
             persona: None,
+            version: 0,
         };
         graph.plans.write().await.insert(plan_id, plan);
 
@@ -234,6 +237,8 @@ mod tests {
                 execution_context: None,
                 persona: None,
                 prompt_cache: None,
+                board_position: None,
+                version: 0,
             };
             graph.tasks.write().await.insert(task_id, task);
             task_ids.push(task_id);
@@ -290,6 +295,7 @@ mod tests {
             entity_id: entity_id.to_string(),
             signature_hash: None,
             body_hash: None,
+            symbol_id: None,
             last_verified: Utc::now(),
             is_valid: true,
         }
@@ -0,0 +1,281 @@
+//! Database Schema Enrichment Stage for the Chat Pipeline.
+//!
+//! Detects table names mentioned in the user message (`users`, `user`,
+//! `order_items`) and injects their columns — types, nullability, primary and
+//! foreign keys — as reconstructed at sync time from the project's SQL schema
+//! and migration files, so persistence code is written against the real schema.
+//!
+//! Skips when the session is not scoped to a project, the project has no
+//! tables, or the lookup is slow.
+//!
+//! Controlled by `ENRICHMENT_SCHEMA` env var (default: true).
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::time::timeout;
+use tracing::debug;
+
+use crate::chat::enrichment::{
+    EnrichmentConfig, EnrichmentInput, EnrichmentSource, ParallelEnrichmentStage, StageOutput,
+};
+use crate::neo4j::models::TableNode;
+use crate::neo4j::traits::GraphStore;
+
+/// Tables injected at most
+const MAX_TABLES: usize = 5;
+/// Shorter names (`id`, `t`) match too many words to be meaningful
+const MIN_NAME_LEN: usize = 3;
+
+/// Enrichment stage that injects the schema of the tables a message mentions.
+pub struct SchemaStage {
+    graph: Arc<dyn GraphStore>,
+}
+
+impl SchemaStage {
+    /// Create a new schema stage.
+    pub fn new(graph: Arc<dyn GraphStore>) -> Self {
+        Self { graph }
+    }
+}
+
+/// Crude singular form, enough to match `user` against `users` and
+/// `category` against `categories`.
+fn singular(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else if word.ends_with("ss") {
+        word.to_string()
+    } else {
+        word.strip_suffix('s').unwrap_or(word).to_string()
+    }
+}
+
+/// Tables whose name (without schema prefix) appears as a word of `message`.
+fn mentioned_tables<'a>(message: &str, tables: &'a [TableNode]) -> Vec<&'a TableNode> {
+    let words: HashSet<String> = message
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|w| w.len() >= MIN_NAME_LEN)
+        .map(|w| singular(&w.to_lowercase()))
+        .collect();
+
+    tables
+        .iter()
+        .filter(|t| {
+            let name = t.name.rsplit('.').next().unwrap_or(&t.name).to_lowercase();
+            name.len() >= MIN_NAME_LEN && words.contains(&singular(&name))
+        })
+        .take(MAX_TABLES)
+        .collect()
+}
+
+fn format_table(table: &TableNode) -> String {
+    let mut origin = Vec::new();
+    if let Some(path) = &table.defined_in {
+        origin.push(format!("defined in `{}`", path));
+    }
+    if !table.altered_by.is_empty() {
+        let files: Vec<String> = table
+            .altered_by
+            .iter()
+            .map(|p| format!("`{}`", p))
+            .collect();
+        origin.push(format!("altered by {}", files.join(", ")));
+    }
+
+    let mut out = format!("### `{}`", table.name);
+    if !origin.is_empty() {
+        out.push_str(&format!(" ({})", origin.join("; ")));
+    }
+    for column in &table.columns {
+        let mut line = format!("\n- `{}`", column.name);
+        if !column.data_type.is_empty() {
+            line.push_str(&format!(" {}", column.data_type));
+        }
+        if column.primary_key {
+            line.push_str(" PK");
+        } else if !column.nullable {
+            line.push_str(" NOT NULL");
+        }
+        if let Some(default) = &column.default_value {
+            line.push_str(&format!(" DEFAULT {}", default));
+        }
+        if let Some(target) = &column.references {
+            line.push_str(&format!(" → `{}`", target));
+        }
+        out.push_str(&line);
+    }
+    out
+}
+
+#[async_trait::async_trait]
+impl ParallelEnrichmentStage for SchemaStage {
+    async fn execute(&self, input: &EnrichmentInput) -> Result<StageOutput> {
+        let mut output = StageOutput::new(self.name());
+
+        let Some(project_id) = input.project_id else {
+            return Ok(output);
+        };
+
+        let tables = match timeout(
+            Duration::from_millis(200),
+            self.graph.list_project_tables(project_id),
+        )
+        .await
+        {
+            Ok(Ok(tables)) => tables,
+            Ok(Err(e)) => {
+                debug!("[schema] Table lookup failed: {}", e);
+                return Ok(output);
+            }
+            Err(_) => {
+                debug!("[schema] Table lookup timed out");
+                return Ok(output);
+            }
+        };
+
+        let mentioned = mentioned_tables(&input.message, &tables);
+        if mentioned.is_empty() {
+            return Ok(output);
+        }
+
+        debug!("[schema] Injecting {} table(s)", mentioned.len());
+        let entries: Vec<String> = mentioned.into_iter().map(format_table).collect();
+        output.add_section(
+            "Database Schema",
+            format!(
+                "## Database Schema\nTables mentioned in the request, as defined by the project's SQL schema and migrations.\n\n{}",
+                entries.join("\n\n")
+            ),
+            self.name(),
+            EnrichmentSource::Schema,
+        );
+
+        Ok(output)
+    }
+
+    fn name(&self) -> &str {
+        "schema"
+    }
+
+    fn is_enabled(&self, config: &EnrichmentConfig) -> bool {
+        config.schema
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::ColumnNode;
+    use uuid::Uuid;
+
+    fn test_input(message: &str, project_id: Option<Uuid>) -> EnrichmentInput {
+        EnrichmentInput {
+            message: message.to_string(),
+            session_id: Uuid::new_v4(),
+            project_slug: None,
+            project_id,
+            cwd: None,
+            protocol_run_id: None,
+            protocol_state: None,
+            excluded_note_ids: Default::default(),
+            reasoning_path_tracker: None,
+        }
+    }
+
+    fn column(name: &str, data_type: &str) -> ColumnNode {
+        ColumnNode {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            nullable: true,
+            primary_key: false,
+            default_value: None,
+            references: None,
+        }
+    }
+
+    async fn seeded_store(project_id: Uuid) -> Arc<MockGraphStore> {
+        let store = Arc::new(MockGraphStore::new());
+        let mut id = column("id", "BIGSERIAL");
+        id.primary_key = true;
+        id.nullable = false;
+        let mut org_id = column("org_id", "BIGINT");
+        org_id.references = Some("orgs.id".to_string());
+        let tables = vec![
+            TableNode {
+                name: "categories".to_string(),
+                project_id,
+                defined_in: None,
+                altered_by: vec![],
+                columns: vec![column("label", "TEXT")],
+            },
+            TableNode {
+                name: "users".to_string(),
+                project_id,
+                defined_in: Some("/repo/migrations/0001_init.sql".to_string()),
+                altered_by: vec!["/repo/migrations/0003_org.sql".to_string()],
+                columns: vec![id, org_id],
+            },
+        ];
+        store
+            .replace_project_schema(project_id, &tables, &[])
+            .await
+            .unwrap();
+        store
+    }
+
+    #[test]
+    fn test_singular() {
+        assert_eq!(singular("users"), "user");
+        assert_eq!(singular("categories"), "category");
+        assert_eq!(singular("address"), "address");
+        assert_eq!(singular("user"), "user");
+    }
+
+    #[tokio::test]
+    async fn test_injects_mentioned_tables() {
+        let pid = Uuid::new_v4();
+        let stage = SchemaStage::new(seeded_store(pid).await);
+        let output = stage
+            .execute(&test_input(
+                "Add a last_login column to the User model",
+                Some(pid),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(output.sections.len(), 1);
+        let content = &output.sections[0].content;
+        assert!(content.contains(
+            "### `users` (defined in `/repo/migrations/0001_init.sql`; altered by `/repo/migrations/0003_org.sql`)"
+        ));
+        assert!(content.contains("- `id` BIGSERIAL PK"));
+        assert!(content.contains("- `org_id` BIGINT → `orgs.id`"));
+        assert!(!content.contains("categories"));
+        assert_eq!(
+            output.sections[0].enrichment_source,
+            EnrichmentSource::Schema
+        );
+    }
+
+    #[tokio::test]
+    async fn test_skips_without_project_or_mention() {
+        let pid = Uuid::new_v4();
+        let stage = SchemaStage::new(seeded_store(pid).await);
+
+        let output = stage
+            .execute(&test_input("list the users", None))
+            .await
+            .unwrap();
+        assert!(output.sections.is_empty());
+
+        let output = stage
+            .execute(&test_input("refactor the parser", Some(pid)))
+            .await
+            .unwrap();
+        assert!(output.sections.is_empty());
+    }
+}
@@ -642,6 +642,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         mock.create_project(&project).await.unwrap();
 
@@ -764,6 +765,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         mock.create_project(&project).await.unwrap();
 
@@ -0,0 +1,384 @@
+//! Rolling summarization of long chat sessions.
+//!
+//! A long conversation eventually runs into `max_turns` and the CLI's own
+//! compaction, losing its early context. When the context of a session's
+//! last model call crosses [`threshold_tokens`], the earlier turns are
+//! summarized by the prompt builder model (`PROMPT_BUILDER_MODEL`) on top of
+//! the previous summary, if any. The summary is stored on the session
+//! ([`ChatSessionMemory`]) and the CLI process is stopped once idle; the next
+//! message resumes the session with a fresh CLI session whose system prompt
+//! carries the summary and the last turns verbatim ([`memory_prompt_section`]).
+
+use crate::neo4j::models::{ChatEventRecord, ChatSessionMemory};
+use crate::neo4j::traits::GraphStore;
+use anyhow::Result;
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock};
+use uuid::Uuid;
+
+use super::types::ChatEvent;
+
+/// Context size above which a session is summarized
+pub const DEFAULT_THRESHOLD_TOKENS: u64 = 150_000;
+/// User turns (with their answers) kept verbatim instead of summarized
+const KEEP_RECENT_TURNS: usize = 2;
+/// Events read per summarization
+const MAX_EVENTS: i64 = 20_000;
+/// Transcript given to the model, most recent part kept
+const MAX_TRANSCRIPT_CHARS: usize = 200_000;
+/// Length of each verbatim recent message in the seeded prompt
+const MAX_RECENT_MESSAGE_CHARS: usize = 2_000;
+
+const SUMMARY_SYSTEM_PROMPT: &str =
+    "You summarize conversations between a developer and a coding agent. \
+     Respond only with the summary, in markdown.";
+
+/// Sessions being summarized, so a session is not summarized twice at once
+fn in_progress() -> &'static Mutex<HashSet<String>> {
+    static IN_PROGRESS: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+    IN_PROGRESS.get_or_init(Default::default)
+}
+
+/// Token threshold (`CHAT_SUMMARIZE_THRESHOLD_TOKENS`), 0 when disabled.
+pub fn threshold_tokens() -> u64 {
+    std::env::var("CHAT_SUMMARIZE_THRESHOLD_TOKENS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(DEFAULT_THRESHOLD_TOKENS)
+}
+
+/// Context size of the model calls of a query, from the `usage` of its
+/// result: input tokens (cached or not) averaged over the turns, plus output.
+pub fn context_tokens(usage: &serde_json::Value, num_turns: u64) -> u64 {
+    let count = |key: &str| usage.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+    let input = count("input_tokens")
+        + count("cache_creation_input_tokens")
+        + count("cache_read_input_tokens");
+    input / num_turns.max(1) + count("output_tokens")
+}
+
+/// One message of the conversation
+#[derive(Debug, Clone, PartialEq)]
+struct Turn {
+    seq: i64,
+    user: bool,
+    text: String,
+}
+
+/// Conversation turns of the persisted events: user messages and the main
+/// agent's text, with the tools it used. Sub-agent output is left out.
+fn transcript_turns(events: &[ChatEventRecord]) -> Vec<Turn> {
+    let mut turns: Vec<Turn> = Vec::new();
+    for record in events {
+        let Ok(event) = serde_json::from_str::<ChatEvent>(&record.data) else {
+            continue;
+        };
+        let (user, text) = match event {
+            ChatEvent::UserMessage { content, .. } => (true, content),
+            ChatEvent::AssistantText {
+                content,
+                parent_tool_use_id: None,
+                ..
+            } => (false, content),
+            ChatEvent::ToolUse {
+                tool,
+                parent_tool_use_id: None,
+                ..
+            } => (false, format!("[used tool {}]", tool)),
+            _ => continue,
+        };
+        match turns.last_mut() {
+            Some(last) if !last.user && !user => {
+                last.text.push('\n');
+                last.text.push_str(&text);
+                last.seq = record.seq;
+            }
+            _ => turns.push(Turn {
+                seq: record.seq,
+                user,
+                text,
+            }),
+        }
+    }
+    turns
+}
+
+/// Split the turns before the last [`KEEP_RECENT_TURNS`] user turns.
+fn split_recent(turns: &[Turn]) -> (&[Turn], &[Turn]) {
+    let start = turns
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| t.user)
+        .map(|(i, _)| i)
+        .rev()
+        .nth(KEEP_RECENT_TURNS - 1)
+        .unwrap_or(0);
+    turns.split_at(start)
+}
+
+fn truncate_start(text: &str, max: usize) -> &str {
+    if text.len() <= max {
+        return text;
+    }
+    let mut start = text.len() - max;
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    &text[start..]
+}
+
+fn truncate_end(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max).collect();
+    format!("{}…", cut)
+}
+
+fn render_turns(turns: &[Turn], max_message_chars: Option<usize>) -> String {
+    let mut out = String::new();
+    for turn in turns {
+        let text = match max_message_chars {
+            Some(max) => truncate_end(&turn.text, max),
+            None => turn.text.clone(),
+        };
+        out.push_str(if turn.user {
+            "**User:** "
+        } else {
+            "**Agent:** "
+        });
+        out.push_str(text.trim());
+        out.push_str("\n\n");
+    }
+    out
+}
+
+fn build_summary_prompt(previous: Option<&str>, earlier: &[Turn]) -> String {
+    let transcript = render_turns(earlier, None);
+    let mut prompt = String::from(
+        "Summarize the conversation below so that the agent can continue it \
+         without the transcript. Keep: the user's goals and requests, decisions \
+         made and their reasons, files and components touched, what was done, \
+         what is left to do and open questions. Drop greetings and tool output \
+         details. Use at most 800 words.\n\n",
+    );
+    if let Some(previous) = previous {
+        prompt.push_str("## Summary of the conversation before\n\n");
+        prompt.push_str(previous.trim());
+        prompt.push_str("\n\n");
+    }
+    prompt.push_str("## Conversation\n\n");
+    prompt.push_str(truncate_start(&transcript, MAX_TRANSCRIPT_CHARS));
+    prompt
+}
+
+/// Summarize the turns of a session not covered by its summary yet, keeping
+/// the last ones out. Returns None when there is nothing to summarize or
+/// the model call fails.
+pub async fn summarize_session(
+    graph: &dyn GraphStore,
+    session_id: Uuid,
+    model: &str,
+) -> Result<Option<ChatSessionMemory>> {
+    let previous = graph.get_chat_session_memory(session_id).await?;
+    let after_seq = previous.as_ref().map_or(0, |m| m.summarized_seq);
+    let events = graph
+        .get_chat_events(session_id, after_seq, MAX_EVENTS)
+        .await?;
+    let turns = transcript_turns(&events);
+    let (earlier, _) = split_recent(&turns);
+    let Some(last) = earlier.last() else {
+        return Ok(None);
+    };
+    let prompt = build_summary_prompt(previous.as_ref().map(|m| m.summary.as_str()), earlier);
+    let Some(summary) = crate::orchestrator::module_summary::complete_with_llm(
+        model,
+        SUMMARY_SYSTEM_PROMPT,
+        prompt,
+    )
+    .await
+    else {
+        return Ok(None);
+    };
+    Ok(Some(ChatSessionMemory {
+        summary: summary.trim().to_string(),
+        summarized_seq: last.seq,
+        updated_at: chrono::Utc::now(),
+    }))
+}
+
+/// System prompt section seeding a session with its summary. The messages
+/// since the summary are included when starting a fresh CLI session, which
+/// does not have them in its history.
+pub async fn memory_prompt_section(
+    graph: &dyn GraphStore,
+    session_id: Uuid,
+    fresh_cli_session: bool,
+) -> Option<String> {
+    let memory = graph.get_chat_session_memory(session_id).await.ok()??;
+    let mut section = format!(
+        "\n\n## Conversation so far\n\nThis conversation was summarized to keep \
+         the context small. Summary of the earlier messages:\n\n{}\n",
+        memory.summary
+    );
+    if fresh_cli_session {
+        let events = graph
+            .get_chat_events(session_id, memory.summarized_seq, MAX_EVENTS)
+            .await
+            .unwrap_or_default();
+        let turns = transcript_turns(&events);
+        if !turns.is_empty() {
+            section.push_str("\n### Most recent messages\n\n");
+            section.push_str(&render_turns(&turns, Some(MAX_RECENT_MESSAGE_CHARS)));
+        }
+    }
+    Some(section)
+}
+
+/// Mark a session as being summarized. False if it already is.
+pub fn begin(session_id: &str) -> bool {
+    in_progress()
+        .lock()
+        .map(|mut s| s.insert(session_id.to_string()))
+        .unwrap_or(false)
+}
+
+/// Clear the mark set by [`begin`].
+pub fn finish(session_id: &str) {
+    if let Ok(mut s) = in_progress().lock() {
+        s.remove(session_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+
+    fn record(session_id: Uuid, seq: i64, event: ChatEvent) -> ChatEventRecord {
+        ChatEventRecord {
+            id: Uuid::new_v4(),
+            session_id,
+            seq,
+            event_type: event.event_type().to_string(),
+            data: serde_json::to_string(&event).unwrap(),
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    fn user(text: &str) -> ChatEvent {
+        ChatEvent::UserMessage {
+            content: text.into(),
+            attachments: vec![],
+        }
+    }
+
+    fn agent(text: &str) -> ChatEvent {
+        ChatEvent::AssistantText {
+            content: text.into(),
+            parent_tool_use_id: None,
+        }
+    }
+
+    fn conversation(session_id: Uuid) -> Vec<ChatEventRecord> {
+        vec![
+            record(session_id, 1, user("Add a login page")),
+            record(session_id, 2, agent("Done, see")),
+            record(session_id, 3, agent("src/login.rs")),
+            record(session_id, 4, user("Now add tests")),
+            record(session_id, 5, agent("Added")),
+            record(session_id, 6, user("Thanks")),
+        ]
+    }
+
+    #[test]
+    fn test_context_tokens() {
+        let usage = serde_json::json!({
+            "input_tokens": 1000,
+            "cache_read_input_tokens": 150_000,
+            "cache_creation_input_tokens": 5000,
+            "output_tokens": 2000,
+        });
+        assert_eq!(context_tokens(&usage, 2), 80_000);
+        assert_eq!(context_tokens(&usage, 0), 158_000);
+        assert_eq!(context_tokens(&serde_json::json!({}), 1), 0);
+    }
+
+    #[test]
+    fn test_transcript_turns_and_split() {
+        let turns = transcript_turns(&conversation(Uuid::new_v4()));
+        assert_eq!(turns.len(), 5);
+        assert_eq!(turns[1].text, "Done, see\nsrc/login.rs");
+        assert_eq!(turns[1].seq, 3);
+
+        let (earlier, recent) = split_recent(&turns);
+        assert_eq!(earlier.len(), 2);
+        assert_eq!(recent[0].text, "Now add tests");
+
+        let (earlier, recent) = split_recent(&turns[..1]);
+        assert!(earlier.is_empty() && recent.len() == 1);
+    }
+
+    #[test]
+    fn test_build_summary_prompt() {
+        let turns = transcript_turns(&conversation(Uuid::new_v4()));
+        let prompt = build_summary_prompt(Some("Earlier: set up the repo"), &turns[..2]);
+        assert!(prompt.contains("Earlier: set up the repo"));
+        assert!(prompt.contains("**User:** Add a login page"));
+        assert!(!prompt.contains("Now add tests"));
+    }
+
+    #[test]
+    fn test_truncate_start_keeps_end() {
+        assert_eq!(truncate_start("abcdef", 3), "def");
+        assert_eq!(truncate_start("héllo", 4), "llo");
+        assert_eq!(truncate_start("abc", 10), "abc");
+    }
+
+    #[tokio::test]
+    async fn test_memory_prompt_section() {
+        let graph = MockGraphStore::new();
+        let session_id = Uuid::new_v4();
+        assert!(memory_prompt_section(&graph, session_id, true)
+            .await
+            .is_none());
+
+        graph
+            .store_chat_events(session_id, conversation(session_id))
+            .await
+            .unwrap();
+        graph
+            .set_chat_session_memory(
+                session_id,
+                &ChatSessionMemory {
+                    summary: "The user wants a login page.".into(),
+                    summarized_seq: 3,
+                    updated_at: chrono::Utc::now(),
+                },
+            )
+            .await
+            .unwrap();
+
+        let fresh = memory_prompt_section(&graph, session_id, true)
+            .await
+            .unwrap();
+        assert!(fresh.contains("The user wants a login page."));
+        assert!(fresh.contains("**User:** Now add tests"));
+        assert!(!fresh.contains("Add a login page"));
+
+        let resumed = memory_prompt_section(&graph, session_id, false)
+            .await
+            .unwrap();
+        assert!(!resumed.contains("Now add tests"));
+    }
+
+    #[test]
+    fn test_begin_finish() {
+        let id = Uuid::new_v4().to_string();
+        assert!(begin(&id));
+        assert!(!begin(&id));
+        finish(&id);
+        assert!(begin(&id));
+        finish(&id);
+    }
+}
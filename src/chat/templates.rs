@@ -0,0 +1,169 @@
+//! Prompt templates: `{{variable}}` substitution.
+//!
+//! Templates are stored in the graph (`PromptTemplate` nodes, see
+//! `/api/prompt-templates`) and rendered into the first message of a chat
+//! session. A placeholder is `{{name}}` or `{{name|default}}`; names are made
+//! of letters, digits, `_`, `-` and `.`. Anything else between braces is
+//! left as is, so templates can quote code with `{{ }}` in it.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A placeholder of a template
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateVariable {
+    pub name: String,
+    /// Value used when none is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+/// A placeholder found in a template, with its byte range
+struct Placeholder<'a> {
+    start: usize,
+    end: usize,
+    name: &'a str,
+    default: Option<&'a str>,
+}
+
+fn is_variable_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+fn placeholders(content: &str) -> Vec<Placeholder<'_>> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    while let Some(open) = content[offset..].find("{{") {
+        let start = offset + open;
+        let Some(close) = content[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + close + 2;
+        let inner = &content[start + 2..end - 2];
+        let (name, default) = match inner.split_once('|') {
+            Some((name, default)) => (name.trim(), Some(default)),
+            None => (inner.trim(), None),
+        };
+        if is_variable_name(name) {
+            found.push(Placeholder {
+                start,
+                end,
+                name,
+                default,
+            });
+            offset = end;
+        } else {
+            offset = start + 2;
+        }
+    }
+    found
+}
+
+/// Variables of a template, in order of first use. The first default given
+/// for a variable wins.
+pub fn variables(content: &str) -> Vec<TemplateVariable> {
+    let mut variables: Vec<TemplateVariable> = Vec::new();
+    for placeholder in placeholders(content) {
+        match variables.iter_mut().find(|v| v.name == placeholder.name) {
+            Some(existing) => {
+                if existing.default.is_none() {
+                    existing.default = placeholder.default.map(str::to_string);
+                }
+            }
+            None => variables.push(TemplateVariable {
+                name: placeholder.name.to_string(),
+                default: placeholder.default.map(str::to_string),
+            }),
+        }
+    }
+    variables
+}
+
+/// Substitute the variables of a template. Fails with the names of the
+/// variables that have neither a value nor a default.
+pub fn render(content: &str, values: &HashMap<String, String>) -> Result<String, Vec<String>> {
+    let defaults = variables(content);
+    let mut missing = Vec::new();
+    let mut rendered = String::with_capacity(content.len());
+    let mut last = 0;
+    for placeholder in placeholders(content) {
+        rendered.push_str(&content[last..placeholder.start]);
+        last = placeholder.end;
+        let value = values
+            .get(placeholder.name)
+            .map(String::as_str)
+            .or_else(|| {
+                defaults
+                    .iter()
+                    .find(|v| v.name == placeholder.name)
+                    .and_then(|v| v.default.as_deref())
+            });
+        match value {
+            Some(value) => rendered.push_str(value),
+            None => {
+                if !missing.iter().any(|m| m == placeholder.name) {
+                    missing.push(placeholder.name.to_string());
+                }
+            }
+        }
+    }
+    rendered.push_str(&content[last..]);
+    if missing.is_empty() {
+        Ok(rendered)
+    } else {
+        Err(missing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_variables() {
+        let vars = variables("Review {{ file }} for {{focus|security}}, then {{file}} {{focus|x}}");
+        assert_eq!(
+            vars,
+            vec![
+                TemplateVariable {
+                    name: "file".into(),
+                    default: None
+                },
+                TemplateVariable {
+                    name: "focus".into(),
+                    default: Some("security".into())
+                },
+            ]
+        );
+        assert!(variables("let x = {{ a: 1 }}; {{}}").is_empty());
+    }
+
+    #[test]
+    fn test_render() {
+        let content = "Review {{file}} for {{focus|security}}.";
+        assert_eq!(
+            render(content, &values(&[("file", "src/auth.rs")])).unwrap(),
+            "Review src/auth.rs for security."
+        );
+        assert_eq!(
+            render(content, &values(&[("file", "a.rs"), ("focus", "style")])).unwrap(),
+            "Review a.rs for style."
+        );
+        assert_eq!(render(content, &values(&[])), Err(vec!["file".to_string()]));
+        // Non-variable braces and unclosed placeholders are kept
+        assert_eq!(
+            render("json {{ \"a\": 1 }} and {{open", &values(&[])).unwrap(),
+            "json {{ \"a\": 1 }} and {{open"
+        );
+    }
+}
@@ -167,10 +167,24 @@ impl RunnerContext {
     }
 }
 
+/// A prompt template to start a session from (see `chat::templates`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatTemplateRequest {
+    /// ID of the template
+    pub id: Uuid,
+    /// Version to render (default: current)
+    #[serde(default)]
+    pub version: Option<i64>,
+    /// Values of the template variables
+    #[serde(default)]
+    pub variables: std::collections::HashMap<String, String>,
+}
+
 /// Request to send a chat message
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatRequest {
-    /// The user's message
+    /// The user's message (appended to the rendered template, if any)
+    #[serde(default)]
     pub message: String,
     /// Session ID to resume (optional — creates new session if None)
     #[serde(default)]
@@ -180,7 +194,7 @@ pub struct ChatRequest {
     /// Project slug to associate with the session
     #[serde(default)]
     pub project_slug: Option<String>,
-    /// Model override (default: from ChatConfig)
+    /// Model override (default: routed by `chat.model_routing`, else from ChatConfig)
     #[serde(default)]
     pub model: Option<String>,
     /// Permission mode override for this session (default: from ChatConfig)
@@ -194,6 +208,17 @@ pub struct ChatRequest {
     /// as add_dirs automatically (mutually exclusive with explicit add_dirs)
     #[serde(default)]
     pub workspace_slug: Option<String>,
+    /// Execution sandbox for this session, combined with the server's
+    /// (`chat.sandbox`) — it can only make the server's stricter
+    #[serde(default)]
+    pub sandbox: Option<super::sandbox::SandboxConfig>,
+    /// Resource limits of this session, combined with the server's
+    /// (`chat.limits`) — each can only be lower than the server's
+    #[serde(default)]
+    pub limits: Option<super::limits::SessionLimits>,
+    /// Prompt template rendered into the first message
+    #[serde(default)]
+    pub template: Option<ChatTemplateRequest>,
     /// Authenticated user claims — injected by the server (not from JSON body).
     /// Used to generate the MCP session token (PO_AUTH_TOKEN).
     #[serde(skip)]
@@ -322,12 +347,40 @@ pub struct BackgroundTaskInfo {
     pub pending_removal_at: Option<std::time::Instant>,
 }
 
+/// A file attached to a user message by the client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatAttachment {
+    /// Original file name
+    pub name: String,
+    /// MIME type reported by the client (e.g. `image/png`)
+    #[serde(default)]
+    pub mime_type: String,
+    /// File content, base64-encoded (a `data:` URL is accepted too)
+    pub data: String,
+}
+
+/// An attachment once stored in the session scratch dir
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatAttachmentRef {
+    pub name: String,
+    pub mime_type: String,
+    /// Size in bytes
+    pub size: u64,
+    /// Absolute path of the stored file
+    pub path: String,
+}
+
 /// Events emitted by the chat system (sent via WebSocket / broadcast)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ChatEvent {
     /// A user message (emitted so multi-tab clients see it)
-    UserMessage { content: String },
+    UserMessage {
+        content: String,
+        /// Files attached to the message, stored in the session scratch dir
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        attachments: Vec<ChatAttachmentRef>,
+    },
     /// A system-generated hint (post-compaction context, guard hints, auto-continue).
     /// NOT a user message — frontends should render this differently (or hide it).
     /// Does NOT increment the session's message_count.
@@ -540,6 +593,17 @@ pub enum ChatEvent {
         /// The error message that triggered the retry
         error_message: String,
     },
+    /// Knowledge items the enrichment pipeline injected into the prompt of
+    /// the current turn, so the frontend can offer thumbs up/down on them
+    /// (`POST /api/chat/sessions/{id}/context-feedback`).
+    ContextItems {
+        /// Notes surfaced, most relevant first
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        note_ids: Vec<String>,
+        /// Symbols whose notes were looked up
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        symbols: Vec<String>,
+    },
     /// **Out-of-band** event captured between turns by the permanent OOB
     /// listener (see `chat::manager::spawn_oob_listener`).
     ///
@@ -609,6 +673,23 @@ pub enum ChatEvent {
         /// ISO-8601 timestamp at which the error was detected.
         received_at: chrono::DateTime<chrono::Utc>,
     },
+    /// The session's MCP server (the `mcp_server` child of the CLI) died
+    /// and the supervisor asked the CLI to restart it. Tool calls made
+    /// in between fail; the tools come back once the CLI has reconnected.
+    ///
+    /// Emitted by `chat::mcp_supervisor` before each restart attempt.
+    /// When the attempts are exhausted, a `SessionError` with reason
+    /// `"mcp_server_unavailable"` follows instead.
+    McpServerRestarted {
+        /// MCP server name as registered with the CLI (`"project-orchestrator"`).
+        server: String,
+        /// Restart attempt (1-indexed) since the server was last healthy
+        attempt: u32,
+        /// Attempts allowed before giving up
+        max_attempts: u32,
+        /// ISO-8601 timestamp at which the restart was requested.
+        received_at: chrono::DateTime<chrono::Utc>,
+    },
     /// Snapshot of all background subprocesses currently attached to this
     /// session. Emitted by the lifecycle hook (T3 of plan 754a1379) on every
     /// mutation of `ActiveSession::active_background_tasks` — i.e.:
@@ -644,6 +725,16 @@ pub enum ChatEvent {
 }
 
 impl ChatEvent {
+    /// User message event for a message as sent to the CLI, with its
+    /// attachment block (see [`super::attachments`]) split out.
+    pub fn user_message(message: impl Into<String>) -> Self {
+        let (content, attachments) = super::attachments::split_message(&message.into());
+        ChatEvent::UserMessage {
+            content,
+            attachments,
+        }
+    }
+
     /// Get the event type name (used for WebSocket messages and persistence)
     pub fn event_type(&self) -> &'static str {
         match self {
@@ -673,8 +764,10 @@ impl ChatEvent {
             ChatEvent::AutoContinue { .. } => "auto_continue",
             ChatEvent::AutoContinueStateChanged { .. } => "auto_continue_state_changed",
             ChatEvent::Retrying { .. } => "retrying",
+            ChatEvent::ContextItems { .. } => "context_items",
             ChatEvent::BackgroundOutput { .. } => "background_output",
             ChatEvent::SessionError { .. } => "session_error",
+            ChatEvent::McpServerRestarted { .. } => "mcp_server_restarted",
             ChatEvent::ToolsCancelled { .. } => "tools_cancelled",
             ChatEvent::ActiveTasksUpdate { .. } => "active_tasks_update",
         }
@@ -728,7 +821,7 @@ impl ChatEvent {
                 content.hash(&mut hasher);
                 Some(format!("assistant_text:{}", hasher.finish()))
             }
-            ChatEvent::UserMessage { content } => {
+            ChatEvent::UserMessage { content, .. } => {
                 use std::hash::{Hash, Hasher};
                 let mut hasher = std::collections::hash_map::DefaultHasher::new();
                 content.hash(&mut hasher);
@@ -784,6 +877,14 @@ impl ChatEvent {
                 ..
             } => Some(format!("retrying:{}:{}", attempt, max_attempts)),
 
+            ChatEvent::ContextItems { note_ids, symbols } => {
+                use std::hash::{Hash, Hasher};
+                let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                note_ids.hash(&mut hasher);
+                symbols.hash(&mut hasher);
+                Some(format!("context_items:{}", hasher.finish()))
+            }
+
             ChatEvent::BackgroundOutput {
                 source,
                 received_at,
@@ -833,6 +934,17 @@ impl ChatEvent {
                     received_at.timestamp_millis()
                 ))
             }
+            ChatEvent::McpServerRestarted {
+                server,
+                attempt,
+                received_at,
+                ..
+            } => Some(format!(
+                "mcp_server_restarted:{}:{}:{}",
+                server,
+                attempt,
+                received_at.timestamp_millis()
+            )),
 
             // StreamDelta and StreamingStatus are never in the snapshot.
             // ActiveTasksUpdate is ephemeral — every emission is a fresh
@@ -865,7 +977,12 @@ pub struct ChatEventPage {
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ClientMessage {
     /// A new user message
-    UserMessage { content: String },
+    UserMessage {
+        content: String,
+        /// Files attached to the message (base64)
+        #[serde(default)]
+        attachments: Vec<ChatAttachment>,
+    },
     /// Response to a permission request
     PermissionResponse {
         allow: bool,
@@ -919,6 +1036,12 @@ pub struct ChatSession {
     /// Additional directories exposed to Claude CLI (--add-dir)
     #[serde(default)]
     pub add_dirs: Option<Vec<String>>,
+    /// Execution sandbox requested for this session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<super::sandbox::SandboxConfig>,
+    /// Resource limits requested for this session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits: Option<super::limits::SessionLimits>,
     /// Origin of the session (runner, sub-conversation, or null for normal)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub spawned_by: Option<serde_json::Value>,
@@ -1523,7 +1646,9 @@ mod tests {
     fn test_client_message_deserialize() {
         let json = r#"{"type": "user_message", "content": "Hi"}"#;
         let msg: ClientMessage = serde_json::from_str(json).unwrap();
-        assert!(matches!(msg, ClientMessage::UserMessage { content } if content == "Hi"));
+        assert!(
+            matches!(msg, ClientMessage::UserMessage { content, attachments } if content == "Hi" && attachments.is_empty())
+        );
 
         let json = r#"{"type": "permission_response", "allow": true}"#;
         let msg: ClientMessage = serde_json::from_str(json).unwrap();
@@ -1703,6 +1828,12 @@ mod tests {
                 message: "Lost connection to the CLI transport.".into(),
                 received_at: chrono::Utc::now(),
             },
+            ChatEvent::McpServerRestarted {
+                server: "project-orchestrator".into(),
+                attempt: 2,
+                max_attempts: 5,
+                received_at: chrono::Utc::now(),
+            },
         ];
 
         for event in &events {
@@ -2000,6 +2131,7 @@ mod tests {
         let messages = vec![
             ClientMessage::UserMessage {
                 content: "Hello".into(),
+                attachments: vec![],
             },
             ClientMessage::PermissionResponse {
                 allow: true,
@@ -2051,6 +2183,22 @@ mod tests {
         assert_eq!(req.model.as_deref(), Some("claude-sonnet-4-6"));
     }
 
+    #[test]
+    fn test_chat_request_from_template() {
+        let json = r#"{
+            "cwd": "/tmp",
+            "template": {
+                "id": "6f1c2c1e-8d8e-4f0b-9a57-3b1f5a0c9e11",
+                "variables": {"file": "src/lib.rs"}
+            }
+        }"#;
+        let req: ChatRequest = serde_json::from_str(json).unwrap();
+        assert!(req.message.is_empty());
+        let template = req.template.unwrap();
+        assert!(template.version.is_none());
+        assert_eq!(template.variables["file"], "src/lib.rs");
+    }
+
     #[test]
     fn test_chat_session_serde_roundtrip() {
         let session = ChatSession {
@@ -2069,6 +2217,8 @@ mod tests {
             preview: Some("Hello, can you help me?".into()),
             permission_mode: None,
             add_dirs: None,
+            sandbox: None,
+            limits: None,
             spawned_by: None,
             linked_plans: Vec::new(),
             linked_tasks: Vec::new(),
@@ -2286,6 +2436,8 @@ mod tests {
             preview: None,
             permission_mode: None,
             add_dirs: Some(vec!["/dir/a".into(), "/dir/b".into()]),
+            sandbox: None,
+            limits: None,
             spawned_by: None,
             linked_plans: Vec::new(),
             linked_tasks: Vec::new(),
@@ -2343,6 +2495,19 @@ mod tests {
         assert!(result.workspace_slug.is_none());
     }
 
+    #[test]
+    fn test_context_items_event_serde() {
+        let event = ChatEvent::ContextItems {
+            note_ids: vec!["n1".into()],
+            symbols: vec![],
+        };
+        assert_eq!(event.event_type(), "context_items");
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(json, r#"{"type":"context_items","note_ids":["n1"]}"#);
+        let deserialized: ChatEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.fingerprint(), event.fingerprint());
+    }
+
     // ====================================================================
     // Retrying event
     // ====================================================================
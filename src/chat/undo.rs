@@ -0,0 +1,225 @@
+//! Undo log of chat-driven graph mutations.
+//!
+//! The MCP proxy sends the agent's chat session as `X-Session-Id`. Mutating
+//! plan, task, step and note endpoints called with it record an
+//! [`UndoEntry`] holding the inverse of the mutation, so a user can revert
+//! what an agent did: the last mutation (`POST /api/chat/sessions/{id}/undo-last`)
+//! or all of them, newest first (`POST /api/chat/sessions/{id}/rollback`).
+//!
+//! Undoable: creating, updating and deleting a note; creating and updating a
+//! plan, a task or a step. Deleting a plan or a task removes its whole
+//! subtree and is not recorded. Undo restores the snapshot taken before the
+//! mutation, overwriting any later change to the same entity.
+
+use anyhow::{bail, Result};
+use axum::http::HeaderMap;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::neo4j::models::{PlanNode, StepNode, TaskNode, UndoEntry};
+use crate::neo4j::traits::GraphStore;
+use crate::notes::{Note, UpdateNoteRequest};
+use crate::orchestrator::Orchestrator;
+use crate::plan::models::{UpdatePlanRequest, UpdateStepRequest, UpdateTaskRequest};
+
+/// Operation reverting a recorded mutation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum UndoOperation {
+    /// Delete a note the session created
+    DeleteNote { note_id: Uuid },
+    /// Bring a note back to a snapshot, recreating it if it was deleted
+    RestoreNote { note: Box<Note> },
+    /// Delete a plan the session created
+    DeletePlan { plan_id: Uuid },
+    /// Bring a plan back to a snapshot
+    RestorePlan { plan: Box<PlanNode> },
+    /// Delete a task the session created
+    DeleteTask { task_id: Uuid },
+    /// Bring a task back to a snapshot
+    RestoreTask { task: Box<TaskNode> },
+    /// Bring a step back to a snapshot
+    RestoreStep { step: Box<StepNode> },
+}
+
+impl UndoOperation {
+    /// Type and ID of the entity the operation changes
+    pub fn target(&self) -> (&'static str, Uuid) {
+        match self {
+            Self::DeleteNote { note_id } => ("note", *note_id),
+            Self::RestoreNote { note } => ("note", note.id),
+            Self::DeletePlan { plan_id } => ("plan", *plan_id),
+            Self::RestorePlan { plan } => ("plan", plan.id),
+            Self::DeleteTask { task_id } => ("task", *task_id),
+            Self::RestoreTask { task } => ("task", task.id),
+            Self::RestoreStep { step } => ("step", step.id),
+        }
+    }
+}
+
+/// Chat session a request was made for (`X-Session-Id` header)
+pub fn session_from_headers(headers: &HeaderMap) -> Option<Uuid> {
+    headers
+        .get("x-session-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.trim().parse().ok())
+}
+
+/// Append a mutation to the undo log of a session. Best-effort: a failure
+/// never fails the mutation, and sessions that do not exist are ignored.
+pub async fn record(
+    graph: &dyn GraphStore,
+    session_id: Uuid,
+    action: &str,
+    summary: String,
+    inverse: UndoOperation,
+) {
+    let (entity_type, entity_id) = inverse.target();
+    let inverse = match serde_json::to_value(&inverse) {
+        Ok(inverse) => inverse,
+        Err(e) => {
+            warn!("Failed to serialize undo operation of {}: {}", action, e);
+            return;
+        }
+    };
+    let entry = UndoEntry {
+        id: Uuid::new_v4(),
+        session_id,
+        action: action.to_string(),
+        entity_type: entity_type.to_string(),
+        entity_id,
+        summary,
+        inverse,
+        created_at: Utc::now(),
+        undone_at: None,
+    };
+    if let Err(e) = graph.push_undo_entry(&entry).await {
+        warn!(
+            "Failed to record {} in the undo log of session {}: {}",
+            action, session_id, e
+        );
+    }
+}
+
+/// Revert a recorded mutation and mark its entry undone. Goes through the
+/// managers directly, so the undo itself is not recorded.
+pub async fn undo(orchestrator: &Orchestrator, entry: &UndoEntry) -> Result<()> {
+    if entry.undone_at.is_some() {
+        bail!("Already undone");
+    }
+    let op: UndoOperation = serde_json::from_value(entry.inverse.clone())?;
+    apply(orchestrator, &op).await?;
+    orchestrator
+        .neo4j()
+        .mark_undo_entry_undone(entry.id, Utc::now())
+        .await
+}
+
+async fn apply(orchestrator: &Orchestrator, op: &UndoOperation) -> Result<()> {
+    let plans = orchestrator.plan_manager();
+    let notes = orchestrator.note_manager();
+    match op {
+        // Deleting what is already gone is a no-op
+        UndoOperation::DeleteNote { note_id } => {
+            notes.delete_note(*note_id).await?;
+        }
+        UndoOperation::RestoreNote { note } => {
+            if notes.get_note(note.id).await?.is_some() {
+                let update = UpdateNoteRequest {
+                    content: Some(note.content.clone()),
+                    importance: Some(note.importance),
+                    status: Some(note.status),
+                    tags: Some(note.tags.clone()),
+                };
+                notes.update_note(note.id, update).await?;
+            } else {
+                notes.restore_note(note).await?;
+            }
+        }
+        UndoOperation::DeletePlan { plan_id } => plans.delete_plan(*plan_id).await?,
+        UndoOperation::RestorePlan { plan } => {
+            if plans.get_plan(plan.id).await?.is_none() {
+                bail!("Plan {} no longer exists", plan.id);
+            }
+            let update = UpdatePlanRequest {
+                title: Some(plan.title.clone()),
+                description: Some(plan.description.clone()),
+                priority: Some(plan.priority),
+            };
+            plans.update_plan(plan.id, update).await?;
+            plans
+                .update_plan_status(plan.id, plan.status.clone())
+                .await?;
+        }
+        UndoOperation::DeleteTask { task_id } => plans.delete_task(*task_id).await?,
+        UndoOperation::RestoreTask { task } => {
+            if orchestrator.neo4j().get_task(task.id).await?.is_none() {
+                bail!("Task {} no longer exists", task.id);
+            }
+            let update = UpdateTaskRequest {
+                title: task.title.clone(),
+                description: Some(task.description.clone()),
+                status: Some(task.status.clone()),
+                assigned_to: task.assigned_to.clone(),
+                priority: task.priority,
+                tags: Some(task.tags.clone()),
+                acceptance_criteria: Some(task.acceptance_criteria.clone()),
+                affected_files: Some(task.affected_files.clone()),
+                actual_complexity: task.actual_complexity,
+                estimated_complexity: task.estimated_complexity,
+                session_id: None,
+            };
+            plans.update_task(task.id, update).await?;
+        }
+        UndoOperation::RestoreStep { step } => {
+            if orchestrator.neo4j().get_step(step.id).await?.is_none() {
+                bail!("Step {} no longer exists", step.id);
+            }
+            plans
+                .update_step_status(step.id, step.status.clone())
+                .await?;
+            let update = UpdateStepRequest {
+                description: Some(step.description.clone()),
+                status: None,
+                verification: step.verification.clone(),
+            };
+            plans.update_step(step.id, &update).await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::test_task;
+
+    #[test]
+    fn test_session_from_headers() {
+        let session_id = Uuid::new_v4();
+        let mut headers = HeaderMap::new();
+        assert_eq!(session_from_headers(&headers), None);
+        headers.insert("x-session-id", session_id.to_string().parse().unwrap());
+        assert_eq!(session_from_headers(&headers), Some(session_id));
+        headers.insert("x-session-id", "not-a-uuid".parse().unwrap());
+        assert_eq!(session_from_headers(&headers), None);
+    }
+
+    #[test]
+    fn test_operation_roundtrip() {
+        let task = test_task();
+        let op = UndoOperation::RestoreTask {
+            task: Box::new(task.clone()),
+        };
+        let json = serde_json::to_value(&op).unwrap();
+        assert_eq!(json["op"], "restore_task");
+        let back: UndoOperation = serde_json::from_value(json).unwrap();
+        assert_eq!(back.target(), ("task", task.id));
+        let UndoOperation::RestoreTask { task: restored } = back else {
+            panic!("expected restore_task");
+        };
+        assert_eq!(restored.description, task.description);
+    }
+}
@@ -835,6 +835,7 @@ mod tests {
             project_id: Some(project_id),
             execution_context: None,
             persona: None,
+            version: 0,
         };
         graph.plans.write().await.insert(plan_id, plan);
 
@@ -861,6 +862,8 @@ mod tests {
             execution_context: None,
             persona: None,
             prompt_cache: None,
+            board_position: None,
+            version: 0,
         };
         let task2 = TaskNode {
             id: task2_id,
@@ -882,6 +885,8 @@ mod tests {
             execution_context: None,
             persona: None,
             prompt_cache: None,
+            board_position: None,
+            version: 0,
         };
         graph.tasks.write().await.insert(task1_id, task1);
         graph.tasks.write().await.insert(task2_id, task2);
@@ -1048,6 +1053,7 @@ mod tests {
             project_id: None,
             execution_context: None,
             persona: None,
+            version: 0,
         };
         graph.plans.write().await.insert(plan_id, plan);
         graph.plan_tasks.write().await.insert(plan_id, vec![]);
@@ -1240,6 +1246,7 @@ mod tests {
             project_id: None,
             execution_context: None,
             persona: None,
+            version: 0,
         };
         graph.plans.write().await.insert(plan_id, plan);
 
@@ -1266,6 +1273,8 @@ mod tests {
                 execution_context: None,
                 persona: None,
                 prompt_cache: None,
+                board_position: None,
+                version: 0,
             },
         );
         graph
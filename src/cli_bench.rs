@@ -0,0 +1,556 @@
+//! `orchestrator bench` — end-to-end throughput and latency benchmark.
+//!
+//! [`generate_repository`] writes the sources of a synthetic Rust crate: modules of
+//! [`FILES_PER_MODULE`] files, each file holding functions that call the
+//! next function of the file and one function of another file (through a
+//! `use`), plus a struct per file. The layout is a pure function of the
+//! options and the seed, so runs on different machines index the same code.
+//!
+//! [`run`] then syncs the crate into a throwaway project, computes its graph
+//! analytics and replays a code search workload, timing each phase. The
+//! project and the generated files are removed afterwards unless
+//! [`BenchOptions::keep`] is set.
+
+use crate::neo4j::models::ProjectNode;
+use crate::orchestrator::Orchestrator;
+use anyhow::{bail, Context, Result};
+use futures::StreamExt;
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Default number of source files generated.
+pub const DEFAULT_FILES: usize = 200;
+
+/// Default number of functions per generated file.
+pub const DEFAULT_SYMBOLS_PER_FILE: usize = 10;
+
+/// Default number of search queries replayed.
+pub const DEFAULT_QUERIES: usize = 500;
+
+/// Default number of search queries in flight at once.
+pub const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Files per generated module directory.
+pub const FILES_PER_MODULE: usize = 25;
+
+/// Results requested per search query.
+const SEARCH_LIMIT: usize = 10;
+
+const VERBS: &[&str] = &[
+    "load", "parse", "validate", "render", "sync", "merge", "compute", "flush", "resolve",
+    "schedule", "encode", "fetch",
+];
+const NOUNS: &[&str] = &[
+    "account", "billing", "cache", "config", "dispatch", "event", "invoice", "ledger", "metric",
+    "order", "payment", "queue", "report", "session", "token", "webhook",
+];
+
+/// Options for [`run`].
+#[derive(Debug, Clone)]
+pub struct BenchOptions {
+    /// Source files generated (at least 1)
+    pub files: usize,
+    /// Functions per file (at least 1)
+    pub symbols_per_file: usize,
+    /// Search queries replayed
+    pub queries: usize,
+    /// Search queries in flight at once (at least 1)
+    pub concurrency: usize,
+    /// Seed of the generated call graph and queries
+    pub seed: u64,
+    /// Keep the project and the generated files
+    pub keep: bool,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            files: DEFAULT_FILES,
+            symbols_per_file: DEFAULT_SYMBOLS_PER_FILE,
+            queries: DEFAULT_QUERIES,
+            concurrency: DEFAULT_CONCURRENCY,
+            seed: 42,
+            keep: false,
+        }
+    }
+}
+
+/// What [`generate_repository`] wrote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct GeneratedRepo {
+    /// Source files, `lib.rs` and module declarations included
+    pub files: usize,
+    pub functions: usize,
+    pub structs: usize,
+}
+
+impl GeneratedRepo {
+    pub fn symbols(&self) -> usize {
+        self.functions + self.structs
+    }
+}
+
+/// Latency distribution of a set of operations.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LatencyStats {
+    pub count: usize,
+    pub errors: usize,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+    /// Operations completed per second of wall time
+    pub per_sec: f64,
+}
+
+impl LatencyStats {
+    /// Statistics of `samples`, completed in `wall` (nearest-rank percentiles).
+    pub fn from_samples(samples: &[Duration], errors: usize, wall: Duration) -> Self {
+        if samples.is_empty() {
+            return Self {
+                errors,
+                ..Self::default()
+            };
+        }
+        let mut ms: Vec<f64> = samples.iter().map(|d| d.as_secs_f64() * 1000.0).collect();
+        ms.sort_by(|a, b| a.total_cmp(b));
+        let percentile = |p: f64| {
+            let rank = ((p / 100.0) * ms.len() as f64).ceil() as usize;
+            ms[rank.clamp(1, ms.len()) - 1]
+        };
+        Self {
+            count: ms.len(),
+            errors,
+            mean_ms: ms.iter().sum::<f64>() / ms.len() as f64,
+            p50_ms: percentile(50.0),
+            p90_ms: percentile(90.0),
+            p99_ms: percentile(99.0),
+            max_ms: ms[ms.len() - 1],
+            per_sec: per_sec(ms.len(), wall),
+        }
+    }
+}
+
+fn per_sec(count: usize, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64();
+    if secs > 0.0 {
+        count as f64 / secs
+    } else {
+        0.0
+    }
+}
+
+/// Timing of the sync phase.
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncPhase {
+    pub files_synced: usize,
+    pub errors: usize,
+    pub secs: f64,
+    pub files_per_sec: f64,
+    pub symbols_per_sec: f64,
+}
+
+/// Timing of the analytics phase.
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalyticsPhase {
+    pub secs: f64,
+    pub file_nodes: usize,
+    pub file_edges: usize,
+    pub function_nodes: usize,
+    pub function_edges: usize,
+}
+
+/// Result of [`run`].
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchReport {
+    pub generated: GeneratedRepo,
+    pub generate_secs: f64,
+    pub sync: SyncPhase,
+    pub analytics: AnalyticsPhase,
+    pub search: LatencyStats,
+    /// Search queries in flight at once
+    pub concurrency: usize,
+    /// Slug of the benchmark project
+    pub project_slug: String,
+    /// Generated crate, when kept
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<PathBuf>,
+}
+
+/// SplitMix64 step: a deterministic, well-spread hash of the seed and `n`.
+fn mix(seed: u64, n: u64) -> u64 {
+    let mut z = seed.wrapping_add(n.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+fn word(words: &[&'static str], hash: u64) -> &'static str {
+    words[(hash % words.len() as u64) as usize]
+}
+
+/// Name of function `j` of file `i`.
+fn function_name(seed: u64, i: usize, j: usize) -> String {
+    let h = mix(seed, (i * 1_000 + j) as u64);
+    format!("{}_{}_{}_{}", word(VERBS, h), word(NOUNS, h >> 16), i, j)
+}
+
+/// Write the synthetic crate under `dir` (created if needed).
+pub fn generate_repository(
+    dir: &Path,
+    files: usize,
+    symbols_per_file: usize,
+    seed: u64,
+) -> Result<GeneratedRepo> {
+    if files == 0 || symbols_per_file == 0 {
+        bail!("files and symbols per file must be at least 1");
+    }
+    let src = dir.join("src");
+    std::fs::create_dir_all(&src).with_context(|| format!("Cannot create {}", src.display()))?;
+
+    let modules = files.div_ceil(FILES_PER_MODULE);
+    let mut lib = String::from("//! Synthetic crate generated by `orchestrator bench`\n\n");
+    for m in 0..modules {
+        let _ = writeln!(lib, "pub mod m{};", m);
+        let module_dir = src.join(format!("m{}", m));
+        std::fs::create_dir_all(&module_dir)?;
+        let mut module = String::new();
+        for i in (m * FILES_PER_MODULE)..((m + 1) * FILES_PER_MODULE).min(files) {
+            let _ = writeln!(module, "pub mod f{};", i);
+        }
+        std::fs::write(module_dir.join("mod.rs"), module)?;
+    }
+    std::fs::write(src.join("lib.rs"), lib)?;
+
+    for i in 0..files {
+        let mut code = String::new();
+        // One call into another file per function, imported at the top
+        let targets: Vec<(usize, usize)> = (0..symbols_per_file)
+            .map(|j| {
+                let h = mix(seed ^ 0xCA11, (i * 1_000 + j) as u64);
+                let file = (h % files as u64) as usize;
+                let function = ((h >> 32) % symbols_per_file as u64) as usize;
+                (file, function)
+            })
+            .collect();
+        for &(file, function) in &targets {
+            if file != i {
+                let _ = writeln!(
+                    code,
+                    "use crate::m{}::f{}::{};",
+                    file / FILES_PER_MODULE,
+                    file,
+                    function_name(seed, file, function)
+                );
+            }
+        }
+        let noun = word(NOUNS, mix(seed, i as u64));
+        let _ = write!(
+            code,
+            "\n/// State of the {noun} pipeline, stage {i}\n\
+             pub struct {}Stage{i} {{\n    pub id: u64,\n    pub label: String,\n}}\n",
+            capitalize(noun)
+        );
+        for (j, &(file, function)) in targets.iter().enumerate() {
+            let name = function_name(seed, i, j);
+            let mut body = format!("    let mut acc = input.wrapping_mul({});\n", j + 1);
+            if j + 1 < symbols_per_file {
+                let _ = writeln!(
+                    body,
+                    "    acc ^= {}(acc >> 1);",
+                    function_name(seed, i, j + 1)
+                );
+            }
+            // Calls between files only go forward, so the graph has no cycles
+            // through which the generated code would recurse forever
+            if file > i {
+                let _ = writeln!(
+                    body,
+                    "    acc = acc.wrapping_add({}(acc));",
+                    function_name(seed, file, function)
+                );
+            }
+            let _ = write!(
+                code,
+                "\n/// {} the {} records of stage {}\npub fn {}(input: u64) -> u64 {{\n{}    acc\n}}\n",
+                capitalize(word(VERBS, mix(seed, (i * 1_000 + j) as u64))),
+                noun,
+                i,
+                name,
+                body
+            );
+        }
+        std::fs::write(
+            src.join(format!("m{}", i / FILES_PER_MODULE))
+                .join(format!("f{}.rs", i)),
+            code,
+        )?;
+    }
+
+    Ok(GeneratedRepo {
+        files: files + modules + 1,
+        functions: files * symbols_per_file,
+        structs: files,
+    })
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// The search workload: `count` queries mixing identifier fragments and
+/// vocabulary words.
+pub fn search_queries(count: usize, seed: u64) -> Vec<String> {
+    (0..count)
+        .map(|n| {
+            let h = mix(seed ^ 0x5EA7, n as u64);
+            match h % 3 {
+                0 => format!("{} {}", word(VERBS, h >> 8), word(NOUNS, h >> 24)),
+                1 => format!("{}_{}", word(VERBS, h >> 8), word(NOUNS, h >> 24)),
+                _ => format!("{} stage", word(NOUNS, h >> 8)),
+            }
+        })
+        .collect()
+}
+
+/// Generate the synthetic crate, sync it into a new project, compute its
+/// analytics and replay the search workload.
+pub async fn run(orchestrator: &Orchestrator, opts: &BenchOptions) -> Result<BenchReport> {
+    let id = Uuid::new_v4();
+    let slug = format!("bench-{}", &id.simple().to_string()[..8]);
+    let dir = std::env::temp_dir().join(format!("orchestrator-{}", slug));
+
+    let started = Instant::now();
+    let generated = generate_repository(&dir, opts.files, opts.symbols_per_file, opts.seed)?;
+    let generate_secs = started.elapsed().as_secs_f64();
+
+    let project = ProjectNode {
+        id,
+        name: slug.clone(),
+        slug: slug.clone(),
+        root_path: dir.to_string_lossy().to_string(),
+        description: Some("Synthetic project of `orchestrator bench`".to_string()),
+        created_at: chrono::Utc::now(),
+        last_synced: None,
+        analytics_computed_at: None,
+        last_co_change_computed_at: None,
+        default_note_energy: None,
+        scaffolding_override: None,
+        sharing_policy: None,
+        watch_enabled: false,
+        archived: false,
+    };
+    orchestrator.create_project(&project).await?;
+
+    let result = measure(orchestrator, opts, &dir, &project, generated, generate_secs).await;
+    if !opts.keep {
+        if let Err(e) = orchestrator.delete_project(id).await {
+            tracing::warn!(project = %slug, error = %e, "Failed to delete the bench project");
+        }
+        if let Err(e) = std::fs::remove_dir_all(&dir) {
+            tracing::warn!(path = %dir.display(), error = %e, "Failed to remove the bench files");
+        }
+    }
+    result
+}
+
+async fn measure(
+    orchestrator: &Orchestrator,
+    opts: &BenchOptions,
+    dir: &Path,
+    project: &ProjectNode,
+    generated: GeneratedRepo,
+    generate_secs: f64,
+) -> Result<BenchReport> {
+    let started = Instant::now();
+    let synced = orchestrator
+        .sync_directory_for_project_with_options(dir, Some(project.id), Some(&project.slug), true)
+        .await?;
+    // Searches below must see the documents the sync queued
+    orchestrator
+        .meili()
+        .flush_pending(Duration::from_secs(600))
+        .await?;
+    let elapsed = started.elapsed();
+    let sync = SyncPhase {
+        files_synced: synced.files_synced,
+        errors: synced.errors,
+        secs: elapsed.as_secs_f64(),
+        files_per_sec: per_sec(synced.files_synced, elapsed),
+        symbols_per_sec: per_sec(generated.symbols(), elapsed),
+    };
+
+    let started = Instant::now();
+    let computed = orchestrator.analytics().analyze_project(project.id).await?;
+    let analytics = AnalyticsPhase {
+        secs: started.elapsed().as_secs_f64(),
+        file_nodes: computed.file_analytics.node_count,
+        file_edges: computed.file_analytics.edge_count,
+        function_nodes: computed.function_analytics.node_count,
+        function_edges: computed.function_analytics.edge_count,
+    };
+
+    let queries = search_queries(opts.queries, opts.seed);
+    let started = Instant::now();
+    let timings: Vec<Option<Duration>> =
+        futures::stream::iter(queries.iter().map(|q| async move {
+            let started = Instant::now();
+            orchestrator
+                .meili()
+                .search_code_in_project(q, SEARCH_LIMIT, None, Some(&project.slug))
+                .await
+                .ok()
+                .map(|_| started.elapsed())
+        }))
+        .buffer_unordered(opts.concurrency.max(1))
+        .collect()
+        .await;
+    let samples: Vec<Duration> = timings.iter().flatten().copied().collect();
+    let search =
+        LatencyStats::from_samples(&samples, timings.len() - samples.len(), started.elapsed());
+
+    Ok(BenchReport {
+        generated,
+        generate_secs,
+        sync,
+        analytics,
+        search,
+        concurrency: opts.concurrency.max(1),
+        project_slug: project.slug.clone(),
+        repository: opts.keep.then(|| dir.to_path_buf()),
+    })
+}
+
+/// Text summary of a benchmark run.
+pub fn render(report: &BenchReport) -> String {
+    let mut out = String::new();
+    let g = &report.generated;
+    let _ = writeln!(
+        out,
+        "generate   {} files, {} functions, {} structs in {:.2}s",
+        g.files, g.functions, g.structs, report.generate_secs
+    );
+    let s = &report.sync;
+    let _ = writeln!(
+        out,
+        "sync       {} files ({} errors) in {:.2}s: {:.1} files/s, {:.0} symbols/s",
+        s.files_synced, s.errors, s.secs, s.files_per_sec, s.symbols_per_sec
+    );
+    let a = &report.analytics;
+    let _ = writeln!(
+        out,
+        "analytics  {:.2}s: files {} nodes/{} edges, functions {} nodes/{} edges",
+        a.secs, a.file_nodes, a.file_edges, a.function_nodes, a.function_edges
+    );
+    let q = &report.search;
+    let _ = writeln!(
+        out,
+        "search     {} queries ({} errors, {} in flight): {:.1} q/s",
+        q.count, q.errors, report.concurrency, q.per_sec
+    );
+    let _ = writeln!(
+        out,
+        "           p50 {:.1}ms  p90 {:.1}ms  p99 {:.1}ms  max {:.1}ms  mean {:.1}ms",
+        q.p50_ms, q.p90_ms, q.p99_ms, q.max_ms, q.mean_ms
+    );
+    if let Some(path) = &report.repository {
+        let _ = writeln!(
+            out,
+            "\nKept project {} ({})",
+            report.project_slug,
+            path.display()
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_stats() {
+        let samples: Vec<Duration> = (1..=100).map(Duration::from_millis).collect();
+        let stats = LatencyStats::from_samples(&samples, 2, Duration::from_secs(4));
+        assert_eq!(stats.count, 100);
+        assert_eq!(stats.errors, 2);
+        assert_eq!(stats.p50_ms, 50.0);
+        assert_eq!(stats.p90_ms, 90.0);
+        assert_eq!(stats.p99_ms, 99.0);
+        assert_eq!(stats.max_ms, 100.0);
+        assert_eq!(stats.mean_ms, 50.5);
+        assert_eq!(stats.per_sec, 25.0);
+
+        let one = LatencyStats::from_samples(&[Duration::from_millis(7)], 0, Duration::ZERO);
+        assert_eq!((one.p50_ms, one.p99_ms), (7.0, 7.0));
+        assert_eq!(one.per_sec, 0.0);
+        assert_eq!(LatencyStats::from_samples(&[], 3, Duration::ZERO).errors, 3);
+    }
+
+    #[test]
+    fn test_generate_repository() {
+        let tmp = tempfile::tempdir().unwrap();
+        let generated = generate_repository(tmp.path(), 30, 4, 7).unwrap();
+        assert_eq!(
+            generated,
+            GeneratedRepo {
+                files: 30 + 2 + 1,
+                functions: 120,
+                structs: 30,
+            }
+        );
+        let lib = std::fs::read_to_string(tmp.path().join("src/lib.rs")).unwrap();
+        assert!(lib.contains("pub mod m0;") && lib.contains("pub mod m1;"));
+        let file = std::fs::read_to_string(tmp.path().join("src/m1/f27.rs")).unwrap();
+        assert!(file.contains(&format!("pub fn {}(", function_name(7, 27, 3))));
+        assert!(file.contains("pub struct"));
+
+        // Same seed, same code
+        let again = tempfile::tempdir().unwrap();
+        generate_repository(again.path(), 30, 4, 7).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(again.path().join("src/m1/f27.rs")).unwrap(),
+            file
+        );
+        assert!(generate_repository(tmp.path(), 0, 4, 7).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run() {
+        let orchestrator = Orchestrator::new(crate::test_helpers::mock_app_state())
+            .await
+            .unwrap();
+        let opts = BenchOptions {
+            files: 6,
+            symbols_per_file: 3,
+            queries: 20,
+            concurrency: 4,
+            ..BenchOptions::default()
+        };
+        let report = run(&orchestrator, &opts).await.unwrap();
+        assert_eq!(report.generated.symbols(), 24);
+        assert_eq!(report.sync.files_synced, report.generated.files);
+        assert_eq!(report.search.count + report.search.errors, 20);
+        assert!(report.repository.is_none());
+        assert!(render(&report).contains("20 queries"));
+
+        // The project and the files are gone
+        assert!(orchestrator
+            .neo4j()
+            .get_project_by_slug(&report.project_slug)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(!std::env::temp_dir()
+            .join(format!("orchestrator-{}", report.project_slug))
+            .exists());
+    }
+}
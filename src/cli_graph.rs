@@ -0,0 +1,334 @@
+//! `orchestrator graph` — explore the code graph from a terminal.
+//!
+//! Thin wrappers over the [`GraphStore`] call-graph and trait queries, for
+//! quick shell exploration without a running server. `path` has no Cypher
+//! counterpart: it is a bounded breadth-first search over direct callees, so
+//! it never issues more than [`PATH_NODE_BUDGET`] queries.
+
+use crate::neo4j::models::TraitImplementorNode;
+use crate::neo4j::GraphStore;
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use uuid::Uuid;
+
+/// Default and maximum number of hops explored by [`path`].
+pub const DEFAULT_PATH_DEPTH: u32 = 6;
+/// Maximum number of functions expanded by [`path`] before giving up.
+pub const PATH_NODE_BUDGET: usize = 500;
+
+/// Direction of a call-graph query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    Callers,
+    Callees,
+}
+
+/// One function on the other end of a CALLS edge.
+#[derive(Debug, Clone, Serialize)]
+pub struct CallEdge {
+    pub name: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub file_path: String,
+    /// Only known for direct (depth 1) edges.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Callers or callees of `symbol` over CALLS edges with a confidence of at
+/// least `min_confidence`.
+///
+/// At depth 1 each edge carries its confidence score, highest first; deeper
+/// queries return the transitive set of names only.
+pub async fn calls(
+    graph: &dyn GraphStore,
+    direction: Direction,
+    symbol: &str,
+    depth: u32,
+    project_id: Option<Uuid>,
+    min_confidence: f64,
+) -> Result<Vec<CallEdge>> {
+    if depth > 1 {
+        let names = match direction {
+            Direction::Callers => {
+                graph
+                    .get_function_callers_by_name(symbol, depth, project_id, min_confidence)
+                    .await?
+            }
+            Direction::Callees => {
+                graph
+                    .get_function_callees_by_name(symbol, depth, project_id, min_confidence)
+                    .await?
+            }
+        };
+        return Ok(names
+            .into_iter()
+            .map(|name| CallEdge {
+                name,
+                file_path: String::new(),
+                confidence: None,
+                reason: None,
+            })
+            .collect());
+    }
+
+    let rows = match direction {
+        Direction::Callers => {
+            graph
+                .get_callers_with_confidence(symbol, project_id)
+                .await?
+        }
+        Direction::Callees => {
+            graph
+                .get_callees_with_confidence(symbol, project_id)
+                .await?
+        }
+    };
+    let mut edges: Vec<CallEdge> = rows
+        .into_iter()
+        .filter(|(_, _, confidence, _)| *confidence >= min_confidence)
+        .map(|(name, file_path, confidence, reason)| CallEdge {
+            name,
+            file_path,
+            confidence: Some(confidence),
+            reason: Some(reason),
+        })
+        .collect();
+    edges.sort_by(|a, b| {
+        b.confidence
+            .unwrap_or_default()
+            .total_cmp(&a.confidence.unwrap_or_default())
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(edges)
+}
+
+/// Types implementing `trait_name`, sorted by file and line.
+pub async fn implementors(
+    graph: &dyn GraphStore,
+    trait_name: &str,
+) -> Result<Vec<TraitImplementorNode>> {
+    let mut implementors = graph.get_trait_implementors_detailed(trait_name).await?;
+    implementors.sort_by(|a, b| (&a.file_path, a.line).cmp(&(&b.file_path, b.line)));
+    Ok(implementors)
+}
+
+/// Shortest call chain from `from` to `to` (both included), or `None` when
+/// `to` is not reachable within `max_depth` hops.
+pub async fn path(
+    graph: &dyn GraphStore,
+    from: &str,
+    to: &str,
+    max_depth: u32,
+    project_id: Option<Uuid>,
+) -> Result<Option<Vec<String>>> {
+    if from == to {
+        return Ok(Some(vec![from.to_string()]));
+    }
+
+    // callee → the caller it was first reached from
+    let mut parent: HashMap<String, String> = HashMap::new();
+    let mut seen: HashSet<String> = HashSet::from([from.to_string()]);
+    let mut queue: VecDeque<(String, u32)> = VecDeque::from([(from.to_string(), 0)]);
+    let mut expanded = 0;
+
+    while let Some((name, depth)) = queue.pop_front() {
+        if depth >= max_depth {
+            continue;
+        }
+        if expanded >= PATH_NODE_BUDGET {
+            tracing::debug!(
+                "graph path {} -> {}: stopped after {} functions",
+                from,
+                to,
+                expanded
+            );
+            break;
+        }
+        expanded += 1;
+
+        for (callee, _, _, _) in graph.get_callees_with_confidence(&name, project_id).await? {
+            if !seen.insert(callee.clone()) {
+                continue;
+            }
+            parent.insert(callee.clone(), name.clone());
+            if callee == to {
+                let mut chain = vec![callee];
+                while let Some(prev) = parent.get(chain.last().unwrap()) {
+                    chain.push(prev.clone());
+                }
+                chain.reverse();
+                return Ok(Some(chain));
+            }
+            queue.push_back((callee, depth + 1));
+        }
+    }
+    Ok(None)
+}
+
+/// Render call edges as an aligned table.
+pub fn render_calls(edges: &[CallEdge]) -> String {
+    if edges.is_empty() {
+        return "No results.\n".to_string();
+    }
+    let width = edges.iter().map(|e| e.name.len()).max().unwrap_or(0).max(8);
+    let detailed = edges.iter().any(|e| e.confidence.is_some());
+    let mut out = if detailed {
+        format!(
+            "{:<width$}  {:>10}  {:<12}  FILE\n",
+            "FUNCTION", "CONFIDENCE", "REASON"
+        )
+    } else {
+        "FUNCTION\n".to_string()
+    };
+    for e in edges {
+        if detailed {
+            out.push_str(&format!(
+                "{:<width$}  {:>10}  {:<12}  {}\n",
+                e.name,
+                e.confidence
+                    .map(|c| format!("{:.2}", c))
+                    .unwrap_or_default(),
+                e.reason.as_deref().unwrap_or_default(),
+                e.file_path
+            ));
+        } else {
+            out.push_str(&format!("{}\n", e.name));
+        }
+    }
+    out
+}
+
+/// Render trait implementors as an aligned table.
+pub fn render_implementors(implementors: &[TraitImplementorNode]) -> String {
+    if implementors.is_empty() {
+        return "No results.\n".to_string();
+    }
+    let width = implementors
+        .iter()
+        .map(|i| i.type_name.len())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let mut out = format!("{:<width$}  LOCATION\n", "TYPE");
+    for i in implementors {
+        out.push_str(&format!(
+            "{:<width$}  {}:{}\n",
+            i.type_name, i.file_path, i.line
+        ));
+    }
+    out
+}
+
+/// Render a call chain as `a -> b -> c`.
+pub fn render_path(chain: Option<&[String]>) -> String {
+    match chain {
+        Some(chain) => format!("{}\n", chain.join(" -> ")),
+        None => "No path found.\n".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+
+    async fn call_graph(edges: &[(&str, &str)]) -> MockGraphStore {
+        let graph = MockGraphStore::new();
+        for (caller, callee) in edges {
+            graph
+                .create_call_relationship(
+                    &format!("src/lib.rs::{}", caller),
+                    callee,
+                    None,
+                    0.9,
+                    "same_file",
+                )
+                .await
+                .unwrap();
+        }
+        graph
+    }
+
+    #[tokio::test]
+    async fn test_direct_callees_and_callers() {
+        let graph = call_graph(&[("main", "run"), ("main", "init"), ("run", "init")]).await;
+
+        let callees = calls(&graph, Direction::Callees, "main", 1, None, 0.0)
+            .await
+            .unwrap();
+        let names: Vec<_> = callees.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["init", "run"]);
+        assert!(callees.iter().all(|e| e.confidence.is_some()));
+
+        let callers = calls(&graph, Direction::Callers, "init", 1, None, 0.0)
+            .await
+            .unwrap();
+        assert_eq!(callers.len(), 2);
+
+        // The mock scores every edge 0.50
+        let confident = calls(&graph, Direction::Callers, "init", 1, None, 0.6)
+            .await
+            .unwrap();
+        assert!(confident.is_empty());
+
+        let table = render_calls(&callees);
+        assert!(table.starts_with("FUNCTION"));
+        assert!(table.contains("CONFIDENCE"));
+        assert_eq!(render_calls(&[]), "No results.\n");
+    }
+
+    #[tokio::test]
+    async fn test_path_finds_shortest_chain() {
+        let graph = call_graph(&[
+            ("main", "serve"),
+            ("serve", "route"),
+            ("route", "handle"),
+            ("main", "handle_later"),
+            ("handle_later", "handle"),
+        ])
+        .await;
+
+        let chain = path(&graph, "main", "handle", DEFAULT_PATH_DEPTH, None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(chain.len(), 3, "{:?}", chain);
+        assert_eq!(chain.first().map(String::as_str), Some("main"));
+        assert_eq!(chain.last().map(String::as_str), Some("handle"));
+        assert_eq!(
+            render_path(Some(chain.as_slice())).matches(" -> ").count(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_path_respects_depth_and_unreachable() {
+        let graph = call_graph(&[("a", "b"), ("b", "c"), ("c", "d"), ("d", "a")]).await;
+
+        assert!(path(&graph, "a", "d", 2, None).await.unwrap().is_none());
+        assert_eq!(
+            path(&graph, "a", "d", 3, None).await.unwrap().unwrap(),
+            vec!["a", "b", "c", "d"]
+        );
+        assert!(path(&graph, "a", "zzz", DEFAULT_PATH_DEPTH, None)
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(render_path(None), "No path found.\n");
+    }
+
+    #[test]
+    fn test_render_implementors() {
+        let rows = vec![TraitImplementorNode {
+            type_name: "MeiliClient".to_string(),
+            file_path: "src/meilisearch/client.rs".to_string(),
+            line: 12,
+        }];
+        let table = render_implementors(&rows);
+        assert!(table.contains("MeiliClient  src/meilisearch/client.rs:12"));
+    }
+}
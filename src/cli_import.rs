@@ -0,0 +1,452 @@
+//! `orchestrator import` — onboard every git repository under a directory.
+//!
+//! [`discover_repositories`] walks the root (hidden and ignored directories
+//! excepted, see [`crate::orchestrator::should_ignore_path`]) and stops at the
+//! first directory holding a `.git` entry, so submodules and vendored
+//! checkouts are not imported on their own. Each repository becomes a project
+//! named after its directory; a slug already taken gets its parent directory
+//! as prefix, then a number. Repositories whose root is already a project are
+//! reported and left alone.
+//!
+//! The new projects are then synced [`ImportOptions::concurrency`] at a time.
+//! Graph analytics are not computed here: the server recomputes the stale
+//! ones at startup.
+
+use crate::neo4j::models::ProjectNode;
+use crate::orchestrator::Orchestrator;
+use anyhow::{bail, Context, Result};
+use futures::StreamExt;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Default depth below the root searched for repositories.
+pub const DEFAULT_MAX_DEPTH: usize = 3;
+
+/// Default number of projects synced at once.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Options for [`import`].
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+    pub root: PathBuf,
+    /// Directory levels below the root searched for repositories
+    pub max_depth: usize,
+    /// Projects synced at once (at least 1)
+    pub concurrency: usize,
+    /// Only report what would be imported
+    pub dry_run: bool,
+}
+
+/// What happened to one repository.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum ImportOutcome {
+    /// Project created and synced
+    Imported {
+        files_synced: usize,
+        errors: usize,
+        duration_secs: f64,
+    },
+    /// Project would be created (dry run)
+    Planned,
+    /// Root of an existing project (`slug` is that project's)
+    Existing,
+    /// Project created but its sync failed
+    SyncFailed { error: String },
+    /// Project could not be created
+    Failed { error: String },
+}
+
+/// One discovered repository.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportedRepo {
+    pub path: PathBuf,
+    pub slug: String,
+    #[serde(flatten)]
+    pub outcome: ImportOutcome,
+}
+
+/// Result of [`import`], in path order.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReport {
+    pub root: PathBuf,
+    pub repositories: Vec<ImportedRepo>,
+    pub duration_secs: f64,
+}
+
+impl ImportReport {
+    fn count(&self, pred: impl Fn(&ImportOutcome) -> bool) -> usize {
+        self.repositories
+            .iter()
+            .filter(|r| pred(&r.outcome))
+            .count()
+    }
+
+    /// Whether any repository failed to import or sync.
+    pub fn has_failures(&self) -> bool {
+        self.count(|o| {
+            matches!(
+                o,
+                ImportOutcome::Failed { .. } | ImportOutcome::SyncFailed { .. }
+            )
+        }) > 0
+    }
+}
+
+/// Git repositories under `root` (`root` itself included), sorted. A
+/// repository's subdirectories are not searched.
+pub fn discover_repositories(root: &Path, max_depth: usize) -> Vec<PathBuf> {
+    let mut repos = Vec::new();
+    let mut pending = vec![(root.to_path_buf(), 0usize)];
+    while let Some((dir, depth)) = pending.pop() {
+        if dir.join(".git").exists() {
+            repos.push(dir);
+            continue;
+        }
+        if depth >= max_depth {
+            continue;
+        }
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            // Symlinks are not followed: they could loop or lead out of the root
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            if is_dir
+                && !name.starts_with('.')
+                && !crate::orchestrator::should_ignore_path(&format!("/{}/", name))
+            {
+                pending.push((entry.path(), depth + 1));
+            }
+        }
+    }
+    repos.sort();
+    repos
+}
+
+/// Slug of `repo` not in `taken` (and added to it): the directory name, then
+/// prefixed with the parent directory name, then with a number appended.
+pub fn unique_slug(repo: &Path, taken: &mut HashSet<String>) -> String {
+    let name = |p: Option<&Path>| {
+        p.and_then(|p| p.file_name())
+            .map(|n| crate::api::project_handlers::slugify(&n.to_string_lossy()))
+            .filter(|s| !s.is_empty())
+    };
+    let base = name(Some(repo)).unwrap_or_else(|| "project".to_string());
+    let mut candidates = vec![base.clone()];
+    if let Some(parent) = name(repo.parent()) {
+        candidates.push(format!("{}-{}", parent, base));
+    }
+    let slug = candidates
+        .into_iter()
+        .find(|s| !taken.contains(s))
+        .unwrap_or_else(|| {
+            (2..)
+                .map(|n| format!("{}-{}", base, n))
+                .find(|s| !taken.contains(s))
+                .expect("unbounded range")
+        });
+    taken.insert(slug.clone());
+    slug
+}
+
+/// Canonical form of a project root, for comparing paths.
+fn canonical_root(root_path: &str) -> PathBuf {
+    let expanded = PathBuf::from(crate::expand_tilde(root_path));
+    std::fs::canonicalize(&expanded).unwrap_or(expanded)
+}
+
+/// Discover the repositories under `opts.root`, create their projects and
+/// sync them.
+pub async fn import(orchestrator: &Orchestrator, opts: &ImportOptions) -> Result<ImportReport> {
+    let started = std::time::Instant::now();
+    let root = std::fs::canonicalize(crate::expand_tilde(&opts.root.to_string_lossy()))
+        .with_context(|| format!("Cannot read {}", opts.root.display()))?;
+    if !root.is_dir() {
+        bail!("{} is not a directory", root.display());
+    }
+
+    let projects = orchestrator.neo4j().list_projects().await?;
+    let existing: HashMap<PathBuf, String> = projects
+        .iter()
+        .map(|p| (canonical_root(&p.root_path), p.slug.clone()))
+        .collect();
+    let mut taken: HashSet<String> = projects.into_iter().map(|p| p.slug).collect();
+
+    let mut repositories = Vec::new();
+    let mut to_sync = Vec::new();
+    for path in discover_repositories(&root, opts.max_depth) {
+        if let Some(slug) = existing.get(&path) {
+            repositories.push(ImportedRepo {
+                path,
+                slug: slug.clone(),
+                outcome: ImportOutcome::Existing,
+            });
+            continue;
+        }
+        let slug = unique_slug(&path, &mut taken);
+        let outcome = if opts.dry_run {
+            ImportOutcome::Planned
+        } else {
+            match create_project(orchestrator, &path, &slug).await {
+                Ok(id) => {
+                    to_sync.push((repositories.len(), id));
+                    ImportOutcome::Planned
+                }
+                Err(e) => ImportOutcome::Failed {
+                    error: e.to_string(),
+                },
+            }
+        };
+        repositories.push(ImportedRepo {
+            path,
+            slug,
+            outcome,
+        });
+    }
+
+    let syncs = to_sync.into_iter().map(|(index, id)| {
+        let repo = &repositories[index];
+        async move {
+            (
+                index,
+                sync_project(orchestrator, &repo.path, id, &repo.slug).await,
+            )
+        }
+    });
+    let outcomes: Vec<(usize, ImportOutcome)> = futures::stream::iter(syncs)
+        .buffer_unordered(opts.concurrency.max(1))
+        .collect()
+        .await;
+    for (index, outcome) in outcomes {
+        repositories[index].outcome = outcome;
+    }
+
+    Ok(ImportReport {
+        root,
+        repositories,
+        duration_secs: started.elapsed().as_secs_f64(),
+    })
+}
+
+async fn create_project(orchestrator: &Orchestrator, path: &Path, slug: &str) -> Result<Uuid> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| slug.to_string());
+    let project = ProjectNode {
+        id: Uuid::new_v4(),
+        name,
+        slug: slug.to_string(),
+        root_path: path.to_string_lossy().to_string(),
+        description: None,
+        created_at: chrono::Utc::now(),
+        last_synced: None,
+        analytics_computed_at: None,
+        last_co_change_computed_at: None,
+        default_note_energy: None,
+        scaffolding_override: None,
+        sharing_policy: None,
+        watch_enabled: true,
+        archived: false,
+    };
+    orchestrator.create_project(&project).await?;
+    // Same default protocols as projects created through the API
+    if let Err(e) =
+        crate::protocol::seed_runner::ensure_default_protocols(orchestrator.neo4j(), project.id)
+            .await
+    {
+        tracing::warn!(project = slug, error = %e, "Failed to seed default protocols");
+    }
+    Ok(project.id)
+}
+
+async fn sync_project(
+    orchestrator: &Orchestrator,
+    path: &Path,
+    id: Uuid,
+    slug: &str,
+) -> ImportOutcome {
+    let started = std::time::Instant::now();
+    let result = orchestrator
+        .sync_directory_for_project_with_options(path, Some(id), Some(slug), false)
+        .await;
+    match result {
+        Ok(result) => {
+            if let Err(e) = orchestrator.neo4j().update_project_synced(id).await {
+                tracing::warn!(project = slug, error = %e, "Failed to update last_synced");
+            }
+            ImportOutcome::Imported {
+                files_synced: result.files_synced,
+                errors: result.errors,
+                duration_secs: started.elapsed().as_secs_f64(),
+            }
+        }
+        Err(e) => ImportOutcome::SyncFailed {
+            error: e.to_string(),
+        },
+    }
+}
+
+/// Text summary of an import: one line per repository, then the totals.
+pub fn render(report: &ImportReport) -> String {
+    use std::fmt::Write;
+
+    let mut out = String::new();
+    if report.repositories.is_empty() {
+        let _ = writeln!(out, "No git repository under {}", report.root.display());
+        return out;
+    }
+    let width = report
+        .repositories
+        .iter()
+        .map(|r| r.slug.len())
+        .max()
+        .unwrap_or(0);
+    for repo in &report.repositories {
+        let status = match &repo.outcome {
+            ImportOutcome::Imported {
+                files_synced,
+                errors,
+                duration_secs,
+            } => format!(
+                "imported  {} files, {} errors, {:.1}s",
+                files_synced, errors, duration_secs
+            ),
+            ImportOutcome::Planned => "to import".to_string(),
+            ImportOutcome::Existing => "exists".to_string(),
+            ImportOutcome::SyncFailed { error } => format!("sync failed: {}", error),
+            ImportOutcome::Failed { error } => format!("failed: {}", error),
+        };
+        let _ = writeln!(
+            out,
+            "{:<width$}  {}  ({})",
+            repo.slug,
+            status,
+            repo.path.display(),
+            width = width
+        );
+    }
+    let _ = writeln!(
+        out,
+        "\n{} imported, {} to import, {} already projects, {} failed in {:.1}s",
+        report.count(|o| matches!(o, ImportOutcome::Imported { .. })),
+        report.count(|o| matches!(o, ImportOutcome::Planned)),
+        report.count(|o| matches!(o, ImportOutcome::Existing)),
+        report.count(|o| {
+            matches!(
+                o,
+                ImportOutcome::Failed { .. } | ImportOutcome::SyncFailed { .. }
+            )
+        }),
+        report.duration_secs
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git_repo(root: &Path, rel: &str) -> PathBuf {
+        let path = root.join(rel);
+        std::fs::create_dir_all(path.join(".git")).unwrap();
+        std::fs::write(path.join("main.rs"), "fn main() {}\n").unwrap();
+        path
+    }
+
+    #[test]
+    fn test_discover_repositories() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let api = git_repo(root, "work/api");
+        let web = git_repo(root, "web");
+        // Not searched: inside a repository, hidden, ignored, too deep
+        git_repo(root, "web/vendor-lib");
+        git_repo(root, ".cache/tool");
+        git_repo(root, "node_modules/pkg");
+        git_repo(root, "a/b/c/deep");
+
+        assert_eq!(discover_repositories(root, 3), vec![api, web]);
+        assert!(discover_repositories(root, 0).is_empty());
+        assert_eq!(discover_repositories(&root.join("web"), 0).len(), 1);
+    }
+
+    #[test]
+    fn test_unique_slug() {
+        let mut taken = HashSet::from(["api".to_string()]);
+        assert_eq!(
+            unique_slug(Path::new("/code/Web App"), &mut taken),
+            "web-app"
+        );
+        assert_eq!(
+            unique_slug(Path::new("/code/work/api"), &mut taken),
+            "work-api"
+        );
+        assert_eq!(
+            unique_slug(Path::new("/code/work/api"), &mut taken),
+            "api-2"
+        );
+        assert!(taken.contains("api-2"));
+    }
+
+    #[tokio::test]
+    async fn test_import() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = std::fs::canonicalize(tmp.path()).unwrap();
+        let api = git_repo(&root, "api");
+        git_repo(&root, "clients/api");
+        let web = git_repo(&root, "web");
+
+        let orchestrator = Orchestrator::new(crate::test_helpers::mock_app_state())
+            .await
+            .unwrap();
+        let mut known = crate::test_helpers::test_project_named("web");
+        known.root_path = web.to_string_lossy().to_string();
+        orchestrator.neo4j().create_project(&known).await.unwrap();
+
+        let mut opts = ImportOptions {
+            root: root.clone(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            concurrency: 2,
+            dry_run: true,
+        };
+        let planned = import(&orchestrator, &opts).await.unwrap();
+        let slugs: Vec<&str> = planned
+            .repositories
+            .iter()
+            .map(|r| r.slug.as_str())
+            .collect();
+        assert_eq!(slugs, vec!["api", "clients-api", "web"]);
+        assert_eq!(planned.repositories[0].outcome, ImportOutcome::Planned);
+        assert_eq!(planned.repositories[2].outcome, ImportOutcome::Existing);
+        assert_eq!(orchestrator.neo4j().list_projects().await.unwrap().len(), 1);
+
+        opts.dry_run = false;
+        let report = import(&orchestrator, &opts).await.unwrap();
+        assert!(!report.has_failures());
+        assert_eq!(report.repositories[0].path, api);
+        assert!(matches!(
+            report.repositories[0].outcome,
+            ImportOutcome::Imported { .. }
+        ));
+        let project = orchestrator
+            .neo4j()
+            .get_project_by_slug("clients-api")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(project.last_synced.is_some());
+        assert!(render(&report).contains("2 imported, 0 to import, 1 already projects"));
+
+        // Imported repositories are existing projects the next time
+        let again = import(&orchestrator, &opts).await.unwrap();
+        assert!(again
+            .repositories
+            .iter()
+            .all(|r| r.outcome == ImportOutcome::Existing));
+    }
+}
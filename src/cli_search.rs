@@ -0,0 +1,372 @@
+//! `orchestrator search` — query the search index from a terminal.
+//!
+//! Talks to the [`SearchStore`] directly (no running server needed). Code
+//! documents are per file and carry no positions, so function hits are
+//! resolved to line numbers through the graph when it is reachable; without
+//! it, function results are printed with their signature only.
+
+use crate::meilisearch::indexes::{CodeDocument, NoteDocument, SearchHit};
+use crate::meilisearch::SearchStore;
+use crate::neo4j::GraphStore;
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// What to search for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchKind {
+    /// Functions whose name matches the query, with file and line
+    Function,
+    /// Knowledge notes
+    Note,
+    /// Indexed source files
+    File,
+}
+
+/// Options for [`search`].
+#[derive(Debug, Clone)]
+pub struct SearchOptions {
+    pub query: String,
+    pub project_slug: Option<String>,
+    /// `None` searches functions, files and notes together.
+    pub kind: Option<SearchKind>,
+    pub limit: usize,
+}
+
+/// One ranked result.
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResult {
+    pub kind: SearchKind,
+    /// Relevance from the search engine (0.0–1.0). Scores from the code and
+    /// note indexes are merged as-is.
+    pub score: f64,
+    pub project_slug: String,
+    /// File path, or the note scope path.
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    /// Function signature, language, or note type/importance.
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+}
+
+/// Maximum characters of note content shown as a snippet.
+const SNIPPET_CHARS: usize = 120;
+
+/// Run a search and return results ranked by score.
+pub async fn search(
+    store: &dyn SearchStore,
+    graph: Option<&dyn GraphStore>,
+    opts: &SearchOptions,
+) -> Result<Vec<SearchResult>> {
+    let project = opts.project_slug.as_deref();
+    let mut results = Vec::new();
+
+    if opts.kind != Some(SearchKind::Note) {
+        let hits = store
+            .search_code_with_scores(&opts.query, opts.limit, None, project, None)
+            .await?;
+        for hit in hits {
+            results.extend(code_results(&hit, &opts.query, opts.kind, graph).await);
+        }
+    }
+
+    if opts.kind.is_none() || opts.kind == Some(SearchKind::Note) {
+        let hits = store
+            .search_notes_with_scores(&opts.query, opts.limit, project, None, None, None)
+            .await?;
+        results.extend(hits.into_iter().map(note_result));
+    }
+
+    results.sort_by(|a, b| b.score.total_cmp(&a.score));
+    results.truncate(opts.limit);
+    Ok(results)
+}
+
+/// Turn a code hit into function rows (names matching the query) or, when
+/// none match or files were requested, a single file row.
+async fn code_results(
+    hit: &SearchHit<CodeDocument>,
+    query: &str,
+    kind: Option<SearchKind>,
+    graph: Option<&dyn GraphStore>,
+) -> Vec<SearchResult> {
+    let doc = &hit.document;
+    let file_row = || SearchResult {
+        kind: SearchKind::File,
+        score: hit.score,
+        project_slug: doc.project_slug.clone(),
+        path: doc.path.clone(),
+        line: None,
+        title: doc.language.clone(),
+        snippet: (!doc.symbols.is_empty()).then(|| truncate(&doc.symbols.join(", "))),
+    };
+    if kind == Some(SearchKind::File) {
+        return vec![file_row()];
+    }
+
+    let matching: Vec<(&str, &str)> = doc
+        .signatures
+        .iter()
+        .filter_map(|sig| function_name(sig).map(|name| (name, sig.as_str())))
+        .filter(|(name, _)| name_matches(name, query))
+        .collect();
+    if matching.is_empty() {
+        return if kind.is_none() {
+            vec![file_row()]
+        } else {
+            vec![]
+        };
+    }
+
+    let lines = match graph {
+        Some(graph) => function_lines(graph, &doc.path).await,
+        None => HashMap::new(),
+    };
+    matching
+        .into_iter()
+        .map(|(name, signature)| SearchResult {
+            kind: SearchKind::Function,
+            score: hit.score,
+            project_slug: doc.project_slug.clone(),
+            path: doc.path.clone(),
+            line: lines.get(name).copied(),
+            title: signature.to_string(),
+            snippet: None,
+        })
+        .collect()
+}
+
+fn note_result(hit: SearchHit<NoteDocument>) -> SearchResult {
+    let note = hit.document;
+    let first_line = note.content.lines().next().unwrap_or_default();
+    SearchResult {
+        kind: SearchKind::Note,
+        score: hit.score,
+        project_slug: note.project_slug,
+        path: if note.scope_path.is_empty() {
+            note.scope_type
+        } else {
+            note.scope_path
+        },
+        line: None,
+        title: format!("{}, {}", note.note_type, note.importance),
+        snippet: Some(truncate(first_line)),
+    }
+}
+
+/// Function name → first line, from the graph. Errors are treated as "no
+/// lines": the index result is still worth printing.
+async fn function_lines(graph: &dyn GraphStore, path: &str) -> HashMap<String, u32> {
+    match graph.get_file_functions_summary(path).await {
+        Ok(functions) => functions.into_iter().map(|f| (f.name, f.line)).collect(),
+        Err(e) => {
+            tracing::debug!("No line numbers for {}: {}", path, e);
+            HashMap::new()
+        }
+    }
+}
+
+/// Extract the function name from a signature such as
+/// `pub async fn new<T>(url: &str) -> Result<Self>` or `def run(self)`.
+fn function_name(signature: &str) -> Option<&str> {
+    let head = &signature[..signature.find('(')?];
+    let name = head.split_whitespace().last()?;
+    let name = name.split('<').next().unwrap_or(name);
+    (!name.is_empty()).then_some(name)
+}
+
+/// Case-insensitive match of a symbol name against the whole query or any
+/// of its terms (terms shorter than 2 characters are ignored).
+fn name_matches(name: &str, query: &str) -> bool {
+    let name = name.to_lowercase();
+    let query = query.to_lowercase();
+    name.contains(query.trim())
+        || query
+            .split_whitespace()
+            .filter(|t| t.len() >= 2)
+            .any(|t| name.contains(t))
+}
+
+fn truncate(s: &str) -> String {
+    if s.chars().count() <= SNIPPET_CHARS {
+        s.to_string()
+    } else {
+        let cut: String = s.chars().take(SNIPPET_CHARS).collect();
+        format!("{}…", cut)
+    }
+}
+
+/// Render results as a numbered list for the terminal.
+pub fn render(results: &[SearchResult]) -> String {
+    if results.is_empty() {
+        return "No results.\n".to_string();
+    }
+    let mut out = String::new();
+    for (i, r) in results.iter().enumerate() {
+        let kind = match r.kind {
+            SearchKind::Function => "function",
+            SearchKind::Note => "note",
+            SearchKind::File => "file",
+        };
+        let location = match r.line {
+            Some(line) => format!("{}:{}", r.path, line),
+            None => r.path.clone(),
+        };
+        out.push_str(&format!(
+            "{:>3}. [{:<8}] {:.2}  {}  ({}) {}\n",
+            i + 1,
+            kind,
+            r.score,
+            location,
+            r.project_slug,
+            r.title
+        ));
+        if let Some(ref snippet) = r.snippet {
+            out.push_str(&format!("                      {}\n", snippet));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meilisearch::mock::MockSearchStore;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::{FunctionNode, Visibility};
+
+    fn code_doc(path: &str, signatures: &[&str]) -> CodeDocument {
+        CodeDocument {
+            id: path.to_string(),
+            path: path.to_string(),
+            language: "rust".to_string(),
+            symbols: signatures
+                .iter()
+                .filter_map(|s| function_name(s).map(String::from))
+                .collect(),
+            docstrings: String::new(),
+            signatures: signatures.iter().map(|s| s.to_string()).collect(),
+            imports: vec![],
+            project_id: "p1".to_string(),
+            project_slug: "demo".to_string(),
+        }
+    }
+
+    fn note_doc(content: &str) -> NoteDocument {
+        NoteDocument {
+            id: "n1".to_string(),
+            project_id: "p1".to_string(),
+            project_slug: "demo".to_string(),
+            note_type: "gotcha".to_string(),
+            status: "active".to_string(),
+            importance: "high".to_string(),
+            scope_type: "file".to_string(),
+            scope_path: "src/auth/jwt.rs".to_string(),
+            content: content.to_string(),
+            tags: vec![],
+            anchor_entities: vec![],
+            created_at: 0,
+            created_by: "test".to_string(),
+            staleness_score: 0.0,
+        }
+    }
+
+    fn opts(query: &str, kind: Option<SearchKind>) -> SearchOptions {
+        SearchOptions {
+            query: query.to_string(),
+            project_slug: None,
+            kind,
+            limit: 10,
+        }
+    }
+
+    #[test]
+    fn test_function_name() {
+        assert_eq!(
+            function_name("pub async fn new<T>(url: &str) -> Result<Self>"),
+            Some("new")
+        );
+        assert_eq!(function_name("def run(self)"), Some("run"));
+        assert_eq!(function_name("no parens"), None);
+    }
+
+    #[test]
+    fn test_name_matches_terms() {
+        assert!(name_matches("validate_token", "token"));
+        assert!(name_matches("validate_token", "jwt TOKEN"));
+        assert!(!name_matches("validate_token", "refresh"));
+    }
+
+    #[tokio::test]
+    async fn test_function_kind_resolves_lines_from_graph() {
+        let store = MockSearchStore::new();
+        store
+            .index_code(&code_doc(
+                "src/auth/jwt.rs",
+                &["fn validate_token(t: &str) -> bool", "fn encode(c: Claims)"],
+            ))
+            .await
+            .unwrap();
+        let graph = MockGraphStore::new();
+        graph
+            .upsert_function(&FunctionNode {
+                name: "validate_token".to_string(),
+                visibility: Visibility::Public,
+                params: vec![],
+                return_type: None,
+                generics: vec![],
+                is_async: false,
+                is_unsafe: false,
+                complexity: 1,
+                file_path: "src/auth/jwt.rs".to_string(),
+                line_start: 42,
+                line_end: 50,
+                docstring: None,
+            })
+            .await
+            .unwrap();
+
+        let results = search(
+            &store,
+            Some(&graph),
+            &opts("token", Some(SearchKind::Function)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(results.len(), 1, "{:?}", results);
+        assert_eq!(results[0].kind, SearchKind::Function);
+        assert_eq!(results[0].line, Some(42));
+        assert_eq!(results[0].title, "fn validate_token(t: &str) -> bool");
+    }
+
+    #[tokio::test]
+    async fn test_default_kind_merges_files_and_notes() {
+        let store = MockSearchStore::new();
+        store
+            .index_code(&code_doc("src/jwt.rs", &["fn sign()"]))
+            .await
+            .unwrap();
+        store
+            .index_note(&note_doc("jwt secrets must be 32 chars\nmore"))
+            .await
+            .unwrap();
+
+        let results = search(&store, None, &opts("jwt", None)).await.unwrap();
+        let kinds: Vec<_> = results.iter().map(|r| r.kind).collect();
+        assert!(kinds.contains(&SearchKind::File), "{:?}", results);
+        assert!(kinds.contains(&SearchKind::Note), "{:?}", results);
+        let note = results.iter().find(|r| r.kind == SearchKind::Note).unwrap();
+        assert_eq!(
+            note.snippet.as_deref(),
+            Some("jwt secrets must be 32 chars")
+        );
+
+        let rendered = render(&results);
+        assert!(rendered.contains("src/auth/jwt.rs"));
+        assert!(rendered.starts_with("  1. ["));
+        assert_eq!(render(&[]), "No results.\n");
+    }
+}
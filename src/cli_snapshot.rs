@@ -0,0 +1,772 @@
+//! `orchestrator snapshot` — point-in-time backup of one project.
+//!
+//! A snapshot captures what agents write and sync cannot rebuild: plans with
+//! their tasks, steps, decisions, constraints and dependencies, milestones,
+//! releases, knowledge notes with their anchors, and the project's search
+//! documents (code, decisions, notes, doc sections). The code graph itself
+//! is derived from the sources and is not included — `orchestrator sync`
+//! rebuilds it.
+//!
+//! The archive is a `.tar.gz` of JSON entries with a `manifest.json`
+//! describing the format version and the counts. Restoring replaces the
+//! project's current knowledge wholesale: everything listed above is
+//! deleted, then recreated from the archive with its original ids.
+
+use crate::meilisearch::indexes::{
+    CodeDocument, DecisionDocument, DocSectionDocument, NoteDocument,
+};
+use crate::meilisearch::SearchStore;
+use crate::neo4j::models::{
+    ConstraintNode, DecisionNode, MilestoneNode, PlanNode, ProjectNode, ReleaseNode, StepNode,
+    TaskNode,
+};
+use crate::neo4j::GraphStore;
+use crate::notes::{Note, NoteFilters};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Write};
+use uuid::Uuid;
+
+/// Version of the archive layout written by [`write_archive`].
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Page size used when listing plans and notes.
+const PAGE_SIZE: usize = 500;
+
+const MANIFEST_ENTRY: &str = "manifest.json";
+const PROJECT_ENTRY: &str = "project.json";
+const PLANS_ENTRY: &str = "graph/plans.json";
+const MILESTONES_ENTRY: &str = "graph/milestones.json";
+const RELEASES_ENTRY: &str = "graph/releases.json";
+const NOTES_ENTRY: &str = "graph/notes.json";
+const SEARCH_CODE_ENTRY: &str = "search/code.json";
+const SEARCH_DECISIONS_ENTRY: &str = "search/decisions.json";
+const SEARCH_NOTES_ENTRY: &str = "search/notes.json";
+const SEARCH_DOCS_ENTRY: &str = "search/docs.json";
+
+/// Number of entities of each kind in a snapshot.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotCounts {
+    pub plans: usize,
+    pub tasks: usize,
+    pub steps: usize,
+    pub decisions: usize,
+    pub constraints: usize,
+    pub milestones: usize,
+    pub releases: usize,
+    pub notes: usize,
+    pub search_documents: usize,
+}
+
+/// `manifest.json`: what the archive holds and where it came from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub format_version: u32,
+    /// Orchestrator version that wrote the archive.
+    pub orchestrator_version: String,
+    pub created_at: DateTime<Utc>,
+    pub project_id: Uuid,
+    pub project_slug: String,
+    pub counts: SnapshotCounts,
+}
+
+/// A task with everything hanging off it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskSnapshot {
+    pub task: TaskNode,
+    pub steps: Vec<StepNode>,
+    pub decisions: Vec<DecisionNode>,
+    /// Tasks this one depends on.
+    pub depends_on: Vec<Uuid>,
+}
+
+/// A plan with its constraints and tasks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanSnapshot {
+    pub plan: PlanNode,
+    pub constraints: Vec<ConstraintNode>,
+    pub tasks: Vec<TaskSnapshot>,
+}
+
+/// A milestone and what it targets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MilestoneSnapshot {
+    pub milestone: MilestoneNode,
+    /// Plans linked to the milestone (their tasks follow implicitly).
+    pub plan_ids: Vec<Uuid>,
+    /// Tasks linked directly, outside of the linked plans.
+    pub task_ids: Vec<Uuid>,
+}
+
+/// A release with its tasks and commits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseSnapshot {
+    pub release: ReleaseNode,
+    pub task_ids: Vec<Uuid>,
+    pub commit_hashes: Vec<String>,
+}
+
+/// The project's documents in each search index.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchSnapshot {
+    pub code: Vec<CodeDocument>,
+    pub decisions: Vec<DecisionDocument>,
+    pub notes: Vec<NoteDocument>,
+    pub docs: Vec<DocSectionDocument>,
+}
+
+impl SearchSnapshot {
+    fn len(&self) -> usize {
+        self.code.len() + self.decisions.len() + self.notes.len() + self.docs.len()
+    }
+}
+
+/// Everything captured for one project.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub manifest: SnapshotManifest,
+    pub project: ProjectNode,
+    pub plans: Vec<PlanSnapshot>,
+    pub milestones: Vec<MilestoneSnapshot>,
+    pub releases: Vec<ReleaseSnapshot>,
+    /// Notes with their anchors filled in.
+    pub notes: Vec<Note>,
+    pub search: SearchSnapshot,
+}
+
+impl Snapshot {
+    /// Count what the snapshot holds.
+    pub fn counts(&self) -> SnapshotCounts {
+        let tasks = || self.plans.iter().flat_map(|p| &p.tasks);
+        SnapshotCounts {
+            plans: self.plans.len(),
+            tasks: tasks().count(),
+            steps: tasks().map(|t| t.steps.len()).sum(),
+            decisions: tasks().map(|t| t.decisions.len()).sum(),
+            constraints: self.plans.iter().map(|p| p.constraints.len()).sum(),
+            milestones: self.milestones.len(),
+            releases: self.releases.len(),
+            notes: self.notes.len(),
+            search_documents: self.search.len(),
+        }
+    }
+}
+
+/// What [`restore`] replaced.
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreReport {
+    pub project_slug: String,
+    /// The project did not exist and was recreated from the snapshot.
+    pub project_created: bool,
+    pub removed: SnapshotCounts,
+    pub restored: SnapshotCounts,
+}
+
+// ============================================================================
+// Capture
+// ============================================================================
+
+/// Capture the current state of `project`.
+pub async fn capture(
+    graph: &dyn GraphStore,
+    search: &dyn SearchStore,
+    project: &ProjectNode,
+) -> Result<Snapshot> {
+    let mut plans = Vec::new();
+    loop {
+        let (page, _) = graph
+            .list_plans_for_project(project.id, None, PAGE_SIZE, plans.len())
+            .await?;
+        let fetched = page.len();
+        for plan in page {
+            plans.push(capture_plan(graph, plan).await?);
+        }
+        if fetched < PAGE_SIZE {
+            break;
+        }
+    }
+
+    let plan_tasks: HashMap<Uuid, HashSet<Uuid>> = plans
+        .iter()
+        .map(|p| (p.plan.id, p.tasks.iter().map(|t| t.task.id).collect()))
+        .collect();
+    let mut milestones = Vec::new();
+    for milestone in graph.list_project_milestones(project.id).await? {
+        let plan_ids = graph.get_milestone_plan_ids(milestone.id).await?;
+        // Tasks of linked plans come back through the plan link
+        let via_plans: HashSet<Uuid> = plan_ids
+            .iter()
+            .filter_map(|id| plan_tasks.get(id))
+            .flatten()
+            .copied()
+            .collect();
+        let task_ids = graph
+            .get_milestone_tasks(milestone.id)
+            .await?
+            .into_iter()
+            .map(|t| t.id)
+            .filter(|id| !via_plans.contains(id))
+            .collect();
+        milestones.push(MilestoneSnapshot {
+            milestone,
+            plan_ids,
+            task_ids,
+        });
+    }
+
+    let mut releases = Vec::new();
+    for release in graph.list_project_releases(project.id).await? {
+        let (task_ids, commit_hashes) = match graph.get_release_details(release.id).await? {
+            Some((_, tasks, commits)) => (
+                tasks.into_iter().map(|t| t.id).collect(),
+                commits.into_iter().map(|c| c.hash).collect(),
+            ),
+            None => (Vec::new(), Vec::new()),
+        };
+        releases.push(ReleaseSnapshot {
+            release,
+            task_ids,
+            commit_hashes,
+        });
+    }
+
+    let mut notes = Vec::new();
+    loop {
+        let filters = NoteFilters {
+            limit: Some(PAGE_SIZE as i64),
+            offset: Some(notes.len() as i64),
+            ..Default::default()
+        };
+        let (page, _) = graph.list_notes(Some(project.id), None, &filters).await?;
+        let fetched = page.len();
+        for mut note in page {
+            note.anchors = graph.get_note_anchors(note.id).await?;
+            notes.push(note);
+        }
+        if fetched < PAGE_SIZE {
+            break;
+        }
+    }
+
+    let search = SearchSnapshot {
+        code: search.list_code_for_project(&project.slug).await?,
+        decisions: search.list_decisions_for_project(&project.slug).await?,
+        notes: search.list_notes_for_project(&project.slug).await?,
+        docs: search.list_docs_for_project(&project.slug).await?,
+    };
+
+    let mut snapshot = Snapshot {
+        manifest: SnapshotManifest {
+            format_version: FORMAT_VERSION,
+            orchestrator_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: Utc::now(),
+            project_id: project.id,
+            project_slug: project.slug.clone(),
+            counts: SnapshotCounts::default(),
+        },
+        project: project.clone(),
+        plans,
+        milestones,
+        releases,
+        notes,
+        search,
+    };
+    snapshot.manifest.counts = snapshot.counts();
+    Ok(snapshot)
+}
+
+async fn capture_plan(graph: &dyn GraphStore, plan: PlanNode) -> Result<PlanSnapshot> {
+    let constraints = graph.get_plan_constraints(plan.id).await?;
+    let mut tasks = Vec::new();
+    for task in graph.get_plan_tasks(plan.id).await? {
+        let Some(details) = graph.get_task_with_full_details(task.id).await? else {
+            continue;
+        };
+        tasks.push(TaskSnapshot {
+            task: details.task,
+            steps: details.steps,
+            decisions: details.decisions,
+            depends_on: details.depends_on,
+        });
+    }
+    Ok(PlanSnapshot {
+        plan,
+        constraints,
+        tasks,
+    })
+}
+
+// ============================================================================
+// Archive
+// ============================================================================
+
+/// Write `snapshot` as a gzipped tar archive.
+pub fn write_archive<W: Write>(snapshot: &Snapshot, writer: W) -> Result<()> {
+    let encoder = flate2::write::GzEncoder::new(writer, flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    let mtime = snapshot.manifest.created_at.timestamp().max(0) as u64;
+
+    let mut append = |path: &str, json: Vec<u8>| -> Result<()> {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(json.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(mtime);
+        header.set_cksum();
+        builder.append_data(&mut header, path, json.as_slice())?;
+        Ok(())
+    };
+    append(
+        MANIFEST_ENTRY,
+        serde_json::to_vec_pretty(&snapshot.manifest)?,
+    )?;
+    append(PROJECT_ENTRY, serde_json::to_vec_pretty(&snapshot.project)?)?;
+    append(PLANS_ENTRY, serde_json::to_vec(&snapshot.plans)?)?;
+    append(MILESTONES_ENTRY, serde_json::to_vec(&snapshot.milestones)?)?;
+    append(RELEASES_ENTRY, serde_json::to_vec(&snapshot.releases)?)?;
+    append(NOTES_ENTRY, serde_json::to_vec(&snapshot.notes)?)?;
+    append(
+        SEARCH_CODE_ENTRY,
+        serde_json::to_vec(&snapshot.search.code)?,
+    )?;
+    append(
+        SEARCH_DECISIONS_ENTRY,
+        serde_json::to_vec(&snapshot.search.decisions)?,
+    )?;
+    append(
+        SEARCH_NOTES_ENTRY,
+        serde_json::to_vec(&snapshot.search.notes)?,
+    )?;
+    append(
+        SEARCH_DOCS_ENTRY,
+        serde_json::to_vec(&snapshot.search.docs)?,
+    )?;
+
+    builder.into_inner()?.finish()?.flush()?;
+    Ok(())
+}
+
+/// Read an archive written by [`write_archive`].
+pub fn read_archive<R: Read>(reader: R) -> Result<Snapshot> {
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(reader));
+    let mut entries = HashMap::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.to_string_lossy().into_owned();
+        let mut buf = Vec::new();
+        entry.read_to_end(&mut buf)?;
+        entries.insert(path, buf);
+    }
+
+    fn parse<T: DeserializeOwned>(entries: &HashMap<String, Vec<u8>>, path: &str) -> Result<T> {
+        let bytes = entries
+            .get(path)
+            .with_context(|| format!("Snapshot archive is missing {}", path))?;
+        serde_json::from_slice(bytes).with_context(|| format!("Invalid {} in snapshot", path))
+    }
+
+    let manifest: SnapshotManifest = parse(&entries, MANIFEST_ENTRY)?;
+    if manifest.format_version > FORMAT_VERSION {
+        bail!(
+            "Snapshot format v{} is newer than this orchestrator supports (v{}); upgrade first",
+            manifest.format_version,
+            FORMAT_VERSION
+        );
+    }
+    Ok(Snapshot {
+        project: parse(&entries, PROJECT_ENTRY)?,
+        plans: parse(&entries, PLANS_ENTRY)?,
+        milestones: parse(&entries, MILESTONES_ENTRY)?,
+        releases: parse(&entries, RELEASES_ENTRY)?,
+        notes: parse(&entries, NOTES_ENTRY)?,
+        search: SearchSnapshot {
+            code: parse(&entries, SEARCH_CODE_ENTRY)?,
+            decisions: parse(&entries, SEARCH_DECISIONS_ENTRY)?,
+            notes: parse(&entries, SEARCH_NOTES_ENTRY)?,
+            docs: parse(&entries, SEARCH_DOCS_ENTRY)?,
+        },
+        manifest,
+    })
+}
+
+// ============================================================================
+// Restore
+// ============================================================================
+
+/// Replace the project's knowledge with the content of `snapshot`.
+///
+/// A project that no longer exists is recreated; restoring over a different
+/// project that took its slug is refused.
+pub async fn restore(
+    graph: &dyn GraphStore,
+    search: &dyn SearchStore,
+    snapshot: &Snapshot,
+) -> Result<RestoreReport> {
+    let (project, project_created) = match graph.get_project(snapshot.project.id).await? {
+        Some(project) => (project, false),
+        None => {
+            if let Some(other) = graph.get_project_by_slug(&snapshot.project.slug).await? {
+                bail!(
+                    "Slug '{}' now belongs to another project ({}); delete or rename it first",
+                    other.slug,
+                    other.id
+                );
+            }
+            graph.create_project(&snapshot.project).await?;
+            (snapshot.project.clone(), true)
+        }
+    };
+
+    let current = capture(graph, search, &project).await?;
+    clear(graph, search, &current).await?;
+
+    for entry in &snapshot.plans {
+        graph.create_plan(&entry.plan).await?;
+        for constraint in &entry.constraints {
+            graph.create_constraint(entry.plan.id, constraint).await?;
+        }
+        for task in &entry.tasks {
+            graph.create_task(entry.plan.id, &task.task).await?;
+            for step in &task.steps {
+                graph.create_step(task.task.id, step).await?;
+            }
+            for decision in &task.decisions {
+                graph.create_decision(task.task.id, decision).await?;
+            }
+        }
+    }
+    // Dependencies once every task exists
+    for task in snapshot.plans.iter().flat_map(|p| &p.tasks) {
+        for depends_on in &task.depends_on {
+            graph.add_task_dependency(task.task.id, *depends_on).await?;
+        }
+    }
+
+    for entry in &snapshot.milestones {
+        graph.create_milestone(&entry.milestone).await?;
+        for plan_id in &entry.plan_ids {
+            graph
+                .link_plan_to_milestone(*plan_id, entry.milestone.id)
+                .await?;
+        }
+        for task_id in &entry.task_ids {
+            graph
+                .add_task_to_milestone(entry.milestone.id, *task_id)
+                .await?;
+        }
+    }
+    for entry in &snapshot.releases {
+        graph.create_release(&entry.release).await?;
+        for task_id in &entry.task_ids {
+            graph
+                .add_task_to_release(entry.release.id, *task_id)
+                .await?;
+        }
+        for hash in &entry.commit_hashes {
+            graph.add_commit_to_release(entry.release.id, hash).await?;
+        }
+    }
+
+    for note in &snapshot.notes {
+        let bare = Note {
+            anchors: Vec::new(),
+            ..note.clone()
+        };
+        graph.create_note(&bare).await?;
+        for anchor in &note.anchors {
+            graph
+                .link_note_to_entity(
+                    note.id,
+                    &anchor.entity_type,
+                    &anchor.entity_id,
+                    anchor.signature_hash.as_deref(),
+                    anchor.body_hash.as_deref(),
+                )
+                .await?;
+        }
+    }
+
+    let docs = &snapshot.search;
+    if !docs.code.is_empty() {
+        search.index_code_batch(&docs.code).await?;
+    }
+    for decision in &docs.decisions {
+        search.index_decision(decision).await?;
+    }
+    if !docs.notes.is_empty() {
+        search.index_notes_batch(&docs.notes).await?;
+    }
+    if !docs.docs.is_empty() {
+        search.index_doc_sections(&docs.docs).await?;
+    }
+
+    Ok(RestoreReport {
+        project_slug: project.slug,
+        project_created,
+        removed: current.counts(),
+        restored: snapshot.counts(),
+    })
+}
+
+/// Delete everything `current` lists (deleting a plan cascades to its
+/// tasks, steps, decisions and constraints).
+async fn clear(graph: &dyn GraphStore, search: &dyn SearchStore, current: &Snapshot) -> Result<()> {
+    for entry in &current.plans {
+        graph.delete_plan(entry.plan.id).await?;
+    }
+    for entry in &current.milestones {
+        graph.delete_milestone(entry.milestone.id).await?;
+    }
+    for entry in &current.releases {
+        graph.delete_release(entry.release.id).await?;
+    }
+    for note in &current.notes {
+        graph.delete_note(note.id).await?;
+    }
+
+    let slug = &current.project.slug;
+    search.delete_code_for_project(slug).await?;
+    search.delete_decisions_for_project(slug).await?;
+    search.delete_notes_for_project(slug).await?;
+    search.delete_docs_for_project(slug).await?;
+    Ok(())
+}
+
+// ============================================================================
+// Rendering
+// ============================================================================
+
+/// One line per entity kind, aligned.
+pub fn render_counts(counts: &SnapshotCounts) -> String {
+    let rows = [
+        ("plans", counts.plans),
+        ("tasks", counts.tasks),
+        ("steps", counts.steps),
+        ("decisions", counts.decisions),
+        ("constraints", counts.constraints),
+        ("milestones", counts.milestones),
+        ("releases", counts.releases),
+        ("notes", counts.notes),
+        ("search documents", counts.search_documents),
+    ];
+    rows.iter()
+        .map(|(label, n)| format!("  {:<18}{}\n", label, n))
+        .collect()
+}
+
+/// Default archive name: `<slug>-<UTC timestamp>.snapshot.tar.gz`.
+pub fn default_archive_name(slug: &str, at: DateTime<Utc>) -> String {
+    format!("{}-{}.snapshot.tar.gz", slug, at.format("%Y%m%d-%H%M%S"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meilisearch::mock::MockSearchStore;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::ConstraintType;
+    use crate::notes::{EntityType, NoteType};
+    use crate::test_helpers::{
+        test_constraint, test_decision, test_milestone, test_note, test_plan_for_project,
+        test_project, test_release, test_step, test_task_titled,
+    };
+
+    fn code_doc(path: &str, project: &ProjectNode) -> CodeDocument {
+        CodeDocument {
+            id: path.to_string(),
+            path: path.to_string(),
+            language: "rust".to_string(),
+            symbols: vec![],
+            docstrings: String::new(),
+            signatures: vec![],
+            imports: vec![],
+            project_id: project.id.to_string(),
+            project_slug: project.slug.clone(),
+        }
+    }
+
+    /// A project with one plan (two dependent tasks), a milestone, a release,
+    /// an anchored note and a code document.
+    async fn seeded() -> (MockGraphStore, MockSearchStore, ProjectNode) {
+        let graph = MockGraphStore::new();
+        let search = MockSearchStore::new();
+        let project = test_project();
+        graph.create_project(&project).await.unwrap();
+
+        let plan = test_plan_for_project(project.id);
+        graph.create_plan(&plan).await.unwrap();
+        graph
+            .create_constraint(
+                plan.id,
+                &test_constraint(ConstraintType::Performance, "Fast"),
+            )
+            .await
+            .unwrap();
+        let first = test_task_titled("First");
+        let second = test_task_titled("Second");
+        graph.create_task(plan.id, &first).await.unwrap();
+        graph.create_task(plan.id, &second).await.unwrap();
+        graph
+            .create_step(first.id, &test_step(0, "Write it"))
+            .await
+            .unwrap();
+        graph
+            .create_decision(first.id, &test_decision("Use tar", "Already a dep"))
+            .await
+            .unwrap();
+        graph
+            .add_task_dependency(second.id, first.id)
+            .await
+            .unwrap();
+
+        let milestone = test_milestone(project.id, "v1");
+        graph.create_milestone(&milestone).await.unwrap();
+        graph
+            .link_plan_to_milestone(plan.id, milestone.id)
+            .await
+            .unwrap();
+        let release = test_release(project.id, "1.0.0");
+        graph.create_release(&release).await.unwrap();
+        graph
+            .add_task_to_release(release.id, second.id)
+            .await
+            .unwrap();
+
+        let note = test_note(project.id, NoteType::Gotcha, "Mind the cascade");
+        graph.create_note(&note).await.unwrap();
+        graph
+            .link_note_to_entity(note.id, &EntityType::File, "src/lib.rs", None, None)
+            .await
+            .unwrap();
+
+        search
+            .index_code_batch(&[code_doc("src/lib.rs", &project)])
+            .await
+            .unwrap();
+        (graph, search, project)
+    }
+
+    #[tokio::test]
+    async fn test_archive_round_trip() {
+        let (graph, search, project) = seeded().await;
+        let snapshot = capture(&graph, &search, &project).await.unwrap();
+        assert_eq!(
+            snapshot.manifest.counts,
+            SnapshotCounts {
+                plans: 1,
+                tasks: 2,
+                steps: 1,
+                decisions: 1,
+                constraints: 1,
+                milestones: 1,
+                releases: 1,
+                notes: 1,
+                search_documents: 1,
+            }
+        );
+        // The plan link covers its tasks
+        assert_eq!(snapshot.milestones[0].plan_ids.len(), 1);
+        assert!(snapshot.milestones[0].task_ids.is_empty());
+        assert_eq!(snapshot.notes[0].anchors.len(), 1);
+
+        let mut bytes = Vec::new();
+        write_archive(&snapshot, &mut bytes).unwrap();
+        let read = read_archive(bytes.as_slice()).unwrap();
+        assert_eq!(read.manifest.project_id, project.id);
+        assert_eq!(read.counts(), snapshot.manifest.counts);
+        let task = |title: &str| {
+            read.plans[0]
+                .tasks
+                .iter()
+                .find(|t| t.task.title.as_deref() == Some(title))
+                .unwrap()
+        };
+        assert_eq!(task("Second").depends_on, vec![task("First").task.id]);
+    }
+
+    #[tokio::test]
+    async fn test_read_archive_rejects_newer_format() {
+        let (graph, search, project) = seeded().await;
+        let mut snapshot = capture(&graph, &search, &project).await.unwrap();
+        snapshot.manifest.format_version = FORMAT_VERSION + 1;
+        let mut bytes = Vec::new();
+        write_archive(&snapshot, &mut bytes).unwrap();
+        let err = read_archive(bytes.as_slice()).unwrap_err();
+        assert!(err.to_string().contains("newer"));
+    }
+
+    #[tokio::test]
+    async fn test_restore_undoes_later_changes() {
+        let (graph, search, project) = seeded().await;
+        let snapshot = capture(&graph, &search, &project).await.unwrap();
+        let plan_id = snapshot.plans[0].plan.id;
+        let note_id = snapshot.notes[0].id;
+
+        // A bad run: the plan and the note are gone, a stray plan appears
+        graph.delete_plan(plan_id).await.unwrap();
+        graph.delete_note(note_id).await.unwrap();
+        search.delete_code_for_project(&project.slug).await.unwrap();
+        let stray = test_plan_for_project(project.id);
+        graph.create_plan(&stray).await.unwrap();
+
+        let report = restore(&graph, &search, &snapshot).await.unwrap();
+        assert!(!report.project_created);
+        assert_eq!(report.removed.plans, 1);
+        assert_eq!(report.restored, snapshot.manifest.counts);
+
+        let after = capture(&graph, &search, &project).await.unwrap();
+        assert_eq!(after.counts(), snapshot.manifest.counts);
+        assert_eq!(after.plans[0].plan.id, plan_id);
+        assert!(graph.get_plan(stray.id).await.unwrap().is_none());
+        let decision = after.plans[0]
+            .tasks
+            .iter()
+            .flat_map(|t| &t.decisions)
+            .next();
+        assert_eq!(decision.unwrap().rationale, "Already a dep");
+        assert_eq!(after.notes[0].id, note_id);
+        assert_eq!(after.notes[0].anchors[0].entity_id, "src/lib.rs");
+        assert_eq!(after.milestones[0].plan_ids, vec![plan_id]);
+        assert_eq!(after.search.code[0].path, "src/lib.rs");
+    }
+
+    #[tokio::test]
+    async fn test_restore_recreates_deleted_project() {
+        let (graph, search, project) = seeded().await;
+        let snapshot = capture(&graph, &search, &project).await.unwrap();
+
+        let fresh = MockGraphStore::new();
+        let report = restore(&fresh, &MockSearchStore::new(), &snapshot)
+            .await
+            .unwrap();
+        assert!(report.project_created);
+        assert_eq!(report.removed, SnapshotCounts::default());
+        assert!(fresh.get_project(project.id).await.unwrap().is_some());
+
+        // Another project holding the slug is left alone
+        let taken = MockGraphStore::new();
+        taken
+            .create_project(&ProjectNode {
+                id: Uuid::new_v4(),
+                ..project.clone()
+            })
+            .await
+            .unwrap();
+        assert!(restore(&taken, &MockSearchStore::new(), &snapshot)
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_default_archive_name() {
+        let at = DateTime::parse_from_rfc3339("2026-03-01T09:05:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            default_archive_name("widgets", at),
+            "widgets-20260301-090500.snapshot.tar.gz"
+        );
+    }
+}
@@ -0,0 +1,82 @@
+//! Shell completion scripts and man pages for the `orchestrator` CLI.
+//!
+//! Both are generated from the clap command tree by `clap_complete` and
+//! `clap_mangen`, so new subcommands and flags show up without touching this
+//! module. Man pages are one per subcommand (`orchestrator-serve.1`,
+//! `orchestrator-config-validate.1`…).
+
+use clap::Command;
+use std::path::Path;
+
+pub use clap_complete::Shell;
+
+/// Generate the completion script for `shell`.
+pub fn completion_script(shell: Shell, cmd: &mut Command) -> String {
+    let name = cmd.get_name().to_string();
+    let mut script = Vec::new();
+    clap_complete::generate(shell, cmd, name, &mut script);
+    String::from_utf8_lossy(&script).into_owned()
+}
+
+/// Render the man page of the root command, `orchestrator(1)`.
+pub fn man_page(cmd: Command) -> std::io::Result<String> {
+    let mut page = Vec::new();
+    clap_mangen::Man::new(cmd).render(&mut page)?;
+    Ok(String::from_utf8_lossy(&page).into_owned())
+}
+
+/// Write the man pages of the command and all its subcommands into `dir`
+/// (created if missing).
+pub fn write_man_pages(cmd: Command, dir: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    clap_mangen::generate_to(cmd, dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Arg;
+
+    fn cli() -> Command {
+        Command::new("orchestrator")
+            .about("AI Agent Orchestrator Server")
+            .subcommand(
+                Command::new("serve")
+                    .about("Start the orchestrator server")
+                    .arg(Arg::new("port").short('p').long("port")),
+            )
+            .subcommand(
+                Command::new("config")
+                    .about("Inspect the configuration file")
+                    .subcommand(Command::new("validate").about("Strictly validate config.yaml")),
+            )
+    }
+
+    #[test]
+    fn test_completion_scripts_cover_subcommands() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let script = completion_script(shell, &mut cli());
+            assert!(script.contains("serve"), "{:?}", shell);
+            assert!(script.contains("validate"), "{:?}", shell);
+            assert!(script.contains("port"), "{:?}", shell);
+        }
+    }
+
+    #[test]
+    fn test_man_pages_one_per_command() {
+        let root = man_page(cli()).unwrap();
+        assert!(root.contains(".TH orchestrator 1"));
+        assert!(root.contains("orchestrator\\-serve(1)"), "{}", root);
+
+        let dir = tempfile::tempdir().unwrap();
+        write_man_pages(cli(), dir.path()).unwrap();
+        for page in [
+            "orchestrator.1",
+            "orchestrator-serve.1",
+            "orchestrator-config.1",
+            "orchestrator-config-validate.1",
+        ] {
+            assert!(dir.path().join(page).is_file(), "{}", page);
+        }
+    }
+}
@@ -0,0 +1,307 @@
+//! Secret indirection for `config.yaml`.
+//!
+//! Secrets should not have to live in plaintext in the config file. Before
+//! the YAML is deserialized, two rewrites are applied to the raw document:
+//!
+//! 1. **Environment interpolation** — every string scalar may reference
+//!    environment variables with `${VAR}` or `${VAR:-default}`. A literal
+//!    `${` is written `$${`. Referencing an unset variable without a default
+//!    is an error, so a missing secret never silently becomes an empty string.
+//! 2. **Secret files** — `neo4j.password_file`, `meilisearch.key_file`,
+//!    `auth.jwt_secret_file`, `embeddings.api_key_file`,
+//!    `anthropic.api_key_file` and `sentry.token_file` are read from disk (Docker / Kubernetes secret
+//!    mounts) and replace their plain counterpart. Relative paths are resolved
+//!    against the directory containing `config.yaml`; a single trailing
+//!    newline is stripped.
+//!
+//! The env-var side follows the same convention: `NEO4J_PASSWORD_FILE` and
+//! `MEILISEARCH_KEY_FILE` are honoured when `NEO4J_PASSWORD` /
+//! `MEILISEARCH_KEY` are not set (see [`env_secret`]).
+
+use std::path::{Path, PathBuf};
+
+/// `(section, file key, target key)` — the file key is replaced by the
+/// contents of the file it points to, stored under the target key.
+pub const SECRET_FILE_KEYS: &[(&str, &str, &str)] = &[
+    ("neo4j", "password_file", "password"),
+    ("meilisearch", "key_file", "key"),
+    ("auth", "jwt_secret_file", "jwt_secret"),
+    ("embeddings", "api_key_file", "api_key"),
+    ("anthropic", "api_key_file", "api_key"),
+    ("sentry", "token_file", "token"),
+];
+
+/// A failure while resolving secrets, tied to the dotted config path that
+/// caused it (e.g. `neo4j.password_file`).
+#[derive(Debug, thiserror::Error)]
+#[error("{path}: {message}")]
+pub struct SecretError {
+    pub path: String,
+    pub message: String,
+}
+
+/// Apply env interpolation and secret-file resolution to a raw YAML document,
+/// using the process environment.
+pub fn resolve(value: &mut serde_yaml::Value, base_dir: Option<&Path>) -> Result<(), SecretError> {
+    resolve_with(value, base_dir, |name| std::env::var(name).ok())
+}
+
+/// Same as [`resolve`] with an injectable environment lookup.
+pub fn resolve_with(
+    value: &mut serde_yaml::Value,
+    base_dir: Option<&Path>,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<(), SecretError> {
+    interpolate_value(value, "", &lookup)?;
+    resolve_secret_files(value, base_dir)
+}
+
+/// Read a secret from `NAME`, or from the file named by `NAME_FILE`.
+///
+/// Returns `Ok(None)` when neither is set. An unreadable `NAME_FILE` is an
+/// error rather than a silent fallback to the YAML value.
+pub fn env_secret(name: &str) -> anyhow::Result<Option<String>> {
+    if let Ok(value) = std::env::var(name) {
+        return Ok(Some(value));
+    }
+    let file_var = format!("{}_FILE", name);
+    match std::env::var(&file_var) {
+        Ok(path) => read_secret_file(Path::new(&path))
+            .map(Some)
+            .map_err(|e| anyhow::anyhow!("{}: {}", file_var, e)),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Expand `${VAR}` / `${VAR:-default}` references in a single string.
+pub fn interpolate(
+    input: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> Result<String, String> {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(pos) = rest.find('$') {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+        if let Some(escaped) = tail.strip_prefix("$${") {
+            out.push_str("${");
+            rest = escaped;
+        } else if let Some(body) = tail.strip_prefix("${") {
+            let end = body
+                .find('}')
+                .ok_or_else(|| format!("unterminated `${{` in `{}`", input))?;
+            let expr = &body[..end];
+            let (name, default) = match expr.split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (expr, None),
+            };
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(format!("invalid variable name `{}`", name));
+            }
+            // `:-` follows shell semantics: the default also covers empty values
+            let value = match default {
+                Some(default) => lookup(name)
+                    .filter(|v| !v.is_empty())
+                    .unwrap_or_else(|| default.to_string()),
+                None => lookup(name)
+                    .ok_or_else(|| format!("environment variable `{}` is not set", name))?,
+            };
+            out.push_str(&value);
+            rest = &body[end + 1..];
+        } else {
+            out.push('$');
+            rest = &tail[1..];
+        }
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn interpolate_value(
+    value: &mut serde_yaml::Value,
+    path: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> Result<(), SecretError> {
+    match value {
+        serde_yaml::Value::String(s) if s.contains('$') => {
+            *s = interpolate(s, lookup).map_err(|message| SecretError {
+                path: path.to_string(),
+                message,
+            })?;
+        }
+        serde_yaml::Value::Mapping(map) => {
+            for (key, child) in map.iter_mut() {
+                let key = key.as_str().unwrap_or_default();
+                let child_path = if path.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                interpolate_value(child, &child_path, lookup)?;
+            }
+        }
+        serde_yaml::Value::Sequence(seq) => {
+            for (i, child) in seq.iter_mut().enumerate() {
+                interpolate_value(child, &format!("{}[{}]", path, i), lookup)?;
+            }
+        }
+        serde_yaml::Value::Tagged(tagged) => {
+            interpolate_value(&mut tagged.value, path, lookup)?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn resolve_secret_files(
+    value: &mut serde_yaml::Value,
+    base_dir: Option<&Path>,
+) -> Result<(), SecretError> {
+    for (section, file_key, target_key) in SECRET_FILE_KEYS {
+        let Some(map) = value.get_mut(*section).and_then(|v| v.as_mapping_mut()) else {
+            continue;
+        };
+        let Some(file_value) = map.remove(*file_key) else {
+            continue;
+        };
+        let path = format!("{}.{}", section, file_key);
+        let Some(file) = file_value.as_str() else {
+            return Err(SecretError {
+                path,
+                message: "expected a file path".to_string(),
+            });
+        };
+        let file = match base_dir {
+            Some(dir) if Path::new(file).is_relative() => dir.join(file),
+            _ => PathBuf::from(file),
+        };
+        let secret = read_secret_file(&file).map_err(|message| SecretError {
+            path: path.clone(),
+            message,
+        })?;
+        map.insert(
+            serde_yaml::Value::String(target_key.to_string()),
+            serde_yaml::Value::String(secret),
+        );
+    }
+    Ok(())
+}
+
+/// Read a secret file, stripping the trailing newline most editors and
+/// `kubectl create secret` leave behind.
+fn read_secret_file(path: &Path) -> Result<String, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+    let secret = contents
+        .strip_suffix("\r\n")
+        .or_else(|| contents.strip_suffix('\n'))
+        .unwrap_or(&contents);
+    if secret.is_empty() {
+        return Err(format!("{} is empty", path.display()));
+    }
+    Ok(secret.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_interpolate_plain_and_default() {
+        let lookup = env(&[("HOST", "db.internal")]);
+        assert_eq!(
+            interpolate("bolt://${HOST}:${PORT:-7687}", &lookup).unwrap(),
+            "bolt://db.internal:7687"
+        );
+    }
+
+    #[test]
+    fn test_interpolate_escape_and_lone_dollar() {
+        let lookup = env(&[]);
+        assert_eq!(interpolate("a$$${b}", &lookup).unwrap(), "a$${b}");
+        assert_eq!(interpolate("$${X}", &lookup).unwrap(), "${X}");
+        assert_eq!(interpolate("cost: $5", &lookup).unwrap(), "cost: $5");
+    }
+
+    #[test]
+    fn test_interpolate_missing_var_is_error() {
+        let err = interpolate("${NOPE}", &env(&[])).unwrap_err();
+        assert!(err.contains("NOPE"));
+        assert!(interpolate("${UNCLOSED", &env(&[])).is_err());
+        assert!(interpolate("${bad-name}", &env(&[])).is_err());
+    }
+
+    #[test]
+    fn test_resolve_interpolates_nested_strings() {
+        let mut value: serde_yaml::Value = serde_yaml::from_str(
+            "neo4j:\n  password: ${NEO4J_PW}\nauth:\n  additional_origins:\n    - ${ORIGIN}\n",
+        )
+        .unwrap();
+        let lookup = env(&[("NEO4J_PW", "s3cret"), ("ORIGIN", "https://po.dev")]);
+        resolve_with(&mut value, None, lookup).unwrap();
+        assert_eq!(value["neo4j"]["password"].as_str(), Some("s3cret"));
+        assert_eq!(
+            value["auth"]["additional_origins"][0].as_str(),
+            Some("https://po.dev")
+        );
+    }
+
+    #[test]
+    fn test_resolve_error_carries_path() {
+        let mut value: serde_yaml::Value =
+            serde_yaml::from_str("meilisearch:\n  key: ${MISSING}\n").unwrap();
+        let err = resolve_with(&mut value, None, env(&[])).unwrap_err();
+        assert_eq!(err.path, "meilisearch.key");
+    }
+
+    #[test]
+    fn test_resolve_secret_file_relative_to_base_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("neo4j_pw"), "from-file\n").unwrap();
+        let mut value: serde_yaml::Value =
+            serde_yaml::from_str("neo4j:\n  password: plaintext\n  password_file: neo4j_pw\n")
+                .unwrap();
+        resolve_with(&mut value, Some(dir.path()), env(&[])).unwrap();
+        assert_eq!(value["neo4j"]["password"].as_str(), Some("from-file"));
+        assert!(value["neo4j"].get("password_file").is_none());
+    }
+
+    #[test]
+    fn test_resolve_secret_file_path_is_interpolated() {
+        let dir = tempfile::tempdir().unwrap();
+        let secret = dir.path().join("jwt");
+        std::fs::write(&secret, "a-very-long-jwt-secret-from-a-k8s-mount").unwrap();
+        let mut value: serde_yaml::Value =
+            serde_yaml::from_str("auth:\n  jwt_secret_file: ${SECRETS_DIR}/jwt\n").unwrap();
+        let lookup = env(&[("SECRETS_DIR", dir.path().to_str().unwrap())]);
+        resolve_with(&mut value, None, lookup).unwrap();
+        assert_eq!(
+            value["auth"]["jwt_secret"].as_str(),
+            Some("a-very-long-jwt-secret-from-a-k8s-mount")
+        );
+    }
+
+    #[test]
+    fn test_resolve_missing_or_empty_secret_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut value: serde_yaml::Value =
+            serde_yaml::from_str("meilisearch:\n  key_file: absent\n").unwrap();
+        let err = resolve_with(&mut value, Some(dir.path()), env(&[])).unwrap_err();
+        assert_eq!(err.path, "meilisearch.key_file");
+
+        std::fs::write(dir.path().join("empty"), "\n").unwrap();
+        let mut value: serde_yaml::Value =
+            serde_yaml::from_str("meilisearch:\n  key_file: empty\n").unwrap();
+        let err = resolve_with(&mut value, Some(dir.path()), env(&[])).unwrap_err();
+        assert!(err.message.contains("empty"));
+    }
+}
@@ -0,0 +1,944 @@
+//! Strict validation of `config.yaml` (`orchestrator config validate`).
+//!
+//! [`Config::from_yaml_and_env`](crate::Config::from_yaml_and_env) is lenient
+//! by design: a parse error logs a warning and the server falls back to
+//! defaults, and unknown keys are silently ignored. That keeps the server
+//! bootable but hides typos. This module re-reads the file strictly and
+//! reports, with YAML line numbers:
+//!
+//! - **errors** — YAML syntax / type errors (from serde) and invalid values
+//!   (e.g. a `jwt_secret` shorter than 32 characters)
+//! - **warnings** — unknown keys (likely typos) and risky values such as the
+//!   default database passwords
+
+use crate::{config_secrets, YamlConfig};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Known keys per section. Sections listed in [`OPAQUE_SECTIONS`] are only
+/// checked at the top level because their schema lives in other crates.
+const KNOWN_KEYS: &[(&str, &[&str])] = &[
+    (
+        "",
+        &[
+            "setup_completed",
+            "infra_mode",
+            "server",
+            "neo4j",
+            "meilisearch",
+            "nats",
+            "ipc",
+            "chat",
+            "embeddings",
+            "auth",
+            "registry",
+            "anthropic",
+            "runner",
+            "neural_routing",
+            "mcp_federation",
+            "logging",
+            "update",
+            "note_federation",
+            "telemetry",
+            "notifications",
+            "lsp",
+            "grammars",
+            "analytics",
+            "sentry",
+        ],
+    ),
+    (
+        "server",
+        &[
+            "host",
+            "port",
+            "workspace_path",
+            "serve_frontend",
+            "frontend_path",
+            "public_url",
+            "read_only",
+        ],
+    ),
+    (
+        "neo4j",
+        &[
+            "uri",
+            "user",
+            "password",
+            "password_file",
+            "query_timeout_ms",
+            "slow_query_ms",
+        ],
+    ),
+    (
+        "meilisearch",
+        &["url", "key", "key_file", "index_queue_path"],
+    ),
+    ("nats", &["url", "jetstream", "consumer"]),
+    ("ipc", &["enabled", "path"]),
+    (
+        "chat",
+        &[
+            "default_model",
+            "max_sessions",
+            "session_timeout_secs",
+            "max_turns",
+            "permissions",
+            "process_path",
+            "claude_cli_path",
+            "auto_update_cli",
+            "auto_update_app",
+            "sandbox",
+            "limits",
+            "model_routing",
+        ],
+    ),
+    (
+        "chat.permissions",
+        &["mode", "allowed_tools", "disallowed_tools"],
+    ),
+    (
+        "chat.sandbox",
+        &[
+            "enabled",
+            "allowed_dirs",
+            "no_network",
+            "allow_unisolated_bash",
+            "memory_max_mb",
+            "cpu_max_percent",
+            "pids_max",
+        ],
+    ),
+    (
+        "chat.limits",
+        &[
+            "max_tool_calls",
+            "max_wall_clock_secs",
+            "max_file_writes",
+            "max_cost_usd",
+        ],
+    ),
+    ("chat.model_routing", &["rules", "projects"]),
+    (
+        "embeddings",
+        &[
+            "provider",
+            "fastembed_model",
+            "fastembed_cache_dir",
+            "url",
+            "model",
+            "api_key",
+            "api_key_file",
+            "dimensions",
+        ],
+    ),
+    (
+        "auth",
+        &[
+            "jwt_secret",
+            "jwt_secret_file",
+            "access_token_expiry_secs",
+            "jwt_expiry_secs",
+            "refresh_token_expiry_secs",
+            "allowed_email_domain",
+            "allowed_emails",
+            "frontend_url",
+            "additional_origins",
+            "allow_registration",
+            "root_account",
+            "oidc",
+            "google_client_id",
+            "google_client_secret",
+            "google_redirect_uri",
+        ],
+    ),
+    ("auth.root_account", &["email", "name", "password_hash"]),
+    (
+        "auth.oidc",
+        &[
+            "provider_key",
+            "discovery_url",
+            "auth_endpoint",
+            "token_endpoint",
+            "userinfo_endpoint",
+            "client_id",
+            "client_secret",
+            "redirect_uri",
+            "provider_name",
+            "scopes",
+            "extra_auth_params",
+        ],
+    ),
+    ("registry", &["remote_url"]),
+    ("anthropic", &["api_key", "api_key_file"]),
+    ("logging", &["format", "level", "targets"]),
+    ("update", &["channel", "public_key"]),
+    ("note_federation", &["interval_secs", "sources"]),
+    ("telemetry", &["enabled"]),
+    ("notifications", &["smtp", "slack", "rules"]),
+    (
+        "notifications.smtp",
+        &["host", "port", "username", "password", "from", "tls"],
+    ),
+    ("notifications.slack", &["webhook_url"]),
+    (
+        "lsp",
+        &["enabled", "servers", "timeout_secs", "max_references"],
+    ),
+    ("grammars", &["dir", "languages"]),
+    ("analytics", &["min_call_confidence"]),
+    (
+        "sentry",
+        &[
+            "url",
+            "token",
+            "token_file",
+            "organization",
+            "projects",
+            "interval_secs",
+        ],
+    ),
+];
+
+/// Sections whose nested keys are not checked.
+const OPAQUE_SECTIONS: &[&str] = &[
+    "runner",
+    "neural_routing",
+    "mcp_federation",
+    "auth.oidc.extra_auth_params",
+    "logging.targets",
+    "lsp.servers",
+];
+
+/// Default credentials shipped in `config.yaml.example` / docker-compose.
+const DEFAULT_NEO4J_PASSWORD: &str = "orchestrator123";
+const DEFAULT_MEILI_KEY: &str = "orchestrator-meili-key-change-me";
+
+/// Severity of a validation issue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A single validation finding.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigIssue {
+    pub severity: Severity,
+    /// Dotted key path (e.g. `auth.jwt_secret`), empty for document-level errors.
+    pub path: String,
+    /// 1-based line number in the YAML file, when known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// Result of validating one config file.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ValidationReport {
+    pub issues: Vec<ConfigIssue>,
+}
+
+impl ValidationReport {
+    pub fn errors(&self) -> impl Iterator<Item = &ConfigIssue> {
+        self.issues.iter().filter(|i| i.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &ConfigIssue> {
+        self.issues
+            .iter()
+            .filter(|i| i.severity == Severity::Warning)
+    }
+
+    /// True when there are no errors (and, in strict mode, no warnings).
+    pub fn is_valid(&self, strict: bool) -> bool {
+        self.errors().next().is_none() && (!strict || self.warnings().next().is_none())
+    }
+
+    /// Render as `file:line: severity: path: message` lines.
+    pub fn render(&self, file: &str) -> String {
+        let mut out = String::new();
+        for issue in &self.issues {
+            let location = match issue.line {
+                Some(line) => format!("{}:{}", file, line),
+                None => file.to_string(),
+            };
+            let severity = match issue.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            };
+            if issue.path.is_empty() {
+                out.push_str(&format!("{}: {}: {}\n", location, severity, issue.message));
+            } else {
+                out.push_str(&format!(
+                    "{}: {}: {}: {}\n",
+                    location, severity, issue.path, issue.message
+                ));
+            }
+        }
+        out
+    }
+
+    fn push(&mut self, severity: Severity, path: &str, lines: &KeyLines, message: String) {
+        self.issues.push(ConfigIssue {
+            severity,
+            path: path.to_string(),
+            line: lines.get(path),
+            message,
+        });
+    }
+}
+
+/// Validate a config file on disk.
+pub fn validate_file(path: &Path) -> anyhow::Result<ValidationReport> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Cannot read {}: {}", path.display(), e))?;
+    Ok(validate(&contents, path.parent()))
+}
+
+/// Validate raw YAML config contents. Relative `*_file` secrets are
+/// resolved against the current directory.
+pub fn validate_yaml(contents: &str) -> ValidationReport {
+    validate(contents, None)
+}
+
+fn validate(contents: &str, base_dir: Option<&Path>) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    let lines = KeyLines::scan(contents);
+
+    // 1. Syntax — bail out early, nothing else is reliable on broken YAML
+    let raw: serde_yaml::Value = match serde_yaml::from_str(contents) {
+        Ok(v) => v,
+        Err(e) => {
+            report.issues.push(ConfigIssue {
+                severity: Severity::Error,
+                path: String::new(),
+                line: e.location().map(|l| l.line()),
+                message: format!("invalid YAML: {}", strip_location(&e.to_string())),
+            });
+            return report;
+        }
+    };
+    if raw.is_null() {
+        // Empty file is valid: every section falls back to defaults
+        return report;
+    }
+
+    // 2. Unknown keys
+    check_unknown_keys(&raw, "", &lines, &mut report);
+
+    // 3. Secrets — `${VAR}` interpolation and `*_file` indirections
+    let mut resolved = raw;
+    if let Err(e) = config_secrets::resolve(&mut resolved, base_dir) {
+        report.push(Severity::Error, &e.path, &lines, e.message);
+        return report;
+    }
+
+    // 4. Types — serde reports the first mismatch. Deserializing from a
+    // `Value` loses positions, so the line comes from re-parsing the text.
+    let config: YamlConfig = match serde_yaml::from_value(resolved) {
+        Ok(c) => c,
+        Err(e) => {
+            let line = serde_yaml::from_str::<YamlConfig>(contents)
+                .err()
+                .and_then(|e| e.location())
+                .map(|l| l.line());
+            report.issues.push(ConfigIssue {
+                severity: Severity::Error,
+                path: String::new(),
+                line,
+                message: strip_location(&e.to_string()),
+            });
+            return report;
+        }
+    };
+
+    // 5. Cross-field and value checks
+    check_values(&config, &lines, &mut report);
+    report
+}
+
+fn check_unknown_keys(
+    value: &serde_yaml::Value,
+    section: &str,
+    lines: &KeyLines,
+    report: &mut ValidationReport,
+) {
+    let Some(map) = value.as_mapping() else {
+        return;
+    };
+    if OPAQUE_SECTIONS.contains(&section) {
+        return;
+    }
+    let Some((_, known)) = KNOWN_KEYS.iter().find(|(s, _)| *s == section) else {
+        return;
+    };
+    for (key, child) in map {
+        let Some(key) = key.as_str() else {
+            continue;
+        };
+        let path = if section.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", section, key)
+        };
+        if known.contains(&key) {
+            check_unknown_keys(child, &path, lines, report);
+        } else {
+            let message = match closest_key(key, known) {
+                Some(suggestion) => {
+                    format!("unknown key (ignored) — did you mean `{}`?", suggestion)
+                }
+                None => "unknown key (ignored)".to_string(),
+            };
+            report.push(Severity::Warning, &path, lines, message);
+        }
+    }
+}
+
+fn check_values(config: &YamlConfig, lines: &KeyLines, report: &mut ValidationReport) {
+    if config.server.port == 0 {
+        report.push(
+            Severity::Warning,
+            "server.port",
+            lines,
+            "port 0 binds a random port".to_string(),
+        );
+    }
+
+    let uri = &config.neo4j.uri;
+    if ![
+        "bolt://",
+        "bolt+s://",
+        "bolt+ssc://",
+        "neo4j://",
+        "neo4j+s://",
+        "neo4j+ssc://",
+    ]
+    .iter()
+    .any(|scheme| uri.starts_with(scheme))
+    {
+        report.push(
+            Severity::Error,
+            "neo4j.uri",
+            lines,
+            format!("`{}` must start with bolt:// or neo4j://", uri),
+        );
+    }
+    if config.neo4j.password == DEFAULT_NEO4J_PASSWORD && lines.get("neo4j.password").is_some() {
+        report.push(
+            Severity::Warning,
+            "neo4j.password",
+            lines,
+            "default password — change it outside local development".to_string(),
+        );
+    }
+
+    let url = &config.meilisearch.url;
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        report.push(
+            Severity::Error,
+            "meilisearch.url",
+            lines,
+            format!("`{}` must start with http:// or https://", url),
+        );
+    }
+    if config.meilisearch.key == DEFAULT_MEILI_KEY && lines.get("meilisearch.key").is_some() {
+        report.push(
+            Severity::Warning,
+            "meilisearch.key",
+            lines,
+            "default master key — change it outside local development".to_string(),
+        );
+    }
+
+    if let Some(ref nats) = config.nats.url {
+        if !nats.starts_with("nats://") && !nats.starts_with("tls://") {
+            report.push(
+                Severity::Error,
+                "nats.url",
+                lines,
+                format!("`{}` must start with nats:// or tls://", nats),
+            );
+        }
+    }
+
+    if let Some(ref perms) = config.chat.permissions {
+        if !crate::chat::config::PermissionConfig::is_valid_mode(&perms.mode) {
+            report.push(
+                Severity::Error,
+                "chat.permissions.mode",
+                lines,
+                format!(
+                    "`{}` is not one of {}",
+                    perms.mode,
+                    crate::chat::config::PermissionConfig::valid_modes().join(", ")
+                ),
+            );
+        }
+    }
+
+    match config.embeddings.provider.as_deref() {
+        None | Some("local") | Some("disabled") => {}
+        Some("http") => {
+            if config.embeddings.url.is_none() {
+                report.push(
+                    Severity::Warning,
+                    "embeddings.url",
+                    lines,
+                    "provider is http but no url is set (defaults to local Ollama)".to_string(),
+                );
+            }
+        }
+        Some(other) => report.push(
+            Severity::Error,
+            "embeddings.provider",
+            lines,
+            format!("`{}` is not one of local, http, disabled", other),
+        ),
+    }
+
+    if let Some(directives) = config.logging.filter_directives() {
+        if let Err(e) = tracing_subscriber::EnvFilter::try_new(&directives) {
+            let path = if config.logging.targets.is_empty() {
+                "logging.level"
+            } else {
+                "logging.targets"
+            };
+            report.push(
+                Severity::Error,
+                path,
+                lines,
+                format!("invalid log filter `{}`: {}", directives, e),
+            );
+        }
+    }
+
+    let mut source_names = std::collections::HashSet::new();
+    for source in &config.note_federation.sources {
+        if !crate::notes::federation::is_valid_source_name(&source.name) {
+            report.push(
+                Severity::Error,
+                "note_federation.sources",
+                lines,
+                format!("source name `{}` must match [a-z0-9_-]+", source.name),
+            );
+        } else if !source_names.insert(source.name.as_str()) {
+            report.push(
+                Severity::Error,
+                "note_federation.sources",
+                lines,
+                format!("duplicate source name `{}`", source.name),
+            );
+        }
+        if !source.url.starts_with("http://") && !source.url.starts_with("https://") {
+            report.push(
+                Severity::Error,
+                "note_federation.sources",
+                lines,
+                format!("`{}` must start with http:// or https://", source.url),
+            );
+        }
+    }
+
+    if let Err(e) = config.notifications.check() {
+        report.push(Severity::Error, "notifications.rules", lines, e);
+    }
+
+    if let Err(e) = config.lsp.check() {
+        report.push(Severity::Error, "lsp.servers", lines, e);
+    }
+
+    if let Err(e) = config.grammars.check() {
+        report.push(Severity::Error, "grammars.languages", lines, e);
+    }
+
+    if let Err(e) = config.analytics.check() {
+        report.push(Severity::Error, "analytics.min_call_confidence", lines, e);
+    }
+
+    if let Err(e) = config.sentry.check() {
+        report.push(Severity::Error, "sentry", lines, e);
+    }
+
+    if let Some(ref auth) = config.auth {
+        if auth.jwt_secret.len() < 32 {
+            report.push(
+                Severity::Error,
+                "auth.jwt_secret",
+                lines,
+                format!(
+                    "must be at least 32 characters (got {})",
+                    auth.jwt_secret.len()
+                ),
+            );
+        }
+        if auth.access_token_expiry_secs >= auth.refresh_token_expiry_secs {
+            report.push(
+                Severity::Warning,
+                "auth.access_token_expiry_secs",
+                lines,
+                "access tokens outlive refresh tokens".to_string(),
+            );
+        }
+        if let Some(ref oidc) = auth.oidc {
+            if oidc.discovery_url.is_none()
+                && (oidc.auth_endpoint.is_none() || oidc.token_endpoint.is_none())
+            {
+                report.push(
+                    Severity::Error,
+                    "auth.oidc",
+                    lines,
+                    "auth_endpoint and token_endpoint are required without discovery_url"
+                        .to_string(),
+                );
+            }
+        }
+        if auth.root_account.is_none() && !auth.has_oidc() {
+            report.push(
+                Severity::Warning,
+                "auth",
+                lines,
+                "no root_account or oidc provider — nobody can log in".to_string(),
+            );
+        }
+    }
+}
+
+/// Suggest a known key within edit distance 2 (typo detection).
+fn closest_key<'a>(key: &str, known: &[&'a str]) -> Option<&'a str> {
+    known
+        .iter()
+        .map(|k| (*k, edit_distance(key, k)))
+        .filter(|(_, d)| *d <= 2)
+        .min_by_key(|(_, d)| *d)
+        .map(|(k, _)| k)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b_chars.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b_chars.len() + 1];
+        for (j, cb) in b_chars.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+    prev[b_chars.len()]
+}
+
+/// serde_yaml appends "at line X column Y" — we report the line separately.
+fn strip_location(message: &str) -> String {
+    match message.find(" at line ") {
+        Some(idx) => message[..idx].to_string(),
+        None => message.to_string(),
+    }
+}
+
+/// Line numbers of mapping keys, indexed by dotted path.
+///
+/// A lightweight indentation scan — serde_yaml values carry no spans. Keys
+/// inside sequences are not indexed (no config field needs them).
+struct KeyLines {
+    lines: HashMap<String, usize>,
+}
+
+impl KeyLines {
+    fn scan(contents: &str) -> Self {
+        let mut lines = HashMap::new();
+        // (indent, key) of the enclosing mappings
+        let mut stack: Vec<(usize, String)> = Vec::new();
+
+        for (idx, line) in contents.lines().enumerate() {
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("- ") {
+                continue;
+            }
+            let indent = line.len() - trimmed.len();
+            let Some(colon) = trimmed.find(':') else {
+                continue;
+            };
+            let key = trimmed[..colon].trim().trim_matches('"').trim_matches('\'');
+            if key.is_empty() || key.contains(' ') {
+                continue;
+            }
+            while stack.last().is_some_and(|(i, _)| *i >= indent) {
+                stack.pop();
+            }
+            let mut path: Vec<&str> = stack.iter().map(|(_, k)| k.as_str()).collect();
+            path.push(key);
+            lines.entry(path.join(".")).or_insert(idx + 1);
+            stack.push((indent, key.to_string()));
+        }
+        Self { lines }
+    }
+
+    fn get(&self, path: &str) -> Option<usize> {
+        self.lines.get(path).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_config_has_no_issues() {
+        let yaml = r#"
+server:
+  port: 9090
+neo4j:
+  uri: bolt://db:7687
+  password: s3cret
+auth:
+  jwt_secret: "super-secret-key-min-32-characters!"
+  root_account:
+    email: admin@example.com
+    name: Admin
+    password_hash: "$2b$12$abc"
+"#;
+        let report = validate_yaml(yaml);
+        assert!(report.issues.is_empty(), "{:?}", report.issues);
+        assert!(report.is_valid(true));
+    }
+
+    #[test]
+    fn test_empty_file_is_valid() {
+        assert!(validate_yaml("").issues.is_empty());
+    }
+
+    #[test]
+    fn test_syntax_error_reports_line() {
+        let yaml = "server:\n  port: 8080\nneo4j:\n  uri: [unclosed\n";
+        let report = validate_yaml(yaml);
+        let err = report.errors().next().expect("syntax error");
+        assert!(err.line.is_some());
+        assert!(err.message.starts_with("invalid YAML"));
+        assert!(!report.is_valid(false));
+    }
+
+    #[test]
+    fn test_type_error_reports_line() {
+        let yaml = "server:\n  port: not-a-number\n";
+        let report = validate_yaml(yaml);
+        let err = report.errors().next().expect("type error");
+        assert_eq!(err.line, Some(2));
+        assert!(!err.message.contains("at line"));
+    }
+
+    #[test]
+    fn test_unknown_keys_warn_with_suggestion_and_line() {
+        let yaml = "server:\n  port: 8080\n  frontend_pth: ./dist\nneo4jj:\n  uri: bolt://x\n";
+        let report = validate_yaml(yaml);
+        let warnings: Vec<_> = report.warnings().collect();
+        assert_eq!(warnings.len(), 2, "{:?}", warnings);
+        assert_eq!(warnings[0].path, "server.frontend_pth");
+        assert_eq!(warnings[0].line, Some(3));
+        assert!(warnings[0].message.contains("`frontend_path`"));
+        assert_eq!(warnings[1].path, "neo4jj");
+        assert_eq!(warnings[1].line, Some(4));
+        assert!(report.is_valid(false));
+        assert!(!report.is_valid(true));
+    }
+
+    #[test]
+    fn test_opaque_sections_are_not_checked() {
+        let yaml = "runner:\n  anything_goes: 1\nneural_routing:\n  enabled: true\n";
+        let report = validate_yaml(yaml);
+        assert!(report.warnings().next().is_none(), "{:?}", report.issues);
+    }
+
+    #[test]
+    fn test_short_jwt_secret_is_an_error() {
+        let yaml = "auth:\n  jwt_secret: short\n  root_account:\n    email: a@b.c\n    name: A\n    password_hash: x\n";
+        let report = validate_yaml(yaml);
+        let err = report.errors().next().expect("jwt error");
+        assert_eq!(err.path, "auth.jwt_secret");
+        assert_eq!(err.line, Some(2));
+    }
+
+    #[test]
+    fn test_secret_files_are_resolved_relative_to_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        std::fs::write(
+            &path,
+            "neo4j:\n  password_file: secrets/neo4j\nmeilisearch:\n  key_file: secrets/meili\n",
+        )
+        .unwrap();
+        std::fs::create_dir(dir.path().join("secrets")).unwrap();
+        std::fs::write(dir.path().join("secrets/neo4j"), "pw\n").unwrap();
+
+        let report = validate_file(&path).unwrap();
+        let err = report.errors().next().expect("missing key file");
+        assert_eq!(err.path, "meilisearch.key_file");
+        assert_eq!(err.line, Some(4));
+
+        std::fs::write(dir.path().join("secrets/meili"), "key\n").unwrap();
+        let report = validate_file(&path).unwrap();
+        assert!(report.errors().next().is_none(), "{:?}", report.issues);
+    }
+
+    #[test]
+    fn test_unset_env_reference_is_an_error() {
+        let yaml = "neo4j:\n  password: ${PO_TEST_SURELY_UNSET_VAR}\n";
+        let report = validate_yaml(yaml);
+        let err = report.errors().next().expect("unset var");
+        assert_eq!(err.path, "neo4j.password");
+        assert_eq!(err.line, Some(2));
+        assert!(err.message.contains("PO_TEST_SURELY_UNSET_VAR"));
+    }
+
+    #[test]
+    fn test_cross_field_checks() {
+        let yaml = r#"
+neo4j:
+  uri: http://localhost:7474
+  password: orchestrator123
+chat:
+  permissions:
+    mode: yolo
+embeddings:
+  provider: magic
+auth:
+  jwt_secret: "super-secret-key-min-32-characters!"
+  oidc:
+    client_id: id
+    client_secret: secret
+    redirect_uri: http://localhost/cb
+"#;
+        let report = validate_yaml(yaml);
+        let error_paths: Vec<_> = report.errors().map(|i| i.path.as_str()).collect();
+        assert!(error_paths.contains(&"neo4j.uri"));
+        assert!(error_paths.contains(&"chat.permissions.mode"));
+        assert!(error_paths.contains(&"embeddings.provider"));
+        assert!(error_paths.contains(&"auth.oidc"));
+        let warning_paths: Vec<_> = report.warnings().map(|i| i.path.as_str()).collect();
+        assert!(warning_paths.contains(&"neo4j.password"));
+    }
+
+    #[test]
+    fn test_note_federation_sources() {
+        let yaml = r#"
+note_federation:
+  interval_secs: 600
+  sources:
+    - name: central
+      url: https://po-central.example.com
+      api_key: token
+    - name: central
+      url: https://po-other.example.com
+    - name: Org Brain
+      url: po-brain.example.com
+"#;
+        let report = validate_yaml(yaml);
+        let errors: Vec<_> = report.errors().map(|i| i.message.as_str()).collect();
+        assert_eq!(errors.len(), 3, "{:?}", errors);
+        assert!(errors[0].contains("duplicate source name `central`"));
+        assert!(errors[1].contains("`Org Brain`"));
+        assert!(errors[2].contains("po-brain.example.com"));
+        assert!(report.warnings().next().is_none(), "{:?}", report.issues);
+    }
+
+    #[test]
+    fn test_notifications() {
+        let yaml = r#"
+notifications:
+  slack:
+    webhook_url: https://hooks.slack.com/services/x
+    chanel: ops
+  rules:
+    - name: approved
+      event: plan_approved
+      channels: [email]
+"#;
+        let report = validate_yaml(yaml);
+        let errors: Vec<_> = report.errors().map(|i| i.message.as_str()).collect();
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].contains("email channel, which is not configured"));
+        let warning_paths: Vec<_> = report.warnings().map(|i| i.path.as_str()).collect();
+        assert_eq!(warning_paths, vec!["notifications.slack.chanel"]);
+    }
+
+    #[test]
+    fn test_lsp() {
+        let yaml = r#"
+lsp:
+  enabled: true
+  servers:
+    pyhton:
+      command: pyright-langserver
+"#;
+        let report = validate_yaml(yaml);
+        let errors: Vec<_> = report.errors().map(|i| i.path.as_str()).collect();
+        assert_eq!(errors, vec!["lsp.servers"]);
+        assert!(report.warnings().next().is_none(), "{:?}", report.issues);
+    }
+
+    #[test]
+    fn test_grammars() {
+        let yaml = r#"
+grammars:
+  dir: /opt/grammars
+  languages:
+    - name: elixir
+      wasm: tree-sitter-elixir.wasm
+      tags_query: elixir-tags.scm
+      extensions: [ex, rs]
+"#;
+        let report = validate_yaml(yaml);
+        let errors: Vec<_> = report.errors().map(|i| i.path.as_str()).collect();
+        assert_eq!(errors, vec!["grammars.languages"]);
+        assert!(report.warnings().next().is_none(), "{:?}", report.issues);
+    }
+
+    #[test]
+    fn test_analytics_min_call_confidence() {
+        let report = validate_yaml("analytics:\n  min_call_confidence: 0.5\n");
+        assert!(report.issues.is_empty(), "{:?}", report.issues);
+
+        let report = validate_yaml("analytics:\n  min_call_confidence: 1.5\n");
+        let errors: Vec<_> = report.errors().map(|i| i.path.as_str()).collect();
+        assert_eq!(errors, vec!["analytics.min_call_confidence"]);
+    }
+
+    #[test]
+    fn test_sentry() {
+        let yaml = r#"
+sentry:
+  organization: acme
+  projects:
+    - sentry_project: backend
+      project_slug: shop
+"#;
+        let report = validate_yaml(yaml);
+        let errors: Vec<_> = report.errors().map(|i| i.message.as_str()).collect();
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+        assert!(errors[0].contains("token"));
+
+        let report = validate_yaml(&format!("{}  token: secret\n", yaml));
+        assert!(report.issues.is_empty(), "{:?}", report.issues);
+    }
+
+    #[test]
+    fn test_render_format() {
+        let report = validate_yaml("server:\n  prt: 1\n");
+        let text = report.render("config.yaml");
+        assert_eq!(
+            text,
+            "config.yaml:2: warning: server.prt: unknown key (ignored) — did you mean `port`?\n"
+        );
+    }
+
+    #[test]
+    fn test_key_lines_nested_paths() {
+        let lines = KeyLines::scan("a:\n  b:\n    c: 1\n  d: 2\ne: 3\n");
+        assert_eq!(lines.get("a.b.c"), Some(3));
+        assert_eq!(lines.get("a.d"), Some(4));
+        assert_eq!(lines.get("e"), Some(5));
+    }
+}
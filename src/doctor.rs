@@ -0,0 +1,753 @@
+//! `orchestrator doctor` — end-to-end setup diagnostics.
+//!
+//! Runs a series of read-only checks against the local installation and the
+//! configured backends, then prints one line per check with an actionable fix
+//! for anything that is not healthy:
+//!
+//! - config.yaml is found and parses, auth secrets are long enough
+//! - Neo4j is reachable, its version supports vector indexes
+//! - Meilisearch is reachable and healthy
+//! - NATS is reachable (when configured)
+//! - Claude Code CLI is installed, the MCP server is registered
+//! - the server host and port are free (or already served by an orchestrator)
+//! - enough disk space is left for the workspace and model caches
+//!
+//! Unlike `AppState::new`, no schema or index is created — doctor never
+//! mutates the backends.
+
+use crate::Config;
+use serde::Serialize;
+use std::time::Duration;
+
+/// Timeout applied to every network probe.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Minimum Neo4j version with native vector indexes (`CREATE VECTOR INDEX`).
+const MIN_VECTOR_NEO4J: (u32, u32) = (5, 11);
+
+/// Free disk space below which a warning is emitted (1 GiB).
+const DISK_WARN_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Free disk space below which the check fails (200 MiB).
+const DISK_FAIL_BYTES: u64 = 200 * 1024 * 1024;
+
+/// Outcome of a single check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+    Skip,
+}
+
+impl CheckStatus {
+    fn symbol(self) -> &'static str {
+        match self {
+            CheckStatus::Pass => "✓",
+            CheckStatus::Warn => "!",
+            CheckStatus::Fail => "✗",
+            CheckStatus::Skip => "-",
+        }
+    }
+}
+
+/// Result of one diagnostic check.
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// Suggested fix when the check did not pass.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+impl CheckResult {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn skip(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Skip,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+}
+
+/// Full diagnostic report.
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorReport {
+    pub checks: Vec<CheckResult>,
+}
+
+impl DoctorReport {
+    /// True when no check failed (warnings are tolerated).
+    pub fn is_healthy(&self) -> bool {
+        !self.checks.iter().any(|c| c.status == CheckStatus::Fail)
+    }
+
+    fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+
+    /// Render the report as human-readable text.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for check in &self.checks {
+            out.push_str(&format!(
+                "  {} {:<14} {}\n",
+                check.status.symbol(),
+                check.name,
+                check.detail
+            ));
+            if let Some(ref fix) = check.fix {
+                out.push_str(&format!("    fix: {}\n", fix));
+            }
+        }
+        out.push_str(&format!(
+            "\n{} passed, {} warnings, {} failed, {} skipped\n",
+            self.count(CheckStatus::Pass),
+            self.count(CheckStatus::Warn),
+            self.count(CheckStatus::Fail),
+            self.count(CheckStatus::Skip),
+        ));
+        out
+    }
+}
+
+/// Run all checks against the given configuration.
+pub async fn run_doctor(config: &Config) -> DoctorReport {
+    let mut checks = vec![check_config(config)];
+    checks.extend(check_auth(config));
+    checks.extend(check_neo4j(config).await);
+    checks.push(check_meilisearch(config).await);
+    checks.push(check_nats(config).await);
+    checks.push(check_claude_cli(config.chat_claude_cli_path.as_deref()).await);
+    checks.push(check_mcp_registration(config));
+    checks.push(check_port(&config.server_host, config.server_port).await);
+    checks.push(check_disk_space(&crate::expand_tilde(
+        &config.workspace_path,
+    )));
+    DoctorReport { checks }
+}
+
+// ============================================================================
+// Individual checks
+// ============================================================================
+
+fn check_config(config: &Config) -> CheckResult {
+    const NAME: &str = "config";
+    let Some(ref path) = config.config_yaml_path else {
+        return CheckResult::warn(
+            NAME,
+            "no config.yaml found, using env vars and defaults",
+            "copy config.yaml.example to ~/.config/project-orchestrator/config.yaml (or pass --config)",
+        );
+    };
+    let contents = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) => {
+            return CheckResult::fail(
+                NAME,
+                format!("cannot read {}: {}", path.display(), e),
+                "check the file exists and is readable by this user",
+            )
+        }
+    };
+    let report = crate::config_validation::validate_yaml(&contents);
+    let errors = report.errors().count();
+    let warnings = report.warnings().count();
+    if let Some(first) = report.errors().next() {
+        let line = first.line.map(|l| format!(":{}", l)).unwrap_or_default();
+        CheckResult::fail(
+            NAME,
+            format!(
+                "{}{} is invalid ({} error(s)): {}",
+                path.display(),
+                line,
+                errors,
+                first.message
+            ),
+            "run `orchestrator config validate` — the server silently falls back to defaults on parse errors",
+        )
+    } else if warnings > 0 {
+        CheckResult::warn(
+            NAME,
+            format!("{} parsed with {} warning(s)", path.display(), warnings),
+            "run `orchestrator config validate` for details (unknown keys are ignored)",
+        )
+    } else if !config.setup_completed {
+        CheckResult::warn(
+            NAME,
+            format!("{} parsed, but setup_completed is false", path.display()),
+            "finish the setup wizard or set `setup_completed: true`",
+        )
+    } else {
+        CheckResult::pass(NAME, format!("{} is valid", path.display()))
+    }
+}
+
+fn check_auth(config: &Config) -> Option<CheckResult> {
+    const NAME: &str = "auth";
+    let auth = config.auth_config.as_ref()?;
+    Some(if auth.jwt_secret.len() < 32 {
+        CheckResult::fail(
+            NAME,
+            format!(
+                "auth.jwt_secret is {} characters (minimum 32)",
+                auth.jwt_secret.len()
+            ),
+            "generate one with `openssl rand -hex 32`",
+        )
+    } else if !auth.has_password_auth() && !auth.has_oidc() {
+        CheckResult::warn(
+            NAME,
+            "auth section present but no root_account or oidc provider",
+            "add auth.root_account or auth.oidc so users can log in",
+        )
+    } else {
+        CheckResult::pass(NAME, "jwt secret and login provider configured")
+    })
+}
+
+async fn check_neo4j(config: &Config) -> Vec<CheckResult> {
+    const NAME: &str = "neo4j";
+    const VECTOR: &str = "vector index";
+    let fix = "start it with `docker compose up -d neo4j` and check neo4j.uri/user/password";
+
+    let connect = neo4rs::Graph::new(
+        &config.neo4j_uri,
+        &config.neo4j_user,
+        &config.neo4j_password,
+    );
+    let graph = match tokio::time::timeout(PROBE_TIMEOUT, connect).await {
+        Ok(Ok(g)) => g,
+        Ok(Err(e)) => {
+            return vec![
+                CheckResult::fail(
+                    NAME,
+                    format!("cannot connect to {}: {}", config.neo4j_uri, e),
+                    fix,
+                ),
+                CheckResult::skip(VECTOR, "neo4j unreachable"),
+            ]
+        }
+        Err(_) => {
+            return vec![
+                CheckResult::fail(
+                    NAME,
+                    format!("timeout connecting to {}", config.neo4j_uri),
+                    fix,
+                ),
+                CheckResult::skip(VECTOR, "neo4j unreachable"),
+            ]
+        }
+    };
+
+    let q = neo4rs::query(
+        "CALL dbms.components() YIELD name, versions, edition \
+         RETURN name, versions[0] AS version, edition",
+    );
+    let probe = async {
+        let mut result = graph.execute(q).await?;
+        let row = result
+            .next()
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("dbms.components() returned no rows"))?;
+        let version: String = row.get("version")?;
+        let edition: String = row.get("edition").unwrap_or_default();
+        anyhow::Ok((version, edition))
+    };
+    let (version, edition) = match tokio::time::timeout(PROBE_TIMEOUT, probe).await {
+        Ok(Ok(v)) => v,
+        Ok(Err(e)) => {
+            return vec![
+                CheckResult::fail(
+                    NAME,
+                    format!("connected but query failed: {}", e),
+                    "check neo4j.user/password — authentication errors surface on first query",
+                ),
+                CheckResult::skip(VECTOR, "neo4j query failed"),
+            ]
+        }
+        Err(_) => {
+            return vec![
+                CheckResult::fail(NAME, "query timed out", fix),
+                CheckResult::skip(VECTOR, "neo4j query timed out"),
+            ]
+        }
+    };
+
+    let neo4j = CheckResult::pass(
+        NAME,
+        format!("{} — Neo4j {} ({})", config.neo4j_uri, version, edition),
+    );
+    let vector = if supports_vector_indexes(&version) {
+        CheckResult::pass(VECTOR, format!("supported by Neo4j {}", version))
+    } else {
+        CheckResult::warn(
+            VECTOR,
+            format!(
+                "Neo4j {} has no native vector indexes (needs {}.{}+)",
+                version, MIN_VECTOR_NEO4J.0, MIN_VECTOR_NEO4J.1
+            ),
+            "upgrade Neo4j — semantic note/code search is unavailable without vector indexes",
+        )
+    };
+    vec![neo4j, vector]
+}
+
+async fn check_meilisearch(config: &Config) -> CheckResult {
+    const NAME: &str = "meilisearch";
+    let fix = "start it with `docker compose up -d meilisearch` and check meilisearch.url/key";
+    let client = match reqwest::Client::builder().timeout(PROBE_TIMEOUT).build() {
+        Ok(c) => c,
+        Err(e) => return CheckResult::fail(NAME, e.to_string(), fix),
+    };
+    let base = config.meilisearch_url.trim_end_matches('/');
+
+    let health = client.get(format!("{}/health", base)).send().await;
+    match health {
+        Ok(resp) if resp.status().is_success() => {}
+        Ok(resp) => {
+            return CheckResult::fail(
+                NAME,
+                format!("{} answered {} on /health", base, resp.status()),
+                fix,
+            )
+        }
+        Err(e) => return CheckResult::fail(NAME, format!("cannot reach {}: {}", base, e), fix),
+    }
+
+    // /version requires the API key — a 401/403 here means a wrong key
+    let version = client
+        .get(format!("{}/version", base))
+        .bearer_auth(&config.meilisearch_key)
+        .send()
+        .await;
+    match version {
+        Ok(resp) if resp.status().is_success() => {
+            let pkg = resp
+                .json::<serde_json::Value>()
+                .await
+                .ok()
+                .and_then(|v| {
+                    v.get("pkgVersion")
+                        .and_then(|p| p.as_str())
+                        .map(String::from)
+                })
+                .unwrap_or_else(|| "unknown".to_string());
+            CheckResult::pass(NAME, format!("{} — Meilisearch {}", base, pkg))
+        }
+        Ok(resp) if resp.status().as_u16() == 401 || resp.status().as_u16() == 403 => {
+            CheckResult::fail(
+                NAME,
+                format!("{} rejected the API key ({})", base, resp.status()),
+                "set meilisearch.key (or MEILISEARCH_KEY) to the instance master key",
+            )
+        }
+        Ok(resp) => CheckResult::warn(
+            NAME,
+            format!("healthy, but /version answered {}", resp.status()),
+            "check the Meilisearch version (v1.x expected)",
+        ),
+        Err(e) => CheckResult::warn(NAME, format!("healthy, but /version failed: {}", e), fix),
+    }
+}
+
+async fn check_nats(config: &Config) -> CheckResult {
+    const NAME: &str = "nats";
+    let Some(ref url) = config.nats_url else {
+        return CheckResult::skip(NAME, "not configured (single-process event bus)");
+    };
+    match tokio::time::timeout(PROBE_TIMEOUT, async_nats::connect(url.as_str())).await {
+        Ok(Ok(client)) => {
+            let info = client.server_info();
+            CheckResult::pass(NAME, format!("{} — NATS {}", url, info.version))
+        }
+        Ok(Err(e)) => CheckResult::fail(
+            NAME,
+            format!("cannot connect to {}: {}", url, e),
+            "start NATS or remove nats.url — events will not sync across processes",
+        ),
+        Err(_) => CheckResult::fail(
+            NAME,
+            format!("timeout connecting to {}", url),
+            "check that NATS listens on the configured host/port",
+        ),
+    }
+}
+
+async fn check_claude_cli(pinned: Option<&str>) -> CheckResult {
+    const NAME: &str = "claude cli";
+    let status = crate::chat::cli_version::check_cli_status(pinned).await;
+    if !status.installed {
+        return match pinned {
+            Some(path) => CheckResult::fail(
+                NAME,
+                format!("pinned Claude Code CLI {} not found", path),
+                "fix chat.claude_cli_path / CLAUDE_CLI_PATH or remove it to use the CLI on PATH",
+            ),
+            None => CheckResult::warn(
+                NAME,
+                "Claude Code CLI not found — chat and plan runner are disabled",
+                "install it with `npm install -g @anthropic-ai/claude-code`",
+            ),
+        };
+    }
+    let supported = status
+        .installed_version
+        .as_deref()
+        .and_then(nexus_claude::SemVer::parse)
+        .is_none_or(|v| crate::chat::cli_version::is_supported_version(&v));
+    if !supported {
+        return CheckResult::fail(
+            NAME,
+            format!(
+                "{} at {} is older than the minimum supported version {}",
+                status.installed_version.unwrap_or_default(),
+                status.cli_path.unwrap_or_default(),
+                crate::chat::cli_version::MIN_SUPPORTED_CLI_VERSION
+            ),
+            "upgrade with `claude update`",
+        );
+    }
+    let version = status
+        .installed_version
+        .unwrap_or_else(|| "unknown version".to_string());
+    let path = status.cli_path.unwrap_or_default();
+    if status.update_available && !status.is_local_build {
+        CheckResult::warn(
+            NAME,
+            format!(
+                "{} at {} (latest: {})",
+                version,
+                path,
+                status.latest_version.unwrap_or_default()
+            ),
+            "upgrade with `claude update`",
+        )
+    } else {
+        CheckResult::pass(NAME, format!("{} at {}", version, path))
+    }
+}
+
+fn check_mcp_registration(config: &Config) -> CheckResult {
+    use crate::setup_claude::ConfigStatus;
+    const NAME: &str = "mcp";
+    // The registration `orchestrator setup-claude` would make
+    let expected = crate::setup_claude::SetupConfig {
+        mcp_server_path: crate::chat::ChatConfig::detect_mcp_server_path_public(),
+        server_port: config.server_port,
+        jwt_secret: config.auth_config.as_ref().map(|a| a.jwt_secret.clone()),
+    };
+    match crate::setup_claude::registration_status(&expected) {
+        Ok(ConfigStatus::UpToDate) => CheckResult::pass(
+            NAME,
+            format!(
+                "registered in Claude Code ({})",
+                expected.mcp_server_path.display()
+            ),
+        ),
+        Ok(ConfigStatus::Stale) => CheckResult::fail(
+            NAME,
+            format!(
+                "registered, but not with {} and this server's URL and secret",
+                expected.mcp_server_path.display()
+            ),
+            "run `orchestrator setup-claude` to update the registration",
+        ),
+        Ok(ConfigStatus::Missing) => CheckResult::warn(
+            NAME,
+            "project-orchestrator is not registered as an MCP server",
+            "run `orchestrator setup-claude`",
+        ),
+        Err(e) => CheckResult::warn(
+            NAME,
+            format!("cannot read Claude Code config: {}", e),
+            "run `orchestrator setup-claude`",
+        ),
+    }
+}
+
+async fn check_port(host: &str, port: u16) -> CheckResult {
+    const NAME: &str = "port";
+    let port = if port == 0 { 8080 } else { port };
+    let addr = format!("{}:{}", url_host(host), port);
+    match std::net::TcpListener::bind((host, port)) {
+        Ok(_) => CheckResult::pass(NAME, format!("{} is available", addr)),
+        Err(_) => {
+            // Busy — tell apart a running orchestrator from a foreign process
+            let url = format!("http://{}:{}/health", url_host(probe_host(host)), port);
+            let ours = match reqwest::Client::builder().timeout(PROBE_TIMEOUT).build() {
+                Ok(client) => match client.get(&url).send().await {
+                    Ok(resp) => resp
+                        .json::<serde_json::Value>()
+                        .await
+                        .is_ok_and(|body| is_orchestrator_health(&body)),
+                    Err(_) => false,
+                },
+                Err(_) => false,
+            };
+            if ours {
+                CheckResult::pass(
+                    NAME,
+                    format!("{} is served by a running orchestrator", addr),
+                )
+            } else {
+                CheckResult::fail(
+                    NAME,
+                    format!("{} is in use by another process", addr),
+                    "stop that process or pick another port with `serve --port` / server.port",
+                )
+            }
+        }
+    }
+}
+
+/// Host to reach a server listening on `host`: the loopback address when it
+/// listens on every interface.
+fn probe_host(host: &str) -> &str {
+    match host.parse::<std::net::IpAddr>() {
+        Ok(ip) if ip.is_unspecified() && ip.is_ipv6() => "::1",
+        Ok(ip) if ip.is_unspecified() => "127.0.0.1",
+        _ => host,
+    }
+}
+
+/// `host` as written in a URL (IPv6 addresses in brackets)
+fn url_host(host: &str) -> String {
+    if host.parse::<std::net::Ipv6Addr>().is_ok() {
+        format!("[{}]", host)
+    } else {
+        host.to_string()
+    }
+}
+
+/// Whether a `/health` body comes from an orchestrator, whatever its status.
+fn is_orchestrator_health(body: &serde_json::Value) -> bool {
+    body["service"] == crate::api::handlers::SERVICE_NAME
+}
+
+fn check_disk_space(path: &str) -> CheckResult {
+    const NAME: &str = "disk";
+    match available_bytes(path) {
+        Some(free) => evaluate_disk_space(path, free),
+        None => CheckResult::skip(NAME, format!("cannot determine free space for {}", path)),
+    }
+}
+
+fn evaluate_disk_space(path: &str, free: u64) -> CheckResult {
+    const NAME: &str = "disk";
+    let detail = format!("{} free at {}", format_bytes(free), path);
+    if free < DISK_FAIL_BYTES {
+        CheckResult::fail(
+            NAME,
+            detail,
+            "free disk space — Neo4j, Meilisearch and embedding models need room to write",
+        )
+    } else if free < DISK_WARN_BYTES {
+        CheckResult::warn(NAME, detail, "free some disk space (less than 1 GiB left)")
+    } else {
+        CheckResult::pass(NAME, detail)
+    }
+}
+
+#[cfg(unix)]
+fn available_bytes(path: &str) -> Option<u64> {
+    let c_path = std::ffi::CString::new(path).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: c_path is a valid NUL-terminated string and stat is a valid out-pointer
+    let rc = unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) };
+    if rc != 0 {
+        return None;
+    }
+    // Field widths differ across platforms (u32 on macOS, u64 on Linux)
+    #[allow(clippy::unnecessary_cast)]
+    let free = stat.f_bavail as u64 * stat.f_frsize as u64;
+    Some(free)
+}
+
+#[cfg(not(unix))]
+fn available_bytes(_path: &str) -> Option<u64> {
+    None
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Whether a Neo4j version string (e.g. "5.26.0", "2025.01.0") supports
+/// native vector indexes.
+fn supports_vector_indexes(version: &str) -> bool {
+    let mut parts = version.split('.').map(|p| {
+        p.chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect::<String>()
+            .parse::<u32>()
+            .unwrap_or(0)
+    });
+    let major = parts.next().unwrap_or(0);
+    let minor = parts.next().unwrap_or(0);
+    (major, minor) >= MIN_VECTOR_NEO4J
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+    const MIB: f64 = 1024.0 * 1024.0;
+    let b = bytes as f64;
+    if b >= GIB {
+        format!("{:.1} GiB", b / GIB)
+    } else {
+        format!("{:.0} MiB", b / MIB)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supports_vector_indexes() {
+        assert!(supports_vector_indexes("5.11.0"));
+        assert!(supports_vector_indexes("5.26.1"));
+        assert!(supports_vector_indexes("2025.01.0"));
+        assert!(!supports_vector_indexes("5.10.0"));
+        assert!(!supports_vector_indexes("4.4.30"));
+        assert!(!supports_vector_indexes("garbage"));
+    }
+
+    #[test]
+    fn test_evaluate_disk_space_thresholds() {
+        assert_eq!(
+            evaluate_disk_space("/", 10 * DISK_WARN_BYTES).status,
+            CheckStatus::Pass
+        );
+        assert_eq!(
+            evaluate_disk_space("/", DISK_WARN_BYTES - 1).status,
+            CheckStatus::Warn
+        );
+        let fail = evaluate_disk_space("/", DISK_FAIL_BYTES - 1);
+        assert_eq!(fail.status, CheckStatus::Fail);
+        assert!(fail.fix.is_some());
+    }
+
+    #[test]
+    fn test_probe_host() {
+        assert_eq!(probe_host("0.0.0.0"), "127.0.0.1");
+        assert_eq!(probe_host("::"), "::1");
+        assert_eq!(probe_host("192.168.1.10"), "192.168.1.10");
+        assert_eq!(probe_host("localhost"), "localhost");
+        assert_eq!(url_host(probe_host("::")), "[::1]");
+        assert_eq!(url_host("127.0.0.1"), "127.0.0.1");
+    }
+
+    #[test]
+    fn test_is_orchestrator_health() {
+        // Unhealthy orchestrators (503) are recognized too
+        assert!(is_orchestrator_health(&serde_json::json!({
+            "service": "project-orchestrator",
+            "status": "unhealthy",
+            "version": "1.0.0"
+        })));
+        assert!(!is_orchestrator_health(
+            &serde_json::json!({"status": "ok"})
+        ));
+        assert!(!is_orchestrator_health(&serde_json::json!("ok")));
+    }
+
+    #[tokio::test]
+    async fn test_check_port_binds_configured_host() {
+        // Busy on the loopback, with a healthy service that is not ours
+        let listener = std::net::TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            use std::io::{Read, Write};
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = stream.read(&mut [0; 1024]);
+            let body = r#"{"status":"ok"}"#;
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+        });
+        let busy = check_port("127.0.0.1", port).await;
+        assert_eq!(busy.status, CheckStatus::Fail);
+        assert!(busy.detail.contains(&format!("127.0.0.1:{}", port)));
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GiB");
+        assert_eq!(format_bytes(512 * 1024 * 1024), "512 MiB");
+    }
+
+    #[test]
+    fn test_report_health_ignores_warnings() {
+        let report = DoctorReport {
+            checks: vec![
+                CheckResult::pass("a", "ok"),
+                CheckResult::warn("b", "meh", "fix b"),
+                CheckResult::skip("c", "n/a"),
+            ],
+        };
+        assert!(report.is_healthy());
+        let text = report.render();
+        assert!(text.contains("fix: fix b"));
+        assert!(text.contains("1 passed, 1 warnings, 0 failed, 1 skipped"));
+
+        let failing = DoctorReport {
+            checks: vec![CheckResult::fail("a", "down", "start it")],
+        };
+        assert!(!failing.is_healthy());
+    }
+
+    #[test]
+    fn test_check_config_without_file_warns() {
+        let mut config = Config::from_env().unwrap();
+        config.config_yaml_path = None;
+        let result = check_config(&config);
+        assert_eq!(result.status, CheckStatus::Warn);
+    }
+
+    #[test]
+    fn test_check_config_reports_parse_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        std::fs::write(&path, "server:\n  port: [not a port\n").unwrap();
+        let mut config = Config::from_env().unwrap();
+        config.config_yaml_path = Some(path);
+        let result = check_config(&config);
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.detail.contains("invalid"));
+    }
+}
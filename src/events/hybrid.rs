@@ -4,10 +4,12 @@
 //! `NatsEmitter` for cross-instance event distribution.
 //!
 //! When NATS is not configured, the emitter works in local-only mode
-//! with zero overhead — no connection attempts, no errors.
+//! with zero overhead — no connection attempts, no errors. Single-machine
+//! setups can use the `IpcEmitter` socket bridge instead of NATS.
 
 use super::bus::EventBus;
 use super::graph::GraphEvent;
+use super::ipc::IpcEmitter;
 use super::nats::NatsEmitter;
 use super::types::{CrudEvent, EventEmitter};
 use std::collections::{HashSet, VecDeque};
@@ -22,6 +24,7 @@ const GRAPH_BUS_CAPACITY: usize = 512;
 ///
 /// - **Local bus**: always active, used by WebSocket handlers for intra-process delivery
 /// - **NATS**: optional, used for inter-process sync (dev + desktop environments)
+/// - **IPC**: optional NATS-free alternative for processes on the same machine
 ///
 /// Implements `EventEmitter` so it can be injected as `Arc<dyn EventEmitter>`
 /// into PlanManager, NoteManager, Orchestrator, etc. without any API change.
@@ -33,6 +36,7 @@ pub struct HybridEmitter {
     /// filtering (by layer) in the WebSocket handler.
     graph_sender: broadcast::Sender<GraphEvent>,
     nats: Option<Arc<NatsEmitter>>,
+    ipc: Option<Arc<IpcEmitter>>,
 }
 
 impl HybridEmitter {
@@ -45,6 +49,7 @@ impl HybridEmitter {
             local_bus,
             graph_sender,
             nats: None,
+            ipc: None,
         }
     }
 
@@ -57,6 +62,20 @@ impl HybridEmitter {
             local_bus,
             graph_sender,
             nats: Some(nats_emitter),
+            ipc: None,
+        }
+    }
+
+    /// Create a HybridEmitter with local broadcast and the local IPC bridge.
+    ///
+    /// Events are emitted to both channels, like [`HybridEmitter::with_nats`].
+    pub fn with_ipc(local_bus: Arc<EventBus>, ipc_emitter: Arc<IpcEmitter>) -> Self {
+        let (graph_sender, _) = broadcast::channel(GRAPH_BUS_CAPACITY);
+        Self {
+            local_bus,
+            graph_sender,
+            nats: None,
+            ipc: Some(ipc_emitter),
         }
     }
 
@@ -89,6 +108,11 @@ impl HybridEmitter {
         self.nats.as_ref()
     }
 
+    /// Whether the local IPC bridge is configured.
+    pub fn has_ipc(&self) -> bool {
+        self.ipc.is_some()
+    }
+
     /// Subscribe to the graph event broadcast channel.
     ///
     /// Used by WebSocket handlers to receive graph mutation events
@@ -133,6 +157,10 @@ impl HybridEmitter {
     /// need to subscribe to the local bus — they no longer need their own
     /// NATS subscriptions for CRUD events.
     ///
+    /// With JetStream enabled, events are read through the durable consumer of
+    /// this instance and acknowledged once injected, so events published while
+    /// the instance was down are replayed when it comes back.
+    ///
     /// **Deduplication**: events that originated locally (and were already
     /// published to NATS by `HybridEmitter::emit`) are detected by fingerprint
     /// (`timestamp:entity_id`) and not re-injected, preventing duplicates.
@@ -150,6 +178,40 @@ impl HybridEmitter {
         let local_bus = self.local_bus.clone();
 
         tokio::spawn(async move {
+            use futures::StreamExt;
+
+            let mut recent = RecentEvents::default();
+
+            if nats.uses_jetstream() {
+                let mut messages = match nats.consume_crud_events().await {
+                    Ok(messages) => messages,
+                    Err(e) => {
+                        warn!("Failed to start NATS→local bridge: {}", e);
+                        return;
+                    }
+                };
+
+                info!("NATS→local bridge started: JetStream consumer → local broadcast");
+
+                while let Some(msg) = messages.next().await {
+                    let msg = match msg {
+                        Ok(msg) => msg,
+                        Err(e) => {
+                            warn!("NATS→local bridge: JetStream delivery failed: {}", e);
+                            continue;
+                        }
+                    };
+                    // Acked even when undecodable: redelivery would fail the same way
+                    bridge_event(&local_bus, &mut recent, &msg.payload);
+                    if let Err(e) = msg.ack().await {
+                        warn!("NATS→local bridge: failed to ack JetStream message: {}", e);
+                    }
+                }
+
+                warn!("NATS→local bridge: JetStream consumer closed");
+                return;
+            }
+
             let mut subscriber = match nats.subscribe_crud_events().await {
                 Ok(sub) => sub,
                 Err(e) => {
@@ -160,57 +222,114 @@ impl HybridEmitter {
 
             info!("NATS→local bridge started: events.crud → local broadcast");
 
-            // Bounded dedup window to avoid re-injecting events that originated locally
-            const BRIDGE_DEDUP_WINDOW: usize = 256;
-            let mut seen: VecDeque<String> = VecDeque::with_capacity(BRIDGE_DEDUP_WINDOW);
-            let mut seen_set: HashSet<String> = HashSet::with_capacity(BRIDGE_DEDUP_WINDOW);
-
-            use futures::StreamExt;
             while let Some(msg) = subscriber.next().await {
-                match serde_json::from_slice::<CrudEvent>(&msg.payload) {
-                    Ok(event) => {
-                        let fp = format!("{}:{}", event.timestamp, event.entity_id);
+                bridge_event(&local_bus, &mut recent, &msg.payload);
+            }
 
-                        // Skip if we already saw this event (local origin → NATS → back)
-                        if seen_set.contains(&fp) {
-                            continue;
-                        }
-                        seen_set.insert(fp.clone());
-                        seen.push_back(fp);
-                        if seen.len() > BRIDGE_DEDUP_WINDOW {
-                            if let Some(old) = seen.pop_front() {
-                                seen_set.remove(&old);
-                            }
-                        }
+            warn!("NATS→local bridge: subscriber closed");
+        });
+    }
+
+    /// Start the IPC→local bridge: re-injects the events received from the
+    /// other processes of this machine into the local broadcast bus.
+    ///
+    /// The IPC bridge never echoes a process's own events back, so unlike the
+    /// NATS bridge no deduplication is needed.
+    ///
+    /// No-op if IPC is not configured.
+    pub fn start_ipc_bridge(&self) {
+        let Some(ipc) = &self.ipc else {
+            return;
+        };
 
-                        // Inject into local bus only (NOT HybridEmitter::emit to avoid NATS loop)
+        let mut incoming = ipc.subscribe_crud_events();
+        let local_bus = self.local_bus.clone();
+
+        tokio::spawn(async move {
+            info!("IPC→local bridge started: local socket → local broadcast");
+            loop {
+                match incoming.recv().await {
+                    Ok(event) => {
                         debug!(
                             entity_type = ?event.entity_type,
                             action = ?event.action,
                             entity_id = %event.entity_id,
-                            "NATS→local bridge: injecting remote event"
+                            "IPC→local bridge: injecting remote event"
                         );
                         local_bus.emit(event);
                     }
-                    Err(e) => {
-                        warn!("NATS→local bridge: failed to deserialize CrudEvent: {}", e);
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("IPC→local bridge lagging, {} events dropped", skipped);
                     }
+                    Err(broadcast::error::RecvError::Closed) => break,
                 }
             }
-
-            warn!("NATS→local bridge: subscriber closed");
+            warn!("IPC→local bridge: channel closed");
         });
     }
 }
 
+/// Bounded dedup window to avoid re-injecting events that originated locally
+const BRIDGE_DEDUP_WINDOW: usize = 256;
+
+/// Fingerprints (`timestamp:entity_id`) of the events the bridge saw last.
+#[derive(Default)]
+struct RecentEvents {
+    order: VecDeque<String>,
+    seen: HashSet<String>,
+}
+
+impl RecentEvents {
+    /// Record an event, returning `false` if it is already in the window.
+    fn insert(&mut self, event: &CrudEvent) -> bool {
+        let fp = format!("{}:{}", event.timestamp, event.entity_id);
+        if !self.seen.insert(fp.clone()) {
+            return false;
+        }
+        self.order.push_back(fp);
+        if self.order.len() > BRIDGE_DEDUP_WINDOW {
+            if let Some(old) = self.order.pop_front() {
+                self.seen.remove(&old);
+            }
+        }
+        true
+    }
+}
+
+/// Decode a CrudEvent received from NATS and inject it into the local bus.
+fn bridge_event(local_bus: &EventBus, recent: &mut RecentEvents, payload: &[u8]) {
+    match serde_json::from_slice::<CrudEvent>(payload) {
+        Ok(event) => {
+            // Skip if we already saw this event (local origin → NATS → back)
+            if !recent.insert(&event) {
+                return;
+            }
+
+            // Inject into local bus only (NOT HybridEmitter::emit to avoid NATS loop)
+            debug!(
+                entity_type = ?event.entity_type,
+                action = ?event.action,
+                entity_id = %event.entity_id,
+                "NATS→local bridge: injecting remote event"
+            );
+            local_bus.emit(event);
+        }
+        Err(e) => {
+            warn!("NATS→local bridge: failed to deserialize CrudEvent: {}", e);
+        }
+    }
+}
+
 impl EventEmitter for HybridEmitter {
     fn emit(&self, event: CrudEvent) {
         // 1. Always emit to local broadcast (intra-process)
         self.local_bus.emit(event.clone());
 
-        // 2. Emit to NATS if configured (inter-process)
+        // 2. Emit to NATS or the IPC bridge if configured (inter-process)
         if let Some(nats) = &self.nats {
             nats.emit(event);
+        } else if let Some(ipc) = &self.ipc {
+            ipc.emit(event);
         } else {
             debug!("HybridEmitter: NATS not configured, local-only mode");
         }
@@ -331,6 +450,37 @@ mod tests {
         assert!(Arc::ptr_eq(hybrid.local_bus(), &bus));
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_ipc_bridge_injects_remote_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.sock");
+
+        let server = HybridEmitter::with_ipc(
+            Arc::new(EventBus::default()),
+            Arc::new(IpcEmitter::start(&path)),
+        );
+        server.start_ipc_bridge();
+        let mut rx = server.subscribe();
+        let desktop = HybridEmitter::with_ipc(
+            Arc::new(EventBus::default()),
+            Arc::new(IpcEmitter::start(&path)),
+        );
+        assert!(desktop.has_ipc() && !desktop.has_nats());
+
+        // The processes connect in the background: retry until bridged
+        for _ in 0..50 {
+            desktop.emit_created(EntityType::Plan, "plan-1", serde_json::Value::Null, None);
+            let received =
+                tokio::time::timeout(std::time::Duration::from_millis(100), rx.recv()).await;
+            if let Ok(Ok(event)) = received {
+                assert_eq!(event.entity_id, "plan-1");
+                return;
+            }
+        }
+        panic!("event never bridged over IPC");
+    }
+
     // ================================================================
     // Graph event bus tests
     // ================================================================
@@ -433,4 +583,37 @@ mod tests {
         let event = rx.try_recv().unwrap();
         assert_eq!(event.event_type, GraphEventType::CommunityChanged);
     }
+
+    #[test]
+    fn test_bridge_event_skips_duplicates_and_garbage() {
+        let bus = EventBus::default();
+        let mut rx = bus.subscribe();
+        let mut recent = RecentEvents::default();
+
+        let event = CrudEvent::new(EntityType::Task, CrudAction::Updated, "task-1");
+        let payload = serde_json::to_vec(&event).unwrap();
+
+        bridge_event(&bus, &mut recent, &payload);
+        // Same event delivered again (redelivery or local echo)
+        bridge_event(&bus, &mut recent, &payload);
+        bridge_event(&bus, &mut recent, b"not json");
+
+        assert_eq!(rx.try_recv().unwrap().entity_id, "task-1");
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_recent_events_window_is_bounded() {
+        let mut recent = RecentEvents::default();
+        let first = CrudEvent::new(EntityType::Note, CrudAction::Created, "note-0");
+        assert!(recent.insert(&first));
+        for i in 1..=BRIDGE_DEDUP_WINDOW {
+            let event = CrudEvent::new(EntityType::Note, CrudAction::Created, format!("note-{i}"));
+            assert!(recent.insert(&event));
+        }
+
+        assert_eq!(recent.order.len(), BRIDGE_DEDUP_WINDOW);
+        // The oldest fingerprint fell out of the window
+        assert!(recent.insert(&first));
+    }
 }
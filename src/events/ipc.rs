@@ -0,0 +1,480 @@
+//! Local IPC event transport: NATS-free inter-process sync on one machine
+//!
+//! Syncs CrudEvents between the orchestrator processes of a single machine
+//! (e.g. the desktop app and a dev server) over a Unix domain socket, or a
+//! named pipe on Windows, so desktop installs don't need a NATS server.
+//!
+//! The first process to start listens on the socket and relays every event
+//! to the other connected processes; the others connect to it. When the
+//! listening process exits, the remaining ones reconnect and one of them
+//! takes over. Events travel as newline-delimited JSON `CrudEvent`s.
+//!
+//! Fire-and-forget publishing, like [`NatsEmitter`](super::NatsEmitter):
+//! events emitted while reconnecting are buffered up to [`OUTGOING_BUFFER`]
+//! and dropped beyond, with a warning log.
+
+use super::types::{CrudEvent, EventEmitter};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, mpsc};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+/// Events waiting to be written to the bridge.
+pub const OUTGOING_BUFFER: usize = 1024;
+
+/// Capacity of the channels fanning received events out.
+const INCOMING_CAPACITY: usize = 1024;
+
+/// Delay before reconnecting after the bridge went away.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Relay id of the listening process's own events.
+const HUB_ID: u64 = 0;
+
+/// Emitter publishing CrudEvents to the other processes of this machine.
+///
+/// Cheap to clone: all clones share the background connection task, which
+/// stops once every clone is dropped.
+#[derive(Clone)]
+pub struct IpcEmitter {
+    path: PathBuf,
+    outgoing: mpsc::Sender<Arc<str>>,
+    incoming: broadcast::Sender<CrudEvent>,
+}
+
+impl IpcEmitter {
+    /// Join the local event bridge at `path`, listening on it when no other
+    /// process does.
+    ///
+    /// Must be called from within a tokio runtime: the connection is handled
+    /// (and re-established) by a background task.
+    pub fn start(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let (outgoing, outgoing_rx) = mpsc::channel(OUTGOING_BUFFER);
+        let (incoming, _) = broadcast::channel(INCOMING_CAPACITY);
+        tokio::spawn(run(path.clone(), outgoing_rx, incoming.clone()));
+        Self {
+            path,
+            outgoing,
+            incoming,
+        }
+    }
+
+    /// Socket (or pipe) path of the bridge.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Subscribe to the CrudEvents emitted by the other processes.
+    ///
+    /// Events emitted by this process are not echoed back.
+    pub fn subscribe_crud_events(&self) -> broadcast::Receiver<CrudEvent> {
+        self.incoming.subscribe()
+    }
+}
+
+impl EventEmitter for IpcEmitter {
+    fn emit(&self, event: CrudEvent) {
+        let line = match serde_json::to_string(&event) {
+            Ok(line) => line,
+            Err(e) => {
+                warn!(
+                    entity_type = ?event.entity_type,
+                    action = ?event.action,
+                    "Failed to serialize CrudEvent for IPC: {}",
+                    e
+                );
+                return;
+            }
+        };
+        match self.outgoing.try_send(line.into()) {
+            Ok(()) => debug!(
+                entity_type = ?event.entity_type,
+                action = ?event.action,
+                entity_id = %event.entity_id,
+                "CrudEvent queued for IPC"
+            ),
+            Err(e) => warn!(
+                entity_type = ?event.entity_type,
+                action = ?event.action,
+                "Dropping CrudEvent, IPC bridge unavailable: {}",
+                e
+            ),
+        }
+    }
+}
+
+/// Default bridge path: `events.sock` in the platform data directory, or
+/// the `project-orchestrator-events` named pipe on Windows.
+pub fn default_ipc_path() -> PathBuf {
+    #[cfg(target_os = "windows")]
+    {
+        PathBuf::from(r"\\.\pipe\project-orchestrator-events")
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let data_dir = dirs::data_local_dir().unwrap_or_else(|| PathBuf::from("."));
+        data_dir.join("project-orchestrator").join("events.sock")
+    }
+}
+
+/// Connection loop: connect to the bridge, or listen on it, until the
+/// emitter is dropped.
+async fn run(
+    path: PathBuf,
+    mut outgoing: mpsc::Receiver<Arc<str>>,
+    incoming: broadcast::Sender<CrudEvent>,
+) {
+    loop {
+        let flow = match transport::connect(&path).await {
+            Ok(stream) => {
+                info!(path = %path.display(), "Connected to local IPC event bridge");
+                peer(stream, &mut outgoing, &incoming).await
+            }
+            Err(_) => match transport::bind(&path) {
+                Ok(listener) => {
+                    info!(path = %path.display(), "Listening as local IPC event bridge");
+                    hub(listener, &mut outgoing, &incoming).await
+                }
+                // Another process is starting to listen: connect on the next round
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => ControlFlow::Continue(()),
+                Err(e) => {
+                    warn!(path = %path.display(), "Failed to open local IPC event bridge: {}", e);
+                    ControlFlow::Continue(())
+                }
+            },
+        };
+        if flow.is_break() {
+            return;
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+/// Exchange events with the listening process until either side goes away.
+async fn peer<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: S,
+    outgoing: &mut mpsc::Receiver<Arc<str>>,
+    incoming: &broadcast::Sender<CrudEvent>,
+) -> ControlFlow<()> {
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+    loop {
+        tokio::select! {
+            line = lines.next_line() => match line {
+                Ok(Some(line)) => {
+                    deliver(incoming, &line);
+                }
+                Ok(None) => {
+                    debug!("Local IPC event bridge closed");
+                    return ControlFlow::Continue(());
+                }
+                Err(e) => {
+                    warn!("Local IPC event bridge read failed: {}", e);
+                    return ControlFlow::Continue(());
+                }
+            },
+            line = outgoing.recv() => {
+                let Some(line) = line else {
+                    return ControlFlow::Break(());
+                };
+                if let Err(e) = write_line(&mut writer, &line).await {
+                    warn!("Local IPC event bridge write failed: {}", e);
+                    return ControlFlow::Continue(());
+                }
+            }
+        }
+    }
+}
+
+/// Accept processes and relay each one's events to all the others.
+async fn hub(
+    mut listener: transport::Listener,
+    outgoing: &mut mpsc::Receiver<Arc<str>>,
+    incoming: &broadcast::Sender<CrudEvent>,
+) -> ControlFlow<()> {
+    let (relay, _) = broadcast::channel::<(u64, Arc<str>)>(INCOMING_CAPACITY);
+    // Disconnect the accepted processes when we stop listening
+    let shutdown = CancellationToken::new();
+    let _shutdown_guard = shutdown.clone().drop_guard();
+    let mut next_id = HUB_ID + 1;
+    loop {
+        tokio::select! {
+            stream = listener.accept() => match stream {
+                Ok(stream) => {
+                    debug!(peer = next_id, "Process joined the local IPC event bridge");
+                    tokio::spawn(serve(
+                        next_id,
+                        stream,
+                        relay.clone(),
+                        incoming.clone(),
+                        shutdown.clone(),
+                    ));
+                    next_id += 1;
+                }
+                Err(e) => {
+                    warn!("Local IPC event bridge accept failed: {}", e);
+                    return ControlFlow::Continue(());
+                }
+            },
+            line = outgoing.recv() => {
+                let Some(line) = line else {
+                    return ControlFlow::Break(());
+                };
+                // No receivers just means no other process is connected
+                let _ = relay.send((HUB_ID, line));
+            }
+        }
+    }
+}
+
+/// Serve one connected process: deliver and relay what it sends, forward
+/// what the others send.
+async fn serve<S: AsyncRead + AsyncWrite>(
+    id: u64,
+    stream: S,
+    relay: broadcast::Sender<(u64, Arc<str>)>,
+    incoming: broadcast::Sender<CrudEvent>,
+    shutdown: CancellationToken,
+) {
+    let mut relayed = relay.subscribe();
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => return,
+            line = lines.next_line() => match line {
+                Ok(Some(line)) => {
+                    if deliver(&incoming, &line) {
+                        let _ = relay.send((id, line.into()));
+                    }
+                }
+                Ok(None) | Err(_) => break,
+            },
+            relayed_line = relayed.recv() => match relayed_line {
+                Ok((from, line)) if from != id => {
+                    if write_line(&mut writer, &line).await.is_err() {
+                        break;
+                    }
+                }
+                Ok(_) => {}
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(peer = id, "Local IPC event bridge lagging, {} events dropped", skipped);
+                }
+                Err(RecvError::Closed) => break,
+            },
+        }
+    }
+    debug!(peer = id, "Process left the local IPC event bridge");
+}
+
+/// Decode a received line and hand it to the local subscribers.
+///
+/// Returns whether the line was a valid CrudEvent.
+fn deliver(incoming: &broadcast::Sender<CrudEvent>, line: &str) -> bool {
+    match serde_json::from_str::<CrudEvent>(line) {
+        Ok(event) => {
+            // No receivers just means the bridge to the local bus isn't started
+            let _ = incoming.send(event);
+            true
+        }
+        Err(e) => {
+            warn!(
+                "Local IPC event bridge: failed to deserialize CrudEvent: {}",
+                e
+            );
+            false
+        }
+    }
+}
+
+async fn write_line<W: AsyncWrite + Unpin>(writer: &mut W, line: &str) -> std::io::Result<()> {
+    writer.write_all(line.as_bytes()).await?;
+    writer.write_all(b"\n").await?;
+    writer.flush().await
+}
+
+#[cfg(unix)]
+mod transport {
+    use std::fs::{File, TryLockError};
+    use std::io;
+    use std::path::Path;
+    use tokio::net::{UnixListener, UnixStream};
+
+    pub async fn connect(path: &Path) -> io::Result<UnixStream> {
+        UnixStream::connect(path).await
+    }
+
+    /// Listening socket, with the lock making this process the only listener.
+    pub struct Listener {
+        socket: UnixListener,
+        _lock: File,
+    }
+
+    /// Listen on `path`, replacing the socket file a crashed process left behind.
+    ///
+    /// Fails with `WouldBlock` while another process holds `<path>.lock`: it
+    /// is listening, or about to.
+    pub fn bind(path: &Path) -> io::Result<Listener> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut lock_path = path.as_os_str().to_owned();
+        lock_path.push(".lock");
+        let lock = File::create(lock_path)?;
+        match lock.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => return Err(io::ErrorKind::WouldBlock.into()),
+            Err(TryLockError::Error(e)) => return Err(e),
+        }
+        match std::fs::remove_file(path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+        Ok(Listener {
+            socket: UnixListener::bind(path)?,
+            _lock: lock,
+        })
+    }
+
+    impl Listener {
+        pub async fn accept(&mut self) -> io::Result<UnixStream> {
+            self.socket.accept().await.map(|(stream, _)| stream)
+        }
+    }
+}
+
+#[cfg(windows)]
+mod transport {
+    use std::io;
+    use std::path::{Path, PathBuf};
+    use tokio::net::windows::named_pipe::{
+        ClientOptions, NamedPipeClient, NamedPipeServer, ServerOptions,
+    };
+
+    pub async fn connect(path: &Path) -> io::Result<NamedPipeClient> {
+        ClientOptions::new().open(path)
+    }
+
+    /// Named pipe server, always holding one instance waiting for a client.
+    pub struct Listener {
+        path: PathBuf,
+        next: NamedPipeServer,
+    }
+
+    pub fn bind(path: &Path) -> io::Result<Listener> {
+        let next = ServerOptions::new()
+            .first_pipe_instance(true)
+            .create(path)?;
+        Ok(Listener {
+            path: path.to_path_buf(),
+            next,
+        })
+    }
+
+    impl Listener {
+        pub async fn accept(&mut self) -> io::Result<NamedPipeServer> {
+            self.next.connect().await?;
+            let next = ServerOptions::new().create(&self.path)?;
+            Ok(std::mem::replace(&mut self.next, next))
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::events::{CrudAction, EntityType};
+
+    async fn recv(rx: &mut broadcast::Receiver<CrudEvent>) -> CrudEvent {
+        tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("event not received in time")
+            .unwrap()
+    }
+
+    /// Emit from `from` until `to` receives; the bridge connects in the background.
+    async fn emit_until_received(
+        from: &IpcEmitter,
+        to: &mut broadcast::Receiver<CrudEvent>,
+        entity_id: &str,
+    ) {
+        for _ in 0..50 {
+            from.emit(CrudEvent::new(
+                EntityType::Plan,
+                CrudAction::Created,
+                entity_id,
+            ));
+            if let Ok(Ok(event)) = tokio::time::timeout(Duration::from_millis(100), to.recv()).await
+            {
+                assert_eq!(event.entity_id, entity_id);
+                return;
+            }
+        }
+        panic!("bridge never delivered {entity_id}");
+    }
+
+    #[tokio::test]
+    async fn test_events_relayed_between_processes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.sock");
+
+        let hub = IpcEmitter::start(&path);
+        let mut hub_rx = hub.subscribe_crud_events();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let a = IpcEmitter::start(&path);
+        let mut a_rx = a.subscribe_crud_events();
+        let b = IpcEmitter::start(&path);
+        let mut b_rx = b.subscribe_crud_events();
+
+        emit_until_received(&a, &mut hub_rx, "warmup-a").await;
+        emit_until_received(&b, &mut hub_rx, "warmup-b").await;
+        // Drain warmup events relayed between the peers
+        while let Ok(Ok(_)) = tokio::time::timeout(Duration::from_millis(200), a_rx.recv()).await {}
+        while let Ok(Ok(_)) = tokio::time::timeout(Duration::from_millis(200), b_rx.recv()).await {}
+
+        // Peer → hub and the other peer, not echoed back
+        a.emit(CrudEvent::new(
+            EntityType::Task,
+            CrudAction::Updated,
+            "task-1",
+        ));
+        assert_eq!(recv(&mut hub_rx).await.entity_id, "task-1");
+        assert_eq!(recv(&mut b_rx).await.entity_id, "task-1");
+
+        // Hub → peers
+        hub.emit(CrudEvent::new(
+            EntityType::Note,
+            CrudAction::Deleted,
+            "note-1",
+        ));
+        assert_eq!(recv(&mut a_rx).await.entity_id, "note-1");
+        assert_eq!(recv(&mut b_rx).await.entity_id, "note-1");
+        assert!(a_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_peer_takes_over_when_hub_exits() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.sock");
+
+        let hub = IpcEmitter::start(&path);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let a = IpcEmitter::start(&path);
+        let mut hub_rx = hub.subscribe_crud_events();
+        emit_until_received(&a, &mut hub_rx, "before").await;
+
+        drop(hub_rx);
+        drop(hub);
+
+        // `a` rebinds after the hub left, `b` joins it
+        let b = IpcEmitter::start(&path);
+        let mut b_rx = b.subscribe_crud_events();
+        emit_until_received(&a, &mut b_rx, "after").await;
+    }
+}
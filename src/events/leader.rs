@@ -0,0 +1,361 @@
+//! Leader election between orchestrator instances sharing Neo4j/NATS.
+//!
+//! Background work — file watching, the heartbeat checks, the protocol and
+//! trigger schedulers — must run on a single instance, otherwise every replica
+//! syncs the same files and fires the same jobs. Instances compete for a lease
+//! in a NATS JetStream KV bucket ([`LEASE_BUCKET`]): the holder renews it every
+//! [`LEASE_TTL_SECS`] / 3 seconds, and when it stops (crash, network split) the
+//! bucket's `max_age` drops the lease and another instance takes over.
+//!
+//! Without NATS there is nothing to share, so [`Leadership::always`] makes the
+//! single instance the leader.
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+/// JetStream KV bucket holding the background jobs lease.
+pub const LEASE_BUCKET: &str = "leader_lease";
+
+/// Key of the lease in [`LEASE_BUCKET`].
+const LEASE_KEY: &str = "background-jobs";
+
+/// How long a lease lasts without renewal (seconds).
+pub const LEASE_TTL_SECS: u64 = 15;
+
+/// Storage for the lease, shared by every instance.
+#[async_trait]
+pub trait LeaseBackend: Send + Sync {
+    /// Take the lease for `holder` if nobody holds it. Returns its revision,
+    /// or None when another instance holds it.
+    async fn acquire(&self, holder: &str) -> anyhow::Result<Option<u64>>;
+
+    /// Extend the lease held at `revision`. Returns the new revision, or None
+    /// when the lease was lost in the meantime.
+    async fn renew(&self, holder: &str, revision: u64) -> anyhow::Result<Option<u64>>;
+
+    /// Give the lease up so another instance can take it immediately.
+    async fn release(&self, revision: u64) -> anyhow::Result<()>;
+}
+
+/// Read side of the election: whether this instance runs background jobs.
+#[derive(Clone)]
+pub struct Leadership {
+    rx: watch::Receiver<bool>,
+}
+
+impl Leadership {
+    /// Leadership of a single instance, which always leads.
+    pub fn always() -> Self {
+        // The value stays readable after the sender is dropped
+        let (_, rx) = watch::channel(true);
+        Self { rx }
+    }
+
+    /// Leadership of an instance that never leads.
+    #[cfg(test)]
+    pub(crate) fn follower() -> Self {
+        let (_, rx) = watch::channel(false);
+        Self { rx }
+    }
+
+    /// Whether this instance currently holds the lease.
+    pub fn is_leader(&self) -> bool {
+        *self.rx.borrow()
+    }
+}
+
+impl Default for Leadership {
+    fn default() -> Self {
+        Self::always()
+    }
+}
+
+/// Write side of the election, driven by [`LeaderElection::spawn`].
+pub struct LeaderElection {
+    backend: Arc<dyn LeaseBackend>,
+    holder: String,
+    /// Revision of the lease while held
+    revision: Option<u64>,
+    /// Last successful acquire/renew, to ride out backend errors
+    renewed_at: Instant,
+    tx: watch::Sender<bool>,
+}
+
+impl LeaderElection {
+    /// Create an election for `holder` (a unique instance id), not leader yet.
+    pub fn new(backend: Arc<dyn LeaseBackend>, holder: impl Into<String>) -> (Self, Leadership) {
+        let (tx, rx) = watch::channel(false);
+        let election = Self {
+            backend,
+            holder: holder.into(),
+            revision: None,
+            renewed_at: Instant::now(),
+            tx,
+        };
+        (election, Leadership { rx })
+    }
+
+    /// Unique id for this process, used as the lease holder.
+    pub fn instance_id(server_port: u16) -> String {
+        let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "localhost".to_string());
+        format!("{}:{}:{}", host, server_port, uuid::Uuid::new_v4())
+    }
+
+    /// Run one round: renew the lease if held, otherwise try to take it.
+    /// Returns whether this instance leads afterwards.
+    pub async fn tick(&mut self) -> bool {
+        let result = match self.revision {
+            Some(revision) => self.backend.renew(&self.holder, revision).await,
+            None => self.backend.acquire(&self.holder).await,
+        };
+        match result {
+            Ok(revision) => {
+                if revision.is_some() {
+                    self.renewed_at = Instant::now();
+                }
+                self.revision = revision;
+            }
+            // Keep leading while the lease can't have expired yet — followers
+            // can't take it over before then either.
+            Err(e) => {
+                warn!("Leader election: lease backend error: {}", e);
+                if self.revision.is_some()
+                    && self.renewed_at.elapsed() >= Duration::from_secs(LEASE_TTL_SECS)
+                {
+                    self.revision = None;
+                }
+            }
+        }
+
+        let leading = self.revision.is_some();
+        if *self.tx.borrow() != leading {
+            if leading {
+                info!("Leader election: {} now runs background jobs", self.holder);
+            } else {
+                warn!("Leader election: {} lost the lease", self.holder);
+            }
+            self.tx.send_replace(leading);
+        }
+        leading
+    }
+
+    /// Release the lease if held, stepping down immediately.
+    pub async fn resign(&mut self) {
+        if let Some(revision) = self.revision.take() {
+            if let Err(e) = self.backend.release(revision).await {
+                warn!("Leader election: failed to release lease: {}", e);
+            }
+            self.tx.send_replace(false);
+        }
+    }
+
+    /// Keep the election running in the background for the process lifetime.
+    pub fn spawn(mut self) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(LEASE_TTL_SECS / 3));
+            loop {
+                interval.tick().await;
+                self.tick().await;
+            }
+        });
+    }
+}
+
+/// [`LeaseBackend`] on a NATS JetStream key-value bucket.
+///
+/// `create` only succeeds on a missing key and `update` only at the expected
+/// revision, so two instances can never both hold the lease.
+pub struct NatsLeaseBackend {
+    kv: async_nats::jetstream::kv::Store,
+}
+
+impl NatsLeaseBackend {
+    /// Open the lease bucket, creating it if needed. Fails when the NATS
+    /// server has JetStream disabled.
+    pub async fn connect(client: &async_nats::Client) -> anyhow::Result<Self> {
+        let jetstream = async_nats::jetstream::new(client.clone());
+        let kv = match jetstream.get_key_value(LEASE_BUCKET).await {
+            Ok(kv) => kv,
+            Err(_) => jetstream
+                .create_key_value(async_nats::jetstream::kv::Config {
+                    bucket: LEASE_BUCKET.to_string(),
+                    history: 1,
+                    max_age: Duration::from_secs(LEASE_TTL_SECS),
+                    ..Default::default()
+                })
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to create KV bucket {LEASE_BUCKET}: {e}"))?,
+        };
+        Ok(Self { kv })
+    }
+
+    /// Whether a failed write means another instance holds the lease, as
+    /// opposed to NATS being unreachable.
+    async fn held_by_other(&self, holder: &str) -> anyhow::Result<bool> {
+        let entry = self
+            .kv
+            .entry(LEASE_KEY)
+            .await
+            .map_err(|e| anyhow::anyhow!("KV get failed: {e}"))?;
+        Ok(entry.is_some_and(|e| !e.value.is_empty() && e.value.as_ref() != holder.as_bytes()))
+    }
+}
+
+#[async_trait]
+impl LeaseBackend for NatsLeaseBackend {
+    async fn acquire(&self, holder: &str) -> anyhow::Result<Option<u64>> {
+        match self
+            .kv
+            .create(LEASE_KEY, holder.as_bytes().to_vec().into())
+            .await
+        {
+            Ok(revision) => Ok(Some(revision)),
+            Err(_) if self.held_by_other(holder).await? => Ok(None),
+            Err(e) => Err(anyhow::anyhow!("KV create failed: {e}")),
+        }
+    }
+
+    async fn renew(&self, holder: &str, revision: u64) -> anyhow::Result<Option<u64>> {
+        match self
+            .kv
+            .update(LEASE_KEY, holder.as_bytes().to_vec().into(), revision)
+            .await
+        {
+            Ok(revision) => Ok(Some(revision)),
+            // Expired and retaken, or expired and gone: either way it's lost
+            Err(_) if self.held_by_other(holder).await? => Ok(None),
+            Err(e) => Err(anyhow::anyhow!("KV update failed: {e}")),
+        }
+    }
+
+    async fn release(&self, revision: u64) -> anyhow::Result<()> {
+        self.kv
+            .delete_expect_revision(LEASE_KEY, Some(revision))
+            .await
+            .map_err(|e| anyhow::anyhow!("KV delete failed: {e}"))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// In-memory lease shared by several elections, standing in for NATS KV.
+    #[derive(Default)]
+    struct FakeLease {
+        /// (holder, revision)
+        current: Mutex<Option<(String, u64)>>,
+        next_revision: Mutex<u64>,
+        failing: Mutex<bool>,
+    }
+
+    impl FakeLease {
+        fn bump(&self) -> u64 {
+            let mut next = self.next_revision.lock().unwrap();
+            *next += 1;
+            *next
+        }
+
+        /// Simulate the bucket's max_age dropping an unrenewed lease.
+        fn expire(&self) {
+            *self.current.lock().unwrap() = None;
+        }
+    }
+
+    #[async_trait]
+    impl LeaseBackend for FakeLease {
+        async fn acquire(&self, holder: &str) -> anyhow::Result<Option<u64>> {
+            if *self.failing.lock().unwrap() {
+                anyhow::bail!("nats down");
+            }
+            if self.current.lock().unwrap().is_some() {
+                return Ok(None);
+            }
+            let revision = self.bump();
+            *self.current.lock().unwrap() = Some((holder.to_string(), revision));
+            Ok(Some(revision))
+        }
+
+        async fn renew(&self, holder: &str, revision: u64) -> anyhow::Result<Option<u64>> {
+            if *self.failing.lock().unwrap() {
+                anyhow::bail!("nats down");
+            }
+            let held = matches!(&*self.current.lock().unwrap(), Some((_, r)) if *r == revision);
+            if !held {
+                return Ok(None);
+            }
+            let revision = self.bump();
+            *self.current.lock().unwrap() = Some((holder.to_string(), revision));
+            Ok(Some(revision))
+        }
+
+        async fn release(&self, revision: u64) -> anyhow::Result<()> {
+            let mut current = self.current.lock().unwrap();
+            if matches!(&*current, Some((_, r)) if *r == revision) {
+                *current = None;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_always_leads() {
+        assert!(Leadership::always().is_leader());
+    }
+
+    #[tokio::test]
+    async fn test_single_leader_and_failover() {
+        let lease = Arc::new(FakeLease::default());
+        let (mut a, leader_a) = LeaderElection::new(lease.clone(), "a");
+        let (mut b, leader_b) = LeaderElection::new(lease.clone(), "b");
+
+        assert!(a.tick().await);
+        assert!(!b.tick().await);
+        assert!(a.tick().await);
+        assert!(leader_a.is_leader() && !leader_b.is_leader());
+
+        // `a` stops renewing: the lease expires and `b` takes over
+        lease.expire();
+        assert!(b.tick().await);
+        assert!(leader_b.is_leader());
+        // `a` notices on its next renewal
+        assert!(!a.tick().await);
+        assert!(!leader_a.is_leader());
+    }
+
+    #[tokio::test]
+    async fn test_resign_hands_over() {
+        let lease = Arc::new(FakeLease::default());
+        let (mut a, leader_a) = LeaderElection::new(lease.clone(), "a");
+        let (mut b, _) = LeaderElection::new(lease.clone(), "b");
+
+        assert!(a.tick().await);
+        a.resign().await;
+        assert!(!leader_a.is_leader());
+        assert!(b.tick().await);
+    }
+
+    #[tokio::test]
+    async fn test_backend_error_keeps_fresh_lease() {
+        let lease = Arc::new(FakeLease::default());
+        let (mut a, _) = LeaderElection::new(lease.clone(), "a");
+        assert!(a.tick().await);
+
+        *lease.failing.lock().unwrap() = true;
+        assert!(a.tick().await);
+
+        // Once the lease may have expired, step down
+        a.renewed_at = Instant::now() - Duration::from_secs(LEASE_TTL_SECS + 1);
+        assert!(!a.tick().await);
+
+        // A follower never becomes leader on errors
+        let (mut b, _) = LeaderElection::new(lease.clone(), "b");
+        assert!(!b.tick().await);
+    }
+}
@@ -9,6 +9,7 @@
 //! ```text
 //! Handler (mutation) ──► HybridEmitter ──┬──► EventBus (local broadcast) ──► WebSocket clients
 //!                                        └──► NatsEmitter (inter-process) ──► Other PO instances
+//!                                             or IpcEmitter (same machine, no NATS)
 //! ```
 //!
 //! ## Components
@@ -17,6 +18,8 @@
 //! - [`GraphEvent`] — fine-grained graph mutation event for visualization
 //! - [`EventBus`] — `tokio::sync::broadcast` channel for local distribution
 //! - [`NatsEmitter`] — NATS JetStream publisher for cross-instance sync
+//! - [`IpcEmitter`] — Unix socket / named pipe bridge syncing the processes of one machine without NATS
+//! - [`LeaderElection`] / [`Leadership`] — NATS KV lease electing the instance that runs background jobs
 //! - [`HybridEmitter`] — combines local + NATS, implements [`EventEmitter`]
 //! - [`EventEmitter`] trait — polymorphic dispatch with convenience methods
 //!
@@ -72,6 +75,8 @@ pub mod builtin_triggers;
 mod bus;
 pub mod graph;
 mod hybrid;
+pub mod ipc;
+pub mod leader;
 pub mod nats;
 mod notifier;
 pub mod reactions;
@@ -83,6 +88,8 @@ mod types;
 pub use bus::EventBus;
 pub use graph::{ActivationTarget, GraphEvent, GraphEventType, GraphLayer};
 pub use hybrid::HybridEmitter;
+pub use ipc::IpcEmitter;
+pub use leader::{LeaderElection, Leadership, NatsLeaseBackend};
 pub use nats::{connect_nats, ChatRpcRequest, ChatRpcResponse, NatsEmitter, StreamingSnapshot};
 #[allow(deprecated)]
 pub use notifier::EventNotifier;
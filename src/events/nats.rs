@@ -4,12 +4,23 @@
 //! Also handles chat event pub/sub and interrupt signaling via NATS.
 //!
 //! Fire-and-forget publishing: errors are logged but never block the caller.
+//! With JetStream enabled, CrudEvents are also stored in a stream so durable
+//! consumers can replay what they missed while offline.
 
 use super::types::{CrudEvent, EventEmitter};
 use crate::chat::types::ChatEvent;
+use async_nats::jetstream::{self, consumer, stream};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use tracing::{debug, warn};
 
+/// JetStream stream holding CrudEvents for durable consumers.
+pub const CRUD_EVENT_STREAM: &str = "PO_CRUD_EVENTS";
+
+/// How long CrudEvents stay in the stream. A consumer offline for longer
+/// misses the events in between, and is itself removed by the server.
+const CRUD_EVENT_RETENTION: Duration = Duration::from_secs(24 * 60 * 60);
+
 // ============================================================================
 // RPC types for cross-instance chat session routing
 // ============================================================================
@@ -60,10 +71,22 @@ pub struct StreamingSnapshot {
 ///
 /// Fire-and-forget: publishing never blocks, never panics.
 /// If NATS is disconnected, events are silently dropped with a warning log.
+///
+/// With [`NatsEmitter::with_jetstream`], CrudEvents are published to the
+/// [`CRUD_EVENT_STREAM`] stream and acknowledged by the server, and this
+/// instance reads them back through its own durable consumer.
 #[derive(Clone)]
 pub struct NatsEmitter {
     client: async_nats::Client,
     subject_prefix: String,
+    jetstream: Option<JetStreamDelivery>,
+}
+
+/// JetStream context and durable consumer used for CrudEvents.
+#[derive(Clone)]
+struct JetStreamDelivery {
+    context: jetstream::Context,
+    consumer: String,
 }
 
 impl NatsEmitter {
@@ -74,9 +97,45 @@ impl NatsEmitter {
         Self {
             client,
             subject_prefix: subject_prefix.into(),
+            jetstream: None,
         }
     }
 
+    /// Deliver CrudEvents through JetStream instead of core NATS.
+    ///
+    /// Creates the [`CRUD_EVENT_STREAM`] stream if needed. `consumer` names
+    /// the durable consumer of this instance: it must stay the same across
+    /// restarts for the instance to catch up on the events it missed.
+    pub async fn with_jetstream(mut self, consumer: impl Into<String>) -> anyhow::Result<Self> {
+        let context = jetstream::new(self.client.clone());
+        context
+            .get_or_create_stream(stream::Config {
+                name: CRUD_EVENT_STREAM.to_string(),
+                subjects: vec![self.crud_subject()],
+                max_age: CRUD_EVENT_RETENTION,
+                storage: stream::StorageType::File,
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| {
+                anyhow::anyhow!(
+                    "Failed to create JetStream stream {}: {}",
+                    CRUD_EVENT_STREAM,
+                    e
+                )
+            })?;
+        self.jetstream = Some(JetStreamDelivery {
+            context,
+            consumer: consumer.into(),
+        });
+        Ok(self)
+    }
+
+    /// Whether CrudEvents go through JetStream.
+    pub fn uses_jetstream(&self) -> bool {
+        self.jetstream.is_some()
+    }
+
     /// Get a reference to the underlying NATS client.
     ///
     /// Useful for creating subscribers or publishing to other subjects
@@ -451,17 +510,80 @@ impl NatsEmitter {
         debug!(subject = %subject, "Subscribed to NATS CRUD events");
         Ok(subscriber)
     }
+
+    /// Consume CRUD events through the durable JetStream consumer of this instance.
+    ///
+    /// The consumer is created on first use and only receives events published
+    /// from then on; afterwards it resumes after the last acknowledged event.
+    /// Each message must be acknowledged with `ack()` once handled.
+    ///
+    /// Fails if JetStream is not enabled (see [`NatsEmitter::with_jetstream`]).
+    pub async fn consume_crud_events(&self) -> anyhow::Result<consumer::pull::Stream> {
+        let Some(ref js) = self.jetstream else {
+            anyhow::bail!("JetStream is not enabled for CRUD events");
+        };
+        let stream = js
+            .context
+            .get_stream(CRUD_EVENT_STREAM)
+            .await
+            .map_err(|e| {
+                anyhow::anyhow!(
+                    "Failed to open JetStream stream {}: {}",
+                    CRUD_EVENT_STREAM,
+                    e
+                )
+            })?;
+        let durable = stream
+            .get_or_create_consumer(
+                &js.consumer,
+                consumer::pull::Config {
+                    durable_name: Some(js.consumer.clone()),
+                    filter_subject: self.crud_subject(),
+                    ack_policy: consumer::AckPolicy::Explicit,
+                    deliver_policy: consumer::DeliverPolicy::New,
+                    inactive_threshold: CRUD_EVENT_RETENTION,
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(|e| {
+                anyhow::anyhow!("Failed to create JetStream consumer {}: {}", js.consumer, e)
+            })?;
+        let messages = durable.messages().await.map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to consume JetStream consumer {}: {}",
+                js.consumer,
+                e
+            )
+        })?;
+        debug!(consumer = %js.consumer, "Consuming CRUD events from JetStream");
+        Ok(messages)
+    }
 }
 
 impl EventEmitter for NatsEmitter {
     fn emit(&self, event: CrudEvent) {
         let client = self.client.clone();
+        let jetstream = self.jetstream.as_ref().map(|js| js.context.clone());
         let subject = self.crud_subject();
 
         tokio::spawn(async move {
             match serde_json::to_vec(&event) {
                 Ok(payload) => {
-                    if let Err(e) = client.publish(subject.clone(), payload.into()).await {
+                    // JetStream: wait for the server to confirm the event is stored
+                    let published = match jetstream {
+                        Some(context) => {
+                            match context.publish(subject.clone(), payload.into()).await {
+                                Ok(ack) => ack.await.map(|_| ()).map_err(anyhow::Error::from),
+                                Err(e) => Err(e.into()),
+                            }
+                        }
+                        None => client
+                            .publish(subject.clone(), payload.into())
+                            .await
+                            .map_err(anyhow::Error::from),
+                    };
+                    if let Err(e) = published {
                         warn!(
                             subject = %subject,
                             entity_type = ?event.entity_type,
@@ -594,6 +716,7 @@ mod tests {
         let events = vec![
             ChatEvent::UserMessage {
                 content: "Hello".into(),
+                attachments: vec![],
             },
             ChatEvent::AssistantText {
                 content: "Hi there!".into(),
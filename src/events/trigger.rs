@@ -113,7 +113,12 @@ impl EventTrigger {
 ///
 /// - Array expected: "any of" semantics — actual must equal one element.
 /// - Scalar expected: exact equality (string, bool, number).
-fn value_matches(expected: &serde_json::Value, actual: &serde_json::Value) -> bool {
+/// - Array actual (e.g. the `mentions` of a comment): matches when one of
+///   its elements does.
+pub(crate) fn value_matches(expected: &serde_json::Value, actual: &serde_json::Value) -> bool {
+    if let serde_json::Value::Array(items) = actual {
+        return items.iter().any(|item| value_matches(expected, item));
+    }
     match expected {
         serde_json::Value::Array(options) => {
             // "any of" — actual must match at least one element
@@ -408,6 +413,27 @@ mod tests {
         assert!(!trigger.matches(&event_gotcha));
     }
 
+    #[test]
+    fn test_payload_array_value_matches_any_element() {
+        let trigger = make_trigger(
+            Some("comment"),
+            Some("created"),
+            Some(json!({"mentions": "bob"})),
+            None,
+        );
+        let mentioning = |mentions: serde_json::Value| {
+            make_event(
+                EntityType::Comment,
+                CrudAction::Created,
+                json!({ "mentions": mentions }),
+                None,
+            )
+        };
+        assert!(trigger.matches(&mentioning(json!(["alice", "bob"]))));
+        assert!(!trigger.matches(&mentioning(json!(["alice"]))));
+        assert!(!trigger.matches(&mentioning(json!([]))));
+    }
+
     #[test]
     fn test_payload_missing_key_fails() {
         let trigger = make_trigger(None, None, Some(json!({"note_type": "rfc"})), None);
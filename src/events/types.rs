@@ -72,6 +72,10 @@ pub enum EntityType {
     LifecycleHook,
     /// Learning system — emitters: reactions.rs (PatternsDetected after episode analysis)
     Learning,
+    /// Security advisory affecting a project dependency — emitters: vulnerability_audit.rs (Created)
+    Advisory,
+    /// Discussion comment on a task, plan or note — emitters: comment_handlers.rs (Created, Updated, Deleted)
+    Comment,
 }
 
 /// The CRUD action performed on an entity.
@@ -372,6 +376,8 @@ mod tests {
             EntityType::TopologyRule,
             EntityType::LifecycleHook,
             EntityType::Learning,
+            EntityType::Advisory,
+            EntityType::Comment,
         ];
 
         for variant in &variants {
@@ -482,7 +488,7 @@ mod tests {
     #[test]
     fn test_entity_type_has_26_variants() {
         // Ensure we don't accidentally add/remove variants
-        let all: [EntityType; 28] = [
+        let all: [EntityType; 30] = [
             EntityType::Project,
             EntityType::Plan,
             EntityType::Task,
@@ -511,8 +517,10 @@ mod tests {
             EntityType::TopologyRule,
             EntityType::LifecycleHook,
             EntityType::Learning,
+            EntityType::Advisory,
+            EntityType::Comment,
         ];
-        assert_eq!(all.len(), 28);
+        assert_eq!(all.len(), 30);
     }
 
     // ================================================================
@@ -4,6 +4,8 @@
 //! 1. **Explicit feedback** (API): Users rate decisions, notes, plans via POST /api/feedback
 //! 2. **Implicit signals**: Automatic detection of CommitReverted, TaskRestarted, etc.
 //! 3. **Score propagation**: Boost/penalize notes, synapses, profiles based on signals
+//! 4. **Context relevance**: Thumbs up/down on the notes surfaced in chat weight
+//!    how those notes rank in later contexts ([`relevance`])
 //!
 //! The OutcomeTracker works standalone (explicit feedback API) even if other
 //! subsystems (HeartbeatEngine, NeuralFeedback) are not deployed.
@@ -11,6 +13,7 @@
 pub mod handlers;
 pub mod models;
 pub mod propagator;
+pub mod relevance;
 pub mod signals;
 pub mod tracker;
 
@@ -0,0 +1,132 @@
+//! Learned context relevance — per-note weights from chat context feedback.
+//!
+//! Users rate the notes surfaced in a chat (thumbs up/down, see
+//! `POST /api/chat/sessions/{id}/context-feedback`). The votes of all
+//! sessions give each note a relevance weight that scales its score in the
+//! `ContextBuilder`, spreading activation and chat knowledge injection.
+//! Notes voted down repeatedly are suppressed from them.
+
+use std::collections::HashMap;
+
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::neo4j::models::NoteRelevanceVotes;
+use crate::neo4j::traits::GraphStore;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Highest weight a note can earn from helpful votes
+const MAX_WEIGHT: f64 = 1.5;
+/// Notes at or below this weight are no longer surfaced (three down votes
+/// without any up vote)
+const SUPPRESSION_WEIGHT: f64 = 0.4;
+
+// ============================================================================
+// Weights
+// ============================================================================
+
+/// Relevance weight of a note from its votes: 1.0 without votes, each up
+/// vote raising it (up to 1.5) and each down vote lowering it toward 0.
+pub fn relevance_weight(helpful: u32, irrelevant: u32) -> f64 {
+    let (helpful, irrelevant) = (helpful as f64, irrelevant as f64);
+    ((2.0 + 2.0 * helpful) / (2.0 + helpful + irrelevant)).min(MAX_WEIGHT)
+}
+
+/// Learned weights of a set of notes.
+#[derive(Debug, Clone, Default)]
+pub struct RelevanceWeights {
+    votes: HashMap<Uuid, NoteRelevanceVotes>,
+}
+
+impl RelevanceWeights {
+    pub fn new(votes: Vec<NoteRelevanceVotes>) -> Self {
+        Self {
+            votes: votes.into_iter().map(|v| (v.note_id, v)).collect(),
+        }
+    }
+
+    /// Weights of the given notes. A failed lookup leaves every weight at
+    /// 1.0 rather than failing the context build.
+    pub async fn load(graph: &dyn GraphStore, note_ids: &[Uuid]) -> Self {
+        if note_ids.is_empty() {
+            return Self::default();
+        }
+        match graph.get_note_relevance_votes(note_ids).await {
+            Ok(votes) => Self::new(votes),
+            Err(e) => {
+                debug!("Failed to load note relevance votes: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn weight(&self, note_id: Uuid) -> f64 {
+        self.votes
+            .get(&note_id)
+            .map(|v| relevance_weight(v.helpful, v.irrelevant))
+            .unwrap_or(1.0)
+    }
+
+    /// Whether the note has been voted irrelevant too often to be surfaced.
+    pub fn is_suppressed(&self, note_id: Uuid) -> bool {
+        self.weight(note_id) <= SUPPRESSION_WEIGHT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::{ContextFeedbackNode, ContextItemType};
+    use crate::test_helpers::test_chat_session;
+
+    #[test]
+    fn test_relevance_weight() {
+        assert_eq!(relevance_weight(0, 0), 1.0);
+        assert!(relevance_weight(1, 0) > 1.0);
+        assert_eq!(relevance_weight(10, 0), MAX_WEIGHT);
+        assert!(relevance_weight(0, 1) < 1.0);
+        assert!(relevance_weight(0, 2) > SUPPRESSION_WEIGHT);
+        assert_eq!(relevance_weight(0, 3), SUPPRESSION_WEIGHT);
+        // An up vote offsets a few down votes
+        assert!(relevance_weight(1, 3) > SUPPRESSION_WEIGHT);
+    }
+
+    #[tokio::test]
+    async fn test_load_weights() {
+        let graph = MockGraphStore::new();
+        let (noisy, useful, unrated) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        // Three sessions vote `noisy` down, four vote `useful` up
+        for votes_noisy in [true, true, true, false] {
+            let session = test_chat_session(None);
+            graph.create_chat_session(&session).await.unwrap();
+            let vote = |note: Uuid, helpful: bool| ContextFeedbackNode {
+                session_id: session.id,
+                item_type: ContextItemType::Note,
+                item_id: note.to_string(),
+                helpful,
+                comment: None,
+                created_at: chrono::Utc::now(),
+            };
+            if votes_noisy {
+                graph
+                    .upsert_context_feedback(&vote(noisy, false))
+                    .await
+                    .unwrap();
+            }
+            graph
+                .upsert_context_feedback(&vote(useful, true))
+                .await
+                .unwrap();
+        }
+
+        let weights = RelevanceWeights::load(&graph, &[noisy, useful, unrated]).await;
+        assert!(weights.is_suppressed(noisy));
+        assert!(weights.weight(useful) > 1.0);
+        assert!(!weights.is_suppressed(useful));
+        assert_eq!(weights.weight(unrated), 1.0);
+    }
+}
@@ -0,0 +1,198 @@
+//! GitHub App authentication and the REST calls the check runs need.
+//!
+//! The App signs a short-lived RS256 JWT with its private key, exchanges it
+//! for an installation token, and uses that token for the repository calls.
+
+use anyhow::{bail, Context, Result};
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use serde::{Deserialize, Serialize};
+
+const USER_AGENT: &str = concat!("orchestrator/", env!("CARGO_PKG_VERSION"));
+const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// GitHub App credentials, read from the environment:
+/// `GITHUB_APP_ID`, `GITHUB_APP_PRIVATE_KEY` (PEM) or
+/// `GITHUB_APP_PRIVATE_KEY_PATH`, `GITHUB_WEBHOOK_SECRET`, and optionally
+/// `GITHUB_API_URL` for GitHub Enterprise Server.
+#[derive(Clone)]
+pub struct GitHubAppConfig {
+    pub app_id: String,
+    pub private_key: String,
+    pub webhook_secret: String,
+    pub api_url: String,
+}
+
+impl std::fmt::Debug for GitHubAppConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GitHubAppConfig")
+            .field("app_id", &self.app_id)
+            .field("api_url", &self.api_url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl GitHubAppConfig {
+    /// `None` unless the App id, private key and webhook secret are all set.
+    pub fn from_env() -> Option<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let private_key = match var("GITHUB_APP_PRIVATE_KEY") {
+            // Keys passed inline often have their newlines escaped
+            Some(key) => key.replace("\\n", "\n"),
+            None => std::fs::read_to_string(var("GITHUB_APP_PRIVATE_KEY_PATH")?).ok()?,
+        };
+        Some(Self {
+            app_id: var("GITHUB_APP_ID")?,
+            private_key,
+            webhook_secret: var("GITHUB_WEBHOOK_SECRET")?,
+            api_url: var("GITHUB_API_URL")
+                .unwrap_or_else(|| DEFAULT_API_URL.to_string())
+                .trim_end_matches('/')
+                .to_string(),
+        })
+    }
+
+    /// JWT authenticating as the App itself, valid for 9 minutes (GitHub
+    /// allows 10). Issued a minute in the past against clock drift.
+    fn app_jwt(&self) -> Result<String> {
+        #[derive(Serialize)]
+        struct AppClaims<'a> {
+            iat: i64,
+            exp: i64,
+            iss: &'a str,
+        }
+        let now = chrono::Utc::now().timestamp();
+        let key = EncodingKey::from_rsa_pem(self.private_key.as_bytes())
+            .context("Invalid GitHub App private key")?;
+        encode(
+            &Header::new(Algorithm::RS256),
+            &AppClaims {
+                iat: now - 60,
+                exp: now + 540,
+                iss: &self.app_id,
+            },
+            &key,
+        )
+        .context("Failed to sign GitHub App JWT")
+    }
+}
+
+/// REST client acting as one installation of the App
+pub struct GitHubClient {
+    http: reqwest::Client,
+    api_url: String,
+    token: String,
+}
+
+async fn check_status(response: reqwest::Response, what: &str) -> Result<reqwest::Response> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    bail!("GitHub API {} returned {}: {}", what, status, body)
+}
+
+impl GitHubClient {
+    /// Exchange the App JWT for a token of `installation_id`.
+    pub async fn for_installation(config: &GitHubAppConfig, installation_id: u64) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct InstallationToken {
+            token: String,
+        }
+
+        let http = reqwest::Client::builder().user_agent(USER_AGENT).build()?;
+        let response = http
+            .post(format!(
+                "{}/app/installations/{}/access_tokens",
+                config.api_url, installation_id
+            ))
+            .bearer_auth(config.app_jwt()?)
+            .header("Accept", "application/vnd.github+json")
+            .send()
+            .await
+            .context("Failed to reach GitHub API")?;
+        let token: InstallationToken = check_status(response, "installation token")
+            .await?
+            .json()
+            .await
+            .context("Failed to parse installation token")?;
+        Ok(Self {
+            http,
+            api_url: config.api_url.clone(),
+            token: token.token,
+        })
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        self.http
+            .request(method, format!("{}{}", self.api_url, path))
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+    }
+
+    /// Unified diff of a pull request.
+    pub async fn pull_request_diff(&self, repository: &str, number: u64) -> Result<String> {
+        let response = self
+            .request(
+                reqwest::Method::GET,
+                &format!("/repos/{}/pulls/{}", repository, number),
+            )
+            .header("Accept", "application/vnd.github.diff")
+            .send()
+            .await
+            .context("Failed to reach GitHub API")?;
+        check_status(response, "pull request diff")
+            .await?
+            .text()
+            .await
+            .context("Failed to read pull request diff")
+    }
+
+    /// Create a check run, returning its id.
+    pub async fn create_check_run(
+        &self,
+        repository: &str,
+        body: &serde_json::Value,
+    ) -> Result<u64> {
+        #[derive(Deserialize)]
+        struct CheckRun {
+            id: u64,
+        }
+
+        let response = self
+            .request(
+                reqwest::Method::POST,
+                &format!("/repos/{}/check-runs", repository),
+            )
+            .json(body)
+            .send()
+            .await
+            .context("Failed to reach GitHub API")?;
+        let run: CheckRun = check_status(response, "create check run")
+            .await?
+            .json()
+            .await
+            .context("Failed to parse check run")?;
+        Ok(run.id)
+    }
+
+    /// Update a check run (status, conclusion, output).
+    pub async fn update_check_run(
+        &self,
+        repository: &str,
+        check_run_id: u64,
+        body: &serde_json::Value,
+    ) -> Result<()> {
+        let response = self
+            .request(
+                reqwest::Method::PATCH,
+                &format!("/repos/{}/check-runs/{}", repository, check_run_id),
+            )
+            .json(body)
+            .send()
+            .await
+            .context("Failed to reach GitHub API")?;
+        check_status(response, "update check run").await?;
+        Ok(())
+    }
+}
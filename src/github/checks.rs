@@ -0,0 +1,639 @@
+//! Pull request check runs.
+//!
+//! [`parse_pull_request_event`] picks the pull requests to check out of a
+//! `pull_request` webhook. [`run_pull_request_check`] then creates an
+//! in-progress check run on the head commit, reviews the pull request diff
+//! against the graph, runs the diff-scoped budget check when the project
+//! checkout has both commits, and completes the run with the output of
+//! [`build_check_outcome`].
+
+use super::app::{GitHubAppConfig, GitHubClient};
+use crate::graph::models::TopologySeverity;
+use crate::neo4j::models::{BudgetViolation, GitHubCheckSettings, ProjectNode};
+use crate::orchestrator::diff_sync::{self, DiffSyncReport};
+use crate::orchestrator::review::{parse_unified_diff, review_diff, ReviewReport};
+use crate::orchestrator::{project_roots, relative_to_roots, Orchestrator};
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Pull request actions that (re)run the check
+const CHECKED_ACTIONS: &[&str] = &["opened", "synchronize", "reopened", "ready_for_review"];
+/// Annotations GitHub accepts per check run update
+const MAX_ANNOTATIONS: usize = 50;
+/// Files listed in the summary table
+const MAX_SUMMARY_FILES: usize = 30;
+/// GitHub rejects summaries over 65535 characters
+const MAX_SUMMARY_CHARS: usize = 60_000;
+
+/// A pull request to check, from a `pull_request` webhook
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequestEvent {
+    /// `owner/name`
+    pub repository: String,
+    pub installation_id: u64,
+    pub number: u64,
+    pub base_sha: String,
+    pub head_sha: String,
+}
+
+/// The pull request of a `pull_request` webhook payload, `None` when the
+/// action does not run the check (closed, labeled, ...) or the payload lacks
+/// a field. Draft pull requests are checked once ready for review.
+pub fn parse_pull_request_event(payload: &serde_json::Value) -> Option<PullRequestEvent> {
+    let action = payload.get("action")?.as_str()?;
+    if !CHECKED_ACTIONS.contains(&action) {
+        return None;
+    }
+    let pull_request = payload.get("pull_request")?;
+    if pull_request.get("draft").and_then(|d| d.as_bool()) == Some(true) {
+        return None;
+    }
+    let sha = |side: &str| {
+        pull_request
+            .get(side)?
+            .get("sha")?
+            .as_str()
+            .map(str::to_string)
+    };
+    Some(PullRequestEvent {
+        repository: payload
+            .get("repository")?
+            .get("full_name")?
+            .as_str()?
+            .to_string(),
+        installation_id: payload.get("installation")?.get("id")?.as_u64()?,
+        number: pull_request.get("number")?.as_u64()?,
+        base_sha: sha("base")?,
+        head_sha: sha("head")?,
+    })
+}
+
+/// A check run annotation, in the shape of the GitHub API
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CheckAnnotation {
+    pub path: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    /// `notice`, `warning` or `failure`
+    pub annotation_level: &'static str,
+    pub title: String,
+    pub message: String,
+}
+
+/// Conclusion and output of a completed check run
+#[derive(Debug, Clone)]
+pub struct CheckOutcome {
+    /// `success`, `failure` or `neutral`
+    pub conclusion: &'static str,
+    pub title: String,
+    pub summary: String,
+    pub annotations: Vec<CheckAnnotation>,
+}
+
+/// File and line a violation target points at: graph ids
+/// (`path:name:line`) give their line, plain paths line 1.
+fn violation_location(target: &str, roots: &[String]) -> (String, u32) {
+    let located = target.rsplit_once(':').and_then(|(rest, line)| {
+        let line: u32 = line.parse().ok()?;
+        let (path, _name) = rest.rsplit_once(':')?;
+        Some((path, line.max(1)))
+    });
+    let (path, line) = located.unwrap_or((target, 1));
+    (relative_to_roots(path, roots).to_string(), line)
+}
+
+fn describe_budget(violation: &BudgetViolation) -> String {
+    let measure = match violation.budget.as_str() {
+        "max_function_complexity" => "Cyclomatic complexity",
+        "max_file_lines" => "File length",
+        "max_module_fan_in" => "Module fan-in",
+        other => other,
+    };
+    format!(
+        "{} is {} (limit {})",
+        measure, violation.actual, violation.limit
+    )
+}
+
+fn truncate_chars(text: &str, max: usize) -> &str {
+    match text.char_indices().nth(max) {
+        Some((end, _)) => &text[..end],
+        None => text,
+    }
+}
+
+/// Check run output for a pull request: budget violations at the head
+/// commit (from `overlay`, when it could be built) and topology rule
+/// violations on the changed files become annotations, and fail the check
+/// as `settings` ask. The impact of the change goes in the summary.
+pub fn build_check_outcome(
+    review: &ReviewReport,
+    overlay: Option<&DiffSyncReport>,
+    settings: &GitHubCheckSettings,
+    roots: &[String],
+) -> CheckOutcome {
+    let changed: HashSet<&str> = review.files.iter().map(|f| f.path.as_str()).collect();
+    let mut annotations = Vec::new();
+
+    let budget_violations = overlay
+        .map(|o| o.budget_violations.as_slice())
+        .unwrap_or_default();
+    for violation in budget_violations {
+        let (path, line) = violation_location(&violation.target, roots);
+        // Module fan-in targets are directories, which cannot be annotated
+        if !changed.contains(path.as_str()) {
+            continue;
+        }
+        annotations.push(CheckAnnotation {
+            path,
+            start_line: line,
+            end_line: line,
+            annotation_level: if settings.fail_on_budgets {
+                "failure"
+            } else {
+                "warning"
+            },
+            title: format!("Budget exceeded: {}", violation.budget),
+            message: describe_budget(violation),
+        });
+    }
+
+    for violation in &review.rule_violations {
+        let (path, line) = violation_location(&violation.violator_path, roots);
+        if !changed.contains(path.as_str()) {
+            continue;
+        }
+        let blocking = violation.severity == TopologySeverity::Error && settings.fail_on_rules;
+        annotations.push(CheckAnnotation {
+            path,
+            start_line: line,
+            end_line: line,
+            annotation_level: if blocking { "failure" } else { "warning" },
+            title: format!("Architecture rule: {}", violation.rule_description),
+            message: violation.details.clone(),
+        });
+    }
+
+    let rule_errors = review
+        .rule_violations
+        .iter()
+        .filter(|v| v.severity == TopologySeverity::Error)
+        .count();
+    let failed = (settings.fail_on_budgets && !budget_violations.is_empty())
+        || (settings.fail_on_rules && rule_errors > 0);
+    let title = if budget_violations.is_empty() && review.rule_violations.is_empty() {
+        format!(
+            "{} risk, {} file(s) reviewed",
+            review.risk_level, review.files_changed
+        )
+    } else {
+        format!(
+            "{} budget violation(s), {} rule violation(s)",
+            budget_violations.len(),
+            review.rule_violations.len()
+        )
+    };
+
+    CheckOutcome {
+        conclusion: if failed { "failure" } else { "success" },
+        title,
+        summary: build_summary(review, overlay),
+        annotations: annotations.into_iter().take(MAX_ANNOTATIONS).collect(),
+    }
+}
+
+fn build_summary(review: &ReviewReport, overlay: Option<&DiffSyncReport>) -> String {
+    let symbols: usize = review.files.iter().map(|f| f.symbols.len()).sum();
+    let dependents: usize = review
+        .files
+        .iter()
+        .flat_map(|f| &f.symbols)
+        .map(|s| s.dependent_count)
+        .sum();
+    let impacted: HashSet<&str> = review
+        .files
+        .iter()
+        .flat_map(|f| &f.impacted_files)
+        .map(String::as_str)
+        .collect();
+
+    let mut summary = format!(
+        "**Risk: {}** — {} file(s) changed, {} symbol(s) touched, {} dependent(s), {} impacted file(s).\n",
+        review.risk_level,
+        review.files_changed,
+        symbols,
+        dependents,
+        impacted.len()
+    );
+
+    if !review.files.is_empty() {
+        summary.push_str("\n| File | Change | Touched symbols | Dependents |\n|---|---|---|---|\n");
+        for file in review.files.iter().take(MAX_SUMMARY_FILES) {
+            let names: Vec<String> = file
+                .symbols
+                .iter()
+                .map(|s| format!("`{}`", s.name))
+                .collect();
+            let _ = writeln!(
+                summary,
+                "| `{}` | {} | {} | {} |",
+                file.path,
+                serde_json::to_value(file.change)
+                    .ok()
+                    .and_then(|v| v.as_str().map(str::to_string))
+                    .unwrap_or_default(),
+                if names.is_empty() {
+                    "—".to_string()
+                } else {
+                    names.join(", ")
+                },
+                file.symbols
+                    .iter()
+                    .map(|s| s.dependent_count)
+                    .sum::<usize>()
+            );
+        }
+        if review.files.len() > MAX_SUMMARY_FILES {
+            let _ = writeln!(
+                summary,
+                "\n…and {} more file(s).",
+                review.files.len() - MAX_SUMMARY_FILES
+            );
+        }
+    }
+
+    summary.push_str("\n### Budgets\n");
+    match overlay {
+        None => summary.push_str(
+            "Not checked: the pull request commits are not available in the project checkout.\n",
+        ),
+        Some(overlay) => {
+            let _ = writeln!(
+                summary,
+                "Complexity {:+}, {} function(s) added, {} removed.",
+                overlay.complexity_delta, overlay.functions_added, overlay.functions_removed
+            );
+            if overlay.budget_violations.is_empty() {
+                summary.push_str("All budgets hold at the head commit.\n");
+            }
+            for violation in &overlay.budget_violations {
+                let _ = writeln!(
+                    summary,
+                    "- `{}`: {}",
+                    violation.target,
+                    describe_budget(violation)
+                );
+            }
+            if !overlay.duplicates.is_empty() {
+                let _ = writeln!(
+                    summary,
+                    "\n{} group(s) of near-duplicate functions involve the changed code.",
+                    overlay.duplicates.len()
+                );
+            }
+        }
+    }
+
+    summary.push_str("\n### Architecture rules\n");
+    if review.rule_violations.is_empty() {
+        summary.push_str("No topology rule is violated by the changed files.\n");
+    }
+    for violation in &review.rule_violations {
+        let _ = writeln!(
+            summary,
+            "- **{}** {}: {}",
+            violation.severity, violation.rule_description, violation.details
+        );
+    }
+
+    let annotated: Vec<_> = review
+        .files
+        .iter()
+        .filter(|f| !f.notes.is_empty() || !f.decisions.is_empty())
+        .collect();
+    if !annotated.is_empty() {
+        summary.push_str("\n### Notes and decisions\n");
+        for file in annotated {
+            for note in &file.notes {
+                let first_line = note.content.lines().next().unwrap_or_default();
+                let _ = writeln!(
+                    summary,
+                    "- `{}` ({}): {}",
+                    file.path,
+                    note.note_type,
+                    truncate_chars(first_line, 200)
+                );
+            }
+            for decision in &file.decisions {
+                let _ = writeln!(
+                    summary,
+                    "- `{}` (decision): {}",
+                    file.path,
+                    truncate_chars(&decision.description, 200)
+                );
+            }
+        }
+    }
+
+    truncate_chars(&summary, MAX_SUMMARY_CHARS).to_string()
+}
+
+async fn analyze_pull_request(
+    orchestrator: &Orchestrator,
+    client: &GitHubClient,
+    project: &ProjectNode,
+    settings: &GitHubCheckSettings,
+    event: &PullRequestEvent,
+) -> Result<CheckOutcome> {
+    let diff = client
+        .pull_request_diff(&event.repository, event.number)
+        .await?;
+    let review = review_diff(orchestrator.neo4j(), project, &parse_unified_diff(&diff)).await?;
+
+    let roots = project_roots(&project.root_path);
+    let cwd = &roots[0];
+    if diff_sync::resolve_commit(cwd, &event.head_sha)
+        .await?
+        .is_none()
+    {
+        diff_sync::fetch_commits(cwd, &[&event.base_sha, &event.head_sha]).await;
+    }
+    // The pull request diff is taken against the merge base, not the base tip
+    let overlay = match diff_sync::merge_base(cwd, &event.base_sha, &event.head_sha).await? {
+        Some(base) => match orchestrator
+            .sync_diff(project, &base, &event.head_sha)
+            .await
+        {
+            Ok(report) => Some(report),
+            Err(e) => {
+                warn!(
+                    "Diff-scoped sync failed for {}#{}: {}",
+                    event.repository, event.number, e
+                );
+                None
+            }
+        },
+        None => None,
+    };
+
+    Ok(build_check_outcome(
+        &review,
+        overlay.as_ref(),
+        settings,
+        &roots,
+    ))
+}
+
+/// Run the check of `event` as a check run named after `settings`.
+///
+/// Analysis errors complete the run as `neutral`; only GitHub API errors
+/// are returned.
+pub async fn run_pull_request_check(
+    orchestrator: Arc<Orchestrator>,
+    config: GitHubAppConfig,
+    project: ProjectNode,
+    settings: GitHubCheckSettings,
+    event: PullRequestEvent,
+) -> Result<()> {
+    let client = GitHubClient::for_installation(&config, event.installation_id).await?;
+    let check_run_id = client
+        .create_check_run(
+            &event.repository,
+            &json!({
+                "name": settings.check_name,
+                "head_sha": event.head_sha,
+                "status": "in_progress",
+            }),
+        )
+        .await?;
+
+    let outcome =
+        match analyze_pull_request(&orchestrator, &client, &project, &settings, &event).await {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                warn!(
+                    "Check of {}#{} failed: {}",
+                    event.repository, event.number, e
+                );
+                CheckOutcome {
+                    conclusion: "neutral",
+                    title: "Analysis failed".to_string(),
+                    summary: format!("The pull request could not be analyzed: {}", e),
+                    annotations: Vec::new(),
+                }
+            }
+        };
+
+    client
+        .update_check_run(
+            &event.repository,
+            check_run_id,
+            &json!({
+                "status": "completed",
+                "conclusion": outcome.conclusion,
+                "completed_at": chrono::Utc::now().to_rfc3339(),
+                "output": {
+                    "title": outcome.title,
+                    "summary": outcome.summary,
+                    "annotations": outcome.annotations,
+                },
+            }),
+        )
+        .await?;
+    info!(
+        "Check run {} on {}#{} completed: {}",
+        check_run_id, event.repository, event.number, outcome.conclusion
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::models::{TopologyRuleType, TopologyViolation};
+    use crate::orchestrator::review::{FileChange, ReviewedFile, ReviewedSymbol};
+    use uuid::Uuid;
+
+    fn pull_request_payload(action: &str, draft: bool) -> serde_json::Value {
+        json!({
+            "action": action,
+            "installation": { "id": 42 },
+            "repository": { "full_name": "acme/widgets" },
+            "pull_request": {
+                "number": 7,
+                "draft": draft,
+                "base": { "sha": "b".repeat(40) },
+                "head": { "sha": "h".repeat(40) },
+            },
+        })
+    }
+
+    fn settings() -> GitHubCheckSettings {
+        serde_json::from_value(json!({ "repository": "acme/widgets" })).unwrap()
+    }
+
+    fn reviewed_file(path: &str) -> ReviewedFile {
+        ReviewedFile {
+            path: path.to_string(),
+            old_path: None,
+            change: FileChange::Modified,
+            additions: 3,
+            deletions: 1,
+            in_graph: true,
+            symbols: vec![ReviewedSymbol {
+                id: format!("/repo/{}:handle:10", path),
+                name: "handle".to_string(),
+                kind: "function".to_string(),
+                line_start: 10,
+                line_end: 40,
+                dependent_count: 4,
+                dependent_files: vec!["src/main.rs".to_string()],
+            }],
+            impacted_files: vec!["src/main.rs".to_string()],
+            notes: Vec::new(),
+            decisions: Vec::new(),
+        }
+    }
+
+    fn review(rule_violations: Vec<TopologyViolation>) -> ReviewReport {
+        ReviewReport {
+            project_id: Uuid::new_v4(),
+            files_changed: 1,
+            files: vec![reviewed_file("src/api.rs")],
+            rule_violations,
+            budget_violations: Vec::new(),
+            risk_level: "medium".to_string(),
+            commentary: None,
+            commentary_model: None,
+        }
+    }
+
+    fn overlay(budget_violations: Vec<BudgetViolation>) -> DiffSyncReport {
+        DiffSyncReport {
+            project_id: Uuid::new_v4(),
+            base: "b".repeat(40),
+            head: "h".repeat(40),
+            files_changed: 1,
+            files: Vec::new(),
+            functions_added: 1,
+            functions_removed: 0,
+            complexity_delta: 12,
+            budget_violations,
+            duplicates: Vec::new(),
+            elapsed_ms: 5,
+        }
+    }
+
+    fn rule_violation(severity: TopologySeverity) -> TopologyViolation {
+        TopologyViolation {
+            rule_id: "r1".to_string(),
+            rule_description: "api must not import neo4j".to_string(),
+            rule_type: TopologyRuleType::MustNotImport,
+            violator_path: "/repo/src/api.rs".to_string(),
+            target_path: Some("/repo/src/neo4j.rs".to_string()),
+            severity,
+            details: "src/api.rs imports src/neo4j.rs".to_string(),
+            violation_score: 0.5,
+        }
+    }
+
+    #[test]
+    fn test_parse_pull_request_event() {
+        let event = parse_pull_request_event(&pull_request_payload("synchronize", false)).unwrap();
+        assert_eq!(event.repository, "acme/widgets");
+        assert_eq!(event.installation_id, 42);
+        assert_eq!(event.number, 7);
+        assert_eq!(event.head_sha, "h".repeat(40));
+
+        assert!(parse_pull_request_event(&pull_request_payload("closed", false)).is_none());
+        assert!(parse_pull_request_event(&pull_request_payload("opened", true)).is_none());
+        assert!(parse_pull_request_event(&json!({ "action": "opened" })).is_none());
+    }
+
+    #[test]
+    fn test_violation_location() {
+        let roots = vec!["/repo".to_string()];
+        assert_eq!(
+            violation_location("/repo/src/api.rs:handle:10", &roots),
+            ("src/api.rs".to_string(), 10)
+        );
+        assert_eq!(
+            violation_location("src/api.rs", &roots),
+            ("src/api.rs".to_string(), 1)
+        );
+        assert_eq!(
+            violation_location("/repo/src/api.rs", &roots),
+            ("src/api.rs".to_string(), 1)
+        );
+    }
+
+    #[test]
+    fn test_build_check_outcome_fails_on_violations() {
+        let roots = vec!["/repo".to_string()];
+        let overlay = overlay(vec![
+            BudgetViolation {
+                budget: "max_function_complexity".to_string(),
+                target: "/repo/src/api.rs:handle:10".to_string(),
+                actual: 31,
+                limit: 15,
+            },
+            BudgetViolation {
+                budget: "max_file_lines".to_string(),
+                target: "src/api.rs".to_string(),
+                actual: 1200,
+                limit: 1000,
+            },
+        ]);
+        let review = review(vec![rule_violation(TopologySeverity::Error)]);
+
+        let outcome = build_check_outcome(&review, Some(&overlay), &settings(), &roots);
+        assert_eq!(outcome.conclusion, "failure");
+        assert_eq!(outcome.annotations.len(), 3);
+        assert_eq!(outcome.annotations[0].start_line, 10);
+        assert_eq!(outcome.annotations[0].path, "src/api.rs");
+        assert!(outcome
+            .annotations
+            .iter()
+            .all(|a| a.annotation_level == "failure"));
+        assert!(outcome.summary.contains("`handle`"));
+        assert!(outcome.summary.contains("Complexity +12"));
+
+        let lenient = GitHubCheckSettings {
+            fail_on_budgets: false,
+            fail_on_rules: false,
+            ..settings()
+        };
+        let outcome = build_check_outcome(&review, Some(&overlay), &lenient, &roots);
+        assert_eq!(outcome.conclusion, "success");
+        assert!(outcome
+            .annotations
+            .iter()
+            .all(|a| a.annotation_level == "warning"));
+    }
+
+    #[test]
+    fn test_build_check_outcome_without_overlay() {
+        let roots = vec!["/repo".to_string()];
+        let outcome = build_check_outcome(
+            &review(vec![rule_violation(TopologySeverity::Warning)]),
+            None,
+            &settings(),
+            &roots,
+        );
+        // Warning-level rules annotate without failing
+        assert_eq!(outcome.conclusion, "success");
+        assert_eq!(outcome.annotations.len(), 1);
+        assert_eq!(outcome.annotations[0].annotation_level, "warning");
+        assert!(outcome.summary.contains("Not checked"));
+
+        let outcome = build_check_outcome(&review(Vec::new()), None, &settings(), &roots);
+        assert_eq!(outcome.conclusion, "success");
+        assert!(outcome.annotations.is_empty());
+        assert_eq!(outcome.title, "medium risk, 1 file(s) reviewed");
+    }
+}
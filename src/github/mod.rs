@@ -0,0 +1,13 @@
+//! GitHub App integration.
+//!
+//! Installed on a repository, the App receives its pull request webhooks
+//! (`POST /api/github/webhook`) and posts a check run on each head commit:
+//! the graph review of the diff ([`crate::orchestrator::review`]) and the
+//! diff-scoped budget check ([`crate::orchestrator::diff_sync`]), with
+//! violations annotated on the changed lines.
+//!
+//! - [`app`] — App authentication and the REST calls
+//! - [`checks`] — webhook parsing, check output and the check run lifecycle
+
+pub mod app;
+pub mod checks;
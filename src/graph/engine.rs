@@ -118,7 +118,8 @@ impl GraphAnalyticsEngine {
     pub fn new(store: Arc<dyn GraphStore>, config: AnalyticsConfig) -> Self {
         Self {
             store: store.clone(),
-            extractor: GraphExtractor::new(store.clone()),
+            extractor: GraphExtractor::new(store.clone())
+                .with_min_call_confidence(config.min_call_confidence),
             writer: AnalyticsWriter::new(store),
             config,
             enricher: Arc::new(NoopCommunityEnricher),
@@ -133,7 +134,8 @@ impl GraphAnalyticsEngine {
     ) -> Self {
         Self {
             store: store.clone(),
-            extractor: GraphExtractor::new(store.clone()),
+            extractor: GraphExtractor::new(store.clone())
+                .with_min_call_confidence(config.min_call_confidence),
             writer: AnalyticsWriter::new(store),
             config,
             enricher,
@@ -438,8 +440,10 @@ mod tests {
                 path: path.clone(),
                 language: "rust".to_string(),
                 hash: "abc123".to_string(),
+                line_count: 0,
                 last_parsed: chrono::Utc::now(),
                 project_id: Some(project_id),
+                encoding: None,
             };
             store.upsert_file(&file).await.unwrap();
             store
@@ -667,8 +671,10 @@ mod tests {
             path: "src/lonely.rs".to_string(),
             language: "rust".to_string(),
             hash: "abc".to_string(),
+            line_count: 0,
             last_parsed: chrono::Utc::now(),
             project_id: Some(project.id),
+            encoding: None,
         };
         store.upsert_file(&file).await.unwrap();
         store
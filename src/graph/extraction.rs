@@ -38,12 +38,23 @@ fn parse_qualified_id(id: &str) -> (Option<String>, String) {
 /// one for nodes, one for edges.
 pub struct GraphExtractor {
     store: Arc<dyn GraphStore>,
+    min_call_confidence: f64,
 }
 
 impl GraphExtractor {
     /// Create a new extractor backed by the given GraphStore.
     pub fn new(store: Arc<dyn GraphStore>) -> Self {
-        Self { store }
+        Self {
+            store,
+            min_call_confidence: 0.0,
+        }
+    }
+
+    /// Leave out of the function graph the CALLS edges whose confidence is
+    /// below `min_confidence` (default: 0.0, every edge is kept).
+    pub fn with_min_call_confidence(mut self, min_confidence: f64) -> Self {
+        self.min_call_confidence = min_confidence;
+        self
     }
 
     /// Extract a file-level graph for a project.
@@ -305,12 +316,16 @@ impl GraphExtractor {
     ///
     /// - Nodes: all functions that appear in CALLS relationships
     /// - Edges: `CALLS` relationships between functions (within the same project)
+    ///   with a confidence of at least the extractor's `min_call_confidence`
     ///
     /// Functions that neither call nor are called by anything are excluded
     /// (they have no edges and would be isolated nodes with no analytics value).
     pub async fn extract_function_graph(&self, project_id: Uuid) -> Result<CodeGraph> {
         // Fetch all call edges in bulk
-        let edges = self.store.get_project_call_edges(project_id).await?;
+        let edges = self
+            .store
+            .get_project_call_edges(project_id, self.min_call_confidence)
+            .await?;
 
         // Build the graph: nodes are discovered from edges
         let mut graph = CodeGraph::with_capacity(edges.len(), edges.len());
@@ -374,8 +389,10 @@ mod tests {
                 path: path.to_string(),
                 language: "rust".to_string(),
                 hash: "abc123".to_string(),
+                line_count: 0,
                 last_parsed: chrono::Utc::now(),
                 project_id: Some(project_id),
+                encoding: None,
             };
             store.upsert_file(&file).await.unwrap();
             // Register in project_files (upsert_file doesn't always do this)
@@ -522,7 +539,8 @@ mod tests {
 
         seed_functions_and_calls(&store, project.id, "src/main.rs", &funcs, &calls).await;
 
-        let extractor = GraphExtractor::new(Arc::new(store));
+        let store: Arc<dyn GraphStore> = Arc::new(store);
+        let extractor = GraphExtractor::new(store.clone());
         let graph = extractor.extract_function_graph(project.id).await.unwrap();
 
         assert_eq!(graph.node_count(), 8);
@@ -531,6 +549,11 @@ mod tests {
         // Verify specific nodes exist (function IDs are now "file_path:name:line_start")
         assert!(graph.get_node("src/main.rs:main:0").is_some());
         assert!(graph.get_node("src/main.rs:respond:70").is_some());
+
+        // The mock records calls at the default 0.50 confidence
+        let confident = GraphExtractor::new(store).with_min_call_confidence(0.7);
+        let graph = confident.extract_function_graph(project.id).await.unwrap();
+        assert_eq!(graph.node_count(), 0);
     }
 
     #[tokio::test]
@@ -577,6 +600,7 @@ mod tests {
                             docstring: None,
                             parent_class: None,
                             interfaces: vec![],
+                            fields: vec![],
                         })
                         .await
                         .unwrap();
@@ -606,6 +630,7 @@ mod tests {
                         docstring: None,
                         parent_class: None,
                         interfaces: vec![],
+                        fields: vec![],
                     })
                     .await
                     .unwrap();
@@ -602,6 +602,9 @@ pub struct AnalyticsConfig {
     /// Optional large-graph mode for Louvain (None = classic mode, Some = adaptive)
     #[serde(default)]
     pub large_graph: Option<LargeGraphConfig>,
+    /// CALLS edges below this confidence are not extracted (default: 0.0)
+    #[serde(default)]
+    pub min_call_confidence: f64,
 }
 
 impl Default for AnalyticsConfig {
@@ -614,6 +617,7 @@ impl Default for AnalyticsConfig {
             louvain_max_iterations: 100,
             god_function_percentile: 0.95,
             large_graph: None,
+            min_call_confidence: 0.0,
         }
     }
 }
@@ -29,7 +29,7 @@ impl HeartbeatCheck for ConventionGuardCheck {
     async fn run(&self, ctx: &HeartbeatContext) -> Result<()> {
         let projects = ctx.graph.list_projects().await?;
 
-        for project in &projects {
+        for project in projects.iter().filter(|p| !p.archived) {
             // Get structural drift report
             let drift_report = match ctx
                 .graph
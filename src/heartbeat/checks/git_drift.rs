@@ -27,7 +27,7 @@ impl HeartbeatCheck for GitDriftCheck {
     async fn run(&self, ctx: &HeartbeatContext) -> Result<()> {
         let projects = ctx.graph.list_projects().await?;
 
-        for project in &projects {
+        for project in projects.iter().filter(|p| !p.archived) {
             let root_path = &project.root_path;
 
             // git fetch (quiet, timeout handled by engine)
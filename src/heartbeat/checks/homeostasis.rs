@@ -89,7 +89,7 @@ impl HeartbeatCheck for HomeostasisCheck {
     async fn run(&self, ctx: &HeartbeatContext) -> Result<()> {
         let projects = ctx.graph.list_projects().await?;
 
-        for project in &projects {
+        for project in projects.iter().filter(|p| !p.archived) {
             debug!("HomeostasisCheck: evaluating project '{}'", project.name);
 
             // 1. Compute homeostasis report from the graph
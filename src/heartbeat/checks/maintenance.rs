@@ -43,7 +43,7 @@ impl HeartbeatCheck for MaintenanceCheck {
         let projects = ctx.graph.list_projects().await?;
         let config = SkillMaintenanceConfig::default();
 
-        for project in &projects {
+        for project in projects.iter().filter(|p| !p.archived) {
             info!(
                 "MaintenanceCheck: running deep maintenance for '{}'",
                 project.name
@@ -9,6 +9,9 @@ pub mod convention_guard;
 pub mod git_drift;
 pub mod homeostasis;
 pub mod maintenance;
+pub mod note_federation;
+pub mod sentry;
 pub mod staleness;
 pub mod synapse_decay;
 pub mod synapse_replenish;
+pub mod vulnerability_audit;
@@ -0,0 +1,79 @@
+//! NoteFederationCheck — mirrors the global notes of remote instances.
+//!
+//! Pulls every source of the `note_federation` config section and syncs its
+//! active global notes locally (see `notes::federation`). A failing source is
+//! logged and retried on the next run.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tracing::{debug, warn};
+
+use crate::heartbeat::{HeartbeatCheck, HeartbeatContext};
+use crate::notes::federation::{self, NoteFederationConfig};
+
+/// Sync federated notes (every `note_federation.interval_secs`, default 1 hour).
+pub struct NoteFederationCheck {
+    config: NoteFederationConfig,
+    http: reqwest::Client,
+}
+
+impl NoteFederationCheck {
+    pub fn new(config: NoteFederationConfig) -> Self {
+        Self {
+            config,
+            http: federation::http_client(),
+        }
+    }
+}
+
+#[async_trait]
+impl HeartbeatCheck for NoteFederationCheck {
+    fn name(&self) -> &str {
+        "note_federation"
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.config.interval_secs.max(60))
+    }
+
+    fn timeout_override(&self) -> Option<Duration> {
+        // Paginated fetches from every source plus one write per changed note
+        Some(Duration::from_secs(120))
+    }
+
+    async fn run(&self, ctx: &HeartbeatContext) -> Result<()> {
+        for source in &self.config.sources {
+            let notes = match federation::fetch_remote_notes(&self.http, source).await {
+                Ok(notes) => notes,
+                Err(e) => {
+                    warn!(
+                        "NoteFederationCheck: fetch failed for '{}': {:#}",
+                        source.name, e
+                    );
+                    continue;
+                }
+            };
+
+            match federation::sync_source(
+                ctx.graph.as_ref(),
+                ctx.search.as_deref(),
+                source,
+                &notes,
+            )
+            .await
+            {
+                Ok(report) => debug!(
+                    "NoteFederationCheck: source '{}': {} notes ({} created, {} updated, {} removed)",
+                    report.source, report.fetched, report.created, report.updated, report.removed
+                ),
+                Err(e) => warn!(
+                    "NoteFederationCheck: sync failed for '{}': {:#}",
+                    source.name, e
+                ),
+            }
+        }
+        Ok(())
+    }
+}
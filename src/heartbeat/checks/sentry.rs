@@ -0,0 +1,82 @@
+//! SentryCheck — polls the open production errors of Sentry projects.
+//!
+//! For every project of the `sentry` config section, replaces the stored
+//! open issues with the unresolved ones in Sentry, linked to the functions
+//! on their stack (see `sentry::sync_project`). A failing project is logged
+//! and retried on the next run.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tracing::{debug, warn};
+
+use crate::heartbeat::{HeartbeatCheck, HeartbeatContext};
+use crate::sentry::client::SentryClient;
+use crate::sentry::{self, SentryConfig};
+
+/// Poll Sentry issues (every `sentry.interval_secs`, default 15 minutes).
+pub struct SentryCheck {
+    config: SentryConfig,
+    client: SentryClient,
+}
+
+impl SentryCheck {
+    /// Check polling the configured organization, `None` without a token.
+    pub fn new(config: SentryConfig) -> Option<Self> {
+        let client = SentryClient::from_config(&config)?;
+        Some(Self { config, client })
+    }
+}
+
+#[async_trait]
+impl HeartbeatCheck for SentryCheck {
+    fn name(&self) -> &str {
+        "sentry"
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.config.interval_secs.max(60))
+    }
+
+    fn timeout_override(&self) -> Option<Duration> {
+        // One issue list plus up to 25 event fetches per Sentry project
+        Some(Duration::from_secs(120))
+    }
+
+    async fn run(&self, ctx: &HeartbeatContext) -> Result<()> {
+        for link in &self.config.projects {
+            match sentry::sync_project(ctx.graph.as_ref(), &self.client, link).await {
+                Ok(report) => debug!(
+                    "SentryCheck: '{}': {} open issues ({} new, {} linked to code)",
+                    report.sentry_project, report.issues, report.new_issues, report.linked_issues
+                ),
+                Err(e) => warn!(
+                    "SentryCheck: sync failed for '{}': {:#}",
+                    link.sentry_project, e
+                ),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sentry_check() {
+        assert!(SentryCheck::new(SentryConfig::default()).is_none());
+
+        let check = SentryCheck::new(SentryConfig {
+            token: Some("secret".to_string()),
+            organization: "acme".to_string(),
+            interval_secs: 10,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(check.name(), "sentry");
+        assert_eq!(check.interval(), Duration::from_secs(60));
+    }
+}
@@ -0,0 +1,162 @@
+//! VulnerabilityAuditCheck — audits locked dependencies against OSV.
+//!
+//! For every project, matches the `(:Package)` versions recorded at sync
+//! against the OSV database and replaces the project's advisories.
+//! Newly found advisories emit `Advisory` Created events and one alert per
+//! project.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tracing::{debug, warn};
+
+use crate::audit::osv::OsvClient;
+use crate::heartbeat::{HeartbeatCheck, HeartbeatContext};
+use crate::neo4j::models::{AdvisorySeverity, AlertNode, AlertSeverity};
+
+/// Audit project dependencies for known vulnerabilities (every 6 hours).
+pub struct VulnerabilityAuditCheck {
+    osv: OsvClient,
+}
+
+impl VulnerabilityAuditCheck {
+    /// Check querying `OSV_API_URL` (default: the public OSV API).
+    pub fn new() -> Self {
+        Self::with_client(OsvClient::from_env())
+    }
+
+    pub fn with_client(osv: OsvClient) -> Self {
+        Self { osv }
+    }
+}
+
+impl Default for VulnerabilityAuditCheck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl HeartbeatCheck for VulnerabilityAuditCheck {
+    fn name(&self) -> &str {
+        "vulnerability_audit"
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(6 * 60 * 60) // 6 hours
+    }
+
+    fn timeout_override(&self) -> Option<Duration> {
+        // One OSV batch query per project plus advisory fetches
+        Some(Duration::from_secs(120))
+    }
+
+    async fn run(&self, ctx: &HeartbeatContext) -> Result<()> {
+        let projects = ctx.graph.list_projects().await?;
+
+        for project in projects.iter().filter(|p| !p.archived) {
+            let report = match crate::audit::audit_project(
+                ctx.graph.as_ref(),
+                &self.osv,
+                project.id,
+            )
+            .await
+            {
+                Ok(r) => r,
+                Err(e) => {
+                    warn!(
+                        "VulnerabilityAuditCheck: audit failed for '{}': {}",
+                        project.name, e
+                    );
+                    continue;
+                }
+            };
+
+            debug!(
+                "VulnerabilityAuditCheck: project '{}': {} packages, {} advisories ({} new)",
+                project.name,
+                report.packages_checked,
+                report.advisories.len(),
+                report.new_advisories.len()
+            );
+
+            if report.new_advisories.is_empty() {
+                continue;
+            }
+
+            if let Some(ref emitter) = ctx.emitter {
+                for adv in &report.new_advisories {
+                    emitter.emit_created(
+                        crate::events::EntityType::Advisory,
+                        &adv.advisory.id,
+                        serde_json::json!({
+                            "summary": adv.advisory.summary,
+                            "severity": adv.advisory.severity,
+                            "package": adv.package,
+                            "version": adv.version,
+                            "fixed_versions": adv.fixed_versions,
+                        }),
+                        Some(project.id.to_string()),
+                    );
+                }
+            }
+
+            let worst = report
+                .new_advisories
+                .iter()
+                .map(|a| a.advisory.severity)
+                .max()
+                .unwrap_or(AdvisorySeverity::Unknown);
+            let severity = match worst {
+                AdvisorySeverity::Critical | AdvisorySeverity::High => AlertSeverity::Critical,
+                AdvisorySeverity::Medium => AlertSeverity::Warning,
+                _ => AlertSeverity::Info,
+            };
+            let ids: Vec<&str> = report
+                .new_advisories
+                .iter()
+                .map(|a| a.advisory.id.as_str())
+                .collect();
+            let alert = AlertNode::new(
+                "vulnerability_audit".to_string(),
+                severity,
+                format!(
+                    "Project '{}' has {} new security advisor{} (worst: {}): {}",
+                    project.name,
+                    ids.len(),
+                    if ids.len() == 1 { "y" } else { "ies" },
+                    worst,
+                    ids.join(", ")
+                ),
+                Some(project.id),
+            );
+            if let Err(e) = ctx.graph.create_alert(&alert).await {
+                warn!(
+                    "VulnerabilityAuditCheck: failed to create alert for '{}': {}",
+                    project.name, e
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vulnerability_audit_check_name() {
+        let check = VulnerabilityAuditCheck::with_client(OsvClient::new("http://localhost"));
+        assert_eq!(check.name(), "vulnerability_audit");
+    }
+
+    #[test]
+    fn test_vulnerability_audit_check_interval() {
+        let check = VulnerabilityAuditCheck::with_client(OsvClient::new("http://localhost"));
+        assert_eq!(check.interval(), Duration::from_secs(21600));
+        assert_eq!(check.timeout_override(), Some(Duration::from_secs(120)));
+    }
+}
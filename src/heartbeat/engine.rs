@@ -8,7 +8,7 @@ use std::time::{Duration, Instant};
 use tokio::sync::watch;
 use tracing::{debug, info, warn};
 
-use crate::events::EventEmitter;
+use crate::events::{EventEmitter, Leadership};
 use crate::meilisearch::SearchStore;
 use crate::neo4j::traits::GraphStore;
 
@@ -26,6 +26,8 @@ pub struct HeartbeatEngine {
     emitter: Option<Arc<dyn EventEmitter>>,
     checks: Vec<Box<dyn HeartbeatCheck>>,
     tick_interval: Duration,
+    /// Checks only run while this instance leads
+    leadership: Leadership,
     shutdown_tx: watch::Sender<bool>,
     shutdown_rx: watch::Receiver<bool>,
 }
@@ -45,11 +47,18 @@ impl HeartbeatEngine {
             emitter,
             checks,
             tick_interval: Duration::from_secs(30),
+            leadership: Leadership::always(),
             shutdown_tx,
             shutdown_rx,
         }
     }
 
+    /// Only run checks while `leadership` holds, so replicas don't repeat them.
+    pub fn with_leadership(mut self, leadership: Leadership) -> Self {
+        self.leadership = leadership;
+        self
+    }
+
     /// Start the engine, consuming it. Spawns a tokio task and returns
     /// a shutdown handle. The engine is leaked (like ScheduleProvider)
     /// so it runs for the lifetime of the process.
@@ -59,6 +68,7 @@ impl HeartbeatEngine {
         let emitter = self.emitter;
         let checks = self.checks;
         let tick_interval = self.tick_interval;
+        let leadership = self.leadership;
         let shutdown_tx = self.shutdown_tx;
         let mut shutdown_rx = self.shutdown_rx;
 
@@ -83,6 +93,9 @@ impl HeartbeatEngine {
             loop {
                 tokio::select! {
                     _ = interval.tick() => {
+                        if !leadership.is_leader() {
+                            continue;
+                        }
                         let now = Instant::now();
 
                         for (i, check) in checks.iter().enumerate() {
@@ -252,6 +265,24 @@ mod tests {
         assert!(count.load(Ordering::SeqCst) >= 1);
     }
 
+    #[tokio::test]
+    async fn test_engine_follower_skips_checks() {
+        let graph = Arc::new(MockGraphStore::new());
+        let count = Arc::new(AtomicU32::new(0));
+        let checks: Vec<Box<dyn HeartbeatCheck>> = vec![Box::new(CountingCheck {
+            count: count.clone(),
+        })];
+
+        let mut engine =
+            HeartbeatEngine::new(graph, None, None, checks).with_leadership(Leadership::follower());
+        engine.tick_interval = Duration::from_millis(10);
+        let handle = engine.start_owned();
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        handle.shutdown();
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+    }
+
     #[tokio::test]
     async fn test_engine_failing_check_doesnt_crash() {
         let graph = Arc::new(MockGraphStore::new());
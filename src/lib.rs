@@ -12,23 +12,37 @@
 
 pub mod analytics;
 pub mod api;
+pub mod audit;
 pub mod auth;
 pub mod chat;
+pub mod cli_bench;
+pub mod cli_graph;
+pub mod cli_import;
+pub mod cli_search;
+pub mod cli_snapshot;
+pub mod completions;
+pub mod config_secrets;
+pub mod config_validation;
+pub mod doctor;
 pub mod embeddings;
 pub mod episodes;
 pub mod events;
 pub mod feedback;
+pub mod github;
 pub mod graph;
 pub mod heartbeat;
 pub mod homeostasis;
 pub mod identity;
 pub mod lifecycle;
+pub mod logging;
+pub mod lsp;
 pub mod mcp;
 pub mod mcp_federation;
 pub mod meilisearch;
 pub mod neo4j;
 pub mod neurons;
 pub mod notes;
+pub mod notifications;
 pub mod orchestrator;
 pub mod parser;
 pub mod pipeline;
@@ -40,15 +54,19 @@ pub mod reception;
 pub mod reflex;
 pub mod resolver;
 pub mod runner;
+pub mod sentry;
 pub mod setup_claude;
 pub mod sharing;
 pub mod skills;
 pub mod transport;
+pub mod tui;
 pub mod update;
 pub(crate) mod utils;
 
 #[cfg(test)]
 pub(crate) mod test_helpers;
+#[cfg(any(test, feature = "testkit"))]
+pub mod testkit;
 
 use anyhow::Result;
 use serde::Deserialize;
@@ -90,6 +108,7 @@ pub struct YamlConfig {
     pub neo4j: Neo4jYamlConfig,
     pub meilisearch: MeilisearchYamlConfig,
     pub nats: NatsYamlConfig,
+    pub ipc: IpcYamlConfig,
     pub chat: ChatYamlConfig,
     pub embeddings: EmbeddingsYamlConfig,
     /// Auth section — if absent, auth_config will be None (deny-by-default)
@@ -109,6 +128,76 @@ pub struct YamlConfig {
     /// MCP Federation section (optional — connect to external MCP servers)
     #[serde(default)]
     pub mcp_federation: McpFederationConfig,
+    /// Logging section (optional — output format and per-target levels)
+    #[serde(default)]
+    pub logging: logging::LoggingYamlConfig,
+    /// Self-update section (optional — release channel, signing key)
+    #[serde(default)]
+    pub update: update::UpdateYamlConfig,
+    /// Note federation section (optional — pull global notes from other instances)
+    #[serde(default)]
+    pub note_federation: notes::federation::NoteFederationConfig,
+    /// Usage telemetry section (optional — opt-in local feature counters)
+    #[serde(default)]
+    pub telemetry: TelemetryYamlConfig,
+    /// Notifications section (optional — email and Slack messages on events)
+    #[serde(default)]
+    pub notifications: notifications::NotificationsConfig,
+    /// Language servers section (optional — resolve CALLS edges after syncs)
+    #[serde(default)]
+    pub lsp: lsp::LspConfig,
+    /// Grammar plugins section (optional — tree-sitter WASM grammars)
+    #[serde(default)]
+    pub grammars: parser::plugins::GrammarsConfig,
+    /// Graph analytics section (optional — call graph confidence threshold)
+    #[serde(default)]
+    pub analytics: AnalyticsYamlConfig,
+    /// Sentry section (optional — poll open production errors)
+    #[serde(default)]
+    pub sentry: sentry::SentryConfig,
+}
+
+/// Graph analytics configuration section.
+///
+/// CALLS edges below `min_call_confidence` are left out of the function
+/// graph the analytics (PageRank, communities, processes) are computed on.
+/// The default keeps every edge.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct AnalyticsYamlConfig {
+    /// Minimum confidence of the CALLS edges analysed, 0.0-1.0 (default: 0.0)
+    pub min_call_confidence: f64,
+}
+
+impl AnalyticsYamlConfig {
+    /// Check the threshold is a confidence.
+    pub fn check(&self) -> Result<(), String> {
+        check_confidence(self.min_call_confidence)
+    }
+}
+
+fn check_confidence(value: f64) -> Result<(), String> {
+    if (0.0..=1.0).contains(&value) {
+        Ok(())
+    } else {
+        Err(format!(
+            "min_call_confidence {} is not within 0.0-1.0",
+            value
+        ))
+    }
+}
+
+/// Usage telemetry configuration section.
+///
+/// When enabled, the server counts the API routes it serves per day and
+/// client (REST or MCP) — route patterns only, never parameters, bodies or
+/// users — and keeps the counters in its own Neo4j for the admin dashboard.
+/// Nothing is sent anywhere.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct TelemetryYamlConfig {
+    /// Opt in to usage counters (default: false)
+    pub enabled: bool,
 }
 
 /// MCP Federation configuration section.
@@ -183,6 +272,8 @@ fn default_true() -> bool {
 #[derive(Debug, Clone, Deserialize)]
 #[serde(default)]
 pub struct ServerYamlConfig {
+    /// Address the server listens on (default: "0.0.0.0")
+    pub host: String,
     pub port: u16,
     pub workspace_path: String,
     /// Whether the backend should serve the frontend static files (default: true)
@@ -193,16 +284,21 @@ pub struct ServerYamlConfig {
     /// Used for frontend_url, redirect_uri, and CORS when present.
     #[serde(default)]
     pub public_url: Option<String>,
+    /// Reject every mutating request with 423 Locked (default: false)
+    #[serde(default)]
+    pub read_only: bool,
 }
 
 impl Default for ServerYamlConfig {
     fn default() -> Self {
         Self {
+            host: "0.0.0.0".into(),
             port: 8080,
             workspace_path: ".".into(),
             serve_frontend: true,
             frontend_path: "./dist".into(),
             public_url: None,
+            read_only: false,
         }
     }
 }
@@ -214,6 +310,10 @@ pub struct Neo4jYamlConfig {
     pub uri: String,
     pub user: String,
     pub password: String,
+    /// Longest a query may run, in milliseconds (0: unbounded)
+    pub query_timeout_ms: u64,
+    /// Queries running at least this long are logged as slow, in milliseconds
+    pub slow_query_ms: u64,
 }
 
 impl Default for Neo4jYamlConfig {
@@ -222,6 +322,8 @@ impl Default for Neo4jYamlConfig {
             uri: "bolt://localhost:7687".into(),
             user: "neo4j".into(),
             password: "orchestrator123".into(),
+            query_timeout_ms: neo4j::query_guard::DEFAULT_QUERY_TIMEOUT.as_millis() as u64,
+            slow_query_ms: neo4j::query_guard::DEFAULT_SLOW_QUERY_THRESHOLD.as_millis() as u64,
         }
     }
 }
@@ -232,6 +334,8 @@ impl Default for Neo4jYamlConfig {
 pub struct MeilisearchYamlConfig {
     pub url: String,
     pub key: String,
+    /// File of the write-behind code indexing queue
+    pub index_queue_path: Option<String>,
 }
 
 impl Default for MeilisearchYamlConfig {
@@ -239,6 +343,7 @@ impl Default for MeilisearchYamlConfig {
         Self {
             url: "http://localhost:7700".into(),
             key: "orchestrator-meili-key-change-me".into(),
+            index_queue_path: None,
         }
     }
 }
@@ -249,6 +354,21 @@ impl Default for MeilisearchYamlConfig {
 pub struct NatsYamlConfig {
     /// NATS server URL (e.g. "nats://localhost:4222")
     pub url: Option<String>,
+    /// Deliver CRUD events through a JetStream stream (default: false)
+    pub jetstream: bool,
+    /// Name of this instance's durable JetStream consumer
+    pub consumer: Option<String>,
+}
+
+/// Local IPC configuration section (optional — NATS-free event sync between
+/// the processes of one machine, e.g. the desktop app)
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct IpcYamlConfig {
+    /// Sync events over a local socket when NATS is not configured (default: false)
+    pub enabled: bool,
+    /// Socket path, or named pipe on Windows (default: see [`events::ipc::default_ipc_path`])
+    pub path: Option<String>,
 }
 
 /// Chat configuration section (YAML only — ChatConfig in chat/config.rs handles full setup)
@@ -273,6 +393,15 @@ pub struct ChatYamlConfig {
     pub auto_update_cli: Option<bool>,
     /// Enable automatic Tauri application updates on startup (default: true).
     pub auto_update_app: Option<bool>,
+    /// Execution sandbox applied to every chat session (sessions may tighten it).
+    #[serde(default)]
+    pub sandbox: Option<chat::sandbox::SandboxConfig>,
+    /// Resource limits applied to every chat session (sessions may lower them).
+    #[serde(default)]
+    pub limits: Option<chat::limits::SessionLimits>,
+    /// Model routing rules by task type, with per-project overrides.
+    #[serde(default)]
+    pub model_routing: Option<chat::model_routing::ModelRoutingConfig>,
 }
 
 /// Embedding provider configuration section.
@@ -321,7 +450,8 @@ pub struct EmbeddingsYamlConfig {
 #[derive(Debug, Clone, Deserialize)]
 pub struct AuthConfig {
     // ── Common fields ──────────────────────────────────────────────────
-    /// JWT signing secret (HS256, minimum 32 characters)
+    /// JWT signing secret (HS256, minimum 32 characters).
+    /// Can be supplied as `jwt_secret_file` instead (see [`config_secrets`]).
     pub jwt_secret: String,
     /// JWT access token lifetime in seconds (default: 900 = 15min).
     /// Short-lived: renewed via the refresh token cookie.
@@ -444,6 +574,45 @@ const GOOGLE_TOKEN_ENDPOINT: &str = "https://oauth2.googleapis.com/token";
 const GOOGLE_USERINFO_ENDPOINT: &str = "https://www.googleapis.com/oauth2/v3/userinfo";
 
 impl AuthConfig {
+    /// Build an auth section from env vars, for deployments without a
+    /// config.yaml (headless profile).
+    ///
+    /// Returns `None` when `JWT_SECRET` (or `JWT_SECRET_FILE`) is unset. A root
+    /// account is configured when `ROOT_ACCOUNT_EMAIL` and
+    /// `ROOT_ACCOUNT_PASSWORD` (or `ROOT_ACCOUNT_PASSWORD_FILE`) are both set;
+    /// `ROOT_ACCOUNT_NAME` defaults to "Admin". OIDC still requires config.yaml.
+    pub fn from_env() -> Result<Option<Self>> {
+        let Some(jwt_secret) = config_secrets::env_secret("JWT_SECRET")? else {
+            return Ok(None);
+        };
+        let root_account = match (
+            std::env::var("ROOT_ACCOUNT_EMAIL").ok(),
+            config_secrets::env_secret("ROOT_ACCOUNT_PASSWORD")?,
+        ) {
+            (Some(email), Some(password_hash)) => Some(RootAccountConfig {
+                email,
+                name: std::env::var("ROOT_ACCOUNT_NAME").unwrap_or_else(|_| "Admin".into()),
+                password_hash,
+            }),
+            _ => None,
+        };
+        Ok(Some(Self {
+            jwt_secret,
+            access_token_expiry_secs: default_access_token_expiry(),
+            refresh_token_expiry_secs: default_refresh_token_expiry(),
+            allowed_email_domain: None,
+            allowed_emails: None,
+            frontend_url: std::env::var("AUTH_FRONTEND_URL").ok(),
+            additional_origins: vec![],
+            allow_registration: false,
+            root_account,
+            oidc: None,
+            google_client_id: None,
+            google_client_secret: None,
+            google_redirect_uri: None,
+        }))
+    }
+
     /// Returns the effective OIDC config, preferring the explicit `oidc` section
     /// and falling back to legacy `google_*` fields for backward compatibility.
     pub fn effective_oidc(&self) -> Option<OidcConfig> {
@@ -487,6 +656,14 @@ impl AuthConfig {
         self.effective_oidc().is_some()
     }
 
+    /// Returns true if `user_id` is the root account's (deterministic UUID
+    /// from its email).
+    pub fn is_root_user(&self, user_id: uuid::Uuid) -> bool {
+        self.root_account.as_ref().is_some_and(|root| {
+            uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_URL, root.email.as_bytes()) == user_id
+        })
+    }
+
     /// Check whether a given email is allowed by the configured restrictions.
     ///
     /// An email passes if **any** of these conditions hold:
@@ -555,11 +732,40 @@ pub struct Config {
     pub neo4j_uri: String,
     pub neo4j_user: String,
     pub neo4j_password: String,
+    /// Longest a Neo4j query may run, in milliseconds (0: unbounded).
+    /// Priority: env var (NEO4J_QUERY_TIMEOUT_MS) > YAML (neo4j.query_timeout_ms) > 60000.
+    pub neo4j_query_timeout_ms: u64,
+    /// Neo4j queries running at least this long are logged as slow.
+    /// Priority: env var (NEO4J_SLOW_QUERY_THRESHOLD_MS) > YAML (neo4j.slow_query_ms) > 100.
+    pub neo4j_slow_query_ms: u64,
     pub meilisearch_url: String,
     pub meilisearch_key: String,
+    /// File of the durable queue of code documents waiting to be indexed.
+    /// Priority: env var (MEILISEARCH_INDEX_QUEUE_PATH) > YAML (meilisearch.index_queue_path)
+    /// > None (`index-queue.redb` in the platform data directory, see [`Config::index_queue_path`]).
+    /// An empty path disables the queue: code is indexed during the sync.
+    pub meilisearch_index_queue_path: Option<String>,
     /// NATS server URL (optional — enables inter-process event sync)
     pub nats_url: Option<String>,
+    /// Deliver CRUD events through JetStream so instances catch up after downtime.
+    /// Priority: env var (NATS_JETSTREAM) > YAML (nats.jetstream) > false
+    pub nats_jetstream: bool,
+    /// Durable JetStream consumer of this instance.
+    /// Priority: env var (NATS_CONSUMER) > YAML (nats.consumer)
+    /// > None (derived from the host and port, see [`Config::nats_consumer_name`])
+    pub nats_consumer: Option<String>,
+    /// Sync events with the other processes of this machine over a local
+    /// socket when NATS is not configured.
+    /// Priority: env var (IPC_ENABLED) > YAML (ipc.enabled) > false
+    pub ipc_enabled: bool,
+    /// Socket of the local IPC event bridge.
+    /// Priority: env var (IPC_PATH) > YAML (ipc.path)
+    /// > None (platform default, see [`Config::ipc_socket_path`])
+    pub ipc_path: Option<String>,
     pub workspace_path: String,
+    /// Address the server listens on.
+    /// Priority: env var (SERVER_HOST) > YAML (server.host) > "0.0.0.0".
+    pub server_host: String,
     pub server_port: u16,
     /// Auth config — None means deny-by-default (no auth section in YAML)
     pub auth_config: Option<AuthConfig>,
@@ -570,6 +776,10 @@ pub struct Config {
     /// Public URL for reverse-proxy setups (e.g. https://ffs.dev).
     /// Used for CORS and OAuth origin whitelist.
     pub public_url: Option<String>,
+    /// Read-only mode: mutating REST calls (and so MCP and chat tool writes,
+    /// which go through the API) are rejected with 423, and projects are not
+    /// auto-watched. Priority: env var (READ_ONLY) > YAML (server.read_only) > false.
+    pub read_only: bool,
     /// Chat permission config from YAML (if present).
     /// Priority: YAML > env vars > defaults.
     pub chat_permissions: Option<chat::config::PermissionConfig>,
@@ -594,6 +804,12 @@ pub struct Config {
     /// Enable automatic Tauri application updates on startup.
     /// Priority: env var (CHAT_AUTO_UPDATE_APP) > YAML (chat.auto_update_app) > None (true).
     pub chat_auto_update_app: Option<bool>,
+    /// Execution sandbox of chat sessions from YAML (chat.sandbox), if present.
+    pub chat_sandbox: Option<chat::sandbox::SandboxConfig>,
+    /// Resource limits of chat sessions from YAML (chat.limits), if present.
+    pub chat_limits: Option<chat::limits::SessionLimits>,
+    /// Model routing rules of chat sessions from YAML (chat.model_routing), if present.
+    pub chat_model_routing: Option<chat::model_routing::ModelRoutingConfig>,
 
     // ── Embedding provider config ────────────────────────────────────────
     /// Embedding provider type: "local", "http", or "disabled".
@@ -639,6 +855,62 @@ pub struct Config {
     /// Resolved path to the config.yaml file that was loaded (if any).
     /// Used for persisting runtime changes back to disk.
     pub config_yaml_path: Option<std::path::PathBuf>,
+
+    // ── Self-update ──────────────────────────────────────────────────────
+    /// Release channel for `orchestrator update`.
+    /// Priority: env var (UPDATE_CHANNEL) > YAML (update.channel) > stable.
+    pub update_channel: update::UpdateChannel,
+    /// Minisign public key trusted for release signatures, overriding the
+    /// key compiled into the binary.
+    /// Priority: env var (UPDATE_PUBLIC_KEY) > YAML (update.public_key).
+    pub update_public_key: Option<String>,
+
+    // ── Note federation ──────────────────────────────────────────────────
+    /// Remote instances whose global notes are mirrored read-only.
+    /// YAML only (note_federation).
+    pub note_federation: notes::federation::NoteFederationConfig,
+
+    // ── Usage telemetry ──────────────────────────────────────────────────
+    /// Count the API routes served, for `GET /api/admin/usage`.
+    /// Priority: env var (TELEMETRY_ENABLED) > YAML (telemetry.enabled) > false.
+    pub telemetry_enabled: bool,
+
+    // ── Notifications ────────────────────────────────────────────────────
+    /// Channels and event rules of the notifier.
+    /// YAML only (notifications).
+    pub notifications: notifications::NotificationsConfig,
+    /// Language servers resolving name-based CALLS / USES_TYPE after syncs.
+    /// YAML only (lsp).
+    pub lsp: lsp::LspConfig,
+    /// Tree-sitter grammars loaded at runtime, next to the built-in ones.
+    /// YAML only (grammars).
+    pub grammars: parser::plugins::GrammarsConfig,
+
+    // ── Graph analytics ──────────────────────────────────────────────────
+    /// Minimum confidence of the CALLS edges extracted for analytics.
+    /// Priority: env var (ANALYTICS_MIN_CALL_CONFIDENCE) > YAML
+    /// (analytics.min_call_confidence) > 0.0.
+    pub analytics_min_call_confidence: f64,
+
+    // ── Sentry ───────────────────────────────────────────────────────────
+    /// Sentry projects whose open issues are polled and linked to code.
+    /// YAML only (sentry).
+    pub sentry: sentry::SentryConfig,
+
+    // ── Logging ──────────────────────────────────────────────────────────
+    /// Log line format.
+    /// Priority: env var (LOG_FORMAT) > YAML (logging.format) > pretty
+    /// (json in headless mode).
+    pub log_format: logging::LogFormat,
+    /// Startup `EnvFilter` directives; adjustable at runtime via
+    /// `PUT /api/admin/log-level`.
+    /// Priority: env var (RUST_LOG) > YAML (logging.level/targets) > default.
+    pub log_filter: String,
+
+    /// Headless (Kubernetes) profile — see [`Config::headless_from_env`].
+    /// Skips desktop-only side effects at startup and shuts down gracefully
+    /// on SIGTERM.
+    pub headless: bool,
 }
 
 impl Config {
@@ -657,17 +929,82 @@ impl Config {
     pub fn from_yaml_and_env(yaml_path: Option<&Path>) -> Result<Self> {
         // 1. Load YAML config (or defaults if file not found)
         let (yaml, resolved_path) = Self::load_yaml_with_path(yaml_path);
+        Self::from_yaml_with_env(yaml, resolved_path)
+    }
+
+    /// Load configuration for the headless (Kubernetes) profile.
+    ///
+    /// Configuration comes from env vars only: config.yaml is not
+    /// auto-detected from the platform config dir or CWD, although an
+    /// explicit `--config` (e.g. a mounted ConfigMap) is still honoured.
+    /// On top of that the profile:
+    /// - marks setup as completed (there is no wizard to run)
+    /// - disables frontend serving and CLI/app auto-updates
+    /// - logs as JSON unless a format is configured explicitly
+    /// - builds the auth section from `JWT_SECRET` / `ROOT_ACCOUNT_*` env vars
+    ///   when config.yaml has none (see [`AuthConfig::from_env`])
+    pub fn headless_from_env(yaml_path: Option<&Path>) -> Result<Self> {
+        let yaml = match yaml_path {
+            Some(path) => Self::try_load_yaml(path),
+            None => YamlConfig::default(),
+        };
+        let explicit_log_format =
+            std::env::var("LOG_FORMAT").is_ok() || yaml.logging.format.is_some();
+        let mut config = Self::from_yaml_with_env(yaml, yaml_path.map(Path::to_path_buf))?;
+        config.headless = true;
+        if !explicit_log_format {
+            config.log_format = logging::LogFormat::Json;
+        }
+        config.setup_completed = true;
+        config.serve_frontend = false;
+        config.chat_auto_update_cli = Some(false);
+        config.chat_auto_update_app = Some(false);
+        if config.auth_config.is_none() {
+            config.auth_config = AuthConfig::from_env()?;
+        }
+        Ok(config)
+    }
+
+    /// Apply env var overrides on top of an already-loaded YAML config.
+    fn from_yaml_with_env(
+        yaml: YamlConfig,
+        resolved_path: Option<std::path::PathBuf>,
+    ) -> Result<Self> {
+        let neo4j_password = config_secrets::env_secret("NEO4J_PASSWORD")?;
+        let meilisearch_key = config_secrets::env_secret("MEILISEARCH_KEY")?;
 
         // 2. Build Config with env var overrides
         Ok(Self {
             setup_completed: yaml.setup_completed,
             neo4j_uri: std::env::var("NEO4J_URI").unwrap_or(yaml.neo4j.uri),
             neo4j_user: std::env::var("NEO4J_USER").unwrap_or(yaml.neo4j.user),
-            neo4j_password: std::env::var("NEO4J_PASSWORD").unwrap_or(yaml.neo4j.password),
+            neo4j_password: neo4j_password.unwrap_or(yaml.neo4j.password),
+            neo4j_query_timeout_ms: std::env::var("NEO4J_QUERY_TIMEOUT_MS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(yaml.neo4j.query_timeout_ms),
+            neo4j_slow_query_ms: std::env::var("NEO4J_SLOW_QUERY_THRESHOLD_MS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(yaml.neo4j.slow_query_ms),
             meilisearch_url: std::env::var("MEILISEARCH_URL").unwrap_or(yaml.meilisearch.url),
-            meilisearch_key: std::env::var("MEILISEARCH_KEY").unwrap_or(yaml.meilisearch.key),
+            meilisearch_key: meilisearch_key.unwrap_or(yaml.meilisearch.key),
+            meilisearch_index_queue_path: std::env::var("MEILISEARCH_INDEX_QUEUE_PATH")
+                .ok()
+                .or(yaml.meilisearch.index_queue_path),
             nats_url: std::env::var("NATS_URL").ok().or(yaml.nats.url),
+            nats_jetstream: std::env::var("NATS_JETSTREAM")
+                .ok()
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(yaml.nats.jetstream),
+            nats_consumer: std::env::var("NATS_CONSUMER").ok().or(yaml.nats.consumer),
+            ipc_enabled: std::env::var("IPC_ENABLED")
+                .ok()
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(yaml.ipc.enabled),
+            ipc_path: std::env::var("IPC_PATH").ok().or(yaml.ipc.path),
             workspace_path: std::env::var("WORKSPACE_PATH").unwrap_or(yaml.server.workspace_path),
+            server_host: std::env::var("SERVER_HOST").unwrap_or(yaml.server.host),
             server_port: std::env::var("SERVER_PORT")
                 .ok()
                 .and_then(|s| s.parse().ok())
@@ -679,6 +1016,10 @@ impl Config {
                 .unwrap_or(yaml.server.serve_frontend),
             frontend_path: std::env::var("FRONTEND_PATH").unwrap_or(yaml.server.frontend_path),
             public_url: std::env::var("PUBLIC_URL").ok().or(yaml.server.public_url),
+            read_only: std::env::var("READ_ONLY")
+                .ok()
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(yaml.server.read_only),
             chat_permissions: yaml.chat.permissions,
             chat_default_model: yaml.chat.default_model,
             chat_max_sessions: yaml.chat.max_sessions,
@@ -698,6 +1039,9 @@ impl Config {
                 .ok()
                 .map(|v| v == "true" || v == "1")
                 .or(yaml.chat.auto_update_app),
+            chat_sandbox: yaml.chat.sandbox,
+            chat_limits: yaml.chat.limits,
+            chat_model_routing: yaml.chat.model_routing,
             // Embedding provider config (env var > YAML > None)
             embedding_provider: std::env::var("EMBEDDING_PROVIDER")
                 .ok()
@@ -753,9 +1097,141 @@ impl Config {
                 nr
             },
             config_yaml_path: resolved_path,
+            update_channel: match std::env::var("UPDATE_CHANNEL") {
+                Ok(v) => v
+                    .parse()
+                    .map_err(|e: String| anyhow::anyhow!("UPDATE_CHANNEL: {}", e))?,
+                Err(_) => yaml.update.channel.unwrap_or_default(),
+            },
+            update_public_key: std::env::var("UPDATE_PUBLIC_KEY")
+                .ok()
+                .or(yaml.update.public_key),
+            note_federation: {
+                if let Some(source) = yaml
+                    .note_federation
+                    .sources
+                    .iter()
+                    .find(|s| !notes::federation::is_valid_source_name(&s.name))
+                {
+                    anyhow::bail!(
+                        "note_federation: invalid source name `{}` (use [a-z0-9_-])",
+                        source.name
+                    );
+                }
+                yaml.note_federation
+            },
+            telemetry_enabled: std::env::var("TELEMETRY_ENABLED")
+                .ok()
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(yaml.telemetry.enabled),
+            notifications: {
+                yaml.notifications
+                    .check()
+                    .map_err(|e| anyhow::anyhow!("notifications: {}", e))?;
+                yaml.notifications
+            },
+            lsp: {
+                yaml.lsp
+                    .check()
+                    .map_err(|e| anyhow::anyhow!("lsp: {}", e))?;
+                yaml.lsp
+            },
+            grammars: {
+                yaml.grammars
+                    .check()
+                    .map_err(|e| anyhow::anyhow!("grammars: {}", e))?;
+                yaml.grammars
+            },
+            analytics_min_call_confidence: match std::env::var("ANALYTICS_MIN_CALL_CONFIDENCE") {
+                Ok(v) => {
+                    let value: f64 = v
+                        .parse()
+                        .map_err(|e| anyhow::anyhow!("ANALYTICS_MIN_CALL_CONFIDENCE: {}", e))?;
+                    check_confidence(value)
+                        .map_err(|e| anyhow::anyhow!("ANALYTICS_MIN_CALL_CONFIDENCE: {}", e))?;
+                    value
+                }
+                Err(_) => {
+                    yaml.analytics
+                        .check()
+                        .map_err(|e| anyhow::anyhow!("analytics: {}", e))?;
+                    yaml.analytics.min_call_confidence
+                }
+            },
+            sentry: {
+                yaml.sentry
+                    .check()
+                    .map_err(|e| anyhow::anyhow!("sentry: {}", e))?;
+                yaml.sentry
+            },
+            log_format: match std::env::var("LOG_FORMAT") {
+                Ok(v) => v
+                    .parse()
+                    .map_err(|e: String| anyhow::anyhow!("LOG_FORMAT: {}", e))?,
+                Err(_) => yaml.logging.format.unwrap_or_default(),
+            },
+            log_filter: std::env::var("RUST_LOG")
+                .ok()
+                .filter(|v| !v.trim().is_empty())
+                .or_else(|| yaml.logging.filter_directives())
+                .unwrap_or_else(|| logging::DEFAULT_FILTER.to_string()),
+            headless: false,
         })
     }
 
+    /// Timeout and slow-query threshold of the Neo4j client.
+    pub fn neo4j_query_limits(&self) -> neo4j::QueryLimits {
+        neo4j::QueryLimits {
+            timeout: (self.neo4j_query_timeout_ms > 0)
+                .then(|| std::time::Duration::from_millis(self.neo4j_query_timeout_ms)),
+            slow_threshold: std::time::Duration::from_millis(self.neo4j_slow_query_ms),
+        }
+    }
+
+    /// File of the write-behind code indexing queue
+    /// (default: `<data dir>/project-orchestrator/index-queue.redb`),
+    /// `None` when it is disabled.
+    pub fn index_queue_path(&self) -> Option<std::path::PathBuf> {
+        if let Some(ref path) = self.meilisearch_index_queue_path {
+            return (!path.is_empty()).then(|| std::path::PathBuf::from(expand_tilde(path)));
+        }
+        let data_dir = dirs::data_local_dir().unwrap_or_else(|| std::path::PathBuf::from("."));
+        #[cfg(target_os = "windows")]
+        let app_dir = data_dir.join("ProjectOrchestrator");
+        #[cfg(not(target_os = "windows"))]
+        let app_dir = data_dir.join("project-orchestrator");
+        Some(app_dir.join("index-queue.redb"))
+    }
+
+    /// Durable JetStream consumer name of this instance
+    /// (default: `po-<host>-<port>`, stable across restarts).
+    ///
+    /// Characters JetStream does not accept in names are replaced with `-`.
+    pub fn nats_consumer_name(&self) -> String {
+        let name = self.nats_consumer.clone().unwrap_or_else(|| {
+            let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "localhost".to_string());
+            format!("po-{}-{}", host, self.server_port)
+        });
+        name.chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect()
+    }
+
+    /// Socket (or Windows named pipe) of the local IPC event bridge
+    /// (default: `<data dir>/project-orchestrator/events.sock`).
+    pub fn ipc_socket_path(&self) -> std::path::PathBuf {
+        match self.ipc_path {
+            Some(ref path) => std::path::PathBuf::from(expand_tilde(path)),
+            None => events::ipc::default_ipc_path(),
+        }
+    }
+
     /// Try to load and parse a YAML config file. Returns defaults on any failure.
     ///
     /// Search order when `yaml_path` is `None`:
@@ -812,9 +1288,12 @@ impl Config {
     }
 
     /// Attempt to load and parse a single YAML config file.
+    ///
+    /// `${VAR}` references and `*_file` secret indirections are resolved
+    /// before deserializing (see [`config_secrets`]).
     fn try_load_yaml(path: &Path) -> YamlConfig {
         match std::fs::read_to_string(path) {
-            Ok(contents) => match serde_yaml::from_str(&contents) {
+            Ok(contents) => match Self::parse_yaml(&contents, path.parent()) {
                 Ok(config) => {
                     tracing::info!("Loaded config from {}", path.display());
                     config
@@ -830,6 +1309,17 @@ impl Config {
             }
         }
     }
+
+    /// Parse YAML text into a [`YamlConfig`], resolving secrets first.
+    /// Relative `*_file` paths are resolved against `base_dir`.
+    fn parse_yaml(contents: &str, base_dir: Option<&Path>) -> Result<YamlConfig> {
+        let mut raw: serde_yaml::Value = serde_yaml::from_str(contents)?;
+        if raw.is_null() {
+            return Ok(YamlConfig::default());
+        }
+        config_secrets::resolve(&mut raw, base_dir)?;
+        Ok(serde_yaml::from_value(raw)?)
+    }
 }
 
 /// Shared application state
@@ -863,14 +1353,38 @@ impl AppState {
                 &config.neo4j_user,
                 &config.neo4j_password,
             )
-            .await?,
+            .await?
+            .with_query_limits(config.neo4j_query_limits()),
         );
 
-        let meili = Arc::new(
+        let meili_client = Arc::new(
             meilisearch::client::MeiliClient::new(&config.meilisearch_url, &config.meilisearch_key)
                 .await?,
         );
+        // Keep syncing and searching through later Meilisearch outages
+        let meili: Arc<dyn meilisearch::SearchStore> = Arc::new(
+            meilisearch::ResilientSearchStore::new(meili_client.clone(), neo4j.clone()),
+        );
+        // Index code behind a durable queue. Another process (e.g. the server
+        // during a CLI sync) may hold it: index synchronously then.
+        let meili: Arc<dyn meilisearch::SearchStore> = match config.index_queue_path() {
+            Some(path) => match meilisearch::IndexQueue::open(&path) {
+                Ok(queue) => Arc::new(meilisearch::WriteBehindStore::new(
+                    meili,
+                    meili_client,
+                    queue,
+                )),
+                Err(e) => {
+                    tracing::info!("Indexing code synchronously: {:#}", e);
+                    meili
+                }
+            },
+            None => meili,
+        };
 
+        // Grammar plugins first: every CodeParser created from now on
+        // (this one, the sync workers', the watcher's) parses their files
+        parser::plugins::install(&config.grammars)?;
         let parser = Arc::new(parser::CodeParser::new()?);
 
         // Initialize neural routing with its own Neo4j connection pool
@@ -936,12 +1450,10 @@ impl AppState {
 ///
 /// This is the main entry point for embedding the server in another application
 /// (e.g., Tauri desktop). It initializes all services, creates the Axum router,
-/// and binds to the configured port.
+/// and binds to the configured host and port.
 ///
 /// Returns when the server shuts down (or an error occurs during startup).
 pub async fn start_server(mut config: Config) -> Result<()> {
-    use std::net::SocketAddr;
-
     // ────────────────────────────────────────────────────────────────────
     // Setup-only mode: if the wizard hasn't been completed yet, start a
     // minimal server that only serves /health and /api/setup-status.
@@ -981,9 +1493,25 @@ pub async fn start_server(mut config: Config) -> Result<()> {
     let nats_emitter = if let Some(ref nats_url) = config.nats_url {
         match events::connect_nats(nats_url).await {
             Ok(client) => {
-                let emitter = Arc::new(events::NatsEmitter::new(client, "events"));
+                let mut emitter = events::NatsEmitter::new(client, "events");
+                if config.nats_jetstream {
+                    let consumer = config.nats_consumer_name();
+                    match emitter.clone().with_jetstream(consumer.clone()).await {
+                        Ok(durable) => {
+                            tracing::info!(
+                                consumer = %consumer,
+                                "JetStream enabled — CRUD events are replayed after downtime"
+                            );
+                            emitter = durable;
+                        }
+                        Err(e) => tracing::warn!(
+                            "Failed to set up JetStream: {} — using core NATS delivery",
+                            e
+                        ),
+                    }
+                }
                 tracing::info!("NATS connected — inter-process event sync enabled");
-                Some(emitter)
+                Some(Arc::new(emitter))
             }
             Err(e) => {
                 tracing::warn!(
@@ -998,16 +1526,53 @@ pub async fn start_server(mut config: Config) -> Result<()> {
         None
     };
 
-    // Create hybrid emitter (local broadcast + optional NATS)
+    // Create hybrid emitter (local broadcast + optional NATS, or the local
+    // IPC bridge when NATS is unavailable)
     let event_bus = Arc::new(match &nats_emitter {
         Some(nats) => events::HybridEmitter::with_nats(local_bus, nats.clone()),
+        None if config.ipc_enabled => {
+            let ipc = events::IpcEmitter::start(config.ipc_socket_path());
+            tracing::info!(
+                path = %ipc.path().display(),
+                "Local IPC event sync enabled"
+            );
+            events::HybridEmitter::with_ipc(local_bus, Arc::new(ipc))
+        }
         None => events::HybridEmitter::new(local_bus),
     });
 
-    // Start NATS→local bridge: subscribes to NATS CRUD events and re-injects
-    // them into the local broadcast bus. This makes the local bus the single
+    // Start the NATS→local (or IPC→local) bridge: subscribes to remote CRUD
+    // events and re-injects them into the local broadcast bus. This makes the local bus the single
     // source of truth — WS handlers only need to listen to the local bus.
     event_bus.start_nats_bridge();
+    event_bus.start_ipc_bridge();
+
+    // Elect the instance that runs the watcher and background jobs. Instances
+    // sharing NATS compete for a KV lease; a lone instance always leads.
+    let leadership = match nats_emitter {
+        Some(ref nats) => match events::NatsLeaseBackend::connect(nats.client()).await {
+            Ok(backend) => {
+                let holder = events::LeaderElection::instance_id(config.server_port);
+                let (mut election, leadership) =
+                    events::LeaderElection::new(Arc::new(backend), holder);
+                if !election.tick().await {
+                    tracing::info!(
+                        "Leader election: another instance runs background jobs, standing by"
+                    );
+                }
+                election.spawn();
+                leadership
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "NATS KV unavailable, this instance runs background jobs unconditionally: {}",
+                    e
+                );
+                events::Leadership::always()
+            }
+        },
+        None => events::Leadership::always(),
+    };
 
     // Extract neural_router, trajectory_collector, trajectory_store, trajectory_store_neo4j,
     // and mcp_registry before state is moved into Orchestrator
@@ -1021,16 +1586,45 @@ pub async fn start_server(mut config: Config) -> Result<()> {
     let orchestrator =
         Arc::new(orchestrator::Orchestrator::with_event_bus(state, event_bus.clone()).await?);
 
+    // Re-apply the stored search settings, in case Meilisearch was reset
+    {
+        let orch = orchestrator.clone();
+        tokio::spawn(async move {
+            match orch.neo4j().get_search_settings().await {
+                Ok(Some(settings)) => {
+                    if let Err(e) = orch.meili().apply_search_settings(&settings).await {
+                        tracing::warn!("Failed to apply search settings: {}", e);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Failed to load search settings: {}", e),
+            }
+        });
+    }
+
     // Create file watcher and auto-register projects with watch_enabled=true
     let watcher = {
         let mut w = orchestrator::FileWatcher::new(orchestrator.clone());
+        w.set_leadership(leadership.clone());
 
-        // Only auto-register projects that have watch_enabled=true (default for existing projects)
-        match orchestrator.neo4j().list_projects().await {
+        // Only auto-register projects that have watch_enabled=true (default for existing projects).
+        // A read-only server watches nothing: re-syncs would write to the graph.
+        let projects = if config.read_only {
+            tracing::info!("Read-only mode: mutating routes return 423, auto-watch disabled");
+            Ok(Vec::new())
+        } else {
+            orchestrator.neo4j().list_projects().await
+        };
+        match projects {
             Ok(projects) => {
                 let mut registered = 0usize;
                 let mut skipped = 0usize;
                 for project in &projects {
+                    if project.archived {
+                        tracing::debug!("Auto-watch: skipping archived project '{}'", project.slug);
+                        skipped += 1;
+                        continue;
+                    }
                     if !project.watch_enabled {
                         tracing::debug!(
                             "Auto-watch: skipping project '{}' — watch_enabled=false",
@@ -1076,11 +1670,13 @@ pub async fn start_server(mut config: Config) -> Result<()> {
                     skipped
                 );
 
-                // Spawn background analytics staleness check (non-blocking)
-                if registered > 0 {
+                // Spawn background analytics staleness check (non-blocking).
+                // Followers leave it to the leader.
+                if registered > 0 && leadership.is_leader() {
                     let orch_bg = orchestrator.clone();
                     let project_ids: Vec<_> = projects
                         .iter()
+                        .filter(|p| !p.archived)
                         .filter(|p| {
                             let expanded = expand_tilde(&p.root_path);
                             std::path::Path::new(&expanded).exists()
@@ -1126,6 +1722,7 @@ pub async fn start_server(mut config: Config) -> Result<()> {
                     let orch_skills = orchestrator.clone();
                     let skill_project_ids: Vec<_> = projects
                         .iter()
+                        .filter(|p| !p.archived)
                         .filter(|p| {
                             let expanded = expand_tilde(&p.root_path);
                             std::path::Path::new(&expanded).exists()
@@ -1275,6 +1872,16 @@ pub async fn start_server(mut config: Config) -> Result<()> {
         if let Some(auto_update_app) = config.chat_auto_update_app {
             chat_config.auto_update_app = auto_update_app;
         }
+        if let Some(ref sandbox) = config.chat_sandbox {
+            chat_config.sandbox = sandbox.clone();
+        }
+        chat::sandbox::warn_if_unisolated(&chat_config.sandbox);
+        if let Some(ref limits) = config.chat_limits {
+            chat_config.limits = limits.clone();
+        }
+        if let Some(ref model_routing) = config.chat_model_routing {
+            chat_config.model_routing = model_routing.clone();
+        }
         // Inject auth context for MCP session token generation.
         // When auth is enabled, build_options() will generate a JWT session token
         // and inject PO_AUTH_TOKEN + PO_SERVER_URL into the MCP server env vars.
@@ -1305,14 +1912,29 @@ pub async fn start_server(mut config: Config) -> Result<()> {
         }
         let cm = Arc::new(cm);
         cm.start_cleanup_task();
+        cm.start_limits_task();
         tracing::info!("Chat manager initialized");
+        // Report an unusable CLI at startup rather than on the first chat message
+        let pinned_cli = config.chat_claude_cli_path.clone();
+        tokio::spawn(async move {
+            let health = chat::cli_version::check_cli_health(pinned_cli.as_deref()).await;
+            match health.error {
+                None => tracing::info!(
+                    "Claude CLI {} at {}",
+                    health.version.unwrap_or_default(),
+                    health.cli_path.unwrap_or_default()
+                ),
+                Some(e) => tracing::warn!("Chat sessions will fail to start: {}", e),
+            }
+        });
         Some(cm)
     };
 
     // Auto-configure Claude Code MCP integration (idempotent — safe to call on every start).
     // Configures ~/.claude/mcp.json with stdio mode pointing to this instance,
     // and updates it if the config is stale (e.g., was SSE mode from an older version).
-    {
+    // Skipped in headless mode: a container has no user home worth writing to.
+    if !config.headless {
         let setup_config = setup_claude::SetupConfig {
             mcp_server_path: chat::ChatConfig::detect_mcp_server_path_public(),
             server_port: config.server_port,
@@ -1337,8 +1959,27 @@ pub async fn start_server(mut config: Config) -> Result<()> {
         }
     }
 
-    // Create WS ticket store (in-memory, for WKWebView cookie workaround)
-    let ws_ticket_store = Arc::new(api::ws_auth::WsTicketStore::new());
+    // Create WS ticket store (for WKWebView cookie workaround). With NATS the
+    // tickets live in a JetStream KV bucket so any replica can consume them.
+    let ws_ticket_store = Arc::new(match nats_emitter {
+        Some(ref nats) => match api::ws_auth::NatsTicketBackend::connect(nats.client()).await {
+            Ok(backend) => {
+                tracing::info!(
+                    "WS tickets shared via NATS KV bucket '{}'",
+                    api::ws_auth::TICKET_BUCKET
+                );
+                api::ws_auth::WsTicketStore::with_backend(Arc::new(backend))
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "NATS KV unavailable, WS tickets stay in memory (single replica only): {}",
+                    e
+                );
+                api::ws_auth::WsTicketStore::new()
+            }
+        },
+        None => api::ws_auth::WsTicketStore::new(),
+    });
 
     // Spawn periodic cleanup of expired WS tickets (every 60s)
     {
@@ -1362,30 +2003,49 @@ pub async fn start_server(mut config: Config) -> Result<()> {
         orchestrator.clone(),
     );
 
-    // Recover orphaned protocol runs from previous server instance
+    // Recover orphaned protocol runs from previous server instance. Only the
+    // leader: a follower would also fail runs the leader is still driving.
     let protocol_emitter: Option<Arc<dyn events::EventEmitter>> =
         Some(event_bus.clone() as Arc<dyn events::EventEmitter>);
-    match crate::protocol::hooks::recover_orphaned_runs(
-        orchestrator.neo4j_arc(),
-        protocol_emitter.clone(),
-    )
-    .await
-    {
-        Ok(count) => {
-            if count > 0 {
-                tracing::info!("Protocol recovery: marked {count} orphaned run(s) as failed");
+    if leadership.is_leader() {
+        match crate::protocol::hooks::recover_orphaned_runs(
+            orchestrator.neo4j_arc(),
+            protocol_emitter.clone(),
+        )
+        .await
+        {
+            Ok(count) => {
+                if count > 0 {
+                    tracing::info!("Protocol recovery: marked {count} orphaned run(s) as failed");
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Protocol recovery failed (non-fatal): {}", e);
             }
-        }
-        Err(e) => {
-            tracing::warn!("Protocol recovery failed (non-fatal): {}", e);
         }
     }
 
     // Spawn the protocol scheduler (hourly evaluation of scheduled protocols)
-    crate::protocol::hooks::spawn_protocol_scheduler(orchestrator.neo4j_arc(), protocol_emitter);
+    crate::protocol::hooks::spawn_protocol_scheduler(
+        orchestrator.neo4j_arc(),
+        protocol_emitter,
+        leadership.clone(),
+    );
+
+    // Spawn the retention purge (daily deletion of expired events and alerts)
+    crate::orchestrator::retention::spawn_retention_purger(
+        orchestrator.neo4j_arc(),
+        leadership.clone(),
+    );
+
+    // Flush the usage counters every minute (opt-in telemetry)
+    if config.telemetry_enabled {
+        tracing::info!("Usage telemetry enabled: counting API routes served");
+        crate::api::usage_handlers::spawn_usage_flusher(orchestrator.neo4j_arc());
+    }
 
     // Recover interrupted plan runner runs from previous server instance
-    if let Some(ref cm) = chat_manager {
+    if let (Some(cm), true) = (&chat_manager, leadership.is_leader()) {
         let graph = orchestrator.neo4j_arc();
         let context_builder = orchestrator.context_builder().clone();
         let runner_config = orchestrator.runner_config();
@@ -1418,7 +2078,8 @@ pub async fn start_server(mut config: Config) -> Result<()> {
             graph.clone(),
             engine.clone(),
             None, // default 60s
-        );
+        )
+        .with_leadership(leadership.clone());
         if let Err(e) = schedule_provider.setup().await {
             tracing::warn!("ScheduleProvider setup failed (non-fatal): {}", e);
         } else {
@@ -1449,8 +2110,9 @@ pub async fn start_server(mut config: Config) -> Result<()> {
         use heartbeat::checks::{
             consolidation::ConsolidationCheck, convention_guard::ConventionGuardCheck,
             git_drift::GitDriftCheck, homeostasis::HomeostasisCheck, maintenance::MaintenanceCheck,
-            staleness::StalenessCheck, synapse_decay::SynapseDecayCheck,
-            synapse_replenish::SynapseReplenishCheck,
+            note_federation::NoteFederationCheck, sentry::SentryCheck, staleness::StalenessCheck,
+            synapse_decay::SynapseDecayCheck, synapse_replenish::SynapseReplenishCheck,
+            vulnerability_audit::VulnerabilityAuditCheck,
         };
         use heartbeat::engine::HeartbeatEngine;
 
@@ -1459,7 +2121,7 @@ pub async fn start_server(mut config: Config) -> Result<()> {
         let emitter: Option<Arc<dyn events::EventEmitter>> =
             Some(event_bus.clone() as Arc<dyn events::EventEmitter>);
 
-        let checks: Vec<Box<dyn heartbeat::HeartbeatCheck>> = vec![
+        let mut checks: Vec<Box<dyn heartbeat::HeartbeatCheck>> = vec![
             Box::new(GitDriftCheck),
             Box::new(StalenessCheck),
             Box::new(SynapseDecayCheck),
@@ -1467,6 +2129,7 @@ pub async fn start_server(mut config: Config) -> Result<()> {
             Box::new(MaintenanceCheck),
             Box::new(ConsolidationCheck),
             Box::new(HomeostasisCheck::new()),
+            Box::new(VulnerabilityAuditCheck::new()),
             // MUST run LAST: the engine executes checks in vec order within a tick
             // (engine.rs:88). SynapseDecayCheck and MaintenanceCheck (deep_maintenance
             // applies an aggressive 3x decay + prune) both delete synapses; replenish
@@ -1474,12 +2137,26 @@ pub async fn start_server(mut config: Config) -> Result<()> {
             // a fresh rebuild would be wiped by the maintenance prune in the same tick.
             Box::new(SynapseReplenishCheck),
         ];
+        if !config.note_federation.sources.is_empty() {
+            // Only writes federated notes, so ordering does not matter
+            checks.push(Box::new(NoteFederationCheck::new(
+                config.note_federation.clone(),
+            )));
+        }
+        if !config.sentry.projects.is_empty() {
+            // Only writes Sentry issues, so ordering does not matter
+            if let Some(check) = SentryCheck::new(config.sentry.clone()) {
+                checks.push(Box::new(check));
+            }
+        }
+        let check_count = checks.len();
 
-        let engine = HeartbeatEngine::new(graph, search, emitter, checks);
+        let engine = HeartbeatEngine::new(graph, search, emitter, checks)
+            .with_leadership(leadership.clone());
         let handle = engine.start_owned();
         // Keep handle alive for the lifetime of the process
         std::mem::forget(handle);
-        tracing::info!("HeartbeatEngine started (7 checks)");
+        tracing::info!("HeartbeatEngine started ({} checks)", check_count);
     }
 
     // Pre-build OIDC client once (avoids fetching discovery document on every request)
@@ -1519,7 +2196,10 @@ pub async fn start_server(mut config: Config) -> Result<()> {
         setup_completed: config.setup_completed,
         server_port: config.server_port,
         public_url: config.public_url.clone(),
+        read_only: config.read_only,
         ws_ticket_store,
+        presence: Arc::new(api::presence::PresenceTracker::new()),
+        idempotency: Arc::new(api::idempotency::IdempotencyStore::new()),
         registry_remote_url: config.registry_remote_url.clone(),
         neural_router: neural_router.clone(),
         trajectory_collector: std::sync::RwLock::new(trajectory_collector.read().unwrap().clone()),
@@ -1554,7 +2234,25 @@ pub async fn start_server(mut config: Config) -> Result<()> {
             reactor_receiver,
             server_state.clone() as Arc<dyn std::any::Any + Send + Sync>,
         );
-        let builder = events::register_builtin_reactions(builder, server_state.clone());
+        let mut builder = events::register_builtin_reactions(builder, server_state.clone());
+        match notifications::Notifier::from_config(
+            &config.notifications,
+            server_state.orchestrator.neo4j_arc(),
+        ) {
+            Ok(Some(notifier)) => {
+                tracing::info!(
+                    "Notifications enabled: {} rule(s)",
+                    config.notifications.rules.len()
+                );
+                builder = notifications::register_reactions(
+                    builder,
+                    Arc::new(notifier),
+                    leadership.clone(),
+                );
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Notifications disabled: {}", e),
+        }
         let (reactor, counters) = builder.build();
 
         // Store counters in ServerState (OnceLock — safe one-time init)
@@ -1755,15 +2453,54 @@ pub async fn start_server(mut config: Config) -> Result<()> {
     }
 
     // Start server
-    let addr = SocketAddr::from(([0, 0, 0, 0], config.server_port));
-    tracing::info!("Server listening on {}", addr);
-
-    let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    let listener =
+        tokio::net::TcpListener::bind((config.server_host.as_str(), config.server_port)).await?;
+    tracing::info!("Server listening on {}", listener.local_addr()?);
+    if config.headless {
+        // Kubernetes sends SIGTERM and waits terminationGracePeriodSeconds:
+        // stop accepting connections and drain in-flight requests.
+        axum::serve(listener, app)
+            .with_graceful_shutdown(shutdown_signal())
+            .await?;
+        tracing::info!("Server shut down gracefully");
+    } else {
+        axum::serve(listener, app).await?;
+    }
 
     Ok(())
 }
 
+/// Resolves on SIGTERM (Unix) or Ctrl-C.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::warn!("Failed to listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+    tracing::info!("Shutdown signal received — draining connections");
+}
+
 /// Start a minimal server for the setup wizard.
 ///
 /// Serves `/health`, `/api/setup-status`, `/auth/providers`, and optionally
@@ -2157,6 +2894,83 @@ server:
         clear_env();
     }
 
+    #[test]
+    fn test_headless_profile_overrides() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        std::fs::write(
+            &path,
+            "setup_completed: false\nserver:\n  serve_frontend: true\nauth:\n  jwt_secret: \"headless-secret-min-32-characters!!\"\n",
+        )
+        .unwrap();
+
+        let config = Config::headless_from_env(Some(&path)).unwrap();
+        assert!(config.headless);
+        assert!(config.setup_completed);
+        assert!(!config.serve_frontend);
+        assert_eq!(config.chat_auto_update_cli, Some(false));
+        assert_eq!(config.chat_auto_update_app, Some(false));
+        // auth from the explicit config file wins over env
+        assert_eq!(
+            config.auth_config.unwrap().jwt_secret,
+            "headless-secret-min-32-characters!!"
+        );
+        assert_eq!(config.config_yaml_path.as_deref(), Some(path.as_path()));
+
+        assert!(!Config::from_yaml_and_env(Some(&path)).unwrap().headless);
+    }
+
+    #[test]
+    fn test_yaml_secret_files_and_interpolation() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("neo4j_password"), "file-password\n").unwrap();
+        std::fs::write(
+            dir.path().join("jwt"),
+            "jwt-secret-from-a-mounted-file-min-32-chars",
+        )
+        .unwrap();
+        let path = dir.path().join("config.yaml");
+        std::fs::write(
+            &path,
+            r#"
+neo4j:
+  uri: "bolt://${PO_TEST_UNSET_NEO4J_HOST:-db}:7687"
+  password_file: neo4j_password
+auth:
+  jwt_secret_file: jwt
+  root_account:
+    email: admin@example.com
+    name: Admin
+    password_hash: "$2b$12$hash"
+"#,
+        )
+        .unwrap();
+
+        let yaml = Config::try_load_yaml(&path);
+        assert_eq!(yaml.neo4j.uri, "bolt://db:7687");
+        assert_eq!(yaml.neo4j.password, "file-password");
+        let auth = yaml.auth.expect("auth section");
+        assert_eq!(
+            auth.jwt_secret,
+            "jwt-secret-from-a-mounted-file-min-32-chars"
+        );
+        // `$2b$` is not a `${` reference and must survive untouched
+        assert_eq!(auth.root_account.unwrap().password_hash, "$2b$12$hash");
+    }
+
+    #[test]
+    fn test_yaml_missing_secret_file_falls_back_to_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        std::fs::write(
+            &path,
+            "server:\n  port: 9999\nneo4j:\n  password_file: nope\n",
+        )
+        .unwrap();
+        let yaml = Config::try_load_yaml(&path);
+        assert_eq!(yaml.server.port, 8080);
+    }
+
     // ========================================================================
     // New auth config format tests
     // ========================================================================
@@ -2312,6 +3126,93 @@ server:
         // When auth is None, the middleware should allow all requests (no-auth mode)
     }
 
+    #[test]
+    fn test_nats_jetstream_yaml_config() {
+        let yaml = r#"
+nats:
+  url: "nats://localhost:4222"
+  jetstream: true
+  consumer: desktop
+"#;
+        let config: YamlConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.nats.jetstream);
+        assert_eq!(config.nats.consumer.as_deref(), Some("desktop"));
+
+        let config: YamlConfig = serde_yaml::from_str("nats:\n  url: nats://x:4222\n").unwrap();
+        assert!(!config.nats.jetstream);
+        assert!(config.nats.consumer.is_none());
+    }
+
+    #[test]
+    fn test_ipc_yaml_config() {
+        let yaml = r#"
+ipc:
+  enabled: true
+  path: ~/po-events.sock
+"#;
+        let config: YamlConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.ipc.enabled);
+        assert_eq!(config.ipc.path.as_deref(), Some("~/po-events.sock"));
+
+        let config: YamlConfig = serde_yaml::from_str("server:\n  port: 8080\n").unwrap();
+        assert!(!config.ipc.enabled);
+        assert!(config.ipc.path.is_none());
+    }
+
+    #[test]
+    fn test_chat_limits_yaml_config() {
+        let yaml = r#"
+chat:
+  limits:
+    max_tool_calls: 300
+    max_wall_clock_secs: 3600
+    max_cost_usd: 10.0
+"#;
+        let config: YamlConfig = serde_yaml::from_str(yaml).unwrap();
+        let limits = config.chat.limits.unwrap();
+        assert_eq!(limits.max_tool_calls, Some(300));
+        assert_eq!(limits.max_wall_clock_secs, Some(3600));
+        assert_eq!(limits.max_file_writes, None);
+        assert_eq!(limits.max_cost_usd, Some(10.0));
+    }
+
+    #[test]
+    fn test_chat_model_routing_yaml_config() {
+        let yaml = r#"
+chat:
+  model_routing:
+    rules:
+      - task: planning
+        model: claude-opus-4-6
+    projects:
+      my-api:
+        - task: quick_edit
+          model: claude-haiku-4-5
+"#;
+        let config: YamlConfig = serde_yaml::from_str(yaml).unwrap();
+        let routing = config.chat.model_routing.unwrap();
+        assert_eq!(routing.rules.len(), 1);
+        assert_eq!(routing.rules[0].model, "claude-opus-4-6");
+        assert_eq!(routing.projects["my-api"][0].model, "claude-haiku-4-5");
+
+        let config: YamlConfig = serde_yaml::from_str("chat:\n  max_sessions: 5\n").unwrap();
+        assert!(config.chat.model_routing.is_none());
+    }
+
+    #[test]
+    fn test_nats_consumer_name_is_sanitized() {
+        let nonexistent = Path::new("/tmp/nonexistent-config-nats.yaml");
+        let mut config = Config::from_yaml_and_env(Some(nonexistent)).unwrap();
+        config.nats_consumer = Some("dev box.local:8080".to_string());
+        assert_eq!(config.nats_consumer_name(), "dev-box-local-8080");
+
+        config.nats_consumer = None;
+        config.server_port = 6600;
+        let name = config.nats_consumer_name();
+        assert!(name.starts_with("po-") && name.ends_with("-6600"));
+        assert!(!name.contains('.'));
+    }
+
     // ========================================================================
     // Frontend serving config tests
     // ========================================================================
@@ -2319,6 +3220,7 @@ server:
     #[test]
     fn test_frontend_config_defaults() {
         let config = ServerYamlConfig::default();
+        assert_eq!(config.host, "0.0.0.0");
         assert!(config.serve_frontend);
         assert_eq!(config.frontend_path, "./dist");
     }
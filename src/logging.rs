@@ -0,0 +1,262 @@
+//! Log output format and runtime level control.
+//!
+//! The `orchestrator` binary installs its subscriber through [`init`], which
+//! wraps the [`EnvFilter`] in a reload layer. The filter can then be changed
+//! while the server runs (`PUT /api/admin/log-level`) — e.g. turning
+//! `project_orchestrator::chat` up to `trace` while debugging a production
+//! issue — without a restart.
+//!
+//! Filter precedence at startup: `RUST_LOG` env > `logging` section of
+//! config.yaml > [`DEFAULT_FILTER`].
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::{
+    layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter, Registry,
+};
+
+/// Filter used when neither `RUST_LOG` nor config.yaml specify one.
+pub const DEFAULT_FILTER: &str = "info,project_orchestrator=debug,tower_http=debug";
+
+/// Log line format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable, colored when attached to a terminal.
+    #[default]
+    Pretty,
+    /// One JSON object per line, for log collectors.
+    Json,
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "pretty" | "text" => Ok(Self::Pretty),
+            "json" => Ok(Self::Json),
+            other => Err(format!("unknown log format `{}` (pretty, json)", other)),
+        }
+    }
+}
+
+/// `logging` section of config.yaml.
+///
+/// ```yaml
+/// logging:
+///   format: json          # LOG_FORMAT env override
+///   level: info           # default level for every target
+///   targets:
+///     project_orchestrator: debug
+///     project_orchestrator::chat: trace
+/// ```
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct LoggingYamlConfig {
+    pub format: Option<LogFormat>,
+    pub level: Option<String>,
+    pub targets: BTreeMap<String, String>,
+}
+
+impl LoggingYamlConfig {
+    /// Build an [`EnvFilter`] directive string, or `None` when the section
+    /// specifies no levels at all.
+    pub fn filter_directives(&self) -> Option<String> {
+        if self.level.is_none() && self.targets.is_empty() {
+            return None;
+        }
+        let mut directives = vec![self.level.clone().unwrap_or_else(|| "info".to_string())];
+        directives.extend(
+            self.targets
+                .iter()
+                .map(|(target, level)| format!("{}={}", target, level)),
+        );
+        Some(directives.join(","))
+    }
+}
+
+/// Why a runtime filter change was rejected.
+#[derive(Debug, thiserror::Error)]
+pub enum LogLevelError {
+    #[error("runtime log level control is not available in this process")]
+    Unavailable,
+    #[error("invalid filter: {0}")]
+    Invalid(String),
+}
+
+struct FilterControl {
+    handle: reload::Handle<EnvFilter, Registry>,
+    initial: String,
+    current: Mutex<String>,
+}
+
+static CONTROL: OnceLock<FilterControl> = OnceLock::new();
+
+/// Install the global subscriber with a reloadable filter.
+///
+/// An invalid `filter` falls back to [`DEFAULT_FILTER`] (with a warning
+/// once logging is up) rather than leaving the process without logs.
+pub fn init(format: LogFormat, filter: &str) {
+    let (env_filter, filter, invalid) = match EnvFilter::try_new(filter) {
+        Ok(f) => (f, filter.to_string(), None),
+        Err(e) => (
+            EnvFilter::new(DEFAULT_FILTER),
+            DEFAULT_FILTER.to_string(),
+            Some(e),
+        ),
+    };
+    let (filter_layer, handle) = reload::Layer::new(env_filter);
+    let json = format == LogFormat::Json;
+    tracing_subscriber::registry()
+        .with(filter_layer)
+        .with(json.then(|| tracing_subscriber::fmt::layer().json()))
+        .with((!json).then(tracing_subscriber::fmt::layer))
+        .init();
+    if let Some(e) = invalid {
+        tracing::warn!("Invalid log filter: {} — using `{}`", e, DEFAULT_FILTER);
+    }
+    let _ = CONTROL.set(FilterControl {
+        handle,
+        initial: filter.clone(),
+        current: Mutex::new(filter),
+    });
+}
+
+/// A plain subscriber for messages emitted before [`init`] — config loading
+/// logs through it, since the final format depends on the loaded config.
+pub fn bootstrap_dispatch(format: LogFormat) -> tracing::Dispatch {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+    match format {
+        LogFormat::Json => tracing::Dispatch::new(builder.json().finish()),
+        LogFormat::Pretty => tracing::Dispatch::new(builder.finish()),
+    }
+}
+
+/// The active filter directives, or `None` when [`init`] was not used.
+pub fn current_filter() -> Option<String> {
+    CONTROL.get().map(|c| c.current.lock().unwrap().clone())
+}
+
+/// Replace the whole filter (same syntax as `RUST_LOG`).
+pub fn set_filter(directives: &str) -> Result<String, LogLevelError> {
+    let control = CONTROL.get().ok_or(LogLevelError::Unavailable)?;
+    let filter =
+        EnvFilter::try_new(directives).map_err(|e| LogLevelError::Invalid(e.to_string()))?;
+    control
+        .handle
+        .reload(filter)
+        .map_err(|e| LogLevelError::Invalid(e.to_string()))?;
+    let directives = directives.to_string();
+    *control.current.lock().unwrap() = directives.clone();
+    tracing::info!(filter = %directives, "Log filter updated");
+    Ok(directives)
+}
+
+/// Change the level of one target (or the default level when `target` is
+/// `None`), keeping every other directive.
+pub fn set_level(target: Option<&str>, level: &str) -> Result<String, LogLevelError> {
+    let current = current_filter().ok_or(LogLevelError::Unavailable)?;
+    set_filter(&with_level(&current, target, level)?)
+}
+
+/// Restore the filter the process started with.
+pub fn reset_filter() -> Result<String, LogLevelError> {
+    let control = CONTROL.get().ok_or(LogLevelError::Unavailable)?;
+    set_filter(&control.initial.clone())
+}
+
+/// Rewrite a directive string so `target` (or the default) logs at `level`.
+fn with_level(current: &str, target: Option<&str>, level: &str) -> Result<String, LogLevelError> {
+    let level = level
+        .trim()
+        .parse::<LevelFilter>()
+        .map_err(|_| LogLevelError::Invalid(format!("unknown level `{}`", level)))?
+        .to_string()
+        .to_lowercase();
+    let target = target.map(str::trim).filter(|t| !t.is_empty());
+    if let Some(t) = target {
+        if t.contains(['=', ',']) {
+            return Err(LogLevelError::Invalid(format!("invalid target `{}`", t)));
+        }
+    }
+
+    let mut directives: Vec<String> = current
+        .split(',')
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .filter(|d| match target {
+            // same target (ignoring span/field filters after `[`)
+            Some(t) => d
+                .split_once('=')
+                .map(|(lhs, _)| lhs.split('[').next().unwrap_or(lhs) != t)
+                .unwrap_or(true),
+            // a bare level is the default directive
+            None => d.parse::<LevelFilter>().is_err(),
+        })
+        .map(String::from)
+        .collect();
+    match target {
+        Some(t) => directives.push(format!("{}={}", t, level)),
+        None => directives.insert(0, level),
+    }
+    Ok(directives.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_format_parse() {
+        assert_eq!("JSON".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert_eq!("pretty".parse::<LogFormat>().unwrap(), LogFormat::Pretty);
+        assert!("xml".parse::<LogFormat>().is_err());
+    }
+
+    #[test]
+    fn test_yaml_filter_directives() {
+        let yaml: LoggingYamlConfig = serde_yaml::from_str(
+            "format: json\nlevel: warn\ntargets:\n  project_orchestrator::chat: trace\n  tower_http: info\n",
+        )
+        .unwrap();
+        assert_eq!(yaml.format, Some(LogFormat::Json));
+        assert_eq!(
+            yaml.filter_directives().as_deref(),
+            Some("warn,project_orchestrator::chat=trace,tower_http=info")
+        );
+        assert!(LoggingYamlConfig::default().filter_directives().is_none());
+    }
+
+    #[test]
+    fn test_with_level_replaces_target() {
+        let f = with_level(DEFAULT_FILTER, Some("project_orchestrator"), "TRACE").unwrap();
+        assert_eq!(f, "info,tower_http=debug,project_orchestrator=trace");
+        let f = with_level(&f, Some("project_orchestrator::chat"), "warn").unwrap();
+        assert!(f.ends_with("project_orchestrator=trace,project_orchestrator::chat=warn"));
+        assert!(EnvFilter::try_new(&f).is_ok());
+    }
+
+    #[test]
+    fn test_with_level_replaces_default() {
+        let f = with_level("info,tower_http=debug", None, "error").unwrap();
+        assert_eq!(f, "error,tower_http=debug");
+        let f = with_level("tower_http=debug", None, "warn").unwrap();
+        assert_eq!(f, "warn,tower_http=debug");
+    }
+
+    #[test]
+    fn test_with_level_rejects_bad_input() {
+        assert!(matches!(
+            with_level("info", Some("x"), "loud"),
+            Err(LogLevelError::Invalid(_))
+        ));
+        assert!(matches!(
+            with_level("info", Some("a=b"), "info"),
+            Err(LogLevelError::Invalid(_))
+        ));
+    }
+}
@@ -0,0 +1,398 @@
+//! Language server client — JSON-RPC 2.0 over the stdin/stdout of a spawned
+//! server, framed with `Content-Length` headers.
+//!
+//! Only what the enrichment pass needs: the `initialize` handshake, opening
+//! documents and `textDocument/definition`. Requests the server sends back
+//! (`workspace/configuration`, progress tokens…) are answered with empty
+//! results so it never waits on us.
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tracing::{debug, trace};
+
+use super::LspServerConfig;
+
+/// Larger messages are not a definition response
+const MAX_MESSAGE_BYTES: usize = 64 * 1024 * 1024;
+
+/// Silence after which a starting server is considered done indexing
+const IDLE: Duration = Duration::from_secs(2);
+
+/// JSON-RPC `ContentModified`: the server was still analyzing, retry
+const CONTENT_MODIFIED: i64 = -32801;
+
+/// Error response of the language server.
+#[derive(Debug, thiserror::Error)]
+#[error("{method} failed ({code}): {message}")]
+pub struct ResponseError {
+    pub method: String,
+    pub code: i64,
+    pub message: String,
+}
+
+/// Target of a definition, `line` 0-based as in LSP.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+    pub path: PathBuf,
+    pub line: u32,
+}
+
+/// A running language server.
+pub struct LspClient {
+    /// Killed on drop
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: i64,
+    timeout: Duration,
+    /// URIs already sent with `didOpen`
+    opened: HashSet<String>,
+}
+
+impl LspClient {
+    /// Spawn the server in `root`, run the `initialize` handshake and wait
+    /// (at most `timeout`) for it to finish indexing the workspace.
+    pub async fn start(server: &LspServerConfig, root: &Path, timeout: Duration) -> Result<Self> {
+        debug!(command = %server.command, args = ?server.args, "Spawning language server");
+
+        let mut child = Command::new(&server.command)
+            .args(&server.args)
+            .current_dir(root)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to spawn language server: {}", server.command))?;
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!("Failed to capture stdin of language server"))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow!("Failed to capture stdout of language server"))?;
+
+        let mut client = Self {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+            next_id: 0,
+            timeout,
+            opened: HashSet::new(),
+        };
+
+        let root_uri = path_to_uri(root);
+        let name = root
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        client
+            .request(
+                "initialize",
+                json!({
+                    "processId": std::process::id(),
+                    "rootUri": root_uri,
+                    "workspaceFolders": [{ "uri": root_uri, "name": name }],
+                    "capabilities": {
+                        "textDocument": { "definition": { "linkSupport": true } },
+                        // rust-analyzer reports when it is done indexing
+                        "experimental": { "serverStatusNotification": true },
+                    },
+                }),
+            )
+            .await?;
+        client.notify("initialized", json!({})).await?;
+        client.wait_until_idle().await?;
+        Ok(client)
+    }
+
+    /// Definitions of the symbol at `line`/`character` (0-based, UTF-16) of
+    /// `path`, whose content is `text`.
+    pub async fn definition(
+        &mut self,
+        path: &Path,
+        language_id: &str,
+        text: &str,
+        line: u32,
+        character: u32,
+    ) -> Result<Vec<Location>> {
+        let uri = path_to_uri(path);
+        if self.opened.insert(uri.clone()) {
+            self.notify(
+                "textDocument/didOpen",
+                json!({
+                    "textDocument": {
+                        "uri": uri,
+                        "languageId": language_id,
+                        "version": 1,
+                        "text": text,
+                    }
+                }),
+            )
+            .await?;
+        }
+
+        let params = json!({
+            "textDocument": { "uri": uri },
+            "position": { "line": line, "character": character },
+        });
+        let mut attempts = 0;
+        loop {
+            match self
+                .request("textDocument/definition", params.clone())
+                .await
+            {
+                Ok(result) => return Ok(parse_locations(&result)),
+                Err(e)
+                    if attempts < 3
+                        && e.downcast_ref::<ResponseError>()
+                            .is_some_and(|e| e.code == CONTENT_MODIFIED) =>
+                {
+                    attempts += 1;
+                    tokio::time::sleep(Duration::from_millis(500)).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Ask the server to exit; it is killed if it does not.
+    pub async fn shutdown(mut self) {
+        if self.request("shutdown", Value::Null).await.is_ok() {
+            let _ = self.notify("exit", Value::Null).await;
+            let _ = tokio::time::timeout(IDLE, self.child.wait()).await;
+        }
+    }
+
+    async fn request(&mut self, method: &str, params: Value) -> Result<Value> {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.send(&json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))
+            .await?;
+        trace!(id, method, "→ lsp request");
+
+        let timeout = self.timeout;
+        tokio::time::timeout(timeout, self.read_response(method, id))
+            .await
+            .map_err(|_| anyhow!("{} timed out after {}s", method, timeout.as_secs()))?
+    }
+
+    /// Read messages up to the response to request `id`.
+    async fn read_response(&mut self, method: &str, id: i64) -> Result<Value> {
+        loop {
+            let msg = read_message(&mut self.stdout).await?;
+            if msg.get("method").is_some() {
+                self.reply(&msg).await?;
+                continue;
+            }
+            if msg.get("id").and_then(Value::as_i64) != Some(id) {
+                continue;
+            }
+            if let Some(error) = msg.get("error") {
+                return Err(ResponseError {
+                    method: method.to_string(),
+                    code: error.get("code").and_then(Value::as_i64).unwrap_or(0),
+                    message: error
+                        .get("message")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                }
+                .into());
+            }
+            return Ok(msg.get("result").cloned().unwrap_or(Value::Null));
+        }
+    }
+
+    async fn notify(&mut self, method: &str, params: Value) -> Result<()> {
+        self.send(&json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+            .await
+    }
+
+    /// Answer a request of the server; notifications need no answer.
+    async fn reply(&mut self, msg: &Value) -> Result<()> {
+        let Some(id) = msg.get("id") else {
+            return Ok(());
+        };
+        let result = match msg.get("method").and_then(Value::as_str) {
+            // One (empty) setting per requested section
+            Some("workspace/configuration") => {
+                let items = msg["params"]["items"].as_array().map_or(0, |i| i.len());
+                Value::Array(vec![Value::Null; items])
+            }
+            _ => Value::Null,
+        };
+        self.send(&json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+            .await
+    }
+
+    /// Read the server's messages until it reports it is quiescent, or stays
+    /// silent for [`IDLE`], or `timeout` runs out.
+    async fn wait_until_idle(&mut self) -> Result<()> {
+        let deadline = tokio::time::Instant::now() + self.timeout;
+        while tokio::time::Instant::now() < deadline {
+            // Only wait for the first byte: a message is never cut by the timeout
+            match tokio::time::timeout(IDLE, self.stdout.fill_buf()).await {
+                Ok(buf) => {
+                    if buf?.is_empty() {
+                        bail!("Language server closed stdout");
+                    }
+                }
+                Err(_) => break,
+            }
+            let msg = read_message(&mut self.stdout).await?;
+            if msg.get("method").and_then(Value::as_str) == Some("experimental/serverStatus")
+                && msg["params"]["quiescent"].as_bool() == Some(true)
+            {
+                break;
+            }
+            self.reply(&msg).await?;
+        }
+        Ok(())
+    }
+
+    async fn send(&mut self, msg: &Value) -> Result<()> {
+        self.stdin.write_all(&encode_message(msg)).await?;
+        self.stdin.flush().await?;
+        Ok(())
+    }
+}
+
+/// Frame a message with its `Content-Length` header.
+pub(crate) fn encode_message(msg: &Value) -> Vec<u8> {
+    let body = msg.to_string();
+    let mut bytes = format!("Content-Length: {}\r\n\r\n", body.len()).into_bytes();
+    bytes.extend_from_slice(body.as_bytes());
+    bytes
+}
+
+/// Read one framed message.
+pub(crate) async fn read_message<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<Value> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).await? == 0 {
+            bail!("Language server closed stdout");
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some(value) = header.strip_prefix("Content-Length:") {
+            content_length = Some(value.trim().parse::<usize>()?);
+        }
+    }
+    let length = content_length.context("Language server message without Content-Length")?;
+    if length > MAX_MESSAGE_BYTES {
+        bail!("Language server message of {} bytes", length);
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await?;
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// `file://` URI of an absolute path.
+pub fn path_to_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let encoded: Vec<_> = path.split('/').map(urlencoding::encode).collect();
+    let encoded = encoded.join("/");
+    if encoded.starts_with('/') {
+        format!("file://{}", encoded)
+    } else {
+        format!("file:///{}", encoded)
+    }
+}
+
+/// Path of a `file://` URI.
+pub fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = urlencoding::decode(uri.strip_prefix("file://")?).ok()?;
+    Some(PathBuf::from(path.into_owned()))
+}
+
+/// Locations of a definition result: `null`, a `Location`, or an array of
+/// `Location` or `LocationLink`.
+pub(crate) fn parse_locations(result: &Value) -> Vec<Location> {
+    let items: Vec<&Value> = match result {
+        Value::Array(items) => items.iter().collect(),
+        Value::Object(_) => vec![result],
+        _ => Vec::new(),
+    };
+    items
+        .into_iter()
+        .filter_map(|item| {
+            let (uri, range) = match item.get("targetUri") {
+                Some(uri) => (
+                    uri,
+                    item.get("targetSelectionRange")
+                        .or_else(|| item.get("targetRange"))?,
+                ),
+                None => (item.get("uri")?, item.get("range")?),
+            };
+            Some(Location {
+                path: uri_to_path(uri.as_str()?)?,
+                line: range["start"]["line"].as_u64()? as u32,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_message_framing() {
+        let first = json!({ "jsonrpc": "2.0", "id": 1, "result": { "name": "é" } });
+        let second = json!({ "jsonrpc": "2.0", "method": "$/progress" });
+        let mut bytes = encode_message(&first);
+        bytes.extend(encode_message(&second));
+
+        let mut reader = BufReader::new(bytes.as_slice());
+        assert_eq!(read_message(&mut reader).await.unwrap(), first);
+        assert_eq!(read_message(&mut reader).await.unwrap(), second);
+        assert!(read_message(&mut reader).await.is_err());
+    }
+
+    #[test]
+    fn test_uri_round_trip() {
+        let path = Path::new("/home/dev/my code/src/lib.rs");
+        let uri = path_to_uri(path);
+        assert_eq!(uri, "file:///home/dev/my%20code/src/lib.rs");
+        assert_eq!(uri_to_path(&uri).unwrap(), path);
+        assert_eq!(uri_to_path("untitled:Untitled-1"), None);
+    }
+
+    #[test]
+    fn test_parse_locations() {
+        let location = json!({
+            "uri": "file:///repo/src/a.rs",
+            "range": { "start": { "line": 4, "character": 7 }, "end": { "line": 4, "character": 12 } },
+        });
+        let a = Location {
+            path: PathBuf::from("/repo/src/a.rs"),
+            line: 4,
+        };
+        assert_eq!(parse_locations(&location), vec![a.clone()]);
+        assert_eq!(parse_locations(&json!([location])), vec![a]);
+
+        let link = json!([{
+            "targetUri": "file:///repo/src/b.rs",
+            "targetRange": { "start": { "line": 10, "character": 0 }, "end": { "line": 20, "character": 1 } },
+            "targetSelectionRange": { "start": { "line": 11, "character": 7 }, "end": { "line": 11, "character": 10 } },
+        }]);
+        assert_eq!(
+            parse_locations(&link),
+            vec![Location {
+                path: PathBuf::from("/repo/src/b.rs"),
+                line: 11
+            }]
+        );
+        assert!(parse_locations(&Value::Null).is_empty());
+    }
+}
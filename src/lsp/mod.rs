@@ -0,0 +1,445 @@
+//! Language-server enrichment of the code graph
+//!
+//! Tree-sitter links a call (or a type use) to a symbol by name, so a call to
+//! `new` in a project with ten `new` functions lands on one of them
+//! (`fuzzy-unique` / `fuzzy-ambiguous`, confidence 0.30-0.50). When enabled
+//! (`lsp.enabled` in config.yaml), [`enrich_project`] runs after a sync that
+//! stored files: it starts the project's language servers (rust-analyzer,
+//! typescript-language-server, gopls by default), asks them for the
+//! definition of each occurrence of the name in the calling function, and
+//! points the relationship at the function or type declared there
+//! (`lsp-resolved`, [`LSP_CONFIDENCE`]). A name defined outside the project
+//! (a dependency, the standard library) gets its relationships removed.
+//! References the server cannot place are left as the sync linked them.
+
+pub mod client;
+
+use crate::neo4j::models::{CodeReferenceKind, CodeReferenceNode};
+use crate::neo4j::GraphStore;
+use crate::parser::SupportedLanguage;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use uuid::Uuid;
+
+use client::LspClient;
+
+/// Confidence of a relationship resolved by a language server
+pub const LSP_CONFIDENCE: f64 = 0.95;
+
+/// Relationships linked at least this confidently at sync time (same file,
+/// resolved import) are not checked
+const MAX_CONFIDENCE: f64 = 0.85;
+
+/// Occurrences of a name in a function asked about before giving up
+const MAX_SITES: usize = 3;
+
+/// A language server command.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LspServerConfig {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// `lsp` section of config.yaml.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LspConfig {
+    /// Run the enrichment after each sync that stored files
+    pub enabled: bool,
+    /// Servers by language (`rust`, `typescript`, `go`, `python`…),
+    /// overriding the built-in ones; an empty command disables a language
+    pub servers: HashMap<String, LspServerConfig>,
+    /// Seconds a server may take to index the project, and to answer
+    pub timeout_secs: u64,
+    /// References checked per sync, least confident first
+    pub max_references: usize,
+}
+
+impl Default for LspConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            servers: HashMap::new(),
+            timeout_secs: 60,
+            max_references: 2000,
+        }
+    }
+}
+
+impl LspConfig {
+    /// Validate the configuration (server languages).
+    pub fn check(&self) -> Result<(), String> {
+        for language in self.servers.keys() {
+            if !SupportedLanguage::all()
+                .iter()
+                .any(|l| l.as_str() == language)
+            {
+                return Err(format!("servers: unknown language `{}`", language));
+            }
+        }
+        Ok(())
+    }
+
+    /// Server of a language: the configured one, else the built-in one.
+    pub fn server(&self, language: SupportedLanguage) -> Option<LspServerConfig> {
+        if let Some(server) = self.servers.get(language.as_str()) {
+            return (!server.command.is_empty()).then(|| server.clone());
+        }
+        let (command, args): (&str, &[&str]) = match language {
+            SupportedLanguage::Rust => ("rust-analyzer", &[]),
+            SupportedLanguage::TypeScript => ("typescript-language-server", &["--stdio"]),
+            SupportedLanguage::Go => ("gopls", &[]),
+            _ => return None,
+        };
+        Some(LspServerConfig {
+            command: command.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+        })
+    }
+}
+
+/// Outcome of [`enrich_project`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EnrichReport {
+    /// References pointed at the symbol the server found
+    pub resolved: usize,
+    /// References to symbols outside the project, removed
+    pub removed: usize,
+    /// References the server could not place
+    pub unresolved: usize,
+    /// Languages whose server could not be started
+    pub failed_languages: Vec<String>,
+}
+
+/// LSP `languageId` of a file.
+fn language_id(path: &Path, language: SupportedLanguage) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("tsx") => "typescriptreact",
+        Some("js" | "mjs" | "cjs") => "javascript",
+        Some("jsx") => "javascriptreact",
+        _ => language.as_str(),
+    }
+}
+
+fn language_of(path: &str) -> Option<SupportedLanguage> {
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .and_then(SupportedLanguage::from_extension)
+}
+
+/// Positions (0-based line, UTF-16 column) of the occurrences of `name` as
+/// a whole identifier in lines `line_start..=line_end` (1-based) of `text`,
+/// at most `max`.
+pub(crate) fn reference_sites(
+    text: &str,
+    line_start: u32,
+    line_end: u32,
+    name: &str,
+    max: usize,
+) -> Vec<(u32, u32)> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    let mut sites = Vec::new();
+    if name.is_empty() {
+        return sites;
+    }
+    let lines = text
+        .lines()
+        .enumerate()
+        .skip(line_start.saturating_sub(1) as usize)
+        .take(line_end.saturating_sub(line_start) as usize + 1);
+    for (index, line) in lines {
+        for (col, _) in line.match_indices(name) {
+            let before = line[..col].chars().next_back();
+            let after = line[col + name.len()..].chars().next();
+            if before.is_some_and(is_ident) || after.is_some_and(is_ident) {
+                continue;
+            }
+            sites.push((index as u32, line[..col].encode_utf16().count() as u32));
+            if sites.len() == max {
+                return sites;
+            }
+        }
+    }
+    sites
+}
+
+/// Graph path of a file the server points to: the graph stores paths under
+/// the root as synced, the server reports canonical ones.
+fn graph_path(location: &Path, root: &Path, canonical_root: &Path) -> Option<String> {
+    let relative = location
+        .strip_prefix(canonical_root)
+        .or_else(|_| location.strip_prefix(root))
+        .ok()?;
+    Some(root.join(relative).to_string_lossy().into_owned())
+}
+
+/// What the server says about one reference.
+enum Resolution {
+    Symbol(String),
+    External,
+    Unknown,
+}
+
+/// Source file of a reference, read once per pass.
+struct SourceFile {
+    /// Path as the server sees it
+    path: PathBuf,
+    text: String,
+}
+
+/// State of the pass for one language server.
+struct Resolver<'a> {
+    graph: &'a dyn GraphStore,
+    client: LspClient,
+    root: &'a Path,
+    canonical_root: &'a Path,
+    files: HashMap<String, Option<SourceFile>>,
+    /// Declared symbols by (kind, graph path, 1-based line)
+    symbols: HashMap<(CodeReferenceKind, String, u32), Option<String>>,
+}
+
+impl Resolver<'_> {
+    async fn resolve(&mut self, reference: &CodeReferenceNode) -> Result<Resolution> {
+        if !self.files.contains_key(&reference.file_path) {
+            let file = match tokio::fs::read_to_string(&reference.file_path).await {
+                Ok(text) => Some(SourceFile {
+                    path: std::fs::canonicalize(&reference.file_path)
+                        .unwrap_or_else(|_| PathBuf::from(&reference.file_path)),
+                    text,
+                }),
+                Err(_) => None,
+            };
+            self.files.insert(reference.file_path.clone(), file);
+        }
+        let Some(Some(file)) = self.files.get(&reference.file_path) else {
+            return Ok(Resolution::Unknown);
+        };
+        let Some(language) = language_of(&reference.file_path) else {
+            return Ok(Resolution::Unknown);
+        };
+        let language_id = language_id(&file.path, language);
+        let sites = reference_sites(
+            &file.text,
+            reference.line_start,
+            reference.line_end,
+            &reference.name,
+            MAX_SITES,
+        );
+
+        // External only if no occurrence points into the project
+        let (mut external, mut internal) = (false, false);
+        for (line, character) in sites {
+            let locations = self
+                .client
+                .definition(&file.path, language_id, &file.text, line, character)
+                .await?;
+            if locations.is_empty() {
+                continue;
+            }
+            let mut in_project = false;
+            for location in locations {
+                let Some(path) = graph_path(&location.path, self.root, self.canonical_root) else {
+                    continue;
+                };
+                in_project = true;
+                let key = (reference.kind, path, location.line + 1);
+                let symbol = match self.symbols.get(&key) {
+                    Some(symbol) => symbol.clone(),
+                    None => {
+                        let symbol = self
+                            .graph
+                            .find_symbol_declared_at(key.0, &key.1, key.2)
+                            .await?;
+                        self.symbols.insert(key, symbol.clone());
+                        symbol
+                    }
+                };
+                // Not the function itself (its own name, a local binding)
+                if let Some(id) = symbol.filter(|id| *id != reference.function_id) {
+                    return Ok(Resolution::Symbol(id));
+                }
+            }
+            if in_project {
+                internal = true;
+            } else {
+                external = true;
+            }
+        }
+        Ok(if external && !internal {
+            Resolution::External
+        } else {
+            Resolution::Unknown
+        })
+    }
+}
+
+/// Resolve the name-based CALLS and USES_TYPE relationships of a project
+/// with its language servers.
+pub async fn enrich_project(
+    graph: &dyn GraphStore,
+    root: &Path,
+    project_id: Uuid,
+    config: &LspConfig,
+) -> Result<EnrichReport> {
+    let references = graph
+        .get_name_based_references(project_id, MAX_CONFIDENCE, config.max_references)
+        .await?;
+    let mut by_language: BTreeMap<&'static str, (SupportedLanguage, Vec<CodeReferenceNode>)> =
+        BTreeMap::new();
+    for reference in references {
+        if let Some(language) = language_of(&reference.file_path) {
+            by_language
+                .entry(language.as_str())
+                .or_insert_with(|| (language, Vec::new()))
+                .1
+                .push(reference);
+        }
+    }
+
+    let canonical_root = std::fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    let timeout = Duration::from_secs(config.timeout_secs.max(1));
+    let mut report = EnrichReport::default();
+    for (name, (language, references)) in by_language {
+        let Some(server) = config.server(language) else {
+            continue;
+        };
+        let client = match LspClient::start(&server, &canonical_root, timeout).await {
+            Ok(client) => client,
+            Err(e) => {
+                tracing::warn!("LSP enrichment: cannot start the {} server: {}", name, e);
+                report.failed_languages.push(name.to_string());
+                continue;
+            }
+        };
+        let mut resolver = Resolver {
+            graph,
+            client,
+            root,
+            canonical_root: &canonical_root,
+            files: HashMap::new(),
+            symbols: HashMap::new(),
+        };
+
+        for reference in &references {
+            let resolution = match resolver.resolve(reference).await {
+                Ok(resolution) => resolution,
+                Err(e) => {
+                    // A server that stopped answering will not answer the rest
+                    tracing::warn!("LSP enrichment: {} server failed: {}", name, e);
+                    report.failed_languages.push(name.to_string());
+                    break;
+                }
+            };
+            let target = match &resolution {
+                Resolution::Symbol(id) => Some(id.as_str()),
+                Resolution::External => None,
+                Resolution::Unknown => {
+                    report.unresolved += 1;
+                    continue;
+                }
+            };
+            graph
+                .resolve_code_reference(
+                    reference.kind,
+                    &reference.function_id,
+                    &reference.name,
+                    target,
+                    LSP_CONFIDENCE,
+                    "lsp-resolved",
+                )
+                .await?;
+            match resolution {
+                Resolution::Symbol(_) => report.resolved += 1,
+                _ => report.removed += 1,
+            }
+        }
+        resolver.client.shutdown().await;
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_sites() {
+        let text =
+            "fn run() {\n    let parser = Parser::new();\n    new_parser(); Parser::new()\n}\n";
+        // `new` as a whole identifier only, on lines 2-3
+        assert_eq!(
+            reference_sites(text, 2, 3, "new", 5),
+            vec![(1, 25), (2, 26)]
+        );
+        assert_eq!(reference_sites(text, 2, 3, "new", 1), vec![(1, 25)]);
+        assert!(reference_sites(text, 1, 1, "new", 5).is_empty());
+        // Columns count UTF-16 units
+        assert_eq!(reference_sites("é(); f()", 1, 1, "f", 5), vec![(0, 5)]);
+    }
+
+    #[test]
+    fn test_graph_path() {
+        let root = Path::new("/home/dev/code/api");
+        let canonical = Path::new("/data/code/api");
+        assert_eq!(
+            graph_path(Path::new("/data/code/api/src/lib.rs"), root, canonical).as_deref(),
+            Some("/home/dev/code/api/src/lib.rs")
+        );
+        assert_eq!(
+            graph_path(Path::new("/home/dev/code/api/src/lib.rs"), root, canonical).as_deref(),
+            Some("/home/dev/code/api/src/lib.rs")
+        );
+        assert_eq!(
+            graph_path(
+                Path::new("/home/dev/.cargo/registry/src/serde/lib.rs"),
+                root,
+                canonical
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_config() {
+        let config: LspConfig = serde_yaml::from_str(
+            "enabled: true\nservers:\n  python:\n    command: pyright-langserver\n    args: [--stdio]\n  go:\n    command: ''\n",
+        )
+        .unwrap();
+        assert!(config.check().is_ok());
+        assert_eq!(config.max_references, 2000);
+        assert_eq!(
+            config.server(SupportedLanguage::Rust).unwrap().command,
+            "rust-analyzer"
+        );
+        assert_eq!(
+            config.server(SupportedLanguage::Python).unwrap().args,
+            vec!["--stdio"]
+        );
+        // Disabled, and no built-in server
+        assert!(config.server(SupportedLanguage::Go).is_none());
+        assert!(config.server(SupportedLanguage::Java).is_none());
+
+        let typo: LspConfig = serde_yaml::from_str("servers:\n  rustt:\n    command: x\n").unwrap();
+        assert!(typo.check().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_enrich_without_references() {
+        let graph = crate::neo4j::mock::MockGraphStore::new();
+        let config = LspConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        // Nothing to resolve: no server is started
+        let report = enrich_project(&graph, Path::new("/nonexistent"), Uuid::new_v4(), &config)
+            .await
+            .unwrap();
+        assert_eq!(report.resolved + report.removed + report.unresolved, 0);
+        assert!(report.failed_languages.is_empty());
+    }
+}
@@ -3,10 +3,16 @@
 //! An AI agent orchestrator with Neo4j, Meilisearch, and Tree-sitter.
 
 use anyhow::Result;
-use clap::{Parser, Subcommand};
-use project_orchestrator::{orchestrator::Orchestrator, setup_claude, update, AppState, Config};
+use clap::{CommandFactory, Parser, Subcommand};
+use project_orchestrator::{
+    cli_bench, cli_graph, cli_import, cli_search, cli_snapshot, completions, config_validation,
+    doctor, logging,
+    meilisearch::MeiliClient,
+    neo4j::{GraphStore, Neo4jClient},
+    orchestrator::Orchestrator,
+    setup_claude, tui, update, AppState, Config,
+};
 use std::path::PathBuf;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[derive(Parser)]
 #[command(name = "orchestrator")]
@@ -35,6 +41,11 @@ enum Commands {
         /// Path to the frontend dist/ directory (overrides config.yaml)
         #[arg(long)]
         frontend_path: Option<String>,
+
+        /// Headless (Kubernetes) profile: config from env only, no frontend,
+        /// JSON logs, graceful SIGTERM shutdown. Also enabled by PO_HEADLESS=1
+        #[arg(long)]
+        headless: bool,
     },
 
     /// Sync a directory to the knowledge base
@@ -49,6 +60,18 @@ enum Commands {
         /// Only check for updates, don't install
         #[arg(long)]
         check: bool,
+
+        /// Release channel to follow; saved to config.yaml (update.channel)
+        #[arg(long, value_enum)]
+        channel: Option<update::UpdateChannel>,
+
+        /// Restore the binary replaced by the last update
+        #[arg(long, conflicts_with_all = ["check", "channel"])]
+        rollback: bool,
+
+        /// Install even if the release signature is missing or invalid
+        #[arg(long, conflicts_with = "rollback")]
+        insecure: bool,
     },
 
     /// Configure Claude Code to use this server as MCP provider
@@ -57,6 +80,224 @@ enum Commands {
         #[arg(long)]
         port: Option<u16>,
     },
+
+    /// Inspect the configuration file
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
+    /// Search the index (code and notes) from the terminal
+    Search {
+        /// Search query
+        query: String,
+
+        /// Restrict to one project (slug)
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Only return one kind of result (default: all)
+        #[arg(long, value_enum)]
+        kind: Option<cli_search::SearchKind>,
+
+        /// Maximum number of results
+        #[arg(short = 'n', long, default_value_t = 10)]
+        limit: usize,
+
+        /// Print the results as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Query the code graph: callers, callees, trait implementors, call paths
+    Graph {
+        #[command(subcommand)]
+        action: GraphAction,
+
+        /// Restrict to one project (slug)
+        #[arg(long, global = true)]
+        project: Option<String>,
+
+        /// Print the results as JSON
+        #[arg(long, global = true)]
+        json: bool,
+    },
+
+    /// Create and sync a project for every git repository under a directory
+    Import {
+        /// Directory holding the repositories (e.g. ~/code)
+        #[arg(long)]
+        root: PathBuf,
+
+        /// Directory levels below the root searched for repositories
+        #[arg(long, default_value_t = cli_import::DEFAULT_MAX_DEPTH)]
+        max_depth: usize,
+
+        /// Number of projects synced at once
+        #[arg(long, default_value_t = cli_import::DEFAULT_CONCURRENCY)]
+        concurrency: usize,
+
+        /// Only list the repositories that would be imported
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Benchmark sync, analytics and search on a generated repository
+    ///
+    /// The synthetic project and its files are deleted afterwards.
+    Bench {
+        /// Source files generated
+        #[arg(long, default_value_t = cli_bench::DEFAULT_FILES)]
+        files: usize,
+
+        /// Functions per generated file
+        #[arg(long, default_value_t = cli_bench::DEFAULT_SYMBOLS_PER_FILE)]
+        symbols_per_file: usize,
+
+        /// Search queries replayed
+        #[arg(long, default_value_t = cli_bench::DEFAULT_QUERIES)]
+        queries: usize,
+
+        /// Search queries in flight at once
+        #[arg(long, default_value_t = cli_bench::DEFAULT_CONCURRENCY)]
+        concurrency: usize,
+
+        /// Seed of the generated code and queries
+        #[arg(long, default_value_t = 42)]
+        seed: u64,
+
+        /// Keep the project and the generated files
+        #[arg(long)]
+        keep: bool,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Back up or restore a project's plans, notes and search documents
+    Snapshot {
+        #[command(subcommand)]
+        action: SnapshotAction,
+    },
+
+    /// Live terminal dashboard: projects, sync status, chat sessions, events
+    Tui {
+        /// URL of the server to watch (default: this host's server port)
+        #[arg(long, env = "ORCHESTRATOR_URL")]
+        url: Option<String>,
+    },
+
+    /// Print a shell completion script
+    ///
+    /// bash: `orchestrator completions bash > /etc/bash_completion.d/orchestrator`,
+    /// zsh: `source <(orchestrator completions zsh)`,
+    /// fish: `orchestrator completions fish > ~/.config/fish/completions/orchestrator.fish`
+    Completions {
+        #[arg(value_enum)]
+        shell: completions::Shell,
+    },
+
+    /// Generate man pages (one per subcommand)
+    Man {
+        /// Write the pages into this directory instead of printing
+        /// orchestrator(1) to stdout
+        #[arg(long)]
+        out_dir: Option<PathBuf>,
+    },
+
+    /// Diagnose the installation: config, backends, Claude CLI, MCP, port, disk
+    Doctor {
+        /// Print the report as JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Strictly validate config.yaml: YAML/type errors with line numbers,
+    /// unknown keys, and cross-field checks
+    Validate {
+        /// Treat warnings (unknown keys, default passwords) as errors
+        #[arg(long)]
+        strict: bool,
+
+        /// Print the issues as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum GraphAction {
+    /// Functions that call <symbol>
+    Callers {
+        /// Function name
+        symbol: String,
+
+        /// Follow calls transitively up to this many hops (names only)
+        #[arg(long, default_value_t = 1)]
+        depth: u32,
+
+        /// Ignore CALLS edges scored below this confidence (0.0-1.0)
+        #[arg(long, default_value_t = 0.0)]
+        min_confidence: f64,
+    },
+    /// Functions called by <symbol>
+    Callees {
+        /// Function name
+        symbol: String,
+
+        /// Follow calls transitively up to this many hops (names only)
+        #[arg(long, default_value_t = 1)]
+        depth: u32,
+
+        /// Ignore CALLS edges scored below this confidence (0.0-1.0)
+        #[arg(long, default_value_t = 0.0)]
+        min_confidence: f64,
+    },
+    /// Types implementing <trait>
+    Implementors {
+        /// Trait name
+        #[arg(value_name = "TRAIT")]
+        trait_name: String,
+    },
+    /// Shortest call chain from <from> to <to>
+    Path {
+        from: String,
+        to: String,
+
+        /// Maximum number of hops to explore (at most 6)
+        #[arg(long, default_value_t = cli_graph::DEFAULT_PATH_DEPTH)]
+        max_depth: u32,
+    },
+}
+
+#[derive(Subcommand)]
+enum SnapshotAction {
+    /// Write a snapshot archive of a project
+    Create {
+        /// Project slug
+        project: String,
+
+        /// Archive path (default: <slug>-<timestamp>.snapshot.tar.gz)
+        #[arg(short, long)]
+        out: Option<PathBuf>,
+    },
+    /// Replace a project's plans, notes and search documents with an archive
+    Restore {
+        /// Archive written by `snapshot create`
+        archive: PathBuf,
+
+        /// Print what the archive holds without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 #[tokio::main]
@@ -64,25 +305,64 @@ async fn main() -> Result<()> {
     // Load .env file
     dotenvy::dotenv().ok();
 
-    // Initialize tracing
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "info,project_orchestrator=debug,tower_http=debug".into()),
-        )
-        .with(tracing_subscriber::fmt::layer())
-        .init();
-
     let cli = Cli::parse();
 
+    // Completions and man pages only describe the CLI — no config needed
+    match cli.command {
+        Commands::Completions { shell } => {
+            print!(
+                "{}",
+                completions::completion_script(shell, &mut Cli::command())
+            );
+            return Ok(());
+        }
+        Commands::Man { ref out_dir } => {
+            match out_dir {
+                Some(dir) => {
+                    completions::write_man_pages(Cli::command(), dir)?;
+                    println!("Man pages written to {}", dir.display());
+                }
+                None => print!("{}", completions::man_page(Cli::command())?),
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let headless = matches!(cli.command, Commands::Serve { headless: true, .. })
+        || std::env::var("PO_HEADLESS").is_ok_and(|v| v == "1" || v == "true");
+
     // Load configuration — explicit --config path wins, otherwise auto-detect
-    let mut config = Config::from_yaml_and_env(cli.config.as_deref())?;
+    // (headless: env only, unless --config is given). The final log format
+    // comes from the config, so loading logs through a bootstrap subscriber.
+    let bootstrap_format = if headless {
+        logging::LogFormat::Json
+    } else {
+        std::env::var("LOG_FORMAT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_default()
+    };
+    let mut config =
+        tracing::dispatcher::with_default(&logging::bootstrap_dispatch(bootstrap_format), || {
+            if headless {
+                Config::headless_from_env(cli.config.as_deref())
+            } else {
+                Config::from_yaml_and_env(cli.config.as_deref())
+            }
+        })?;
+
+    // Initialize tracing — JSON lines in headless mode (or logging.format: json)
+    // so log collectors (Loki, CloudWatch, Stackdriver…) can parse fields; the
+    // filter stays adjustable at runtime via PUT /api/admin/log-level
+    logging::init(config.log_format, &config.log_filter);
 
     match cli.command {
         Commands::Serve {
             port,
             no_frontend,
             frontend_path,
+            headless: _,
         } => {
             // --port flag overrides config.yaml; if neither is set, default to 8080
             if let Some(p) = port {
@@ -99,13 +379,319 @@ async fn main() -> Result<()> {
             project_orchestrator::start_server(config).await
         }
         Commands::Sync { path } => run_sync(config, &path).await,
-        Commands::Update { check } => run_update(check).await,
+        Commands::Update {
+            check,
+            channel,
+            rollback,
+            insecure,
+        } => {
+            if rollback {
+                run_rollback()
+            } else {
+                run_update(&config, check, channel, insecure).await
+            }
+        }
         Commands::SetupClaude { port } => {
             let effective_port = port.unwrap_or(config.server_port);
             run_setup_claude(&config, effective_port);
             Ok(())
         }
+        Commands::Config {
+            action: ConfigAction::Validate { strict, json },
+        } => run_config_validate(&config, strict, json),
+        Commands::Doctor { json } => run_doctor(&config, json).await,
+        Commands::Tui { url } => tui::run(&config, url).await,
+        Commands::Completions { .. } | Commands::Man { .. } => unreachable!("handled above"),
+        Commands::Search {
+            query,
+            project,
+            kind,
+            limit,
+            json,
+        } => {
+            let opts = cli_search::SearchOptions {
+                query,
+                project_slug: project,
+                kind,
+                limit,
+            };
+            run_search(&config, &opts, json).await
+        }
+        Commands::Graph {
+            action,
+            project,
+            json,
+        } => run_graph(&config, action, project.as_deref(), json).await,
+        Commands::Snapshot { action } => run_snapshot(&config, action).await,
+        Commands::Import {
+            root,
+            max_depth,
+            concurrency,
+            dry_run,
+            json,
+        } => {
+            let opts = cli_import::ImportOptions {
+                root,
+                max_depth,
+                concurrency,
+                dry_run,
+            };
+            run_import(config, opts, json).await
+        }
+        Commands::Bench {
+            files,
+            symbols_per_file,
+            queries,
+            concurrency,
+            seed,
+            keep,
+            json,
+        } => {
+            let opts = cli_bench::BenchOptions {
+                files,
+                symbols_per_file,
+                queries,
+                concurrency,
+                seed,
+                keep,
+            };
+            run_bench(config, opts, json).await
+        }
+    }
+}
+
+async fn run_search(config: &Config, opts: &cli_search::SearchOptions, json: bool) -> Result<()> {
+    let meili = MeiliClient::new(&config.meilisearch_url, &config.meilisearch_key).await?;
+
+    // Line numbers for function hits come from the graph — optional, so a
+    // down Neo4j only costs the line column
+    let graph = if opts.kind.is_none() || opts.kind == Some(cli_search::SearchKind::Function) {
+        match tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            Neo4jClient::new(
+                &config.neo4j_uri,
+                &config.neo4j_user,
+                &config.neo4j_password,
+            ),
+        )
+        .await
+        {
+            Ok(Ok(client)) => Some(client.with_query_limits(config.neo4j_query_limits())),
+            Ok(Err(e)) => {
+                tracing::warn!("Neo4j unavailable, printing results without lines: {}", e);
+                None
+            }
+            Err(_) => {
+                tracing::warn!("Neo4j connection timed out, printing results without lines");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let results =
+        cli_search::search(&meili, graph.as_ref().map(|g| g as &dyn GraphStore), opts).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+    } else {
+        print!("{}", cli_search::render(&results));
+    }
+    Ok(())
+}
+
+async fn run_graph(
+    config: &Config,
+    action: GraphAction,
+    project: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let neo4j = Neo4jClient::new(
+        &config.neo4j_uri,
+        &config.neo4j_user,
+        &config.neo4j_password,
+    )
+    .await?
+    .with_query_limits(config.neo4j_query_limits());
+
+    let project_id = match project {
+        Some(slug) => Some(
+            neo4j
+                .get_project_by_slug(slug)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("Project not found: {}", slug))?
+                .id,
+        ),
+        None => None,
+    };
+
+    let output = match action {
+        GraphAction::Callers {
+            symbol,
+            depth,
+            min_confidence,
+        } => {
+            let edges = cli_graph::calls(
+                &neo4j,
+                cli_graph::Direction::Callers,
+                &symbol,
+                depth,
+                project_id,
+                min_confidence,
+            )
+            .await?;
+            render_graph_calls(&edges, json)?
+        }
+        GraphAction::Callees {
+            symbol,
+            depth,
+            min_confidence,
+        } => {
+            let edges = cli_graph::calls(
+                &neo4j,
+                cli_graph::Direction::Callees,
+                &symbol,
+                depth,
+                project_id,
+                min_confidence,
+            )
+            .await?;
+            render_graph_calls(&edges, json)?
+        }
+        GraphAction::Implementors { trait_name } => {
+            let implementors = cli_graph::implementors(&neo4j, &trait_name).await?;
+            if json {
+                serde_json::to_string_pretty(&implementors)? + "\n"
+            } else {
+                cli_graph::render_implementors(&implementors)
+            }
+        }
+        GraphAction::Path {
+            from,
+            to,
+            max_depth,
+        } => {
+            let max_depth = max_depth.min(cli_graph::DEFAULT_PATH_DEPTH);
+            let chain = cli_graph::path(&neo4j, &from, &to, max_depth, project_id).await?;
+            if json {
+                serde_json::to_string_pretty(&chain)? + "\n"
+            } else {
+                cli_graph::render_path(chain.as_deref())
+            }
+        }
+    };
+    print!("{}", output);
+    Ok(())
+}
+
+async fn run_snapshot(config: &Config, action: SnapshotAction) -> Result<()> {
+    if let SnapshotAction::Restore {
+        ref archive,
+        dry_run: true,
+    } = action
+    {
+        let snapshot = cli_snapshot::read_archive(std::fs::File::open(archive)?)?;
+        let manifest = &snapshot.manifest;
+        println!(
+            "Snapshot of {} taken {} (orchestrator {})",
+            manifest.project_slug,
+            manifest.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
+            manifest.orchestrator_version
+        );
+        print!("{}", cli_snapshot::render_counts(&manifest.counts));
+        return Ok(());
     }
+
+    let neo4j = Neo4jClient::new(
+        &config.neo4j_uri,
+        &config.neo4j_user,
+        &config.neo4j_password,
+    )
+    .await?
+    .with_query_limits(config.neo4j_query_limits());
+    let meili = MeiliClient::new(&config.meilisearch_url, &config.meilisearch_key).await?;
+
+    match action {
+        SnapshotAction::Create { project, out } => {
+            let project = neo4j
+                .get_project_by_slug(&project)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("Project not found: {}", project))?;
+            let snapshot = cli_snapshot::capture(&neo4j, &meili, &project).await?;
+            let path = out.unwrap_or_else(|| {
+                PathBuf::from(cli_snapshot::default_archive_name(
+                    &project.slug,
+                    snapshot.manifest.created_at,
+                ))
+            });
+            let file = std::fs::File::create(&path)?;
+            cli_snapshot::write_archive(&snapshot, std::io::BufWriter::new(file))?;
+            println!("Snapshot of {} written to {}", project.slug, path.display());
+            print!("{}", cli_snapshot::render_counts(&snapshot.manifest.counts));
+        }
+        SnapshotAction::Restore { archive, .. } => {
+            let snapshot = cli_snapshot::read_archive(std::fs::File::open(&archive)?)?;
+            let report = cli_snapshot::restore(&neo4j, &meili, &snapshot).await?;
+            if report.project_created {
+                println!("Project {} recreated", report.project_slug);
+            }
+            println!("Removed from {}:", report.project_slug);
+            print!("{}", cli_snapshot::render_counts(&report.removed));
+            println!("Restored from {}:", archive.display());
+            print!("{}", cli_snapshot::render_counts(&report.restored));
+        }
+    }
+    Ok(())
+}
+
+fn render_graph_calls(edges: &[cli_graph::CallEdge], json: bool) -> Result<String> {
+    Ok(if json {
+        serde_json::to_string_pretty(edges)? + "\n"
+    } else {
+        cli_graph::render_calls(edges)
+    })
+}
+
+fn run_config_validate(config: &Config, strict: bool, json: bool) -> Result<()> {
+    let Some(ref path) = config.config_yaml_path else {
+        eprintln!("No config.yaml found (pass --config <path>).");
+        std::process::exit(2);
+    };
+    let report = config_validation::validate_file(path)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", report.render(&path.display().to_string()));
+        let errors = report.errors().count();
+        let warnings = report.warnings().count();
+        if errors == 0 && warnings == 0 {
+            println!("{}: OK", path.display());
+        } else {
+            println!("{} error(s), {} warning(s)", errors, warnings);
+        }
+    }
+    if !report.is_valid(strict) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+async fn run_doctor(config: &Config, json: bool) -> Result<()> {
+    let report = doctor::run_doctor(config).await;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!(
+            "Project Orchestrator doctor (v{})",
+            env!("CARGO_PKG_VERSION")
+        );
+        println!();
+        print!("{}", report.render());
+    }
+    if !report.is_healthy() {
+        std::process::exit(1);
+    }
+    Ok(())
 }
 
 fn run_setup_claude(config: &Config, port: u16) {
@@ -182,14 +768,39 @@ fn run_setup_claude(config: &Config, port: u16) {
     }
 }
 
-async fn run_update(check_only: bool) -> Result<()> {
-    println!("Checking for updates...");
+async fn run_update(
+    config: &Config,
+    check_only: bool,
+    channel: Option<update::UpdateChannel>,
+    insecure: bool,
+) -> Result<()> {
+    // An explicit --channel becomes the configured channel
+    if let Some(channel) = channel.filter(|c| *c != config.update_channel) {
+        match config.config_yaml_path {
+            Some(ref path) => {
+                update::persist_channel(path, channel)?;
+                println!(
+                    "Update channel set to {} (saved to {}).",
+                    channel,
+                    path.display()
+                );
+            }
+            None => println!(
+                "Using the {} channel for this run (no config.yaml to save it to).",
+                channel
+            ),
+        }
+    }
+    let channel = channel.unwrap_or(config.update_channel);
+
+    println!("Checking for updates ({} channel)...", channel);
 
-    let info = match update::check_for_update().await? {
+    let info = match update::check_for_update(channel).await? {
         Some(info) => info,
         None => {
             println!(
-                "You're already on the latest version (v{}).",
+                "You're already on the latest {} version (v{}).",
+                channel,
                 env!("CARGO_PKG_VERSION")
             );
             return Ok(());
@@ -223,6 +834,9 @@ async fn run_update(check_only: bool) -> Result<()> {
 
     // Ask for confirmation
     println!();
+    if insecure {
+        println!("  WARNING: --insecure skips the release signature check.");
+    }
     print!("  Install update? [Y/n] ");
     std::io::Write::flush(&mut std::io::stdout())?;
 
@@ -236,19 +850,62 @@ async fn run_update(check_only: bool) -> Result<()> {
     }
 
     println!();
-    match update::perform_update(&info).await? {
+    match update::perform_update(&info, config.update_public_key.as_deref(), insecure).await? {
         update::UpdateStatus::Updated { from, to } => {
             println!("  Successfully updated from v{} to v{}!", from, to);
             println!("  Please restart orchestrator to use the new version.");
+            println!("  (`orchestrator update --rollback` restores v{}.)", from);
         }
         update::UpdateStatus::AlreadyUpToDate => {
             println!("  Already up to date.");
         }
+        update::UpdateStatus::RolledBack { .. } => unreachable!("perform_update never rolls back"),
     }
 
     Ok(())
 }
 
+fn run_rollback() -> Result<()> {
+    match update::rollback()? {
+        update::UpdateStatus::RolledBack { from, to } => {
+            println!("Rolled back from v{} to v{}.", from, to);
+            println!("Please restart orchestrator to use the restored version.");
+        }
+        _ => unreachable!("rollback only reports RolledBack"),
+    }
+    Ok(())
+}
+
+async fn run_import(config: Config, opts: cli_import::ImportOptions, json: bool) -> Result<()> {
+    let state = AppState::new(config).await?;
+    let orchestrator = Orchestrator::new(state).await?;
+
+    let report = cli_import::import(&orchestrator, &opts).await?;
+    flush_index_queue(&orchestrator).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", cli_import::render(&report));
+    }
+    if report.has_failures() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+async fn run_bench(config: Config, opts: cli_bench::BenchOptions, json: bool) -> Result<()> {
+    let state = AppState::new(config).await?;
+    let orchestrator = Orchestrator::new(state).await?;
+
+    let report = cli_bench::run(&orchestrator, &opts).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", cli_bench::render(&report));
+    }
+    Ok(())
+}
+
 async fn run_sync(config: Config, path: &str) -> Result<()> {
     tracing::info!("Syncing directory: {}", path);
 
@@ -263,6 +920,7 @@ async fn run_sync(config: Config, path: &str) -> Result<()> {
     let result = orchestrator
         .sync_directory(std::path::Path::new(path))
         .await?;
+    flush_index_queue(&orchestrator).await?;
 
     tracing::info!(
         "Sync complete: {} files synced, {} skipped, {} errors",
@@ -273,3 +931,22 @@ async fn run_sync(config: Config, path: &str) -> Result<()> {
 
     Ok(())
 }
+
+/// Longest a CLI command waits for its queued code documents to be indexed
+const CLI_INDEX_FLUSH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// Index the code documents a CLI command queued before it exits. Whatever
+/// is left stays queued for the next orchestrator process.
+async fn flush_index_queue(orchestrator: &Orchestrator) -> Result<()> {
+    let pending = orchestrator
+        .meili()
+        .flush_pending(CLI_INDEX_FLUSH_TIMEOUT)
+        .await?;
+    if pending > 0 {
+        tracing::warn!(
+            "{} code documents are not indexed yet; the next orchestrator process will index them",
+            pending
+        );
+    }
+    Ok(())
+}
@@ -186,6 +186,8 @@ impl ToolHandler {
             "admin",
             "skill",
             "analysis_profile",
+            "view",
+            "comment",
             "protocol",
             "episode",
             "persona",
@@ -234,6 +236,10 @@ impl ToolHandler {
             ("project", "set_scaffolding_override") => "set_scaffolding_override",
             ("project", "get_health_dashboard") => "get_health_dashboard",
             ("project", "get_auto_roadmap") => "get_auto_roadmap",
+            ("project", "list_advisories") => "list_project_advisories",
+            ("project", "list_documents") => "list_project_documents",
+            ("project", "get_schema") => "get_project_schema",
+            ("project", "get_overview") => "get_project_overview",
 
             // Plan
             ("plan", "list") => "list_plans",
@@ -302,6 +308,7 @@ impl ToolHandler {
             ("decision", "get_affecting") => "get_decisions_affecting",
             ("decision", "supersede") => "supersede_decision",
             ("decision", "get_timeline") => "get_decision_timeline",
+            ("decision", "export_adrs") => "export_adrs",
 
             // Constraint
             ("constraint", "list") => "list_constraints",
@@ -339,6 +346,7 @@ impl ToolHandler {
             ("commit", "get_plan_commits") => "get_plan_commits",
             ("commit", "get_commit_files") => "get_commit_files",
             ("commit", "get_file_history") => "get_file_history",
+            ("commit", "suggest_message") => "suggest_commit_message",
 
             // Note
             ("note", "list") => "list_notes",
@@ -375,7 +383,13 @@ impl ToolHandler {
             ("workspace", "add_project") => "add_project_to_workspace",
             ("workspace", "remove_project") => "remove_project_from_workspace",
             ("workspace", "get_topology") => "get_workspace_topology",
+            ("workspace", "get_topology_drift") => "get_workspace_topology_drift",
+            ("workspace", "get_license_policy") => "get_workspace_license_policy",
+            ("workspace", "set_license_policy") => "set_workspace_license_policy",
+            ("workspace", "get_license_violations") => "get_workspace_license_violations",
             ("workspace", "get_coupling_matrix") => "get_coupling_matrix",
+            ("workspace", "search") => "search_workspace",
+            ("workspace", "get_context") => "get_workspace_context",
 
             // Workspace Milestone
             ("workspace_milestone", "list_all") => "list_all_workspace_milestones",
@@ -388,6 +402,7 @@ impl ToolHandler {
             ("workspace_milestone", "link_plan") => "link_plan_to_workspace_milestone",
             ("workspace_milestone", "unlink_plan") => "unlink_plan_from_workspace_milestone",
             ("workspace_milestone", "get_progress") => "get_workspace_milestone_progress",
+            ("workspace_milestone", "get_rollup") => "get_workspace_milestone_rollup",
 
             // Resource
             ("resource", "list") => "list_resources",
@@ -396,6 +411,10 @@ impl ToolHandler {
             ("resource", "update") => "update_resource",
             ("resource", "delete") => "delete_resource",
             ("resource", "link_to_project") => "link_resource_to_project",
+            ("resource", "get_usages") => "get_resource_usages",
+            ("resource", "parse") => "parse_resource",
+            ("resource", "list_elements") => "list_resource_elements",
+            ("resource", "diff") => "diff_resources",
 
             // Component
             ("component", "list") => "list_components",
@@ -435,6 +454,12 @@ impl ToolHandler {
             ("code", "search") => "search_code",
             ("code", "search_project") => "search_project_code",
             ("code", "search_workspace") => "search_workspace_code",
+            ("code", "search_docs") => "search_docs",
+            ("code", "find_config_usages") => "find_config_usages",
+            ("code", "get_snippet") => "get_code_snippet",
+            ("code", "propose_rename") => "propose_rename",
+            ("code", "explain_symbol") => "explain_symbol",
+            ("code", "resolve_stack_trace") => "resolve_stack_trace",
             ("code", "get_file_symbols") => "get_file_symbols",
             ("code", "find_references") => "find_references",
             ("code", "get_file_dependencies") => "get_file_dependencies",
@@ -447,6 +472,7 @@ impl ToolHandler {
             ("code", "get_impl_blocks") => "get_impl_blocks",
             ("code", "get_communities") => "get_code_communities",
             ("code", "get_health") => "get_code_health",
+            ("code", "find_duplicates") => "find_duplicates",
             ("code", "get_node_importance") => "get_node_importance",
             ("code", "plan_implementation") => "plan_implementation",
             ("code", "get_co_change_graph") => "get_co_change_graph",
@@ -606,12 +632,28 @@ impl ToolHandler {
             ("analysis_profile", "get") => "get_analysis_profile",
             ("analysis_profile", "delete") => "delete_analysis_profile",
 
+            // Saved View
+            ("view", "list") => "list_views",
+            ("view", "create") => "create_view",
+            ("view", "get") => "get_view",
+            ("view", "update") => "update_view",
+            ("view", "delete") => "delete_view",
+            ("view", "get_results") => "get_view_results",
+
+            // Comment
+            ("comment", "list") => "list_comments",
+            ("comment", "create") => "create_comment",
+            ("comment", "get") => "get_comment",
+            ("comment", "update") => "update_comment",
+            ("comment", "delete") => "delete_comment",
+
             // Admin
             ("admin", "sync_directory") => "sync_directory",
             ("admin", "start_watch") => "start_watch",
             ("admin", "stop_watch") => "stop_watch",
             ("admin", "watch_status") => "watch_status",
             ("admin", "meilisearch_stats") => "get_meilisearch_stats",
+            ("admin", "search_analytics") => "get_search_analytics",
             ("admin", "delete_meilisearch_orphans") => "delete_meilisearch_orphans",
             ("admin", "cleanup_cross_project_calls") => "cleanup_cross_project_calls",
             ("admin", "cleanup_builtin_calls") => "cleanup_builtin_calls",
@@ -934,6 +976,9 @@ impl ToolHandler {
                 if let Some(so) = args.get("sort_order").and_then(|v| v.as_str()) {
                     query.push(("sort_order".to_string(), so.to_string()));
                 }
+                if let Some(ia) = args.get("include_archived").and_then(|v| v.as_bool()) {
+                    query.push(("include_archived".to_string(), ia.to_string()));
+                }
                 let result = if query.is_empty() {
                     http.get("/api/projects").await?
                 } else {
@@ -1003,6 +1048,35 @@ impl ToolHandler {
                 Ok(Some(result))
             }
 
+            "list_project_advisories" => {
+                let project_id = extract_id(args, "project_id")?;
+                let path = format!("/api/projects/{}/advisories", project_id);
+                let result = match args.get("min_severity").and_then(|v| v.as_str()) {
+                    Some(s) => {
+                        http.get_with_query(&path, &[("min_severity".to_string(), s.to_string())])
+                            .await?
+                    }
+                    None => http.get(&path).await?,
+                };
+                Ok(Some(result))
+            }
+
+            "list_project_documents" => {
+                let project_id = extract_id(args, "project_id")?;
+                let result = http
+                    .get(&format!("/api/projects/{}/documents", project_id))
+                    .await?;
+                Ok(Some(result))
+            }
+
+            "get_project_schema" => {
+                let project_id = extract_id(args, "project_id")?;
+                let result = http
+                    .get(&format!("/api/projects/{}/schema", project_id))
+                    .await?;
+                Ok(Some(result))
+            }
+
             "list_project_plans" => {
                 let slug = extract_string(args, "slug")?;
                 let mut query = Vec::new();
@@ -1053,6 +1127,14 @@ impl ToolHandler {
                 Ok(Some(result))
             }
 
+            "get_project_overview" => {
+                let slug = extract_string(args, "slug")?;
+                let result = http
+                    .get(&format!("/api/projects/{}/overview", slug))
+                    .await?;
+                Ok(Some(result))
+            }
+
             "get_health_dashboard" => {
                 let slug = extract_string(args, "slug")?;
                 let result = http
@@ -1936,6 +2018,18 @@ impl ToolHandler {
                 Ok(Some(result))
             }
 
+            "export_adrs" => {
+                let project_id = extract_id(args, "project_id")?;
+                let mut body = json!({});
+                if let Some(did) = extract_optional_string(args, "decision_id") {
+                    body["decision_id"] = json!(did);
+                }
+                let result = http
+                    .post(&format!("/api/projects/{}/adrs/export", project_id), &body)
+                    .await?;
+                Ok(Some(result))
+            }
+
             // ── P5: Milestones (9 tools) ────────────────────────────────
             "list_milestones" => {
                 let project_id = extract_id(args, "project_id")?;
@@ -2290,6 +2384,24 @@ impl ToolHandler {
                 Ok(Some(result))
             }
 
+            "suggest_commit_message" => {
+                let project_id = extract_id(args, "project_id")?;
+                let mut body = json!({});
+                if let Some(v) = args.get("diff").and_then(|v| v.as_str()) {
+                    body["diff"] = json!(v);
+                }
+                if let Some(v) = args.get("staged").and_then(|v| v.as_bool()) {
+                    body["staged"] = json!(v);
+                }
+                let result = http
+                    .post(
+                        &format!("/api/projects/{}/commit-message", project_id),
+                        &body,
+                    )
+                    .await?;
+                Ok(Some(result))
+            }
+
             // ── P7: Notes & Knowledge (23 tools) ──────────────────────────
 
             // --- CRUD (5) ---
@@ -3171,6 +3283,47 @@ impl ToolHandler {
                 Ok(Some(result))
             }
 
+            "get_workspace_topology_drift" => {
+                let slug = extract_string(args, "slug")?;
+                let result = http
+                    .get(&format!("/api/workspaces/{}/topology/drift", slug))
+                    .await?;
+                Ok(Some(result))
+            }
+
+            "get_workspace_license_policy" => {
+                let slug = extract_string(args, "slug")?;
+                let result = http
+                    .get(&format!("/api/workspaces/{}/license-policy", slug))
+                    .await?;
+                Ok(Some(result))
+            }
+
+            "set_workspace_license_policy" => {
+                let slug = extract_string(args, "slug")?;
+                let mut body = serde_json::Map::new();
+                for key in ["allowed", "denied", "flag_unknown"] {
+                    if let Some(v) = args.get(key) {
+                        body.insert(key.to_string(), v.clone());
+                    }
+                }
+                let result = http
+                    .put(
+                        &format!("/api/workspaces/{}/license-policy", slug),
+                        &Value::Object(body),
+                    )
+                    .await?;
+                Ok(Some(result))
+            }
+
+            "get_workspace_license_violations" => {
+                let slug = extract_string(args, "slug")?;
+                let result = http
+                    .get(&format!("/api/workspaces/{}/license-violations", slug))
+                    .await?;
+                Ok(Some(result))
+            }
+
             "get_coupling_matrix" => {
                 let slug = extract_string(args, "slug")?;
                 let result = http
@@ -3179,6 +3332,36 @@ impl ToolHandler {
                 Ok(Some(result))
             }
 
+            "search_workspace" => {
+                let slug = extract_string(args, "slug")?;
+                let mut query = vec![("q".to_string(), extract_string(args, "query")?)];
+                if let Some(v) = args.get("kinds").and_then(|v| v.as_str()) {
+                    query.push(("kinds".to_string(), v.to_string()));
+                }
+                if let Some(v) = args.get("language").and_then(|v| v.as_str()) {
+                    query.push(("language".to_string(), v.to_string()));
+                }
+                if let Some(v) = args.get("limit").and_then(|v| v.as_i64()) {
+                    query.push(("limit".to_string(), v.to_string()));
+                }
+                let result = http
+                    .get_with_query(&format!("/api/workspaces/{}/search", slug), &query)
+                    .await?;
+                Ok(Some(result))
+            }
+
+            "get_workspace_context" => {
+                let slug = extract_string(args, "slug")?;
+                let mut query = Vec::new();
+                if let Some(v) = args.get("limit").and_then(|v| v.as_i64()) {
+                    query.push(("limit".to_string(), v.to_string()));
+                }
+                let result = http
+                    .get_with_query(&format!("/api/workspaces/{}/context", slug), &query)
+                    .await?;
+                Ok(Some(result))
+            }
+
             // --- Workspace Milestones (10) ---
             "list_all_workspace_milestones" => {
                 let mut query = Vec::new();
@@ -3332,6 +3515,18 @@ impl ToolHandler {
                 Ok(Some(result))
             }
 
+            "get_workspace_milestone_rollup" => {
+                let id = extract_id(args, "milestone_id")?;
+                let mut query = Vec::new();
+                if let Some(v) = args.get("window_days").and_then(|v| v.as_u64()) {
+                    query.push(("window_days".to_string(), v.to_string()));
+                }
+                let result = http
+                    .get_with_query(&format!("/api/workspace-milestones/{}/rollup", id), &query)
+                    .await?;
+                Ok(Some(result))
+            }
+
             // --- Resources (6) ---
             "list_resources" => {
                 let slug = extract_string(args, "slug")?;
@@ -3421,6 +3616,42 @@ impl ToolHandler {
                 }))
             }
 
+            "get_resource_usages" => {
+                let id = extract_id(args, "id")?;
+                let result = http.get(&format!("/api/resources/{}/usages", id)).await?;
+                Ok(Some(result))
+            }
+
+            "parse_resource" => {
+                let id = extract_id(args, "id")?;
+                let result = http
+                    .post(&format!("/api/resources/{}/parse", id), &json!({}))
+                    .await?;
+                Ok(Some(result))
+            }
+
+            "list_resource_elements" => {
+                let id = extract_id(args, "id")?;
+                let mut query = Vec::new();
+                if let Some(v) = args.get("kind").and_then(|v| v.as_str()) {
+                    query.push(("kind".to_string(), v.to_string()));
+                }
+                let result = http
+                    .get_with_query(&format!("/api/resources/{}/elements", id), &query)
+                    .await?;
+                Ok(Some(result))
+            }
+
+            "diff_resources" => {
+                let id = extract_id(args, "id")?;
+                let against = extract_id(args, "against")?;
+                let query = vec![("against".to_string(), against)];
+                let result = http
+                    .get_with_query(&format!("/api/resources/{}/diff", id), &query)
+                    .await?;
+                Ok(Some(result))
+            }
+
             // --- Components (8) ---
             "list_components" => {
                 let slug = extract_string(args, "slug")?;
@@ -3564,6 +3795,71 @@ impl ToolHandler {
                 Ok(Some(result))
             }
 
+            "search_docs" => {
+                let query_str = extract_string(args, "query")?;
+                let mut query = vec![("query".to_string(), query_str)];
+                if let Some(v) = args.get("limit").and_then(|v| v.as_i64()) {
+                    query.push(("limit".to_string(), v.to_string()));
+                }
+                if let Some(v) = args.get("project_slug").and_then(|v| v.as_str()) {
+                    query.push(("project_slug".to_string(), v.to_string()));
+                }
+                let result = http.get_with_query("/api/code/docs/search", &query).await?;
+                Ok(Some(result))
+            }
+
+            "find_config_usages" => {
+                let project_slug = extract_string(args, "project_slug")?;
+                let mut query = vec![("project_slug".to_string(), project_slug)];
+                if let Some(v) = args.get("key").and_then(|v| v.as_str()) {
+                    query.push(("key".to_string(), v.to_string()));
+                }
+                if let Some(v) = args.get("limit").and_then(|v| v.as_i64()) {
+                    query.push(("limit".to_string(), v.to_string()));
+                }
+                let result = http.get_with_query("/api/code/config-keys", &query).await?;
+                Ok(Some(result))
+            }
+
+            "get_code_snippet" => {
+                let symbol_id = extract_string(args, "symbol_id")?;
+                let mut query = vec![("symbol_id".to_string(), symbol_id)];
+                if let Some(v) = args.get("context_lines").and_then(|v| v.as_i64()) {
+                    query.push(("context_lines".to_string(), v.to_string()));
+                }
+                let result = http.get_with_query("/api/code/snippet", &query).await?;
+                Ok(Some(result))
+            }
+
+            "propose_rename" => {
+                let query = vec![
+                    ("symbol_id".to_string(), extract_string(args, "symbol_id")?),
+                    ("new_name".to_string(), extract_string(args, "new_name")?),
+                ];
+                let result = http
+                    .get_with_query("/api/code/rename-proposal", &query)
+                    .await?;
+                Ok(Some(result))
+            }
+
+            "explain_symbol" => {
+                let mut query = vec![("symbol".to_string(), extract_string(args, "symbol")?)];
+                if let Some(slug) = extract_optional_string(args, "project_slug") {
+                    query.push(("project_slug".to_string(), slug));
+                }
+                let result = http.get_with_query("/api/code/explain", &query).await?;
+                Ok(Some(result))
+            }
+
+            "resolve_stack_trace" => {
+                let body = json!({
+                    "trace": extract_string(args, "trace")?,
+                    "project_slug": extract_string(args, "project_slug")?,
+                });
+                let result = http.post("/api/code/stack-trace", &body).await?;
+                Ok(Some(result))
+            }
+
             "search_project_code" => {
                 let project_slug = extract_string(args, "project_slug")?;
                 let query_str = extract_string(args, "query")?;
@@ -3646,6 +3942,9 @@ impl ToolHandler {
                 if let Some(v) = args.get("project_slug").and_then(|v| v.as_str()) {
                     query.push(("project_slug".to_string(), v.to_string()));
                 }
+                if let Some(v) = args.get("min_confidence").and_then(|v| v.as_f64()) {
+                    query.push(("min_confidence".to_string(), v.to_string()));
+                }
                 let result = http.get_with_query("/api/code/callgraph", &query).await?;
                 Ok(Some(result))
             }
@@ -3747,6 +4046,19 @@ impl ToolHandler {
                 Ok(Some(result))
             }
 
+            "find_duplicates" => {
+                let project_slug = extract_string(args, "project_slug")?;
+                let mut query = vec![("project_slug".to_string(), project_slug)];
+                if let Some(v) = args.get("min_similarity").and_then(|v| v.as_f64()) {
+                    query.push(("threshold".to_string(), v.to_string()));
+                }
+                if let Some(v) = args.get("limit").and_then(|v| v.as_i64()) {
+                    query.push(("limit".to_string(), v.to_string()));
+                }
+                let result = http.get_with_query("/api/code/duplicates", &query).await?;
+                Ok(Some(result))
+            }
+
             "get_node_importance" => {
                 let project_slug = extract_string(args, "project_slug")?;
                 let node_path = extract_string(args, "node_path")?;
@@ -4445,6 +4757,20 @@ impl ToolHandler {
                 Ok(Some(result))
             }
 
+            "get_search_analytics" => {
+                let mut query = Vec::new();
+                for key in ["endpoint", "project_slug", "since"] {
+                    if let Some(v) = args.get(key).and_then(|v| v.as_str()) {
+                        query.push((key.to_string(), v.to_string()));
+                    }
+                }
+                if let Some(v) = args.get("limit").and_then(|v| v.as_i64()) {
+                    query.push(("limit".to_string(), v.to_string()));
+                }
+                let result = http.get_with_query("/api/search/analytics", &query).await?;
+                Ok(Some(result))
+            }
+
             "delete_meilisearch_orphans" => {
                 let result = http.delete("/api/meilisearch/orphans").await?;
                 Ok(Some(result))
@@ -4558,6 +4884,124 @@ impl ToolHandler {
                 }))
             }
 
+            // ── Saved Views (6 tools) ───────────────────────────────────
+            "list_views" => {
+                let result = http.get("/api/views").await?;
+                Ok(Some(result))
+            }
+
+            "create_view" => {
+                let mut body = serde_json::Map::new();
+                for key in ["name", "description", "target", "filters"] {
+                    if let Some(v) = args.get(key) {
+                        body.insert(key.to_string(), v.clone());
+                    }
+                }
+                let result = http.post("/api/views", &Value::Object(body)).await?;
+                Ok(Some(result))
+            }
+
+            "get_view" => {
+                let id = extract_id(args, "id")?;
+                let result = http.get(&format!("/api/views/{}", id)).await?;
+                Ok(Some(result))
+            }
+
+            "update_view" => {
+                let id = extract_id(args, "id")?;
+                let mut body = serde_json::Map::new();
+                for key in ["name", "description", "target", "filters"] {
+                    if let Some(v) = args.get(key) {
+                        body.insert(key.to_string(), v.clone());
+                    }
+                }
+                let result = http
+                    .patch(&format!("/api/views/{}", id), &Value::Object(body))
+                    .await?;
+                Ok(Some(result))
+            }
+
+            "delete_view" => {
+                let id = extract_id(args, "id")?;
+                let result = http.delete(&format!("/api/views/{}", id)).await?;
+                Ok(Some(if result.is_null() {
+                    json!({"deleted": true})
+                } else {
+                    result
+                }))
+            }
+
+            "get_view_results" => {
+                let id = extract_id(args, "id")?;
+                let mut query = Vec::new();
+                for key in ["limit", "offset"] {
+                    if let Some(v) = args.get(key).and_then(|v| v.as_u64()) {
+                        query.push((key.to_string(), v.to_string()));
+                    }
+                }
+                let result = http
+                    .get_with_query(&format!("/api/views/{}/results", id), &query)
+                    .await?;
+                Ok(Some(result))
+            }
+
+            // ── Comments (5 tools) ──────────────────────────────────────
+            "list_comments" => {
+                let mut query = Vec::new();
+                for key in ["entity_type", "entity_id", "mentioned"] {
+                    if let Some(v) = args.get(key).and_then(|v| v.as_str()) {
+                        query.push((key.to_string(), v.to_string()));
+                    }
+                }
+                if let Some(limit) = args.get("limit").and_then(|v| v.as_u64()) {
+                    query.push(("limit".to_string(), limit.to_string()));
+                }
+                let result = http.get_with_query("/api/comments", &query).await?;
+                Ok(Some(result))
+            }
+
+            "create_comment" => {
+                let mut body = serde_json::Map::new();
+                for key in ["entity_type", "entity_id", "content", "parent_id"] {
+                    if let Some(v) = args.get(key) {
+                        body.insert(key.to_string(), v.clone());
+                    }
+                }
+                let result = http.post("/api/comments", &Value::Object(body)).await?;
+                Ok(Some(result))
+            }
+
+            "get_comment" => {
+                let id = extract_id(args, "id")?;
+                let result = http.get(&format!("/api/comments/{}", id)).await?;
+                Ok(Some(result))
+            }
+
+            "update_comment" => {
+                let id = extract_id(args, "id")?;
+                let content = args
+                    .get("content")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow!("content is required"))?;
+                let result = http
+                    .patch(
+                        &format!("/api/comments/{}", id),
+                        &json!({ "content": content }),
+                    )
+                    .await?;
+                Ok(Some(result))
+            }
+
+            "delete_comment" => {
+                let id = extract_id(args, "id")?;
+                let result = http.delete(&format!("/api/comments/{}", id)).await?;
+                Ok(Some(if result.is_null() {
+                    json!({"deleted": true})
+                } else {
+                    result
+                }))
+            }
+
             // ── Reasoning Tree ────────────────────────────────────────
             "reason" => {
                 let result = http.post("/api/reason", args).await?;
@@ -5994,6 +6438,7 @@ mod tests {
             ("get_graph", "get_project_graph"),
             ("get_intelligence_summary", "get_intelligence_summary"),
             ("get_embeddings_projection", "get_embeddings_projection"),
+            ("get_overview", "get_project_overview"),
         ] {
             let args = json!({"action": action});
             let (name, _) = handler.resolve_mega_tool("project", &args).unwrap();
@@ -6089,6 +6534,13 @@ mod tests {
         for (action, expected) in [
             ("search", "search_code"),
             ("search_project", "search_project_code"),
+            ("search_docs", "search_docs"),
+            ("find_config_usages", "find_config_usages"),
+            ("get_snippet", "get_code_snippet"),
+            ("propose_rename", "propose_rename"),
+            ("explain_symbol", "explain_symbol"),
+            ("resolve_stack_trace", "resolve_stack_trace"),
+            ("find_duplicates", "find_duplicates"),
             ("get_file_symbols", "get_file_symbols"),
             ("find_references", "find_references"),
             ("get_call_graph", "get_call_graph"),
@@ -6176,6 +6628,8 @@ mod tests {
             ("admin", "meilisearch_stats"),
             ("skill", "list"),
             ("analysis_profile", "list"),
+            ("view", "get_results"),
+            ("comment", "list"),
             ("protocol", "list"),
             ("persona", "list"),
             ("episode", "list"),
@@ -6606,68 +7060,242 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_mega_tool_note_search_semantic() {
+    async fn test_mega_tool_note_search_semantic() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle(
+                "note",
+                Some(json!({"action": "search_semantic", "query": "how to"})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "GET");
+        assert_eq!(result["path"], "/api/notes/search-semantic");
+    }
+
+    // -- Error paths -------------------------------------------------------
+
+    #[tokio::test]
+    async fn test_handle_unknown_tool_returns_error() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler.handle("totally_unknown_tool", None).await;
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Unknown tool"));
+    }
+
+    #[tokio::test]
+    async fn test_handle_missing_required_arg() {
+        let (handler, _) = make_http_handler().await;
+        // get_project requires "slug" — call without it
+        let result = handler.handle("get_project", Some(json!({}))).await;
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("slug"));
+    }
+
+    // ========================================================================
+    // Comprehensive HTTP routing tests — all remaining handlers
+    // ========================================================================
+
+    // Helper UUID for tests
+    const UUID1: &str = "550e8400-e29b-41d4-a716-446655440000";
+    const UUID2: &str = "660e8400-e29b-41d4-a716-446655440000";
+
+    // -- Projects (remaining) -----------------------------------------------
+
+    #[tokio::test]
+    async fn test_http_sync_project() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle("sync_project", Some(json!({"slug": "my-proj"})))
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "POST");
+        assert_eq!(result["path"], "/api/projects/my-proj/sync");
+    }
+
+    #[tokio::test]
+    async fn test_http_get_project_roadmap() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle("get_project_roadmap", Some(json!({"slug": "my-proj"})))
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "GET");
+        assert_eq!(result["path"], "/api/projects/my-proj/roadmap");
+    }
+
+    #[tokio::test]
+    async fn test_http_list_project_advisories() {
+        let (handler, _) = make_http_handler().await;
+        let pid = uuid::Uuid::new_v4();
+        let result = handler
+            .handle(
+                "list_project_advisories",
+                Some(json!({"project_id": pid.to_string(), "min_severity": "high"})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "GET");
+        assert_eq!(result["path"], format!("/api/projects/{}/advisories", pid));
+        assert_eq!(result["query"], "min_severity=high");
+    }
+
+    #[tokio::test]
+    async fn test_http_list_project_documents() {
+        let (handler, _) = make_http_handler().await;
+        let pid = uuid::Uuid::new_v4();
+        let result = handler
+            .handle(
+                "list_project_documents",
+                Some(json!({"project_id": pid.to_string()})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "GET");
+        assert_eq!(result["path"], format!("/api/projects/{}/documents", pid));
+    }
+
+    #[tokio::test]
+    async fn test_http_get_project_schema() {
+        let (handler, _) = make_http_handler().await;
+        let pid = uuid::Uuid::new_v4();
+        let result = handler
+            .handle(
+                "get_project_schema",
+                Some(json!({"project_id": pid.to_string()})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "GET");
+        assert_eq!(result["path"], format!("/api/projects/{}/schema", pid));
+    }
+
+    #[tokio::test]
+    async fn test_http_get_project_overview() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle("get_project_overview", Some(json!({"slug": "my-proj"})))
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "GET");
+        assert_eq!(result["path"], "/api/projects/my-proj/overview");
+    }
+
+    #[tokio::test]
+    async fn test_http_search_docs() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle(
+                "search_docs",
+                Some(json!({"query": "install", "project_slug": "demo", "limit": 3})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "GET");
+        assert_eq!(result["path"], "/api/code/docs/search");
+        let query = result["query"].as_str().unwrap();
+        assert!(query.contains("query=install"), "got: {}", query);
+        assert!(query.contains("project_slug=demo"), "got: {}", query);
+        assert!(query.contains("limit=3"), "got: {}", query);
+    }
+
+    #[tokio::test]
+    async fn test_http_find_config_usages() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle(
+                "find_config_usages",
+                Some(json!({"project_slug": "demo", "key": "MEILISEARCH_URL"})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "GET");
+        assert_eq!(result["path"], "/api/code/config-keys");
+        let query = result["query"].as_str().unwrap();
+        assert!(query.contains("project_slug=demo"), "got: {}", query);
+        assert!(query.contains("key=MEILISEARCH_URL"), "got: {}", query);
+    }
+
+    #[tokio::test]
+    async fn test_http_get_code_snippet() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle(
+                "get_code_snippet",
+                Some(json!({"symbol_id": "src/lib.rs:load:8", "context_lines": 5})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "GET");
+        assert_eq!(result["path"], "/api/code/snippet");
+        let query = result["query"].as_str().unwrap();
+        assert!(query.contains("symbol_id=src"), "got: {}", query);
+        assert!(query.contains("context_lines=5"), "got: {}", query);
+    }
+
+    #[tokio::test]
+    async fn test_http_propose_rename() {
         let (handler, _) = make_http_handler().await;
         let result = handler
             .handle(
-                "note",
-                Some(json!({"action": "search_semantic", "query": "how to"})),
+                "propose_rename",
+                Some(json!({"symbol_id": "src/lib.rs:Config", "new_name": "Settings"})),
             )
             .await
             .unwrap();
         assert_eq!(result["method"], "GET");
-        assert_eq!(result["path"], "/api/notes/search-semantic");
-    }
-
-    // -- Error paths -------------------------------------------------------
-
-    #[tokio::test]
-    async fn test_handle_unknown_tool_returns_error() {
-        let (handler, _) = make_http_handler().await;
-        let result = handler.handle("totally_unknown_tool", None).await;
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("Unknown tool"));
-    }
+        assert_eq!(result["path"], "/api/code/rename-proposal");
+        let query = result["query"].as_str().unwrap();
+        assert!(query.contains("new_name=Settings"), "got: {}", query);
 
-    #[tokio::test]
-    async fn test_handle_missing_required_arg() {
-        let (handler, _) = make_http_handler().await;
-        // get_project requires "slug" — call without it
-        let result = handler.handle("get_project", Some(json!({}))).await;
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("slug"));
+        let err = handler
+            .handle(
+                "propose_rename",
+                Some(json!({"symbol_id": "src/lib.rs:Config"})),
+            )
+            .await;
+        assert!(err.is_err());
     }
 
-    // ========================================================================
-    // Comprehensive HTTP routing tests — all remaining handlers
-    // ========================================================================
-
-    // Helper UUID for tests
-    const UUID1: &str = "550e8400-e29b-41d4-a716-446655440000";
-    const UUID2: &str = "660e8400-e29b-41d4-a716-446655440000";
-
-    // -- Projects (remaining) -----------------------------------------------
-
     #[tokio::test]
-    async fn test_http_sync_project() {
+    async fn test_http_explain_symbol() {
         let (handler, _) = make_http_handler().await;
         let result = handler
-            .handle("sync_project", Some(json!({"slug": "my-proj"})))
+            .handle(
+                "explain_symbol",
+                Some(json!({"symbol": "load", "project_slug": "my-proj"})),
+            )
             .await
             .unwrap();
-        assert_eq!(result["method"], "POST");
-        assert_eq!(result["path"], "/api/projects/my-proj/sync");
+        assert_eq!(result["method"], "GET");
+        assert_eq!(result["path"], "/api/code/explain");
+        let query = result["query"].as_str().unwrap();
+        assert!(query.contains("symbol=load"), "got: {}", query);
+        assert!(query.contains("project_slug=my-proj"), "got: {}", query);
+
+        let err = handler.handle("explain_symbol", Some(json!({}))).await;
+        assert!(err.is_err());
     }
 
     #[tokio::test]
-    async fn test_http_get_project_roadmap() {
+    async fn test_http_resolve_stack_trace() {
         let (handler, _) = make_http_handler().await;
         let result = handler
-            .handle("get_project_roadmap", Some(json!({"slug": "my-proj"})))
+            .handle(
+                "resolve_stack_trace",
+                Some(json!({"trace": "at load (src/app.js:3:7)", "project_slug": "my-proj"})),
+            )
             .await
             .unwrap();
-        assert_eq!(result["method"], "GET");
-        assert_eq!(result["path"], "/api/projects/my-proj/roadmap");
+        assert_eq!(result["method"], "POST");
+        assert_eq!(result["path"], "/api/code/stack-trace");
+        assert_eq!(result["body"]["trace"], "at load (src/app.js:3:7)");
+        assert_eq!(result["body"]["project_slug"], "my-proj");
+
+        let err = handler
+            .handle("resolve_stack_trace", Some(json!({"trace": "x"})))
+            .await;
+        assert!(err.is_err());
     }
 
     #[tokio::test]
@@ -7115,6 +7743,17 @@ mod tests {
         assert!(result["path"].as_str().unwrap().contains("/timeline"));
     }
 
+    #[tokio::test]
+    async fn test_http_export_adrs() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle("export_adrs", Some(json!({"project_id": UUID1})))
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "POST");
+        assert!(result["path"].as_str().unwrap().ends_with("/adrs/export"));
+    }
+
     // -- Constraints --------------------------------------------------------
 
     #[tokio::test]
@@ -7514,6 +8153,23 @@ mod tests {
         assert_eq!(result["path"], "/api/files/history");
     }
 
+    #[tokio::test]
+    async fn test_http_suggest_commit_message() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle(
+                "suggest_commit_message",
+                Some(json!({"project_id": UUID1, "staged": true})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "POST");
+        assert!(result["path"]
+            .as_str()
+            .unwrap()
+            .ends_with("/commit-message"));
+    }
+
     // -- Notes (remaining) --------------------------------------------------
 
     #[tokio::test]
@@ -8012,6 +8668,22 @@ mod tests {
         assert_eq!(result["method"], "GET");
     }
 
+    #[tokio::test]
+    async fn test_http_get_search_analytics() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle(
+                "get_search_analytics",
+                Some(json!({"endpoint": "notes", "limit": 5})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "GET");
+        assert_eq!(result["path"], "/api/search/analytics");
+        assert!(result["query"].as_str().unwrap().contains("endpoint=notes"));
+        assert!(result["query"].as_str().unwrap().contains("limit=5"));
+    }
+
     #[tokio::test]
     async fn test_http_delete_meilisearch_orphans() {
         let (handler, _) = make_http_handler().await;
@@ -8230,6 +8902,60 @@ mod tests {
         assert!(result["path"].as_str().unwrap().ends_with("/topology"));
     }
 
+    #[tokio::test]
+    async fn test_http_get_workspace_topology_drift() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle(
+                "get_workspace_topology_drift",
+                Some(json!({"slug": "my-ws"})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "GET");
+        assert!(result["path"]
+            .as_str()
+            .unwrap()
+            .ends_with("/my-ws/topology/drift"));
+    }
+
+    #[tokio::test]
+    async fn test_http_set_workspace_license_policy() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle(
+                "set_workspace_license_policy",
+                Some(json!({"slug": "my-ws", "denied": ["GPL-*"], "flag_unknown": true})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "PUT");
+        assert!(result["path"]
+            .as_str()
+            .unwrap()
+            .ends_with("/my-ws/license-policy"));
+        assert_eq!(result["body"]["denied"], json!(["GPL-*"]));
+        assert_eq!(result["body"]["flag_unknown"], true);
+        assert!(result["body"].get("allowed").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_http_get_workspace_license_violations() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle(
+                "get_workspace_license_violations",
+                Some(json!({"slug": "my-ws"})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "GET");
+        assert!(result["path"]
+            .as_str()
+            .unwrap()
+            .ends_with("/my-ws/license-violations"));
+    }
+
     // -- Workspace milestones -----------------------------------------------
 
     #[tokio::test]
@@ -8381,6 +9107,20 @@ mod tests {
         assert!(result["path"].as_str().unwrap().ends_with("/progress"));
     }
 
+    #[tokio::test]
+    async fn test_http_get_workspace_milestone_rollup() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle(
+                "get_workspace_milestone_rollup",
+                Some(json!({"milestone_id": UUID1, "window_days": 14})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "GET");
+        assert!(result["path"].as_str().unwrap().ends_with("/rollup"));
+    }
+
     // -- Resources ----------------------------------------------------------
 
     #[tokio::test]
@@ -8471,6 +9211,46 @@ mod tests {
         assert!(result["path"].as_str().unwrap().ends_with("/projects"));
     }
 
+    #[tokio::test]
+    async fn test_http_get_resource_usages() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle("get_resource_usages", Some(json!({"id": UUID1})))
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "GET");
+        assert!(result["path"].as_str().unwrap().ends_with("/usages"));
+    }
+
+    #[tokio::test]
+    async fn test_http_parse_resource() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle("parse_resource", Some(json!({"id": UUID1})))
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "POST");
+        assert!(result["path"].as_str().unwrap().ends_with("/parse"));
+    }
+
+    #[tokio::test]
+    async fn test_http_diff_resources() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle(
+                "diff_resources",
+                Some(json!({"id": UUID1, "against": UUID2})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "GET");
+        assert!(result["path"].as_str().unwrap().contains("/diff"));
+        assert!(handler
+            .handle("diff_resources", Some(json!({"id": UUID1})))
+            .await
+            .is_err());
+    }
+
     // -- Components ---------------------------------------------------------
 
     #[tokio::test]
@@ -8631,6 +9411,40 @@ mod tests {
             .contains("/api/code/search"));
     }
 
+    #[tokio::test]
+    async fn test_http_search_workspace() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle(
+                "search_workspace",
+                Some(json!({"slug": "my-ws", "query": "auth", "kinds": "code,notes"})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "GET");
+        assert!(result["path"]
+            .as_str()
+            .unwrap()
+            .contains("/api/workspaces/my-ws/search"));
+    }
+
+    #[tokio::test]
+    async fn test_http_get_workspace_context() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle(
+                "get_workspace_context",
+                Some(json!({"slug": "my-ws", "limit": 5})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "GET");
+        assert!(result["path"]
+            .as_str()
+            .unwrap()
+            .contains("/api/workspaces/my-ws/context"));
+    }
+
     #[tokio::test]
     async fn test_http_get_file_symbols() {
         let (handler, _) = make_http_handler().await;
@@ -8790,6 +9604,27 @@ mod tests {
         assert!(result["path"].as_str().unwrap().contains("/code/health"));
     }
 
+    #[tokio::test]
+    async fn test_http_find_duplicates() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle(
+                "find_duplicates",
+                Some(json!({"project_slug": "my-proj", "min_similarity": 0.9})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "GET");
+        assert_eq!(result["path"], "/api/code/duplicates");
+        let query = result["query"].as_str().unwrap();
+        assert!(query.contains("threshold=0.9"), "got: {}", query);
+
+        assert!(handler
+            .handle("find_duplicates", Some(json!({})))
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn test_http_get_node_importance() {
         let (handler, _) = make_http_handler().await;
@@ -9881,6 +10716,7 @@ mod tests {
             ("get_affecting", "get_decisions_affecting"),
             ("supersede", "supersede_decision"),
             ("get_timeline", "get_decision_timeline"),
+            ("export_adrs", "export_adrs"),
         ] {
             let args = json!({"action": action});
             let (name, _) = handler.resolve_mega_tool("decision", &args).unwrap();
@@ -9958,6 +10794,7 @@ mod tests {
             ("get_plan_commits", "get_plan_commits"),
             ("get_commit_files", "get_commit_files"),
             ("get_file_history", "get_file_history"),
+            ("suggest_message", "suggest_commit_message"),
         ] {
             let args = json!({"action": action});
             let (name, _) = handler.resolve_mega_tool("commit", &args).unwrap();
@@ -9979,6 +10816,12 @@ mod tests {
             ("add_project", "add_project_to_workspace"),
             ("remove_project", "remove_project_from_workspace"),
             ("get_topology", "get_workspace_topology"),
+            ("get_topology_drift", "get_workspace_topology_drift"),
+            ("get_license_policy", "get_workspace_license_policy"),
+            ("set_license_policy", "set_workspace_license_policy"),
+            ("get_license_violations", "get_workspace_license_violations"),
+            ("search", "search_workspace"),
+            ("get_context", "get_workspace_context"),
         ] {
             let args = json!({"action": action});
             let (name, _) = handler.resolve_mega_tool("workspace", &args).unwrap();
@@ -9986,6 +10829,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resolve_mega_tool_resource_actions() {
+        let handler = make_handler();
+        for (action, expected) in [
+            ("list", "list_resources"),
+            ("get", "get_resource"),
+            ("link_to_project", "link_resource_to_project"),
+            ("get_usages", "get_resource_usages"),
+            ("parse", "parse_resource"),
+            ("list_elements", "list_resource_elements"),
+            ("diff", "diff_resources"),
+        ] {
+            let args = json!({"action": action});
+            let (name, _) = handler.resolve_mega_tool("resource", &args).unwrap();
+            assert_eq!(name, expected);
+        }
+    }
+
     #[test]
     fn test_resolve_mega_tool_skill_actions() {
         let handler = make_handler();
@@ -10040,6 +10901,7 @@ mod tests {
             ("stop_watch", "stop_watch"),
             ("watch_status", "watch_status"),
             ("meilisearch_stats", "get_meilisearch_stats"),
+            ("search_analytics", "get_search_analytics"),
             ("delete_meilisearch_orphans", "delete_meilisearch_orphans"),
             ("update_staleness_scores", "update_staleness_scores"),
             ("update_energy_scores", "update_energy_scores"),
@@ -11274,6 +12136,77 @@ mod tests {
         assert_eq!(result["path"], format!("/api/analysis-profiles/{}", pid));
     }
 
+    // -- Saved Views -----------------------------------------------------------
+
+    #[tokio::test]
+    async fn test_http_create_view() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle(
+                "create_view",
+                Some(json!({
+                    "action": "create",
+                    "name": "stale critical notes",
+                    "target": "notes",
+                    "filters": {"importance": ["critical"], "min_staleness": 0.7}
+                })),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "POST");
+        assert_eq!(result["path"], "/api/views");
+        assert_eq!(result["body"]["target"], "notes");
+        assert!(result["body"].get("action").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_http_get_view_results() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle("get_view_results", Some(json!({"id": UUID1, "limit": 10})))
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "GET");
+        assert_eq!(result["path"], format!("/api/views/{}/results", UUID1));
+    }
+
+    // -- Comments --------------------------------------------------------------
+
+    #[tokio::test]
+    async fn test_http_create_comment() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle(
+                "create_comment",
+                Some(json!({
+                    "action": "create",
+                    "entity_type": "task",
+                    "entity_id": UUID1,
+                    "content": "@alice the migration is blocked"
+                })),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "POST");
+        assert_eq!(result["path"], "/api/comments");
+        assert_eq!(result["body"]["entity_type"], "task");
+        assert!(result["body"].get("action").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_http_list_comments() {
+        let (handler, _) = make_http_handler().await;
+        let result = handler
+            .handle(
+                "list_comments",
+                Some(json!({"entity_type": "plan", "entity_id": UUID1})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["method"], "GET");
+        assert_eq!(result["path"], "/api/comments");
+    }
+
     // -- Neural Routing --------------------------------------------------------
 
     #[tokio::test]
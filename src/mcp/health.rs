@@ -0,0 +1,180 @@
+//! Liveness probe of the MCP server.
+//!
+//! The CLI owns the server's stdio, so the chat supervisor
+//! (`chat::mcp_supervisor`) can't ping the server there. With
+//! [`HEALTH_SOCKET_ENV`] set, the server also listens on that Unix socket and
+//! hands every line it receives (an MCP `ping`) to its main loop, which
+//! answers it between two stdio messages. Other methods are refused, so the
+//! socket can't be used to call tools. A server
+//! whose loop is stuck never answers, which tells a hung server from one
+//! whose process merely still exists.
+
+use super::protocol::JsonRpcResponse;
+use std::path::Path;
+use std::time::Duration;
+#[cfg(unix)]
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+
+/// Path of the health socket, set by the chat manager for each session.
+pub const HEALTH_SOCKET_ENV: &str = "PO_MCP_HEALTH_SOCKET";
+
+/// A message received on the health socket, to be answered by the server
+/// loop.
+pub struct HealthProbe {
+    pub message: String,
+    pub reply: oneshot::Sender<Option<JsonRpcResponse>>,
+}
+
+/// Listen on the health socket at `path`, replacing a stale one.
+#[cfg(unix)]
+pub fn listen(path: &Path) -> std::io::Result<mpsc::Receiver<HealthProbe>> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let _ = std::fs::remove_file(path);
+    let listener = tokio::net::UnixListener::bind(path)?;
+    // Only the user running the server may probe it
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    let (probes, received) = mpsc::channel(8);
+    tokio::spawn(async move {
+        loop {
+            let stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    tracing::warn!("Health socket stopped accepting: {}", e);
+                    return;
+                }
+            };
+            let probes = probes.clone();
+            tokio::spawn(async move {
+                if let Err(e) = answer(stream, probes).await {
+                    tracing::debug!("Health probe connection failed: {}", e);
+                }
+            });
+        }
+    });
+    Ok(received)
+}
+
+/// Pass the lines of one connection to the server loop and write back its
+/// answers.
+#[cfg(unix)]
+async fn answer(
+    stream: tokio::net::UnixStream,
+    probes: mpsc::Sender<HealthProbe>,
+) -> std::io::Result<()> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let (read, mut write) = stream.into_split();
+    let mut lines = BufReader::new(read).lines();
+    while let Some(message) = lines.next_line().await? {
+        let (reply, response) = oneshot::channel();
+        if probes.send(HealthProbe { message, reply }).await.is_err() {
+            break;
+        }
+        let Ok(Some(response)) = response.await else {
+            break;
+        };
+        let mut json = serde_json::to_string(&response)?;
+        json.push('\n');
+        write.write_all(json.as_bytes()).await?;
+    }
+    Ok(())
+}
+
+/// Outcome of [`ping`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PingOutcome {
+    /// The server loop answered.
+    Alive,
+    /// Connected, but no answer within the timeout: the server is hung.
+    Unresponsive,
+    /// Nothing listens on the socket: the server is gone, or it is a build
+    /// without the health socket.
+    NoSocket,
+}
+
+/// Send an MCP `ping` to the server listening at `path`.
+#[cfg(unix)]
+pub async fn ping(path: &Path, timeout: Duration) -> PingOutcome {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let Ok(stream) = tokio::net::UnixStream::connect(path).await else {
+        return PingOutcome::NoSocket;
+    };
+    let exchange = async {
+        let (read, mut write) = stream.into_split();
+        let mut request =
+            serde_json::json!({"jsonrpc": "2.0", "id": "health", "method": "ping"}).to_string();
+        request.push('\n');
+        write.write_all(request.as_bytes()).await?;
+        BufReader::new(read).lines().next_line().await
+    };
+    match tokio::time::timeout(timeout, exchange).await {
+        Ok(Ok(Some(_))) => PingOutcome::Alive,
+        _ => PingOutcome::Unresponsive,
+    }
+}
+
+/// Non-Unix stub: there is no health socket.
+#[cfg(not(unix))]
+pub async fn ping(_path: &Path, _timeout: Duration) -> PingOutcome {
+    PingOutcome::NoSocket
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_ping_answered_by_server_loop() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mcp.sock");
+        let mut probes = listen(&path).unwrap();
+        tokio::spawn(async move {
+            while let Some(probe) = probes.recv().await {
+                let request: serde_json::Value = serde_json::from_str(&probe.message).unwrap();
+                assert_eq!(request["method"], "ping");
+                let _ = probe.reply.send(Some(JsonRpcResponse::success(
+                    request["id"].clone(),
+                    serde_json::json!({}),
+                )));
+            }
+        });
+
+        assert_eq!(
+            ping(&path, Duration::from_secs(5)).await,
+            PingOutcome::Alive
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ping_times_out_on_hung_server() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mcp.sock");
+        // The loop never picks the probes up, as when stuck in a tool call
+        let _probes = listen(&path).unwrap();
+
+        assert_eq!(
+            ping(&path, Duration::from_millis(200)).await,
+            PingOutcome::Unresponsive
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ping_without_server() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mcp.sock");
+        assert_eq!(
+            ping(&path, Duration::from_secs(1)).await,
+            PingOutcome::NoSocket
+        );
+
+        // A socket left by a server that is gone
+        drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+        assert_eq!(
+            ping(&path, Duration::from_secs(1)).await,
+            PingOutcome::NoSocket
+        );
+    }
+}
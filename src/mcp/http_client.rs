@@ -204,7 +204,10 @@ impl McpHttpClient {
     // ── Helpers ──────────────────────────────────────────────────────────
 
     /// Inject Authorization: Bearer header and X-Session-Id header if available.
+    /// Every request also carries `X-PO-Client: mcp` so that the server can
+    /// tell agent searches from REST ones in its search analytics.
     fn inject_auth(&self, req: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        let req = req.header("X-PO-Client", "mcp");
         let req = match &self.auth_token {
             Some(token) => req.bearer_auth(token),
             None => req,
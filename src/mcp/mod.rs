@@ -5,7 +5,9 @@
 
 pub mod formatter;
 pub mod handlers;
+pub mod health;
 pub mod http_client;
+pub mod pagination;
 pub mod pipeline_handler;
 pub mod protocol;
 pub mod server;
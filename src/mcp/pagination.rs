@@ -0,0 +1,416 @@
+//! Pagination and result size limits for MCP tool responses.
+//!
+//! Some tools (file lists, call graphs, search results) can return payloads
+//! that exceed what MCP clients accept in a single message. This module
+//! applies a uniform convention on top of every tool result:
+//!
+//! - **Cursor**: every tool accepts an optional `cursor` argument (opaque
+//!   string returned as `next_cursor` by a previous call) and an optional
+//!   `page_size`. Both are consumed here and never forwarded to the REST API.
+//! - **Pageable array**: the paginated collection is the top-level array, or
+//!   — for object results — the largest array field (e.g. `items`, `nodes`).
+//! - **Truncated flag**: when items were left out, the result carries
+//!   `truncated: true`, `next_cursor` and `total_items`. When a cursor was
+//!   supplied, `truncated` is always present so clients know when to stop.
+//! - **Byte budget**: the page is shrunk until its compact rendering fits in
+//!   `max_result_bytes`. If a single item is still too large, long strings
+//!   inside it are cut with a `[truncated N chars]` marker.
+
+use super::formatter::json_to_compact;
+use serde_json::{json, Value};
+
+/// Default maximum size of a rendered tool result (~25k tokens).
+pub const DEFAULT_MAX_RESULT_BYTES: usize = 100_000;
+
+/// Smallest string length kept when shrinking oversized items.
+const MIN_STRING_CHARS: usize = 64;
+
+/// Pagination settings shared by all tools.
+#[derive(Debug, Clone)]
+pub struct PaginationConfig {
+    /// Maximum size in bytes of the compact-rendered result.
+    pub max_result_bytes: usize,
+    /// Page size applied when the caller passes a cursor without `page_size`.
+    /// `None` means pages are bounded by `max_result_bytes` only.
+    pub default_page_size: Option<usize>,
+}
+
+impl Default for PaginationConfig {
+    fn default() -> Self {
+        Self {
+            max_result_bytes: DEFAULT_MAX_RESULT_BYTES,
+            default_page_size: None,
+        }
+    }
+}
+
+impl PaginationConfig {
+    /// Load from `PO_MCP_MAX_RESULT_BYTES` and `PO_MCP_PAGE_SIZE`, falling back
+    /// to defaults for missing or invalid values.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            max_result_bytes: std::env::var("PO_MCP_MAX_RESULT_BYTES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v: &usize| *v > 0)
+                .unwrap_or(defaults.max_result_bytes),
+            default_page_size: std::env::var("PO_MCP_PAGE_SIZE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v: &usize| *v > 0)
+                .or(defaults.default_page_size),
+        }
+    }
+}
+
+/// Pagination arguments extracted from a tool call.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PageRequest {
+    /// Offset into the pageable array (decoded from `cursor`).
+    pub offset: usize,
+    /// Maximum items to return (explicit `page_size`).
+    pub page_size: Option<usize>,
+    /// Whether the caller supplied a cursor.
+    pub has_cursor: bool,
+}
+
+impl PageRequest {
+    /// Remove `cursor` and `page_size` from the tool arguments and decode them.
+    ///
+    /// An unparsable cursor is treated as the first page.
+    pub fn take_from_args(args: &mut Option<Value>) -> Self {
+        let Some(obj) = args.as_mut().and_then(|a| a.as_object_mut()) else {
+            return Self::default();
+        };
+        let cursor = obj.remove("cursor");
+        let page_size = obj.remove("page_size");
+
+        let offset = cursor.as_ref().and_then(decode_cursor);
+        Self {
+            offset: offset.unwrap_or(0),
+            page_size: page_size
+                .as_ref()
+                .and_then(|v| {
+                    v.as_u64()
+                        .or_else(|| v.as_str().and_then(|s| s.trim().parse().ok()))
+                })
+                .map(|n| n as usize)
+                .filter(|n| *n > 0),
+            has_cursor: cursor.is_some_and(|c| !c.is_null()),
+        }
+    }
+}
+
+/// Encode an offset as an opaque cursor string.
+pub fn encode_cursor(offset: usize) -> String {
+    format!("o{}", offset)
+}
+
+fn decode_cursor(value: &Value) -> Option<usize> {
+    let s = value.as_str()?;
+    s.strip_prefix('o').unwrap_or(s).parse().ok()
+}
+
+/// Apply pagination and the byte budget to a tool result.
+pub fn paginate(value: Value, page: &PageRequest, config: &PaginationConfig) -> Value {
+    let page_size = page.page_size.or(if page.has_cursor {
+        config.default_page_size
+    } else {
+        None
+    });
+
+    match value {
+        Value::Array(items) => {
+            let (items, meta) = paginate_items(items, page, page_size, config, |slice| {
+                Value::Array(slice.to_vec())
+            });
+            if meta.truncated || page.has_cursor {
+                let mut out = json!({ "items": items });
+                meta.annotate(&mut out);
+                out
+            } else {
+                Value::Array(items)
+            }
+        }
+        Value::Object(mut map) => {
+            let Some(key) = largest_array_key(&map) else {
+                return shrink_to_fit(Value::Object(map), config.max_result_bytes);
+            };
+            let items = match map.remove(&key) {
+                Some(Value::Array(items)) => items,
+                _ => unreachable!("largest_array_key returned a non-array key"),
+            };
+            let (items, meta) = paginate_items(items, page, page_size, config, |slice| {
+                let mut probe = map.clone();
+                probe.insert(key.clone(), Value::Array(slice.to_vec()));
+                Value::Object(probe)
+            });
+            map.insert(key, Value::Array(items));
+            let mut out = Value::Object(map);
+            if meta.truncated || page.has_cursor {
+                meta.annotate(&mut out);
+            }
+            out
+        }
+        other => shrink_to_fit(other, config.max_result_bytes),
+    }
+}
+
+/// Outcome of slicing the pageable array.
+struct PageMeta {
+    truncated: bool,
+    next_offset: usize,
+    total: usize,
+}
+
+impl PageMeta {
+    fn annotate(&self, out: &mut Value) {
+        let Some(obj) = out.as_object_mut() else {
+            return;
+        };
+        obj.insert("truncated".to_string(), Value::Bool(self.truncated));
+        obj.insert("total_items".to_string(), json!(self.total));
+        if self.truncated {
+            obj.insert(
+                "next_cursor".to_string(),
+                Value::String(encode_cursor(self.next_offset)),
+            );
+        }
+    }
+}
+
+/// Slice `items` to the requested page, then shrink the page until the
+/// rendered result (built by `render`) fits in the byte budget.
+fn paginate_items<F>(
+    items: Vec<Value>,
+    page: &PageRequest,
+    page_size: Option<usize>,
+    config: &PaginationConfig,
+    render: F,
+) -> (Vec<Value>, PageMeta)
+where
+    F: Fn(&[Value]) -> Value,
+{
+    let total = items.len();
+    let offset = page.offset.min(total);
+    let mut window: Vec<Value> = items.into_iter().skip(offset).collect();
+    if let Some(size) = page_size {
+        window.truncate(size);
+    }
+
+    let fits = |slice: &[Value]| rendered_len(&render(slice)) <= config.max_result_bytes;
+
+    let mut count = window.len();
+    if count > 0 && !fits(&window) {
+        // Largest n in [1, count) that fits — at least one item is always kept
+        let (mut lo, mut hi) = (1usize, count - 1);
+        let mut best = 1usize;
+        while lo <= hi {
+            let mid = lo + (hi - lo) / 2;
+            if fits(&window[..mid]) {
+                best = mid;
+                lo = mid + 1;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        count = best;
+        window.truncate(count);
+
+        if !fits(&window) {
+            // A single oversized item: cut its long strings
+            let budget = config
+                .max_result_bytes
+                .saturating_sub(rendered_len(&render(&[])));
+            let item = window.pop().unwrap_or(Value::Null);
+            window.push(shrink_to_fit(item, budget));
+        }
+    }
+
+    let next_offset = offset + count;
+    let meta = PageMeta {
+        truncated: next_offset < total,
+        next_offset,
+        total,
+    };
+    (window, meta)
+}
+
+/// Key of the largest non-empty array field of an object (by rendered size).
+fn largest_array_key(map: &serde_json::Map<String, Value>) -> Option<String> {
+    map.iter()
+        .filter_map(|(k, v)| match v {
+            Value::Array(arr) if !arr.is_empty() => Some((k, rendered_len(v))),
+            _ => None,
+        })
+        .max_by_key(|(_, len)| *len)
+        .map(|(k, _)| k.clone())
+}
+
+/// Cut long strings (progressively shorter) until the value fits in `budget`.
+fn shrink_to_fit(value: Value, budget: usize) -> Value {
+    if rendered_len(&value) <= budget {
+        return value;
+    }
+    let mut limit = 4096usize;
+    let mut current = value;
+    loop {
+        let candidate = truncate_strings(current.clone(), limit);
+        if rendered_len(&candidate) <= budget || limit <= MIN_STRING_CHARS {
+            return candidate;
+        }
+        current = candidate;
+        limit /= 2;
+    }
+}
+
+/// Recursively truncate every string longer than `limit` characters.
+fn truncate_strings(value: Value, limit: usize) -> Value {
+    match value {
+        Value::String(s) => {
+            let char_count = s.chars().count();
+            if char_count <= limit {
+                Value::String(s)
+            } else {
+                let kept: String = s.chars().take(limit).collect();
+                Value::String(format!(
+                    "{}… [truncated {} chars]",
+                    kept,
+                    char_count - limit
+                ))
+            }
+        }
+        Value::Array(arr) => Value::Array(
+            arr.into_iter()
+                .map(|v| truncate_strings(v, limit))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(k, v)| (k, truncate_strings(v, limit)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+fn rendered_len(value: &Value) -> usize {
+    json_to_compact(value).len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(max_result_bytes: usize) -> PaginationConfig {
+        PaginationConfig {
+            max_result_bytes,
+            default_page_size: None,
+        }
+    }
+
+    fn files(n: usize) -> Vec<Value> {
+        (0..n)
+            .map(|i| json!({"path": format!("src/file_{:04}.rs", i), "symbols": i}))
+            .collect()
+    }
+
+    #[test]
+    fn test_take_from_args_strips_pagination_keys() {
+        let mut args = Some(json!({"action": "list", "cursor": "o40", "page_size": 20}));
+        let page = PageRequest::take_from_args(&mut args);
+        assert_eq!(page.offset, 40);
+        assert_eq!(page.page_size, Some(20));
+        assert!(page.has_cursor);
+        let obj = args.unwrap();
+        assert!(obj.get("cursor").is_none());
+        assert!(obj.get("page_size").is_none());
+        assert_eq!(obj["action"], "list");
+    }
+
+    #[test]
+    fn test_take_from_args_invalid_cursor_is_first_page() {
+        let mut args = Some(json!({"cursor": "garbage"}));
+        let page = PageRequest::take_from_args(&mut args);
+        assert_eq!(page.offset, 0);
+        assert!(page.has_cursor);
+
+        let mut none = None;
+        assert_eq!(
+            PageRequest::take_from_args(&mut none),
+            PageRequest::default()
+        );
+    }
+
+    #[test]
+    fn test_small_result_passes_through_unchanged() {
+        let value = json!({"items": files(3), "count": 3});
+        let out = paginate(value.clone(), &PageRequest::default(), &config(10_000));
+        assert_eq!(out, value);
+    }
+
+    #[test]
+    fn test_top_level_array_is_wrapped_when_truncated() {
+        let page = PageRequest {
+            page_size: Some(10),
+            ..Default::default()
+        };
+        let out = paginate(Value::Array(files(25)), &page, &config(100_000));
+        assert_eq!(out["items"].as_array().unwrap().len(), 10);
+        assert_eq!(out["truncated"], true);
+        assert_eq!(out["next_cursor"], "o10");
+        assert_eq!(out["total_items"], 25);
+    }
+
+    #[test]
+    fn test_cursor_walks_to_last_page() {
+        let page = PageRequest {
+            offset: 20,
+            page_size: Some(10),
+            has_cursor: true,
+        };
+        let out = paginate(json!({"files": files(25)}), &page, &config(100_000));
+        let items = out["files"].as_array().unwrap();
+        assert_eq!(items.len(), 5);
+        assert_eq!(items[0]["path"], "src/file_0020.rs");
+        assert_eq!(out["truncated"], false);
+        assert!(out.get("next_cursor").is_none());
+    }
+
+    #[test]
+    fn test_byte_budget_shrinks_largest_array() {
+        let value = json!({"project": "demo", "nodes": files(500), "edges": files(2)});
+        let out = paginate(value, &PageRequest::default(), &config(2_000));
+        assert!(json_to_compact(&out).len() <= 2_200);
+        assert_eq!(out["truncated"], true);
+        assert_eq!(out["edges"].as_array().unwrap().len(), 2);
+        let kept = out["nodes"].as_array().unwrap().len();
+        assert!(kept > 0 && kept < 500);
+        assert_eq!(out["next_cursor"], encode_cursor(kept));
+    }
+
+    #[test]
+    fn test_single_oversized_item_has_strings_cut() {
+        let big = "x".repeat(50_000);
+        let value = json!([{"path": "a.rs", "content": big}]);
+        let out = paginate(value, &PageRequest::default(), &config(5_000));
+        let content = out[0]["content"].as_str().unwrap();
+        assert!(content.contains("[truncated"));
+        assert!(json_to_compact(&out).len() <= 5_000);
+    }
+
+    #[test]
+    fn test_scalar_object_without_arrays_is_shrunk() {
+        let value = json!({"content": "y".repeat(20_000)});
+        let out = paginate(value, &PageRequest::default(), &config(1_000));
+        assert!(out["content"].as_str().unwrap().ends_with("chars]"));
+        assert!(out.get("truncated").is_none());
+    }
+
+    #[test]
+    fn test_truncate_strings_is_char_boundary_safe() {
+        let value = truncate_strings(json!("ééééé"), 2);
+        assert_eq!(value, json!("éé… [truncated 3 chars]"));
+    }
+}
@@ -4,12 +4,16 @@
 
 use super::formatter::json_to_compact;
 use super::handlers::ToolHandler;
+use super::health::HealthProbe;
 use super::http_client::McpHttpClient;
+use super::pagination::{paginate, PageRequest, PaginationConfig};
 use super::protocol::*;
 use super::tools::all_tools;
 use anyhow::Result;
 use serde_json::{json, Value};
-use std::io::{BufRead, BufReader, Write};
+use std::io::Write;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
 const PROTOCOL_VERSION: &str = "2024-11-05";
@@ -19,7 +23,9 @@ const SERVER_VERSION: &str = env!("CARGO_PKG_VERSION");
 /// MCP Server that handles JSON-RPC 2.0 requests over stdio
 pub struct McpServer {
     tool_handler: ToolHandler,
+    pagination: PaginationConfig,
     initialized: bool,
+    health_probes: Option<mpsc::Receiver<HealthProbe>>,
 }
 
 impl McpServer {
@@ -28,25 +34,47 @@ impl McpServer {
         let tool_handler = ToolHandler::new(http_client);
         Self {
             tool_handler,
+            pagination: PaginationConfig::from_env(),
             initialized: false,
+            health_probes: None,
         }
     }
 
+    /// Override the pagination / result size limits (default: from env).
+    pub fn with_pagination(mut self, pagination: PaginationConfig) -> Self {
+        self.pagination = pagination;
+        self
+    }
+
+    /// Answer the health probes received on `probes` (see
+    /// [`super::health`]) between two stdio messages.
+    pub fn with_health_probes(mut self, probes: mpsc::Receiver<HealthProbe>) -> Self {
+        self.health_probes = Some(probes);
+        self
+    }
+
     /// Run the server, reading from stdin and writing to stdout
     pub async fn run(&mut self) -> Result<()> {
-        let stdin = std::io::stdin();
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
         let stdout = std::io::stdout();
-        let reader = BufReader::new(stdin.lock());
         let mut writer = stdout.lock();
+        let mut probes = self.health_probes.take();
 
         info!("MCP server starting on stdio");
 
-        for line in reader.lines() {
-            let line = match line {
-                Ok(l) => l,
-                Err(e) => {
-                    error!("Failed to read line: {}", e);
-                    break;
+        loop {
+            let line = tokio::select! {
+                line = lines.next_line() => match line {
+                    Ok(Some(l)) => l,
+                    Ok(None) => break,
+                    Err(e) => {
+                        error!("Failed to read line: {}", e);
+                        break;
+                    }
+                },
+                Some(probe) = next_probe(&mut probes) => {
+                    let _ = probe.reply.send(Some(handle_probe(&probe.message)));
+                    continue;
                 }
             };
 
@@ -193,7 +221,7 @@ impl McpServer {
             return Err(JsonRpcError::invalid_request("Server not initialized"));
         }
 
-        let params: ToolCallParams = params
+        let mut params: ToolCallParams = params
             .as_ref()
             .ok_or_else(|| JsonRpcError::invalid_params("params required"))?
             .clone()
@@ -203,13 +231,18 @@ impl McpServer {
         info!("Tool call: {}", params.name);
         debug!("Arguments: {:?}", params.arguments);
 
+        // Pagination args are handled here for every tool — strip them before proxying
+        let page = PageRequest::take_from_args(&mut params.arguments);
+
         let result = self
             .tool_handler
             .handle(&params.name, params.arguments)
             .await;
 
         let tool_result = match result {
-            Ok(value) => ToolCallResult::success(json_to_compact(&value)),
+            Ok(value) => {
+                ToolCallResult::success(json_to_compact(&paginate(value, &page, &self.pagination)))
+            }
             Err(e) => {
                 error!("Tool error: {}", e);
                 ToolCallResult::error(e.to_string())
@@ -220,6 +253,28 @@ impl McpServer {
     }
 }
 
+/// Answer a health probe. Only `ping` is, so the socket can't call tools.
+fn handle_probe(message: &str) -> JsonRpcResponse {
+    match serde_json::from_str::<JsonRpcRequest>(message) {
+        Ok(request) if request.method == "ping" => {
+            JsonRpcResponse::success(request.id.unwrap_or(Value::Null), json!({}))
+        }
+        Ok(request) => JsonRpcResponse::error(
+            request.id.unwrap_or(Value::Null),
+            JsonRpcError::method_not_found(&request.method),
+        ),
+        Err(e) => JsonRpcResponse::error(Value::Null, JsonRpcError::parse_error(e.to_string())),
+    }
+}
+
+/// Next health probe, never without a health socket
+async fn next_probe(probes: &mut Option<mpsc::Receiver<HealthProbe>>) -> Option<HealthProbe> {
+    match probes {
+        Some(probes) => probes.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
 /// Extension trait for pipe operator
 trait Pipe: Sized {
     fn pipe<F, R>(self, f: F) -> R
@@ -269,6 +324,19 @@ mod tests {
         assert!(json.contains("\"status\""));
     }
 
+    #[test]
+    fn test_probes_only_answer_ping() {
+        let pong = handle_probe(r#"{"jsonrpc":"2.0","method":"ping","id":"health"}"#);
+        assert_eq!(pong.id, "health");
+        assert!(pong.result.is_some());
+
+        let call = handle_probe(
+            r#"{"jsonrpc":"2.0","method":"tools/call","params":{"name":"list_projects","arguments":{}},"id":1}"#,
+        );
+        assert!(call.result.is_none());
+        assert_eq!(call.error.unwrap().code, -32601);
+    }
+
     #[test]
     fn test_error_response() {
         let resp = JsonRpcResponse::error(
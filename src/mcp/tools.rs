@@ -11,7 +11,7 @@ use serde_json::json;
 
 /// Generate all tool definitions (mega-tools architecture)
 pub fn all_tools() -> Vec<ToolDefinition> {
-    vec![
+    let tools = vec![
         project_tool(),
         plan_tool(),
         task_tool(),
@@ -32,6 +32,8 @@ pub fn all_tools() -> Vec<ToolDefinition> {
         reasoning_tool(),
         episode_tool(),
         analysis_profile_tool(),
+        view_tool(),
+        comment_tool(),
         admin_tool(),
         skill_tool(),
         protocol_tool(),
@@ -41,7 +43,29 @@ pub fn all_tools() -> Vec<ToolDefinition> {
         trajectory_tool(),
         lifecycle_hook_tool(),
         mcp_federation_tool(),
-    ]
+    ];
+    tools.into_iter().map(with_pagination_params).collect()
+}
+
+/// Add the shared pagination arguments (handled by `McpServer`, see
+/// `mcp::pagination`) to a tool's input schema.
+fn with_pagination_params(mut tool: ToolDefinition) -> ToolDefinition {
+    if let Some(props) = tool
+        .input_schema
+        .properties
+        .as_mut()
+        .and_then(|p| p.as_object_mut())
+    {
+        props.insert(
+            "cursor".to_string(),
+            json!({"type": "string", "description": "Opaque cursor from a previous truncated result's next_cursor (any action)"}),
+        );
+        props.insert(
+            "page_size".to_string(),
+            json!({"type": "integer", "description": "Max items per page when paginating with cursor (any action)"}),
+        );
+    }
+    tool
 }
 
 // ============================================================================
@@ -61,6 +85,10 @@ pub fn resolve_legacy_alias(name: &str) -> Option<(&'static str, &'static str)>
         "sync_project" => Some(("project", "sync")),
         "get_project_roadmap" => Some(("project", "get_roadmap")),
         "list_project_plans" => Some(("project", "list_plans")),
+        "list_project_advisories" => Some(("project", "list_advisories")),
+        "list_project_documents" => Some(("project", "list_documents")),
+        "get_project_schema" => Some(("project", "get_schema")),
+        "get_project_overview" => Some(("project", "get_overview")),
 
         // Plan
         "list_plans" => Some(("plan", "list")),
@@ -111,6 +139,7 @@ pub fn resolve_legacy_alias(name: &str) -> Option<(&'static str, &'static str)>
         "get_decisions_affecting" => Some(("decision", "get_affecting")),
         "supersede_decision" => Some(("decision", "supersede")),
         "get_decision_timeline" => Some(("decision", "get_timeline")),
+        "export_adrs" => Some(("decision", "export_adrs")),
 
         // Constraint
         "list_constraints" => Some(("constraint", "list")),
@@ -146,6 +175,7 @@ pub fn resolve_legacy_alias(name: &str) -> Option<(&'static str, &'static str)>
         "link_commit_to_plan" => Some(("commit", "link_to_plan")),
         "get_task_commits" => Some(("commit", "get_task_commits")),
         "get_plan_commits" => Some(("commit", "get_plan_commits")),
+        "suggest_commit_message" => Some(("commit", "suggest_message")),
 
         // Note
         "list_notes" => Some(("note", "list")),
@@ -178,6 +208,12 @@ pub fn resolve_legacy_alias(name: &str) -> Option<(&'static str, &'static str)>
         "add_project_to_workspace" => Some(("workspace", "add_project")),
         "remove_project_from_workspace" => Some(("workspace", "remove_project")),
         "get_workspace_topology" => Some(("workspace", "get_topology")),
+        "get_workspace_topology_drift" => Some(("workspace", "get_topology_drift")),
+        "get_workspace_license_policy" => Some(("workspace", "get_license_policy")),
+        "set_workspace_license_policy" => Some(("workspace", "set_license_policy")),
+        "get_workspace_license_violations" => Some(("workspace", "get_license_violations")),
+        "search_workspace" => Some(("workspace", "search")),
+        "get_workspace_context" => Some(("workspace", "get_context")),
 
         // Workspace Milestone
         "list_all_workspace_milestones" => Some(("workspace_milestone", "list_all")),
@@ -190,6 +226,7 @@ pub fn resolve_legacy_alias(name: &str) -> Option<(&'static str, &'static str)>
         "link_plan_to_workspace_milestone" => Some(("workspace_milestone", "link_plan")),
         "unlink_plan_from_workspace_milestone" => Some(("workspace_milestone", "unlink_plan")),
         "get_workspace_milestone_progress" => Some(("workspace_milestone", "get_progress")),
+        "get_workspace_milestone_rollup" => Some(("workspace_milestone", "get_rollup")),
 
         // Resource
         "list_resources" => Some(("resource", "list")),
@@ -198,6 +235,10 @@ pub fn resolve_legacy_alias(name: &str) -> Option<(&'static str, &'static str)>
         "update_resource" => Some(("resource", "update")),
         "delete_resource" => Some(("resource", "delete")),
         "link_resource_to_project" => Some(("resource", "link_to_project")),
+        "get_resource_usages" => Some(("resource", "get_usages")),
+        "parse_resource" => Some(("resource", "parse")),
+        "list_resource_elements" => Some(("resource", "list_elements")),
+        "diff_resources" => Some(("resource", "diff")),
 
         // Component
         "list_components" => Some(("component", "list")),
@@ -235,6 +276,12 @@ pub fn resolve_legacy_alias(name: &str) -> Option<(&'static str, &'static str)>
         "search_code" => Some(("code", "search")),
         "search_project_code" => Some(("code", "search_project")),
         "search_workspace_code" => Some(("code", "search_workspace")),
+        "search_docs" => Some(("code", "search_docs")),
+        "find_config_usages" => Some(("code", "find_config_usages")),
+        "get_code_snippet" => Some(("code", "get_snippet")),
+        "propose_rename" => Some(("code", "propose_rename")),
+        "explain_symbol" => Some(("code", "explain_symbol")),
+        "resolve_stack_trace" => Some(("code", "resolve_stack_trace")),
         "get_file_symbols" => Some(("code", "get_file_symbols")),
         "find_references" => Some(("code", "find_references")),
         "get_file_dependencies" => Some(("code", "get_file_dependencies")),
@@ -247,6 +294,7 @@ pub fn resolve_legacy_alias(name: &str) -> Option<(&'static str, &'static str)>
         "get_impl_blocks" => Some(("code", "get_impl_blocks")),
         "get_code_communities" => Some(("code", "get_communities")),
         "get_code_health" => Some(("code", "get_health")),
+        "find_duplicates" => Some(("code", "find_duplicates")),
         "get_node_importance" => Some(("code", "get_node_importance")),
         "plan_implementation" => Some(("code", "plan_implementation")),
         "detect_processes" => Some(("code", "detect_processes")),
@@ -290,6 +338,21 @@ pub fn resolve_legacy_alias(name: &str) -> Option<(&'static str, &'static str)>
         "get_analysis_profile" => Some(("analysis_profile", "get")),
         "delete_analysis_profile" => Some(("analysis_profile", "delete")),
 
+        // Saved View
+        "list_views" => Some(("view", "list")),
+        "create_view" => Some(("view", "create")),
+        "get_view" => Some(("view", "get")),
+        "update_view" => Some(("view", "update")),
+        "delete_view" => Some(("view", "delete")),
+        "get_view_results" => Some(("view", "get_results")),
+
+        // Comment
+        "list_comments" => Some(("comment", "list")),
+        "create_comment" => Some(("comment", "create")),
+        "get_comment" => Some(("comment", "get")),
+        "update_comment" => Some(("comment", "update")),
+        "delete_comment" => Some(("comment", "delete")),
+
         // Skill
         "list_skills" => Some(("skill", "list")),
         "create_skill" => Some(("skill", "create")),
@@ -324,6 +387,7 @@ pub fn resolve_legacy_alias(name: &str) -> Option<(&'static str, &'static str)>
         "stop_watch" => Some(("admin", "stop_watch")),
         "watch_status" => Some(("admin", "watch_status")),
         "get_meilisearch_stats" => Some(("admin", "meilisearch_stats")),
+        "get_search_analytics" => Some(("admin", "search_analytics")),
         "delete_meilisearch_orphans" => Some(("admin", "delete_meilisearch_orphans")),
         "cleanup_cross_project_calls" => Some(("admin", "cleanup_cross_project_calls")),
         "cleanup_builtin_calls" => Some(("admin", "cleanup_builtin_calls")),
@@ -359,16 +423,16 @@ pub fn resolve_legacy_alias(name: &str) -> Option<(&'static str, &'static str)>
 fn project_tool() -> ToolDefinition {
     ToolDefinition {
         name: "project".to_string(),
-        description: "Manage projects. Actions: list, create, get, update, delete, sync, get_roadmap, list_plans, get_graph, get_intelligence_summary, get_embeddings_projection, get_scaffolding_level, set_scaffolding_override, get_health_dashboard, get_auto_roadmap".to_string(),
+        description: "Manage projects. Actions: list, create, get, update, delete, sync, get_roadmap, list_plans, get_graph, get_intelligence_summary, get_embeddings_projection, get_scaffolding_level, set_scaffolding_override, get_health_dashboard, get_auto_roadmap, list_advisories, list_documents (indexed Markdown docs), get_schema (SQL tables & migrations), get_overview (onboarding summary: communities, central symbols, module summaries, resources, open plans)".to_string(),
         input_schema: InputSchema {
             schema_type: "object".to_string(),
             properties: Some(json!({
                 "action": {
                     "type": "string",
-                    "enum": ["list", "create", "get", "update", "delete", "sync", "get_roadmap", "list_plans", "get_graph", "get_intelligence_summary", "get_embeddings_projection", "get_scaffolding_level", "set_scaffolding_override", "get_health_dashboard", "get_auto_roadmap"],
+                    "enum": ["list", "create", "get", "update", "delete", "sync", "get_roadmap", "list_plans", "get_graph", "get_intelligence_summary", "get_embeddings_projection", "get_scaffolding_level", "set_scaffolding_override", "get_health_dashboard", "get_auto_roadmap", "list_advisories", "list_documents", "get_schema", "get_overview"],
                     "description": "Operation to perform"
                 },
-                "slug": {"type": "string", "description": "Project slug (get/update/delete/sync/get_roadmap/list_plans/get_graph/get_intelligence_summary/get_embeddings_projection/get_scaffolding_level/set_scaffolding_override/get_health_dashboard/get_auto_roadmap)"},
+                "slug": {"type": "string", "description": "Project slug (get/update/delete/sync/get_roadmap/list_plans/get_graph/get_intelligence_summary/get_embeddings_projection/get_scaffolding_level/set_scaffolding_override/get_health_dashboard/get_auto_roadmap/get_overview)"},
                 "name": {"type": "string", "description": "Project name (create/update)"},
                 "root_path": {"type": "string", "description": "Path to codebase root (create/update)"},
                 "description": {"type": "string", "description": "Project description (create/update)"},
@@ -377,9 +441,12 @@ fn project_tool() -> ToolDefinition {
                 "offset": {"type": "integer", "description": "Skip items (list)"},
                 "sort_by": {"type": "string", "description": "Sort field (list)"},
                 "sort_order": {"type": "string", "description": "asc or desc (list)"},
+                "include_archived": {"type": "boolean", "description": "Also list archived projects (list)"},
                 "layers": {"type": "string", "description": "Comma-separated layers: code,knowledge,fabric,neural,skills,behavioral (get_graph, default: code)"},
                 "community": {"type": "integer", "description": "Filter by community_id (get_graph)"},
-                "level": {"type": "integer", "description": "Scaffolding level 0-4 to override, or null to clear (set_scaffolding_override)"}
+                "level": {"type": "integer", "description": "Scaffolding level 0-4 to override, or null to clear (set_scaffolding_override)"},
+                "project_id": {"type": "string", "description": "Project UUID (list_advisories/list_documents/get_schema)"},
+                "min_severity": {"type": "string", "description": "low, medium, high or critical (list_advisories)"}
             })),
             required: Some(vec!["action".to_string()]),
         },
@@ -491,17 +558,17 @@ fn step_tool() -> ToolDefinition {
 fn decision_tool() -> ToolDefinition {
     ToolDefinition {
         name: "decision".to_string(),
-        description: "Manage architectural decisions. Actions: add, get, update, delete, search, search_semantic, add_affects, remove_affects, list_affects, get_affecting, supersede, get_timeline"
+        description: "Manage architectural decisions. Actions: add, get, update, delete, search, search_semantic, add_affects, remove_affects, list_affects, get_affecting, supersede, get_timeline, export_adrs"
             .to_string(),
         input_schema: InputSchema {
             schema_type: "object".to_string(),
             properties: Some(json!({
                 "action": {
                     "type": "string",
-                    "enum": ["add", "get", "update", "delete", "search", "search_semantic", "add_affects", "remove_affects", "list_affects", "get_affecting", "supersede", "get_timeline"],
+                    "enum": ["add", "get", "update", "delete", "search", "search_semantic", "add_affects", "remove_affects", "list_affects", "get_affecting", "supersede", "get_timeline", "export_adrs"],
                     "description": "Operation to perform"
                 },
-                "decision_id": {"type": "string", "description": "Decision UUID (get/update/delete/add_affects/remove_affects/list_affects)"},
+                "decision_id": {"type": "string", "description": "Decision UUID (get/update/delete/add_affects/remove_affects/list_affects; export_adrs: export only this decision)"},
                 "task_id": {"type": "string", "description": "Task UUID (add/get_timeline)"},
                 "description": {"type": "string", "description": "Decision description (add/update)"},
                 "rationale": {"type": "string", "description": "Rationale (add/update)"},
@@ -509,7 +576,7 @@ fn decision_tool() -> ToolDefinition {
                 "chosen_option": {"type": "string", "description": "Chosen option (add/update)"},
                 "status": {"type": "string", "description": "New status (update): proposed, accepted, deprecated, superseded"},
                 "query": {"type": "string", "description": "Search query (search/search_semantic)"},
-                "project_id": {"type": "string", "description": "Project UUID filter (search_semantic — post-query filtering)"},
+                "project_id": {"type": "string", "description": "Project UUID (export_adrs, required; search_semantic — post-query filtering)"},
                 "entity_type": {"type": "string", "description": "Entity type (add_affects/remove_affects/get_affecting)"},
                 "entity_id": {"type": "string", "description": "Entity identifier (add_affects/remove_affects)"},
                 "impact_description": {"type": "string", "description": "Description of how the decision impacts the entity (add_affects)"},
@@ -606,20 +673,22 @@ fn milestone_tool() -> ToolDefinition {
 fn commit_tool() -> ToolDefinition {
     ToolDefinition {
         name: "commit".to_string(),
-        description: "Register and link git commits. Actions: create, link_to_task, link_to_plan, get_task_commits, get_plan_commits, get_commit_files, get_file_history".to_string(),
+        description: "Register and link git commits. Actions: create, link_to_task, link_to_plan, get_task_commits, get_plan_commits, get_commit_files, get_file_history, suggest_message (conventional commit message for the working diff, with task references)".to_string(),
         input_schema: InputSchema {
             schema_type: "object".to_string(),
             properties: Some(json!({
                 "action": {
                     "type": "string",
-                    "enum": ["create", "link_to_task", "link_to_plan", "get_task_commits", "get_plan_commits", "get_commit_files", "get_file_history"],
+                    "enum": ["create", "link_to_task", "link_to_plan", "get_task_commits", "get_plan_commits", "get_commit_files", "get_file_history", "suggest_message"],
                     "description": "Operation to perform"
                 },
                 "sha": {"type": "string", "description": "Commit SHA (create/link_to_task/link_to_plan/get_commit_files)"},
                 "message": {"type": "string", "description": "Commit message (create)"},
                 "author": {"type": "string", "description": "Author name (create)"},
                 "files_changed": {"type": "array", "items": {"type": "string"}, "description": "Files changed (create)"},
-                "project_id": {"type": "string", "description": "Project UUID for incremental sync (create)"},
+                "project_id": {"type": "string", "description": "Project UUID for incremental sync (create), or whose diff to describe (suggest_message)"},
+                "diff": {"type": "string", "description": "Unified diff to describe instead of the working diff (suggest_message)"},
+                "staged": {"type": "boolean", "description": "Describe only staged changes (suggest_message)"},
                 "task_id": {"type": "string", "description": "Task UUID (link_to_task/get_task_commits)"},
                 "plan_id": {"type": "string", "description": "Plan UUID (link_to_plan/get_plan_commits)"},
                 "commit_sha": {"type": "string", "description": "Alias for sha (link_to_task/link_to_plan)"},
@@ -673,13 +742,13 @@ fn note_tool() -> ToolDefinition {
 fn workspace_tool() -> ToolDefinition {
     ToolDefinition {
         name: "workspace".to_string(),
-        description: "Manage workspaces. Actions: list, create, get, update, delete, get_overview, list_projects, add_project, remove_project, get_topology, get_coupling_matrix".to_string(),
+        description: "Manage workspaces. Actions: list, create, get, update, delete, get_overview, list_projects, add_project, remove_project, get_topology, get_topology_drift (declared vs observed component dependencies), get_license_policy, set_license_policy, get_license_violations (packages breaking the license policy), get_coupling_matrix, search, get_context (workspace notes, key notes and live decisions of all member projects)".to_string(),
         input_schema: InputSchema {
             schema_type: "object".to_string(),
             properties: Some(json!({
                 "action": {
                     "type": "string",
-                    "enum": ["list", "create", "get", "update", "delete", "get_overview", "list_projects", "add_project", "remove_project", "get_topology"],
+                    "enum": ["list", "create", "get", "update", "delete", "get_overview", "list_projects", "add_project", "remove_project", "get_topology", "get_topology_drift", "get_license_policy", "set_license_policy", "get_license_violations", "search", "get_context"],
                     "description": "Operation to perform"
                 },
                 "slug": {"type": "string", "description": "Workspace slug (identifier)"},
@@ -688,7 +757,13 @@ fn workspace_tool() -> ToolDefinition {
                 "description": {"type": "string", "description": "Description (create/update)"},
                 "project_id": {"type": "string", "description": "Project UUID (add_project/remove_project)"},
                 "role": {"type": "string", "description": "Project role in workspace (add_project)"},
-                "limit": {"type": "integer", "description": "Max items (list)"},
+                "allowed": {"type": "array", "items": {"type": "string"}, "description": "Allowed SPDX licenses, trailing * for prefixes; empty = all not denied (set_license_policy)"},
+                "denied": {"type": "array", "items": {"type": "string"}, "description": "Denied SPDX licenses, e.g. [\"GPL-*\", \"AGPL-*\"] (set_license_policy)"},
+                "flag_unknown": {"type": "boolean", "description": "Report packages without a known license (set_license_policy)"},
+                "query": {"type": "string", "description": "Search query across all member projects (search)"},
+                "kinds": {"type": "string", "description": "Comma-separated result kinds: code, notes, decisions (search, default: all)"},
+                "language": {"type": "string", "description": "Code language filter (search)"},
+                "limit": {"type": "integer", "description": "Max items (list), max results per kind (search), or max notes and decisions (get_context)"},
                 "offset": {"type": "integer", "description": "Skip items (list)"}
             })),
             required: Some(vec!["action".to_string()]),
@@ -699,13 +774,13 @@ fn workspace_tool() -> ToolDefinition {
 fn workspace_milestone_tool() -> ToolDefinition {
     ToolDefinition {
         name: "workspace_milestone".to_string(),
-        description: "Manage workspace milestones. Actions: list_all, list, create, get, update, delete, add_task, link_plan, unlink_plan, get_progress".to_string(),
+        description: "Manage workspace milestones. Actions: list_all, list, create, get, update, delete, add_task, link_plan, unlink_plan, get_progress, get_rollup (progress per plan, velocity and forecast completion date)".to_string(),
         input_schema: InputSchema {
             schema_type: "object".to_string(),
             properties: Some(json!({
                 "action": {
                     "type": "string",
-                    "enum": ["list_all", "list", "create", "get", "update", "delete", "add_task", "link_plan", "unlink_plan", "get_progress"],
+                    "enum": ["list_all", "list", "create", "get", "update", "delete", "add_task", "link_plan", "unlink_plan", "get_progress", "get_rollup"],
                     "description": "Operation to perform"
                 },
                 "milestone_id": {"type": "string", "description": "Workspace milestone UUID"},
@@ -717,6 +792,7 @@ fn workspace_milestone_tool() -> ToolDefinition {
                 "target_date": {"type": "string", "description": "Target date ISO (create/update)"},
                 "task_id": {"type": "string", "description": "Task UUID (add_task)"},
                 "plan_id": {"type": "string", "description": "Plan UUID (link_plan/unlink_plan)"},
+                "window_days": {"type": "integer", "description": "Days of completion history for the velocity (get_rollup, default 28)"},
                 "limit": {"type": "integer", "description": "Max items"},
                 "offset": {"type": "integer", "description": "Skip items"}
             })),
@@ -728,16 +804,18 @@ fn workspace_milestone_tool() -> ToolDefinition {
 fn resource_tool() -> ToolDefinition {
     ToolDefinition {
         name: "resource".to_string(),
-        description: "Manage workspace resources (API contracts, schemas). Actions: list, create, get, update, delete, link_to_project".to_string(),
+        description: "Manage workspace resources (API contracts, schemas). Actions: list, create, get, update, delete, link_to_project, get_usages (files of other projects importing the resource), parse (extract OpenAPI operations/schemas or proto services/messages), list_elements, diff (element changes vs an older version)".to_string(),
         input_schema: InputSchema {
             schema_type: "object".to_string(),
             properties: Some(json!({
                 "action": {
                     "type": "string",
-                    "enum": ["list", "create", "get", "update", "delete", "link_to_project"],
+                    "enum": ["list", "create", "get", "update", "delete", "link_to_project", "get_usages", "parse", "list_elements", "diff"],
                     "description": "Operation to perform"
                 },
-                "id": {"type": "string", "description": "Resource UUID (get/update/delete)"},
+                "id": {"type": "string", "description": "Resource UUID (get/update/delete/get_usages/parse/list_elements/diff)"},
+                "kind": {"type": "string", "description": "Element kind filter (list_elements): operation, schema, service, rpc, message, enum"},
+                "against": {"type": "string", "description": "UUID of the resource holding the older version (diff)"},
                 "slug": {"type": "string", "description": "Workspace slug (list/create)"},
                 "name": {"type": "string", "description": "Resource name (create/update)"},
                 "resource_type": {"type": "string", "description": "Type (create): api_contract, schema, config, documentation, other"},
@@ -858,20 +936,25 @@ fn feature_graph_tool() -> ToolDefinition {
 fn code_tool() -> ToolDefinition {
     ToolDefinition {
         name: "code".to_string(),
-        description: "Explore and analyze code. Actions: search, search_project, search_workspace, get_file_symbols, find_references, get_file_dependencies, get_call_graph, analyze_impact, get_architecture, find_similar, find_trait_implementations, find_type_traits, get_impl_blocks, get_communities, get_health, get_node_importance, plan_implementation, get_co_change_graph, get_file_co_changers, detect_processes, get_class_hierarchy, find_subclasses, find_interface_implementors, list_processes, get_process, get_entry_points, enrich_communities, get_hotspots, get_knowledge_gaps, get_risk_assessment, get_homeostasis, get_structural_drift, get_structural_profile, find_structural_twins, cluster_dna, find_cross_project_twins, predict_missing_links, check_link_plausibility, stress_test_node, stress_test_edge, stress_test_cascade, find_bridges, get_context_card, refresh_context_cards, get_fingerprint, find_isomorphic, suggest_structural_templates, get_bridge, check_topology, list_topology_rules, create_topology_rule, delete_topology_rule, check_file_topology".to_string(),
+        description: "Explore and analyze code. Actions: search, search_project, search_workspace, search_docs (project Markdown documentation), find_config_usages (where config keys / env vars are defined and read), get_file_symbols, get_snippet (source of one symbol with context lines), propose_rename (every edit a symbol rename needs, without applying it), explain_symbol (definition, callers/callees, traits, notes, recent commits and metrics of one symbol in one call), resolve_stack_trace (map a pasted Rust/Python/JavaScript stack trace onto project functions with their context), find_references, get_file_dependencies, get_call_graph, analyze_impact, get_architecture, find_similar, find_trait_implementations, find_type_traits, get_impl_blocks, get_communities, get_health, find_duplicates (groups of near-duplicate functions, refactor candidates), get_node_importance, plan_implementation, get_co_change_graph, get_file_co_changers, detect_processes, get_class_hierarchy, find_subclasses, find_interface_implementors, list_processes, get_process, get_entry_points, enrich_communities, get_hotspots, get_knowledge_gaps, get_risk_assessment, get_homeostasis, get_structural_drift, get_structural_profile, find_structural_twins, cluster_dna, find_cross_project_twins, predict_missing_links, check_link_plausibility, stress_test_node, stress_test_edge, stress_test_cascade, find_bridges, get_context_card, refresh_context_cards, get_fingerprint, find_isomorphic, suggest_structural_templates, get_bridge, check_topology, list_topology_rules, create_topology_rule, delete_topology_rule, check_file_topology".to_string(),
         input_schema: InputSchema {
             schema_type: "object".to_string(),
             properties: Some(json!({
                 "action": {
                     "type": "string",
-                    "enum": ["search", "search_project", "search_workspace", "get_file_symbols", "find_references", "get_file_dependencies", "get_call_graph", "analyze_impact", "get_architecture", "find_similar", "find_trait_implementations", "find_type_traits", "get_impl_blocks", "get_communities", "get_health", "get_node_importance", "plan_implementation", "get_co_change_graph", "get_file_co_changers", "detect_processes", "get_class_hierarchy", "find_subclasses", "find_interface_implementors", "list_processes", "get_process", "get_entry_points", "enrich_communities", "get_hotspots", "get_knowledge_gaps", "get_risk_assessment", "get_homeostasis", "get_structural_profile", "find_structural_twins", "cluster_dna", "find_cross_project_twins", "predict_missing_links", "check_link_plausibility", "stress_test_node", "stress_test_edge", "stress_test_cascade", "find_bridges", "get_context_card", "refresh_context_cards", "get_fingerprint", "find_isomorphic", "suggest_structural_templates", "get_bridge", "check_topology", "list_topology_rules", "create_topology_rule", "delete_topology_rule", "check_file_topology"],
+                    "enum": ["search", "search_project", "search_workspace", "search_docs", "find_config_usages", "get_file_symbols", "get_snippet", "propose_rename", "explain_symbol", "resolve_stack_trace", "find_references", "get_file_dependencies", "get_call_graph", "analyze_impact", "get_architecture", "find_similar", "find_trait_implementations", "find_type_traits", "get_impl_blocks", "get_communities", "get_health", "find_duplicates", "get_node_importance", "plan_implementation", "get_co_change_graph", "get_file_co_changers", "detect_processes", "get_class_hierarchy", "find_subclasses", "find_interface_implementors", "list_processes", "get_process", "get_entry_points", "enrich_communities", "get_hotspots", "get_knowledge_gaps", "get_risk_assessment", "get_homeostasis", "get_structural_profile", "find_structural_twins", "cluster_dna", "find_cross_project_twins", "predict_missing_links", "check_link_plausibility", "stress_test_node", "stress_test_edge", "stress_test_cascade", "find_bridges", "get_context_card", "refresh_context_cards", "get_fingerprint", "find_isomorphic", "suggest_structural_templates", "get_bridge", "check_topology", "list_topology_rules", "create_topology_rule", "delete_topology_rule", "check_file_topology"],
                     "description": "Operation to perform"
                 },
-                "query": {"type": "string", "description": "Search query (search/search_project/search_workspace)"},
+                "query": {"type": "string", "description": "Search query (search/search_project/search_workspace/search_docs)"},
                 "workspace_slug": {"type": "string", "description": "Workspace slug (search_workspace/find_cross_project_twins)"},
-                "project_slug": {"type": "string", "description": "Project slug (search_project/get_communities/get_health/get_node_importance/plan_implementation/get_architecture/get_context_card/get_fingerprint/find_isomorphic/get_structural_profile/find_structural_twins/cluster_dna/find_bridges/predict_missing_links/stress_test_node/stress_test_edge/stress_test_cascade/refresh_context_cards/get_bridge/check_topology/list_topology_rules/create_topology_rule/check_file_topology/check_link_plausibility/suggest_structural_templates)"},
+                "project_slug": {"type": "string", "description": "Project slug (search_project/search_docs/explain_symbol/resolve_stack_trace/find_config_usages/get_communities/get_health/find_duplicates/get_node_importance/plan_implementation/get_architecture/get_context_card/get_fingerprint/find_isomorphic/get_structural_profile/find_structural_twins/cluster_dna/find_bridges/predict_missing_links/stress_test_node/stress_test_edge/stress_test_cascade/refresh_context_cards/get_bridge/check_topology/list_topology_rules/create_topology_rule/check_file_topology/check_link_plausibility/suggest_structural_templates)"},
                 "file_path": {"type": "string", "description": "File path (get_file_symbols/get_file_dependencies/get_context_card/get_fingerprint/get_structural_profile/find_structural_twins/find_cross_project_twins/check_file_topology)"},
-                "symbol": {"type": "string", "description": "Symbol name (find_references)"},
+                "symbol": {"type": "string", "description": "Symbol name (find_references); name or graph id (explain_symbol)"},
+                "trace": {"type": "string", "description": "Stack trace or log excerpt, as printed (resolve_stack_trace)"},
+                "symbol_id": {"type": "string", "description": "Function/Struct/Trait/Enum id, `path:name:line_start` for functions, `path:name` for types (get_snippet/propose_rename)"},
+                "new_name": {"type": "string", "description": "New identifier for the symbol (propose_rename)"},
+                "context_lines": {"type": "integer", "description": "Lines of context before and after the symbol (get_snippet, default 0, max 100)"},
+                "key": {"type": "string", "description": "Config key or env var name, matched case-insensitively as a substring (find_config_usages)"},
                 "function": {"type": "string", "description": "Function name (get_call_graph)"},
                 "target": {"type": "string", "description": "Target for impact analysis (analyze_impact)"},
                 "code_snippet": {"type": "string", "description": "Code to find similar (find_similar)"},
@@ -908,7 +991,9 @@ fn code_tool() -> ToolDefinition {
                 "threshold": {"type": "integer", "description": "Threshold value for max_distance/max_fan_out rules (create_topology_rule)"},
                 "severity": {"type": "string", "description": "Rule severity (create_topology_rule): error, warning"},
                 "new_imports": {"type": "array", "items": {"type": "string"}, "description": "New import paths to check against topology rules (check_file_topology)"},
-                "limit": {"type": "integer", "description": "Max results / depth (search/get_call_graph)"}
+                "min_similarity": {"type": "number", "description": "Minimum estimated similarity between clones, 0.5-1.0 (find_duplicates, default 0.85)"},
+                "min_confidence": {"type": "number", "description": "Ignore CALLS edges scored below this confidence, 0.0-1.0 (get_call_graph, default 0.0)"},
+                "limit": {"type": "integer", "description": "Max results / depth (search/get_call_graph/find_config_usages/find_duplicates)"}
             })),
             required: Some(vec!["action".to_string()]),
         },
@@ -992,16 +1077,66 @@ fn analysis_profile_tool() -> ToolDefinition {
     }
 }
 
+fn view_tool() -> ToolDefinition {
+    ToolDefinition {
+        name: "view".to_string(),
+        description: "Manage saved views (your persisted queries over tasks, notes or project symbols). Actions: list, create, get, update, delete, get_results".to_string(),
+        input_schema: InputSchema {
+            schema_type: "object".to_string(),
+            properties: Some(json!({
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "create", "get", "update", "delete", "get_results"],
+                    "description": "Operation to perform"
+                },
+                "id": {"type": "string", "description": "View UUID (get/update/delete/get_results)"},
+                "name": {"type": "string", "description": "View name, unique per user (create/update)"},
+                "description": {"type": "string", "description": "View description (create/update)"},
+                "target": {"type": "string", "enum": ["tasks", "notes", "symbols"], "description": "What the view lists (create/update)"},
+                "filters": {"type": "object", "description": "View filters (create/update — replaces all filters). Common: project_id (required for symbols), sort_by, sort_order, limit. Tasks/notes: workspace_slug, status (array), tags (array). Tasks: plan_id, priority_min, priority_max, assigned_to. Notes: note_type (array), importance (array), min_staleness. Symbols: visibility, min_complexity, untested (bool), name_contains"},
+                "limit": {"type": "integer", "description": "Page size, overrides the view limit (get_results, max 100)"},
+                "offset": {"type": "integer", "description": "Items to skip (get_results)"}
+            })),
+            required: Some(vec!["action".to_string()]),
+        },
+    }
+}
+
+fn comment_tool() -> ToolDefinition {
+    ToolDefinition {
+        name: "comment".to_string(),
+        description: "Discuss tasks, plans and notes: comment, reply, mention people with @handle. Actions: list, create, get, update, delete".to_string(),
+        input_schema: InputSchema {
+            schema_type: "object".to_string(),
+            properties: Some(json!({
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "create", "get", "update", "delete"],
+                    "description": "Operation to perform"
+                },
+                "id": {"type": "string", "description": "Comment UUID (get/update/delete)"},
+                "entity_type": {"type": "string", "enum": ["task", "plan", "note"], "description": "Kind of entity commented (list/create)"},
+                "entity_id": {"type": "string", "description": "Task, plan or note UUID (list/create)"},
+                "content": {"type": "string", "description": "Comment text, @handle mentions people (create/update)"},
+                "parent_id": {"type": "string", "description": "Comment UUID to reply to, on the same entity (create)"},
+                "mentioned": {"type": "string", "description": "List the comments mentioning this handle instead of an entity's (list)"},
+                "limit": {"type": "integer", "description": "Max comments mentioning the handle (list with mentioned, default 50)"}
+            })),
+            required: Some(vec!["action".to_string()]),
+        },
+    }
+}
+
 fn admin_tool() -> ToolDefinition {
     ToolDefinition {
         name: "admin".to_string(),
-        description: "Admin operations. Actions: sync_directory, start_watch, stop_watch, watch_status, meilisearch_stats, delete_meilisearch_orphans, cleanup_cross_project_calls, cleanup_builtin_calls, migrate_calls_confidence, cleanup_sync_data, update_staleness_scores, update_energy_scores, search_neurons, reinforce_neurons, decay_synapses, backfill_synapses, reindex_decisions, backfill_decision_embeddings, backfill_note_embeddings, backfill_note_embeddings_status, backfill_touches, backfill_discussed, update_fabric_scores, bootstrap_knowledge_fabric, reinforce_isomorphic, detect_skills, detect_skill_fission, detect_skill_fusion, heal_scars, consolidate_memory, detect_stagnation, deep_maintenance, analyze_runner_feedback, install_hooks".to_string(),
+        description: "Admin operations. Actions: sync_directory, start_watch, stop_watch, watch_status, meilisearch_stats, search_analytics, delete_meilisearch_orphans, cleanup_cross_project_calls, cleanup_builtin_calls, migrate_calls_confidence, cleanup_sync_data, update_staleness_scores, update_energy_scores, search_neurons, reinforce_neurons, decay_synapses, backfill_synapses, reindex_decisions, backfill_decision_embeddings, backfill_note_embeddings, backfill_note_embeddings_status, backfill_touches, backfill_discussed, update_fabric_scores, bootstrap_knowledge_fabric, reinforce_isomorphic, detect_skills, detect_skill_fission, detect_skill_fusion, heal_scars, consolidate_memory, detect_stagnation, deep_maintenance, analyze_runner_feedback, install_hooks".to_string(),
         input_schema: InputSchema {
             schema_type: "object".to_string(),
             properties: Some(json!({
                 "action": {
                     "type": "string",
-                    "enum": ["sync_directory", "start_watch", "stop_watch", "watch_status", "meilisearch_stats", "delete_meilisearch_orphans", "cleanup_cross_project_calls", "cleanup_builtin_calls", "migrate_calls_confidence", "cleanup_sync_data", "update_staleness_scores", "update_energy_scores", "search_neurons", "reinforce_neurons", "decay_synapses", "backfill_synapses", "reindex_decisions", "backfill_decision_embeddings", "backfill_note_embeddings", "backfill_note_embeddings_status", "backfill_touches", "backfill_discussed", "update_fabric_scores", "bootstrap_knowledge_fabric", "reinforce_isomorphic", "detect_skills", "detect_skill_fission", "detect_skill_fusion", "maintain_skills", "auto_anchor_notes", "reconstruct_knowledge", "heal_scars", "consolidate_memory", "detect_stagnation", "deep_maintenance", "seed_prompt_fragments", "analyze_runner_feedback", "install_hooks"],
+                    "enum": ["sync_directory", "start_watch", "stop_watch", "watch_status", "meilisearch_stats", "search_analytics", "delete_meilisearch_orphans", "cleanup_cross_project_calls", "cleanup_builtin_calls", "migrate_calls_confidence", "cleanup_sync_data", "update_staleness_scores", "update_energy_scores", "search_neurons", "reinforce_neurons", "decay_synapses", "backfill_synapses", "reindex_decisions", "backfill_decision_embeddings", "backfill_note_embeddings", "backfill_note_embeddings_status", "backfill_touches", "backfill_discussed", "update_fabric_scores", "bootstrap_knowledge_fabric", "reinforce_isomorphic", "detect_skills", "detect_skill_fission", "detect_skill_fusion", "maintain_skills", "auto_anchor_notes", "reconstruct_knowledge", "heal_scars", "consolidate_memory", "detect_stagnation", "deep_maintenance", "seed_prompt_fragments", "analyze_runner_feedback", "install_hooks"],
                     "description": "Operation to perform"
                 },
                 "path": {"type": "string", "description": "Directory path (sync_directory/start_watch)"},
@@ -1015,7 +1150,10 @@ fn admin_tool() -> ToolDefinition {
                 "min_strength": {"type": "number", "description": "Min strength filter (search_neurons)"},
                 "decay_amount": {"type": "number", "description": "Amount to subtract from each synapse weight (decay_synapses, default 0.01)"},
                 "prune_threshold": {"type": "number", "description": "Prune synapses below this weight (decay_synapses, default 0.1)"},
-                "limit": {"type": "integer", "description": "Max items (search_neurons)"},
+                "limit": {"type": "integer", "description": "Max items (search_neurons/search_analytics)"},
+                "endpoint": {"type": "string", "enum": ["code", "docs", "notes", "notes_semantic", "decisions", "decisions_semantic"], "description": "Restrict to one search endpoint (search_analytics)"},
+                "project_slug": {"type": "string", "description": "Restrict to one project (search_analytics)"},
+                "since": {"type": "string", "description": "RFC 3339 date: only queries searched since then (search_analytics)"},
                 "level": {"type": "string", "enum": ["hourly", "daily", "weekly", "full"], "description": "Maintenance level (maintain_skills, default: daily)"},
                 "force": {"type": "boolean", "description": "Force re-detection from scratch, deleting all existing skills first (detect_skills, default: false)"},
                 "node_id": {"type": "string", "description": "Note or Decision UUID (heal_scars)"}
@@ -1435,8 +1573,8 @@ mod tests {
         let tools = all_tools();
         assert_eq!(
             tools.len(),
-            29,
-            "Expected 29 mega-tools, got {}",
+            31,
+            "Expected 31 mega-tools, got {}",
             tools.len()
         );
     }
@@ -1480,6 +1618,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_all_tools_accept_pagination_params() {
+        for tool in all_tools() {
+            let props = tool.input_schema.properties.as_ref().unwrap();
+            assert!(props.get("cursor").is_some(), "{} lacks cursor", tool.name);
+            assert!(
+                props.get("page_size").is_some(),
+                "{} lacks page_size",
+                tool.name
+            );
+        }
+    }
+
     #[test]
     fn test_all_tools_have_valid_input_schema() {
         let tools = all_tools();
@@ -1504,6 +1655,10 @@ mod tests {
             "sync_project",
             "get_project_roadmap",
             "list_project_plans",
+            "list_project_advisories",
+            "list_project_documents",
+            "get_project_schema",
+            "get_project_overview",
             "list_plans",
             "create_plan",
             "get_plan",
@@ -1560,9 +1715,16 @@ mod tests {
             "link_commit_to_plan",
             "get_task_commits",
             "get_plan_commits",
+            "suggest_commit_message",
             "search_code",
             "search_project_code",
+            "search_docs",
+            "find_config_usages",
             "get_file_symbols",
+            "get_code_snippet",
+            "propose_rename",
+            "explain_symbol",
+            "resolve_stack_trace",
             "find_references",
             "get_file_dependencies",
             "get_call_graph",
@@ -1578,11 +1740,14 @@ mod tests {
             "search_decisions",
             "search_decisions_semantic",
             "search_workspace_code",
+            "search_workspace",
+            "get_workspace_context",
             "sync_directory",
             "start_watch",
             "stop_watch",
             "watch_status",
             "get_meilisearch_stats",
+            "get_search_analytics",
             "delete_meilisearch_orphans",
             "cleanup_cross_project_calls",
             "cleanup_builtin_calls",
@@ -1630,12 +1795,17 @@ mod tests {
             "link_plan_to_workspace_milestone",
             "unlink_plan_from_workspace_milestone",
             "get_workspace_milestone_progress",
+            "get_workspace_milestone_rollup",
             "list_resources",
             "create_resource",
             "get_resource",
             "update_resource",
             "delete_resource",
             "link_resource_to_project",
+            "get_resource_usages",
+            "parse_resource",
+            "list_resource_elements",
+            "diff_resources",
             "list_components",
             "create_component",
             "get_component",
@@ -1645,6 +1815,10 @@ mod tests {
             "remove_component_dependency",
             "map_component_to_project",
             "get_workspace_topology",
+            "get_workspace_topology_drift",
+            "get_workspace_license_policy",
+            "set_workspace_license_policy",
+            "get_workspace_license_violations",
             "list_chat_messages",
             "list_chat_sessions",
             "get_chat_session",
@@ -1661,6 +1835,7 @@ mod tests {
             "delete_feature_graph",
             "get_code_communities",
             "get_code_health",
+            "find_duplicates",
             "get_node_importance",
             "plan_implementation",
             "detect_processes",
@@ -1686,6 +1861,18 @@ mod tests {
             "stress_test_edge",
             "stress_test_cascade",
             "find_bridges",
+            "list_views",
+            "create_view",
+            "get_view",
+            "update_view",
+            "delete_view",
+            "get_view_results",
+            "list_comments",
+            "create_comment",
+            "get_comment",
+            "update_comment",
+            "delete_comment",
+            "export_adrs",
         ];
 
         for name in &old_names {
@@ -2,9 +2,21 @@
 
 use super::indexes::*;
 use anyhow::{Context, Result};
-use meilisearch_sdk::{client::Client, indexes::Index, search::SearchResults, settings::Settings};
+use meilisearch_sdk::{
+    client::Client,
+    indexes::Index,
+    search::{SearchResults, Selectors},
+    settings::Settings,
+};
 use serde::{de::DeserializeOwned, Serialize};
 
+/// Highlighted fields of code hits
+const CODE_HIGHLIGHT_FIELDS: &[&str] = &["symbols", "signatures", "docstrings"];
+/// Highlighted fields of documentation hits
+const DOC_HIGHLIGHT_FIELDS: &[&str] = &["heading", "content"];
+/// Words kept around the matches of cropped fields
+const HIGHLIGHT_CROP_LENGTH: usize = 24;
+
 /// Client for Meilisearch operations
 pub struct MeiliClient {
     client: Client,
@@ -34,6 +46,7 @@ impl MeiliClient {
             index_names::CODE,
             index_names::DECISIONS,
             index_names::NOTES,
+            index_names::DOCS,
         ];
 
         for index_name in indexes {
@@ -57,6 +70,9 @@ impl MeiliClient {
         // Configure notes index
         self.configure_notes_index().await?;
 
+        // Configure docs index
+        self.configure_docs_index().await?;
+
         Ok(())
     }
 
@@ -135,6 +151,67 @@ impl MeiliClient {
         Ok(())
     }
 
+    /// Configure the docs index settings
+    async fn configure_docs_index(&self) -> Result<()> {
+        let index = self.client.index(index_names::DOCS);
+
+        let settings = Settings::new()
+            .with_searchable_attributes([
+                "heading", // Section heading (highest priority)
+                "title",   // Document title
+                "content", // Section text
+                "path",    // Document path
+            ])
+            .with_filterable_attributes(["path", "project_id", "project_slug", "code_languages"])
+            .with_sortable_attributes(["path", "line_start"]);
+
+        let task = index.set_settings(&settings).await?;
+        task.wait_for_completion(&self.client, None, Some(std::time::Duration::from_secs(30)))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Apply synonyms, stop words and ranking rules to every index. Without
+    /// ranking rules the indexes go back to the Meilisearch defaults.
+    pub async fn apply_search_settings(&self, search: &SearchSettings) -> Result<()> {
+        let synonyms: std::collections::HashMap<String, Vec<String>> = search
+            .synonyms
+            .iter()
+            .map(|(word, words)| (word.clone(), words.clone()))
+            .collect();
+        for index_name in [
+            index_names::CODE,
+            index_names::DECISIONS,
+            index_names::NOTES,
+            index_names::DOCS,
+        ] {
+            let index = self.client.index(index_name);
+            let mut settings = Settings::new()
+                .with_synonyms(synonyms.clone())
+                .with_stop_words(search.stop_words.clone());
+            if let Some(ref rules) = search.ranking_rules {
+                settings = settings.with_ranking_rules(rules.clone());
+            } else {
+                let task = index.reset_ranking_rules().await?;
+                task.wait_for_completion(
+                    &self.client,
+                    None,
+                    Some(std::time::Duration::from_secs(30)),
+                )
+                .await?;
+            }
+
+            let task = index
+                .set_settings(&settings)
+                .await
+                .context(format!("Failed to update settings of index {}", index_name))?;
+            task.wait_for_completion(&self.client, None, Some(std::time::Duration::from_secs(30)))
+                .await?;
+        }
+        Ok(())
+    }
+
     /// Get an index by name
     pub fn index(&self, name: &str) -> Index {
         self.client.index(name)
@@ -222,7 +299,10 @@ impl MeiliClient {
         search
             .with_query(query)
             .with_limit(limit)
-            .with_show_ranking_score(true);
+            .with_show_ranking_score(true)
+            .with_attributes_to_highlight(Selectors::Some(CODE_HIGHLIGHT_FIELDS))
+            .with_attributes_to_crop(Selectors::Some(&[("docstrings", None)]))
+            .with_crop_length(HIGHLIGHT_CROP_LENGTH);
 
         if let Some(ref filter) = filter_str {
             search.with_filter(filter);
@@ -233,6 +313,11 @@ impl MeiliClient {
             .hits
             .into_iter()
             .map(|h| SearchHit {
+                highlights: h
+                    .formatted_result
+                    .as_ref()
+                    .map(|f| extract_highlights(f, CODE_HIGHLIGHT_FIELDS))
+                    .unwrap_or_default(),
                 document: h.result,
                 score: h.ranking_score.unwrap_or(0.0),
             })
@@ -249,6 +334,12 @@ impl MeiliClient {
         Ok(())
     }
 
+    /// List all code documents for a project
+    pub async fn list_code_for_project(&self, project_slug: &str) -> Result<Vec<CodeDocument>> {
+        self.list_documents_for_project(index_names::CODE, project_slug)
+            .await
+    }
+
     /// Delete all code documents for a project
     pub async fn delete_code_for_project(&self, project_slug: &str) -> Result<()> {
         use meilisearch_sdk::documents::DocumentDeletionQuery;
@@ -379,6 +470,15 @@ impl MeiliClient {
         Ok(())
     }
 
+    /// List all decision documents for a project
+    pub async fn list_decisions_for_project(
+        &self,
+        project_slug: &str,
+    ) -> Result<Vec<DecisionDocument>> {
+        self.list_documents_for_project(index_names::DECISIONS, project_slug)
+            .await
+    }
+
     /// Delete all decision documents for a project
     pub async fn delete_decisions_for_project(&self, project_slug: &str) -> Result<()> {
         use meilisearch_sdk::documents::DocumentDeletionQuery;
@@ -509,6 +609,7 @@ impl MeiliClient {
             .map(|h| SearchHit {
                 document: h.result,
                 score: h.ranking_score.unwrap_or(0.0),
+                highlights: Vec::new(),
             })
             .collect())
     }
@@ -522,6 +623,12 @@ impl MeiliClient {
         Ok(())
     }
 
+    /// List all notes for a project
+    pub async fn list_notes_for_project(&self, project_slug: &str) -> Result<Vec<NoteDocument>> {
+        self.list_documents_for_project(index_names::NOTES, project_slug)
+            .await
+    }
+
     /// Delete all note documents for a project
     pub async fn delete_notes_for_project(&self, project_slug: &str) -> Result<()> {
         use meilisearch_sdk::documents::DocumentDeletionQuery;
@@ -567,6 +674,111 @@ impl MeiliClient {
         })
     }
 
+    // ========================================================================
+    // Documentation indexing
+    // ========================================================================
+
+    /// Index documentation sections
+    pub async fn index_doc_sections(&self, docs: &[DocSectionDocument]) -> Result<()> {
+        if docs.is_empty() {
+            return Ok(());
+        }
+        let index = self.client.index(index_names::DOCS);
+        let task = index.add_documents(docs, Some("id")).await?;
+        task.wait_for_completion(&self.client, None, Some(std::time::Duration::from_secs(30)))
+            .await?;
+        Ok(())
+    }
+
+    /// Search documentation sections with ranking scores
+    pub async fn search_docs_with_scores(
+        &self,
+        query: &str,
+        limit: usize,
+        project_slug: Option<&str>,
+    ) -> Result<Vec<SearchHit<DocSectionDocument>>> {
+        let index = self.client.index(index_names::DOCS);
+
+        let filter = project_slug.map(|slug| format!("project_slug = \"{}\"", slug));
+
+        let mut search = index.search();
+        search
+            .with_query(query)
+            .with_limit(limit)
+            .with_show_ranking_score(true)
+            .with_attributes_to_highlight(Selectors::Some(DOC_HIGHLIGHT_FIELDS))
+            .with_attributes_to_crop(Selectors::Some(&[("content", None)]))
+            .with_crop_length(HIGHLIGHT_CROP_LENGTH);
+
+        if let Some(ref filter) = filter {
+            search.with_filter(filter);
+        }
+
+        let results: SearchResults<DocSectionDocument> = search.execute().await?;
+        Ok(results
+            .hits
+            .into_iter()
+            .map(|h| {
+                let mut highlights = h
+                    .formatted_result
+                    .as_ref()
+                    .map(|f| extract_highlights(f, DOC_HIGHLIGHT_FIELDS))
+                    .unwrap_or_default();
+                for highlight in &mut highlights {
+                    highlight.line = if highlight.field == "content" {
+                        estimate_line_in_text(highlight, &h.result.content, h.result.line_start)
+                    } else {
+                        Some(h.result.line_start)
+                    };
+                }
+                SearchHit {
+                    document: h.result,
+                    score: h.ranking_score.unwrap_or(0.0),
+                    highlights,
+                }
+            })
+            .collect())
+    }
+
+    /// Delete the sections of a document
+    pub async fn delete_docs_for_path(&self, path: &str) -> Result<()> {
+        use meilisearch_sdk::documents::DocumentDeletionQuery;
+
+        let index = self.client.index(index_names::DOCS);
+        let mut query = DocumentDeletionQuery::new(&index);
+        let filter = format!("path = \"{}\"", path);
+        query.with_filter(&filter);
+
+        let task = index.delete_documents_with(&query).await?;
+        task.wait_for_completion(&self.client, None, Some(std::time::Duration::from_secs(30)))
+            .await?;
+        Ok(())
+    }
+
+    /// List all documentation sections for a project
+    pub async fn list_docs_for_project(
+        &self,
+        project_slug: &str,
+    ) -> Result<Vec<DocSectionDocument>> {
+        self.list_documents_for_project(index_names::DOCS, project_slug)
+            .await
+    }
+
+    /// Delete all documentation sections for a project
+    pub async fn delete_docs_for_project(&self, project_slug: &str) -> Result<()> {
+        use meilisearch_sdk::documents::DocumentDeletionQuery;
+
+        let index = self.client.index(index_names::DOCS);
+        let mut query = DocumentDeletionQuery::new(&index);
+        let filter = format!("project_slug = \"{}\"", project_slug);
+        query.with_filter(&filter);
+
+        let task = index.delete_documents_with(&query).await?;
+        task.wait_for_completion(&self.client, None, Some(std::time::Duration::from_secs(30)))
+            .await?;
+        Ok(())
+    }
+
     // ========================================================================
     // Generic operations
     // ========================================================================
@@ -603,6 +815,33 @@ impl MeiliClient {
         Ok(())
     }
 
+    /// List every document of an index belonging to a project, page by page
+    async fn list_documents_for_project<T: DeserializeOwned + Send + Sync + 'static>(
+        &self,
+        index_name: &str,
+        project_slug: &str,
+    ) -> Result<Vec<T>> {
+        use meilisearch_sdk::documents::DocumentsQuery;
+
+        const PAGE_SIZE: usize = 1000;
+        let index = self.client.index(index_name);
+        let filter = format!("project_slug = \"{}\"", project_slug);
+        let mut documents = Vec::new();
+        loop {
+            let mut query = DocumentsQuery::new(&index);
+            query
+                .with_filter(&filter)
+                .with_limit(PAGE_SIZE)
+                .with_offset(documents.len());
+            let page = query.execute::<T>().await?;
+            let fetched = page.results.len();
+            documents.extend(page.results);
+            if fetched < PAGE_SIZE {
+                return Ok(documents);
+            }
+        }
+    }
+
     // ========================================================================
     // Utilities
     // ========================================================================
@@ -664,5 +903,6 @@ mod tests {
         assert_eq!(index_names::CODE, "code");
         assert_eq!(index_names::DECISIONS, "decisions");
         assert_eq!(index_names::NOTES, "notes");
+        assert_eq!(index_names::DOCS, "docs");
     }
 }
@@ -7,7 +7,10 @@ use anyhow::Result;
 use async_trait::async_trait;
 
 use super::client::MeiliClient;
-use super::indexes::{CodeDocument, DecisionDocument, IndexStats, NoteDocument, SearchHit};
+use super::indexes::{
+    CodeDocument, DecisionDocument, DocSectionDocument, IndexStats, NoteDocument, SearchHit,
+    SearchSettings,
+};
 use super::traits::SearchStore;
 
 #[async_trait]
@@ -60,6 +63,10 @@ impl SearchStore for MeiliClient {
         self.delete_code(path).await
     }
 
+    async fn list_code_for_project(&self, project_slug: &str) -> Result<Vec<CodeDocument>> {
+        self.list_code_for_project(project_slug).await
+    }
+
     async fn delete_code_for_project(&self, project_slug: &str) -> Result<()> {
         self.delete_code_for_project(project_slug).await
     }
@@ -112,6 +119,13 @@ impl SearchStore for MeiliClient {
         self.delete_decision(id).await
     }
 
+    async fn list_decisions_for_project(
+        &self,
+        project_slug: &str,
+    ) -> Result<Vec<DecisionDocument>> {
+        self.list_decisions_for_project(project_slug).await
+    }
+
     async fn delete_decisions_for_project(&self, project_slug: &str) -> Result<()> {
         self.delete_decisions_for_project(project_slug).await
     }
@@ -166,6 +180,10 @@ impl SearchStore for MeiliClient {
         self.delete_note(id).await
     }
 
+    async fn list_notes_for_project(&self, project_slug: &str) -> Result<Vec<NoteDocument>> {
+        self.list_notes_for_project(project_slug).await
+    }
+
     async fn delete_notes_for_project(&self, project_slug: &str) -> Result<()> {
         self.delete_notes_for_project(project_slug).await
     }
@@ -178,6 +196,40 @@ impl SearchStore for MeiliClient {
         self.get_notes_stats().await
     }
 
+    // ========================================================================
+    // Documentation indexing
+    // ========================================================================
+
+    async fn index_doc_sections(&self, docs: &[DocSectionDocument]) -> Result<()> {
+        self.index_doc_sections(docs).await
+    }
+
+    async fn search_docs_with_scores(
+        &self,
+        query: &str,
+        limit: usize,
+        project_slug: Option<&str>,
+    ) -> Result<Vec<SearchHit<DocSectionDocument>>> {
+        self.search_docs_with_scores(query, limit, project_slug)
+            .await
+    }
+
+    async fn delete_docs_for_path(&self, path: &str) -> Result<()> {
+        self.delete_docs_for_path(path).await
+    }
+
+    async fn list_docs_for_project(&self, project_slug: &str) -> Result<Vec<DocSectionDocument>> {
+        self.list_docs_for_project(project_slug).await
+    }
+
+    async fn delete_docs_for_project(&self, project_slug: &str) -> Result<()> {
+        self.delete_docs_for_project(project_slug).await
+    }
+
+    async fn apply_search_settings(&self, settings: &SearchSettings) -> Result<()> {
+        self.apply_search_settings(settings).await
+    }
+
     async fn health_check(&self) -> Result<bool> {
         Ok(self.is_healthy().await)
     }
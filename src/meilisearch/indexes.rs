@@ -8,6 +8,95 @@ pub struct SearchHit<T> {
     pub document: T,
     /// Ranking score from Meilisearch (0.0 to 1.0, higher is better)
     pub score: f64,
+    /// Matching fragments, when the search engine returned them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<SearchHighlight>,
+}
+
+/// Opening and closing tags around the matched words of a highlight
+pub const HIGHLIGHT_PRE_TAG: &str = "<em>";
+pub const HIGHLIGHT_POST_TAG: &str = "</em>";
+
+/// Most highlights kept per hit
+const MAX_HIGHLIGHTS: usize = 5;
+
+/// A fragment of a hit that matched the query, from Meilisearch's
+/// `_formatted` result: the matched words are wrapped in `<em>` tags and
+/// long texts are cropped around them (`…`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchHighlight {
+    /// Document field the fragment comes from (`symbols`, `docstrings`, ...)
+    pub field: String,
+    pub snippet: String,
+    /// Estimated line of the fragment in the source file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+impl SearchHighlight {
+    /// The highlighted words, lowercased
+    pub fn terms(&self) -> Vec<String> {
+        let mut terms = Vec::new();
+        let mut rest = self.snippet.as_str();
+        while let Some(start) = rest.find(HIGHLIGHT_PRE_TAG) {
+            rest = &rest[start + HIGHLIGHT_PRE_TAG.len()..];
+            let Some(end) = rest.find(HIGHLIGHT_POST_TAG) else {
+                break;
+            };
+            let term = rest[..end].trim().to_lowercase();
+            if !term.is_empty() && !terms.contains(&term) {
+                terms.push(term);
+            }
+            rest = &rest[end + HIGHLIGHT_POST_TAG.len()..];
+        }
+        terms
+    }
+}
+
+/// Collect the fragments of `fields` that contain a highlighted word, from
+/// the `_formatted` object of a hit. Array fields give one fragment per
+/// matching element.
+pub fn extract_highlights(
+    formatted: &serde_json::Map<String, serde_json::Value>,
+    fields: &[&str],
+) -> Vec<SearchHighlight> {
+    let mut highlights = Vec::new();
+    for field in fields {
+        let values: Vec<&str> = match formatted.get(*field) {
+            Some(serde_json::Value::String(s)) => vec![s.as_str()],
+            Some(serde_json::Value::Array(items)) => {
+                items.iter().filter_map(|v| v.as_str()).collect()
+            }
+            _ => continue,
+        };
+        for value in values {
+            if value.contains(HIGHLIGHT_PRE_TAG) {
+                highlights.push(SearchHighlight {
+                    field: field.to_string(),
+                    snippet: value.to_string(),
+                    line: None,
+                });
+            }
+        }
+    }
+    highlights.truncate(MAX_HIGHLIGHTS);
+    highlights
+}
+
+/// Estimate the line of the first highlighted word of a fragment in `text`,
+/// a text starting at line `first_line`.
+pub fn estimate_line_in_text(
+    highlight: &SearchHighlight,
+    text: &str,
+    first_line: usize,
+) -> Option<usize> {
+    let lower = text.to_lowercase();
+    highlight
+        .terms()
+        .iter()
+        .filter_map(|term| lower.find(term.as_str()))
+        .min()
+        .map(|pos| first_line + lower[..pos].matches('\n').count())
 }
 
 /// Code document for indexing
@@ -82,6 +171,25 @@ pub struct NoteDocument {
     pub staleness_score: f64,
 }
 
+/// Documentation section document for indexing (one per Markdown heading)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocSectionDocument {
+    /// Hash of `<path>#<anchor>`
+    pub id: String,
+    pub path: String,
+    /// Document title
+    pub title: String,
+    pub heading: String,
+    pub anchor: String,
+    pub level: u8,
+    pub line_start: usize,
+    /// Section text, code blocks included
+    pub content: String,
+    pub code_languages: Vec<String>,
+    pub project_id: String,
+    pub project_slug: String,
+}
+
 /// Statistics for a Meilisearch index
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IndexStats {
@@ -89,11 +197,28 @@ pub struct IndexStats {
     pub is_indexing: bool,
 }
 
+/// Search settings applied to every index: synonyms, stop words and
+/// ranking rules. Stored once per deployment and re-applied at startup.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchSettings {
+    /// Word -> words it also matches. One-way, like Meilisearch synonyms:
+    /// list both directions for mutual synonyms.
+    #[serde(default)]
+    pub synonyms: std::collections::BTreeMap<String, Vec<String>>,
+    /// Words ignored in queries and documents
+    #[serde(default)]
+    pub stop_words: Vec<String>,
+    /// Meilisearch ranking rules, in order. `None` keeps the defaults.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ranking_rules: Option<Vec<String>>,
+}
+
 /// Index names
 pub mod index_names {
     pub const CODE: &str = "code";
     pub const DECISIONS: &str = "decisions";
     pub const NOTES: &str = "notes";
+    pub const DOCS: &str = "docs";
 }
 
 #[cfg(test)]
@@ -206,6 +331,7 @@ mod tests {
                 project_slug: "".to_string(),
             },
             score: 0.85,
+            highlights: vec![],
         };
 
         let json = serde_json::to_string(&hit).unwrap();
@@ -215,6 +341,33 @@ mod tests {
         assert_eq!(hit.document.id, deserialized.document.id);
     }
 
+    #[test]
+    fn test_extract_highlights() {
+        let formatted = serde_json::json!({
+            "symbols": ["MeiliClient", "<em>search</em>_code"],
+            "docstrings": "…run a <em>search</em> in the code index…",
+            "path": "src/meilisearch/client.rs",
+        });
+        let highlights = extract_highlights(
+            formatted.as_object().unwrap(),
+            &["symbols", "signatures", "docstrings"],
+        );
+        assert_eq!(highlights.len(), 2);
+        assert_eq!(highlights[0].field, "symbols");
+        assert_eq!(highlights[0].snippet, "<em>search</em>_code");
+        assert_eq!(highlights[1].terms(), vec!["search"]);
+
+        let highlight = SearchHighlight {
+            field: "content".to_string(),
+            snippet: "set the <em>Port</em>".to_string(),
+            line: None,
+        };
+        assert_eq!(
+            estimate_line_in_text(&highlight, "Intro\n\nSet the port here", 12),
+            Some(14)
+        );
+    }
+
     #[test]
     fn test_index_stats_serialization() {
         let stats = IndexStats {
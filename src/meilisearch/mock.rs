@@ -15,6 +15,9 @@ pub struct MockSearchStore {
     pub code_documents: RwLock<Vec<CodeDocument>>,
     pub decision_documents: RwLock<Vec<DecisionDocument>>,
     pub note_documents: RwLock<Vec<NoteDocument>>,
+    pub doc_section_documents: RwLock<Vec<DocSectionDocument>>,
+    /// Last settings passed to `apply_search_settings`
+    pub search_settings: RwLock<Option<SearchSettings>>,
 }
 
 impl MockSearchStore {
@@ -24,6 +27,8 @@ impl MockSearchStore {
             code_documents: RwLock::new(Vec::new()),
             decision_documents: RwLock::new(Vec::new()),
             note_documents: RwLock::new(Vec::new()),
+            doc_section_documents: RwLock::new(Vec::new()),
+            search_settings: RwLock::new(None),
         }
     }
 }
@@ -60,6 +65,31 @@ fn any_field_matches(fields: &[&str], query: &str) -> bool {
         .any(|f| f.to_lowercase().contains(&query_lower))
 }
 
+/// Wrap the first case-insensitive occurrence of the query in `text` in
+/// highlight tags, like Meilisearch's `_formatted` (without cropping).
+fn highlight(field: &str, text: &str, query: &str) -> Option<SearchHighlight> {
+    if query.is_empty() || query == "*" {
+        return None;
+    }
+    let start = text.to_lowercase().find(&query.to_lowercase())?;
+    let end = start + query.len();
+    if !text.is_char_boundary(start) || !text.is_char_boundary(end) {
+        return None;
+    }
+    Some(SearchHighlight {
+        field: field.to_string(),
+        snippet: format!(
+            "{}{}{}{}{}",
+            &text[..start],
+            HIGHLIGHT_PRE_TAG,
+            &text[start..end],
+            HIGHLIGHT_POST_TAG,
+            &text[end..]
+        ),
+        line: None,
+    })
+}
+
 /// Best score across multiple text fields.
 fn best_score(fields: &[&str], query: &str) -> f64 {
     fields
@@ -179,9 +209,16 @@ impl SearchStore for MockSearchStore {
                 let symbols_text = d.symbols.join(" ");
                 let score = best_score(&[&symbols_text, &d.docstrings, &d.path], query);
                 if score > 0.0 {
+                    let highlights = d
+                        .symbols
+                        .iter()
+                        .filter_map(|sym| highlight("symbols", sym, query))
+                        .chain(highlight("docstrings", &d.docstrings, query))
+                        .collect();
                     Some(SearchHit {
                         document: d.clone(),
                         score,
+                        highlights,
                     })
                 } else {
                     None
@@ -204,6 +241,15 @@ impl SearchStore for MockSearchStore {
         Ok(())
     }
 
+    async fn list_code_for_project(&self, project_slug: &str) -> Result<Vec<CodeDocument>> {
+        let docs = self.code_documents.read().await;
+        Ok(docs
+            .iter()
+            .filter(|d| d.project_slug == project_slug)
+            .cloned()
+            .collect())
+    }
+
     async fn delete_code_for_project(&self, project_slug: &str) -> Result<()> {
         let mut docs = self.code_documents.write().await;
         docs.retain(|d| d.project_slug != project_slug);
@@ -309,6 +355,18 @@ impl SearchStore for MockSearchStore {
         Ok(())
     }
 
+    async fn list_decisions_for_project(
+        &self,
+        project_slug: &str,
+    ) -> Result<Vec<DecisionDocument>> {
+        let docs = self.decision_documents.read().await;
+        Ok(docs
+            .iter()
+            .filter(|d| d.project_slug.as_deref() == Some(project_slug))
+            .cloned()
+            .collect())
+    }
+
     async fn delete_decisions_for_project(&self, project_slug: &str) -> Result<()> {
         let mut docs = self.decision_documents.write().await;
         docs.retain(|d| d.project_slug.as_deref() != Some(project_slug));
@@ -437,6 +495,7 @@ impl SearchStore for MockSearchStore {
                     Some(SearchHit {
                         document: d.clone(),
                         score,
+                        highlights: Vec::new(),
                     })
                 } else {
                     None
@@ -458,6 +517,15 @@ impl SearchStore for MockSearchStore {
         Ok(())
     }
 
+    async fn list_notes_for_project(&self, project_slug: &str) -> Result<Vec<NoteDocument>> {
+        let docs = self.note_documents.read().await;
+        Ok(docs
+            .iter()
+            .filter(|d| d.project_slug == project_slug)
+            .cloned()
+            .collect())
+    }
+
     async fn delete_notes_for_project(&self, project_slug: &str) -> Result<()> {
         let mut docs = self.note_documents.write().await;
         docs.retain(|d| d.project_slug != project_slug);
@@ -480,6 +548,95 @@ impl SearchStore for MockSearchStore {
         })
     }
 
+    // ======================================================================
+    // Documentation
+    // ======================================================================
+
+    async fn index_doc_sections(&self, docs: &[DocSectionDocument]) -> Result<()> {
+        let mut stored = self.doc_section_documents.write().await;
+        for doc in docs {
+            if let Some(existing) = stored.iter_mut().find(|d| d.id == doc.id) {
+                *existing = doc.clone();
+            } else {
+                stored.push(doc.clone());
+            }
+        }
+        Ok(())
+    }
+
+    async fn search_docs_with_scores(
+        &self,
+        query: &str,
+        limit: usize,
+        project_slug: Option<&str>,
+    ) -> Result<Vec<SearchHit<DocSectionDocument>>> {
+        let docs = self.doc_section_documents.read().await;
+        let mut results: Vec<SearchHit<DocSectionDocument>> = docs
+            .iter()
+            .filter(|d| project_slug.is_none_or(|slug| d.project_slug == slug))
+            .filter_map(|d| {
+                let score = best_score(&[&d.heading, &d.title, &d.content, &d.path], query);
+                (score > 0.0).then(|| {
+                    let highlights = highlight("heading", &d.heading, query)
+                        .map(|h| SearchHighlight {
+                            line: Some(d.line_start),
+                            ..h
+                        })
+                        .into_iter()
+                        .chain(
+                            highlight("content", &d.content, query).map(|h| SearchHighlight {
+                                line: estimate_line_in_text(&h, &d.content, d.line_start),
+                                ..h
+                            }),
+                        )
+                        .collect();
+                    SearchHit {
+                        document: d.clone(),
+                        score,
+                        highlights,
+                    }
+                })
+            })
+            .collect();
+        results.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        results.truncate(limit);
+        Ok(results)
+    }
+
+    async fn delete_docs_for_path(&self, path: &str) -> Result<()> {
+        let mut docs = self.doc_section_documents.write().await;
+        docs.retain(|d| d.path != path);
+        Ok(())
+    }
+
+    async fn list_docs_for_project(&self, project_slug: &str) -> Result<Vec<DocSectionDocument>> {
+        let docs = self.doc_section_documents.read().await;
+        Ok(docs
+            .iter()
+            .filter(|d| d.project_slug == project_slug)
+            .cloned()
+            .collect())
+    }
+
+    async fn delete_docs_for_project(&self, project_slug: &str) -> Result<()> {
+        let mut docs = self.doc_section_documents.write().await;
+        docs.retain(|d| d.project_slug != project_slug);
+        Ok(())
+    }
+
+    // ======================================================================
+    // Settings
+    // ======================================================================
+
+    async fn apply_search_settings(&self, settings: &SearchSettings) -> Result<()> {
+        *self.search_settings.write().await = Some(settings.clone());
+        Ok(())
+    }
+
     async fn health_check(&self) -> Result<bool> {
         Ok(true)
     }
@@ -961,4 +1118,43 @@ mod tests {
         assert_eq!(hits.len(), 1);
         assert_eq!(hits[0].document.project_slug, "proj-a");
     }
+
+    #[tokio::test]
+    async fn test_doc_sections_search_and_delete() {
+        let store = MockSearchStore::new();
+        let section = |id: &str, path: &str, heading: &str, slug: &str| DocSectionDocument {
+            id: id.to_string(),
+            path: path.to_string(),
+            title: "Guide".to_string(),
+            heading: heading.to_string(),
+            anchor: heading.to_lowercase(),
+            level: 2,
+            line_start: 1,
+            content: "Run the installer".to_string(),
+            code_languages: vec![],
+            project_id: "proj-1".to_string(),
+            project_slug: slug.to_string(),
+        };
+        store
+            .index_doc_sections(&[
+                section("a", "/p/README.md", "Install", "proj-a"),
+                section("b", "/p/README.md", "Usage", "proj-a"),
+                section("c", "/q/README.md", "Install", "proj-b"),
+            ])
+            .await
+            .unwrap();
+
+        let hits = store
+            .search_docs_with_scores("install", 10, Some("proj-a"))
+            .await
+            .unwrap();
+        // Heading match ranks first, content match second
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].document.id, "a");
+
+        store.delete_docs_for_path("/p/README.md").await.unwrap();
+        assert_eq!(store.doc_section_documents.read().await.len(), 1);
+        store.delete_docs_for_project("proj-b").await.unwrap();
+        assert!(store.doc_section_documents.read().await.is_empty());
+    }
 }
@@ -3,10 +3,14 @@
 pub mod client;
 mod impl_search_store;
 pub mod indexes;
+pub mod resilient;
 pub mod traits;
+pub mod write_behind;
 
 pub use client::MeiliClient;
+pub use resilient::{ResilientSearchStore, SearchCircuitStatus};
 pub use traits::SearchStore;
+pub use write_behind::{IndexQueue, IndexQueueStatus, WriteBehindStore};
 
-#[cfg(test)]
-pub(crate) mod mock;
+#[cfg(any(test, feature = "testkit"))]
+pub mod mock;
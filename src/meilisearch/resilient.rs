@@ -0,0 +1,1034 @@
+//! Circuit breaker around the search store.
+//!
+//! [`ResilientSearchStore`] wraps the Meilisearch client so an outage degrades
+//! the orchestrator instead of failing every sync and search:
+//!
+//! - After [`FAILURE_THRESHOLD`] consecutive connection failures the circuit
+//!   opens and calls stop reaching Meilisearch. Once [`OPEN_COOLDOWN`] has
+//!   elapsed, the next call starts a background health probe (half-open);
+//!   a healthy answer closes the circuit.
+//! - Index and delete operations are queued while the circuit is open (or when
+//!   they fail for lack of a connection) and replayed in order once it closes.
+//!   The queue is bounded by [`MAX_QUEUED_WRITES`]; the oldest writes are
+//!   dropped beyond it, and a later re-sync restores them.
+//! - Code searches fall back to matching symbol names in the graph.
+//! - Other searches and admin operations fail fast with [`SearchUnavailable`].
+//!
+//! Only transport failures count against the circuit: an error answered by
+//! Meilisearch itself (bad filter, missing index...) means the engine is up.
+
+use super::client::MeiliClient;
+use super::indexes::*;
+use super::traits::SearchStore;
+use crate::neo4j::traits::GraphStore;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Consecutive connection failures that open the circuit
+pub const FAILURE_THRESHOLD: u32 = 3;
+
+/// Time the circuit stays open before Meilisearch is probed again
+pub const OPEN_COOLDOWN: Duration = Duration::from_secs(15);
+
+/// Writes kept for replay while Meilisearch is unreachable
+pub const MAX_QUEUED_WRITES: usize = 10_000;
+
+/// Graph symbols fetched per requested hit by the fallback code search
+const FALLBACK_SYMBOLS_PER_HIT: usize = 5;
+
+/// Returned instead of calling Meilisearch while the circuit is open.
+#[derive(Debug, thiserror::Error)]
+#[error("Meilisearch is unavailable (circuit open)")]
+pub struct SearchUnavailable;
+
+/// State of the circuit breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Calls go through
+    Closed,
+    /// Calls fail fast (or degrade) until the cooldown elapses
+    Open,
+    /// A health probe is deciding whether to close the circuit
+    HalfOpen,
+}
+
+/// Circuit breaker status reported by `/health`
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchCircuitStatus {
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    /// When the current outage started
+    pub open_since: Option<DateTime<Utc>>,
+    /// Writes waiting to be replayed
+    pub queued_writes: usize,
+    /// Writes discarded because the queue was full (since startup)
+    pub dropped_writes: u64,
+}
+
+impl SearchCircuitStatus {
+    /// True while searches are degraded or indexing lags behind
+    pub fn is_degraded(&self) -> bool {
+        self.state != CircuitState::Closed || self.queued_writes > 0
+    }
+}
+
+/// True when `err` means Meilisearch could not be reached
+pub fn is_outage(err: &anyhow::Error) -> bool {
+    use meilisearch_sdk::errors::Error as MeiliError;
+    err.chain().any(|e| {
+        e.is::<SearchUnavailable>()
+            || matches!(
+                e.downcast_ref::<MeiliError>(),
+                Some(
+                    MeiliError::MeilisearchCommunication(_)
+                        | MeiliError::HttpError(_)
+                        | MeiliError::Timeout
+                )
+            )
+    })
+}
+
+/// An index or delete operation waiting for Meilisearch to come back
+#[derive(Debug, Clone)]
+enum PendingWrite {
+    IndexCode(Vec<CodeDocument>),
+    DeleteCode(String),
+    DeleteCodeForProject(String),
+    DeleteOrphanCode,
+    DeleteAllCode,
+    IndexDecision(DecisionDocument),
+    DeleteDecision(String),
+    DeleteDecisionsForProject(String),
+    DeleteDecisionsForTask(String),
+    IndexNotes(Vec<NoteDocument>),
+    DeleteNote(String),
+    DeleteNotesForProject(String),
+    UpdateNoteStatus { id: String, status: String },
+    IndexDocSections(Vec<DocSectionDocument>),
+    DeleteDocsForPath(String),
+    DeleteDocsForProject(String),
+}
+
+impl PendingWrite {
+    async fn apply(&self, store: &dyn SearchStore) -> Result<()> {
+        match self {
+            Self::IndexCode(docs) => store.index_code_batch(docs).await,
+            Self::DeleteCode(path) => store.delete_code(path).await,
+            Self::DeleteCodeForProject(slug) => store.delete_code_for_project(slug).await,
+            Self::DeleteOrphanCode => store.delete_orphan_code_documents().await,
+            Self::DeleteAllCode => store.delete_all_code().await,
+            Self::IndexDecision(doc) => store.index_decision(doc).await,
+            Self::DeleteDecision(id) => store.delete_decision(id).await,
+            Self::DeleteDecisionsForProject(slug) => store.delete_decisions_for_project(slug).await,
+            Self::DeleteDecisionsForTask(id) => store.delete_decisions_for_task(id).await,
+            Self::IndexNotes(docs) => store.index_notes_batch(docs).await,
+            Self::DeleteNote(id) => store.delete_note(id).await,
+            Self::DeleteNotesForProject(slug) => store.delete_notes_for_project(slug).await,
+            Self::UpdateNoteStatus { id, status } => store.update_note_status(id, status).await,
+            Self::IndexDocSections(docs) => store.index_doc_sections(docs).await,
+            Self::DeleteDocsForPath(path) => store.delete_docs_for_path(path).await,
+            Self::DeleteDocsForProject(slug) => store.delete_docs_for_project(slug).await,
+        }
+    }
+}
+
+struct Breaker {
+    state: CircuitState,
+    failures: u32,
+    opened_at: Option<Instant>,
+    open_since: Option<DateTime<Utc>>,
+}
+
+impl Breaker {
+    fn open(&mut self) {
+        if self.state == CircuitState::Closed {
+            self.open_since = Some(Utc::now());
+        }
+        self.state = CircuitState::Open;
+        self.opened_at = Some(Instant::now());
+    }
+}
+
+/// State shared with the probe and flush tasks
+struct Shared {
+    inner: Arc<dyn SearchStore>,
+    breaker: Mutex<Breaker>,
+    queue: Mutex<VecDeque<PendingWrite>>,
+    dropped: AtomicU64,
+    flushing: AtomicBool,
+}
+
+impl Shared {
+    /// Whether calls may reach Meilisearch. Starts the health probe when an
+    /// open circuit has cooled down.
+    fn allows(self: &Arc<Self>) -> bool {
+        let mut breaker = self.breaker.lock().unwrap();
+        match breaker.state {
+            CircuitState::Closed => true,
+            CircuitState::HalfOpen => false,
+            CircuitState::Open => {
+                if breaker
+                    .opened_at
+                    .is_some_and(|at| at.elapsed() >= OPEN_COOLDOWN)
+                {
+                    breaker.state = CircuitState::HalfOpen;
+                    tokio::spawn(self.clone().probe());
+                }
+                false
+            }
+        }
+    }
+
+    /// Count the outcome of a call that reached Meilisearch
+    fn record<T>(&self, result: &Result<T>) {
+        let outage = matches!(result, Err(e) if is_outage(e));
+        let mut breaker = self.breaker.lock().unwrap();
+        if !outage {
+            breaker.failures = 0;
+            return;
+        }
+        breaker.failures += 1;
+        if breaker.state == CircuitState::Closed && breaker.failures >= FAILURE_THRESHOLD {
+            breaker.open();
+            tracing::warn!(
+                failures = breaker.failures,
+                "Meilisearch unreachable, opening the search circuit"
+            );
+        }
+    }
+
+    /// Ask Meilisearch whether it is back; close the circuit and replay the
+    /// queue if so.
+    async fn probe(self: Arc<Self>) {
+        let healthy = self.inner.health_check().await.unwrap_or(false);
+        if !healthy {
+            self.breaker.lock().unwrap().open();
+            return;
+        }
+        {
+            let mut breaker = self.breaker.lock().unwrap();
+            breaker.state = CircuitState::Closed;
+            breaker.failures = 0;
+            breaker.opened_at = None;
+            breaker.open_since = None;
+        }
+        tracing::info!(
+            queued = self.queue.lock().unwrap().len(),
+            "Meilisearch is reachable again, closing the search circuit"
+        );
+        self.flush().await;
+    }
+
+    fn enqueue(&self, write: PendingWrite) {
+        let mut queue = self.queue.lock().unwrap();
+        if queue.len() >= MAX_QUEUED_WRITES {
+            queue.pop_front();
+            let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+            if dropped == 1 || dropped % 1000 == 0 {
+                tracing::warn!(
+                    dropped,
+                    "Search write queue full, dropping the oldest writes (re-sync to restore them)"
+                );
+            }
+        }
+        queue.push_back(write);
+    }
+
+    fn has_queued(&self) -> bool {
+        !self.queue.lock().unwrap().is_empty()
+    }
+
+    /// Replay queued writes in order. Only one flush runs at a time; it stops
+    /// at the first connection failure, leaving that write at the front.
+    async fn flush(&self) {
+        if self.flushing.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        loop {
+            let next = self.queue.lock().unwrap().pop_front();
+            let Some(write) = next else {
+                self.flushing.store(false, Ordering::SeqCst);
+                // A write queued between the pop and the reset is ours to replay
+                if self.has_queued() && !self.flushing.swap(true, Ordering::SeqCst) {
+                    continue;
+                }
+                return;
+            };
+            let result = write.apply(self.inner.as_ref()).await;
+            self.record(&result);
+            match result {
+                Ok(()) => {}
+                Err(e) if is_outage(&e) => {
+                    self.queue.lock().unwrap().push_front(write);
+                    self.flushing.store(false, Ordering::SeqCst);
+                    return;
+                }
+                Err(e) => tracing::warn!("Dropping queued search write {:?}: {:#}", write, e),
+            }
+        }
+    }
+}
+
+/// [`SearchStore`] that keeps the orchestrator working through Meilisearch
+/// outages (see the module documentation).
+pub struct ResilientSearchStore {
+    shared: Arc<Shared>,
+    graph: Arc<dyn GraphStore>,
+}
+
+impl ResilientSearchStore {
+    pub fn new(inner: Arc<dyn SearchStore>, graph: Arc<dyn GraphStore>) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                inner,
+                breaker: Mutex::new(Breaker {
+                    state: CircuitState::Closed,
+                    failures: 0,
+                    opened_at: None,
+                    open_since: None,
+                }),
+                queue: Mutex::new(VecDeque::new()),
+                dropped: AtomicU64::new(0),
+                flushing: AtomicBool::new(false),
+            }),
+            graph,
+        }
+    }
+
+    fn inner(&self) -> &dyn SearchStore {
+        self.shared.inner.as_ref()
+    }
+
+    /// Run a read through the circuit
+    async fn call<T>(&self, call: impl Future<Output = Result<T>>) -> Result<T> {
+        if !self.shared.allows() {
+            return Err(SearchUnavailable.into());
+        }
+        let result = call.await;
+        self.shared.record(&result);
+        result
+    }
+
+    /// Run a write through the circuit, queueing it (built by `pending`) when
+    /// Meilisearch is unreachable or earlier writes are still waiting.
+    async fn write(
+        &self,
+        call: impl Future<Output = Result<()>>,
+        pending: impl FnOnce() -> PendingWrite,
+    ) -> Result<()> {
+        if !self.shared.allows() {
+            self.shared.enqueue(pending());
+            return Ok(());
+        }
+        if self.shared.has_queued() {
+            // Keep the order of writes: this one goes behind the queue
+            self.shared.enqueue(pending());
+            let shared = self.shared.clone();
+            tokio::spawn(async move { shared.flush().await });
+            return Ok(());
+        }
+        let result = call.await;
+        self.shared.record(&result);
+        match result {
+            Err(e) if is_outage(&e) => {
+                tracing::debug!("Queueing search write after a connection failure: {:#}", e);
+                self.shared.enqueue(pending());
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    /// Code search through the circuit, falling back to the graph
+    async fn search_code_hits(
+        &self,
+        query: &str,
+        limit: usize,
+        language_filter: Option<&str>,
+        project_slug: Option<&str>,
+        path_prefix: Option<&str>,
+    ) -> Result<Vec<SearchHit<CodeDocument>>> {
+        let result = self
+            .call(self.inner().search_code_with_scores(
+                query,
+                limit,
+                language_filter,
+                project_slug,
+                path_prefix,
+            ))
+            .await;
+        match result {
+            Err(e) if is_outage(&e) => {
+                self.graph_code_search(query, limit, language_filter, project_slug, path_prefix)
+                    .await
+            }
+            result => result,
+        }
+    }
+
+    /// Match query terms against symbol names in the graph, one hit per file,
+    /// scored by the fraction of terms matched.
+    async fn graph_code_search(
+        &self,
+        query: &str,
+        limit: usize,
+        language_filter: Option<&str>,
+        project_slug: Option<&str>,
+        path_prefix: Option<&str>,
+    ) -> Result<Vec<SearchHit<CodeDocument>>> {
+        let terms = query_terms(query);
+        if terms.is_empty() || limit == 0 {
+            return Ok(Vec::new());
+        }
+        let mut slugs: HashMap<Uuid, String> = HashMap::new();
+        let project_id = match project_slug {
+            Some(slug) => match self.graph.get_project_by_slug(slug).await? {
+                Some(project) => {
+                    slugs.insert(project.id, project.slug);
+                    Some(project.id)
+                }
+                None => return Ok(Vec::new()),
+            },
+            None => None,
+        };
+        let symbols = self
+            .graph
+            .find_symbols_matching(&terms, project_id, limit * FALLBACK_SYMBOLS_PER_HIT)
+            .await?;
+
+        struct FileMatch {
+            path: String,
+            language: String,
+            project_id: Uuid,
+            symbols: Vec<String>,
+            docstrings: Vec<String>,
+        }
+        let mut files: Vec<FileMatch> = Vec::new();
+        let mut by_path: HashMap<String, usize> = HashMap::new();
+        for symbol in symbols {
+            let (Some(pid), language) = (symbol.project_id, symbol.language.unwrap_or_default())
+            else {
+                continue;
+            };
+            if language_filter.is_some_and(|l| !l.eq_ignore_ascii_case(&language))
+                || path_prefix.is_some_and(|p| !symbol.file_path.starts_with(p))
+            {
+                continue;
+            }
+            let index = *by_path.entry(symbol.file_path.clone()).or_insert_with(|| {
+                files.push(FileMatch {
+                    path: symbol.file_path.clone(),
+                    language,
+                    project_id: pid,
+                    symbols: Vec::new(),
+                    docstrings: Vec::new(),
+                });
+                files.len() - 1
+            });
+            let file = &mut files[index];
+            if !file.symbols.contains(&symbol.name) {
+                file.symbols.push(symbol.name);
+            }
+            file.docstrings.extend(symbol.docstring);
+        }
+
+        let mut hits = Vec::new();
+        for file in files {
+            let names: Vec<String> = file.symbols.iter().map(|s| s.to_lowercase()).collect();
+            let matched = terms
+                .iter()
+                .filter(|t| names.iter().any(|n| n.contains(t.as_str())))
+                .count();
+            let slug = match slugs.get(&file.project_id) {
+                Some(slug) => slug.clone(),
+                None => {
+                    let slug = self
+                        .graph
+                        .get_project(file.project_id)
+                        .await?
+                        .map(|p| p.slug)
+                        .unwrap_or_default();
+                    slugs.insert(file.project_id, slug.clone());
+                    slug
+                }
+            };
+            hits.push(SearchHit {
+                document: CodeDocument {
+                    id: MeiliClient::path_to_id(&file.path),
+                    path: file.path,
+                    language: file.language,
+                    symbols: file.symbols,
+                    docstrings: file.docstrings.join("\n\n"),
+                    signatures: Vec::new(),
+                    imports: Vec::new(),
+                    project_id: file.project_id.to_string(),
+                    project_slug: slug,
+                },
+                score: matched as f64 / terms.len() as f64,
+                highlights: Vec::new(),
+            });
+        }
+        // Stable: files keep the shortest-name-first order within a score
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(limit);
+        Ok(hits)
+    }
+
+    /// Open the circuit as if Meilisearch had stopped answering
+    #[cfg(test)]
+    fn force_open(&self) {
+        self.shared.breaker.lock().unwrap().open();
+    }
+
+    /// Run the health probe an elapsed cooldown would start
+    #[cfg(test)]
+    async fn probe(&self) {
+        self.shared.breaker.lock().unwrap().state = CircuitState::HalfOpen;
+        self.shared.clone().probe().await;
+    }
+}
+
+/// Lowercase identifier-like words of a search query (at least 2 characters)
+fn query_terms(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for word in query.split(|c: char| !(c.is_alphanumeric() || c == '_')) {
+        let word = word.to_lowercase();
+        if word.chars().count() >= 2 && !terms.contains(&word) {
+            terms.push(word);
+        }
+    }
+    terms
+}
+
+#[async_trait]
+impl SearchStore for ResilientSearchStore {
+    // ========================================================================
+    // Code indexing
+    // ========================================================================
+
+    async fn index_code(&self, doc: &CodeDocument) -> Result<()> {
+        self.write(self.inner().index_code(doc), || {
+            PendingWrite::IndexCode(vec![doc.clone()])
+        })
+        .await
+    }
+
+    async fn index_code_batch(&self, docs: &[CodeDocument]) -> Result<()> {
+        self.write(self.inner().index_code_batch(docs), || {
+            PendingWrite::IndexCode(docs.to_vec())
+        })
+        .await
+    }
+
+    async fn search_code(
+        &self,
+        query: &str,
+        limit: usize,
+        language_filter: Option<&str>,
+    ) -> Result<Vec<CodeDocument>> {
+        self.search_code_in_project(query, limit, language_filter, None)
+            .await
+    }
+
+    async fn search_code_in_project(
+        &self,
+        query: &str,
+        limit: usize,
+        language_filter: Option<&str>,
+        project_slug: Option<&str>,
+    ) -> Result<Vec<CodeDocument>> {
+        let result = self
+            .call(
+                self.inner()
+                    .search_code_in_project(query, limit, language_filter, project_slug),
+            )
+            .await;
+        match result {
+            Err(e) if is_outage(&e) => Ok(self
+                .graph_code_search(query, limit, language_filter, project_slug, None)
+                .await?
+                .into_iter()
+                .map(|hit| hit.document)
+                .collect()),
+            result => result,
+        }
+    }
+
+    async fn search_code_with_scores(
+        &self,
+        query: &str,
+        limit: usize,
+        language_filter: Option<&str>,
+        project_slug: Option<&str>,
+        path_prefix: Option<&str>,
+    ) -> Result<Vec<SearchHit<CodeDocument>>> {
+        self.search_code_hits(query, limit, language_filter, project_slug, path_prefix)
+            .await
+    }
+
+    async fn delete_code(&self, path: &str) -> Result<()> {
+        self.write(self.inner().delete_code(path), || {
+            PendingWrite::DeleteCode(path.to_string())
+        })
+        .await
+    }
+
+    async fn list_code_for_project(&self, project_slug: &str) -> Result<Vec<CodeDocument>> {
+        self.call(self.inner().list_code_for_project(project_slug))
+            .await
+    }
+
+    async fn delete_code_for_project(&self, project_slug: &str) -> Result<()> {
+        self.write(self.inner().delete_code_for_project(project_slug), || {
+            PendingWrite::DeleteCodeForProject(project_slug.to_string())
+        })
+        .await
+    }
+
+    async fn delete_orphan_code_documents(&self) -> Result<()> {
+        self.write(self.inner().delete_orphan_code_documents(), || {
+            PendingWrite::DeleteOrphanCode
+        })
+        .await
+    }
+
+    async fn delete_all_code(&self) -> Result<()> {
+        self.write(self.inner().delete_all_code(), || {
+            PendingWrite::DeleteAllCode
+        })
+        .await
+    }
+
+    async fn get_code_stats(&self) -> Result<IndexStats> {
+        self.call(self.inner().get_code_stats()).await
+    }
+
+    // ========================================================================
+    // Decision indexing
+    // ========================================================================
+
+    async fn index_decision(&self, doc: &DecisionDocument) -> Result<()> {
+        self.write(self.inner().index_decision(doc), || {
+            PendingWrite::IndexDecision(doc.clone())
+        })
+        .await
+    }
+
+    async fn search_decisions(&self, query: &str, limit: usize) -> Result<Vec<DecisionDocument>> {
+        self.call(self.inner().search_decisions(query, limit)).await
+    }
+
+    async fn search_decisions_in_project(
+        &self,
+        query: &str,
+        limit: usize,
+        project_slug: Option<&str>,
+    ) -> Result<Vec<DecisionDocument>> {
+        self.call(
+            self.inner()
+                .search_decisions_in_project(query, limit, project_slug),
+        )
+        .await
+    }
+
+    async fn search_decisions_in_projects(
+        &self,
+        query: &str,
+        limit: usize,
+        project_slugs: &[String],
+    ) -> Result<Vec<DecisionDocument>> {
+        self.call(
+            self.inner()
+                .search_decisions_in_projects(query, limit, project_slugs),
+        )
+        .await
+    }
+
+    async fn delete_decision(&self, id: &str) -> Result<()> {
+        self.write(self.inner().delete_decision(id), || {
+            PendingWrite::DeleteDecision(id.to_string())
+        })
+        .await
+    }
+
+    async fn list_decisions_for_project(
+        &self,
+        project_slug: &str,
+    ) -> Result<Vec<DecisionDocument>> {
+        self.call(self.inner().list_decisions_for_project(project_slug))
+            .await
+    }
+
+    async fn delete_decisions_for_project(&self, project_slug: &str) -> Result<()> {
+        self.write(
+            self.inner().delete_decisions_for_project(project_slug),
+            || PendingWrite::DeleteDecisionsForProject(project_slug.to_string()),
+        )
+        .await
+    }
+
+    async fn delete_decisions_for_task(&self, task_id: &str) -> Result<()> {
+        self.write(self.inner().delete_decisions_for_task(task_id), || {
+            PendingWrite::DeleteDecisionsForTask(task_id.to_string())
+        })
+        .await
+    }
+
+    // ========================================================================
+    // Note indexing
+    // ========================================================================
+
+    async fn index_note(&self, doc: &NoteDocument) -> Result<()> {
+        self.write(self.inner().index_note(doc), || {
+            PendingWrite::IndexNotes(vec![doc.clone()])
+        })
+        .await
+    }
+
+    async fn index_notes_batch(&self, docs: &[NoteDocument]) -> Result<()> {
+        self.write(self.inner().index_notes_batch(docs), || {
+            PendingWrite::IndexNotes(docs.to_vec())
+        })
+        .await
+    }
+
+    async fn search_notes(&self, query: &str, limit: usize) -> Result<Vec<NoteDocument>> {
+        self.call(self.inner().search_notes(query, limit)).await
+    }
+
+    async fn search_notes_with_filters(
+        &self,
+        query: &str,
+        limit: usize,
+        project_slug: Option<&str>,
+        note_type: Option<&str>,
+        status: Option<&str>,
+        importance: Option<&str>,
+    ) -> Result<Vec<NoteDocument>> {
+        self.call(self.inner().search_notes_with_filters(
+            query,
+            limit,
+            project_slug,
+            note_type,
+            status,
+            importance,
+        ))
+        .await
+    }
+
+    async fn search_notes_with_scores(
+        &self,
+        query: &str,
+        limit: usize,
+        project_slug: Option<&str>,
+        note_type: Option<&str>,
+        status: Option<&str>,
+        importance: Option<&str>,
+    ) -> Result<Vec<SearchHit<NoteDocument>>> {
+        self.call(self.inner().search_notes_with_scores(
+            query,
+            limit,
+            project_slug,
+            note_type,
+            status,
+            importance,
+        ))
+        .await
+    }
+
+    async fn delete_note(&self, id: &str) -> Result<()> {
+        self.write(self.inner().delete_note(id), || {
+            PendingWrite::DeleteNote(id.to_string())
+        })
+        .await
+    }
+
+    async fn list_notes_for_project(&self, project_slug: &str) -> Result<Vec<NoteDocument>> {
+        self.call(self.inner().list_notes_for_project(project_slug))
+            .await
+    }
+
+    async fn delete_notes_for_project(&self, project_slug: &str) -> Result<()> {
+        self.write(self.inner().delete_notes_for_project(project_slug), || {
+            PendingWrite::DeleteNotesForProject(project_slug.to_string())
+        })
+        .await
+    }
+
+    async fn update_note_status(&self, id: &str, status: &str) -> Result<()> {
+        self.write(self.inner().update_note_status(id, status), || {
+            PendingWrite::UpdateNoteStatus {
+                id: id.to_string(),
+                status: status.to_string(),
+            }
+        })
+        .await
+    }
+
+    async fn get_notes_stats(&self) -> Result<IndexStats> {
+        self.call(self.inner().get_notes_stats()).await
+    }
+
+    // ========================================================================
+    // Documentation indexing
+    // ========================================================================
+
+    async fn index_doc_sections(&self, docs: &[DocSectionDocument]) -> Result<()> {
+        self.write(self.inner().index_doc_sections(docs), || {
+            PendingWrite::IndexDocSections(docs.to_vec())
+        })
+        .await
+    }
+
+    async fn search_docs_with_scores(
+        &self,
+        query: &str,
+        limit: usize,
+        project_slug: Option<&str>,
+    ) -> Result<Vec<SearchHit<DocSectionDocument>>> {
+        self.call(
+            self.inner()
+                .search_docs_with_scores(query, limit, project_slug),
+        )
+        .await
+    }
+
+    async fn delete_docs_for_path(&self, path: &str) -> Result<()> {
+        self.write(self.inner().delete_docs_for_path(path), || {
+            PendingWrite::DeleteDocsForPath(path.to_string())
+        })
+        .await
+    }
+
+    async fn list_docs_for_project(&self, project_slug: &str) -> Result<Vec<DocSectionDocument>> {
+        self.call(self.inner().list_docs_for_project(project_slug))
+            .await
+    }
+
+    async fn delete_docs_for_project(&self, project_slug: &str) -> Result<()> {
+        self.write(self.inner().delete_docs_for_project(project_slug), || {
+            PendingWrite::DeleteDocsForProject(project_slug.to_string())
+        })
+        .await
+    }
+
+    // ========================================================================
+    // Settings
+    // ========================================================================
+
+    async fn apply_search_settings(&self, settings: &SearchSettings) -> Result<()> {
+        self.call(self.inner().apply_search_settings(settings))
+            .await
+    }
+
+    // ========================================================================
+    // Health check
+    // ========================================================================
+
+    async fn health_check(&self) -> Result<bool> {
+        if !self.shared.allows() {
+            return Ok(false);
+        }
+        let result = self.inner().health_check().await;
+        let healthy = matches!(result, Ok(true));
+        if healthy {
+            self.shared.record(&Ok(()));
+        } else {
+            self.shared.record::<()>(&Err(SearchUnavailable.into()));
+        }
+        Ok(healthy)
+    }
+
+    fn circuit_status(&self) -> Option<SearchCircuitStatus> {
+        let breaker = self.shared.breaker.lock().unwrap();
+        Some(SearchCircuitStatus {
+            state: breaker.state,
+            consecutive_failures: breaker.failures,
+            open_since: breaker.open_since,
+            queued_writes: self.shared.queue.lock().unwrap().len(),
+            dropped_writes: self.shared.dropped.load(Ordering::Relaxed),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meilisearch::mock::MockSearchStore;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::{FileNode, FunctionNode, Visibility};
+    use crate::test_helpers::test_project_named;
+
+    fn code_doc(path: &str) -> CodeDocument {
+        CodeDocument {
+            id: MeiliClient::path_to_id(path),
+            path: path.to_string(),
+            language: "rust".to_string(),
+            symbols: vec![],
+            docstrings: String::new(),
+            signatures: vec![],
+            imports: vec![],
+            project_id: Uuid::new_v4().to_string(),
+            project_slug: "demo".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_query_terms() {
+        assert_eq!(
+            query_terms("parse_config, Load a FILE!"),
+            vec!["parse_config", "load", "file"]
+        );
+        assert!(query_terms("* a").is_empty());
+    }
+
+    #[test]
+    fn test_is_outage() {
+        let err: anyhow::Error = meilisearch_sdk::errors::Error::Timeout.into();
+        assert!(is_outage(&err.context("Failed to search code")));
+        assert!(is_outage(&SearchUnavailable.into()));
+        assert!(!is_outage(&anyhow::anyhow!("invalid filter")));
+    }
+
+    #[tokio::test]
+    async fn test_failures_open_the_circuit() {
+        let store = ResilientSearchStore::new(
+            Arc::new(MockSearchStore::new()),
+            Arc::new(MockGraphStore::new()),
+        );
+        for _ in 0..FAILURE_THRESHOLD - 1 {
+            store.shared.record::<()>(&Err(SearchUnavailable.into()));
+        }
+        assert_eq!(store.circuit_status().unwrap().state, CircuitState::Closed);
+
+        // A non-outage error resets the count
+        store
+            .shared
+            .record::<()>(&Err(anyhow::anyhow!("bad filter")));
+        assert_eq!(store.circuit_status().unwrap().consecutive_failures, 0);
+
+        for _ in 0..FAILURE_THRESHOLD {
+            store.shared.record::<()>(&Err(SearchUnavailable.into()));
+        }
+        let status = store.circuit_status().unwrap();
+        assert_eq!(status.state, CircuitState::Open);
+        assert!(status.open_since.is_some());
+        assert!(status.is_degraded());
+
+        // Reads fail fast while open
+        let err = store.search_notes("x", 5).await.unwrap_err();
+        assert!(err.is::<SearchUnavailable>());
+        assert!(!store.health_check().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_writes_are_queued_and_replayed() {
+        let inner = Arc::new(MockSearchStore::new());
+        let store = ResilientSearchStore::new(inner.clone(), Arc::new(MockGraphStore::new()));
+        store.force_open();
+
+        store.index_code(&code_doc("src/a.rs")).await.unwrap();
+        store.index_code(&code_doc("src/b.rs")).await.unwrap();
+        store.delete_code("src/a.rs").await.unwrap();
+        assert!(inner.code_documents.read().await.is_empty());
+        assert_eq!(store.circuit_status().unwrap().queued_writes, 3);
+
+        store.probe().await;
+        let status = store.circuit_status().unwrap();
+        assert_eq!(status.state, CircuitState::Closed);
+        assert_eq!(status.queued_writes, 0);
+        assert!(status.open_since.is_none());
+        assert!(!status.is_degraded());
+        let paths: Vec<String> = inner
+            .code_documents
+            .read()
+            .await
+            .iter()
+            .map(|d| d.path.clone())
+            .collect();
+        assert_eq!(paths, vec!["src/b.rs"]);
+    }
+
+    #[tokio::test]
+    async fn test_code_search_falls_back_to_graph() {
+        let graph = MockGraphStore::new();
+        let project = test_project_named("fallback");
+        graph.create_project(&project).await.unwrap();
+        for (path, language, function) in [
+            ("/repo/src/config.rs", "rust", "load_config"),
+            ("/repo/src/server.rs", "rust", "start_server"),
+            ("/repo/web/config.ts", "typescript", "readConfig"),
+        ] {
+            graph.link_file_to_project(path, project.id).await.unwrap();
+            graph
+                .upsert_file(&FileNode {
+                    path: path.to_string(),
+                    language: language.to_string(),
+                    hash: "h".to_string(),
+                    line_count: 10,
+                    last_parsed: Utc::now(),
+                    project_id: Some(project.id),
+                    encoding: None,
+                })
+                .await
+                .unwrap();
+            graph
+                .upsert_function(&FunctionNode {
+                    name: function.to_string(),
+                    visibility: Visibility::Public,
+                    params: vec![],
+                    return_type: None,
+                    generics: vec![],
+                    is_async: false,
+                    is_unsafe: false,
+                    complexity: 1,
+                    file_path: path.to_string(),
+                    line_start: 1,
+                    line_end: 3,
+                    docstring: Some(format!("Docs of {}", function)),
+                })
+                .await
+                .unwrap();
+        }
+        let store = ResilientSearchStore::new(Arc::new(MockSearchStore::new()), Arc::new(graph));
+        store.force_open();
+
+        let hits = store
+            .search_code_with_scores("load config", 10, None, Some("fallback"), None)
+            .await
+            .unwrap();
+        let paths: Vec<&str> = hits.iter().map(|h| h.document.path.as_str()).collect();
+        assert_eq!(paths, vec!["/repo/src/config.rs", "/repo/web/config.ts"]);
+        assert_eq!(hits[0].score, 1.0);
+        assert_eq!(hits[1].score, 0.5);
+        assert_eq!(hits[0].document.symbols, vec!["load_config"]);
+        assert_eq!(hits[0].document.docstrings, "Docs of load_config");
+        assert_eq!(hits[0].document.project_slug, "fallback");
+        assert_eq!(
+            hits[0].document.id,
+            MeiliClient::path_to_id("/repo/src/config.rs")
+        );
+
+        let docs = store
+            .search_code("config", 10, Some("typescript"))
+            .await
+            .unwrap();
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].path, "/repo/web/config.ts");
+
+        assert!(store
+            .search_code_in_project("config", 10, None, Some("unknown"))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -1,8 +1,11 @@
 //! Trait abstraction for Meilisearch search operations
 
 use super::indexes::*;
+use super::resilient::SearchCircuitStatus;
+use super::write_behind::IndexQueueStatus;
 use anyhow::Result;
 use async_trait::async_trait;
+use std::time::Duration;
 
 /// Trait abstracting all Meilisearch search and indexing operations.
 ///
@@ -51,6 +54,9 @@ pub trait SearchStore: Send + Sync {
     /// Delete code document by path
     async fn delete_code(&self, path: &str) -> Result<()>;
 
+    /// List all code documents for a project
+    async fn list_code_for_project(&self, project_slug: &str) -> Result<Vec<CodeDocument>>;
+
     /// Delete all code documents for a project
     async fn delete_code_for_project(&self, project_slug: &str) -> Result<()>;
 
@@ -92,6 +98,10 @@ pub trait SearchStore: Send + Sync {
     /// Delete a decision document by ID
     async fn delete_decision(&self, id: &str) -> Result<()>;
 
+    /// List all decision documents for a project
+    async fn list_decisions_for_project(&self, project_slug: &str)
+        -> Result<Vec<DecisionDocument>>;
+
     /// Delete all decision documents for a project
     async fn delete_decisions_for_project(&self, project_slug: &str) -> Result<()>;
 
@@ -136,6 +146,9 @@ pub trait SearchStore: Send + Sync {
     /// Delete a note document by ID
     async fn delete_note(&self, id: &str) -> Result<()>;
 
+    /// List all notes for a project
+    async fn list_notes_for_project(&self, project_slug: &str) -> Result<Vec<NoteDocument>>;
+
     /// Delete all note documents for a project
     async fn delete_notes_for_project(&self, project_slug: &str) -> Result<()>;
 
@@ -145,6 +158,37 @@ pub trait SearchStore: Send + Sync {
     /// Get statistics for the notes index
     async fn get_notes_stats(&self) -> Result<IndexStats>;
 
+    // ========================================================================
+    // Documentation indexing
+    // ========================================================================
+
+    /// Index documentation sections (replaces documents with the same ID)
+    async fn index_doc_sections(&self, docs: &[DocSectionDocument]) -> Result<()>;
+
+    /// Search documentation sections with ranking scores
+    async fn search_docs_with_scores(
+        &self,
+        query: &str,
+        limit: usize,
+        project_slug: Option<&str>,
+    ) -> Result<Vec<SearchHit<DocSectionDocument>>>;
+
+    /// Delete the sections of a document
+    async fn delete_docs_for_path(&self, path: &str) -> Result<()>;
+
+    /// List all documentation sections for a project
+    async fn list_docs_for_project(&self, project_slug: &str) -> Result<Vec<DocSectionDocument>>;
+
+    /// Delete all documentation sections for a project
+    async fn delete_docs_for_project(&self, project_slug: &str) -> Result<()>;
+
+    // ========================================================================
+    // Settings
+    // ========================================================================
+
+    /// Apply synonyms, stop words and ranking rules to every index.
+    async fn apply_search_settings(&self, settings: &SearchSettings) -> Result<()>;
+
     // ========================================================================
     // Health check
     // ========================================================================
@@ -152,4 +196,27 @@ pub trait SearchStore: Send + Sync {
     /// Check connectivity to the search engine.
     /// Returns Ok(true) if the engine is reachable, Ok(false) if not.
     async fn health_check(&self) -> Result<bool>;
+
+    /// Circuit breaker status, for stores wrapped in a
+    /// [`ResilientSearchStore`](super::ResilientSearchStore)
+    fn circuit_status(&self) -> Option<SearchCircuitStatus> {
+        None
+    }
+
+    // ========================================================================
+    // Write-behind indexing
+    // ========================================================================
+
+    /// Queue status, for stores wrapped in a
+    /// [`WriteBehindStore`](super::WriteBehindStore)
+    fn index_queue_status(&self) -> Option<IndexQueueStatus> {
+        None
+    }
+
+    /// Wait (at most `timeout`) until queued writes reached the search engine.
+    /// Returns the number still pending; stores that write synchronously
+    /// return 0 at once.
+    async fn flush_pending(&self, _timeout: Duration) -> Result<usize> {
+        Ok(0)
+    }
 }
@@ -0,0 +1,747 @@
+//! Write-behind indexing of code documents.
+//!
+//! [`WriteBehindStore`] records code index and delete operations in a local
+//! redb file ([`IndexQueue`]) and returns as soon as they are on disk. A
+//! background indexer replays them into Meilisearch in batches, retrying with
+//! exponential backoff, so a sync neither waits on the search engine nor fails
+//! because of it, and operations queued before a crash or a restart are
+//! indexed once the queue is reopened.
+//!
+//! Pending operations are keyed by file path: re-indexing or deleting a file
+//! before the indexer got to it replaces the earlier operation. Searches see
+//! a document once the indexer has sent it.
+
+use super::indexes::*;
+use super::resilient::{is_outage, SearchCircuitStatus};
+use super::traits::SearchStore;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use redb::{Database, ReadableDatabase, ReadableTable, ReadableTableMetadata, TableDefinition};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// Pending operations by file path (JSON-encoded [`PendingCode`])
+const PENDING_CODE: TableDefinition<&str, &[u8]> = TableDefinition::new("pending_code");
+
+/// Operations sent to Meilisearch per indexer round
+pub const INDEX_BATCH_SIZE: usize = 200;
+
+/// First retry delay after a failed round, doubled up to [`MAX_RETRY_DELAY`]
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(1);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// Pause between rounds of [`SearchStore::flush_pending`] while Meilisearch fails
+const FLUSH_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Serialize, Deserialize)]
+enum PendingCode {
+    Index(CodeDocument),
+    Delete,
+}
+
+/// Durable queue of code index operations, backed by a redb file.
+///
+/// redb locks the file, so a second process opening the same queue gets an
+/// error (and should index synchronously instead).
+pub struct IndexQueue {
+    db: Database,
+    path: PathBuf,
+}
+
+impl IndexQueue {
+    /// Open (or create) the queue file, creating its directory if needed
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let db = Database::create(path)
+            .with_context(|| format!("Failed to open index queue {}", path.display()))?;
+        let txn = db.begin_write()?;
+        txn.open_table(PENDING_CODE)?;
+        txn.commit()?;
+        Ok(Self {
+            db,
+            path: path.to_path_buf(),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of pending operations
+    pub fn len(&self) -> Result<usize> {
+        let txn = self.db.begin_read()?;
+        Ok(txn.open_table(PENDING_CODE)?.len()? as usize)
+    }
+
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    fn put(&self, ops: &[(String, PendingCode)]) -> Result<()> {
+        let txn = self.db.begin_write()?;
+        {
+            let mut table = txn.open_table(PENDING_CODE)?;
+            for (path, op) in ops {
+                table.insert(path.as_str(), serde_json::to_vec(op)?.as_slice())?;
+            }
+        }
+        txn.commit()?;
+        Ok(())
+    }
+
+    /// Up to `limit` pending operations, as stored
+    fn peek(&self, limit: usize) -> Result<Vec<(String, Vec<u8>)>> {
+        let txn = self.db.begin_read()?;
+        let table = txn.open_table(PENDING_CODE)?;
+        let mut ops = Vec::new();
+        for entry in table.iter()?.take(limit) {
+            let (path, op) = entry?;
+            ops.push((path.value().to_string(), op.value().to_vec()));
+        }
+        Ok(ops)
+    }
+
+    /// Remove the `done` operations that were not replaced since they were read
+    fn ack(&self, done: &[(String, Vec<u8>)]) -> Result<()> {
+        let txn = self.db.begin_write()?;
+        {
+            let mut table = txn.open_table(PENDING_CODE)?;
+            for (path, op) in done {
+                let unchanged = table
+                    .get(path.as_str())?
+                    .is_some_and(|current| current.value() == op.as_slice());
+                if unchanged {
+                    table.remove(path.as_str())?;
+                }
+            }
+        }
+        txn.commit()?;
+        Ok(())
+    }
+
+    /// Drop the pending index operations of a project
+    fn discard_project(&self, project_slug: &str) -> Result<()> {
+        let txn = self.db.begin_write()?;
+        {
+            let mut table = txn.open_table(PENDING_CODE)?;
+            let mut doomed = Vec::new();
+            for entry in table.iter()? {
+                let (path, op) = entry?;
+                if let Ok(PendingCode::Index(doc)) =
+                    serde_json::from_slice::<PendingCode>(op.value())
+                {
+                    if doc.project_slug == project_slug {
+                        doomed.push(path.value().to_string());
+                    }
+                }
+            }
+            for path in doomed {
+                table.remove(path.as_str())?;
+            }
+        }
+        txn.commit()?;
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<()> {
+        let txn = self.db.begin_write()?;
+        txn.delete_table(PENDING_CODE)?;
+        txn.open_table(PENDING_CODE)?;
+        txn.commit()?;
+        Ok(())
+    }
+}
+
+/// Queue status reported by `GET /api/admin/metrics`
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexQueueStatus {
+    pub path: String,
+    /// Operations waiting to be sent to Meilisearch
+    pub pending: usize,
+    /// Operations sent since startup
+    pub indexed: u64,
+    /// Operations Meilisearch rejected (logged and dropped) since startup
+    pub rejected: u64,
+    /// Failed indexer rounds since startup
+    pub failed_rounds: u64,
+    pub last_error: Option<String>,
+}
+
+/// State shared with the indexer task
+struct Shared {
+    queue: Arc<IndexQueue>,
+    /// Where queued operations are replayed
+    sink: Arc<dyn SearchStore>,
+    /// Held while operations reach Meilisearch, so project-wide deletes
+    /// cannot be overtaken by a batch already in flight
+    sending: tokio::sync::Mutex<()>,
+    wakeup: Notify,
+    indexed: AtomicU64,
+    rejected: AtomicU64,
+    failed_rounds: AtomicU64,
+    last_error: Mutex<Option<String>>,
+}
+
+impl Shared {
+    async fn on_queue<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&IndexQueue) -> Result<T> + Send + 'static,
+    ) -> Result<T> {
+        let queue = self.queue.clone();
+        tokio::task::spawn_blocking(move || f(&queue)).await?
+    }
+
+    /// Send one batch of pending operations. Returns how many were sent
+    /// (0 when the queue is empty); fails when Meilisearch is unreachable.
+    async fn index_batch(&self) -> Result<usize> {
+        let _sending = self.sending.lock().await;
+        let batch = self.on_queue(|q| q.peek(INDEX_BATCH_SIZE)).await?;
+        if batch.is_empty() {
+            return Ok(0);
+        }
+
+        let mut docs = Vec::new();
+        let mut deletes = Vec::new();
+        for (path, op) in &batch {
+            match serde_json::from_slice::<PendingCode>(op) {
+                Ok(PendingCode::Index(doc)) => docs.push(doc),
+                Ok(PendingCode::Delete) => deletes.push(path.as_str()),
+                Err(e) => {
+                    tracing::warn!("Discarding unreadable queued operation for {}: {}", path, e)
+                }
+            }
+        }
+        if !docs.is_empty() {
+            if let Err(e) = self.sink.index_code_batch(&docs).await {
+                if is_outage(&e) || docs.len() == 1 {
+                    self.reject_or_fail(e, docs.len())?;
+                } else {
+                    // Find the documents Meilisearch refuses, keep the others
+                    for doc in &docs {
+                        if let Err(e) = self.sink.index_code(doc).await {
+                            self.reject_or_fail(e, 1)?;
+                        }
+                    }
+                }
+            }
+        }
+        for path in deletes {
+            if let Err(e) = self.sink.delete_code(path).await {
+                self.reject_or_fail(e, 1)?;
+            }
+        }
+
+        let sent = batch.len();
+        self.on_queue(move |q| q.ack(&batch)).await?;
+        self.indexed.fetch_add(sent as u64, Ordering::Relaxed);
+        Ok(sent)
+    }
+
+    /// Propagate an outage (the round is retried); log and count anything
+    /// else as `count` rejected operations.
+    fn reject_or_fail(&self, err: anyhow::Error, count: usize) -> Result<()> {
+        if is_outage(&err) {
+            return Err(err);
+        }
+        tracing::warn!("Meilisearch rejected a queued code document: {:#}", err);
+        self.rejected.fetch_add(count as u64, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn fail_round(&self, err: &anyhow::Error) {
+        self.failed_rounds.fetch_add(1, Ordering::Relaxed);
+        *self.last_error.lock().unwrap() = Some(format!("{:#}", err));
+    }
+
+    /// Background indexer: drain the queue, back off while Meilisearch fails
+    async fn run(self: Arc<Self>) {
+        let mut delay = INITIAL_RETRY_DELAY;
+        loop {
+            match self.index_batch().await {
+                Ok(0) => self.wakeup.notified().await,
+                Ok(_) => delay = INITIAL_RETRY_DELAY,
+                Err(e) => {
+                    tracing::warn!(
+                        "Code indexing failed, retrying in {}s: {:#}",
+                        delay.as_secs(),
+                        e
+                    );
+                    self.fail_round(&e);
+                    tokio::time::sleep(delay).await;
+                    delay = (delay * 2).min(MAX_RETRY_DELAY);
+                }
+            }
+        }
+    }
+}
+
+/// [`SearchStore`] whose code index and delete operations go through a
+/// durable [`IndexQueue`] (see the module documentation).
+pub struct WriteBehindStore {
+    inner: Arc<dyn SearchStore>,
+    shared: Arc<Shared>,
+}
+
+impl WriteBehindStore {
+    /// Queue code writes in `queue` and replay them into `sink`; everything
+    /// else goes to `inner`. Starts the background indexer.
+    ///
+    /// `sink` is the Meilisearch client itself rather than `inner`: a store
+    /// that buffers writes in memory (like the circuit breaker) would lose
+    /// what the queue acknowledged.
+    pub fn new(inner: Arc<dyn SearchStore>, sink: Arc<dyn SearchStore>, queue: IndexQueue) -> Self {
+        let shared = Arc::new(Shared {
+            queue: Arc::new(queue),
+            sink,
+            sending: tokio::sync::Mutex::new(()),
+            wakeup: Notify::new(),
+            indexed: AtomicU64::new(0),
+            rejected: AtomicU64::new(0),
+            failed_rounds: AtomicU64::new(0),
+            last_error: Mutex::new(None),
+        });
+        match shared.queue.len() {
+            Ok(0) => {}
+            Ok(pending) => {
+                tracing::info!("Resuming indexing of {} queued code operations", pending)
+            }
+            Err(e) => tracing::warn!("Failed to read the index queue: {:#}", e),
+        }
+        tokio::spawn(shared.clone().run());
+        Self { inner, shared }
+    }
+
+    async fn enqueue(&self, ops: Vec<(String, PendingCode)>) -> Result<()> {
+        self.shared.on_queue(move |q| q.put(&ops)).await?;
+        self.shared.wakeup.notify_one();
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SearchStore for WriteBehindStore {
+    // ========================================================================
+    // Code indexing
+    // ========================================================================
+
+    async fn index_code(&self, doc: &CodeDocument) -> Result<()> {
+        self.enqueue(vec![(doc.path.clone(), PendingCode::Index(doc.clone()))])
+            .await
+    }
+
+    async fn index_code_batch(&self, docs: &[CodeDocument]) -> Result<()> {
+        self.enqueue(
+            docs.iter()
+                .map(|doc| (doc.path.clone(), PendingCode::Index(doc.clone())))
+                .collect(),
+        )
+        .await
+    }
+
+    async fn search_code(
+        &self,
+        query: &str,
+        limit: usize,
+        language_filter: Option<&str>,
+    ) -> Result<Vec<CodeDocument>> {
+        self.inner.search_code(query, limit, language_filter).await
+    }
+
+    async fn search_code_in_project(
+        &self,
+        query: &str,
+        limit: usize,
+        language_filter: Option<&str>,
+        project_slug: Option<&str>,
+    ) -> Result<Vec<CodeDocument>> {
+        self.inner
+            .search_code_in_project(query, limit, language_filter, project_slug)
+            .await
+    }
+
+    async fn search_code_with_scores(
+        &self,
+        query: &str,
+        limit: usize,
+        language_filter: Option<&str>,
+        project_slug: Option<&str>,
+        path_prefix: Option<&str>,
+    ) -> Result<Vec<SearchHit<CodeDocument>>> {
+        self.inner
+            .search_code_with_scores(query, limit, language_filter, project_slug, path_prefix)
+            .await
+    }
+
+    async fn delete_code(&self, path: &str) -> Result<()> {
+        self.enqueue(vec![(path.to_string(), PendingCode::Delete)])
+            .await
+    }
+
+    async fn list_code_for_project(&self, project_slug: &str) -> Result<Vec<CodeDocument>> {
+        self.inner.list_code_for_project(project_slug).await
+    }
+
+    async fn delete_code_for_project(&self, project_slug: &str) -> Result<()> {
+        let _sending = self.shared.sending.lock().await;
+        let slug = project_slug.to_string();
+        self.shared
+            .on_queue(move |q| q.discard_project(&slug))
+            .await?;
+        self.inner.delete_code_for_project(project_slug).await
+    }
+
+    async fn delete_orphan_code_documents(&self) -> Result<()> {
+        self.inner.delete_orphan_code_documents().await
+    }
+
+    async fn delete_all_code(&self) -> Result<()> {
+        let _sending = self.shared.sending.lock().await;
+        self.shared.on_queue(|q| q.clear()).await?;
+        self.inner.delete_all_code().await
+    }
+
+    async fn get_code_stats(&self) -> Result<IndexStats> {
+        self.inner.get_code_stats().await
+    }
+
+    // ========================================================================
+    // Decision indexing
+    // ========================================================================
+
+    async fn index_decision(&self, doc: &DecisionDocument) -> Result<()> {
+        self.inner.index_decision(doc).await
+    }
+
+    async fn search_decisions(&self, query: &str, limit: usize) -> Result<Vec<DecisionDocument>> {
+        self.inner.search_decisions(query, limit).await
+    }
+
+    async fn search_decisions_in_project(
+        &self,
+        query: &str,
+        limit: usize,
+        project_slug: Option<&str>,
+    ) -> Result<Vec<DecisionDocument>> {
+        self.inner
+            .search_decisions_in_project(query, limit, project_slug)
+            .await
+    }
+
+    async fn search_decisions_in_projects(
+        &self,
+        query: &str,
+        limit: usize,
+        project_slugs: &[String],
+    ) -> Result<Vec<DecisionDocument>> {
+        self.inner
+            .search_decisions_in_projects(query, limit, project_slugs)
+            .await
+    }
+
+    async fn delete_decision(&self, id: &str) -> Result<()> {
+        self.inner.delete_decision(id).await
+    }
+
+    async fn list_decisions_for_project(
+        &self,
+        project_slug: &str,
+    ) -> Result<Vec<DecisionDocument>> {
+        self.inner.list_decisions_for_project(project_slug).await
+    }
+
+    async fn delete_decisions_for_project(&self, project_slug: &str) -> Result<()> {
+        self.inner.delete_decisions_for_project(project_slug).await
+    }
+
+    async fn delete_decisions_for_task(&self, task_id: &str) -> Result<()> {
+        self.inner.delete_decisions_for_task(task_id).await
+    }
+
+    // ========================================================================
+    // Note indexing
+    // ========================================================================
+
+    async fn index_note(&self, doc: &NoteDocument) -> Result<()> {
+        self.inner.index_note(doc).await
+    }
+
+    async fn index_notes_batch(&self, docs: &[NoteDocument]) -> Result<()> {
+        self.inner.index_notes_batch(docs).await
+    }
+
+    async fn search_notes(&self, query: &str, limit: usize) -> Result<Vec<NoteDocument>> {
+        self.inner.search_notes(query, limit).await
+    }
+
+    async fn search_notes_with_filters(
+        &self,
+        query: &str,
+        limit: usize,
+        project_slug: Option<&str>,
+        note_type: Option<&str>,
+        status: Option<&str>,
+        importance: Option<&str>,
+    ) -> Result<Vec<NoteDocument>> {
+        self.inner
+            .search_notes_with_filters(query, limit, project_slug, note_type, status, importance)
+            .await
+    }
+
+    async fn search_notes_with_scores(
+        &self,
+        query: &str,
+        limit: usize,
+        project_slug: Option<&str>,
+        note_type: Option<&str>,
+        status: Option<&str>,
+        importance: Option<&str>,
+    ) -> Result<Vec<SearchHit<NoteDocument>>> {
+        self.inner
+            .search_notes_with_scores(query, limit, project_slug, note_type, status, importance)
+            .await
+    }
+
+    async fn delete_note(&self, id: &str) -> Result<()> {
+        self.inner.delete_note(id).await
+    }
+
+    async fn list_notes_for_project(&self, project_slug: &str) -> Result<Vec<NoteDocument>> {
+        self.inner.list_notes_for_project(project_slug).await
+    }
+
+    async fn delete_notes_for_project(&self, project_slug: &str) -> Result<()> {
+        self.inner.delete_notes_for_project(project_slug).await
+    }
+
+    async fn update_note_status(&self, id: &str, status: &str) -> Result<()> {
+        self.inner.update_note_status(id, status).await
+    }
+
+    async fn get_notes_stats(&self) -> Result<IndexStats> {
+        self.inner.get_notes_stats().await
+    }
+
+    // ========================================================================
+    // Documentation indexing
+    // ========================================================================
+
+    async fn index_doc_sections(&self, docs: &[DocSectionDocument]) -> Result<()> {
+        self.inner.index_doc_sections(docs).await
+    }
+
+    async fn search_docs_with_scores(
+        &self,
+        query: &str,
+        limit: usize,
+        project_slug: Option<&str>,
+    ) -> Result<Vec<SearchHit<DocSectionDocument>>> {
+        self.inner
+            .search_docs_with_scores(query, limit, project_slug)
+            .await
+    }
+
+    async fn delete_docs_for_path(&self, path: &str) -> Result<()> {
+        self.inner.delete_docs_for_path(path).await
+    }
+
+    async fn list_docs_for_project(&self, project_slug: &str) -> Result<Vec<DocSectionDocument>> {
+        self.inner.list_docs_for_project(project_slug).await
+    }
+
+    async fn delete_docs_for_project(&self, project_slug: &str) -> Result<()> {
+        self.inner.delete_docs_for_project(project_slug).await
+    }
+
+    // ========================================================================
+    // Settings
+    // ========================================================================
+
+    async fn apply_search_settings(&self, settings: &SearchSettings) -> Result<()> {
+        self.inner.apply_search_settings(settings).await
+    }
+
+    // ========================================================================
+    // Health check
+    // ========================================================================
+
+    async fn health_check(&self) -> Result<bool> {
+        self.inner.health_check().await
+    }
+
+    fn circuit_status(&self) -> Option<SearchCircuitStatus> {
+        self.inner.circuit_status()
+    }
+
+    fn index_queue_status(&self) -> Option<IndexQueueStatus> {
+        let shared = &self.shared;
+        Some(IndexQueueStatus {
+            path: shared.queue.path().display().to_string(),
+            pending: shared.queue.len().unwrap_or(0),
+            indexed: shared.indexed.load(Ordering::Relaxed),
+            rejected: shared.rejected.load(Ordering::Relaxed),
+            failed_rounds: shared.failed_rounds.load(Ordering::Relaxed),
+            last_error: shared.last_error.lock().unwrap().clone(),
+        })
+    }
+
+    async fn flush_pending(&self, timeout: Duration) -> Result<usize> {
+        let drain = async {
+            loop {
+                match self.shared.index_batch().await {
+                    Ok(0) => return,
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.shared.fail_round(&e);
+                        tokio::time::sleep(FLUSH_RETRY_DELAY).await;
+                    }
+                }
+            }
+        };
+        let _ = tokio::time::timeout(timeout, drain).await;
+        self.shared.on_queue(|q| q.len()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meilisearch::mock::MockSearchStore;
+
+    fn code_doc(path: &str, slug: &str) -> CodeDocument {
+        CodeDocument {
+            id: crate::meilisearch::MeiliClient::path_to_id(path),
+            path: path.to_string(),
+            language: "rust".to_string(),
+            symbols: vec![],
+            docstrings: String::new(),
+            signatures: vec![],
+            imports: vec![],
+            project_id: uuid::Uuid::new_v4().to_string(),
+            project_slug: slug.to_string(),
+        }
+    }
+
+    async fn indexed_paths(store: &MockSearchStore) -> Vec<String> {
+        let mut paths: Vec<String> = store
+            .code_documents
+            .read()
+            .await
+            .iter()
+            .map(|d| d.path.clone())
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn test_queue_replaces_pending_operations() {
+        let dir = tempfile::tempdir().unwrap();
+        let queue = IndexQueue::open(&dir.path().join("queue/index.redb")).unwrap();
+        queue
+            .put(&[
+                (
+                    "a.rs".to_string(),
+                    PendingCode::Index(code_doc("a.rs", "p")),
+                ),
+                (
+                    "b.rs".to_string(),
+                    PendingCode::Index(code_doc("b.rs", "q")),
+                ),
+            ])
+            .unwrap();
+        let read = queue.peek(10).unwrap();
+        assert_eq!(read.len(), 2);
+
+        // a.rs is deleted after being read: acking the read leaves the delete
+        queue
+            .put(&[("a.rs".to_string(), PendingCode::Delete)])
+            .unwrap();
+        queue.ack(&read).unwrap();
+        let left = queue.peek(10).unwrap();
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].0, "a.rs");
+        assert!(matches!(
+            serde_json::from_slice::<PendingCode>(&left[0].1).unwrap(),
+            PendingCode::Delete
+        ));
+
+        queue
+            .put(&[(
+                "c.rs".to_string(),
+                PendingCode::Index(code_doc("c.rs", "q")),
+            )])
+            .unwrap();
+        queue.discard_project("q").unwrap();
+        assert_eq!(queue.len().unwrap(), 1);
+        queue.clear().unwrap();
+        assert!(queue.is_empty().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_queued_writes_survive_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.redb");
+        {
+            let queue = IndexQueue::open(&path).unwrap();
+            queue
+                .put(&[(
+                    "a.rs".to_string(),
+                    PendingCode::Index(code_doc("a.rs", "p")),
+                )])
+                .unwrap();
+        }
+
+        let sink = Arc::new(MockSearchStore::new());
+        let store = WriteBehindStore::new(
+            Arc::new(MockSearchStore::new()),
+            sink.clone(),
+            IndexQueue::open(&path).unwrap(),
+        );
+        store.index_code(&code_doc("b.rs", "p")).await.unwrap();
+        assert_eq!(
+            store.flush_pending(Duration::from_secs(5)).await.unwrap(),
+            0
+        );
+        assert_eq!(indexed_paths(&sink).await, vec!["a.rs", "b.rs"]);
+
+        let status = store.index_queue_status().unwrap();
+        assert_eq!(status.pending, 0);
+        assert_eq!(status.indexed, 2);
+        assert!(status.last_error.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_deletes_go_through_the_queue() {
+        let dir = tempfile::tempdir().unwrap();
+        let meili = Arc::new(MockSearchStore::new());
+        let store = WriteBehindStore::new(
+            meili.clone(),
+            meili.clone(),
+            IndexQueue::open(&dir.path().join("index.redb")).unwrap(),
+        );
+        store
+            .index_code_batch(&[code_doc("a.rs", "p"), code_doc("b.rs", "q")])
+            .await
+            .unwrap();
+        store.delete_code("a.rs").await.unwrap();
+        store.flush_pending(Duration::from_secs(5)).await.unwrap();
+        assert_eq!(indexed_paths(&meili).await, vec!["b.rs"]);
+
+        // A project delete also drops the project's pending documents
+        store.index_code(&code_doc("c.rs", "q")).await.unwrap();
+        store.index_code(&code_doc("d.rs", "r")).await.unwrap();
+        store.delete_code_for_project("q").await.unwrap();
+        assert_eq!(
+            store.flush_pending(Duration::from_secs(5)).await.unwrap(),
+            0
+        );
+        assert_eq!(indexed_paths(&meili).await, vec!["d.rs"]);
+    }
+}
@@ -923,15 +923,22 @@ impl Neo4jClient {
     }
 
     /// Get all CALLS edges between functions in a project as (caller_id, callee_id) pairs.
-    /// Scoped to the same project (no cross-project calls).
-    pub async fn get_project_call_edges(&self, project_id: Uuid) -> Result<Vec<(String, String)>> {
+    /// Scoped to the same project (no cross-project calls), skipping edges whose
+    /// confidence is below `min_confidence`.
+    pub async fn get_project_call_edges(
+        &self,
+        project_id: Uuid,
+        min_confidence: f64,
+    ) -> Result<Vec<(String, String)>> {
         let q = query(
             r#"
-            MATCH (p:Project {id: $project_id})-[:CONTAINS]->(:File)-[:CONTAINS]->(f1:Function)-[:CALLS]->(f2:Function)<-[:CONTAINS]-(:File)<-[:CONTAINS]-(p)
+            MATCH (p:Project {id: $project_id})-[:CONTAINS]->(:File)-[:CONTAINS]->(f1:Function)-[r:CALLS]->(f2:Function)<-[:CONTAINS]-(:File)<-[:CONTAINS]-(p)
+            WHERE coalesce(r.confidence, 0.50) >= $min_confidence
             RETURN f1.id AS source, f2.id AS target
             "#,
         )
-        .param("project_id", project_id.to_string());
+        .param("project_id", project_id.to_string())
+        .param("min_confidence", min_confidence);
 
         let mut result = self.graph.execute(q).await?;
         let mut edges = Vec::new();
@@ -947,6 +954,42 @@ impl Neo4jClient {
         Ok(edges)
     }
 
+    /// Get every function of a project with its PageRank and Louvain community.
+    /// Analytics fields are None for functions the last analytics run did not score.
+    pub async fn get_project_call_graph_functions(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<CallGraphFunctionNode>> {
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $project_id})-[:CONTAINS]->(f:File)-[:CONTAINS]->(func:Function)
+            RETURN func.id AS id, func.name AS name, f.path AS file_path,
+                   func.line_start AS line_start, func.pagerank AS pagerank,
+                   func.community_id AS community_id
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut functions = Vec::new();
+
+        while let Some(row) = result.next().await? {
+            let (Ok(id), Ok(name)) = (row.get::<String>("id"), row.get::<String>("name")) else {
+                continue;
+            };
+            functions.push(CallGraphFunctionNode {
+                id,
+                name,
+                file_path: row.get("file_path").unwrap_or_default(),
+                line_start: row.get::<i64>("line_start").unwrap_or(0) as u32,
+                pagerank: row.get::<f64>("pagerank").ok(),
+                community_id: row.get::<i64>("community_id").ok(),
+            });
+        }
+
+        Ok(functions)
+    }
+
     /// Get all EXTENDS edges between structs/classes in a project as (child_file, parent_file) pairs.
     /// Returns file-level edges so the graph analytics engine can weight inter-file coupling.
     pub async fn get_project_extends_edges(
@@ -0,0 +1,332 @@
+//! Neo4j public API surface operations (public symbols and their snapshots)
+
+use super::client::Neo4jClient;
+use super::code::json_list;
+use super::models::*;
+use anyhow::{Context, Result};
+use neo4rs::query;
+use uuid::Uuid;
+
+/// `[async ]fn name<G>(a: A, b: B) -> R`
+pub(super) fn function_signature(
+    name: &str,
+    generics: &[String],
+    params: &[Parameter],
+    return_type: Option<&str>,
+    is_async: bool,
+) -> String {
+    let params: Vec<String> = params
+        .iter()
+        .map(|p| match &p.type_name {
+            Some(type_name) => format!("{}: {}", p.name, type_name),
+            None => p.name.clone(),
+        })
+        .collect();
+    let mut signature = format!(
+        "{}fn {}{}({})",
+        if is_async { "async " } else { "" },
+        name,
+        generic_list(generics),
+        params.join(", ")
+    );
+    if let Some(return_type) = return_type.filter(|r| !r.is_empty()) {
+        signature.push_str(" -> ");
+        signature.push_str(return_type);
+    }
+    signature
+}
+
+/// `struct Name<G> { a: A, .. }`, with the public fields only
+pub(super) fn struct_signature(name: &str, generics: &[String], fields: &[FieldNode]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .filter(|f| f.visibility == Visibility::Public)
+        .map(|f| format!("{}: {}", f.name, f.type_name))
+        .collect();
+    let mut signature = format!("struct {}{}", name, generic_list(generics));
+    if !fields.is_empty() {
+        signature.push_str(&format!(" {{ {} }}", fields.join(", ")));
+    }
+    signature
+}
+
+/// `enum Name { A, B(T), C { x: X } }`
+pub(super) fn enum_signature(name: &str, variants: &[String], payloads: &[VariantNode]) -> String {
+    let variants: Vec<String> = variants
+        .iter()
+        .map(
+            |variant| match payloads.iter().find(|p| &p.name == variant) {
+                Some(payload)
+                    if payload
+                        .fields
+                        .iter()
+                        .all(|f| f.name.parse::<usize>().is_ok()) =>
+                {
+                    let types: Vec<&str> = payload
+                        .fields
+                        .iter()
+                        .map(|f| f.type_name.as_str())
+                        .collect();
+                    format!("{}({})", variant, types.join(", "))
+                }
+                Some(payload) => {
+                    let fields: Vec<String> = payload
+                        .fields
+                        .iter()
+                        .map(|f| format!("{}: {}", f.name, f.type_name))
+                        .collect();
+                    format!("{} {{ {} }}", variant, fields.join(", "))
+                }
+                None => variant.clone(),
+            },
+        )
+        .collect();
+    format!("enum {} {{ {} }}", name, variants.join(", "))
+}
+
+/// `trait Name<G>`
+pub(super) fn trait_signature(name: &str, generics: &[String]) -> String {
+    format!("trait {}{}", name, generic_list(generics))
+}
+
+fn generic_list(generics: &[String]) -> String {
+    if generics.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", generics.join(", "))
+    }
+}
+
+impl Neo4jClient {
+    /// Public functions, structs, traits and enums of a project, by file and
+    /// line.
+    pub async fn get_project_public_symbols_impl(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<PublicSymbolRow>> {
+        let q = query(
+            r#"
+            MATCH (:Project {id: $project_id})-[:CONTAINS]->(f:File)-[:CONTAINS]->(s)
+            WHERE (s:Function OR s:Struct OR s:Trait OR s:Enum) AND s.visibility = 'Public'
+            RETURN s, labels(s) AS labels, f.path AS file_path
+            ORDER BY f.path, s.line_start
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut symbols = Vec::new();
+        while let Some(row) = result.next().await? {
+            let node: neo4rs::Node = row.get("s")?;
+            let labels: Vec<String> = row.get("labels").unwrap_or_default();
+            let name: String = node.get("name")?;
+            let generics: Vec<String> = node.get("generics").unwrap_or_default();
+            let (kind, signature) = if labels.iter().any(|l| l == "Function") {
+                let return_type: String = node.get("return_type").unwrap_or_default();
+                let signature = function_signature(
+                    &name,
+                    &generics,
+                    &json_list::<Parameter>(&node, "params"),
+                    Some(&return_type),
+                    node.get("is_async").unwrap_or(false),
+                );
+                ("function", signature)
+            } else if labels.iter().any(|l| l == "Struct") {
+                let fields = json_list::<FieldNode>(&node, "fields");
+                ("struct", struct_signature(&name, &generics, &fields))
+            } else if labels.iter().any(|l| l == "Enum") {
+                let variants: Vec<String> = node.get("variants").unwrap_or_default();
+                let payloads = json_list::<VariantNode>(&node, "variant_payloads");
+                ("enum", enum_signature(&name, &variants, &payloads))
+            } else {
+                ("trait", trait_signature(&name, &generics))
+            };
+            symbols.push(PublicSymbolRow {
+                file_path: row.get("file_path")?,
+                name,
+                kind: kind.to_string(),
+                signature,
+            });
+        }
+        Ok(symbols)
+    }
+
+    /// Record an API surface snapshot, keeping the `keep` latest of the
+    /// project.
+    pub async fn create_api_surface_snapshot_impl(
+        &self,
+        snapshot: &ApiSurfaceSnapshotNode,
+        keep: usize,
+    ) -> Result<()> {
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $project_id})
+            CREATE (s:ApiSurfaceSnapshot {
+                id: $id,
+                project_id: $project_id,
+                commit: $commit,
+                hash: $hash,
+                symbol_count: $symbol_count,
+                symbols: $symbols,
+                created_at: datetime($created_at)
+            })
+            CREATE (p)-[:HAS_API_SNAPSHOT]->(s)
+            WITH p
+            MATCH (p)-[:HAS_API_SNAPSHOT]->(old:ApiSurfaceSnapshot)
+            WITH old ORDER BY old.created_at DESC
+            SKIP $keep
+            DETACH DELETE old
+            "#,
+        )
+        .param("id", snapshot.id.to_string())
+        .param("project_id", snapshot.project_id.to_string())
+        .param("commit", snapshot.commit.clone().unwrap_or_default())
+        .param("hash", snapshot.hash.clone())
+        .param("symbol_count", snapshot.symbol_count as i64)
+        .param("symbols", serde_json::to_string(&snapshot.symbols)?)
+        .param("created_at", snapshot.created_at.to_rfc3339())
+        .param("keep", keep as i64);
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
+    /// API surface snapshots of a project, newest first, without their
+    /// symbols.
+    pub async fn list_api_surface_snapshots_impl(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<ApiSurfaceSnapshotNode>> {
+        let q = query(
+            r#"
+            MATCH (s:ApiSurfaceSnapshot {project_id: $project_id})
+            RETURN s.id AS id, s.commit AS commit, s.hash AS hash,
+                   s.symbol_count AS symbol_count, toString(s.created_at) AS created_at
+            ORDER BY s.created_at DESC
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut snapshots = Vec::new();
+        while let Some(row) = result.next().await? {
+            snapshots.push(snapshot_from_row(&row, project_id, vec![])?);
+        }
+        Ok(snapshots)
+    }
+
+    /// One API surface snapshot of a project, with its symbols.
+    pub async fn get_api_surface_snapshot_impl(
+        &self,
+        project_id: Uuid,
+        snapshot_id: Uuid,
+    ) -> Result<Option<ApiSurfaceSnapshotNode>> {
+        let q = query(
+            r#"
+            MATCH (s:ApiSurfaceSnapshot {project_id: $project_id, id: $id})
+            RETURN s.id AS id, s.commit AS commit, s.hash AS hash,
+                   s.symbol_count AS symbol_count, toString(s.created_at) AS created_at,
+                   s.symbols AS symbols
+            "#,
+        )
+        .param("project_id", project_id.to_string())
+        .param("id", snapshot_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let Some(row) = result.next().await? else {
+            return Ok(None);
+        };
+        let symbols: String = row.get("symbols").unwrap_or_default();
+        let symbols = serde_json::from_str(&symbols)
+            .context("Failed to deserialize API surface snapshot symbols")?;
+        Ok(Some(snapshot_from_row(&row, project_id, symbols)?))
+    }
+}
+
+fn snapshot_from_row(
+    row: &neo4rs::Row,
+    project_id: Uuid,
+    symbols: Vec<ApiSymbol>,
+) -> Result<ApiSurfaceSnapshotNode> {
+    let id: String = row.get("id")?;
+    let commit: String = row.get("commit").unwrap_or_default();
+    Ok(ApiSurfaceSnapshotNode {
+        id: id.parse()?,
+        project_id,
+        commit: (!commit.is_empty()).then_some(commit),
+        hash: row.get("hash").unwrap_or_default(),
+        symbol_count: row.get::<i64>("symbol_count").unwrap_or(0) as usize,
+        symbols,
+        created_at: row
+            .get::<String>("created_at")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(chrono::Utc::now),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str, type_name: &str, visibility: Visibility) -> FieldNode {
+        FieldNode {
+            name: name.to_string(),
+            type_name: type_name.to_string(),
+            visibility,
+            default_value: None,
+        }
+    }
+
+    #[test]
+    fn test_signatures() {
+        let params = vec![
+            Parameter {
+                name: "path".to_string(),
+                type_name: Some("&Path".to_string()),
+            },
+            Parameter {
+                name: "_".to_string(),
+                type_name: None,
+            },
+        ];
+        assert_eq!(
+            function_signature("load", &["T".to_string()], &params, Some("Result<T>"), true),
+            "async fn load<T>(path: &Path, _) -> Result<T>"
+        );
+        assert_eq!(
+            function_signature("run", &[], &[], Some(""), false),
+            "fn run()"
+        );
+
+        let fields = vec![
+            field("port", "u16", Visibility::Public),
+            field("secret", "String", Visibility::Private),
+        ];
+        assert_eq!(
+            struct_signature("Config", &[], &fields),
+            "struct Config { port: u16 }"
+        );
+        assert_eq!(struct_signature("Marker", &[], &[]), "struct Marker");
+
+        let variants = ["Idle", "Moved", "Failed"].map(String::from);
+        let payloads = vec![
+            VariantNode {
+                name: "Moved".to_string(),
+                fields: vec![field("x", "i32", Visibility::Private)],
+            },
+            VariantNode {
+                name: "Failed".to_string(),
+                fields: vec![field("0", "String", Visibility::Private)],
+            },
+        ];
+        assert_eq!(
+            enum_signature("Event", &variants, &payloads),
+            "enum Event { Idle, Moved { x: i32 }, Failed(String) }"
+        );
+        assert_eq!(
+            trait_signature("Store", &["K".to_string()]),
+            "trait Store<K>"
+        );
+    }
+}
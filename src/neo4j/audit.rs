@@ -0,0 +1,252 @@
+//! Neo4j Package & Advisory operations (vulnerability audit)
+
+use super::client::Neo4jClient;
+use super::models::*;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use neo4rs::query;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+impl Neo4jClient {
+    // ========================================================================
+    // Package operations
+    // ========================================================================
+
+    /// Replace the DEPENDS_ON_PACKAGE edges of a project.
+    pub async fn replace_project_packages_impl(
+        &self,
+        project_id: Uuid,
+        packages: &[PackageNode],
+    ) -> Result<usize> {
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $project_id})-[r:DEPENDS_ON_PACKAGE]->(:Package)
+            DELETE r
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+        self.graph.run(q).await?;
+
+        if packages.is_empty() {
+            return Ok(0);
+        }
+
+        let items: Vec<HashMap<String, neo4rs::BoltType>> = packages
+            .iter()
+            .map(|pkg| {
+                let mut m = HashMap::new();
+                m.insert("ecosystem".into(), pkg.ecosystem.to_string().into());
+                m.insert("name".into(), pkg.name.clone().into());
+                m.insert("version".into(), pkg.version.clone().into());
+                m.insert("lockfile".into(), pkg.lockfile.clone().into());
+                m.insert(
+                    "license".into(),
+                    pkg.license.clone().unwrap_or_default().into(),
+                );
+                m
+            })
+            .collect();
+
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $project_id})
+            UNWIND $items AS item
+            MERGE (pkg:Package {ecosystem: item.ecosystem, name: item.name, version: item.version})
+            MERGE (p)-[r:DEPENDS_ON_PACKAGE]->(pkg)
+            SET r.lockfile = item.lockfile,
+                pkg.license = CASE WHEN item.license <> '' THEN item.license ELSE pkg.license END
+            "#,
+        )
+        .param("project_id", project_id.to_string())
+        .param("items", items);
+        self.graph.run(q).await?;
+
+        Ok(packages.len())
+    }
+
+    /// List the packages locked by a project.
+    pub async fn list_project_packages_impl(&self, project_id: Uuid) -> Result<Vec<PackageNode>> {
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $project_id})-[r:DEPENDS_ON_PACKAGE]->(pkg:Package)
+            RETURN pkg.ecosystem AS ecosystem, pkg.name AS name,
+                   pkg.version AS version, r.lockfile AS lockfile,
+                   coalesce(pkg.license, '') AS license
+            ORDER BY ecosystem, name, version
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut packages = Vec::new();
+
+        while let Some(row) = result.next().await? {
+            let ecosystem: String = row.get("ecosystem")?;
+            let Ok(ecosystem) = ecosystem.parse::<PackageEcosystem>() else {
+                continue;
+            };
+            packages.push(PackageNode {
+                ecosystem,
+                name: row.get("name")?,
+                version: row.get("version")?,
+                lockfile: row.get("lockfile").unwrap_or_default(),
+                license: Some(row.get::<String>("license").unwrap_or_default())
+                    .filter(|l| !l.is_empty()),
+            });
+        }
+
+        Ok(packages)
+    }
+
+    // ========================================================================
+    // Advisory operations
+    // ========================================================================
+
+    /// Upsert advisories and replace the AFFECTS_PROJECT edges of a project.
+    pub async fn replace_project_advisories_impl(
+        &self,
+        project_id: Uuid,
+        advisories: &[ProjectAdvisory],
+    ) -> Result<()> {
+        let q = query(
+            r#"
+            MATCH (:Advisory)-[r:AFFECTS_PROJECT]->(p:Project {id: $project_id})
+            DELETE r
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+        self.graph.run(q).await?;
+
+        if advisories.is_empty() {
+            return Ok(());
+        }
+
+        let items: Vec<HashMap<String, neo4rs::BoltType>> = advisories
+            .iter()
+            .map(|pa| {
+                let a = &pa.advisory;
+                let mut m = HashMap::new();
+                m.insert("id".into(), a.id.clone().into());
+                m.insert("summary".into(), a.summary.clone().into());
+                m.insert("aliases".into(), a.aliases.clone().into());
+                m.insert("severity".into(), a.severity.to_string().into());
+                m.insert("cvss".into(), a.cvss.clone().unwrap_or_default().into());
+                m.insert("url".into(), a.url.clone().unwrap_or_default().into());
+                m.insert(
+                    "published".into(),
+                    a.published
+                        .map(|d| d.to_rfc3339())
+                        .unwrap_or_default()
+                        .into(),
+                );
+                m.insert(
+                    "modified".into(),
+                    a.modified
+                        .map(|d| d.to_rfc3339())
+                        .unwrap_or_default()
+                        .into(),
+                );
+                m.insert("ecosystem".into(), pa.ecosystem.to_string().into());
+                m.insert("package".into(), pa.package.clone().into());
+                m.insert("version".into(), pa.version.clone().into());
+                m.insert("fixed_versions".into(), pa.fixed_versions.clone().into());
+                m
+            })
+            .collect();
+
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $project_id})
+            UNWIND $items AS item
+            MERGE (a:Advisory {id: item.id})
+            SET a.summary = item.summary,
+                a.aliases = item.aliases,
+                a.severity = item.severity,
+                a.cvss = item.cvss,
+                a.url = item.url,
+                a.published = item.published,
+                a.modified = item.modified
+            MERGE (a)-[r:AFFECTS_PROJECT {package: item.package, version: item.version}]->(p)
+            SET r.ecosystem = item.ecosystem,
+                r.fixed_versions = item.fixed_versions
+            WITH a, item
+            OPTIONAL MATCH (pkg:Package {ecosystem: item.ecosystem, name: item.package, version: item.version})
+            FOREACH (_ IN CASE WHEN pkg IS NOT NULL THEN [1] ELSE [] END |
+                MERGE (a)-[:AFFECTS_PACKAGE]->(pkg)
+            )
+            "#,
+        )
+        .param("project_id", project_id.to_string())
+        .param("items", items);
+        self.graph.run(q).await?;
+
+        Ok(())
+    }
+
+    /// List the advisories affecting a project.
+    pub async fn list_project_advisories_impl(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<ProjectAdvisory>> {
+        let q = query(
+            r#"
+            MATCH (a:Advisory)-[r:AFFECTS_PROJECT]->(p:Project {id: $project_id})
+            RETURN a.id AS id, a.summary AS summary, a.aliases AS aliases,
+                   a.severity AS severity, a.cvss AS cvss, a.url AS url,
+                   a.published AS published, a.modified AS modified,
+                   r.ecosystem AS ecosystem, r.package AS package,
+                   r.version AS version, r.fixed_versions AS fixed_versions
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut advisories = Vec::new();
+
+        let opt = |s: String| (!s.is_empty()).then_some(s);
+        let date = |s: String| {
+            DateTime::parse_from_rfc3339(&s)
+                .ok()
+                .map(|d| d.with_timezone(&Utc))
+        };
+
+        while let Some(row) = result.next().await? {
+            let ecosystem: String = row.get("ecosystem").unwrap_or_default();
+            let Ok(ecosystem) = ecosystem.parse::<PackageEcosystem>() else {
+                continue;
+            };
+            let severity: String = row.get("severity").unwrap_or_default();
+            advisories.push(ProjectAdvisory {
+                advisory: AdvisoryNode {
+                    id: row.get("id")?,
+                    summary: row.get("summary").unwrap_or_default(),
+                    aliases: row.get("aliases").unwrap_or_default(),
+                    severity: severity.parse().unwrap_or(AdvisorySeverity::Unknown),
+                    cvss: opt(row.get("cvss").unwrap_or_default()),
+                    url: opt(row.get("url").unwrap_or_default()),
+                    published: date(row.get("published").unwrap_or_default()),
+                    modified: date(row.get("modified").unwrap_or_default()),
+                },
+                ecosystem,
+                package: row.get("package")?,
+                version: row.get("version")?,
+                fixed_versions: row.get("fixed_versions").unwrap_or_default(),
+            });
+        }
+
+        sort_project_advisories(&mut advisories);
+        Ok(advisories)
+    }
+}
+
+/// Most severe first, then by package and advisory id.
+pub(crate) fn sort_project_advisories(advisories: &mut [ProjectAdvisory]) {
+    advisories.sort_by(|a, b| {
+        b.advisory
+            .severity
+            .cmp(&a.advisory.severity)
+            .then_with(|| a.package.cmp(&b.package))
+            .then_with(|| a.advisory.id.cmp(&b.advisory.id))
+    });
+}
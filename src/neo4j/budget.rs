@@ -0,0 +1,87 @@
+//! Neo4j project budget operations (declared limits and last check report)
+
+use super::client::Neo4jClient;
+use super::models::*;
+use anyhow::{Context, Result};
+use neo4rs::query;
+use uuid::Uuid;
+
+impl Neo4jClient {
+    /// Read a JSON property of a Project node, `None` when unset or empty.
+    pub(super) async fn get_project_json_property(
+        &self,
+        project_id: Uuid,
+        property: &str,
+    ) -> Result<Option<String>> {
+        let q = query(&format!(
+            "MATCH (p:Project {{id: $id}}) RETURN p.{} AS value",
+            property
+        ))
+        .param("id", project_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        Ok(result
+            .next()
+            .await?
+            .and_then(|row| row.get::<String>("value").ok())
+            .filter(|s| !s.is_empty()))
+    }
+
+    /// Get the budgets declared for a project.
+    pub async fn get_project_budgets_impl(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Option<ProjectBudgets>> {
+        self.get_project_json_property(project_id, "budgets")
+            .await?
+            .map(|json| serde_json::from_str(&json).context("Failed to deserialize budgets"))
+            .transpose()
+    }
+
+    /// Replace the budgets of a project. The last report was computed
+    /// against the old ones, so it is dropped.
+    pub async fn update_project_budgets_impl(
+        &self,
+        project_id: Uuid,
+        budgets: &ProjectBudgets,
+    ) -> Result<()> {
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $id})
+            SET p.budgets = $budgets
+            REMOVE p.budget_report
+            "#,
+        )
+        .param("id", project_id.to_string())
+        .param("budgets", serde_json::to_string(budgets)?);
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
+    /// Get the last budget check report of a project.
+    pub async fn get_budget_report_impl(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Option<BudgetCheckReport>> {
+        self.get_project_json_property(project_id, "budget_report")
+            .await?
+            .map(|json| serde_json::from_str(&json).context("Failed to deserialize budget report"))
+            .transpose()
+    }
+
+    /// Store the latest budget check report of a project.
+    pub async fn save_budget_report_impl(&self, report: &BudgetCheckReport) -> Result<()> {
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $id})
+            SET p.budget_report = $report
+            "#,
+        )
+        .param("id", report.project_id.to_string())
+        .param("report", serde_json::to_string(report)?);
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+}
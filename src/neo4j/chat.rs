@@ -33,6 +33,8 @@ impl Neo4jClient {
                     preview: $preview,
                     permission_mode: $permission_mode,
                     add_dirs: $add_dirs,
+                    sandbox: $sandbox,
+                    limits: $limits,
                     spawned_by: $spawned_by
                 })
                 WITH s
@@ -61,6 +63,8 @@ impl Neo4jClient {
                     preview: $preview,
                     permission_mode: $permission_mode,
                     add_dirs: $add_dirs,
+                    sandbox: $sandbox,
+                    limits: $limits,
                     spawned_by: $spawned_by
                 })
                 "#,
@@ -103,6 +107,8 @@ impl Neo4jClient {
                         serde_json::to_string(&session.add_dirs.clone().unwrap_or_default())
                             .unwrap_or_else(|_| "[]".to_string()),
                     )
+                    .param("sandbox", session.sandbox.clone().unwrap_or_default())
+                    .param("limits", session.limits.clone().unwrap_or_default())
                     .param("spawned_by", session.spawned_by.clone().unwrap_or_default()),
             )
             .await?;
@@ -325,6 +331,87 @@ impl Neo4jClient {
         }
     }
 
+    /// Store the rolling summary of a chat session and clear its cli_session_id.
+    pub async fn set_chat_session_memory(
+        &self,
+        id: Uuid,
+        memory: &ChatSessionMemory,
+    ) -> Result<()> {
+        let cypher = "MATCH (s:ChatSession {id: $id})
+             SET s.memory_summary = $summary,
+                 s.memory_summarized_seq = $summarized_seq,
+                 s.memory_updated_at = $updated_at,
+                 s.cli_session_id = null,
+                 s.updated_at = datetime()";
+        let q = query(cypher)
+            .param("id", id.to_string())
+            .param("summary", memory.summary.clone())
+            .param("summarized_seq", memory.summarized_seq)
+            .param("updated_at", memory.updated_at.to_rfc3339());
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
+    /// Get the rolling summary of a chat session.
+    /// Returns `None` if the session doesn't exist or was never summarized.
+    pub async fn get_chat_session_memory(&self, id: Uuid) -> Result<Option<ChatSessionMemory>> {
+        let cypher = "MATCH (s:ChatSession {id: $id})
+             RETURN s.memory_summary AS summary,
+                    s.memory_summarized_seq AS summarized_seq,
+                    s.memory_updated_at AS updated_at";
+        let q = query(cypher).param("id", id.to_string());
+        let mut result = self.graph.execute(q).await?;
+        let Some(row) = result.next().await? else {
+            return Ok(None);
+        };
+        let Some(summary) = row.get::<Option<String>>("summary").unwrap_or(None) else {
+            return Ok(None);
+        };
+        let updated_at = row
+            .get::<Option<String>>("updated_at")
+            .unwrap_or(None)
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(chrono::Utc::now);
+        Ok(Some(ChatSessionMemory {
+            summary,
+            summarized_seq: row
+                .get::<Option<i64>>("summarized_seq")
+                .unwrap_or(None)
+                .unwrap_or(0),
+            updated_at,
+        }))
+    }
+
+    /// Store the usage of a chat session (JSON in `usage`), or remove it.
+    pub async fn set_chat_session_usage(
+        &self,
+        id: Uuid,
+        usage: Option<&ChatSessionUsage>,
+    ) -> Result<()> {
+        let q = match usage {
+            Some(usage) => query("MATCH (s:ChatSession {id: $id}) SET s.usage = $usage")
+                .param("usage", serde_json::to_string(usage)?),
+            None => query("MATCH (s:ChatSession {id: $id}) REMOVE s.usage"),
+        };
+        self.graph.run(q.param("id", id.to_string())).await?;
+        Ok(())
+    }
+
+    /// Get the usage of a chat session.
+    /// Returns `None` if the session doesn't exist or has no usage stored.
+    pub async fn get_chat_session_usage(&self, id: Uuid) -> Result<Option<ChatSessionUsage>> {
+        let q = query("MATCH (s:ChatSession {id: $id}) RETURN s.usage AS usage")
+            .param("id", id.to_string());
+        let mut result = self.graph.execute(q).await?;
+        let Some(row) = result.next().await? else {
+            return Ok(None);
+        };
+        Ok(row
+            .get::<Option<String>>("usage")
+            .unwrap_or(None)
+            .and_then(|u| serde_json::from_str(&u).ok()))
+    }
+
     /// Backfill title and preview for sessions that don't have them yet.
     /// Uses the first user_message event stored in Neo4j.
     /// Returns the number of sessions updated.
@@ -396,8 +483,15 @@ impl Neo4jClient {
         let exists = check_result.next().await?.is_some();
 
         if exists {
-            let q = query("MATCH (s:ChatSession {id: $id}) DETACH DELETE s")
-                .param("id", id.to_string());
+            let q = query(
+                r#"
+                MATCH (s:ChatSession {id: $id})
+                OPTIONAL MATCH (s)-[:HAS_CONTEXT_FEEDBACK]->(f:ContextFeedback)
+                OPTIONAL MATCH (s)-[:HAS_UNDO_ENTRY]->(u:UndoEntry)
+                DETACH DELETE f, u, s
+                "#,
+            )
+            .param("id", id.to_string());
             self.graph.run(q).await?;
             Ok(true)
         } else {
@@ -663,6 +757,8 @@ impl Neo4jClient {
         let preview: String = node.get("preview").unwrap_or_default();
         let permission_mode: String = node.get("permission_mode").unwrap_or_default();
         let add_dirs_json: String = node.get("add_dirs").unwrap_or_default();
+        let sandbox: String = node.get("sandbox").unwrap_or_default();
+        let limits: String = node.get("limits").unwrap_or_default();
         let spawned_by: String = node.get("spawned_by").unwrap_or_default();
 
         // Deserialize add_dirs from JSON string (backward compat: empty string → None)
@@ -727,6 +823,16 @@ impl Neo4jClient {
                 Some(permission_mode)
             },
             add_dirs,
+            sandbox: if sandbox.is_empty() {
+                None
+            } else {
+                Some(sandbox)
+            },
+            limits: if limits.is_empty() {
+                None
+            } else {
+                Some(limits)
+            },
             spawned_by: if spawned_by.is_empty() {
                 None
             } else {
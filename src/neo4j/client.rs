@@ -1,16 +1,18 @@
 //! Neo4j client for interacting with the knowledge graph
 
+use super::query_guard::{GuardedGraph, QueryLimits, QueryMetrics};
 use anyhow::{Context, Result};
 use neo4rs::{query, Graph, Query};
 use std::collections::HashMap;
-use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
 /// Client for Neo4j operations
 pub struct Neo4jClient {
-    pub(crate) graph: Arc<Graph>,
+    /// Connection pool; every query through it is bounded by the client's
+    /// [`QueryLimits`]
+    pub(crate) graph: GuardedGraph,
     /// TTL cache for pairwise coupling scores between projects.
     /// Key: (min(project_a, project_b), max(project_a, project_b)) — sorted for symmetry.
     /// Value: (coupling_score, cached_at).
@@ -130,6 +132,13 @@ impl WhereBuilder {
         self
     }
 
+    /// Exclude archived nodes (`archived` unset counts as not archived)
+    pub fn add_archived_filter(&mut self, alias: &str) -> &mut Self {
+        self.conditions
+            .push(format!("COALESCE({}.archived, false) = false", alias));
+        self
+    }
+
     /// Build the WHERE clause (returns empty string if no conditions)
     pub fn build(&self) -> String {
         if self.conditions.is_empty() {
@@ -162,7 +171,7 @@ impl Neo4jClient {
             .context("Failed to connect to Neo4j")?;
 
         let client = Self {
-            graph: Arc::new(graph),
+            graph: GuardedGraph::new(graph, QueryLimits::default()),
             coupling_cache: RwLock::new(HashMap::new()),
         };
 
@@ -172,11 +181,20 @@ impl Neo4jClient {
         Ok(client)
     }
 
+    /// Bound the queries of this client by `limits` instead of the defaults
+    /// (the schema initialization of [`Neo4jClient::new`] keeps the defaults).
+    pub fn with_query_limits(mut self, limits: QueryLimits) -> Self {
+        self.graph = self.graph.with_limits(limits);
+        self
+    }
+
     // ========================================================================
     // Query performance monitoring
     // ========================================================================
 
-    /// Log a warning if a Neo4j query exceeded the slow query threshold.
+    /// Log a warning if a step of a multi-query operation exceeded the slow
+    /// query threshold. Single queries are measured by [`GuardedGraph`]
+    /// already; this covers labelled sequences of them.
     ///
     /// Usage:
     /// ```rust,ignore
@@ -185,14 +203,11 @@ impl Neo4jClient {
     /// self.log_slow_query("my_query_label", start);
     /// ```
     ///
-    /// Threshold is configurable via env `NEO4J_SLOW_QUERY_THRESHOLD_MS` (default 100ms).
+    /// The threshold is [`QueryLimits::slow_threshold`] (config
+    /// `neo4j.slow_query_ms`, env `NEO4J_SLOW_QUERY_THRESHOLD_MS`).
     pub fn log_slow_query(&self, label: &str, start: std::time::Instant) {
         let elapsed_ms = start.elapsed().as_millis() as u64;
-
-        let threshold: u64 = std::env::var("NEO4J_SLOW_QUERY_THRESHOLD_MS")
-            .ok()
-            .and_then(|v| v.parse().ok())
-            .unwrap_or(100);
+        let threshold = self.graph.limits().slow_threshold.as_millis() as u64;
 
         if elapsed_ms > threshold {
             tracing::warn!(
@@ -206,6 +221,41 @@ impl Neo4jClient {
         }
     }
 
+    /// Counters of the queries run by this client, with the latest slow ones.
+    pub fn query_metrics(&self) -> QueryMetrics {
+        self.graph.metrics()
+    }
+
+    // ========================================================================
+    // Transactions
+    // ========================================================================
+
+    /// Run queries in a single transaction: either all of them are applied or,
+    /// if one fails, none is.
+    pub async fn run_in_txn(&self, queries: Vec<Query>) -> Result<()> {
+        // Bounded as a whole: a transaction dropped on timeout is rolled back
+        // by the server
+        self.graph
+            .bound(async {
+                let mut txn = self
+                    .graph
+                    .start_txn()
+                    .await
+                    .context("Failed to start Neo4j transaction")?;
+                if let Err(e) = txn.run_queries(queries).await {
+                    if let Err(rollback) = txn.rollback().await {
+                        tracing::warn!("Failed to roll back Neo4j transaction: {}", rollback);
+                    }
+                    return Err(e.into());
+                }
+                txn.commit()
+                    .await
+                    .context("Failed to commit Neo4j transaction")?;
+                Ok::<_, anyhow::Error>(())
+            })
+            .await
+    }
+
     /// Initialize the graph schema with constraints and indexes
     async fn init_schema(&self) -> Result<()> {
         let constraints = vec![
@@ -236,6 +286,9 @@ impl Neo4jClient {
             "CREATE CONSTRAINT workspace_slug IF NOT EXISTS FOR (w:Workspace) REQUIRE w.slug IS UNIQUE",
             "CREATE CONSTRAINT workspace_milestone_id IF NOT EXISTS FOR (wm:WorkspaceMilestone) REQUIRE wm.id IS UNIQUE",
             "CREATE CONSTRAINT resource_id IF NOT EXISTS FOR (r:Resource) REQUIRE r.id IS UNIQUE",
+            "CREATE CONSTRAINT advisory_id IF NOT EXISTS FOR (a:Advisory) REQUIRE a.id IS UNIQUE",
+            "CREATE CONSTRAINT document_path IF NOT EXISTS FOR (d:Document) REQUIRE d.path IS UNIQUE",
+            "CREATE CONSTRAINT section_id IF NOT EXISTS FOR (s:Section) REQUIRE s.id IS UNIQUE",
             "CREATE CONSTRAINT component_id IF NOT EXISTS FOR (c:Component) REQUIRE c.id IS UNIQUE",
             // Chat constraints
             "CREATE CONSTRAINT chat_session_id IF NOT EXISTS FOR (s:ChatSession) REQUIRE s.id IS UNIQUE",
@@ -303,6 +356,24 @@ impl Neo4jClient {
             "CREATE INDEX resource_workspace IF NOT EXISTS FOR (r:Resource) ON (r.workspace_id)",
             "CREATE INDEX resource_project IF NOT EXISTS FOR (r:Resource) ON (r.project_id)",
             "CREATE INDEX resource_type IF NOT EXISTS FOR (r:Resource) ON (r.resource_type)",
+            "CREATE INDEX resource_element_resource IF NOT EXISTS FOR (e:ResourceElement) ON (e.resource_id)",
+            "CREATE INDEX package_key IF NOT EXISTS FOR (p:Package) ON (p.ecosystem, p.name, p.version)",
+            "CREATE INDEX table_project_name IF NOT EXISTS FOR (t:Table) ON (t.project_id, t.name)",
+            "CREATE INDEX column_project_table IF NOT EXISTS FOR (c:Column) ON (c.project_id, c.table)",
+            "CREATE INDEX migration_project IF NOT EXISTS FOR (m:Migration) ON (m.project_id)",
+            "CREATE INDEX config_key_project_key IF NOT EXISTS FOR (k:ConfigKey) ON (k.project_id, k.key)",
+            "CREATE INDEX config_file_project_path IF NOT EXISTS FOR (f:ConfigFile) ON (f.project_id, f.path)",
+            "CREATE INDEX module_summary_project_path IF NOT EXISTS FOR (m:ModuleSummary) ON (m.project_id, m.path)",
+            "CREATE INDEX api_surface_snapshot_project IF NOT EXISTS FOR (s:ApiSurfaceSnapshot) ON (s.project_id, s.created_at)",
+            "CREATE INDEX test_run_project IF NOT EXISTS FOR (r:TestRun) ON (r.project_id, r.created_at)",
+            "CREATE INDEX test_case_project_key IF NOT EXISTS FOR (t:TestCase) ON (t.project_id, t.key)",
+            "CREATE INDEX sentry_issue_project_id IF NOT EXISTS FOR (i:SentryIssue) ON (i.project_id, i.id)",
+            "CREATE INDEX code_owner_project_name IF NOT EXISTS FOR (o:CodeOwner) ON (o.project_id, o.name)",
+            "CREATE INDEX code_owner_rule_project_id IF NOT EXISTS FOR (r:CodeOwnerRule) ON (r.project_id)",
+            "CREATE INDEX context_feedback_item IF NOT EXISTS FOR (f:ContextFeedback) ON (f.item_type, f.item_id)",
+            "CREATE INDEX undo_entry_id IF NOT EXISTS FOR (u:UndoEntry) ON (u.id)",
+            "CREATE INDEX comment_id IF NOT EXISTS FOR (c:Comment) ON (c.id)",
+            "CREATE INDEX note_endorsement_note IF NOT EXISTS FOR (e:NoteEndorsement) ON (e.note_id)",
             "CREATE INDEX component_workspace IF NOT EXISTS FOR (c:Component) ON (c.workspace_id)",
             "CREATE INDEX component_type IF NOT EXISTS FOR (c:Component) ON (c.component_type)",
             // ChatEvent indexes — critical for performance (120K+ nodes)
@@ -25,6 +25,39 @@ const CODE_REL_TYPES: &[&str] = &[
     "STEP_IN_PROCESS",
 ];
 
+/// A list property stored as a JSON string (struct fields, variant
+/// payloads); empty when absent or unreadable.
+pub(super) fn json_list<T: serde::de::DeserializeOwned>(node: &neo4rs::Node, key: &str) -> Vec<T> {
+    node.get::<String>(key)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// A symbol of a `RETURN s, kind, language, hash, project_id, root` row.
+fn symbol_location_from_row(row: &neo4rs::Row) -> Result<SymbolLocationNode> {
+    let node: neo4rs::Node = row.get("s")?;
+    let docstring: String = node.get("docstring").unwrap_or_default();
+    Ok(SymbolLocationNode {
+        id: node.get("id")?,
+        name: node.get("name")?,
+        kind: row.get("kind")?,
+        file_path: node.get("file_path")?,
+        line_start: node.get::<i64>("line_start").unwrap_or(0) as u32,
+        line_end: node.get::<i64>("line_end").unwrap_or(0) as u32,
+        language: row.get("language").ok(),
+        docstring: (!docstring.is_empty()).then_some(docstring),
+        file_hash: row.get("hash").ok(),
+        project_id: row
+            .get::<String>("project_id")
+            .ok()
+            .and_then(|id| id.parse().ok()),
+        project_root: row.get("root").ok(),
+        fields: json_list(&node, "fields"),
+        variant_payloads: json_list(&node, "variant_payloads"),
+    })
+}
+
 impl Neo4jClient {
     // ========================================================================
     // File operations
@@ -182,6 +215,8 @@ impl Neo4jClient {
             MERGE (f:File {path: $path})
             SET f.language = $language,
                 f.hash = $hash,
+                f.line_count = $line_count,
+                f.encoding = CASE WHEN $encoding = '' THEN null ELSE $encoding END,
                 f.last_parsed = datetime($last_parsed),
                 f.project_id = $project_id
             "#,
@@ -189,6 +224,8 @@ impl Neo4jClient {
         .param("path", file.path.clone())
         .param("language", file.language.clone())
         .param("hash", file.hash.clone())
+        .param("line_count", file.line_count as i64)
+        .param("encoding", file.encoding.clone().unwrap_or_default())
         .param("last_parsed", file.last_parsed.to_rfc3339())
         .param(
             "project_id",
@@ -233,6 +270,11 @@ impl Neo4jClient {
                 m.insert("path".into(), f.path.clone().into());
                 m.insert("language".into(), f.language.clone().into());
                 m.insert("hash".into(), f.hash.clone().into());
+                m.insert("line_count".into(), (f.line_count as i64).into());
+                m.insert(
+                    "encoding".into(),
+                    f.encoding.clone().unwrap_or_default().into(),
+                );
                 m.insert("last_parsed".into(), f.last_parsed.to_rfc3339().into());
                 m.insert(
                     "project_id".into(),
@@ -253,6 +295,8 @@ impl Neo4jClient {
             MERGE (f:File {path: item.path})
             SET f.language = item.language,
                 f.hash = item.hash,
+                f.line_count = item.line_count,
+                f.encoding = CASE WHEN item.encoding = '' THEN null ELSE item.encoding END,
                 f.last_parsed = datetime(item.last_parsed),
                 f.project_id = item.project_id
             "#,
@@ -297,6 +341,7 @@ impl Neo4jClient {
             r#"
             MATCH (f:File {path: $path})
             RETURN f.path AS path, f.language AS language, f.hash AS hash,
+                   f.line_count AS line_count, f.encoding AS encoding,
                    f.last_parsed AS last_parsed, f.project_id AS project_id
             "#,
         )
@@ -308,6 +353,7 @@ impl Neo4jClient {
                 path: row.get("path")?,
                 language: row.get("language")?,
                 hash: row.get("hash")?,
+                line_count: row.get::<i64>("line_count").unwrap_or(0).max(0) as u32,
                 last_parsed: row
                     .get::<String>("last_parsed")?
                     .parse()
@@ -316,6 +362,7 @@ impl Neo4jClient {
                     .get::<String>("project_id")
                     .ok()
                     .and_then(|s| s.parse().ok()),
+                encoding: row.get::<String>("encoding").ok(),
             }))
         } else {
             Ok(None)
@@ -328,6 +375,7 @@ impl Neo4jClient {
             r#"
             MATCH (p:Project {id: $project_id})-[:CONTAINS]->(f:File)
             RETURN f.path AS path, f.language AS language, f.hash AS hash,
+                   f.line_count AS line_count, f.encoding AS encoding,
                    f.last_parsed AS last_parsed, f.project_id AS project_id
             ORDER BY f.path
             "#,
@@ -342,11 +390,13 @@ impl Neo4jClient {
                 path: row.get("path")?,
                 language: row.get("language")?,
                 hash: row.get("hash")?,
+                line_count: row.get::<i64>("line_count").unwrap_or(0).max(0) as u32,
                 last_parsed: row
                     .get::<String>("last_parsed")?
                     .parse()
                     .unwrap_or_else(|_| chrono::Utc::now()),
                 project_id: Some(project_id),
+                encoding: row.get::<String>("encoding").ok(),
             });
         }
 
@@ -368,6 +418,180 @@ impl Neo4jClient {
         }
     }
 
+    /// Content hash of the search document last indexed for each of the
+    /// files (`indexed_hash`), for the files that have one.
+    pub async fn get_indexed_hashes(
+        &self,
+        paths: &[String],
+    ) -> Result<std::collections::HashMap<String, String>> {
+        if paths.is_empty() {
+            return Ok(Default::default());
+        }
+        let q = query(
+            r#"
+            UNWIND $paths AS path
+            MATCH (f:File {path: path})
+            WHERE f.indexed_hash IS NOT NULL
+            RETURN f.path AS path, f.indexed_hash AS indexed_hash
+            "#,
+        )
+        .param("paths", paths.to_vec());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut hashes = std::collections::HashMap::new();
+        while let Some(row) = result.next().await? {
+            hashes.insert(row.get("path")?, row.get("indexed_hash")?);
+        }
+        Ok(hashes)
+    }
+
+    /// Record the content hash of the search documents just indexed, as
+    /// (path, hash) pairs.
+    pub async fn set_indexed_hashes(&self, hashes: &[(String, String)]) -> Result<()> {
+        if hashes.is_empty() {
+            return Ok(());
+        }
+
+        use crate::neo4j::batch::{run_unwind_in_chunks, BoltMap};
+
+        let items: Vec<BoltMap> = hashes
+            .iter()
+            .map(|(path, hash)| {
+                let mut m = BoltMap::new();
+                m.insert("path".into(), path.clone().into());
+                m.insert("hash".into(), hash.clone().into());
+                m
+            })
+            .collect();
+        run_unwind_in_chunks(
+            &self.graph,
+            items,
+            r#"
+            UNWIND $items AS item
+            MATCH (f:File {path: item.path})
+            SET f.indexed_hash = item.hash
+            "#,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Per-file symbol counts, complexity and analytics flags for a project.
+    pub async fn get_project_file_stats(&self, project_id: Uuid) -> Result<Vec<FileStatsRow>> {
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $project_id})-[:CONTAINS]->(f:File)
+            OPTIONAL MATCH (f)-[:CONTAINS]->(s)
+            WHERE s:Function OR s:Struct OR s:Trait OR s:Enum
+            WITH f,
+                 sum(CASE WHEN s:Function THEN 1 ELSE 0 END) AS function_count,
+                 sum(CASE WHEN s IS NOT NULL AND NOT s:Function THEN 1 ELSE 0 END) AS type_count,
+                 sum(CASE WHEN s:Function THEN coalesce(s.complexity, 0) ELSE 0 END) AS complexity_total,
+                 max(CASE WHEN s:Function THEN s.complexity END) AS max_complexity
+            RETURN f.path AS path, f.language AS language,
+                   toString(f.last_parsed) AS last_parsed,
+                   function_count, type_count, complexity_total,
+                   coalesce(max_complexity, 0) AS max_complexity,
+                   f.pagerank IS NOT NULL AS analyzed,
+                   coalesce(f.analytics_updated_at < f.last_parsed, false) AS analytics_stale,
+                   f.risk_level AS risk_level
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut rows = Vec::new();
+        while let Some(row) = result.next().await? {
+            rows.push(FileStatsRow {
+                path: row.get("path")?,
+                language: row.get("language").unwrap_or_default(),
+                last_parsed: row
+                    .get::<String>("last_parsed")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or_else(chrono::Utc::now),
+                function_count: row.get::<i64>("function_count").unwrap_or(0) as u32,
+                type_count: row.get::<i64>("type_count").unwrap_or(0) as u32,
+                complexity_total: row.get::<i64>("complexity_total").unwrap_or(0) as u64,
+                max_complexity: row.get::<i64>("max_complexity").unwrap_or(0) as u32,
+                analyzed: row.get("analyzed").unwrap_or(false),
+                analytics_stale: row.get("analytics_stale").unwrap_or(false),
+                risk_level: row.get::<String>("risk_level").ok(),
+            });
+        }
+        Ok(rows)
+    }
+
+    /// Functions of a project whose complexity exceeds `max_complexity`.
+    pub async fn get_functions_above_complexity(
+        &self,
+        project_id: Uuid,
+        max_complexity: u32,
+    ) -> Result<Vec<FunctionComplexityRow>> {
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $project_id})-[:CONTAINS]->(:File)-[:CONTAINS]->(f:Function)
+            WHERE f.complexity > $max_complexity
+            RETURN f.id AS id, f.name AS name, f.file_path AS file_path,
+                   f.line_start AS line_start, f.complexity AS complexity
+            ORDER BY f.complexity DESC
+            "#,
+        )
+        .param("project_id", project_id.to_string())
+        .param("max_complexity", max_complexity as i64);
+
+        let mut result = self.graph.execute(q).await?;
+        let mut rows = Vec::new();
+        while let Some(row) = result.next().await? {
+            rows.push(FunctionComplexityRow {
+                id: row.get("id")?,
+                name: row.get("name").unwrap_or_default(),
+                file_path: row.get("file_path").unwrap_or_default(),
+                line_start: row.get::<i64>("line_start").unwrap_or(0) as u32,
+                complexity: row.get::<i64>("complexity").unwrap_or(0) as u32,
+            });
+        }
+        Ok(rows)
+    }
+
+    /// Functions of a project carrying a clone fingerprint.
+    pub async fn get_project_function_fingerprints(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<FunctionFingerprintRow>> {
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $project_id})-[:CONTAINS]->(:File)-[:CONTAINS]->(f:Function)
+            WHERE f.minhash IS NOT NULL
+            RETURN f.id AS id, f.name AS name, f.file_path AS file_path,
+                   f.line_start AS line_start, f.line_end AS line_end,
+                   f.clone_tokens AS token_count, f.minhash AS minhash
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut rows = Vec::new();
+        while let Some(row) = result.next().await? {
+            rows.push(FunctionFingerprintRow {
+                id: row.get("id")?,
+                name: row.get("name").unwrap_or_default(),
+                file_path: row.get("file_path").unwrap_or_default(),
+                line_start: row.get::<i64>("line_start").unwrap_or(0) as u32,
+                line_end: row.get::<i64>("line_end").unwrap_or(0) as u32,
+                token_count: row.get::<i64>("token_count").unwrap_or(0) as u32,
+                // Stored as i64 bit patterns (Neo4j has no unsigned integers)
+                minhash: row
+                    .get::<Vec<i64>>("minhash")
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|v| v as u64)
+                    .collect(),
+            });
+        }
+        Ok(rows)
+    }
+
     /// List all sub-file symbols (Function, Struct, Trait, Enum) for a project.
     /// Returns tuples of (id, name, symbol_type, file_path, visibility, line_start).
     /// Used by the graph visualization endpoint to include code-level detail nodes.
@@ -530,7 +754,8 @@ impl Neo4jClient {
                 s.line_end = $line_end,
                 s.docstring = $docstring,
                 s.parent_class = $parent_class,
-                s.interfaces = $interfaces
+                s.interfaces = $interfaces,
+                s.fields = $fields
             WITH s
             MATCH (file:File {path: $file_path})
             MERGE (file)-[:CONTAINS]->(s)
@@ -545,7 +770,8 @@ impl Neo4jClient {
         .param("line_end", s.line_end as i64)
         .param("docstring", s.docstring.clone().unwrap_or_default())
         .param("parent_class", s.parent_class.clone().unwrap_or_default())
-        .param("interfaces", s.interfaces.clone());
+        .param("interfaces", s.interfaces.clone())
+        .param("fields", serde_json::to_string(&s.fields)?);
 
         self.graph.run(q).await?;
         Ok(())
@@ -621,7 +847,8 @@ impl Neo4jClient {
                 e.file_path = $file_path,
                 e.line_start = $line_start,
                 e.line_end = $line_end,
-                e.docstring = $docstring
+                e.docstring = $docstring,
+                e.variant_payloads = $variant_payloads
             WITH e
             MATCH (file:File {path: $file_path})
             MERGE (file)-[:CONTAINS]->(e)
@@ -634,7 +861,11 @@ impl Neo4jClient {
         .param("file_path", e.file_path.clone())
         .param("line_start", e.line_start as i64)
         .param("line_end", e.line_end as i64)
-        .param("docstring", e.docstring.clone().unwrap_or_default());
+        .param("docstring", e.docstring.clone().unwrap_or_default())
+        .param(
+            "variant_payloads",
+            serde_json::to_string(&e.variant_payloads)?,
+        );
 
         self.graph.run(q).await?;
         Ok(())
@@ -662,7 +893,8 @@ impl Neo4jClient {
                 i.where_clause = $where_clause,
                 i.file_path = $file_path,
                 i.line_start = $line_start,
-                i.line_end = $line_end
+                i.line_end = $line_end,
+                i.derived = $derived
             WITH i
             MATCH (file:File {path: $file_path})
             MERGE (file)-[:CONTAINS]->(i)
@@ -681,7 +913,8 @@ impl Neo4jClient {
         )
         .param("file_path", impl_node.file_path.clone())
         .param("line_start", impl_node.line_start as i64)
-        .param("line_end", impl_node.line_end as i64);
+        .param("line_end", impl_node.line_end as i64)
+        .param("derived", impl_node.derived);
 
         self.graph.run(q).await?;
 
@@ -747,12 +980,14 @@ impl Neo4jClient {
                 MATCH (i:Impl {id: $impl_id})
                 MATCH (t:Trait {name: $trait_name})
                 WHERE t.is_external IS NULL OR t.is_external = false
-                MERGE (i)-[:IMPLEMENTS_TRAIT]->(t)
+                MERGE (i)-[r:IMPLEMENTS_TRAIT]->(t)
+                SET r.synthetic = $derived
                 RETURN count(*) AS linked
                 "#,
             )
             .param("impl_id", id.clone())
-            .param("trait_name", trait_name.clone());
+            .param("trait_name", trait_name.clone())
+            .param("derived", impl_node.derived);
 
             let rows = self.execute_with_params(q).await?;
             let linked: i64 = rows.first().and_then(|r| r.get("linked").ok()).unwrap_or(0);
@@ -779,14 +1014,16 @@ impl Neo4jClient {
                         t.source = CASE WHEN t.source = 'unknown' THEN $source ELSE t.source END
                     WITH t
                     MATCH (i:Impl {id: $impl_id})
-                    MERGE (i)-[:IMPLEMENTS_TRAIT]->(t)
+                    MERGE (i)-[r:IMPLEMENTS_TRAIT]->(t)
+                    SET r.synthetic = $derived
                     "#,
                 )
                 .param("trait_id", external_id)
                 .param("name", simple_name)
                 .param("full_path", trait_name.clone())
                 .param("source", source)
-                .param("impl_id", id);
+                .param("impl_id", id)
+                .param("derived", impl_node.derived);
 
                 let _ = self.graph.run(q).await;
             }
@@ -1188,6 +1425,64 @@ impl Neo4jClient {
         Ok(())
     }
 
+    /// Clear the clone fingerprints of the functions in `file_paths`, then
+    /// store `fingerprints` on their Function nodes.
+    pub async fn batch_set_function_fingerprints(
+        &self,
+        file_paths: &[String],
+        fingerprints: &[FunctionFingerprint],
+    ) -> Result<()> {
+        if file_paths.is_empty() {
+            return Ok(());
+        }
+
+        // Functions that shrank below the token floor must not keep an old one
+        let clear = query(
+            r#"
+            UNWIND $paths AS path
+            MATCH (:File {path: path})-[:CONTAINS]->(f:Function)
+            WHERE f.minhash IS NOT NULL
+            REMOVE f.minhash, f.clone_tokens
+            "#,
+        )
+        .param("paths", file_paths.to_vec());
+        self.graph.run(clear).await?;
+
+        if fingerprints.is_empty() {
+            return Ok(());
+        }
+
+        let items: Vec<std::collections::HashMap<String, neo4rs::BoltType>> = fingerprints
+            .iter()
+            .map(|fp| {
+                let mut m = std::collections::HashMap::new();
+                m.insert("id".into(), fp.function_id.clone().into());
+                m.insert("token_count".into(), (fp.token_count as i64).into());
+                // Neo4j integers are signed: store the u64 bit patterns as i64
+                let minhash: Vec<neo4rs::BoltType> =
+                    fp.minhash.iter().map(|&v| (v as i64).into()).collect();
+                m.insert(
+                    "minhash".into(),
+                    neo4rs::BoltType::List(neo4rs::BoltList::from(minhash)),
+                );
+                m
+            })
+            .collect();
+
+        let q = query(
+            r#"
+            UNWIND $items AS fp
+            MATCH (f:Function {id: fp.id})
+            SET f.minhash = fp.minhash,
+                f.clone_tokens = fp.token_count
+            "#,
+        )
+        .param("items", items);
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
     /// Batch upsert structs using UNWIND.
     pub async fn batch_upsert_structs(&self, structs: &[StructNode]) -> Result<()> {
         if structs.is_empty() {
@@ -1214,6 +1509,10 @@ impl Neo4jClient {
                     s.parent_class.clone().unwrap_or_default().into(),
                 );
                 m.insert("interfaces".into(), s.interfaces.clone().into());
+                m.insert(
+                    "fields".into(),
+                    serde_json::to_string(&s.fields).unwrap_or_default().into(),
+                );
                 m
             })
             .collect();
@@ -1230,7 +1529,8 @@ impl Neo4jClient {
                 st.line_end = s.line_end,
                 st.docstring = s.docstring,
                 st.parent_class = s.parent_class,
-                st.interfaces = s.interfaces
+                st.interfaces = s.interfaces,
+                st.fields = s.fields
             WITH st, s
             MATCH (file:File {path: s.file_path})
             MERGE (file)-[:CONTAINS]->(st)
@@ -1312,6 +1612,12 @@ impl Neo4jClient {
                     "docstring".into(),
                     e.docstring.clone().unwrap_or_default().into(),
                 );
+                m.insert(
+                    "variant_payloads".into(),
+                    serde_json::to_string(&e.variant_payloads)
+                        .unwrap_or_default()
+                        .into(),
+                );
                 m
             })
             .collect();
@@ -1326,7 +1632,8 @@ impl Neo4jClient {
                 en.file_path = e.file_path,
                 en.line_start = e.line_start,
                 en.line_end = e.line_end,
-                en.docstring = e.docstring
+                en.docstring = e.docstring,
+                en.variant_payloads = e.variant_payloads
             WITH en, e
             MATCH (file:File {path: e.file_path})
             MERGE (file)-[:CONTAINS]->(en)
@@ -1373,6 +1680,7 @@ impl Neo4jClient {
                 m.insert("file_path".into(), imp.file_path.clone().into());
                 m.insert("line_start".into(), (imp.line_start as i64).into());
                 m.insert("line_end".into(), (imp.line_end as i64).into());
+                m.insert("derived".into(), imp.derived.into());
                 // Pre-computed IDs for Phase 2
                 m.insert(
                     "struct_id".into(),
@@ -1392,7 +1700,8 @@ impl Neo4jClient {
                 i.where_clause = imp.where_clause,
                 i.file_path = imp.file_path,
                 i.line_start = imp.line_start,
-                i.line_end = imp.line_end
+                i.line_end = imp.line_end,
+                i.derived = imp.derived
             WITH i, imp
             MATCH (file:File {path: imp.file_path})
             MERGE (file)-[:CONTAINS]->(i)
@@ -1477,6 +1786,7 @@ impl Neo4jClient {
                 m.insert("source".into(), source.into());
                 m.insert("full_path".into(), trait_name.to_string().into());
                 m.insert("external_id".into(), external_id.into());
+                m.insert("derived".into(), imp.derived.into());
                 m
             })
             .collect();
@@ -1489,7 +1799,8 @@ impl Neo4jClient {
                 MATCH (i:Impl {id: imp.impl_id})
                 MATCH (t:Trait {name: imp.trait_name})
                 WHERE t.is_external IS NULL OR t.is_external = false
-                MERGE (i)-[:IMPLEMENTS_TRAIT]->(t)
+                MERGE (i)-[r:IMPLEMENTS_TRAIT]->(t)
+                SET r.synthetic = imp.derived
                 RETURN imp.impl_id AS linked_id
                 "#,
             )
@@ -1536,7 +1847,8 @@ impl Neo4jClient {
                         t.source = CASE WHEN t.source = 'unknown' THEN imp.source ELSE t.source END
                     WITH t, imp
                     MATCH (i:Impl {id: imp.impl_id})
-                    MERGE (i)-[:IMPLEMENTS_TRAIT]->(t)
+                    MERGE (i)-[r:IMPLEMENTS_TRAIT]->(t)
+                    SET r.synthetic = imp.derived
                     "#,
                 )
                 .param("items", unresolved_traits);
@@ -1722,9 +2034,14 @@ impl Neo4jClient {
 
     /// Batch create CALLS relationships using UNWIND — 2-phase strategy:
     /// Phase 1: same-file callee match (most common, O(1) via index)
-    /// Phase 2: project-scoped fallback for unresolved calls
+    /// Phase 2: project-scoped fallback for unresolved calls, preferring a
+    /// callee in the caller's directory
+    ///
+    /// Calls with a receiver type skip both phases: they only link to a method
+    /// of an impl block for that type, and to nothing when there is none (a
+    /// trait default method or a method of an external type).
     ///
-    /// Both phases are chunked (BATCH_SIZE items per query) to avoid Neo4j OOM/timeout
+    /// All phases are chunked (BATCH_SIZE items per query) to avoid Neo4j OOM/timeout
     /// on large projects (50K+ calls).
     pub async fn batch_create_call_relationships(
         &self,
@@ -1740,30 +2057,86 @@ impl Neo4jClient {
         // (primary filter is in the parser, this catches any remaining)
         use crate::parser::noise_filter;
 
-        let items: Vec<std::collections::HashMap<String, neo4rs::BoltType>> = calls
+        let (method_calls, calls): (Vec<_>, Vec<_>) = calls
             .iter()
             .filter(|call| !noise_filter::is_builtin_call(&call.callee_name))
-            .map(|call| {
-                let caller_file_path = call
-                    .caller_id
-                    .rsplitn(3, ':')
-                    .last()
-                    .unwrap_or(&call.caller_id)
-                    .to_string();
-                let mut m = std::collections::HashMap::new();
-                m.insert("caller_id".into(), call.caller_id.clone().into());
-                m.insert("callee_name".into(), call.callee_name.clone().into());
-                m.insert("caller_file_path".into(), caller_file_path.into());
-                m.insert(
-                    "confidence".into(),
-                    neo4rs::BoltType::Float(neo4rs::BoltFloat {
-                        value: call.confidence,
-                    }),
-                );
-                m.insert("reason".into(), call.reason.clone().into());
-                m
-            })
-            .collect();
+            .partition(|call| call.receiver_type.is_some());
+        type CallItem = std::collections::HashMap<String, neo4rs::BoltType>;
+        let to_item = |call: &crate::parser::FunctionCall| -> CallItem {
+            let caller_file_path = call
+                .caller_id
+                .rsplitn(3, ':')
+                .last()
+                .unwrap_or(&call.caller_id)
+                .to_string();
+            let mut m = std::collections::HashMap::new();
+            m.insert("caller_id".into(), call.caller_id.clone().into());
+            m.insert("callee_name".into(), call.callee_name.clone().into());
+            let caller_dir = caller_file_path
+                .rsplit_once('/')
+                .map(|(dir, _)| format!("{}/", dir))
+                .unwrap_or_default();
+            m.insert("caller_file_path".into(), caller_file_path.into());
+            m.insert("caller_dir".into(), caller_dir.into());
+            m.insert(
+                "confidence".into(),
+                neo4rs::BoltType::Float(neo4rs::BoltFloat {
+                    value: call.confidence,
+                }),
+            );
+            m.insert("reason".into(), call.reason.clone().into());
+            m.insert(
+                "receiver_type".into(),
+                call.receiver_type.clone().unwrap_or_default().into(),
+            );
+            m
+        };
+
+        // Method calls: callee among the methods of the receiver type's impls
+        if !method_calls.is_empty() {
+            let method_items: Vec<_> = method_calls.into_iter().map(to_item).collect();
+            let cypher = format!(
+                r#"
+                UNWIND $items AS call
+                CALL {{
+                    WITH call
+                    MATCH (caller:Function {{id: call.caller_id}})
+                    MATCH (i:Impl {{for_type: call.receiver_type}})
+                    MATCH (callee:Function {{name: call.callee_name, file_path: i.file_path{}}})
+                    WHERE callee.id <> call.caller_id
+                      AND i.line_start <= callee.line_start AND callee.line_end <= i.line_end
+                    WITH caller, callee, call
+                    ORDER BY CASE
+                        WHEN callee.file_path = call.caller_file_path THEN 0
+                        WHEN callee.file_path STARTS WITH call.caller_dir THEN 1
+                        ELSE 2
+                    END
+                    LIMIT 1
+                    MERGE (caller)-[r:CALLS]->(callee)
+                    SET r.confidence = call.confidence, r.reason = call.reason
+                }}
+                "#,
+                if project_id.is_some() {
+                    ", project_id: $project_id"
+                } else {
+                    ""
+                }
+            );
+            let pid_str = project_id.map(|p| p.to_string()).unwrap_or_default();
+            for chunk in method_items.chunks(BATCH_SIZE) {
+                let q = query(&cypher)
+                    .param("items", chunk.to_vec())
+                    .param("project_id", pid_str.clone());
+                if let Err(e) = self.graph.run(q).await {
+                    tracing::warn!("batch_create_call_relationships method chunk failed: {}", e);
+                }
+            }
+        }
+
+        let items: Vec<CallItem> = calls.into_iter().map(to_item).collect();
+        if items.is_empty() {
+            return Ok(());
+        }
 
         // Phase 1: same-file match with CALL {} subquery for per-row LIMIT 1
         // Chunked to avoid OOM on large call arrays (50K+ items)
@@ -1825,7 +2198,9 @@ impl Neo4jClient {
                             MATCH (caller:Function {id: call.caller_id})
                             MATCH (callee:Function {name: call.callee_name, project_id: $project_id})
                             WHERE callee.id <> call.caller_id
-                            WITH caller, callee, call LIMIT 1
+                            WITH caller, callee, call
+                            ORDER BY CASE WHEN callee.file_path STARTS WITH call.caller_dir THEN 0 ELSE 1 END
+                            LIMIT 1
                             MERGE (caller)-[r:CALLS]->(callee)
                             SET r.confidence = call.confidence, r.reason = call.reason
                         }
@@ -2903,6 +3278,7 @@ impl Neo4jClient {
                 line: line as u32,
                 is_public,
                 docstring,
+                fields: json_list(&node, "fields"),
             });
         }
 
@@ -2932,6 +3308,308 @@ impl Neo4jClient {
         Ok(imports)
     }
 
+    /// Resolve a Function/Struct/Trait/Enum id to its file, line range and
+    /// project root
+    pub async fn get_symbol_location(&self, symbol_id: &str) -> Result<Option<SymbolLocationNode>> {
+        let q = query(
+            r#"
+            CALL {
+                MATCH (s:Function {id: $id}) RETURN s, 'function' AS kind
+                UNION
+                MATCH (s:Struct {id: $id}) RETURN s, 'struct' AS kind
+                UNION
+                MATCH (s:Trait {id: $id}) RETURN s, 'trait' AS kind
+                UNION
+                MATCH (s:Enum {id: $id}) RETURN s, 'enum' AS kind
+            }
+            OPTIONAL MATCH (f:File {path: s.file_path})
+            OPTIONAL MATCH (p:Project)-[:CONTAINS]->(f)
+            RETURN s, kind, f.language AS language, f.hash AS hash,
+                   p.id AS project_id, p.root_path AS root
+            LIMIT 1
+            "#,
+        )
+        .param("id", symbol_id);
+
+        let mut result = self.graph.execute(q).await?;
+        let Some(row) = result.next().await? else {
+            return Ok(None);
+        };
+        let node: neo4rs::Node = row.get("s")?;
+        let docstring: String = node.get("docstring").unwrap_or_default();
+        Ok(Some(SymbolLocationNode {
+            id: symbol_id.to_string(),
+            name: node.get("name")?,
+            kind: row.get("kind")?,
+            file_path: node.get("file_path")?,
+            line_start: node.get::<i64>("line_start").unwrap_or(0) as u32,
+            line_end: node.get::<i64>("line_end").unwrap_or(0) as u32,
+            language: row.get("language").ok(),
+            docstring: (!docstring.is_empty()).then_some(docstring),
+            file_hash: row.get("hash").ok(),
+            project_id: row
+                .get::<String>("project_id")
+                .ok()
+                .and_then(|id| id.parse().ok()),
+            project_root: row.get("root").ok(),
+            fields: json_list(&node, "fields"),
+            variant_payloads: json_list(&node, "variant_payloads"),
+        }))
+    }
+
+    /// Functions, structs, traits and enums defined in a file, by line.
+    pub async fn list_file_symbols(&self, path: &str) -> Result<Vec<SymbolLocationNode>> {
+        let q = query(
+            r#"
+            MATCH (f:File {path: $path})
+            OPTIONAL MATCH (p:Project)-[:CONTAINS]->(f)
+            CALL {
+                WITH f
+                MATCH (f)-[:CONTAINS]->(s:Function) RETURN s, 'function' AS kind
+                UNION
+                WITH f
+                MATCH (f)-[:CONTAINS]->(s:Struct) RETURN s, 'struct' AS kind
+                UNION
+                WITH f
+                MATCH (f)-[:CONTAINS]->(s:Trait) RETURN s, 'trait' AS kind
+                UNION
+                WITH f
+                MATCH (f)-[:CONTAINS]->(s:Enum) RETURN s, 'enum' AS kind
+            }
+            RETURN s, kind, f.language AS language, f.hash AS hash,
+                   p.id AS project_id, p.root_path AS root
+            ORDER BY s.line_start, s.name
+            "#,
+        )
+        .param("path", path);
+
+        let mut result = self.graph.execute(q).await?;
+        let mut symbols = Vec::new();
+        while let Some(row) = result.next().await? {
+            let node: neo4rs::Node = row.get("s")?;
+            let docstring: String = node.get("docstring").unwrap_or_default();
+            symbols.push(SymbolLocationNode {
+                id: node.get("id")?,
+                name: node.get("name")?,
+                kind: row.get("kind")?,
+                file_path: path.to_string(),
+                line_start: node.get::<i64>("line_start").unwrap_or(0) as u32,
+                line_end: node.get::<i64>("line_end").unwrap_or(0) as u32,
+                language: row.get("language").ok(),
+                docstring: (!docstring.is_empty()).then_some(docstring),
+                file_hash: row.get("hash").ok(),
+                project_id: row
+                    .get::<String>("project_id")
+                    .ok()
+                    .and_then(|id| id.parse().ok()),
+                project_root: row.get("root").ok(),
+                fields: json_list(&node, "fields"),
+                variant_payloads: json_list(&node, "variant_payloads"),
+            });
+        }
+        Ok(symbols)
+    }
+
+    /// Functions, structs, traits and enums with this exact name, by file
+    /// and line.
+    pub async fn find_symbols_by_name(
+        &self,
+        name: &str,
+        project_id: Option<Uuid>,
+        limit: usize,
+    ) -> Result<Vec<SymbolLocationNode>> {
+        let project_filter = if project_id.is_some() {
+            "WHERE p.id = $project_id"
+        } else {
+            ""
+        };
+        let q = query(&format!(
+            r#"
+            CALL {{
+                MATCH (s:Function {{name: $name}}) RETURN s, 'function' AS kind
+                UNION
+                MATCH (s:Struct {{name: $name}}) RETURN s, 'struct' AS kind
+                UNION
+                MATCH (s:Trait {{name: $name}}) RETURN s, 'trait' AS kind
+                UNION
+                MATCH (s:Enum {{name: $name}}) RETURN s, 'enum' AS kind
+            }}
+            OPTIONAL MATCH (f:File {{path: s.file_path}})
+            OPTIONAL MATCH (p:Project)-[:CONTAINS]->(f)
+            WITH s, kind, f, p
+            {}
+            RETURN s, kind, f.language AS language, f.hash AS hash,
+                   p.id AS project_id, p.root_path AS root
+            ORDER BY s.file_path, s.line_start
+            LIMIT $limit
+            "#,
+            project_filter
+        ))
+        .param("name", name)
+        .param(
+            "project_id",
+            project_id.map(|id| id.to_string()).unwrap_or_default(),
+        )
+        .param("limit", limit as i64);
+
+        let mut result = self.graph.execute(q).await?;
+        let mut symbols = Vec::new();
+        while let Some(row) = result.next().await? {
+            symbols.push(symbol_location_from_row(&row)?);
+        }
+        Ok(symbols)
+    }
+
+    /// Functions, structs, traits and enums whose name contains one of the
+    /// lowercase `terms`, shortest names first.
+    pub async fn find_symbols_matching(
+        &self,
+        terms: &[String],
+        project_id: Option<Uuid>,
+        limit: usize,
+    ) -> Result<Vec<SymbolLocationNode>> {
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+        let project_filter = if project_id.is_some() {
+            "WHERE p.id = $project_id"
+        } else {
+            ""
+        };
+        let q = query(&format!(
+            r#"
+            CALL {{
+                MATCH (s:Function) WHERE any(t IN $terms WHERE toLower(s.name) CONTAINS t)
+                RETURN s, 'function' AS kind
+                UNION
+                MATCH (s:Struct) WHERE any(t IN $terms WHERE toLower(s.name) CONTAINS t)
+                RETURN s, 'struct' AS kind
+                UNION
+                MATCH (s:Trait) WHERE any(t IN $terms WHERE toLower(s.name) CONTAINS t)
+                RETURN s, 'trait' AS kind
+                UNION
+                MATCH (s:Enum) WHERE any(t IN $terms WHERE toLower(s.name) CONTAINS t)
+                RETURN s, 'enum' AS kind
+            }}
+            OPTIONAL MATCH (f:File {{path: s.file_path}})
+            OPTIONAL MATCH (p:Project)-[:CONTAINS]->(f)
+            WITH s, kind, f, p
+            {}
+            RETURN s, kind, f.language AS language, f.hash AS hash,
+                   p.id AS project_id, p.root_path AS root
+            ORDER BY size(s.name), s.file_path, s.line_start
+            LIMIT $limit
+            "#,
+            project_filter
+        ))
+        .param("terms", terms.to_vec())
+        .param(
+            "project_id",
+            project_id.map(|id| id.to_string()).unwrap_or_default(),
+        )
+        .param("limit", limit as i64);
+
+        let mut result = self.graph.execute(q).await?;
+        let mut symbols = Vec::new();
+        while let Some(row) = result.next().await? {
+            symbols.push(symbol_location_from_row(&row)?);
+        }
+        Ok(symbols)
+    }
+
+    /// Code depending on one symbol, by graph id: functions calling it
+    /// (CALLS) or using it as a type (USES_TYPE), and imports naming it
+    /// (IMPORTS_SYMBOL).
+    pub async fn find_symbol_dependents(
+        &self,
+        symbol_id: &str,
+        limit: usize,
+    ) -> Result<Vec<SymbolReferenceNode>> {
+        let q = query(
+            r#"
+            CALL {
+                MATCH (s:Function {id: $id}) RETURN s
+                UNION
+                MATCH (s:Struct {id: $id}) RETURN s
+                UNION
+                MATCH (s:Trait {id: $id}) RETURN s
+                UNION
+                MATCH (s:Enum {id: $id}) RETURN s
+            }
+            CALL {
+                WITH s
+                MATCH (caller:Function)-[:CALLS]->(s)
+                RETURN 'call' AS ref_type, caller.file_path AS file_path,
+                       caller.line_start AS line, caller.name AS context
+                UNION
+                WITH s
+                MATCH (user:Function)-[:USES_TYPE]->(s)
+                RETURN 'uses_type' AS ref_type, user.file_path AS file_path,
+                       user.line_start AS line, user.name AS context
+                UNION
+                WITH s
+                MATCH (i:Import)-[:IMPORTS_SYMBOL]->(s)
+                RETURN 'import' AS ref_type, i.file_path AS file_path,
+                       i.line AS line, i.path AS context
+            }
+            RETURN ref_type, file_path, line, context
+            ORDER BY file_path, line
+            LIMIT $limit
+            "#,
+        )
+        .param("id", symbol_id)
+        .param("limit", limit as i64);
+
+        let mut result = self.graph.execute(q).await?;
+        let mut references = Vec::new();
+        while let Some(row) = result.next().await? {
+            if let (Ok(reference_type), Ok(file_path)) = (
+                row.get::<String>("ref_type"),
+                row.get::<String>("file_path"),
+            ) {
+                references.push(SymbolReferenceNode {
+                    file_path,
+                    line: row.get::<i64>("line").unwrap_or(0) as u32,
+                    context: row.get("context").unwrap_or_default(),
+                    reference_type,
+                });
+            }
+        }
+        Ok(references)
+    }
+
+    /// Get every import of a project's files
+    pub async fn list_project_imports(&self, project_id: Uuid) -> Result<Vec<ImportNode>> {
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $project_id})-[:CONTAINS]->(f:File)-[:HAS_IMPORT]->(i:Import)
+            RETURN i.path AS path, i.alias AS alias, i.items AS items,
+                   f.path AS file_path, i.line AS line
+            ORDER BY f.path, i.line
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut imports = Vec::new();
+
+        while let Some(row) = result.next().await? {
+            let Ok(path) = row.get::<String>("path") else {
+                continue;
+            };
+            let alias: String = row.get("alias").unwrap_or_default();
+            imports.push(ImportNode {
+                path,
+                alias: (!alias.is_empty()).then_some(alias),
+                items: row.get("items").unwrap_or_default(),
+                file_path: row.get("file_path")?,
+                line: row.get::<i64>("line").unwrap_or(0) as u32,
+            });
+        }
+
+        Ok(imports)
+    }
+
     /// Find references to a symbol (function callers, struct importers, file importers).
     /// When project_id is provided, results are scoped to the same project.
     pub async fn find_symbol_references(
@@ -3125,18 +3803,21 @@ impl Neo4jClient {
 
     /// Get callers chain for a function name (by name, variable depth).
     /// When project_id is provided, scopes the start function and callers to the same project.
+    /// Only paths whose CALLS edges all have a confidence of at least `min_confidence` count.
     pub async fn get_function_callers_by_name(
         &self,
         function_name: &str,
         depth: u32,
         project_id: Option<Uuid>,
+        min_confidence: f64,
     ) -> Result<Vec<String>> {
         let q = match project_id {
             Some(pid) => query(&format!(
                 r#"
                 MATCH (f:Function {{name: $name}})<-[:CONTAINS]-(:File)<-[:CONTAINS]-(p:Project {{id: $project_id}})
-                MATCH (caller:Function)-[:CALLS*1..{}]->(f)
+                MATCH path = (caller:Function)-[:CALLS*1..{}]->(f)
                 WHERE EXISTS {{ MATCH (caller)<-[:CONTAINS]-(:File)<-[:CONTAINS]-(p) }}
+                  AND ALL(r IN relationships(path) WHERE coalesce(r.confidence, 0.50) >= $min_confidence)
                 RETURN DISTINCT caller.name AS name, caller.file_path AS file
                 "#,
                 depth
@@ -3146,13 +3827,15 @@ impl Neo4jClient {
             None => query(&format!(
                 r#"
                 MATCH (f:Function {{name: $name}})
-                MATCH (caller:Function)-[:CALLS*1..{}]->(f)
+                MATCH path = (caller:Function)-[:CALLS*1..{}]->(f)
+                WHERE ALL(r IN relationships(path) WHERE coalesce(r.confidence, 0.50) >= $min_confidence)
                 RETURN DISTINCT caller.name AS name, caller.file_path AS file
                 "#,
                 depth
             ))
             .param("name", function_name),
-        };
+        }
+        .param("min_confidence", min_confidence);
 
         let mut result = self.graph.execute(q).await?;
         let mut callers = Vec::new();
@@ -3168,18 +3851,21 @@ impl Neo4jClient {
 
     /// Get callees chain for a function name (by name, variable depth).
     /// When project_id is provided, scopes the start function and callees to the same project.
+    /// Only paths whose CALLS edges all have a confidence of at least `min_confidence` count.
     pub async fn get_function_callees_by_name(
         &self,
         function_name: &str,
         depth: u32,
         project_id: Option<Uuid>,
+        min_confidence: f64,
     ) -> Result<Vec<String>> {
         let q = match project_id {
             Some(pid) => query(&format!(
                 r#"
                 MATCH (f:Function {{name: $name}})<-[:CONTAINS]-(:File)<-[:CONTAINS]-(p:Project {{id: $project_id}})
-                MATCH (f)-[:CALLS*1..{}]->(callee:Function)
+                MATCH path = (f)-[:CALLS*1..{}]->(callee:Function)
                 WHERE EXISTS {{ MATCH (callee)<-[:CONTAINS]-(:File)<-[:CONTAINS]-(p) }}
+                  AND ALL(r IN relationships(path) WHERE coalesce(r.confidence, 0.50) >= $min_confidence)
                 RETURN DISTINCT callee.name AS name, callee.file_path AS file
                 "#,
                 depth
@@ -3189,13 +3875,15 @@ impl Neo4jClient {
             None => query(&format!(
                 r#"
                 MATCH (f:Function {{name: $name}})
-                MATCH (f)-[:CALLS*1..{}]->(callee:Function)
+                MATCH path = (f)-[:CALLS*1..{}]->(callee:Function)
+                WHERE ALL(r IN relationships(path) WHERE coalesce(r.confidence, 0.50) >= $min_confidence)
                 RETURN DISTINCT callee.name AS name, callee.file_path AS file
                 "#,
                 depth
             ))
             .param("name", function_name),
-        };
+        }
+        .param("min_confidence", min_confidence);
 
         let mut result = self.graph.execute(q).await?;
         let mut callees = Vec::new();
@@ -0,0 +1,163 @@
+//! Neo4j code ownership operations (CodeOwnerRule and CodeOwner nodes)
+
+use super::client::Neo4jClient;
+use super::models::*;
+use anyhow::Result;
+use neo4rs::query;
+use std::collections::{BTreeSet, HashMap};
+use uuid::Uuid;
+
+fn string_list(values: &[String]) -> neo4rs::BoltType {
+    let items: Vec<neo4rs::BoltType> = values.iter().map(|v| v.clone().into()).collect();
+    neo4rs::BoltType::List(neo4rs::BoltList::from(items))
+}
+
+impl Neo4jClient {
+    /// Replace the CODEOWNERS rules, owners and OWNED_BY edges of a project.
+    pub async fn replace_code_owners_impl(
+        &self,
+        project_id: Uuid,
+        rules: &[CodeOwnerRule],
+        file_owners: &[(String, Vec<String>)],
+    ) -> Result<()> {
+        let q = query(
+            r#"
+            MATCH (n)
+            WHERE (n:CodeOwnerRule OR n:CodeOwner) AND n.project_id = $project_id
+            DETACH DELETE n
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+        self.graph.run(q).await?;
+
+        let rule_items: Vec<HashMap<String, neo4rs::BoltType>> = rules
+            .iter()
+            .map(|r| {
+                let mut m = HashMap::new();
+                m.insert("line".into(), (r.line as i64).into());
+                m.insert("pattern".into(), r.pattern.clone().into());
+                m.insert("owners".into(), string_list(&r.owners));
+                m
+            })
+            .collect();
+        let owners: BTreeSet<&String> = rules.iter().flat_map(|r| &r.owners).collect();
+        let owner_items: Vec<HashMap<String, neo4rs::BoltType>> = owners
+            .into_iter()
+            .map(|name| {
+                let mut m = HashMap::new();
+                m.insert("name".into(), name.clone().into());
+                m.insert("kind".into(), CodeOwnerNode::kind_of(name).into());
+                m
+            })
+            .collect();
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $project_id})
+            UNWIND $rules AS r
+            CREATE (rule:CodeOwnerRule {
+                project_id: $project_id, line: r.line, pattern: r.pattern, owners: r.owners
+            })
+            CREATE (p)-[:HAS_CODEOWNER_RULE]->(rule)
+            WITH DISTINCT p
+            UNWIND $owners AS o
+            CREATE (:CodeOwner {project_id: $project_id, name: o.name, kind: o.kind})
+            "#,
+        )
+        .param("project_id", project_id.to_string())
+        .param("rules", rule_items)
+        .param("owners", owner_items);
+        self.graph.run(q).await?;
+
+        for chunk in file_owners.chunks(500) {
+            let items: Vec<HashMap<String, neo4rs::BoltType>> = chunk
+                .iter()
+                .map(|(path, owners)| {
+                    let mut m = HashMap::new();
+                    m.insert("path".into(), path.clone().into());
+                    m.insert("owners".into(), string_list(owners));
+                    m
+                })
+                .collect();
+            let q = query(
+                r#"
+                UNWIND $items AS item
+                MATCH (f:File {path: item.path})
+                UNWIND item.owners AS name
+                MATCH (o:CodeOwner {project_id: $project_id, name: name})
+                MERGE (f)-[:OWNED_BY]->(o)
+                "#,
+            )
+            .param("project_id", project_id.to_string())
+            .param("items", items);
+            self.graph.run(q).await?;
+        }
+        Ok(())
+    }
+
+    /// CODEOWNERS rules of a project, in file order.
+    pub async fn get_code_owner_rules_impl(&self, project_id: Uuid) -> Result<Vec<CodeOwnerRule>> {
+        let q = query(
+            r#"
+            MATCH (r:CodeOwnerRule {project_id: $project_id})
+            RETURN r.line AS line, r.pattern AS pattern, r.owners AS owners
+            ORDER BY r.line
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut rules = Vec::new();
+        while let Some(row) = result.next().await? {
+            rules.push(CodeOwnerRule {
+                line: row.get::<i64>("line").unwrap_or(0) as u32,
+                pattern: row.get("pattern")?,
+                owners: row.get::<Vec<String>>("owners").unwrap_or_default(),
+            });
+        }
+        Ok(rules)
+    }
+
+    /// Owners of a project with their file counts, most files first.
+    pub async fn list_code_owners_impl(&self, project_id: Uuid) -> Result<Vec<CodeOwnerNode>> {
+        let q = query(
+            r#"
+            MATCH (o:CodeOwner {project_id: $project_id})
+            OPTIONAL MATCH (f:File)-[:OWNED_BY]->(o)
+            WITH o, count(f) AS files
+            RETURN o.name AS name, o.kind AS kind, files
+            ORDER BY files DESC, name
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut owners = Vec::new();
+        while let Some(row) = result.next().await? {
+            owners.push(CodeOwnerNode {
+                name: row.get("name")?,
+                kind: row.get("kind").unwrap_or_default(),
+                files: row.get::<i64>("files").unwrap_or(0) as usize,
+            });
+        }
+        Ok(owners)
+    }
+
+    /// Owners of a synced file.
+    pub async fn get_file_owners_impl(&self, file_path: &str) -> Result<Vec<String>> {
+        let q = query(
+            r#"
+            MATCH (:File {path: $path})-[:OWNED_BY]->(o:CodeOwner)
+            RETURN o.name AS name
+            ORDER BY name
+            "#,
+        )
+        .param("path", file_path);
+
+        let mut result = self.graph.execute(q).await?;
+        let mut owners = Vec::new();
+        while let Some(row) = result.next().await? {
+            owners.push(row.get("name")?);
+        }
+        Ok(owners)
+    }
+}
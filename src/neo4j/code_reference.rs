@@ -0,0 +1,153 @@
+//! Neo4j operations on the name-based CALLS / USES_TYPE relationships of
+//! functions, for their resolution by a language server
+
+use super::client::Neo4jClient;
+use super::models::{CodeReferenceKind, CodeReferenceNode};
+use anyhow::Result;
+use neo4rs::query;
+use uuid::Uuid;
+
+/// Relationship type of a reference kind.
+fn rel_type(kind: CodeReferenceKind) -> &'static str {
+    match kind {
+        CodeReferenceKind::Call => "CALLS",
+        CodeReferenceKind::Type => "USES_TYPE",
+    }
+}
+
+/// Subquery returning as `t` the nodes a reference kind can target among
+/// those matching `props` (a Cypher map).
+fn targets(kind: CodeReferenceKind, props: &str) -> String {
+    match kind {
+        CodeReferenceKind::Call => format!("MATCH (t:Function {}) RETURN t", props),
+        CodeReferenceKind::Type => ["Struct", "Enum", "Trait"]
+            .iter()
+            .map(|label| format!("MATCH (t:{} {}) RETURN t", label, props))
+            .collect::<Vec<_>>()
+            .join(" UNION "),
+    }
+}
+
+impl Neo4jClient {
+    /// References of the project's functions whose relationships all have a
+    /// confidence below `max_confidence`, least confident first.
+    pub async fn get_name_based_references_impl(
+        &self,
+        project_id: Uuid,
+        max_confidence: f64,
+        limit: usize,
+    ) -> Result<Vec<CodeReferenceNode>> {
+        let mut references = Vec::new();
+        for kind in [CodeReferenceKind::Call, CodeReferenceKind::Type] {
+            let cypher = format!(
+                r#"
+                MATCH (:Project {{id: $project_id}})-[:CONTAINS]->(:File)-[:CONTAINS]->(f:Function)
+                MATCH (f)-[r:{}]->(target)
+                WITH f, target.name AS name, max(coalesce(r.confidence, 0.50)) AS confidence
+                WHERE confidence < $max_confidence
+                RETURN f.id AS function_id, f.file_path AS file_path,
+                       f.line_start AS line_start, f.line_end AS line_end,
+                       name, confidence
+                ORDER BY confidence, file_path
+                LIMIT $limit
+                "#,
+                rel_type(kind)
+            );
+            let q = query(&cypher)
+                .param("project_id", project_id.to_string())
+                .param("max_confidence", max_confidence)
+                .param("limit", limit as i64);
+
+            let mut result = self.graph.execute(q).await?;
+            while let Some(row) = result.next().await? {
+                references.push(CodeReferenceNode {
+                    kind,
+                    function_id: row.get("function_id")?,
+                    file_path: row.get("file_path")?,
+                    line_start: row.get::<i64>("line_start").unwrap_or(0) as u32,
+                    line_end: row.get::<i64>("line_end").unwrap_or(0) as u32,
+                    name: row.get("name")?,
+                    confidence: row.get::<f64>("confidence").unwrap_or(0.50),
+                });
+            }
+        }
+        references.truncate(limit);
+        Ok(references)
+    }
+
+    /// Id of the symbol a reference kind can target declared on `line`
+    /// (1-based) of `file_path`, the innermost one if several start there.
+    pub async fn find_symbol_declared_at_impl(
+        &self,
+        kind: CodeReferenceKind,
+        file_path: &str,
+        line: u32,
+    ) -> Result<Option<String>> {
+        let cypher = format!(
+            r#"
+            CALL {{ {} }}
+            WITH t WHERE t.line_start = $line
+            RETURN t.id AS id
+            ORDER BY t.line_end - t.line_start
+            LIMIT 1
+            "#,
+            targets(kind, "{file_path: $file_path}")
+        );
+        let q = query(&cypher)
+            .param("file_path", file_path)
+            .param("line", line as i64);
+
+        let mut result = self.graph.execute(q).await?;
+        match result.next().await? {
+            Some(row) => Ok(Some(row.get("id")?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Point the references of `function_id` to `name` at `target_id` with
+    /// the given confidence, removing the relationships to other symbols of
+    /// that name. Without a target (the name is defined outside the project)
+    /// the relationships are all removed.
+    pub async fn resolve_code_reference_impl(
+        &self,
+        kind: CodeReferenceKind,
+        function_id: &str,
+        name: &str,
+        target_id: Option<&str>,
+        confidence: f64,
+        reason: &str,
+    ) -> Result<()> {
+        let rel = rel_type(kind);
+        let q = match target_id {
+            Some(target_id) => query(&format!(
+                r#"
+                MATCH (f:Function {{id: $function_id}})
+                OPTIONAL MATCH (f)-[old:{rel}]->(other {{name: $name}})
+                WHERE other.id <> $target_id
+                DELETE old
+                WITH DISTINCT f
+                CALL {{ {targets} }}
+                MERGE (f)-[r:{rel}]->(t)
+                SET r.confidence = $confidence, r.reason = $reason
+                "#,
+                rel = rel,
+                targets = targets(kind, "{id: $target_id}")
+            ))
+            .param("target_id", target_id)
+            .param("confidence", confidence)
+            .param("reason", reason),
+            None => query(&format!(
+                r#"
+                MATCH (f:Function {{id: $function_id}})-[old:{}]->({{name: $name}})
+                DELETE old
+                "#,
+                rel
+            )),
+        }
+        .param("function_id", function_id)
+        .param("name", name);
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,212 @@
+//! Neo4j comment operations (discussion threads on tasks, plans and notes)
+
+use super::client::Neo4jClient;
+use super::models::*;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use neo4rs::query;
+use uuid::Uuid;
+
+/// Columns returned by the comment queries, for `c` with its target `t`
+const COMMENT_COLUMNS: &str = r#"
+    c.id AS id, labels(t)[0] AS label, t.id AS entity_id, c.author AS author,
+    c.author_id AS author_id, c.content AS content, c.mentions AS mentions,
+    c.parent_id AS parent_id, toString(c.created_at) AS created_at,
+    toString(c.updated_at) AS updated_at
+"#;
+
+fn parse_comment_row(row: &neo4rs::Row) -> Result<Option<CommentNode>> {
+    let entity_type = match row.get::<String>("label")?.as_str() {
+        "Task" => CommentTarget::Task,
+        "Plan" => CommentTarget::Plan,
+        "Note" => CommentTarget::Note,
+        _ => return Ok(None),
+    };
+    let (Ok(id), Ok(entity_id)) = (
+        row.get::<String>("id")?.parse::<Uuid>(),
+        row.get::<String>("entity_id")?.parse::<Uuid>(),
+    ) else {
+        return Ok(None);
+    };
+    let parse_date = |key: &str| {
+        row.get::<String>(key)
+            .ok()
+            .and_then(|s| s.parse::<DateTime<Utc>>().ok())
+            .unwrap_or_else(Utc::now)
+    };
+    Ok(Some(CommentNode {
+        id,
+        entity_type,
+        entity_id,
+        author: row.get("author").unwrap_or_default(),
+        author_id: row.get("author_id").unwrap_or_default(),
+        content: row.get("content")?,
+        mentions: row.get("mentions").unwrap_or_default(),
+        parent_id: row
+            .get::<String>("parent_id")
+            .ok()
+            .and_then(|s| s.parse().ok()),
+        created_at: parse_date("created_at"),
+        updated_at: parse_date("updated_at"),
+    }))
+}
+
+impl Neo4jClient {
+    /// Attach a comment to its task, plan or note, linking replies to their
+    /// parent with REPLY_TO.
+    pub async fn create_comment_impl(&self, comment: &CommentNode) -> Result<bool> {
+        let q = query(&format!(
+            r#"
+            MATCH (t:{} {{id: $entity_id}})
+            CREATE (t)-[:HAS_COMMENT]->(c:Comment {{
+                id: $id,
+                author: $author,
+                author_id: $author_id,
+                content: $content,
+                mentions: $mentions,
+                parent_id: $parent_id,
+                created_at: datetime($created_at),
+                updated_at: datetime($updated_at)
+            }})
+            WITH c
+            OPTIONAL MATCH (parent:Comment {{id: $parent_id}})
+            FOREACH (_ IN CASE WHEN parent IS NULL THEN [] ELSE [1] END |
+                CREATE (c)-[:REPLY_TO]->(parent)
+            )
+            RETURN count(c) AS cnt
+            "#,
+            comment.entity_type.label()
+        ))
+        .param("id", comment.id.to_string())
+        .param("entity_id", comment.entity_id.to_string())
+        .param("author", comment.author.clone())
+        .param("author_id", comment.author_id.clone())
+        .param("content", comment.content.clone())
+        .param("mentions", comment.mentions.clone())
+        .param(
+            "parent_id",
+            comment
+                .parent_id
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+        )
+        .param("created_at", comment.created_at.to_rfc3339())
+        .param("updated_at", comment.updated_at.to_rfc3339());
+
+        let mut result = self.graph.execute(q).await?;
+        let count = match result.next().await? {
+            Some(row) => row.get::<i64>("cnt").unwrap_or(0),
+            None => 0,
+        };
+        Ok(count > 0)
+    }
+
+    /// Get a comment by ID.
+    pub async fn get_comment_impl(&self, id: Uuid) -> Result<Option<CommentNode>> {
+        let q = query(&format!(
+            "MATCH (t)-[:HAS_COMMENT]->(c:Comment {{id: $id}}) RETURN {}",
+            COMMENT_COLUMNS
+        ))
+        .param("id", id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        match result.next().await? {
+            Some(row) => parse_comment_row(&row),
+            None => Ok(None),
+        }
+    }
+
+    /// Comments of a task, plan or note, oldest first.
+    pub async fn list_comments_impl(
+        &self,
+        entity_type: CommentTarget,
+        entity_id: Uuid,
+    ) -> Result<Vec<CommentNode>> {
+        let q = query(&format!(
+            r#"
+            MATCH (t:{} {{id: $entity_id}})-[:HAS_COMMENT]->(c:Comment)
+            RETURN {}
+            ORDER BY c.created_at
+            "#,
+            entity_type.label(),
+            COMMENT_COLUMNS
+        ))
+        .param("entity_id", entity_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut comments = Vec::new();
+        while let Some(row) = result.next().await? {
+            comments.extend(parse_comment_row(&row)?);
+        }
+        Ok(comments)
+    }
+
+    /// Comments mentioning a handle, most recent first.
+    pub async fn list_comments_mentioning_impl(
+        &self,
+        handle: &str,
+        limit: usize,
+    ) -> Result<Vec<CommentNode>> {
+        let q = query(&format!(
+            r#"
+            MATCH (t)-[:HAS_COMMENT]->(c:Comment)
+            WHERE $handle IN c.mentions
+            RETURN {}
+            ORDER BY c.created_at DESC
+            LIMIT $limit
+            "#,
+            COMMENT_COLUMNS
+        ))
+        .param("handle", handle.to_lowercase())
+        .param("limit", limit as i64);
+
+        let mut result = self.graph.execute(q).await?;
+        let mut comments = Vec::new();
+        while let Some(row) = result.next().await? {
+            comments.extend(parse_comment_row(&row)?);
+        }
+        Ok(comments)
+    }
+
+    /// Save the content and mentions of a comment.
+    pub async fn update_comment_impl(&self, comment: &CommentNode) -> Result<()> {
+        let q = query(
+            r#"
+            MATCH (c:Comment {id: $id})
+            SET c.content = $content,
+                c.mentions = $mentions,
+                c.updated_at = datetime($updated_at)
+            "#,
+        )
+        .param("id", comment.id.to_string())
+        .param("content", comment.content.clone())
+        .param("mentions", comment.mentions.clone())
+        .param("updated_at", comment.updated_at.to_rfc3339());
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
+    /// Delete a comment and, transitively, its replies.
+    pub async fn delete_comment_impl(&self, id: Uuid) -> Result<usize> {
+        let q = query(
+            r#"
+            MATCH (c:Comment {id: $id})
+            OPTIONAL MATCH (r:Comment)-[:REPLY_TO*]->(c)
+            WITH c, collect(DISTINCT r) AS replies
+            WITH c, replies, size(replies) + 1 AS deleted
+            FOREACH (r IN replies | DETACH DELETE r)
+            DETACH DELETE c
+            RETURN deleted
+            "#,
+        )
+        .param("id", id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let deleted: i64 = match result.next().await? {
+            Some(row) => row.get("deleted").unwrap_or(0),
+            None => 0,
+        };
+        Ok(deleted as usize)
+    }
+}
@@ -116,6 +116,56 @@ impl Neo4jClient {
         Ok(commits)
     }
 
+    /// Link a commit to the task it implements (IMPLEMENTED_BY relationship).
+    /// The session that produced the commit is kept on the first link.
+    pub async fn link_commit_implements_task(
+        &self,
+        commit_hash: &str,
+        task_id: Uuid,
+        session_id: Option<Uuid>,
+    ) -> Result<()> {
+        let q = query(
+            r#"
+            MATCH (t:Task {id: $task_id})
+            MATCH (c:Commit {hash: $hash})
+            MERGE (t)-[r:IMPLEMENTED_BY]->(c)
+            ON CREATE SET r.linked_at = datetime(),
+                          r.session_id = CASE WHEN $session_id = '' THEN null ELSE $session_id END
+            "#,
+        )
+        .param("task_id", task_id.to_string())
+        .param("hash", commit_hash)
+        .param(
+            "session_id",
+            session_id.map(|id| id.to_string()).unwrap_or_default(),
+        );
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
+    /// Get the commits implementing a task
+    pub async fn get_task_implementing_commits(&self, task_id: Uuid) -> Result<Vec<CommitNode>> {
+        let q = query(
+            r#"
+            MATCH (t:Task {id: $task_id})-[:IMPLEMENTED_BY]->(c:Commit)
+            RETURN c
+            ORDER BY c.timestamp DESC
+            "#,
+        )
+        .param("task_id", task_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut commits = Vec::new();
+
+        while let Some(row) = result.next().await? {
+            let node: neo4rs::Node = row.get("c")?;
+            commits.push(self.node_to_commit(&node)?);
+        }
+
+        Ok(commits)
+    }
+
     /// Get commits for a plan
     pub async fn get_plan_commits(&self, plan_id: Uuid) -> Result<Vec<CommitNode>> {
         let q = query(
@@ -0,0 +1,193 @@
+//! Neo4j ConfigKey & ConfigFile operations (configuration cross-references)
+
+use super::client::Neo4jClient;
+use super::models::*;
+use anyhow::Result;
+use neo4rs::query;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+fn locations(paths: Vec<String>, lines: Vec<i64>) -> Vec<ConfigKeyLocation> {
+    paths
+        .into_iter()
+        .zip(lines)
+        .map(|(file_path, line)| ConfigKeyLocation {
+            file_path,
+            line: line as u32,
+        })
+        .collect()
+}
+
+impl Neo4jClient {
+    /// Replace the configuration keys of a project.
+    ///
+    /// Locations are stored twice: as parallel lists on the `(:ConfigKey)`
+    /// node, read back by [`Self::list_project_config_keys_impl`], and as
+    /// DEFINED_IN / READS_CONFIG edges for graph traversals. READS_CONFIG
+    /// starts from the `(:File)` when the reading file is indexed code, from a
+    /// `(:ConfigFile)` otherwise.
+    pub async fn replace_project_config_keys_impl(
+        &self,
+        project_id: Uuid,
+        keys: &[ConfigKeyNode],
+    ) -> Result<()> {
+        let q = query(
+            r#"
+            OPTIONAL MATCH (k:ConfigKey {project_id: $project_id})
+            DETACH DELETE k
+            WITH count(*) AS deleted
+            OPTIONAL MATCH (f:ConfigFile {project_id: $project_id})
+            DETACH DELETE f
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+        self.graph.run(q).await?;
+
+        if keys.is_empty() {
+            return Ok(());
+        }
+
+        let items: Vec<HashMap<String, neo4rs::BoltType>> = keys
+            .iter()
+            .map(|k| {
+                let mut m = HashMap::new();
+                m.insert("key".into(), k.key.clone().into());
+                m.insert("kind".into(), k.kind.to_string().into());
+                let (paths, lines): (Vec<String>, Vec<i64>) = k
+                    .defined_in
+                    .iter()
+                    .map(|l| (l.file_path.clone(), l.line as i64))
+                    .unzip();
+                m.insert("defined_paths".into(), paths.into());
+                m.insert("defined_lines".into(), lines.into());
+                let (paths, lines): (Vec<String>, Vec<i64>) = k
+                    .referenced_by
+                    .iter()
+                    .map(|l| (l.file_path.clone(), l.line as i64))
+                    .unzip();
+                m.insert("reference_paths".into(), paths.into());
+                m.insert("reference_lines".into(), lines.into());
+                m
+            })
+            .collect();
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $project_id})
+            UNWIND $items AS item
+            CREATE (k:ConfigKey {
+                project_id: $project_id,
+                key: item.key,
+                kind: item.kind,
+                defined_paths: item.defined_paths,
+                defined_lines: item.defined_lines,
+                reference_paths: item.reference_paths,
+                reference_lines: item.reference_lines
+            })
+            CREATE (p)-[:HAS_CONFIG_KEY]->(k)
+            "#,
+        )
+        .param("project_id", project_id.to_string())
+        .param("items", items);
+        self.graph.run(q).await?;
+
+        let definitions: Vec<HashMap<String, neo4rs::BoltType>> = keys
+            .iter()
+            .flat_map(|k| {
+                k.defined_in.iter().map(|l| {
+                    let mut m = HashMap::new();
+                    m.insert("key".into(), k.key.clone().into());
+                    m.insert("path".into(), l.file_path.clone().into());
+                    m.insert("line".into(), (l.line as i64).into());
+                    m
+                })
+            })
+            .collect();
+        if !definitions.is_empty() {
+            let q = query(
+                r#"
+                UNWIND $items AS item
+                MATCH (k:ConfigKey {project_id: $project_id, key: item.key})
+                MERGE (f:ConfigFile {project_id: $project_id, path: item.path})
+                CREATE (k)-[:DEFINED_IN {line: item.line}]->(f)
+                "#,
+            )
+            .param("project_id", project_id.to_string())
+            .param("items", definitions);
+            self.graph.run(q).await?;
+        }
+
+        let references: Vec<HashMap<String, neo4rs::BoltType>> = keys
+            .iter()
+            .flat_map(|k| {
+                k.referenced_by.iter().map(|l| {
+                    let mut m = HashMap::new();
+                    m.insert("key".into(), k.key.clone().into());
+                    m.insert("path".into(), l.file_path.clone().into());
+                    m.insert("line".into(), (l.line as i64).into());
+                    m
+                })
+            })
+            .collect();
+        if !references.is_empty() {
+            let q = query(
+                r#"
+                UNWIND $items AS item
+                MATCH (k:ConfigKey {project_id: $project_id, key: item.key})
+                OPTIONAL MATCH (file:File {path: item.path})
+                FOREACH (_ IN CASE WHEN file IS NOT NULL THEN [1] ELSE [] END |
+                    CREATE (file)-[:READS_CONFIG {line: item.line}]->(k)
+                )
+                FOREACH (_ IN CASE WHEN file IS NULL THEN [1] ELSE [] END |
+                    MERGE (f:ConfigFile {project_id: $project_id, path: item.path})
+                    CREATE (f)-[:READS_CONFIG {line: item.line}]->(k)
+                )
+                "#,
+            )
+            .param("project_id", project_id.to_string())
+            .param("items", references);
+            self.graph.run(q).await?;
+        }
+
+        Ok(())
+    }
+
+    /// List the configuration keys of a project ordered by key, optionally
+    /// restricted to keys containing `filter` (case-insensitive).
+    pub async fn list_project_config_keys_impl(
+        &self,
+        project_id: Uuid,
+        filter: Option<&str>,
+    ) -> Result<Vec<ConfigKeyNode>> {
+        let q = query(
+            r#"
+            MATCH (k:ConfigKey {project_id: $project_id})
+            WHERE $filter = '' OR toLower(k.key) CONTAINS toLower($filter)
+            RETURN k
+            ORDER BY k.key
+            "#,
+        )
+        .param("project_id", project_id.to_string())
+        .param("filter", filter.unwrap_or_default().to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut keys = Vec::new();
+        while let Some(row) = result.next().await? {
+            let node: neo4rs::Node = row.get("k")?;
+            let kind: String = node.get("kind").unwrap_or_default();
+            keys.push(ConfigKeyNode {
+                key: node.get("key")?,
+                project_id,
+                kind: kind.parse().unwrap_or(ConfigKeyKind::Env),
+                defined_in: locations(
+                    node.get("defined_paths").unwrap_or_default(),
+                    node.get("defined_lines").unwrap_or_default(),
+                ),
+                referenced_by: locations(
+                    node.get("reference_paths").unwrap_or_default(),
+                    node.get("reference_lines").unwrap_or_default(),
+                ),
+            });
+        }
+        Ok(keys)
+    }
+}
@@ -3,9 +3,36 @@
 use super::client::Neo4jClient;
 use super::models::*;
 use anyhow::Result;
-use neo4rs::query;
+use neo4rs::{query, Query};
 use uuid::Uuid;
 
+/// Query creating a constraint for a plan
+pub(crate) fn create_constraint_query(plan_id: Uuid, constraint: &ConstraintNode) -> Query {
+    query(
+        r#"
+        MATCH (p:Plan {id: $plan_id})
+        CREATE (c:Constraint {
+            id: $id,
+            constraint_type: $constraint_type,
+            description: $description,
+            enforced_by: $enforced_by
+        })
+        CREATE (p)-[:CONSTRAINED_BY]->(c)
+        "#,
+    )
+    .param("plan_id", plan_id.to_string())
+    .param("id", constraint.id.to_string())
+    .param(
+        "constraint_type",
+        format!("{:?}", constraint.constraint_type),
+    )
+    .param("description", constraint.description.clone())
+    .param(
+        "enforced_by",
+        constraint.enforced_by.clone().unwrap_or_default(),
+    )
+}
+
 impl Neo4jClient {
     // ========================================================================
     // Constraint operations
@@ -17,31 +44,9 @@ impl Neo4jClient {
         plan_id: Uuid,
         constraint: &ConstraintNode,
     ) -> Result<()> {
-        let q = query(
-            r#"
-            MATCH (p:Plan {id: $plan_id})
-            CREATE (c:Constraint {
-                id: $id,
-                constraint_type: $constraint_type,
-                description: $description,
-                enforced_by: $enforced_by
-            })
-            CREATE (p)-[:CONSTRAINED_BY]->(c)
-            "#,
-        )
-        .param("plan_id", plan_id.to_string())
-        .param("id", constraint.id.to_string())
-        .param(
-            "constraint_type",
-            format!("{:?}", constraint.constraint_type),
-        )
-        .param("description", constraint.description.clone())
-        .param(
-            "enforced_by",
-            constraint.enforced_by.clone().unwrap_or_default(),
-        );
-
-        self.graph.run(q).await?;
+        self.graph
+            .run(create_constraint_query(plan_id, constraint))
+            .await?;
         Ok(())
     }
 
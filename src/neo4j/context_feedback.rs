@@ -0,0 +1,117 @@
+//! Neo4j context feedback operations (ContextFeedback nodes)
+
+use super::client::Neo4jClient;
+use super::models::*;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use neo4rs::query;
+use uuid::Uuid;
+
+impl Neo4jClient {
+    /// Record a vote on a context item of a chat session, replacing the
+    /// session's previous vote on the same item.
+    pub async fn upsert_context_feedback_impl(
+        &self,
+        feedback: &ContextFeedbackNode,
+    ) -> Result<bool> {
+        let q = query(
+            r#"
+            MATCH (s:ChatSession {id: $session_id})
+            MERGE (s)-[:HAS_CONTEXT_FEEDBACK]->(f:ContextFeedback {
+                session_id: $session_id, item_type: $item_type, item_id: $item_id
+            })
+            SET f.helpful = $helpful,
+                f.comment = $comment,
+                f.created_at = datetime($created_at)
+            RETURN count(f) AS cnt
+            "#,
+        )
+        .param("session_id", feedback.session_id.to_string())
+        .param("item_type", feedback.item_type.as_str())
+        .param("item_id", feedback.item_id.clone())
+        .param("helpful", feedback.helpful)
+        .param("comment", feedback.comment.clone().unwrap_or_default())
+        .param("created_at", feedback.created_at.to_rfc3339());
+
+        let mut result = self.graph.execute(q).await?;
+        let count = match result.next().await? {
+            Some(row) => row.get::<i64>("cnt").unwrap_or(0),
+            None => 0,
+        };
+        Ok(count > 0)
+    }
+
+    /// Votes recorded in a chat session, most recent first.
+    pub async fn list_context_feedback_impl(
+        &self,
+        session_id: Uuid,
+    ) -> Result<Vec<ContextFeedbackNode>> {
+        let q = query(
+            r#"
+            MATCH (:ChatSession {id: $session_id})-[:HAS_CONTEXT_FEEDBACK]->(f:ContextFeedback)
+            RETURN f.item_type AS item_type, f.item_id AS item_id, f.helpful AS helpful,
+                   f.comment AS comment, toString(f.created_at) AS created_at
+            ORDER BY f.created_at DESC
+            "#,
+        )
+        .param("session_id", session_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut feedback = Vec::new();
+        while let Some(row) = result.next().await? {
+            let item_type: String = row.get("item_type")?;
+            let Ok(item_type) = item_type.parse() else {
+                continue;
+            };
+            feedback.push(ContextFeedbackNode {
+                session_id,
+                item_type,
+                item_id: row.get("item_id")?,
+                helpful: row.get("helpful").unwrap_or(false),
+                comment: row.get::<String>("comment").ok().filter(|c| !c.is_empty()),
+                created_at: row
+                    .get::<String>("created_at")
+                    .ok()
+                    .and_then(|s| s.parse::<DateTime<Utc>>().ok())
+                    .unwrap_or_else(Utc::now),
+            });
+        }
+        Ok(feedback)
+    }
+
+    /// Votes of all sessions on the given notes.
+    pub async fn get_note_relevance_votes_impl(
+        &self,
+        note_ids: &[Uuid],
+    ) -> Result<Vec<NoteRelevanceVotes>> {
+        if note_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let ids: Vec<String> = note_ids.iter().map(|id| id.to_string()).collect();
+        let q = query(
+            r#"
+            MATCH (f:ContextFeedback {item_type: 'note'})
+            WHERE f.item_id IN $ids
+            RETURN f.item_id AS note_id,
+                   sum(CASE WHEN f.helpful THEN 1 ELSE 0 END) AS helpful,
+                   sum(CASE WHEN f.helpful THEN 0 ELSE 1 END) AS irrelevant
+            "#,
+        )
+        .param("ids", ids);
+
+        let mut result = self.graph.execute(q).await?;
+        let mut votes = Vec::new();
+        while let Some(row) = result.next().await? {
+            let note_id: String = row.get("note_id")?;
+            let Ok(note_id) = note_id.parse() else {
+                continue;
+            };
+            votes.push(NoteRelevanceVotes {
+                note_id,
+                helpful: row.get::<i64>("helpful").unwrap_or(0) as u32,
+                irrelevant: row.get::<i64>("irrelevant").unwrap_or(0) as u32,
+            });
+        }
+        Ok(votes)
+    }
+}
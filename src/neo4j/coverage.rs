@@ -0,0 +1,186 @@
+//! Neo4j test coverage operations (`coverage_pct` on Function and File nodes)
+
+use super::client::Neo4jClient;
+use super::models::*;
+use anyhow::Result;
+use neo4rs::query;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+fn coverage_items(updates: &[CoverageUpdate]) -> Vec<HashMap<String, neo4rs::BoltType>> {
+    updates
+        .iter()
+        .map(|u| {
+            let mut m = HashMap::new();
+            m.insert("id".into(), u.id.clone().into());
+            m.insert("covered".into(), (u.covered_lines as i64).into());
+            m.insert("instrumented".into(), (u.instrumented_lines as i64).into());
+            m.insert("pct".into(), u.coverage_pct.into());
+            m
+        })
+        .collect()
+}
+
+fn untested_from_row(row: &neo4rs::Row) -> Result<UntestedFunctionRow> {
+    Ok(UntestedFunctionRow {
+        id: row.get("id")?,
+        name: row.get("name").unwrap_or_default(),
+        file_path: row.get("file_path").unwrap_or_default(),
+        line_start: row.get::<i64>("line_start").unwrap_or(0) as u32,
+        coverage_pct: row.get("coverage_pct").unwrap_or(0.0),
+        pagerank: row.get("pagerank").unwrap_or(0.0),
+    })
+}
+
+impl Neo4jClient {
+    /// Id, name and line range of every function of a project.
+    pub async fn get_project_function_spans_impl(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<FunctionSpanRow>> {
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $project_id})-[:CONTAINS]->(file:File)-[:CONTAINS]->(f:Function)
+            RETURN f.id AS id, f.name AS name, file.path AS file_path,
+                   f.line_start AS line_start, f.line_end AS line_end
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut spans = Vec::new();
+        while let Some(row) = result.next().await? {
+            spans.push(FunctionSpanRow {
+                id: row.get("id")?,
+                name: row.get("name").unwrap_or_default(),
+                file_path: row.get("file_path").unwrap_or_default(),
+                line_start: row.get::<i64>("line_start").unwrap_or(0) as u32,
+                line_end: row.get::<i64>("line_end").unwrap_or(0) as u32,
+            });
+        }
+        Ok(spans)
+    }
+
+    /// Clear the coverage of `file_paths` and their functions, then store
+    /// the new one.
+    pub async fn batch_set_coverage_impl(
+        &self,
+        file_paths: &[String],
+        functions: &[CoverageUpdate],
+        files: &[CoverageUpdate],
+    ) -> Result<()> {
+        if file_paths.is_empty() {
+            return Ok(());
+        }
+
+        // Functions the new report no longer instruments must not keep an old value
+        let clear = query(
+            r#"
+            UNWIND $paths AS path
+            MATCH (file:File {path: path})
+            REMOVE file.coverage_pct, file.covered_lines, file.instrumented_lines
+            WITH file
+            MATCH (file)-[:CONTAINS]->(f:Function)
+            WHERE f.coverage_pct IS NOT NULL
+            REMOVE f.coverage_pct, f.covered_lines, f.instrumented_lines
+            "#,
+        )
+        .param("paths", file_paths.to_vec());
+        self.graph.run(clear).await?;
+
+        for (label, key, updates) in [("Function", "id", functions), ("File", "path", files)] {
+            for chunk in updates.chunks(1000) {
+                let q = query(&format!(
+                    r#"
+                    UNWIND $items AS u
+                    MATCH (n:{label} {{{key}: u.id}})
+                    SET n.coverage_pct = u.pct,
+                        n.covered_lines = u.covered,
+                        n.instrumented_lines = u.instrumented,
+                        n.coverage_updated_at = datetime()
+                    "#
+                ))
+                .param("items", coverage_items(chunk));
+                self.graph.run(q).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Functions of a project covered below `max_coverage_pct`, highest
+    /// PageRank first.
+    pub async fn get_untested_functions_impl(
+        &self,
+        project_id: Uuid,
+        max_coverage_pct: f64,
+        limit: usize,
+    ) -> Result<Vec<UntestedFunctionRow>> {
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $project_id})-[:CONTAINS]->(file:File)-[:CONTAINS]->(f:Function)
+            WHERE f.coverage_pct IS NOT NULL AND f.coverage_pct < $max_pct
+            RETURN f.id AS id, f.name AS name, file.path AS file_path,
+                   f.line_start AS line_start, f.coverage_pct AS coverage_pct,
+                   COALESCE(f.pagerank, 0.0) AS pagerank
+            ORDER BY pagerank DESC, coverage_pct ASC
+            LIMIT $limit
+            "#,
+        )
+        .param("project_id", project_id.to_string())
+        .param("max_pct", max_coverage_pct)
+        .param("limit", limit as i64);
+
+        let mut result = self.graph.execute(q).await?;
+        let mut rows = Vec::new();
+        while let Some(row) = result.next().await? {
+            rows.push(untested_from_row(&row)?);
+        }
+        Ok(rows)
+    }
+
+    /// Coverage of a file, with its functions covered below
+    /// `max_coverage_pct`.
+    pub async fn get_file_coverage_impl(
+        &self,
+        file_path: &str,
+        max_coverage_pct: f64,
+    ) -> Result<Option<FileCoverageRow>> {
+        let q = query(
+            r#"
+            MATCH (file:File {path: $path})
+            WHERE file.coverage_pct IS NOT NULL
+            RETURN file.coverage_pct AS coverage_pct, file.covered_lines AS covered,
+                   file.instrumented_lines AS instrumented
+            "#,
+        )
+        .param("path", file_path);
+        let mut result = self.graph.execute(q).await?;
+        let Some(row) = result.next().await? else {
+            return Ok(None);
+        };
+        let mut coverage = FileCoverageRow {
+            covered_lines: row.get::<i64>("covered").unwrap_or(0) as u32,
+            instrumented_lines: row.get::<i64>("instrumented").unwrap_or(0) as u32,
+            coverage_pct: row.get("coverage_pct").unwrap_or(0.0),
+            untested_functions: Vec::new(),
+        };
+
+        let q = query(
+            r#"
+            MATCH (file:File {path: $path})-[:CONTAINS]->(f:Function)
+            WHERE f.coverage_pct IS NOT NULL AND f.coverage_pct < $max_pct
+            RETURN f.id AS id, f.name AS name, file.path AS file_path,
+                   f.line_start AS line_start, f.coverage_pct AS coverage_pct,
+                   COALESCE(f.pagerank, 0.0) AS pagerank
+            ORDER BY pagerank DESC, coverage_pct ASC
+            "#,
+        )
+        .param("path", file_path)
+        .param("max_pct", max_coverage_pct);
+        let mut result = self.graph.execute(q).await?;
+        while let Some(row) = result.next().await? {
+            coverage.untested_functions.push(untested_from_row(&row)?);
+        }
+        Ok(Some(coverage))
+    }
+}
@@ -0,0 +1,290 @@
+//! Neo4j Table, Column & Migration operations (SQL schema)
+
+use super::client::Neo4jClient;
+use super::models::*;
+use anyhow::Result;
+use neo4rs::query;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+impl Neo4jClient {
+    /// Replace the tables, columns and migrations of a project.
+    pub async fn replace_project_schema_impl(
+        &self,
+        project_id: Uuid,
+        tables: &[TableNode],
+        migrations: &[MigrationNode],
+    ) -> Result<()> {
+        let q = query(
+            r#"
+            MATCH (t:Table {project_id: $project_id})
+            OPTIONAL MATCH (t)-[:HAS_COLUMN]->(c:Column)
+            DETACH DELETE c, t
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+        self.graph.run(q).await?;
+
+        let q = query(
+            r#"
+            MATCH (m:Migration {project_id: $project_id})
+            DETACH DELETE m
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+        self.graph.run(q).await?;
+
+        if !tables.is_empty() {
+            let items: Vec<HashMap<String, neo4rs::BoltType>> = tables
+                .iter()
+                .map(|t| {
+                    let mut m = HashMap::new();
+                    m.insert("name".into(), t.name.clone().into());
+                    m.insert(
+                        "defined_in".into(),
+                        t.defined_in.clone().unwrap_or_default().into(),
+                    );
+                    m.insert("altered_by".into(), t.altered_by.clone().into());
+                    m
+                })
+                .collect();
+            let q = query(
+                r#"
+                MATCH (p:Project {id: $project_id})
+                UNWIND $items AS item
+                CREATE (t:Table {
+                    project_id: $project_id,
+                    name: item.name,
+                    defined_in: item.defined_in,
+                    altered_by: item.altered_by
+                })
+                CREATE (p)-[:HAS_TABLE]->(t)
+                "#,
+            )
+            .param("project_id", project_id.to_string())
+            .param("items", items);
+            self.graph.run(q).await?;
+
+            let columns: Vec<HashMap<String, neo4rs::BoltType>> = tables
+                .iter()
+                .flat_map(|t| {
+                    t.columns.iter().enumerate().map(|(ordinal, c)| {
+                        let mut m = HashMap::new();
+                        m.insert("table".into(), t.name.clone().into());
+                        m.insert("name".into(), c.name.clone().into());
+                        m.insert("ordinal".into(), (ordinal as i64).into());
+                        m.insert("data_type".into(), c.data_type.clone().into());
+                        m.insert("nullable".into(), c.nullable.into());
+                        m.insert("primary_key".into(), c.primary_key.into());
+                        m.insert(
+                            "default_value".into(),
+                            c.default_value.clone().unwrap_or_default().into(),
+                        );
+                        m.insert(
+                            "references".into(),
+                            c.references.clone().unwrap_or_default().into(),
+                        );
+                        m
+                    })
+                })
+                .collect();
+            if !columns.is_empty() {
+                let q = query(
+                    r#"
+                    UNWIND $items AS item
+                    MATCH (t:Table {project_id: $project_id, name: item.table})
+                    CREATE (c:Column {
+                        project_id: $project_id,
+                        table: item.table,
+                        name: item.name,
+                        ordinal: item.ordinal,
+                        data_type: item.data_type,
+                        nullable: item.nullable,
+                        primary_key: item.primary_key,
+                        default_value: item.default_value,
+                        references: item.references
+                    })
+                    CREATE (t)-[:HAS_COLUMN]->(c)
+                    "#,
+                )
+                .param("project_id", project_id.to_string())
+                .param("items", columns);
+                self.graph.run(q).await?;
+            }
+
+            // Foreign keys as Column-[:REFERENCES]->Column
+            let references: Vec<HashMap<String, neo4rs::BoltType>> = tables
+                .iter()
+                .flat_map(|t| {
+                    t.columns.iter().filter_map(|c| {
+                        let (target_table, target_column) =
+                            c.references.as_deref()?.rsplit_once('.')?;
+                        let mut m = HashMap::new();
+                        m.insert("table".into(), t.name.clone().into());
+                        m.insert("column".into(), c.name.clone().into());
+                        m.insert("target_table".into(), target_table.to_string().into());
+                        m.insert("target_column".into(), target_column.to_string().into());
+                        Some(m)
+                    })
+                })
+                .collect();
+            if !references.is_empty() {
+                let q = query(
+                    r#"
+                    UNWIND $items AS item
+                    MATCH (c:Column {project_id: $project_id, table: item.table, name: item.column})
+                    MATCH (target:Column {project_id: $project_id, table: item.target_table, name: item.target_column})
+                    MERGE (c)-[:REFERENCES]->(target)
+                    "#,
+                )
+                .param("project_id", project_id.to_string())
+                .param("items", references);
+                self.graph.run(q).await?;
+            }
+        }
+
+        if migrations.is_empty() {
+            return Ok(());
+        }
+
+        let items: Vec<HashMap<String, neo4rs::BoltType>> = migrations
+            .iter()
+            .map(|mig| {
+                let mut m = HashMap::new();
+                m.insert("path".into(), mig.path.clone().into());
+                m.insert("version".into(), mig.version.clone().into());
+                m.insert("ordinal".into(), (mig.ordinal as i64).into());
+                m
+            })
+            .collect();
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $project_id})
+            UNWIND $items AS item
+            CREATE (m:Migration {
+                project_id: $project_id,
+                path: item.path,
+                version: item.version,
+                ordinal: item.ordinal
+            })
+            CREATE (p)-[:HAS_MIGRATION]->(m)
+            "#,
+        )
+        .param("project_id", project_id.to_string())
+        .param("items", items);
+        self.graph.run(q).await?;
+
+        let alters: Vec<HashMap<String, neo4rs::BoltType>> = migrations
+            .iter()
+            .flat_map(|mig| {
+                mig.alters.iter().map(|a| {
+                    let mut m = HashMap::new();
+                    m.insert("path".into(), mig.path.clone().into());
+                    m.insert("table".into(), a.table.clone().into());
+                    m.insert("operations".into(), a.operations.clone().into());
+                    m
+                })
+            })
+            .collect();
+        if !alters.is_empty() {
+            let q = query(
+                r#"
+                UNWIND $items AS item
+                MATCH (m:Migration {project_id: $project_id, path: item.path})
+                MATCH (t:Table {project_id: $project_id, name: item.table})
+                CREATE (m)-[:ALTERS {operations: item.operations}]->(t)
+                "#,
+            )
+            .param("project_id", project_id.to_string())
+            .param("items", alters);
+            self.graph.run(q).await?;
+        }
+
+        Ok(())
+    }
+
+    /// List the tables of a project with their columns, ordered by name.
+    pub async fn list_project_tables_impl(&self, project_id: Uuid) -> Result<Vec<TableNode>> {
+        let q = query(
+            r#"
+            MATCH (t:Table {project_id: $project_id})
+            OPTIONAL MATCH (t)-[:HAS_COLUMN]->(c:Column)
+            WITH t, c
+            ORDER BY c.ordinal
+            RETURN t, collect(c) AS columns
+            ORDER BY t.name
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut tables = Vec::new();
+        while let Some(row) = result.next().await? {
+            let node: neo4rs::Node = row.get("t")?;
+            let column_nodes: Vec<neo4rs::Node> = row.get("columns").unwrap_or_default();
+            let defined_in: String = node.get("defined_in").unwrap_or_default();
+            tables.push(TableNode {
+                name: node.get("name")?,
+                project_id,
+                defined_in: (!defined_in.is_empty()).then_some(defined_in),
+                altered_by: node.get("altered_by").unwrap_or_default(),
+                columns: column_nodes
+                    .iter()
+                    .map(|c| {
+                        let default_value: String = c.get("default_value").unwrap_or_default();
+                        let references: String = c.get("references").unwrap_or_default();
+                        ColumnNode {
+                            name: c.get("name").unwrap_or_default(),
+                            data_type: c.get("data_type").unwrap_or_default(),
+                            nullable: c.get("nullable").unwrap_or(true),
+                            primary_key: c.get("primary_key").unwrap_or(false),
+                            default_value: (!default_value.is_empty()).then_some(default_value),
+                            references: (!references.is_empty()).then_some(references),
+                        }
+                    })
+                    .collect(),
+            });
+        }
+        Ok(tables)
+    }
+
+    /// List the migrations of a project in apply order, with the tables they
+    /// alter that still exist.
+    pub async fn list_project_migrations_impl(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<MigrationNode>> {
+        let q = query(
+            r#"
+            MATCH (m:Migration {project_id: $project_id})
+            OPTIONAL MATCH (m)-[r:ALTERS]->(t:Table)
+            WITH m, r, t
+            ORDER BY t.name
+            RETURN m, collect(t.name) AS tables, collect(r.operations) AS operations
+            ORDER BY m.ordinal
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut migrations = Vec::new();
+        while let Some(row) = result.next().await? {
+            let node: neo4rs::Node = row.get("m")?;
+            let tables: Vec<String> = row.get("tables").unwrap_or_default();
+            let operations: Vec<Vec<String>> = row.get("operations").unwrap_or_default();
+            let alters = tables
+                .into_iter()
+                .zip(operations)
+                .map(|(table, operations)| MigrationAlter { table, operations })
+                .collect();
+            migrations.push(MigrationNode {
+                path: node.get("path")?,
+                version: node.get("version").unwrap_or_default(),
+                ordinal: node.get::<i64>("ordinal").unwrap_or(0) as usize,
+                project_id,
+                alters,
+            });
+        }
+        Ok(migrations)
+    }
+}
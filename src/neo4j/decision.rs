@@ -479,7 +479,9 @@ impl Neo4jClient {
             MATCH (proj:Project)-[:HAS_PLAN]->(plan:Plan)
                   -[:HAS_TASK]->(task:Task)-[:INFORMED_BY]->(d:Decision {id: $id})
             RETURN proj.id AS project_id
-            LIMIT 1
+            UNION
+            MATCH (proj:Project)-[:HAS_ADR]->(d:Decision {id: $id})
+            RETURN proj.id AS project_id
             "#,
         )
         .param("id", decision_id.to_string());
@@ -747,6 +749,125 @@ impl Neo4jClient {
         Ok(entries)
     }
 
+    // ========================================================================
+    // ADR files (Project -[:HAS_ADR]-> Decision)
+    // ========================================================================
+
+    /// Decisions of a project, oldest first: the ones informing its tasks and
+    /// the ones imported from its ADR files, with the ADR documenting each.
+    pub async fn list_project_decisions(&self, project_id: Uuid) -> Result<Vec<ProjectDecision>> {
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $project_id})
+            CALL {
+                WITH p
+                MATCH (p)-[:HAS_PLAN]->(:Plan)-[:HAS_TASK]->(t:Task)-[:INFORMED_BY]->(d:Decision)
+                RETURN d, t.id AS task_id
+                UNION
+                WITH p
+                MATCH (p)-[:HAS_ADR]->(d:Decision)
+                WHERE NOT EXISTS { MATCH (:Task)-[:INFORMED_BY]->(d) }
+                RETURN d, null AS task_id
+            }
+            OPTIONAL MATCH (p)-[a:HAS_ADR]->(d)
+            RETURN d, task_id, a.number AS adr_number, a.path AS adr_path
+            ORDER BY d.decided_at, d.id
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut decisions = Vec::new();
+        while let Some(row) = result.next().await? {
+            let node: neo4rs::Node = row.get("d")?;
+            let adr = match (
+                row.get::<i64>("adr_number").ok(),
+                row.get::<String>("adr_path").ok(),
+            ) {
+                (Some(number), Some(path)) => Some(AdrLink {
+                    number: number as u32,
+                    path,
+                }),
+                _ => None,
+            };
+            decisions.push(ProjectDecision {
+                decision: Self::node_to_decision(&node)?,
+                task_id: row
+                    .get::<String>("task_id")
+                    .ok()
+                    .and_then(|s| s.parse().ok()),
+                adr,
+            });
+        }
+        Ok(decisions)
+    }
+
+    /// Record a decision imported from an ADR file of a project.
+    pub async fn create_adr_decision(
+        &self,
+        project_id: Uuid,
+        decision: &DecisionNode,
+        adr: &AdrLink,
+    ) -> Result<()> {
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $project_id})
+            CREATE (d:Decision {
+                id: $id,
+                description: $description,
+                rationale: $rationale,
+                alternatives: $alternatives,
+                chosen_option: $chosen_option,
+                decided_by: $decided_by,
+                decided_at: datetime($decided_at),
+                status: $status
+            })
+            CREATE (p)-[:HAS_ADR {number: $number, path: $path}]->(d)
+            "#,
+        )
+        .param("project_id", project_id.to_string())
+        .param("id", decision.id.to_string())
+        .param("description", decision.description.clone())
+        .param("rationale", decision.rationale.clone())
+        .param("alternatives", decision.alternatives.clone())
+        .param(
+            "chosen_option",
+            decision.chosen_option.clone().unwrap_or_default(),
+        )
+        .param("decided_by", decision.decided_by.clone())
+        .param("decided_at", decision.decided_at.to_rfc3339())
+        .param("status", decision.status.to_string())
+        .param("number", adr.number as i64)
+        .param("path", adr.path.clone());
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
+    /// Record the ADR file documenting a decision of a project.
+    pub async fn set_decision_adr(
+        &self,
+        project_id: Uuid,
+        decision_id: Uuid,
+        adr: &AdrLink,
+    ) -> Result<()> {
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $project_id})
+            MATCH (d:Decision {id: $decision_id})
+            MERGE (p)-[a:HAS_ADR]->(d)
+            SET a.number = $number, a.path = $path
+            "#,
+        )
+        .param("project_id", project_id.to_string())
+        .param("decision_id", decision_id.to_string())
+        .param("number", adr.number as i64)
+        .param("path", adr.path.clone());
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
     // ========================================================================
     // SUPERSEDES relation (Decision → Decision)
     // ========================================================================
@@ -0,0 +1,260 @@
+//! Neo4j Document & Section operations (Markdown documentation)
+
+use super::client::Neo4jClient;
+use super::models::*;
+use anyhow::Result;
+use chrono::Utc;
+use neo4rs::query;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+impl Neo4jClient {
+    /// Upsert a document and replace its sections and their LINKS_TO edges.
+    pub async fn upsert_document_impl(
+        &self,
+        document: &DocumentNode,
+        sections: &[DocSectionNode],
+    ) -> Result<()> {
+        let q = query(
+            r#"
+            MERGE (d:Document {path: $path})
+            SET d.title = $title,
+                d.hash = $hash,
+                d.section_count = $section_count,
+                d.indexed_at = datetime($indexed_at),
+                d.project_id = $project_id
+            WITH d
+            OPTIONAL MATCH (d)-[:HAS_SECTION]->(old:Section)
+            DETACH DELETE old
+            WITH DISTINCT d
+            OPTIONAL MATCH (p:Project {id: $project_id})
+            FOREACH (_ IN CASE WHEN p IS NOT NULL THEN [1] ELSE [] END |
+                MERGE (p)-[:HAS_DOCUMENT]->(d)
+            )
+            "#,
+        )
+        .param("path", document.path.clone())
+        .param("title", document.title.clone().unwrap_or_default())
+        .param("hash", document.hash.clone())
+        .param("section_count", document.section_count as i64)
+        .param("indexed_at", document.indexed_at.to_rfc3339())
+        .param(
+            "project_id",
+            document
+                .project_id
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+        );
+        self.graph.run(q).await?;
+
+        if sections.is_empty() {
+            return Ok(());
+        }
+
+        let items: Vec<HashMap<String, neo4rs::BoltType>> = sections
+            .iter()
+            .map(|s| {
+                let mut m = HashMap::new();
+                m.insert("id".into(), s.id.clone().into());
+                m.insert("ordinal".into(), (s.ordinal as i64).into());
+                m.insert("heading".into(), s.heading.clone().into());
+                m.insert("level".into(), (s.level as i64).into());
+                m.insert("anchor".into(), s.anchor.clone().into());
+                m.insert(
+                    "parent_anchor".into(),
+                    s.parent_anchor.clone().unwrap_or_default().into(),
+                );
+                m.insert("line_start".into(), (s.line_start as i64).into());
+                m.insert("line_end".into(), (s.line_end as i64).into());
+                m.insert("summary".into(), s.summary.clone().into());
+                m.insert("code_languages".into(), s.code_languages.clone().into());
+                m.insert("links".into(), s.links.clone().into());
+                m
+            })
+            .collect();
+
+        let q = query(
+            r#"
+            MATCH (d:Document {path: $path})
+            UNWIND $items AS item
+            CREATE (s:Section {
+                id: item.id,
+                document_path: $path,
+                ordinal: item.ordinal,
+                heading: item.heading,
+                level: item.level,
+                anchor: item.anchor,
+                parent_anchor: item.parent_anchor,
+                line_start: item.line_start,
+                line_end: item.line_end,
+                summary: item.summary,
+                code_languages: item.code_languages,
+                links: item.links
+            })
+            CREATE (d)-[:HAS_SECTION]->(s)
+            "#,
+        )
+        .param("path", document.path.clone())
+        .param("items", items);
+        self.graph.run(q).await?;
+
+        // Links to synced files and other documents
+        let q = query(
+            r#"
+            MATCH (:Document {path: $path})-[:HAS_SECTION]->(s:Section)
+            UNWIND s.links AS target
+            MATCH (t:File {path: target})
+            MERGE (s)-[:LINKS_TO]->(t)
+            "#,
+        )
+        .param("path", document.path.clone());
+        self.graph.run(q).await?;
+
+        let q = query(
+            r#"
+            MATCH (:Document {path: $path})-[:HAS_SECTION]->(s:Section)
+            UNWIND s.links AS target
+            MATCH (t:Document {path: target})
+            MERGE (s)-[:LINKS_TO]->(t)
+            "#,
+        )
+        .param("path", document.path.clone());
+        self.graph.run(q).await?;
+
+        Ok(())
+    }
+
+    /// Get a document by path.
+    pub async fn get_document_impl(&self, path: &str) -> Result<Option<DocumentNode>> {
+        let q = query(
+            r#"
+            MATCH (d:Document {path: $path})
+            RETURN d
+            "#,
+        )
+        .param("path", path);
+
+        let mut result = self.graph.execute(q).await?;
+        match result.next().await? {
+            Some(row) => {
+                let node: neo4rs::Node = row.get("d")?;
+                Ok(Some(Self::node_to_document(&node)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// List the documents of a project, ordered by path.
+    pub async fn list_project_documents_impl(&self, project_id: Uuid) -> Result<Vec<DocumentNode>> {
+        let q = query(
+            r#"
+            MATCH (:Project {id: $project_id})-[:HAS_DOCUMENT]->(d:Document)
+            RETURN d
+            ORDER BY d.path
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut documents = Vec::new();
+        while let Some(row) = result.next().await? {
+            let node: neo4rs::Node = row.get("d")?;
+            documents.push(Self::node_to_document(&node));
+        }
+        Ok(documents)
+    }
+
+    /// List the sections of a document in document order.
+    pub async fn get_document_sections_impl(&self, path: &str) -> Result<Vec<DocSectionNode>> {
+        let q = query(
+            r#"
+            MATCH (:Document {path: $path})-[:HAS_SECTION]->(s:Section)
+            RETURN s
+            ORDER BY s.ordinal
+            "#,
+        )
+        .param("path", path);
+
+        let mut result = self.graph.execute(q).await?;
+        let mut sections = Vec::new();
+        while let Some(row) = result.next().await? {
+            let node: neo4rs::Node = row.get("s")?;
+            let parent_anchor: String = node.get("parent_anchor").unwrap_or_default();
+            sections.push(DocSectionNode {
+                id: node.get("id")?,
+                document_path: node.get("document_path").unwrap_or_default(),
+                ordinal: node.get::<i64>("ordinal").unwrap_or(0) as usize,
+                heading: node.get("heading").unwrap_or_default(),
+                level: node.get::<i64>("level").unwrap_or(0) as u8,
+                anchor: node.get("anchor").unwrap_or_default(),
+                parent_anchor: (!parent_anchor.is_empty()).then_some(parent_anchor),
+                line_start: node.get::<i64>("line_start").unwrap_or(0) as usize,
+                line_end: node.get::<i64>("line_end").unwrap_or(0) as usize,
+                summary: node.get("summary").unwrap_or_default(),
+                code_languages: node.get("code_languages").unwrap_or_default(),
+                links: node.get("links").unwrap_or_default(),
+            });
+        }
+        Ok(sections)
+    }
+
+    /// Delete a document and its sections.
+    pub async fn delete_document_impl(&self, path: &str) -> Result<()> {
+        let q = query(
+            r#"
+            MATCH (d:Document {path: $path})
+            OPTIONAL MATCH (d)-[:HAS_SECTION]->(s:Section)
+            DETACH DELETE s, d
+            "#,
+        )
+        .param("path", path);
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
+    /// Delete the documents of a project whose path is not in `valid_paths`.
+    /// Returns the deleted paths.
+    pub async fn delete_stale_documents_impl(
+        &self,
+        project_id: Uuid,
+        valid_paths: &[String],
+    ) -> Result<Vec<String>> {
+        let q = query(
+            r#"
+            MATCH (:Project {id: $project_id})-[:HAS_DOCUMENT]->(d:Document)
+            WHERE NOT d.path IN $valid_paths
+            OPTIONAL MATCH (d)-[:HAS_SECTION]->(s:Section)
+            WITH d, d.path AS path, collect(s) AS sections
+            FOREACH (s IN sections | DETACH DELETE s)
+            DETACH DELETE d
+            RETURN path
+            "#,
+        )
+        .param("project_id", project_id.to_string())
+        .param("valid_paths", valid_paths.to_vec());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut paths = Vec::new();
+        while let Some(row) = result.next().await? {
+            paths.push(row.get("path")?);
+        }
+        Ok(paths)
+    }
+
+    fn node_to_document(node: &neo4rs::Node) -> DocumentNode {
+        let title: String = node.get("title").unwrap_or_default();
+        let project_id: String = node.get("project_id").unwrap_or_default();
+        DocumentNode {
+            path: node.get("path").unwrap_or_default(),
+            project_id: project_id.parse().ok(),
+            title: (!title.is_empty()).then_some(title),
+            hash: node.get("hash").unwrap_or_default(),
+            section_count: node.get::<i64>("section_count").unwrap_or(0) as usize,
+            indexed_at: node
+                .get::<String>("indexed_at")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or_else(Utc::now),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Neo4j GitHub check run settings of projects
+
+use super::client::Neo4jClient;
+use super::models::*;
+use anyhow::{Context, Result};
+use neo4rs::query;
+use uuid::Uuid;
+
+impl Neo4jClient {
+    /// Get the GitHub check run settings of a project.
+    pub async fn get_project_github_checks_impl(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Option<GitHubCheckSettings>> {
+        self.get_project_json_property(project_id, "github_checks")
+            .await?
+            .map(|json| {
+                serde_json::from_str(&json).context("Failed to deserialize GitHub check settings")
+            })
+            .transpose()
+    }
+
+    /// Replace the GitHub check run settings of a project. The repository is
+    /// also kept lowercased in `github_repository` for webhook lookups.
+    pub async fn update_project_github_checks_impl(
+        &self,
+        project_id: Uuid,
+        settings: Option<&GitHubCheckSettings>,
+    ) -> Result<()> {
+        let q = match settings {
+            Some(settings) => query(
+                r#"
+                MATCH (p:Project {id: $id})
+                SET p.github_checks = $settings, p.github_repository = $repository
+                "#,
+            )
+            .param("settings", serde_json::to_string(settings)?)
+            .param("repository", settings.repository.to_lowercase()),
+            None => query(
+                r#"
+                MATCH (p:Project {id: $id})
+                REMOVE p.github_checks, p.github_repository
+                "#,
+            ),
+        }
+        .param("id", project_id.to_string());
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
+    /// Find the project checking the pull requests of `repository`.
+    pub async fn find_project_by_github_repository_impl(
+        &self,
+        repository: &str,
+    ) -> Result<Option<ProjectNode>> {
+        let q = query(
+            r#"
+            MATCH (p:Project {github_repository: $repository})
+            RETURN p
+            LIMIT 1
+            "#,
+        )
+        .param("repository", repository.to_lowercase());
+
+        let mut result = self.graph.execute(q).await?;
+        match result.next().await? {
+            Some(row) => {
+                let node: neo4rs::Node = row.get("p")?;
+                Ok(Some(self.node_to_project(&node)?))
+            }
+            None => Ok(None),
+        }
+    }
+}
@@ -50,6 +50,13 @@ impl GraphStore for Neo4jClient {
         self.update_project_synced(id).await
     }
 
+    async fn get_project_language_stats(
+        &self,
+        project_id: Uuid,
+    ) -> anyhow::Result<Vec<LanguageStats>> {
+        self.get_project_language_stats(project_id).await
+    }
+
     async fn update_project_analytics_timestamp(&self, id: Uuid) -> anyhow::Result<()> {
         self.update_project_analytics_timestamp(id).await
     }
@@ -133,6 +140,10 @@ impl GraphStore for Neo4jClient {
         self.compute_coupling_matrix(workspace_id).await
     }
 
+    async fn get_workspace_stats(&self, workspace_id: Uuid) -> anyhow::Result<WorkspaceStats> {
+        self.get_workspace_stats(workspace_id).await
+    }
+
     // ========================================================================
     // Workspace Milestone operations
     // ========================================================================
@@ -327,6 +338,37 @@ impl GraphStore for Neo4jClient {
         self.get_resource_consumers(resource_id).await
     }
 
+    async fn replace_resource_symbol_links(
+        &self,
+        project_id: Uuid,
+        links: &[ResourceSymbolLink],
+    ) -> anyhow::Result<usize> {
+        self.replace_resource_symbol_links(project_id, links).await
+    }
+
+    async fn get_resource_symbol_links(
+        &self,
+        resource_id: Uuid,
+    ) -> anyhow::Result<Vec<ResourceSymbolLink>> {
+        self.get_resource_symbol_links(resource_id).await
+    }
+
+    async fn replace_resource_elements(
+        &self,
+        resource_id: Uuid,
+        elements: &[ResourceElementNode],
+    ) -> anyhow::Result<usize> {
+        self.replace_resource_elements(resource_id, elements).await
+    }
+
+    async fn list_resource_elements(
+        &self,
+        resource_id: Uuid,
+        kind: Option<ResourceElementKind>,
+    ) -> anyhow::Result<Vec<ResourceElementNode>> {
+        self.list_resource_elements(resource_id, kind).await
+    }
+
     // ========================================================================
     // Component operations (Topology)
     // ========================================================================
@@ -396,6 +438,13 @@ impl GraphStore for Neo4jClient {
         self.get_workspace_topology(workspace_id).await
     }
 
+    async fn list_component_project_mappings(
+        &self,
+        workspace_id: Uuid,
+    ) -> anyhow::Result<Vec<(Uuid, Uuid)>> {
+        self.list_component_project_mappings(workspace_id).await
+    }
+
     // ========================================================================
     // File operations
     // ========================================================================
@@ -436,10 +485,41 @@ impl GraphStore for Neo4jClient {
         self.list_project_files(project_id).await
     }
 
+    async fn get_project_file_stats(&self, project_id: Uuid) -> anyhow::Result<Vec<FileStatsRow>> {
+        self.get_project_file_stats(project_id).await
+    }
+
+    async fn get_project_function_fingerprints(
+        &self,
+        project_id: Uuid,
+    ) -> anyhow::Result<Vec<FunctionFingerprintRow>> {
+        self.get_project_function_fingerprints(project_id).await
+    }
+
+    async fn get_functions_above_complexity(
+        &self,
+        project_id: Uuid,
+        max_complexity: u32,
+    ) -> anyhow::Result<Vec<FunctionComplexityRow>> {
+        self.get_functions_above_complexity(project_id, max_complexity)
+            .await
+    }
+
     async fn count_project_files(&self, project_id: Uuid) -> anyhow::Result<i64> {
         self.count_project_files(project_id).await
     }
 
+    async fn get_indexed_hashes(
+        &self,
+        paths: &[String],
+    ) -> anyhow::Result<std::collections::HashMap<String, String>> {
+        self.get_indexed_hashes(paths).await
+    }
+
+    async fn set_indexed_hashes(&self, hashes: &[(String, String)]) -> anyhow::Result<()> {
+        self.set_indexed_hashes(hashes).await
+    }
+
     async fn count_orphan_files(&self, project_id: Uuid) -> anyhow::Result<i64> {
         self.count_orphan_files(project_id).await
     }
@@ -535,6 +615,15 @@ impl GraphStore for Neo4jClient {
         self.batch_upsert_functions(functions).await
     }
 
+    async fn batch_set_function_fingerprints(
+        &self,
+        file_paths: &[String],
+        fingerprints: &[FunctionFingerprint],
+    ) -> anyhow::Result<()> {
+        self.batch_set_function_fingerprints(file_paths, fingerprints)
+            .await
+    }
+
     async fn batch_upsert_structs(&self, structs: &[StructNode]) -> anyhow::Result<()> {
         self.batch_upsert_structs(structs).await
     }
@@ -710,6 +799,47 @@ impl GraphStore for Neo4jClient {
         self.get_file_import_paths_list(path).await
     }
 
+    async fn get_symbol_location(
+        &self,
+        symbol_id: &str,
+    ) -> anyhow::Result<Option<SymbolLocationNode>> {
+        self.get_symbol_location(symbol_id).await
+    }
+
+    async fn find_symbol_dependents(
+        &self,
+        symbol_id: &str,
+        limit: usize,
+    ) -> anyhow::Result<Vec<SymbolReferenceNode>> {
+        self.find_symbol_dependents(symbol_id, limit).await
+    }
+
+    async fn list_file_symbols(&self, path: &str) -> anyhow::Result<Vec<SymbolLocationNode>> {
+        self.list_file_symbols(path).await
+    }
+
+    async fn find_symbols_by_name(
+        &self,
+        name: &str,
+        project_id: Option<Uuid>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<SymbolLocationNode>> {
+        self.find_symbols_by_name(name, project_id, limit).await
+    }
+
+    async fn find_symbols_matching(
+        &self,
+        terms: &[String],
+        project_id: Option<Uuid>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<SymbolLocationNode>> {
+        self.find_symbols_matching(terms, project_id, limit).await
+    }
+
+    async fn list_project_imports(&self, project_id: Uuid) -> anyhow::Result<Vec<ImportNode>> {
+        self.list_project_imports(project_id).await
+    }
+
     async fn find_symbol_references(
         &self,
         symbol: &str,
@@ -728,8 +858,9 @@ impl GraphStore for Neo4jClient {
         function_name: &str,
         depth: u32,
         project_id: Option<Uuid>,
+        min_confidence: f64,
     ) -> anyhow::Result<Vec<String>> {
-        self.get_function_callers_by_name(function_name, depth, project_id)
+        self.get_function_callers_by_name(function_name, depth, project_id, min_confidence)
             .await
     }
 
@@ -738,8 +869,9 @@ impl GraphStore for Neo4jClient {
         function_name: &str,
         depth: u32,
         project_id: Option<Uuid>,
+        min_confidence: f64,
     ) -> anyhow::Result<Vec<String>> {
-        self.get_function_callees_by_name(function_name, depth, project_id)
+        self.get_function_callees_by_name(function_name, depth, project_id, min_confidence)
             .await
     }
 
@@ -761,6 +893,39 @@ impl GraphStore for Neo4jClient {
             .await
     }
 
+    async fn get_name_based_references(
+        &self,
+        project_id: Uuid,
+        max_confidence: f64,
+        limit: usize,
+    ) -> anyhow::Result<Vec<CodeReferenceNode>> {
+        self.get_name_based_references_impl(project_id, max_confidence, limit)
+            .await
+    }
+
+    async fn find_symbol_declared_at(
+        &self,
+        kind: CodeReferenceKind,
+        file_path: &str,
+        line: u32,
+    ) -> anyhow::Result<Option<String>> {
+        self.find_symbol_declared_at_impl(kind, file_path, line)
+            .await
+    }
+
+    async fn resolve_code_reference(
+        &self,
+        kind: CodeReferenceKind,
+        function_id: &str,
+        name: &str,
+        target_id: Option<&str>,
+        confidence: f64,
+        reason: &str,
+    ) -> anyhow::Result<()> {
+        self.resolve_code_reference_impl(kind, function_id, name, target_id, confidence, reason)
+            .await
+    }
+
     async fn get_language_stats(&self) -> anyhow::Result<Vec<LanguageStatsNode>> {
         self.get_language_stats().await
     }
@@ -845,6 +1010,10 @@ impl GraphStore for Neo4jClient {
         self.set_watch_enabled(project_id, enabled).await
     }
 
+    async fn set_project_archived(&self, project_id: Uuid, archived: bool) -> anyhow::Result<()> {
+        self.set_project_archived(project_id, archived).await
+    }
+
     async fn set_default_note_energy(
         &self,
         project_id: Uuid,
@@ -938,6 +1107,23 @@ impl GraphStore for Neo4jClient {
         self.create_plan(plan).await
     }
 
+    async fn create_plan_with_constraints(
+        &self,
+        plan: &PlanNode,
+        constraints: &[ConstraintNode],
+    ) -> anyhow::Result<()> {
+        self.create_plan_with_constraints(plan, constraints).await
+    }
+
+    async fn create_plan_with_tasks(
+        &self,
+        plan: &PlanNode,
+        constraints: &[ConstraintNode],
+        tasks: &[NewPlanTask],
+    ) -> anyhow::Result<()> {
+        self.create_plan_with_tasks(plan, constraints, tasks).await
+    }
+
     async fn get_plan(&self, id: Uuid) -> anyhow::Result<Option<PlanNode>> {
         self.get_plan(id).await
     }
@@ -993,6 +1179,17 @@ impl GraphStore for Neo4jClient {
         self.create_task(plan_id, task).await
     }
 
+    async fn create_task_with_steps(
+        &self,
+        plan_id: Uuid,
+        task: &TaskNode,
+        depends_on: &[Uuid],
+        steps: &[StepNode],
+    ) -> anyhow::Result<()> {
+        self.create_task_with_steps(plan_id, task, depends_on, steps)
+            .await
+    }
+
     async fn get_plan_tasks(&self, plan_id: Uuid) -> anyhow::Result<Vec<TaskNode>> {
         self.get_plan_tasks(plan_id).await
     }
@@ -1104,6 +1301,10 @@ impl GraphStore for Neo4jClient {
             .await
     }
 
+    async fn set_task_board_position(&self, task_id: Uuid, position: &str) -> anyhow::Result<()> {
+        self.set_task_board_position(task_id, position).await
+    }
+
     async fn delete_task(&self, task_id: Uuid) -> anyhow::Result<()> {
         self.delete_task(task_id).await
     }
@@ -1317,6 +1518,31 @@ impl GraphStore for Neo4jClient {
         self.get_decision_timeline(task_id, from, to).await
     }
 
+    async fn list_project_decisions(
+        &self,
+        project_id: Uuid,
+    ) -> anyhow::Result<Vec<ProjectDecision>> {
+        self.list_project_decisions(project_id).await
+    }
+
+    async fn create_adr_decision(
+        &self,
+        project_id: Uuid,
+        decision: &DecisionNode,
+        adr: &AdrLink,
+    ) -> anyhow::Result<()> {
+        self.create_adr_decision(project_id, decision, adr).await
+    }
+
+    async fn set_decision_adr(
+        &self,
+        project_id: Uuid,
+        decision_id: Uuid,
+        adr: &AdrLink,
+    ) -> anyhow::Result<()> {
+        self.set_decision_adr(project_id, decision_id, adr).await
+    }
+
     // ========================================================================
     // Dependency analysis
     // ========================================================================
@@ -1380,6 +1606,23 @@ impl GraphStore for Neo4jClient {
         self.get_task_commits(task_id).await
     }
 
+    async fn link_commit_implements_task(
+        &self,
+        commit_hash: &str,
+        task_id: Uuid,
+        session_id: Option<Uuid>,
+    ) -> anyhow::Result<()> {
+        self.link_commit_implements_task(commit_hash, task_id, session_id)
+            .await
+    }
+
+    async fn get_task_implementing_commits(
+        &self,
+        task_id: Uuid,
+    ) -> anyhow::Result<Vec<CommitNode>> {
+        self.get_task_implementing_commits(task_id).await
+    }
+
     async fn get_plan_commits(&self, plan_id: Uuid) -> anyhow::Result<Vec<CommitNode>> {
         self.get_plan_commits(plan_id).await
     }
@@ -1586,6 +1829,10 @@ impl GraphStore for Neo4jClient {
         self.unlink_plan_from_milestone(plan_id, milestone_id).await
     }
 
+    async fn get_milestone_plan_ids(&self, milestone_id: Uuid) -> anyhow::Result<Vec<Uuid>> {
+        self.get_milestone_plan_ids(milestone_id).await
+    }
+
     async fn get_milestone_details(
         &self,
         milestone_id: Uuid,
@@ -1744,8 +1991,9 @@ impl GraphStore for Neo4jClient {
         offset: usize,
         sort_by: Option<&str>,
         sort_order: &str,
+        include_archived: bool,
     ) -> anyhow::Result<(Vec<ProjectNode>, usize)> {
-        self.list_projects_filtered(search, limit, offset, sort_by, sort_order)
+        self.list_projects_filtered(search, limit, offset, sort_by, sort_order, include_archived)
             .await
     }
 
@@ -1931,6 +2179,41 @@ impl GraphStore for Neo4jClient {
         self.get_note_anchors(note_id).await
     }
 
+    async fn get_symbol_anchors_for_file(
+        &self,
+        file_path: &str,
+    ) -> anyhow::Result<Vec<(Uuid, NoteAnchor)>> {
+        self.get_symbol_anchors_for_file(file_path).await
+    }
+
+    async fn reanchor_note(
+        &self,
+        note_id: Uuid,
+        entity_type: &EntityType,
+        old_entity_id: &str,
+        new_entity_id: &str,
+        symbol_id: &str,
+    ) -> anyhow::Result<()> {
+        self.reanchor_note(
+            note_id,
+            entity_type,
+            old_entity_id,
+            new_entity_id,
+            symbol_id,
+        )
+        .await
+    }
+
+    async fn mark_note_anchor_orphaned(
+        &self,
+        note_id: Uuid,
+        entity_type: &EntityType,
+        entity_id: &str,
+    ) -> anyhow::Result<()> {
+        self.mark_note_anchor_orphaned(note_id, entity_type, entity_id)
+            .await
+    }
+
     async fn set_note_embedding(
         &self,
         note_id: Uuid,
@@ -2270,6 +2553,30 @@ impl GraphStore for Neo4jClient {
         self.get_session_auto_continue(id).await
     }
 
+    async fn set_chat_session_memory(
+        &self,
+        id: Uuid,
+        memory: &ChatSessionMemory,
+    ) -> anyhow::Result<()> {
+        self.set_chat_session_memory(id, memory).await
+    }
+
+    async fn get_chat_session_memory(&self, id: Uuid) -> anyhow::Result<Option<ChatSessionMemory>> {
+        self.get_chat_session_memory(id).await
+    }
+
+    async fn set_chat_session_usage(
+        &self,
+        id: Uuid,
+        usage: Option<&ChatSessionUsage>,
+    ) -> anyhow::Result<()> {
+        self.set_chat_session_usage(id, usage).await
+    }
+
+    async fn get_chat_session_usage(&self, id: Uuid) -> anyhow::Result<Option<ChatSessionUsage>> {
+        self.get_chat_session_usage(id).await
+    }
+
     async fn backfill_chat_session_previews(&self) -> anyhow::Result<usize> {
         self.backfill_chat_session_previews().await
     }
@@ -2592,8 +2899,17 @@ impl GraphStore for Neo4jClient {
     async fn get_project_call_edges(
         &self,
         project_id: Uuid,
+        min_confidence: f64,
     ) -> anyhow::Result<Vec<(String, String)>> {
-        self.get_project_call_edges(project_id).await
+        self.get_project_call_edges(project_id, min_confidence)
+            .await
+    }
+
+    async fn get_project_call_graph_functions(
+        &self,
+        project_id: Uuid,
+    ) -> anyhow::Result<Vec<CallGraphFunctionNode>> {
+        self.get_project_call_graph_functions(project_id).await
     }
 
     async fn get_project_extends_edges(
@@ -3184,6 +3500,10 @@ impl GraphStore for Neo4jClient {
         }
     }
 
+    fn query_metrics(&self) -> Option<super::query_guard::QueryMetrics> {
+        Some(Neo4jClient::query_metrics(self))
+    }
+
     // ========================================================================
     // Skill operations (delegates to neo4j/skill.rs)
     // ========================================================================
@@ -3630,6 +3950,17 @@ impl GraphStore for Neo4jClient {
         self.get_plan_run(run_id).await
     }
 
+    async fn save_run_report(&self, report: &crate::runner::RunReport) -> anyhow::Result<()> {
+        self.save_run_report_impl(report).await
+    }
+
+    async fn get_run_report(
+        &self,
+        run_id: Uuid,
+    ) -> anyhow::Result<Option<crate::runner::RunReport>> {
+        self.get_run_report_impl(run_id).await
+    }
+
     async fn list_active_plan_runs(&self) -> anyhow::Result<Vec<crate::runner::RunnerState>> {
         self.list_active_plan_runs_impl().await
     }
@@ -3891,6 +4222,682 @@ impl GraphStore for Neo4jClient {
         self.list_alerts_impl(project_id, limit, offset).await
     }
 
+    // ========================================================================
+    // Package & advisory operations (vulnerability audit)
+    // ========================================================================
+
+    async fn replace_project_packages(
+        &self,
+        project_id: Uuid,
+        packages: &[PackageNode],
+    ) -> anyhow::Result<usize> {
+        self.replace_project_packages_impl(project_id, packages)
+            .await
+    }
+
+    async fn list_project_packages(&self, project_id: Uuid) -> anyhow::Result<Vec<PackageNode>> {
+        self.list_project_packages_impl(project_id).await
+    }
+
+    async fn replace_project_advisories(
+        &self,
+        project_id: Uuid,
+        advisories: &[ProjectAdvisory],
+    ) -> anyhow::Result<()> {
+        self.replace_project_advisories_impl(project_id, advisories)
+            .await
+    }
+
+    async fn list_project_advisories(
+        &self,
+        project_id: Uuid,
+    ) -> anyhow::Result<Vec<ProjectAdvisory>> {
+        self.list_project_advisories_impl(project_id).await
+    }
+
+    // ========================================================================
+    // Document operations (Markdown documentation)
+    // ========================================================================
+
+    async fn upsert_document(
+        &self,
+        document: &DocumentNode,
+        sections: &[DocSectionNode],
+    ) -> anyhow::Result<()> {
+        self.upsert_document_impl(document, sections).await
+    }
+
+    async fn get_document(&self, path: &str) -> anyhow::Result<Option<DocumentNode>> {
+        self.get_document_impl(path).await
+    }
+
+    async fn list_project_documents(&self, project_id: Uuid) -> anyhow::Result<Vec<DocumentNode>> {
+        self.list_project_documents_impl(project_id).await
+    }
+
+    async fn get_document_sections(&self, path: &str) -> anyhow::Result<Vec<DocSectionNode>> {
+        self.get_document_sections_impl(path).await
+    }
+
+    async fn delete_document(&self, path: &str) -> anyhow::Result<()> {
+        self.delete_document_impl(path).await
+    }
+
+    async fn delete_stale_documents(
+        &self,
+        project_id: Uuid,
+        valid_paths: &[String],
+    ) -> anyhow::Result<Vec<String>> {
+        self.delete_stale_documents_impl(project_id, valid_paths)
+            .await
+    }
+
+    // ========================================================================
+    // Database schema operations (SQL tables & migrations)
+    // ========================================================================
+
+    async fn replace_project_schema(
+        &self,
+        project_id: Uuid,
+        tables: &[TableNode],
+        migrations: &[MigrationNode],
+    ) -> anyhow::Result<()> {
+        self.replace_project_schema_impl(project_id, tables, migrations)
+            .await
+    }
+
+    async fn list_project_tables(&self, project_id: Uuid) -> anyhow::Result<Vec<TableNode>> {
+        self.list_project_tables_impl(project_id).await
+    }
+
+    async fn list_project_migrations(
+        &self,
+        project_id: Uuid,
+    ) -> anyhow::Result<Vec<MigrationNode>> {
+        self.list_project_migrations_impl(project_id).await
+    }
+
+    // ========================================================================
+    // Configuration key operations
+    // ========================================================================
+
+    async fn replace_project_config_keys(
+        &self,
+        project_id: Uuid,
+        keys: &[ConfigKeyNode],
+    ) -> anyhow::Result<()> {
+        self.replace_project_config_keys_impl(project_id, keys)
+            .await
+    }
+
+    async fn list_project_config_keys(
+        &self,
+        project_id: Uuid,
+        filter: Option<&str>,
+    ) -> anyhow::Result<Vec<ConfigKeyNode>> {
+        self.list_project_config_keys_impl(project_id, filter).await
+    }
+
+    // ========================================================================
+    // Module summary operations
+    // ========================================================================
+
+    async fn get_project_module_digest(
+        &self,
+        project_id: Uuid,
+        symbols_per_file: usize,
+    ) -> anyhow::Result<Vec<ModuleFileDigest>> {
+        self.get_project_module_digest_impl(project_id, symbols_per_file)
+            .await
+    }
+
+    async fn list_module_summaries(
+        &self,
+        project_id: Uuid,
+    ) -> anyhow::Result<Vec<ModuleSummaryNode>> {
+        self.list_module_summaries_impl(project_id).await
+    }
+
+    async fn upsert_module_summaries(&self, summaries: &[ModuleSummaryNode]) -> anyhow::Result<()> {
+        self.upsert_module_summaries_impl(summaries).await
+    }
+
+    async fn delete_module_summaries(
+        &self,
+        project_id: Uuid,
+        paths: &[String],
+    ) -> anyhow::Result<()> {
+        self.delete_module_summaries_impl(project_id, paths).await
+    }
+
+    // ========================================================================
+    // Sync limit operations
+    // ========================================================================
+
+    async fn get_project_sync_limits(
+        &self,
+        project_id: Uuid,
+    ) -> anyhow::Result<Option<SyncLimits>> {
+        self.get_project_sync_limits_impl(project_id).await
+    }
+
+    async fn update_project_sync_limits(
+        &self,
+        project_id: Uuid,
+        limits: &SyncLimits,
+    ) -> anyhow::Result<()> {
+        self.update_project_sync_limits_impl(project_id, limits)
+            .await
+    }
+
+    // ========================================================================
+    // Budget operations
+    // ========================================================================
+
+    async fn get_project_budgets(
+        &self,
+        project_id: Uuid,
+    ) -> anyhow::Result<Option<ProjectBudgets>> {
+        self.get_project_budgets_impl(project_id).await
+    }
+
+    async fn update_project_budgets(
+        &self,
+        project_id: Uuid,
+        budgets: &ProjectBudgets,
+    ) -> anyhow::Result<()> {
+        self.update_project_budgets_impl(project_id, budgets).await
+    }
+
+    async fn get_budget_report(
+        &self,
+        project_id: Uuid,
+    ) -> anyhow::Result<Option<BudgetCheckReport>> {
+        self.get_budget_report_impl(project_id).await
+    }
+
+    async fn save_budget_report(&self, report: &BudgetCheckReport) -> anyhow::Result<()> {
+        self.save_budget_report_impl(report).await
+    }
+
+    // ========================================================================
+    // API surface operations
+    // ========================================================================
+
+    async fn get_project_public_symbols(
+        &self,
+        project_id: Uuid,
+    ) -> anyhow::Result<Vec<PublicSymbolRow>> {
+        self.get_project_public_symbols_impl(project_id).await
+    }
+
+    async fn create_api_surface_snapshot(
+        &self,
+        snapshot: &ApiSurfaceSnapshotNode,
+        keep: usize,
+    ) -> anyhow::Result<()> {
+        self.create_api_surface_snapshot_impl(snapshot, keep).await
+    }
+
+    async fn list_api_surface_snapshots(
+        &self,
+        project_id: Uuid,
+    ) -> anyhow::Result<Vec<ApiSurfaceSnapshotNode>> {
+        self.list_api_surface_snapshots_impl(project_id).await
+    }
+
+    async fn get_api_surface_snapshot(
+        &self,
+        project_id: Uuid,
+        snapshot_id: Uuid,
+    ) -> anyhow::Result<Option<ApiSurfaceSnapshotNode>> {
+        self.get_api_surface_snapshot_impl(project_id, snapshot_id)
+            .await
+    }
+
+    // ========================================================================
+    // Test coverage operations
+    // ========================================================================
+
+    async fn get_project_function_spans(
+        &self,
+        project_id: Uuid,
+    ) -> anyhow::Result<Vec<FunctionSpanRow>> {
+        self.get_project_function_spans_impl(project_id).await
+    }
+
+    async fn batch_set_coverage(
+        &self,
+        file_paths: &[String],
+        functions: &[CoverageUpdate],
+        files: &[CoverageUpdate],
+    ) -> anyhow::Result<()> {
+        self.batch_set_coverage_impl(file_paths, functions, files)
+            .await
+    }
+
+    async fn get_untested_functions(
+        &self,
+        project_id: Uuid,
+        max_coverage_pct: f64,
+        limit: usize,
+    ) -> anyhow::Result<Vec<UntestedFunctionRow>> {
+        self.get_untested_functions_impl(project_id, max_coverage_pct, limit)
+            .await
+    }
+
+    async fn get_file_coverage(
+        &self,
+        file_path: &str,
+        max_coverage_pct: f64,
+    ) -> anyhow::Result<Option<FileCoverageRow>> {
+        self.get_file_coverage_impl(file_path, max_coverage_pct)
+            .await
+    }
+
+    // ========================================================================
+    // Test result operations
+    // ========================================================================
+
+    async fn create_test_run(&self, run: &TestRunNode, keep: usize) -> anyhow::Result<()> {
+        self.create_test_run_impl(run, keep).await
+    }
+
+    async fn list_test_runs(
+        &self,
+        project_id: Uuid,
+        limit: usize,
+    ) -> anyhow::Result<Vec<TestRunNode>> {
+        self.list_test_runs_impl(project_id, limit).await
+    }
+
+    async fn get_test_cases(&self, project_id: Uuid) -> anyhow::Result<Vec<TestCaseNode>> {
+        self.get_test_cases_impl(project_id).await
+    }
+
+    async fn upsert_test_cases(&self, cases: &[TestCaseNode]) -> anyhow::Result<()> {
+        self.upsert_test_cases_impl(cases).await
+    }
+
+    // ========================================================================
+    // Production error operations
+    // ========================================================================
+
+    async fn replace_sentry_issues(
+        &self,
+        project_id: Uuid,
+        issues: &[SentryIssueNode],
+    ) -> anyhow::Result<()> {
+        self.replace_sentry_issues_impl(project_id, issues).await
+    }
+
+    async fn list_sentry_issues(&self, project_id: Uuid) -> anyhow::Result<Vec<SentryIssueNode>> {
+        self.list_sentry_issues_impl(project_id).await
+    }
+
+    async fn get_file_sentry_issues(
+        &self,
+        file_path: &str,
+    ) -> anyhow::Result<Vec<SentryIssueNode>> {
+        self.get_file_sentry_issues_impl(file_path).await
+    }
+
+    // ========================================================================
+    // Code ownership operations
+    // ========================================================================
+
+    async fn replace_code_owners(
+        &self,
+        project_id: Uuid,
+        rules: &[CodeOwnerRule],
+        file_owners: &[(String, Vec<String>)],
+    ) -> anyhow::Result<()> {
+        self.replace_code_owners_impl(project_id, rules, file_owners)
+            .await
+    }
+
+    async fn get_code_owner_rules(&self, project_id: Uuid) -> anyhow::Result<Vec<CodeOwnerRule>> {
+        self.get_code_owner_rules_impl(project_id).await
+    }
+
+    async fn list_code_owners(&self, project_id: Uuid) -> anyhow::Result<Vec<CodeOwnerNode>> {
+        self.list_code_owners_impl(project_id).await
+    }
+
+    async fn get_file_owners(&self, file_path: &str) -> anyhow::Result<Vec<String>> {
+        self.get_file_owners_impl(file_path).await
+    }
+
+    // ========================================================================
+    // Context feedback operations
+    // ========================================================================
+
+    async fn upsert_context_feedback(
+        &self,
+        feedback: &ContextFeedbackNode,
+    ) -> anyhow::Result<bool> {
+        self.upsert_context_feedback_impl(feedback).await
+    }
+
+    async fn list_context_feedback(
+        &self,
+        session_id: Uuid,
+    ) -> anyhow::Result<Vec<ContextFeedbackNode>> {
+        self.list_context_feedback_impl(session_id).await
+    }
+
+    async fn get_note_relevance_votes(
+        &self,
+        note_ids: &[Uuid],
+    ) -> anyhow::Result<Vec<NoteRelevanceVotes>> {
+        self.get_note_relevance_votes_impl(note_ids).await
+    }
+
+    // ========================================================================
+    // Note endorsement operations
+    // ========================================================================
+
+    async fn add_note_endorsement(
+        &self,
+        endorsement: &NoteEndorsementNode,
+    ) -> anyhow::Result<bool> {
+        self.add_note_endorsement_impl(endorsement).await
+    }
+
+    async fn remove_note_endorsement(
+        &self,
+        note_id: Uuid,
+        user_id: &str,
+        kind: EndorsementKind,
+    ) -> anyhow::Result<bool> {
+        self.remove_note_endorsement_impl(note_id, user_id, kind)
+            .await
+    }
+
+    async fn list_note_endorsements(
+        &self,
+        note_id: Uuid,
+    ) -> anyhow::Result<Vec<NoteEndorsementNode>> {
+        self.list_note_endorsements_impl(note_id).await
+    }
+
+    // ========================================================================
+    // Chat undo log operations
+    // ========================================================================
+
+    async fn push_undo_entry(&self, entry: &UndoEntry) -> anyhow::Result<bool> {
+        self.push_undo_entry_impl(entry).await
+    }
+
+    async fn list_undo_entries(&self, session_id: Uuid) -> anyhow::Result<Vec<UndoEntry>> {
+        self.list_undo_entries_impl(session_id).await
+    }
+
+    async fn mark_undo_entry_undone(
+        &self,
+        entry_id: Uuid,
+        undone_at: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<()> {
+        self.mark_undo_entry_undone_impl(entry_id, undone_at).await
+    }
+
+    // ========================================================================
+    // Comment operations
+    // ========================================================================
+
+    async fn create_comment(&self, comment: &CommentNode) -> anyhow::Result<bool> {
+        self.create_comment_impl(comment).await
+    }
+
+    async fn get_comment(&self, id: Uuid) -> anyhow::Result<Option<CommentNode>> {
+        self.get_comment_impl(id).await
+    }
+
+    async fn list_comments(
+        &self,
+        entity_type: CommentTarget,
+        entity_id: Uuid,
+    ) -> anyhow::Result<Vec<CommentNode>> {
+        self.list_comments_impl(entity_type, entity_id).await
+    }
+
+    async fn list_comments_mentioning(
+        &self,
+        handle: &str,
+        limit: usize,
+    ) -> anyhow::Result<Vec<CommentNode>> {
+        self.list_comments_mentioning_impl(handle, limit).await
+    }
+
+    async fn update_comment(&self, comment: &CommentNode) -> anyhow::Result<()> {
+        self.update_comment_impl(comment).await
+    }
+
+    async fn delete_comment(&self, id: Uuid) -> anyhow::Result<usize> {
+        self.delete_comment_impl(id).await
+    }
+
+    // ========================================================================
+    // GitHub check operations
+    // ========================================================================
+
+    async fn get_project_github_checks(
+        &self,
+        project_id: Uuid,
+    ) -> anyhow::Result<Option<GitHubCheckSettings>> {
+        self.get_project_github_checks_impl(project_id).await
+    }
+
+    async fn update_project_github_checks(
+        &self,
+        project_id: Uuid,
+        settings: Option<&GitHubCheckSettings>,
+    ) -> anyhow::Result<()> {
+        self.update_project_github_checks_impl(project_id, settings)
+            .await
+    }
+
+    async fn find_project_by_github_repository(
+        &self,
+        repository: &str,
+    ) -> anyhow::Result<Option<ProjectNode>> {
+        self.find_project_by_github_repository_impl(repository)
+            .await
+    }
+
+    // ========================================================================
+    // Quota operations
+    // ========================================================================
+
+    async fn get_quota_settings(&self) -> anyhow::Result<Option<QuotaSettings>> {
+        self.get_quota_settings_impl().await
+    }
+
+    async fn update_quota_settings(&self, settings: &QuotaSettings) -> anyhow::Result<()> {
+        self.update_quota_settings_impl(settings).await
+    }
+
+    async fn get_workspace_quotas(
+        &self,
+        workspace_id: Uuid,
+    ) -> anyhow::Result<Option<QuotaLimits>> {
+        self.get_workspace_quotas_impl(workspace_id).await
+    }
+
+    async fn update_workspace_quotas(
+        &self,
+        workspace_id: Uuid,
+        limits: Option<&QuotaLimits>,
+    ) -> anyhow::Result<()> {
+        self.update_workspace_quotas_impl(workspace_id, limits)
+            .await
+    }
+
+    // ========================================================================
+    // Prompt Template operations
+    // ========================================================================
+
+    async fn create_prompt_template(&self, template: &PromptTemplateNode) -> anyhow::Result<()> {
+        self.create_prompt_template_impl(template).await
+    }
+
+    async fn get_prompt_template(&self, id: Uuid) -> anyhow::Result<Option<PromptTemplateNode>> {
+        self.get_prompt_template_impl(id).await
+    }
+
+    async fn list_prompt_templates(&self) -> anyhow::Result<Vec<PromptTemplateNode>> {
+        self.list_prompt_templates_impl().await
+    }
+
+    async fn update_prompt_template(&self, template: &PromptTemplateNode) -> anyhow::Result<()> {
+        self.update_prompt_template_impl(template).await
+    }
+
+    async fn list_prompt_template_versions(
+        &self,
+        id: Uuid,
+    ) -> anyhow::Result<Vec<PromptTemplateVersion>> {
+        self.list_prompt_template_versions_impl(id).await
+    }
+
+    async fn delete_prompt_template(&self, id: Uuid) -> anyhow::Result<bool> {
+        self.delete_prompt_template_impl(id).await
+    }
+
+    // ========================================================================
+    // Saved View operations
+    // ========================================================================
+
+    async fn create_saved_view(&self, view: &SavedViewNode) -> anyhow::Result<()> {
+        self.create_saved_view_impl(view).await
+    }
+
+    async fn get_saved_view(&self, id: Uuid) -> anyhow::Result<Option<SavedViewNode>> {
+        self.get_saved_view_impl(id).await
+    }
+
+    async fn list_saved_views(&self, owner_id: &str) -> anyhow::Result<Vec<SavedViewNode>> {
+        self.list_saved_views_impl(owner_id).await
+    }
+
+    async fn update_saved_view(&self, view: &SavedViewNode) -> anyhow::Result<()> {
+        self.update_saved_view_impl(view).await
+    }
+
+    async fn delete_saved_view(&self, id: Uuid) -> anyhow::Result<bool> {
+        self.delete_saved_view_impl(id).await
+    }
+
+    async fn list_view_functions(
+        &self,
+        project_id: Uuid,
+        filters: &SavedViewFilters,
+    ) -> anyhow::Result<Vec<ViewFunctionRow>> {
+        self.list_view_functions_impl(project_id, filters).await
+    }
+
+    // ========================================================================
+    // Search query analytics
+    // ========================================================================
+
+    async fn record_search_query(&self, event: &SearchQueryEvent) -> anyhow::Result<()> {
+        self.record_search_query_impl(event).await
+    }
+
+    async fn list_search_query_stats(
+        &self,
+        endpoint: Option<&str>,
+        project_slug: Option<&str>,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> anyhow::Result<Vec<SearchQueryStats>> {
+        self.list_search_query_stats_impl(endpoint, project_slug, since)
+            .await
+    }
+
+    async fn get_search_settings(
+        &self,
+    ) -> anyhow::Result<Option<crate::meilisearch::indexes::SearchSettings>> {
+        self.get_search_settings_impl().await
+    }
+
+    async fn update_search_settings(
+        &self,
+        settings: &crate::meilisearch::indexes::SearchSettings,
+    ) -> anyhow::Result<()> {
+        self.update_search_settings_impl(settings).await
+    }
+
+    async fn get_retention_policy(&self) -> anyhow::Result<Option<RetentionPolicy>> {
+        self.get_retention_policy_impl().await
+    }
+
+    async fn update_retention_policy(&self, policy: &RetentionPolicy) -> anyhow::Result<()> {
+        self.update_retention_policy_impl(policy).await
+    }
+
+    async fn purge_expired(
+        &self,
+        kind: RetentionKind,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<u64> {
+        self.purge_expired_impl(kind, cutoff).await
+    }
+
+    async fn get_retention_report(&self) -> anyhow::Result<Option<RetentionReport>> {
+        self.get_retention_report_impl().await
+    }
+
+    async fn save_retention_report(&self, report: &RetentionReport) -> anyhow::Result<()> {
+        self.save_retention_report_impl(report).await
+    }
+
+    async fn record_usage(&self, counts: &[UsageCount]) -> anyhow::Result<()> {
+        self.record_usage_impl(counts).await
+    }
+
+    async fn list_usage(&self, since: chrono::NaiveDate) -> anyhow::Result<Vec<UsageCount>> {
+        self.list_usage_impl(since).await
+    }
+
+    async fn get_notification_preferences(
+        &self,
+        user_id: Uuid,
+    ) -> anyhow::Result<Option<NotificationPreferences>> {
+        self.get_notification_preferences_impl(user_id).await
+    }
+
+    async fn update_notification_preferences(
+        &self,
+        user_id: Uuid,
+        email: &str,
+        preferences: &NotificationPreferences,
+    ) -> anyhow::Result<()> {
+        self.update_notification_preferences_impl(user_id, email, preferences)
+            .await
+    }
+
+    async fn list_notification_subscribers(&self) -> anyhow::Result<Vec<NotificationSubscriber>> {
+        self.list_notification_subscribers_impl().await
+    }
+
+    async fn bump_version(
+        &self,
+        node: VersionedNode,
+        id: Uuid,
+        expected: Option<i64>,
+    ) -> anyhow::Result<VersionBump> {
+        self.bump_version_impl(node, id, expected).await
+    }
+
+    async fn restore_version(
+        &self,
+        node: VersionedNode,
+        id: Uuid,
+        bumped: i64,
+    ) -> anyhow::Result<bool> {
+        self.restore_version_impl(node, id, bumped).await
+    }
+
     // ========================================================================
     // EventTrigger operations
     // ========================================================================
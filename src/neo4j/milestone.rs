@@ -265,6 +265,25 @@ impl Neo4jClient {
         Ok(())
     }
 
+    /// Get the plans linked to a project milestone
+    pub async fn get_milestone_plan_ids(&self, milestone_id: Uuid) -> Result<Vec<Uuid>> {
+        let q = query(
+            r#"
+            MATCH (p:Plan)-[:TARGETS_MILESTONE]->(m:Milestone {id: $milestone_id})
+            RETURN p.id AS id
+            "#,
+        )
+        .param("milestone_id", milestone_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut ids = Vec::new();
+        while let Some(row) = result.next().await? {
+            let id: String = row.get("id")?;
+            ids.push(id.parse()?);
+        }
+        Ok(ids)
+    }
+
     /// Get milestone details with tasks
     /// Returns tasks from both direct links (INCLUDES_TASK) and
     /// plan-based links (TARGETS_MILESTONE → HAS_TASK)
@@ -2,7 +2,7 @@
 //!
 //! Provides a complete mock of all graph operations using
 //! `tokio::sync::RwLock<HashMap<K, V>>` collections.
-//! Conditionally compiled with `#[cfg(test)]`.
+//! Compiled for tests and with the `testkit` feature (see `crate::testkit`).
 
 use crate::events::trigger::EventTrigger;
 use crate::lifecycle::{LifecycleHook, LifecycleScope, UpdateLifecycleHookRequest};
@@ -72,8 +72,13 @@ pub struct MockGraphStore {
     pub chat_events: RwLock<HashMap<Uuid, Vec<ChatEventRecord>>>,
     /// Per-session auto_continue flag (stored separately from ChatSessionNode)
     pub session_auto_continue: RwLock<HashMap<Uuid, bool>>,
+    pub chat_session_memories: RwLock<HashMap<Uuid, ChatSessionMemory>>,
+    pub chat_session_usages: RwLock<HashMap<Uuid, ChatSessionUsage>>,
     /// PlanRun states (Runner)
     pub plan_runs: RwLock<HashMap<Uuid, crate::runner::RunnerState>>,
+    pub run_reports: RwLock<HashMap<Uuid, crate::runner::RunReport>>,
+    /// AgentExecution nodes keyed by id
+    pub agent_executions: RwLock<HashMap<Uuid, crate::neo4j::agent_execution::AgentExecutionNode>>,
     /// Triggers
     pub triggers: RwLock<HashMap<Uuid, crate::runner::Trigger>>,
     /// Trigger firings
@@ -87,9 +92,13 @@ pub struct MockGraphStore {
     pub plan_constraints: RwLock<HashMap<Uuid, Vec<Uuid>>>,
     pub project_plans: RwLock<HashMap<Uuid, Vec<Uuid>>>,
     pub project_files: RwLock<HashMap<Uuid, Vec<String>>>,
+    /// File path -> content hash of its last indexed search document
+    pub indexed_hashes: RwLock<HashMap<String, String>>,
+    pub project_language_stats: RwLock<HashMap<Uuid, Vec<LanguageStats>>>,
     pub file_symbols: RwLock<HashMap<String, Vec<String>>>,
     pub task_files: RwLock<HashMap<Uuid, Vec<String>>>,
     pub task_commits: RwLock<HashMap<Uuid, Vec<String>>>,
+    pub task_implementing_commits: RwLock<HashMap<Uuid, Vec<String>>>,
     pub plan_commits: RwLock<HashMap<Uuid, Vec<String>>>,
     pub project_releases: RwLock<HashMap<Uuid, Vec<Uuid>>>,
     pub project_milestones: RwLock<HashMap<Uuid, Vec<Uuid>>>,
@@ -108,6 +117,62 @@ pub struct MockGraphStore {
     pub component_projects: RwLock<HashMap<Uuid, Uuid>>,
     pub resource_implementers: RwLock<HashMap<Uuid, Vec<Uuid>>>,
     pub resource_consumers: RwLock<HashMap<Uuid, Vec<Uuid>>>,
+    pub resource_symbol_links: RwLock<Vec<ResourceSymbolLink>>,
+    pub resource_elements: RwLock<HashMap<Uuid, Vec<ResourceElementNode>>>,
+    pub project_packages: RwLock<HashMap<Uuid, Vec<PackageNode>>>,
+    pub project_advisories: RwLock<HashMap<Uuid, Vec<ProjectAdvisory>>>,
+    pub documents: RwLock<HashMap<String, DocumentNode>>,
+    pub document_sections: RwLock<HashMap<String, Vec<DocSectionNode>>>,
+    pub project_tables: RwLock<HashMap<Uuid, Vec<TableNode>>>,
+    pub project_migrations: RwLock<HashMap<Uuid, Vec<MigrationNode>>>,
+    pub project_config_keys: RwLock<HashMap<Uuid, Vec<ConfigKeyNode>>>,
+    pub module_summaries: RwLock<HashMap<Uuid, Vec<ModuleSummaryNode>>>,
+    /// Clone fingerprints keyed by function id (`file_path:name:line_start`)
+    pub function_fingerprints: RwLock<HashMap<String, FunctionFingerprint>>,
+    pub project_budgets: RwLock<HashMap<Uuid, ProjectBudgets>>,
+    pub project_sync_limits: RwLock<HashMap<Uuid, SyncLimits>>,
+    pub budget_reports: RwLock<HashMap<Uuid, BudgetCheckReport>>,
+    /// Test coverage keyed by function id (`file_path:name:line_start`)
+    pub function_coverage: RwLock<HashMap<String, CoverageUpdate>>,
+    /// Test coverage keyed by file path
+    pub file_coverage: RwLock<HashMap<String, CoverageUpdate>>,
+    /// API surface snapshots per project, oldest first
+    pub api_surface_snapshots: RwLock<HashMap<Uuid, Vec<ApiSurfaceSnapshotNode>>>,
+    /// CI test runs per project, oldest first
+    pub test_runs: RwLock<HashMap<Uuid, Vec<TestRunNode>>>,
+    /// Test cases keyed by (project, key)
+    pub test_cases: RwLock<HashMap<(Uuid, String), TestCaseNode>>,
+    /// Open Sentry issues per project
+    pub sentry_issues: RwLock<HashMap<Uuid, Vec<SentryIssueNode>>>,
+    /// CODEOWNERS rules per project
+    pub code_owner_rules: RwLock<HashMap<Uuid, Vec<CodeOwnerRule>>>,
+    /// Owners of each owned file, per project
+    pub file_owners: RwLock<HashMap<Uuid, HashMap<String, Vec<String>>>>,
+    /// Context item votes per chat session
+    pub context_feedback: RwLock<HashMap<Uuid, Vec<ContextFeedbackNode>>>,
+    /// Endorsements per note, oldest first
+    pub note_endorsements: RwLock<HashMap<Uuid, Vec<NoteEndorsementNode>>>,
+    /// Undo log per chat session, oldest first
+    pub undo_log: RwLock<HashMap<Uuid, Vec<UndoEntry>>>,
+    /// Comments on tasks, plans and notes
+    pub comments: RwLock<HashMap<Uuid, CommentNode>>,
+    pub github_checks: RwLock<HashMap<Uuid, GitHubCheckSettings>>,
+    pub quota_settings: RwLock<Option<QuotaSettings>>,
+    pub workspace_quotas: RwLock<HashMap<Uuid, QuotaLimits>>,
+    pub prompt_templates: RwLock<HashMap<Uuid, PromptTemplateNode>>,
+    pub prompt_template_versions: RwLock<HashMap<Uuid, Vec<PromptTemplateVersion>>>,
+    pub saved_views: RwLock<HashMap<Uuid, SavedViewNode>>,
+    /// SearchQuery counters keyed by (endpoint, project_slug, query)
+    pub search_queries: RwLock<HashMap<(String, String, String), SearchQueryStats>>,
+    pub search_settings: RwLock<Option<crate::meilisearch::indexes::SearchSettings>>,
+    pub retention_policy: RwLock<Option<RetentionPolicy>>,
+    pub retention_report: RwLock<Option<RetentionReport>>,
+    /// UsageCounter counts keyed by (day, route, client)
+    pub usage_counters: RwLock<HashMap<(chrono::NaiveDate, String, String), u64>>,
+    /// Notification preferences keyed by user id (subscriber = email + preferences)
+    pub notification_preferences: RwLock<HashMap<Uuid, NotificationSubscriber>>,
+    /// HAS_ADR: project_id -> decision_id -> ADR file
+    pub project_adrs: RwLock<HashMap<Uuid, HashMap<Uuid, AdrLink>>>,
     pub import_relationships: RwLock<HashMap<String, Vec<String>>>,
     pub call_relationships: RwLock<HashMap<String, Vec<String>>>,
     pub note_anchors: RwLock<HashMap<Uuid, Vec<NoteAnchor>>>,
@@ -210,6 +275,14 @@ pub struct MockGraphStore {
     pub mock_has_context_cards: std::sync::atomic::AtomicBool,
     /// When true, `set_watch_enabled()` returns an error (default: false)
     pub mock_fail_set_watch_enabled: std::sync::atomic::AtomicBool,
+    /// When set, `create_step()` returns an error for the step of this order
+    /// (default: None)
+    pub mock_fail_step_order: std::sync::Mutex<Option<u32>>,
+    /// When true, `create_constraint()` returns an error (default: false)
+    pub mock_fail_create_constraint: std::sync::atomic::AtomicBool,
+    /// When set, `create_task()` returns an error for the task of this title
+    /// (default: None)
+    pub mock_fail_task_title: std::sync::Mutex<Option<String>>,
 }
 
 #[allow(dead_code)]
@@ -241,7 +314,11 @@ impl MockGraphStore {
             chat_sessions: RwLock::new(HashMap::new()),
             chat_events: RwLock::new(HashMap::new()),
             session_auto_continue: RwLock::new(HashMap::new()),
+            chat_session_memories: RwLock::new(HashMap::new()),
+            chat_session_usages: RwLock::new(HashMap::new()),
             plan_runs: RwLock::new(HashMap::new()),
+            run_reports: RwLock::new(HashMap::new()),
+            agent_executions: RwLock::new(HashMap::new()),
             triggers: RwLock::new(HashMap::new()),
             trigger_firings: RwLock::new(HashMap::new()),
             plan_tasks: RwLock::new(HashMap::new()),
@@ -251,9 +328,12 @@ impl MockGraphStore {
             plan_constraints: RwLock::new(HashMap::new()),
             project_plans: RwLock::new(HashMap::new()),
             project_files: RwLock::new(HashMap::new()),
+            indexed_hashes: RwLock::new(HashMap::new()),
+            project_language_stats: RwLock::new(HashMap::new()),
             file_symbols: RwLock::new(HashMap::new()),
             task_files: RwLock::new(HashMap::new()),
             task_commits: RwLock::new(HashMap::new()),
+            task_implementing_commits: RwLock::new(HashMap::new()),
             plan_commits: RwLock::new(HashMap::new()),
             project_releases: RwLock::new(HashMap::new()),
             project_milestones: RwLock::new(HashMap::new()),
@@ -271,6 +351,45 @@ impl MockGraphStore {
             component_projects: RwLock::new(HashMap::new()),
             resource_implementers: RwLock::new(HashMap::new()),
             resource_consumers: RwLock::new(HashMap::new()),
+            resource_symbol_links: RwLock::new(Vec::new()),
+            resource_elements: RwLock::new(HashMap::new()),
+            project_packages: RwLock::new(HashMap::new()),
+            project_advisories: RwLock::new(HashMap::new()),
+            documents: RwLock::new(HashMap::new()),
+            document_sections: RwLock::new(HashMap::new()),
+            project_tables: RwLock::new(HashMap::new()),
+            project_migrations: RwLock::new(HashMap::new()),
+            project_config_keys: RwLock::new(HashMap::new()),
+            module_summaries: RwLock::new(HashMap::new()),
+            function_fingerprints: RwLock::new(HashMap::new()),
+            project_budgets: RwLock::new(HashMap::new()),
+            project_sync_limits: RwLock::new(HashMap::new()),
+            budget_reports: RwLock::new(HashMap::new()),
+            function_coverage: RwLock::new(HashMap::new()),
+            file_coverage: RwLock::new(HashMap::new()),
+            api_surface_snapshots: RwLock::new(HashMap::new()),
+            test_runs: RwLock::new(HashMap::new()),
+            test_cases: RwLock::new(HashMap::new()),
+            sentry_issues: RwLock::new(HashMap::new()),
+            code_owner_rules: RwLock::new(HashMap::new()),
+            file_owners: RwLock::new(HashMap::new()),
+            context_feedback: RwLock::new(HashMap::new()),
+            note_endorsements: RwLock::new(HashMap::new()),
+            undo_log: RwLock::new(HashMap::new()),
+            comments: RwLock::new(HashMap::new()),
+            github_checks: RwLock::new(HashMap::new()),
+            quota_settings: RwLock::new(None),
+            workspace_quotas: RwLock::new(HashMap::new()),
+            prompt_templates: RwLock::new(HashMap::new()),
+            prompt_template_versions: RwLock::new(HashMap::new()),
+            saved_views: RwLock::new(HashMap::new()),
+            search_queries: RwLock::new(HashMap::new()),
+            search_settings: RwLock::new(None),
+            retention_policy: RwLock::new(None),
+            retention_report: RwLock::new(None),
+            usage_counters: RwLock::new(HashMap::new()),
+            notification_preferences: RwLock::new(HashMap::new()),
+            project_adrs: RwLock::new(HashMap::new()),
             import_relationships: RwLock::new(HashMap::new()),
             call_relationships: RwLock::new(HashMap::new()),
             note_anchors: RwLock::new(HashMap::new()),
@@ -319,6 +438,9 @@ impl MockGraphStore {
             mcp_often_follows: RwLock::new(HashMap::new()),
             mock_has_context_cards: std::sync::atomic::AtomicBool::new(false),
             mock_fail_set_watch_enabled: std::sync::atomic::AtomicBool::new(false),
+            mock_fail_step_order: std::sync::Mutex::new(None),
+            mock_fail_create_constraint: std::sync::atomic::AtomicBool::new(false),
+            mock_fail_task_title: std::sync::Mutex::new(None),
         }
     }
 
@@ -458,6 +580,42 @@ impl MockGraphStore {
         self.milestones.write().await.insert(ms_id, milestone);
         self
     }
+
+    /// Functions kept by `keep` covered below `max_coverage_pct`, highest
+    /// PageRank first.
+    async fn untested_functions(
+        &self,
+        keep: impl Fn(&FunctionNode) -> bool,
+        max_coverage_pct: f64,
+    ) -> Vec<UntestedFunctionRow> {
+        let coverage = self.function_coverage.read().await;
+        let analytics = self.function_analytics.read().await;
+        let mut rows: Vec<UntestedFunctionRow> = self
+            .functions
+            .read()
+            .await
+            .values()
+            .filter(|f| keep(f))
+            .filter_map(|f| {
+                let id = format!("{}:{}:{}", f.file_path, f.name, f.line_start);
+                let coverage_pct = coverage.get(&id)?.coverage_pct;
+                (coverage_pct < max_coverage_pct).then(|| UntestedFunctionRow {
+                    name: f.name.clone(),
+                    file_path: f.file_path.clone(),
+                    line_start: f.line_start,
+                    coverage_pct,
+                    pagerank: analytics.get(&id).map(|a| a.pagerank).unwrap_or(0.0),
+                    id,
+                })
+            })
+            .collect();
+        rows.sort_by(|a, b| {
+            b.pagerank
+                .total_cmp(&a.pagerank)
+                .then(a.coverage_pct.total_cmp(&b.coverage_pct))
+        });
+        rows
+    }
 }
 
 // ============================================================================
@@ -527,10 +685,46 @@ impl GraphStore for MockGraphStore {
     async fn update_project_synced(&self, id: Uuid) -> Result<()> {
         if let Some(p) = self.projects.write().await.get_mut(&id) {
             p.last_synced = Some(Utc::now());
+        } else {
+            return Ok(());
+        }
+        let mut by_language: HashMap<String, LanguageStats> = HashMap::new();
+        let files = self.files.read().await;
+        for file in self
+            .project_files
+            .read()
+            .await
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .filter_map(|path| files.get(path))
+        {
+            let entry = by_language
+                .entry(file.language.clone())
+                .or_insert_with(|| LanguageStats {
+                    language: file.language.clone(),
+                    files: 0,
+                    lines: 0,
+                });
+            entry.files += 1;
+            entry.lines += file.line_count as u64;
         }
+        let mut stats: Vec<LanguageStats> = by_language.into_values().collect();
+        stats.sort_by(|a, b| b.lines.cmp(&a.lines).then(a.language.cmp(&b.language)));
+        self.project_language_stats.write().await.insert(id, stats);
         Ok(())
     }
 
+    async fn get_project_language_stats(&self, project_id: Uuid) -> Result<Vec<LanguageStats>> {
+        Ok(self
+            .project_language_stats
+            .read()
+            .await
+            .get(&project_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
     async fn update_project_analytics_timestamp(&self, id: Uuid) -> Result<()> {
         if let Some(p) = self.projects.write().await.get_mut(&id) {
             p.analytics_computed_at = Some(Utc::now());
@@ -542,6 +736,7 @@ impl GraphStore for MockGraphStore {
         self.projects.write().await.remove(&id);
         // Cascade: remove project files
         self.project_files.write().await.remove(&id);
+        self.project_language_stats.write().await.remove(&id);
         // Cascade: remove project plans and their children
         // Note: collect plan_ids first and drop the lock before calling delete_plan
         // to avoid deadlock (delete_plan also acquires project_plans lock)
@@ -686,6 +881,104 @@ impl GraphStore for MockGraphStore {
         Ok(None)
     }
 
+    async fn get_workspace_stats(&self, workspace_id: Uuid) -> Result<WorkspaceStats> {
+        let projects = self.list_workspace_projects(workspace_id).await?;
+        let project_ids: Vec<Uuid> = projects.iter().map(|p| p.id).collect();
+        let mut stats = WorkspaceStats {
+            project_count: projects.len() as u64,
+            projects_without_analytics: projects
+                .iter()
+                .filter(|p| p.analytics_computed_at.is_none())
+                .count() as u64,
+            analytics_oldest_at: projects
+                .iter()
+                .filter_map(|p| p.analytics_computed_at)
+                .min(),
+            analytics_newest_at: projects
+                .iter()
+                .filter_map(|p| p.analytics_computed_at)
+                .max(),
+            ..Default::default()
+        };
+
+        let pf = self.project_files.read().await;
+        let files = self.files.read().await;
+        let functions = self.functions.read().await;
+        let structs = self.structs_map.read().await;
+        let traits = self.traits_map.read().await;
+        let enums = self.enums_map.read().await;
+        for file in project_ids
+            .iter()
+            .flat_map(|id| pf.get(id).cloned().unwrap_or_default())
+            .filter_map(|path| files.get(&path))
+        {
+            stats.files += 1;
+            *stats.languages.entry(file.language.clone()).or_default() += 1;
+            let in_file = |file_path: &str| file_path == file.path;
+            stats.functions += functions.values().filter(|f| in_file(&f.file_path)).count() as u64;
+            stats.structs += structs.values().filter(|s| in_file(&s.file_path)).count() as u64;
+            stats.traits += traits.values().filter(|t| in_file(&t.file_path)).count() as u64;
+            stats.enums += enums.values().filter(|e| in_file(&e.file_path)).count() as u64;
+            stats.lines_indexed += functions
+                .values()
+                .filter(|f| in_file(&f.file_path))
+                .map(|f| f.line_end)
+                .chain(
+                    structs
+                        .values()
+                        .filter(|s| in_file(&s.file_path))
+                        .map(|s| s.line_end),
+                )
+                .chain(
+                    traits
+                        .values()
+                        .filter(|t| in_file(&t.file_path))
+                        .map(|t| t.line_end),
+                )
+                .chain(
+                    enums
+                        .values()
+                        .filter(|e| in_file(&e.file_path))
+                        .map(|e| e.line_end),
+                )
+                .max()
+                .unwrap_or(0) as u64;
+        }
+
+        for note in self.notes.read().await.values() {
+            if note.project_id.is_some_and(|id| project_ids.contains(&id)) {
+                *stats
+                    .notes_by_type
+                    .entry(note.note_type.to_string())
+                    .or_default() += 1;
+            }
+        }
+
+        let pp = self.project_plans.read().await;
+        let plans = self.plans.read().await;
+        let pt = self.plan_tasks.read().await;
+        let tasks = self.tasks.read().await;
+        for plan_id in project_ids
+            .iter()
+            .flat_map(|id| pp.get(id).cloned().unwrap_or_default())
+        {
+            if plans
+                .get(&plan_id)
+                .is_some_and(|p| !matches!(p.status, PlanStatus::Completed | PlanStatus::Cancelled))
+            {
+                stats.open_plans += 1;
+            }
+            stats.open_tasks += pt
+                .get(&plan_id)
+                .into_iter()
+                .flatten()
+                .filter_map(|id| tasks.get(id))
+                .filter(|t| !matches!(t.status, TaskStatus::Completed | TaskStatus::Failed))
+                .count() as u64;
+        }
+        Ok(stats)
+    }
+
     async fn compute_coupling_matrix(&self, workspace_id: Uuid) -> Result<CouplingMatrix> {
         let projects = self.list_workspace_projects(workspace_id).await?;
         Ok(CouplingMatrix {
@@ -1086,6 +1379,7 @@ impl GraphStore for MockGraphStore {
         }
         self.resource_implementers.write().await.remove(&id);
         self.resource_consumers.write().await.remove(&id);
+        self.resource_elements.write().await.remove(&id);
         Ok(())
     }
 
@@ -1133,6 +1427,60 @@ impl GraphStore for MockGraphStore {
             .collect())
     }
 
+    async fn replace_resource_symbol_links(
+        &self,
+        project_id: Uuid,
+        links: &[ResourceSymbolLink],
+    ) -> Result<usize> {
+        let mut store = self.resource_symbol_links.write().await;
+        store.retain(|l| l.project_id != project_id);
+        store.extend(links.iter().cloned());
+        Ok(links.len())
+    }
+
+    async fn get_resource_symbol_links(
+        &self,
+        resource_id: Uuid,
+    ) -> Result<Vec<ResourceSymbolLink>> {
+        Ok(self
+            .resource_symbol_links
+            .read()
+            .await
+            .iter()
+            .filter(|l| l.resource_id == resource_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn replace_resource_elements(
+        &self,
+        resource_id: Uuid,
+        elements: &[ResourceElementNode],
+    ) -> Result<usize> {
+        self.resource_elements
+            .write()
+            .await
+            .insert(resource_id, elements.to_vec());
+        Ok(elements.len())
+    }
+
+    async fn list_resource_elements(
+        &self,
+        resource_id: Uuid,
+        kind: Option<ResourceElementKind>,
+    ) -> Result<Vec<ResourceElementNode>> {
+        let store = self.resource_elements.read().await;
+        let mut elements: Vec<ResourceElementNode> = store
+            .get(&resource_id)
+            .into_iter()
+            .flatten()
+            .filter(|e| kind.is_none_or(|k| e.kind == k))
+            .cloned()
+            .collect();
+        elements.sort_by(|a, b| (a.kind, &a.name).cmp(&(b.kind, &b.name)));
+        Ok(elements)
+    }
+
     // ========================================================================
     // Component operations (Topology)
     // ========================================================================
@@ -1287,6 +1635,18 @@ impl GraphStore for MockGraphStore {
         Ok(result)
     }
 
+    async fn list_component_project_mappings(
+        &self,
+        workspace_id: Uuid,
+    ) -> Result<Vec<(Uuid, Uuid)>> {
+        let components = self.list_components(workspace_id).await?;
+        let cp = self.component_projects.read().await;
+        Ok(components
+            .iter()
+            .filter_map(|c| cp.get(&c.id).map(|pid| (c.id, *pid)))
+            .collect())
+    }
+
     // ========================================================================
     // File operations
     // ========================================================================
@@ -1303,6 +1663,7 @@ impl GraphStore for MockGraphStore {
 
     async fn delete_file(&self, path: &str) -> Result<()> {
         self.files.write().await.remove(path);
+        self.indexed_hashes.write().await.remove(path);
         // Remove from project_files
         let mut pf = self.project_files.write().await;
         for paths in pf.values_mut() {
@@ -1417,11 +1778,124 @@ impl GraphStore for MockGraphStore {
         Ok(paths.iter().filter_map(|p| files.get(p).cloned()).collect())
     }
 
+    async fn get_project_file_stats(&self, project_id: Uuid) -> Result<Vec<FileStatsRow>> {
+        let pf = self.project_files.read().await;
+        let files = self.files.read().await;
+        let functions = self.functions.read().await;
+        let structs = self.structs_map.read().await;
+        let traits = self.traits_map.read().await;
+        let enums = self.enums_map.read().await;
+        let fa = self.file_analytics.read().await;
+        let paths = pf.get(&project_id).cloned().unwrap_or_default();
+        Ok(paths
+            .iter()
+            .filter_map(|p| files.get(p))
+            .map(|file| {
+                let complexities: Vec<u32> = functions
+                    .values()
+                    .filter(|f| f.file_path == file.path)
+                    .map(|f| f.complexity)
+                    .collect();
+                let type_count = structs
+                    .values()
+                    .filter(|s| s.file_path == file.path)
+                    .count()
+                    + traits.values().filter(|t| t.file_path == file.path).count()
+                    + enums.values().filter(|e| e.file_path == file.path).count();
+                FileStatsRow {
+                    path: file.path.clone(),
+                    language: file.language.clone(),
+                    last_parsed: file.last_parsed,
+                    function_count: complexities.len() as u32,
+                    type_count: type_count as u32,
+                    complexity_total: complexities.iter().map(|c| *c as u64).sum(),
+                    max_complexity: complexities.iter().copied().max().unwrap_or(0),
+                    analyzed: fa.contains_key(&file.path),
+                    analytics_stale: false,
+                    risk_level: None,
+                }
+            })
+            .collect())
+    }
+
+    async fn get_functions_above_complexity(
+        &self,
+        project_id: Uuid,
+        max_complexity: u32,
+    ) -> Result<Vec<FunctionComplexityRow>> {
+        let pf = self.project_files.read().await;
+        let functions = self.functions.read().await;
+        let paths = pf.get(&project_id).cloned().unwrap_or_default();
+        let mut rows: Vec<FunctionComplexityRow> = functions
+            .values()
+            .filter(|f| paths.contains(&f.file_path) && f.complexity > max_complexity)
+            .map(|f| FunctionComplexityRow {
+                id: format!("{}:{}:{}", f.file_path, f.name, f.line_start),
+                name: f.name.clone(),
+                file_path: f.file_path.clone(),
+                line_start: f.line_start,
+                complexity: f.complexity,
+            })
+            .collect();
+        rows.sort_by(|a, b| b.complexity.cmp(&a.complexity).then(a.id.cmp(&b.id)));
+        Ok(rows)
+    }
+
+    async fn get_project_function_fingerprints(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<FunctionFingerprintRow>> {
+        let pf = self.project_files.read().await;
+        let functions = self.functions.read().await;
+        let fingerprints = self.function_fingerprints.read().await;
+        let paths = pf.get(&project_id).cloned().unwrap_or_default();
+        let mut rows: Vec<FunctionFingerprintRow> = functions
+            .values()
+            .filter(|f| paths.contains(&f.file_path))
+            .filter_map(|f| {
+                let id = format!("{}:{}:{}", f.file_path, f.name, f.line_start);
+                let fp = fingerprints.get(&id)?;
+                Some(FunctionFingerprintRow {
+                    id,
+                    name: f.name.clone(),
+                    file_path: f.file_path.clone(),
+                    line_start: f.line_start,
+                    line_end: f.line_end,
+                    token_count: fp.token_count,
+                    minhash: fp.minhash.clone(),
+                })
+            })
+            .collect();
+        rows.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(rows)
+    }
+
     async fn count_project_files(&self, project_id: Uuid) -> Result<i64> {
         let pf = self.project_files.read().await;
         Ok(pf.get(&project_id).map(|p| p.len() as i64).unwrap_or(0))
     }
 
+    async fn get_indexed_hashes(&self, paths: &[String]) -> Result<HashMap<String, String>> {
+        let files = self.files.read().await;
+        let indexed = self.indexed_hashes.read().await;
+        Ok(paths
+            .iter()
+            .filter(|path| files.contains_key(*path))
+            .filter_map(|path| Some((path.clone(), indexed.get(path)?.clone())))
+            .collect())
+    }
+
+    async fn set_indexed_hashes(&self, hashes: &[(String, String)]) -> Result<()> {
+        let files = self.files.read().await;
+        let mut indexed = self.indexed_hashes.write().await;
+        for (path, hash) in hashes {
+            if files.contains_key(path) {
+                indexed.insert(path.clone(), hash.clone());
+            }
+        }
+        Ok(())
+    }
+
     async fn count_orphan_files(&self, _project_id: Uuid) -> Result<i64> {
         Ok(0)
     }
@@ -1601,6 +2075,24 @@ impl GraphStore for MockGraphStore {
         Ok(())
     }
 
+    async fn batch_set_function_fingerprints(
+        &self,
+        file_paths: &[String],
+        fingerprints: &[FunctionFingerprint],
+    ) -> Result<()> {
+        let mut stored = self.function_fingerprints.write().await;
+        stored.retain(|id, _| {
+            !file_paths.iter().any(|p| {
+                id.strip_prefix(p.as_str())
+                    .is_some_and(|r| r.starts_with(':'))
+            })
+        });
+        for fp in fingerprints {
+            stored.insert(fp.function_id.clone(), fp.clone());
+        }
+        Ok(())
+    }
+
     async fn batch_upsert_structs(&self, structs: &[StructNode]) -> Result<()> {
         for s in structs {
             self.upsert_struct(s).await?;
@@ -1972,6 +2464,7 @@ impl GraphStore for MockGraphStore {
                     line: s.line_start,
                     is_public: s.visibility == Visibility::Public,
                     docstring: s.docstring.clone(),
+                    fields: s.fields.clone(),
                 });
             }
         }
@@ -1989,83 +2482,354 @@ impl GraphStore for MockGraphStore {
         Ok(result)
     }
 
-    async fn find_symbol_references(
+    async fn get_symbol_location(&self, symbol_id: &str) -> Result<Option<SymbolLocationNode>> {
+        // (kind, name, file_path, line_start, line_end, docstring), matched
+        // against the ids the Neo4j store assigns
+        let mut found = None;
+        for f in self.functions.read().await.values() {
+            if format!("{}:{}:{}", f.file_path, f.name, f.line_start) == symbol_id {
+                found = Some((
+                    "function",
+                    f.name.clone(),
+                    f.file_path.clone(),
+                    f.line_start,
+                    f.line_end,
+                    f.docstring.clone(),
+                ));
+            }
+        }
+        let mut fields = vec![];
+        let mut variant_payloads = vec![];
+        for s in self.structs_map.read().await.values() {
+            if format!("{}:{}", s.file_path, s.name) == symbol_id {
+                fields = s.fields.clone();
+                found = Some((
+                    "struct",
+                    s.name.clone(),
+                    s.file_path.clone(),
+                    s.line_start,
+                    s.line_end,
+                    s.docstring.clone(),
+                ));
+            }
+        }
+        for t in self.traits_map.read().await.values() {
+            if format!("{}:{}", t.file_path, t.name) == symbol_id {
+                found = Some((
+                    "trait",
+                    t.name.clone(),
+                    t.file_path.clone(),
+                    t.line_start,
+                    t.line_end,
+                    t.docstring.clone(),
+                ));
+            }
+        }
+        for e in self.enums_map.read().await.values() {
+            if format!("{}:{}", e.file_path, e.name) == symbol_id {
+                variant_payloads = e.variant_payloads.clone();
+                found = Some((
+                    "enum",
+                    e.name.clone(),
+                    e.file_path.clone(),
+                    e.line_start,
+                    e.line_end,
+                    e.docstring.clone(),
+                ));
+            }
+        }
+        let Some((kind, name, file_path, line_start, line_end, docstring)) = found else {
+            return Ok(None);
+        };
+
+        let file = self.files.read().await.get(&file_path).cloned();
+        let project_id = file.as_ref().and_then(|f| f.project_id);
+        let project_root = match project_id {
+            Some(pid) => self
+                .projects
+                .read()
+                .await
+                .get(&pid)
+                .map(|p| p.root_path.clone()),
+            None => None,
+        };
+        Ok(Some(SymbolLocationNode {
+            id: symbol_id.to_string(),
+            name,
+            kind: kind.to_string(),
+            file_path,
+            line_start,
+            line_end,
+            language: file.as_ref().map(|f| f.language.clone()),
+            docstring,
+            file_hash: file.map(|f| f.hash),
+            project_id,
+            project_root,
+            fields,
+            variant_payloads,
+        }))
+    }
+
+    async fn find_symbol_dependents(
         &self,
-        symbol: &str,
+        symbol_id: &str,
         limit: usize,
-        project_id: Option<Uuid>,
     ) -> Result<Vec<SymbolReferenceNode>> {
-        let mut references = Vec::new();
-
-        // If project_id provided, get the set of file paths belonging to this project
-        let project_file_paths: Option<Vec<String>> = if let Some(pid) = project_id {
-            let pf = self.project_files.read().await;
-            Some(pf.get(&pid).cloned().unwrap_or_default())
-        } else {
-            None
+        // Callers are recorded by callee name; USES_TYPE is not tracked
+        let Some(location) = self.get_symbol_location(symbol_id).await? else {
+            return Ok(vec![]);
         };
-
-        // Find function callers (via call_relationships)
-        let cr = self.call_relationships.read().await;
-        let functions = self.functions.read().await;
-        for (caller_id, callees) in cr.iter() {
-            if callees.contains(&symbol.to_string()) {
-                if let Some(caller_fn) = functions.get(caller_id) {
-                    if let Some(ref paths) = project_file_paths {
-                        if !paths.contains(&caller_fn.file_path) {
-                            continue;
-                        }
-                    }
+        let mut references = Vec::new();
+        if location.kind == "function" {
+            let cr = self.call_relationships.read().await;
+            let functions = self.functions.read().await;
+            for (caller_id, callees) in cr.iter() {
+                if !callees.contains(&location.name) {
+                    continue;
+                }
+                if let Some(caller) = functions.get(caller_id) {
                     references.push(SymbolReferenceNode {
-                        file_path: caller_fn.file_path.clone(),
-                        line: caller_fn.line_start,
-                        context: format!("called from {}", caller_fn.name),
+                        file_path: caller.file_path.clone(),
+                        line: caller.line_start,
+                        context: caller.name.clone(),
                         reference_type: "call".to_string(),
                     });
-                    if references.len() >= limit {
-                        return Ok(references);
-                    }
                 }
             }
         }
-
-        // Find struct import usages (via imports)
-        let imports = self.imports.read().await;
-        let structs = self.structs_map.read().await;
-        let has_struct = structs.values().any(|s| s.name == symbol);
-        if has_struct {
-            for import in imports.values() {
-                if import.path.ends_with(symbol) {
-                    if let Some(ref paths) = project_file_paths {
-                        if !paths.contains(&import.file_path) {
-                            continue;
-                        }
-                    }
-                    references.push(SymbolReferenceNode {
-                        file_path: import.file_path.clone(),
-                        line: import.line,
-                        context: format!("imported via {}", import.path),
-                        reference_type: "import".to_string(),
-                    });
-                    if references.len() >= limit {
-                        return Ok(references);
-                    }
-                }
+        for imp in self.imports.read().await.values() {
+            if imp.items.contains(&location.name) {
+                references.push(SymbolReferenceNode {
+                    file_path: imp.file_path.clone(),
+                    line: imp.line,
+                    context: imp.path.clone(),
+                    reference_type: "import".to_string(),
+                });
             }
         }
-
+        references.sort_by(|a, b| (&a.file_path, a.line).cmp(&(&b.file_path, b.line)));
+        references.truncate(limit);
         Ok(references)
     }
 
-    async fn get_file_direct_imports(&self, path: &str) -> Result<Vec<FileImportNode>> {
-        let ir = self.import_relationships.read().await;
-        let files = self.files.read().await;
-        let imported = ir.get(path).cloned().unwrap_or_default();
-        Ok(imported
-            .iter()
-            .map(|p| {
-                let lang = files
-                    .get(p)
+    async fn list_file_symbols(&self, path: &str) -> Result<Vec<SymbolLocationNode>> {
+        let mut ids: Vec<String> = self
+            .functions
+            .read()
+            .await
+            .values()
+            .filter(|f| f.file_path == path)
+            .map(|f| format!("{}:{}:{}", f.file_path, f.name, f.line_start))
+            .collect();
+        for s in self.structs_map.read().await.values() {
+            if s.file_path == path {
+                ids.push(format!("{}:{}", s.file_path, s.name));
+            }
+        }
+        for t in self.traits_map.read().await.values() {
+            if t.file_path == path {
+                ids.push(format!("{}:{}", t.file_path, t.name));
+            }
+        }
+        for e in self.enums_map.read().await.values() {
+            if e.file_path == path {
+                ids.push(format!("{}:{}", e.file_path, e.name));
+            }
+        }
+        let mut symbols = Vec::new();
+        for id in ids {
+            if let Some(location) = self.get_symbol_location(&id).await? {
+                symbols.push(location);
+            }
+        }
+        symbols.sort_by(|a, b| (a.line_start, &a.name).cmp(&(b.line_start, &b.name)));
+        Ok(symbols)
+    }
+
+    async fn find_symbols_by_name(
+        &self,
+        name: &str,
+        project_id: Option<Uuid>,
+        limit: usize,
+    ) -> Result<Vec<SymbolLocationNode>> {
+        let mut ids: Vec<String> = self
+            .functions
+            .read()
+            .await
+            .values()
+            .filter(|f| f.name == name)
+            .map(|f| format!("{}:{}:{}", f.file_path, f.name, f.line_start))
+            .collect();
+        for s in self.structs_map.read().await.values() {
+            if s.name == name {
+                ids.push(format!("{}:{}", s.file_path, s.name));
+            }
+        }
+        for t in self.traits_map.read().await.values() {
+            if t.name == name {
+                ids.push(format!("{}:{}", t.file_path, t.name));
+            }
+        }
+        for e in self.enums_map.read().await.values() {
+            if e.name == name {
+                ids.push(format!("{}:{}", e.file_path, e.name));
+            }
+        }
+        let mut symbols = Vec::new();
+        for id in ids {
+            if let Some(location) = self.get_symbol_location(&id).await? {
+                if project_id.is_none() || location.project_id == project_id {
+                    symbols.push(location);
+                }
+            }
+        }
+        symbols.sort_by(|a, b| (&a.file_path, a.line_start).cmp(&(&b.file_path, b.line_start)));
+        symbols.truncate(limit);
+        Ok(symbols)
+    }
+
+    async fn find_symbols_matching(
+        &self,
+        terms: &[String],
+        project_id: Option<Uuid>,
+        limit: usize,
+    ) -> Result<Vec<SymbolLocationNode>> {
+        let matches = |name: &str| {
+            let name = name.to_lowercase();
+            terms.iter().any(|t| name.contains(t.as_str()))
+        };
+        let mut ids: Vec<String> = self
+            .functions
+            .read()
+            .await
+            .values()
+            .filter(|f| matches(&f.name))
+            .map(|f| format!("{}:{}:{}", f.file_path, f.name, f.line_start))
+            .collect();
+        for s in self.structs_map.read().await.values() {
+            if matches(&s.name) {
+                ids.push(format!("{}:{}", s.file_path, s.name));
+            }
+        }
+        for t in self.traits_map.read().await.values() {
+            if matches(&t.name) {
+                ids.push(format!("{}:{}", t.file_path, t.name));
+            }
+        }
+        for e in self.enums_map.read().await.values() {
+            if matches(&e.name) {
+                ids.push(format!("{}:{}", e.file_path, e.name));
+            }
+        }
+        let mut symbols = Vec::new();
+        for id in ids {
+            if let Some(location) = self.get_symbol_location(&id).await? {
+                if project_id.is_none() || location.project_id == project_id {
+                    symbols.push(location);
+                }
+            }
+        }
+        symbols.sort_by(|a, b| {
+            (a.name.len(), &a.file_path, a.line_start).cmp(&(
+                b.name.len(),
+                &b.file_path,
+                b.line_start,
+            ))
+        });
+        symbols.truncate(limit);
+        Ok(symbols)
+    }
+
+    async fn list_project_imports(&self, project_id: Uuid) -> Result<Vec<ImportNode>> {
+        let pf = self.project_files.read().await;
+        let files = pf.get(&project_id).cloned().unwrap_or_default();
+        let imports = self.imports.read().await;
+        let mut result: Vec<ImportNode> = imports
+            .values()
+            .filter(|imp| files.contains(&imp.file_path))
+            .cloned()
+            .collect();
+        result.sort_by(|a, b| (&a.file_path, a.line).cmp(&(&b.file_path, b.line)));
+        Ok(result)
+    }
+
+    async fn find_symbol_references(
+        &self,
+        symbol: &str,
+        limit: usize,
+        project_id: Option<Uuid>,
+    ) -> Result<Vec<SymbolReferenceNode>> {
+        let mut references = Vec::new();
+
+        // If project_id provided, get the set of file paths belonging to this project
+        let project_file_paths: Option<Vec<String>> = if let Some(pid) = project_id {
+            let pf = self.project_files.read().await;
+            Some(pf.get(&pid).cloned().unwrap_or_default())
+        } else {
+            None
+        };
+
+        // Find function callers (via call_relationships)
+        let cr = self.call_relationships.read().await;
+        let functions = self.functions.read().await;
+        for (caller_id, callees) in cr.iter() {
+            if callees.contains(&symbol.to_string()) {
+                if let Some(caller_fn) = functions.get(caller_id) {
+                    if let Some(ref paths) = project_file_paths {
+                        if !paths.contains(&caller_fn.file_path) {
+                            continue;
+                        }
+                    }
+                    references.push(SymbolReferenceNode {
+                        file_path: caller_fn.file_path.clone(),
+                        line: caller_fn.line_start,
+                        context: format!("called from {}", caller_fn.name),
+                        reference_type: "call".to_string(),
+                    });
+                    if references.len() >= limit {
+                        return Ok(references);
+                    }
+                }
+            }
+        }
+
+        // Find struct import usages (via imports)
+        let imports = self.imports.read().await;
+        let structs = self.structs_map.read().await;
+        let has_struct = structs.values().any(|s| s.name == symbol);
+        if has_struct {
+            for import in imports.values() {
+                if import.path.ends_with(symbol) {
+                    if let Some(ref paths) = project_file_paths {
+                        if !paths.contains(&import.file_path) {
+                            continue;
+                        }
+                    }
+                    references.push(SymbolReferenceNode {
+                        file_path: import.file_path.clone(),
+                        line: import.line,
+                        context: format!("imported via {}", import.path),
+                        reference_type: "import".to_string(),
+                    });
+                    if references.len() >= limit {
+                        return Ok(references);
+                    }
+                }
+            }
+        }
+
+        Ok(references)
+    }
+
+    async fn get_file_direct_imports(&self, path: &str) -> Result<Vec<FileImportNode>> {
+        let ir = self.import_relationships.read().await;
+        let files = self.files.read().await;
+        let imported = ir.get(path).cloned().unwrap_or_default();
+        Ok(imported
+            .iter()
+            .map(|p| {
+                let lang = files
+                    .get(p)
                     .map(|f| f.language.clone())
                     .unwrap_or_else(|| "unknown".to_string());
                 FileImportNode {
@@ -2081,7 +2845,12 @@ impl GraphStore for MockGraphStore {
         function_name: &str,
         _depth: u32,
         project_id: Option<Uuid>,
+        min_confidence: f64,
     ) -> Result<Vec<String>> {
+        // Calls are recorded at the default confidence
+        if min_confidence > 0.50 {
+            return Ok(Vec::new());
+        }
         let cr = self.call_relationships.read().await;
         let functions = self.functions.read().await;
 
@@ -2115,7 +2884,12 @@ impl GraphStore for MockGraphStore {
         function_name: &str,
         _depth: u32,
         project_id: Option<Uuid>,
+        min_confidence: f64,
     ) -> Result<Vec<String>> {
+        // Calls are recorded at the default confidence
+        if min_confidence > 0.50 {
+            return Ok(Vec::new());
+        }
         let cr = self.call_relationships.read().await;
         let functions = self.functions.read().await;
 
@@ -2186,6 +2960,107 @@ impl GraphStore for MockGraphStore {
         Ok(result)
     }
 
+    async fn get_name_based_references(
+        &self,
+        project_id: Uuid,
+        max_confidence: f64,
+        limit: usize,
+    ) -> Result<Vec<CodeReferenceNode>> {
+        // Calls are recorded by callee name only, at the default confidence
+        if max_confidence <= 0.50 {
+            return Ok(Vec::new());
+        }
+        let project_files = self
+            .project_files
+            .read()
+            .await
+            .get(&project_id)
+            .cloned()
+            .unwrap_or_default();
+        let functions = self.functions.read().await;
+        let mut references: Vec<CodeReferenceNode> = Vec::new();
+        for (caller_id, callees) in self.call_relationships.read().await.iter() {
+            let Some(caller) = functions.get(caller_id) else {
+                continue;
+            };
+            if !project_files.contains(&caller.file_path) {
+                continue;
+            }
+            for callee in callees {
+                references.push(CodeReferenceNode {
+                    kind: CodeReferenceKind::Call,
+                    function_id: caller_id.clone(),
+                    file_path: caller.file_path.clone(),
+                    line_start: caller.line_start,
+                    line_end: caller.line_end,
+                    name: callee.clone(),
+                    confidence: 0.50,
+                });
+            }
+        }
+        references.sort_by(|a, b| (&a.file_path, &a.name).cmp(&(&b.file_path, &b.name)));
+        references.truncate(limit);
+        Ok(references)
+    }
+
+    async fn find_symbol_declared_at(
+        &self,
+        kind: CodeReferenceKind,
+        file_path: &str,
+        line: u32,
+    ) -> Result<Option<String>> {
+        let mut spans: Vec<(String, u32, u32)> = Vec::new();
+        match kind {
+            CodeReferenceKind::Call => {
+                for (id, f) in self.functions.read().await.iter() {
+                    if f.file_path == file_path {
+                        spans.push((id.clone(), f.line_start, f.line_end));
+                    }
+                }
+            }
+            CodeReferenceKind::Type => {
+                for (id, s) in self.structs_map.read().await.iter() {
+                    if s.file_path == file_path {
+                        spans.push((id.clone(), s.line_start, s.line_end));
+                    }
+                }
+                for (id, e) in self.enums_map.read().await.iter() {
+                    if e.file_path == file_path {
+                        spans.push((id.clone(), e.line_start, e.line_end));
+                    }
+                }
+                for (id, t) in self.traits_map.read().await.iter() {
+                    if t.file_path == file_path {
+                        spans.push((id.clone(), t.line_start, t.line_end));
+                    }
+                }
+            }
+        }
+        Ok(spans
+            .into_iter()
+            .filter(|(_, start, _)| *start == line)
+            .min_by_key(|(_, start, end)| end - start)
+            .map(|(id, _, _)| id))
+    }
+
+    async fn resolve_code_reference(
+        &self,
+        kind: CodeReferenceKind,
+        function_id: &str,
+        name: &str,
+        target_id: Option<&str>,
+        _confidence: f64,
+        _reason: &str,
+    ) -> Result<()> {
+        // Calls are keyed by callee name: only a removal is visible
+        if kind == CodeReferenceKind::Call && target_id.is_none() {
+            if let Some(callees) = self.call_relationships.write().await.get_mut(function_id) {
+                callees.retain(|callee| callee != name);
+            }
+        }
+        Ok(())
+    }
+
     async fn get_language_stats(&self) -> Result<Vec<LanguageStatsNode>> {
         let files = self.files.read().await;
         let mut counts: HashMap<String, usize> = HashMap::new();
@@ -2690,6 +3565,14 @@ impl GraphStore for MockGraphStore {
         Ok(())
     }
 
+    async fn set_project_archived(&self, project_id: Uuid, archived: bool) -> Result<()> {
+        let mut projects = self.projects.write().await;
+        if let Some(p) = projects.get_mut(&project_id) {
+            p.archived = archived;
+        }
+        Ok(())
+    }
+
     async fn set_default_note_energy(&self, project_id: Uuid, energy: Option<f64>) -> Result<()> {
         let mut projects = self.projects.write().await;
         if let Some(p) = projects.get_mut(&project_id) {
@@ -3234,6 +4117,55 @@ impl GraphStore for MockGraphStore {
         Ok(())
     }
 
+    async fn create_plan_with_constraints(
+        &self,
+        plan: &PlanNode,
+        constraints: &[ConstraintNode],
+    ) -> Result<()> {
+        // All or nothing, like the Neo4j transaction
+        let result: Result<()> = async {
+            self.create_plan(plan).await?;
+            for constraint in constraints {
+                self.create_constraint(plan.id, constraint).await?;
+            }
+            Ok(())
+        }
+        .await;
+        if result.is_err() {
+            self.delete_plan(plan.id).await?;
+        }
+        result
+    }
+
+    async fn create_plan_with_tasks(
+        &self,
+        plan: &PlanNode,
+        constraints: &[ConstraintNode],
+        tasks: &[NewPlanTask],
+    ) -> Result<()> {
+        // All or nothing, like the Neo4j transaction
+        let result: Result<()> = async {
+            self.create_plan_with_constraints(plan, constraints).await?;
+            for new_task in tasks {
+                self.create_task(plan.id, &new_task.task).await?;
+                for step in &new_task.steps {
+                    self.create_step(new_task.task.id, step).await?;
+                }
+            }
+            for new_task in tasks {
+                for dep_id in &new_task.depends_on {
+                    self.add_task_dependency(new_task.task.id, *dep_id).await?;
+                }
+            }
+            Ok(())
+        }
+        .await;
+        if result.is_err() {
+            self.delete_plan(plan.id).await?;
+        }
+        result
+    }
+
     async fn get_plan(&self, id: Uuid) -> Result<Option<PlanNode>> {
         Ok(self.plans.read().await.get(&id).cloned())
     }
@@ -3318,7 +4250,11 @@ impl GraphStore for MockGraphStore {
     }
 
     async fn update_plan(&self, id: Uuid, updates: &UpdatePlanRequest) -> Result<()> {
+        if updates.title.is_none() && updates.description.is_none() && updates.priority.is_none() {
+            return Ok(());
+        }
         if let Some(p) = self.plans.write().await.get_mut(&id) {
+            p.version += 1;
             if let Some(title) = &updates.title {
                 p.title = title.clone();
             }
@@ -3335,6 +4271,7 @@ impl GraphStore for MockGraphStore {
     async fn update_plan_status(&self, id: Uuid, status: PlanStatus) -> Result<()> {
         if let Some(p) = self.plans.write().await.get_mut(&id) {
             p.status = status;
+            p.version += 1;
         }
         Ok(())
     }
@@ -3383,6 +4320,10 @@ impl GraphStore for MockGraphStore {
             }
         }
         self.plan_commits.write().await.remove(&plan_id);
+        self.comments
+            .write()
+            .await
+            .retain(|_, c| c.entity_id != plan_id);
         if let Some(plan) = self.plans.write().await.remove(&plan_id) {
             if let Some(pid) = plan.project_id {
                 if let Some(ids) = self.project_plans.write().await.get_mut(&pid) {
@@ -3398,6 +4339,9 @@ impl GraphStore for MockGraphStore {
     // ========================================================================
 
     async fn create_task(&self, plan_id: Uuid, task: &TaskNode) -> Result<()> {
+        if task.title.is_some() && *self.mock_fail_task_title.lock().unwrap() == task.title {
+            return Err(anyhow::anyhow!("mock: create_task forced failure"));
+        }
         let task_id = task.id;
         self.plan_tasks
             .write()
@@ -3409,6 +4353,34 @@ impl GraphStore for MockGraphStore {
         Ok(())
     }
 
+    async fn create_task_with_steps(
+        &self,
+        plan_id: Uuid,
+        task: &TaskNode,
+        depends_on: &[Uuid],
+        steps: &[StepNode],
+    ) -> Result<()> {
+        // All or nothing, like the Neo4j transaction
+        let result: Result<()> = async {
+            self.create_task(plan_id, task).await?;
+            for dep_id in depends_on {
+                self.add_task_dependency(task.id, *dep_id).await?;
+            }
+            for step in steps {
+                self.create_step(task.id, step).await?;
+            }
+            Ok(())
+        }
+        .await;
+        if result.is_err() {
+            self.delete_task(task.id).await?;
+            if let Some(ids) = self.plan_tasks.write().await.get_mut(&plan_id) {
+                ids.retain(|id| *id != task.id);
+            }
+        }
+        result
+    }
+
     async fn get_plan_tasks(&self, plan_id: Uuid) -> Result<Vec<TaskNode>> {
         let pt = self.plan_tasks.read().await;
         let tasks = self.tasks.read().await;
@@ -3446,12 +4418,14 @@ impl GraphStore for MockGraphStore {
             .get(&task_id)
             .cloned()
             .unwrap_or_default();
+        let implemented_by = self.get_task_implementing_commits(task_id).await?;
         Ok(Some(TaskDetails {
             task,
             steps,
             decisions,
             depends_on,
             modifies_files,
+            implemented_by,
         }))
     }
 
@@ -3520,6 +4494,7 @@ impl GraphStore for MockGraphStore {
         if let Some(t) = self.tasks.write().await.get_mut(&task_id) {
             t.status = status.clone();
             t.updated_at = Some(Utc::now());
+            t.version += 1;
             if status == TaskStatus::InProgress && t.started_at.is_none() {
                 t.started_at = Some(Utc::now());
             }
@@ -4001,6 +4976,7 @@ impl GraphStore for MockGraphStore {
                 t.estimated_complexity = Some(complexity);
             }
             t.updated_at = Some(Utc::now());
+            t.version += 1;
         }
         Ok(())
     }
@@ -4027,8 +5003,19 @@ impl GraphStore for MockGraphStore {
         Ok(())
     }
 
+    async fn set_task_board_position(&self, task_id: Uuid, position: &str) -> Result<()> {
+        if let Some(task) = self.tasks.write().await.get_mut(&task_id) {
+            task.board_position = Some(position.to_string());
+        }
+        Ok(())
+    }
+
     async fn delete_task(&self, task_id: Uuid) -> Result<()> {
         self.tasks.write().await.remove(&task_id);
+        self.comments
+            .write()
+            .await
+            .retain(|_, c| c.entity_id != task_id);
         // Cascade: steps
         if let Some(step_ids) = self.task_steps.write().await.remove(&task_id) {
             let mut steps = self.steps.write().await;
@@ -4046,6 +5033,10 @@ impl GraphStore for MockGraphStore {
         self.task_dependencies.write().await.remove(&task_id);
         self.task_files.write().await.remove(&task_id);
         self.task_commits.write().await.remove(&task_id);
+        self.task_implementing_commits
+            .write()
+            .await
+            .remove(&task_id);
         // Remove from other tasks' dependency lists
         let mut deps = self.task_dependencies.write().await;
         for dep_list in deps.values_mut() {
@@ -4099,6 +5090,9 @@ impl GraphStore for MockGraphStore {
     // ========================================================================
 
     async fn create_step(&self, task_id: Uuid, step: &StepNode) -> Result<()> {
+        if *self.mock_fail_step_order.lock().unwrap() == Some(step.order) {
+            return Err(anyhow::anyhow!("mock: create_step forced failure"));
+        }
         let step_id = step.id;
         self.task_steps
             .write()
@@ -4191,6 +5185,12 @@ impl GraphStore for MockGraphStore {
     // ========================================================================
 
     async fn create_constraint(&self, plan_id: Uuid, constraint: &ConstraintNode) -> Result<()> {
+        if self
+            .mock_fail_create_constraint
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            return Err(anyhow::anyhow!("mock: create_constraint forced failure"));
+        }
         let cid = constraint.id;
         self.plan_constraints
             .write()
@@ -4269,9 +5269,15 @@ impl GraphStore for MockGraphStore {
         Ok(self.decisions.read().await.get(&decision_id).cloned())
     }
 
-    async fn get_decision_project_id(&self, _decision_id: Uuid) -> Result<Option<String>> {
-        // Mock doesn't track Decision→Task→Plan→Project chain
-        Ok(None)
+    async fn get_decision_project_id(&self, decision_id: Uuid) -> Result<Option<String>> {
+        // Mock doesn't track Decision→Task→Plan→Project chain, only HAS_ADR
+        Ok(self
+            .project_adrs
+            .read()
+            .await
+            .iter()
+            .find(|(_, adrs)| adrs.contains_key(&decision_id))
+            .map(|(pid, _)| pid.to_string()))
     }
 
     async fn update_decision(
@@ -4305,6 +5311,9 @@ impl GraphStore for MockGraphStore {
         for ids in td.values_mut() {
             ids.retain(|id| *id != decision_id);
         }
+        for adrs in self.project_adrs.write().await.values_mut() {
+            adrs.remove(&decision_id);
+        }
         Ok(())
     }
 
@@ -4467,6 +5476,79 @@ impl GraphStore for MockGraphStore {
         Ok(vec![])
     }
 
+    async fn list_project_decisions(&self, project_id: Uuid) -> Result<Vec<ProjectDecision>> {
+        let decisions = self.decisions.read().await;
+        let adrs = self.project_adrs.read().await;
+        let adrs = adrs.get(&project_id);
+        let pp = self.project_plans.read().await;
+        let pt = self.plan_tasks.read().await;
+        let td = self.task_decisions.read().await;
+
+        let mut result = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        let task_ids = pp
+            .get(&project_id)
+            .into_iter()
+            .flatten()
+            .flat_map(|plan_id| pt.get(plan_id).into_iter().flatten());
+        for task_id in task_ids {
+            for did in td.get(task_id).into_iter().flatten() {
+                if let Some(d) = decisions.get(did) {
+                    seen.insert(*did);
+                    result.push(ProjectDecision {
+                        decision: d.clone(),
+                        task_id: Some(*task_id),
+                        adr: adrs.and_then(|a| a.get(did)).cloned(),
+                    });
+                }
+            }
+        }
+        for (did, adr) in adrs.into_iter().flatten() {
+            if seen.contains(did) || td.values().any(|ids| ids.contains(did)) {
+                continue;
+            }
+            if let Some(d) = decisions.get(did) {
+                result.push(ProjectDecision {
+                    decision: d.clone(),
+                    task_id: None,
+                    adr: Some(adr.clone()),
+                });
+            }
+        }
+        result.sort_by(|a, b| {
+            (a.decision.decided_at, a.decision.id).cmp(&(b.decision.decided_at, b.decision.id))
+        });
+        Ok(result)
+    }
+
+    async fn create_adr_decision(
+        &self,
+        project_id: Uuid,
+        decision: &DecisionNode,
+        adr: &AdrLink,
+    ) -> Result<()> {
+        self.decisions
+            .write()
+            .await
+            .insert(decision.id, decision.clone());
+        self.set_decision_adr(project_id, decision.id, adr).await
+    }
+
+    async fn set_decision_adr(
+        &self,
+        project_id: Uuid,
+        decision_id: Uuid,
+        adr: &AdrLink,
+    ) -> Result<()> {
+        self.project_adrs
+            .write()
+            .await
+            .entry(project_id)
+            .or_default()
+            .insert(decision_id, adr.clone());
+        Ok(())
+    }
+
     // ========================================================================
     // Dependency analysis
     // ========================================================================
@@ -4645,6 +5727,30 @@ impl GraphStore for MockGraphStore {
             .collect())
     }
 
+    async fn link_commit_implements_task(
+        &self,
+        commit_hash: &str,
+        task_id: Uuid,
+        _session_id: Option<Uuid>,
+    ) -> Result<()> {
+        let mut tc = self.task_implementing_commits.write().await;
+        let hashes = tc.entry(task_id).or_default();
+        if !hashes.iter().any(|h| h == commit_hash) {
+            hashes.push(commit_hash.to_string());
+        }
+        Ok(())
+    }
+
+    async fn get_task_implementing_commits(&self, task_id: Uuid) -> Result<Vec<CommitNode>> {
+        let tc = self.task_implementing_commits.read().await;
+        let commits = self.commits.read().await;
+        let hashes = tc.get(&task_id).cloned().unwrap_or_default();
+        Ok(hashes
+            .iter()
+            .filter_map(|h| commits.get(h).cloned())
+            .collect())
+    }
+
     async fn get_plan_commits(&self, plan_id: Uuid) -> Result<Vec<CommitNode>> {
         let pc = self.plan_commits.read().await;
         let commits = self.commits.read().await;
@@ -4999,6 +6105,16 @@ impl GraphStore for MockGraphStore {
         Ok(())
     }
 
+    async fn get_milestone_plan_ids(&self, milestone_id: Uuid) -> Result<Vec<Uuid>> {
+        Ok(self
+            .milestone_plans
+            .read()
+            .await
+            .get(&milestone_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
     async fn get_milestone_details(
         &self,
         milestone_id: Uuid,
@@ -5113,6 +6229,7 @@ impl GraphStore for MockGraphStore {
             }
         }
         self.milestone_tasks.write().await.remove(&milestone_id);
+        self.milestone_plans.write().await.remove(&milestone_id);
         Ok(())
     }
 
@@ -5430,10 +6547,12 @@ impl GraphStore for MockGraphStore {
         offset: usize,
         _sort_by: Option<&str>,
         _sort_order: &str,
+        include_archived: bool,
     ) -> Result<(Vec<ProjectNode>, usize)> {
         let projects = self.projects.read().await;
         let filtered: Vec<ProjectNode> = projects
             .values()
+            .filter(|p| include_archived || !p.archived)
             .filter(|p| {
                 if let Some(q) = search {
                     let q = q.to_lowercase();
@@ -5493,6 +6612,7 @@ impl GraphStore for MockGraphStore {
             if let Some(ss) = staleness_score {
                 n.staleness_score = ss;
             }
+            n.version += 1;
             Ok(Some(n.clone()))
         } else {
             Ok(None)
@@ -5502,6 +6622,8 @@ impl GraphStore for MockGraphStore {
     async fn delete_note(&self, id: Uuid) -> Result<bool> {
         let removed = self.notes.write().await.remove(&id).is_some();
         self.note_anchors.write().await.remove(&id);
+        self.comments.write().await.retain(|_, c| c.entity_id != id);
+        self.note_endorsements.write().await.remove(&id);
         // Also clean up synapses (both directions)
         if removed {
             let mut synapses = self.note_synapses.write().await;
@@ -5593,6 +6715,7 @@ impl GraphStore for MockGraphStore {
             entity_id: entity_id.to_string(),
             signature_hash: signature_hash.map(|s| s.to_string()),
             body_hash: body_hash.map(|s| s.to_string()),
+            symbol_id: None,
             last_verified: Utc::now(),
             is_valid: true,
         };
@@ -5818,6 +6941,63 @@ impl GraphStore for MockGraphStore {
             .unwrap_or_default())
     }
 
+    async fn get_symbol_anchors_for_file(
+        &self,
+        file_path: &str,
+    ) -> Result<Vec<(Uuid, NoteAnchor)>> {
+        let prefix = format!("{}:", file_path);
+        let anchors = self.note_anchors.read().await;
+        let mut found = Vec::new();
+        for (note_id, note_anchors) in anchors.iter() {
+            for a in note_anchors {
+                if matches!(a.entity_type, EntityType::Function | EntityType::Struct)
+                    && a.entity_id.starts_with(&prefix)
+                {
+                    found.push((*note_id, a.clone()));
+                }
+            }
+        }
+        Ok(found)
+    }
+
+    async fn reanchor_note(
+        &self,
+        note_id: Uuid,
+        entity_type: &EntityType,
+        old_entity_id: &str,
+        new_entity_id: &str,
+        symbol_id: &str,
+    ) -> Result<()> {
+        if let Some(anchors) = self.note_anchors.write().await.get_mut(&note_id) {
+            for a in anchors.iter_mut() {
+                if &a.entity_type == entity_type && a.entity_id == old_entity_id {
+                    a.entity_id = new_entity_id.to_string();
+                    a.symbol_id = Some(symbol_id.to_string());
+                    a.is_valid = true;
+                    a.last_verified = Utc::now();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn mark_note_anchor_orphaned(
+        &self,
+        note_id: Uuid,
+        entity_type: &EntityType,
+        entity_id: &str,
+    ) -> Result<()> {
+        if let Some(anchors) = self.note_anchors.write().await.get_mut(&note_id) {
+            for a in anchors.iter_mut() {
+                if &a.entity_type == entity_type && a.entity_id == entity_id {
+                    a.is_valid = false;
+                    a.last_verified = Utc::now();
+                }
+            }
+        }
+        Ok(())
+    }
+
     async fn set_note_embedding(
         &self,
         note_id: Uuid,
@@ -6848,12 +8028,46 @@ impl GraphStore for MockGraphStore {
         Ok(auto_continue.get(&id).copied().unwrap_or(false))
     }
 
+    async fn set_chat_session_memory(&self, id: Uuid, memory: &ChatSessionMemory) -> Result<()> {
+        if let Some(session) = self.chat_sessions.write().await.get_mut(&id) {
+            session.cli_session_id = None;
+        }
+        self.chat_session_memories
+            .write()
+            .await
+            .insert(id, memory.clone());
+        Ok(())
+    }
+
+    async fn get_chat_session_memory(&self, id: Uuid) -> Result<Option<ChatSessionMemory>> {
+        Ok(self.chat_session_memories.read().await.get(&id).cloned())
+    }
+
+    async fn set_chat_session_usage(
+        &self,
+        id: Uuid,
+        usage: Option<&ChatSessionUsage>,
+    ) -> Result<()> {
+        let mut usages = self.chat_session_usages.write().await;
+        match usage {
+            Some(usage) => usages.insert(id, usage.clone()),
+            None => usages.remove(&id),
+        };
+        Ok(())
+    }
+
+    async fn get_chat_session_usage(&self, id: Uuid) -> Result<Option<ChatSessionUsage>> {
+        Ok(self.chat_session_usages.read().await.get(&id).cloned())
+    }
+
     async fn backfill_chat_session_previews(&self) -> Result<usize> {
         // Mock: no events stored, nothing to backfill
         Ok(0)
     }
 
     async fn delete_chat_session(&self, id: Uuid) -> Result<bool> {
+        self.context_feedback.write().await.remove(&id);
+        self.undo_log.write().await.remove(&id);
         Ok(self.chat_sessions.write().await.remove(&id).is_some())
     }
 
@@ -8243,7 +9457,11 @@ impl GraphStore for MockGraphStore {
     async fn get_project_call_edges(
         &self,
         project_id: Uuid,
+        min_confidence: f64,
     ) -> anyhow::Result<Vec<(String, String)>> {
+        if min_confidence > 0.50 {
+            return Ok(vec![]);
+        }
         let pf = self.project_files.read().await;
         let project_paths: std::collections::HashSet<&String> = pf
             .get(&project_id)
@@ -8288,6 +9506,36 @@ impl GraphStore for MockGraphStore {
         Ok(edges)
     }
 
+    async fn get_project_call_graph_functions(
+        &self,
+        project_id: Uuid,
+    ) -> anyhow::Result<Vec<CallGraphFunctionNode>> {
+        let pf = self.project_files.read().await;
+        let project_paths: std::collections::HashSet<&String> = pf
+            .get(&project_id)
+            .map(|v| v.iter().collect())
+            .unwrap_or_default();
+
+        let functions = self.functions.read().await;
+        let fa = self.function_analytics.read().await;
+        Ok(functions
+            .values()
+            .filter(|f| project_paths.contains(&f.file_path))
+            .map(|f| {
+                let id = format!("{}:{}:{}", f.file_path, f.name, f.line_start);
+                let analytics = fa.get(&id);
+                CallGraphFunctionNode {
+                    name: f.name.clone(),
+                    file_path: f.file_path.clone(),
+                    line_start: f.line_start,
+                    pagerank: analytics.map(|a| a.pagerank),
+                    community_id: analytics.map(|a| a.community_id as i64),
+                    id,
+                }
+            })
+            .collect())
+    }
+
     async fn get_project_extends_edges(
         &self,
         project_id: Uuid,
@@ -10366,6 +11614,21 @@ impl GraphStore for MockGraphStore {
         Ok(runs.get(&run_id).cloned())
     }
 
+    async fn save_run_report(&self, report: &crate::runner::RunReport) -> anyhow::Result<()> {
+        self.run_reports
+            .write()
+            .await
+            .insert(report.run_id, report.clone());
+        Ok(())
+    }
+
+    async fn get_run_report(
+        &self,
+        run_id: Uuid,
+    ) -> anyhow::Result<Option<crate::runner::RunReport>> {
+        Ok(self.run_reports.read().await.get(&run_id).cloned())
+    }
+
     async fn list_active_plan_runs(&self) -> anyhow::Result<Vec<crate::runner::RunnerState>> {
         let runs = self.plan_runs.read().await;
         Ok(runs
@@ -10526,23 +11789,53 @@ impl GraphStore for MockGraphStore {
 
     async fn create_agent_execution(
         &self,
-        _ae: &crate::neo4j::agent_execution::AgentExecutionNode,
+        ae: &crate::neo4j::agent_execution::AgentExecutionNode,
     ) -> anyhow::Result<()> {
+        self.agent_executions
+            .write()
+            .await
+            .insert(ae.id, ae.clone());
         Ok(())
     }
 
     async fn update_agent_execution(
         &self,
-        _ae: &crate::neo4j::agent_execution::AgentExecutionNode,
+        ae: &crate::neo4j::agent_execution::AgentExecutionNode,
     ) -> anyhow::Result<()> {
+        if let Some(existing) = self.agent_executions.write().await.get_mut(&ae.id) {
+            existing.cost_usd = ae.cost_usd;
+            existing.duration_secs = ae.duration_secs;
+            existing.status = ae.status;
+            existing.tools_used = ae.tools_used.clone();
+            existing.files_modified = ae.files_modified.clone();
+            existing.commits = ae.commits.clone();
+            if ae.completed_at.is_some() {
+                existing.completed_at = ae.completed_at;
+            }
+            if ae.vector_json.is_some() {
+                existing.vector_json = ae.vector_json.clone();
+            }
+            if ae.report_json.is_some() {
+                existing.report_json = ae.report_json.clone();
+            }
+        }
         Ok(())
     }
 
     async fn get_agent_executions_for_run(
         &self,
-        _run_id: Uuid,
+        run_id: Uuid,
     ) -> anyhow::Result<Vec<crate::neo4j::agent_execution::AgentExecutionNode>> {
-        Ok(Vec::new())
+        let mut executions: Vec<_> = self
+            .agent_executions
+            .read()
+            .await
+            .values()
+            .filter(|ae| ae.run_id == run_id)
+            .cloned()
+            .collect();
+        executions.sort_by_key(|ae| ae.started_at);
+        Ok(executions)
     }
 
     async fn create_used_skill_relation(
@@ -10577,188 +11870,1607 @@ impl GraphStore for MockGraphStore {
         Ok(None)
     }
 
-    async fn update_sharing_policy(
-        &self,
-        _project_id: Uuid,
-        _policy: &crate::episodes::distill_models::SharingPolicy,
-    ) -> anyhow::Result<()> {
-        Ok(())
+    async fn update_sharing_policy(
+        &self,
+        _project_id: Uuid,
+        _policy: &crate::episodes::distill_models::SharingPolicy,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn get_sharing_consent(
+        &self,
+        _note_id: Uuid,
+    ) -> anyhow::Result<crate::episodes::distill_models::SharingConsent> {
+        Ok(crate::episodes::distill_models::SharingConsent::default())
+    }
+
+    async fn update_sharing_consent(
+        &self,
+        _note_id: Uuid,
+        _consent: &crate::episodes::distill_models::SharingConsent,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn create_sharing_event(
+        &self,
+        _event: &crate::episodes::distill_models::SharingEvent,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn list_sharing_events(
+        &self,
+        _project_id: Uuid,
+        _limit: i64,
+        _offset: i64,
+    ) -> anyhow::Result<Vec<crate::episodes::distill_models::SharingEvent>> {
+        Ok(Vec::new())
+    }
+
+    async fn persist_tombstone(
+        &self,
+        _tombstone: &crate::reception::anchor::SignedTombstone,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn list_tombstones(
+        &self,
+    ) -> anyhow::Result<Vec<crate::reception::anchor::SignedTombstone>> {
+        Ok(Vec::new())
+    }
+
+    async fn is_tombstoned(&self, _content_hash: &str) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+
+    // ========================================================================
+    // UserProfile operations
+    // ========================================================================
+
+    async fn create_or_get_user_profile(
+        &self,
+        user_id: &str,
+    ) -> anyhow::Result<crate::profile::UserProfile> {
+        let mut profiles = self.user_profiles.write().await;
+        if let Some(profile) = profiles.get(user_id) {
+            return Ok(profile.clone());
+        }
+        let profile = crate::profile::UserProfile::new(user_id);
+        profiles.insert(user_id.to_string(), profile.clone());
+        Ok(profile)
+    }
+
+    async fn update_user_profile(
+        &self,
+        profile: &crate::profile::UserProfile,
+    ) -> anyhow::Result<()> {
+        let mut profiles = self.user_profiles.write().await;
+        profiles.insert(profile.user_id.clone(), profile.clone());
+        Ok(())
+    }
+
+    async fn get_user_profile(
+        &self,
+        user_id: &str,
+    ) -> anyhow::Result<Option<crate::profile::UserProfile>> {
+        let profiles = self.user_profiles.read().await;
+        Ok(profiles.get(user_id).cloned())
+    }
+
+    async fn upsert_works_on(&self, user_id: &str, project_id: Uuid) -> anyhow::Result<()> {
+        let mut works = self.works_on.write().await;
+        let key = (user_id.to_string(), project_id);
+        let entry = works
+            .entry(key)
+            .or_insert_with(|| crate::profile::WorksOnRelation {
+                user_id: user_id.to_string(),
+                project_id,
+                frequency: 0,
+                last_active: chrono::Utc::now(),
+            });
+        entry.frequency += 1;
+        entry.last_active = chrono::Utc::now();
+        Ok(())
+    }
+
+    async fn get_works_on(
+        &self,
+        user_id: &str,
+    ) -> anyhow::Result<Vec<crate::profile::WorksOnRelation>> {
+        let works = self.works_on.read().await;
+        let mut result: Vec<_> = works
+            .iter()
+            .filter(|((uid, _), _)| uid == user_id)
+            .map(|(_, rel)| rel.clone())
+            .collect();
+        result.sort_by(|a, b| b.last_active.cmp(&a.last_active));
+        Ok(result)
+    }
+
+    // ========================================================================
+    // Alert operations (Heartbeat Engine)
+    // ========================================================================
+
+    async fn create_alert(&self, alert: &AlertNode) -> anyhow::Result<()> {
+        self.alerts.write().await.insert(alert.id, alert.clone());
+        Ok(())
+    }
+
+    async fn list_pending_alerts(
+        &self,
+        project_id: Option<Uuid>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<AlertNode>> {
+        let alerts = self.alerts.read().await;
+        let mut result: Vec<_> = alerts
+            .values()
+            .filter(|a| !a.acknowledged)
+            .filter(|a| match project_id {
+                Some(pid) => a.project_id == Some(pid),
+                None => true,
+            })
+            .cloned()
+            .collect();
+        result.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        result.truncate(limit);
+        Ok(result)
+    }
+
+    async fn acknowledge_alert(&self, alert_id: Uuid, acknowledged_by: &str) -> anyhow::Result<()> {
+        let mut alerts = self.alerts.write().await;
+        if let Some(alert) = alerts.get_mut(&alert_id) {
+            alert.acknowledged = true;
+            alert.acknowledged_by = Some(acknowledged_by.to_string());
+            alert.acknowledged_at = Some(Utc::now());
+        }
+        Ok(())
+    }
+
+    async fn get_alert(&self, alert_id: Uuid) -> anyhow::Result<Option<AlertNode>> {
+        Ok(self.alerts.read().await.get(&alert_id).cloned())
+    }
+
+    async fn list_alerts(
+        &self,
+        project_id: Option<Uuid>,
+        limit: usize,
+        offset: usize,
+    ) -> anyhow::Result<(Vec<AlertNode>, usize)> {
+        let alerts = self.alerts.read().await;
+        let mut all: Vec<_> = alerts
+            .values()
+            .filter(|a| match project_id {
+                Some(pid) => a.project_id == Some(pid),
+                None => true,
+            })
+            .cloned()
+            .collect();
+        all.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        let total = all.len();
+        let page = all.into_iter().skip(offset).take(limit).collect();
+        Ok((page, total))
+    }
+
+    async fn replace_project_packages(
+        &self,
+        project_id: Uuid,
+        packages: &[PackageNode],
+    ) -> Result<usize> {
+        let mut packages = packages.to_vec();
+        packages.sort_by(|a, b| {
+            (a.ecosystem, &a.name, &a.version).cmp(&(b.ecosystem, &b.name, &b.version))
+        });
+        let count = packages.len();
+        self.project_packages
+            .write()
+            .await
+            .insert(project_id, packages);
+        Ok(count)
+    }
+
+    async fn list_project_packages(&self, project_id: Uuid) -> Result<Vec<PackageNode>> {
+        Ok(self
+            .project_packages
+            .read()
+            .await
+            .get(&project_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn replace_project_advisories(
+        &self,
+        project_id: Uuid,
+        advisories: &[ProjectAdvisory],
+    ) -> Result<()> {
+        self.project_advisories
+            .write()
+            .await
+            .insert(project_id, advisories.to_vec());
+        Ok(())
+    }
+
+    async fn list_project_advisories(&self, project_id: Uuid) -> Result<Vec<ProjectAdvisory>> {
+        let mut advisories = self
+            .project_advisories
+            .read()
+            .await
+            .get(&project_id)
+            .cloned()
+            .unwrap_or_default();
+        super::audit::sort_project_advisories(&mut advisories);
+        Ok(advisories)
+    }
+
+    async fn upsert_document(
+        &self,
+        document: &DocumentNode,
+        sections: &[DocSectionNode],
+    ) -> Result<()> {
+        self.documents
+            .write()
+            .await
+            .insert(document.path.clone(), document.clone());
+        self.document_sections
+            .write()
+            .await
+            .insert(document.path.clone(), sections.to_vec());
+        Ok(())
+    }
+
+    async fn get_document(&self, path: &str) -> Result<Option<DocumentNode>> {
+        Ok(self.documents.read().await.get(path).cloned())
+    }
+
+    async fn list_project_documents(&self, project_id: Uuid) -> Result<Vec<DocumentNode>> {
+        let mut documents: Vec<DocumentNode> = self
+            .documents
+            .read()
+            .await
+            .values()
+            .filter(|d| d.project_id == Some(project_id))
+            .cloned()
+            .collect();
+        documents.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(documents)
+    }
+
+    async fn get_document_sections(&self, path: &str) -> Result<Vec<DocSectionNode>> {
+        let mut sections = self
+            .document_sections
+            .read()
+            .await
+            .get(path)
+            .cloned()
+            .unwrap_or_default();
+        sections.sort_by_key(|s| s.ordinal);
+        Ok(sections)
+    }
+
+    async fn delete_document(&self, path: &str) -> Result<()> {
+        self.documents.write().await.remove(path);
+        self.document_sections.write().await.remove(path);
+        Ok(())
+    }
+
+    async fn delete_stale_documents(
+        &self,
+        project_id: Uuid,
+        valid_paths: &[String],
+    ) -> Result<Vec<String>> {
+        let mut documents = self.documents.write().await;
+        let mut stale: Vec<String> = documents
+            .values()
+            .filter(|d| d.project_id == Some(project_id) && !valid_paths.contains(&d.path))
+            .map(|d| d.path.clone())
+            .collect();
+        stale.sort();
+        let mut sections = self.document_sections.write().await;
+        for path in &stale {
+            documents.remove(path);
+            sections.remove(path);
+        }
+        Ok(stale)
+    }
+
+    async fn replace_project_schema(
+        &self,
+        project_id: Uuid,
+        tables: &[TableNode],
+        migrations: &[MigrationNode],
+    ) -> Result<()> {
+        let mut tables = tables.to_vec();
+        tables.sort_by(|a, b| a.name.cmp(&b.name));
+        self.project_tables.write().await.insert(project_id, tables);
+        let mut migrations = migrations.to_vec();
+        migrations.sort_by_key(|m| m.ordinal);
+        self.project_migrations
+            .write()
+            .await
+            .insert(project_id, migrations);
+        Ok(())
+    }
+
+    async fn list_project_tables(&self, project_id: Uuid) -> Result<Vec<TableNode>> {
+        Ok(self
+            .project_tables
+            .read()
+            .await
+            .get(&project_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn list_project_migrations(&self, project_id: Uuid) -> Result<Vec<MigrationNode>> {
+        Ok(self
+            .project_migrations
+            .read()
+            .await
+            .get(&project_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn replace_project_config_keys(
+        &self,
+        project_id: Uuid,
+        keys: &[ConfigKeyNode],
+    ) -> Result<()> {
+        let mut keys = keys.to_vec();
+        keys.sort_by(|a, b| a.key.cmp(&b.key));
+        self.project_config_keys
+            .write()
+            .await
+            .insert(project_id, keys);
+        Ok(())
+    }
+
+    async fn list_project_config_keys(
+        &self,
+        project_id: Uuid,
+        filter: Option<&str>,
+    ) -> Result<Vec<ConfigKeyNode>> {
+        let filter = filter.unwrap_or_default().to_lowercase();
+        Ok(self
+            .project_config_keys
+            .read()
+            .await
+            .get(&project_id)
+            .map(|keys| {
+                keys.iter()
+                    .filter(|k| k.key.to_lowercase().contains(&filter))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn get_project_module_digest(
+        &self,
+        project_id: Uuid,
+        symbols_per_file: usize,
+    ) -> Result<Vec<ModuleFileDigest>> {
+        let pf = self.project_files.read().await;
+        let files = self.files.read().await;
+        let functions = self.functions.read().await;
+        let structs = self.structs_map.read().await;
+        let traits = self.traits_map.read().await;
+        let enums = self.enums_map.read().await;
+        let paths = pf.get(&project_id).cloned().unwrap_or_default();
+        Ok(paths
+            .iter()
+            .filter_map(|p| files.get(p))
+            .map(|file| {
+                let mut symbols: Vec<(u32, ModuleSymbolDigest)> = Vec::new();
+                let mut push = |line: u32, name: &str, kind: &str, docstring: &Option<String>| {
+                    symbols.push((
+                        line,
+                        ModuleSymbolDigest {
+                            name: name.to_string(),
+                            kind: kind.to_string(),
+                            docstring: docstring.clone(),
+                        },
+                    ))
+                };
+                for f in functions
+                    .values()
+                    .filter(|f| f.file_path == file.path && f.visibility == Visibility::Public)
+                {
+                    push(f.line_start, &f.name, "Function", &f.docstring);
+                }
+                for s in structs
+                    .values()
+                    .filter(|s| s.file_path == file.path && s.visibility == Visibility::Public)
+                {
+                    push(s.line_start, &s.name, "Struct", &s.docstring);
+                }
+                for t in traits
+                    .values()
+                    .filter(|t| t.file_path == file.path && t.visibility == Visibility::Public)
+                {
+                    push(t.line_start, &t.name, "Trait", &t.docstring);
+                }
+                for e in enums
+                    .values()
+                    .filter(|e| e.file_path == file.path && e.visibility == Visibility::Public)
+                {
+                    push(e.line_start, &e.name, "Enum", &e.docstring);
+                }
+                symbols.sort_by_key(|(line, _)| *line);
+                ModuleFileDigest {
+                    path: file.path.clone(),
+                    hash: file.hash.clone(),
+                    symbols: symbols
+                        .into_iter()
+                        .take(symbols_per_file)
+                        .map(|(_, s)| s)
+                        .collect(),
+                }
+            })
+            .collect())
+    }
+
+    async fn list_module_summaries(&self, project_id: Uuid) -> Result<Vec<ModuleSummaryNode>> {
+        let mut summaries = self
+            .module_summaries
+            .read()
+            .await
+            .get(&project_id)
+            .cloned()
+            .unwrap_or_default();
+        summaries.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(summaries)
+    }
+
+    async fn upsert_module_summaries(&self, summaries: &[ModuleSummaryNode]) -> Result<()> {
+        let mut all = self.module_summaries.write().await;
+        for summary in summaries {
+            let project = all.entry(summary.project_id).or_default();
+            project.retain(|m| m.path != summary.path);
+            project.push(summary.clone());
+        }
+        Ok(())
+    }
+
+    async fn delete_module_summaries(&self, project_id: Uuid, paths: &[String]) -> Result<()> {
+        if let Some(project) = self.module_summaries.write().await.get_mut(&project_id) {
+            project.retain(|m| !paths.contains(&m.path));
+        }
+        Ok(())
+    }
+
+    async fn get_project_sync_limits(&self, project_id: Uuid) -> Result<Option<SyncLimits>> {
+        Ok(self
+            .project_sync_limits
+            .read()
+            .await
+            .get(&project_id)
+            .cloned())
+    }
+
+    async fn update_project_sync_limits(
+        &self,
+        project_id: Uuid,
+        limits: &SyncLimits,
+    ) -> Result<()> {
+        self.project_sync_limits
+            .write()
+            .await
+            .insert(project_id, limits.clone());
+        Ok(())
+    }
+
+    async fn get_project_budgets(&self, project_id: Uuid) -> Result<Option<ProjectBudgets>> {
+        Ok(self.project_budgets.read().await.get(&project_id).cloned())
+    }
+
+    async fn update_project_budgets(
+        &self,
+        project_id: Uuid,
+        budgets: &ProjectBudgets,
+    ) -> Result<()> {
+        self.project_budgets
+            .write()
+            .await
+            .insert(project_id, budgets.clone());
+        self.budget_reports.write().await.remove(&project_id);
+        Ok(())
+    }
+
+    async fn get_budget_report(&self, project_id: Uuid) -> Result<Option<BudgetCheckReport>> {
+        Ok(self.budget_reports.read().await.get(&project_id).cloned())
+    }
+
+    async fn save_budget_report(&self, report: &BudgetCheckReport) -> Result<()> {
+        self.budget_reports
+            .write()
+            .await
+            .insert(report.project_id, report.clone());
+        Ok(())
+    }
+
+    async fn get_project_public_symbols(&self, project_id: Uuid) -> Result<Vec<PublicSymbolRow>> {
+        use super::api_surface::{
+            enum_signature, function_signature, struct_signature, trait_signature,
+        };
+
+        let paths = self
+            .project_files
+            .read()
+            .await
+            .get(&project_id)
+            .cloned()
+            .unwrap_or_default();
+        let functions = self.functions.read().await;
+        let structs = self.structs_map.read().await;
+        let traits = self.traits_map.read().await;
+        let enums = self.enums_map.read().await;
+        let mut symbols: Vec<(String, u32, PublicSymbolRow)> = Vec::new();
+        let mut push = |file_path: &str, line: u32, name: &str, kind: &str, signature: String| {
+            symbols.push((
+                file_path.to_string(),
+                line,
+                PublicSymbolRow {
+                    file_path: file_path.to_string(),
+                    name: name.to_string(),
+                    kind: kind.to_string(),
+                    signature,
+                },
+            ))
+        };
+        let public = |path: &String, visibility: &Visibility| {
+            paths.contains(path) && *visibility == Visibility::Public
+        };
+        for f in functions
+            .values()
+            .filter(|f| public(&f.file_path, &f.visibility))
+        {
+            let signature = function_signature(
+                &f.name,
+                &f.generics,
+                &f.params,
+                f.return_type.as_deref(),
+                f.is_async,
+            );
+            push(&f.file_path, f.line_start, &f.name, "function", signature);
+        }
+        for st in structs
+            .values()
+            .filter(|s| public(&s.file_path, &s.visibility))
+        {
+            let signature = struct_signature(&st.name, &st.generics, &st.fields);
+            push(&st.file_path, st.line_start, &st.name, "struct", signature);
+        }
+        for t in traits
+            .values()
+            .filter(|t| public(&t.file_path, &t.visibility))
+        {
+            let signature = trait_signature(&t.name, &t.generics);
+            push(&t.file_path, t.line_start, &t.name, "trait", signature);
+        }
+        for e in enums
+            .values()
+            .filter(|e| public(&e.file_path, &e.visibility))
+        {
+            let signature = enum_signature(&e.name, &e.variants, &e.variant_payloads);
+            push(&e.file_path, e.line_start, &e.name, "enum", signature);
+        }
+        symbols.sort_by(|a, b| (&a.0, a.1).cmp(&(&b.0, b.1)));
+        Ok(symbols.into_iter().map(|(_, _, row)| row).collect())
+    }
+
+    async fn create_api_surface_snapshot(
+        &self,
+        snapshot: &ApiSurfaceSnapshotNode,
+        keep: usize,
+    ) -> Result<()> {
+        let mut all = self.api_surface_snapshots.write().await;
+        let snapshots = all.entry(snapshot.project_id).or_default();
+        snapshots.push(snapshot.clone());
+        let excess = snapshots.len().saturating_sub(keep);
+        snapshots.drain(..excess);
+        Ok(())
+    }
+
+    async fn list_api_surface_snapshots(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<ApiSurfaceSnapshotNode>> {
+        Ok(self
+            .api_surface_snapshots
+            .read()
+            .await
+            .get(&project_id)
+            .map(|snapshots| {
+                snapshots
+                    .iter()
+                    .rev()
+                    .map(|s| ApiSurfaceSnapshotNode {
+                        symbols: vec![],
+                        ..s.clone()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn get_api_surface_snapshot(
+        &self,
+        project_id: Uuid,
+        snapshot_id: Uuid,
+    ) -> Result<Option<ApiSurfaceSnapshotNode>> {
+        Ok(self
+            .api_surface_snapshots
+            .read()
+            .await
+            .get(&project_id)
+            .and_then(|snapshots| snapshots.iter().find(|s| s.id == snapshot_id).cloned()))
+    }
+
+    async fn get_project_function_spans(&self, project_id: Uuid) -> Result<Vec<FunctionSpanRow>> {
+        let paths = self
+            .project_files
+            .read()
+            .await
+            .get(&project_id)
+            .cloned()
+            .unwrap_or_default();
+        Ok(self
+            .functions
+            .read()
+            .await
+            .values()
+            .filter(|f| paths.contains(&f.file_path))
+            .map(|f| FunctionSpanRow {
+                id: format!("{}:{}:{}", f.file_path, f.name, f.line_start),
+                name: f.name.clone(),
+                file_path: f.file_path.clone(),
+                line_start: f.line_start,
+                line_end: f.line_end,
+            })
+            .collect())
+    }
+
+    async fn batch_set_coverage(
+        &self,
+        file_paths: &[String],
+        functions: &[CoverageUpdate],
+        files: &[CoverageUpdate],
+    ) -> Result<()> {
+        let mut function_coverage = self.function_coverage.write().await;
+        function_coverage.retain(|id, _| {
+            !file_paths.iter().any(|p| {
+                id.strip_prefix(p.as_str())
+                    .is_some_and(|r| r.starts_with(':'))
+            })
+        });
+        for update in functions {
+            function_coverage.insert(update.id.clone(), update.clone());
+        }
+        let mut file_coverage = self.file_coverage.write().await;
+        file_coverage.retain(|path, _| !file_paths.contains(path));
+        for update in files {
+            file_coverage.insert(update.id.clone(), update.clone());
+        }
+        Ok(())
+    }
+
+    async fn get_untested_functions(
+        &self,
+        project_id: Uuid,
+        max_coverage_pct: f64,
+        limit: usize,
+    ) -> Result<Vec<UntestedFunctionRow>> {
+        let paths = self
+            .project_files
+            .read()
+            .await
+            .get(&project_id)
+            .cloned()
+            .unwrap_or_default();
+        let mut rows = self
+            .untested_functions(|f| paths.contains(&f.file_path), max_coverage_pct)
+            .await;
+        rows.truncate(limit);
+        Ok(rows)
+    }
+
+    async fn get_file_coverage(
+        &self,
+        file_path: &str,
+        max_coverage_pct: f64,
+    ) -> Result<Option<FileCoverageRow>> {
+        let Some(file) = self.file_coverage.read().await.get(file_path).cloned() else {
+            return Ok(None);
+        };
+        Ok(Some(FileCoverageRow {
+            covered_lines: file.covered_lines,
+            instrumented_lines: file.instrumented_lines,
+            coverage_pct: file.coverage_pct,
+            untested_functions: self
+                .untested_functions(|f| f.file_path == file_path, max_coverage_pct)
+                .await,
+        }))
+    }
+
+    async fn create_test_run(&self, run: &TestRunNode, keep: usize) -> Result<()> {
+        let mut all = self.test_runs.write().await;
+        let runs = all.entry(run.project_id).or_default();
+        runs.push(run.clone());
+        let excess = runs.len().saturating_sub(keep);
+        runs.drain(..excess);
+        Ok(())
+    }
+
+    async fn list_test_runs(&self, project_id: Uuid, limit: usize) -> Result<Vec<TestRunNode>> {
+        Ok(self
+            .test_runs
+            .read()
+            .await
+            .get(&project_id)
+            .map(|runs| runs.iter().rev().take(limit).cloned().collect())
+            .unwrap_or_default())
+    }
+
+    async fn get_test_cases(&self, project_id: Uuid) -> Result<Vec<TestCaseNode>> {
+        Ok(self
+            .test_cases
+            .read()
+            .await
+            .values()
+            .filter(|c| c.project_id == project_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn upsert_test_cases(&self, cases: &[TestCaseNode]) -> Result<()> {
+        let mut all = self.test_cases.write().await;
+        for case in cases {
+            all.insert((case.project_id, case.key.clone()), case.clone());
+        }
+        Ok(())
+    }
+
+    async fn replace_sentry_issues(
+        &self,
+        project_id: Uuid,
+        issues: &[SentryIssueNode],
+    ) -> Result<()> {
+        self.sentry_issues
+            .write()
+            .await
+            .insert(project_id, issues.to_vec());
+        Ok(())
+    }
+
+    async fn list_sentry_issues(&self, project_id: Uuid) -> Result<Vec<SentryIssueNode>> {
+        let mut issues = self
+            .sentry_issues
+            .read()
+            .await
+            .get(&project_id)
+            .cloned()
+            .unwrap_or_default();
+        issues.sort_by(|a, b| b.last_seen.cmp(&a.last_seen));
+        Ok(issues)
+    }
+
+    async fn get_file_sentry_issues(&self, file_path: &str) -> Result<Vec<SentryIssueNode>> {
+        let function_ids: std::collections::HashSet<String> = self
+            .functions
+            .read()
+            .await
+            .values()
+            .filter(|f| f.file_path == file_path)
+            .map(|f| format!("{}:{}:{}", f.file_path, f.name, f.line_start))
+            .collect();
+        let mut issues: Vec<SentryIssueNode> = self
+            .sentry_issues
+            .read()
+            .await
+            .values()
+            .flatten()
+            .filter(|i| i.function_ids.iter().any(|id| function_ids.contains(id)))
+            .cloned()
+            .collect();
+        issues.sort_by(|a, b| b.events.cmp(&a.events));
+        Ok(issues)
+    }
+
+    async fn replace_code_owners(
+        &self,
+        project_id: Uuid,
+        rules: &[CodeOwnerRule],
+        file_owners: &[(String, Vec<String>)],
+    ) -> Result<()> {
+        self.code_owner_rules
+            .write()
+            .await
+            .insert(project_id, rules.to_vec());
+        self.file_owners
+            .write()
+            .await
+            .insert(project_id, file_owners.iter().cloned().collect());
+        Ok(())
+    }
+
+    async fn get_code_owner_rules(&self, project_id: Uuid) -> Result<Vec<CodeOwnerRule>> {
+        let mut rules = self
+            .code_owner_rules
+            .read()
+            .await
+            .get(&project_id)
+            .cloned()
+            .unwrap_or_default();
+        rules.sort_by_key(|r| r.line);
+        Ok(rules)
+    }
+
+    async fn list_code_owners(&self, project_id: Uuid) -> Result<Vec<CodeOwnerNode>> {
+        let rules = self.get_code_owner_rules(project_id).await?;
+        let file_owners = self.file_owners.read().await;
+        let files = file_owners.get(&project_id);
+        let names: std::collections::BTreeSet<&String> =
+            rules.iter().flat_map(|r| &r.owners).collect();
+        let mut owners: Vec<CodeOwnerNode> = names
+            .into_iter()
+            .map(|name| CodeOwnerNode {
+                name: name.clone(),
+                kind: CodeOwnerNode::kind_of(name).to_string(),
+                files: files
+                    .map(|f| f.values().filter(|o| o.contains(name)).count())
+                    .unwrap_or(0),
+            })
+            .collect();
+        owners.sort_by(|a, b| b.files.cmp(&a.files).then_with(|| a.name.cmp(&b.name)));
+        Ok(owners)
+    }
+
+    async fn get_file_owners(&self, file_path: &str) -> Result<Vec<String>> {
+        let mut owners: Vec<String> = self
+            .file_owners
+            .read()
+            .await
+            .values()
+            .filter_map(|files| files.get(file_path))
+            .flatten()
+            .cloned()
+            .collect();
+        owners.sort();
+        owners.dedup();
+        Ok(owners)
+    }
+
+    async fn upsert_context_feedback(&self, feedback: &ContextFeedbackNode) -> Result<bool> {
+        if !self
+            .chat_sessions
+            .read()
+            .await
+            .contains_key(&feedback.session_id)
+        {
+            return Ok(false);
+        }
+        let mut store = self.context_feedback.write().await;
+        let votes = store.entry(feedback.session_id).or_default();
+        votes.retain(|f| !(f.item_type == feedback.item_type && f.item_id == feedback.item_id));
+        votes.push(feedback.clone());
+        Ok(true)
+    }
+
+    async fn list_context_feedback(&self, session_id: Uuid) -> Result<Vec<ContextFeedbackNode>> {
+        let mut votes = self
+            .context_feedback
+            .read()
+            .await
+            .get(&session_id)
+            .cloned()
+            .unwrap_or_default();
+        votes.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(votes)
+    }
+
+    async fn get_note_relevance_votes(&self, note_ids: &[Uuid]) -> Result<Vec<NoteRelevanceVotes>> {
+        let mut votes: HashMap<Uuid, NoteRelevanceVotes> = HashMap::new();
+        for feedback in self.context_feedback.read().await.values().flatten() {
+            if feedback.item_type != ContextItemType::Note {
+                continue;
+            }
+            let Ok(note_id) = feedback.item_id.parse::<Uuid>() else {
+                continue;
+            };
+            if !note_ids.contains(&note_id) {
+                continue;
+            }
+            let entry = votes.entry(note_id).or_insert(NoteRelevanceVotes {
+                note_id,
+                ..Default::default()
+            });
+            if feedback.helpful {
+                entry.helpful += 1;
+            } else {
+                entry.irrelevant += 1;
+            }
+        }
+        Ok(votes.into_values().collect())
+    }
+
+    async fn add_note_endorsement(&self, endorsement: &NoteEndorsementNode) -> Result<bool> {
+        if !self.notes.read().await.contains_key(&endorsement.note_id) {
+            return Ok(false);
+        }
+        let mut store = self.note_endorsements.write().await;
+        let endorsements = store.entry(endorsement.note_id).or_default();
+        if endorsements
+            .iter()
+            .any(|e| e.user_id == endorsement.user_id && e.kind == endorsement.kind)
+        {
+            return Ok(false);
+        }
+        endorsements.push(endorsement.clone());
+        Ok(true)
+    }
+
+    async fn remove_note_endorsement(
+        &self,
+        note_id: Uuid,
+        user_id: &str,
+        kind: EndorsementKind,
+    ) -> Result<bool> {
+        let mut store = self.note_endorsements.write().await;
+        let Some(endorsements) = store.get_mut(&note_id) else {
+            return Ok(false);
+        };
+        let before = endorsements.len();
+        endorsements.retain(|e| !(e.user_id == user_id && e.kind == kind));
+        Ok(endorsements.len() < before)
+    }
+
+    async fn list_note_endorsements(&self, note_id: Uuid) -> Result<Vec<NoteEndorsementNode>> {
+        Ok(self
+            .note_endorsements
+            .read()
+            .await
+            .get(&note_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn push_undo_entry(&self, entry: &UndoEntry) -> Result<bool> {
+        if !self
+            .chat_sessions
+            .read()
+            .await
+            .contains_key(&entry.session_id)
+        {
+            return Ok(false);
+        }
+        self.undo_log
+            .write()
+            .await
+            .entry(entry.session_id)
+            .or_default()
+            .push(entry.clone());
+        Ok(true)
+    }
+
+    async fn list_undo_entries(&self, session_id: Uuid) -> Result<Vec<UndoEntry>> {
+        Ok(self
+            .undo_log
+            .read()
+            .await
+            .get(&session_id)
+            .map(|entries| entries.iter().rev().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    async fn mark_undo_entry_undone(
+        &self,
+        entry_id: Uuid,
+        undone_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<()> {
+        for entry in self.undo_log.write().await.values_mut().flatten() {
+            if entry.id == entry_id {
+                entry.undone_at = Some(undone_at);
+            }
+        }
+        Ok(())
+    }
+
+    async fn create_comment(&self, comment: &CommentNode) -> Result<bool> {
+        let exists = match comment.entity_type {
+            CommentTarget::Task => self.tasks.read().await.contains_key(&comment.entity_id),
+            CommentTarget::Plan => self.plans.read().await.contains_key(&comment.entity_id),
+            CommentTarget::Note => self.notes.read().await.contains_key(&comment.entity_id),
+        };
+        if !exists {
+            return Ok(false);
+        }
+        self.comments
+            .write()
+            .await
+            .insert(comment.id, comment.clone());
+        Ok(true)
+    }
+
+    async fn get_comment(&self, id: Uuid) -> Result<Option<CommentNode>> {
+        Ok(self.comments.read().await.get(&id).cloned())
+    }
+
+    async fn list_comments(
+        &self,
+        entity_type: CommentTarget,
+        entity_id: Uuid,
+    ) -> Result<Vec<CommentNode>> {
+        let mut comments: Vec<CommentNode> = self
+            .comments
+            .read()
+            .await
+            .values()
+            .filter(|c| c.entity_type == entity_type && c.entity_id == entity_id)
+            .cloned()
+            .collect();
+        comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(comments)
+    }
+
+    async fn list_comments_mentioning(
+        &self,
+        handle: &str,
+        limit: usize,
+    ) -> Result<Vec<CommentNode>> {
+        let handle = handle.to_lowercase();
+        let mut comments: Vec<CommentNode> = self
+            .comments
+            .read()
+            .await
+            .values()
+            .filter(|c| c.mentions.contains(&handle))
+            .cloned()
+            .collect();
+        comments.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        comments.truncate(limit);
+        Ok(comments)
+    }
+
+    async fn update_comment(&self, comment: &CommentNode) -> Result<()> {
+        if let Some(existing) = self.comments.write().await.get_mut(&comment.id) {
+            existing.content = comment.content.clone();
+            existing.mentions = comment.mentions.clone();
+            existing.updated_at = comment.updated_at;
+        }
+        Ok(())
+    }
+
+    async fn delete_comment(&self, id: Uuid) -> Result<usize> {
+        let mut comments = self.comments.write().await;
+        if !comments.contains_key(&id) {
+            return Ok(0);
+        }
+        let mut doomed = vec![id];
+        let mut i = 0;
+        while i < doomed.len() {
+            let parent = doomed[i];
+            doomed.extend(
+                comments
+                    .values()
+                    .filter(|c| c.parent_id == Some(parent))
+                    .map(|c| c.id),
+            );
+            i += 1;
+        }
+        for id in &doomed {
+            comments.remove(id);
+        }
+        Ok(doomed.len())
+    }
+
+    async fn get_project_github_checks(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Option<GitHubCheckSettings>> {
+        Ok(self.github_checks.read().await.get(&project_id).cloned())
+    }
+
+    async fn update_project_github_checks(
+        &self,
+        project_id: Uuid,
+        settings: Option<&GitHubCheckSettings>,
+    ) -> Result<()> {
+        let mut checks = self.github_checks.write().await;
+        match settings {
+            Some(settings) => checks.insert(project_id, settings.clone()),
+            None => checks.remove(&project_id),
+        };
+        Ok(())
+    }
+
+    async fn find_project_by_github_repository(
+        &self,
+        repository: &str,
+    ) -> Result<Option<ProjectNode>> {
+        let checks = self.github_checks.read().await;
+        let Some(project_id) = checks
+            .iter()
+            .find(|(_, s)| s.repository.eq_ignore_ascii_case(repository))
+            .map(|(id, _)| *id)
+        else {
+            return Ok(None);
+        };
+        Ok(self.projects.read().await.get(&project_id).cloned())
+    }
+
+    async fn get_quota_settings(&self) -> Result<Option<QuotaSettings>> {
+        Ok(self.quota_settings.read().await.clone())
+    }
+
+    async fn update_quota_settings(&self, settings: &QuotaSettings) -> Result<()> {
+        *self.quota_settings.write().await = Some(settings.clone());
+        Ok(())
+    }
+
+    async fn get_workspace_quotas(&self, workspace_id: Uuid) -> Result<Option<QuotaLimits>> {
+        Ok(self
+            .workspace_quotas
+            .read()
+            .await
+            .get(&workspace_id)
+            .cloned())
+    }
+
+    async fn update_workspace_quotas(
+        &self,
+        workspace_id: Uuid,
+        limits: Option<&QuotaLimits>,
+    ) -> Result<()> {
+        let mut quotas = self.workspace_quotas.write().await;
+        match limits {
+            Some(limits) => quotas.insert(workspace_id, limits.clone()),
+            None => quotas.remove(&workspace_id),
+        };
+        Ok(())
+    }
+
+    async fn create_prompt_template(&self, template: &PromptTemplateNode) -> Result<()> {
+        self.prompt_templates
+            .write()
+            .await
+            .insert(template.id, template.clone());
+        self.prompt_template_versions.write().await.insert(
+            template.id,
+            vec![PromptTemplateVersion {
+                version: template.version,
+                content: template.content.clone(),
+                created_at: template.updated_at,
+            }],
+        );
+        Ok(())
+    }
+
+    async fn get_prompt_template(&self, id: Uuid) -> Result<Option<PromptTemplateNode>> {
+        Ok(self.prompt_templates.read().await.get(&id).cloned())
+    }
+
+    async fn list_prompt_templates(&self) -> Result<Vec<PromptTemplateNode>> {
+        let mut templates: Vec<_> = self
+            .prompt_templates
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(templates)
+    }
+
+    async fn update_prompt_template(&self, template: &PromptTemplateNode) -> Result<()> {
+        let mut templates = self.prompt_templates.write().await;
+        if !templates.contains_key(&template.id) {
+            return Ok(());
+        }
+        templates.insert(template.id, template.clone());
+        let mut versions = self.prompt_template_versions.write().await;
+        let versions = versions.entry(template.id).or_default();
+        if !versions.iter().any(|v| v.version == template.version) {
+            versions.push(PromptTemplateVersion {
+                version: template.version,
+                content: template.content.clone(),
+                created_at: template.updated_at,
+            });
+        }
+        Ok(())
+    }
+
+    async fn list_prompt_template_versions(&self, id: Uuid) -> Result<Vec<PromptTemplateVersion>> {
+        let mut versions = self
+            .prompt_template_versions
+            .read()
+            .await
+            .get(&id)
+            .cloned()
+            .unwrap_or_default();
+        versions.sort_by(|a, b| b.version.cmp(&a.version));
+        Ok(versions)
+    }
+
+    async fn delete_prompt_template(&self, id: Uuid) -> Result<bool> {
+        self.prompt_template_versions.write().await.remove(&id);
+        Ok(self.prompt_templates.write().await.remove(&id).is_some())
+    }
+
+    async fn create_saved_view(&self, view: &SavedViewNode) -> Result<()> {
+        self.saved_views.write().await.insert(view.id, view.clone());
+        Ok(())
+    }
+
+    async fn get_saved_view(&self, id: Uuid) -> Result<Option<SavedViewNode>> {
+        Ok(self.saved_views.read().await.get(&id).cloned())
+    }
+
+    async fn list_saved_views(&self, owner_id: &str) -> Result<Vec<SavedViewNode>> {
+        let mut views: Vec<SavedViewNode> = self
+            .saved_views
+            .read()
+            .await
+            .values()
+            .filter(|v| v.owner_id == owner_id)
+            .cloned()
+            .collect();
+        views.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(views)
+    }
+
+    async fn update_saved_view(&self, view: &SavedViewNode) -> Result<()> {
+        let mut views = self.saved_views.write().await;
+        if let Some(existing) = views.get_mut(&view.id) {
+            *existing = SavedViewNode {
+                owner_id: existing.owner_id.clone(),
+                created_at: existing.created_at,
+                ..view.clone()
+            };
+        }
+        Ok(())
+    }
+
+    async fn delete_saved_view(&self, id: Uuid) -> Result<bool> {
+        Ok(self.saved_views.write().await.remove(&id).is_some())
     }
 
-    async fn get_sharing_consent(
+    async fn list_view_functions(
         &self,
-        _note_id: Uuid,
-    ) -> anyhow::Result<crate::episodes::distill_models::SharingConsent> {
-        Ok(crate::episodes::distill_models::SharingConsent::default())
+        project_id: Uuid,
+        filters: &SavedViewFilters,
+    ) -> Result<Vec<ViewFunctionRow>> {
+        let pf = self.project_files.read().await;
+        let functions = self.functions.read().await;
+        let calls = self.call_relationships.read().await;
+        let paths = pf.get(&project_id).cloned().unwrap_or_default();
+        // Caller ids embed the file path and name of the caller
+        let tested: std::collections::HashSet<&str> = calls
+            .iter()
+            .filter(|(caller_id, _)| caller_id.to_lowercase().contains("test"))
+            .flat_map(|(_, callees)| callees.iter().map(String::as_str))
+            .collect();
+        let visibility = filters.visibility.as_ref().map(|v| v.to_lowercase());
+        let name_contains = filters.name_contains.as_ref().map(|n| n.to_lowercase());
+        let mut rows: Vec<ViewFunctionRow> = functions
+            .values()
+            .filter(|f| paths.contains(&f.file_path))
+            .map(|f| (f, format!("{:?}", f.visibility).to_lowercase()))
+            .filter(|(f, vis)| {
+                visibility.as_ref().is_none_or(|v| v == vis)
+                    && filters.min_complexity.is_none_or(|c| f.complexity >= c)
+                    && name_contains
+                        .as_ref()
+                        .is_none_or(|n| f.name.to_lowercase().contains(n.as_str()))
+                    && (filters.untested != Some(true) || !tested.contains(f.name.as_str()))
+            })
+            .map(|(f, visibility)| ViewFunctionRow {
+                id: format!("{}:{}:{}", f.file_path, f.name, f.line_start),
+                name: f.name.clone(),
+                file_path: f.file_path.clone(),
+                line_start: f.line_start,
+                visibility,
+                complexity: f.complexity,
+            })
+            .collect();
+        rows.sort_by(|a, b| b.complexity.cmp(&a.complexity).then(a.id.cmp(&b.id)));
+        Ok(rows)
     }
 
-    async fn update_sharing_consent(
-        &self,
-        _note_id: Uuid,
-        _consent: &crate::episodes::distill_models::SharingConsent,
-    ) -> anyhow::Result<()> {
+    async fn record_search_query(&self, event: &SearchQueryEvent) -> Result<()> {
+        let key = (
+            event.endpoint.clone(),
+            event.project_slug.clone().unwrap_or_default(),
+            event.query.clone(),
+        );
+        let mut queries = self.search_queries.write().await;
+        let stats = queries.entry(key).or_insert_with(|| SearchQueryStats {
+            endpoint: event.endpoint.clone(),
+            query: event.query.clone(),
+            project_slug: event.project_slug.clone(),
+            count: 0,
+            zero_result_count: 0,
+            mcp_count: 0,
+            last_hits: 0,
+            first_seen_at: event.at,
+            last_seen_at: event.at,
+        });
+        stats.count += 1;
+        if event.hits == 0 {
+            stats.zero_result_count += 1;
+        }
+        if event.from_mcp {
+            stats.mcp_count += 1;
+        }
+        stats.last_hits = event.hits as u64;
+        stats.last_seen_at = event.at;
         Ok(())
     }
 
-    async fn create_sharing_event(
+    async fn list_search_query_stats(
         &self,
-        _event: &crate::episodes::distill_models::SharingEvent,
-    ) -> anyhow::Result<()> {
-        Ok(())
+        endpoint: Option<&str>,
+        project_slug: Option<&str>,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<SearchQueryStats>> {
+        let mut stats: Vec<SearchQueryStats> = self
+            .search_queries
+            .read()
+            .await
+            .values()
+            .filter(|s| {
+                endpoint.is_none_or(|e| s.endpoint == e)
+                    && project_slug.is_none_or(|p| s.project_slug.as_deref() == Some(p))
+                    && since.is_none_or(|d| s.last_seen_at >= d)
+            })
+            .cloned()
+            .collect();
+        stats.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then(b.last_seen_at.cmp(&a.last_seen_at))
+        });
+        Ok(stats)
     }
 
-    async fn list_sharing_events(
+    async fn get_search_settings(
         &self,
-        _project_id: Uuid,
-        _limit: i64,
-        _offset: i64,
-    ) -> anyhow::Result<Vec<crate::episodes::distill_models::SharingEvent>> {
-        Ok(Vec::new())
+    ) -> Result<Option<crate::meilisearch::indexes::SearchSettings>> {
+        Ok(self.search_settings.read().await.clone())
     }
 
-    async fn persist_tombstone(
+    async fn update_search_settings(
         &self,
-        _tombstone: &crate::reception::anchor::SignedTombstone,
-    ) -> anyhow::Result<()> {
+        settings: &crate::meilisearch::indexes::SearchSettings,
+    ) -> Result<()> {
+        *self.search_settings.write().await = Some(settings.clone());
         Ok(())
     }
 
-    async fn list_tombstones(
-        &self,
-    ) -> anyhow::Result<Vec<crate::reception::anchor::SignedTombstone>> {
-        Ok(Vec::new())
+    async fn get_retention_policy(&self) -> Result<Option<RetentionPolicy>> {
+        Ok(self.retention_policy.read().await.clone())
     }
 
-    async fn is_tombstoned(&self, _content_hash: &str) -> anyhow::Result<bool> {
-        Ok(false)
+    async fn update_retention_policy(&self, policy: &RetentionPolicy) -> Result<()> {
+        *self.retention_policy.write().await = Some(policy.clone());
+        Ok(())
     }
 
-    // ========================================================================
-    // UserProfile operations
-    // ========================================================================
-
-    async fn create_or_get_user_profile(
+    async fn purge_expired(
         &self,
-        user_id: &str,
-    ) -> anyhow::Result<crate::profile::UserProfile> {
-        let mut profiles = self.user_profiles.write().await;
-        if let Some(profile) = profiles.get(user_id) {
-            return Ok(profile.clone());
-        }
-        let profile = crate::profile::UserProfile::new(user_id);
-        profiles.insert(user_id.to_string(), profile.clone());
-        Ok(profile)
+        kind: RetentionKind,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64> {
+        let deleted = match kind {
+            RetentionKind::ChatEvents => {
+                let mut sessions = self.chat_events.write().await;
+                let mut deleted = 0;
+                for events in sessions.values_mut() {
+                    let before = events.len();
+                    events.retain(|e| e.created_at >= cutoff);
+                    deleted += before - events.len();
+                }
+                deleted
+            }
+            // Sharing events are not stored by the mock
+            RetentionKind::SharingEvents => 0,
+            RetentionKind::SearchQueries => {
+                let mut queries = self.search_queries.write().await;
+                let before = queries.len();
+                queries.retain(|_, s| s.last_seen_at >= cutoff);
+                before - queries.len()
+            }
+            RetentionKind::Alerts => {
+                let mut alerts = self.alerts.write().await;
+                let before = alerts.len();
+                alerts.retain(|_, a| !a.acknowledged || a.created_at >= cutoff);
+                before - alerts.len()
+            }
+            RetentionKind::UndoEntries => {
+                let mut sessions = self.undo_log.write().await;
+                let mut deleted = 0;
+                for entries in sessions.values_mut() {
+                    let before = entries.len();
+                    entries.retain(|e| e.created_at >= cutoff);
+                    deleted += before - entries.len();
+                }
+                deleted
+            }
+            RetentionKind::SessionUsage => {
+                let sessions = self.chat_sessions.read().await;
+                let mut usages = self.chat_session_usages.write().await;
+                let before = usages.len();
+                usages.retain(|id, usage| {
+                    usage.killed.is_some()
+                        || sessions.get(id).is_none_or(|s| s.updated_at >= cutoff)
+                });
+                before - usages.len()
+            }
+            RetentionKind::TestRuns => {
+                let mut projects = self.test_runs.write().await;
+                let mut deleted = 0;
+                for runs in projects.values_mut() {
+                    let before = runs.len();
+                    runs.retain(|r| r.created_at >= cutoff);
+                    deleted += before - runs.len();
+                }
+                deleted
+            }
+            RetentionKind::RunReports => {
+                let mut reports = self.run_reports.write().await;
+                let before = reports.len();
+                reports.retain(|_, r| r.generated_at >= cutoff);
+                before - reports.len()
+            }
+        };
+        Ok(deleted as u64)
     }
 
-    async fn update_user_profile(
-        &self,
-        profile: &crate::profile::UserProfile,
-    ) -> anyhow::Result<()> {
-        let mut profiles = self.user_profiles.write().await;
-        profiles.insert(profile.user_id.clone(), profile.clone());
-        Ok(())
+    async fn get_retention_report(&self) -> Result<Option<RetentionReport>> {
+        Ok(self.retention_report.read().await.clone())
     }
 
-    async fn get_user_profile(
-        &self,
-        user_id: &str,
-    ) -> anyhow::Result<Option<crate::profile::UserProfile>> {
-        let profiles = self.user_profiles.read().await;
-        Ok(profiles.get(user_id).cloned())
+    async fn save_retention_report(&self, report: &RetentionReport) -> Result<()> {
+        *self.retention_report.write().await = Some(report.clone());
+        Ok(())
     }
 
-    async fn upsert_works_on(&self, user_id: &str, project_id: Uuid) -> anyhow::Result<()> {
-        let mut works = self.works_on.write().await;
-        let key = (user_id.to_string(), project_id);
-        let entry = works
-            .entry(key)
-            .or_insert_with(|| crate::profile::WorksOnRelation {
-                user_id: user_id.to_string(),
-                project_id,
-                frequency: 0,
-                last_active: chrono::Utc::now(),
-            });
-        entry.frequency += 1;
-        entry.last_active = chrono::Utc::now();
+    async fn record_usage(&self, counts: &[UsageCount]) -> Result<()> {
+        let mut counters = self.usage_counters.write().await;
+        for c in counts {
+            *counters
+                .entry((c.day, c.route.clone(), c.client.clone()))
+                .or_insert(0) += c.count;
+        }
         Ok(())
     }
 
-    async fn get_works_on(
-        &self,
-        user_id: &str,
-    ) -> anyhow::Result<Vec<crate::profile::WorksOnRelation>> {
-        let works = self.works_on.read().await;
-        let mut result: Vec<_> = works
+    async fn list_usage(&self, since: chrono::NaiveDate) -> Result<Vec<UsageCount>> {
+        let mut counts: Vec<UsageCount> = self
+            .usage_counters
+            .read()
+            .await
             .iter()
-            .filter(|((uid, _), _)| uid == user_id)
-            .map(|(_, rel)| rel.clone())
+            .filter(|((day, _, _), _)| *day >= since)
+            .map(|((day, route, client), count)| UsageCount {
+                day: *day,
+                route: route.clone(),
+                client: client.clone(),
+                count: *count,
+            })
             .collect();
-        result.sort_by(|a, b| b.last_active.cmp(&a.last_active));
-        Ok(result)
+        counts.sort_by(|a, b| (a.day, &a.route, &a.client).cmp(&(b.day, &b.route, &b.client)));
+        Ok(counts)
     }
 
-    // ========================================================================
-    // Alert operations (Heartbeat Engine)
-    // ========================================================================
+    async fn get_notification_preferences(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<NotificationPreferences>> {
+        Ok(self
+            .notification_preferences
+            .read()
+            .await
+            .get(&user_id)
+            .map(|s| s.preferences.clone()))
+    }
 
-    async fn create_alert(&self, alert: &AlertNode) -> anyhow::Result<()> {
-        self.alerts.write().await.insert(alert.id, alert.clone());
+    async fn update_notification_preferences(
+        &self,
+        user_id: Uuid,
+        email: &str,
+        preferences: &NotificationPreferences,
+    ) -> Result<()> {
+        self.notification_preferences.write().await.insert(
+            user_id,
+            NotificationSubscriber {
+                user_id,
+                email: email.to_string(),
+                preferences: preferences.clone(),
+            },
+        );
         Ok(())
     }
 
-    async fn list_pending_alerts(
-        &self,
-        project_id: Option<Uuid>,
-        limit: usize,
-    ) -> anyhow::Result<Vec<AlertNode>> {
-        let alerts = self.alerts.read().await;
-        let mut result: Vec<_> = alerts
+    async fn list_notification_subscribers(&self) -> Result<Vec<NotificationSubscriber>> {
+        let mut subscribers: Vec<NotificationSubscriber> = self
+            .notification_preferences
+            .read()
+            .await
             .values()
-            .filter(|a| !a.acknowledged)
-            .filter(|a| match project_id {
-                Some(pid) => a.project_id == Some(pid),
-                None => true,
-            })
+            .filter(|s| s.preferences.email_enabled)
             .cloned()
             .collect();
-        result.sort_by(|a, b| b.created_at.cmp(&a.created_at));
-        result.truncate(limit);
-        Ok(result)
-    }
-
-    async fn acknowledge_alert(&self, alert_id: Uuid, acknowledged_by: &str) -> anyhow::Result<()> {
-        let mut alerts = self.alerts.write().await;
-        if let Some(alert) = alerts.get_mut(&alert_id) {
-            alert.acknowledged = true;
-            alert.acknowledged_by = Some(acknowledged_by.to_string());
-            alert.acknowledged_at = Some(Utc::now());
-        }
-        Ok(())
+        subscribers.sort_by(|a, b| a.email.cmp(&b.email));
+        Ok(subscribers)
     }
 
-    async fn get_alert(&self, alert_id: Uuid) -> anyhow::Result<Option<AlertNode>> {
-        Ok(self.alerts.read().await.get(&alert_id).cloned())
+    async fn bump_version(
+        &self,
+        node: VersionedNode,
+        id: Uuid,
+        expected: Option<i64>,
+    ) -> Result<VersionBump> {
+        let bump = |version: &mut i64| match expected {
+            Some(expected) if expected != *version => VersionBump::Conflict(*version),
+            _ => {
+                *version += 1;
+                VersionBump::Bumped(*version)
+            }
+        };
+        Ok(match node {
+            VersionedNode::Plan => self
+                .plans
+                .write()
+                .await
+                .get_mut(&id)
+                .map_or(VersionBump::NotFound, |p| bump(&mut p.version)),
+            VersionedNode::Task => self
+                .tasks
+                .write()
+                .await
+                .get_mut(&id)
+                .map_or(VersionBump::NotFound, |t| bump(&mut t.version)),
+            VersionedNode::Note => self
+                .notes
+                .write()
+                .await
+                .get_mut(&id)
+                .map_or(VersionBump::NotFound, |n| bump(&mut n.version)),
+        })
     }
 
-    async fn list_alerts(
-        &self,
-        project_id: Option<Uuid>,
-        limit: usize,
-        offset: usize,
-    ) -> anyhow::Result<(Vec<AlertNode>, usize)> {
-        let alerts = self.alerts.read().await;
-        let mut all: Vec<_> = alerts
-            .values()
-            .filter(|a| match project_id {
-                Some(pid) => a.project_id == Some(pid),
-                None => true,
-            })
-            .cloned()
-            .collect();
-        all.sort_by(|a, b| b.created_at.cmp(&a.created_at));
-        let total = all.len();
-        let page = all.into_iter().skip(offset).take(limit).collect();
-        Ok((page, total))
+    async fn restore_version(&self, node: VersionedNode, id: Uuid, bumped: i64) -> Result<bool> {
+        let restore = |version: &mut i64| {
+            let restored = *version == bumped;
+            if restored {
+                *version -= 1;
+            }
+            restored
+        };
+        Ok(match node {
+            VersionedNode::Plan => self
+                .plans
+                .write()
+                .await
+                .get_mut(&id)
+                .is_some_and(|p| restore(&mut p.version)),
+            VersionedNode::Task => self
+                .tasks
+                .write()
+                .await
+                .get_mut(&id)
+                .is_some_and(|t| restore(&mut t.version)),
+            VersionedNode::Note => self
+                .notes
+                .write()
+                .await
+                .get_mut(&id)
+                .is_some_and(|n| restore(&mut n.version)),
+        })
     }
 
     async fn list_event_triggers(
@@ -11543,8 +14255,10 @@ mod tests {
             path: path.to_string(),
             language: "rust".to_string(),
             hash: "abc123".to_string(),
+            line_count: 0,
             last_parsed: Utc::now(),
             project_id,
+            encoding: None,
         }
     }
 
@@ -11779,7 +14493,7 @@ mod tests {
 
         // Scoped: only callers from project_a
         let callers = store
-            .get_function_callers_by_name("target", 2, Some(project_a.id))
+            .get_function_callers_by_name("target", 2, Some(project_a.id), 0.0)
             .await
             .unwrap();
         assert_eq!(callers.len(), 1);
@@ -11787,10 +14501,17 @@ mod tests {
 
         // Unscoped: both
         let callers_all = store
-            .get_function_callers_by_name("target", 2, None)
+            .get_function_callers_by_name("target", 2, None, 0.0)
             .await
             .unwrap();
         assert_eq!(callers_all.len(), 2);
+
+        // Edges below the confidence threshold are left out
+        let confident = store
+            .get_function_callers_by_name("target", 2, None, 0.6)
+            .await
+            .unwrap();
+        assert!(confident.is_empty());
     }
 
     #[tokio::test]
@@ -11823,7 +14544,7 @@ mod tests {
 
         // Scoped to project_a: only callees from process in project_a
         let callees = store
-            .get_function_callees_by_name("process", 2, Some(project_a.id))
+            .get_function_callees_by_name("process", 2, Some(project_a.id), 0.0)
             .await
             .unwrap();
         assert_eq!(callees.len(), 1);
@@ -11831,7 +14552,7 @@ mod tests {
 
         // Unscoped: callees from both process functions
         let callees_all = store
-            .get_function_callees_by_name("process", 2, None)
+            .get_function_callees_by_name("process", 2, None, 0.0)
             .await
             .unwrap();
         assert_eq!(callees_all.len(), 2);
@@ -12009,7 +14730,7 @@ mod tests {
 
         // Verify: callers of "validate" scoped to project A = only handle_request from A
         let callers_a = store
-            .get_function_callers_by_name("validate", 2, Some(project_a.id))
+            .get_function_callers_by_name("validate", 2, Some(project_a.id), 0.0)
             .await
             .unwrap();
         assert_eq!(callers_a.len(), 1);
@@ -12017,7 +14738,7 @@ mod tests {
 
         // Verify: callers scoped to project B = only handle_request from B
         let callers_b = store
-            .get_function_callers_by_name("validate", 2, Some(project_b.id))
+            .get_function_callers_by_name("validate", 2, Some(project_b.id), 0.0)
             .await
             .unwrap();
         assert_eq!(callers_b.len(), 1);
@@ -12303,6 +15024,7 @@ mod tests {
             docstring: None,
             parent_class: None,
             interfaces: vec![],
+            fields: vec![],
         }
     }
 
@@ -12315,6 +15037,7 @@ mod tests {
             file_path: file_path.to_string(),
             line_start: 1,
             line_end: 10,
+            derived: false,
         }
     }
 
@@ -14011,6 +16734,7 @@ mod tests {
             docstring: None,
             parent_class: None,
             interfaces: vec![],
+            fields: vec![],
         }
     }
 
@@ -14037,6 +16761,7 @@ mod tests {
             line_start: 1,
             line_end: 10,
             docstring: None,
+            variant_payloads: vec![],
         }
     }
 
@@ -14049,6 +16774,7 @@ mod tests {
             file_path: file_path.to_string(),
             line_start: 1,
             line_end: 10,
+            derived: false,
         }
     }
 
@@ -14170,6 +16896,7 @@ mod tests {
                 line: 5,
                 confidence: 0.85,
                 reason: "same-file".to_string(),
+                receiver_type: None,
             },
             crate::parser::FunctionCall {
                 caller_id: "src/lib.rs:foo:1".to_string(),
@@ -14177,6 +16904,7 @@ mod tests {
                 line: 6,
                 confidence: 0.50,
                 reason: "fuzzy-unique".to_string(),
+                receiver_type: None,
             },
         ];
 
@@ -14267,8 +16995,10 @@ mod tests {
                 path: path.clone(),
                 language: "rust".to_string(),
                 hash: format!("hash{}", i),
+                line_count: 0,
                 last_parsed: Utc::now(),
                 project_id: Some(project.id),
+                encoding: None,
             };
             store.upsert_file(&file).await.unwrap();
             store.link_file_to_project(&path, project.id).await.unwrap();
@@ -14293,8 +17023,10 @@ mod tests {
                 path: path.clone(),
                 language: "rust".to_string(),
                 hash: format!("a{}", i),
+                line_count: 0,
                 last_parsed: Utc::now(),
                 project_id: Some(p1.id),
+                encoding: None,
             };
             store.upsert_file(&file).await.unwrap();
             store.link_file_to_project(&path, p1.id).await.unwrap();
@@ -14305,8 +17037,10 @@ mod tests {
                 path: path.clone(),
                 language: "rust".to_string(),
                 hash: format!("b{}", i),
+                line_count: 0,
                 last_parsed: Utc::now(),
                 project_id: Some(p2.id),
+                encoding: None,
             };
             store.upsert_file(&file).await.unwrap();
             store.link_file_to_project(&path, p2.id).await.unwrap();
@@ -14987,4 +17721,96 @@ mod tests {
         let result = store.set_watch_enabled(Uuid::new_v4(), false).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_update_project_synced_refreshes_language_stats() {
+        let store = MockGraphStore::new();
+        let project = test_project();
+        store.create_project(&project).await.unwrap();
+        assert!(store
+            .get_project_language_stats(project.id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        for (path, language, lines) in [
+            ("src/main.rs", "rust", 120),
+            ("src/lib.rs", "rust", 80),
+            ("web/app.ts", "typescript", 300),
+        ] {
+            let file = FileNode {
+                language: language.to_string(),
+                line_count: lines,
+                encoding: None,
+                ..make_file(path, Some(project.id))
+            };
+            store.upsert_file(&file).await.unwrap();
+            store.link_file_to_project(path, project.id).await.unwrap();
+        }
+        store.update_project_synced(project.id).await.unwrap();
+
+        let stats = store.get_project_language_stats(project.id).await.unwrap();
+        assert_eq!(
+            stats,
+            vec![
+                LanguageStats {
+                    language: "typescript".to_string(),
+                    files: 1,
+                    lines: 300,
+                },
+                LanguageStats {
+                    language: "rust".to_string(),
+                    files: 2,
+                    lines: 200,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_workspace_stats() {
+        let store = MockGraphStore::new();
+        let ws = crate::test_helpers::test_workspace();
+        store.create_workspace(&ws).await.unwrap();
+        let project = test_project();
+        seed_project(
+            &store,
+            &project,
+            &[
+                ("src/main.rs", &["main", "helper"]),
+                ("src/lib.rs", &["run"]),
+            ],
+        )
+        .await;
+        store
+            .add_project_to_workspace(ws.id, project.id)
+            .await
+            .unwrap();
+        // Projects outside the workspace are not counted
+        seed_project(
+            &store,
+            &test_project_named("other"),
+            &[("other.rs", &["f"])],
+        )
+        .await;
+        store
+            .create_note(&crate::test_helpers::test_note(
+                project.id,
+                crate::notes::NoteType::Gotcha,
+                "Watch out",
+            ))
+            .await
+            .unwrap();
+
+        let stats = store.get_workspace_stats(ws.id).await.unwrap();
+        assert_eq!(stats.project_count, 1);
+        assert_eq!(stats.files, 2);
+        assert_eq!(stats.functions, 3);
+        assert_eq!(stats.languages["rust"], 2);
+        // Last symbol of main.rs ends at line 20, of lib.rs at line 10
+        assert_eq!(stats.lines_indexed, 30);
+        assert_eq!(stats.notes_by_type["gotcha"], 1);
+        assert_eq!(stats.projects_without_analytics, 1);
+        assert!(stats.analytics_oldest_at.is_none());
+    }
 }
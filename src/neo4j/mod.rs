@@ -3,44 +3,74 @@
 pub(crate) mod agent_execution;
 mod alert;
 mod analytics;
+mod api_surface;
+mod audit;
 pub(crate) mod batch;
+mod budget;
 mod chat;
 pub mod client;
 mod code;
+mod code_owner;
+mod code_reference;
+mod comment;
 mod commit;
+mod config_key;
 mod constraint;
+mod context_feedback;
+mod coverage;
+mod db_schema;
 mod decision;
+mod document;
 mod event_trigger;
 mod feature_graph;
+mod github_checks;
 mod impl_graph_store;
 mod lifecycle_hook;
 mod mcp_federation;
 mod milestone;
 pub mod models;
+mod module_summary;
 mod note;
+mod note_endorsement;
+mod notification;
 mod persona;
 pub mod plan;
 mod plan_run;
 mod profile;
 mod project;
+mod prompt_template;
 mod protocol;
+pub mod query_guard;
+mod quota;
 pub(crate) mod reasoning;
 mod registry;
 mod release;
+mod retention;
+mod run_report;
+mod saved_view;
+mod search_query;
+mod search_settings;
+mod sentry_issue;
 mod sharing;
 mod skill;
 mod step;
+mod sync_limits;
 mod task;
+mod test_result;
 mod topology;
 pub mod traits;
 mod trigger;
+mod undo_log;
+mod usage;
 mod user;
+mod version;
 mod workspace;
 
 pub use agent_execution::{AgentExecutionNode, AgentExecutionStatus};
 pub use client::Neo4jClient;
 pub use models::*;
+pub use query_guard::{QueryLimits, QueryMetrics, QueryTimeout};
 pub use traits::GraphStore;
 
-#[cfg(test)]
-pub(crate) mod mock;
+#[cfg(any(test, feature = "testkit"))]
+pub mod mock;
@@ -41,12 +41,25 @@ pub struct ProjectNode {
     /// Defaults to true for backward compatibility with existing projects.
     #[serde(default = "default_watch_enabled")]
     pub watch_enabled: bool,
+    /// Archived projects are not watched, analyzed by the periodic checks,
+    /// searched or listed unless asked for.
+    #[serde(default)]
+    pub archived: bool,
 }
 
 fn default_watch_enabled() -> bool {
     true
 }
 
+/// Files and lines of one language in a project, as of the last sync.
+/// Returned by `get_project_language_stats()`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LanguageStats {
+    pub language: String,
+    pub files: u64,
+    pub lines: u64,
+}
+
 // ============================================================================
 // Workspace Node (multi-project grouping)
 // ============================================================================
@@ -66,6 +79,35 @@ pub struct WorkspaceNode {
     pub metadata: serde_json::Value,
 }
 
+/// Knowledge graph statistics of the member projects of a workspace.
+/// Returned by `get_workspace_stats()`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkspaceStats {
+    pub project_count: u64,
+    pub files: u64,
+    pub functions: u64,
+    pub structs: u64,
+    pub traits: u64,
+    pub enums: u64,
+    /// Number of files per language
+    pub languages: std::collections::BTreeMap<String, u64>,
+    /// Lines indexed, counted up to the last symbol of each file
+    pub lines_indexed: u64,
+    /// Number of notes per note type
+    pub notes_by_type: std::collections::BTreeMap<String, u64>,
+    /// Plans neither completed nor cancelled
+    pub open_plans: u64,
+    /// Tasks neither completed nor failed
+    pub open_tasks: u64,
+    /// Projects whose graph analytics were never computed
+    pub projects_without_analytics: u64,
+    /// Oldest and newest analytics computation among the other projects
+    pub analytics_oldest_at: Option<DateTime<Utc>>,
+    pub analytics_newest_at: Option<DateTime<Utc>>,
+    /// Files re-parsed since their analytics scores were computed
+    pub analytics_stale_files: u64,
+}
+
 // ============================================================================
 // Chat Session Node
 // ============================================================================
@@ -109,6 +151,12 @@ pub struct ChatSessionNode {
     /// Additional directories exposed to Claude CLI (serialized as JSON array string in Neo4j)
     #[serde(default)]
     pub add_dirs: Option<Vec<String>>,
+    /// Execution sandbox requested for the session (serialized `SandboxConfig` JSON)
+    #[serde(default)]
+    pub sandbox: Option<String>,
+    /// Resource limits requested for the session (serialized `SessionLimits` JSON)
+    #[serde(default)]
+    pub limits: Option<String>,
     /// Origin of the session — JSON string stored in Neo4j.
     /// Pattern: `{"type":"runner","run_id":"...","plan_id":"..."}` for PlanRunner sessions.
     /// None/empty for normal user-initiated sessions.
@@ -169,6 +217,234 @@ pub struct ChatEventRecord {
     pub created_at: DateTime<Utc>,
 }
 
+/// Rolling summary of the earlier turns of a long chat session, seeded into
+/// the system prompt of the CLI session that replaces the summarized one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatSessionMemory {
+    pub summary: String,
+    /// Last event sequence number covered by the summary
+    pub summarized_seq: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Resources a chat session used across its CLI processes, restored on
+/// resume so its limits cover the whole session (see `chat::limits`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChatSessionUsage {
+    pub tool_calls: u64,
+    pub file_writes: u64,
+    pub cost_usd: f64,
+    /// Time the session's CLI processes ran, in seconds
+    pub elapsed_secs: u64,
+    /// Why the session was killed. A killed session refuses new messages
+    /// until its usage is reset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub killed: Option<String>,
+}
+
+// ============================================================================
+// Prompt Template nodes
+// ============================================================================
+
+/// A reusable chat prompt with `{{variable}}` placeholders (see
+/// `chat::templates`). Every change of its content bumps `version`; each
+/// version's content is kept on a `PromptTemplateVersion` node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptTemplateNode {
+    pub id: Uuid,
+    /// Unique name
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Content of the current version
+    pub content: String,
+    /// Current version, starting at 1
+    pub version: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The content of a prompt template at one of its versions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptTemplateVersion {
+    pub version: i64,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// Saved view nodes
+// ============================================================================
+
+/// What a saved view lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SavedViewTarget {
+    Tasks,
+    Notes,
+    /// Functions of a project
+    Symbols,
+}
+
+/// Filters of a saved view. Fields that do not apply to the view's target
+/// are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedViewFilters {
+    /// Required for symbols
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<Uuid>,
+    /// Tasks and notes: restrict to the projects of a workspace
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_slug: Option<String>,
+    /// Tasks and notes: task or note statuses (any match)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<Vec<String>>,
+    /// Tasks and notes: tags (any match)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// Tasks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority_min: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority_max: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assigned_to: Option<String>,
+    /// Notes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note_type: Option<Vec<crate::notes::models::NoteType>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub importance: Option<Vec<crate::notes::models::NoteImportance>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_staleness: Option<f64>,
+    /// Symbols: "public", "private", "crate", "super"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
+    /// Symbols: minimum cyclomatic complexity
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_complexity: Option<u32>,
+    /// Symbols: only functions no test function calls
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub untested: Option<bool>,
+    /// Symbols: case-insensitive substring of the function name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name_contains: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_by: Option<String>,
+    /// "asc" or "desc"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<String>,
+    /// Default page size of the results
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+/// A named, persisted query over tasks, notes or symbols, owned by a user
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedViewNode {
+    pub id: Uuid,
+    /// `sub` of the user who created the view
+    pub owner_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub target: SavedViewTarget,
+    #[serde(default)]
+    pub filters: SavedViewFilters,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A function matched by a symbols view, as returned by
+/// `list_view_functions()`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ViewFunctionRow {
+    pub id: String,
+    pub name: String,
+    pub file_path: String,
+    pub line_start: u32,
+    pub visibility: String,
+    pub complexity: u32,
+}
+
+/// One search served by a search endpoint, as recorded by
+/// `record_search_query()`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchQueryEvent {
+    /// "code", "docs", "notes", "notes_semantic", "decisions", "decisions_semantic"
+    pub endpoint: String,
+    /// Normalized query text (trimmed, lowercased, whitespace collapsed)
+    pub query: String,
+    pub project_slug: Option<String>,
+    pub hits: usize,
+    /// True when the search came through the MCP server
+    pub from_mcp: bool,
+    pub at: DateTime<Utc>,
+}
+
+/// Counters of one (endpoint, project, query) triple, aggregated over all
+/// the times it was searched.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchQueryStats {
+    pub endpoint: String,
+    pub query: String,
+    pub project_slug: Option<String>,
+    pub count: u64,
+    /// Searches that returned no hit
+    pub zero_result_count: u64,
+    /// Searches that came through the MCP server
+    pub mcp_count: u64,
+    /// Hits of the most recent search
+    pub last_hits: u64,
+    pub first_seen_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+}
+
+/// Requests served by one API route on one day, as recorded by
+/// `record_usage()`. Stored as `UsageCounter` nodes when usage telemetry is
+/// enabled.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageCount {
+    pub day: chrono::NaiveDate,
+    /// Method and route pattern, e.g. "POST /api/projects/{slug}/sync"
+    pub route: String,
+    /// "rest" or "mcp"
+    pub client: String,
+    pub count: u64,
+}
+
+/// Nodes carrying a `version` for optimistic concurrency control
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionedNode {
+    Plan,
+    Task,
+    Note,
+}
+
+impl VersionedNode {
+    /// Neo4j label of the node
+    pub fn label(&self) -> &'static str {
+        match self {
+            VersionedNode::Plan => "Plan",
+            VersionedNode::Task => "Task",
+            VersionedNode::Note => "Note",
+        }
+    }
+}
+
+/// Outcome of `bump_version()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionBump {
+    /// The version was incremented to this value
+    Bumped(i64),
+    /// The node is at this version, not the expected one: nothing changed
+    Conflict(i64),
+    NotFound,
+}
+
 /// An entity discussed in a chat session (via DISCUSSED relation).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiscussedEntity {
@@ -277,9 +553,36 @@ pub struct FileNode {
     pub path: String,
     pub language: String,
     pub hash: String,
+    /// Lines of the file when it was last parsed
+    #[serde(default)]
+    pub line_count: u32,
     pub last_parsed: DateTime<Utc>,
     #[serde(default)]
     pub project_id: Option<Uuid>,
+    /// Encoding the file was converted from, `None` for UTF-8
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+}
+
+/// Symbol and analytics aggregates of one file, rolled up by the file tree API.
+/// Returned by `get_project_file_stats()`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileStatsRow {
+    pub path: String,
+    pub language: String,
+    pub last_parsed: DateTime<Utc>,
+    pub function_count: u32,
+    /// Structs, traits and enums
+    pub type_count: u32,
+    /// Sum of the functions' cyclomatic complexity
+    pub complexity_total: u64,
+    pub max_complexity: u32,
+    /// Graph analytics have scored this file
+    pub analyzed: bool,
+    /// The analytics scores predate the last parse of the file
+    pub analytics_stale: bool,
+    /// "low", "medium", "high" or "critical" once risk scores are computed
+    pub risk_level: Option<String>,
 }
 
 /// A module/namespace
@@ -307,6 +610,16 @@ pub struct StructNode {
     /// Implemented interfaces/protocols (Java implements, TS implements, Swift protocol conformance, etc.)
     #[serde(default)]
     pub interfaces: Vec<String>,
+    /// Declared fields (Rust)
+    #[serde(default)]
+    pub fields: Vec<FieldNode>,
+}
+
+/// An enum variant carrying data, and its fields
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VariantNode {
+    pub name: String,
+    pub fields: Vec<FieldNode>,
 }
 
 /// A trait/interface definition
@@ -337,6 +650,9 @@ pub struct EnumNode {
     pub line_start: u32,
     pub line_end: u32,
     pub docstring: Option<String>,
+    /// Payloads of the variants carrying data (Rust), in declaration order
+    #[serde(default)]
+    pub variant_payloads: Vec<VariantNode>,
 }
 
 /// A function/method definition
@@ -373,10 +689,15 @@ pub struct ImplNode {
     pub file_path: String,
     pub line_start: u32,
     pub line_end: u32,
+    /// Generated by a `#[derive(..)]` rather than written out: a synthetic
+    /// impl spanning the type's first line
+    #[serde(default)]
+    pub derived: bool,
 }
 
-/// A field in a struct
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A field of a struct or of an enum variant. Tuple fields are named by
+/// position (`0`, `1`, ..).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FieldNode {
     pub name: String,
     pub type_name: String,
@@ -428,6 +749,10 @@ pub struct PlanNode {
     /// Pre-enriched persona profile (JSON) — cached by plan enrich action.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub persona: Option<String>,
+    /// Incremented by every update through the API (0 until the first one).
+    /// Sent back as `If-Match` to update only an unchanged plan.
+    #[serde(default)]
+    pub version: i64,
 }
 
 /// Status of a plan
@@ -481,6 +806,14 @@ pub struct TaskNode {
     /// Pre-built prompt cache — full prompt ready for the runner.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub prompt_cache: Option<String>,
+    /// Rank of the task within its status column of the board (lexicographic,
+    /// see [`crate::plan::board`]). Set only by the position endpoint.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub board_position: Option<String>,
+    /// Incremented by every update through the API (0 until the first one).
+    /// Sent back as `If-Match` to update only an unchanged task.
+    #[serde(default)]
+    pub version: i64,
 }
 
 /// Status of a task
@@ -633,6 +966,35 @@ pub struct DecisionTimelineEntry {
     pub superseded_by: Option<Uuid>,
 }
 
+/// The ADR file documenting a decision in the repository of a project
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdrLink {
+    pub number: u32,
+    /// Path relative to the project root (`docs/adr/007-use-neo4j.md`)
+    pub path: String,
+}
+
+/// A decision of a project, recorded on one of its tasks or imported from
+/// one of its ADR files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectDecision {
+    pub decision: DecisionNode,
+    /// Task the decision informs (None for decisions imported from ADRs)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adr: Option<AdrLink>,
+}
+
+/// A task created along with its plan (`create_plan_with_tasks()`)
+#[derive(Debug, Clone)]
+pub struct NewPlanTask {
+    pub task: TaskNode,
+    /// Tasks it depends on, of the same plan or not
+    pub depends_on: Vec<Uuid>,
+    pub steps: Vec<StepNode>,
+}
+
 /// A constraint on a plan
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConstraintNode {
@@ -979,6 +1341,108 @@ pub struct ResourceNode {
     pub metadata: serde_json::Value,
 }
 
+/// A file of one project importing a resource owned by another
+/// (`(:File)-[:USES_RESOURCE_SYMBOL]->(:Resource)`), found at sync time.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ResourceSymbolLink {
+    pub resource_id: Uuid,
+    /// Project of the importing file
+    pub project_id: Uuid,
+    pub file_path: String,
+    /// Import path as written in the source
+    pub import_path: String,
+    /// Resource identifier the import matched (e.g. `billing_pb2`)
+    pub symbol: String,
+    pub line: u32,
+}
+
+/// Kind of element extracted from a resource spec
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceElementKind {
+    /// OpenAPI operation (`GET /users/{id}`)
+    Operation,
+    /// OpenAPI component schema
+    Schema,
+    /// Protobuf service
+    Service,
+    /// Protobuf rpc (`pkg.Service/Method`)
+    Rpc,
+    /// Protobuf message
+    Message,
+    /// Protobuf enum
+    Enum,
+}
+
+impl std::fmt::Display for ResourceElementKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResourceElementKind::Operation => write!(f, "operation"),
+            ResourceElementKind::Schema => write!(f, "schema"),
+            ResourceElementKind::Service => write!(f, "service"),
+            ResourceElementKind::Rpc => write!(f, "rpc"),
+            ResourceElementKind::Message => write!(f, "message"),
+            ResourceElementKind::Enum => write!(f, "enum"),
+        }
+    }
+}
+
+impl std::str::FromStr for ResourceElementKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "operation" | "endpoint" => Ok(ResourceElementKind::Operation),
+            "schema" => Ok(ResourceElementKind::Schema),
+            "service" => Ok(ResourceElementKind::Service),
+            "rpc" => Ok(ResourceElementKind::Rpc),
+            "message" => Ok(ResourceElementKind::Message),
+            "enum" => Ok(ResourceElementKind::Enum),
+            _ => Err(format!("Unknown resource element kind: {}", s)),
+        }
+    }
+}
+
+/// An operation, schema, message, … defined by a resource
+/// (`(:Resource)-[:DEFINES]->(:ResourceElement)`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ResourceElementNode {
+    pub resource_id: Uuid,
+    pub kind: ResourceElementKind,
+    /// Unique per (resource, kind): `GET /users/{id}`, `billing.v1.Invoice`
+    pub name: String,
+    /// OpenAPI summary / operationId, proto leading comment
+    pub summary: Option<String>,
+    /// Normalized shape (parameters, fields, request/response types) —
+    /// two versions of an element differ iff their signatures differ
+    pub signature: String,
+    /// 1-based line in the spec file, when known
+    pub line: Option<u32>,
+}
+
+/// A resource element whose signature changed between two versions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ResourceElementChange {
+    pub kind: ResourceElementKind,
+    pub name: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// Element-level diff between two versions of a contract
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ResourceElementDiff {
+    pub added: Vec<ResourceElementNode>,
+    pub removed: Vec<ResourceElementNode>,
+    pub changed: Vec<ResourceElementChange>,
+}
+
+impl ResourceElementDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
 // ============================================================================
 // Component Node (deployment topology)
 // ============================================================================
@@ -1083,6 +1547,8 @@ pub struct StructSummaryNode {
     pub line: u32,
     pub is_public: bool,
     pub docstring: Option<String>,
+    #[serde(default)]
+    pub fields: Vec<FieldNode>,
 }
 
 /// A reference to a symbol found in the codebase
@@ -1094,6 +1560,55 @@ pub struct SymbolReferenceNode {
     pub reference_type: String,
 }
 
+/// Where a code symbol is defined, resolved from its graph id
+/// (`path:name:line_start` for functions, `path:name` for types).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolLocationNode {
+    pub id: String,
+    pub name: String,
+    /// `function`, `struct`, `trait` or `enum`
+    pub kind: String,
+    pub file_path: String,
+    pub line_start: u32,
+    pub line_end: u32,
+    pub language: Option<String>,
+    pub docstring: Option<String>,
+    /// Content hash of the file at its last sync
+    pub file_hash: Option<String>,
+    /// Project containing the file, and its root
+    pub project_id: Option<Uuid>,
+    pub project_root: Option<String>,
+    /// Fields of a struct
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldNode>,
+    /// Payloads of an enum's data-carrying variants
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variant_payloads: Vec<VariantNode>,
+}
+
+/// Relationship from a function to a symbol it names: `CALLS` to a
+/// function, `USES_TYPE` to a struct, enum or trait.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CodeReferenceKind {
+    Call,
+    Type,
+}
+
+/// A function's reference to a symbol, linked by name at sync time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeReferenceNode {
+    pub kind: CodeReferenceKind,
+    pub function_id: String,
+    pub file_path: String,
+    pub line_start: u32,
+    pub line_end: u32,
+    /// Name of the called function or used type
+    pub name: String,
+    /// Highest confidence of the relationships to that name
+    pub confidence: f64,
+}
+
 /// Language statistics for architecture overview
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LanguageStatsNode {
@@ -1172,6 +1687,21 @@ pub struct ConnectedFileNode {
     pub community_id: Option<i64>,
 }
 
+/// A function of a project with its graph analytics scores, as needed to draw
+/// the call graph. Returned by `get_project_call_graph_functions()`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallGraphFunctionNode {
+    /// Function ID (format: "file_path:name:line_start")
+    pub id: String,
+    pub name: String,
+    pub file_path: String,
+    pub line_start: u32,
+    /// PageRank score (None until graph analytics have run)
+    pub pagerank: Option<f64>,
+    /// Louvain community identifier (None until graph analytics have run)
+    pub community_id: Option<i64>,
+}
+
 /// GDS analytics properties for a single node (File or Function).
 /// Returned by `get_node_analytics()`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1888,6 +2418,26 @@ pub struct RefreshTokenNode {
     pub revoked: bool,
 }
 
+/// Notification preferences of a user. Stored on a `NotificationPreferences`
+/// node keyed by the user id, since the root account has no `User` node.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationPreferences {
+    /// Receive notifications by email (default: false)
+    #[serde(default)]
+    pub email_enabled: bool,
+    /// Names of the notification rules this user does not want to hear about
+    #[serde(default)]
+    pub muted_rules: Vec<String>,
+}
+
+/// A user who opted in to email notifications
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationSubscriber {
+    pub user_id: Uuid,
+    pub email: String,
+    pub preferences: NotificationPreferences,
+}
+
 // ============================================================================
 // Analytics: Churn, Knowledge Density, Risk Score (T5.5, T5.6, T5.7)
 // ============================================================================
@@ -2302,6 +2852,1075 @@ impl AlertNode {
     }
 }
 
+// ============================================================================
+// Packages & advisories (vulnerability audit)
+// ============================================================================
+
+/// Package registry a locked dependency comes from.
+///
+/// Displayed with the OSV ecosystem names (`crates.io`, `npm`, `Go`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PackageEcosystem {
+    CratesIo,
+    Npm,
+    Go,
+}
+
+impl std::fmt::Display for PackageEcosystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CratesIo => write!(f, "crates.io"),
+            Self::Npm => write!(f, "npm"),
+            Self::Go => write!(f, "Go"),
+        }
+    }
+}
+
+impl std::str::FromStr for PackageEcosystem {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "crates.io" | "crates_io" | "cargo" => Ok(Self::CratesIo),
+            "npm" => Ok(Self::Npm),
+            "go" => Ok(Self::Go),
+            _ => Err(format!("Unknown PackageEcosystem: {s}")),
+        }
+    }
+}
+
+/// A third-party dependency locked by a project, read from its lockfiles at
+/// sync time (`(:Project)-[:DEPENDS_ON_PACKAGE]->(:Package)`).
+///
+/// `(:Package)` nodes are shared: one node per (ecosystem, name, version).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PackageNode {
+    pub ecosystem: PackageEcosystem,
+    pub name: String,
+    pub version: String,
+    /// Lockfile the version was read from, relative to the project root
+    /// (stored on the DEPENDS_ON_PACKAGE edge).
+    pub lockfile: String,
+    /// SPDX license expression (`MIT OR Apache-2.0`), when it could be found
+    /// in the lockfile or in the package's own manifest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+}
+
+/// Severity of a security advisory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdvisorySeverity {
+    /// No CVSS score or database severity (e.g. RustSec "unmaintained" notices)
+    Unknown,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl std::fmt::Display for AdvisorySeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unknown => write!(f, "unknown"),
+            Self::Low => write!(f, "low"),
+            Self::Medium => write!(f, "medium"),
+            Self::High => write!(f, "high"),
+            Self::Critical => write!(f, "critical"),
+        }
+    }
+}
+
+impl std::str::FromStr for AdvisorySeverity {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "unknown" | "none" => Ok(Self::Unknown),
+            "low" => Ok(Self::Low),
+            "medium" | "moderate" => Ok(Self::Medium),
+            "high" => Ok(Self::High),
+            "critical" => Ok(Self::Critical),
+            _ => Err(format!("Unknown AdvisorySeverity: {s}")),
+        }
+    }
+}
+
+/// A security advisory from the OSV database (RustSec, GHSA, Go vulndb, …).
+///
+/// Stored as `(:Advisory {id})`, linked to every package version it affects
+/// via `(:Advisory)-[:AFFECTS_PACKAGE]->(:Package)` and to the projects
+/// locking one via `(:Advisory)-[:AFFECTS_PROJECT]->(:Project)`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdvisoryNode {
+    /// OSV id (e.g. `RUSTSEC-2023-0071`, `GHSA-xxxx-xxxx-xxxx`)
+    pub id: String,
+    pub summary: String,
+    /// Other ids of the same vulnerability (CVE, GHSA, …)
+    pub aliases: Vec<String>,
+    pub severity: AdvisorySeverity,
+    /// CVSS v3 vector, when the advisory has one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cvss: Option<String>,
+    /// Advisory page (first ADVISORY/WEB reference)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub published: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified: Option<DateTime<Utc>>,
+}
+
+/// An advisory affecting a package version locked by a project.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectAdvisory {
+    #[serde(flatten)]
+    pub advisory: AdvisoryNode,
+    pub ecosystem: PackageEcosystem,
+    pub package: String,
+    pub version: String,
+    /// Versions fixing the advisory for this package (empty: no fix yet)
+    pub fixed_versions: Vec<String>,
+}
+
+// ============================================================================
+// Documentation (Markdown)
+// ============================================================================
+
+/// A Markdown documentation file of a project
+/// (`(:Project)-[:HAS_DOCUMENT]->(:Document)`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocumentNode {
+    pub path: String,
+    #[serde(default)]
+    pub project_id: Option<Uuid>,
+    /// Front matter title or first level-1 heading
+    pub title: Option<String>,
+    pub hash: String,
+    pub section_count: usize,
+    pub indexed_at: DateTime<Utc>,
+}
+
+/// A heading section of a document (`(:Document)-[:HAS_SECTION]->(:Section)`).
+///
+/// Links to synced files and other documents are materialized as
+/// `(:Section)-[:LINKS_TO]->(:File|:Document)`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocSectionNode {
+    /// `<document path>#<anchor>`
+    pub id: String,
+    pub document_path: String,
+    /// Position in the document
+    pub ordinal: usize,
+    /// Heading text (empty for the text before the first heading)
+    pub heading: String,
+    /// Heading level 1-6 (0 for the preamble)
+    pub level: u8,
+    /// GitHub-style anchor, unique within the document
+    pub anchor: String,
+    pub parent_anchor: Option<String>,
+    pub line_start: usize,
+    pub line_end: usize,
+    /// First paragraph of the section
+    pub summary: String,
+    /// Languages of the fenced code blocks
+    pub code_languages: Vec<String>,
+    /// Link targets: absolute paths for relative links, URLs otherwise
+    pub links: Vec<String>,
+}
+
+// ============================================================================
+// Database schema (SQL)
+// ============================================================================
+
+/// A database table reconstructed from the SQL schema and migration files
+/// of a project (`(:Project)-[:HAS_TABLE]->(:Table)`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableNode {
+    /// Lowercased unless quoted; non-default schemas are kept (`auth.users`)
+    pub name: String,
+    pub project_id: Uuid,
+    /// File with the `CREATE TABLE` statement
+    pub defined_in: Option<String>,
+    /// Migrations that changed the table after its creation, in apply order
+    #[serde(default)]
+    pub altered_by: Vec<String>,
+    /// In declaration order (`(:Table)-[:HAS_COLUMN]->(:Column)`)
+    pub columns: Vec<ColumnNode>,
+}
+
+/// A column of a table.
+///
+/// Foreign keys are materialized as `(:Column)-[:REFERENCES]->(:Column)`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnNode {
+    pub name: String,
+    /// As declared, keywords uppercased (`VARCHAR(255)`)
+    pub data_type: String,
+    pub nullable: bool,
+    pub primary_key: bool,
+    #[serde(default)]
+    pub default_value: Option<String>,
+    /// `table.column` (or `table` when the column is implicit)
+    #[serde(default)]
+    pub references: Option<String>,
+}
+
+/// A SQL migration file (`(:Project)-[:HAS_MIGRATION]->(:Migration)`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrationNode {
+    pub path: String,
+    /// Version prefix of the file or directory name (`20240101120000`, `0003`)
+    pub version: String,
+    /// Position in apply order
+    pub ordinal: usize,
+    pub project_id: Uuid,
+    /// Tables touched, as `(:Migration)-[:ALTERS {operations}]->(:Table)`
+    pub alters: Vec<MigrationAlter>,
+}
+
+/// The operations a migration applies to one table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrationAlter {
+    pub table: String,
+    /// `create_table`, `add_column`, `drop_column`, `rename_column`,
+    /// `alter_column`, `add_primary_key`, `add_foreign_key`, `create_index`,
+    /// `rename_table` or `drop_table`
+    pub operations: Vec<String>,
+}
+
+// ============================================================================
+// Configuration keys
+// ============================================================================
+
+/// How a configuration key is named.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigKeyKind {
+    /// Environment variable (`MEILISEARCH_URL`), defined in `.env` files,
+    /// compose `environment:` blocks or as an UPPER_SNAKE config leaf
+    Env,
+    /// Dotted path of a config file leaf (`server.port`)
+    Path,
+}
+
+impl std::fmt::Display for ConfigKeyKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Env => write!(f, "env"),
+            Self::Path => write!(f, "path"),
+        }
+    }
+}
+
+impl std::str::FromStr for ConfigKeyKind {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "env" => Ok(Self::Env),
+            "path" => Ok(Self::Path),
+            _ => Err(format!("Unknown ConfigKeyKind: {s}")),
+        }
+    }
+}
+
+/// A line of a file that defines or reads a configuration key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ConfigKeyLocation {
+    pub file_path: String,
+    /// 1-based
+    pub line: u32,
+}
+
+/// A configuration key of a project, cross-referenced at sync time between
+/// the config files defining it and the files reading it
+/// (`(:Project)-[:HAS_CONFIG_KEY]->(:ConfigKey)`).
+///
+/// Only key names are stored, never values: `.env` files hold secrets.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigKeyNode {
+    pub key: String,
+    pub project_id: Uuid,
+    pub kind: ConfigKeyKind,
+    /// `(:ConfigKey)-[:DEFINED_IN {line}]->(:ConfigFile)`
+    pub defined_in: Vec<ConfigKeyLocation>,
+    /// String literals and `${VAR}` interpolations naming the key, as
+    /// `(:File|ConfigFile)-[:READS_CONFIG {line}]->(:ConfigKey)`
+    pub referenced_by: Vec<ConfigKeyLocation>,
+}
+
+// ============================================================================
+// Module summaries
+// ============================================================================
+
+/// A public symbol of a file, as fed to the module summarizer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleSymbolDigest {
+    pub name: String,
+    /// "Function", "Struct", "Trait" or "Enum"
+    pub kind: String,
+    pub docstring: Option<String>,
+}
+
+/// A synced file with its content hash and most prominent public symbols
+/// (highest PageRank first). Returned by `get_project_module_digest()`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleFileDigest {
+    pub path: String,
+    pub hash: String,
+    pub symbols: Vec<ModuleSymbolDigest>,
+}
+
+/// Cached natural-language summary of one directory of a project, stored as
+/// `(:Project)-[:HAS_MODULE_SUMMARY]->(:ModuleSummary)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleSummaryNode {
+    pub project_id: Uuid,
+    /// Directory relative to the project root ("." for the root itself)
+    pub path: String,
+    pub summary: String,
+    /// Hash of the contained files' content hashes; a change triggers a refresh
+    pub source_hash: String,
+    pub file_count: usize,
+    /// Model that wrote the summary, or "heuristic"
+    pub generated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// Clone detection
+// ============================================================================
+
+/// MinHash fingerprint of a function body, computed at parse time and stored
+/// on the Function node (`minhash`, `clone_tokens`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FunctionFingerprint {
+    /// Function node id (`file_path:name:line_start`)
+    pub function_id: String,
+    /// Number of normalized tokens in the body
+    pub token_count: u32,
+    pub minhash: Vec<u64>,
+}
+
+/// A fingerprinted function of a project, as read back for clone grouping.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionFingerprintRow {
+    pub id: String,
+    pub name: String,
+    pub file_path: String,
+    pub line_start: u32,
+    pub line_end: u32,
+    pub token_count: u32,
+    pub minhash: Vec<u64>,
+}
+
+// ============================================================================
+// Sync limits
+// ============================================================================
+
+/// Files a project sync leaves unparsed, stored as JSON on the Project node
+/// (`sync_limits`). Projects without limits use the defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncLimits {
+    /// Largest file parsed, in bytes
+    #[serde(default = "default_max_file_bytes")]
+    pub max_file_bytes: u64,
+    /// Skip files containing a NUL byte in their first 8 KiB
+    #[serde(default = "default_skip_binary")]
+    pub skip_binary: bool,
+    /// File name suffixes never parsed, without the leading dot
+    /// (`"min.js"`, `"pb.go"`), matched case-insensitively
+    #[serde(default)]
+    pub excluded_extensions: Vec<String>,
+}
+
+/// 1 MiB
+fn default_max_file_bytes() -> u64 {
+    1024 * 1024
+}
+
+fn default_skip_binary() -> bool {
+    true
+}
+
+impl Default for SyncLimits {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: default_max_file_bytes(),
+            skip_binary: default_skip_binary(),
+            excluded_extensions: Vec::new(),
+        }
+    }
+}
+
+// ============================================================================
+// Retention
+// ============================================================================
+
+/// How long time-stamped records are kept before the retention purge deletes
+/// them, in days. Stored once per deployment on the `RetentionSettings` node.
+/// A `null` window keeps the records forever.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    /// Chat session events (messages, tool calls, results)
+    #[serde(default = "default_chat_events_days")]
+    pub chat_events_days: Option<u32>,
+    /// Sharing events, the audit trail of what was shared with whom
+    #[serde(default = "default_sharing_events_days")]
+    pub sharing_events_days: Option<u32>,
+    /// Search analytics, by last time the query was seen
+    #[serde(default = "default_search_queries_days")]
+    pub search_queries_days: Option<u32>,
+    /// Acknowledged alerts (pending alerts are never purged)
+    #[serde(default = "default_alerts_days")]
+    pub alerts_days: Option<u32>,
+    /// Undo log entries of chat sessions
+    #[serde(default = "default_undo_entries_days")]
+    pub undo_entries_days: Option<u32>,
+    /// Usage of chat sessions (tool calls, cost, time), by last activity of
+    /// the session. Killed sessions keep theirs, so they stay killed.
+    #[serde(default = "default_session_usage_days")]
+    pub session_usage_days: Option<u32>,
+    /// CI test runs (the history of each test case is kept)
+    #[serde(default = "default_test_runs_days")]
+    pub test_runs_days: Option<u32>,
+    /// Reports of finished plan runs
+    #[serde(default = "default_run_reports_days")]
+    pub run_reports_days: Option<u32>,
+}
+
+fn default_chat_events_days() -> Option<u32> {
+    Some(90)
+}
+
+fn default_sharing_events_days() -> Option<u32> {
+    Some(365)
+}
+
+fn default_search_queries_days() -> Option<u32> {
+    Some(180)
+}
+
+fn default_alerts_days() -> Option<u32> {
+    Some(30)
+}
+
+fn default_undo_entries_days() -> Option<u32> {
+    Some(30)
+}
+
+fn default_session_usage_days() -> Option<u32> {
+    Some(90)
+}
+
+fn default_test_runs_days() -> Option<u32> {
+    Some(90)
+}
+
+fn default_run_reports_days() -> Option<u32> {
+    Some(180)
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            chat_events_days: default_chat_events_days(),
+            sharing_events_days: default_sharing_events_days(),
+            search_queries_days: default_search_queries_days(),
+            alerts_days: default_alerts_days(),
+            undo_entries_days: default_undo_entries_days(),
+            session_usage_days: default_session_usage_days(),
+            test_runs_days: default_test_runs_days(),
+            run_reports_days: default_run_reports_days(),
+        }
+    }
+}
+
+impl RetentionPolicy {
+    /// Retention window of each kind of record
+    pub fn windows(&self) -> [(RetentionKind, Option<u32>); 8] {
+        [
+            (RetentionKind::ChatEvents, self.chat_events_days),
+            (RetentionKind::SharingEvents, self.sharing_events_days),
+            (RetentionKind::SearchQueries, self.search_queries_days),
+            (RetentionKind::Alerts, self.alerts_days),
+            (RetentionKind::UndoEntries, self.undo_entries_days),
+            (RetentionKind::SessionUsage, self.session_usage_days),
+            (RetentionKind::TestRuns, self.test_runs_days),
+            (RetentionKind::RunReports, self.run_reports_days),
+        ]
+    }
+}
+
+/// Kind of record covered by a [`RetentionPolicy`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionKind {
+    ChatEvents,
+    SharingEvents,
+    SearchQueries,
+    Alerts,
+    UndoEntries,
+    SessionUsage,
+    TestRuns,
+    RunReports,
+}
+
+/// Records of one kind deleted by a retention purge
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetentionPurge {
+    pub kind: RetentionKind,
+    /// Records older than this were deleted
+    pub cutoff: DateTime<Utc>,
+    pub deleted: u64,
+}
+
+/// Outcome of a retention purge
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetentionReport {
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    /// Triggered through the API rather than by the daily job
+    pub manual: bool,
+    /// One entry per kind with a retention window
+    pub purges: Vec<RetentionPurge>,
+}
+
+// ============================================================================
+// Budgets
+// ============================================================================
+
+/// Complexity and size limits declared for a project, stored as JSON on the
+/// Project node (`budgets`). Unset limits are not checked.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProjectBudgets {
+    /// Highest complexity allowed for a single function
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_function_complexity: Option<u32>,
+    /// Longest file allowed, in lines
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_file_lines: Option<u32>,
+    /// Most files outside a directory allowed to import files inside it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_module_fan_in: Option<u32>,
+}
+
+impl ProjectBudgets {
+    /// Whether no limit is set
+    pub fn is_empty(&self) -> bool {
+        self.max_function_complexity.is_none()
+            && self.max_file_lines.is_none()
+            && self.max_module_fan_in.is_none()
+    }
+}
+
+/// A function over a complexity limit, as returned by
+/// `get_functions_above_complexity()`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionComplexityRow {
+    pub id: String,
+    pub name: String,
+    pub file_path: String,
+    pub line_start: u32,
+    pub complexity: u32,
+}
+
+/// One budget exceeded by one function, file or directory.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BudgetViolation {
+    /// "max_function_complexity", "max_file_lines" or "max_module_fan_in"
+    pub budget: String,
+    /// Function id, or file / directory path relative to the project root
+    pub target: String,
+    pub actual: u64,
+    pub limit: u64,
+}
+
+/// Result of checking a project against its budgets, stored as JSON on the
+/// Project node (`budget_report`) after each sync.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetCheckReport {
+    pub project_id: Uuid,
+    pub passed: bool,
+    pub budgets: ProjectBudgets,
+    /// Largest overshoot first, capped (see `violation_count` for the total)
+    pub violations: Vec<BudgetViolation>,
+    pub violation_count: usize,
+    pub checked_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// GitHub check runs
+// ============================================================================
+
+fn default_check_name() -> String {
+    "Project Orchestrator".to_string()
+}
+
+/// GitHub App check runs for the pull requests of a project's repository,
+/// stored as JSON on the Project node (`github_checks`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GitHubCheckSettings {
+    /// `owner/name` of the repository
+    pub repository: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Name of the check run on the pull request
+    #[serde(default = "default_check_name")]
+    pub check_name: String,
+    /// Fail the check when the head commit exceeds a budget
+    #[serde(default = "default_true")]
+    pub fail_on_budgets: bool,
+    /// Fail the check when a changed file breaks an `error` topology rule
+    #[serde(default = "default_true")]
+    pub fail_on_rules: bool,
+}
+
+// ============================================================================
+// Public API surface
+// ============================================================================
+
+/// A public function, struct, trait or enum of a project, as returned by
+/// `get_project_public_symbols()`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicSymbolRow {
+    pub file_path: String,
+    pub name: String,
+    /// "function", "struct", "trait" or "enum"
+    pub kind: String,
+    pub signature: String,
+}
+
+/// One symbol of a public API surface.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ApiSymbol {
+    /// File relative to the project root
+    pub module: String,
+    pub name: String,
+    /// "function", "struct", "trait" or "enum"
+    pub kind: String,
+    /// Declaration without body: parameters and return type of a function,
+    /// generics of a type, public fields of a struct, variants of an enum
+    pub signature: String,
+}
+
+/// The public API surface of a project at one sync, stored as
+/// `(:Project)-[:HAS_API_SNAPSHOT]->(:ApiSurfaceSnapshot)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiSurfaceSnapshotNode {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    /// HEAD of the project's repository at the sync
+    pub commit: Option<String>,
+    /// Hash of the symbols; a sync leaving it unchanged records no snapshot
+    pub hash: String,
+    pub symbol_count: usize,
+    /// Sorted by module, name and kind; left empty in snapshot listings
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub symbols: Vec<ApiSymbol>,
+    pub created_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// Test coverage
+// ============================================================================
+
+/// Line range of a function, for mapping coverage and test reports onto it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionSpanRow {
+    /// Function node id (`file_path:name:line_start`)
+    pub id: String,
+    pub name: String,
+    pub file_path: String,
+    pub line_start: u32,
+    pub line_end: u32,
+}
+
+/// Line coverage of a function or a file, stored as `coverage_pct` (0–100),
+/// `covered_lines` and `instrumented_lines` on its node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoverageUpdate {
+    /// Function node id, or file path
+    pub id: String,
+    pub covered_lines: u32,
+    pub instrumented_lines: u32,
+    pub coverage_pct: f64,
+}
+
+/// A poorly tested function, ranked by PageRank.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UntestedFunctionRow {
+    pub id: String,
+    pub name: String,
+    pub file_path: String,
+    pub line_start: u32,
+    pub coverage_pct: f64,
+    /// 0 when analytics have not run
+    pub pagerank: f64,
+}
+
+/// Coverage of a file, with its poorly tested functions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileCoverageRow {
+    pub covered_lines: u32,
+    pub instrumented_lines: u32,
+    pub coverage_pct: f64,
+    /// Highest PageRank first
+    pub untested_functions: Vec<UntestedFunctionRow>,
+}
+
+// ============================================================================
+// Test results
+// ============================================================================
+
+/// Outcome of a test case in a CI run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TestOutcome {
+    Passed,
+    /// Failed or errored
+    Failed,
+    Skipped,
+}
+
+/// An uploaded CI test run (JUnit XML report)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestRunNode {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    pub total: u32,
+    pub passed: u32,
+    pub failed: u32,
+    pub skipped: u32,
+    pub duration_ms: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A test case of a project with its pass/fail history across CI runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestCaseNode {
+    pub project_id: Uuid,
+    /// `classname::name`, unique within the project
+    pub key: String,
+    pub classname: String,
+    pub name: String,
+    /// Test function the case runs (`file_path:name:line_start`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<String>,
+    /// Outcomes of the most recent runs where the case ran, oldest first:
+    /// `P` (passed), `F` (failed) or `R` (passed on a retry, in the same run
+    /// or in a rerun of a commit it failed on)
+    pub history: String,
+    /// Runs where the case passed or failed
+    pub runs: u64,
+    pub failures: u64,
+    pub last_outcome: TestOutcome,
+    /// Commit of the last run, when given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_commit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_failure_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_failed_at: Option<DateTime<Utc>>,
+    pub last_run_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// Production errors
+// ============================================================================
+
+/// An open Sentry issue of a project, linked to the project functions on the
+/// stack of its latest event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SentryIssueNode {
+    pub project_id: Uuid,
+    /// Sentry issue id
+    pub id: String,
+    /// Human-readable id (`BACKEND-1A`)
+    pub short_id: String,
+    pub title: String,
+    /// Where Sentry places the error (function or transaction)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub culprit: Option<String>,
+    /// `fatal`, `error`, `warning`, `info` or `debug`
+    pub level: String,
+    /// Events of the issue over its lifetime
+    pub events: u64,
+    pub users: u64,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permalink: Option<String>,
+    /// Project functions on the latest event's stack, innermost first
+    /// (`file_path:name:line_start`)
+    pub function_ids: Vec<String>,
+}
+
+// ============================================================================
+// Code ownership
+// ============================================================================
+
+/// A rule of a project's CODEOWNERS file: the files matching `pattern` are
+/// owned by `owners`. Rules without owners leave the files unowned.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeOwnerRule {
+    /// Line of the rule in the CODEOWNERS file (1-based)
+    pub line: u32,
+    pub pattern: String,
+    /// `@user`, `@org/team` or email addresses
+    pub owners: Vec<String>,
+}
+
+/// An owner named in a project's CODEOWNERS file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeOwnerNode {
+    /// `@user`, `@org/team` or an email address
+    pub name: String,
+    /// `user`, `team` or `email`
+    pub kind: String,
+    /// Synced files of the project it owns
+    pub files: usize,
+}
+
+impl CodeOwnerNode {
+    /// `team` for `@org/team`, `user` for `@user`, `email` otherwise.
+    pub fn kind_of(name: &str) -> &'static str {
+        match name.strip_prefix('@') {
+            Some(handle) if handle.contains('/') => "team",
+            Some(_) => "user",
+            None => "email",
+        }
+    }
+}
+
+// ============================================================================
+// Context feedback
+// ============================================================================
+
+/// Kind of chat context item a user can rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextItemType {
+    Note,
+    Symbol,
+}
+
+impl ContextItemType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Note => "note",
+            Self::Symbol => "symbol",
+        }
+    }
+}
+
+impl std::str::FromStr for ContextItemType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "note" => Ok(Self::Note),
+            "symbol" => Ok(Self::Symbol),
+            other => Err(format!("Unknown context item type: {}", other)),
+        }
+    }
+}
+
+/// A thumbs up/down on a context item surfaced in a chat session, stored as
+/// `(:ChatSession)-[:HAS_CONTEXT_FEEDBACK]->(:ContextFeedback)`. A session
+/// holds one vote per item; voting again replaces it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContextFeedbackNode {
+    pub session_id: Uuid,
+    pub item_type: ContextItemType,
+    /// Note UUID or symbol name
+    pub item_id: String,
+    /// Thumbs up (`true`) or down (`false`)
+    pub helpful: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Thumbs a note received across all chat sessions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoteRelevanceVotes {
+    pub note_id: Uuid,
+    pub helpful: u32,
+    pub irrelevant: u32,
+}
+
+// ============================================================================
+// Note endorsements
+// ============================================================================
+
+/// How a user endorses a note.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EndorsementKind {
+    /// "I've read this and it holds"
+    Ack,
+    /// "This is useful"
+    Upvote,
+}
+
+impl EndorsementKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ack => "ack",
+            Self::Upvote => "upvote",
+        }
+    }
+}
+
+impl std::str::FromStr for EndorsementKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ack" => Ok(Self::Ack),
+            "upvote" => Ok(Self::Upvote),
+            other => Err(format!("Unknown endorsement kind: {}", other)),
+        }
+    }
+}
+
+/// A user's endorsement of a note, stored as
+/// `(:Note)-[:HAS_ENDORSEMENT]->(:NoteEndorsement)`. A user gives each kind
+/// at most once per note.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoteEndorsementNode {
+    pub note_id: Uuid,
+    pub kind: EndorsementKind,
+    pub user_id: String,
+    pub user_name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// Chat undo log
+// ============================================================================
+
+/// A graph mutation made through the API on behalf of a chat session (the
+/// agent's MCP tools send `X-Session-Id`), with the operation that reverts
+/// it. Stored as `(:ChatSession)-[:HAS_UNDO_ENTRY]->(:UndoEntry)`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UndoEntry {
+    pub id: Uuid,
+    pub session_id: Uuid,
+    /// Mutation that was made, e.g. `note.update`
+    pub action: String,
+    /// `note`, `plan`, `task` or `step`
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    /// Human-readable description of the mutation
+    pub summary: String,
+    /// Inverse operation (a serialized `chat::undo::UndoOperation`)
+    pub inverse: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    /// Set once the mutation has been undone
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub undone_at: Option<DateTime<Utc>>,
+}
+
+// ============================================================================
+// Comments
+// ============================================================================
+
+/// Kind of entity a comment is attached to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentTarget {
+    Task,
+    Plan,
+    Note,
+}
+
+impl CommentTarget {
+    /// Neo4j label of the target node
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Task => "Task",
+            Self::Plan => "Plan",
+            Self::Note => "Note",
+        }
+    }
+}
+
+/// A comment on a task, plan or note. Replies set `parent_id` and are
+/// attached to the same entity, so a thread is the comments of an entity
+/// sharing a root. Stored as `(target)-[:HAS_COMMENT]->(:Comment)`, replies
+/// also `-[:REPLY_TO]->` their parent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommentNode {
+    pub id: Uuid,
+    pub entity_type: CommentTarget,
+    pub entity_id: Uuid,
+    /// Display name of the author
+    pub author: String,
+    /// `sub` of the author
+    pub author_id: String,
+    pub content: String,
+    /// Lowercased `@handles` found in the content
+    #[serde(default)]
+    pub mentions: Vec<String>,
+    /// Comment this one replies to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// Quotas
+// ============================================================================
+
+/// Resource limits of a tenant (the whole deployment or a workspace).
+/// Unset limits are unlimited.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuotaLimits {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_projects: Option<u64>,
+    /// Files indexed across the projects
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_indexed_files: Option<u64>,
+    /// Chat sessions active at the same time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_chat_sessions: Option<u64>,
+    /// Estimated storage, in MiB: the size of the indexed source files
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_storage_mb: Option<u64>,
+}
+
+/// Server-wide quota settings, stored on the `QuotaSettings` node.
+/// Workspaces may override `workspace_default` with limits of their own,
+/// stored as JSON on the Workspace node (`quotas`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuotaSettings {
+    /// Totals across the deployment
+    #[serde(default)]
+    pub deployment: QuotaLimits,
+    /// Limits of each workspace without its own
+    #[serde(default)]
+    pub workspace_default: QuotaLimits,
+}
+
+/// Resource usage counted against quotas
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuotaUsage {
+    pub projects: u64,
+    pub indexed_files: u64,
+    /// Active chat sessions
+    pub chat_sessions: u64,
+    /// Size of the indexed source files
+    pub storage_bytes: u64,
+}
+
 // ============================================================================
 // MCP Federation nodes
 // ============================================================================
@@ -2614,6 +4233,8 @@ mod tests {
             preview: None,
             permission_mode: None,
             add_dirs: Some(vec!["/dir/a".to_string(), "/dir/b".to_string()]),
+            sandbox: None,
+            limits: None,
             spawned_by: None,
         };
 
@@ -2642,6 +4263,8 @@ mod tests {
             preview: None,
             permission_mode: None,
             add_dirs: None,
+            sandbox: None,
+            limits: None,
             spawned_by: None,
         };
 
@@ -0,0 +1,160 @@
+//! Neo4j ModuleSummary operations (cached per-directory summaries)
+
+use super::client::Neo4jClient;
+use super::models::*;
+use anyhow::Result;
+use neo4rs::query;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+impl Neo4jClient {
+    /// Every synced file of a project with its hash and up to
+    /// `symbols_per_file` public symbols, highest PageRank first.
+    ///
+    /// Symbols come back as parallel lists. `collect` drops nulls, so the
+    /// CASE guards keep the lists aligned for files without symbols.
+    pub async fn get_project_module_digest_impl(
+        &self,
+        project_id: Uuid,
+        symbols_per_file: usize,
+    ) -> Result<Vec<ModuleFileDigest>> {
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $project_id})-[:CONTAINS]->(f:File)
+            OPTIONAL MATCH (f)-[:CONTAINS]->(s)
+            WHERE (s:Function OR s:Struct OR s:Trait OR s:Enum) AND s.visibility = 'Public'
+            WITH f, s
+            ORDER BY coalesce(s.pagerank, 0.0) DESC, s.line_start
+            WITH f,
+                 collect(s.name)[0..$limit] AS names,
+                 collect(CASE WHEN s IS NULL THEN null ELSE labels(s)[0] END)[0..$limit] AS kinds,
+                 collect(CASE WHEN s IS NULL THEN null ELSE coalesce(s.docstring, '') END)[0..$limit] AS docstrings
+            RETURN f.path AS path, coalesce(f.hash, '') AS hash, names, kinds, docstrings
+            "#,
+        )
+        .param("project_id", project_id.to_string())
+        .param("limit", symbols_per_file as i64);
+
+        let mut result = self.graph.execute(q).await?;
+        let mut files = Vec::new();
+        while let Some(row) = result.next().await? {
+            let names: Vec<String> = row.get("names").unwrap_or_default();
+            let kinds: Vec<String> = row.get("kinds").unwrap_or_default();
+            let docstrings: Vec<String> = row.get("docstrings").unwrap_or_default();
+            files.push(ModuleFileDigest {
+                path: row.get("path")?,
+                hash: row.get("hash").unwrap_or_default(),
+                symbols: names
+                    .into_iter()
+                    .zip(kinds)
+                    .zip(docstrings)
+                    .map(|((name, kind), docstring)| ModuleSymbolDigest {
+                        name,
+                        kind,
+                        docstring: (!docstring.is_empty()).then_some(docstring),
+                    })
+                    .collect(),
+            });
+        }
+        Ok(files)
+    }
+
+    /// List the cached module summaries of a project, ordered by path.
+    pub async fn list_module_summaries_impl(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<ModuleSummaryNode>> {
+        let q = query(
+            r#"
+            MATCH (m:ModuleSummary {project_id: $project_id})
+            RETURN m.path AS path, m.summary AS summary, m.source_hash AS source_hash,
+                   m.file_count AS file_count, m.generated_by AS generated_by,
+                   toString(m.updated_at) AS updated_at
+            ORDER BY m.path
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut summaries = Vec::new();
+        while let Some(row) = result.next().await? {
+            summaries.push(ModuleSummaryNode {
+                project_id,
+                path: row.get("path")?,
+                summary: row.get("summary").unwrap_or_default(),
+                source_hash: row.get("source_hash").unwrap_or_default(),
+                file_count: row.get::<i64>("file_count").unwrap_or(0) as usize,
+                generated_by: row.get("generated_by").unwrap_or_default(),
+                updated_at: row
+                    .get::<String>("updated_at")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or_else(chrono::Utc::now),
+            });
+        }
+        Ok(summaries)
+    }
+
+    /// Create or replace module summaries, keyed by (project_id, path).
+    pub async fn upsert_module_summaries_impl(
+        &self,
+        summaries: &[ModuleSummaryNode],
+    ) -> Result<()> {
+        if summaries.is_empty() {
+            return Ok(());
+        }
+
+        let items: Vec<HashMap<String, neo4rs::BoltType>> = summaries
+            .iter()
+            .map(|m| {
+                let mut item = HashMap::new();
+                item.insert("project_id".into(), m.project_id.to_string().into());
+                item.insert("path".into(), m.path.clone().into());
+                item.insert("summary".into(), m.summary.clone().into());
+                item.insert("source_hash".into(), m.source_hash.clone().into());
+                item.insert("file_count".into(), (m.file_count as i64).into());
+                item.insert("generated_by".into(), m.generated_by.clone().into());
+                item.insert("updated_at".into(), m.updated_at.to_rfc3339().into());
+                item
+            })
+            .collect();
+        let q = query(
+            r#"
+            UNWIND $items AS item
+            MATCH (p:Project {id: item.project_id})
+            MERGE (m:ModuleSummary {project_id: item.project_id, path: item.path})
+            SET m.summary = item.summary,
+                m.source_hash = item.source_hash,
+                m.file_count = item.file_count,
+                m.generated_by = item.generated_by,
+                m.updated_at = datetime(item.updated_at)
+            MERGE (p)-[:HAS_MODULE_SUMMARY]->(m)
+            "#,
+        )
+        .param("items", items);
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
+    /// Delete the module summaries of a project for the given paths.
+    pub async fn delete_module_summaries_impl(
+        &self,
+        project_id: Uuid,
+        paths: &[String],
+    ) -> Result<()> {
+        if paths.is_empty() {
+            return Ok(());
+        }
+        let q = query(
+            r#"
+            MATCH (m:ModuleSummary {project_id: $project_id})
+            WHERE m.path IN $paths
+            DETACH DELETE m
+            "#,
+        )
+        .param("project_id", project_id.to_string())
+        .param("paths", paths.to_vec());
+        self.graph.run(q).await?;
+        Ok(())
+    }
+}
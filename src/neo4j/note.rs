@@ -135,7 +135,10 @@ impl Neo4jClient {
         tags: Option<Vec<String>>,
         staleness_score: Option<f64>,
     ) -> Result<Option<Note>> {
-        let mut set_clauses = vec!["n.updated_at = datetime()".to_string()];
+        let mut set_clauses = vec![
+            "n.updated_at = datetime()".to_string(),
+            "n.version = coalesce(n.version, 0) + 1".to_string(),
+        ];
 
         if let Some(ref c) = content {
             set_clauses.push(format!("n.content = '{}'", c.replace('\'', "\\'")));
@@ -178,13 +181,15 @@ impl Neo4jClient {
         }
     }
 
-    /// Delete a note
+    /// Delete a note with its comments and endorsements
     pub async fn delete_note(&self, id: Uuid) -> Result<bool> {
         let q = query(
             r#"
             MATCH (n:Note {id: $id})
-            DETACH DELETE n
-            RETURN count(n) AS deleted
+            OPTIONAL MATCH (n)-[:HAS_COMMENT]->(c:Comment)
+            OPTIONAL MATCH (n)-[:HAS_ENDORSEMENT]->(e:NoteEndorsement)
+            DETACH DELETE c, e, n
+            RETURN count(DISTINCT n) AS deleted
             "#,
         )
         .param("id", id.to_string());
@@ -1882,7 +1887,9 @@ impl Neo4jClient {
                    coalesce(e.id, e.path, e.hash) AS entity_id,
                    r.signature_hash AS sig_hash,
                    r.body_hash AS body_hash,
-                   r.last_verified AS last_verified
+                   r.symbol_id AS symbol_id,
+                   r.last_verified AS last_verified,
+                   coalesce(r.is_valid, true) AS is_valid
             "#,
         )
         .param("id", note_id.to_string());
@@ -1895,6 +1902,7 @@ impl Neo4jClient {
             let entity_id: String = row.get("entity_id")?;
             let sig_hash: Option<String> = row.get("sig_hash").ok();
             let body_hash: Option<String> = row.get("body_hash").ok();
+            let symbol_id: Option<String> = row.get("symbol_id").ok();
             let last_verified: String = row
                 .get::<String>("last_verified")
                 .unwrap_or_else(|_| chrono::Utc::now().to_rfc3339());
@@ -1909,14 +1917,140 @@ impl Neo4jClient {
                 entity_id,
                 signature_hash: sig_hash.filter(|s| !s.is_empty()),
                 body_hash: body_hash.filter(|s| !s.is_empty()),
+                symbol_id: symbol_id.filter(|s| !s.is_empty()),
                 last_verified: last_verified.parse().unwrap_or_else(|_| chrono::Utc::now()),
-                is_valid: true,
+                is_valid: row.get("is_valid").unwrap_or(true),
             });
         }
 
         Ok(anchors)
     }
 
+    /// Get the anchors of notes linked to the functions and structs of a
+    /// file, including symbol nodes left behind by an earlier sync (function
+    /// IDs embed the start line, so a moved function gets a new node).
+    pub async fn get_symbol_anchors_for_file(
+        &self,
+        file_path: &str,
+    ) -> Result<Vec<(Uuid, NoteAnchor)>> {
+        let q = query(
+            r#"
+            MATCH (n:Note)-[r:LINKED_TO]->(e)
+            WHERE (e:Function OR e:Struct) AND e.file_path = $path
+              AND n.status IN ['active', 'needs_review']
+            RETURN n.id AS note_id,
+                   CASE WHEN e:Function THEN 'function' ELSE 'struct' END AS entity_type,
+                   e.id AS entity_id,
+                   r.signature_hash AS sig_hash,
+                   r.body_hash AS body_hash,
+                   r.symbol_id AS symbol_id,
+                   r.last_verified AS last_verified,
+                   coalesce(r.is_valid, true) AS is_valid
+            "#,
+        )
+        .param("path", file_path);
+
+        let mut result = self.graph.execute(q).await?;
+        let mut anchors = Vec::new();
+
+        while let Some(row) = result.next().await? {
+            let note_id: Uuid = row.get::<String>("note_id")?.parse()?;
+            let entity_type: EntityType = row
+                .get::<String>("entity_type")?
+                .parse()
+                .unwrap_or(EntityType::Function);
+            let sig_hash: Option<String> = row.get("sig_hash").ok();
+            let body_hash: Option<String> = row.get("body_hash").ok();
+            let symbol_id: Option<String> = row.get("symbol_id").ok();
+            let last_verified: String = row
+                .get::<String>("last_verified")
+                .unwrap_or_else(|_| chrono::Utc::now().to_rfc3339());
+
+            anchors.push((
+                note_id,
+                NoteAnchor {
+                    entity_type,
+                    entity_id: row.get("entity_id")?,
+                    signature_hash: sig_hash.filter(|s| !s.is_empty()),
+                    body_hash: body_hash.filter(|s| !s.is_empty()),
+                    symbol_id: symbol_id.filter(|s| !s.is_empty()),
+                    last_verified: last_verified.parse().unwrap_or_else(|_| chrono::Utc::now()),
+                    is_valid: row.get("is_valid").unwrap_or(true),
+                },
+            ));
+        }
+
+        Ok(anchors)
+    }
+
+    /// Move a note's anchor from one function/struct node to another,
+    /// keeping its hashes and recording the symbol identity. `old_entity_id`
+    /// and `new_entity_id` may be equal to only record the identity.
+    pub async fn reanchor_note(
+        &self,
+        note_id: Uuid,
+        entity_type: &EntityType,
+        old_entity_id: &str,
+        new_entity_id: &str,
+        symbol_id: &str,
+    ) -> Result<()> {
+        let label = match entity_type {
+            EntityType::Struct => "Struct",
+            _ => "Function",
+        };
+        let cypher = format!(
+            r#"
+            MATCH (n:Note {{id: $note_id}})-[r:LINKED_TO]->(:{label} {{id: $old_id}})
+            MATCH (e:{label} {{id: $new_id}})
+            MERGE (n)-[r2:LINKED_TO]->(e)
+            SET r2.signature_hash = r.signature_hash,
+                r2.body_hash = r.body_hash,
+                r2.symbol_id = $symbol_id,
+                r2.is_valid = true,
+                r2.last_verified = datetime()
+            WITH r, r2
+            WHERE elementId(r) <> elementId(r2)
+            DELETE r
+            "#
+        );
+
+        let q = query(&cypher)
+            .param("note_id", note_id.to_string())
+            .param("old_id", old_entity_id)
+            .param("new_id", new_entity_id)
+            .param("symbol_id", symbol_id);
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
+    /// Mark a note's anchor as orphaned: its symbol no longer exists.
+    pub async fn mark_note_anchor_orphaned(
+        &self,
+        note_id: Uuid,
+        entity_type: &EntityType,
+        entity_id: &str,
+    ) -> Result<()> {
+        let label = match entity_type {
+            EntityType::Struct => "Struct",
+            _ => "Function",
+        };
+        let cypher = format!(
+            r#"
+            MATCH (n:Note {{id: $note_id}})-[r:LINKED_TO]->(:{label} {{id: $entity_id}})
+            SET r.is_valid = false,
+                r.last_verified = datetime()
+            "#
+        );
+
+        let q = query(&cypher)
+            .param("note_id", note_id.to_string())
+            .param("entity_id", entity_id);
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
     /// Store a vector embedding on a Note node.
     ///
     /// Uses `db.create.setNodeVectorProperty` to ensure the correct type
@@ -3364,6 +3498,7 @@ impl Neo4jClient {
                 .ok()
                 .and_then(|s| serde_json::from_str(&format!("\"{}\"", s)).ok())
                 .unwrap_or_default(),
+            version: node.get("version").unwrap_or(0),
         })
     }
 
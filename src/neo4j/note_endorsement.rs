@@ -0,0 +1,104 @@
+//! Neo4j note endorsement operations (NoteEndorsement nodes)
+
+use super::client::Neo4jClient;
+use super::models::*;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use neo4rs::query;
+use uuid::Uuid;
+
+impl Neo4jClient {
+    /// Record a user's endorsement of a note, once per user and kind.
+    pub async fn add_note_endorsement_impl(
+        &self,
+        endorsement: &NoteEndorsementNode,
+    ) -> Result<bool> {
+        let q = query(
+            r#"
+            MATCH (n:Note {id: $note_id})
+            OPTIONAL MATCH (n)-[:HAS_ENDORSEMENT]->(old:NoteEndorsement {user_id: $user_id, kind: $kind})
+            WITH n, old IS NULL AS is_new
+            MERGE (n)-[:HAS_ENDORSEMENT]->(e:NoteEndorsement {
+                note_id: $note_id, user_id: $user_id, kind: $kind
+            })
+            ON CREATE SET e.user_name = $user_name,
+                          e.created_at = datetime($created_at)
+            RETURN is_new
+            "#,
+        )
+        .param("note_id", endorsement.note_id.to_string())
+        .param("user_id", endorsement.user_id.clone())
+        .param("kind", endorsement.kind.as_str())
+        .param("user_name", endorsement.user_name.clone())
+        .param("created_at", endorsement.created_at.to_rfc3339());
+
+        let mut result = self.graph.execute(q).await?;
+        match result.next().await? {
+            Some(row) => Ok(row.get::<bool>("is_new").unwrap_or(false)),
+            None => Ok(false),
+        }
+    }
+
+    /// Withdraw a user's endorsement of a note.
+    pub async fn remove_note_endorsement_impl(
+        &self,
+        note_id: Uuid,
+        user_id: &str,
+        kind: EndorsementKind,
+    ) -> Result<bool> {
+        let q = query(
+            r#"
+            MATCH (:Note {id: $note_id})-[:HAS_ENDORSEMENT]->(e:NoteEndorsement {user_id: $user_id, kind: $kind})
+            DETACH DELETE e
+            RETURN count(e) AS deleted
+            "#,
+        )
+        .param("note_id", note_id.to_string())
+        .param("user_id", user_id)
+        .param("kind", kind.as_str());
+
+        let mut result = self.graph.execute(q).await?;
+        let deleted = match result.next().await? {
+            Some(row) => row.get::<i64>("deleted").unwrap_or(0),
+            None => 0,
+        };
+        Ok(deleted > 0)
+    }
+
+    /// Endorsements of a note, oldest first.
+    pub async fn list_note_endorsements_impl(
+        &self,
+        note_id: Uuid,
+    ) -> Result<Vec<NoteEndorsementNode>> {
+        let q = query(
+            r#"
+            MATCH (:Note {id: $note_id})-[:HAS_ENDORSEMENT]->(e:NoteEndorsement)
+            RETURN e.kind AS kind, e.user_id AS user_id, e.user_name AS user_name,
+                   toString(e.created_at) AS created_at
+            ORDER BY e.created_at
+            "#,
+        )
+        .param("note_id", note_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut endorsements = Vec::new();
+        while let Some(row) = result.next().await? {
+            let kind: String = row.get("kind")?;
+            let Ok(kind) = kind.parse() else {
+                continue;
+            };
+            endorsements.push(NoteEndorsementNode {
+                note_id,
+                kind,
+                user_id: row.get("user_id")?,
+                user_name: row.get("user_name").unwrap_or_default(),
+                created_at: row
+                    .get::<String>("created_at")
+                    .ok()
+                    .and_then(|s| s.parse::<DateTime<Utc>>().ok())
+                    .unwrap_or_else(Utc::now),
+            });
+        }
+        Ok(endorsements)
+    }
+}
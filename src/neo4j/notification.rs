@@ -0,0 +1,82 @@
+//! Neo4j notification preference operations
+
+use super::client::Neo4jClient;
+use super::models::{NotificationPreferences, NotificationSubscriber};
+use anyhow::Result;
+use neo4rs::query;
+use uuid::Uuid;
+
+impl Neo4jClient {
+    /// Get the notification preferences of a user.
+    pub async fn get_notification_preferences_impl(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<NotificationPreferences>> {
+        let q = query(
+            r#"
+            MATCH (p:NotificationPreferences {user_id: $user_id})
+            RETURN p.email_enabled AS email_enabled, p.muted_rules AS muted_rules
+            "#,
+        )
+        .param("user_id", user_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        Ok(result.next().await?.map(|row| NotificationPreferences {
+            email_enabled: row.get("email_enabled").unwrap_or(false),
+            muted_rules: row.get("muted_rules").unwrap_or_default(),
+        }))
+    }
+
+    /// Replace the notification preferences of a user. The email address is
+    /// stored alongside so notifications need no user lookup.
+    pub async fn update_notification_preferences_impl(
+        &self,
+        user_id: Uuid,
+        email: &str,
+        preferences: &NotificationPreferences,
+    ) -> Result<()> {
+        let q = query(
+            r#"
+            MERGE (p:NotificationPreferences {user_id: $user_id})
+            SET p.email = $email,
+                p.email_enabled = $email_enabled,
+                p.muted_rules = $muted_rules,
+                p.updated_at = datetime()
+            "#,
+        )
+        .param("user_id", user_id.to_string())
+        .param("email", email)
+        .param("email_enabled", preferences.email_enabled)
+        .param("muted_rules", preferences.muted_rules.clone());
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
+    /// Users who opted in to email notifications.
+    pub async fn list_notification_subscribers_impl(&self) -> Result<Vec<NotificationSubscriber>> {
+        let q = query(
+            r#"
+            MATCH (p:NotificationPreferences)
+            WHERE p.email_enabled = true
+            RETURN p.user_id AS user_id, p.email AS email, p.muted_rules AS muted_rules
+            ORDER BY email
+            "#,
+        );
+
+        let mut result = self.graph.execute(q).await?;
+        let mut subscribers = Vec::new();
+        while let Some(row) = result.next().await? {
+            let user_id: String = row.get("user_id")?;
+            subscribers.push(NotificationSubscriber {
+                user_id: user_id.parse()?,
+                email: row.get("email")?,
+                preferences: NotificationPreferences {
+                    email_enabled: true,
+                    muted_rules: row.get("muted_rules").unwrap_or_default(),
+                },
+            });
+        }
+        Ok(subscribers)
+    }
+}
@@ -1,10 +1,13 @@
 //! Neo4j Plan operations
 
 use super::client::{pascal_to_snake_case, Neo4jClient, WhereBuilder};
+use super::constraint::create_constraint_query;
 use super::models::*;
+use super::step::create_step_query;
+use super::task::{add_task_dependency_query, create_task_query};
 use crate::plan::models::UpdatePlanRequest;
 use anyhow::{bail, Result};
-use neo4rs::query;
+use neo4rs::{query, Query};
 use serde::Serialize;
 use std::collections::{HashMap, HashSet, VecDeque};
 use uuid::Uuid;
@@ -141,57 +144,110 @@ pub struct WaveComputationResult {
     pub edges: Vec<(Uuid, Uuid)>,
 }
 
+/// Queries creating a plan and linking it to its project
+fn create_plan_queries(plan: &PlanNode) -> Vec<Query> {
+    let q = query(
+        r#"
+        CREATE (p:Plan {
+            id: $id,
+            title: $title,
+            description: $description,
+            status: $status,
+            created_at: datetime($created_at),
+            created_by: $created_by,
+            priority: $priority,
+            project_id: $project_id
+        })
+        "#,
+    )
+    .param("id", plan.id.to_string())
+    .param("title", plan.title.clone())
+    .param("description", plan.description.clone())
+    .param("status", format!("{:?}", plan.status))
+    .param("created_at", plan.created_at.to_rfc3339())
+    .param("created_by", plan.created_by.clone())
+    .param("priority", plan.priority as i64)
+    .param(
+        "project_id",
+        plan.project_id.map(|id| id.to_string()).unwrap_or_default(),
+    );
+    let mut queries = vec![q];
+
+    // Link to project if specified
+    if let Some(project_id) = plan.project_id {
+        let q = query(
+            r#"
+            MATCH (project:Project {id: $project_id})
+            MATCH (plan:Plan {id: $plan_id})
+            MERGE (project)-[:HAS_PLAN]->(plan)
+            "#,
+        )
+        .param("project_id", project_id.to_string())
+        .param("plan_id", plan.id.to_string());
+        queries.push(q);
+    }
+
+    queries
+}
+
 impl Neo4jClient {
     // ========================================================================
     // Plan operations
     // ========================================================================
 
-    /// Create a new plan
+    /// Create a new plan (and its link to its project) in a single transaction
     pub async fn create_plan(&self, plan: &PlanNode) -> Result<()> {
-        let q = query(
-            r#"
-            CREATE (p:Plan {
-                id: $id,
-                title: $title,
-                description: $description,
-                status: $status,
-                created_at: datetime($created_at),
-                created_by: $created_by,
-                priority: $priority,
-                project_id: $project_id
-            })
-            "#,
-        )
-        .param("id", plan.id.to_string())
-        .param("title", plan.title.clone())
-        .param("description", plan.description.clone())
-        .param("status", format!("{:?}", plan.status))
-        .param("created_at", plan.created_at.to_rfc3339())
-        .param("created_by", plan.created_by.clone())
-        .param("priority", plan.priority as i64)
-        .param(
-            "project_id",
-            plan.project_id.map(|id| id.to_string()).unwrap_or_default(),
-        );
-
-        self.graph.run(q).await?;
+        self.run_in_txn(create_plan_queries(plan)).await
+    }
 
-        // Link to project if specified
-        if let Some(project_id) = plan.project_id {
-            let q = query(
-                r#"
-                MATCH (project:Project {id: $project_id})
-                MATCH (plan:Plan {id: $plan_id})
-                MERGE (project)-[:HAS_PLAN]->(plan)
-                "#,
-            )
-            .param("project_id", project_id.to_string())
-            .param("plan_id", plan.id.to_string());
+    /// Create a plan with its constraints in a single transaction
+    pub async fn create_plan_with_constraints(
+        &self,
+        plan: &PlanNode,
+        constraints: &[ConstraintNode],
+    ) -> Result<()> {
+        let mut queries = create_plan_queries(plan);
+        queries.extend(
+            constraints
+                .iter()
+                .map(|c| create_constraint_query(plan.id, c)),
+        );
+        self.run_in_txn(queries).await
+    }
 
-            self.graph.run(q).await?;
+    /// Create a plan with its constraints, tasks, steps and task dependencies
+    /// in a single transaction
+    pub async fn create_plan_with_tasks(
+        &self,
+        plan: &PlanNode,
+        constraints: &[ConstraintNode],
+        tasks: &[NewPlanTask],
+    ) -> Result<()> {
+        let mut queries = create_plan_queries(plan);
+        queries.extend(
+            constraints
+                .iter()
+                .map(|c| create_constraint_query(plan.id, c)),
+        );
+        for new_task in tasks {
+            queries.push(create_task_query(plan.id, &new_task.task));
+            queries.extend(
+                new_task
+                    .steps
+                    .iter()
+                    .map(|step| create_step_query(new_task.task.id, step)),
+            );
         }
-
-        Ok(())
+        // Dependencies once every task exists
+        for new_task in tasks {
+            queries.extend(
+                new_task
+                    .depends_on
+                    .iter()
+                    .map(|dep_id| add_task_dependency_query(new_task.task.id, *dep_id)),
+            );
+        }
+        self.run_in_txn(queries).await
     }
 
     /// Get a plan by ID
@@ -242,6 +298,7 @@ impl Neo4jClient {
                 .ok()
                 .filter(|s| !s.is_empty()),
             persona: node.get::<String>("persona").ok().filter(|s| !s.is_empty()),
+            version: node.get("version").unwrap_or(0),
         })
     }
 
@@ -400,6 +457,7 @@ impl Neo4jClient {
         if set_clauses.is_empty() {
             return Ok(());
         }
+        set_clauses.push("p.version = coalesce(p.version, 0) + 1");
 
         let cypher = format!("MATCH (p:Plan {{id: $id}}) SET {}", set_clauses.join(", "));
         let mut q = query(&cypher).param("id", id.to_string());
@@ -423,7 +481,8 @@ impl Neo4jClient {
         let q = query(
             r#"
             MATCH (p:Plan {id: $id})
-            SET p.status = $status
+            SET p.status = $status,
+                p.version = coalesce(p.version, 0) + 1
             "#,
         )
         .param("id", id.to_string())
@@ -465,8 +524,11 @@ impl Neo4jClient {
         Ok(())
     }
 
-    /// Delete a plan and all its related data (tasks, steps, decisions, constraints)
+    /// Delete a plan and all its related data (tasks, steps, decisions,
+    /// constraints, comments) in a single transaction
     pub async fn delete_plan(&self, plan_id: Uuid) -> Result<()> {
+        let mut queries = Vec::new();
+
         // Delete all steps belonging to tasks of this plan
         let q = query(
             r#"
@@ -475,7 +537,7 @@ impl Neo4jClient {
             "#,
         )
         .param("id", plan_id.to_string());
-        self.graph.run(q).await?;
+        queries.push(q);
 
         // Delete all decisions belonging to tasks of this plan
         let q = query(
@@ -485,7 +547,19 @@ impl Neo4jClient {
             "#,
         )
         .param("id", plan_id.to_string());
-        self.graph.run(q).await?;
+        queries.push(q);
+
+        // Delete the comments on the plan and its tasks
+        let q = query(
+            r#"
+            MATCH (p:Plan {id: $id})
+            OPTIONAL MATCH (p)-[:HAS_COMMENT]->(pc:Comment)
+            OPTIONAL MATCH (p)-[:HAS_TASK]->(:Task)-[:HAS_COMMENT]->(tc:Comment)
+            DETACH DELETE pc, tc
+            "#,
+        )
+        .param("id", plan_id.to_string());
+        queries.push(q);
 
         // Delete all tasks belonging to this plan
         let q = query(
@@ -495,7 +569,7 @@ impl Neo4jClient {
             "#,
         )
         .param("id", plan_id.to_string());
-        self.graph.run(q).await?;
+        queries.push(q);
 
         // Delete all constraints belonging to this plan
         let q = query(
@@ -505,7 +579,7 @@ impl Neo4jClient {
             "#,
         )
         .param("id", plan_id.to_string());
-        self.graph.run(q).await?;
+        queries.push(q);
 
         // Delete the plan itself
         let q = query(
@@ -515,9 +589,9 @@ impl Neo4jClient {
             "#,
         )
         .param("id", plan_id.to_string());
-        self.graph.run(q).await?;
+        queries.push(q);
 
-        Ok(())
+        self.run_in_txn(queries).await
     }
 
     /// Get dependency graph for a plan (all tasks and their dependencies)
@@ -22,7 +22,8 @@ impl Neo4jClient {
                 root_path: $root_path,
                 description: $description,
                 created_at: datetime($created_at),
-                watch_enabled: $watch_enabled
+                watch_enabled: $watch_enabled,
+                archived: $archived
             })
             "#,
         )
@@ -35,7 +36,8 @@ impl Neo4jClient {
             project.description.clone().unwrap_or_default(),
         )
         .param("created_at", project.created_at.to_rfc3339())
-        .param("watch_enabled", project.watch_enabled);
+        .param("watch_enabled", project.watch_enabled)
+        .param("archived", project.archived);
 
         self.graph.run(q).await?;
         Ok(())
@@ -145,12 +147,25 @@ impl Neo4jClient {
         Ok(())
     }
 
-    /// Update project last_synced timestamp
+    /// Update project last_synced timestamp and refresh its language
+    /// breakdown (parallel `languages` / `language_files` / `language_lines`
+    /// lists, most lines first, and the total `line_count`).
     pub async fn update_project_synced(&self, id: Uuid) -> Result<()> {
         let q = query(
             r#"
             MATCH (p:Project {id: $id})
             SET p.last_synced = datetime($now)
+            WITH p
+            OPTIONAL MATCH (p)-[:CONTAINS]->(f:File)
+            WITH p, f.language AS language, count(f) AS files,
+                 sum(coalesce(f.line_count, 0)) AS lines
+            ORDER BY lines DESC, language
+            WITH p, [s IN collect({language: language, files: files, lines: lines})
+                     WHERE s.language IS NOT NULL] AS stats
+            SET p.languages = [s IN stats | s.language],
+                p.language_files = [s IN stats | s.files],
+                p.language_lines = [s IN stats | s.lines],
+                p.line_count = reduce(total = 0, s IN stats | total + s.lines)
             "#,
         )
         .param("id", id.to_string())
@@ -160,6 +175,37 @@ impl Neo4jClient {
         Ok(())
     }
 
+    /// Language breakdown stored by the last `update_project_synced`
+    pub async fn get_project_language_stats(&self, project_id: Uuid) -> Result<Vec<LanguageStats>> {
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $id})
+            RETURN coalesce(p.languages, []) AS languages,
+                   coalesce(p.language_files, []) AS files,
+                   coalesce(p.language_lines, []) AS lines
+            "#,
+        )
+        .param("id", project_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let Some(row) = result.next().await? else {
+            return Ok(Vec::new());
+        };
+        let languages: Vec<String> = row.get("languages")?;
+        let files: Vec<i64> = row.get("files")?;
+        let lines: Vec<i64> = row.get("lines")?;
+        Ok(languages
+            .into_iter()
+            .zip(files)
+            .zip(lines)
+            .map(|((language, files), lines)| LanguageStats {
+                language,
+                files: files.max(0) as u64,
+                lines: lines.max(0) as u64,
+            })
+            .collect())
+    }
+
     /// Update project analytics_computed_at timestamp
     pub async fn update_project_analytics_timestamp(&self, id: Uuid) -> Result<()> {
         let q = query(
@@ -216,6 +262,15 @@ impl Neo4jClient {
         Ok(())
     }
 
+    /// Set the archived flag on a project.
+    pub async fn set_project_archived(&self, id: Uuid, archived: bool) -> Result<()> {
+        let q = query("MATCH (p:Project {id: $id}) SET p.archived = $archived")
+            .param("id", id.to_string())
+            .param("archived", archived);
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
     /// Set or clear default_note_energy on a project (homeostasis).
     pub async fn set_default_note_energy(&self, id: Uuid, energy: Option<f64>) -> Result<()> {
         let q = if let Some(e) = energy {
@@ -363,6 +418,95 @@ impl Neo4jClient {
         .param("id", id.to_string());
         self.graph.run(q).await?;
 
+        // Delete documentation (documents + sections)
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $id})-[:HAS_DOCUMENT]->(d:Document)
+            OPTIONAL MATCH (d)-[:HAS_SECTION]->(s:Section)
+            DETACH DELETE s, d
+            "#,
+        )
+        .param("id", id.to_string());
+        self.graph.run(q).await?;
+
+        // Delete the SQL schema (tables + columns + migrations)
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $id})
+            OPTIONAL MATCH (p)-[:HAS_TABLE]->(t:Table)
+            OPTIONAL MATCH (t)-[:HAS_COLUMN]->(c:Column)
+            OPTIONAL MATCH (p)-[:HAS_MIGRATION]->(m:Migration)
+            DETACH DELETE c, t, m
+            "#,
+        )
+        .param("id", id.to_string());
+        self.graph.run(q).await?;
+
+        // Delete configuration keys and the config files they link to
+        let q = query(
+            r#"
+            OPTIONAL MATCH (k:ConfigKey {project_id: $id})
+            DETACH DELETE k
+            WITH count(*) AS deleted
+            OPTIONAL MATCH (f:ConfigFile {project_id: $id})
+            DETACH DELETE f
+            "#,
+        )
+        .param("id", id.to_string());
+        self.graph.run(q).await?;
+
+        // Delete cached module summaries
+        let q = query(
+            r#"
+            MATCH (m:ModuleSummary {project_id: $id})
+            DETACH DELETE m
+            "#,
+        )
+        .param("id", id.to_string());
+        self.graph.run(q).await?;
+
+        // Delete API surface snapshots
+        let q = query(
+            r#"
+            MATCH (s:ApiSurfaceSnapshot {project_id: $id})
+            DETACH DELETE s
+            "#,
+        )
+        .param("id", id.to_string());
+        self.graph.run(q).await?;
+
+        // Delete CI test runs and test case histories
+        let q = query(
+            r#"
+            MATCH (t)
+            WHERE (t:TestRun OR t:TestCase) AND t.project_id = $id
+            DETACH DELETE t
+            "#,
+        )
+        .param("id", id.to_string());
+        self.graph.run(q).await?;
+
+        // Delete Sentry issues
+        let q = query(
+            r#"
+            MATCH (i:SentryIssue {project_id: $id})
+            DETACH DELETE i
+            "#,
+        )
+        .param("id", id.to_string());
+        self.graph.run(q).await?;
+
+        // Delete CODEOWNERS rules and owners
+        let q = query(
+            r#"
+            MATCH (n)
+            WHERE (n:CodeOwnerRule OR n:CodeOwner) AND n.project_id = $id
+            DETACH DELETE n
+            "#,
+        )
+        .param("id", id.to_string());
+        self.graph.run(q).await?;
+
         // ================================================================
         // Phase 4: Delete the project node itself
         // ================================================================
@@ -416,6 +560,7 @@ impl Neo4jClient {
                 .ok()
                 .and_then(|s| serde_json::from_str(&s).ok()),
             watch_enabled: node.get::<bool>("watch_enabled").unwrap_or(true),
+            archived: node.get::<bool>("archived").unwrap_or(false),
         })
     }
 
@@ -499,7 +644,8 @@ impl Neo4jClient {
         Ok(tasks)
     }
 
-    /// List projects with search and pagination
+    /// List projects with search and pagination. Archived projects are left
+    /// out unless `include_archived`.
     pub async fn list_projects_filtered(
         &self,
         search: Option<&str>,
@@ -507,9 +653,13 @@ impl Neo4jClient {
         offset: usize,
         sort_by: Option<&str>,
         sort_order: &str,
+        include_archived: bool,
     ) -> Result<(Vec<ProjectNode>, usize)> {
         let mut where_builder = WhereBuilder::new();
         where_builder.add_search_filter("p", search);
+        if !include_archived {
+            where_builder.add_archived_filter("p");
+        }
 
         let where_clause = where_builder.build();
         let order_field = match sort_by {
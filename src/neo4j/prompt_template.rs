@@ -0,0 +1,173 @@
+//! Neo4j prompt template operations (templates and their versions)
+
+use super::client::Neo4jClient;
+use super::models::*;
+use anyhow::Result;
+use neo4rs::query;
+use uuid::Uuid;
+
+fn parse_datetime(node: &neo4rs::Node, key: &str) -> chrono::DateTime<chrono::Utc> {
+    node.get::<String>(key)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or_else(chrono::Utc::now)
+}
+
+fn parse_template_node(node: &neo4rs::Node) -> Result<PromptTemplateNode> {
+    let description: String = node.get("description").unwrap_or_default();
+    Ok(PromptTemplateNode {
+        id: node.get::<String>("id")?.parse()?,
+        name: node.get("name")?,
+        description: if description.is_empty() {
+            None
+        } else {
+            Some(description)
+        },
+        content: node.get("content")?,
+        version: node.get("version").unwrap_or(1),
+        created_at: parse_datetime(node, "created_at"),
+        updated_at: parse_datetime(node, "updated_at"),
+    })
+}
+
+impl Neo4jClient {
+    /// Create a prompt template and its first version.
+    pub async fn create_prompt_template_impl(&self, template: &PromptTemplateNode) -> Result<()> {
+        let q = query(
+            r#"
+            CREATE (t:PromptTemplate {
+                id: $id,
+                name: $name,
+                description: $description,
+                content: $content,
+                version: $version,
+                created_at: datetime($created_at),
+                updated_at: datetime($updated_at)
+            })
+            CREATE (t)-[:HAS_VERSION]->(:PromptTemplateVersion {
+                template_id: $id,
+                version: $version,
+                content: $content,
+                created_at: datetime($updated_at)
+            })
+            "#,
+        )
+        .param("id", template.id.to_string())
+        .param("name", template.name.clone())
+        .param(
+            "description",
+            template.description.clone().unwrap_or_default(),
+        )
+        .param("content", template.content.clone())
+        .param("version", template.version)
+        .param("created_at", template.created_at.to_rfc3339())
+        .param("updated_at", template.updated_at.to_rfc3339());
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
+    /// Get a prompt template by ID.
+    pub async fn get_prompt_template_impl(&self, id: Uuid) -> Result<Option<PromptTemplateNode>> {
+        let q = query("MATCH (t:PromptTemplate {id: $id}) RETURN t").param("id", id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        match result.next().await? {
+            Some(row) => {
+                let node: neo4rs::Node = row.get("t")?;
+                Ok(Some(parse_template_node(&node)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// List prompt templates by name.
+    pub async fn list_prompt_templates_impl(&self) -> Result<Vec<PromptTemplateNode>> {
+        let q = query("MATCH (t:PromptTemplate) RETURN t ORDER BY t.name");
+
+        let mut result = self.graph.execute(q).await?;
+        let mut templates = Vec::new();
+        while let Some(row) = result.next().await? {
+            let node: neo4rs::Node = row.get("t")?;
+            templates.push(parse_template_node(&node)?);
+        }
+        Ok(templates)
+    }
+
+    /// Save a prompt template, recording its content as `template.version`
+    /// if that version does not exist yet.
+    pub async fn update_prompt_template_impl(&self, template: &PromptTemplateNode) -> Result<()> {
+        let q = query(
+            r#"
+            MATCH (t:PromptTemplate {id: $id})
+            SET t.name = $name,
+                t.description = $description,
+                t.content = $content,
+                t.version = $version,
+                t.updated_at = datetime($updated_at)
+            MERGE (t)-[:HAS_VERSION]->(v:PromptTemplateVersion {template_id: $id, version: $version})
+            ON CREATE SET v.content = $content, v.created_at = datetime($updated_at)
+            "#,
+        )
+        .param("id", template.id.to_string())
+        .param("name", template.name.clone())
+        .param(
+            "description",
+            template.description.clone().unwrap_or_default(),
+        )
+        .param("content", template.content.clone())
+        .param("version", template.version)
+        .param("updated_at", template.updated_at.to_rfc3339());
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
+    /// List the versions of a prompt template, newest first.
+    pub async fn list_prompt_template_versions_impl(
+        &self,
+        id: Uuid,
+    ) -> Result<Vec<PromptTemplateVersion>> {
+        let q = query(
+            r#"
+            MATCH (:PromptTemplate {id: $id})-[:HAS_VERSION]->(v:PromptTemplateVersion)
+            RETURN v
+            ORDER BY v.version DESC
+            "#,
+        )
+        .param("id", id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut versions = Vec::new();
+        while let Some(row) = result.next().await? {
+            let node: neo4rs::Node = row.get("v")?;
+            versions.push(PromptTemplateVersion {
+                version: node.get("version")?,
+                content: node.get("content")?,
+                created_at: parse_datetime(&node, "created_at"),
+            });
+        }
+        Ok(versions)
+    }
+
+    /// Delete a prompt template and its versions. Returns false if it did
+    /// not exist.
+    pub async fn delete_prompt_template_impl(&self, id: Uuid) -> Result<bool> {
+        let q = query(
+            r#"
+            MATCH (t:PromptTemplate {id: $id})
+            OPTIONAL MATCH (t)-[:HAS_VERSION]->(v:PromptTemplateVersion)
+            DETACH DELETE v, t
+            RETURN count(DISTINCT t) AS deleted
+            "#,
+        )
+        .param("id", id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let deleted: i64 = match result.next().await? {
+            Some(row) => row.get("deleted").unwrap_or(0),
+            None => 0,
+        };
+        Ok(deleted > 0)
+    }
+}
@@ -0,0 +1,485 @@
+//! Timeouts and slow-query tracking for the Cypher queries of [`Neo4jClient`].
+//!
+//! The graph handle of [`Neo4jClient`] is a [`GuardedGraph`]: its `execute`
+//! and `run` take the same queries as `neo4rs::Graph`'s, but bound each query
+//! by [`QueryLimits::timeout`] — from sending it to reading its last row —
+//! and record its duration in [`QueryMetrics`]. Queries slower than
+//! [`QueryLimits::slow_threshold`] are logged at WARN. neo4rs does not expose
+//! the text of a `Query`, so a query is identified by the source location
+//! that issued it (`src/neo4j/note.rs:212:14`).
+//!
+//! A query that times out, or whose caller is dropped (e.g. the handler of a
+//! request whose client went away), gives its connection back to the pool in
+//! the middle of the exchange. The pool resets connections before reuse and
+//! discards the ones it cannot reset, either of which makes Neo4j abort the
+//! transaction.
+//!
+//! The chunked helpers of [`super::batch`] take the bare `neo4rs::Graph`
+//! (through `Deref`) and are not bounded: each chunk is already sized to
+//! finish well within a transaction.
+//!
+//! [`Neo4jClient`]: super::Neo4jClient
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use neo4rs::{DetachedRowStream, Graph, Query, Row};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::ops::Deref;
+use std::panic::Location;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Default [`QueryLimits::timeout`].
+pub const DEFAULT_QUERY_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Default [`QueryLimits::slow_threshold`].
+pub const DEFAULT_SLOW_QUERY_THRESHOLD: Duration = Duration::from_millis(100);
+
+/// Slow queries kept in [`QueryMetrics::recent_slow`].
+const RECENT_SLOW_QUERIES: usize = 50;
+
+/// Bounds applied to every query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryLimits {
+    /// Longest a query may run (`None`: unbounded)
+    pub timeout: Option<Duration>,
+    /// Queries running at least this long are logged and counted as slow
+    pub slow_threshold: Duration,
+}
+
+impl Default for QueryLimits {
+    fn default() -> Self {
+        Self {
+            timeout: Some(DEFAULT_QUERY_TIMEOUT),
+            slow_threshold: DEFAULT_SLOW_QUERY_THRESHOLD,
+        }
+    }
+}
+
+/// A query ran longer than [`QueryLimits::timeout`].
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Neo4j query at {caller} timed out after {}ms", .timeout.as_millis())]
+pub struct QueryTimeout {
+    /// Source location of the query
+    pub caller: String,
+    pub timeout: Duration,
+}
+
+/// A query slower than the threshold.
+#[derive(Debug, Clone, Serialize)]
+pub struct SlowQuery {
+    /// Source location of the query
+    pub caller: String,
+    pub duration_ms: u64,
+    /// "ok", "failed", "timed_out" or "cancelled"
+    pub outcome: &'static str,
+    pub finished_at: DateTime<Utc>,
+}
+
+/// Counters of the queries run since the client was created.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct QueryMetrics {
+    pub queries: u64,
+    pub failed: u64,
+    pub timed_out: u64,
+    /// Queries whose caller stopped waiting before they finished
+    pub cancelled: u64,
+    pub slow: u64,
+    /// `None` when queries are unbounded
+    pub timeout_ms: Option<u64>,
+    pub slow_threshold_ms: u64,
+    /// Latest slow queries, most recent first
+    pub recent_slow: Vec<SlowQuery>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Ok,
+    Failed,
+    TimedOut,
+    Cancelled,
+}
+
+impl Outcome {
+    fn as_str(self) -> &'static str {
+        match self {
+            Outcome::Ok => "ok",
+            Outcome::Failed => "failed",
+            Outcome::TimedOut => "timed_out",
+            Outcome::Cancelled => "cancelled",
+        }
+    }
+}
+
+struct QueryStats {
+    limits: QueryLimits,
+    queries: AtomicU64,
+    failed: AtomicU64,
+    timed_out: AtomicU64,
+    cancelled: AtomicU64,
+    slow: AtomicU64,
+    recent_slow: Mutex<VecDeque<SlowQuery>>,
+}
+
+impl QueryStats {
+    fn new(limits: QueryLimits) -> Self {
+        Self {
+            limits,
+            queries: AtomicU64::new(0),
+            failed: AtomicU64::new(0),
+            timed_out: AtomicU64::new(0),
+            cancelled: AtomicU64::new(0),
+            slow: AtomicU64::new(0),
+            recent_slow: Mutex::new(VecDeque::with_capacity(RECENT_SLOW_QUERIES)),
+        }
+    }
+
+    fn record(&self, caller: &Location<'static>, elapsed: Duration, outcome: Outcome) {
+        self.queries.fetch_add(1, Ordering::Relaxed);
+        match outcome {
+            Outcome::Ok => {}
+            Outcome::Failed => {
+                self.failed.fetch_add(1, Ordering::Relaxed);
+            }
+            Outcome::TimedOut => {
+                self.timed_out.fetch_add(1, Ordering::Relaxed);
+            }
+            Outcome::Cancelled => {
+                self.cancelled.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let query_ms = elapsed.as_millis() as u64;
+        if elapsed < self.limits.slow_threshold && outcome != Outcome::TimedOut {
+            tracing::trace!(query_ms, %caller, "Neo4j query completed");
+            return;
+        }
+        self.slow.fetch_add(1, Ordering::Relaxed);
+        tracing::warn!(
+            query_ms,
+            threshold_ms = self.limits.slow_threshold.as_millis() as u64,
+            %caller,
+            outcome = outcome.as_str(),
+            "Slow Neo4j query detected"
+        );
+        let mut recent = self.recent_slow.lock().unwrap_or_else(|e| e.into_inner());
+        if recent.len() == RECENT_SLOW_QUERIES {
+            recent.pop_back();
+        }
+        recent.push_front(SlowQuery {
+            caller: caller.to_string(),
+            duration_ms: query_ms,
+            outcome: outcome.as_str(),
+            finished_at: Utc::now(),
+        });
+    }
+
+    fn snapshot(&self) -> QueryMetrics {
+        QueryMetrics {
+            queries: self.queries.load(Ordering::Relaxed),
+            failed: self.failed.load(Ordering::Relaxed),
+            timed_out: self.timed_out.load(Ordering::Relaxed),
+            cancelled: self.cancelled.load(Ordering::Relaxed),
+            slow: self.slow.load(Ordering::Relaxed),
+            timeout_ms: self.limits.timeout.map(|t| t.as_millis() as u64),
+            slow_threshold_ms: self.limits.slow_threshold.as_millis() as u64,
+            recent_slow: self
+                .recent_slow
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .iter()
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// One query, recorded once when it finishes, fails, times out or is dropped.
+struct InFlight {
+    stats: Arc<QueryStats>,
+    caller: &'static Location<'static>,
+    started: Instant,
+    /// When the last response of the server was read
+    last_read: Instant,
+    /// Set while waiting for the server: being dropped then is a cancellation
+    waiting: bool,
+    finished: bool,
+}
+
+impl InFlight {
+    fn start(stats: Arc<QueryStats>, caller: &'static Location<'static>) -> Self {
+        let now = Instant::now();
+        Self {
+            stats,
+            caller,
+            started: now,
+            last_read: now,
+            waiting: false,
+            finished: false,
+        }
+    }
+
+    /// Await one exchange with the server within what is left of the timeout.
+    async fn bound<T, E, F>(&mut self, fut: F) -> Result<T>
+    where
+        F: Future<Output = std::result::Result<T, E>>,
+        E: Into<anyhow::Error>,
+    {
+        let remaining = self
+            .stats
+            .limits
+            .timeout
+            .map(|t| t.saturating_sub(self.started.elapsed()));
+        self.waiting = true;
+        let result = match remaining {
+            Some(remaining) => tokio::time::timeout(remaining, fut).await,
+            None => Ok(fut.await),
+        };
+        self.waiting = false;
+        self.last_read = Instant::now();
+        match result {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(e)) => {
+                self.finish(Outcome::Failed);
+                Err(e.into())
+            }
+            Err(_) => {
+                self.finish(Outcome::TimedOut);
+                Err(QueryTimeout {
+                    caller: self.caller.to_string(),
+                    timeout: self.stats.limits.timeout.unwrap_or_default(),
+                }
+                .into())
+            }
+        }
+    }
+
+    fn finish(&mut self, outcome: Outcome) {
+        if !self.finished {
+            self.finished = true;
+            self.stats
+                .record(self.caller, self.last_read - self.started, outcome);
+        }
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        if self.waiting {
+            self.last_read = Instant::now();
+            self.finish(Outcome::Cancelled);
+        } else {
+            // Rows left unread (e.g. only the first one was needed)
+            self.finish(Outcome::Ok);
+        }
+    }
+}
+
+/// Rows of a query run through [`GuardedGraph::execute`].
+pub(crate) struct GuardedRows {
+    rows: DetachedRowStream,
+    query: InFlight,
+}
+
+impl GuardedRows {
+    /// Next row, `None` once all have been read.
+    pub(crate) async fn next(&mut self) -> Result<Option<Row>> {
+        let row = self.query.bound(self.rows.next()).await?;
+        if row.is_none() {
+            self.query.finish(Outcome::Ok);
+        }
+        Ok(row)
+    }
+}
+
+/// `neo4rs::Graph` whose queries are bounded and measured.
+#[derive(Clone)]
+pub(crate) struct GuardedGraph {
+    graph: Arc<Graph>,
+    stats: Arc<QueryStats>,
+}
+
+impl GuardedGraph {
+    pub(crate) fn new(graph: Graph, limits: QueryLimits) -> Self {
+        Self {
+            graph: Arc::new(graph),
+            stats: Arc::new(QueryStats::new(limits)),
+        }
+    }
+
+    /// Same connection pool, other limits (counters start over).
+    pub(crate) fn with_limits(&self, limits: QueryLimits) -> Self {
+        Self {
+            graph: self.graph.clone(),
+            stats: Arc::new(QueryStats::new(limits)),
+        }
+    }
+
+    pub(crate) fn limits(&self) -> QueryLimits {
+        self.stats.limits
+    }
+
+    pub(crate) fn metrics(&self) -> QueryMetrics {
+        self.stats.snapshot()
+    }
+
+    /// Run a query and stream its rows.
+    #[track_caller]
+    pub(crate) fn execute(
+        &self,
+        q: Query,
+    ) -> impl Future<Output = Result<GuardedRows>> + Send + '_ {
+        let mut query = InFlight::start(self.stats.clone(), Location::caller());
+        async move {
+            let rows = query.bound(self.graph.execute(q)).await?;
+            Ok(GuardedRows { rows, query })
+        }
+    }
+
+    /// Run a query, discarding its rows.
+    #[track_caller]
+    pub(crate) fn run(&self, q: Query) -> impl Future<Output = Result<()>> + Send + '_ {
+        self.bound(self.graph.run(q))
+    }
+
+    /// Bound and measure any work on the graph, such as a transaction.
+    #[track_caller]
+    pub(crate) fn bound<T, E, F>(&self, fut: F) -> impl Future<Output = Result<T>> + Send
+    where
+        F: Future<Output = std::result::Result<T, E>> + Send,
+        E: Into<anyhow::Error>,
+        T: Send,
+    {
+        let mut query = InFlight::start(self.stats.clone(), Location::caller());
+        async move {
+            let value = query.bound(fut).await?;
+            query.finish(Outcome::Ok);
+            Ok(value)
+        }
+    }
+}
+
+impl Deref for GuardedGraph {
+    type Target = Graph;
+
+    fn deref(&self) -> &Graph {
+        &self.graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(timeout_ms: u64, slow_ms: u64) -> Arc<QueryStats> {
+        Arc::new(QueryStats::new(QueryLimits {
+            timeout: Some(Duration::from_millis(timeout_ms)),
+            slow_threshold: Duration::from_millis(slow_ms),
+        }))
+    }
+
+    async fn sleep_ok(ms: u64) -> Result<()> {
+        tokio::time::sleep(Duration::from_millis(ms)).await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fast_query_is_counted_not_logged() {
+        let stats = stats(1_000, 500);
+        let mut query = InFlight::start(stats.clone(), Location::caller());
+        query.bound(sleep_ok(0)).await.unwrap();
+        query.finish(Outcome::Ok);
+
+        let metrics = stats.snapshot();
+        assert_eq!(metrics.queries, 1);
+        assert_eq!(metrics.slow, 0);
+        assert!(metrics.recent_slow.is_empty());
+        assert_eq!(metrics.timeout_ms, Some(1_000));
+    }
+
+    #[tokio::test]
+    async fn test_slow_query_is_recorded_with_its_caller() {
+        let stats = stats(1_000, 10);
+        let mut query = InFlight::start(stats.clone(), Location::caller());
+        query.bound(sleep_ok(30)).await.unwrap();
+        drop(query);
+
+        let metrics = stats.snapshot();
+        assert_eq!((metrics.queries, metrics.slow), (1, 1));
+        let slow = &metrics.recent_slow[0];
+        assert!(slow.caller.starts_with("src/neo4j/query_guard.rs:"));
+        assert!(slow.duration_ms >= 30);
+        assert_eq!(slow.outcome, "ok");
+    }
+
+    #[tokio::test]
+    async fn test_query_times_out() {
+        let stats = stats(20, 10);
+        let mut query = InFlight::start(stats.clone(), Location::caller());
+        let err = query.bound(sleep_ok(5_000)).await.unwrap_err();
+        let timeout = err.downcast_ref::<QueryTimeout>().unwrap();
+        assert_eq!(timeout.timeout, Duration::from_millis(20));
+
+        let metrics = stats.snapshot();
+        assert_eq!((metrics.queries, metrics.timed_out), (1, 1));
+        assert_eq!(metrics.recent_slow[0].outcome, "timed_out");
+    }
+
+    #[tokio::test]
+    async fn test_timeout_covers_the_whole_query() {
+        let stats = stats(50, 1_000);
+        let mut query = InFlight::start(stats.clone(), Location::caller());
+        // Each exchange fits in the timeout, not all of them together
+        query.bound(sleep_ok(30)).await.unwrap();
+        let err = query.bound(sleep_ok(30)).await.unwrap_err();
+        assert!(err.is::<QueryTimeout>());
+        assert_eq!(stats.snapshot().timed_out, 1);
+    }
+
+    #[tokio::test]
+    async fn test_dropped_query_is_cancelled() {
+        let stats = stats(5_000, 1_000);
+        let pending = {
+            let stats = stats.clone();
+            async move {
+                let mut query = InFlight::start(stats, Location::caller());
+                query.bound(sleep_ok(5_000)).await
+            }
+        };
+        assert!(tokio::time::timeout(Duration::from_millis(10), pending)
+            .await
+            .is_err());
+
+        let metrics = stats.snapshot();
+        assert_eq!((metrics.queries, metrics.cancelled), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_failed_query_is_counted_once() {
+        let stats = stats(1_000, 1_000);
+        let mut query = InFlight::start(stats.clone(), Location::caller());
+        let err = query
+            .bound(async { Err::<(), _>(anyhow::anyhow!("syntax error")) })
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "syntax error");
+        drop(query);
+
+        let metrics = stats.snapshot();
+        assert_eq!((metrics.queries, metrics.failed), (1, 1));
+    }
+
+    #[test]
+    fn test_recent_slow_is_capped() {
+        let stats = stats(1_000, 0);
+        for _ in 0..RECENT_SLOW_QUERIES + 5 {
+            stats.record(Location::caller(), Duration::from_millis(1), Outcome::Ok);
+        }
+        let metrics = stats.snapshot();
+        assert_eq!(metrics.slow, RECENT_SLOW_QUERIES as u64 + 5);
+        assert_eq!(metrics.recent_slow.len(), RECENT_SLOW_QUERIES);
+    }
+}
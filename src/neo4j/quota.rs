@@ -0,0 +1,82 @@
+//! Neo4j quota operations (server-wide settings and workspace overrides)
+
+use super::client::Neo4jClient;
+use super::models::*;
+use anyhow::{Context, Result};
+use neo4rs::query;
+use uuid::Uuid;
+
+impl Neo4jClient {
+    /// Get the server-wide quota settings.
+    pub async fn get_quota_settings_impl(&self) -> Result<Option<QuotaSettings>> {
+        let q = query("MATCH (q:QuotaSettings {id: 'default'}) RETURN q.settings AS value");
+
+        let mut result = self.graph.execute(q).await?;
+        result
+            .next()
+            .await?
+            .and_then(|row| row.get::<String>("value").ok())
+            .filter(|s| !s.is_empty())
+            .map(|json| serde_json::from_str(&json).context("Failed to deserialize quotas"))
+            .transpose()
+    }
+
+    /// Replace the server-wide quota settings.
+    pub async fn update_quota_settings_impl(&self, settings: &QuotaSettings) -> Result<()> {
+        let q = query(
+            r#"
+            MERGE (q:QuotaSettings {id: 'default'})
+            SET q.settings = $settings, q.updated_at = datetime()
+            "#,
+        )
+        .param("settings", serde_json::to_string(settings)?);
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
+    /// Get the quota override of a workspace.
+    pub async fn get_workspace_quotas_impl(
+        &self,
+        workspace_id: Uuid,
+    ) -> Result<Option<QuotaLimits>> {
+        let q = query("MATCH (w:Workspace {id: $id}) RETURN w.quotas AS value")
+            .param("id", workspace_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        result
+            .next()
+            .await?
+            .and_then(|row| row.get::<String>("value").ok())
+            .filter(|s| !s.is_empty())
+            .map(|json| serde_json::from_str(&json).context("Failed to deserialize quotas"))
+            .transpose()
+    }
+
+    /// Replace the quota override of a workspace (`None` removes it).
+    pub async fn update_workspace_quotas_impl(
+        &self,
+        workspace_id: Uuid,
+        limits: Option<&QuotaLimits>,
+    ) -> Result<()> {
+        let q = match limits {
+            Some(limits) => query(
+                r#"
+                MATCH (w:Workspace {id: $id})
+                SET w.quotas = $quotas
+                "#,
+            )
+            .param("quotas", serde_json::to_string(limits)?),
+            None => query(
+                r#"
+                MATCH (w:Workspace {id: $id})
+                REMOVE w.quotas
+                "#,
+            ),
+        }
+        .param("id", workspace_id.to_string());
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,175 @@
+//! Neo4j retention operations (retention policy, purges of expired records)
+
+use super::batch::BATCH_SIZE;
+use super::client::Neo4jClient;
+use super::models::{RetentionKind, RetentionPolicy, RetentionReport};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use neo4rs::query;
+
+impl Neo4jClient {
+    /// Read one JSON property of the `RetentionSettings` node.
+    async fn get_retention_json(&self, property: &str) -> Result<Option<String>> {
+        let q = query(&format!(
+            "MATCH (r:RetentionSettings {{id: 'default'}}) RETURN r.{} AS value",
+            property
+        ));
+
+        let mut result = self.graph.execute(q).await?;
+        Ok(result
+            .next()
+            .await?
+            .and_then(|row| row.get::<String>("value").ok())
+            .filter(|s| !s.is_empty()))
+    }
+
+    /// Get the retention policy of the deployment.
+    pub async fn get_retention_policy_impl(&self) -> Result<Option<RetentionPolicy>> {
+        self.get_retention_json("policy")
+            .await?
+            .map(|json| {
+                serde_json::from_str(&json).context("Failed to deserialize retention policy")
+            })
+            .transpose()
+    }
+
+    /// Replace the retention policy of the deployment.
+    pub async fn update_retention_policy_impl(&self, policy: &RetentionPolicy) -> Result<()> {
+        let q = query(
+            r#"
+            MERGE (r:RetentionSettings {id: 'default'})
+            SET r.policy = $policy, r.updated_at = datetime()
+            "#,
+        )
+        .param("policy", serde_json::to_string(policy)?);
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
+    /// Get the report of the last retention purge.
+    pub async fn get_retention_report_impl(&self) -> Result<Option<RetentionReport>> {
+        self.get_retention_json("last_report")
+            .await?
+            .map(|json| {
+                serde_json::from_str(&json).context("Failed to deserialize retention report")
+            })
+            .transpose()
+    }
+
+    /// Store the report of the last retention purge.
+    pub async fn save_retention_report_impl(&self, report: &RetentionReport) -> Result<()> {
+        let q = query(
+            r#"
+            MERGE (r:RetentionSettings {id: 'default'})
+            SET r.last_report = $report
+            "#,
+        )
+        .param("report", serde_json::to_string(report)?);
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
+    /// Delete the records of a kind older than the cutoff, in batches of
+    /// [`BATCH_SIZE`]. Returns the number of records deleted.
+    pub async fn purge_expired_impl(
+        &self,
+        kind: RetentionKind,
+        cutoff: DateTime<Utc>,
+    ) -> Result<u64> {
+        // Chat events and alerts store RFC 3339 strings, which sort
+        // chronologically; the other records store datetimes.
+        let cypher = match kind {
+            RetentionKind::ChatEvents => {
+                r#"
+                MATCH (e:ChatEvent) WHERE e.created_at < $cutoff
+                WITH e LIMIT $batch_size
+                DETACH DELETE e
+                RETURN count(e) AS cnt
+                "#
+            }
+            RetentionKind::SharingEvents => {
+                r#"
+                MATCH (se:SharingEvent) WHERE se.timestamp < datetime($cutoff)
+                WITH se LIMIT $batch_size
+                DETACH DELETE se
+                RETURN count(se) AS cnt
+                "#
+            }
+            RetentionKind::SearchQueries => {
+                r#"
+                MATCH (s:SearchQuery) WHERE s.last_seen_at < datetime($cutoff)
+                WITH s LIMIT $batch_size
+                DETACH DELETE s
+                RETURN count(s) AS cnt
+                "#
+            }
+            RetentionKind::Alerts => {
+                r#"
+                MATCH (a:Alert) WHERE a.acknowledged = true AND a.created_at < $cutoff
+                WITH a LIMIT $batch_size
+                DETACH DELETE a
+                RETURN count(a) AS cnt
+                "#
+            }
+            RetentionKind::UndoEntries => {
+                r#"
+                MATCH (u:UndoEntry) WHERE u.created_at < datetime($cutoff)
+                WITH u LIMIT $batch_size
+                DETACH DELETE u
+                RETURN count(u) AS cnt
+                "#
+            }
+            // Only the usage is removed: the session is kept. `killed` is
+            // only serialized when set.
+            RetentionKind::SessionUsage => {
+                r#"
+                MATCH (s:ChatSession)
+                WHERE s.usage IS NOT NULL AND NOT s.usage CONTAINS '"killed"'
+                  AND s.updated_at < datetime($cutoff)
+                WITH s LIMIT $batch_size
+                REMOVE s.usage
+                RETURN count(s) AS cnt
+                "#
+            }
+            RetentionKind::TestRuns => {
+                r#"
+                MATCH (r:TestRun) WHERE r.created_at < datetime($cutoff)
+                WITH r LIMIT $batch_size
+                DETACH DELETE r
+                RETURN count(r) AS cnt
+                "#
+            }
+            RetentionKind::RunReports => {
+                r#"
+                MATCH (rep:RunReport) WHERE rep.generated_at < datetime($cutoff)
+                WITH rep LIMIT $batch_size
+                DETACH DELETE rep
+                RETURN count(rep) AS cnt
+                "#
+            }
+        };
+
+        let mut total: u64 = 0;
+        loop {
+            let q = query(cypher)
+                .param("cutoff", cutoff.to_rfc3339())
+                .param("batch_size", BATCH_SIZE as i64);
+            let mut result = self
+                .graph
+                .execute(q)
+                .await
+                .with_context(|| format!("Failed to purge expired {:?}", kind))?;
+            let cnt = match result.next().await? {
+                Some(row) => row.get::<i64>("cnt").unwrap_or(0),
+                None => 0,
+            };
+            if cnt <= 0 {
+                break;
+            }
+            total += cnt as u64;
+        }
+        Ok(total)
+    }
+}
@@ -0,0 +1,54 @@
+//! Neo4j RunReport operations — reports of finished plan runs
+
+use super::client::Neo4jClient;
+use crate::runner::RunReport;
+use anyhow::{Context, Result};
+use neo4rs::query;
+use uuid::Uuid;
+
+impl Neo4jClient {
+    /// Store the report of a run and link it to its PlanRun. The report is
+    /// kept as JSON; status, cost and generation time are also stored as
+    /// properties for queries.
+    pub async fn save_run_report_impl(&self, report: &RunReport) -> Result<()> {
+        let q = query(
+            r#"
+            MERGE (rep:RunReport {run_id: $run_id})
+            SET rep.plan_id = $plan_id,
+                rep.status = $status,
+                rep.cost_usd = $cost_usd,
+                rep.generated_at = datetime($generated_at),
+                rep.data = $data
+            WITH rep
+            MATCH (r:PlanRun {run_id: $run_id})
+            MERGE (r)-[:HAS_REPORT]->(rep)
+            "#,
+        )
+        .param("run_id", report.run_id.to_string())
+        .param("plan_id", report.plan_id.to_string())
+        .param("status", report.status.to_string())
+        .param("cost_usd", report.cost_usd)
+        .param("generated_at", report.generated_at.to_rfc3339())
+        .param("data", serde_json::to_string(report)?);
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
+    /// Get the report of a run.
+    pub async fn get_run_report_impl(&self, run_id: Uuid) -> Result<Option<RunReport>> {
+        let q = query("MATCH (rep:RunReport {run_id: $run_id}) RETURN rep.data AS data")
+            .param("run_id", run_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        match result.next().await? {
+            Some(row) => {
+                let data: String = row.get("data")?;
+                Ok(Some(
+                    serde_json::from_str(&data).context("Failed to deserialize run report")?,
+                ))
+            }
+            None => Ok(None),
+        }
+    }
+}
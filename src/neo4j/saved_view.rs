@@ -0,0 +1,222 @@
+//! Neo4j saved view operations (per-user persisted queries)
+
+use super::client::Neo4jClient;
+use super::models::*;
+use anyhow::Result;
+use neo4rs::query;
+use uuid::Uuid;
+
+fn parse_datetime(node: &neo4rs::Node, key: &str) -> chrono::DateTime<chrono::Utc> {
+    node.get::<String>(key)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or_else(chrono::Utc::now)
+}
+
+fn parse_view_node(node: &neo4rs::Node) -> Result<SavedViewNode> {
+    let description: String = node.get("description").unwrap_or_default();
+    let target: String = node.get("target")?;
+    let filters: String = node.get("filters").unwrap_or_default();
+    Ok(SavedViewNode {
+        id: node.get::<String>("id")?.parse()?,
+        owner_id: node.get("owner_id")?,
+        name: node.get("name")?,
+        description: if description.is_empty() {
+            None
+        } else {
+            Some(description)
+        },
+        target: serde_json::from_value(serde_json::Value::String(target))?,
+        filters: serde_json::from_str(&filters).unwrap_or_default(),
+        created_at: parse_datetime(node, "created_at"),
+        updated_at: parse_datetime(node, "updated_at"),
+    })
+}
+
+fn target_str(target: SavedViewTarget) -> String {
+    match target {
+        SavedViewTarget::Tasks => "tasks",
+        SavedViewTarget::Notes => "notes",
+        SavedViewTarget::Symbols => "symbols",
+    }
+    .to_string()
+}
+
+impl Neo4jClient {
+    /// Create a saved view.
+    pub async fn create_saved_view_impl(&self, view: &SavedViewNode) -> Result<()> {
+        let q = query(
+            r#"
+            CREATE (v:SavedView {
+                id: $id,
+                owner_id: $owner_id,
+                name: $name,
+                description: $description,
+                target: $target,
+                filters: $filters,
+                created_at: datetime($created_at),
+                updated_at: datetime($updated_at)
+            })
+            "#,
+        )
+        .param("id", view.id.to_string())
+        .param("owner_id", view.owner_id.clone())
+        .param("name", view.name.clone())
+        .param("description", view.description.clone().unwrap_or_default())
+        .param("target", target_str(view.target))
+        .param("filters", serde_json::to_string(&view.filters)?)
+        .param("created_at", view.created_at.to_rfc3339())
+        .param("updated_at", view.updated_at.to_rfc3339());
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
+    /// Get a saved view by ID.
+    pub async fn get_saved_view_impl(&self, id: Uuid) -> Result<Option<SavedViewNode>> {
+        let q = query("MATCH (v:SavedView {id: $id}) RETURN v").param("id", id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        match result.next().await? {
+            Some(row) => {
+                let node: neo4rs::Node = row.get("v")?;
+                Ok(Some(parse_view_node(&node)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// List the saved views of a user by name.
+    pub async fn list_saved_views_impl(&self, owner_id: &str) -> Result<Vec<SavedViewNode>> {
+        let q = query("MATCH (v:SavedView {owner_id: $owner_id}) RETURN v ORDER BY v.name")
+            .param("owner_id", owner_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut views = Vec::new();
+        while let Some(row) = result.next().await? {
+            let node: neo4rs::Node = row.get("v")?;
+            views.push(parse_view_node(&node)?);
+        }
+        Ok(views)
+    }
+
+    /// Save the name, description, target and filters of a saved view.
+    pub async fn update_saved_view_impl(&self, view: &SavedViewNode) -> Result<()> {
+        let q = query(
+            r#"
+            MATCH (v:SavedView {id: $id})
+            SET v.name = $name,
+                v.description = $description,
+                v.target = $target,
+                v.filters = $filters,
+                v.updated_at = datetime($updated_at)
+            "#,
+        )
+        .param("id", view.id.to_string())
+        .param("name", view.name.clone())
+        .param("description", view.description.clone().unwrap_or_default())
+        .param("target", target_str(view.target))
+        .param("filters", serde_json::to_string(&view.filters)?)
+        .param("updated_at", view.updated_at.to_rfc3339());
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
+    /// Delete a saved view. Returns false if it did not exist.
+    pub async fn delete_saved_view_impl(&self, id: Uuid) -> Result<bool> {
+        let q = query(
+            r#"
+            MATCH (v:SavedView {id: $id})
+            DETACH DELETE v
+            RETURN count(v) AS deleted
+            "#,
+        )
+        .param("id", id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let deleted: i64 = match result.next().await? {
+            Some(row) => row.get("deleted").unwrap_or(0),
+            None => 0,
+        };
+        Ok(deleted > 0)
+    }
+
+    /// Functions of a project matching the symbol filters of a saved view,
+    /// most complex first. A function is untested when no test function
+    /// (named `test*` or defined in a file whose path contains "test") calls it.
+    pub async fn list_view_functions_impl(
+        &self,
+        project_id: Uuid,
+        filters: &SavedViewFilters,
+    ) -> Result<Vec<ViewFunctionRow>> {
+        let mut conditions = Vec::new();
+        if filters.visibility.is_some() {
+            conditions.push("toLower(f.visibility) = $visibility");
+        }
+        if filters.min_complexity.is_some() {
+            conditions.push("f.complexity >= $min_complexity");
+        }
+        if filters.name_contains.is_some() {
+            conditions.push("toLower(f.name) CONTAINS $name_contains");
+        }
+        if filters.untested == Some(true) {
+            conditions.push(
+                "NOT EXISTS { MATCH (t:Function)-[:CALLS]->(f) \
+                 WHERE t.name STARTS WITH 'test' OR toLower(t.file_path) CONTAINS 'test' }",
+            );
+        }
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+
+        let q = query(&format!(
+            r#"
+            MATCH (p:Project {{id: $project_id}})-[:CONTAINS]->(:File)-[:CONTAINS]->(f:Function)
+            {}
+            RETURN f.id AS id, f.name AS name, f.file_path AS file_path,
+                   f.line_start AS line_start, f.visibility AS visibility,
+                   f.complexity AS complexity
+            ORDER BY f.complexity DESC, f.id
+            "#,
+            where_clause
+        ))
+        .param("project_id", project_id.to_string())
+        .param(
+            "visibility",
+            filters
+                .visibility
+                .clone()
+                .unwrap_or_default()
+                .to_lowercase(),
+        )
+        .param("min_complexity", filters.min_complexity.unwrap_or(0) as i64)
+        .param(
+            "name_contains",
+            filters
+                .name_contains
+                .clone()
+                .unwrap_or_default()
+                .to_lowercase(),
+        );
+
+        let mut result = self.graph.execute(q).await?;
+        let mut rows = Vec::new();
+        while let Some(row) = result.next().await? {
+            rows.push(ViewFunctionRow {
+                id: row.get("id")?,
+                name: row.get("name").unwrap_or_default(),
+                file_path: row.get("file_path").unwrap_or_default(),
+                line_start: row.get::<i64>("line_start").unwrap_or(0) as u32,
+                visibility: row
+                    .get::<String>("visibility")
+                    .unwrap_or_default()
+                    .to_lowercase(),
+                complexity: row.get::<i64>("complexity").unwrap_or(0) as u32,
+            });
+        }
+        Ok(rows)
+    }
+}
@@ -0,0 +1,97 @@
+//! Neo4j search query analytics (aggregated counters per searched query)
+
+use super::client::Neo4jClient;
+use super::models::*;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use neo4rs::query;
+
+fn parse_datetime(node: &neo4rs::Node, key: &str) -> DateTime<Utc> {
+    node.get::<String>(key)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or_else(Utc::now)
+}
+
+fn parse_search_query_node(node: &neo4rs::Node) -> Result<SearchQueryStats> {
+    let project_slug: String = node.get("project_slug").unwrap_or_default();
+    let counter = |key: &str| node.get::<i64>(key).unwrap_or(0).max(0) as u64;
+    Ok(SearchQueryStats {
+        endpoint: node.get("endpoint")?,
+        query: node.get("query")?,
+        project_slug: if project_slug.is_empty() {
+            None
+        } else {
+            Some(project_slug)
+        },
+        count: counter("count"),
+        zero_result_count: counter("zero_result_count"),
+        mcp_count: counter("mcp_count"),
+        last_hits: counter("last_hits"),
+        first_seen_at: parse_datetime(node, "first_seen_at"),
+        last_seen_at: parse_datetime(node, "last_seen_at"),
+    })
+}
+
+impl Neo4jClient {
+    /// Add one search to the counters of its (endpoint, project, query) triple.
+    pub async fn record_search_query_impl(&self, event: &SearchQueryEvent) -> Result<()> {
+        let q = query(
+            r#"
+            MERGE (s:SearchQuery {endpoint: $endpoint, project_slug: $project_slug, query: $query})
+            ON CREATE SET s.count = 0,
+                          s.zero_result_count = 0,
+                          s.mcp_count = 0,
+                          s.first_seen_at = datetime($at)
+            SET s.count = s.count + 1,
+                s.zero_result_count = s.zero_result_count + $zero,
+                s.mcp_count = s.mcp_count + $mcp,
+                s.last_hits = $hits,
+                s.last_seen_at = datetime($at)
+            "#,
+        )
+        .param("endpoint", event.endpoint.clone())
+        .param(
+            "project_slug",
+            event.project_slug.clone().unwrap_or_default(),
+        )
+        .param("query", event.query.clone())
+        .param("zero", if event.hits == 0 { 1i64 } else { 0 })
+        .param("mcp", if event.from_mcp { 1i64 } else { 0 })
+        .param("hits", event.hits as i64)
+        .param("at", event.at.to_rfc3339());
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
+    /// Counters of the recorded searches, most searched first.
+    pub async fn list_search_query_stats_impl(
+        &self,
+        endpoint: Option<&str>,
+        project_slug: Option<&str>,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<SearchQueryStats>> {
+        let q = query(
+            r#"
+            MATCH (s:SearchQuery)
+            WHERE ($endpoint = '' OR s.endpoint = $endpoint)
+              AND ($project_slug = '' OR s.project_slug = $project_slug)
+              AND ($since = '' OR s.last_seen_at >= datetime($since))
+            RETURN s
+            ORDER BY s.count DESC, s.last_seen_at DESC
+            "#,
+        )
+        .param("endpoint", endpoint.unwrap_or_default().to_string())
+        .param("project_slug", project_slug.unwrap_or_default().to_string())
+        .param("since", since.map(|d| d.to_rfc3339()).unwrap_or_default());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut stats = Vec::new();
+        while let Some(row) = result.next().await? {
+            let node: neo4rs::Node = row.get("s")?;
+            stats.push(parse_search_query_node(&node)?);
+        }
+        Ok(stats)
+    }
+}
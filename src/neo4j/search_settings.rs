@@ -0,0 +1,38 @@
+//! Neo4j search settings operations (deployment-wide synonyms and stop words)
+
+use super::client::Neo4jClient;
+use crate::meilisearch::indexes::SearchSettings;
+use anyhow::{Context, Result};
+use neo4rs::query;
+
+impl Neo4jClient {
+    /// Get the search settings of the deployment.
+    pub async fn get_search_settings_impl(&self) -> Result<Option<SearchSettings>> {
+        let q = query("MATCH (s:SearchSettings {id: 'default'}) RETURN s.settings AS value");
+
+        let mut result = self.graph.execute(q).await?;
+        result
+            .next()
+            .await?
+            .and_then(|row| row.get::<String>("value").ok())
+            .filter(|s| !s.is_empty())
+            .map(|json| {
+                serde_json::from_str(&json).context("Failed to deserialize search settings")
+            })
+            .transpose()
+    }
+
+    /// Replace the search settings of the deployment.
+    pub async fn update_search_settings_impl(&self, settings: &SearchSettings) -> Result<()> {
+        let q = query(
+            r#"
+            MERGE (s:SearchSettings {id: 'default'})
+            SET s.settings = $settings, s.updated_at = datetime()
+            "#,
+        )
+        .param("settings", serde_json::to_string(settings)?);
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+//! Neo4j production error operations (SentryIssue nodes)
+
+use super::client::Neo4jClient;
+use super::models::*;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use neo4rs::query;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const ISSUE_FIELDS: &str = r#"
+    i.id AS id, i.short_id AS short_id, i.title AS title, i.culprit AS culprit,
+    i.level AS level, i.events AS events, i.users AS users,
+    toString(i.first_seen) AS first_seen, toString(i.last_seen) AS last_seen,
+    i.permalink AS permalink, i.function_ids AS function_ids,
+    i.project_id AS project_id
+"#;
+
+fn optional(row: &neo4rs::Row, key: &str) -> Option<String> {
+    row.get::<String>(key).ok().filter(|s| !s.is_empty())
+}
+
+fn datetime(row: &neo4rs::Row, key: &str) -> DateTime<Utc> {
+    optional(row, key)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or_else(Utc::now)
+}
+
+fn issue_from_row(row: &neo4rs::Row) -> Result<SentryIssueNode> {
+    let project_id: String = row.get("project_id")?;
+    Ok(SentryIssueNode {
+        project_id: project_id.parse()?,
+        id: row.get("id")?,
+        short_id: row.get("short_id").unwrap_or_default(),
+        title: row.get("title").unwrap_or_default(),
+        culprit: optional(row, "culprit"),
+        level: row.get("level").unwrap_or_default(),
+        events: row.get::<i64>("events").unwrap_or(0) as u64,
+        users: row.get::<i64>("users").unwrap_or(0) as u64,
+        first_seen: datetime(row, "first_seen"),
+        last_seen: datetime(row, "last_seen"),
+        permalink: optional(row, "permalink"),
+        function_ids: row.get::<Vec<String>>("function_ids").unwrap_or_default(),
+    })
+}
+
+fn issue_item(issue: &SentryIssueNode) -> HashMap<String, neo4rs::BoltType> {
+    let mut m = HashMap::new();
+    m.insert("id".into(), issue.id.clone().into());
+    m.insert("short_id".into(), issue.short_id.clone().into());
+    m.insert("title".into(), issue.title.clone().into());
+    m.insert(
+        "culprit".into(),
+        issue.culprit.clone().unwrap_or_default().into(),
+    );
+    m.insert("level".into(), issue.level.clone().into());
+    m.insert("events".into(), (issue.events as i64).into());
+    m.insert("users".into(), (issue.users as i64).into());
+    m.insert("first_seen".into(), issue.first_seen.to_rfc3339().into());
+    m.insert("last_seen".into(), issue.last_seen.to_rfc3339().into());
+    m.insert(
+        "permalink".into(),
+        issue.permalink.clone().unwrap_or_default().into(),
+    );
+    let function_ids: Vec<neo4rs::BoltType> = issue
+        .function_ids
+        .iter()
+        .map(|id| id.clone().into())
+        .collect();
+    m.insert(
+        "function_ids".into(),
+        neo4rs::BoltType::List(neo4rs::BoltList::from(function_ids)),
+    );
+    m
+}
+
+impl Neo4jClient {
+    /// Replace the open Sentry issues of a project and their AFFECTS edges.
+    pub async fn replace_sentry_issues_impl(
+        &self,
+        project_id: Uuid,
+        issues: &[SentryIssueNode],
+    ) -> Result<()> {
+        let ids: Vec<String> = issues.iter().map(|i| i.id.clone()).collect();
+        let q = query(
+            r#"
+            MATCH (i:SentryIssue {project_id: $project_id})
+            WHERE NOT i.id IN $ids
+            DETACH DELETE i
+            "#,
+        )
+        .param("project_id", project_id.to_string())
+        .param("ids", ids);
+        self.graph.run(q).await?;
+
+        for chunk in issues.chunks(500) {
+            let items: Vec<_> = chunk.iter().map(issue_item).collect();
+            let q = query(
+                r#"
+                MATCH (p:Project {id: $project_id})
+                UNWIND $items AS c
+                MERGE (i:SentryIssue {project_id: $project_id, id: c.id})
+                SET i.short_id = c.short_id,
+                    i.title = c.title,
+                    i.culprit = c.culprit,
+                    i.level = c.level,
+                    i.events = c.events,
+                    i.users = c.users,
+                    i.first_seen = datetime(c.first_seen),
+                    i.last_seen = datetime(c.last_seen),
+                    i.permalink = c.permalink,
+                    i.function_ids = c.function_ids
+                MERGE (p)-[:HAS_SENTRY_ISSUE]->(i)
+                WITH i, c
+                OPTIONAL MATCH (i)-[old:AFFECTS]->(:Function)
+                DELETE old
+                WITH DISTINCT i, c
+                UNWIND c.function_ids AS function_id
+                MATCH (f:Function {id: function_id})
+                MERGE (i)-[:AFFECTS]->(f)
+                "#,
+            )
+            .param("project_id", project_id.to_string())
+            .param("items", items);
+            self.graph.run(q).await?;
+        }
+        Ok(())
+    }
+
+    /// Open Sentry issues of a project, most recently seen first.
+    pub async fn list_sentry_issues_impl(&self, project_id: Uuid) -> Result<Vec<SentryIssueNode>> {
+        let q = query(&format!(
+            r#"
+            MATCH (i:SentryIssue {{project_id: $project_id}})
+            RETURN {}
+            ORDER BY i.last_seen DESC
+            "#,
+            ISSUE_FIELDS
+        ))
+        .param("project_id", project_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut issues = Vec::new();
+        while let Some(row) = result.next().await? {
+            issues.push(issue_from_row(&row)?);
+        }
+        Ok(issues)
+    }
+
+    /// Open Sentry issues affecting a function of the file, most events first.
+    pub async fn get_file_sentry_issues_impl(
+        &self,
+        file_path: &str,
+    ) -> Result<Vec<SentryIssueNode>> {
+        let q = query(&format!(
+            r#"
+            MATCH (i:SentryIssue)-[:AFFECTS]->(:Function {{file_path: $path}})
+            WITH DISTINCT i
+            RETURN {}
+            ORDER BY i.events DESC
+            "#,
+            ISSUE_FIELDS
+        ))
+        .param("path", file_path);
+
+        let mut result = self.graph.execute(q).await?;
+        let mut issues = Vec::new();
+        while let Some(row) = result.next().await? {
+            issues.push(issue_from_row(&row)?);
+        }
+        Ok(issues)
+    }
+}
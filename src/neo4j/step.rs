@@ -4,9 +4,40 @@ use super::client::{pascal_to_snake_case, Neo4jClient};
 use super::models::*;
 use crate::plan::models::UpdateStepRequest;
 use anyhow::Result;
-use neo4rs::query;
+use neo4rs::{query, Query};
 use uuid::Uuid;
 
+/// Query creating a step for a task
+pub(crate) fn create_step_query(task_id: Uuid, step: &StepNode) -> Query {
+    let now = step.created_at.to_rfc3339();
+    query(
+        r#"
+        MATCH (t:Task {id: $task_id})
+        CREATE (s:Step {
+            id: $id,
+            order: $order,
+            description: $description,
+            status: $status,
+            verification: $verification,
+            created_at: datetime($created_at),
+            updated_at: datetime($updated_at)
+        })
+        CREATE (t)-[:HAS_STEP]->(s)
+        "#,
+    )
+    .param("task_id", task_id.to_string())
+    .param("id", step.id.to_string())
+    .param("order", step.order as i64)
+    .param("description", step.description.clone())
+    .param("status", format!("{:?}", step.status))
+    .param(
+        "verification",
+        step.verification.clone().unwrap_or_default(),
+    )
+    .param("created_at", now.clone())
+    .param("updated_at", now)
+}
+
 impl Neo4jClient {
     // ========================================================================
     // Step operations
@@ -14,35 +45,7 @@ impl Neo4jClient {
 
     /// Create a step for a task
     pub async fn create_step(&self, task_id: Uuid, step: &StepNode) -> Result<()> {
-        let now = step.created_at.to_rfc3339();
-        let q = query(
-            r#"
-            MATCH (t:Task {id: $task_id})
-            CREATE (s:Step {
-                id: $id,
-                order: $order,
-                description: $description,
-                status: $status,
-                verification: $verification,
-                created_at: datetime($created_at),
-                updated_at: datetime($updated_at)
-            })
-            CREATE (t)-[:HAS_STEP]->(s)
-            "#,
-        )
-        .param("task_id", task_id.to_string())
-        .param("id", step.id.to_string())
-        .param("order", step.order as i64)
-        .param("description", step.description.clone())
-        .param("status", format!("{:?}", step.status))
-        .param(
-            "verification",
-            step.verification.clone().unwrap_or_default(),
-        )
-        .param("created_at", now.clone())
-        .param("updated_at", now);
-
-        self.graph.run(q).await?;
+        self.graph.run(create_step_query(task_id, step)).await?;
         Ok(())
     }
 
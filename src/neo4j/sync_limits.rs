@@ -0,0 +1,39 @@
+//! Neo4j sync limits of projects (file size, binary files, excluded extensions)
+
+use super::client::Neo4jClient;
+use super::models::*;
+use anyhow::{Context, Result};
+use neo4rs::query;
+use uuid::Uuid;
+
+impl Neo4jClient {
+    /// Get the sync limits of a project.
+    pub async fn get_project_sync_limits_impl(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Option<SyncLimits>> {
+        self.get_project_json_property(project_id, "sync_limits")
+            .await?
+            .map(|json| serde_json::from_str(&json).context("Failed to deserialize sync limits"))
+            .transpose()
+    }
+
+    /// Replace the sync limits of a project.
+    pub async fn update_project_sync_limits_impl(
+        &self,
+        project_id: Uuid,
+        limits: &SyncLimits,
+    ) -> Result<()> {
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $id})
+            SET p.sync_limits = $limits
+            "#,
+        )
+        .param("id", project_id.to_string())
+        .param("limits", serde_json::to_string(limits)?);
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+}
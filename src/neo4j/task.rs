@@ -2,11 +2,65 @@
 
 use super::client::{pascal_to_snake_case, Neo4jClient, WhereBuilder};
 use super::models::*;
+use super::step::create_step_query;
 use crate::plan::models::TaskDetails;
 use anyhow::Result;
-use neo4rs::query;
+use neo4rs::{query, Query};
 use uuid::Uuid;
 
+/// Query creating a task for a plan
+pub(crate) fn create_task_query(plan_id: Uuid, task: &TaskNode) -> Query {
+    let now = task.created_at.to_rfc3339();
+    query(
+        r#"
+        MATCH (p:Plan {id: $plan_id})
+        CREATE (t:Task {
+            id: $id,
+            title: $title,
+            description: $description,
+            status: $status,
+            priority: $priority,
+            tags: $tags,
+            acceptance_criteria: $acceptance_criteria,
+            affected_files: $affected_files,
+            estimated_complexity: $estimated_complexity,
+            frustration_score: 0.0,
+            created_at: datetime($created_at),
+            updated_at: datetime($updated_at)
+        })
+        CREATE (p)-[:HAS_TASK]->(t)
+        "#,
+    )
+    .param("plan_id", plan_id.to_string())
+    .param("id", task.id.to_string())
+    .param("title", task.title.clone().unwrap_or_default())
+    .param("description", task.description.clone())
+    .param("status", format!("{:?}", task.status))
+    .param("priority", task.priority.unwrap_or(0) as i64)
+    .param("tags", task.tags.clone())
+    .param("acceptance_criteria", task.acceptance_criteria.clone())
+    .param("affected_files", task.affected_files.clone())
+    .param(
+        "estimated_complexity",
+        task.estimated_complexity.map(|c| c as i64).unwrap_or(0),
+    )
+    .param("created_at", now.clone())
+    .param("updated_at", now)
+}
+
+/// Query making a task depend on another one
+pub(crate) fn add_task_dependency_query(task_id: Uuid, depends_on_id: Uuid) -> Query {
+    query(
+        r#"
+        MATCH (t:Task {id: $task_id})
+        MATCH (dep:Task {id: $depends_on_id})
+        MERGE (t)-[:DEPENDS_ON]->(dep)
+        "#,
+    )
+    .param("task_id", task_id.to_string())
+    .param("depends_on_id", depends_on_id.to_string())
+}
+
 impl Neo4jClient {
     // ========================================================================
     // Task operations
@@ -14,47 +68,28 @@ impl Neo4jClient {
 
     /// Create a task for a plan
     pub async fn create_task(&self, plan_id: Uuid, task: &TaskNode) -> Result<()> {
-        let now = task.created_at.to_rfc3339();
-        let q = query(
-            r#"
-            MATCH (p:Plan {id: $plan_id})
-            CREATE (t:Task {
-                id: $id,
-                title: $title,
-                description: $description,
-                status: $status,
-                priority: $priority,
-                tags: $tags,
-                acceptance_criteria: $acceptance_criteria,
-                affected_files: $affected_files,
-                estimated_complexity: $estimated_complexity,
-                frustration_score: 0.0,
-                created_at: datetime($created_at),
-                updated_at: datetime($updated_at)
-            })
-            CREATE (p)-[:HAS_TASK]->(t)
-            "#,
-        )
-        .param("plan_id", plan_id.to_string())
-        .param("id", task.id.to_string())
-        .param("title", task.title.clone().unwrap_or_default())
-        .param("description", task.description.clone())
-        .param("status", format!("{:?}", task.status))
-        .param("priority", task.priority.unwrap_or(0) as i64)
-        .param("tags", task.tags.clone())
-        .param("acceptance_criteria", task.acceptance_criteria.clone())
-        .param("affected_files", task.affected_files.clone())
-        .param(
-            "estimated_complexity",
-            task.estimated_complexity.map(|c| c as i64).unwrap_or(0),
-        )
-        .param("created_at", now.clone())
-        .param("updated_at", now);
-
-        self.graph.run(q).await?;
+        self.graph.run(create_task_query(plan_id, task)).await?;
         Ok(())
     }
 
+    /// Create a task with its dependencies and steps in a single transaction
+    pub async fn create_task_with_steps(
+        &self,
+        plan_id: Uuid,
+        task: &TaskNode,
+        depends_on: &[Uuid],
+        steps: &[StepNode],
+    ) -> Result<()> {
+        let mut queries = vec![create_task_query(plan_id, task)];
+        queries.extend(
+            depends_on
+                .iter()
+                .map(|dep_id| add_task_dependency_query(task.id, *dep_id)),
+        );
+        queries.extend(steps.iter().map(|step| create_step_query(task.id, step)));
+        self.run_in_txn(queries).await
+    }
+
     /// Get tasks for a plan
     pub async fn get_plan_tasks(&self, plan_id: Uuid) -> Result<Vec<TaskNode>> {
         let q = query(
@@ -129,6 +164,11 @@ impl Neo4jClient {
                 .get::<String>("prompt_cache")
                 .ok()
                 .filter(|s| !s.is_empty()),
+            board_position: node
+                .get::<String>("board_position")
+                .ok()
+                .filter(|s| !s.is_empty()),
+            version: node.get("version").unwrap_or(0),
         })
     }
 
@@ -179,11 +219,13 @@ impl Neo4jClient {
             OPTIONAL MATCH (t)-[:INFORMED_BY]->(d:Decision)
             OPTIONAL MATCH (t)-[:DEPENDS_ON]->(dep:Task)
             OPTIONAL MATCH (t)-[:MODIFIES]->(f:File)
+            OPTIONAL MATCH (t)-[:IMPLEMENTED_BY]->(c:Commit)
             RETURN t,
                    collect(DISTINCT s) AS steps,
                    collect(DISTINCT d) AS decisions,
                    collect(DISTINCT dep.id) AS depends_on,
-                   collect(DISTINCT f.path) AS files
+                   collect(DISTINCT f.path) AS files,
+                   collect(DISTINCT c) AS commits
             "#,
         )
         .param("id", task_id.to_string());
@@ -222,12 +264,21 @@ impl Neo4jClient {
 
         let modifies_files: Vec<String> = row.get("files").unwrap_or_default();
 
+        // Parse implementing commits, newest first
+        let commit_nodes: Vec<neo4rs::Node> = row.get("commits").unwrap_or_default();
+        let mut implemented_by: Vec<CommitNode> = commit_nodes
+            .iter()
+            .filter_map(|n| self.node_to_commit(n).ok())
+            .collect();
+        implemented_by.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+
         Ok(Some(TaskDetails {
             task,
             steps,
             decisions,
             depends_on,
             modifies_files,
+            implemented_by,
         }))
     }
 
@@ -300,7 +351,8 @@ impl Neo4jClient {
                 MATCH (t:Task {id: $id})
                 SET t.status = $status,
                     t.started_at = datetime($now),
-                    t.updated_at = datetime($now)
+                    t.updated_at = datetime($now),
+                    t.version = coalesce(t.version, 0) + 1
                 "#,
             ),
             TaskStatus::Completed | TaskStatus::Failed => query(
@@ -308,7 +360,8 @@ impl Neo4jClient {
                 MATCH (t:Task {id: $id})
                 SET t.status = $status,
                     t.completed_at = datetime($now),
-                    t.updated_at = datetime($now)
+                    t.updated_at = datetime($now),
+                    t.version = coalesce(t.version, 0) + 1
                 "#,
             ),
             TaskStatus::Blocked => query(
@@ -319,14 +372,16 @@ impl Neo4jClient {
                         WHEN coalesce(t.frustration_score, 0.0) + 0.2 > 1.0 THEN 1.0
                         ELSE coalesce(t.frustration_score, 0.0) + 0.2
                     END,
-                    t.updated_at = datetime($now)
+                    t.updated_at = datetime($now),
+                    t.version = coalesce(t.version, 0) + 1
                 "#,
             ),
             _ => query(
                 r#"
                 MATCH (t:Task {id: $id})
                 SET t.status = $status,
-                    t.updated_at = datetime($now)
+                    t.updated_at = datetime($now),
+                    t.version = coalesce(t.version, 0) + 1
                 "#,
             ),
         }
@@ -357,17 +412,9 @@ impl Neo4jClient {
 
     /// Add task dependency
     pub async fn add_task_dependency(&self, task_id: Uuid, depends_on_id: Uuid) -> Result<()> {
-        let q = query(
-            r#"
-            MATCH (t:Task {id: $task_id})
-            MATCH (dep:Task {id: $depends_on_id})
-            MERGE (t)-[:DEPENDS_ON]->(dep)
-            "#,
-        )
-        .param("task_id", task_id.to_string())
-        .param("depends_on_id", depends_on_id.to_string());
-
-        self.graph.run(q).await?;
+        self.graph
+            .run(add_task_dependency_query(task_id, depends_on_id))
+            .await?;
         Ok(())
     }
 
@@ -537,8 +584,9 @@ impl Neo4jClient {
             return Ok(());
         }
 
-        // Always update updated_at
+        // Always update updated_at and the version
         set_clauses.push("t.updated_at = datetime($updated_at)");
+        set_clauses.push("t.version = coalesce(t.version, 0) + 1");
 
         let cypher = format!("MATCH (t:Task {{id: $id}}) SET {}", set_clauses.join(", "));
 
@@ -578,6 +626,17 @@ impl Neo4jClient {
         Ok(())
     }
 
+    /// Set the board position of a task. `updated_at` is left alone: moving
+    /// a card on the board is not a change of the task.
+    pub async fn set_task_board_position(&self, task_id: Uuid, position: &str) -> Result<()> {
+        let q = query("MATCH (t:Task {id: $id}) SET t.board_position = $position")
+            .param("id", task_id.to_string())
+            .param("position", position);
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
     /// Update pre-enrichment fields on a task (execution_context, persona, prompt_cache).
     ///
     /// Used by the pre-enrichment pipeline to cache context and prompt data
@@ -627,8 +686,11 @@ impl Neo4jClient {
         Ok(())
     }
 
-    /// Delete a task and all its related data (steps, decisions)
+    /// Delete a task and all its related data (steps, decisions, comments) in
+    /// a single transaction
     pub async fn delete_task(&self, task_id: Uuid) -> Result<()> {
+        let mut queries = Vec::new();
+
         // Delete all steps belonging to this task
         let q = query(
             r#"
@@ -637,7 +699,7 @@ impl Neo4jClient {
             "#,
         )
         .param("id", task_id.to_string());
-        self.graph.run(q).await?;
+        queries.push(q);
 
         // Delete all decisions belonging to this task
         let q = query(
@@ -647,7 +709,17 @@ impl Neo4jClient {
             "#,
         )
         .param("id", task_id.to_string());
-        self.graph.run(q).await?;
+        queries.push(q);
+
+        // Delete the comments on this task
+        let q = query(
+            r#"
+            MATCH (t:Task {id: $id})-[:HAS_COMMENT]->(c:Comment)
+            DETACH DELETE c
+            "#,
+        )
+        .param("id", task_id.to_string());
+        queries.push(q);
 
         // Delete the task itself
         let q = query(
@@ -657,9 +729,9 @@ impl Neo4jClient {
             "#,
         )
         .param("id", task_id.to_string());
-        self.graph.run(q).await?;
+        queries.push(q);
 
-        Ok(())
+        self.run_in_txn(queries).await
     }
 
     /// Get the project that owns a task (via Plan←Task, Project←Plan chain).
@@ -775,9 +847,13 @@ impl Neo4jClient {
             Some("status") => "t.status",
             Some("created_at") => "t.created_at",
             Some("updated_at") => "t.updated_at",
+            // Board order: ranked tasks first (nulls sort last), then the default
+            Some("position") => "t.board_position, COALESCE(t.priority, 0) DESC, t.created_at",
             _ => "COALESCE(t.priority, 0) DESC, t.created_at",
         };
-        let order_dir = if sort_by.is_some() && sort_order == "asc" {
+        let order_dir = if sort_by == Some("position") {
+            "" // Ranks only make sense ascending
+        } else if sort_by.is_some() && sort_order == "asc" {
             "ASC"
         } else if sort_by.is_some() {
             "DESC"
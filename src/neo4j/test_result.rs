@@ -0,0 +1,231 @@
+//! Neo4j CI test result operations (TestRun and TestCase nodes)
+
+use super::client::Neo4jClient;
+use super::models::*;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use neo4rs::query;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+fn outcome_str(outcome: TestOutcome) -> &'static str {
+    match outcome {
+        TestOutcome::Passed => "passed",
+        TestOutcome::Failed => "failed",
+        TestOutcome::Skipped => "skipped",
+    }
+}
+
+fn parse_outcome(s: &str) -> TestOutcome {
+    match s {
+        "failed" => TestOutcome::Failed,
+        "skipped" => TestOutcome::Skipped,
+        _ => TestOutcome::Passed,
+    }
+}
+
+fn optional(row: &neo4rs::Row, key: &str) -> Option<String> {
+    row.get::<String>(key).ok().filter(|s| !s.is_empty())
+}
+
+fn datetime(row: &neo4rs::Row, key: &str) -> Option<DateTime<Utc>> {
+    optional(row, key).and_then(|s| s.parse().ok())
+}
+
+fn case_item(case: &TestCaseNode) -> HashMap<String, neo4rs::BoltType> {
+    let mut m = HashMap::new();
+    m.insert("project_id".into(), case.project_id.to_string().into());
+    m.insert("key".into(), case.key.clone().into());
+    m.insert("classname".into(), case.classname.clone().into());
+    m.insert("name".into(), case.name.clone().into());
+    m.insert(
+        "function_id".into(),
+        case.function_id.clone().unwrap_or_default().into(),
+    );
+    m.insert(
+        "file_path".into(),
+        case.file_path.clone().unwrap_or_default().into(),
+    );
+    m.insert("history".into(), case.history.clone().into());
+    m.insert("runs".into(), (case.runs as i64).into());
+    m.insert("failures".into(), (case.failures as i64).into());
+    m.insert(
+        "last_outcome".into(),
+        outcome_str(case.last_outcome).to_string().into(),
+    );
+    m.insert(
+        "last_commit".into(),
+        case.last_commit.clone().unwrap_or_default().into(),
+    );
+    m.insert(
+        "last_failure_message".into(),
+        case.last_failure_message.clone().unwrap_or_default().into(),
+    );
+    m.insert(
+        "last_failed_at".into(),
+        case.last_failed_at
+            .map(|t| t.to_rfc3339())
+            .unwrap_or_default()
+            .into(),
+    );
+    m.insert("last_run_at".into(), case.last_run_at.to_rfc3339().into());
+    m
+}
+
+impl Neo4jClient {
+    /// Record a test run, keeping the `keep` latest of the project.
+    pub async fn create_test_run_impl(&self, run: &TestRunNode, keep: usize) -> Result<()> {
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $project_id})
+            CREATE (r:TestRun {
+                id: $id,
+                project_id: $project_id,
+                commit: $commit,
+                branch: $branch,
+                total: $total,
+                passed: $passed,
+                failed: $failed,
+                skipped: $skipped,
+                duration_ms: $duration_ms,
+                created_at: datetime($created_at)
+            })
+            CREATE (p)-[:HAS_TEST_RUN]->(r)
+            WITH p
+            MATCH (p)-[:HAS_TEST_RUN]->(old:TestRun)
+            WITH old ORDER BY old.created_at DESC
+            SKIP $keep
+            DETACH DELETE old
+            "#,
+        )
+        .param("id", run.id.to_string())
+        .param("project_id", run.project_id.to_string())
+        .param("commit", run.commit.clone().unwrap_or_default())
+        .param("branch", run.branch.clone().unwrap_or_default())
+        .param("total", run.total as i64)
+        .param("passed", run.passed as i64)
+        .param("failed", run.failed as i64)
+        .param("skipped", run.skipped as i64)
+        .param("duration_ms", run.duration_ms as i64)
+        .param("created_at", run.created_at.to_rfc3339())
+        .param("keep", keep as i64);
+
+        self.graph.run(q).await?;
+        Ok(())
+    }
+
+    /// Test runs of a project, newest first.
+    pub async fn list_test_runs_impl(
+        &self,
+        project_id: Uuid,
+        limit: usize,
+    ) -> Result<Vec<TestRunNode>> {
+        let q = query(
+            r#"
+            MATCH (r:TestRun {project_id: $project_id})
+            RETURN r.id AS id, r.commit AS commit, r.branch AS branch,
+                   r.total AS total, r.passed AS passed, r.failed AS failed,
+                   r.skipped AS skipped, r.duration_ms AS duration_ms,
+                   toString(r.created_at) AS created_at
+            ORDER BY r.created_at DESC
+            LIMIT $limit
+            "#,
+        )
+        .param("project_id", project_id.to_string())
+        .param("limit", limit as i64);
+
+        let mut result = self.graph.execute(q).await?;
+        let mut runs = Vec::new();
+        while let Some(row) = result.next().await? {
+            let id: String = row.get("id")?;
+            runs.push(TestRunNode {
+                id: id.parse()?,
+                project_id,
+                commit: optional(&row, "commit"),
+                branch: optional(&row, "branch"),
+                total: row.get::<i64>("total").unwrap_or(0) as u32,
+                passed: row.get::<i64>("passed").unwrap_or(0) as u32,
+                failed: row.get::<i64>("failed").unwrap_or(0) as u32,
+                skipped: row.get::<i64>("skipped").unwrap_or(0) as u32,
+                duration_ms: row.get::<i64>("duration_ms").unwrap_or(0) as u64,
+                created_at: datetime(&row, "created_at").unwrap_or_else(Utc::now),
+            });
+        }
+        Ok(runs)
+    }
+
+    /// Pass/fail history of every test case of a project.
+    pub async fn get_test_cases_impl(&self, project_id: Uuid) -> Result<Vec<TestCaseNode>> {
+        let q = query(
+            r#"
+            MATCH (t:TestCase {project_id: $project_id})
+            RETURN t.key AS key, t.classname AS classname, t.name AS name,
+                   t.function_id AS function_id, t.file_path AS file_path,
+                   t.history AS history, t.runs AS runs, t.failures AS failures,
+                   t.last_outcome AS last_outcome, t.last_commit AS last_commit,
+                   t.last_failure_message AS last_failure_message,
+                   toString(t.last_failed_at) AS last_failed_at,
+                   toString(t.last_run_at) AS last_run_at
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut cases = Vec::new();
+        while let Some(row) = result.next().await? {
+            cases.push(TestCaseNode {
+                project_id,
+                key: row.get("key")?,
+                classname: row.get("classname").unwrap_or_default(),
+                name: row.get("name").unwrap_or_default(),
+                function_id: optional(&row, "function_id"),
+                file_path: optional(&row, "file_path"),
+                history: row.get("history").unwrap_or_default(),
+                runs: row.get::<i64>("runs").unwrap_or(0) as u64,
+                failures: row.get::<i64>("failures").unwrap_or(0) as u64,
+                last_outcome: parse_outcome(&row.get::<String>("last_outcome").unwrap_or_default()),
+                last_commit: optional(&row, "last_commit"),
+                last_failure_message: optional(&row, "last_failure_message"),
+                last_failed_at: datetime(&row, "last_failed_at"),
+                last_run_at: datetime(&row, "last_run_at").unwrap_or_else(Utc::now),
+            });
+        }
+        Ok(cases)
+    }
+
+    /// Create or replace test cases, re-linking them to their test function.
+    pub async fn upsert_test_cases_impl(&self, cases: &[TestCaseNode]) -> Result<()> {
+        for chunk in cases.chunks(500) {
+            let items: Vec<_> = chunk.iter().map(case_item).collect();
+            let q = query(
+                r#"
+                UNWIND $items AS c
+                MERGE (t:TestCase {project_id: c.project_id, key: c.key})
+                SET t.classname = c.classname,
+                    t.name = c.name,
+                    t.function_id = c.function_id,
+                    t.file_path = c.file_path,
+                    t.history = c.history,
+                    t.runs = c.runs,
+                    t.failures = c.failures,
+                    t.last_outcome = c.last_outcome,
+                    t.last_commit = c.last_commit,
+                    t.last_failure_message = c.last_failure_message,
+                    t.last_failed_at = CASE WHEN c.last_failed_at = '' THEN null
+                                            ELSE datetime(c.last_failed_at) END,
+                    t.last_run_at = datetime(c.last_run_at)
+                WITH t, c
+                OPTIONAL MATCH (t)-[old:RUNS]->(:Function)
+                DELETE old
+                WITH DISTINCT t, c
+                OPTIONAL MATCH (f:Function {id: c.function_id})
+                FOREACH (_ IN CASE WHEN f IS NULL THEN [] ELSE [1] END |
+                    MERGE (t)-[:RUNS]->(f))
+                "#,
+            )
+            .param("items", items);
+            self.graph.run(q).await?;
+        }
+        Ok(())
+    }
+}
@@ -11,6 +11,7 @@ use crate::graph::models::{
 };
 use crate::lifecycle::{LifecycleHook, LifecycleScope, UpdateLifecycleHookRequest};
 use crate::neo4j::models::*;
+use crate::neo4j::query_guard::QueryMetrics;
 use crate::notes::{
     EntityType, Note, NoteAnchor, NoteFilters, NoteImportance, NoteStatus, PropagatedNote,
 };
@@ -55,9 +56,12 @@ pub trait GraphStore: Send + Sync {
         root_path: Option<String>,
     ) -> Result<()>;
 
-    /// Update project last_synced timestamp
+    /// Update project last_synced timestamp and refresh its language breakdown
     async fn update_project_synced(&self, id: Uuid) -> Result<()>;
 
+    /// Language breakdown of a project as of its last sync, most lines first
+    async fn get_project_language_stats(&self, project_id: Uuid) -> Result<Vec<LanguageStats>>;
+
     /// Update project analytics_computed_at timestamp
     async fn update_project_analytics_timestamp(&self, id: Uuid) -> Result<()>;
 
@@ -110,6 +114,10 @@ pub trait GraphStore: Send + Sync {
     /// Get the workspace a project belongs to
     async fn get_project_workspace(&self, project_id: Uuid) -> Result<Option<WorkspaceNode>>;
 
+    /// Node counts, languages, notes, open work and analytics freshness of
+    /// the projects of a workspace
+    async fn get_workspace_stats(&self, workspace_id: Uuid) -> Result<WorkspaceStats>;
+
     /// Compute the P2P coupling matrix for all projects in a workspace.
     async fn compute_coupling_matrix(
         &self,
@@ -260,6 +268,34 @@ pub trait GraphStore: Send + Sync {
     /// Get projects that use a resource
     async fn get_resource_consumers(&self, resource_id: Uuid) -> Result<Vec<ProjectNode>>;
 
+    /// Replace all USES_RESOURCE_SYMBOL edges from a project's files with
+    /// `links`. Returns the number of edges written.
+    async fn replace_resource_symbol_links(
+        &self,
+        project_id: Uuid,
+        links: &[ResourceSymbolLink],
+    ) -> Result<usize>;
+
+    /// Get the files (across projects) that import a resource
+    async fn get_resource_symbol_links(&self, resource_id: Uuid)
+        -> Result<Vec<ResourceSymbolLink>>;
+
+    /// Replace the elements (operations, messages, …) parsed from a
+    /// resource spec. Returns the number of elements written.
+    async fn replace_resource_elements(
+        &self,
+        resource_id: Uuid,
+        elements: &[ResourceElementNode],
+    ) -> Result<usize>;
+
+    /// List the elements of a resource, optionally of one kind, sorted by
+    /// (kind, name)
+    async fn list_resource_elements(
+        &self,
+        resource_id: Uuid,
+        kind: Option<ResourceElementKind>,
+    ) -> Result<Vec<ResourceElementNode>>;
+
     // ========================================================================
     // Component operations (Topology)
     // ========================================================================
@@ -312,6 +348,12 @@ pub trait GraphStore: Send + Sync {
         workspace_id: Uuid,
     ) -> Result<Vec<(ComponentNode, Option<String>, Vec<ComponentDependency>)>>;
 
+    /// Get the (component_id, project_id) MAPS_TO_PROJECT pairs of a workspace
+    async fn list_component_project_mappings(
+        &self,
+        workspace_id: Uuid,
+    ) -> Result<Vec<(Uuid, Uuid)>>;
+
     // ========================================================================
     // File operations
     // ========================================================================
@@ -348,6 +390,34 @@ pub trait GraphStore: Send + Sync {
     /// Count files for a project (lightweight, no data transfer)
     async fn count_project_files(&self, project_id: Uuid) -> Result<i64>;
 
+    /// Content hash of the search document last indexed for each of the
+    /// files, for the files that have one.
+    async fn get_indexed_hashes(
+        &self,
+        paths: &[String],
+    ) -> Result<std::collections::HashMap<String, String>>;
+
+    /// Record the content hash of the search documents just indexed, as
+    /// (path, hash) pairs.
+    async fn set_indexed_hashes(&self, hashes: &[(String, String)]) -> Result<()>;
+
+    /// Per-file symbol counts, complexity and analytics flags for a project,
+    /// aggregated in a single query.
+    async fn get_project_file_stats(&self, project_id: Uuid) -> Result<Vec<FileStatsRow>>;
+
+    /// Functions of a project carrying a clone fingerprint.
+    async fn get_project_function_fingerprints(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<FunctionFingerprintRow>>;
+
+    /// Functions of a project whose complexity exceeds `max_complexity`.
+    async fn get_functions_above_complexity(
+        &self,
+        project_id: Uuid,
+        max_complexity: u32,
+    ) -> Result<Vec<FunctionComplexityRow>>;
+
     /// Count orphan files for a project (lightweight — avoids the full health
     /// report and its distribution fitting). Used by the real-time overview.
     async fn count_orphan_files(&self, project_id: Uuid) -> Result<i64>;
@@ -433,6 +503,14 @@ pub trait GraphStore: Send + Sync {
     /// Batch upsert functions using UNWIND for a single Neo4j transaction per call.
     async fn batch_upsert_functions(&self, functions: &[FunctionNode]) -> Result<()>;
 
+    /// Clear the clone fingerprints of the functions in `file_paths`, then
+    /// store `fingerprints` on their Function nodes.
+    async fn batch_set_function_fingerprints(
+        &self,
+        file_paths: &[String],
+        fingerprints: &[FunctionFingerprint],
+    ) -> Result<()>;
+
     /// Batch upsert structs using UNWIND.
     async fn batch_upsert_structs(&self, structs: &[StructNode]) -> Result<()>;
 
@@ -567,6 +645,45 @@ pub trait GraphStore: Send + Sync {
     /// Get import paths for a file
     async fn get_file_import_paths_list(&self, path: &str) -> Result<Vec<String>>;
 
+    /// Resolve a Function/Struct/Trait/Enum id to its file, line range and
+    /// project root
+    async fn get_symbol_location(&self, symbol_id: &str) -> Result<Option<SymbolLocationNode>>;
+
+    /// Code depending on one symbol, by graph id: callers (CALLS), functions
+    /// using it as a type (USES_TYPE) and imports naming it (IMPORTS_SYMBOL)
+    async fn find_symbol_dependents(
+        &self,
+        symbol_id: &str,
+        limit: usize,
+    ) -> Result<Vec<SymbolReferenceNode>>;
+
+    /// Functions, structs, traits and enums defined in a file, with their
+    /// graph ids and line ranges, ordered by line
+    async fn list_file_symbols(&self, path: &str) -> Result<Vec<SymbolLocationNode>>;
+
+    /// Functions, structs, traits and enums named exactly `name`, ordered by
+    /// file and line. When project_id is provided, only symbols of that
+    /// project are returned.
+    async fn find_symbols_by_name(
+        &self,
+        name: &str,
+        project_id: Option<Uuid>,
+        limit: usize,
+    ) -> Result<Vec<SymbolLocationNode>>;
+
+    /// Functions, structs, traits and enums whose name contains one of
+    /// `terms` (lowercase), shortest names first. Used to search code by name
+    /// when the search engine is unavailable.
+    async fn find_symbols_matching(
+        &self,
+        terms: &[String],
+        project_id: Option<Uuid>,
+        limit: usize,
+    ) -> Result<Vec<SymbolLocationNode>>;
+
+    /// Get every import of a project's files
+    async fn list_project_imports(&self, project_id: Uuid) -> Result<Vec<ImportNode>>;
+
     /// Find references to a symbol (function callers, struct importers, file importers).
     /// When project_id is provided, results are scoped to the same project.
     async fn find_symbol_references(
@@ -581,20 +698,24 @@ pub trait GraphStore: Send + Sync {
 
     /// Get callers chain for a function name (by name, variable depth).
     /// When project_id is provided, scopes start/end to the same project.
+    /// Paths through a CALLS edge below `min_confidence` are ignored.
     async fn get_function_callers_by_name(
         &self,
         function_name: &str,
         depth: u32,
         project_id: Option<Uuid>,
+        min_confidence: f64,
     ) -> Result<Vec<String>>;
 
     /// Get callees chain for a function name (by name, variable depth).
     /// When project_id is provided, scopes start/end to the same project.
+    /// Paths through a CALLS edge below `min_confidence` are ignored.
     async fn get_function_callees_by_name(
         &self,
         function_name: &str,
         depth: u32,
         project_id: Option<Uuid>,
+        min_confidence: f64,
     ) -> Result<Vec<String>>;
 
     /// Get direct callers with confidence scores (depth 1 only).
@@ -612,6 +733,38 @@ pub trait GraphStore: Send + Sync {
         project_id: Option<Uuid>,
     ) -> Result<Vec<(String, String, f64, String)>>;
 
+    /// References (CALLS and USES_TYPE) of a project's functions linked by
+    /// name only: all their relationships have a confidence below
+    /// `max_confidence`. Least confident first, at most `limit`.
+    async fn get_name_based_references(
+        &self,
+        project_id: Uuid,
+        max_confidence: f64,
+        limit: usize,
+    ) -> Result<Vec<CodeReferenceNode>>;
+
+    /// Id of the function (or struct, enum, trait for
+    /// [`CodeReferenceKind::Type`]) declared on `line` (1-based) of `file_path`.
+    async fn find_symbol_declared_at(
+        &self,
+        kind: CodeReferenceKind,
+        file_path: &str,
+        line: u32,
+    ) -> Result<Option<String>>;
+
+    /// Replace the relationships of `function_id` to symbols named `name` by
+    /// one to `target_id`, with the given confidence and reason. With no
+    /// target, the relationships are removed.
+    async fn resolve_code_reference(
+        &self,
+        kind: CodeReferenceKind,
+        function_id: &str,
+        name: &str,
+        target_id: Option<&str>,
+        confidence: f64,
+        reason: &str,
+    ) -> Result<()>;
+
     /// Get language statistics across all files
     async fn get_language_stats(&self) -> Result<Vec<LanguageStatsNode>>;
 
@@ -678,6 +831,9 @@ pub trait GraphStore: Send + Sync {
     /// Set the watch_enabled flag on a project.
     async fn set_watch_enabled(&self, project_id: Uuid, enabled: bool) -> Result<()>;
 
+    /// Set the archived flag on a project.
+    async fn set_project_archived(&self, project_id: Uuid, archived: bool) -> Result<()>;
+
     /// Set or clear the default note energy on a project (homeostasis).
     /// When `Some(energy)`, new notes created in this project start at this energy.
     /// When `None`, new notes start at the default energy (1.0).
@@ -749,6 +905,23 @@ pub trait GraphStore: Send + Sync {
     /// Create a new plan
     async fn create_plan(&self, plan: &PlanNode) -> Result<()>;
 
+    /// Create a plan with its constraints atomically: if any part fails,
+    /// nothing is created
+    async fn create_plan_with_constraints(
+        &self,
+        plan: &PlanNode,
+        constraints: &[ConstraintNode],
+    ) -> Result<()>;
+
+    /// Create a plan with its constraints, tasks, steps and task
+    /// dependencies atomically: if any part fails, nothing is created
+    async fn create_plan_with_tasks(
+        &self,
+        plan: &PlanNode,
+        constraints: &[ConstraintNode],
+        tasks: &[NewPlanTask],
+    ) -> Result<()>;
+
     /// Get a plan by ID
     async fn get_plan(&self, id: Uuid) -> Result<Option<PlanNode>>;
 
@@ -770,10 +943,11 @@ pub trait GraphStore: Send + Sync {
         offset: usize,
     ) -> Result<(Vec<PlanNode>, usize)>;
 
-    /// Update plan fields (title, description, priority)
+    /// Update plan fields (title, description, priority) and increment its
+    /// version
     async fn update_plan(&self, id: Uuid, updates: &UpdatePlanRequest) -> Result<()>;
 
-    /// Update plan status
+    /// Update plan status and increment its version
     async fn update_plan_status(&self, id: Uuid, status: PlanStatus) -> Result<()>;
 
     /// Link a plan to a project (creates HAS_PLAN relationship)
@@ -792,6 +966,16 @@ pub trait GraphStore: Send + Sync {
     /// Create a task for a plan
     async fn create_task(&self, plan_id: Uuid, task: &TaskNode) -> Result<()>;
 
+    /// Create a task with its dependencies and steps atomically: if any part
+    /// fails, nothing is created
+    async fn create_task_with_steps(
+        &self,
+        plan_id: Uuid,
+        task: &TaskNode,
+        depends_on: &[Uuid],
+        steps: &[StepNode],
+    ) -> Result<()>;
+
     /// Get tasks for a plan
     async fn get_plan_tasks(&self, plan_id: Uuid) -> Result<Vec<TaskNode>>;
 
@@ -804,7 +988,7 @@ pub trait GraphStore: Send + Sync {
     /// Find pending tasks in a plan that are blocked by uncompleted dependencies
     async fn find_blocked_tasks(&self, plan_id: Uuid) -> Result<Vec<(TaskNode, Vec<TaskNode>)>>;
 
-    /// Update task status
+    /// Update task status and increment its version
     async fn update_task_status(&self, task_id: Uuid, status: TaskStatus) -> Result<()>;
 
     /// Assign task to an agent
@@ -858,7 +1042,7 @@ pub trait GraphStore: Send + Sync {
     /// Get a single task by ID
     async fn get_task(&self, task_id: Uuid) -> Result<Option<TaskNode>>;
 
-    /// Update a task with new values
+    /// Update a task with new values and increment its version
     async fn update_task(&self, task_id: Uuid, updates: &UpdateTaskRequest) -> Result<()>;
 
     /// Update pre-enrichment fields on a task (execution_context, persona, prompt_cache).
@@ -870,6 +1054,9 @@ pub trait GraphStore: Send + Sync {
         prompt_cache: Option<&str>,
     ) -> Result<()>;
 
+    /// Set the board position of a task (its rank within its status column)
+    async fn set_task_board_position(&self, task_id: Uuid, position: &str) -> Result<()>;
+
     /// Delete a task and all its related data (steps, decisions)
     async fn delete_task(&self, task_id: Uuid) -> Result<()>;
 
@@ -1037,6 +1224,25 @@ pub trait GraphStore: Send + Sync {
         to: Option<&str>,
     ) -> Result<Vec<DecisionTimelineEntry>>;
 
+    /// Decisions of a project (from its tasks and its ADR files), oldest first
+    async fn list_project_decisions(&self, project_id: Uuid) -> Result<Vec<ProjectDecision>>;
+
+    /// Record a decision imported from an ADR file of a project (no task)
+    async fn create_adr_decision(
+        &self,
+        project_id: Uuid,
+        decision: &DecisionNode,
+        adr: &AdrLink,
+    ) -> Result<()>;
+
+    /// Record the ADR file documenting a decision of a project
+    async fn set_decision_adr(
+        &self,
+        project_id: Uuid,
+        decision_id: Uuid,
+        adr: &AdrLink,
+    ) -> Result<()>;
+
     // ========================================================================
     // Dependency analysis
     // ========================================================================
@@ -1095,6 +1301,18 @@ pub trait GraphStore: Send + Sync {
     /// Get commits for a task
     async fn get_task_commits(&self, task_id: Uuid) -> Result<Vec<CommitNode>>;
 
+    /// Link a commit to the task it implements (IMPLEMENTED_BY relationship),
+    /// recording the chat session that produced it
+    async fn link_commit_implements_task(
+        &self,
+        commit_hash: &str,
+        task_id: Uuid,
+        session_id: Option<Uuid>,
+    ) -> Result<()>;
+
+    /// Get the commits implementing a task (IMPLEMENTED_BY relationship)
+    async fn get_task_implementing_commits(&self, task_id: Uuid) -> Result<Vec<CommitNode>>;
+
     /// Get commits for a plan
     async fn get_plan_commits(&self, plan_id: Uuid) -> Result<Vec<CommitNode>>;
 
@@ -1257,6 +1475,9 @@ pub trait GraphStore: Send + Sync {
     /// Unlink a plan from a project milestone
     async fn unlink_plan_from_milestone(&self, plan_id: Uuid, milestone_id: Uuid) -> Result<()>;
 
+    /// Get the plans linked to a project milestone (not their tasks)
+    async fn get_milestone_plan_ids(&self, milestone_id: Uuid) -> Result<Vec<Uuid>>;
+
     /// Get milestone details with tasks
     async fn get_milestone_details(
         &self,
@@ -1362,7 +1583,8 @@ pub trait GraphStore: Send + Sync {
         sort_order: &str,
     ) -> Result<(Vec<MilestoneNode>, usize)>;
 
-    /// List projects with search and pagination
+    /// List projects with search and pagination (archived ones only if
+    /// `include_archived`)
     async fn list_projects_filtered(
         &self,
         search: Option<&str>,
@@ -1370,6 +1592,7 @@ pub trait GraphStore: Send + Sync {
         offset: usize,
         sort_by: Option<&str>,
         sort_order: &str,
+        include_archived: bool,
     ) -> Result<(Vec<ProjectNode>, usize)>;
 
     // ========================================================================
@@ -1382,7 +1605,7 @@ pub trait GraphStore: Send + Sync {
     /// Get a note by ID
     async fn get_note(&self, id: Uuid) -> Result<Option<Note>>;
 
-    /// Update a note
+    /// Update a note and increment its version
     async fn update_note(
         &self,
         id: Uuid,
@@ -1514,6 +1737,30 @@ pub trait GraphStore: Send + Sync {
     /// Get anchors for a note
     async fn get_note_anchors(&self, note_id: Uuid) -> Result<Vec<NoteAnchor>>;
 
+    /// Get the anchors of notes linked to the functions and structs of a
+    /// file, including symbol nodes left behind by an earlier sync
+    async fn get_symbol_anchors_for_file(&self, file_path: &str)
+        -> Result<Vec<(Uuid, NoteAnchor)>>;
+
+    /// Move a note's anchor to another function/struct node, recording the
+    /// symbol identity
+    async fn reanchor_note(
+        &self,
+        note_id: Uuid,
+        entity_type: &EntityType,
+        old_entity_id: &str,
+        new_entity_id: &str,
+        symbol_id: &str,
+    ) -> Result<()>;
+
+    /// Mark a note's anchor as orphaned (its symbol no longer exists)
+    async fn mark_note_anchor_orphaned(
+        &self,
+        note_id: Uuid,
+        entity_type: &EntityType,
+        entity_id: &str,
+    ) -> Result<()>;
+
     /// Store a vector embedding on a Note node.
     ///
     /// Uses `db.create.setNodeVectorProperty` to ensure the correct type
@@ -1876,6 +2123,23 @@ pub trait GraphStore: Send + Sync {
     /// Get the auto_continue flag from a chat session node (false if not set)
     async fn get_session_auto_continue(&self, id: Uuid) -> Result<bool>;
 
+    /// Store the rolling summary of a chat session and clear its
+    /// cli_session_id, so the next resume starts a fresh CLI session
+    async fn set_chat_session_memory(&self, id: Uuid, memory: &ChatSessionMemory) -> Result<()>;
+
+    /// Get the rolling summary of a chat session (None if never summarized)
+    async fn get_chat_session_memory(&self, id: Uuid) -> Result<Option<ChatSessionMemory>>;
+
+    /// Store the usage of a chat session, or clear it with `None`
+    async fn set_chat_session_usage(
+        &self,
+        id: Uuid,
+        usage: Option<&ChatSessionUsage>,
+    ) -> Result<()>;
+
+    /// Get the usage of a chat session (None if never stored or reset)
+    async fn get_chat_session_usage(&self, id: Uuid) -> Result<Option<ChatSessionUsage>>;
+
     /// Backfill title and preview for sessions that don't have them yet
     async fn backfill_chat_session_previews(&self) -> Result<usize>;
 
@@ -2158,9 +2422,21 @@ pub trait GraphStore: Send + Sync {
     async fn get_project_import_edges(&self, project_id: Uuid) -> Result<Vec<(String, String)>>;
 
     /// Get all CALLS edges between functions in a project as (caller_id, callee_id) pairs.
-    /// Scoped to the same project (no cross-project calls).
+    /// Scoped to the same project (no cross-project calls), without the edges
+    /// whose confidence is below `min_confidence`.
     /// Single bulk query — used by the graph analytics engine for extraction.
-    async fn get_project_call_edges(&self, project_id: Uuid) -> Result<Vec<(String, String)>>;
+    async fn get_project_call_edges(
+        &self,
+        project_id: Uuid,
+        min_confidence: f64,
+    ) -> Result<Vec<(String, String)>>;
+
+    /// Get every function of a project with its PageRank and Louvain community.
+    /// Paired with `get_project_call_edges` to build the call graph view.
+    async fn get_project_call_graph_functions(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<CallGraphFunctionNode>>;
 
     /// Get all EXTENDS edges between structs/classes in a project as (child_file, parent_file) pairs.
     /// Returns file-level edges for the graph analytics engine.
@@ -2775,6 +3051,12 @@ pub trait GraphStore: Send + Sync {
     /// Returns Ok(true) if the database is reachable, Ok(false) if not.
     async fn health_check(&self) -> Result<bool>;
 
+    /// Counters of the queries run so far, with the latest slow ones.
+    /// `None` for stores that do not measure their queries.
+    fn query_metrics(&self) -> Option<QueryMetrics> {
+        None
+    }
+
     // ========================================================================
     // Context Cards persistence
     // ========================================================================
@@ -3045,6 +3327,13 @@ pub trait GraphStore: Send + Sync {
     /// Get a PlanRun by its run_id.
     async fn get_plan_run(&self, run_id: Uuid) -> Result<Option<crate::runner::RunnerState>>;
 
+    /// Store the report of a finished run, linked to its PlanRun via
+    /// (:PlanRun)-[:HAS_REPORT]->(:RunReport). Replaces a previous report.
+    async fn save_run_report(&self, report: &crate::runner::RunReport) -> Result<()>;
+
+    /// Get the report of a finished run.
+    async fn get_run_report(&self, run_id: Uuid) -> Result<Option<crate::runner::RunReport>>;
+
     /// List all PlanRuns with status=Running (for crash recovery at boot).
     async fn list_active_plan_runs(&self) -> Result<Vec<crate::runner::RunnerState>>;
 
@@ -3278,6 +3567,560 @@ pub trait GraphStore: Send + Sync {
         offset: usize,
     ) -> Result<(Vec<AlertNode>, usize)>;
 
+    // ========================================================================
+    // Package & advisory operations (vulnerability audit)
+    // ========================================================================
+
+    /// Replace the DEPENDS_ON_PACKAGE edges of a project with `packages`,
+    /// merging the shared `(:Package)` nodes. Returns the number of edges written.
+    async fn replace_project_packages(
+        &self,
+        project_id: Uuid,
+        packages: &[PackageNode],
+    ) -> Result<usize>;
+
+    /// List the packages locked by a project, ordered by ecosystem, name, version.
+    async fn list_project_packages(&self, project_id: Uuid) -> Result<Vec<PackageNode>>;
+
+    /// Upsert the `(:Advisory)` nodes and replace the AFFECTS_PROJECT edges
+    /// of a project with `advisories`.
+    async fn replace_project_advisories(
+        &self,
+        project_id: Uuid,
+        advisories: &[ProjectAdvisory],
+    ) -> Result<()>;
+
+    /// List the advisories affecting a project, most severe first.
+    async fn list_project_advisories(&self, project_id: Uuid) -> Result<Vec<ProjectAdvisory>>;
+
+    // ========================================================================
+    // Document operations (Markdown documentation)
+    // ========================================================================
+
+    /// Upsert a `(:Document)` and replace its `(:Section)` nodes, linking each
+    /// section to the files and documents it references (LINKS_TO).
+    async fn upsert_document(
+        &self,
+        document: &DocumentNode,
+        sections: &[DocSectionNode],
+    ) -> Result<()>;
+
+    /// Get a document by path.
+    async fn get_document(&self, path: &str) -> Result<Option<DocumentNode>>;
+
+    /// List the documents of a project, ordered by path.
+    async fn list_project_documents(&self, project_id: Uuid) -> Result<Vec<DocumentNode>>;
+
+    /// List the sections of a document in document order.
+    async fn get_document_sections(&self, path: &str) -> Result<Vec<DocSectionNode>>;
+
+    /// Delete a document and its sections.
+    async fn delete_document(&self, path: &str) -> Result<()>;
+
+    /// Delete the documents of a project whose path is not in `valid_paths`.
+    /// Returns the deleted paths.
+    async fn delete_stale_documents(
+        &self,
+        project_id: Uuid,
+        valid_paths: &[String],
+    ) -> Result<Vec<String>>;
+
+    // ========================================================================
+    // Database schema operations (SQL tables & migrations)
+    // ========================================================================
+
+    /// Replace the `(:Table)`/`(:Column)` and `(:Migration)` nodes of a project,
+    /// linking each migration to the tables it changes (ALTERS) and foreign
+    /// key columns to their target (REFERENCES).
+    async fn replace_project_schema(
+        &self,
+        project_id: Uuid,
+        tables: &[TableNode],
+        migrations: &[MigrationNode],
+    ) -> Result<()>;
+
+    /// List the tables of a project with their columns, ordered by name.
+    async fn list_project_tables(&self, project_id: Uuid) -> Result<Vec<TableNode>>;
+
+    /// List the migrations of a project in apply order.
+    async fn list_project_migrations(&self, project_id: Uuid) -> Result<Vec<MigrationNode>>;
+
+    // ========================================================================
+    // Configuration key operations
+    // ========================================================================
+
+    /// Replace the `(:ConfigKey)` nodes of a project, linked to the config
+    /// files defining them (DEFINED_IN) and the files reading them
+    /// (READS_CONFIG).
+    async fn replace_project_config_keys(
+        &self,
+        project_id: Uuid,
+        keys: &[ConfigKeyNode],
+    ) -> Result<()>;
+
+    /// List the configuration keys of a project ordered by key, optionally
+    /// restricted to keys containing `filter` (case-insensitive).
+    async fn list_project_config_keys(
+        &self,
+        project_id: Uuid,
+        filter: Option<&str>,
+    ) -> Result<Vec<ConfigKeyNode>>;
+
+    // ========================================================================
+    // Module summary operations
+    // ========================================================================
+
+    /// Every synced file of a project with its hash and up to
+    /// `symbols_per_file` public symbols, highest PageRank first.
+    async fn get_project_module_digest(
+        &self,
+        project_id: Uuid,
+        symbols_per_file: usize,
+    ) -> Result<Vec<ModuleFileDigest>>;
+
+    /// List the cached module summaries of a project, ordered by path.
+    async fn list_module_summaries(&self, project_id: Uuid) -> Result<Vec<ModuleSummaryNode>>;
+
+    /// Create or replace module summaries, keyed by (project_id, path).
+    async fn upsert_module_summaries(&self, summaries: &[ModuleSummaryNode]) -> Result<()>;
+
+    /// Delete the module summaries of a project for the given paths.
+    async fn delete_module_summaries(&self, project_id: Uuid, paths: &[String]) -> Result<()>;
+
+    // ========================================================================
+    // Sync limit operations
+    // ========================================================================
+
+    /// Get the sync limits of a project (`None` when it uses the defaults).
+    async fn get_project_sync_limits(&self, project_id: Uuid) -> Result<Option<SyncLimits>>;
+
+    /// Replace the sync limits of a project.
+    async fn update_project_sync_limits(&self, project_id: Uuid, limits: &SyncLimits)
+        -> Result<()>;
+
+    // ========================================================================
+    // Budget operations
+    // ========================================================================
+
+    /// Get the complexity and size budgets declared for a project.
+    async fn get_project_budgets(&self, project_id: Uuid) -> Result<Option<ProjectBudgets>>;
+
+    /// Replace the budgets of a project, dropping its last check report.
+    async fn update_project_budgets(
+        &self,
+        project_id: Uuid,
+        budgets: &ProjectBudgets,
+    ) -> Result<()>;
+
+    /// Get the last budget check report of a project.
+    async fn get_budget_report(&self, project_id: Uuid) -> Result<Option<BudgetCheckReport>>;
+
+    /// Store the latest budget check report of a project.
+    async fn save_budget_report(&self, report: &BudgetCheckReport) -> Result<()>;
+
+    // ========================================================================
+    // API surface operations
+    // ========================================================================
+
+    /// Public functions, structs, traits and enums of a project, with their
+    /// signatures, by file and line.
+    async fn get_project_public_symbols(&self, project_id: Uuid) -> Result<Vec<PublicSymbolRow>>;
+
+    /// Record an API surface snapshot, keeping the `keep` latest of the project.
+    async fn create_api_surface_snapshot(
+        &self,
+        snapshot: &ApiSurfaceSnapshotNode,
+        keep: usize,
+    ) -> Result<()>;
+
+    /// API surface snapshots of a project, newest first, without their symbols.
+    async fn list_api_surface_snapshots(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<ApiSurfaceSnapshotNode>>;
+
+    /// One API surface snapshot of a project, with its symbols.
+    async fn get_api_surface_snapshot(
+        &self,
+        project_id: Uuid,
+        snapshot_id: Uuid,
+    ) -> Result<Option<ApiSurfaceSnapshotNode>>;
+
+    // ========================================================================
+    // Test coverage operations
+    // ========================================================================
+
+    /// Id, name and line range of every function of a project.
+    async fn get_project_function_spans(&self, project_id: Uuid) -> Result<Vec<FunctionSpanRow>>;
+
+    /// Clear the coverage of the files in `file_paths` and of their
+    /// functions, then store `functions` (by function id) and `files` (by
+    /// path).
+    async fn batch_set_coverage(
+        &self,
+        file_paths: &[String],
+        functions: &[CoverageUpdate],
+        files: &[CoverageUpdate],
+    ) -> Result<()>;
+
+    /// Functions of a project covered below `max_coverage_pct`, highest
+    /// PageRank first. Functions without coverage data are left out.
+    async fn get_untested_functions(
+        &self,
+        project_id: Uuid,
+        max_coverage_pct: f64,
+        limit: usize,
+    ) -> Result<Vec<UntestedFunctionRow>>;
+
+    /// Coverage of a file, with its functions covered below
+    /// `max_coverage_pct` (highest PageRank first). None without coverage
+    /// data.
+    async fn get_file_coverage(
+        &self,
+        file_path: &str,
+        max_coverage_pct: f64,
+    ) -> Result<Option<FileCoverageRow>>;
+
+    // ========================================================================
+    // Test result operations
+    // ========================================================================
+
+    /// Record an uploaded CI test run of a project, keeping its `keep` most
+    /// recent runs.
+    async fn create_test_run(&self, run: &TestRunNode, keep: usize) -> Result<()>;
+
+    /// Test runs of a project, newest first.
+    async fn list_test_runs(&self, project_id: Uuid, limit: usize) -> Result<Vec<TestRunNode>>;
+
+    /// Pass/fail history of every test case of a project.
+    async fn get_test_cases(&self, project_id: Uuid) -> Result<Vec<TestCaseNode>>;
+
+    /// Create or replace test cases (by project and key), linking each to its
+    /// test function when `function_id` is set.
+    async fn upsert_test_cases(&self, cases: &[TestCaseNode]) -> Result<()>;
+
+    // ========================================================================
+    // Production error operations
+    // ========================================================================
+
+    /// Replace the open Sentry issues of a project: issues missing from
+    /// `issues` are removed, the others created or updated and linked to
+    /// their functions.
+    async fn replace_sentry_issues(
+        &self,
+        project_id: Uuid,
+        issues: &[SentryIssueNode],
+    ) -> Result<()>;
+
+    /// Open Sentry issues of a project, most recently seen first.
+    async fn list_sentry_issues(&self, project_id: Uuid) -> Result<Vec<SentryIssueNode>>;
+
+    /// Open Sentry issues whose stack goes through a function of the file,
+    /// most events first.
+    async fn get_file_sentry_issues(&self, file_path: &str) -> Result<Vec<SentryIssueNode>>;
+
+    // ========================================================================
+    // Code ownership operations
+    // ========================================================================
+
+    /// Replace the CODEOWNERS rules of a project and the OWNED_BY edges of
+    /// its files. `file_owners` lists the owners of each owned file.
+    async fn replace_code_owners(
+        &self,
+        project_id: Uuid,
+        rules: &[CodeOwnerRule],
+        file_owners: &[(String, Vec<String>)],
+    ) -> Result<()>;
+
+    /// CODEOWNERS rules of a project, in file order.
+    async fn get_code_owner_rules(&self, project_id: Uuid) -> Result<Vec<CodeOwnerRule>>;
+
+    /// Owners of a project with the number of files they own, most files first.
+    async fn list_code_owners(&self, project_id: Uuid) -> Result<Vec<CodeOwnerNode>>;
+
+    /// Owners of a synced file, as resolved at the last CODEOWNERS ingestion.
+    async fn get_file_owners(&self, file_path: &str) -> Result<Vec<String>>;
+
+    // ========================================================================
+    // Context feedback operations
+    // ========================================================================
+
+    /// Record a vote on a context item of a chat session, replacing the
+    /// session's previous vote on the same item. Returns false when the
+    /// session does not exist.
+    async fn upsert_context_feedback(&self, feedback: &ContextFeedbackNode) -> Result<bool>;
+
+    /// Votes recorded in a chat session, most recent first.
+    async fn list_context_feedback(&self, session_id: Uuid) -> Result<Vec<ContextFeedbackNode>>;
+
+    /// Votes of all sessions on the given notes. Notes never rated are omitted.
+    async fn get_note_relevance_votes(&self, note_ids: &[Uuid]) -> Result<Vec<NoteRelevanceVotes>>;
+
+    // ========================================================================
+    // Note endorsement operations
+    // ========================================================================
+
+    /// Record a user's endorsement of a note. Returns false when the note
+    /// does not exist or the user already gave this kind of endorsement.
+    async fn add_note_endorsement(&self, endorsement: &NoteEndorsementNode) -> Result<bool>;
+
+    /// Withdraw a user's endorsement of a note. Returns false when there
+    /// was none.
+    async fn remove_note_endorsement(
+        &self,
+        note_id: Uuid,
+        user_id: &str,
+        kind: EndorsementKind,
+    ) -> Result<bool>;
+
+    /// Endorsements of a note, oldest first.
+    async fn list_note_endorsements(&self, note_id: Uuid) -> Result<Vec<NoteEndorsementNode>>;
+
+    // ========================================================================
+    // Chat undo log operations
+    // ========================================================================
+
+    /// Append a mutation to the undo log of its chat session. Returns false
+    /// when the session does not exist.
+    async fn push_undo_entry(&self, entry: &UndoEntry) -> Result<bool>;
+
+    /// Undo log of a chat session, most recent first (undone entries included).
+    async fn list_undo_entries(&self, session_id: Uuid) -> Result<Vec<UndoEntry>>;
+
+    /// Mark an undo log entry as undone.
+    async fn mark_undo_entry_undone(
+        &self,
+        entry_id: Uuid,
+        undone_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<()>;
+
+    // ========================================================================
+    // Comment operations
+    // ========================================================================
+
+    /// Attach a comment to its task, plan or note. Returns false when the
+    /// entity does not exist.
+    async fn create_comment(&self, comment: &CommentNode) -> Result<bool>;
+
+    /// Get a comment by ID.
+    async fn get_comment(&self, id: Uuid) -> Result<Option<CommentNode>>;
+
+    /// Comments of a task, plan or note (replies included), oldest first.
+    async fn list_comments(
+        &self,
+        entity_type: CommentTarget,
+        entity_id: Uuid,
+    ) -> Result<Vec<CommentNode>>;
+
+    /// Comments mentioning a handle, most recent first.
+    async fn list_comments_mentioning(
+        &self,
+        handle: &str,
+        limit: usize,
+    ) -> Result<Vec<CommentNode>>;
+
+    /// Save the content and mentions of a comment.
+    async fn update_comment(&self, comment: &CommentNode) -> Result<()>;
+
+    /// Delete a comment and its replies. Returns the number of comments
+    /// deleted (0 if it did not exist).
+    async fn delete_comment(&self, id: Uuid) -> Result<usize>;
+
+    // ========================================================================
+    // GitHub check operations
+    // ========================================================================
+
+    /// Get the GitHub check run settings of a project.
+    async fn get_project_github_checks(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Option<GitHubCheckSettings>>;
+
+    /// Replace the GitHub check run settings of a project (`None` removes them).
+    async fn update_project_github_checks(
+        &self,
+        project_id: Uuid,
+        settings: Option<&GitHubCheckSettings>,
+    ) -> Result<()>;
+
+    /// Find the project whose GitHub check settings name `repository`
+    /// (`owner/name`, case-insensitive).
+    async fn find_project_by_github_repository(
+        &self,
+        repository: &str,
+    ) -> Result<Option<ProjectNode>>;
+
+    // ========================================================================
+    // Quota operations
+    // ========================================================================
+
+    /// Get the server-wide quota settings.
+    async fn get_quota_settings(&self) -> Result<Option<QuotaSettings>>;
+
+    /// Replace the server-wide quota settings.
+    async fn update_quota_settings(&self, settings: &QuotaSettings) -> Result<()>;
+
+    /// Get the quota override of a workspace.
+    async fn get_workspace_quotas(&self, workspace_id: Uuid) -> Result<Option<QuotaLimits>>;
+
+    /// Replace the quota override of a workspace (`None` removes it).
+    async fn update_workspace_quotas(
+        &self,
+        workspace_id: Uuid,
+        limits: Option<&QuotaLimits>,
+    ) -> Result<()>;
+
+    // ========================================================================
+    // Prompt Template operations
+    // ========================================================================
+
+    /// Create a prompt template and its first version.
+    async fn create_prompt_template(&self, template: &PromptTemplateNode) -> Result<()>;
+
+    /// Get a prompt template by ID.
+    async fn get_prompt_template(&self, id: Uuid) -> Result<Option<PromptTemplateNode>>;
+
+    /// List prompt templates by name.
+    async fn list_prompt_templates(&self) -> Result<Vec<PromptTemplateNode>>;
+
+    /// Save a prompt template, recording its content as `template.version`
+    /// if that version does not exist yet.
+    async fn update_prompt_template(&self, template: &PromptTemplateNode) -> Result<()>;
+
+    /// List the versions of a prompt template, newest first.
+    async fn list_prompt_template_versions(&self, id: Uuid) -> Result<Vec<PromptTemplateVersion>>;
+
+    /// Delete a prompt template and its versions. Returns false if it did
+    /// not exist.
+    async fn delete_prompt_template(&self, id: Uuid) -> Result<bool>;
+
+    // ========================================================================
+    // Saved View operations
+    // ========================================================================
+
+    /// Create a saved view.
+    async fn create_saved_view(&self, view: &SavedViewNode) -> Result<()>;
+
+    /// Get a saved view by ID.
+    async fn get_saved_view(&self, id: Uuid) -> Result<Option<SavedViewNode>>;
+
+    /// List the saved views of a user by name.
+    async fn list_saved_views(&self, owner_id: &str) -> Result<Vec<SavedViewNode>>;
+
+    /// Save the name, description, target and filters of a saved view.
+    async fn update_saved_view(&self, view: &SavedViewNode) -> Result<()>;
+
+    /// Delete a saved view. Returns false if it did not exist.
+    async fn delete_saved_view(&self, id: Uuid) -> Result<bool>;
+
+    /// Functions of a project matching the symbol filters of a saved view,
+    /// most complex first.
+    async fn list_view_functions(
+        &self,
+        project_id: Uuid,
+        filters: &SavedViewFilters,
+    ) -> Result<Vec<ViewFunctionRow>>;
+
+    // ========================================================================
+    // Search query analytics
+    // ========================================================================
+
+    /// Add one search to the counters of its (endpoint, project, query) triple.
+    async fn record_search_query(&self, event: &SearchQueryEvent) -> Result<()>;
+
+    /// Counters of the recorded searches, optionally restricted to one
+    /// endpoint, one project and the queries searched since a date.
+    async fn list_search_query_stats(
+        &self,
+        endpoint: Option<&str>,
+        project_slug: Option<&str>,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<SearchQueryStats>>;
+
+    /// Get the deployment-wide search settings (synonyms, stop words,
+    /// ranking rules).
+    async fn get_search_settings(
+        &self,
+    ) -> Result<Option<crate::meilisearch::indexes::SearchSettings>>;
+
+    /// Replace the deployment-wide search settings.
+    async fn update_search_settings(
+        &self,
+        settings: &crate::meilisearch::indexes::SearchSettings,
+    ) -> Result<()>;
+
+    // ========================================================================
+    // Retention
+    // ========================================================================
+
+    /// Get the deployment-wide retention policy, if one was set.
+    async fn get_retention_policy(&self) -> Result<Option<RetentionPolicy>>;
+
+    /// Replace the deployment-wide retention policy.
+    async fn update_retention_policy(&self, policy: &RetentionPolicy) -> Result<()>;
+
+    /// Delete the records of a kind older than the cutoff. Returns the number
+    /// of records deleted.
+    async fn purge_expired(
+        &self,
+        kind: RetentionKind,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64>;
+
+    /// Get the report of the last retention purge.
+    async fn get_retention_report(&self) -> Result<Option<RetentionReport>>;
+
+    /// Store the report of the last retention purge.
+    async fn save_retention_report(&self, report: &RetentionReport) -> Result<()>;
+
+    // ========================================================================
+    // Usage telemetry
+    // ========================================================================
+
+    /// Add request counts to the daily usage counters.
+    async fn record_usage(&self, counts: &[UsageCount]) -> Result<()>;
+
+    /// Usage counters of the days since `since` (included), oldest first.
+    async fn list_usage(&self, since: chrono::NaiveDate) -> Result<Vec<UsageCount>>;
+
+    // ========================================================================
+    // Notification preferences
+    // ========================================================================
+
+    /// Get the notification preferences of a user, if they ever set them.
+    async fn get_notification_preferences(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<NotificationPreferences>>;
+
+    /// Replace the notification preferences of a user.
+    async fn update_notification_preferences(
+        &self,
+        user_id: Uuid,
+        email: &str,
+        preferences: &NotificationPreferences,
+    ) -> Result<()>;
+
+    /// Users who opted in to email notifications.
+    async fn list_notification_subscribers(&self) -> Result<Vec<NotificationSubscriber>>;
+
+    // ========================================================================
+    // Optimistic concurrency
+    // ========================================================================
+
+    /// Increment the `version` of a plan, task or note. With `expected`, only
+    /// a node still at that version is incremented.
+    async fn bump_version(
+        &self,
+        node: VersionedNode,
+        id: Uuid,
+        expected: Option<i64>,
+    ) -> Result<VersionBump>;
+
+    /// Undo a bump to `bumped` whose update failed: the version goes back to
+    /// `bumped - 1` unless it moved on since. Returns whether it did.
+    async fn restore_version(&self, node: VersionedNode, id: Uuid, bumped: i64) -> Result<bool>;
+
     // ========================================================================
     // EventTrigger operations
     // ========================================================================
@@ -0,0 +1,107 @@
+//! Neo4j chat undo log operations (UndoEntry nodes)
+
+use super::client::Neo4jClient;
+use super::models::*;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use neo4rs::query;
+use uuid::Uuid;
+
+impl Neo4jClient {
+    /// Append a mutation to the undo log of its chat session.
+    pub async fn push_undo_entry_impl(&self, entry: &UndoEntry) -> Result<bool> {
+        let q = query(
+            r#"
+            MATCH (s:ChatSession {id: $session_id})
+            CREATE (s)-[:HAS_UNDO_ENTRY]->(u:UndoEntry {
+                id: $id,
+                session_id: $session_id,
+                action: $action,
+                entity_type: $entity_type,
+                entity_id: $entity_id,
+                summary: $summary,
+                inverse: $inverse,
+                created_at: datetime($created_at)
+            })
+            RETURN count(u) AS cnt
+            "#,
+        )
+        .param("id", entry.id.to_string())
+        .param("session_id", entry.session_id.to_string())
+        .param("action", entry.action.clone())
+        .param("entity_type", entry.entity_type.clone())
+        .param("entity_id", entry.entity_id.to_string())
+        .param("summary", entry.summary.clone())
+        .param("inverse", entry.inverse.to_string())
+        .param("created_at", entry.created_at.to_rfc3339());
+
+        let mut result = self.graph.execute(q).await?;
+        let count = match result.next().await? {
+            Some(row) => row.get::<i64>("cnt").unwrap_or(0),
+            None => 0,
+        };
+        Ok(count > 0)
+    }
+
+    /// Undo log of a chat session, most recent first.
+    pub async fn list_undo_entries_impl(&self, session_id: Uuid) -> Result<Vec<UndoEntry>> {
+        let q = query(
+            r#"
+            MATCH (:ChatSession {id: $session_id})-[:HAS_UNDO_ENTRY]->(u:UndoEntry)
+            RETURN u.id AS id, u.action AS action, u.entity_type AS entity_type,
+                   u.entity_id AS entity_id, u.summary AS summary, u.inverse AS inverse,
+                   toString(u.created_at) AS created_at,
+                   toString(u.undone_at) AS undone_at
+            ORDER BY u.created_at DESC
+            "#,
+        )
+        .param("session_id", session_id.to_string());
+
+        let parse_date = |s: String| s.parse::<DateTime<Utc>>().ok();
+        let mut result = self.graph.execute(q).await?;
+        let mut entries = Vec::new();
+        while let Some(row) = result.next().await? {
+            let (Ok(id), Ok(entity_id)) = (
+                row.get::<String>("id")?.parse::<Uuid>(),
+                row.get::<String>("entity_id")?.parse::<Uuid>(),
+            ) else {
+                continue;
+            };
+            let inverse: String = row.get("inverse")?;
+            entries.push(UndoEntry {
+                id,
+                session_id,
+                action: row.get("action")?,
+                entity_type: row.get("entity_type")?,
+                entity_id,
+                summary: row.get("summary").unwrap_or_default(),
+                inverse: serde_json::from_str(&inverse)?,
+                created_at: row
+                    .get::<String>("created_at")
+                    .ok()
+                    .and_then(parse_date)
+                    .unwrap_or_else(Utc::now),
+                undone_at: row.get::<String>("undone_at").ok().and_then(parse_date),
+            });
+        }
+        Ok(entries)
+    }
+
+    /// Mark an undo log entry as undone.
+    pub async fn mark_undo_entry_undone_impl(
+        &self,
+        entry_id: Uuid,
+        undone_at: DateTime<Utc>,
+    ) -> Result<()> {
+        let q = query(
+            r#"
+            MATCH (u:UndoEntry {id: $id})
+            SET u.undone_at = datetime($undone_at)
+            "#,
+        )
+        .param("id", entry_id.to_string())
+        .param("undone_at", undone_at.to_rfc3339());
+        self.graph.run(q).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+//! Neo4j usage telemetry operations (daily route counters)
+
+use super::batch::{run_unwind_in_chunks, BoltMap};
+use super::client::Neo4jClient;
+use super::models::UsageCount;
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use neo4rs::query;
+
+impl Neo4jClient {
+    /// Add counts to the `UsageCounter` of their (day, route, client).
+    pub async fn record_usage_impl(&self, counts: &[UsageCount]) -> Result<()> {
+        if counts.is_empty() {
+            return Ok(());
+        }
+
+        let items: Vec<BoltMap> = counts
+            .iter()
+            .map(|c| {
+                let mut m = BoltMap::new();
+                m.insert("day".into(), c.day.to_string().into());
+                m.insert("route".into(), c.route.clone().into());
+                m.insert("client".into(), c.client.clone().into());
+                m.insert("count".into(), (c.count as i64).into());
+                m
+            })
+            .collect();
+        run_unwind_in_chunks(
+            &self.graph,
+            items,
+            r#"
+            UNWIND $items AS item
+            MERGE (u:UsageCounter {day: item.day, route: item.route, client: item.client})
+            ON CREATE SET u.count = 0
+            SET u.count = u.count + item.count
+            "#,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Usage counters of the days since `since` (included), oldest first.
+    pub async fn list_usage_impl(&self, since: NaiveDate) -> Result<Vec<UsageCount>> {
+        let q = query(
+            r#"
+            MATCH (u:UsageCounter)
+            WHERE u.day >= $since
+            RETURN u.day AS day, u.route AS route, u.client AS client, u.count AS count
+            ORDER BY day, route, client
+            "#,
+        )
+        .param("since", since.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut counts = Vec::new();
+        while let Some(row) = result.next().await? {
+            let day: String = row.get("day")?;
+            counts.push(UsageCount {
+                day: day
+                    .parse()
+                    .with_context(|| format!("Invalid usage counter day '{}'", day))?,
+                route: row.get("route")?,
+                client: row.get("client")?,
+                count: row.get::<i64>("count").unwrap_or(0).max(0) as u64,
+            });
+        }
+        Ok(counts)
+    }
+}
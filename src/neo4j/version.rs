@@ -0,0 +1,77 @@
+//! Neo4j optimistic concurrency (version counters of plans, tasks and notes)
+
+use super::client::Neo4jClient;
+use super::models::*;
+use anyhow::Result;
+use neo4rs::query;
+use uuid::Uuid;
+
+impl Neo4jClient {
+    /// Increment the version of a node, if it is still at `expected`.
+    ///
+    /// Writing the lock property first takes the node's write lock, so
+    /// concurrent bumps of the same node are serialized and the version they
+    /// read is the one they increment.
+    pub async fn bump_version_impl(
+        &self,
+        node: VersionedNode,
+        id: Uuid,
+        expected: Option<i64>,
+    ) -> Result<VersionBump> {
+        let cypher = format!(
+            r#"
+            MATCH (n:{} {{id: $id}})
+            SET n._version_lock = true
+            WITH n, coalesce(n.version, 0) AS current
+            SET n.version = CASE WHEN $expected < 0 OR current = $expected
+                                 THEN current + 1 ELSE current END
+            REMOVE n._version_lock
+            RETURN current, n.version AS version
+            "#,
+            node.label()
+        );
+        let q = query(&cypher)
+            .param("id", id.to_string())
+            .param("expected", expected.unwrap_or(-1));
+
+        let mut result = self.graph.execute(q).await?;
+        let Some(row) = result.next().await? else {
+            return Ok(VersionBump::NotFound);
+        };
+        let current: i64 = row.get("current")?;
+        let version: i64 = row.get("version")?;
+        Ok(if version == current {
+            VersionBump::Conflict(current)
+        } else {
+            VersionBump::Bumped(version)
+        })
+    }
+
+    /// Set the version of a node back to `bumped - 1`, if it is still `bumped`
+    pub async fn restore_version_impl(
+        &self,
+        node: VersionedNode,
+        id: Uuid,
+        bumped: i64,
+    ) -> Result<bool> {
+        let cypher = format!(
+            r#"
+            MATCH (n:{} {{id: $id}})
+            WHERE n.version = $bumped
+            SET n.version = $bumped - 1
+            RETURN count(n) AS restored
+            "#,
+            node.label()
+        );
+        let q = query(&cypher)
+            .param("id", id.to_string())
+            .param("bumped", bumped);
+
+        let mut result = self.graph.execute(q).await?;
+        let restored: i64 = match result.next().await? {
+            Some(row) => row.get("restored")?,
+            None => 0,
+        };
+        Ok(restored > 0)
+    }
+}
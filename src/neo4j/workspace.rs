@@ -1120,7 +1120,8 @@ impl Neo4jClient {
         let q = query(
             r#"
             MATCH (r:Resource {id: $id})
-            DETACH DELETE r
+            OPTIONAL MATCH (r)-[:DEFINES]->(e:ResourceElement)
+            DETACH DELETE e, r
             "#,
         )
         .param("id", id.to_string());
@@ -1211,6 +1212,197 @@ impl Neo4jClient {
         Ok(projects)
     }
 
+    /// Replace all USES_RESOURCE_SYMBOL edges from a project's files
+    pub async fn replace_resource_symbol_links(
+        &self,
+        project_id: Uuid,
+        links: &[ResourceSymbolLink],
+    ) -> Result<usize> {
+        let q = query(
+            r#"
+            MATCH (p:Project {id: $project_id})-[:CONTAINS]->(:File)-[u:USES_RESOURCE_SYMBOL]->(:Resource)
+            DELETE u
+            "#,
+        )
+        .param("project_id", project_id.to_string());
+        self.graph.run(q).await?;
+
+        if links.is_empty() {
+            return Ok(0);
+        }
+
+        let items: Vec<std::collections::HashMap<String, neo4rs::BoltType>> = links
+            .iter()
+            .map(|link| {
+                let mut m = std::collections::HashMap::new();
+                m.insert("resource_id".into(), link.resource_id.to_string().into());
+                m.insert("file_path".into(), link.file_path.clone().into());
+                m.insert("import_path".into(), link.import_path.clone().into());
+                m.insert("symbol".into(), link.symbol.clone().into());
+                m.insert("line".into(), (link.line as i64).into());
+                m
+            })
+            .collect();
+
+        let q = query(
+            r#"
+            UNWIND $items AS link
+            MATCH (f:File {path: link.file_path})
+            MATCH (r:Resource {id: link.resource_id})
+            MERGE (f)-[u:USES_RESOURCE_SYMBOL {import_path: link.import_path}]->(r)
+            SET u.symbol = link.symbol, u.line = link.line, u.project_id = $project_id
+            RETURN count(u) AS created
+            "#,
+        )
+        .param("items", items)
+        .param("project_id", project_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let created = match result.next().await? {
+            Some(row) => row.get::<i64>("created").unwrap_or(0) as usize,
+            None => 0,
+        };
+        Ok(created)
+    }
+
+    /// Get the files (across projects) that import a resource
+    pub async fn get_resource_symbol_links(
+        &self,
+        resource_id: Uuid,
+    ) -> Result<Vec<ResourceSymbolLink>> {
+        let q = query(
+            r#"
+            MATCH (f:File)-[u:USES_RESOURCE_SYMBOL]->(r:Resource {id: $resource_id})
+            RETURN f.path AS file_path, u.import_path AS import_path, u.symbol AS symbol,
+                   u.line AS line, u.project_id AS project_id
+            ORDER BY f.path, u.line
+            "#,
+        )
+        .param("resource_id", resource_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut links = Vec::new();
+
+        while let Some(row) = result.next().await? {
+            let project_id: String = row.get("project_id").unwrap_or_default();
+            let Ok(project_id) = project_id.parse::<Uuid>() else {
+                continue;
+            };
+            links.push(ResourceSymbolLink {
+                resource_id,
+                project_id,
+                file_path: row.get("file_path")?,
+                import_path: row.get("import_path").unwrap_or_default(),
+                symbol: row.get("symbol").unwrap_or_default(),
+                line: row.get::<i64>("line").unwrap_or(0) as u32,
+            });
+        }
+
+        Ok(links)
+    }
+
+    /// Replace the elements parsed from a resource spec
+    pub async fn replace_resource_elements(
+        &self,
+        resource_id: Uuid,
+        elements: &[ResourceElementNode],
+    ) -> Result<usize> {
+        let q = query(
+            r#"
+            MATCH (e:ResourceElement {resource_id: $resource_id})
+            DETACH DELETE e
+            "#,
+        )
+        .param("resource_id", resource_id.to_string());
+        self.graph.run(q).await?;
+
+        if elements.is_empty() {
+            return Ok(0);
+        }
+
+        let items: Vec<std::collections::HashMap<String, neo4rs::BoltType>> = elements
+            .iter()
+            .map(|e| {
+                let mut m = std::collections::HashMap::new();
+                m.insert("kind".into(), e.kind.to_string().into());
+                m.insert("name".into(), e.name.clone().into());
+                m.insert(
+                    "summary".into(),
+                    e.summary.clone().unwrap_or_default().into(),
+                );
+                m.insert("signature".into(), e.signature.clone().into());
+                m.insert("line".into(), e.line.map(i64::from).unwrap_or(0).into());
+                m
+            })
+            .collect();
+
+        let q = query(
+            r#"
+            MATCH (r:Resource {id: $resource_id})
+            UNWIND $items AS item
+            CREATE (r)-[:DEFINES]->(e:ResourceElement {
+                resource_id: $resource_id,
+                kind: item.kind,
+                name: item.name,
+                summary: item.summary,
+                signature: item.signature,
+                line: item.line
+            })
+            RETURN count(e) AS created
+            "#,
+        )
+        .param("resource_id", resource_id.to_string())
+        .param("items", items);
+
+        let mut result = self.graph.execute(q).await?;
+        let created = match result.next().await? {
+            Some(row) => row.get::<i64>("created").unwrap_or(0) as usize,
+            None => 0,
+        };
+        Ok(created)
+    }
+
+    /// List the elements of a resource, optionally of one kind
+    pub async fn list_resource_elements(
+        &self,
+        resource_id: Uuid,
+        kind: Option<ResourceElementKind>,
+    ) -> Result<Vec<ResourceElementNode>> {
+        let q = query(
+            r#"
+            MATCH (e:ResourceElement {resource_id: $resource_id})
+            WHERE $kind = '' OR e.kind = $kind
+            RETURN e
+            "#,
+        )
+        .param("resource_id", resource_id.to_string())
+        .param("kind", kind.map(|k| k.to_string()).unwrap_or_default());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut elements = Vec::new();
+
+        while let Some(row) = result.next().await? {
+            let node: neo4rs::Node = row.get("e")?;
+            let kind_str: String = node.get("kind")?;
+            let Ok(kind) = kind_str.parse::<ResourceElementKind>() else {
+                continue;
+            };
+            let summary: String = node.get("summary").unwrap_or_default();
+            let line: i64 = node.get("line").unwrap_or(0);
+            elements.push(ResourceElementNode {
+                resource_id,
+                kind,
+                name: node.get("name")?,
+                summary: (!summary.is_empty()).then_some(summary),
+                signature: node.get("signature").unwrap_or_default(),
+                line: (line > 0).then_some(line as u32),
+            });
+        }
+
+        elements.sort_by(|a, b| (a.kind, &a.name).cmp(&(b.kind, &b.name)));
+        Ok(elements)
+    }
+
     /// Helper to convert Neo4j node to ResourceNode
     fn node_to_resource(&self, node: &neo4rs::Node) -> Result<ResourceNode> {
         let type_str: String = node
@@ -1530,6 +1722,34 @@ impl Neo4jClient {
         Ok(topology)
     }
 
+    /// Get the (component_id, project_id) MAPS_TO_PROJECT pairs of a workspace
+    pub async fn list_component_project_mappings(
+        &self,
+        workspace_id: Uuid,
+    ) -> Result<Vec<(Uuid, Uuid)>> {
+        let q = query(
+            r#"
+            MATCH (w:Workspace {id: $workspace_id})-[:HAS_COMPONENT]->(c:Component)
+                  -[:MAPS_TO_PROJECT]->(p:Project)
+            RETURN c.id AS component_id, p.id AS project_id
+            "#,
+        )
+        .param("workspace_id", workspace_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let mut mappings = Vec::new();
+
+        while let Some(row) = result.next().await? {
+            let component_id: String = row.get("component_id")?;
+            let project_id: String = row.get("project_id")?;
+            if let (Ok(c), Ok(p)) = (component_id.parse(), project_id.parse()) {
+                mappings.push((c, p));
+            }
+        }
+
+        Ok(mappings)
+    }
+
     /// Helper to convert Neo4j node to ComponentNode
     fn node_to_component(&self, node: &neo4rs::Node) -> Result<ComponentNode> {
         let type_str: String = node
@@ -1757,4 +1977,106 @@ impl Neo4jClient {
             project_count,
         })
     }
+
+    /// Aggregate the statistics of the projects of a workspace in one query.
+    pub async fn get_workspace_stats(&self, workspace_id: Uuid) -> Result<WorkspaceStats> {
+        let q = query(
+            r#"
+            OPTIONAL MATCH (p:Project)-[:BELONGS_TO_WORKSPACE]->(:Workspace {id: $workspace_id})
+            WITH collect(p) AS projects
+            CALL {
+                WITH projects
+                UNWIND projects AS p
+                MATCH (p)-[:CONTAINS]->(f:File)
+                OPTIONAL MATCH (f)-[:CONTAINS]->(s)
+                WHERE s:Function OR s:Struct OR s:Trait OR s:Enum
+                WITH f,
+                     sum(CASE WHEN s:Function THEN 1 ELSE 0 END) AS functions,
+                     sum(CASE WHEN s:Struct THEN 1 ELSE 0 END) AS structs,
+                     sum(CASE WHEN s:Trait THEN 1 ELSE 0 END) AS traits,
+                     sum(CASE WHEN s:Enum THEN 1 ELSE 0 END) AS enums,
+                     coalesce(max(s.line_end), 0) AS lines
+                RETURN count(f) AS files, sum(functions) AS functions,
+                       sum(structs) AS structs, sum(traits) AS traits,
+                       sum(enums) AS enums, sum(lines) AS lines_indexed,
+                       sum(CASE WHEN f.analytics_updated_at < f.last_parsed THEN 1 ELSE 0 END)
+                           AS analytics_stale_files
+            }
+            CALL {
+                WITH projects
+                UNWIND projects AS p
+                MATCH (p)-[:CONTAINS]->(f:File)
+                WITH coalesce(f.language, 'unknown') AS language, count(f) AS files
+                RETURN collect(language) AS languages, collect(files) AS language_files
+            }
+            CALL {
+                WITH projects
+                UNWIND projects AS p
+                MATCH (n:Note {project_id: p.id})
+                WITH n.note_type AS note_type, count(n) AS notes
+                RETURN collect(note_type) AS note_types, collect(notes) AS note_counts
+            }
+            CALL {
+                WITH projects
+                UNWIND projects AS p
+                MATCH (p)-[:HAS_PLAN]->(plan:Plan)
+                WHERE NOT plan.status IN ['Completed', 'Cancelled']
+                RETURN count(plan) AS open_plans
+            }
+            CALL {
+                WITH projects
+                UNWIND projects AS p
+                MATCH (p)-[:HAS_PLAN]->(:Plan)-[:HAS_TASK]->(t:Task)
+                WHERE NOT t.status IN ['Completed', 'Failed']
+                RETURN count(t) AS open_tasks
+            }
+            RETURN size(projects) AS project_count,
+                   files, functions, structs, traits, enums, lines_indexed,
+                   analytics_stale_files, languages, language_files,
+                   note_types, note_counts, open_plans, open_tasks,
+                   size([p IN projects WHERE p.analytics_computed_at IS NULL])
+                       AS projects_without_analytics,
+                   toString(reduce(oldest = null, p IN projects |
+                       CASE WHEN oldest IS NULL OR p.analytics_computed_at < oldest
+                            THEN p.analytics_computed_at ELSE oldest END))
+                       AS analytics_oldest_at,
+                   toString(reduce(newest = null, p IN projects |
+                       CASE WHEN newest IS NULL OR p.analytics_computed_at > newest
+                            THEN p.analytics_computed_at ELSE newest END))
+                       AS analytics_newest_at
+            "#,
+        )
+        .param("workspace_id", workspace_id.to_string());
+
+        let mut result = self.graph.execute(q).await?;
+        let Some(row) = result.next().await? else {
+            return Ok(WorkspaceStats::default());
+        };
+        let count = |key: &str| row.get::<i64>(key).unwrap_or(0).max(0) as u64;
+        let counts_by = |keys: &str, counts: &str| -> std::collections::BTreeMap<String, u64> {
+            let keys: Vec<String> = row.get(keys).unwrap_or_default();
+            let counts: Vec<i64> = row.get(counts).unwrap_or_default();
+            keys.into_iter()
+                .zip(counts.into_iter().map(|c| c.max(0) as u64))
+                .collect()
+        };
+        let datetime = |key: &str| row.get::<String>(key).ok().and_then(|s| s.parse().ok());
+        Ok(WorkspaceStats {
+            project_count: count("project_count"),
+            files: count("files"),
+            functions: count("functions"),
+            structs: count("structs"),
+            traits: count("traits"),
+            enums: count("enums"),
+            languages: counts_by("languages", "language_files"),
+            lines_indexed: count("lines_indexed"),
+            notes_by_type: counts_by("note_types", "note_counts"),
+            open_plans: count("open_plans"),
+            open_tasks: count("open_tasks"),
+            projects_without_analytics: count("projects_without_analytics"),
+            analytics_oldest_at: datetime("analytics_oldest_at"),
+            analytics_newest_at: datetime("analytics_newest_at"),
+            analytics_stale_files: count("analytics_stale_files"),
+        })
+    }
 }
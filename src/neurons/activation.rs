@@ -5,6 +5,9 @@
 //! 2. **Spreading**: propagate activation through SYNAPSE edges (weighted, energy-gated)
 //! 3. **Ranking**: merge direct + propagated activations, deduplicate, sort by score
 //!
+//! Before ranking, note scores are scaled by the relevance weight learned from
+//! chat context feedback ([`crate::feedback::relevance`]).
+//!
 //! This is an **independent** retrieval mechanism that coexists with the existing
 //! PageRank-weighted propagation in `get_context_notes`. Both systems run in
 //! parallel during Phase 3 (dual-run comparison).
@@ -18,6 +21,7 @@ use tracing::debug;
 use uuid::Uuid;
 
 use crate::embeddings::EmbeddingProvider;
+use crate::feedback::relevance::RelevanceWeights;
 use crate::graph::models::AnalysisProfile;
 use crate::neo4j::GraphStore;
 use crate::notes::{Note, NoteImportance, NoteType};
@@ -420,6 +424,19 @@ impl SpreadingActivationEngine {
             }
         }
 
+        // Phase 2.6: Learned relevance — scale notes by the weight earned from
+        // chat context feedback, dropping the ones voted down repeatedly.
+        let note_ids: Vec<Uuid> = activations
+            .iter()
+            .filter(|(_, (_, _, _, entity_type))| entity_type == "note")
+            .map(|(id, _)| *id)
+            .collect();
+        let weights = RelevanceWeights::load(self.graph_store.as_ref(), &note_ids).await;
+        activations.retain(|id, _| !weights.is_suppressed(*id));
+        for (id, entry) in activations.iter_mut() {
+            entry.0 *= weights.weight(*id);
+        }
+
         // Phase 3: Ranking with slot reservation
         //
         // Problem: direct matches score ~0.91 while propagated notes score
@@ -741,6 +758,53 @@ mod tests {
         assert!(results[0].activation_score > 0.0);
     }
 
+    #[tokio::test]
+    async fn test_notes_voted_irrelevant_are_dropped() {
+        let mock = Arc::new(MockGraphStore::new());
+        let store = gs(&mock);
+        let project_id = Uuid::new_v4();
+
+        let note = note_with_energy(Uuid::new_v4(), Some(project_id), "release process", 1.0);
+        store.create_note(&note).await.unwrap();
+        let embedding = mock_embedding_provider()
+            .embed_text("release process")
+            .await
+            .unwrap();
+        store
+            .set_note_embedding(note.id, &embedding, "mock")
+            .await
+            .unwrap();
+
+        let engine = SpreadingActivationEngine::new(store.clone(), mock_embedding_provider());
+        let config = SpreadingActivationConfig::default();
+        let before = engine
+            .activate("release process", Some(project_id), &config)
+            .await
+            .unwrap();
+        assert_eq!(before.len(), 1);
+
+        for _ in 0..3 {
+            let session = crate::test_helpers::test_chat_session(None);
+            store.create_chat_session(&session).await.unwrap();
+            store
+                .upsert_context_feedback(&crate::neo4j::models::ContextFeedbackNode {
+                    session_id: session.id,
+                    item_type: crate::neo4j::models::ContextItemType::Note,
+                    item_id: note.id.to_string(),
+                    helpful: false,
+                    comment: None,
+                    created_at: chrono::Utc::now(),
+                })
+                .await
+                .unwrap();
+        }
+        let after = engine
+            .activate("release process", Some(project_id), &config)
+            .await
+            .unwrap();
+        assert!(after.is_empty());
+    }
+
     #[tokio::test]
     async fn test_spreading_through_synapses() {
         let mock = Arc::new(MockGraphStore::new());
@@ -0,0 +1,118 @@
+//! Note endorsements — per-user acks and upvotes on notes.
+//!
+//! Humans endorse the notes they rely on. Each new endorsement renews the
+//! note's energy, which keeps it in spreading activation and context
+//! retrieval, and a note endorsed by enough distinct users is promoted to
+//! high importance. Withdrawing an endorsement never demotes a note: its
+//! importance may have been set by hand since.
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::models::NoteImportance;
+use crate::neo4j::models::{EndorsementKind, NoteEndorsementNode};
+
+/// Energy a note gains from each new endorsement (a confirmation gives 0.3)
+pub const ENDORSEMENT_ENERGY_BOOST: f64 = 0.2;
+/// Distinct endorsing users that make a note at least high importance
+pub const ENDORSERS_FOR_HIGH: usize = 3;
+
+/// Importance of a note endorsed by `endorsers` distinct users: any
+/// endorsement lifts a low note to medium, and `ENDORSERS_FOR_HIGH` lift it
+/// to high. Critical is only ever set by hand.
+pub fn endorsed_importance(current: NoteImportance, endorsers: usize) -> NoteImportance {
+    let earned = match endorsers {
+        0 => return current,
+        n if n >= ENDORSERS_FOR_HIGH => NoteImportance::High,
+        _ => NoteImportance::Medium,
+    };
+    if earned.weight() > current.weight() {
+        earned
+    } else {
+        current
+    }
+}
+
+/// Endorsements of a note, as returned by the API.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EndorsementSummary {
+    pub note_id: Uuid,
+    pub acks: usize,
+    pub upvotes: usize,
+    /// Distinct users who endorsed the note either way
+    pub endorsers: usize,
+    /// Importance of the note after the endorsements
+    pub importance: NoteImportance,
+    pub endorsements: Vec<NoteEndorsementNode>,
+}
+
+impl EndorsementSummary {
+    pub fn new(
+        note_id: Uuid,
+        importance: NoteImportance,
+        endorsements: Vec<NoteEndorsementNode>,
+    ) -> Self {
+        let count = |kind: EndorsementKind| endorsements.iter().filter(|e| e.kind == kind).count();
+        Self {
+            note_id,
+            acks: count(EndorsementKind::Ack),
+            upvotes: count(EndorsementKind::Upvote),
+            endorsers: distinct_endorsers(&endorsements),
+            importance,
+            endorsements,
+        }
+    }
+}
+
+/// Number of distinct users behind a set of endorsements
+pub fn distinct_endorsers(endorsements: &[NoteEndorsementNode]) -> usize {
+    let mut users: Vec<&str> = endorsements.iter().map(|e| e.user_id.as_str()).collect();
+    users.sort_unstable();
+    users.dedup();
+    users.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn endorsement(user_id: &str, kind: EndorsementKind) -> NoteEndorsementNode {
+        NoteEndorsementNode {
+            note_id: Uuid::nil(),
+            kind,
+            user_id: user_id.to_string(),
+            user_name: user_id.to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_endorsed_importance() {
+        use NoteImportance::*;
+        assert_eq!(endorsed_importance(Low, 0), Low);
+        assert_eq!(endorsed_importance(Low, 1), Medium);
+        assert_eq!(endorsed_importance(Medium, 2), Medium);
+        assert_eq!(endorsed_importance(Medium, ENDORSERS_FOR_HIGH), High);
+        assert_eq!(endorsed_importance(Low, 10), High);
+        // Never lowered, never raised to critical
+        assert_eq!(endorsed_importance(Critical, 1), Critical);
+        assert_eq!(endorsed_importance(High, 1), High);
+    }
+
+    #[test]
+    fn test_summary_counts_users_once() {
+        let summary = EndorsementSummary::new(
+            Uuid::nil(),
+            NoteImportance::Medium,
+            vec![
+                endorsement("alice", EndorsementKind::Ack),
+                endorsement("alice", EndorsementKind::Upvote),
+                endorsement("bob", EndorsementKind::Upvote),
+            ],
+        );
+        assert_eq!(summary.acks, 1);
+        assert_eq!(summary.upvotes, 2);
+        assert_eq!(summary.endorsers, 2);
+    }
+}
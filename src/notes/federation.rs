@@ -0,0 +1,437 @@
+//! Cross-instance note federation.
+//!
+//! An instance can mirror the global notes of other orchestrator instances
+//! (an org-level shared brain). Each source of the `note_federation` config
+//! section is pulled periodically by the `note_federation` heartbeat check:
+//! its active global notes are copied as global notes created by
+//! `federation:<source>` and tagged with that namespace. Copies are
+//! read-only — edits happen on the source instance and flow back on the next
+//! sync — and are never shared further. Global notes feed context building,
+//! which renders the provenance of federated ones.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::models::{Note, NoteFilters, NoteScope, NoteStatus};
+use crate::episodes::distill_models::SharingConsent;
+use crate::meilisearch::indexes::NoteDocument;
+use crate::meilisearch::SearchStore;
+use crate::neo4j::traits::GraphStore;
+
+/// Prefix of the `created_by` and namespace tag of federated notes.
+pub const FEDERATION_PREFIX: &str = "federation:";
+
+/// Notes fetched per request to a source.
+const PAGE_SIZE: usize = 100;
+
+/// Upper bound on the pages fetched from a source in one sync.
+const MAX_PAGES: usize = 50;
+
+/// `note_federation` section of config.yaml.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct NoteFederationConfig {
+    /// Instances to pull global notes from.
+    pub sources: Vec<NoteFederationSource>,
+    /// Seconds between two syncs (default: 3600).
+    pub interval_secs: u64,
+}
+
+impl Default for NoteFederationConfig {
+    fn default() -> Self {
+        Self {
+            sources: Vec::new(),
+            interval_secs: 3600,
+        }
+    }
+}
+
+/// A remote orchestrator instance sharing its global notes.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NoteFederationSource {
+    /// Namespace of the notes pulled from this source (`[a-z0-9_-]+`).
+    pub name: String,
+    /// Base URL of the instance (e.g. "https://po-central.example.com").
+    pub url: String,
+    /// Bearer token accepted by the instance (supports `${VAR}`).
+    #[serde(default)]
+    pub api_key: Option<String>,
+}
+
+/// Outcome of syncing one source.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FederationSyncReport {
+    pub source: String,
+    /// Remote notes eligible for mirroring
+    pub fetched: usize,
+    pub created: usize,
+    pub updated: usize,
+    pub removed: usize,
+}
+
+/// Page of `GET /api/notes` on the remote instance.
+#[derive(Deserialize)]
+struct RemotePage {
+    items: Vec<Note>,
+    #[serde(default)]
+    has_more: bool,
+}
+
+/// Whether `name` can be used as a federation namespace.
+pub fn is_valid_source_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+/// Namespace tag (and author) of the notes pulled from `source`.
+pub fn namespace(source: &str) -> String {
+    format!("{}{}", FEDERATION_PREFIX, source)
+}
+
+/// Name of the source a note was pulled from, if it is federated.
+pub fn source_of(note: &Note) -> Option<&str> {
+    note.created_by.strip_prefix(FEDERATION_PREFIX)
+}
+
+/// Stable local id of a remote note, so repeated syncs update in place.
+fn local_id(source: &NoteFederationSource, remote_id: Uuid) -> Uuid {
+    let key = format!("{}/notes/{}", source.url.trim_end_matches('/'), remote_id);
+    Uuid::new_v5(&Uuid::NAMESPACE_URL, key.as_bytes())
+}
+
+/// Local copy of a remote note. Anchors, scope paths and assertion rules
+/// point at the remote instance's code, so they are dropped.
+fn federated_copy(source: &NoteFederationSource, remote: &Note) -> Note {
+    let ns = namespace(&source.name);
+    let mut tags: Vec<String> = remote
+        .tags
+        .iter()
+        .filter(|t| !t.starts_with(FEDERATION_PREFIX))
+        .cloned()
+        .collect();
+    tags.push(ns.clone());
+
+    let mut note = remote.clone();
+    note.id = local_id(source, remote.id);
+    note.project_id = None;
+    note.scope = NoteScope::Project;
+    note.tags = tags;
+    note.anchors = vec![];
+    note.created_by = ns;
+    note.supersedes = None;
+    note.superseded_by = None;
+    note.changes = vec![];
+    note.assertion_rule = None;
+    note.last_assertion_result = None;
+    note.sharing_consent = SharingConsent::ExplicitDeny;
+    note
+}
+
+fn to_document(note: &Note) -> NoteDocument {
+    NoteDocument {
+        id: note.id.to_string(),
+        project_id: String::new(),
+        project_slug: String::new(),
+        note_type: note.note_type.to_string(),
+        status: note.status.to_string(),
+        importance: note.importance.to_string(),
+        scope_type: "project".to_string(),
+        scope_path: String::new(),
+        content: note.content.clone(),
+        tags: note.tags.clone(),
+        anchor_entities: vec![],
+        created_at: note.created_at.timestamp(),
+        created_by: note.created_by.clone(),
+        staleness_score: note.staleness_score,
+    }
+}
+
+/// HTTP client used to pull from sources.
+pub fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new())
+}
+
+/// Fetch the active global notes of a source, skipping the ones it
+/// federates itself from elsewhere.
+pub async fn fetch_remote_notes(
+    client: &reqwest::Client,
+    source: &NoteFederationSource,
+) -> Result<Vec<Note>> {
+    let url = format!("{}/api/notes", source.url.trim_end_matches('/'));
+    let mut notes = Vec::new();
+    for page in 0..MAX_PAGES {
+        let mut request = client.get(&url).query(&[
+            ("global_only", "true".to_string()),
+            ("status", "active".to_string()),
+            ("limit", PAGE_SIZE.to_string()),
+            ("offset", (page * PAGE_SIZE).to_string()),
+        ]);
+        if let Some(ref key) = source.api_key {
+            request = request.bearer_auth(key);
+        }
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach {}", url))?;
+        if !response.status().is_success() {
+            bail!("{} returned {}", url, response.status());
+        }
+        let body: RemotePage = response
+            .json()
+            .await
+            .with_context(|| format!("Invalid notes page from {}", url))?;
+        let done = !body.has_more || body.items.is_empty();
+        notes.extend(body.items.into_iter().filter(|n| source_of(n).is_none()));
+        if done {
+            break;
+        }
+    }
+    Ok(notes)
+}
+
+/// Mirror `remote_notes` of a source locally: create new notes, update
+/// changed ones and remove the ones gone (or no longer active) remotely.
+pub async fn sync_source(
+    graph: &dyn GraphStore,
+    search: Option<&dyn SearchStore>,
+    source: &NoteFederationSource,
+    remote_notes: &[Note],
+) -> Result<FederationSyncReport> {
+    let ns = namespace(&source.name);
+    let filters = NoteFilters {
+        tags: Some(vec![ns.clone()]),
+        global_only: Some(true),
+        limit: Some(10_000),
+        ..Default::default()
+    };
+    let (local, _) = graph.list_notes(None, None, &filters).await?;
+    let mut existing: HashMap<Uuid, Note> = local
+        .into_iter()
+        .filter(|n| n.created_by == ns)
+        .map(|n| (n.id, n))
+        .collect();
+
+    let mut report = FederationSyncReport {
+        source: source.name.clone(),
+        fetched: remote_notes.len(),
+        ..Default::default()
+    };
+
+    for remote in remote_notes {
+        let copy = federated_copy(source, remote);
+        let changed = match existing.remove(&copy.id) {
+            None => {
+                graph.create_note(&copy).await?;
+                report.created += 1;
+                true
+            }
+            Some(old) if old.note_type != copy.note_type => {
+                // The note type is immutable through update_note
+                graph.delete_note(old.id).await?;
+                graph.create_note(&copy).await?;
+                report.updated += 1;
+                true
+            }
+            Some(old)
+                if old.content != copy.content
+                    || old.importance != copy.importance
+                    || old.status != NoteStatus::Active
+                    || old.tags != copy.tags =>
+            {
+                graph
+                    .update_note(
+                        old.id,
+                        Some(copy.content.clone()),
+                        Some(copy.importance),
+                        Some(NoteStatus::Active),
+                        Some(copy.tags.clone()),
+                        None,
+                    )
+                    .await?;
+                report.updated += 1;
+                true
+            }
+            Some(_) => false,
+        };
+        if changed {
+            if let Some(search) = search {
+                if let Err(e) = search.index_note(&to_document(&copy)).await {
+                    tracing::warn!("Failed to index federated note {}: {}", copy.id, e);
+                }
+            }
+        }
+    }
+
+    for stale in existing.into_values() {
+        graph.delete_note(stale.id).await?;
+        if let Some(search) = search {
+            if let Err(e) = search.delete_note(&stale.id.to_string()).await {
+                tracing::warn!("Failed to unindex federated note {}: {}", stale.id, e);
+            }
+        }
+        report.removed += 1;
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meilisearch::mock::MockSearchStore;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::notes::models::{NoteImportance, NoteType};
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn source(url: &str) -> NoteFederationSource {
+        NoteFederationSource {
+            name: "central".to_string(),
+            url: url.to_string(),
+            api_key: Some("secret".to_string()),
+        }
+    }
+
+    fn remote_note(content: &str) -> Note {
+        let mut note = Note::new_full(
+            None,
+            NoteType::Guideline,
+            NoteImportance::High,
+            NoteScope::File("src/lib.rs".to_string()),
+            content.to_string(),
+            vec!["style".to_string()],
+            "alice".to_string(),
+        );
+        note.sharing_consent = SharingConsent::ExplicitAllow;
+        note
+    }
+
+    #[test]
+    fn test_source_names() {
+        assert!(is_valid_source_name("central"));
+        assert!(is_valid_source_name("org_brain-2"));
+        assert!(!is_valid_source_name(""));
+        assert!(!is_valid_source_name("Central"));
+        assert!(!is_valid_source_name("a:b"));
+    }
+
+    #[test]
+    fn test_federated_copy() {
+        let src = source("https://central.example.com/");
+        let remote = remote_note("Prefer thiserror in libraries");
+        let copy = federated_copy(&src, &remote);
+
+        assert_ne!(copy.id, remote.id);
+        assert_eq!(
+            copy.id,
+            federated_copy(&source("https://central.example.com"), &remote).id
+        );
+        assert_eq!(copy.project_id, None);
+        assert_eq!(copy.scope, NoteScope::Project);
+        assert_eq!(copy.tags, vec!["style", "federation:central"]);
+        assert_eq!(copy.sharing_consent, SharingConsent::ExplicitDeny);
+        assert_eq!(source_of(&copy), Some("central"));
+        assert_eq!(source_of(&remote), None);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_remote_notes_pages() {
+        let server = MockServer::start().await;
+        let first: Vec<Note> = (0..PAGE_SIZE)
+            .map(|i| remote_note(&format!("note {i}")))
+            .collect();
+        let mut relayed = remote_note("from another instance");
+        relayed.created_by = "federation:other".to_string();
+
+        Mock::given(method("GET"))
+            .and(path("/api/notes"))
+            .and(query_param("global_only", "true"))
+            .and(query_param("offset", "0"))
+            .and(header("authorization", "Bearer secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "items": first, "total": 102, "limit": 100, "offset": 0, "has_more": true
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/notes"))
+            .and(query_param("offset", "100"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "items": [remote_note("last"), relayed],
+                "total": 102, "limit": 100, "offset": 100, "has_more": false
+            })))
+            .mount(&server)
+            .await;
+
+        let notes = fetch_remote_notes(&http_client(), &source(&server.uri()))
+            .await
+            .unwrap();
+        assert_eq!(notes.len(), PAGE_SIZE + 1);
+        assert_eq!(notes.last().unwrap().content, "last");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_remote_notes_error_status() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/notes"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        let err = fetch_remote_notes(&http_client(), &source(&server.uri()))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("401"));
+    }
+
+    #[tokio::test]
+    async fn test_sync_source() {
+        let graph = MockGraphStore::new();
+        let search = MockSearchStore::new();
+        let src = source("https://central.example.com");
+        let kept = remote_note("Prefer thiserror in libraries");
+        let mut edited = remote_note("Run clippy before pushing");
+        let dropped = remote_note("Use tabs");
+        let local_note = remote_note("A note of this instance");
+        graph.create_note(&local_note).await.unwrap();
+
+        let report = sync_source(
+            &graph,
+            Some(&search),
+            &src,
+            &[kept.clone(), edited.clone(), dropped],
+        )
+        .await
+        .unwrap();
+        assert_eq!((report.created, report.updated, report.removed), (3, 0, 0));
+        assert_eq!(search.note_documents.read().await.len(), 3);
+
+        // Unchanged notes are left alone; edits and removals propagate
+        edited.content = "Run clippy and tests before pushing".to_string();
+        let report = sync_source(&graph, Some(&search), &src, &[kept, edited.clone()])
+            .await
+            .unwrap();
+        assert_eq!((report.created, report.updated, report.removed), (0, 1, 1));
+
+        let copy = graph
+            .get_note(local_id(&src, edited.id))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(copy.content, "Run clippy and tests before pushing");
+        assert_eq!(search.note_documents.read().await.len(), 2);
+
+        // Local notes are never touched
+        assert!(graph.get_note(local_note.id).await.unwrap().is_some());
+    }
+}
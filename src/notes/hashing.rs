@@ -168,6 +168,21 @@ pub fn hash_module_structure(
     hex::encode(hasher.finalize())
 }
 
+/// Stable identity of a code symbol (qualified name + signature hash)
+///
+/// Unlike graph IDs, which embed line numbers, the identity does not change
+/// when the symbol moves within its file, so anchors can follow it.
+pub fn symbol_identity(qualified_name: &str, signature_hash: &str) -> String {
+    let mut hasher = Sha256::new();
+
+    hasher.update(b"symbol:");
+    hasher.update(qualified_name.as_bytes());
+    hasher.update(b"#");
+    hasher.update(signature_hash.as_bytes());
+
+    hex::encode(hasher.finalize())
+}
+
 /// Calculate similarity between two hashes
 ///
 /// Returns a value between 0.0 (completely different) and 1.0 (identical).
@@ -443,6 +458,14 @@ mod tests {
         assert_ne!(hash1, hash2);
     }
 
+    #[test]
+    fn test_symbol_identity() {
+        let id = symbol_identity("src/lib.rs::parse", "abc");
+        assert_eq!(id, symbol_identity("src/lib.rs::parse", "abc"));
+        assert_ne!(id, symbol_identity("src/lib.rs::parse", "abd"));
+        assert_ne!(id, symbol_identity("src/main.rs::parse", "abc"));
+    }
+
     #[test]
     fn test_struct_signature_hash() {
         let hash1 = hash_struct_signature(
@@ -3,7 +3,10 @@
 //! Handles automatic detection of note obsolescence during code sync,
 //! staleness calculation, and note status transitions.
 
-use super::hashing::{hash_function_body, hash_function_signature, similarity_score};
+use super::hashing::{
+    hash_function_body, hash_function_signature, hash_struct_signature, similarity_score,
+    symbol_identity,
+};
 use super::models::*;
 use crate::neo4j::models::FunctionNode;
 use crate::parser::ParsedFile;
@@ -44,6 +47,41 @@ pub struct AnchorCheckResult {
     pub migration_target: Option<MigrationTarget>,
 }
 
+/// Outcome of re-anchoring a function/struct anchor after a sync
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReanchorOutcome {
+    /// The anchor still points at a current symbol
+    Current { symbol_id: String },
+    /// The symbol moved: the anchor should follow it to `new_entity_id`
+    Moved {
+        new_entity_id: String,
+        symbol_id: String,
+    },
+    /// No symbol of the file has the anchor's identity
+    Orphaned { reason: String },
+}
+
+/// Result of re-anchoring a single anchor
+#[derive(Debug, Clone)]
+pub struct ReanchorResult {
+    /// The note owning the anchor
+    pub note_id: Uuid,
+    /// Entity type of the anchor
+    pub entity_type: EntityType,
+    /// Entity ID the anchor pointed to
+    pub entity_id: String,
+    pub outcome: ReanchorOutcome,
+}
+
+/// A function or struct of a file, with its graph ID and stable identity
+struct SymbolEntry {
+    entity_type: EntityType,
+    entity_id: String,
+    qualified_name: String,
+    symbol_id: String,
+    line_start: u32,
+}
+
 /// Information about a parsed function for verification
 #[derive(Debug, Clone)]
 pub struct FunctionInfo {
@@ -425,6 +463,157 @@ impl NoteLifecycleManager {
         best_match
     }
 
+    /// Re-anchor the function/struct anchors of a file after a sync
+    ///
+    /// Graph IDs of functions embed their start line, so an anchor breaks as
+    /// soon as code above the function changes. Anchors are matched to the
+    /// current symbols by ID first, then by stable identity (qualified name
+    /// + signature hash, see [`symbol_identity`]). Anchors created before
+    /// identities existed fall back to their signature hash, or to the name
+    /// when it is unique in the file. Anchors matching nothing are orphaned.
+    pub fn reanchor_symbols(
+        &self,
+        anchors: &[(Uuid, NoteAnchor)],
+        file_info: &FileInfo,
+    ) -> Vec<ReanchorResult> {
+        let symbols = Self::symbol_entries(file_info);
+
+        anchors
+            .iter()
+            .filter(|(_, a)| matches!(a.entity_type, EntityType::Function | EntityType::Struct))
+            .map(|(note_id, anchor)| ReanchorResult {
+                note_id: *note_id,
+                entity_type: anchor.entity_type.clone(),
+                entity_id: anchor.entity_id.clone(),
+                outcome: self.reanchor_single(anchor, &symbols, file_info),
+            })
+            .collect()
+    }
+
+    fn reanchor_single(
+        &self,
+        anchor: &NoteAnchor,
+        symbols: &[SymbolEntry],
+        file_info: &FileInfo,
+    ) -> ReanchorOutcome {
+        let candidates: Vec<&SymbolEntry> = symbols
+            .iter()
+            .filter(|s| s.entity_type == anchor.entity_type)
+            .collect();
+
+        if let Some(current) = candidates.iter().find(|s| s.entity_id == anchor.entity_id) {
+            return ReanchorOutcome::Current {
+                symbol_id: current.symbol_id.clone(),
+            };
+        }
+
+        let (qualified_name, old_line) =
+            Self::qualified_name_from_id(&anchor.entity_type, &anchor.entity_id);
+        let identity = anchor.symbol_id.clone().or_else(|| {
+            anchor
+                .signature_hash
+                .as_deref()
+                .map(|sig| symbol_identity(&qualified_name, sig))
+        });
+
+        let target = match &identity {
+            // Closest to the old position if the identity is not unique
+            Some(identity) => candidates
+                .iter()
+                .filter(|s| &s.symbol_id == identity)
+                .min_by_key(|s| s.line_start.abs_diff(old_line.unwrap_or(0))),
+            None => {
+                let mut same_name = candidates
+                    .iter()
+                    .filter(|s| s.qualified_name == qualified_name);
+                match (same_name.next(), same_name.next()) {
+                    (Some(only), None) => Some(only),
+                    _ => None,
+                }
+            }
+        };
+
+        if let Some(target) = target {
+            return ReanchorOutcome::Moved {
+                new_entity_id: target.entity_id.clone(),
+                symbol_id: target.symbol_id.clone(),
+            };
+        }
+
+        let kind = match anchor.entity_type {
+            EntityType::Struct => "Struct",
+            _ => "Function",
+        };
+        let name = qualified_name
+            .rsplit_once("::")
+            .map(|(_, name)| name)
+            .unwrap_or(&qualified_name);
+        let renamed = match (&anchor.entity_type, &anchor.body_hash) {
+            (EntityType::Function, Some(body_hash)) => {
+                self.find_similar_function(file_info, body_hash)
+            }
+            _ => None,
+        };
+        let reason = match renamed {
+            Some((new_name, _, _)) => format!(
+                "{} '{}' not found in {} (possibly renamed to '{}')",
+                kind, name, file_info.path, new_name
+            ),
+            None => format!("{} '{}' not found in {}", kind, name, file_info.path),
+        };
+        ReanchorOutcome::Orphaned { reason }
+    }
+
+    /// Functions and structs of a file, keyed like their graph nodes
+    fn symbol_entries(file_info: &FileInfo) -> Vec<SymbolEntry> {
+        let functions = file_info.functions.iter().map(|f| {
+            let qualified_name = format!("{}::{}", file_info.path, f.name);
+            let sig_hash = hash_function_signature(
+                &f.name,
+                &f.params,
+                f.return_type.as_deref(),
+                f.is_async,
+                f.is_unsafe,
+            );
+            SymbolEntry {
+                entity_type: EntityType::Function,
+                entity_id: format!("{}:{}:{}", file_info.path, f.name, f.line_start),
+                symbol_id: symbol_identity(&qualified_name, &sig_hash),
+                qualified_name,
+                line_start: f.line_start,
+            }
+        });
+        let structs = file_info.structs.iter().map(|s| {
+            let qualified_name = format!("{}::{}", file_info.path, s.name);
+            let sig_hash = hash_struct_signature(&s.name, &s.fields, &s.generics);
+            SymbolEntry {
+                entity_type: EntityType::Struct,
+                entity_id: format!("{}:{}", file_info.path, s.name),
+                symbol_id: symbol_identity(&qualified_name, &sig_hash),
+                qualified_name,
+                line_start: 0,
+            }
+        });
+        functions.chain(structs).collect()
+    }
+
+    /// Qualified name (`path::name`) and start line encoded in a graph ID
+    /// (`path:name:line` for functions, `path:name` for structs)
+    fn qualified_name_from_id(entity_type: &EntityType, entity_id: &str) -> (String, Option<u32>) {
+        let (rest, line) = match entity_type {
+            EntityType::Function => match entity_id.rsplit_once(':') {
+                Some((rest, line)) if line.parse::<u32>().is_ok() => (rest, line.parse().ok()),
+                _ => (entity_id, None),
+            },
+            _ => (entity_id, None),
+        };
+        let qualified_name = match rest.rsplit_once(':') {
+            Some((path, name)) => format!("{}::{}", path, name),
+            None => rest.to_string(),
+        };
+        (qualified_name, line)
+    }
+
     /// Calculate staleness score for a note
     pub fn calculate_staleness_score(&self, note: &Note, now: DateTime<Utc>) -> f64 {
         let base_decay_days = note.base_decay_days();
@@ -1328,4 +1517,69 @@ mod tests {
             "Non-reactivated note at 50h idle SHOULD be archived (48h grace)"
         );
     }
+
+    #[test]
+    fn test_reanchor_symbols() {
+        let manager = NoteLifecycleManager::new();
+        let file_info = create_test_file_info();
+        let sig_hash = hash_function_signature(
+            "test_func",
+            &[("x".to_string(), Some("i32".to_string()))],
+            Some("String"),
+            false,
+            false,
+        );
+        let symbol_id = symbol_identity("test.rs::test_func", &sig_hash);
+        let note_id = Uuid::new_v4();
+
+        // Function moved from line 10 to line 1
+        let mut moved = NoteAnchor::new(EntityType::Function, "test.rs:test_func:10".to_string());
+        moved.symbol_id = Some(symbol_id.clone());
+        // Legacy anchor without identity, matched through its signature hash
+        let legacy = NoteAnchor::with_hashes(
+            EntityType::Function,
+            "test.rs:test_func:42".to_string(),
+            Some(sig_hash),
+            None,
+        );
+        let current = NoteAnchor::new(EntityType::Struct, "test.rs:TestStruct".to_string());
+        let mut orphaned = NoteAnchor::new(EntityType::Function, "test.rs:gone_func:5".to_string());
+        orphaned.symbol_id = Some(symbol_identity("test.rs::gone_func", "x"));
+
+        let anchors: Vec<_> = [moved, legacy, current, orphaned]
+            .into_iter()
+            .map(|a| (note_id, a))
+            .collect();
+        let results = manager.reanchor_symbols(&anchors, &file_info);
+        assert_eq!(results.len(), 4);
+
+        let expected_move = ReanchorOutcome::Moved {
+            new_entity_id: "test.rs:test_func:1".to_string(),
+            symbol_id,
+        };
+        assert_eq!(results[0].outcome, expected_move);
+        assert_eq!(results[1].outcome, expected_move);
+        assert!(matches!(
+            results[2].outcome,
+            ReanchorOutcome::Current { .. }
+        ));
+        match &results[3].outcome {
+            ReanchorOutcome::Orphaned { reason } => assert!(reason.contains("gone_func")),
+            other => panic!("expected orphaned anchor, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_reanchor_changed_signature_is_orphaned() {
+        let manager = NoteLifecycleManager::new();
+        let file_info = create_test_file_info();
+        let mut anchor = NoteAnchor::new(EntityType::Function, "test.rs:test_func:7".to_string());
+        anchor.symbol_id = Some(symbol_identity("test.rs::test_func", "old-signature"));
+
+        let results = manager.reanchor_symbols(&[(Uuid::new_v4(), anchor)], &file_info);
+        assert!(matches!(
+            results[0].outcome,
+            ReanchorOutcome::Orphaned { .. }
+        ));
+    }
 }
@@ -3,12 +3,16 @@
 //! Provides high-level operations for creating, reading, updating, and deleting notes,
 //! including linking notes to entities and managing note lifecycle.
 
+use super::endorsement::{
+    distinct_endorsers, endorsed_importance, EndorsementSummary, ENDORSEMENT_ENERGY_BOOST,
+};
 use super::models::*;
 use crate::embeddings::EmbeddingProvider;
 use crate::events::graph::{GraphEvent, GraphEventType, GraphLayer};
 use crate::events::{CrudAction, CrudEvent, EntityType as EventEntityType, EventEmitter};
 use crate::meilisearch::indexes::NoteDocument;
 use crate::meilisearch::SearchStore;
+use crate::neo4j::models::{EndorsementKind, NoteEndorsementNode};
 use crate::neo4j::GraphStore;
 use anyhow::Result;
 use std::sync::Arc;
@@ -624,6 +628,39 @@ impl NoteManager {
         Ok(deleted)
     }
 
+    /// Put back a deleted note from a snapshot taken before its deletion,
+    /// with the same ID and anchors. Synapses are not restored; they are
+    /// reconnected from the content like for a new note.
+    pub async fn restore_note(&self, note: &Note) -> Result<()> {
+        self.neo4j.create_note(note).await?;
+        let doc = self.note_to_document(note, None).await?;
+        self.meilisearch.index_note(&doc).await?;
+        for anchor in &note.anchors {
+            self.neo4j
+                .link_note_to_entity(
+                    note.id,
+                    &anchor.entity_type,
+                    &anchor.entity_id,
+                    anchor.signature_hash.as_deref(),
+                    anchor.body_hash.as_deref(),
+                )
+                .await?;
+        }
+        self.embed_note(note.id, &note.content).await;
+        self.spawn_auto_connect_synapses(note.id, &note.content, note.project_id);
+
+        self.emit(
+            CrudEvent::new(
+                EventEntityType::Note,
+                CrudAction::Created,
+                note.id.to_string(),
+            )
+            .with_payload(serde_json::json!({"note_type": note.note_type.to_string()}))
+            .with_project_id(note.project_id.map(|id| id.to_string()).unwrap_or_default()),
+        );
+        Ok(())
+    }
+
     /// List notes with filters and pagination
     pub async fn list_notes(
         &self,
@@ -897,6 +934,133 @@ impl NoteManager {
         self.neo4j.update_energy_scores(half_life_days).await
     }
 
+    // ========================================================================
+    // Endorsements
+    // ========================================================================
+
+    /// Endorse a note on behalf of a user (see [`super::endorsement`]).
+    ///
+    /// A new endorsement boosts the note's energy and may promote its
+    /// importance; endorsing twice the same way changes nothing. Returns
+    /// None when the note does not exist.
+    pub async fn endorse_note(
+        &self,
+        note_id: Uuid,
+        kind: EndorsementKind,
+        user_id: &str,
+        user_name: &str,
+    ) -> Result<Option<EndorsementSummary>> {
+        let Some(note) = self.neo4j.get_note(note_id).await? else {
+            return Ok(None);
+        };
+        let is_new = self
+            .neo4j
+            .add_note_endorsement(&NoteEndorsementNode {
+                note_id,
+                kind,
+                user_id: user_id.to_string(),
+                user_name: user_name.to_string(),
+                created_at: chrono::Utc::now(),
+            })
+            .await?;
+        let endorsements = self.neo4j.list_note_endorsements(note_id).await?;
+        if !is_new {
+            return Ok(Some(EndorsementSummary::new(
+                note_id,
+                note.importance,
+                endorsements,
+            )));
+        }
+
+        self.neo4j
+            .boost_energy(note_id, ENDORSEMENT_ENERGY_BOOST)
+            .await?;
+        let importance = endorsed_importance(note.importance, distinct_endorsers(&endorsements));
+        if importance != note.importance {
+            if let Some(updated) = self
+                .neo4j
+                .update_note(note_id, None, Some(importance), None, None, None)
+                .await?
+            {
+                let doc = self.note_to_document(&updated, None).await?;
+                self.meilisearch.index_note(&doc).await?;
+            }
+        }
+
+        let project_id_str = note.project_id.map(|id| id.to_string()).unwrap_or_default();
+        self.emit(
+            CrudEvent::new(
+                EventEntityType::Note,
+                CrudAction::Updated,
+                note_id.to_string(),
+            )
+            .with_payload(serde_json::json!({
+                "endorsed_by": user_id,
+                "endorsement": kind.as_str(),
+                "importance": importance.to_string(),
+            }))
+            .with_project_id(project_id_str.clone()),
+        );
+        if !project_id_str.is_empty() {
+            self.emit_graph_event(GraphEvent::reinforcement(
+                note_id.to_string(),
+                ENDORSEMENT_ENERGY_BOOST,
+                &project_id_str,
+            ));
+        }
+
+        Ok(Some(EndorsementSummary::new(
+            note_id,
+            importance,
+            endorsements,
+        )))
+    }
+
+    /// Withdraw a user's endorsement of a note. The energy and importance
+    /// the note gained are kept. Returns None when the note does not exist.
+    pub async fn withdraw_endorsement(
+        &self,
+        note_id: Uuid,
+        kind: EndorsementKind,
+        user_id: &str,
+    ) -> Result<Option<EndorsementSummary>> {
+        let Some(note) = self.neo4j.get_note(note_id).await? else {
+            return Ok(None);
+        };
+        if self
+            .neo4j
+            .remove_note_endorsement(note_id, user_id, kind)
+            .await?
+        {
+            self.emit(
+                CrudEvent::new(
+                    EventEntityType::Note,
+                    CrudAction::Updated,
+                    note_id.to_string(),
+                )
+                .with_payload(serde_json::json!({
+                    "endorsement_withdrawn_by": user_id,
+                    "endorsement": kind.as_str(),
+                }))
+                .with_project_id(note.project_id.map(|id| id.to_string()).unwrap_or_default()),
+            );
+        }
+        self.get_endorsements(note_id).await
+    }
+
+    /// Endorsements of a note. Returns None when the note does not exist.
+    pub async fn get_endorsements(&self, note_id: Uuid) -> Result<Option<EndorsementSummary>> {
+        let Some(note) = self.neo4j.get_note(note_id).await? else {
+            return Ok(None);
+        };
+        let endorsements = self.neo4j.list_note_endorsements(note_id).await?;
+        Ok(Some(EndorsementSummary::new(
+            note_id,
+            note.importance,
+            endorsements,
+        )))
+    }
+
     // ========================================================================
     // Search Operations
     // ========================================================================
@@ -2213,6 +2377,52 @@ mod tests {
         assert!(confirmed.last_confirmed_at.is_some());
     }
 
+    #[tokio::test]
+    async fn test_endorse_note() {
+        let (mgr, pid) = create_note_manager().await;
+        let mut req = make_create_request(pid, "Endorse me");
+        req.importance = Some(NoteImportance::Low);
+        let note = mgr.create_note(req, "agent-1").await.unwrap();
+        let summary = mgr
+            .endorse_note(note.id, EndorsementKind::Ack, "u-alice", "Alice")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!((summary.acks, summary.endorsers), (1, 1));
+        assert_eq!(summary.importance, NoteImportance::Medium);
+
+        // Endorsing twice the same way is a no-op
+        let again = mgr
+            .endorse_note(note.id, EndorsementKind::Ack, "u-alice", "Alice")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(again.acks, 1);
+
+        for user in ["u-bob", "u-carol"] {
+            mgr.endorse_note(note.id, EndorsementKind::Upvote, user, user)
+                .await
+                .unwrap();
+        }
+        let endorsed = mgr.get_note(note.id).await.unwrap().unwrap();
+        assert_eq!(endorsed.importance, NoteImportance::High);
+
+        // Withdrawing keeps the importance the note earned
+        let summary = mgr
+            .withdraw_endorsement(note.id, EndorsementKind::Upvote, "u-bob")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!((summary.upvotes, summary.endorsers), (1, 2));
+        assert_eq!(summary.importance, NoteImportance::High);
+
+        assert!(mgr
+            .endorse_note(Uuid::new_v4(), EndorsementKind::Ack, "u-alice", "Alice")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
     #[tokio::test]
     async fn test_invalidate_note() {
         let (mgr, pid) = create_note_manager().await;
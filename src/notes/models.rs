@@ -454,6 +454,10 @@ pub struct NoteAnchor {
     pub signature_hash: Option<String>,
     /// SHA256 hash of the entity's body (for functions)
     pub body_hash: Option<String>,
+    /// Stable identity of the symbol (see `hashing::symbol_identity`), used
+    /// to follow functions and structs when their graph ID changes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol_id: Option<String>,
     /// When this anchor was last verified as valid
     pub last_verified: DateTime<Utc>,
     /// Whether the anchor is currently valid
@@ -477,6 +481,7 @@ impl NoteAnchor {
             entity_id,
             signature_hash: None,
             body_hash: None,
+            symbol_id: None,
             last_verified: Utc::now(),
             is_valid: true,
         }
@@ -494,6 +499,7 @@ impl NoteAnchor {
             entity_id,
             signature_hash,
             body_hash,
+            symbol_id: None,
             last_verified: Utc::now(),
             is_valid: true,
         }
@@ -759,6 +765,12 @@ pub struct Note {
     /// Controls whether this note can be shared via the distillation pipeline.
     #[serde(default)]
     pub sharing_consent: crate::episodes::distill_models::SharingConsent,
+
+    // Optimistic concurrency
+    /// Incremented by every update through the API (0 until the first one).
+    /// Sent back as `If-Match` to update only an unchanged note.
+    #[serde(default)]
+    pub version: i64,
 }
 
 impl Note {
@@ -803,6 +815,7 @@ impl Note {
             assertion_rule: None,
             last_assertion_result: None,
             sharing_consent: crate::episodes::distill_models::SharingConsent::NotSet,
+            version: 0,
         }
     }
 
@@ -871,6 +884,7 @@ impl Note {
             assertion_rule: None,
             last_assertion_result: None,
             sharing_consent: crate::episodes::distill_models::SharingConsent::NotSet,
+            version: 0,
         }
     }
 
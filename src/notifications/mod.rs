@@ -0,0 +1,844 @@
+//! # Notifications — email and Slack messages on selected events
+//!
+//! The `notifications` section of config.yaml declares the channels
+//! ([`SmtpChannel`], [`SlackChannel`]) and the rules: each
+//! [`NotificationRule`] matches [`CrudEvent`]s — either a built-in
+//! [`NotificationEvent`] ("plan approved", "agent failed", "advisory found",
+//! "run reported")
+//! or an entity type / action / payload pattern — and names the channels it
+//! notifies. Rules listing `owners` only match events touching a file those
+//! CODEOWNERS owners own.
+//!
+//! Slack messages go to the channel of the webhook. Emails go to the
+//! addresses listed in the rule and to the users who opted in through their
+//! [`NotificationPreferences`](crate::neo4j::models::NotificationPreferences)
+//! (`PUT /api/notifications/preferences`), minus the rules they muted.
+//!
+//! The [`Notifier`] is registered on the EventReactor. Every instance sees
+//! every event (NATS re-injects remote events on the local bus), so only the
+//! leader sends.
+
+mod slack;
+mod smtp;
+
+pub use slack::SlackChannel;
+pub use smtp::SmtpChannel;
+
+use crate::events::{CrudEvent, Leadership, ReactorBuilder};
+use crate::neo4j::traits::GraphStore;
+use crate::orchestrator::ownership::{self, Ownership};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// `notifications` section of config.yaml.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct NotificationsConfig {
+    /// SMTP server sending the emails (email channel disabled when absent)
+    pub smtp: Option<SmtpConfig>,
+    /// Slack incoming webhook (Slack channel disabled when absent)
+    pub slack: Option<SlackConfig>,
+    /// Events to notify, in order
+    pub rules: Vec<NotificationRule>,
+}
+
+/// SMTP server settings.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SmtpConfig {
+    pub host: String,
+    /// Port (default: 587 with STARTTLS, 465 with TLS, 25 without)
+    #[serde(default)]
+    pub port: Option<u16>,
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Password (supports `${VAR}`)
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Sender address, e.g. "Orchestrator <po@example.com>"
+    pub from: String,
+    #[serde(default)]
+    pub tls: SmtpTls,
+}
+
+/// Transport security of the SMTP connection.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpTls {
+    /// Plain connection upgraded with STARTTLS (default)
+    #[default]
+    Starttls,
+    /// TLS from the start (SMTPS)
+    Tls,
+    /// No encryption — local relays only
+    None,
+}
+
+/// Slack incoming webhook settings.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SlackConfig {
+    /// Incoming webhook URL (supports `${VAR}`)
+    pub webhook_url: String,
+}
+
+/// A notification channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelKind {
+    Email,
+    Slack,
+}
+
+/// Built-in event patterns a rule can refer to by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    /// A plan moved to `approved`
+    PlanApproved,
+    /// A runner agent failed its task, timed out or could not be spawned
+    AgentFailed,
+    /// The vulnerability audit found a new advisory
+    AdvisoryFound,
+    /// A plan run finished and its report was recorded
+    RunReported,
+}
+
+impl NotificationEvent {
+    fn matches(self, event: &CrudEvent) -> bool {
+        use crate::events::{CrudAction, EntityType};
+        match self {
+            Self::PlanApproved => {
+                event.entity_type == EntityType::Plan
+                    && event.action == CrudAction::Updated
+                    && event.payload["status"] == "approved"
+            }
+            Self::AgentFailed => {
+                event.entity_type == EntityType::Runner
+                    && matches!(
+                        event.payload["event"].as_str(),
+                        Some("task_failed" | "task_timeout" | "task_spawning_timeout")
+                    )
+            }
+            Self::AdvisoryFound => {
+                event.entity_type == EntityType::Advisory && event.action == CrudAction::Created
+            }
+            Self::RunReported => {
+                event.entity_type == EntityType::Runner && event.payload["event"] == "run_reported"
+            }
+        }
+    }
+}
+
+/// A rule of the `notifications.rules` list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationRule {
+    /// Unique name, used by users to mute the rule
+    pub name: String,
+    /// Built-in event pattern
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event: Option<NotificationEvent>,
+    /// Entity type (snake_case, e.g. "project") — narrows `event` when both are set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity_type: Option<String>,
+    /// Action (snake_case, e.g. "synced")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+    /// Payload conditions, with the semantics of event trigger conditions
+    /// (exact match, arrays meaning "any of")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<serde_json::Value>,
+    /// Channels to notify (default: every configured channel)
+    #[serde(default)]
+    pub channels: Vec<ChannelKind>,
+    /// Email addresses always notified, on top of the opted-in users
+    #[serde(default)]
+    pub recipients: Vec<String>,
+    /// CODEOWNERS owners (`@org/team`, `@user` or email): when set, the rule
+    /// only matches events touching a file one of them owns
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub owners: Vec<String>,
+}
+
+impl NotificationRule {
+    /// Whether the event matches the built-in event and every pattern of the rule.
+    pub fn matches(&self, event: &CrudEvent) -> bool {
+        if self.event.is_some_and(|e| !e.matches(event)) {
+            return false;
+        }
+        if self
+            .entity_type
+            .as_ref()
+            .is_some_and(|t| *t != snake_case(&event.entity_type))
+        {
+            return false;
+        }
+        if self
+            .action
+            .as_ref()
+            .is_some_and(|a| *a != snake_case(&event.action))
+        {
+            return false;
+        }
+        if let Some(conditions) = self.payload.as_ref().and_then(|p| p.as_object()) {
+            for (key, expected) in conditions {
+                let actual = &event.payload[key];
+                if actual.is_null() || !crate::events::trigger::value_matches(expected, actual) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Whether the rule notifies the channel.
+    fn uses(&self, kind: ChannelKind) -> bool {
+        self.channels.is_empty() || self.channels.contains(&kind)
+    }
+}
+
+fn snake_case<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value)
+        .unwrap_or_default()
+        .trim_matches('"')
+        .to_string()
+}
+
+impl NotificationsConfig {
+    /// Names of the rules notifying by email, the ones users can mute.
+    pub fn email_rules(&self) -> Vec<String> {
+        if self.smtp.is_none() {
+            return Vec::new();
+        }
+        self.rules
+            .iter()
+            .filter(|r| r.uses(ChannelKind::Email))
+            .map(|r| r.name.clone())
+            .collect()
+    }
+
+    /// Check the rules against the configured channels.
+    pub fn check(&self) -> Result<(), String> {
+        let mut names = HashSet::new();
+        for rule in &self.rules {
+            if rule.name.trim().is_empty() {
+                return Err("notification rule without a name".to_string());
+            }
+            if !names.insert(rule.name.as_str()) {
+                return Err(format!("duplicate notification rule `{}`", rule.name));
+            }
+            if rule.event.is_none() && rule.entity_type.is_none() {
+                return Err(format!(
+                    "notification rule `{}` needs an `event` or an `entity_type`",
+                    rule.name
+                ));
+            }
+            if rule.payload.as_ref().is_some_and(|p| !p.is_object()) {
+                return Err(format!(
+                    "notification rule `{}`: `payload` must be a mapping",
+                    rule.name
+                ));
+            }
+            for kind in &rule.channels {
+                let configured = match kind {
+                    ChannelKind::Email => self.smtp.is_some(),
+                    ChannelKind::Slack => self.slack.is_some(),
+                };
+                if !configured {
+                    return Err(format!(
+                        "notification rule `{}` uses the {} channel, which is not configured",
+                        rule.name,
+                        snake_case(kind)
+                    ));
+                }
+            }
+            if let Some(address) = rule.recipients.iter().find(|r| !r.contains('@')) {
+                return Err(format!(
+                    "notification rule `{}`: `{}` is not an email address",
+                    rule.name, address
+                ));
+            }
+            if let Some(owner) = rule.owners.iter().find(|o| !ownership::is_owner(o)) {
+                return Err(format!(
+                    "notification rule `{}`: `{}` is not a user, team or email",
+                    rule.name, owner
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A rendered notification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    /// Name of the rule that matched
+    pub rule: String,
+    pub subject: String,
+    pub body: String,
+}
+
+impl Notification {
+    /// Render the notification of a rule for an event.
+    pub fn render(rule: &NotificationRule, event: &CrudEvent) -> Self {
+        let p = &event.payload;
+        let text = |key: &str| p[key].as_str().unwrap_or("?").to_string();
+        let (subject, mut body) = match rule.event {
+            Some(NotificationEvent::PlanApproved) => (
+                "Plan approved".to_string(),
+                format!("Plan {} was approved.", event.entity_id),
+            ),
+            Some(NotificationEvent::AgentFailed) => {
+                let reason = match p["event"].as_str() {
+                    Some("task_failed") => format!(
+                        "failed after {} attempt(s): {}",
+                        p["attempts"].as_u64().unwrap_or(1),
+                        text("reason")
+                    ),
+                    Some("task_spawning_timeout") => "could not be spawned in time".to_string(),
+                    _ => "timed out".to_string(),
+                };
+                (
+                    format!("Agent failed: {}", text("task_title")),
+                    format!(
+                        "The agent of task \"{}\" ({}) in run {} {}.",
+                        text("task_title"),
+                        text("task_id"),
+                        text("run_id"),
+                        reason
+                    ),
+                )
+            }
+            Some(NotificationEvent::AdvisoryFound) => {
+                let fixed: Vec<&str> = p["fixed_versions"]
+                    .as_array()
+                    .map(|v| v.iter().filter_map(|v| v.as_str()).collect())
+                    .unwrap_or_default();
+                (
+                    format!(
+                        "Security advisory in {} {} ({})",
+                        text("package"),
+                        text("version"),
+                        text("severity")
+                    ),
+                    format!(
+                        "{}: {}\nFixed in: {}",
+                        event.entity_id,
+                        text("summary"),
+                        if fixed.is_empty() {
+                            "no fixed version yet".to_string()
+                        } else {
+                            fixed.join(", ")
+                        }
+                    ),
+                )
+            }
+            Some(NotificationEvent::RunReported) => (
+                format!("Plan run {}: {}", text("status"), text("plan_title")),
+                format!(
+                    "{} task(s) completed, {} failed, {} file(s) changed, {} note(s) created.\n\
+                     Cost: ${:.2}, duration: {:.0}s\nReport: /api/runs/{}",
+                    p["tasks_completed"].as_u64().unwrap_or(0),
+                    p["tasks_failed"].as_u64().unwrap_or(0),
+                    p["files_changed"].as_u64().unwrap_or(0),
+                    p["notes_created"].as_u64().unwrap_or(0),
+                    p["cost_usd"].as_f64().unwrap_or(0.0),
+                    p["duration_secs"].as_f64().unwrap_or(0.0),
+                    text("run_id")
+                ),
+            ),
+            None => (
+                format!(
+                    "{}: {} {}",
+                    rule.name,
+                    snake_case(&event.entity_type),
+                    snake_case(&event.action)
+                ),
+                format!(
+                    "{} {} {}\n{}",
+                    snake_case(&event.entity_type),
+                    event.entity_id,
+                    snake_case(&event.action),
+                    serde_json::to_string_pretty(p).unwrap_or_default()
+                ),
+            ),
+        };
+        if let Some(ref project_id) = event.project_id {
+            body.push_str(&format!("\nProject: {}", project_id));
+        }
+        Self {
+            rule: rule.name.clone(),
+            subject,
+            body,
+        }
+    }
+}
+
+/// A way of delivering notifications.
+#[async_trait]
+pub trait NotificationChannel: Send + Sync {
+    fn kind(&self) -> ChannelKind;
+
+    /// Deliver a notification. `recipients` are the email addresses to
+    /// notify; channels without individual recipients ignore them.
+    async fn send(&self, notification: &Notification, recipients: &[String]) -> Result<()>;
+}
+
+/// Matches events against the rules and sends the notifications.
+pub struct Notifier {
+    rules: Vec<NotificationRule>,
+    channels: Vec<Arc<dyn NotificationChannel>>,
+    store: Arc<dyn GraphStore>,
+}
+
+impl Notifier {
+    pub fn new(
+        rules: Vec<NotificationRule>,
+        channels: Vec<Arc<dyn NotificationChannel>>,
+        store: Arc<dyn GraphStore>,
+    ) -> Self {
+        Self {
+            rules,
+            channels,
+            store,
+        }
+    }
+
+    /// Build the notifier of the `notifications` section, or `None` when it
+    /// has no rule or no channel.
+    pub fn from_config(
+        config: &NotificationsConfig,
+        store: Arc<dyn GraphStore>,
+    ) -> Result<Option<Self>> {
+        let mut channels: Vec<Arc<dyn NotificationChannel>> = Vec::new();
+        if let Some(ref smtp) = config.smtp {
+            channels.push(Arc::new(SmtpChannel::new(smtp)?));
+        }
+        if let Some(ref slack) = config.slack {
+            channels.push(Arc::new(SlackChannel::new(slack)));
+        }
+        if config.rules.is_empty() || channels.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(Self::new(config.rules.clone(), channels, store)))
+    }
+
+    /// Files an event touches: the `file_path`, `files` or `affected_files`
+    /// of its payload, else the affected files of its `task_id` task.
+    async fn event_files(&self, event: &CrudEvent) -> Vec<String> {
+        let p = &event.payload;
+        let mut files: Vec<String> = p["file_path"]
+            .as_str()
+            .map(str::to_string)
+            .into_iter()
+            .collect();
+        for key in ["files", "affected_files"] {
+            let listed = p[key].as_array().into_iter().flatten();
+            files.extend(listed.filter_map(|f| f.as_str().map(str::to_string)));
+        }
+        if files.is_empty() {
+            if let Some(task_id) = p["task_id"].as_str().and_then(|id| id.parse().ok()) {
+                if let Ok(Some(task)) = self.store.get_task(task_id).await {
+                    files = task.affected_files;
+                }
+            }
+        }
+        files
+    }
+
+    /// CODEOWNERS owners of the files an event touches, resolved with the
+    /// rules of the event's project. Empty for events without a project.
+    async fn event_owners(&self, event: &CrudEvent) -> HashSet<String> {
+        let Some(project_id) = event.project_id.as_deref().and_then(|id| id.parse().ok()) else {
+            return HashSet::new();
+        };
+        let files = self.event_files(event).await;
+        if files.is_empty() {
+            return HashSet::new();
+        }
+        let Ok(Some(project)) = self.store.get_project(project_id).await else {
+            return HashSet::new();
+        };
+        match Ownership::load(self.store.as_ref(), &project).await {
+            Ok(ownership) => files
+                .iter()
+                .flat_map(|f| ownership.owners_of(f))
+                .cloned()
+                .collect(),
+            Err(e) => {
+                tracing::warn!("Notifications: failed to load code owners: {}", e);
+                HashSet::new()
+            }
+        }
+    }
+
+    /// Send the notifications of the rules matching the event. Returns the
+    /// number of notifications delivered.
+    pub async fn notify(&self, event: &CrudEvent) -> usize {
+        let mut subscribers = None;
+        let mut event_owners = None;
+        let mut sent = 0;
+        for rule in self.rules.iter().filter(|r| r.matches(event)) {
+            if !rule.owners.is_empty() {
+                if event_owners.is_none() {
+                    event_owners = Some(self.event_owners(event).await);
+                }
+                let owned = event_owners
+                    .as_ref()
+                    .is_some_and(|owners| rule.owners.iter().any(|o| owners.contains(o)));
+                if !owned {
+                    continue;
+                }
+            }
+            let notification = Notification::render(rule, event);
+            for channel in self.channels.iter().filter(|c| rule.uses(c.kind())) {
+                let recipients = match channel.kind() {
+                    ChannelKind::Email => {
+                        if subscribers.is_none() {
+                            subscribers = Some(
+                                self.store
+                                    .list_notification_subscribers()
+                                    .await
+                                    .unwrap_or_else(|e| {
+                                        tracing::warn!(
+                                            "Notifications: failed to list subscribers: {}",
+                                            e
+                                        );
+                                        Vec::new()
+                                    }),
+                            );
+                        }
+                        let mut recipients = rule.recipients.clone();
+                        for s in subscribers.iter().flatten() {
+                            if !s.preferences.muted_rules.contains(&rule.name)
+                                && !recipients.contains(&s.email)
+                            {
+                                recipients.push(s.email.clone());
+                            }
+                        }
+                        if recipients.is_empty() {
+                            continue;
+                        }
+                        recipients
+                    }
+                    ChannelKind::Slack => Vec::new(),
+                };
+                match channel.send(&notification, &recipients).await {
+                    Ok(()) => sent += 1,
+                    Err(e) => tracing::warn!(
+                        "Notifications: rule `{}` failed on {}: {}",
+                        rule.name,
+                        snake_case(&channel.kind()),
+                        e
+                    ),
+                }
+            }
+        }
+        sent
+    }
+}
+
+/// Register the notifier on the EventReactor. Only the leader sends.
+pub fn register_reactions(
+    builder: ReactorBuilder,
+    notifier: Arc<Notifier>,
+    leadership: Leadership,
+) -> ReactorBuilder {
+    builder.on(
+        "notifications",
+        None,
+        None,
+        Arc::new(
+            move |event, _ctx| -> Pin<Box<dyn Future<Output = ()> + Send>> {
+                let notifier = notifier.clone();
+                let leadership = leadership.clone();
+                Box::pin(async move {
+                    if leadership.is_leader() {
+                        notifier.notify(&event).await;
+                    }
+                })
+            },
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{CrudAction, EntityType};
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::NotificationPreferences;
+    use tokio::sync::Mutex;
+    use uuid::Uuid;
+
+    /// Channel recording what it was asked to send
+    struct RecordingChannel {
+        kind: ChannelKind,
+        sent: Mutex<Vec<(Notification, Vec<String>)>>,
+    }
+
+    #[async_trait]
+    impl NotificationChannel for RecordingChannel {
+        fn kind(&self) -> ChannelKind {
+            self.kind
+        }
+
+        async fn send(&self, notification: &Notification, recipients: &[String]) -> Result<()> {
+            self.sent
+                .lock()
+                .await
+                .push((notification.clone(), recipients.to_vec()));
+            Ok(())
+        }
+    }
+
+    fn recording(kind: ChannelKind) -> Arc<RecordingChannel> {
+        Arc::new(RecordingChannel {
+            kind,
+            sent: Mutex::new(Vec::new()),
+        })
+    }
+
+    fn rules(yaml: &str) -> Vec<NotificationRule> {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    fn task_failed() -> CrudEvent {
+        CrudEvent::new(EntityType::Runner, CrudAction::Updated, "run-1").with_payload(
+            serde_json::json!({
+                "event": "task_failed",
+                "run_id": "run-1",
+                "task_id": "task-1",
+                "task_title": "Add login",
+                "reason": "tests failed",
+                "attempts": 2,
+            }),
+        )
+    }
+
+    #[test]
+    fn test_rule_matching() {
+        let rules = rules(
+            r#"
+- name: approved
+  event: plan_approved
+- name: agents
+  event: agent_failed
+- name: synced
+  entity_type: project
+  action: synced
+  payload: {slug: [api, web]}
+"#,
+        );
+        let approved = CrudEvent::new(EntityType::Plan, CrudAction::Updated, "p1")
+            .with_payload(serde_json::json!({"status": "approved"}));
+        let draft = CrudEvent::new(EntityType::Plan, CrudAction::Updated, "p1")
+            .with_payload(serde_json::json!({"status": "draft"}));
+        let synced = |slug: &str| {
+            CrudEvent::new(EntityType::Project, CrudAction::Synced, "pr1")
+                .with_payload(serde_json::json!({ "slug": slug }))
+        };
+
+        assert!(rules[0].matches(&approved));
+        assert!(!rules[0].matches(&draft));
+        assert!(rules[1].matches(&task_failed()));
+        assert!(!rules[1].matches(&approved));
+        assert!(rules[2].matches(&synced("web")));
+        assert!(!rules[2].matches(&synced("docs")));
+    }
+
+    #[test]
+    fn test_render() {
+        let rules =
+            rules("[{name: agents, event: agent_failed}, {name: syncs, entity_type: project}]");
+        let n = Notification::render(&rules[0], &task_failed().with_project_id("proj-1"));
+        assert_eq!(n.subject, "Agent failed: Add login");
+        assert_eq!(
+            n.body,
+            "The agent of task \"Add login\" (task-1) in run run-1 failed after 2 attempt(s): \
+             tests failed.\nProject: proj-1"
+        );
+
+        let n = Notification::render(
+            &rules[1],
+            &CrudEvent::new(EntityType::Project, CrudAction::Synced, "pr1"),
+        );
+        assert_eq!(n.subject, "syncs: project synced");
+
+        let runs = rules("[{name: runs, event: run_reported}]");
+        let reported = CrudEvent::new(EntityType::Runner, CrudAction::Created, "run-1")
+            .with_payload(serde_json::json!({
+                "event": "run_reported",
+                "run_id": "run-1",
+                "plan_title": "Auth",
+                "status": "completed",
+                "tasks_completed": 3,
+                "tasks_failed": 0,
+                "files_changed": 5,
+                "notes_created": 2,
+                "cost_usd": 1.234,
+                "duration_secs": 95.4,
+            }));
+        assert!(runs[0].matches(&reported));
+        assert!(!runs[0].matches(&task_failed()));
+        let n = Notification::render(&runs[0], &reported);
+        assert_eq!(n.subject, "Plan run completed: Auth");
+        assert_eq!(
+            n.body,
+            "3 task(s) completed, 0 failed, 5 file(s) changed, 2 note(s) created.\n\
+             Cost: $1.23, duration: 95s\nReport: /api/runs/run-1"
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let config = |yaml: &str| serde_yaml::from_str::<NotificationsConfig>(yaml).unwrap();
+        let slack = "slack: {webhook_url: https://hooks.slack.com/x}\n";
+        assert!(config(&format!(
+            "{slack}rules: [{{name: a, event: plan_approved}}]"
+        ))
+        .check()
+        .is_ok());
+        assert!(config(&format!(
+            "{slack}rules: [{{name: a, event: plan_approved}}, {{name: a, event: advisory_found}}]"
+        ))
+        .check()
+        .unwrap_err()
+        .contains("duplicate"));
+        assert!(config(&format!("{slack}rules: [{{name: a}}]"))
+            .check()
+            .is_err());
+        assert!(config(&format!(
+            "{slack}rules: [{{name: a, event: plan_approved, channels: [email]}}]"
+        ))
+        .check()
+        .unwrap_err()
+        .contains("not configured"));
+        assert!(config(&format!(
+            "{slack}rules: [{{name: a, event: plan_approved, owners: [api-team]}}]"
+        ))
+        .check()
+        .unwrap_err()
+        .contains("not a user, team or email"));
+    }
+
+    #[tokio::test]
+    async fn test_notify_recipients() {
+        let store = Arc::new(MockGraphStore::new());
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+        store
+            .update_notification_preferences(
+                alice,
+                "alice@example.com",
+                &NotificationPreferences {
+                    email_enabled: true,
+                    muted_rules: vec![],
+                },
+            )
+            .await
+            .unwrap();
+        store
+            .update_notification_preferences(
+                bob,
+                "bob@example.com",
+                &NotificationPreferences {
+                    email_enabled: true,
+                    muted_rules: vec!["agents".to_string()],
+                },
+            )
+            .await
+            .unwrap();
+
+        let email = recording(ChannelKind::Email);
+        let slack = recording(ChannelKind::Slack);
+        let notifier = Notifier::new(
+            rules(
+                r#"
+- name: agents
+  event: agent_failed
+  recipients: [oncall@example.com]
+- name: advisories
+  event: advisory_found
+  channels: [slack]
+"#,
+            ),
+            vec![email.clone(), slack.clone()],
+            store,
+        );
+
+        assert_eq!(notifier.notify(&task_failed()).await, 2);
+        // Bob muted the rule
+        assert_eq!(
+            email.sent.lock().await[0].1,
+            vec![
+                "oncall@example.com".to_string(),
+                "alice@example.com".to_string()
+            ]
+        );
+        assert_eq!(slack.sent.lock().await[0].0.rule, "agents");
+
+        let advisory = CrudEvent::new(EntityType::Advisory, CrudAction::Created, "GHSA-1");
+        assert_eq!(notifier.notify(&advisory).await, 1);
+        assert_eq!(email.sent.lock().await.len(), 1);
+        assert_eq!(slack.sent.lock().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_notify_owners() {
+        use crate::neo4j::models::CodeOwnerRule;
+        use crate::test_helpers::{test_plan_for_project, test_project_named, test_task};
+
+        let store = Arc::new(MockGraphStore::new());
+        let project = test_project_named("shop");
+        store.create_project(&project).await.unwrap();
+        store
+            .replace_code_owners(
+                project.id,
+                &[CodeOwnerRule {
+                    line: 1,
+                    pattern: "/src/api/".to_string(),
+                    owners: vec!["@acme/api".to_string()],
+                }],
+                &[],
+            )
+            .await
+            .unwrap();
+        let plan = test_plan_for_project(project.id);
+        store.create_plan(&plan).await.unwrap();
+        let mut task = test_task();
+        task.affected_files = vec!["/tmp/shop/src/api/users.rs".to_string()];
+        store.create_task(plan.id, &task).await.unwrap();
+
+        let slack = recording(ChannelKind::Slack);
+        let notifier = Notifier::new(
+            rules("[{name: api, event: agent_failed, owners: ['@acme/api']}]"),
+            vec![slack.clone()],
+            store,
+        );
+        let failed = |payload: serde_json::Value| {
+            CrudEvent::new(EntityType::Runner, CrudAction::Updated, "run-1")
+                .with_payload(payload)
+                .with_project_id(project.id.to_string())
+        };
+
+        // Files of the task
+        let api_task = failed(serde_json::json!({
+            "event": "task_failed", "task_id": task.id.to_string()
+        }));
+        assert_eq!(notifier.notify(&api_task).await, 1);
+        // Files of the payload
+        let web = failed(serde_json::json!({
+            "event": "task_failed", "files": ["/tmp/shop/src/web/app.rs"]
+        }));
+        assert_eq!(notifier.notify(&web).await, 0);
+        // Without a project, owners cannot be resolved
+        let mut unscoped = api_task.clone();
+        unscoped.project_id = None;
+        assert_eq!(notifier.notify(&unscoped).await, 0);
+        assert_eq!(slack.sent.lock().await.len(), 1);
+    }
+}
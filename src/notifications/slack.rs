@@ -0,0 +1,46 @@
+//! Slack channel — posts to an incoming webhook.
+
+use super::{ChannelKind, Notification, NotificationChannel, SlackConfig};
+use anyhow::Result;
+use async_trait::async_trait;
+use std::time::Duration;
+
+/// Posts notifications to the Slack channel of an incoming webhook.
+pub struct SlackChannel {
+    webhook_url: String,
+    client: reqwest::Client,
+}
+
+impl SlackChannel {
+    pub fn new(config: &SlackConfig) -> Self {
+        Self {
+            webhook_url: config.webhook_url.clone(),
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_else(|_| reqwest::Client::new()),
+        }
+    }
+}
+
+/// Slack message text: the subject in bold, then the body.
+fn message_text(notification: &Notification) -> String {
+    format!("*{}*\n{}", notification.subject, notification.body)
+}
+
+#[async_trait]
+impl NotificationChannel for SlackChannel {
+    fn kind(&self) -> ChannelKind {
+        ChannelKind::Slack
+    }
+
+    async fn send(&self, notification: &Notification, _recipients: &[String]) -> Result<()> {
+        self.client
+            .post(&self.webhook_url)
+            .json(&serde_json::json!({ "text": message_text(notification) }))
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
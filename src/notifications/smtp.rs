@@ -0,0 +1,78 @@
+//! Email channel — sends one message per recipient through an SMTP server.
+
+use super::{ChannelKind, Notification, NotificationChannel, SmtpConfig, SmtpTls};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use lettre::message::{header::ContentType, Mailbox};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+/// Subject prefix of the notification emails
+const SUBJECT_PREFIX: &str = "[Project Orchestrator]";
+
+/// Sends notifications by email.
+pub struct SmtpChannel {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpChannel {
+    pub fn new(config: &SmtpConfig) -> Result<Self> {
+        let mut builder = match config.tls {
+            SmtpTls::Starttls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+                .with_context(|| format!("Invalid SMTP host '{}'", config.host))?,
+            SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)
+                .with_context(|| format!("Invalid SMTP host '{}'", config.host))?,
+            SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host),
+        };
+        if let Some(port) = config.port {
+            builder = builder.port(port);
+        }
+        if let Some(ref username) = config.username {
+            builder = builder.credentials(Credentials::new(
+                username.clone(),
+                config.password.clone().unwrap_or_default(),
+            ));
+        }
+        Ok(Self {
+            transport: builder.build(),
+            from: config
+                .from
+                .parse()
+                .with_context(|| format!("Invalid SMTP sender '{}'", config.from))?,
+        })
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for SmtpChannel {
+    fn kind(&self) -> ChannelKind {
+        ChannelKind::Email
+    }
+
+    async fn send(&self, notification: &Notification, recipients: &[String]) -> Result<()> {
+        let mut failures = Vec::new();
+        for recipient in recipients {
+            let to: Mailbox = match recipient.parse() {
+                Ok(to) => to,
+                Err(e) => {
+                    failures.push(format!("{}: {}", recipient, e));
+                    continue;
+                }
+            };
+            let message = Message::builder()
+                .from(self.from.clone())
+                .to(to)
+                .subject(format!("{} {}", SUBJECT_PREFIX, notification.subject))
+                .header(ContentType::TEXT_PLAIN)
+                .body(notification.body.clone())?;
+            if let Err(e) = self.transport.send(message).await {
+                failures.push(format!("{}: {}", recipient, e));
+            }
+        }
+        if !failures.is_empty() {
+            anyhow::bail!("{}", failures.join("; "));
+        }
+        Ok(())
+    }
+}
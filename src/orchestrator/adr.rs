@@ -0,0 +1,342 @@
+//! ADR notebook: the decisions of a project as files of its repository.
+//!
+//! [`export_adrs`] renders the decisions of a project into
+//! `docs/adr/NNN-title.md` (see [`crate::parser::adr`]) and records the file
+//! of each decision on a `HAS_ADR` edge, so the next export rewrites the
+//! same file. `POST /api/projects/{id}/adrs/export` runs it on demand; new
+//! decisions are exported as they are recorded when the project already has
+//! a `docs/adr` directory.
+//!
+//! [`import_adrs`] runs during sync and reads the files back: a file
+//! matching a decision (by its `Decision-Id` line, else by path) updates it,
+//! any other file — typically a hand-written ADR — becomes a new decision of
+//! the project. Removing a file never deletes a decision.
+
+use crate::neo4j::models::{AdrLink, DecisionNode, ProjectDecision, ProjectNode};
+use crate::neo4j::traits::GraphStore;
+use crate::parser::adr::{self, ParsedAdr, ADR_DIR};
+use anyhow::{bail, Context, Result};
+use chrono::{TimeZone, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// ADR files larger than this are not decisions
+const MAX_ADR_BYTES: u64 = 256 * 1024;
+
+/// Outcome of an export
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AdrExportReport {
+    /// Files created or rewritten, relative to the project root
+    pub written: Vec<String>,
+    /// Files already up to date
+    pub unchanged: usize,
+}
+
+/// Outcome of an import
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AdrImportReport {
+    pub created: usize,
+    pub updated: usize,
+}
+
+fn project_root(project: &ProjectNode) -> PathBuf {
+    PathBuf::from(crate::expand_tilde(&project.root_path))
+}
+
+/// Whether the repository of a project keeps an ADR directory.
+pub fn has_adr_dir(project: &ProjectNode) -> bool {
+    project_root(project).join(ADR_DIR).is_dir()
+}
+
+/// ADR files of a directory as `(file name, content)`, by name
+async fn read_adr_files(dir: &Path) -> Result<Vec<(String, String)>> {
+    let mut files = Vec::new();
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(files),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", dir.display())),
+    };
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Ok(meta) = entry.metadata().await else {
+            continue;
+        };
+        if !meta.is_file() || meta.len() > MAX_ADR_BYTES || !adr::is_adr_file(&name) {
+            continue;
+        }
+        match tokio::fs::read_to_string(entry.path()).await {
+            Ok(content) => files.push((name, content)),
+            Err(e) => tracing::warn!("Failed to read ADR {}: {}", name, e),
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Highest ADR number used by the decisions or the files
+fn last_number(decisions: &[ProjectDecision], files: &[(String, String)]) -> u32 {
+    let linked = decisions
+        .iter()
+        .filter_map(|d| d.adr.as_ref().map(|a| a.number));
+    let on_disk = files
+        .iter()
+        .filter_map(|(name, content)| adr::parse_adr(name, content).number);
+    linked.chain(on_disk).max().unwrap_or(0)
+}
+
+/// Write the ADR files of the decisions of a project (only `decision_id`
+/// when given). Decisions without a file get the next free number.
+pub async fn export_adrs(
+    graph: &dyn GraphStore,
+    project: &ProjectNode,
+    decision_id: Option<Uuid>,
+) -> Result<AdrExportReport> {
+    let root = project_root(project);
+    let dir = root.join(ADR_DIR);
+    let decisions = graph.list_project_decisions(project.id).await?;
+    if let Some(id) = decision_id {
+        if !decisions.iter().any(|d| d.decision.id == id) {
+            bail!(
+                "Decision {} is not a decision of project {}",
+                id,
+                project.slug
+            );
+        }
+    }
+
+    let files = read_adr_files(&dir).await?;
+    let mut next = last_number(&decisions, &files) + 1;
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    let mut report = AdrExportReport::default();
+    for pd in &decisions {
+        if decision_id.is_some_and(|id| id != pd.decision.id) {
+            continue;
+        }
+        let link = match &pd.adr {
+            Some(link) => link.clone(),
+            None => {
+                let number = next;
+                next += 1;
+                AdrLink {
+                    number,
+                    path: format!("{}/{}", ADR_DIR, adr::adr_file_name(number, &pd.decision)),
+                }
+            }
+        };
+
+        let content = adr::render_adr(link.number, &pd.decision);
+        let path = root.join(&link.path);
+        if tokio::fs::read_to_string(&path).await.ok().as_deref() == Some(content.as_str()) {
+            report.unchanged += 1;
+        } else {
+            tokio::fs::write(&path, content)
+                .await
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            report.written.push(link.path.clone());
+        }
+        if pd.adr.is_none() {
+            graph
+                .set_decision_adr(project.id, pd.decision.id, &link)
+                .await?;
+        }
+    }
+    Ok(report)
+}
+
+fn parsed_decision(parsed: &ParsedAdr) -> DecisionNode {
+    DecisionNode {
+        id: Uuid::new_v4(),
+        description: parsed.description.clone(),
+        rationale: parsed.rationale.clone(),
+        alternatives: parsed.alternatives.clone(),
+        chosen_option: parsed.chosen_option.clone(),
+        decided_by: parsed.deciders.clone().unwrap_or_else(|| "adr".to_string()),
+        decided_at: parsed
+            .date
+            .and_then(|d| d.and_hms_opt(0, 0, 0))
+            .map(|d| Utc.from_utc_datetime(&d))
+            .unwrap_or_else(Utc::now),
+        status: parsed.status,
+        embedding: None,
+        embedding_model: None,
+        scar_intensity: 0.0,
+    }
+}
+
+/// Read the ADR files of a project's repository into its decisions.
+pub async fn import_adrs(
+    graph: &dyn GraphStore,
+    project_id: Uuid,
+    root: &Path,
+) -> Result<AdrImportReport> {
+    let files = read_adr_files(&root.join(ADR_DIR)).await?;
+    let mut report = AdrImportReport::default();
+    if files.is_empty() {
+        return Ok(report);
+    }
+
+    let decisions = graph.list_project_decisions(project_id).await?;
+    let by_id: HashMap<Uuid, &ProjectDecision> =
+        decisions.iter().map(|d| (d.decision.id, d)).collect();
+    let by_path: HashMap<&str, &ProjectDecision> = decisions
+        .iter()
+        .filter_map(|d| d.adr.as_ref().map(|a| (a.path.as_str(), d)))
+        .collect();
+    let mut next = last_number(&decisions, &files) + 1;
+
+    for (name, content) in &files {
+        let path = format!("{}/{}", ADR_DIR, name);
+        let parsed = adr::parse_adr(name, content);
+        let matched = parsed
+            .decision_id
+            .and_then(|id| by_id.get(&id))
+            .or_else(|| by_path.get(path.as_str()));
+
+        let Some(pd) = matched else {
+            let number = parsed.number.unwrap_or_else(|| {
+                next += 1;
+                next - 1
+            });
+            let decision = parsed_decision(&parsed);
+            graph
+                .create_adr_decision(project_id, &decision, &AdrLink { number, path })
+                .await?;
+            report.created += 1;
+            continue;
+        };
+
+        let d = &pd.decision;
+        let chosen = parsed.chosen_option.clone().unwrap_or_default();
+        if d.description != parsed.description
+            || d.rationale != parsed.rationale
+            || d.chosen_option.clone().unwrap_or_default() != chosen
+            || d.status != parsed.status
+        {
+            graph
+                .update_decision(
+                    d.id,
+                    Some(parsed.description.clone()),
+                    Some(parsed.rationale.clone()),
+                    Some(chosen),
+                    Some(parsed.status),
+                )
+                .await?;
+            report.updated += 1;
+        }
+        if pd.adr.as_ref().map(|a| a.path.as_str()) != Some(path.as_str()) {
+            let number = parsed
+                .number
+                .or(pd.adr.as_ref().map(|a| a.number))
+                .unwrap_or(0);
+            graph
+                .set_decision_adr(project_id, d.id, &AdrLink { number, path })
+                .await?;
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::DecisionStatus;
+    use crate::test_helpers::{test_decision, test_plan_for_project, test_project, test_task};
+
+    fn decision(description: &str) -> DecisionNode {
+        test_decision(description, "Because.")
+    }
+
+    async fn setup(dir: &Path) -> (MockGraphStore, ProjectNode, Uuid) {
+        let graph = MockGraphStore::new();
+        let mut project = test_project();
+        project.root_path = dir.to_string_lossy().to_string();
+        graph.create_project(&project).await.unwrap();
+        let plan = test_plan_for_project(project.id);
+        graph.create_plan(&plan).await.unwrap();
+        let task = test_task();
+        graph.create_task(plan.id, &task).await.unwrap();
+        (graph, project, task.id)
+    }
+
+    #[tokio::test]
+    async fn test_export_adrs() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(ADR_DIR)).unwrap();
+        std::fs::write(
+            dir.path().join(ADR_DIR).join("0004-hand-written.md"),
+            "# Hand written\n",
+        )
+        .unwrap();
+        let (graph, project, task_id) = setup(dir.path()).await;
+        let first = decision("Use Neo4j");
+        graph.create_decision(task_id, &first).await.unwrap();
+
+        let report = export_adrs(&graph, &project, None).await.unwrap();
+        assert_eq!(report.written, vec!["docs/adr/005-use-neo4j.md"]);
+        let content = std::fs::read_to_string(dir.path().join(&report.written[0])).unwrap();
+        assert!(content.starts_with("# 5. Use Neo4j\n"));
+
+        // Re-exports keep files; new decisions take the next number
+        let mut second = decision("Use Meilisearch");
+        second.decided_at = first.decided_at + chrono::Duration::seconds(1);
+        graph.create_decision(task_id, &second).await.unwrap();
+        let report = export_adrs(&graph, &project, None).await.unwrap();
+        assert_eq!(report.written, vec!["docs/adr/006-use-meilisearch.md"]);
+        assert_eq!(report.unchanged, 1);
+
+        let err = export_adrs(&graph, &project, Some(Uuid::new_v4()))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not a decision of project"));
+    }
+
+    #[tokio::test]
+    async fn test_import_adrs() {
+        let dir = tempfile::tempdir().unwrap();
+        let (graph, project, task_id) = setup(dir.path()).await;
+        let exported = decision("Use Neo4j");
+        graph.create_decision(task_id, &exported).await.unwrap();
+        export_adrs(&graph, &project, None).await.unwrap();
+
+        // Edit the exported ADR and add a hand-written one
+        let adr_dir = dir.path().join(ADR_DIR);
+        let path = adr_dir.join("001-use-neo4j.md");
+        let edited = std::fs::read_to_string(&path)
+            .unwrap()
+            .replace("Because.", "Relationships are first-class.");
+        std::fs::write(&path, edited).unwrap();
+        std::fs::write(
+            adr_dir.join("0002-record-decisions.md"),
+            "# 2. Record decisions\n\nDate: 2024-05-01\n\n## Status\n\nProposed\n\n## Decision\n\nWe keep ADRs.\n",
+        )
+        .unwrap();
+        std::fs::write(adr_dir.join("README.md"), "# Decisions\n").unwrap();
+
+        let report = import_adrs(&graph, project.id, dir.path()).await.unwrap();
+        assert_eq!((report.created, report.updated), (1, 1));
+        let stored = graph.get_decision(exported.id).await.unwrap().unwrap();
+        assert_eq!(stored.rationale, "Relationships are first-class.");
+
+        let decisions = graph.list_project_decisions(project.id).await.unwrap();
+        let imported = decisions.iter().find(|d| d.task_id.is_none()).unwrap();
+        assert_eq!(imported.decision.description, "We keep ADRs.");
+        assert_eq!(imported.decision.status, DecisionStatus::Proposed);
+        assert_eq!(
+            imported.adr,
+            Some(AdrLink {
+                number: 2,
+                path: "docs/adr/0002-record-decisions.md".to_string()
+            })
+        );
+
+        // Syncing again is a no-op
+        let report = import_adrs(&graph, project.id, dir.path()).await.unwrap();
+        assert_eq!((report.created, report.updated), (0, 0));
+    }
+}
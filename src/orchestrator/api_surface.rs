@@ -0,0 +1,367 @@
+//! Public API surface of a project.
+//!
+//! The surface is the set of public functions, structs, traits and enums of a
+//! project with their signatures, grouped by module (the file, relative to
+//! the project root). Constants are not part of the code graph and are left
+//! out.
+//!
+//! After each sync the surface is recorded on an `(:ApiSurfaceSnapshot)` node
+//! when it changed since the previous snapshot, so that two sync points can be
+//! compared: removed symbols and changed signatures are breaking changes.
+
+use crate::neo4j::models::{ApiSurfaceSnapshotNode, ApiSymbol, ProjectNode};
+use crate::neo4j::traits::GraphStore;
+use anyhow::Result;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// Snapshots kept per project, the latest ones
+pub const MAX_SNAPSHOTS: usize = 50;
+/// Shortest commit prefix accepted to name a snapshot
+const MIN_COMMIT_PREFIX: usize = 7;
+
+/// The public symbols of one module
+#[derive(Debug, Serialize)]
+pub struct ApiModule {
+    pub module: String,
+    pub symbols: Vec<ApiSymbol>,
+}
+
+/// A symbol whose signature differs between two surfaces
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ApiSymbolChange {
+    pub module: String,
+    pub name: String,
+    pub kind: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// Differences between two API surfaces
+#[derive(Debug, Default, Serialize)]
+pub struct ApiSurfaceDiff {
+    pub added: Vec<ApiSymbol>,
+    pub removed: Vec<ApiSymbol>,
+    pub changed: Vec<ApiSymbolChange>,
+    /// Whether a symbol was removed or changed its signature
+    pub breaking: bool,
+}
+
+/// The current public surface of a project, sorted by module, name and kind.
+pub async fn current_surface(
+    neo4j: &dyn GraphStore,
+    project: &ProjectNode,
+) -> Result<Vec<ApiSymbol>> {
+    let roots = super::project_roots(&project.root_path);
+    let mut symbols: Vec<ApiSymbol> = neo4j
+        .get_project_public_symbols(project.id)
+        .await?
+        .into_iter()
+        .map(|row| ApiSymbol {
+            module: super::relative_to_roots(&row.file_path, &roots).to_string(),
+            name: row.name,
+            kind: row.kind,
+            signature: row.signature,
+        })
+        .collect();
+    symbols.sort();
+    symbols.dedup();
+    Ok(symbols)
+}
+
+/// Group sorted symbols by module.
+pub fn group_by_module(symbols: Vec<ApiSymbol>) -> Vec<ApiModule> {
+    let mut modules: Vec<ApiModule> = Vec::new();
+    for symbol in symbols {
+        match modules.last_mut() {
+            Some(module) if module.module == symbol.module => module.symbols.push(symbol),
+            _ => modules.push(ApiModule {
+                module: symbol.module.clone(),
+                symbols: vec![symbol],
+            }),
+        }
+    }
+    modules
+}
+
+fn surface_hash(symbols: &[ApiSymbol]) -> String {
+    let mut hasher = Sha256::new();
+    for symbol in symbols {
+        for part in [
+            &symbol.module,
+            &symbol.name,
+            &symbol.kind,
+            &symbol.signature,
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        hasher.update([b'\n']);
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Record the current surface of a project, unless it is the same as in the
+/// latest snapshot. Returns the new snapshot.
+pub async fn record_snapshot(
+    neo4j: &dyn GraphStore,
+    project_id: Uuid,
+) -> Result<Option<ApiSurfaceSnapshotNode>> {
+    let project = neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Project {} not found", project_id))?;
+    let symbols = current_surface(neo4j, &project).await?;
+    let hash = surface_hash(&symbols);
+    let latest = neo4j.list_api_surface_snapshots(project_id).await?;
+    if latest.first().is_some_and(|s| s.hash == hash) {
+        return Ok(None);
+    }
+
+    let commit = match super::project_roots(&project.root_path).first() {
+        Some(root) => super::diff_sync::resolve_commit(root, "HEAD")
+            .await
+            .unwrap_or(None),
+        None => None,
+    };
+    let snapshot = ApiSurfaceSnapshotNode {
+        id: Uuid::new_v4(),
+        project_id,
+        commit,
+        hash,
+        symbol_count: symbols.len(),
+        symbols,
+        created_at: chrono::Utc::now(),
+    };
+    neo4j
+        .create_api_surface_snapshot(&snapshot, MAX_SNAPSHOTS)
+        .await?;
+    Ok(Some(snapshot))
+}
+
+/// The snapshot of a project named by `reference`: its id, a commit SHA
+/// (7 characters at least) or a branch or tag of the project repository. A
+/// commit names the latest snapshot taken at that commit.
+pub async fn find_snapshot(
+    neo4j: &dyn GraphStore,
+    project: &ProjectNode,
+    reference: &str,
+) -> Result<Option<ApiSurfaceSnapshotNode>> {
+    if let Ok(id) = reference.parse::<Uuid>() {
+        return neo4j.get_api_surface_snapshot(project.id, id).await;
+    }
+    let snapshots = neo4j.list_api_surface_snapshots(project.id).await?;
+    let at_commit = |sha: &str| {
+        snapshots
+            .iter()
+            .find(|s| s.commit.as_deref().is_some_and(|c| c.starts_with(sha)))
+            .map(|s| s.id)
+    };
+    let mut id = None;
+    if reference.len() >= MIN_COMMIT_PREFIX {
+        id = at_commit(reference);
+    }
+    if id.is_none() {
+        if let Some(root) = super::project_roots(&project.root_path).first() {
+            if let Ok(Some(sha)) = super::diff_sync::resolve_commit(root, reference).await {
+                id = at_commit(&sha);
+            }
+        }
+    }
+    match id {
+        Some(id) => neo4j.get_api_surface_snapshot(project.id, id).await,
+        None => Ok(None),
+    }
+}
+
+/// Compare two surfaces. Symbols are matched by module, name and kind; one
+/// symbol on each side with different signatures is a change, anything else
+/// (overloads, same-named methods of several types) is compared as sets.
+pub fn diff_surfaces(before: &[ApiSymbol], after: &[ApiSymbol]) -> ApiSurfaceDiff {
+    type Key<'a> = (&'a str, &'a str, &'a str);
+    let mut sides: BTreeMap<Key, (Vec<&ApiSymbol>, Vec<&ApiSymbol>)> = BTreeMap::new();
+    for symbol in before {
+        let key = (
+            symbol.module.as_str(),
+            symbol.name.as_str(),
+            symbol.kind.as_str(),
+        );
+        sides.entry(key).or_default().0.push(symbol);
+    }
+    for symbol in after {
+        let key = (
+            symbol.module.as_str(),
+            symbol.name.as_str(),
+            symbol.kind.as_str(),
+        );
+        sides.entry(key).or_default().1.push(symbol);
+    }
+
+    let mut diff = ApiSurfaceDiff::default();
+    for (old, new) in sides.into_values() {
+        match (old.as_slice(), new.as_slice()) {
+            ([old], [new]) if old.signature != new.signature => {
+                diff.changed.push(ApiSymbolChange {
+                    module: new.module.clone(),
+                    name: new.name.clone(),
+                    kind: new.kind.clone(),
+                    before: old.signature.clone(),
+                    after: new.signature.clone(),
+                });
+            }
+            _ => {
+                diff.removed.extend(
+                    old.iter()
+                        .filter(|s| !new.contains(s))
+                        .map(|s| (*s).clone()),
+                );
+                diff.added.extend(
+                    new.iter()
+                        .filter(|s| !old.contains(s))
+                        .map(|s| (*s).clone()),
+                );
+            }
+        }
+    }
+    diff.breaking = !diff.removed.is_empty() || !diff.changed.is_empty();
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::{FunctionNode, Parameter, Visibility};
+
+    fn symbol(module: &str, name: &str, signature: &str) -> ApiSymbol {
+        ApiSymbol {
+            module: module.to_string(),
+            name: name.to_string(),
+            kind: "function".to_string(),
+            signature: signature.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_diff_surfaces() {
+        let before = vec![
+            symbol("src/lib.rs", "load", "fn load(path: &Path)"),
+            symbol("src/lib.rs", "save", "fn save()"),
+            symbol("src/lib.rs", "new", "fn new()"),
+            symbol("src/lib.rs", "new", "fn new(cap: usize)"),
+        ];
+        let after = vec![
+            symbol("src/lib.rs", "load", "fn load(path: &Path, strict: bool)"),
+            symbol("src/lib.rs", "new", "fn new()"),
+            symbol("src/lib.rs", "new", "fn new(capacity: usize)"),
+            symbol("src/io.rs", "save", "fn save()"),
+        ];
+        let diff = diff_surfaces(&before, &after);
+        assert_eq!(
+            diff.changed,
+            vec![ApiSymbolChange {
+                module: "src/lib.rs".to_string(),
+                name: "load".to_string(),
+                kind: "function".to_string(),
+                before: "fn load(path: &Path)".to_string(),
+                after: "fn load(path: &Path, strict: bool)".to_string(),
+            }]
+        );
+        assert_eq!(
+            diff.removed,
+            vec![
+                symbol("src/lib.rs", "new", "fn new(cap: usize)"),
+                symbol("src/lib.rs", "save", "fn save()"),
+            ]
+        );
+        assert_eq!(
+            diff.added,
+            vec![
+                symbol("src/io.rs", "save", "fn save()"),
+                symbol("src/lib.rs", "new", "fn new(capacity: usize)"),
+            ]
+        );
+        assert!(diff.breaking);
+        assert!(!diff_surfaces(&before, &before).breaking);
+    }
+
+    #[test]
+    fn test_group_by_module() {
+        let modules = group_by_module(vec![
+            symbol("src/io.rs", "read", "fn read()"),
+            symbol("src/lib.rs", "load", "fn load()"),
+            symbol("src/lib.rs", "save", "fn save()"),
+        ]);
+        assert_eq!(modules.len(), 2);
+        assert_eq!(modules[1].module, "src/lib.rs");
+        assert_eq!(modules[1].symbols.len(), 2);
+    }
+
+    async fn add_function(graph: &MockGraphStore, project_id: Uuid, name: &str, params: &[&str]) {
+        graph
+            .link_file_to_project("/repo/src/lib.rs", project_id)
+            .await
+            .unwrap();
+        graph
+            .upsert_function(&FunctionNode {
+                name: name.to_string(),
+                visibility: Visibility::Public,
+                params: params
+                    .iter()
+                    .map(|p| Parameter {
+                        name: p.to_string(),
+                        type_name: Some("u32".to_string()),
+                    })
+                    .collect(),
+                return_type: None,
+                generics: vec![],
+                is_async: false,
+                is_unsafe: false,
+                complexity: 1,
+                file_path: "/repo/src/lib.rs".to_string(),
+                line_start: 1,
+                line_end: 3,
+                docstring: None,
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_record_snapshot() {
+        let graph = MockGraphStore::new();
+        let mut project = crate::test_helpers::test_project_named("surfaced");
+        project.root_path = "/repo".to_string();
+        graph.create_project(&project).await.unwrap();
+        add_function(&graph, project.id, "start", &["port"]).await;
+
+        let first = record_snapshot(&graph, project.id).await.unwrap().unwrap();
+        assert_eq!(
+            first.symbols,
+            vec![symbol("src/lib.rs", "start", "fn start(port: u32)")]
+        );
+
+        // An unchanged surface records nothing
+        assert!(record_snapshot(&graph, project.id).await.unwrap().is_none());
+
+        add_function(&graph, project.id, "start", &["port", "retries"]).await;
+        let second = record_snapshot(&graph, project.id).await.unwrap().unwrap();
+        let snapshots = graph.list_api_surface_snapshots(project.id).await.unwrap();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].id, second.id);
+        assert!(snapshots[0].symbols.is_empty());
+
+        let found = find_snapshot(&graph, &project, &first.id.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        let diff = diff_surfaces(&found.symbols, &second.symbols);
+        assert_eq!(diff.changed[0].after, "fn start(port: u32, retries: u32)");
+        assert!(find_snapshot(&graph, &project, "abc")
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
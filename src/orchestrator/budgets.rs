@@ -0,0 +1,246 @@
+//! Complexity and size budgets.
+//!
+//! A project declares limits on its Project node ([`ProjectBudgets`]):
+//! function complexity, file length and module fan-in, a module being a
+//! directory relative to the project root. [`check_budgets`] measures the
+//! synced code against them. It runs after each sync, storing the report on
+//! the project, and on demand from `GET /api/projects/{id}/budget-check` for
+//! CI gates.
+
+use crate::neo4j::models::{BudgetCheckReport, BudgetViolation, ProjectBudgets, ProjectNode};
+use crate::neo4j::traits::GraphStore;
+use anyhow::Result;
+use std::collections::{BTreeMap, HashSet};
+
+/// Violations kept in a report (`violation_count` keeps the total)
+pub const MAX_REPORTED_VIOLATIONS: usize = 500;
+
+/// Directory of a project-relative path, "." for files at the root
+pub(super) fn module_of(relative: &str) -> &str {
+    relative.rsplit_once('/').map_or(".", |(dir, _)| dir)
+}
+
+/// Number of distinct files outside each module importing a file inside it.
+fn module_fan_in(edges: &[(String, String)], roots: &[String]) -> BTreeMap<String, u32> {
+    let mut importers: BTreeMap<String, HashSet<&str>> = BTreeMap::new();
+    for (source, target) in edges {
+        let source_module = module_of(super::relative_to_roots(source, roots));
+        let target_module = module_of(super::relative_to_roots(target, roots));
+        if source_module != target_module {
+            importers
+                .entry(target_module.to_string())
+                .or_default()
+                .insert(source);
+        }
+    }
+    importers
+        .into_iter()
+        .map(|(module, files)| (module, files.len() as u32))
+        .collect()
+}
+
+pub(super) fn violation(budget: &str, target: String, actual: u64, limit: u32) -> BudgetViolation {
+    BudgetViolation {
+        budget: budget.to_string(),
+        target,
+        actual,
+        limit: limit as u64,
+    }
+}
+
+/// Order by overshoot ratio (worst first), then budget and target, and cap.
+pub(super) fn finish_violations(
+    mut violations: Vec<BudgetViolation>,
+) -> (Vec<BudgetViolation>, usize) {
+    let ratio = |v: &BudgetViolation| v.actual as f64 / v.limit.max(1) as f64;
+    violations.sort_by(|a, b| {
+        ratio(b)
+            .total_cmp(&ratio(a))
+            .then_with(|| a.budget.cmp(&b.budget))
+            .then_with(|| a.target.cmp(&b.target))
+    });
+    let count = violations.len();
+    violations.truncate(MAX_REPORTED_VIOLATIONS);
+    (violations, count)
+}
+
+/// Check the synced code of `project` against `budgets`.
+///
+/// File lengths are read from disk; files that cannot be read are skipped.
+pub async fn check_budgets(
+    neo4j: &dyn GraphStore,
+    project: &ProjectNode,
+    budgets: &ProjectBudgets,
+) -> Result<BudgetCheckReport> {
+    let roots = super::project_roots(&project.root_path);
+    let mut violations = Vec::new();
+
+    if let Some(limit) = budgets.max_function_complexity {
+        for func in neo4j
+            .get_functions_above_complexity(project.id, limit)
+            .await?
+        {
+            violations.push(violation(
+                "max_function_complexity",
+                func.id,
+                func.complexity as u64,
+                limit,
+            ));
+        }
+    }
+
+    if let Some(limit) = budgets.max_file_lines {
+        for file in neo4j.list_project_files(project.id).await? {
+            let Ok(content) = tokio::fs::read_to_string(&file.path).await else {
+                continue;
+            };
+            let lines = content.lines().count() as u64;
+            if lines > limit as u64 {
+                let relative = super::relative_to_roots(&file.path, &roots).to_string();
+                violations.push(violation("max_file_lines", relative, lines, limit));
+            }
+        }
+    }
+
+    if let Some(limit) = budgets.max_module_fan_in {
+        let edges = neo4j.get_project_import_edges(project.id).await?;
+        for (module, fan_in) in module_fan_in(&edges, &roots) {
+            if fan_in > limit {
+                violations.push(violation("max_module_fan_in", module, fan_in as u64, limit));
+            }
+        }
+    }
+
+    let (violations, violation_count) = finish_violations(violations);
+    Ok(BudgetCheckReport {
+        project_id: project.id,
+        passed: violation_count == 0,
+        budgets: budgets.clone(),
+        violations,
+        violation_count,
+        checked_at: chrono::Utc::now(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::{FileNode, FunctionNode, Visibility};
+    use crate::test_helpers::test_project_named;
+
+    fn edge(source: &str, target: &str) -> (String, String) {
+        (source.to_string(), target.to_string())
+    }
+
+    #[test]
+    fn test_module_fan_in() {
+        let roots = vec!["/repo".to_string()];
+        let edges = vec![
+            edge("/repo/src/api/a.rs", "/repo/src/neo4j/client.rs"),
+            edge("/repo/src/api/b.rs", "/repo/src/neo4j/models.rs"),
+            // Same importer counted once per module
+            edge("/repo/src/api/b.rs", "/repo/src/neo4j/client.rs"),
+            // Imports inside a module do not count
+            edge("/repo/src/neo4j/mock.rs", "/repo/src/neo4j/models.rs"),
+            edge("/repo/main.rs", "/repo/src/api/a.rs"),
+        ];
+        let fan_in = module_fan_in(&edges, &roots);
+        assert_eq!(fan_in.get("src/neo4j"), Some(&2));
+        assert_eq!(fan_in.get("src/api"), Some(&1));
+        assert_eq!(fan_in.get("."), None);
+    }
+
+    #[test]
+    fn test_finish_violations_orders_by_overshoot() {
+        let (violations, count) = finish_violations(vec![
+            violation("max_file_lines", "a.rs".into(), 1100, 1000),
+            violation("max_function_complexity", "a.rs:f:1".into(), 30, 10),
+            violation("max_module_fan_in", "src".into(), 12, 8),
+        ]);
+        assert_eq!(count, 3);
+        let targets: Vec<&str> = violations.iter().map(|v| v.target.as_str()).collect();
+        assert_eq!(targets, ["a.rs:f:1", "src", "a.rs"]);
+    }
+
+    #[tokio::test]
+    async fn test_check_budgets() {
+        let dir = tempfile::tempdir().unwrap();
+        let graph = MockGraphStore::new();
+        let mut project = test_project_named("budgets");
+        project.root_path = dir.path().to_string_lossy().to_string();
+        graph.create_project(&project).await.unwrap();
+
+        let mut paths = Vec::new();
+        for (name, lines) in [("src/long.rs", 30), ("src/short.rs", 5), ("main.rs", 3)] {
+            let path = dir.path().join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, "// line\n".repeat(lines)).unwrap();
+            let path = path.to_string_lossy().to_string();
+            graph
+                .upsert_file(&FileNode {
+                    path: path.clone(),
+                    language: "rust".to_string(),
+                    hash: "h".to_string(),
+                    line_count: 0,
+                    last_parsed: chrono::Utc::now(),
+                    project_id: Some(project.id),
+                    encoding: None,
+                })
+                .await
+                .unwrap();
+            graph.link_file_to_project(&path, project.id).await.unwrap();
+            paths.push(path);
+        }
+        for (name, complexity) in [("tangled", 25), ("simple", 3)] {
+            graph
+                .upsert_function(&FunctionNode {
+                    name: name.to_string(),
+                    visibility: Visibility::Public,
+                    params: vec![],
+                    return_type: None,
+                    generics: vec![],
+                    is_async: false,
+                    is_unsafe: false,
+                    complexity,
+                    file_path: paths[0].clone(),
+                    line_start: 1,
+                    line_end: 10,
+                    docstring: None,
+                })
+                .await
+                .unwrap();
+        }
+        graph
+            .create_import_relationship(&paths[2], &paths[0], "src::long")
+            .await
+            .unwrap();
+
+        let budgets = ProjectBudgets {
+            max_function_complexity: Some(15),
+            max_file_lines: Some(20),
+            max_module_fan_in: Some(0),
+        };
+        let report = check_budgets(&graph, &project, &budgets).await.unwrap();
+        assert!(!report.passed);
+        assert_eq!(report.violation_count, 3);
+        let found: Vec<(&str, &str)> = report
+            .violations
+            .iter()
+            .map(|v| (v.budget.as_str(), v.target.as_str()))
+            .collect();
+        let tangled = format!("{}:tangled:1", paths[0]);
+        assert!(found.contains(&("max_function_complexity", tangled.as_str())));
+        assert!(found.contains(&("max_file_lines", "src/long.rs")));
+        assert!(found.contains(&("max_module_fan_in", "src")));
+
+        let relaxed = ProjectBudgets {
+            max_function_complexity: Some(30),
+            max_file_lines: Some(100),
+            max_module_fan_in: None,
+        };
+        let report = check_budgets(&graph, &project, &relaxed).await.unwrap();
+        assert!(report.passed);
+        assert!(report.violations.is_empty());
+    }
+}
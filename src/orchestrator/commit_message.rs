@@ -0,0 +1,621 @@
+//! Commit message suggestions.
+//!
+//! [`suggest_commit_message`] maps a unified diff — by default the working
+//! diff of the project, see [`working_diff`] — onto the graph symbols it
+//! touches and the open tasks expecting the changed files, and proposes a
+//! conventional commit message: `type(scope): subject`, a body listing the
+//! touched symbols per file, and a `Refs:` footer per task.
+//! `POST /api/projects/{id}/commit-message` and the `commit` tool's
+//! `suggest_message` action serve it.
+//!
+//! The type is a heuristic (file kinds, task titles and tags, added files);
+//! the proposal is a starting point for the author, not a final message.
+
+use super::review::{DiffFile, FileChange};
+use crate::neo4j::models::{ProjectNode, TaskStatus, TaskWithPlan};
+use crate::neo4j::traits::GraphStore;
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// Files described per suggestion, in diff order
+const MAX_DESCRIBED_FILES: usize = 100;
+/// Files listed in the message body
+const MAX_BODY_FILES: usize = 20;
+/// Symbols named per file in the body
+const MAX_BODY_SYMBOLS: usize = 5;
+/// Open tasks scanned for the changed files
+const MAX_SCANNED_TASKS: usize = 500;
+/// Length limit of the header line
+const MAX_HEADER_CHARS: usize = 72;
+
+const CONVENTIONAL_TYPES: &[&str] = &[
+    "feat", "fix", "refactor", "perf", "docs", "test", "build", "ci", "chore",
+];
+
+/// A graph symbol whose lines the diff changes
+#[derive(Debug, Clone, Serialize)]
+pub struct CommitSymbol {
+    pub name: String,
+    /// `function`, `struct`, `trait` or `enum`
+    pub kind: String,
+}
+
+/// One changed file with the symbols it touches
+#[derive(Debug, Clone, Serialize)]
+pub struct CommitFile {
+    /// Path relative to the project root
+    pub path: String,
+    pub change: FileChange,
+    pub additions: u32,
+    pub deletions: u32,
+    pub symbols: Vec<CommitSymbol>,
+}
+
+/// An open task the commit relates to
+#[derive(Debug, Clone, Serialize)]
+pub struct CommitTaskRef {
+    pub task_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub status: TaskStatus,
+    pub plan_id: Uuid,
+    pub plan_title: String,
+    /// Changed files listed in the task's affected files; empty for the
+    /// single in-progress task used when no task lists a changed file
+    pub matched_files: Vec<String>,
+}
+
+/// Conventional commit message proposal
+#[derive(Debug, Clone, Serialize)]
+pub struct CommitSuggestion {
+    pub project_id: Uuid,
+    /// `feat`, `fix`, `refactor`, `perf`, `docs`, `test`, `build` or `ci`
+    pub commit_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    pub subject: String,
+    pub body: String,
+    /// `Refs: <task id>` lines, one per related task
+    pub footers: Vec<String>,
+    /// Header, body and footers, ready for `git commit -F`
+    pub message: String,
+    /// Files in the diff; only the first 100 are described
+    pub files_changed: usize,
+    pub files: Vec<CommitFile>,
+    pub tasks: Vec<CommitTaskRef>,
+}
+
+/// Unified diff of the uncommitted changes of the repository at `cwd`
+/// against HEAD, or of the staged changes only.
+pub async fn working_diff(cwd: &str, staged: bool) -> Result<String> {
+    let mut args = vec!["diff", "--no-color", "--no-ext-diff"];
+    if staged {
+        args.push("--cached");
+    } else {
+        args.push("HEAD");
+    }
+    let output = super::diff_sync::git(cwd, &args).await?;
+    if !output.status.success() {
+        bail!(
+            "git diff failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// What a changed file is, for the commit type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileKind {
+    Docs,
+    Test,
+    Ci,
+    Build,
+    Source,
+}
+
+fn file_kind(path: &str) -> FileKind {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    let lower = name.to_lowercase();
+    if path.starts_with(".github/workflows/")
+        || path.starts_with(".gitlab-ci")
+        || path.starts_with(".circleci/")
+        || lower == "jenkinsfile"
+    {
+        FileKind::Ci
+    } else if lower.ends_with(".md")
+        || lower.ends_with(".rst")
+        || lower.ends_with(".adoc")
+        || lower == "license"
+        || path.starts_with("docs/")
+    {
+        FileKind::Docs
+    } else if path.starts_with("tests/")
+        || path.contains("/tests/")
+        || path.contains("/__tests__/")
+        || lower.starts_with("test_")
+        || lower.contains("_test.")
+        || lower.contains(".test.")
+        || lower.contains(".spec.")
+    {
+        FileKind::Test
+    } else if matches!(
+        lower.as_str(),
+        "cargo.toml"
+            | "cargo.lock"
+            | "build.rs"
+            | "package.json"
+            | "package-lock.json"
+            | "yarn.lock"
+            | "pnpm-lock.yaml"
+            | "go.mod"
+            | "go.sum"
+            | "pyproject.toml"
+            | "requirements.txt"
+            | "makefile"
+            | "dockerfile"
+    ) {
+        FileKind::Build
+    } else {
+        FileKind::Source
+    }
+}
+
+/// Commit type suggested by a task title or tags
+fn task_type_hint(task: &TaskWithPlan) -> Option<&'static str> {
+    let title = task.task.title.as_deref().unwrap_or(&task.task.description);
+    let first_word = title.split_whitespace().next().unwrap_or("").to_lowercase();
+    let words = std::iter::once(first_word.as_str())
+        .chain(task.task.tags.iter().map(String::as_str))
+        .map(str::to_lowercase);
+    for word in words {
+        let hint = match word.trim_end_matches(':') {
+            "fix" | "bug" | "bugfix" | "hotfix" => "fix",
+            "refactor" | "refactoring" | "cleanup" => "refactor",
+            "perf" | "performance" | "optimize" => "perf",
+            "feat" | "feature" | "add" | "implement" => "feat",
+            _ => continue,
+        };
+        return Some(hint);
+    }
+    None
+}
+
+/// Conventional commit type: only docs/tests/CI/build files give their own
+/// type; otherwise a hint of the first related task, then `feat` for new
+/// source files or related tasks, `refactor` for pure removals, `fix` else.
+fn commit_type(files: &[CommitFile], tasks: &[CommitTaskRef], hints: &[&'static str]) -> String {
+    let kinds: Vec<FileKind> = files.iter().map(|f| file_kind(&f.path)).collect();
+    let only = |kind: FileKind| !kinds.is_empty() && kinds.iter().all(|k| *k == kind);
+    let commit_type = if only(FileKind::Docs) {
+        "docs"
+    } else if only(FileKind::Test) {
+        "test"
+    } else if only(FileKind::Ci) {
+        "ci"
+    } else if !kinds.is_empty()
+        && kinds
+            .iter()
+            .all(|k| matches!(k, FileKind::Build | FileKind::Ci))
+    {
+        "build"
+    } else if let Some(hint) = hints.first() {
+        *hint
+    } else {
+        let sources = || {
+            files
+                .iter()
+                .zip(&kinds)
+                .filter(|(_, k)| **k == FileKind::Source)
+                .map(|(f, _)| f)
+        };
+        if sources().any(|f| f.change == FileChange::Added) || !tasks.is_empty() {
+            "feat"
+        } else if sources().all(|f| f.additions == 0) {
+            "refactor"
+        } else {
+            "fix"
+        }
+    };
+    commit_type.to_string()
+}
+
+/// Scope: the innermost directory (below `src/`) holding most of the
+/// source files, when it holds more than half of them.
+fn commit_scope(files: &[CommitFile]) -> Option<String> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut total = 0;
+    for file in files {
+        if matches!(file_kind(&file.path), FileKind::Docs | FileKind::Build) {
+            continue;
+        }
+        total += 1;
+        let module = super::budgets::module_of(&file.path);
+        let scope = module.rsplit('/').next().unwrap_or(module);
+        if !matches!(scope, "." | "src" | "lib" | "tests") {
+            *counts.entry(scope).or_default() += 1;
+        }
+    }
+    let (scope, count) = counts.into_iter().max_by_key(|(_, count)| *count)?;
+    (count * 2 > total).then(|| scope.to_string())
+}
+
+fn file_stem(path: &str) -> &str {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.split('.')
+        .next()
+        .filter(|s| !s.is_empty())
+        .unwrap_or(name)
+}
+
+/// "a", "a and b", "a, b and 2 more"
+fn join_names(names: &[String]) -> String {
+    match names {
+        [] => String::new(),
+        [one] => one.clone(),
+        [a, b] => format!("{} and {}", a, b),
+        [a, b, rest @ ..] => format!("{}, {} and {} more", a, b, rest.len()),
+    }
+}
+
+fn subject(files: &[CommitFile], tasks: &[CommitTaskRef]) -> String {
+    if let [task] = tasks {
+        if let Some(title) = task.title.as_deref().filter(|t| !t.trim().is_empty()) {
+            let title = title.trim().trim_end_matches('.');
+            // The type goes in the header prefix: "Fix: x" -> "x"
+            let title = match title.split_once(char::is_whitespace) {
+                Some((first, rest))
+                    if CONVENTIONAL_TYPES
+                        .contains(&first.trim_end_matches(':').to_lowercase().as_str()) =>
+                {
+                    rest.trim_start()
+                }
+                _ => title,
+            };
+            let mut chars = title.chars();
+            return match chars.next() {
+                Some(first) => first.to_lowercase().chain(chars).collect(),
+                None => String::new(),
+            };
+        }
+    }
+
+    let names = || -> Vec<String> {
+        files
+            .iter()
+            .map(|f| file_stem(&f.path).to_string())
+            .collect()
+    };
+    if files.iter().all(|f| f.change == FileChange::Added) {
+        return format!("add {}", join_names(&names()));
+    }
+    if files.iter().all(|f| f.change == FileChange::Deleted) {
+        return format!("remove {}", join_names(&names()));
+    }
+    if files.iter().all(|f| f.change == FileChange::Renamed) {
+        return format!("rename {}", join_names(&names()));
+    }
+    let mut symbols: Vec<String> = Vec::new();
+    for symbol in files.iter().flat_map(|f| &f.symbols) {
+        if !symbols.contains(&symbol.name) {
+            symbols.push(symbol.name.clone());
+        }
+    }
+    if symbols.is_empty() {
+        format!("update {}", join_names(&names()))
+    } else {
+        format!("update {}", join_names(&symbols))
+    }
+}
+
+fn body(files: &[CommitFile], files_changed: usize) -> String {
+    let mut lines = Vec::new();
+    for file in files.iter().take(MAX_BODY_FILES) {
+        let what = match file.change {
+            FileChange::Added => "new file".to_string(),
+            FileChange::Deleted => "deleted".to_string(),
+            FileChange::Renamed if file.symbols.is_empty() => "renamed".to_string(),
+            _ if file.symbols.is_empty() => format!("+{} -{}", file.additions, file.deletions),
+            _ => {
+                let mut names: Vec<String> = file
+                    .symbols
+                    .iter()
+                    .take(MAX_BODY_SYMBOLS)
+                    .map(|s| format!("`{}`", s.name))
+                    .collect();
+                if file.symbols.len() > MAX_BODY_SYMBOLS {
+                    names.push(format!("{} more", file.symbols.len() - MAX_BODY_SYMBOLS));
+                }
+                format!("change {}", names.join(", "))
+            }
+        };
+        lines.push(format!("- {}: {}", file.path, what));
+    }
+    let listed = files.len().min(MAX_BODY_FILES);
+    if files_changed > listed {
+        lines.push(format!("- and {} more files", files_changed - listed));
+    }
+    lines.join("\n")
+}
+
+/// Whether a task's affected file names the project-relative `relative`.
+fn names_changed_file(affected: &str, relative: &str, roots: &[String]) -> bool {
+    let affected = super::relative_to_roots(affected, roots);
+    affected.trim_start_matches("./") == relative
+}
+
+/// Propose a conventional commit message for `files` of `project`.
+pub async fn suggest_commit_message(
+    neo4j: &dyn GraphStore,
+    project: &ProjectNode,
+    files: &[DiffFile],
+) -> Result<CommitSuggestion> {
+    let roots = super::project_roots(&project.root_path);
+
+    let mut described = Vec::new();
+    for file in files.iter().take(MAX_DESCRIBED_FILES) {
+        let mut entry = CommitFile {
+            path: file.path.clone(),
+            change: file.change,
+            additions: file.additions,
+            deletions: file.deletions,
+            symbols: Vec::new(),
+        };
+        if file.change != FileChange::Added {
+            for root in &roots {
+                let absolute = format!("{}/{}", root.trim_end_matches('/'), file.base_path());
+                let symbols = neo4j.list_file_symbols(&absolute).await?;
+                if symbols.is_empty() {
+                    continue;
+                }
+                entry.symbols = symbols
+                    .into_iter()
+                    .filter(|s| {
+                        file.change == FileChange::Deleted
+                            || file
+                                .hunks
+                                .iter()
+                                .any(|h| h.touches(s.line_start, s.line_end))
+                    })
+                    .map(|s| CommitSymbol {
+                        name: s.name,
+                        kind: s.kind,
+                    })
+                    .collect();
+                break;
+            }
+        }
+        described.push(entry);
+    }
+
+    let (open_tasks, _) = neo4j
+        .list_all_tasks_filtered(
+            None,
+            Some(project.id),
+            None,
+            Some(vec![
+                "in_progress".to_string(),
+                "pending".to_string(),
+                "blocked".to_string(),
+            ]),
+            None,
+            None,
+            None,
+            None,
+            MAX_SCANNED_TASKS,
+            0,
+            None,
+            "desc",
+        )
+        .await?;
+
+    let mut related: Vec<(&TaskWithPlan, Vec<String>)> = open_tasks
+        .iter()
+        .filter_map(|t| {
+            let matched: Vec<String> = described
+                .iter()
+                .filter(|f| {
+                    t.task
+                        .affected_files
+                        .iter()
+                        .any(|a| names_changed_file(a, &f.path, &roots))
+                })
+                .map(|f| f.path.clone())
+                .collect();
+            (!matched.is_empty()).then_some((t, matched))
+        })
+        .collect();
+    if related.is_empty() {
+        let mut in_progress = open_tasks
+            .iter()
+            .filter(|t| t.task.status == TaskStatus::InProgress);
+        if let (Some(only), None) = (in_progress.next(), in_progress.next()) {
+            related.push((only, Vec::new()));
+        }
+    }
+    // In-progress tasks first, then by matched files
+    related.sort_by_key(|(t, matched)| {
+        (
+            t.task.status != TaskStatus::InProgress,
+            std::cmp::Reverse(matched.len()),
+        )
+    });
+
+    let hints: Vec<&'static str> = related
+        .iter()
+        .filter_map(|(t, _)| task_type_hint(t))
+        .collect();
+    let tasks: Vec<CommitTaskRef> = related
+        .into_iter()
+        .map(|(t, matched_files)| CommitTaskRef {
+            task_id: t.task.id,
+            title: t.task.title.clone(),
+            status: t.task.status.clone(),
+            plan_id: t.plan_id,
+            plan_title: t.plan_title.clone(),
+            matched_files,
+        })
+        .collect();
+
+    let commit_type = commit_type(&described, &tasks, &hints);
+    let scope = commit_scope(&described);
+    let prefix = match &scope {
+        Some(scope) => format!("{}({}): ", commit_type, scope),
+        None => format!("{}: ", commit_type),
+    };
+    let mut subject = subject(&described, &tasks);
+    let room = MAX_HEADER_CHARS.saturating_sub(prefix.chars().count());
+    if subject.chars().count() > room {
+        subject = subject.chars().take(room.saturating_sub(1)).collect();
+        subject = format!("{}…", subject.trim_end());
+    }
+    let body = body(&described, files.len());
+    let footers: Vec<String> = tasks
+        .iter()
+        .map(|t| format!("Refs: {}", t.task_id))
+        .collect();
+
+    let mut message = format!("{}{}", prefix, subject);
+    if !body.is_empty() {
+        message.push_str("\n\n");
+        message.push_str(&body);
+    }
+    if !footers.is_empty() {
+        message.push_str("\n\n");
+        message.push_str(&footers.join("\n"));
+    }
+
+    Ok(CommitSuggestion {
+        project_id: project.id,
+        commit_type,
+        scope,
+        subject,
+        body,
+        footers,
+        message,
+        files_changed: files.len(),
+        files: described,
+        tasks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::{FileNode, FunctionNode, Visibility};
+    use crate::orchestrator::review::parse_unified_diff;
+    use crate::test_helpers::{test_plan_for_project, test_project_named, test_task_titled};
+
+    fn file(path: &str, change: FileChange, additions: u32, deletions: u32) -> CommitFile {
+        CommitFile {
+            path: path.to_string(),
+            change,
+            additions,
+            deletions,
+            symbols: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_commit_type_and_scope() {
+        let docs = [file("README.md", FileChange::Modified, 1, 1)];
+        assert_eq!(commit_type(&docs, &[], &[]), "docs");
+        assert_eq!(commit_scope(&docs), None);
+
+        let tests = [file("tests/api.rs", FileChange::Modified, 3, 0)];
+        assert_eq!(commit_type(&tests, &[], &[]), "test");
+
+        let build = [
+            file("Cargo.toml", FileChange::Modified, 1, 1),
+            file(".github/workflows/ci.yml", FileChange::Modified, 2, 0),
+        ];
+        assert_eq!(commit_type(&build, &[], &[]), "build");
+
+        let code = [
+            file("src/api/handlers.rs", FileChange::Modified, 4, 2),
+            file("src/api/routes.rs", FileChange::Modified, 1, 0),
+            file("src/lib.rs", FileChange::Modified, 1, 0),
+        ];
+        assert_eq!(commit_type(&code, &[], &[]), "fix");
+        assert_eq!(commit_type(&code, &[], &["refactor"]), "refactor");
+        assert_eq!(commit_scope(&code).as_deref(), Some("api"));
+
+        let added = [file("src/chat/sandbox.rs", FileChange::Added, 40, 0)];
+        assert_eq!(commit_type(&added, &[], &[]), "feat");
+        assert_eq!(subject(&added, &[]), "add sandbox");
+
+        let removed = [file("src/old.rs", FileChange::Modified, 0, 12)];
+        assert_eq!(commit_type(&removed, &[], &[]), "refactor");
+    }
+
+    #[tokio::test]
+    async fn test_suggest_commit_message() {
+        let graph = MockGraphStore::new();
+        let mut project = test_project_named("committed");
+        project.root_path = "/repo".to_string();
+        graph.create_project(&project).await.unwrap();
+        graph
+            .upsert_file(&FileNode {
+                path: "/repo/src/auth/token.rs".to_string(),
+                language: "rust".to_string(),
+                hash: "h".to_string(),
+                line_count: 0,
+                last_parsed: chrono::Utc::now(),
+                project_id: Some(project.id),
+                encoding: None,
+            })
+            .await
+            .unwrap();
+        graph
+            .upsert_function(&FunctionNode {
+                name: "refresh".to_string(),
+                visibility: Visibility::Public,
+                params: vec![],
+                return_type: None,
+                generics: vec![],
+                is_async: false,
+                is_unsafe: false,
+                complexity: 1,
+                file_path: "/repo/src/auth/token.rs".to_string(),
+                line_start: 1,
+                line_end: 8,
+                docstring: None,
+            })
+            .await
+            .unwrap();
+
+        let plan = test_plan_for_project(project.id);
+        graph.create_plan(&plan).await.unwrap();
+        let mut task = test_task_titled("Fix token refresh race");
+        task.status = TaskStatus::InProgress;
+        task.affected_files = vec!["src/auth/token.rs".to_string()];
+        graph.create_task(plan.id, &task).await.unwrap();
+        let mut unrelated = test_task_titled("Add billing");
+        unrelated.affected_files = vec!["src/billing.rs".to_string()];
+        graph.create_task(plan.id, &unrelated).await.unwrap();
+
+        let diff = "--- a/src/auth/token.rs\n+++ b/src/auth/token.rs\n@@ -3,1 +3,2 @@\n-    old();\n+    lock();\n+    new();\n";
+        let files = parse_unified_diff(diff);
+        let suggestion = suggest_commit_message(&graph, &project, &files)
+            .await
+            .unwrap();
+
+        assert_eq!(suggestion.commit_type, "fix");
+        assert_eq!(suggestion.scope.as_deref(), Some("auth"));
+        assert_eq!(suggestion.subject, "token refresh race");
+        assert_eq!(suggestion.files[0].symbols[0].name, "refresh");
+        assert_eq!(suggestion.tasks.len(), 1);
+        assert_eq!(suggestion.tasks[0].task_id, task.id);
+        assert_eq!(suggestion.tasks[0].matched_files, ["src/auth/token.rs"]);
+        assert_eq!(
+            suggestion.message,
+            format!(
+                "fix(auth): token refresh race\n\n- src/auth/token.rs: change `refresh`\n\nRefs: {}",
+                task.id
+            )
+        );
+    }
+}
@@ -4,7 +4,9 @@
 //! - `generate_prompt()` — static markdown prompt (backward compatible)
 //! - `build_enriched_context()` — runs the EnrichmentPipeline before prompt assembly
 
+use super::context_bundle::{self, ContextBundle};
 use crate::chat::enrichment::{EnrichmentInput, EnrichmentPipeline};
+use crate::feedback::relevance::RelevanceWeights;
 use crate::meilisearch::SearchStore;
 use crate::neo4j::models::*;
 use crate::neo4j::GraphStore;
@@ -132,6 +134,9 @@ impl ContextBuilder {
             }
         }
 
+        // Discussion on the task and its plan (best-effort)
+        let comments = self.get_discussion(task_id, plan_id).await;
+
         Ok(AgentContext {
             task: task_details.task,
             steps: task_details.steps,
@@ -142,9 +147,33 @@ impl ContextBuilder {
             related_decisions,
             notes: all_notes,
             frustration_signals,
+            comments,
         })
     }
 
+    /// Build a portable bundle for a task of `project`: plan state, task
+    /// context, the files it modifies (with their content unless
+    /// `include_contents` is false) and the generated prompt.
+    pub async fn build_context_bundle(
+        &self,
+        project: &ProjectNode,
+        task_id: Uuid,
+        plan_id: Uuid,
+        include_contents: bool,
+    ) -> Result<ContextBundle> {
+        let plan = self
+            .plan_manager
+            .get_plan_details(plan_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Plan not found"))?;
+        let context = self.build_context(task_id, plan_id).await?;
+        let prompt = self.generate_prompt(&context);
+        let files = context_bundle::read_target_files(project, &context, include_contents);
+        Ok(context_bundle::assemble(
+            project, &plan, context, files, prompt,
+        ))
+    }
+
     /// Get context for a specific file
     pub async fn get_file_context(&self, file_path: &str) -> Result<FileContext> {
         // Get file info from Neo4j
@@ -159,6 +188,32 @@ impl ContextBuilder {
         // Get files this file imports
         let dependencies = self.get_file_imports(file_path).await?;
 
+        // Test coverage, once a report was ingested (best-effort)
+        let coverage = self
+            .neo4j
+            .get_file_coverage(file_path, super::coverage::UNTESTED_COVERAGE_PCT)
+            .await
+            .ok()
+            .flatten();
+
+        // Open production errors through the file's functions (best-effort)
+        let production_errors = self
+            .neo4j
+            .get_file_sentry_issues(file_path)
+            .await
+            .unwrap_or_default()
+            .iter()
+            .take(MAX_CONTEXT_PRODUCTION_ERRORS)
+            .map(production_error)
+            .collect();
+
+        // CODEOWNERS owners, once the file was ingested (best-effort)
+        let owners = self
+            .neo4j
+            .get_file_owners(file_path)
+            .await
+            .unwrap_or_default();
+
         Ok(FileContext {
             path: file_path.to_string(),
             language: file.map(|f| f.language).unwrap_or_default(),
@@ -166,6 +221,12 @@ impl ContextBuilder {
             dependent_files,
             dependencies,
             notes: Vec::new(),
+            coverage_pct: coverage.as_ref().map(|c| c.coverage_pct),
+            untested_functions: coverage
+                .map(|c| c.untested_functions.into_iter().map(|f| f.name).collect())
+                .unwrap_or_default(),
+            production_errors,
+            owners,
         })
     }
 
@@ -213,6 +274,14 @@ impl ContextBuilder {
             });
         }
 
+        // Learned relevance from chat context feedback
+        let ids: Vec<Uuid> = context_notes.iter().map(|n| n.id).collect();
+        let weights = RelevanceWeights::load(self.neo4j.as_ref(), &ids).await;
+        context_notes.retain(|n| !weights.is_suppressed(n.id));
+        for note in &mut context_notes {
+            note.relevance_score *= weights.weight(note.id);
+        }
+
         Ok(context_notes)
     }
 
@@ -237,6 +306,26 @@ impl ContextBuilder {
         }
     }
 
+    /// Comments on a task and its plan, oldest first, keeping the most recent
+    /// [`MAX_CONTEXT_COMMENTS`]
+    async fn get_discussion(&self, task_id: Uuid, plan_id: Uuid) -> Vec<CommentNode> {
+        let mut comments = self
+            .neo4j
+            .list_comments(CommentTarget::Task, task_id)
+            .await
+            .unwrap_or_default();
+        comments.extend(
+            self.neo4j
+                .list_comments(CommentTarget::Plan, plan_id)
+                .await
+                .unwrap_or_default(),
+        );
+        comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        let excess = comments.len().saturating_sub(MAX_CONTEXT_COMMENTS);
+        comments.drain(..excess);
+        comments
+    }
+
     /// Get symbols defined in a file
     async fn get_file_symbols(&self, file_path: &str) -> Result<Vec<String>> {
         let names = self.neo4j.get_file_symbol_names(file_path).await?;
@@ -254,12 +343,12 @@ impl ContextBuilder {
         Ok(imports.into_iter().map(|i| i.path).collect())
     }
 
-    /// Search for similar code using Meilisearch
+    /// Search for similar code using Meilisearch (archived projects excepted)
     async fn search_similar_code(&self, query: &str, limit: usize) -> Result<Vec<CodeReference>> {
-        let hits = self
-            .meili
-            .search_code_with_scores(query, limit, None, None, None)
-            .await?;
+        let archived = super::archived_project_slugs(self.neo4j.as_ref()).await?;
+        let hits =
+            super::search_unarchived_code(self.meili.as_ref(), &archived, query, limit, None)
+                .await?;
 
         let references = hits
             .into_iter()
@@ -386,6 +475,11 @@ impl ContextBuilder {
             builder = builder.with_steps(s);
         }
 
+        // Discussion
+        if !context.comments.is_empty() {
+            builder = builder.with_discussion(discussion_lines(&context.comments));
+        }
+
         // Knowledge Notes
         if !context.notes.is_empty() {
             let mut s = String::new();
@@ -463,6 +557,9 @@ impl ContextBuilder {
             for file in &context.target_files {
                 s.push_str(&format!("### {}\n", file.path));
                 s.push_str(&format!("- Language: {}\n", file.language));
+                if !file.owners.is_empty() {
+                    s.push_str(&format!("- Owners: {}\n", file.owners.join(", ")));
+                }
                 if !file.symbols.is_empty() {
                     s.push_str(&format!("- Symbols: {}\n", file.symbols.join(", ")));
                 }
@@ -472,6 +569,10 @@ impl ContextBuilder {
                         file.dependent_files.join(", ")
                     ));
                 }
+                if let Some(coverage) = coverage_line(file) {
+                    s.push_str(&coverage);
+                }
+                s.push_str(&production_error_lines(file));
                 // File-specific notes
                 for note in &file.notes {
                     s.push_str(&format!("- [{}] {}\n", note.note_type, note.content));
@@ -611,12 +712,22 @@ impl ContextBuilder {
             prompt.push('\n');
         }
 
+        // Discussion on the task and its plan
+        if !context.comments.is_empty() {
+            prompt.push_str("## Discussion\n");
+            prompt.push_str(&discussion_lines(&context.comments));
+            prompt.push('\n');
+        }
+
         // Target files
         if !context.target_files.is_empty() {
             prompt.push_str("## Files to Modify\n");
             for file in &context.target_files {
                 prompt.push_str(&format!("### {}\n", file.path));
                 prompt.push_str(&format!("- Language: {}\n", file.language));
+                if !file.owners.is_empty() {
+                    prompt.push_str(&format!("- Owners: {}\n", file.owners.join(", ")));
+                }
                 if !file.symbols.is_empty() {
                     prompt.push_str(&format!("- Symbols: {}\n", file.symbols.join(", ")));
                 }
@@ -626,6 +737,10 @@ impl ContextBuilder {
                         file.dependent_files.join(", ")
                     ));
                 }
+                if let Some(coverage) = coverage_line(file) {
+                    prompt.push_str(&coverage);
+                }
+                prompt.push_str(&production_error_lines(file));
                 prompt.push('\n');
             }
         }
@@ -794,6 +909,7 @@ impl ContextBuilder {
             "similar_code_count": context.similar_code.len(),
             "related_decisions_count": context.related_decisions.len(),
             "notes_count": context.notes.len(),
+            "comments_count": context.comments.len(),
             "propagated_notes_len": propagated_notes.len(),
         });
         let exec_ctx_json = serde_json::to_string(&exec_ctx)?;
@@ -996,6 +1112,78 @@ pub async fn pre_read_affected_files(
     output
 }
 
+/// `- Test coverage: …` line of a target file, with its untested functions.
+fn coverage_line(file: &FileContext) -> Option<String> {
+    let pct = file.coverage_pct?;
+    if file.untested_functions.is_empty() {
+        Some(format!("- Test coverage: {}%\n", pct))
+    } else {
+        Some(format!(
+            "- Test coverage: {}% (untested: {})\n",
+            pct,
+            file.untested_functions.join(", ")
+        ))
+    }
+}
+
+/// Open production errors listed per target file
+const MAX_CONTEXT_PRODUCTION_ERRORS: usize = 5;
+
+/// `SHOP-1A: TypeError: … (12 events, last seen 2026-10-13)`
+fn production_error(issue: &SentryIssueNode) -> String {
+    format!(
+        "{}: {} ({} event{}, last seen {})",
+        issue.short_id,
+        issue.title,
+        issue.events,
+        if issue.events == 1 { "" } else { "s" },
+        issue.last_seen.format("%Y-%m-%d")
+    )
+}
+
+/// Comments of the discussion included in the agent context
+const MAX_CONTEXT_COMMENTS: usize = 20;
+
+/// `- [2026-10-13] Alice on the task: …` lines of a discussion, replies
+/// marked as such and multi-line comments indented.
+fn discussion_lines(comments: &[CommentNode]) -> String {
+    let mut lines = String::new();
+    for comment in comments {
+        let target = match comment.entity_type {
+            CommentTarget::Task => "the task",
+            CommentTarget::Plan => "the plan",
+            CommentTarget::Note => "a note",
+        };
+        let reply = if comment.parent_id.is_some() {
+            " (reply)"
+        } else {
+            ""
+        };
+        lines.push_str(&format!(
+            "- [{}] {} on {}{}: {}\n",
+            comment.created_at.format("%Y-%m-%d"),
+            comment.author,
+            target,
+            reply,
+            comment.content.trim().replace('\n', "\n  ")
+        ));
+    }
+    lines
+}
+
+/// `- Open production errors (Sentry):` lines of a target file, empty when
+/// there is none.
+fn production_error_lines(file: &FileContext) -> String {
+    if file.production_errors.is_empty() {
+        return String::new();
+    }
+    let mut lines = String::from("- Open production errors (Sentry):\n");
+    for error in &file.production_errors {
+        lines.push_str(&format!("  - {}\n", error));
+    }
+    lines
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1027,6 +1215,8 @@ mod tests {
             execution_context: None,
             persona: None,
             prompt_cache: None,
+            board_position: None,
+            version: 0,
         }
     }
 
@@ -1059,6 +1249,7 @@ mod tests {
             related_decisions: vec![],
             notes: vec![],
             frustration_signals: None,
+            comments: vec![],
         };
 
         // Create a mock builder (we don't actually need database for generate_prompt)
@@ -1100,6 +1291,7 @@ mod tests {
             related_decisions: vec![],
             notes: vec![],
             frustration_signals: None,
+            comments: vec![],
         };
 
         // Verify that constraint formatting works
@@ -1148,6 +1340,7 @@ mod tests {
             related_decisions: vec![],
             notes: vec![],
             frustration_signals: None,
+            comments: vec![],
         };
 
         assert_eq!(context.steps.len(), 2);
@@ -1179,6 +1372,7 @@ mod tests {
             related_decisions: vec![],
             notes: vec![],
             frustration_signals: None,
+            comments: vec![],
         };
 
         assert_eq!(context.decisions.len(), 1);
@@ -1200,16 +1394,113 @@ mod tests {
                 dependent_files: vec!["src/api/routes.rs".to_string()],
                 dependencies: vec!["src/plan/models.rs".to_string()],
                 notes: vec![],
+                coverage_pct: None,
+                untested_functions: vec![],
+                production_errors: vec![],
+                owners: vec![],
             }],
             similar_code: vec![],
             related_decisions: vec![],
             notes: vec![],
             frustration_signals: None,
+            comments: vec![],
         };
 
         assert_eq!(context.target_files.len(), 1);
         assert_eq!(context.target_files[0].language, "rust");
         assert_eq!(context.target_files[0].symbols.len(), 2);
+        assert_eq!(coverage_line(&context.target_files[0]), None);
+    }
+
+    #[test]
+    fn test_coverage_line() {
+        let mut file = FileContext {
+            path: "src/lib.rs".to_string(),
+            language: "rust".to_string(),
+            symbols: vec![],
+            dependent_files: vec![],
+            dependencies: vec![],
+            notes: vec![],
+            coverage_pct: Some(62.5),
+            untested_functions: vec![],
+            production_errors: vec![],
+            owners: vec![],
+        };
+        assert_eq!(
+            coverage_line(&file).as_deref(),
+            Some("- Test coverage: 62.5%\n")
+        );
+        file.untested_functions = vec!["dispatch".to_string(), "retry".to_string()];
+        assert_eq!(
+            coverage_line(&file).as_deref(),
+            Some("- Test coverage: 62.5% (untested: dispatch, retry)\n")
+        );
+    }
+
+    #[test]
+    fn test_production_error_lines() {
+        let issue = SentryIssueNode {
+            project_id: Uuid::new_v4(),
+            id: "42".to_string(),
+            short_id: "SHOP-1A".to_string(),
+            title: "TypeError: boom".to_string(),
+            culprit: None,
+            level: "error".to_string(),
+            events: 12,
+            users: 3,
+            first_seen: "2026-10-01T08:00:00Z".parse().unwrap(),
+            last_seen: "2026-10-13T09:30:00Z".parse().unwrap(),
+            permalink: None,
+            function_ids: vec![],
+        };
+        let mut file = FileContext {
+            path: "src/user.js".to_string(),
+            language: "javascript".to_string(),
+            symbols: vec![],
+            dependent_files: vec![],
+            dependencies: vec![],
+            notes: vec![],
+            coverage_pct: None,
+            untested_functions: vec![],
+            production_errors: vec![],
+            owners: vec![],
+        };
+        assert_eq!(production_error_lines(&file), "");
+        file.production_errors = vec![production_error(&issue)];
+        assert_eq!(
+            production_error_lines(&file),
+            "- Open production errors (Sentry):\n  - SHOP-1A: TypeError: boom (12 events, last seen 2026-10-13)\n"
+        );
+    }
+
+    #[test]
+    fn test_discussion_lines() {
+        let at: chrono::DateTime<chrono::Utc> = "2026-10-13T09:30:00Z".parse().unwrap();
+        let comment = CommentNode {
+            id: Uuid::new_v4(),
+            entity_type: CommentTarget::Plan,
+            entity_id: Uuid::new_v4(),
+            author: "Alice".to_string(),
+            author_id: "alice".to_string(),
+            content: "Keep the v1 endpoint\nuntil clients migrate".to_string(),
+            mentions: vec![],
+            parent_id: None,
+            created_at: at,
+            updated_at: at,
+        };
+        let reply = CommentNode {
+            id: Uuid::new_v4(),
+            entity_type: CommentTarget::Task,
+            author: "Bob".to_string(),
+            content: "Agreed".to_string(),
+            parent_id: Some(comment.id),
+            ..comment.clone()
+        };
+        assert_eq!(
+            discussion_lines(&[comment, reply]),
+            "- [2026-10-13] Alice on the plan: Keep the v1 endpoint\n  until clients migrate\n\
+             - [2026-10-13] Bob on the task (reply): Agreed\n"
+        );
     }
 
     #[test]
@@ -1228,6 +1519,7 @@ mod tests {
             related_decisions: vec![],
             notes: vec![],
             frustration_signals: None,
+            comments: vec![],
         };
 
         assert_eq!(context.similar_code.len(), 1);
@@ -1256,6 +1548,7 @@ mod tests {
                 create_test_context_note("observation", "This pattern is common", "low", true),
             ],
             frustration_signals: None,
+            comments: vec![],
         };
 
         // Verify notes are present
@@ -1302,11 +1595,16 @@ mod tests {
                     "high",
                     false,
                 )],
+                coverage_pct: None,
+                untested_functions: vec![],
+                production_errors: vec![],
+                owners: vec![],
             }],
             similar_code: vec![],
             related_decisions: vec![],
             notes: vec![],
             frustration_signals: None,
+            comments: vec![],
         };
 
         assert_eq!(context.target_files[0].notes.len(), 1);
@@ -1363,6 +1661,7 @@ mod tests {
                 false,
             )],
             frustration_signals: None,
+            comments: vec![],
         };
 
         let json = serde_json::to_string(&context).unwrap();
@@ -1390,6 +1689,10 @@ mod tests {
                 "medium",
                 false,
             )],
+            coverage_pct: None,
+            untested_functions: vec![],
+            production_errors: vec![],
+            owners: vec![],
         };
 
         assert_eq!(file_context.path, "src/lib.rs");
@@ -0,0 +1,335 @@
+//! Context bundles for offline agents.
+//!
+//! A [`ContextBundle`] packs everything an agent needs to work on one task —
+//! the plan state, the task context built by [`ContextBuilder`], the contents
+//! of the files it modifies and the generated prompt — into a single artifact
+//! that can be handed to an external agent or attached to an issue.
+//! `POST /api/projects/{id}/context-bundle` serves it as JSON or as a zip
+//! archive written by [`write_zip`].
+//!
+//! [`ContextBuilder`]: super::ContextBuilder
+
+use crate::expand_tilde;
+use crate::neo4j::models::{ConstraintNode, PlanStatus, ProjectNode, TaskStatus};
+use crate::plan::models::{AgentContext, PlanDetails};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Version of the bundle layout, bumped on breaking changes
+pub const FORMAT_VERSION: u32 = 1;
+/// Largest file whose content is embedded
+const MAX_FILE_BYTES: u64 = 256 * 1024;
+/// Total file content embedded in one bundle
+const MAX_TOTAL_BYTES: u64 = 4 * 1024 * 1024;
+
+/// The project a bundle comes from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleProject {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+}
+
+/// Where a task of the plan stands
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleTaskState {
+    pub id: Uuid,
+    pub title: Option<String>,
+    pub status: TaskStatus,
+    pub depends_on: Vec<Uuid>,
+}
+
+/// The plan the task belongs to, with the state of every task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundlePlan {
+    pub id: Uuid,
+    pub title: String,
+    pub description: String,
+    pub status: PlanStatus,
+    pub constraints: Vec<ConstraintNode>,
+    pub tasks: Vec<BundleTaskState>,
+}
+
+impl From<&PlanDetails> for BundlePlan {
+    fn from(details: &PlanDetails) -> Self {
+        Self {
+            id: details.plan.id,
+            title: details.plan.title.clone(),
+            description: details.plan.description.clone(),
+            status: details.plan.status.clone(),
+            constraints: details.constraints.clone(),
+            tasks: details
+                .tasks
+                .iter()
+                .map(|t| BundleTaskState {
+                    id: t.task.id,
+                    title: t.task.title.clone(),
+                    status: t.task.status.clone(),
+                    depends_on: t.depends_on.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A file the task modifies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleFile {
+    /// Path relative to the project root
+    pub path: String,
+    pub language: String,
+    /// File content, None when not embedded
+    pub content: Option<String>,
+    /// Why the content is missing (missing, too large, binary, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub omitted: Option<String>,
+}
+
+/// Everything an agent needs for one task, in one artifact
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextBundle {
+    pub format_version: u32,
+    pub generated_at: DateTime<Utc>,
+    pub project: BundleProject,
+    pub plan: BundlePlan,
+    /// Task, steps, decisions, symbols of the target files and notes
+    pub context: AgentContext,
+    pub files: Vec<BundleFile>,
+    /// Prompt generated from the context, as given to internal agents
+    pub prompt: String,
+}
+
+/// Resolve a graph file path inside the project root. Returns the absolute
+/// path and the path relative to the root, or None for files outside it.
+fn resolve_in_project(root: &Path, path: &str) -> Option<(PathBuf, String)> {
+    let root = root.canonicalize().ok()?;
+    let candidate = Path::new(path);
+    let absolute = if candidate.is_absolute() {
+        candidate.to_path_buf()
+    } else {
+        root.join(candidate)
+    };
+    let absolute = absolute.canonicalize().ok()?;
+    let relative = absolute
+        .strip_prefix(&root)
+        .ok()?
+        .to_string_lossy()
+        .into_owned();
+    Some((absolute, relative))
+}
+
+/// Read the target files of a task context from the project checkout.
+///
+/// Content is embedded up to [`MAX_FILE_BYTES`] per file and
+/// [`MAX_TOTAL_BYTES`] overall; files outside the project root are skipped.
+pub fn read_target_files(
+    project: &ProjectNode,
+    context: &AgentContext,
+    include_contents: bool,
+) -> Vec<BundleFile> {
+    let root = PathBuf::from(expand_tilde(&project.root_path));
+    let mut budget = MAX_TOTAL_BYTES;
+    let mut files = Vec::new();
+    for target in &context.target_files {
+        let Some((absolute, relative)) = resolve_in_project(&root, &target.path) else {
+            files.push(BundleFile {
+                path: target.path.clone(),
+                language: target.language.clone(),
+                content: None,
+                omitted: Some("not found in the project checkout".to_string()),
+            });
+            continue;
+        };
+        let (content, omitted) = if !include_contents {
+            (None, None)
+        } else {
+            match std::fs::metadata(&absolute) {
+                Ok(meta) if meta.len() > MAX_FILE_BYTES => {
+                    (None, Some(format!("larger than {} bytes", MAX_FILE_BYTES)))
+                }
+                Ok(meta) if meta.len() > budget => {
+                    (None, Some("bundle size limit reached".to_string()))
+                }
+                Ok(meta) => match std::fs::read_to_string(&absolute) {
+                    Ok(text) => {
+                        budget -= meta.len();
+                        (Some(text), None)
+                    }
+                    Err(_) => (None, Some("not valid UTF-8".to_string())),
+                },
+                Err(e) => (None, Some(format!("unreadable: {}", e))),
+            }
+        };
+        files.push(BundleFile {
+            path: relative,
+            language: target.language.clone(),
+            content,
+            omitted,
+        });
+    }
+    files
+}
+
+/// Assemble a bundle from its parts.
+pub fn assemble(
+    project: &ProjectNode,
+    plan: &PlanDetails,
+    context: AgentContext,
+    files: Vec<BundleFile>,
+    prompt: String,
+) -> ContextBundle {
+    ContextBundle {
+        format_version: FORMAT_VERSION,
+        generated_at: Utc::now(),
+        project: BundleProject {
+            id: project.id,
+            slug: project.slug.clone(),
+            name: project.name.clone(),
+        },
+        plan: BundlePlan::from(plan),
+        context,
+        files,
+        prompt,
+    }
+}
+
+/// File name of a bundle download, e.g. `my-project-task-1a2b3c4d.context.zip`
+pub fn bundle_file_name(bundle: &ContextBundle, extension: &str) -> String {
+    let task = bundle.context.task.id.simple().to_string();
+    format!(
+        "{}-task-{}.context.{}",
+        bundle.project.slug,
+        &task[..8],
+        extension
+    )
+}
+
+/// Write a bundle as a zip archive: `bundle.json` (without file contents),
+/// `PROMPT.md`, and the embedded files under `files/`.
+pub fn write_zip(bundle: &ContextBundle) -> Result<Vec<u8>> {
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+
+    let mut manifest = bundle.clone();
+    for file in &mut manifest.files {
+        file.content = None;
+    }
+    zip.start_file("bundle.json", options)?;
+    zip.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+
+    zip.start_file("PROMPT.md", options)?;
+    zip.write_all(bundle.prompt.as_bytes())?;
+
+    for file in &bundle.files {
+        if let Some(ref content) = file.content {
+            zip.start_file(format!("files/{}", file.path), options)?;
+            zip.write_all(content.as_bytes())?;
+        }
+    }
+    Ok(zip.finish()?.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plan::models::FileContext;
+    use crate::test_helpers::{test_plan, test_project, test_task};
+    use std::io::Read;
+
+    fn context_with_files(paths: &[&str]) -> AgentContext {
+        AgentContext {
+            task: test_task(),
+            steps: vec![],
+            constraints: vec![],
+            decisions: vec![],
+            target_files: paths
+                .iter()
+                .map(|p| FileContext {
+                    path: p.to_string(),
+                    language: "rust".to_string(),
+                    symbols: vec![],
+                    dependent_files: vec![],
+                    dependencies: vec![],
+                    notes: vec![],
+                    coverage_pct: None,
+                    untested_functions: vec![],
+                    production_errors: vec![],
+                    owners: vec![],
+                })
+                .collect(),
+            similar_code: vec![],
+            related_decisions: vec![],
+            notes: vec![],
+            frustration_signals: None,
+            comments: vec![],
+        }
+    }
+
+    #[test]
+    fn test_read_target_files_stays_in_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("repo");
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), "pub fn f() {}\n").unwrap();
+        std::fs::write(dir.path().join("secret.txt"), "nope").unwrap();
+        let mut project = test_project();
+        project.root_path = root.to_string_lossy().into_owned();
+
+        let absolute = root.join("src/lib.rs").to_string_lossy().into_owned();
+        let context = context_with_files(&[&absolute, "../secret.txt", "missing.rs"]);
+        let files = read_target_files(&project, &context, true);
+
+        assert_eq!(files[0].path, "src/lib.rs");
+        assert_eq!(files[0].content.as_deref(), Some("pub fn f() {}\n"));
+        assert!(files[1].content.is_none() && files[1].omitted.is_some());
+        assert!(files[2].content.is_none() && files[2].omitted.is_some());
+
+        let files = read_target_files(&project, &context, false);
+        assert!(files[0].content.is_none() && files[0].omitted.is_none());
+    }
+
+    #[test]
+    fn test_write_zip() {
+        let project = test_project();
+        let plan = PlanDetails {
+            plan: test_plan(),
+            tasks: vec![],
+            constraints: vec![],
+        };
+        let files = vec![BundleFile {
+            path: "src/lib.rs".to_string(),
+            language: "rust".to_string(),
+            content: Some("pub fn f() {}\n".to_string()),
+            omitted: None,
+        }];
+        let bundle = assemble(
+            &project,
+            &plan,
+            context_with_files(&[]),
+            files,
+            "# Task".to_string(),
+        );
+        assert!(bundle_file_name(&bundle, "zip").ends_with(".context.zip"));
+
+        let bytes = write_zip(&bundle).unwrap();
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+        let mut source = String::new();
+        archive
+            .by_name("files/src/lib.rs")
+            .unwrap()
+            .read_to_string(&mut source)
+            .unwrap();
+        assert_eq!(source, "pub fn f() {}\n");
+
+        let manifest: ContextBundle =
+            serde_json::from_reader(archive.by_name("bundle.json").unwrap()).unwrap();
+        assert_eq!(manifest.format_version, FORMAT_VERSION);
+        assert!(manifest.files[0].content.is_none());
+        assert!(archive.by_name("PROMPT.md").is_ok());
+    }
+}
@@ -0,0 +1,486 @@
+//! Test coverage ingestion.
+//!
+//! LCOV (`lcov.info`, written by cargo-llvm-cov, c8, istanbul, …) and
+//! Cobertura XML (coverage.py, cargo-tarpaulin, …) reports are read into line
+//! hits per file. Report paths are matched against the synced files of the
+//! project: as is, under a Cobertura `<source>` or the project root, and
+//! finally by their longest unambiguous path suffix, which covers reports
+//! generated on another machine.
+//!
+//! The coverage of a function is the share of its instrumented lines (between
+//! `line_start` and `line_end`) that were hit. It is stored as `coverage_pct`
+//! on Function and File nodes and replaces the previous coverage of the files
+//! in the report; other files keep theirs.
+
+use crate::neo4j::models::{CoverageUpdate, FunctionSpanRow, ProjectNode};
+use crate::neo4j::traits::GraphStore;
+use crate::resolver::SuffixIndex;
+use crate::utils::xml;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Functions covered below this share of their lines count as untested
+pub const UNTESTED_COVERAGE_PCT: f64 = 50.0;
+/// Unmatched report paths listed in the ingestion summary
+const MAX_UNMATCHED_LISTED: usize = 50;
+
+/// Format of a coverage report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CoverageFormat {
+    Lcov,
+    Cobertura,
+}
+
+impl CoverageFormat {
+    /// Cobertura for XML, LCOV otherwise.
+    pub fn detect(report: &str) -> Self {
+        if report.trim_start().starts_with('<') {
+            Self::Cobertura
+        } else {
+            Self::Lcov
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lcov => "lcov",
+            Self::Cobertura => "cobertura",
+        }
+    }
+}
+
+/// Line hits per file of a report
+#[derive(Debug, Default, PartialEq)]
+pub struct CoverageReport {
+    /// Cobertura `<source>` directories, which file paths are relative to
+    pub sources: Vec<String>,
+    /// Report path → line number → hits
+    pub files: BTreeMap<String, BTreeMap<u32, u64>>,
+}
+
+impl CoverageReport {
+    fn add_hits(&mut self, path: &str, line: u32, hits: u64) {
+        let count = self
+            .files
+            .entry(path.to_string())
+            .or_default()
+            .entry(line)
+            .or_default();
+        *count = count.saturating_add(hits);
+    }
+}
+
+/// Parse an LCOV tracefile (`SF:`, `DA:` and `end_of_record` records).
+pub fn parse_lcov(report: &str) -> CoverageReport {
+    let mut parsed = CoverageReport::default();
+    let mut current: Option<&str> = None;
+    for line in report.lines() {
+        let line = line.trim();
+        if let Some(path) = line.strip_prefix("SF:") {
+            current = Some(path.trim());
+        } else if line == "end_of_record" {
+            current = None;
+        } else if let (Some(path), Some(data)) = (current, line.strip_prefix("DA:")) {
+            let mut fields = data.split(',');
+            let (Some(number), Some(hits)) = (fields.next(), fields.next()) else {
+                continue;
+            };
+            let Ok(number) = number.trim().parse::<u32>() else {
+                continue;
+            };
+            // Some generators write hit counts as floats
+            let hits = hits.trim();
+            let hits = hits
+                .parse::<u64>()
+                .or_else(|_| hits.parse::<f64>().map(|h| h.max(0.0) as u64));
+            if let Ok(hits) = hits {
+                parsed.add_hits(path, number, hits);
+            }
+        }
+    }
+    parsed
+}
+
+/// Parse a Cobertura XML report (`<source>`, `<class filename>` and their
+/// `<line number hits>`).
+pub fn parse_cobertura(report: &str) -> CoverageReport {
+    let mut parsed = CoverageReport::default();
+    let mut current: Option<String> = None;
+    for tag in xml::tags(report) {
+        match (tag.name, tag.closing) {
+            ("source", false) => {
+                let source = tag.text();
+                if !source.is_empty() {
+                    parsed.sources.push(source);
+                }
+            }
+            ("class", false) => current = tag.attr("filename"),
+            ("class", true) => current = None,
+            ("line", false) => {
+                let Some(path) = &current else {
+                    continue;
+                };
+                let number = tag.attr("number").and_then(|n| n.parse::<u32>().ok());
+                let hits = tag.attr("hits").and_then(|h| h.parse::<u64>().ok());
+                if let (Some(number), Some(hits)) = (number, hits) {
+                    parsed.add_hits(path, number, hits);
+                }
+            }
+            _ => {}
+        }
+    }
+    parsed
+}
+
+fn join(dir: &str, path: &str) -> String {
+    format!("{}/{}", dir.trim_end_matches('/'), path)
+}
+
+/// The synced file a report path names, `None` when none or several do.
+pub(crate) fn resolve_path(
+    report_path: &str,
+    bases: &[String],
+    synced: &HashSet<&str>,
+    index: &SuffixIndex,
+) -> Option<String> {
+    let path = report_path.replace('\\', "/");
+    let path = path.trim_start_matches("./");
+    if synced.contains(path) {
+        return Some(path.to_string());
+    }
+    if !path.starts_with('/') {
+        for base in bases {
+            let joined = join(base, path);
+            if synced.contains(joined.as_str()) {
+                return Some(joined);
+            }
+        }
+    }
+
+    // Longest unambiguous suffix, down to `dir/file`, or the bare file name
+    // of a report listing file names only
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    for start in 0..segments.len() {
+        if start > 0 && segments.len() - start < 2 {
+            break;
+        }
+        let suffix = segments[start..].join("/");
+        if let Some(found) = index.get(&suffix) {
+            return Some(found.to_string());
+        }
+        if index.is_ambiguous(&suffix) {
+            return None;
+        }
+    }
+    None
+}
+
+fn pct(covered: u32, instrumented: u32) -> f64 {
+    (covered as f64 * 10_000.0 / instrumented as f64).round() / 100.0
+}
+
+/// Coverage of each function of a file with instrumented lines.
+pub fn function_coverage(
+    spans: &[&FunctionSpanRow],
+    lines: &BTreeMap<u32, u64>,
+) -> Vec<CoverageUpdate> {
+    spans
+        .iter()
+        .filter_map(|span| {
+            let in_span: Vec<u64> = lines
+                .range(span.line_start..=span.line_end.max(span.line_start))
+                .map(|(_, hits)| *hits)
+                .collect();
+            if in_span.is_empty() {
+                return None;
+            }
+            let covered = in_span.iter().filter(|h| **h > 0).count() as u32;
+            let instrumented = in_span.len() as u32;
+            Some(CoverageUpdate {
+                id: span.id.clone(),
+                covered_lines: covered,
+                instrumented_lines: instrumented,
+                coverage_pct: pct(covered, instrumented),
+            })
+        })
+        .collect()
+}
+
+/// Outcome of a coverage ingestion
+#[derive(Debug, Serialize)]
+pub struct CoverageIngestion {
+    pub format: CoverageFormat,
+    /// Files in the report
+    pub report_files: usize,
+    /// Report files matched to a synced file
+    pub matched_files: usize,
+    /// Report paths matched to no synced file (or to several), the first 50
+    pub unmatched_files: Vec<String>,
+    /// Functions given a `coverage_pct`
+    pub functions_covered: usize,
+    pub covered_lines: u32,
+    pub instrumented_lines: u32,
+    /// Line coverage of the matched files (0–100)
+    pub coverage_pct: f64,
+}
+
+/// Parse a report in `format`, detected when not given.
+pub fn parse_report(
+    report: &str,
+    format: Option<CoverageFormat>,
+) -> (CoverageFormat, CoverageReport) {
+    let format = format.unwrap_or_else(|| CoverageFormat::detect(report));
+    let parsed = match format {
+        CoverageFormat::Lcov => parse_lcov(report),
+        CoverageFormat::Cobertura => parse_cobertura(report),
+    };
+    (format, parsed)
+}
+
+/// Store a parsed report on the project's Function and File nodes.
+pub async fn ingest_coverage(
+    neo4j: &dyn GraphStore,
+    project: &ProjectNode,
+    format: CoverageFormat,
+    parsed: &CoverageReport,
+) -> Result<CoverageIngestion> {
+    let synced_paths = neo4j.get_project_file_paths(project.id).await?;
+    let synced: HashSet<&str> = synced_paths.iter().map(String::as_str).collect();
+    let index = SuffixIndex::build(&synced_paths);
+    let mut bases: Vec<String> = parsed
+        .sources
+        .iter()
+        .map(|s| s.replace('\\', "/"))
+        .collect();
+    bases.extend(super::project_roots(&project.root_path));
+
+    // Several report entries may name the same file
+    let mut matched: BTreeMap<String, BTreeMap<u32, u64>> = BTreeMap::new();
+    let mut unmatched = Vec::new();
+    for (report_path, lines) in &parsed.files {
+        match resolve_path(report_path, &bases, &synced, &index) {
+            Some(path) => {
+                let hits = matched.entry(path).or_default();
+                for (line, count) in lines {
+                    let total = hits.entry(*line).or_default();
+                    *total = total.saturating_add(*count);
+                }
+            }
+            None => unmatched.push(report_path.clone()),
+        }
+    }
+
+    let spans = neo4j.get_project_function_spans(project.id).await?;
+    let mut spans_by_file: HashMap<&str, Vec<&FunctionSpanRow>> = HashMap::new();
+    for span in &spans {
+        spans_by_file
+            .entry(span.file_path.as_str())
+            .or_default()
+            .push(span);
+    }
+
+    let mut functions = Vec::new();
+    let mut files = Vec::new();
+    for (path, lines) in &matched {
+        let covered = lines.values().filter(|h| **h > 0).count() as u32;
+        let instrumented = lines.len() as u32;
+        files.push(CoverageUpdate {
+            id: path.clone(),
+            covered_lines: covered,
+            instrumented_lines: instrumented,
+            coverage_pct: pct(covered, instrumented),
+        });
+        if let Some(spans) = spans_by_file.get(path.as_str()) {
+            functions.extend(function_coverage(spans, lines));
+        }
+    }
+
+    let file_paths: Vec<String> = matched.keys().cloned().collect();
+    neo4j
+        .batch_set_coverage(&file_paths, &functions, &files)
+        .await?;
+
+    let covered_lines = files.iter().map(|f| f.covered_lines).sum();
+    let instrumented_lines = files.iter().map(|f| f.instrumented_lines).sum();
+    let unmatched_count = unmatched.len();
+    unmatched.truncate(MAX_UNMATCHED_LISTED);
+    Ok(CoverageIngestion {
+        format,
+        report_files: matched.len() + unmatched_count,
+        matched_files: matched.len(),
+        unmatched_files: unmatched,
+        functions_covered: functions.len(),
+        covered_lines,
+        instrumented_lines,
+        coverage_pct: if instrumented_lines == 0 {
+            0.0
+        } else {
+            pct(covered_lines, instrumented_lines)
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::{FunctionNode, Visibility};
+
+    const LCOV: &str = "TN:\n\
+        SF:/home/runner/work/demo/demo/src/lib.rs\n\
+        FN:1,parse\n\
+        DA:1,4\n\
+        DA:2,4\n\
+        DA:3,0\n\
+        DA:10,0\n\
+        DA:11,0\n\
+        end_of_record\n\
+        SF:/home/runner/work/demo/demo/tests/it.rs\n\
+        DA:1,1\n\
+        end_of_record\n";
+
+    const COBERTURA: &str = r#"<?xml version="1.0" ?>
+<!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">
+<coverage line-rate="0.5" version="7.4">
+  <sources>
+    <source>/ci/checkout</source>
+  </sources>
+  <packages>
+    <package name="app">
+      <classes>
+        <!-- <class filename="ignored.py"> -->
+        <class name="models.py" filename="app/models.py" line-rate="0.5">
+          <methods/>
+          <lines>
+            <line number="1" hits="1"/>
+            <line number='2' hits='0' branch="false"/>
+          </lines>
+        </class>
+      </classes>
+    </package>
+  </packages>
+</coverage>"#;
+
+    #[test]
+    fn test_parse_reports() {
+        assert_eq!(CoverageFormat::detect(LCOV), CoverageFormat::Lcov);
+        assert_eq!(CoverageFormat::detect(COBERTURA), CoverageFormat::Cobertura);
+
+        let lcov = parse_lcov(LCOV);
+        assert_eq!(lcov.files.len(), 2);
+        let lib = &lcov.files["/home/runner/work/demo/demo/src/lib.rs"];
+        assert_eq!(lib.len(), 5);
+        assert_eq!(lib[&1], 4);
+        assert_eq!(lib[&3], 0);
+
+        let cobertura = parse_cobertura(COBERTURA);
+        assert_eq!(cobertura.sources, ["/ci/checkout"]);
+        assert_eq!(cobertura.files.len(), 1);
+        let models = &cobertura.files["app/models.py"];
+        assert_eq!(models.iter().collect::<Vec<_>>(), [(&1, &1), (&2, &0)]);
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        let paths = vec![
+            "/repo/src/lib.rs".to_string(),
+            "/repo/crates/a/src/main.rs".to_string(),
+            "/repo/crates/b/src/main.rs".to_string(),
+        ];
+        let synced: HashSet<&str> = paths.iter().map(String::as_str).collect();
+        let index = SuffixIndex::build(&paths);
+        let bases = vec!["/repo".to_string()];
+        let resolve = |p: &str| resolve_path(p, &bases, &synced, &index);
+
+        assert_eq!(
+            resolve("/repo/src/lib.rs").as_deref(),
+            Some("/repo/src/lib.rs")
+        );
+        assert_eq!(resolve("./src/lib.rs").as_deref(), Some("/repo/src/lib.rs"));
+        assert_eq!(
+            resolve("crates/a/src/main.rs").as_deref(),
+            Some("/repo/crates/a/src/main.rs")
+        );
+        assert_eq!(
+            resolve("/ci/work/crates/b/src/main.rs").as_deref(),
+            Some("/repo/crates/b/src/main.rs")
+        );
+        assert_eq!(resolve("/ci/work/src/main.rs"), None);
+        assert_eq!(resolve("lib.rs").as_deref(), Some("/repo/src/lib.rs"));
+        assert_eq!(resolve("/elsewhere/lib.rs"), None);
+    }
+
+    fn function(name: &str, line_start: u32, line_end: u32) -> FunctionNode {
+        FunctionNode {
+            name: name.to_string(),
+            visibility: Visibility::Public,
+            params: vec![],
+            return_type: None,
+            generics: vec![],
+            is_async: false,
+            is_unsafe: false,
+            complexity: 1,
+            file_path: "/repo/src/lib.rs".to_string(),
+            line_start,
+            line_end,
+            docstring: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ingest_coverage() {
+        let graph = MockGraphStore::new();
+        let mut project = crate::test_helpers::test_project_named("covered");
+        project.root_path = "/repo".to_string();
+        graph.create_project(&project).await.unwrap();
+        graph
+            .link_file_to_project("/repo/src/lib.rs", project.id)
+            .await
+            .unwrap();
+        for f in [
+            function("parse", 1, 5),
+            function("render", 10, 12),
+            function("noop", 20, 21),
+        ] {
+            graph.upsert_function(&f).await.unwrap();
+        }
+
+        let (format, parsed) = parse_report(LCOV, None);
+        let ingestion = ingest_coverage(&graph, &project, format, &parsed)
+            .await
+            .unwrap();
+        assert_eq!(ingestion.format, CoverageFormat::Lcov);
+        assert_eq!(ingestion.report_files, 2);
+        assert_eq!(ingestion.matched_files, 1);
+        assert_eq!(
+            ingestion.unmatched_files,
+            ["/home/runner/work/demo/demo/tests/it.rs"]
+        );
+        // `noop` has no instrumented line
+        assert_eq!(ingestion.functions_covered, 2);
+        assert_eq!(ingestion.coverage_pct, 40.0);
+
+        let coverage = graph.function_coverage.read().await;
+        assert_eq!(coverage["/repo/src/lib.rs:parse:1"].coverage_pct, 66.67);
+        assert_eq!(coverage["/repo/src/lib.rs:render:10"].coverage_pct, 0.0);
+        drop(coverage);
+
+        let untested = graph
+            .get_untested_functions(project.id, UNTESTED_COVERAGE_PCT, 10)
+            .await
+            .unwrap();
+        assert_eq!(untested.len(), 1);
+        assert_eq!(untested[0].name, "render");
+        let file = graph
+            .get_file_coverage("/repo/src/lib.rs", UNTESTED_COVERAGE_PCT)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(file.instrumented_lines, 5);
+        assert_eq!(file.untested_functions[0].name, "render");
+
+        assert!(parse_report("TN:\n", None).1.files.is_empty());
+    }
+}
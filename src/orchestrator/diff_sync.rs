@@ -0,0 +1,594 @@
+//! Diff-scoped sync for pull request branches.
+//!
+//! [`sync_diff`] parses the files changed between two commits, at both
+//! commits, into an in-memory overlay rather than the graph.
+//! [`analyze_overlay`] then compares each file across the two sides
+//! (functions added and removed, complexity changes), checks the head side
+//! against the project budgets, and fingerprints its functions against the
+//! rest of the project for duplicates. The overlay is dropped once the
+//! report is built: the project graph is never written.
+
+use super::budgets::{finish_violations, violation};
+use super::review::FileChange;
+use crate::neo4j::models::{FunctionFingerprintRow, FunctionNode, ProjectNode};
+use crate::neo4j::traits::GraphStore;
+use crate::parser::clones::{find_clone_groups, CloneGroup, DEFAULT_CLONE_THRESHOLD};
+use crate::parser::{CodeParser, ParsedFile};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tokio::process::Command;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Files analyzed per diff, in `git diff` order
+pub const MAX_DIFF_FILES: usize = 500;
+/// Duplicate groups listed in a report
+const MAX_DUPLICATES: usize = 20;
+
+/// One side (base or head) of an overlay file
+#[derive(Debug)]
+pub struct OverlaySide {
+    pub lines: u64,
+    /// `None` for languages the parser does not support
+    pub parsed: Option<ParsedFile>,
+}
+
+/// A changed file, parsed at the commits where it exists
+#[derive(Debug)]
+pub struct OverlayFile {
+    /// Relative to the project root
+    pub path: String,
+    /// Path the file has (or would have) in the graph
+    pub graph_path: String,
+    pub change: FileChange,
+    pub base: Option<OverlaySide>,
+    pub head: Option<OverlaySide>,
+}
+
+/// A function found at both commits with a different complexity
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ComplexityChange {
+    pub name: String,
+    pub before: u32,
+    pub after: u32,
+}
+
+/// Delta of one changed file
+#[derive(Debug, Clone, Serialize)]
+pub struct DiffSyncFile {
+    pub path: String,
+    pub change: FileChange,
+    /// `None` when the file is not parsed (unsupported language)
+    pub language: Option<String>,
+    pub lines_before: Option<u64>,
+    pub lines_after: Option<u64>,
+    pub functions_added: Vec<String>,
+    pub functions_removed: Vec<String>,
+    pub complexity_changes: Vec<ComplexityChange>,
+}
+
+/// Analytics of the files changed between two commits
+#[derive(Debug, Clone, Serialize)]
+pub struct DiffSyncReport {
+    pub project_id: Uuid,
+    /// Commit SHAs the refs resolved to
+    pub base: String,
+    pub head: String,
+    /// Files changed between the commits; only the first 500 are analyzed
+    pub files_changed: usize,
+    pub files: Vec<DiffSyncFile>,
+    pub functions_added: usize,
+    pub functions_removed: usize,
+    /// Sum of the function complexities at head minus at base
+    pub complexity_delta: i64,
+    /// Project budgets exceeded by the changed files at head
+    pub budget_violations: Vec<crate::neo4j::models::BudgetViolation>,
+    /// Clone groups including a function of the changed files at head
+    pub duplicates: Vec<CloneGroup>,
+    pub elapsed_ms: u64,
+}
+
+pub(super) async fn git(cwd: &str, args: &[&str]) -> Result<std::process::Output> {
+    Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .await
+        .with_context(|| format!("Failed to run git {}", args.first().unwrap_or(&"")))
+}
+
+/// SHA of the commit `rev` names in the repository at `cwd`, `None` when it
+/// names none. Revisions starting with `-` are refused.
+pub async fn resolve_commit(cwd: &str, rev: &str) -> Result<Option<String>> {
+    if rev.is_empty() || rev.starts_with('-') {
+        return Ok(None);
+    }
+    let spec = format!("{}^{{commit}}", rev);
+    let output = git(cwd, &["rev-parse", "--verify", "--quiet", &spec]).await?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    Ok(Some(
+        String::from_utf8_lossy(&output.stdout).trim().to_string(),
+    ))
+}
+
+/// Best-effort `git fetch` of `commits` from `origin`, for commits pushed
+/// after the checkout was last updated (pull request heads).
+pub async fn fetch_commits(cwd: &str, commits: &[&str]) -> bool {
+    if commits.iter().any(|c| c.is_empty() || c.starts_with('-')) {
+        return false;
+    }
+    let mut args = vec!["fetch", "--quiet", "--no-tags", "origin"];
+    args.extend_from_slice(commits);
+    git(cwd, &args)
+        .await
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Best common ancestor of commits `a` and `b`, the base a pull request
+/// diff is taken against.
+pub async fn merge_base(cwd: &str, a: &str, b: &str) -> Result<Option<String>> {
+    let (Some(a), Some(b)) = (resolve_commit(cwd, a).await?, resolve_commit(cwd, b).await?) else {
+        return Ok(None);
+    };
+    let output = git(cwd, &["merge-base", &a, &b]).await?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    Ok(Some(
+        String::from_utf8_lossy(&output.stdout).trim().to_string(),
+    ))
+}
+
+/// Parse `git diff --name-status -z` output. Renames are expected to be
+/// split into a deletion and an addition (`--no-renames`).
+fn parse_name_status(output: &[u8]) -> Vec<(FileChange, String)> {
+    let output = String::from_utf8_lossy(output);
+    let mut fields = output.split('\0').filter(|f| !f.is_empty());
+    let mut changes = Vec::new();
+    while let (Some(status), Some(path)) = (fields.next(), fields.next()) {
+        let change = match status.as_bytes().first() {
+            Some(b'A') => FileChange::Added,
+            Some(b'D') => FileChange::Deleted,
+            Some(b'M') | Some(b'T') => FileChange::Modified,
+            _ => continue,
+        };
+        changes.push((change, path.to_string()));
+    }
+    changes
+}
+
+/// Content of `path` (relative to `cwd`) at `commit`, `None` when absent
+/// or not UTF-8.
+async fn show_file(cwd: &str, commit: &str, path: &str) -> Result<Option<String>> {
+    let spec = format!("{}:./{}", commit, path);
+    let output = git(cwd, &["show", &spec]).await?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    Ok(String::from_utf8(output.stdout).ok())
+}
+
+async fn overlay_side(
+    parser: &RwLock<CodeParser>,
+    graph_path: &str,
+    content: Option<String>,
+) -> Option<OverlaySide> {
+    let content = content?;
+    let parsed = parser
+        .write()
+        .await
+        .parse_file(std::path::Path::new(graph_path), &content)
+        .ok();
+    Some(OverlaySide {
+        lines: content.lines().count() as u64,
+        parsed,
+    })
+}
+
+/// Parse the files changed between commits `base` and `head` into an
+/// overlay and analyze it (see [`analyze_overlay`]).
+pub async fn sync_diff(
+    neo4j: &dyn GraphStore,
+    parser: &RwLock<CodeParser>,
+    project: &ProjectNode,
+    base: &str,
+    head: &str,
+) -> Result<DiffSyncReport> {
+    let started = std::time::Instant::now();
+    let roots = super::project_roots(&project.root_path);
+    let cwd = &roots[0];
+
+    let output = git(
+        cwd,
+        &[
+            "diff",
+            "--name-status",
+            "--no-renames",
+            "--relative",
+            "-z",
+            base,
+            head,
+        ],
+    )
+    .await?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git diff failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    let changes = parse_name_status(&output.stdout);
+
+    let synced: HashSet<String> = neo4j
+        .list_project_files(project.id)
+        .await?
+        .into_iter()
+        .map(|f| f.path)
+        .collect();
+    let mut overlay = Vec::new();
+    for (change, path) in changes.iter().take(MAX_DIFF_FILES) {
+        let candidates: Vec<String> = roots
+            .iter()
+            .map(|root| format!("{}/{}", root.trim_end_matches('/'), path))
+            .collect();
+        // New files get the canonical root, like synced files
+        let graph_path = candidates
+            .iter()
+            .find(|p| synced.contains(*p))
+            .or(candidates.last())
+            .cloned()
+            .unwrap_or_default();
+
+        let base_content = match change {
+            FileChange::Added => None,
+            _ => show_file(cwd, base, path).await?,
+        };
+        let head_content = match change {
+            FileChange::Deleted => None,
+            _ => show_file(cwd, head, path).await?,
+        };
+        overlay.push(OverlayFile {
+            path: path.clone(),
+            change: *change,
+            base: overlay_side(parser, &graph_path, base_content).await,
+            head: overlay_side(parser, &graph_path, head_content).await,
+            graph_path,
+        });
+    }
+
+    let mut report = analyze_overlay(neo4j, project, &overlay).await?;
+    report.base = base.to_string();
+    report.head = head.to_string();
+    report.files_changed = changes.len();
+    report.elapsed_ms = started.elapsed().as_millis() as u64;
+    Ok(report)
+}
+
+fn functions_of(side: &Option<OverlaySide>) -> &[FunctionNode] {
+    side.as_ref()
+        .and_then(|s| s.parsed.as_ref())
+        .map(|p| p.functions.as_slice())
+        .unwrap_or_default()
+}
+
+/// Functions added and removed between two versions of a file, and those
+/// whose complexity changed. Functions are told apart by name (the highest
+/// complexity wins among namesakes).
+fn compare_functions(
+    base: &[FunctionNode],
+    head: &[FunctionNode],
+) -> (Vec<String>, Vec<String>, Vec<ComplexityChange>) {
+    let by_name = |functions: &[FunctionNode]| {
+        let mut map: BTreeMap<String, u32> = BTreeMap::new();
+        for f in functions {
+            let complexity = map.entry(f.name.clone()).or_default();
+            *complexity = (*complexity).max(f.complexity);
+        }
+        map
+    };
+    let (base, head) = (by_name(base), by_name(head));
+    let added = head
+        .keys()
+        .filter(|name| !base.contains_key(*name))
+        .cloned()
+        .collect();
+    let removed = base
+        .keys()
+        .filter(|name| !head.contains_key(*name))
+        .cloned()
+        .collect();
+    let changes = head
+        .iter()
+        .filter_map(|(name, &after)| {
+            let &before = base.get(name)?;
+            (before != after).then(|| ComplexityChange {
+                name: name.clone(),
+                before,
+                after,
+            })
+        })
+        .collect();
+    (added, removed, changes)
+}
+
+/// Analyze an overlay against `project`: per-file deltas, head-side budget
+/// violations and duplicates. Commits and timing are left empty.
+pub async fn analyze_overlay(
+    neo4j: &dyn GraphStore,
+    project: &ProjectNode,
+    overlay: &[OverlayFile],
+) -> Result<DiffSyncReport> {
+    let mut files = Vec::new();
+    let (mut functions_added, mut functions_removed) = (0, 0);
+    let mut complexity_delta = 0i64;
+    for file in overlay {
+        let (base, head) = (functions_of(&file.base), functions_of(&file.head));
+        let (added, removed, complexity_changes) = compare_functions(base, head);
+        functions_added += added.len();
+        functions_removed += removed.len();
+        complexity_delta += head.iter().map(|f| f.complexity as i64).sum::<i64>()
+            - base.iter().map(|f| f.complexity as i64).sum::<i64>();
+        let language = [&file.head, &file.base]
+            .into_iter()
+            .flatten()
+            .find_map(|side| side.parsed.as_ref().map(|p| p.language.clone()));
+        files.push(DiffSyncFile {
+            path: file.path.clone(),
+            change: file.change,
+            language,
+            lines_before: file.base.as_ref().map(|s| s.lines),
+            lines_after: file.head.as_ref().map(|s| s.lines),
+            functions_added: added,
+            functions_removed: removed,
+            complexity_changes,
+        });
+    }
+
+    let mut violations = Vec::new();
+    if let Some(budgets) = neo4j.get_project_budgets(project.id).await? {
+        for file in overlay {
+            let Some(head) = &file.head else { continue };
+            if let Some(limit) = budgets.max_function_complexity {
+                for f in functions_of(&file.head) {
+                    if f.complexity > limit {
+                        let id = format!("{}:{}:{}", f.file_path, f.name, f.line_start);
+                        violations.push(violation(
+                            "max_function_complexity",
+                            id,
+                            f.complexity as u64,
+                            limit,
+                        ));
+                    }
+                }
+            }
+            if let Some(limit) = budgets.max_file_lines {
+                if head.lines > limit as u64 {
+                    violations.push(violation(
+                        "max_file_lines",
+                        file.path.clone(),
+                        head.lines,
+                        limit,
+                    ));
+                }
+            }
+        }
+    }
+    let (budget_violations, _) = finish_violations(violations);
+
+    // The changed files' graph functions are replaced by their head version
+    let changed: HashSet<&str> = overlay.iter().map(|f| f.graph_path.as_str()).collect();
+    let mut rows: Vec<FunctionFingerprintRow> = neo4j
+        .get_project_function_fingerprints(project.id)
+        .await?
+        .into_iter()
+        .filter(|r| !changed.contains(r.file_path.as_str()))
+        .collect();
+    let mut head_ids = HashSet::new();
+    for parsed in overlay
+        .iter()
+        .filter_map(|f| f.head.as_ref().and_then(|s| s.parsed.as_ref()))
+    {
+        let functions: HashMap<String, &FunctionNode> = parsed
+            .functions
+            .iter()
+            .map(|f| (format!("{}:{}:{}", f.file_path, f.name, f.line_start), f))
+            .collect();
+        for fp in &parsed.function_fingerprints {
+            let Some(f) = functions.get(&fp.function_id) else {
+                continue;
+            };
+            head_ids.insert(fp.function_id.clone());
+            rows.push(FunctionFingerprintRow {
+                id: fp.function_id.clone(),
+                name: f.name.clone(),
+                file_path: f.file_path.clone(),
+                line_start: f.line_start,
+                line_end: f.line_end,
+                token_count: fp.token_count,
+                minhash: fp.minhash.clone(),
+            });
+        }
+    }
+    let mut duplicates: Vec<CloneGroup> = if head_ids.is_empty() {
+        Vec::new()
+    } else {
+        find_clone_groups(&rows, DEFAULT_CLONE_THRESHOLD)
+            .into_iter()
+            .filter(|g| g.members.iter().any(|m| head_ids.contains(&m.id)))
+            .collect()
+    };
+    duplicates.truncate(MAX_DUPLICATES);
+
+    Ok(DiffSyncReport {
+        project_id: project.id,
+        base: String::new(),
+        head: String::new(),
+        files_changed: overlay.len(),
+        files,
+        functions_added,
+        functions_removed,
+        complexity_delta,
+        budget_violations,
+        duplicates,
+        elapsed_ms: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::{FileNode, ProjectBudgets};
+    use crate::test_helpers::test_project_named;
+
+    #[test]
+    fn test_parse_name_status() {
+        let output = b"M\0src/lib.rs\0A\0src/new.rs\0D\0old.rs\0X\0weird\0";
+        assert_eq!(
+            parse_name_status(output),
+            [
+                (FileChange::Modified, "src/lib.rs".to_string()),
+                (FileChange::Added, "src/new.rs".to_string()),
+                (FileChange::Deleted, "old.rs".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_commit_refuses_options() {
+        assert_eq!(resolve_commit(".", "--output=x").await.unwrap(), None);
+        assert_eq!(resolve_commit(".", "").await.unwrap(), None);
+    }
+
+    // Long enough to be fingerprinted
+    const SHARED_BODY: &str = "let mut total = 0;
+    for item in items.iter() {
+        if item.enabled && item.weight > 3 {
+            total += item.weight * 2 + item.bonus;
+        } else {
+            total -= item.penalty;
+        }
+    }
+    for (index, item) in items.iter().enumerate() {
+        if index % 2 == 0 && item.bonus > item.penalty {
+            total += item.bonus - item.penalty;
+        } else if item.weight == 0 {
+            total -= 1;
+        }
+    }
+    total";
+
+    fn parse(path: &str, content: &str) -> OverlaySide {
+        let mut parser = CodeParser::new().unwrap();
+        OverlaySide {
+            lines: content.lines().count() as u64,
+            parsed: parser.parse_file(std::path::Path::new(path), content).ok(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_analyze_overlay() {
+        let graph = MockGraphStore::new();
+        let mut project = test_project_named("overlay");
+        project.root_path = "/repo".to_string();
+        graph.create_project(&project).await.unwrap();
+
+        // A synced function the head side duplicates
+        let existing = format!(
+            "fn score(items: &[Item]) -> i32 {{\n    {}\n}}\n",
+            SHARED_BODY
+        );
+        let synced = parse("/repo/src/score.rs", &existing).parsed.unwrap();
+        graph
+            .upsert_file(&FileNode {
+                path: synced.path.clone(),
+                language: "rust".to_string(),
+                hash: synced.hash.clone(),
+                line_count: 0,
+                last_parsed: chrono::Utc::now(),
+                project_id: Some(project.id),
+                encoding: None,
+            })
+            .await
+            .unwrap();
+        graph
+            .link_file_to_project(&synced.path, project.id)
+            .await
+            .unwrap();
+        for f in &synced.functions {
+            graph.upsert_function(f).await.unwrap();
+        }
+        graph
+            .batch_set_function_fingerprints(
+                std::slice::from_ref(&synced.path),
+                &synced.function_fingerprints,
+            )
+            .await
+            .unwrap();
+        graph
+            .update_project_budgets(
+                project.id,
+                &ProjectBudgets {
+                    max_function_complexity: Some(2),
+                    max_file_lines: None,
+                    max_module_fan_in: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let base = "fn kept(x: i32) -> i32 {\n    x\n}\n\nfn dropped() {}\n";
+        let head = format!(
+            "fn kept(x: i32) -> i32 {{\n    if x > 0 {{ x }} else if x < -10 {{ 0 }} else {{ -x }}\n}}\n\n\
+             fn copied(items: &[Item]) -> i32 {{\n    {}\n}}\n",
+            SHARED_BODY
+        );
+        let overlay = vec![
+            OverlayFile {
+                path: "src/lib.rs".to_string(),
+                graph_path: "/repo/src/lib.rs".to_string(),
+                change: FileChange::Modified,
+                base: Some(parse("/repo/src/lib.rs", base)),
+                head: Some(parse("/repo/src/lib.rs", &head)),
+            },
+            OverlayFile {
+                path: "README.md".to_string(),
+                graph_path: "/repo/README.md".to_string(),
+                change: FileChange::Added,
+                base: None,
+                head: Some(parse("/repo/README.md", "# Title\n")),
+            },
+        ];
+
+        let report = analyze_overlay(&graph, &project, &overlay).await.unwrap();
+        let lib = &report.files[0];
+        assert_eq!(lib.language.as_deref(), Some("rust"));
+        assert_eq!(lib.functions_added, ["copied"]);
+        assert_eq!(lib.functions_removed, ["dropped"]);
+        assert_eq!(lib.complexity_changes.len(), 1);
+        assert_eq!(lib.complexity_changes[0].name, "kept");
+        assert!(lib.complexity_changes[0].after > lib.complexity_changes[0].before);
+        assert_eq!((report.functions_added, report.functions_removed), (1, 1));
+        assert!(report.complexity_delta > 0);
+
+        assert_eq!(report.files[1].language, None);
+        assert_eq!(report.files[1].lines_after, Some(1));
+
+        assert!(report
+            .budget_violations
+            .iter()
+            .all(|v| v.target.starts_with("/repo/src/lib.rs:")));
+        assert!(!report.budget_violations.is_empty());
+
+        assert_eq!(report.duplicates.len(), 1);
+        let names: HashSet<&str> = report.duplicates[0]
+            .members
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect();
+        assert_eq!(names, HashSet::from(["score", "copied"]));
+    }
+}
@@ -1,8 +1,25 @@
 //! Orchestrator module for coordinating agents
 
+pub mod adr;
+pub mod api_surface;
+pub mod budgets;
+pub mod commit_message;
 pub mod context;
+pub mod context_bundle;
+pub mod coverage;
+pub mod diff_sync;
+pub mod module_summary;
+pub mod ownership;
 pub mod planner;
+pub mod resource_link_hook;
+pub mod retention;
+pub mod review;
 pub mod runner;
+pub mod semver_advice;
+pub mod stack_trace;
+pub mod sync_limits;
+pub mod test_results;
+pub mod topology_drift;
 pub mod topology_hook;
 pub mod watcher;
 
@@ -60,10 +77,99 @@ pub fn should_ignore_path(path_str: &str) -> bool {
         .any(|seg| normalized.contains(seg))
 }
 
+// ============================================================================
+// Project-relative paths
+// ============================================================================
+
+/// Prefixes under which the synced files of a project are stored: the root
+/// with `~` expanded and, when it exists, its canonical form (synced paths
+/// are canonicalized, the stored root may not be).
+pub fn project_roots(root_path: &str) -> Vec<String> {
+    let expanded = crate::expand_tilde(root_path);
+    let mut roots = vec![expanded.clone()];
+    if let Ok(canonical) = std::fs::canonicalize(&expanded) {
+        let canonical = canonical.to_string_lossy().to_string();
+        if canonical != expanded {
+            roots.push(canonical);
+        }
+    }
+    roots
+}
+
+/// `path` relative to the first of `roots` containing it, or without its
+/// leading `/` when none does.
+pub fn relative_to_roots<'a>(path: &'a str, roots: &[String]) -> &'a str {
+    roots
+        .iter()
+        .find_map(|root| {
+            path.strip_prefix(root.trim_end_matches('/'))
+                .filter(|rest| rest.starts_with('/'))
+        })
+        .unwrap_or(path)
+        .trim_start_matches('/')
+}
+
+// ============================================================================
+// Archived projects
+// ============================================================================
+
+/// Slugs of the archived projects.
+pub async fn archived_project_slugs(
+    graph: &dyn crate::neo4j::GraphStore,
+) -> anyhow::Result<std::collections::HashSet<String>> {
+    Ok(graph
+        .list_projects()
+        .await?
+        .into_iter()
+        .filter(|p| p.archived)
+        .map(|p| p.slug)
+        .collect())
+}
+
+/// Code search across all projects, leaving out the code of the `archived`
+/// ones. Fetches more hits than `limit` when there are archived projects so
+/// that dropping theirs still fills the limit in most cases.
+pub async fn search_unarchived_code(
+    meili: &dyn crate::meilisearch::SearchStore,
+    archived: &std::collections::HashSet<String>,
+    query: &str,
+    limit: usize,
+    language: Option<&str>,
+) -> anyhow::Result<
+    Vec<crate::meilisearch::indexes::SearchHit<crate::meilisearch::indexes::CodeDocument>>,
+> {
+    let fetch = if archived.is_empty() {
+        limit
+    } else {
+        limit * 3
+    };
+    let mut hits = meili
+        .search_code_with_scores(query, fetch, language, None, None)
+        .await?;
+    hits.retain(|h| !archived.contains(&h.document.project_slug));
+    hits.truncate(limit);
+    Ok(hits)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_relative_to_roots() {
+        let roots = vec!["/home/me/repo/".to_string(), "/srv/repo".to_string()];
+        assert_eq!(
+            relative_to_roots("/home/me/repo/src/lib.rs", &roots),
+            "src/lib.rs"
+        );
+        assert_eq!(relative_to_roots("/srv/repo/build.rs", &roots), "build.rs");
+        assert_eq!(
+            relative_to_roots("/srv/repository/a.rs", &roots),
+            "srv/repository/a.rs"
+        );
+        assert_eq!(relative_to_roots("/vendor/x.rs", &roots), "vendor/x.rs");
+    }
+
     #[test]
     fn test_should_ignore_all_patterns() {
         // Every segment in IGNORED_PATH_SEGMENTS must trigger ignoring
@@ -0,0 +1,450 @@
+//! Per-directory module summaries.
+//!
+//! Every directory holding synced files gets a short natural-language summary
+//! built from its file names, top public symbols and their docstrings. The
+//! summaries are cached on `(:ModuleSummary)` nodes together with a hash of
+//! the contained files' content hashes, so a refresh only summarizes again the
+//! directories whose files changed.
+//!
+//! Summaries are written in batches by the prompt builder model
+//! (`PROMPT_BUILDER_MODEL`). When no model is set or a call fails, a summary
+//! assembled from the same facts is stored instead.
+
+use crate::neo4j::models::{ModuleFileDigest, ModuleSummaryNode};
+use crate::neo4j::traits::GraphStore;
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+/// Public symbols fetched per file
+const SYMBOLS_PER_FILE: usize = 5;
+/// Directories kept per project, those with the most files first
+const MAX_MODULES: usize = 200;
+/// Directories summarized per LLM call
+const LLM_BATCH_SIZE: usize = 20;
+const MAX_SUMMARY_CHARS: usize = 400;
+const MAX_LISTED_FILES: usize = 6;
+const MAX_LISTED_SYMBOLS: usize = 6;
+
+const SUMMARY_SYSTEM_PROMPT: &str =
+    "You are a code architecture analyst. Respond only with valid JSON.";
+
+/// `generated_by` of the summaries assembled without an LLM
+pub const HEURISTIC_GENERATOR: &str = "heuristic";
+
+/// The files directly inside one directory (not in its subdirectories)
+#[derive(Debug)]
+struct ModuleDigest {
+    /// Relative to the project root, "." for the root itself
+    path: String,
+    files: Vec<ModuleFileDigest>,
+    source_hash: String,
+}
+
+/// Outcome of [`refresh_module_summaries`]
+#[derive(Debug, Default)]
+pub struct ModuleSummaryRefresh {
+    /// Directories with a summary after the refresh
+    pub modules: usize,
+    /// Summaries written because the directory is new or its files changed
+    pub regenerated: usize,
+    /// Summaries deleted because the directory no longer holds synced files
+    pub removed: usize,
+}
+
+/// Group the synced files by directory, relative to the project `roots`.
+fn group_modules(roots: &[String], files: Vec<ModuleFileDigest>) -> Vec<ModuleDigest> {
+    let mut by_dir: BTreeMap<String, Vec<ModuleFileDigest>> = BTreeMap::new();
+    for mut file in files {
+        let relative = super::relative_to_roots(&file.path, roots).to_string();
+        let dir = match relative.rsplit_once('/') {
+            Some((dir, _)) => dir.to_string(),
+            None => ".".to_string(),
+        };
+        file.path = relative;
+        by_dir.entry(dir).or_default().push(file);
+    }
+
+    let mut modules: Vec<ModuleDigest> = by_dir
+        .into_iter()
+        .map(|(path, mut files)| {
+            files.sort_by(|a, b| a.path.cmp(&b.path));
+            let mut hasher = Sha256::new();
+            for file in &files {
+                hasher.update(file.path.as_bytes());
+                hasher.update([0]);
+                hasher.update(file.hash.as_bytes());
+                hasher.update([b'\n']);
+            }
+            ModuleDigest {
+                path,
+                files,
+                source_hash: hex::encode(hasher.finalize()),
+            }
+        })
+        .collect();
+    modules.sort_by(|a, b| b.files.len().cmp(&a.files.len()).then(a.path.cmp(&b.path)));
+    modules.truncate(MAX_MODULES);
+    modules.sort_by(|a, b| a.path.cmp(&b.path));
+    modules
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// First sentence of a docstring, at most 80 characters.
+fn first_sentence(docstring: &str) -> String {
+    let line = docstring
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or("");
+    let sentence = line
+        .split(". ")
+        .next()
+        .unwrap_or(line)
+        .trim_end_matches('.');
+    truncate_chars(sentence, 80)
+}
+
+fn truncate_chars(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((end, _)) => format!("{}…", text[..end].trim_end()),
+        None => text.to_string(),
+    }
+}
+
+/// Summary assembled from the directory's facts, used without an LLM.
+fn heuristic_summary(module: &ModuleDigest) -> String {
+    let count = module.files.len();
+    let mut summary = format!(
+        "{} file{}: {}",
+        count,
+        if count == 1 { "" } else { "s" },
+        module
+            .files
+            .iter()
+            .take(MAX_LISTED_FILES)
+            .map(|f| file_name(&f.path))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    if count > MAX_LISTED_FILES {
+        summary.push_str(&format!(" (+{} more)", count - MAX_LISTED_FILES));
+    }
+    summary.push('.');
+
+    // Documented symbols say more about the module, list them first
+    let mut symbols: Vec<_> = module.files.iter().flat_map(|f| &f.symbols).collect();
+    symbols.sort_by_key(|s| s.docstring.is_none());
+    let listed: Vec<String> = symbols
+        .into_iter()
+        .take(MAX_LISTED_SYMBOLS)
+        .map(|s| match s.docstring.as_deref().map(first_sentence) {
+            Some(doc) if !doc.is_empty() => format!("`{}` ({})", s.name, doc),
+            _ => format!("`{}`", s.name),
+        })
+        .collect();
+    if !listed.is_empty() {
+        summary.push_str(&format!(" Key symbols: {}.", listed.join(", ")));
+    }
+    truncate_chars(&summary, MAX_SUMMARY_CHARS)
+}
+
+fn build_summary_prompt(modules: &[&ModuleDigest]) -> String {
+    let mut prompt = String::from(
+        "Summarize each directory of a codebase in one or two sentences: what the \
+         module is responsible for, not a list of its files. Respond only with a JSON \
+         object mapping each directory path to its summary.\n",
+    );
+    for module in modules {
+        prompt.push_str(&format!("\n## {}\n", module.path));
+        let names: Vec<&str> = module.files.iter().map(|f| file_name(&f.path)).collect();
+        prompt.push_str(&format!("Files: {}\n", names.join(", ")));
+        for symbol in module.files.iter().flat_map(|f| &f.symbols) {
+            match symbol.docstring.as_deref().map(first_sentence) {
+                Some(doc) if !doc.is_empty() => {
+                    prompt.push_str(&format!("- {} `{}`: {}\n", symbol.kind, symbol.name, doc))
+                }
+                _ => prompt.push_str(&format!("- {} `{}`\n", symbol.kind, symbol.name)),
+            }
+        }
+    }
+    prompt
+}
+
+/// Parse the `{path: summary}` object answered by the LLM, which may be
+/// wrapped in a markdown fence.
+fn parse_summary_response(text: &str) -> HashMap<String, String> {
+    let json = text
+        .trim()
+        .trim_start_matches("```json")
+        .trim_start_matches("```")
+        .trim_end_matches("```")
+        .trim();
+    serde_json::from_str::<HashMap<String, String>>(json)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(path, summary)| (path, truncate_chars(summary.trim(), MAX_SUMMARY_CHARS)))
+        .filter(|(_, summary)| !summary.is_empty())
+        .collect()
+}
+
+/// One-turn LLM call, `None` on any failure.
+pub(crate) async fn complete_with_llm(
+    model: &str,
+    system_prompt: &str,
+    prompt: String,
+) -> Option<String> {
+    use nexus_claude::{
+        ClaudeCodeOptions, ContentBlock, InteractiveClient, Message, PermissionMode,
+    };
+
+    #[allow(deprecated)]
+    let options = ClaudeCodeOptions::builder()
+        .model(model)
+        .system_prompt(system_prompt)
+        .permission_mode(PermissionMode::BypassPermissions)
+        .max_turns(1)
+        .build();
+
+    let mut client = match InteractiveClient::new(options) {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::warn!("Failed to create LLM client: {}", e);
+            return None;
+        }
+    };
+    if let Err(e) = client.connect().await {
+        tracing::warn!("Failed to connect LLM client: {}", e);
+        return None;
+    }
+    let messages = client.send_and_receive(prompt).await;
+    let _ = client.disconnect().await;
+    let messages = match messages {
+        Ok(m) => m,
+        Err(e) => {
+            tracing::warn!("LLM call failed: {}", e);
+            return None;
+        }
+    };
+
+    let mut text = String::new();
+    for msg in &messages {
+        if let Message::Assistant { message, .. } = msg {
+            for block in &message.content {
+                if let ContentBlock::Text(t) = block {
+                    text.push_str(&t.text);
+                }
+            }
+        }
+    }
+    (!text.is_empty()).then_some(text)
+}
+
+/// Bring the module summaries of a project in line with its synced files.
+///
+/// Directories that are new or whose files changed are summarized by `model`
+/// (heuristically when `None`, empty, or when the call fails); summaries of
+/// directories without synced files any more are deleted.
+pub async fn refresh_module_summaries(
+    neo4j: &dyn GraphStore,
+    project_id: Uuid,
+    model: Option<&str>,
+) -> Result<ModuleSummaryRefresh> {
+    let project = neo4j
+        .get_project(project_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Project {} not found", project_id))?;
+    let roots = super::project_roots(&project.root_path);
+    let files = neo4j
+        .get_project_module_digest(project_id, SYMBOLS_PER_FILE)
+        .await?;
+    let modules = group_modules(&roots, files);
+
+    let existing: HashMap<String, String> = neo4j
+        .list_module_summaries(project_id)
+        .await?
+        .into_iter()
+        .map(|m| (m.path, m.source_hash))
+        .collect();
+    let removed: Vec<String> = existing
+        .keys()
+        .filter(|path| !modules.iter().any(|m| &m.path == *path))
+        .cloned()
+        .collect();
+    let stale: Vec<&ModuleDigest> = modules
+        .iter()
+        .filter(|m| existing.get(&m.path) != Some(&m.source_hash))
+        .collect();
+
+    let model = model.filter(|m| !m.is_empty());
+    let mut generated = HashMap::new();
+    if let Some(model) = model {
+        for batch in stale.chunks(LLM_BATCH_SIZE) {
+            // A failed call would most likely fail again for the next batches
+            let Some(text) =
+                complete_with_llm(model, SUMMARY_SYSTEM_PROMPT, build_summary_prompt(batch)).await
+            else {
+                break;
+            };
+            generated.extend(parse_summary_response(&text));
+        }
+    }
+
+    let now = chrono::Utc::now();
+    let summaries: Vec<ModuleSummaryNode> = stale
+        .iter()
+        .map(|module| {
+            let (summary, generated_by) = match (generated.remove(&module.path), model) {
+                (Some(summary), Some(model)) => (summary, model.to_string()),
+                _ => (heuristic_summary(module), HEURISTIC_GENERATOR.to_string()),
+            };
+            ModuleSummaryNode {
+                project_id,
+                path: module.path.clone(),
+                summary,
+                source_hash: module.source_hash.clone(),
+                file_count: module.files.len(),
+                generated_by,
+                updated_at: now,
+            }
+        })
+        .collect();
+
+    neo4j.upsert_module_summaries(&summaries).await?;
+    neo4j.delete_module_summaries(project_id, &removed).await?;
+
+    Ok(ModuleSummaryRefresh {
+        modules: modules.len(),
+        regenerated: summaries.len(),
+        removed: removed.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::ModuleSymbolDigest;
+
+    fn digest(path: &str, hash: &str, symbols: &[(&str, Option<&str>)]) -> ModuleFileDigest {
+        ModuleFileDigest {
+            path: path.to_string(),
+            hash: hash.to_string(),
+            symbols: symbols
+                .iter()
+                .map(|(name, doc)| ModuleSymbolDigest {
+                    name: name.to_string(),
+                    kind: "Function".to_string(),
+                    docstring: doc.map(str::to_string),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_group_modules() {
+        let roots = vec!["/repo".to_string()];
+        let modules = group_modules(
+            &roots,
+            vec![
+                digest("/repo/src/api/routes.rs", "a", &[]),
+                digest("/repo/src/api/handlers.rs", "b", &[]),
+                digest("/repo/build.rs", "c", &[]),
+            ],
+        );
+        let paths: Vec<&str> = modules.iter().map(|m| m.path.as_str()).collect();
+        assert_eq!(paths, [".", "src/api"]);
+        assert_eq!(modules[1].files[0].path, "src/api/handlers.rs");
+
+        let changed = group_modules(
+            &roots,
+            vec![
+                digest("/repo/src/api/routes.rs", "a2", &[]),
+                digest("/repo/src/api/handlers.rs", "b", &[]),
+            ],
+        );
+        assert_ne!(changed[0].source_hash, modules[1].source_hash);
+    }
+
+    #[test]
+    fn test_heuristic_summary() {
+        let modules = group_modules(
+            &[],
+            vec![
+                digest("src/api/routes.rs", "a", &[("create_router", None)]),
+                digest(
+                    "src/api/handlers.rs",
+                    "b",
+                    &[("AppError", Some("API error type. Maps to HTTP statuses."))],
+                ),
+            ],
+        );
+        assert_eq!(
+            heuristic_summary(&modules[0]),
+            "2 files: handlers.rs, routes.rs. Key symbols: `AppError` (API error type), `create_router`."
+        );
+    }
+
+    #[test]
+    fn test_parse_summary_response() {
+        let parsed =
+            parse_summary_response("```json\n{\"src/api\": \"HTTP layer.\", \"src\": \"  \"}\n```");
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed["src/api"], "HTTP layer.");
+        assert!(parse_summary_response("not json").is_empty());
+    }
+
+    async fn add_file(graph: &MockGraphStore, project_id: Uuid, path: &str, hash: &str) {
+        graph
+            .upsert_file(&crate::neo4j::models::FileNode {
+                path: path.to_string(),
+                language: "rust".to_string(),
+                hash: hash.to_string(),
+                line_count: 0,
+                last_parsed: chrono::Utc::now(),
+                project_id: Some(project_id),
+                encoding: None,
+            })
+            .await
+            .unwrap();
+        graph.link_file_to_project(path, project_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_refresh_module_summaries() {
+        let graph = MockGraphStore::new();
+        let mut project = crate::test_helpers::test_project_named("summarized");
+        project.root_path = "/repo".to_string();
+        graph.create_project(&project).await.unwrap();
+        add_file(&graph, project.id, "/repo/src/lib.rs", "a").await;
+        add_file(&graph, project.id, "/repo/src/api/mod.rs", "b").await;
+
+        let refresh = refresh_module_summaries(&graph, project.id, None)
+            .await
+            .unwrap();
+        assert_eq!((refresh.modules, refresh.regenerated), (2, 2));
+        let summaries = graph.list_module_summaries(project.id).await.unwrap();
+        assert_eq!(summaries[0].path, "src");
+        assert_eq!(summaries[0].generated_by, HEURISTIC_GENERATOR);
+        assert_eq!(summaries[0].summary, "1 file: lib.rs.");
+
+        // Unchanged files keep their cached summary
+        let refresh = refresh_module_summaries(&graph, project.id, None)
+            .await
+            .unwrap();
+        assert_eq!(refresh.regenerated, 0);
+
+        // A changed file only refreshes its own directory
+        add_file(&graph, project.id, "/repo/src/api/routes.rs", "c").await;
+        let refresh = refresh_module_summaries(&graph, project.id, None)
+            .await
+            .unwrap();
+        assert_eq!(refresh.regenerated, 1);
+        let summaries = graph.list_module_summaries(project.id).await.unwrap();
+        assert_eq!(summaries[1].summary, "2 files: mod.rs, routes.rs.");
+    }
+}
@@ -0,0 +1,433 @@
+//! Code ownership from CODEOWNERS files.
+//!
+//! The CODEOWNERS file of a project (`.github/`, the root, `docs/` or
+//! `.gitlab/`) is read after each sync, or posted to
+//! `POST /api/projects/{id}/codeowners`. Its rules are stored on the project
+//! and every synced file is linked to its owners with
+//! `(:File)-[:OWNED_BY]->(:CodeOwner)`.
+//!
+//! Patterns follow GitHub's semantics: the last matching rule wins, a pattern
+//! with a leading or inner `/` is anchored at the root, a pattern without one
+//! matches at any depth, a trailing `/` only matches directories, and a match
+//! on a directory covers the files below it (except after a trailing
+//! wildcard: `docs/*` only owns the files directly in `docs/`).
+//!
+//! Ownership routes plan tasks to the owners of their files, narrows
+//! notification rules to owners, and lists the reviewers of an impact
+//! analysis.
+
+use crate::neo4j::models::{CodeOwnerRule, ProjectNode};
+use crate::neo4j::traits::GraphStore;
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Where CODEOWNERS files are looked for, in GitHub's order
+pub const CODEOWNERS_LOCATIONS: &[&str] = &[
+    ".github/CODEOWNERS",
+    "CODEOWNERS",
+    "docs/CODEOWNERS",
+    ".gitlab/CODEOWNERS",
+];
+
+/// Rules of a CODEOWNERS file, and the tokens that are not owners
+#[derive(Debug, Default, PartialEq)]
+pub struct ParsedCodeOwners {
+    pub rules: Vec<CodeOwnerRule>,
+    /// `line N: ...` for every ignored token
+    pub errors: Vec<String>,
+}
+
+/// Whether a token names an owner: `@user`, `@org/team` or an email address.
+pub fn is_owner(token: &str) -> bool {
+    match token.strip_prefix('@') {
+        Some(handle) => !handle.is_empty(),
+        None => token
+            .split_once('@')
+            .is_some_and(|(user, domain)| !user.is_empty() && domain.contains('.')),
+    }
+}
+
+/// Parse a CODEOWNERS file. Comments, blank lines and GitLab section headers
+/// (`[Section]`) are skipped.
+pub fn parse_codeowners(content: &str) -> ParsedCodeOwners {
+    let mut parsed = ParsedCodeOwners::default();
+    for (index, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty()
+            || line.starts_with('#')
+            || line.starts_with('[')
+            || line.starts_with("^[")
+        {
+            continue;
+        }
+        let mut tokens = line.split_whitespace();
+        let Some(pattern) = tokens.next() else {
+            continue;
+        };
+        // `\#file` owns a file whose name starts with `#`
+        let pattern = pattern
+            .strip_prefix('\\')
+            .filter(|p| p.starts_with('#'))
+            .unwrap_or(pattern);
+        let mut owners = Vec::new();
+        for token in tokens.take_while(|t| !t.starts_with('#')) {
+            if is_owner(token) {
+                owners.push(token.to_string());
+            } else {
+                parsed.errors.push(format!(
+                    "line {}: `{}` is not a user, team or email",
+                    index + 1,
+                    token
+                ));
+            }
+        }
+        parsed.rules.push(CodeOwnerRule {
+            line: index as u32 + 1,
+            pattern: pattern.to_string(),
+            owners,
+        });
+    }
+    parsed
+}
+
+/// `*` and `?` matching within a path segment.
+fn segment_matches(pattern: &str, name: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let n: Vec<char> = name.chars().collect();
+    let (mut pi, mut ni) = (0, 0);
+    // Last `*` seen, and the name position it currently stops at
+    let mut star: Option<(usize, usize)> = None;
+    while ni < n.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == n[ni]) {
+            pi += 1;
+            ni += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ni));
+            pi += 1;
+        } else if let Some((sp, sn)) = star {
+            pi = sp + 1;
+            ni = sn + 1;
+            star = Some((sp, sn + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|c| *c == '*')
+}
+
+/// Path segments against pattern segments, `**` matching any number of them.
+fn segments_match(pattern: &[&str], parts: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => parts.is_empty(),
+        Some((&"**", rest)) => (0..=parts.len()).any(|i| segments_match(rest, &parts[i..])),
+        Some((segment, rest)) => parts.split_first().is_some_and(|(part, tail)| {
+            segment_matches(segment, part) && segments_match(rest, tail)
+        }),
+    }
+}
+
+/// Whether a CODEOWNERS pattern matches a project-relative file path.
+pub fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let dir_only = pattern.ends_with('/');
+    let body = pattern.trim_end_matches('/');
+    let anchored = body.contains('/');
+    let segments: Vec<&str> = body.split('/').filter(|s| !s.is_empty()).collect();
+    let Some(last) = segments.last() else {
+        return false;
+    };
+    let covers_below = *last == "**" || !last.contains(['*', '?']);
+    let parts: Vec<&str> = path
+        .trim_start_matches("./")
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect();
+    let starts = if anchored { 0..1 } else { 0..parts.len() };
+    starts.into_iter().any(|start| {
+        (start + 1..=parts.len()).any(|end| {
+            let is_file = end == parts.len();
+            if (is_file && dir_only) || (!is_file && !covers_below) {
+                return false;
+            }
+            segments_match(&segments, &parts[start..end])
+        })
+    })
+}
+
+/// The rule deciding the owners of a project-relative path: the last one
+/// matching it.
+pub fn matching_rule<'a>(rules: &'a [CodeOwnerRule], path: &str) -> Option<&'a CodeOwnerRule> {
+    rules
+        .iter()
+        .rev()
+        .find(|r| pattern_matches(&r.pattern, path))
+}
+
+/// An owner and the number of files it owns among a set of files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OwnerShare {
+    pub owner: String,
+    pub files: usize,
+}
+
+/// The CODEOWNERS rules of a project, resolving absolute or relative paths.
+pub struct Ownership {
+    rules: Vec<CodeOwnerRule>,
+    roots: Vec<String>,
+}
+
+impl Ownership {
+    pub fn new(rules: Vec<CodeOwnerRule>, root_path: &str) -> Self {
+        Self {
+            rules,
+            roots: super::project_roots(root_path),
+        }
+    }
+
+    /// Load the stored rules of a project.
+    pub async fn load(graph: &dyn GraphStore, project: &ProjectNode) -> Result<Self> {
+        let rules = graph.get_code_owner_rules(project.id).await?;
+        Ok(Self::new(rules, &project.root_path))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn rules(&self) -> &[CodeOwnerRule] {
+        &self.rules
+    }
+
+    /// `path` relative to the project root.
+    pub fn relative<'a>(&self, path: &'a str) -> &'a str {
+        if path.starts_with('/') {
+            super::relative_to_roots(path, &self.roots)
+        } else {
+            path.trim_start_matches("./")
+        }
+    }
+
+    /// The rule deciding the owners of a file.
+    pub fn rule_for(&self, path: &str) -> Option<&CodeOwnerRule> {
+        matching_rule(&self.rules, self.relative(path))
+    }
+
+    /// Owners of a file (empty when unowned).
+    pub fn owners_of(&self, path: &str) -> &[String] {
+        self.rule_for(path)
+            .map(|r| r.owners.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Owners of a set of files, most files owned first.
+    pub fn owner_shares<'a>(&self, paths: impl IntoIterator<Item = &'a str>) -> Vec<OwnerShare> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for path in paths {
+            for owner in self.owners_of(path) {
+                *counts.entry(owner.as_str()).or_default() += 1;
+            }
+        }
+        let mut shares: Vec<OwnerShare> = counts
+            .into_iter()
+            .map(|(owner, files)| OwnerShare {
+                owner: owner.to_string(),
+                files,
+            })
+            .collect();
+        shares.sort_by(|a, b| b.files.cmp(&a.files).then_with(|| a.owner.cmp(&b.owner)));
+        shares
+    }
+}
+
+/// The first CODEOWNERS file found under the root: (relative location, content).
+pub fn read_codeowners(root: &Path) -> Option<(String, String)> {
+    CODEOWNERS_LOCATIONS.iter().find_map(|location| {
+        let content = std::fs::read_to_string(root.join(location)).ok()?;
+        Some((location.to_string(), content))
+    })
+}
+
+/// Outcome of a CODEOWNERS ingestion
+#[derive(Debug, Serialize)]
+pub struct OwnershipIngestion {
+    /// Where the rules came from (a CODEOWNERS location or `request`)
+    pub source: String,
+    pub rules: usize,
+    /// Distinct owners named by the rules
+    pub owners: usize,
+    /// Synced files with at least one owner
+    pub owned_files: usize,
+    pub unowned_files: usize,
+    /// Tokens ignored because they are not owners
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+/// Store the rules of a CODEOWNERS file and link the synced files of the
+/// project to their owners.
+pub async fn ingest_code_owners(
+    graph: &dyn GraphStore,
+    project: &ProjectNode,
+    source: &str,
+    content: &str,
+) -> Result<OwnershipIngestion> {
+    let parsed = parse_codeowners(content);
+    let ownership = Ownership::new(parsed.rules, &project.root_path);
+    let paths = graph.get_project_file_paths(project.id).await?;
+    let file_owners: Vec<(String, Vec<String>)> = paths
+        .iter()
+        .filter_map(|path| {
+            let owners = ownership.owners_of(path);
+            (!owners.is_empty()).then(|| (path.clone(), owners.to_vec()))
+        })
+        .collect();
+    graph
+        .replace_code_owners(project.id, ownership.rules(), &file_owners)
+        .await?;
+
+    let mut owners: Vec<&String> = ownership.rules().iter().flat_map(|r| &r.owners).collect();
+    owners.sort();
+    owners.dedup();
+    Ok(OwnershipIngestion {
+        source: source.to_string(),
+        rules: ownership.rules().len(),
+        owners: owners.len(),
+        owned_files: file_owners.len(),
+        unowned_files: paths.len() - file_owners.len(),
+        errors: parsed.errors,
+    })
+}
+
+/// Ingest the CODEOWNERS file of the project root. When there is none, the
+/// stored rules are cleared and `None` is returned.
+pub async fn sync_code_owners(
+    graph: &dyn GraphStore,
+    project: &ProjectNode,
+) -> Result<Option<OwnershipIngestion>> {
+    let root = crate::expand_tilde(&project.root_path);
+    match read_codeowners(Path::new(&root)) {
+        Some((location, content)) => ingest_code_owners(graph, project, &location, &content)
+            .await
+            .map(Some),
+        None => {
+            if !graph.get_code_owner_rules(project.id).await?.is_empty() {
+                graph.replace_code_owners(project.id, &[], &[]).await?;
+            }
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::test_helpers::test_project_named;
+
+    const CODEOWNERS: &str = "\
+# Default owners
+*       @acme/core
+
+[Frontend]
+*.js    @acme/web alice@example.com  # inline comment
+/docs/  @docs-bot
+docs/*  @writer
+/src/api/ @acme/api nobody
+apps/   @acme/apps
+/build/logs @ops
+/vendored/
+";
+
+    #[test]
+    fn test_parse_codeowners() {
+        let parsed = parse_codeowners(CODEOWNERS);
+        assert_eq!(parsed.rules.len(), 8);
+        assert_eq!(parsed.rules[0].line, 2);
+        assert_eq!(parsed.rules[1].pattern, "*.js");
+        assert_eq!(parsed.rules[1].owners, ["@acme/web", "alice@example.com"]);
+        assert!(parsed.rules[7].owners.is_empty());
+        assert_eq!(
+            parsed.errors,
+            ["line 8: `nobody` is not a user, team or email"]
+        );
+    }
+
+    #[test]
+    fn test_pattern_matches() {
+        assert!(pattern_matches("*", "src/main.rs"));
+        assert!(pattern_matches("*.js", "web/app/index.js"));
+        assert!(!pattern_matches("*.js", "web/app/index.ts"));
+        assert!(pattern_matches("/docs/", "docs/guide/intro.md"));
+        assert!(!pattern_matches("/docs/", "src/docs/intro.md"));
+        assert!(!pattern_matches("/docs/", "docs"));
+        assert!(pattern_matches("docs/*", "docs/intro.md"));
+        assert!(!pattern_matches("docs/*", "docs/guide/intro.md"));
+        assert!(pattern_matches("apps/", "services/apps/web/main.go"));
+        assert!(pattern_matches("/build/logs", "build/logs/today.log"));
+        assert!(!pattern_matches("/build/logs", "src/build/logs/today.log"));
+        assert!(pattern_matches("**/logs", "deep/nested/logs/a.log"));
+        assert!(pattern_matches("src/**/test_?.py", "src/a/b/test_1.py"));
+    }
+
+    #[test]
+    fn test_ownership() {
+        let ownership = Ownership::new(parse_codeowners(CODEOWNERS).rules, "/tmp/shop");
+        // Last matching rule wins
+        assert_eq!(ownership.owners_of("/tmp/shop/src/main.rs"), ["@acme/core"]);
+        assert_eq!(ownership.owners_of("src/api/users.js"), ["@acme/api"]);
+        assert_eq!(ownership.owners_of("docs/intro.md"), ["@writer"]);
+        assert_eq!(ownership.owners_of("docs/guide/intro.md"), ["@docs-bot"]);
+        assert!(ownership.owners_of("vendored/lib.c").is_empty());
+        assert_eq!(ownership.rule_for("vendored/lib.c").unwrap().line, 11);
+
+        let shares = ownership.owner_shares([
+            "/tmp/shop/src/main.rs",
+            "/tmp/shop/src/lib.rs",
+            "/tmp/shop/web/index.js",
+        ]);
+        assert_eq!(
+            shares[0],
+            OwnerShare {
+                owner: "@acme/core".to_string(),
+                files: 2
+            }
+        );
+        assert_eq!(shares.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_ingest_code_owners() {
+        let graph = MockGraphStore::new();
+        let project = test_project_named("shop");
+        graph.create_project(&project).await.unwrap();
+        for path in ["src/main.rs", "web/index.js", "vendored/lib.c"] {
+            graph
+                .link_file_to_project(&format!("/tmp/shop/{}", path), project.id)
+                .await
+                .unwrap();
+        }
+
+        let report = ingest_code_owners(&graph, &project, "CODEOWNERS", CODEOWNERS)
+            .await
+            .unwrap();
+        assert_eq!((report.rules, report.owners), (8, 8));
+        assert_eq!((report.owned_files, report.unowned_files), (2, 1));
+        assert_eq!(report.errors.len(), 1);
+
+        assert_eq!(
+            graph
+                .get_file_owners("/tmp/shop/web/index.js")
+                .await
+                .unwrap(),
+            ["@acme/web", "alice@example.com"]
+        );
+        let owners = graph.list_code_owners(project.id).await.unwrap();
+        assert_eq!(owners.len(), 8);
+        assert_eq!((owners[0].files, owners[0].kind.as_str()), (1, "team"));
+        assert_eq!(
+            graph.get_code_owner_rules(project.id).await.unwrap().len(),
+            8
+        );
+    }
+}
@@ -6,6 +6,7 @@ use crate::neo4j::models::{StepNode as StepNodeModel, StepStatus};
 use crate::neo4j::GraphStore;
 use crate::notes::models::EntityType;
 use crate::notes::NoteManager;
+use crate::plan::manager::new_plan_task;
 use crate::plan::models::CreatePlanRequest;
 use crate::plan::models::CreateTaskRequest;
 use crate::plan::PlanManager;
@@ -1534,13 +1535,9 @@ impl ImplementationPlanner {
             project_id: Some(project_id),
         };
 
-        let plan_node = self.plan_manager.create_plan(create_req, "planner").await?;
-        let plan_id = plan_node.id;
-
-        // Link to project
-        self.neo4j.link_plan_to_project(plan_id, project_id).await?;
-
-        // Create tasks for each phase, chained by dependencies
+        // Tasks for each phase, chained by dependencies, all created with
+        // the plan in one transaction
+        let mut tasks = Vec::with_capacity(plan.phases.len());
         let mut prev_task_id: Option<Uuid> = None;
         for phase in &plan.phases {
             let affected_files: Vec<String> = if phase.parallel {
@@ -1565,7 +1562,7 @@ impl ImplementationPlanner {
                 estimated_complexity: None,
             };
 
-            let task_node = self.plan_manager.add_task(plan_id, task_req).await?;
+            let mut new_task = new_plan_task(task_req);
 
             // Create steps — granularity adapts to scaffolding level
             let mut step_order = 0u32;
@@ -1588,7 +1585,7 @@ impl ImplementationPlanner {
                     execution_context: None,
                     persona: None,
                 };
-                self.plan_manager.add_step(task_node.id, &step).await?;
+                new_task.steps.push(step);
             } else if phase.parallel {
                 for branch in &phase.branches {
                     let step = StepNodeModel {
@@ -1603,7 +1600,7 @@ impl ImplementationPlanner {
                         execution_context: None,
                         persona: None,
                     };
-                    self.plan_manager.add_step(task_node.id, &step).await?;
+                    new_task.steps.push(step);
                     step_order += 1;
                 }
             } else {
@@ -1631,15 +1628,20 @@ impl ImplementationPlanner {
                         execution_context: None,
                         persona: None,
                     };
-                    self.plan_manager.add_step(task_node.id, &step).await?;
+                    new_task.steps.push(step);
                     step_order += 1;
                 }
             }
 
-            prev_task_id = Some(task_node.id);
+            prev_task_id = Some(new_task.task.id);
+            tasks.push(new_task);
         }
 
-        Ok(plan_id)
+        let plan_node = self
+            .plan_manager
+            .create_plan_with_tasks(create_req, tasks, "planner")
+            .await?;
+        Ok(plan_node.id)
     }
 
     // ========================================================================
@@ -1894,6 +1896,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         let pid = project.id;
         graph.create_project(&project).await.unwrap();
@@ -1911,8 +1914,10 @@ mod tests {
                     path: path.to_string(),
                     language: lang.to_string(),
                     hash: "abc".to_string(),
+                    line_count: 0,
                     last_parsed: chrono::Utc::now(),
                     project_id: Some(pid),
+                    encoding: None,
                 })
                 .await
                 .unwrap();
@@ -2822,6 +2827,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         let pid = project.id;
         graph.create_project(&project).await.unwrap();
@@ -2841,8 +2847,10 @@ mod tests {
                     path: path.to_string(),
                     language: lang.to_string(),
                     hash: "abc".to_string(),
+                    line_count: 0,
                     last_parsed: chrono::Utc::now(),
                     project_id: Some(pid),
+                    encoding: None,
                 })
                 .await
                 .unwrap();
@@ -3149,6 +3157,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         let pid = project.id;
         graph.create_project(&project).await.unwrap();
@@ -3163,8 +3172,10 @@ mod tests {
                     path: path.to_string(),
                     language: "rust".to_string(),
                     hash: "abc".to_string(),
+                    line_count: 0,
                     last_parsed: chrono::Utc::now(),
                     project_id: Some(pid),
+                    encoding: None,
                 })
                 .await
                 .unwrap();
@@ -3365,6 +3376,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         let pid = project.id;
         graph.create_project(&project).await.unwrap();
@@ -3376,8 +3388,10 @@ mod tests {
                     path: path.to_string(),
                     language: "rust".to_string(),
                     hash: "abc".to_string(),
+                    line_count: 0,
                     last_parsed: chrono::Utc::now(),
                     project_id: Some(pid),
+                    encoding: None,
                 })
                 .await
                 .unwrap();
@@ -0,0 +1,342 @@
+//! Post-sync cross-project resource linking.
+//!
+//! After a project is synced, its imports are matched against the Resources
+//! registered in its workspace (protobuf packages, OpenAPI clients, shared
+//! type packages). Each match becomes a
+//! `(:File)-[:USES_RESOURCE_SYMBOL]->(:Resource)` edge — imports are tracked
+//! per file, so the importing file is the finest anchor available. Impact
+//! analysis can then follow a contract change into every consuming project.
+//!
+//! Resources owned or implemented by the synced project are skipped: only
+//! cross-project usage is linked. The project's previous links are replaced
+//! on every run, so removed imports drop their edges.
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::neo4j::models::{ImportNode, ResourceNode, ResourceSymbolLink, ResourceType};
+use crate::neo4j::GraphStore;
+
+/// Stems too generic to identify a resource on their own (`api/openapi.yaml`).
+const GENERIC_STEMS: &[&str] = &[
+    "api", "apis", "openapi", "swagger", "schema", "schemas", "spec", "specs", "index", "main",
+    "types", "common", "service", "services", "proto", "contract", "v1", "v2",
+];
+
+/// Result of a post-sync resource linking pass.
+#[derive(Debug, Default)]
+pub struct ResourceLinkHookResult {
+    /// Workspace resources considered for matching
+    pub resources_considered: usize,
+    /// Imports of the synced project that were scanned
+    pub imports_scanned: usize,
+    /// USES_RESOURCE_SYMBOL edges written
+    pub links_created: usize,
+}
+
+/// Match the imports of `project_id` against the resources of its workspace
+/// and replace its USES_RESOURCE_SYMBOL edges.
+///
+/// Designed to be called from a `tokio::spawn` (best-effort, non-blocking).
+/// A project outside any workspace has nothing to link against.
+pub async fn link_resource_symbols_post_sync(
+    neo4j: Arc<dyn GraphStore>,
+    project_id: Uuid,
+) -> anyhow::Result<ResourceLinkHookResult> {
+    let mut result = ResourceLinkHookResult::default();
+
+    let Some(workspace) = neo4j.get_project_workspace(project_id).await? else {
+        return Ok(result);
+    };
+
+    let mut candidates = Vec::new();
+    for resource in neo4j.list_workspace_resources(workspace.id).await? {
+        if resource.project_id == Some(project_id) {
+            continue;
+        }
+        let implementers = neo4j.get_resource_implementers(resource.id).await?;
+        if implementers.iter().any(|p| p.id == project_id) {
+            continue;
+        }
+        let identifiers = resource_identifiers(&resource);
+        if !identifiers.is_empty() {
+            candidates.push((resource, identifiers));
+        }
+    }
+    result.resources_considered = candidates.len();
+
+    let imports = if candidates.is_empty() {
+        Vec::new()
+    } else {
+        neo4j.list_project_imports(project_id).await?
+    };
+    result.imports_scanned = imports.len();
+
+    let links = match_imports(project_id, &imports, &candidates);
+    result.links_created = neo4j
+        .replace_resource_symbol_links(project_id, &links)
+        .await?;
+    Ok(result)
+}
+
+/// Names an import can use to refer to `resource`, lowercased, as path
+/// segments (`acme.billing.v1` → `["acme", "billing", "v1"]`).
+///
+/// Sources, most specific first:
+/// - `metadata.symbols` (array) and `metadata.package` (string), set by the
+///   user when registering the resource;
+/// - the spec file stem, plus protoc's generated module names for
+///   protobuf (`billing_pb2`, `billing_grpc`, `billingpb`);
+/// - the resource name, slugified (`Billing API` → `billing_api`).
+///
+/// Generic names (`openapi`, `schema`, …) and names shorter than 3
+/// characters are dropped to keep false positives down.
+pub fn resource_identifiers(resource: &ResourceNode) -> Vec<Vec<String>> {
+    let mut names: Vec<String> = Vec::new();
+    if let Some(symbols) = resource.metadata.get("symbols").and_then(|v| v.as_array()) {
+        names.extend(symbols.iter().filter_map(|s| s.as_str()).map(String::from));
+    }
+    if let Some(package) = resource.metadata.get("package").and_then(|v| v.as_str()) {
+        names.push(package.to_string());
+    }
+
+    let file_name = resource
+        .file_path
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default();
+    let stem = file_name.split('.').next().unwrap_or_default();
+    if !stem.is_empty() {
+        names.push(stem.to_string());
+        if resource.resource_type == ResourceType::Protobuf || file_name.ends_with(".proto") {
+            for suffix in ["_pb2", "_pb", "pb", "_grpc", "_pb2_grpc"] {
+                names.push(format!("{}{}", stem, suffix));
+            }
+        }
+    }
+    names.push(resource.name.replace([' ', '-'], "_"));
+
+    let mut identifiers: Vec<Vec<String>> = Vec::new();
+    for name in names {
+        let segments = segments(&name);
+        let specific = match segments.as_slice() {
+            [] => false,
+            [only] => only.len() >= 3 && !GENERIC_STEMS.contains(&only.as_str()),
+            _ => true,
+        };
+        if specific && !identifiers.contains(&segments) {
+            identifiers.push(segments);
+        }
+    }
+    identifiers
+}
+
+/// Split an import path or identifier into lowercase segments. Separators
+/// cover Rust (`::`), Python/Java/proto (`.`), JS/Go (`/`) and scoped npm
+/// packages (`@scope/pkg`); `-` and `_` are treated alike.
+fn segments(path: &str) -> Vec<String> {
+    path.split([':', '.', '/', '\\', '@'])
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_lowercase().replace('-', "_"))
+        .collect()
+}
+
+/// Whether `identifier` appears as a contiguous run of segments in the
+/// import path, or (single-segment identifiers) as one of the imported items.
+fn import_matches(import: &ImportNode, import_segments: &[String], identifier: &[String]) -> bool {
+    let in_path = identifier.len() <= import_segments.len()
+        && import_segments
+            .windows(identifier.len())
+            .any(|w| w == identifier);
+    in_path
+        || (identifier.len() == 1
+            && import
+                .items
+                .iter()
+                .any(|item| item.to_lowercase().replace('-', "_") == identifier[0]))
+}
+
+/// One link per (import, resource): the first (most specific) identifier
+/// that matches wins.
+fn match_imports(
+    project_id: Uuid,
+    imports: &[ImportNode],
+    candidates: &[(ResourceNode, Vec<Vec<String>>)],
+) -> Vec<ResourceSymbolLink> {
+    let mut links = Vec::new();
+    for import in imports {
+        let import_segments = segments(&import.path);
+        for (resource, identifiers) in candidates {
+            if let Some(identifier) = identifiers
+                .iter()
+                .find(|id| import_matches(import, &import_segments, id))
+            {
+                links.push(ResourceSymbolLink {
+                    resource_id: resource.id,
+                    project_id,
+                    file_path: import.file_path.clone(),
+                    import_path: import.path.clone(),
+                    symbol: identifier.join("."),
+                    line: import.line,
+                });
+            }
+        }
+    }
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::test_helpers::{test_project_named, test_workspace};
+
+    fn resource(name: &str, file_path: &str, resource_type: ResourceType) -> ResourceNode {
+        ResourceNode {
+            id: Uuid::new_v4(),
+            workspace_id: None,
+            project_id: None,
+            name: name.to_string(),
+            resource_type,
+            file_path: file_path.to_string(),
+            url: None,
+            format: None,
+            version: None,
+            description: None,
+            created_at: chrono::Utc::now(),
+            updated_at: None,
+            metadata: serde_json::Value::Null,
+        }
+    }
+
+    fn import(path: &str, items: &[&str]) -> ImportNode {
+        ImportNode {
+            path: path.to_string(),
+            alias: None,
+            items: items.iter().map(|s| s.to_string()).collect(),
+            file_path: "/web/src/checkout.ts".to_string(),
+            line: 3,
+        }
+    }
+
+    #[test]
+    fn test_resource_identifiers() {
+        let proto = resource(
+            "Billing",
+            "/api/proto/billing.proto",
+            ResourceType::Protobuf,
+        );
+        let ids = resource_identifiers(&proto);
+        assert!(ids.contains(&vec!["billing".to_string()]));
+        assert!(ids.contains(&vec!["billing_pb2".to_string()]));
+
+        // Generic stem falls back to the resource name
+        let mut openapi = resource(
+            "Orders API",
+            "specs/openapi.yaml",
+            ResourceType::ApiContract,
+        );
+        openapi.metadata = serde_json::json!({"package": "@acme/orders-client"});
+        let ids = resource_identifiers(&openapi);
+        assert_eq!(ids[0], vec!["acme", "orders_client"]);
+        assert!(ids.contains(&vec!["orders_api".to_string()]));
+        assert!(!ids.contains(&vec!["openapi".to_string()]));
+    }
+
+    #[test]
+    fn test_match_imports_by_segment_and_item() {
+        let pid = Uuid::new_v4();
+        let proto = resource("Billing", "billing.proto", ResourceType::Protobuf);
+        let mut shared = resource("Shared", "types.ts", ResourceType::SharedTypes);
+        shared.metadata = serde_json::json!({"symbols": ["OrderId"]});
+        let candidates = vec![
+            (proto.clone(), resource_identifiers(&proto)),
+            (shared.clone(), resource_identifiers(&shared)),
+        ];
+        let imports = vec![
+            import("gen/billing_pb2", &["InvoiceRequest"]),
+            import("crate::billing::v1::Invoice", &[]),
+            import("./models", &["OrderId"]),
+            import("billingservice", &[]),
+            import("lodash/debounce", &[]),
+        ];
+
+        let links = match_imports(pid, &imports, &candidates);
+        let matched: Vec<(&str, &str)> = links
+            .iter()
+            .map(|l| (l.import_path.as_str(), l.symbol.as_str()))
+            .collect();
+        assert_eq!(
+            matched,
+            vec![
+                ("gen/billing_pb2", "billing_pb2"),
+                ("crate::billing::v1::Invoice", "billing"),
+                ("./models", "orderid"),
+            ]
+        );
+        assert!(links.iter().all(|l| l.project_id == pid && l.line == 3));
+    }
+
+    #[tokio::test]
+    async fn test_post_sync_links_only_foreign_resources() {
+        let graph = Arc::new(MockGraphStore::new());
+        let ws = test_workspace();
+        graph.create_workspace(&ws).await.unwrap();
+        let api = test_project_named("api");
+        let web = test_project_named("web");
+        for p in [&api, &web] {
+            graph.create_project(p).await.unwrap();
+            graph.add_project_to_workspace(ws.id, p.id).await.unwrap();
+        }
+
+        let mut billing = resource("Billing", "billing.proto", ResourceType::Protobuf);
+        billing.workspace_id = Some(ws.id);
+        graph.create_resource(&billing).await.unwrap();
+        graph
+            .link_project_implements_resource(api.id, billing.id)
+            .await
+            .unwrap();
+
+        for (project, file) in [(&api, "/api/src/server.rs"), (&web, "/web/src/pay.ts")] {
+            graph.link_file_to_project(file, project.id).await.unwrap();
+            graph
+                .upsert_import(&ImportNode {
+                    path: "gen/billing_pb".to_string(),
+                    alias: None,
+                    items: vec![],
+                    file_path: file.to_string(),
+                    line: 1,
+                })
+                .await
+                .unwrap();
+        }
+
+        // The implementing project gets no link to its own contract
+        let r = link_resource_symbols_post_sync(graph.clone(), api.id)
+            .await
+            .unwrap();
+        assert_eq!(r.links_created, 0);
+
+        let r = link_resource_symbols_post_sync(graph.clone(), web.id)
+            .await
+            .unwrap();
+        assert_eq!(r.resources_considered, 1);
+        assert_eq!(r.links_created, 1);
+        let usages = graph.get_resource_symbol_links(billing.id).await.unwrap();
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].file_path, "/web/src/pay.ts");
+
+        // Re-running replaces rather than duplicates
+        link_resource_symbols_post_sync(graph.clone(), web.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            graph
+                .get_resource_symbol_links(billing.id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,293 @@
+//! Retention purges.
+//!
+//! Chat events, sharing events (the audit trail), search analytics,
+//! acknowledged alerts, undo logs, chat session usage, CI test runs and run
+//! reports accumulate forever in Neo4j unless something deletes them. The deployment's [`RetentionPolicy`] gives each kind a window in
+//! days; [`purge`] deletes the records older than their window and stores a
+//! [`RetentionReport`]. It runs daily on the leader
+//! ([`spawn_retention_purger`]) and on demand from
+//! `POST /api/admin/retention/purge`.
+//!
+//! CRUD events are only broadcast (WebSocket, NATS), never stored, so they
+//! have nothing to purge.
+
+use crate::neo4j::models::{RetentionPolicy, RetentionPurge, RetentionReport};
+use crate::neo4j::traits::GraphStore;
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+
+/// Interval between two scheduled purges (one day)
+const PURGE_INTERVAL_SECS: u64 = 24 * 60 * 60;
+
+/// Delete the records older than the retention windows of the stored policy
+/// (the defaults when none was set) and store the report.
+pub async fn purge(store: &dyn GraphStore, manual: bool) -> Result<RetentionReport> {
+    let policy = store.get_retention_policy().await?.unwrap_or_default();
+    let started_at = Utc::now();
+
+    let mut purges = Vec::new();
+    for (kind, days) in policy.windows() {
+        let Some(days) = days else { continue };
+        let cutoff = started_at - Duration::days(days as i64);
+        let deleted = store.purge_expired(kind, cutoff).await?;
+        if deleted > 0 {
+            tracing::info!(
+                "Retention: purged {} {:?} older than {}",
+                deleted,
+                kind,
+                cutoff
+            );
+        }
+        purges.push(RetentionPurge {
+            kind,
+            cutoff,
+            deleted,
+        });
+    }
+
+    let report = RetentionReport {
+        started_at,
+        finished_at: Utc::now(),
+        manual,
+        purges,
+    };
+    store.save_retention_report(&report).await?;
+    Ok(report)
+}
+
+/// Spawn the daily retention purge. Only the leader purges.
+///
+/// This task runs for the lifetime of the server and never returns.
+pub fn spawn_retention_purger(store: Arc<dyn GraphStore>, leadership: crate::events::Leadership) {
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(PURGE_INTERVAL_SECS));
+
+        // The first tick fires immediately — skip it so startup is not slowed
+        // down by a purge.
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            if !leadership.is_leader() {
+                continue;
+            }
+            if let Err(e) = purge(&*store, false).await {
+                tracing::warn!("Retention purge failed: {}", e);
+            }
+        }
+    });
+}
+
+/// Check the windows of a policy: a window must be at least one day.
+pub fn validate_policy(policy: &RetentionPolicy) -> Result<(), String> {
+    for (kind, days) in policy.windows() {
+        if days == Some(0) {
+            return Err(format!(
+                "Retention window of {:?} must be at least 1 day, or null to keep forever",
+                kind
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::{
+        AlertNode, AlertSeverity, ChatEventRecord, ChatSessionUsage, RetentionKind, TestRunNode,
+        UndoEntry,
+    };
+    use crate::runner::models::TriggerSource;
+    use crate::runner::report::RunReport;
+    use crate::runner::state::RunnerState;
+    use crate::test_helpers::test_chat_session;
+    use uuid::Uuid;
+
+    fn chat_event(session_id: Uuid, seq: i64, age_days: i64) -> ChatEventRecord {
+        ChatEventRecord {
+            id: Uuid::new_v4(),
+            session_id,
+            seq,
+            event_type: "user_message".to_string(),
+            data: "{}".to_string(),
+            created_at: Utc::now() - Duration::days(age_days),
+        }
+    }
+
+    fn undo_entry(session_id: Uuid, age_days: i64) -> UndoEntry {
+        UndoEntry {
+            id: Uuid::new_v4(),
+            session_id,
+            action: "note.update".to_string(),
+            entity_type: "note".to_string(),
+            entity_id: Uuid::new_v4(),
+            summary: "Updated a note".to_string(),
+            inverse: serde_json::json!({}),
+            created_at: Utc::now() - Duration::days(age_days),
+            undone_at: None,
+        }
+    }
+
+    fn test_run(project_id: Uuid, age_days: i64) -> TestRunNode {
+        TestRunNode {
+            id: Uuid::new_v4(),
+            project_id,
+            commit: None,
+            branch: None,
+            total: 1,
+            passed: 1,
+            failed: 0,
+            skipped: 0,
+            duration_ms: 10,
+            created_at: Utc::now() - Duration::days(age_days),
+        }
+    }
+
+    fn run_report(age_days: i64) -> RunReport {
+        let state = RunnerState::new(Uuid::new_v4(), Uuid::new_v4(), 0, TriggerSource::Manual);
+        let mut report = RunReport::build(
+            &state,
+            "Plan".to_string(),
+            &[],
+            &Default::default(),
+            &Default::default(),
+        );
+        report.generated_at = Utc::now() - Duration::days(age_days);
+        report
+    }
+
+    #[tokio::test]
+    async fn test_purge_deletes_expired_records() {
+        let store = MockGraphStore::new();
+        let session_id = Uuid::new_v4();
+        store.chat_events.write().await.insert(
+            session_id,
+            vec![
+                chat_event(session_id, 1, 120),
+                chat_event(session_id, 2, 10),
+            ],
+        );
+        let mut old_acknowledged = AlertNode::new(
+            "git_drift".to_string(),
+            AlertSeverity::Warning,
+            "Drift".to_string(),
+            None,
+        );
+        old_acknowledged.acknowledged = true;
+        old_acknowledged.created_at = Utc::now() - Duration::days(60);
+        let mut old_pending = old_acknowledged.clone();
+        old_pending.id = Uuid::new_v4();
+        old_pending.acknowledged = false;
+        for alert in [&old_acknowledged, &old_pending] {
+            store.alerts.write().await.insert(alert.id, alert.clone());
+        }
+        store.undo_log.write().await.insert(
+            session_id,
+            vec![undo_entry(session_id, 5), undo_entry(session_id, 40)],
+        );
+        // Idle sessions lose their usage, unless they were killed
+        let mut idle = test_chat_session(None);
+        idle.updated_at = Utc::now() - Duration::days(100);
+        let mut killed = test_chat_session(None);
+        killed.updated_at = idle.updated_at;
+        let active = test_chat_session(None);
+        for session in [&idle, &killed, &active] {
+            store
+                .chat_sessions
+                .write()
+                .await
+                .insert(session.id, session.clone());
+        }
+        {
+            let mut usages = store.chat_session_usages.write().await;
+            usages.insert(idle.id, ChatSessionUsage::default());
+            usages.insert(
+                killed.id,
+                ChatSessionUsage {
+                    killed: Some("cost limit".to_string()),
+                    ..Default::default()
+                },
+            );
+            usages.insert(active.id, ChatSessionUsage::default());
+        }
+        let project_id = Uuid::new_v4();
+        store.test_runs.write().await.insert(
+            project_id,
+            vec![test_run(project_id, 120), test_run(project_id, 1)],
+        );
+        let (old_report, recent_report) = (run_report(200), run_report(1));
+        for report in [&old_report, &recent_report] {
+            store
+                .run_reports
+                .write()
+                .await
+                .insert(report.run_id, report.clone());
+        }
+
+        let report = purge(&store, true).await.unwrap();
+        assert!(report.manual);
+        let deleted = |kind| {
+            report
+                .purges
+                .iter()
+                .find(|p| p.kind == kind)
+                .map(|p| p.deleted)
+        };
+        assert_eq!(deleted(RetentionKind::ChatEvents), Some(1));
+        assert_eq!(deleted(RetentionKind::Alerts), Some(1));
+        assert_eq!(deleted(RetentionKind::UndoEntries), Some(1));
+        assert_eq!(deleted(RetentionKind::SessionUsage), Some(1));
+        assert_eq!(deleted(RetentionKind::TestRuns), Some(1));
+        assert_eq!(deleted(RetentionKind::RunReports), Some(1));
+        assert_eq!(store.chat_events.read().await[&session_id][0].seq, 2);
+        assert!(store.alerts.read().await.contains_key(&old_pending.id));
+        assert_eq!(store.undo_log.read().await[&session_id].len(), 1);
+        let usages = store.chat_session_usages.read().await;
+        assert!(!usages.contains_key(&idle.id));
+        assert!(usages.contains_key(&killed.id) && usages.contains_key(&active.id));
+        drop(usages);
+        assert_eq!(store.test_runs.read().await[&project_id].len(), 1);
+        assert!(store
+            .run_reports
+            .read()
+            .await
+            .contains_key(&recent_report.run_id));
+        assert_eq!(store.retention_report.read().await.as_ref(), Some(&report));
+
+        // A null window keeps the records forever
+        let policy = RetentionPolicy {
+            chat_events_days: None,
+            ..Default::default()
+        };
+        store.update_retention_policy(&policy).await.unwrap();
+        let report = purge(&store, false).await.unwrap();
+        assert!(report
+            .purges
+            .iter()
+            .all(|p| p.kind != RetentionKind::ChatEvents));
+    }
+
+    #[test]
+    fn test_validate_policy() {
+        assert!(validate_policy(&RetentionPolicy::default()).is_ok());
+        let policy: RetentionPolicy =
+            serde_json::from_value(serde_json::json!({"alerts_days": null})).unwrap();
+        assert_eq!(policy.alerts_days, None);
+        assert_eq!(policy.chat_events_days, Some(90));
+        assert!(validate_policy(&policy).is_ok());
+        let policy = RetentionPolicy {
+            search_queries_days: Some(0),
+            ..Default::default()
+        };
+        assert!(validate_policy(&policy).is_err());
+        let policy = RetentionPolicy {
+            undo_entries_days: Some(0),
+            ..Default::default()
+        };
+        assert!(validate_policy(&policy).is_err());
+    }
+}
@@ -0,0 +1,792 @@
+//! Pull request review assistant.
+//!
+//! [`parse_unified_diff`] reads a unified diff (`git diff` output) into its
+//! changed files and hunks. [`review_diff`] maps the hunks onto the graph
+//! symbols they touch and gathers, per file, the code depending on it, the
+//! notes and decisions attached to it, and the architecture rules (topology
+//! rules, budgets) it is involved in. `POST /api/projects/{id}/review` serves
+//! the result to CI bots, optionally with a comment written by the prompt
+//! builder model.
+//!
+//! Line numbers are matched against the synced graph, which is taken to
+//! reflect the base side of the diff.
+
+use crate::graph::models::{TopologySeverity, TopologyViolation};
+use crate::neo4j::models::{BudgetViolation, DecisionStatus, ProjectNode};
+use crate::neo4j::traits::GraphStore;
+use crate::notes::{EntityType, NoteImportance, NoteStatus, NoteType};
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashSet};
+use uuid::Uuid;
+
+/// Files reviewed per diff, in diff order
+pub const MAX_REVIEWED_FILES: usize = 200;
+/// Dependents fetched per touched symbol
+const DEPENDENTS_PER_SYMBOL: usize = 50;
+/// Impacted files listed per changed file
+const MAX_IMPACTED_FILES: usize = 50;
+/// Diff characters quoted in the commentary prompt
+const MAX_PROMPT_DIFF_CHARS: usize = 20_000;
+
+const REVIEW_SYSTEM_PROMPT: &str =
+    "You are a senior engineer reviewing a pull request. Be concise and specific.";
+
+/// How a file is changed by the diff
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileChange {
+    Added,
+    Modified,
+    Deleted,
+    Renamed,
+}
+
+/// One `@@ -old_start,old_lines +new_start,new_lines @@` hunk, with the base
+/// side lines its changes touch.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiffHunk {
+    pub old_start: u32,
+    pub old_lines: u32,
+    pub new_start: u32,
+    pub new_lines: u32,
+    /// Base lines removed (or replaced), as inclusive spans
+    removed: Vec<(u32, u32)>,
+    /// Base lines after which lines are inserted
+    inserted_after: Vec<u32>,
+}
+
+impl DiffHunk {
+    fn remove(&mut self, line: u32) {
+        match self.removed.last_mut() {
+            Some((_, end)) if *end + 1 == line => *end = line,
+            _ => self.removed.push((line, line)),
+        }
+    }
+
+    fn insert_after(&mut self, line: u32) {
+        if self.inserted_after.last() != Some(&line) {
+            self.inserted_after.push(line);
+        }
+    }
+
+    /// Whether a change of this hunk falls within base lines `start..=end`.
+    /// Lines inserted right after `end` are outside.
+    pub fn touches(&self, start: u32, end: u32) -> bool {
+        self.removed.iter().any(|&(a, b)| a <= end && start <= b)
+            || self
+                .inserted_after
+                .iter()
+                .any(|&line| start <= line && line < end)
+    }
+}
+
+/// A file of a unified diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffFile {
+    /// Path on the head side (the base side for deleted files)
+    pub path: String,
+    /// Path on the base side, for renamed files only
+    pub old_path: Option<String>,
+    pub change: FileChange,
+    pub additions: u32,
+    pub deletions: u32,
+    pub hunks: Vec<DiffHunk>,
+}
+
+impl DiffFile {
+    fn new(path: String, old_path: Option<String>) -> Self {
+        Self {
+            path,
+            old_path,
+            change: FileChange::Modified,
+            additions: 0,
+            deletions: 0,
+            hunks: Vec::new(),
+        }
+    }
+
+    /// Path of the file in the synced graph
+    pub fn base_path(&self) -> &str {
+        self.old_path.as_deref().unwrap_or(&self.path)
+    }
+}
+
+/// Path of a `---`/`+++` header, `None` for `/dev/null`.
+fn header_path(rest: &str) -> Option<String> {
+    // `diff -u` appends a tab and a timestamp
+    let path = rest.split('\t').next().unwrap_or(rest).trim();
+    if path == "/dev/null" {
+        return None;
+    }
+    let path = path
+        .strip_prefix("a/")
+        .or_else(|| path.strip_prefix("b/"))
+        .unwrap_or(path);
+    Some(path.to_string())
+}
+
+/// The two paths of a `diff --git a/<old> b/<new>` line.
+fn git_header_paths(rest: &str) -> (String, String) {
+    let rest = rest.strip_prefix("a/").unwrap_or(rest);
+    match rest.split_once(" b/") {
+        Some((old, new)) => (old.to_string(), new.to_string()),
+        None => (rest.to_string(), rest.to_string()),
+    }
+}
+
+fn parse_hunk_header(line: &str) -> Option<DiffHunk> {
+    let range = |s: &str| -> Option<(u32, u32)> {
+        match s.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((s.parse().ok()?, 1)),
+        }
+    };
+    let (ranges, _) = line.strip_prefix("@@ -")?.split_once(" @@")?;
+    let (old, new) = ranges.split_once(" +")?;
+    let (old_start, old_lines) = range(old)?;
+    let (new_start, new_lines) = range(new)?;
+    Some(DiffHunk {
+        old_start,
+        old_lines,
+        new_start,
+        new_lines,
+        ..Default::default()
+    })
+}
+
+/// Parse a unified diff, with or without git headers. Files without a path
+/// are dropped.
+pub fn parse_unified_diff(diff: &str) -> Vec<DiffFile> {
+    let mut files = Vec::new();
+    let mut current: Option<DiffFile> = None;
+    // Lines still expected in the current hunk, base and head side
+    let (mut old_left, mut new_left) = (0u32, 0u32);
+    // Base line of the next hunk line
+    let mut old_line = 0u32;
+
+    for line in diff.lines() {
+        if old_left > 0 || new_left > 0 {
+            if let Some(file) = current.as_mut() {
+                if let Some(hunk) = file.hunks.last_mut() {
+                    match line.as_bytes().first() {
+                        Some(b'+') => {
+                            file.additions += 1;
+                            new_left = new_left.saturating_sub(1);
+                            hunk.insert_after(old_line.saturating_sub(1));
+                        }
+                        Some(b'-') => {
+                            file.deletions += 1;
+                            old_left = old_left.saturating_sub(1);
+                            hunk.remove(old_line);
+                            old_line += 1;
+                        }
+                        Some(b'\\') => {}
+                        // Context, possibly stripped of its leading space
+                        _ => {
+                            old_left = old_left.saturating_sub(1);
+                            new_left = new_left.saturating_sub(1);
+                            old_line += 1;
+                        }
+                    }
+                    continue;
+                }
+            }
+        }
+
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            files.extend(current.take());
+            let (old, new) = git_header_paths(rest);
+            current = Some(DiffFile::new(new, Some(old)));
+        } else if let Some(rest) = line.strip_prefix("--- ") {
+            let path = header_path(rest);
+            match current.as_mut() {
+                Some(file) if file.hunks.is_empty() => {
+                    if path.is_none() {
+                        file.change = FileChange::Added;
+                    }
+                }
+                _ => {
+                    files.extend(current.take());
+                    let mut file = DiffFile::new(path.clone().unwrap_or_default(), path.clone());
+                    if path.is_none() {
+                        file.change = FileChange::Added;
+                    }
+                    current = Some(file);
+                }
+            }
+        } else if let Some(rest) = line.strip_prefix("+++ ") {
+            if let Some(file) = current.as_mut() {
+                match header_path(rest) {
+                    Some(path) => file.path = path,
+                    None => file.change = FileChange::Deleted,
+                }
+            }
+        } else if let Some(file) = current.as_mut() {
+            if line.starts_with("new file mode") {
+                file.change = FileChange::Added;
+            } else if line.starts_with("deleted file mode") {
+                file.change = FileChange::Deleted;
+            } else if let Some(old) = line.strip_prefix("rename from ") {
+                file.old_path = Some(old.to_string());
+                file.change = FileChange::Renamed;
+            } else if let Some(new) = line.strip_prefix("rename to ") {
+                file.path = new.to_string();
+                file.change = FileChange::Renamed;
+            } else if let Some(hunk) = parse_hunk_header(line) {
+                old_left = hunk.old_lines;
+                new_left = hunk.new_lines;
+                // An empty base range starts after `old_start`
+                old_line = if hunk.old_lines == 0 {
+                    hunk.old_start + 1
+                } else {
+                    hunk.old_start
+                };
+                file.hunks.push(hunk);
+            }
+        }
+    }
+    files.extend(current);
+
+    for file in &mut files {
+        if file.change == FileChange::Deleted {
+            if let Some(old) = file.old_path.take() {
+                file.path = old;
+            }
+        }
+        if file.change != FileChange::Renamed {
+            file.old_path = None;
+        }
+    }
+    files.retain(|f| !f.path.is_empty());
+    files
+}
+
+/// A graph symbol whose lines the diff changes
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewedSymbol {
+    pub id: String,
+    pub name: String,
+    /// `function`, `struct`, `trait` or `enum`
+    pub kind: String,
+    pub line_start: u32,
+    pub line_end: u32,
+    /// Callers, type users and imports of the symbol (capped at 50)
+    pub dependent_count: usize,
+    /// Other files holding those dependents, relative to the project root
+    pub dependent_files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewNote {
+    pub id: Uuid,
+    pub note_type: NoteType,
+    pub importance: NoteImportance,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewDecision {
+    pub id: Uuid,
+    pub description: String,
+    pub status: DecisionStatus,
+}
+
+/// One changed file with what the graph knows about it
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewedFile {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_path: Option<String>,
+    pub change: FileChange,
+    pub additions: u32,
+    pub deletions: u32,
+    /// False for new files and files outside the synced project
+    pub in_graph: bool,
+    pub symbols: Vec<ReviewedSymbol>,
+    /// Files importing or calling into this one, relative to the project root
+    pub impacted_files: Vec<String>,
+    /// Active notes anchored to the file
+    pub notes: Vec<ReviewNote>,
+    /// Decisions affecting the file
+    pub decisions: Vec<ReviewDecision>,
+}
+
+impl ReviewedFile {
+    fn new(file: &DiffFile) -> Self {
+        Self {
+            path: file.path.clone(),
+            old_path: file.old_path.clone(),
+            change: file.change,
+            additions: file.additions,
+            deletions: file.deletions,
+            in_graph: false,
+            symbols: Vec::new(),
+            impacted_files: Vec::new(),
+            notes: Vec::new(),
+            decisions: Vec::new(),
+        }
+    }
+}
+
+/// Structured review of a diff
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewReport {
+    pub project_id: Uuid,
+    /// Files in the diff; only the first 200 are reviewed
+    pub files_changed: usize,
+    pub files: Vec<ReviewedFile>,
+    /// Topology rule violations involving a changed file
+    pub rule_violations: Vec<TopologyViolation>,
+    /// Violations of the last budget check involving a changed file
+    pub budget_violations: Vec<BudgetViolation>,
+    /// "low", "medium" or "high"
+    pub risk_level: String,
+    /// Review comment written by the prompt builder model, when requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commentary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commentary_model: Option<String>,
+}
+
+/// Whether a graph id or path names `path` or something inside it.
+fn names_file(target: &str, path: &str) -> bool {
+    target
+        .strip_prefix(path)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(':'))
+}
+
+/// Same thresholds as the impact analysis without analytics, raised by rule
+/// violations.
+fn risk_level(
+    files: &[ReviewedFile],
+    rule_violations: &[TopologyViolation],
+    budget_violations: &[BudgetViolation],
+) -> &'static str {
+    let dependents: usize = files
+        .iter()
+        .flat_map(|f| &f.symbols)
+        .map(|s| s.dependent_count)
+        .sum();
+    let impacted: HashSet<&str> = files
+        .iter()
+        .flat_map(|f| &f.impacted_files)
+        .map(String::as_str)
+        .collect();
+    let blocking = rule_violations
+        .iter()
+        .any(|v| v.severity == TopologySeverity::Error);
+    if blocking || dependents > 10 || impacted.len() > 10 {
+        "high"
+    } else if !rule_violations.is_empty()
+        || !budget_violations.is_empty()
+        || dependents > 3
+        || impacted.len() > 3
+    {
+        "medium"
+    } else {
+        "low"
+    }
+}
+
+/// Review `files` against the synced graph of `project`.
+pub async fn review_diff(
+    neo4j: &dyn GraphStore,
+    project: &ProjectNode,
+    files: &[DiffFile],
+) -> Result<ReviewReport> {
+    let roots = super::project_roots(&project.root_path);
+    let synced: HashSet<String> = neo4j
+        .list_project_files(project.id)
+        .await?
+        .into_iter()
+        .map(|f| f.path)
+        .collect();
+
+    let mut reviewed = Vec::new();
+    // (absolute, relative) base paths of the reviewed files in the graph
+    let mut changed = Vec::new();
+    for file in files.iter().take(MAX_REVIEWED_FILES) {
+        let mut entry = ReviewedFile::new(file);
+        let absolute = roots
+            .iter()
+            .map(|root| format!("{}/{}", root.trim_end_matches('/'), file.base_path()))
+            .find(|path| synced.contains(path));
+        let Some(absolute) = absolute.filter(|_| file.change != FileChange::Added) else {
+            reviewed.push(entry);
+            continue;
+        };
+        entry.in_graph = true;
+
+        for symbol in neo4j.list_file_symbols(&absolute).await? {
+            let touched = file.change == FileChange::Deleted
+                || file
+                    .hunks
+                    .iter()
+                    .any(|h| h.touches(symbol.line_start, symbol.line_end));
+            if !touched {
+                continue;
+            }
+            let dependents = neo4j
+                .find_symbol_dependents(&symbol.id, DEPENDENTS_PER_SYMBOL)
+                .await?;
+            let dependent_files: BTreeSet<String> = dependents
+                .iter()
+                .filter(|d| d.file_path != absolute)
+                .map(|d| super::relative_to_roots(&d.file_path, &roots).to_string())
+                .collect();
+            entry.symbols.push(ReviewedSymbol {
+                id: symbol.id,
+                name: symbol.name,
+                kind: symbol.kind,
+                line_start: symbol.line_start,
+                line_end: symbol.line_end,
+                dependent_count: dependents.len(),
+                dependent_files: dependent_files.into_iter().collect(),
+            });
+        }
+
+        let impacted: BTreeSet<String> = neo4j
+            .find_impacted_files(&absolute, 2, Some(project.id))
+            .await?
+            .iter()
+            .filter(|path| **path != absolute)
+            .map(|path| super::relative_to_roots(path, &roots).to_string())
+            .collect();
+        entry.impacted_files = impacted.into_iter().take(MAX_IMPACTED_FILES).collect();
+
+        entry.notes = neo4j
+            .get_notes_for_entity(&EntityType::File, &absolute)
+            .await?
+            .into_iter()
+            .filter(|n| matches!(n.status, NoteStatus::Active | NoteStatus::NeedsReview))
+            .map(|n| ReviewNote {
+                id: n.id,
+                note_type: n.note_type,
+                importance: n.importance,
+                content: n.content,
+            })
+            .collect();
+        entry.decisions = neo4j
+            .get_decisions_affecting("File", &absolute, None)
+            .await?
+            .into_iter()
+            .map(|d| ReviewDecision {
+                id: d.id,
+                description: d.description,
+                status: d.status,
+            })
+            .collect();
+
+        let relative = super::relative_to_roots(&absolute, &roots).to_string();
+        changed.push((absolute, relative));
+        reviewed.push(entry);
+    }
+
+    let rule_violations: Vec<TopologyViolation> = if changed.is_empty() {
+        Vec::new()
+    } else {
+        neo4j
+            .check_topology_rules(&project.id.to_string())
+            .await?
+            .into_iter()
+            .filter(|v| {
+                changed.iter().any(|(absolute, _)| {
+                    names_file(&v.violator_path, absolute)
+                        || v.target_path
+                            .as_deref()
+                            .is_some_and(|t| names_file(t, absolute))
+                })
+            })
+            .collect()
+    };
+
+    let budget_violations: Vec<BudgetViolation> = neo4j
+        .get_budget_report(project.id)
+        .await?
+        .map(|report| report.violations)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|v| {
+            changed.iter().any(|(absolute, relative)| {
+                names_file(&v.target, absolute)
+                    || v.target == *relative
+                    || (v.budget == "max_module_fan_in"
+                        && v.target == super::budgets::module_of(relative))
+            })
+        })
+        .collect();
+
+    let risk_level = risk_level(&reviewed, &rule_violations, &budget_violations).to_string();
+    Ok(ReviewReport {
+        project_id: project.id,
+        files_changed: files.len(),
+        files: reviewed,
+        rule_violations,
+        budget_violations,
+        risk_level,
+        commentary: None,
+        commentary_model: None,
+    })
+}
+
+fn truncate_chars(text: &str, max: usize) -> &str {
+    match text.char_indices().nth(max) {
+        Some((end, _)) => &text[..end],
+        None => text,
+    }
+}
+
+fn build_review_prompt(report: &ReviewReport, diff: &str) -> String {
+    let mut prompt = String::from(
+        "Write a review comment for this pull request in markdown, under 250 words: \
+         the risks of the change, what the reviewer should double-check and which \
+         tests to run. Use the graph facts below; do not restate the diff.\n",
+    );
+    prompt.push_str(&format!("\nOverall risk: {}\n", report.risk_level));
+    for file in &report.files {
+        prompt.push_str(&format!(
+            "\n## {} ({:?}, +{} -{})\n",
+            file.path, file.change, file.additions, file.deletions
+        ));
+        for symbol in &file.symbols {
+            prompt.push_str(&format!(
+                "- changes {} `{}` ({} dependents)\n",
+                symbol.kind, symbol.name, symbol.dependent_count
+            ));
+        }
+        if !file.impacted_files.is_empty() {
+            prompt.push_str(&format!(
+                "- impacted files: {}\n",
+                file.impacted_files.join(", ")
+            ));
+        }
+        for note in &file.notes {
+            prompt.push_str(&format!(
+                "- note ({:?}): {}\n",
+                note.note_type,
+                truncate_chars(&note.content, 300)
+            ));
+        }
+        for decision in &file.decisions {
+            prompt.push_str(&format!("- decision: {}\n", decision.description));
+        }
+    }
+    for violation in &report.rule_violations {
+        prompt.push_str(&format!(
+            "\nRule violation ({}): {}\n",
+            violation.severity, violation.details
+        ));
+    }
+    for violation in &report.budget_violations {
+        prompt.push_str(&format!(
+            "\nBudget exceeded: {} {} is {} (limit {})\n",
+            violation.target, violation.budget, violation.actual, violation.limit
+        ));
+    }
+    prompt.push_str(&format!(
+        "\n```diff\n{}\n```\n",
+        truncate_chars(diff, MAX_PROMPT_DIFF_CHARS)
+    ));
+    prompt
+}
+
+/// Ask `model` for a review comment on `report`, `None` on failure.
+pub async fn write_commentary(report: &ReviewReport, diff: &str, model: &str) -> Option<String> {
+    let prompt = build_review_prompt(report, diff);
+    let text =
+        super::module_summary::complete_with_llm(model, REVIEW_SYSTEM_PROMPT, prompt).await?;
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::{FileNode, FunctionNode, Visibility};
+    use crate::test_helpers::test_project_named;
+
+    const DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -10,4 +10,4 @@ fn helper() {
+ fn context() {
+-    old();
+---- old line of code
++    new();
++    more();
+ }
+diff --git a/src/gone.rs b/src/gone.rs
+deleted file mode 100644
+--- a/src/gone.rs
++++ /dev/null
+@@ -1,2 +0,0 @@
+-fn gone() {}
+-
+diff --git a/README.md b/README.md
+new file mode 100644
+--- /dev/null
++++ b/README.md
+@@ -0,0 +1 @@
++# Hello
+diff --git a/src/old_name.rs b/src/new_name.rs
+similarity index 100%
+rename from src/old_name.rs
+rename to src/new_name.rs
+";
+
+    #[test]
+    fn test_parse_unified_diff() {
+        let files = parse_unified_diff(DIFF);
+        let summary: Vec<(&str, FileChange, u32, u32)> = files
+            .iter()
+            .map(|f| (f.path.as_str(), f.change, f.additions, f.deletions))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("src/lib.rs", FileChange::Modified, 2, 2),
+                ("src/gone.rs", FileChange::Deleted, 0, 2),
+                ("README.md", FileChange::Added, 1, 0),
+                ("src/new_name.rs", FileChange::Renamed, 0, 0),
+            ]
+        );
+        assert_eq!(files[3].old_path.as_deref(), Some("src/old_name.rs"));
+        assert_eq!(files[3].base_path(), "src/old_name.rs");
+        assert_eq!(files[0].old_path, None);
+
+        let hunk = &files[0].hunks[0];
+        assert_eq!((hunk.old_start, hunk.old_lines), (10, 4));
+        assert_eq!((hunk.new_start, hunk.new_lines), (10, 4));
+        assert_eq!(hunk.removed, [(11, 12)]);
+        assert_eq!(hunk.inserted_after, [12]);
+    }
+
+    #[test]
+    fn test_parse_plain_diff() {
+        let diff = "\
+--- src/a.rs\t2026-10-14 09:12:00
++++ src/a.rs\t2026-10-14 09:13:00
+@@ -5,0 +6,2 @@
++fn added() {}
++
+--- src/b.rs
++++ src/b.rs
+@@ -3 +3 @@
+-x
++y
+";
+        let files = parse_unified_diff(diff);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "src/a.rs");
+        assert_eq!(files[0].hunks[0].inserted_after, [5]);
+        assert_eq!(files[1].path, "src/b.rs");
+        assert_eq!(files[1].hunks[0].removed, [(3, 3)]);
+    }
+
+    #[test]
+    fn test_hunk_touches() {
+        let hunk = DiffHunk {
+            removed: vec![(20, 22)],
+            inserted_after: vec![40],
+            ..Default::default()
+        };
+        assert!(hunk.touches(10, 20));
+        assert!(hunk.touches(22, 30));
+        assert!(!hunk.touches(23, 39));
+        // Inserted after a symbol's last line: outside it
+        assert!(!hunk.touches(30, 40));
+        assert!(hunk.touches(35, 45));
+        assert!(!hunk.touches(41, 50));
+    }
+
+    #[tokio::test]
+    async fn test_review_diff() {
+        let graph = MockGraphStore::new();
+        let mut project = test_project_named("reviewed");
+        project.root_path = "/repo".to_string();
+        graph.create_project(&project).await.unwrap();
+
+        for path in ["/repo/src/lib.rs", "/repo/src/main.rs", "/repo/src/gone.rs"] {
+            graph
+                .upsert_file(&FileNode {
+                    path: path.to_string(),
+                    language: "rust".to_string(),
+                    hash: "h".to_string(),
+                    line_count: 0,
+                    last_parsed: chrono::Utc::now(),
+                    project_id: Some(project.id),
+                    encoding: None,
+                })
+                .await
+                .unwrap();
+            graph.link_file_to_project(path, project.id).await.unwrap();
+        }
+        let function = |name: &str, path: &str, line_start: u32, line_end: u32| FunctionNode {
+            name: name.to_string(),
+            visibility: Visibility::Public,
+            params: vec![],
+            return_type: None,
+            generics: vec![],
+            is_async: false,
+            is_unsafe: false,
+            complexity: 1,
+            file_path: path.to_string(),
+            line_start,
+            line_end,
+            docstring: None,
+        };
+        graph
+            .upsert_function(&function("helper", "/repo/src/lib.rs", 1, 9))
+            .await
+            .unwrap();
+        graph
+            .upsert_function(&function("context", "/repo/src/lib.rs", 10, 13))
+            .await
+            .unwrap();
+        graph
+            .upsert_function(&function("main", "/repo/src/main.rs", 1, 5))
+            .await
+            .unwrap();
+        graph
+            .upsert_function(&function("gone", "/repo/src/gone.rs", 1, 1))
+            .await
+            .unwrap();
+        graph
+            .create_call_relationship("/repo/src/main.rs::main", "context", None, 1.0, "test")
+            .await
+            .unwrap();
+        graph
+            .create_import_relationship("/repo/src/main.rs", "/repo/src/lib.rs", "crate::lib")
+            .await
+            .unwrap();
+
+        let files = parse_unified_diff(DIFF);
+        let report = review_diff(&graph, &project, &files).await.unwrap();
+        assert_eq!(report.files_changed, 4);
+
+        let lib = &report.files[0];
+        assert!(lib.in_graph);
+        let touched: Vec<&str> = lib.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(touched, ["context"]);
+        assert_eq!(lib.symbols[0].dependent_count, 1);
+        assert_eq!(lib.symbols[0].dependent_files, ["src/main.rs"]);
+        assert_eq!(lib.impacted_files, ["src/main.rs"]);
+
+        let gone = &report.files[1];
+        assert!(gone.in_graph);
+        assert_eq!(gone.symbols[0].name, "gone");
+
+        // New and unsynced files carry no graph facts
+        assert!(!report.files[2].in_graph);
+        assert!(!report.files[3].in_graph);
+        assert_eq!(report.risk_level, "low");
+
+        let prompt = build_review_prompt(&report, DIFF);
+        assert!(prompt.contains("changes function `context` (1 dependents)"));
+        assert!(prompt.contains("```diff"));
+    }
+}
@@ -11,6 +11,7 @@ use crate::graph::{
 use crate::neo4j::models::*;
 use crate::neurons::{AutoReinforcementConfig, SpreadingActivationEngine};
 use crate::notes::{EntityType, NoteLifecycleManager, NoteManager};
+use crate::parser::contracts::{self, ContractFormat};
 use crate::parser::{CodeParser, ParsedFile};
 use crate::plan::models::*;
 use crate::plan::PlanManager;
@@ -24,6 +25,7 @@ use uuid::Uuid;
 use walkdir::WalkDir;
 
 use super::context::ContextBuilder;
+use super::sync_limits::{looks_binary, skip_reason, SkipReason, SkippedFile};
 
 /// Normalize a file path to an absolute canonical form.
 /// - Resolves `~` to home directory
@@ -321,7 +323,10 @@ impl Orchestrator {
         let planner = Arc::new(planner);
         let analytics: Arc<dyn AnalyticsEngine> = Arc::new(GraphAnalyticsEngine::new(
             state.neo4j.clone(),
-            AnalyticsConfig::default(),
+            AnalyticsConfig {
+                min_call_confidence: state.config.analytics_min_call_confidence,
+                ..Default::default()
+            },
         ));
         let analytics_debouncer = AnalyticsDebouncer::with_graph_store(
             analytics.clone(),
@@ -419,7 +424,10 @@ impl Orchestrator {
         let planner = Arc::new(planner);
         let analytics: Arc<dyn AnalyticsEngine> = Arc::new(GraphAnalyticsEngine::new(
             state.neo4j.clone(),
-            AnalyticsConfig::default(),
+            AnalyticsConfig {
+                min_call_confidence: state.config.analytics_min_call_confidence,
+                ..Default::default()
+            },
         ));
         let analytics_debouncer = AnalyticsDebouncer::with_graph_store(
             analytics.clone(),
@@ -519,7 +527,10 @@ impl Orchestrator {
         let planner = Arc::new(planner);
         let analytics: Arc<dyn AnalyticsEngine> = Arc::new(GraphAnalyticsEngine::new(
             state.neo4j.clone(),
-            AnalyticsConfig::default(),
+            AnalyticsConfig {
+                min_call_confidence: state.config.analytics_min_call_confidence,
+                ..Default::default()
+            },
         ));
         let analytics_debouncer = AnalyticsDebouncer::with_graph_store(
             analytics.clone(),
@@ -595,6 +606,11 @@ impl Orchestrator {
         self.state.neo4j.clone()
     }
 
+    /// Get the application config
+    pub fn config(&self) -> &crate::Config {
+        &self.state.config
+    }
+
     /// Get the runner config.
     ///
     /// TODO: propagate RunnerConfig from YamlConfig to Config for YAML-based customization.
@@ -789,11 +805,12 @@ impl Orchestrator {
         })
     }
 
-    /// Check staleness for all projects and return a list of stale project IDs.
+    /// Check staleness for all projects (archived ones excepted) and return a
+    /// list of stale project IDs.
     pub async fn get_stale_projects(&self) -> Result<Vec<StalenessReport>> {
         let projects = self.neo4j().list_projects().await?;
         let mut stale = Vec::new();
-        for project in &projects {
+        for project in projects.iter().filter(|p| !p.archived) {
             let report = self.check_analytics_staleness(project.id).await?;
             if report.is_stale {
                 stale.push(report);
@@ -975,6 +992,210 @@ impl Orchestrator {
         });
     }
 
+    /// Spawn cross-project resource linking in background.
+    ///
+    /// Matches the project's imports against the resources of its workspace
+    /// and replaces its USES_RESOURCE_SYMBOL edges (see
+    /// [`crate::orchestrator::resource_link_hook`]).
+    /// Best-effort: logs info on success, warn on failure.
+    pub fn spawn_link_resource_symbols(&self, project_id: Uuid) {
+        let neo4j = self.neo4j_arc();
+        tokio::spawn(async move {
+            match crate::orchestrator::resource_link_hook::link_resource_symbols_post_sync(
+                neo4j, project_id,
+            )
+            .await
+            {
+                Ok(r) if r.links_created > 0 => {
+                    tracing::info!(
+                        %project_id,
+                        resources = r.resources_considered,
+                        imports = r.imports_scanned,
+                        "Post-sync resource linking: {} cross-project usages",
+                        r.links_created
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!(%project_id, "Post-sync resource linking failed: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Record the packages locked by the project's lockfiles (best-effort).
+    /// The vulnerability audit heartbeat check matches them against OSV.
+    pub fn spawn_ingest_packages(&self, project_id: Uuid) {
+        let neo4j = self.neo4j_arc();
+        tokio::spawn(async move {
+            let root = match neo4j.get_project(project_id).await {
+                Ok(Some(project)) => crate::expand_tilde(&project.root_path),
+                Ok(None) => return,
+                Err(e) => {
+                    tracing::warn!(%project_id, "Package ingestion: project lookup failed: {}", e);
+                    return;
+                }
+            };
+            match crate::audit::ingest_project_packages(
+                neo4j.as_ref(),
+                project_id,
+                std::path::Path::new(&root),
+            )
+            .await
+            {
+                Ok(count) => {
+                    tracing::debug!(%project_id, "Post-sync package ingestion: {} packages", count)
+                }
+                Err(e) => {
+                    tracing::warn!(%project_id, "Post-sync package ingestion failed: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Ingest the CODEOWNERS file of a project (best-effort). Files are
+    /// linked to their owners; the rules are cleared when the file is gone.
+    pub fn spawn_ingest_code_owners(&self, project_id: Uuid) {
+        let neo4j = self.neo4j_arc();
+        tokio::spawn(async move {
+            let project = match neo4j.get_project(project_id).await {
+                Ok(Some(project)) => project,
+                Ok(None) => return,
+                Err(e) => {
+                    tracing::warn!(%project_id, "CODEOWNERS ingestion: project lookup failed: {}", e);
+                    return;
+                }
+            };
+            match crate::orchestrator::ownership::sync_code_owners(neo4j.as_ref(), &project).await {
+                Ok(Some(report)) => tracing::debug!(
+                    %project_id,
+                    "Post-sync CODEOWNERS ingestion: {} rules, {} owned files",
+                    report.rules,
+                    report.owned_files
+                ),
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!(%project_id, "Post-sync CODEOWNERS ingestion failed: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Refresh the per-directory module summaries of a project (best-effort).
+    /// Only directories whose files changed since the last refresh are
+    /// summarized again, by the prompt builder model (`PROMPT_BUILDER_MODEL`,
+    /// empty to keep the heuristic summaries).
+    pub fn spawn_refresh_module_summaries(&self, project_id: Uuid) {
+        let neo4j = self.neo4j_arc();
+        tokio::spawn(async move {
+            let model =
+                std::env::var("PROMPT_BUILDER_MODEL").unwrap_or_else(|_| "claude-opus-4-6".into());
+            match crate::orchestrator::module_summary::refresh_module_summaries(
+                neo4j.as_ref(),
+                project_id,
+                Some(&model),
+            )
+            .await
+            {
+                Ok(r) if r.regenerated > 0 || r.removed > 0 => {
+                    tracing::info!(
+                        %project_id,
+                        modules = r.modules,
+                        removed = r.removed,
+                        "Post-sync module summaries: {} regenerated",
+                        r.regenerated
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!(%project_id, "Post-sync module summaries failed: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Snapshot the public API surface of a project (best-effort), when it
+    /// changed since the latest snapshot.
+    pub fn spawn_record_api_surface(&self, project_id: Uuid) {
+        let neo4j = self.neo4j_arc();
+        tokio::spawn(async move {
+            match crate::orchestrator::api_surface::record_snapshot(neo4j.as_ref(), project_id)
+                .await
+            {
+                Ok(Some(snapshot)) => {
+                    tracing::info!(
+                        %project_id,
+                        commit = snapshot.commit.as_deref().unwrap_or(""),
+                        "Post-sync API surface snapshot: {} public symbols",
+                        snapshot.symbol_count
+                    );
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!(%project_id, "Post-sync API surface snapshot failed: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Parse the files changed between commits `base` and `head` of a
+    /// project into a throwaway overlay and analyze the delta. The project
+    /// graph is left untouched.
+    pub async fn sync_diff(
+        &self,
+        project: &ProjectNode,
+        base: &str,
+        head: &str,
+    ) -> Result<crate::orchestrator::diff_sync::DiffSyncReport> {
+        crate::orchestrator::diff_sync::sync_diff(
+            self.state.neo4j.as_ref(),
+            &self.parser,
+            project,
+            base,
+            head,
+        )
+        .await
+    }
+
+    /// Spawn a background check of the project's complexity and size
+    /// budgets (no-op when none are declared), storing the report on the
+    /// project.
+    pub fn spawn_check_budgets(&self, project_id: Uuid) {
+        let neo4j = self.neo4j_arc();
+        tokio::spawn(async move {
+            let result = async {
+                let Some(budgets) = neo4j.get_project_budgets(project_id).await? else {
+                    return Ok(None);
+                };
+                let Some(project) = neo4j.get_project(project_id).await? else {
+                    return Ok(None);
+                };
+                if budgets.is_empty() {
+                    return Ok(None);
+                }
+                let report =
+                    crate::orchestrator::budgets::check_budgets(neo4j.as_ref(), &project, &budgets)
+                        .await?;
+                neo4j.save_budget_report(&report).await?;
+                anyhow::Ok(Some(report))
+            }
+            .await;
+            match result {
+                Ok(Some(report)) if !report.passed => {
+                    tracing::warn!(
+                        %project_id,
+                        "Post-sync budget check failed: {} violation(s)",
+                        report.violation_count
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!(%project_id, "Post-sync budget check errored: {}", e);
+                }
+            }
+        });
+    }
+
     /// Gather codebase context for LLM-based feature graph proposal.
     ///
     /// Collects top functions, module structure, and existing feature graphs
@@ -1551,24 +1772,76 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
     ) -> Result<SyncResult> {
         let project_slug = project_slug.map(|s| s.to_string());
         let mut result = SyncResult::default();
+        let limits = self.sync_limits_for(project_id).await?;
 
         // ── Phase 1: Scan ──────────────────────────────────────────
-        let entries = scan_files(dir_path);
+        let mut entries = Vec::new();
+        for entry in scan_files(dir_path) {
+            match skip_reason(&limits, &entry.path, entry.size) {
+                Some(reason) => result.skipped_files.push(SkippedFile {
+                    path: entry.path,
+                    reason,
+                    size: entry.size,
+                }),
+                None => entries.push(entry),
+            }
+        }
 
-        // Track all scanned paths for stale-file cleanup
-        let synced_paths: HashSet<String> = entries.iter().map(|e| e.path.clone()).collect();
+        // Track all scanned paths for stale-file cleanup (skipped files are
+        // dropped from the graph like deleted ones)
+        let mut synced_paths: HashSet<String> = entries.iter().map(|e| e.path.clone()).collect();
 
         // ── Phase 2: Read ───────────────────────────────────────────
-        let file_contents = read_files(entries).await;
+        let mut file_contents = read_files(entries).await;
+        if limits.skip_binary {
+            file_contents.retain(|fc| {
+                if !looks_binary(fc.content.as_bytes()) {
+                    return true;
+                }
+                synced_paths.remove(&fc.path);
+                result.skipped_files.push(SkippedFile {
+                    path: fc.path.clone(),
+                    reason: SkipReason::Binary,
+                    size: fc.size,
+                });
+                false
+            });
+        }
+        if !result.skipped_files.is_empty() {
+            tracing::info!(
+                "sync: {} file(s) skipped by the sync limits",
+                result.skipped_files.len()
+            );
+        }
 
-        // ── Hash check: skip unchanged files ───────────────────────
+        // ── Hash check: skip files unchanged in the graph and the index ─
+        // Code is only indexed in Meilisearch with a project
+        let indexes_code = project_id.is_some() && project_slug.is_some();
+        let indexed_hashes = if force || !indexes_code {
+            std::collections::HashMap::new()
+        } else {
+            let paths: Vec<String> = file_contents.iter().map(|fc| fc.path.clone()).collect();
+            self.state
+                .neo4j
+                .get_indexed_hashes(&paths)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to load indexed hashes: {}", e);
+                    Default::default()
+                })
+        };
+        // Files current in the graph whose search document is not
+        let mut index_only: HashSet<String> = HashSet::new();
         let mut to_parse = Vec::with_capacity(file_contents.len());
         for fc in file_contents {
             if !force {
                 if let Ok(Some(existing)) = self.state.neo4j.get_file(&fc.path).await {
                     if existing.hash == fc.hash {
-                        result.files_skipped += 1;
-                        continue;
+                        if !indexes_code || indexed_hashes.get(&fc.path) == Some(&fc.hash) {
+                            result.files_skipped += 1;
+                            continue;
+                        }
+                        index_only.insert(fc.path.clone());
                     }
                 }
             }
@@ -1577,9 +1850,14 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
 
         // ── Phase 3: Parse (with AST cache) ─────────────────────────
         let mut cache_guard = self.ast_cache.lock().await;
-        let parsed_files = parse_files_with_cache(to_parse, Some(&mut *cache_guard)).await;
+        let (index_only_files, parsed_files): (Vec<ParsedFile>, Vec<ParsedFile>) =
+            parse_files_with_cache(to_parse, Some(&mut *cache_guard))
+                .await
+                .into_iter()
+                .partition(|p| index_only.contains(&p.path));
         drop(cache_guard); // Release lock before Neo4j calls
         let parse_count = parsed_files.len();
+        result.files_reindexed = index_only_files.len();
 
         // ── Build ImportResolutionContext once for all files ────────
         // SuffixIndex is built from ALL scanned paths (not just changed ones)
@@ -1613,17 +1891,25 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
 
         // ── Index in MeiliSearch ───────────────────────────────────
         if let (Some(pid), Some(slug)) = (project_id, project_slug.as_deref()) {
-            for parsed in &parsed_files {
+            let mut indexed = Vec::new();
+            for parsed in parsed_files.iter().chain(&index_only_files) {
                 let doc = CodeParser::to_code_document(parsed, &pid.to_string(), slug);
-                if let Err(e) = self.state.meili.index_code(&doc).await {
-                    tracing::warn!("Failed to index {} in Meilisearch: {}", parsed.path, e);
+                match self.state.meili.index_code(&doc).await {
+                    Ok(()) => indexed.push((parsed.path.clone(), parsed.hash.clone())),
+                    Err(e) => {
+                        tracing::warn!("Failed to index {} in Meilisearch: {}", parsed.path, e)
+                    }
                 }
             }
+            if let Err(e) = self.state.neo4j.set_indexed_hashes(&indexed).await {
+                tracing::warn!("Failed to record indexed hashes: {}", e);
+            }
         }
 
         // ── Verify notes (best-effort) ─────────────────────────────
         for parsed in &parsed_files {
-            if let Ok(content) = tokio::fs::read_to_string(&parsed.path).await {
+            if let Ok(bytes) = tokio::fs::read(&parsed.path).await {
+                let content = crate::parser::encoding::decode_source(bytes).content;
                 if let Err(e) = self
                     .verify_notes_for_file(&parsed.path, parsed, &content)
                     .await
@@ -1634,11 +1920,12 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
         }
 
         tracing::info!(
-            "sync pipeline: scanned {} → read {} → parsed {} → stored {}",
+            "sync pipeline: scanned {} → read {} → parsed {} → stored {} (reindexed {})",
             synced_paths.len(),
             synced_paths.len(),
             parse_count,
             result.files_synced,
+            result.files_reindexed,
         );
 
         // ── Cleanup: remove stale files ────────────────────────────
@@ -1673,9 +1960,393 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
             }
         }
 
+        // ── Documentation: Markdown files (best-effort) ────────────
+        self.sync_documents(
+            dir_path,
+            project_id,
+            project_slug.as_deref(),
+            force,
+            &mut result,
+        )
+        .await;
+
+        // ── Database schema: SQL schema & migration files (best-effort) ─
+        if let Some(pid) = project_id {
+            if let Err(e) = self.sync_sql_schema(dir_path, pid).await {
+                tracing::warn!("Failed to sync SQL schema: {}", e);
+            }
+        }
+
+        // ── Deployment topology: Dockerfiles & compose files (best-effort) ─
+        if let Some(pid) = project_id {
+            if let Err(e) = self
+                .sync_docker_topology(dir_path, pid, project_slug.as_deref())
+                .await
+            {
+                tracing::warn!("Failed to sync Docker topology: {}", e);
+            }
+        }
+
+        // ── Configuration keys: config files & the code reading them (best-effort) ─
+        if let Some(pid) = project_id {
+            if let Err(e) = self.sync_config_keys(dir_path, pid).await {
+                tracing::warn!("Failed to sync config keys: {}", e);
+            }
+        }
+
+        // ── Architecture decisions: docs/adr files (best-effort) ───
+        if let Some(pid) = project_id {
+            match super::adr::import_adrs(self.neo4j(), pid, dir_path).await {
+                Ok(report) if report.created + report.updated > 0 => tracing::info!(
+                    "Synced ADRs: {} decisions created, {} updated",
+                    report.created,
+                    report.updated
+                ),
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Failed to sync ADRs: {}", e),
+            }
+        }
+
+        // ── Language servers: resolve name-based CALLS / USES_TYPE (optional) ─
+        let lsp = &self.state.config.lsp;
+        let pid = project_id.filter(|_| lsp.enabled && result.files_synced > 0);
+        if let Some(pid) = pid {
+            match crate::lsp::enrich_project(self.neo4j(), dir_path, pid, lsp).await {
+                Ok(report) => tracing::info!(
+                    "LSP enrichment: {} references resolved, {} external removed, {} unresolved",
+                    report.resolved,
+                    report.removed,
+                    report.unresolved
+                ),
+                Err(e) => tracing::warn!("LSP enrichment failed: {}", e),
+            }
+        }
+
         Ok(result)
     }
 
+    /// Rebuild the Table/Column/Migration nodes of a project from the `.sql`
+    /// files under a directory. Returns the number of tables.
+    pub async fn sync_sql_schema(&self, dir_path: &Path, project_id: Uuid) -> Result<usize> {
+        use crate::parser::sql;
+
+        let mut files = Vec::new();
+        for path in scan_sql_files(dir_path) {
+            match tokio::fs::read_to_string(&path).await {
+                Ok(content) => files.push((path, content)),
+                Err(e) => tracing::warn!("Failed to read SQL file {}: {}", path, e),
+            }
+        }
+
+        let schema = sql::build_schema(&files);
+        let tables = sql::to_table_nodes(project_id, &schema);
+        let migrations = sql::to_migration_nodes(project_id, &schema);
+        self.neo4j()
+            .replace_project_schema(project_id, &tables, &migrations)
+            .await?;
+
+        if !tables.is_empty() {
+            tracing::info!(
+                "Synced SQL schema: {} tables, {} migrations",
+                tables.len(),
+                migrations.len()
+            );
+        }
+        Ok(tables.len())
+    }
+
+    /// Rebuild the ConfigKey nodes of a project: the keys defined by the
+    /// `.env`, YAML, TOML and JSON files under a directory, cross-referenced
+    /// with the source files naming them. Returns the number of keys.
+    pub async fn sync_config_keys(&self, dir_path: &Path, project_id: Uuid) -> Result<usize> {
+        use crate::parser::config_keys;
+
+        /// Larger config files are data dumps, larger sources generated code
+        const MAX_CONFIG_BYTES: u64 = 256 * 1024;
+        const MAX_SOURCE_BYTES: u64 = 512 * 1024;
+
+        let mut configs = Vec::new();
+        for path in scan_config_files(dir_path) {
+            let size = tokio::fs::metadata(&path)
+                .await
+                .map(|m| m.len())
+                .unwrap_or(0);
+            if size > MAX_CONFIG_BYTES {
+                continue;
+            }
+            match tokio::fs::read_to_string(&path).await {
+                Ok(content) => configs.push((path, content)),
+                Err(e) => tracing::warn!("Failed to read config file {}: {}", path, e),
+            }
+        }
+
+        let mut sources = Vec::new();
+        for entry in scan_files(dir_path) {
+            if entry.size > MAX_SOURCE_BYTES {
+                continue;
+            }
+            // Unreadable (binary, non-UTF-8) sources name no keys
+            if let Ok(content) = tokio::fs::read_to_string(&entry.path).await {
+                sources.push((entry.path, content));
+            }
+        }
+
+        let keys = config_keys::build_config_keys(project_id, &configs, &sources);
+        self.neo4j()
+            .replace_project_config_keys(project_id, &keys)
+            .await?;
+
+        if !keys.is_empty() {
+            tracing::info!(
+                "Synced config keys: {} keys from {} config files",
+                keys.len(),
+                configs.len()
+            );
+        }
+        Ok(keys.len())
+    }
+
+    /// Create or update the workspace components declared by the Dockerfiles
+    /// and compose files under a directory, with their `depends_on` edges.
+    /// Components built from the project's sources are mapped to it.
+    ///
+    /// Never deletes components or edges — the topology may also hold
+    /// hand-declared entries. Returns the number of components created or
+    /// updated (0 when the project is not in a workspace).
+    pub async fn sync_docker_topology(
+        &self,
+        dir_path: &Path,
+        project_id: Uuid,
+        project_slug: Option<&str>,
+    ) -> Result<usize> {
+        use crate::parser::docker;
+
+        let Some(workspace) = self.neo4j().get_project_workspace(project_id).await? else {
+            return Ok(0);
+        };
+
+        let mut files = Vec::new();
+        for path in scan_docker_files(dir_path) {
+            match tokio::fs::read_to_string(&path).await {
+                Ok(content) => files.push((path, content)),
+                Err(e) => tracing::warn!("Failed to read {}: {}", path, e),
+            }
+        }
+        if files.is_empty() {
+            return Ok(0);
+        }
+
+        let root = normalize_path(&dir_path.to_string_lossy());
+        let root_name = project_slug.map(str::to_string).unwrap_or_else(|| {
+            dir_path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "app".to_string())
+        });
+        let discovered = docker::discover_components(Path::new(&root), &root_name, &files);
+
+        let topology = self.neo4j().get_workspace_topology(workspace.id).await?;
+        let mapped: HashSet<Uuid> = self
+            .neo4j()
+            .list_component_project_mappings(workspace.id)
+            .await?
+            .into_iter()
+            .map(|(component_id, _)| component_id)
+            .collect();
+
+        let mut written = 0;
+        let mut ids = std::collections::HashMap::new();
+        for found in &discovered {
+            let existing = topology
+                .iter()
+                .map(|(component, _, _)| component)
+                .find(|c| c.name.eq_ignore_ascii_case(&found.name));
+
+            let id = match existing {
+                Some(component) => {
+                    let mut config = component.config.as_object().cloned().unwrap_or_default();
+                    let had_port = config.contains_key("port");
+                    config.extend(found.to_config());
+                    if let Some(port) = found.primary_port().filter(|_| !had_port) {
+                        config.insert("port".to_string(), port.into());
+                    }
+                    let config = serde_json::Value::Object(config);
+                    let mut tags = component.tags.clone();
+                    if !tags.iter().any(|t| t == "docker") {
+                        tags.push("docker".to_string());
+                    }
+                    let runtime = component.runtime.is_none().then(|| "docker".to_string());
+
+                    if config != component.config || tags != component.tags || runtime.is_some() {
+                        self.update_component(
+                            component.id,
+                            None,
+                            None,
+                            runtime,
+                            Some(config),
+                            Some(tags),
+                        )
+                        .await?;
+                        written += 1;
+                    }
+                    component.id
+                }
+                None => {
+                    let mut config = found.to_config();
+                    if let Some(port) = found.primary_port() {
+                        config.insert("port".to_string(), port.into());
+                    }
+                    let component = ComponentNode {
+                        id: Uuid::new_v4(),
+                        workspace_id: workspace.id,
+                        name: found.name.clone(),
+                        component_type: found.component_type(),
+                        description: Some(format!("Discovered in {}", found.source)),
+                        runtime: Some("docker".to_string()),
+                        config: serde_json::Value::Object(config),
+                        created_at: chrono::Utc::now(),
+                        tags: vec!["docker".to_string()],
+                    };
+                    self.create_component(&component).await?;
+                    written += 1;
+                    component.id
+                }
+            };
+
+            if found.is_built() && !mapped.contains(&id) {
+                self.map_component_to_project(id, project_id).await?;
+            }
+            ids.insert(found.name.clone(), id);
+        }
+
+        for found in &discovered {
+            let from = ids[&found.name];
+            let declared: Vec<Uuid> = topology
+                .iter()
+                .find(|(component, _, _)| component.id == from)
+                .map(|(_, _, deps)| deps.iter().map(|d| d.to_id).collect())
+                .unwrap_or_default();
+            for target in &found.depends_on {
+                let Some(&to) = ids.get(target) else {
+                    continue;
+                };
+                if !declared.contains(&to) {
+                    self.add_component_dependency(from, to, None, true).await?;
+                }
+            }
+        }
+
+        if written > 0 {
+            tracing::info!(
+                "Synced Docker topology: {} component(s) in workspace {}",
+                written,
+                workspace.slug
+            );
+        }
+        Ok(written)
+    }
+
+    /// Index the Markdown documentation under a directory and remove the
+    /// documents of the project that no longer exist on disk.
+    async fn sync_documents(
+        &self,
+        dir_path: &Path,
+        project_id: Option<Uuid>,
+        project_slug: Option<&str>,
+        force: bool,
+        result: &mut SyncResult,
+    ) {
+        let paths = scan_documents(dir_path);
+
+        for path in &paths {
+            match self
+                .sync_document(Path::new(path), project_id, project_slug, force)
+                .await
+            {
+                Ok(true) => result.documents_synced += 1,
+                Ok(false) => {}
+                Err(e) => tracing::warn!("Failed to index document {}: {}", path, e),
+            }
+        }
+
+        if let Some(pid) = project_id {
+            match self.neo4j().delete_stale_documents(pid, &paths).await {
+                Ok(stale_paths) => {
+                    result.documents_deleted = stale_paths.len();
+                    for path in &stale_paths {
+                        if let Err(e) = self.meili().delete_docs_for_path(path).await {
+                            tracing::warn!(
+                                "Failed to delete stale document {} from Meilisearch: {}",
+                                path,
+                                e
+                            );
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to clean up stale documents: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Parse a Markdown document into Document/Section nodes and index its
+    /// sections in Meilisearch. Returns `false` when the document is unchanged.
+    pub async fn sync_document(
+        &self,
+        path: &Path,
+        project_id: Option<Uuid>,
+        project_slug: Option<&str>,
+        force: bool,
+    ) -> Result<bool> {
+        use crate::parser::markdown;
+        use sha2::{Digest, Sha256};
+
+        let content = tokio::fs::read_to_string(path)
+            .await
+            .context("Failed to read document")?;
+
+        let path_str = normalize_path(&path.to_string_lossy());
+        let mut hasher = Sha256::new();
+        hasher.update(content.as_bytes());
+        let hash = hex::encode(hasher.finalize());
+
+        if !force {
+            if let Some(existing) = self.neo4j().get_document(&path_str).await? {
+                if existing.hash == hash {
+                    return Ok(false); // Document unchanged
+                }
+            }
+        }
+
+        let parsed = markdown::parse_markdown(&content);
+        let sections = markdown::to_section_nodes(&path_str, &parsed);
+        let document = DocumentNode {
+            path: path_str.clone(),
+            project_id,
+            title: parsed.title.clone(),
+            hash,
+            section_count: sections.len(),
+            indexed_at: chrono::Utc::now(),
+        };
+        self.neo4j().upsert_document(&document, &sections).await?;
+
+        if let (Some(pid), Some(slug)) = (project_id, project_slug) {
+            let docs = markdown::to_search_documents(&path_str, &parsed, &pid.to_string(), slug);
+            self.meili().delete_docs_for_path(&path_str).await?;
+            self.meili().index_doc_sections(&docs).await?;
+        }
+
+        Ok(true)
+    }
+
+    /// Remove a Markdown document from the graph and the search index.
+    pub async fn delete_document(&self, path: &str) -> Result<()> {
+        self.neo4j().delete_document(path).await?;
+        self.meili().delete_docs_for_path(path).await?;
+        Ok(())
+    }
+
     /// Sync a single file to the knowledge base (legacy, no project)
     pub async fn sync_file(&self, path: &Path) -> Result<bool> {
         self.sync_file_for_project(path, None, None).await
@@ -1700,12 +2371,36 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
         project_slug: Option<&str>,
         force: bool,
     ) -> Result<bool> {
-        let content = tokio::fs::read_to_string(path)
+        if crate::parser::markdown::is_markdown_path(path) {
+            return self
+                .sync_document(path, project_id, project_slug, force)
+                .await;
+        }
+
+        let limits = self.sync_limits_for(project_id).await?;
+        let size = tokio::fs::metadata(path)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0);
+        if let Some(reason) = skip_reason(&limits, &path.to_string_lossy(), size) {
+            tracing::debug!("Skipping {} ({:?})", path.display(), reason);
+            return Ok(false);
+        }
+
+        let decoded = crate::parser::encoding::read_source(path)
             .await
             .context("Failed to read file")?;
+        let content = decoded.content;
+        if limits.skip_binary && looks_binary(content.as_bytes()) {
+            tracing::debug!("Skipping {} (Binary)", path.display());
+            return Ok(false);
+        }
 
         // Normalize path to absolute form for consistent Neo4j storage
         let path_str = normalize_path(&path.to_string_lossy());
+        let indexes_code = project_id.is_some() && project_slug.is_some();
+        // Current in the graph, only the search document is stale
+        let mut index_only = false;
         if !force {
             if let Some(existing) = self.state.neo4j.get_file(&path_str).await? {
                 use sha2::{Digest, Sha256};
@@ -1714,35 +2409,68 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
                 let hash = hex::encode(hasher.finalize());
 
                 if existing.hash == hash {
-                    return Ok(false); // File unchanged
+                    let indexed = !indexes_code
+                        || self
+                            .state
+                            .neo4j
+                            .get_indexed_hashes(std::slice::from_ref(&path_str))
+                            .await?
+                            .get(&path_str)
+                            == Some(&hash);
+                    if indexed {
+                        return Ok(false); // File unchanged
+                    }
+                    index_only = true;
                 }
             }
         }
 
         // Parse the file using normalized path
         let norm_path = std::path::Path::new(&path_str);
-        let parsed = {
+        let mut parsed = {
             let mut parser = self.parser.write().await;
             parser.parse_file(norm_path, &content)?
         };
+        parsed.encoding = decoded.encoding.map(str::to_string);
 
         // Store in Neo4j with project association
-        self.store_parsed_file_for_project(&parsed, project_id)
-            .await?;
+        if !index_only {
+            self.store_parsed_file_for_project(&parsed, project_id)
+                .await?;
+        }
 
         // Index in Meilisearch only if project context is available
         if let (Some(pid), Some(slug)) = (project_id, project_slug) {
             let doc = CodeParser::to_code_document(&parsed, &pid.to_string(), slug);
             self.state.meili.index_code(&doc).await?;
+            self.state
+                .neo4j
+                .set_indexed_hashes(&[(path_str.clone(), parsed.hash.clone())])
+                .await?;
         }
 
         // Verify notes attached to this file
-        self.verify_notes_for_file(&path_str, &parsed, &content)
-            .await?;
+        if !index_only {
+            self.verify_notes_for_file(&path_str, &parsed, &content)
+                .await?;
+        }
 
         Ok(true)
     }
 
+    /// Sync limits of a project, the defaults without a project
+    async fn sync_limits_for(&self, project_id: Option<Uuid>) -> Result<SyncLimits> {
+        Ok(match project_id {
+            Some(pid) => self
+                .state
+                .neo4j
+                .get_project_sync_limits(pid)
+                .await?
+                .unwrap_or_default(),
+            None => SyncLimits::default(),
+        })
+    }
+
     /// Verify notes attached to a file after it has been modified
     ///
     /// This checks if any notes anchored to entities in this file need
@@ -1753,6 +2481,12 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
         parsed: &ParsedFile,
         source: &str,
     ) -> Result<()> {
+        // Create FileInfo from parsed data
+        let file_info = NoteLifecycleManager::create_file_info(parsed, source);
+
+        // Follow moved functions/structs before verifying their notes
+        self.reanchor_notes_for_file(file_path, &file_info).await?;
+
         // Get all notes attached to this file
         let notes = self
             .state
@@ -1766,9 +2500,6 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
 
         tracing::debug!("Verifying {} notes for file: {}", notes.len(), file_path);
 
-        // Create FileInfo from parsed data
-        let file_info = NoteLifecycleManager::create_file_info(parsed, source);
-
         // Verify each note's anchors
         let results = self
             .note_lifecycle
@@ -1821,6 +2552,115 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
         Ok(())
     }
 
+    /// Re-anchor notes linked to the functions and structs of a file
+    ///
+    /// Anchors whose symbol moved are relinked to its new node by stable
+    /// symbol identity; anchors whose symbol is gone are marked invalid and
+    /// their note is flagged for review.
+    async fn reanchor_notes_for_file(
+        &self,
+        file_path: &str,
+        file_info: &crate::notes::FileInfo,
+    ) -> Result<()> {
+        use crate::notes::{NoteStatus, ReanchorOutcome};
+
+        let anchors = self
+            .state
+            .neo4j
+            .get_symbol_anchors_for_file(file_path)
+            .await?;
+
+        if anchors.is_empty() {
+            return Ok(());
+        }
+
+        let results = self.note_lifecycle.reanchor_symbols(&anchors, file_info);
+
+        for result in results {
+            let Some((_, anchor)) = anchors
+                .iter()
+                .find(|(id, a)| *id == result.note_id && a.entity_id == result.entity_id)
+            else {
+                continue;
+            };
+
+            match result.outcome {
+                ReanchorOutcome::Current { symbol_id } => {
+                    // Record the identity of anchors created before it existed
+                    if anchor.symbol_id.as_deref() != Some(&symbol_id) || !anchor.is_valid {
+                        self.state
+                            .neo4j
+                            .reanchor_note(
+                                result.note_id,
+                                &result.entity_type,
+                                &result.entity_id,
+                                &result.entity_id,
+                                &symbol_id,
+                            )
+                            .await?;
+                    }
+                }
+                ReanchorOutcome::Moved {
+                    new_entity_id,
+                    symbol_id,
+                } => {
+                    self.state
+                        .neo4j
+                        .reanchor_note(
+                            result.note_id,
+                            &result.entity_type,
+                            &result.entity_id,
+                            &new_entity_id,
+                            &symbol_id,
+                        )
+                        .await?;
+
+                    tracing::debug!(
+                        "Note {} re-anchored from {} to {}",
+                        result.note_id,
+                        result.entity_id,
+                        new_entity_id
+                    );
+                }
+                ReanchorOutcome::Orphaned { reason } => {
+                    if !anchor.is_valid {
+                        continue;
+                    }
+
+                    self.state
+                        .neo4j
+                        .mark_note_anchor_orphaned(
+                            result.note_id,
+                            &result.entity_type,
+                            &result.entity_id,
+                        )
+                        .await?;
+
+                    self.state
+                        .neo4j
+                        .update_note(
+                            result.note_id,
+                            None,
+                            None,
+                            Some(NoteStatus::NeedsReview),
+                            None,
+                            None,
+                        )
+                        .await?;
+
+                    self.state
+                        .meili
+                        .update_note_status(&result.note_id.to_string(), "needs_review")
+                        .await?;
+
+                    tracing::warn!("Note {} orphaned: {}", result.note_id, reason);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Verify assertion notes that apply to a file
     async fn verify_assertions_for_file(
         &self,
@@ -2041,8 +2881,10 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
             path: normalize_path(&parsed.path),
             language: parsed.language.clone(),
             hash: parsed.hash.clone(),
+            line_count: parsed.line_count,
             last_parsed: chrono::Utc::now(),
             project_id,
+            encoding: parsed.encoding.clone(),
         };
         self.state.neo4j.upsert_file(&file_node).await?;
 
@@ -2051,6 +2893,13 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
             .neo4j
             .batch_upsert_functions(&parsed.functions)
             .await?;
+        self.state
+            .neo4j
+            .batch_set_function_fingerprints(
+                &[normalize_path(&parsed.path)],
+                &parsed.function_fingerprints,
+            )
+            .await?;
         self.state
             .neo4j
             .batch_upsert_structs(&parsed.structs)
@@ -2209,7 +3058,7 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
     /// entities across files and issues ~10 batch queries total:
     ///
     /// 1. batch_upsert_files
-    /// 2. batch_upsert_functions
+    /// 2. batch_upsert_functions (+ batch_set_function_fingerprints)
     /// 3. batch_upsert_structs
     /// 4. batch_upsert_traits
     /// 5. batch_upsert_enums
@@ -2239,8 +3088,10 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
                 path: normalize_path(&p.path),
                 language: p.language.clone(),
                 hash: p.hash.clone(),
+                line_count: p.line_count,
                 last_parsed: chrono::Utc::now(),
                 project_id,
+                encoding: p.encoding.clone(),
             })
             .collect();
         self.state.neo4j.batch_upsert_files(&file_nodes).await?;
@@ -2275,6 +3126,18 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
             .neo4j
             .batch_upsert_functions(&all_functions)
             .await?;
+        let fingerprinted_paths: Vec<String> = parsed_files
+            .iter()
+            .map(|p| normalize_path(&p.path))
+            .collect();
+        let all_fingerprints: Vec<_> = parsed_files
+            .iter()
+            .flat_map(|p| p.function_fingerprints.iter().cloned())
+            .collect();
+        self.state
+            .neo4j
+            .batch_set_function_fingerprints(&fingerprinted_paths, &all_fingerprints)
+            .await?;
         self.state.neo4j.batch_upsert_structs(&all_structs).await?;
         self.state.neo4j.batch_upsert_traits(&all_traits).await?;
         self.state.neo4j.batch_upsert_enums(&all_enums).await?;
@@ -4057,8 +4920,10 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
     /// Score each function call with a confidence level based on how the callee was resolved.
     ///
     /// Scoring levels:
+    /// - **receiver-typed** (0.90): the parser inferred the type the callee is a method of
     /// - **import-resolved** (0.90): callee is defined in a file that the caller's file imports
     /// - **same-file** (0.85): callee is defined in the same file as the caller
+    /// - **same-module** (0.70): exactly one definition lives in the caller's directory
     /// - **fuzzy-unique** (0.50): callee name found exactly once in the SymbolTable
     /// - **fuzzy-ambiguous** (0.30): callee name found in multiple files
     /// - **unscored** (0.50): no SymbolTable available (legacy fallback)
@@ -4084,6 +4949,7 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
             .map(|call| {
                 let (confidence, reason) = Self::score_single_call(
                     &call.callee_name,
+                    call.receiver_type.as_deref(),
                     &call.caller_id,
                     &same_file_names,
                     &imported_files,
@@ -4096,6 +4962,7 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
                     line: call.line,
                     confidence,
                     reason,
+                    receiver_type: call.receiver_type.clone(),
                 }
             })
             .collect()
@@ -4104,11 +4971,17 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
     /// Score a single function call.
     fn score_single_call(
         callee_name: &str,
+        receiver_type: Option<&str>,
         caller_id: &str,
         same_file_names: &std::collections::HashSet<&str>,
         imported_files: &std::collections::HashSet<&str>,
         ctx: Option<&crate::resolver::ImportResolutionContext>,
     ) -> (f64, String) {
+        // Priority 0: method of a known type, only linked to that type's impls
+        if receiver_type.is_some() {
+            return (0.90, "receiver-typed".to_string());
+        }
+
         // Priority 1: same-file match (callee defined in same file as caller)
         if same_file_names.contains(callee_name) {
             return (0.85, "same-file".to_string());
@@ -4144,6 +5017,26 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
                 }
             }
 
+            // Same module: a single definition sits next to the caller's file,
+            // the others (if any) live elsewhere in the project
+            let caller_dir = caller_file
+                .rsplit_once('/')
+                .map(|(dir, _)| dir)
+                .unwrap_or("");
+            let in_module = defs
+                .iter()
+                .filter(|def| {
+                    def.file_path
+                        .rsplit_once('/')
+                        .map(|(dir, _)| dir)
+                        .unwrap_or("")
+                        == caller_dir
+                })
+                .count();
+            if in_module == 1 {
+                return (0.70, "same-module".to_string());
+            }
+
             // Fuzzy: callee found but not in imported or same file
             if defs.len() == 1 {
                 return (0.50, "fuzzy-unique".to_string());
@@ -4326,6 +5219,22 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
         Ok(())
     }
 
+    /// Archive or unarchive a project and emit event. The
+    /// ProjectWatcherBridge unregisters archived projects from the file
+    /// watcher and re-registers unarchived ones.
+    pub async fn set_project_archived(&self, id: Uuid, archived: bool) -> Result<()> {
+        self.neo4j().set_project_archived(id, archived).await?;
+        self.emit(
+            CrudEvent::new(
+                EventEntityType::Project,
+                CrudAction::Updated,
+                id.to_string(),
+            )
+            .with_payload(serde_json::json!({ "archived": archived })),
+        );
+        Ok(())
+    }
+
     /// Delete a project and emit event.
     ///
     /// Cleanup order:
@@ -4349,6 +5258,13 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
                 e
             );
         }
+        if let Err(e) = self.meili().delete_docs_for_project(&project.slug).await {
+            tracing::warn!(
+                "Failed to delete MeiliSearch doc sections for project '{}': {}",
+                project.slug,
+                e
+            );
+        }
 
         // Neo4j cascade delete (archives notes/decisions, deletes everything else)
         self.neo4j().delete_project(id, &project.name).await?;
@@ -4952,6 +5868,9 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
             )
             .with_payload(serde_json::json!({"name": &resource.name})),
         );
+        if let Err(e) = self.refresh_resource_elements(resource).await {
+            tracing::warn!(resource_id = %resource.id, "Failed to parse resource spec: {:#}", e);
+        }
         Ok(())
     }
 
@@ -4965,6 +5884,7 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
         version: Option<String>,
         description: Option<String>,
     ) -> Result<()> {
+        let reparse = file_path.is_some() || version.is_some();
         self.neo4j()
             .update_resource(id, name, file_path, url, version, description)
             .await?;
@@ -4973,6 +5893,13 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
             CrudAction::Updated,
             id.to_string(),
         ));
+        if reparse {
+            if let Some(resource) = self.neo4j().get_resource(id).await? {
+                if let Err(e) = self.refresh_resource_elements(&resource).await {
+                    tracing::warn!(resource_id = %id, "Failed to parse resource spec: {:#}", e);
+                }
+            }
+        }
         Ok(())
     }
 
@@ -5025,6 +5952,103 @@ Respond with ONLY a JSON array, no markdown fences, no explanation:
         Ok(())
     }
 
+    /// Locate a resource's spec file on disk.
+    ///
+    /// Absolute paths are used as-is; relative paths are tried against the
+    /// root of the owning project, then of each implementing project.
+    pub async fn resolve_resource_path(
+        &self,
+        resource: &ResourceNode,
+    ) -> Result<Option<std::path::PathBuf>> {
+        let path = Path::new(&resource.file_path);
+        if path.is_absolute() {
+            return Ok(path.exists().then(|| path.to_path_buf()));
+        }
+
+        let mut roots = Vec::new();
+        if let Some(project_id) = resource.project_id {
+            if let Some(project) = self.neo4j().get_project(project_id).await? {
+                roots.push(project.root_path);
+            }
+        }
+        for project in self.neo4j().get_resource_implementers(resource.id).await? {
+            roots.push(project.root_path);
+        }
+
+        Ok(roots
+            .iter()
+            .map(|root| Path::new(&crate::expand_tilde(root)).join(path))
+            .find(|candidate| candidate.exists()))
+    }
+
+    /// Replace a resource's ResourceElement nodes with freshly parsed
+    /// `elements`, returning the diff against the previous parse.
+    pub async fn store_resource_elements(
+        &self,
+        resource: &ResourceNode,
+        format: ContractFormat,
+        elements: Vec<ResourceElementNode>,
+    ) -> Result<ResourceParseResult> {
+        let previous = self
+            .neo4j()
+            .list_resource_elements(resource.id, None)
+            .await?;
+        let diff = contracts::diff_elements(&previous, &elements);
+        let stored = self
+            .neo4j()
+            .replace_resource_elements(resource.id, &elements)
+            .await?;
+
+        if !diff.is_empty() {
+            self.emit(
+                CrudEvent::new(
+                    EventEntityType::Resource,
+                    CrudAction::Updated,
+                    resource.id.to_string(),
+                )
+                .with_payload(serde_json::json!({
+                    "elements": stored,
+                    "added": diff.added.len(),
+                    "removed": diff.removed.len(),
+                    "changed": diff.changed.len(),
+                })),
+            );
+        }
+
+        Ok(ResourceParseResult {
+            resource_id: resource.id,
+            format,
+            elements: stored,
+            by_kind: contracts::count_by_kind(&elements),
+            diff,
+        })
+    }
+
+    /// Re-parse a resource spec if it is an OpenAPI/protobuf file found on
+    /// disk. `None` when there is nothing to parse.
+    pub async fn refresh_resource_elements(
+        &self,
+        resource: &ResourceNode,
+    ) -> Result<Option<ResourceParseResult>> {
+        let Some(format) = ContractFormat::detect(
+            &resource.resource_type,
+            resource.format.as_deref(),
+            &resource.file_path,
+        ) else {
+            return Ok(None);
+        };
+        let Some(path) = self.resolve_resource_path(resource).await? else {
+            return Ok(None);
+        };
+        let content = tokio::fs::read_to_string(&path)
+            .await
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let elements = contracts::parse_contract(resource.id, format, &content)?;
+        self.store_resource_elements(resource, format, elements)
+            .await
+            .map(Some)
+    }
+
     // --- Components ---
 
     /// Create a component and emit event
@@ -5145,6 +6169,19 @@ pub struct BackfillResult {
     pub elapsed_ms: u64,
 }
 
+/// Result of parsing a resource spec into ResourceElement nodes
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ResourceParseResult {
+    pub resource_id: Uuid,
+    pub format: ContractFormat,
+    /// Elements now stored for the resource
+    pub elements: usize,
+    /// Element count per kind (`operation`, `message`, …)
+    pub by_kind: std::collections::HashMap<String, usize>,
+    /// Changes since the previous parse
+    pub diff: ResourceElementDiff,
+}
+
 /// A commit with its touched files (parsed from git log)
 #[derive(Debug, Clone)]
 pub struct GitCommitFiles {
@@ -5254,6 +6291,77 @@ pub fn scan_files(root: &Path) -> Vec<FileEntry> {
     entries
 }
 
+/// Collect the Markdown documents under `root` (normalized paths, sorted),
+/// skipping the same ignored directories as [`scan_files`].
+pub fn scan_documents(root: &Path) -> Vec<String> {
+    let mut paths: Vec<String> = WalkDir::new(root)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| crate::parser::markdown::is_markdown_path(e.path()))
+        .map(|e| e.path().to_string_lossy().to_string())
+        .filter(|p| !super::should_ignore_path(p))
+        .map(|p| normalize_path(&p))
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Collect the Dockerfiles and compose files under `root` (normalized paths,
+/// sorted), skipping the same ignored directories as [`scan_files`].
+pub fn scan_docker_files(root: &Path) -> Vec<String> {
+    use crate::parser::docker::{is_compose_path, is_dockerfile_path};
+
+    let mut paths: Vec<String> = WalkDir::new(root)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| is_dockerfile_path(e.path()) || is_compose_path(e.path()))
+        .map(|e| e.path().to_string_lossy().to_string())
+        .filter(|p| !super::should_ignore_path(p))
+        .map(|p| normalize_path(&p))
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Collect the configuration files under `root` — `.env`, YAML, TOML, JSON
+/// (normalized paths, sorted), skipping the same ignored directories as
+/// [`scan_files`].
+pub fn scan_config_files(root: &Path) -> Vec<String> {
+    let mut paths: Vec<String> = WalkDir::new(root)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| crate::parser::config_keys::config_format(e.path()).is_some())
+        .map(|e| e.path().to_string_lossy().to_string())
+        .filter(|p| !super::should_ignore_path(p))
+        .map(|p| normalize_path(&p))
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Collect the `.sql` files under `root` (normalized paths, sorted),
+/// skipping the same ignored directories as [`scan_files`].
+pub fn scan_sql_files(root: &Path) -> Vec<String> {
+    let mut paths: Vec<String> = WalkDir::new(root)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| crate::parser::sql::is_sql_path(e.path()))
+        .map(|e| e.path().to_string_lossy().to_string())
+        .filter(|p| !super::should_ignore_path(p))
+        .map(|p| normalize_path(&p))
+        .collect();
+    paths.sort();
+    paths
+}
+
 /// Default byte budget per chunk: 20 MB.
 ///
 /// Files are grouped so that each chunk's total size does not exceed this limit.
@@ -5410,7 +6518,10 @@ pub async fn parse_files_with_cache(
                             let parser = p.as_mut()?;
                             let file_path = std::path::Path::new(&file.path);
                             match parser.parse_file(file_path, &file.content) {
-                                Ok(pf) => Some(pf),
+                                Ok(mut pf) => {
+                                    pf.encoding = file.encoding.clone();
+                                    Some(pf)
+                                }
                                 Err(e) => {
                                     tracing::warn!(
                                         "parse_files: failed to parse {}: {}",
@@ -5478,7 +6589,8 @@ pub async fn parse_files_with_cache(
 
 /// Phase 2: Read file contents from disk.
 ///
-/// Loads each file's content and computes a SHA-256 hash.
+/// Loads each file's content, converted to UTF-8 when it is in another
+/// encoding (see [`crate::parser::encoding`]), and computes a SHA-256 hash.
 /// Files that fail to read (permission errors, etc.) are logged and skipped.
 ///
 /// Returns only successfully read files.
@@ -5490,18 +6602,19 @@ pub async fn read_files(entries: Vec<FileEntry>) -> Vec<FileContent> {
     let mut read_errors = 0usize;
 
     for entry in entries {
-        match tokio::fs::read_to_string(&entry.path).await {
-            Ok(content) => {
+        match crate::parser::encoding::read_source(&entry.path).await {
+            Ok(decoded) => {
                 let mut hasher = Sha256::new();
-                hasher.update(content.as_bytes());
+                hasher.update(decoded.content.as_bytes());
                 let hash = hex::encode(hasher.finalize());
 
                 files.push(FileContent {
                     path: entry.path,
-                    content,
+                    content: decoded.content,
                     size: entry.size,
                     language: entry.language,
                     hash,
+                    encoding: decoded.encoding.map(str::to_string),
                 });
             }
             Err(e) => {
@@ -5551,6 +6664,8 @@ pub struct FileContent {
     pub language: crate::parser::SupportedLanguage,
     /// SHA-256 hash of content
     pub hash: String,
+    /// Encoding the content was converted from, `None` for UTF-8
+    pub encoding: Option<String>,
 }
 
 /// Result of a sync operation
@@ -5561,6 +6676,15 @@ pub struct SyncResult {
     pub files_deleted: usize,
     pub symbols_deleted: usize,
     pub errors: usize,
+    /// Markdown documents (re)indexed
+    pub documents_synced: usize,
+    /// Markdown documents removed because they no longer exist on disk
+    pub documents_deleted: usize,
+    /// Files left unparsed by the project's sync limits
+    pub skipped_files: Vec<SkippedFile>,
+    /// Files unchanged in the graph whose Meilisearch document was stale,
+    /// re-indexed without rewriting the graph
+    pub files_reindexed: usize,
 }
 
 #[cfg(test)]
@@ -6500,8 +7624,10 @@ mod tests {
             path: path.to_string(),
             language: "rust".to_string(),
             hash: "abc123".to_string(),
+            line_count: 0,
             last_parsed: Utc::now(),
             project_id: Some(project_id),
+            encoding: None,
         }
     }
 
@@ -6746,8 +7872,10 @@ mod tests {
                 path: path.to_string(),
                 language: "rust".to_string(),
                 hash: "abc".to_string(),
+                line_count: 0,
                 last_parsed: chrono::Utc::now(),
                 project_id: Some(project.id),
+                encoding: None,
             };
             GraphStore::upsert_file(&*mock_store, &file).await.unwrap();
         }
@@ -6954,6 +8082,7 @@ mod tests {
             path: file_path.clone(),
             language: "rust".to_string(),
             hash: "abc123".to_string(),
+            line_count: 0,
             functions: vec![
                 FunctionNode {
                     name: "foo".to_string(),
@@ -6994,6 +8123,7 @@ mod tests {
                 docstring: None,
                 parent_class: None,
                 interfaces: vec![],
+                fields: vec![],
             }],
             traits: vec![TraitNode {
                 name: "MyTrait".to_string(),
@@ -7014,6 +8144,7 @@ mod tests {
                 line_start: 80,
                 line_end: 90,
                 docstring: None,
+                variant_payloads: vec![],
             }],
             impl_blocks: vec![ImplNode {
                 for_type: "MyStruct".to_string(),
@@ -7023,6 +8154,7 @@ mod tests {
                 file_path: file_path.clone(),
                 line_start: 100,
                 line_end: 110,
+                derived: false,
             }],
             imports: vec![ImportNode {
                 path: "std::fmt".to_string(),
@@ -7037,8 +8169,11 @@ mod tests {
                 line: 5,
                 confidence: 0.50,
                 reason: "unscored".to_string(),
+                receiver_type: None,
             }],
             symbols: vec!["foo".to_string(), "bar".to_string()],
+            function_fingerprints: vec![],
+            encoding: None,
         };
 
         // Call without project_id to avoid mock's project-scoped call filtering
@@ -7079,6 +8214,7 @@ mod tests {
             path: "/tmp/test-project/src/empty.rs".to_string(),
             language: "rust".to_string(),
             hash: "empty".to_string(),
+            line_count: 0,
             functions: vec![],
             structs: vec![],
             traits: vec![],
@@ -7087,6 +8223,8 @@ mod tests {
             imports: vec![],
             function_calls: vec![],
             symbols: vec![],
+            function_fingerprints: vec![],
+            encoding: None,
         };
 
         orch.store_parsed_file_for_project(&parsed, None)
@@ -7109,6 +8247,7 @@ mod tests {
             path: "src/api/handlers.rs".to_string(),
             language: "rust".to_string(),
             hash: "abc123".to_string(),
+            line_count: 0,
             functions: vec![FunctionNode {
                 name: "create_plan".to_string(),
                 visibility: Visibility::Public,
@@ -7133,6 +8272,7 @@ mod tests {
                 docstring: None,
                 parent_class: None,
                 interfaces: vec![],
+                fields: vec![],
             }],
             traits: vec![],
             enums: vec![],
@@ -7140,6 +8280,8 @@ mod tests {
             impl_blocks: vec![],
             function_calls: vec![],
             symbols: vec![],
+            function_fingerprints: vec![],
+            encoding: None,
         };
 
         let text = Orchestrator::build_file_embedding_text(&parsed);
@@ -8329,6 +9471,7 @@ mod tests {
             path: path.to_string(),
             language: "rust".to_string(),
             hash: "test".to_string(),
+            line_count: 0,
             functions: func_names
                 .iter()
                 .enumerate()
@@ -8354,6 +9497,8 @@ mod tests {
             impl_blocks: vec![],
             function_calls: vec![],
             symbols: vec![],
+            function_fingerprints: vec![],
+            encoding: None,
         }
     }
 
@@ -8367,6 +9512,7 @@ mod tests {
             line: 15,
             confidence: 0.50,
             reason: "unscored".to_string(),
+            receiver_type: None,
         }];
         let import_rels = vec![];
 
@@ -8387,6 +9533,7 @@ mod tests {
             line: 15,
             confidence: 0.50,
             reason: "unscored".to_string(),
+            receiver_type: None,
         }];
         let import_rels = vec![(
             "src/lib.rs".to_string(),
@@ -8416,7 +9563,8 @@ mod tests {
 
     #[test]
     fn test_score_fuzzy_unique() {
-        // foo calls helper, helper exists in exactly 1 file but NOT imported
+        // foo calls helper, helper exists in exactly 1 file, NOT imported and
+        // outside the caller's directory
         let parsed = make_test_parsed_file("src/lib.rs", &["foo"]);
         let calls = vec![FunctionCall {
             caller_id: "src/lib.rs:foo:10".to_string(),
@@ -8424,17 +9572,18 @@ mod tests {
             line: 15,
             confidence: 0.50,
             reason: "unscored".to_string(),
+            receiver_type: None,
         }];
         let import_rels = vec![]; // No imports
 
         let mut ctx = crate::resolver::ImportResolutionContext::new(&[
             "src/lib.rs".to_string(),
-            "src/other.rs".to_string(),
+            "src/net/other.rs".to_string(),
         ]);
         ctx.symbol_table.add(
             "helper",
-            "src/other.rs:helper:1",
-            "src/other.rs",
+            "src/net/other.rs:helper:1",
+            "src/net/other.rs",
             crate::resolver::symbol_table::SymbolType::Function,
             1,
         );
@@ -8446,6 +9595,67 @@ mod tests {
         assert_eq!(scored[0].reason, "fuzzy-unique");
     }
 
+    #[test]
+    fn test_score_same_module() {
+        // foo calls process, defined in a sibling file and in another directory
+        let parsed = make_test_parsed_file("src/api/handlers.rs", &["foo"]);
+        let calls = vec![FunctionCall {
+            caller_id: "src/api/handlers.rs:foo:10".to_string(),
+            callee_name: "process".to_string(),
+            line: 15,
+            confidence: 0.50,
+            reason: "unscored".to_string(),
+            receiver_type: None,
+        }];
+        let import_rels = vec![];
+
+        let mut ctx = crate::resolver::ImportResolutionContext::new(&[
+            "src/api/handlers.rs".to_string(),
+            "src/api/routes.rs".to_string(),
+            "src/jobs/worker.rs".to_string(),
+        ]);
+        ctx.symbol_table.add(
+            "process",
+            "src/api/routes.rs:process:1",
+            "src/api/routes.rs",
+            crate::resolver::symbol_table::SymbolType::Function,
+            1,
+        );
+        ctx.symbol_table.add(
+            "process",
+            "src/jobs/worker.rs:process:10",
+            "src/jobs/worker.rs",
+            crate::resolver::symbol_table::SymbolType::Function,
+            10,
+        );
+
+        let scored = Orchestrator::score_function_calls(&calls, &parsed, &import_rels, Some(&ctx));
+
+        assert_eq!(scored.len(), 1);
+        assert_eq!(scored[0].confidence, 0.70);
+        assert_eq!(scored[0].reason, "same-module");
+    }
+
+    #[test]
+    fn test_score_receiver_typed() {
+        // self.save() inside impl Repo: scoped to Repo's methods
+        let parsed = make_test_parsed_file("src/repo.rs", &["sync"]);
+        let calls = vec![FunctionCall {
+            caller_id: "src/repo.rs:sync:10".to_string(),
+            callee_name: "save".to_string(),
+            line: 12,
+            confidence: 0.50,
+            reason: "unscored".to_string(),
+            receiver_type: Some("Repo".to_string()),
+        }];
+
+        let scored = Orchestrator::score_function_calls(&calls, &parsed, &[], None);
+
+        assert_eq!(scored[0].confidence, 0.90);
+        assert_eq!(scored[0].reason, "receiver-typed");
+        assert_eq!(scored[0].receiver_type.as_deref(), Some("Repo"));
+    }
+
     #[test]
     fn test_score_fuzzy_ambiguous() {
         // foo calls process, process exists in 2 different files, not imported
@@ -8456,6 +9666,7 @@ mod tests {
             line: 15,
             confidence: 0.50,
             reason: "unscored".to_string(),
+            receiver_type: None,
         }];
         let import_rels = vec![];
 
@@ -8496,6 +9707,7 @@ mod tests {
             line: 15,
             confidence: 0.50,
             reason: "unscored".to_string(),
+            receiver_type: None,
         }];
         let import_rels = vec![];
 
@@ -8518,6 +9730,7 @@ mod tests {
             line: 15,
             confidence: 0.50,
             reason: "unscored".to_string(),
+            receiver_type: None,
         }];
         let import_rels = vec![];
 
@@ -8551,6 +9764,7 @@ mod tests {
             path: file_path.clone(),
             language: "rust".to_string(),
             hash: "hash1".to_string(),
+            line_count: 0,
             functions: vec![FunctionNode {
                 name: "my_func".to_string(),
                 visibility: Visibility::Public,
@@ -8575,6 +9789,7 @@ mod tests {
                 docstring: None,
                 parent_class: None,
                 interfaces: vec![],
+                fields: vec![],
             }],
             traits: vec![],
             enums: vec![],
@@ -8582,6 +9797,8 @@ mod tests {
             imports: vec![],
             function_calls: vec![],
             symbols: vec!["my_func".to_string()],
+            function_fingerprints: vec![],
+            encoding: None,
         };
 
         // Store once
@@ -8842,6 +10059,7 @@ mod tests {
                 size: mb5,
                 language: SupportedLanguage::Rust,
                 hash: format!("h{}", i),
+                encoding: None,
             })
             .collect();
 
@@ -8864,6 +10082,7 @@ mod tests {
                 size: 1024 * 1024, // 1MB
                 language: SupportedLanguage::Rust,
                 hash: "h1".to_string(),
+                encoding: None,
             },
             FileContent {
                 path: "/tmp/huge.rs".to_string(),
@@ -8871,6 +10090,7 @@ mod tests {
                 size: 25 * 1024 * 1024, // 25MB > budget
                 language: SupportedLanguage::Rust,
                 hash: "h2".to_string(),
+                encoding: None,
             },
             FileContent {
                 path: "/tmp/small2.rs".to_string(),
@@ -8878,6 +10098,7 @@ mod tests {
                 size: 2 * 1024 * 1024, // 2MB
                 language: SupportedLanguage::Rust,
                 hash: "h3".to_string(),
+                encoding: None,
             },
         ];
 
@@ -8906,6 +10127,7 @@ mod tests {
                 size: 1000, // tiny
                 language: SupportedLanguage::Rust,
                 hash: format!("h{}", i),
+                encoding: None,
             })
             .collect();
 
@@ -8929,6 +10151,7 @@ mod tests {
                 size: mb5,
                 language: SupportedLanguage::Rust,
                 hash: format!("h{}", i),
+                encoding: None,
             })
             .collect();
 
@@ -8949,6 +10172,7 @@ mod tests {
             size: 100,                            // but size metadata says 100 bytes
             language: SupportedLanguage::Rust,
             hash: "h".to_string(),
+            encoding: None,
         }];
 
         // Budget = 1MB → file should get its own chunk (content is 2MB)
@@ -8977,6 +10201,7 @@ mod tests {
             size: 100,
             language: SupportedLanguage::Rust,
             hash: "abc123".to_string(),
+            encoding: None,
         }];
 
         let parsed = parse_files(files, &parser).await;
@@ -9006,6 +10231,7 @@ mod tests {
                 size: 44,
                 language: SupportedLanguage::Rust,
                 hash: "h1".to_string(),
+                encoding: None,
             },
             FileContent {
                 path: "/tmp/app.py".to_string(),
@@ -9013,6 +10239,7 @@ mod tests {
                 size: 21,
                 language: SupportedLanguage::Python,
                 hash: "h2".to_string(),
+                encoding: None,
             },
         ];
 
@@ -9034,6 +10261,7 @@ mod tests {
                 size: 3 * 1024 * 1024, // 3MB each
                 language: SupportedLanguage::Rust,
                 hash: format!("h{}", i),
+                encoding: None,
             })
             .collect();
 
@@ -9068,6 +10296,7 @@ mod tests {
                 size: 34,
                 language: SupportedLanguage::Rust,
                 hash: "h1".to_string(),
+                encoding: None,
             },
             FileContent {
                 path: "/tmp/b.py".to_string(),
@@ -9075,6 +10304,7 @@ mod tests {
                 size: 21,
                 language: SupportedLanguage::Python,
                 hash: "h2".to_string(),
+                encoding: None,
             },
         ];
 
@@ -9149,6 +10379,7 @@ mod tests {
                 path: "/tmp/batch_a.rs".to_string(),
                 language: "rust".to_string(),
                 hash: "ha".to_string(),
+                line_count: 0,
                 functions: vec![FunctionNode {
                     name: "fn_a".to_string(),
                     file_path: "/tmp/batch_a.rs".to_string(),
@@ -9170,11 +10401,14 @@ mod tests {
                 impl_blocks: vec![],
                 function_calls: vec![],
                 symbols: vec![],
+                function_fingerprints: vec![],
+                encoding: None,
             },
             ParsedFile {
                 path: "/tmp/batch_b.rs".to_string(),
                 language: "rust".to_string(),
                 hash: "hb".to_string(),
+                line_count: 0,
                 functions: vec![FunctionNode {
                     name: "fn_b".to_string(),
                     file_path: "/tmp/batch_b.rs".to_string(),
@@ -9196,6 +10430,8 @@ mod tests {
                 impl_blocks: vec![],
                 function_calls: vec![],
                 symbols: vec![],
+                function_fingerprints: vec![],
+                encoding: None,
             },
         ];
 
@@ -9256,6 +10492,7 @@ mod tests {
             size: 21,
             language: SupportedLanguage::Rust,
             hash: "hash_v1".to_string(),
+            encoding: None,
         }];
 
         // First parse — cache miss
@@ -9292,6 +10529,7 @@ mod tests {
             size: 23,
             language: SupportedLanguage::Rust,
             hash: "hash_v1".to_string(),
+            encoding: None,
         }];
         let p1 = parse_files_with_cache(files_v1, Some(&mut cache)).await;
         assert_eq!(p1.len(), 1);
@@ -9303,6 +10541,7 @@ mod tests {
             size: 23,
             language: SupportedLanguage::Rust,
             hash: "hash_v2".to_string(),
+            encoding: None,
         }];
         let p2 = parse_files_with_cache(files_v2, Some(&mut cache)).await;
         assert_eq!(p2.len(), 1);
@@ -9322,6 +10561,7 @@ mod tests {
             size: 20,
             language: SupportedLanguage::Rust,
             hash: "h".to_string(),
+            encoding: None,
         }];
 
         // None cache — should still parse
@@ -9404,6 +10644,386 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_sync_skips_files_unchanged_in_graph_and_index() {
+        use crate::meilisearch::SearchStore;
+        use crate::test_helpers::mock_app_state_with_stores;
+
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("lib.rs"), "pub fn run() {}").unwrap();
+        let (state, neo4j, meili) = mock_app_state_with_stores();
+        let orch = Orchestrator::new(state).await.unwrap();
+        let pid = Uuid::new_v4();
+        let sync = || {
+            orch.sync_directory_for_project_with_options(tmp.path(), Some(pid), Some("demo"), false)
+        };
+
+        let result = sync().await.unwrap();
+        assert_eq!(result.files_synced, 1);
+        let path = meili.code_documents.read().await[0].path.clone();
+        assert_eq!(neo4j.indexed_hashes.read().await.len(), 1);
+
+        // Unchanged everywhere: neither store is written
+        meili.delete_code(&path).await.unwrap();
+        let result = sync().await.unwrap();
+        assert_eq!(result.files_skipped, 1);
+        assert_eq!(result.files_reindexed, 0);
+        assert!(meili.code_documents.read().await.is_empty());
+
+        // Current graph, stale index: only Meilisearch is written
+        neo4j.indexed_hashes.write().await.clear();
+        let result = sync().await.unwrap();
+        assert_eq!(result.files_skipped, 0);
+        assert_eq!(result.files_synced, 0);
+        assert_eq!(result.files_reindexed, 1);
+        assert_eq!(meili.code_documents.read().await.len(), 1);
+        assert_eq!(neo4j.indexed_hashes.read().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sync_decodes_non_utf8_files() {
+        use crate::test_helpers::mock_app_state_with_stores;
+
+        let tmp = tempfile::tempdir().unwrap();
+        // Latin-1: "// Décompte des entrées"
+        std::fs::write(
+            tmp.path().join("legacy.rs"),
+            b"// D\xe9compte des entr\xe9es\npub fn compter() -> u32 { 0 }\n",
+        )
+        .unwrap();
+
+        let (state, neo4j, _) = mock_app_state_with_stores();
+        let orch = Orchestrator::new(state).await.unwrap();
+        let result = orch
+            .sync_directory_for_project_with_options(
+                tmp.path(),
+                Some(Uuid::new_v4()),
+                Some("demo"),
+                false,
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.files_synced, 1);
+        assert_eq!(result.errors, 0);
+
+        let files = neo4j.files.read().await;
+        let file = files.values().next().unwrap();
+        assert_eq!(file.encoding.as_deref(), Some("windows-1252"));
+        assert_eq!(file.line_count, 2);
+        assert!(neo4j
+            .functions
+            .read()
+            .await
+            .values()
+            .any(|f| f.name == "compter"));
+    }
+
+    #[tokio::test]
+    async fn test_sync_skips_files_over_the_sync_limits() {
+        use crate::neo4j::GraphStore;
+        use crate::orchestrator::sync_limits::SkipReason;
+        use crate::test_helpers::mock_app_state_with_stores;
+        use std::fs;
+
+        let tmp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(tmp.path().join("src")).unwrap();
+        fs::create_dir_all(tmp.path().join("dist")).unwrap();
+        fs::write(tmp.path().join("src/lib.rs"), "pub fn run() {}").unwrap();
+        fs::write(
+            tmp.path().join("src/generated.rs"),
+            "pub fn f() {}\n".repeat(20),
+        )
+        .unwrap();
+        fs::write(tmp.path().join("src/blob.rs"), "pub fn g() {}\0\0").unwrap();
+        fs::write(tmp.path().join("dist/app.min.js"), "function a(){}").unwrap();
+
+        let (state, neo4j, _) = mock_app_state_with_stores();
+        let orch = Orchestrator::new(state).await.unwrap();
+        let pid = Uuid::new_v4();
+        neo4j
+            .update_project_sync_limits(
+                pid,
+                &SyncLimits {
+                    max_file_bytes: 100,
+                    excluded_extensions: vec!["min.js".to_string()],
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let result = orch
+            .sync_directory_for_project_with_options(tmp.path(), Some(pid), Some("demo"), false)
+            .await
+            .unwrap();
+        assert_eq!(result.files_synced, 1);
+        let reason_of = |name: &str| {
+            result
+                .skipped_files
+                .iter()
+                .find(|f| f.path.ends_with(name))
+                .map(|f| f.reason)
+        };
+        assert_eq!(result.skipped_files.len(), 3);
+        assert_eq!(reason_of("generated.rs"), Some(SkipReason::TooLarge));
+        assert_eq!(reason_of("blob.rs"), Some(SkipReason::Binary));
+        assert_eq!(reason_of("app.min.js"), Some(SkipReason::ExcludedExtension));
+        let files: Vec<String> = neo4j.files.read().await.keys().cloned().collect();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].ends_with("src/lib.rs"));
+
+        // The watcher path honours the same limits
+        assert!(!orch
+            .sync_file_for_project(&tmp.path().join("src/generated.rs"), Some(pid), None)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_sync_indexes_markdown_documents() {
+        use crate::meilisearch::SearchStore;
+        use crate::neo4j::GraphStore;
+        use crate::test_helpers::mock_app_state_with_stores;
+        use std::fs;
+
+        let tmp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(tmp.path().join("docs")).unwrap();
+        fs::create_dir_all(tmp.path().join("src")).unwrap();
+        fs::write(tmp.path().join("src/lib.rs"), "pub fn run() {}").unwrap();
+        fs::write(
+            tmp.path().join("README.md"),
+            "# Demo\n\nSee [the guide](docs/guide.md) and [lib](src/lib.rs).\n\n## Install\n\n```sh\ncargo install demo\n```\n",
+        )
+        .unwrap();
+        fs::write(
+            tmp.path().join("docs/guide.md"),
+            "# Guide\n\nDeployment walkthrough.\n",
+        )
+        .unwrap();
+
+        let (state, neo4j, meili) = mock_app_state_with_stores();
+        let orch = Orchestrator::new(state).await.unwrap();
+        let pid = Uuid::new_v4();
+
+        let result = orch
+            .sync_directory_for_project_with_options(tmp.path(), Some(pid), Some("demo"), false)
+            .await
+            .unwrap();
+        assert_eq!(result.files_synced, 1);
+        assert_eq!(result.documents_synced, 2);
+
+        let documents = neo4j.list_project_documents(pid).await.unwrap();
+        assert_eq!(documents.len(), 2);
+        let readme = documents
+            .iter()
+            .find(|d| d.path.ends_with("README.md"))
+            .unwrap();
+        assert_eq!(readme.title.as_deref(), Some("Demo"));
+
+        let sections = neo4j.get_document_sections(&readme.path).await.unwrap();
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[1].anchor, "install");
+        assert_eq!(sections[1].code_languages, vec!["sh".to_string()]);
+        assert!(sections[0]
+            .links
+            .iter()
+            .any(|l| l.ends_with("docs/guide.md")));
+        assert!(sections[0].links.iter().any(|l| l.ends_with("src/lib.rs")));
+
+        let hits = meili
+            .search_docs_with_scores("deployment", 10, Some("demo"))
+            .await
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0].document.path.ends_with("docs/guide.md"));
+
+        // Unchanged documents are skipped, removed ones are cleaned up
+        fs::remove_file(tmp.path().join("docs/guide.md")).unwrap();
+        let result = orch
+            .sync_directory_for_project_with_options(tmp.path(), Some(pid), Some("demo"), false)
+            .await
+            .unwrap();
+        assert_eq!(result.documents_synced, 0);
+        assert_eq!(result.documents_deleted, 1);
+        assert_eq!(neo4j.list_project_documents(pid).await.unwrap().len(), 1);
+        assert!(meili
+            .search_docs_with_scores("deployment", 10, Some("demo"))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sync_builds_sql_schema() {
+        use crate::neo4j::GraphStore;
+        use crate::test_helpers::mock_app_state_with_stores;
+        use std::fs;
+
+        let tmp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(tmp.path().join("migrations")).unwrap();
+        fs::write(
+            tmp.path().join("migrations/0001_init.sql"),
+            "CREATE TABLE users (id BIGSERIAL PRIMARY KEY, email TEXT NOT NULL);",
+        )
+        .unwrap();
+        fs::write(
+            tmp.path().join("migrations/0002_orders.sql"),
+            "CREATE TABLE orders (id BIGSERIAL PRIMARY KEY, user_id BIGINT REFERENCES users(id));\n\
+             ALTER TABLE users ADD COLUMN name TEXT;",
+        )
+        .unwrap();
+        fs::write(
+            tmp.path().join("migrations/0002_orders.down.sql"),
+            "DROP TABLE orders;",
+        )
+        .unwrap();
+
+        let (state, neo4j, _meili) = mock_app_state_with_stores();
+        let orch = Orchestrator::new(state).await.unwrap();
+        let pid = Uuid::new_v4();
+
+        orch.sync_directory_for_project_with_options(tmp.path(), Some(pid), Some("demo"), false)
+            .await
+            .unwrap();
+
+        let tables = neo4j.list_project_tables(pid).await.unwrap();
+        let names: Vec<&str> = tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["orders", "users"]);
+        let users = &tables[1];
+        assert_eq!(users.columns.len(), 3);
+        assert!(users.altered_by[0].ends_with("0002_orders.sql"));
+        assert_eq!(tables[0].columns[1].references.as_deref(), Some("users.id"));
+
+        let migrations = neo4j.list_project_migrations(pid).await.unwrap();
+        assert_eq!(migrations.len(), 2);
+        assert_eq!(migrations[1].version, "0002");
+        assert_eq!(migrations[1].alters.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_sync_discovers_docker_topology() {
+        use crate::neo4j::GraphStore;
+        use crate::test_helpers::mock_app_state_with_stores;
+        use std::fs;
+
+        let tmp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(tmp.path().join("api")).unwrap();
+        fs::write(
+            tmp.path().join("docker-compose.yml"),
+            "services:\n  api:\n    build: ./api\n    ports: [\"8080:3000\"]\n    depends_on: [db]\n  db:\n    image: postgres:16\n",
+        )
+        .unwrap();
+        fs::write(
+            tmp.path().join("api/Dockerfile"),
+            "FROM node:20-alpine\nEXPOSE 3000\n",
+        )
+        .unwrap();
+
+        let (state, neo4j, _meili) = mock_app_state_with_stores();
+        let orch = Orchestrator::new(state).await.unwrap();
+        let project = test_project_named("shop");
+        neo4j.create_project(&project).await.unwrap();
+        let workspace = WorkspaceNode {
+            id: Uuid::new_v4(),
+            name: "Shop".to_string(),
+            slug: "shop".to_string(),
+            description: None,
+            created_at: chrono::Utc::now(),
+            updated_at: None,
+            metadata: serde_json::Value::Null,
+        };
+        neo4j.create_workspace(&workspace).await.unwrap();
+        neo4j
+            .add_project_to_workspace(workspace.id, project.id)
+            .await
+            .unwrap();
+
+        orch.sync_directory_for_project_with_options(
+            tmp.path(),
+            Some(project.id),
+            Some("shop"),
+            false,
+        )
+        .await
+        .unwrap();
+
+        let topology = neo4j.get_workspace_topology(workspace.id).await.unwrap();
+        assert_eq!(topology.len(), 2);
+        let (api, api_project, api_deps) =
+            topology.iter().find(|(c, _, _)| c.name == "api").unwrap();
+        let (db, db_project, _) = topology.iter().find(|(c, _, _)| c.name == "db").unwrap();
+        assert_eq!(api.component_type, ComponentType::Service);
+        assert_eq!(api.config["base_image"], "node:20-alpine");
+        assert_eq!(api.config["port"], 3000);
+        assert_eq!(api_project.as_deref(), Some(project.slug.as_str()));
+        assert_eq!(api_deps.len(), 1);
+        assert_eq!(api_deps[0].to_id, db.id);
+        assert_eq!(db.component_type, ComponentType::Database);
+        assert!(db_project.is_none());
+
+        // Re-sync updates in place without duplicating components or edges
+        let written = orch
+            .sync_docker_topology(tmp.path(), project.id, Some("shop"))
+            .await
+            .unwrap();
+        assert_eq!(written, 0);
+        let topology = neo4j.get_workspace_topology(workspace.id).await.unwrap();
+        assert_eq!(topology.len(), 2);
+        let (_, _, api_deps) = topology.iter().find(|(c, _, _)| c.name == "api").unwrap();
+        assert_eq!(api_deps.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sync_cross_references_config_keys() {
+        use crate::neo4j::models::ConfigKeyKind;
+        use crate::neo4j::GraphStore;
+        use crate::test_helpers::mock_app_state_with_stores;
+        use std::fs;
+
+        let tmp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(tmp.path().join("src")).unwrap();
+        fs::write(
+            tmp.path().join(".env"),
+            "MEILISEARCH_URL=http://localhost:7700\nMEILISEARCH_KEY=secret\n",
+        )
+        .unwrap();
+        fs::write(
+            tmp.path().join("config.yaml"),
+            "search:\n  url: ${MEILISEARCH_URL}\n",
+        )
+        .unwrap();
+        fs::write(
+            tmp.path().join("src/main.rs"),
+            "fn main() {\n    let url = std::env::var(\"MEILISEARCH_URL\").unwrap();\n}\n",
+        )
+        .unwrap();
+
+        let (state, neo4j, _meili) = mock_app_state_with_stores();
+        let orch = Orchestrator::new(state).await.unwrap();
+        let pid = Uuid::new_v4();
+
+        orch.sync_directory_for_project_with_options(tmp.path(), Some(pid), Some("demo"), false)
+            .await
+            .unwrap();
+
+        let keys = neo4j
+            .list_project_config_keys(pid, Some("meilisearch_url"))
+            .await
+            .unwrap();
+        assert_eq!(keys.len(), 1);
+        let key = &keys[0];
+        assert_eq!(key.kind, ConfigKeyKind::Env);
+        assert!(key.defined_in[0].file_path.ends_with(".env"));
+        assert_eq!(key.referenced_by.len(), 2);
+        assert!(key.referenced_by[0].file_path.ends_with("config.yaml"));
+        assert!(key.referenced_by[1].file_path.ends_with("src/main.rs"));
+        assert_eq!(key.referenced_by[1].line, 2);
+
+        let all = neo4j.list_project_config_keys(pid, None).await.unwrap();
+        let names: Vec<&str> = all.iter().map(|k| k.key.as_str()).collect();
+        assert_eq!(names, ["MEILISEARCH_KEY", "MEILISEARCH_URL"]);
+    }
+
     #[test]
     fn test_rayon_pool_init_idempotent() {
         // Calling init_rayon_pool multiple times should not panic
@@ -9424,6 +11044,7 @@ mod tests {
                 size: 40,
                 language: SupportedLanguage::Rust,
                 hash: format!("h{}", i),
+                encoding: None,
             })
             .collect();
 
@@ -9453,6 +11074,7 @@ mod tests {
                 size: 19,
                 language: SupportedLanguage::Rust,
                 hash: "h1".to_string(),
+                encoding: None,
             },
             FileContent {
                 path: "/tmp/b.py".to_string(),
@@ -9460,6 +11082,7 @@ mod tests {
                 size: 25,
                 language: SupportedLanguage::Python,
                 hash: "h2".to_string(),
+                encoding: None,
             },
             FileContent {
                 path: "/tmp/c.ts".to_string(),
@@ -9467,6 +11090,7 @@ mod tests {
                 size: 26,
                 language: SupportedLanguage::TypeScript,
                 hash: "h3".to_string(),
+                encoding: None,
             },
             FileContent {
                 path: "/tmp/d.go".to_string(),
@@ -9474,6 +11098,7 @@ mod tests {
                 size: 28,
                 language: SupportedLanguage::Go,
                 hash: "h4".to_string(),
+                encoding: None,
             },
         ];
 
@@ -9508,6 +11133,7 @@ mod tests {
                     size: 50,
                     language: SupportedLanguage::Rust,
                     hash: format!("h{}", i),
+                    encoding: None,
                 })
                 .collect();
 
@@ -9582,6 +11208,7 @@ mod tests {
             path: file_path.clone(),
             language: "rust".to_string(),
             hash: "add-test-hash".to_string(),
+            line_count: 0,
             functions: vec![FunctionNode {
                 name: "handler".to_string(),
                 visibility: Visibility::Public,
@@ -9606,6 +11233,7 @@ mod tests {
                 docstring: None,
                 parent_class: None,
                 interfaces: vec![],
+                fields: vec![],
             }],
             traits: vec![],
             enums: vec![],
@@ -9619,6 +11247,8 @@ mod tests {
             }],
             function_calls: vec![],
             symbols: vec!["handler".to_string(), "Config".to_string()],
+            function_fingerprints: vec![],
+            encoding: None,
         };
 
         // Store with project context so MeiliSearch gets indexed
@@ -9668,6 +11298,7 @@ mod tests {
             path: file_path.clone(),
             language: "rust".to_string(),
             hash: "delete-test-hash".to_string(),
+            line_count: 0,
             functions: vec![FunctionNode {
                 name: "temp_func".to_string(),
                 visibility: Visibility::Public,
@@ -9689,6 +11320,8 @@ mod tests {
             imports: vec![],
             function_calls: vec![],
             symbols: vec!["temp_func".to_string()],
+            function_fingerprints: vec![],
+            encoding: None,
         };
 
         orch.store_parsed_file_for_project(&parsed, Some(project_id))
@@ -9748,6 +11381,7 @@ mod tests {
             path: old_path.clone(),
             language: "rust".to_string(),
             hash: "old-hash".to_string(),
+            line_count: 0,
             functions: vec![FunctionNode {
                 name: "my_fn".to_string(),
                 visibility: Visibility::Public,
@@ -9769,6 +11403,8 @@ mod tests {
             imports: vec![],
             function_calls: vec![],
             symbols: vec!["my_fn".to_string()],
+            function_fingerprints: vec![],
+            encoding: None,
         };
 
         orch.store_parsed_file_for_project(&old_parsed, Some(project_id))
@@ -9791,6 +11427,7 @@ mod tests {
             path: new_path.clone(),
             language: "rust".to_string(),
             hash: "new-hash".to_string(),
+            line_count: 0,
             functions: vec![FunctionNode {
                 name: "my_fn".to_string(),
                 visibility: Visibility::Public,
@@ -9812,6 +11449,8 @@ mod tests {
             imports: vec![],
             function_calls: vec![],
             symbols: vec!["my_fn".to_string()],
+            function_fingerprints: vec![],
+            encoding: None,
         };
 
         orch.store_parsed_file_for_project(&new_parsed, Some(project_id))
@@ -9892,8 +11531,10 @@ mod tests {
                     path: path.clone(),
                     language: "rust".to_string(),
                     hash: format!("hash_{}", i),
+                    line_count: 0,
                     last_parsed: chrono::Utc::now(),
                     project_id: Some(project_id),
+                    encoding: None,
                 },
             )
             .await
@@ -9947,6 +11588,7 @@ mod tests {
                 path: path.clone(),
                 language: "rust".to_string(),
                 hash: "hash".to_string(),
+                line_count: 0,
                 functions: vec![FunctionNode {
                     name: format!("fn_{}", path.split('/').next_back().unwrap()),
                     visibility: Visibility::Public,
@@ -9968,6 +11610,8 @@ mod tests {
                 imports: vec![],
                 function_calls: vec![],
                 symbols: vec![],
+                function_fingerprints: vec![],
+                encoding: None,
             };
             orch.store_parsed_file_for_project(&parsed, Some(project_id))
                 .await
@@ -9993,6 +11637,7 @@ mod tests {
             path: file_b.clone(),
             language: "rust".to_string(),
             hash: "new-hash".to_string(),
+            line_count: 0,
             functions: vec![
                 FunctionNode {
                     name: "fn_file_b.rs".to_string(),
@@ -10030,6 +11675,8 @@ mod tests {
             imports: vec![],
             function_calls: vec![],
             symbols: vec![],
+            function_fingerprints: vec![],
+            encoding: None,
         };
         orch.store_parsed_file_for_project(&parsed_b_modified, Some(project_id))
             .await
@@ -10072,6 +11719,7 @@ mod tests {
             path: importer.clone(),
             language: "rust".to_string(),
             hash: "importer-hash".to_string(),
+            line_count: 0,
             functions: vec![FunctionNode {
                 name: "main".to_string(),
                 visibility: Visibility::Public,
@@ -10099,12 +11747,15 @@ mod tests {
             }],
             function_calls: vec![],
             symbols: vec!["main".to_string()],
+            function_fingerprints: vec![],
+            encoding: None,
         };
 
         let parsed_imported = ParsedFile {
             path: imported.clone(),
             language: "rust".to_string(),
             hash: "imported-hash".to_string(),
+            line_count: 0,
             functions: vec![FunctionNode {
                 name: "helper".to_string(),
                 visibility: Visibility::Public,
@@ -10126,6 +11777,8 @@ mod tests {
             imports: vec![],
             function_calls: vec![],
             symbols: vec!["helper".to_string()],
+            function_fingerprints: vec![],
+            encoding: None,
         };
 
         orch.store_parsed_file_for_project(&parsed_importer, None)
@@ -10172,6 +11825,7 @@ mod tests {
                 path: path.clone(),
                 language: "rust".to_string(),
                 hash: format!("hash_{}", i),
+                line_count: 0,
                 functions: vec![FunctionNode {
                     name: format!("func_{}", i),
                     visibility: Visibility::Public,
@@ -10193,6 +11847,8 @@ mod tests {
                 imports: vec![],
                 function_calls: vec![],
                 symbols: vec![format!("func_{}", i)],
+                function_fingerprints: vec![],
+                encoding: None,
             };
             orch.store_parsed_file_for_project(&parsed, Some(project_id))
                 .await
@@ -10313,6 +11969,7 @@ mod tests {
                         line: func_idx * 10 + 5,
                         confidence: 0.8,
                         reason: "same_file".to_string(),
+                        receiver_type: None,
                     }
                 })
                 .collect();
@@ -10415,8 +12072,10 @@ mod tests {
                     path: file_path.clone(),
                     language: "rust".to_string(),
                     hash: format!("hash_{}", file_idx),
+                    line_count: 0,
                     last_parsed: chrono::Utc::now(),
                     project_id: None,
+                    encoding: None,
                 },
             )
             .await
@@ -10483,6 +12142,7 @@ mod tests {
             path: file_path.clone(),
             language: "java".to_string(),
             hash: "heritage123".to_string(),
+            line_count: 0,
             functions: vec![],
             structs: vec![
                 // Dog extends Animal
@@ -10496,6 +12156,7 @@ mod tests {
                     docstring: None,
                     parent_class: Some("Animal".to_string()),
                     interfaces: vec!["Serializable".to_string(), "Comparable".to_string()],
+                    fields: vec![],
                 },
                 // Cat extends Animal implements Serializable
                 StructNode {
@@ -10508,6 +12169,7 @@ mod tests {
                     docstring: None,
                     parent_class: Some("Animal".to_string()),
                     interfaces: vec!["Serializable".to_string()],
+                    fields: vec![],
                 },
                 // Animal — no parent, no interfaces
                 StructNode {
@@ -10520,6 +12182,7 @@ mod tests {
                     docstring: None,
                     parent_class: None,
                     interfaces: vec![],
+                    fields: vec![],
                 },
             ],
             traits: vec![],
@@ -10528,6 +12191,8 @@ mod tests {
             imports: vec![],
             function_calls: vec![],
             symbols: vec![],
+            function_fingerprints: vec![],
+            encoding: None,
         };
 
         orch.store_parsed_file_for_project(&parsed, None)
@@ -10600,6 +12265,7 @@ mod tests {
             path: file_path.clone(),
             language: "rust".to_string(),
             hash: "noinherit".to_string(),
+            line_count: 0,
             functions: vec![],
             structs: vec![StructNode {
                 name: "PlainStruct".to_string(),
@@ -10611,6 +12277,7 @@ mod tests {
                 docstring: None,
                 parent_class: None,
                 interfaces: vec![],
+                fields: vec![],
             }],
             traits: vec![],
             enums: vec![],
@@ -10618,6 +12285,8 @@ mod tests {
             imports: vec![],
             function_calls: vec![],
             symbols: vec![],
+            function_fingerprints: vec![],
+            encoding: None,
         };
 
         orch.store_parsed_file_for_project(&parsed, None)
@@ -0,0 +1,229 @@
+//! Semantic versioning advice from API surface diffs.
+//!
+//! A diff between two surfaces is classified with the semver rules: a removed
+//! symbol or a changed signature needs a major release, an added symbol a
+//! minor one, and anything else a patch. For `0.y.z` versions the Cargo and
+//! npm caret conventions apply: breaking changes bump the minor version (any
+//! change, for `0.0.z`) and additions the patch version.
+//!
+//! The current version is read from the `Cargo.toml` or `package.json` at the
+//! project root unless given.
+
+use super::api_surface::ApiSurfaceDiff;
+use serde::Serialize;
+use std::path::Path;
+
+/// Level of a semver release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Bump {
+    Patch,
+    Minor,
+    Major,
+}
+
+/// The release a surface diff calls for
+#[derive(Debug, Serialize)]
+pub struct SemverAdvice {
+    pub bump: Bump,
+    /// Why this level, in one sentence
+    pub reason: String,
+    pub current_version: Option<String>,
+    /// Where `current_version` comes from: `Cargo.toml`, `package.json` or
+    /// `query`
+    pub version_source: Option<String>,
+    /// None without a current version, or for a pre-release version
+    pub next_version: Option<String>,
+}
+
+/// Classify a surface diff.
+pub fn classify(diff: &ApiSurfaceDiff) -> (Bump, String) {
+    if diff.breaking {
+        let reason = format!(
+            "{} public symbol(s) removed and {} changed signature",
+            diff.removed.len(),
+            diff.changed.len()
+        );
+        (Bump::Major, reason)
+    } else if !diff.added.is_empty() {
+        let reason = format!(
+            "{} public symbol(s) added, none removed or changed",
+            diff.added.len()
+        );
+        (Bump::Minor, reason)
+    } else {
+        (
+            Bump::Patch,
+            "The public API surface is unchanged".to_string(),
+        )
+    }
+}
+
+/// The version after a `bump` release of `current` (`MAJOR.MINOR.PATCH`,
+/// with an optional `v` prefix and build metadata). Pre-release versions get
+/// no advice.
+pub fn next_version(current: &str, bump: Bump) -> Option<String> {
+    let core = current.trim().trim_start_matches('v');
+    let core = core.split('+').next()?;
+    if core.contains('-') {
+        return None;
+    }
+    let parts: Vec<u64> = core
+        .split('.')
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+    let [major, minor, patch] = parts[..] else {
+        return None;
+    };
+    let (major, minor, patch) = match (major, minor, bump) {
+        (0, 0, _) => (0, 0, patch + 1),
+        (0, _, Bump::Major) => (0, minor + 1, 0),
+        (0, _, _) => (0, minor, patch + 1),
+        (_, _, Bump::Major) => (major + 1, 0, 0),
+        (_, _, Bump::Minor) => (major, minor + 1, 0),
+        (_, _, Bump::Patch) => (major, minor, patch + 1),
+    };
+    Some(format!("{}.{}.{}", major, minor, patch))
+}
+
+/// The version declared by the manifest at `root`, with the manifest name.
+pub fn manifest_version(root: &Path) -> Option<(String, &'static str)> {
+    if let Ok(manifest) = std::fs::read_to_string(root.join("Cargo.toml")) {
+        if let Some(version) = cargo_manifest_version(&manifest) {
+            return Some((version, "Cargo.toml"));
+        }
+    }
+    let json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(root.join("package.json")).ok()?).ok()?;
+    let version = json.get("version")?.as_str()?;
+    Some((version.to_string(), "package.json"))
+}
+
+/// `version = "..."` of the `[package]` table of a Cargo.toml, or of
+/// `[workspace.package]` for `version.workspace = true`.
+fn cargo_manifest_version(manifest: &str) -> Option<String> {
+    let mut table = "";
+    let mut package = None;
+    let mut workspace = None;
+    let mut inherited = false;
+    for line in manifest.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            table = line;
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim().trim_matches('"').trim();
+        match (table, key.trim()) {
+            ("[package]", "version") => package = Some(value.to_string()),
+            ("[package]", "version.workspace") => inherited = value == "true",
+            ("[workspace.package]", "version") => workspace = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    if inherited {
+        workspace
+    } else {
+        package
+    }
+}
+
+/// Advice for a surface diff of the project rooted at `root`, bumping
+/// `version` when given, else the manifest version.
+pub fn advise(diff: &ApiSurfaceDiff, root: Option<&Path>, version: Option<&str>) -> SemverAdvice {
+    let (bump, reason) = classify(diff);
+    let current = match version {
+        Some(version) => Some((version.to_string(), "query")),
+        None => root.and_then(manifest_version),
+    };
+    let next_version = current
+        .as_ref()
+        .and_then(|(version, _)| next_version(version, bump));
+    let (current_version, version_source) = match current {
+        Some((version, source)) => (Some(version), Some(source.to_string())),
+        None => (None, None),
+    };
+    SemverAdvice {
+        bump,
+        reason,
+        current_version,
+        version_source,
+        next_version,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::models::ApiSymbol;
+
+    fn symbol(name: &str) -> ApiSymbol {
+        ApiSymbol {
+            module: "src/lib.rs".to_string(),
+            name: name.to_string(),
+            kind: "function".to_string(),
+            signature: format!("fn {}()", name),
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        let mut diff = ApiSurfaceDiff::default();
+        assert_eq!(classify(&diff).0, Bump::Patch);
+        diff.added.push(symbol("reload"));
+        assert_eq!(classify(&diff).0, Bump::Minor);
+        diff.removed.push(symbol("load"));
+        diff.breaking = true;
+        let (bump, reason) = classify(&diff);
+        assert_eq!(bump, Bump::Major);
+        assert_eq!(reason, "1 public symbol(s) removed and 0 changed signature");
+    }
+
+    #[test]
+    fn test_next_version() {
+        assert_eq!(next_version("1.4.2", Bump::Major).as_deref(), Some("2.0.0"));
+        assert_eq!(
+            next_version("v1.4.2", Bump::Minor).as_deref(),
+            Some("1.5.0")
+        );
+        assert_eq!(
+            next_version("1.4.2+build.7", Bump::Patch).as_deref(),
+            Some("1.4.3")
+        );
+        assert_eq!(next_version("0.3.1", Bump::Major).as_deref(), Some("0.4.0"));
+        assert_eq!(next_version("0.3.1", Bump::Minor).as_deref(), Some("0.3.2"));
+        assert_eq!(next_version("0.0.3", Bump::Major).as_deref(), Some("0.0.4"));
+        assert_eq!(next_version("2.0.0-rc.1", Bump::Patch), None);
+        assert_eq!(next_version("1.4", Bump::Patch), None);
+    }
+
+    #[test]
+    fn test_manifest_version() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(manifest_version(dir.path()), None);
+
+        std::fs::write(dir.path().join("package.json"), r#"{"version": "2.1.0"}"#).unwrap();
+        assert_eq!(
+            manifest_version(dir.path()),
+            Some(("2.1.0".to_string(), "package.json"))
+        );
+
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nversion.workspace = true\n\n\
+             [dependencies]\nserde = { version = \"1\" }\n\n\
+             [workspace.package]\nversion = \"0.9.0\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            manifest_version(dir.path()),
+            Some(("0.9.0".to_string(), "Cargo.toml"))
+        );
+
+        let advice = advise(&ApiSurfaceDiff::default(), Some(dir.path()), Some("3.0.0"));
+        assert_eq!(advice.version_source.as_deref(), Some("query"));
+        assert_eq!(advice.next_version.as_deref(), Some("3.0.1"));
+    }
+}
@@ -0,0 +1,457 @@
+//! Stack trace parsing and resolution onto the graph.
+//!
+//! Rust panics and backtraces, Python tracebacks and JavaScript stacks (V8
+//! `at fn (file:line:col)` lines and the `fn@file:line:col` lines of Firefox
+//! and Safari) are read into frames. Each frame's file is matched against the
+//! synced files of a project, like coverage report paths, and its line against
+//! the line ranges of the file's functions. Frames in the standard library or
+//! in dependencies stay unresolved.
+
+use super::coverage::resolve_path;
+use crate::neo4j::models::{FunctionSpanRow, ProjectNode};
+use crate::neo4j::traits::GraphStore;
+use crate::resolver::SuffixIndex;
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Frames read from a trace at most
+pub const MAX_FRAMES: usize = 500;
+
+/// Language a trace was written by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TraceLanguage {
+    Rust,
+    Python,
+    JavaScript,
+    Unknown,
+}
+
+/// One frame of a trace, in the order of the trace
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StackFrame {
+    /// Function name as printed, when the frame has one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    /// File as printed
+    pub file: String,
+    pub line: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<u32>,
+    /// Synced file of the project the frame points into
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<String>,
+    /// Innermost function of `file_path` containing `line`
+    /// (`file_path:name:line_start`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function_id: Option<String>,
+}
+
+/// A parsed trace
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParsedTrace {
+    pub language: TraceLanguage,
+    /// Panic message or exception line
+    pub message: Option<String>,
+    pub frames: Vec<StackFrame>,
+}
+
+fn frame(function: Option<String>, location: (String, u32, Option<u32>)) -> StackFrame {
+    let (file, line, column) = location;
+    StackFrame {
+        function,
+        file,
+        line,
+        column,
+        file_path: None,
+        function_id: None,
+    }
+}
+
+/// `path:line[:column]`, as printed by all three languages
+fn split_location(location: &str) -> Option<(String, u32, Option<u32>)> {
+    let location = location.trim().trim_end_matches(':');
+    let (rest, last) = location.rsplit_once(':')?;
+    let last: u32 = last.parse().ok()?;
+    let (file, line, column) = match rest.rsplit_once(':') {
+        Some((file, line)) if line.parse::<u32>().is_ok() => (file, line.parse().ok()?, Some(last)),
+        _ => (rest, last, None),
+    };
+    let file = strip_url(file).replace("/./", "/");
+    (!file.is_empty() && line > 0).then_some((file, line, column))
+}
+
+/// Path of a `file://` or `http(s)://` location
+fn strip_url(file: &str) -> &str {
+    if let Some(path) = file.strip_prefix("file://") {
+        return path;
+    }
+    for scheme in ["http://", "https://", "webpack://", "webpack-internal:///"] {
+        if let Some(rest) = file.strip_prefix(scheme) {
+            return rest.find('/').map_or(rest, |slash| &rest[slash..]);
+        }
+    }
+    file
+}
+
+/// Rust symbol without its `::h0123…` hash
+fn demangled(name: &str) -> String {
+    let name = name.trim();
+    match name.rsplit_once("::h") {
+        Some((path, hash)) if hash.len() == 16 && hash.chars().all(|c| c.is_ascii_hexdigit()) => {
+            path.to_string()
+        }
+        _ => name.to_string(),
+    }
+}
+
+/// `  File "app/views.py", line 12, in handler`
+fn python_frame(line: &str) -> Option<StackFrame> {
+    let rest = line.strip_prefix("File \"")?;
+    let (file, rest) = rest.split_once('"')?;
+    let rest = rest.trim_start_matches(',').trim().strip_prefix("line ")?;
+    let (number, function) = match rest.split_once(',') {
+        Some((number, after)) => (number, after.trim().strip_prefix("in ").map(str::trim)),
+        None => (rest, None),
+    };
+    let line: u32 = number.trim().parse().ok()?;
+    Some(frame(
+        function.map(String::from),
+        (file.to_string(), line, None),
+    ))
+}
+
+/// `at fn (file:line:col)`, `at file:line:col` or `fn@file:line:col`
+fn javascript_frame(line: &str) -> Option<StackFrame> {
+    if let Some(rest) = line.strip_prefix("at ") {
+        let rest = rest.trim_start_matches("async ");
+        return match rest.strip_suffix(')').and_then(|r| r.rsplit_once(" (")) {
+            Some((function, location)) => {
+                Some(frame(Some(function.to_string()), split_location(location)?))
+            }
+            None => Some(frame(None, split_location(rest)?)),
+        };
+    }
+    let (function, location) = line.split_once('@')?;
+    if function.contains(char::is_whitespace) {
+        return None;
+    }
+    let function = (!function.is_empty()).then(|| function.to_string());
+    Some(frame(function, split_location(location)?))
+}
+
+/// Parse a stack trace.
+pub fn parse_stack_trace(trace: &str) -> ParsedTrace {
+    let mut rust = false;
+    let mut python = false;
+    let mut javascript = false;
+    let mut message: Option<String> = None;
+    let mut python_message: Option<String> = None;
+    let mut message_next = false;
+    let mut pending_function: Option<String> = None;
+    let mut frames = Vec::new();
+
+    for raw in trace.lines() {
+        let line = raw.trim();
+        if line.is_empty() || frames.len() >= MAX_FRAMES {
+            continue;
+        }
+        if message_next {
+            message_next = false;
+            message = Some(line.to_string());
+            continue;
+        }
+
+        // Rust: `thread 'main' panicked at src/main.rs:4:5:` + message, or
+        // `panicked at 'message', src/main.rs:4:5` before Rust 1.73
+        if let Some((_, at)) = line.split_once("panicked at ") {
+            rust = true;
+            if let Some((quoted, location)) =
+                at.strip_prefix('\'').and_then(|a| a.rsplit_once("', "))
+            {
+                message = Some(quoted.to_string());
+                frames.extend(split_location(location).map(|l| frame(None, l)));
+            } else {
+                message_next = at.ends_with(':');
+                frames.extend(split_location(at).map(|l| frame(None, l)));
+            }
+            continue;
+        }
+        // Rust backtrace: `  3: crate::module::function` then
+        // `        at ./src/module.rs:42:9`
+        if let Some((index, function)) = line.split_once(": ") {
+            if !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()) {
+                let function = function.rsplit(" - ").next().unwrap_or(function);
+                pending_function = Some(demangled(function));
+                rust = true;
+                continue;
+            }
+        }
+        if let (Some(location), Some(function)) = (line.strip_prefix("at "), &pending_function) {
+            if !location.contains(" (") {
+                if let Some(location) = split_location(location) {
+                    frames.push(frame(Some(function.clone()), location));
+                }
+                pending_function = None;
+                continue;
+            }
+        }
+        pending_function = None;
+
+        if let Some(frame) = python_frame(line) {
+            python = true;
+            frames.push(frame);
+            continue;
+        }
+        if let Some(frame) = javascript_frame(line) {
+            javascript = true;
+            frames.push(frame);
+            continue;
+        }
+
+        // Python prints the exception last, the others first
+        let indented = raw.starts_with([' ', '\t']);
+        if line.starts_with("Traceback (") || line.starts_with("During handling") {
+            python = true;
+        } else if python {
+            // Indented lines are the source of the frames
+            if !indented {
+                python_message = Some(line.to_string());
+            }
+        } else if message.is_none() && line != "stack backtrace:" && !line.starts_with("note: ") {
+            message = Some(line.to_string());
+        }
+    }
+
+    let language = match (rust, python, javascript) {
+        (true, false, false) => TraceLanguage::Rust,
+        (false, true, false) => TraceLanguage::Python,
+        (false, false, true) => TraceLanguage::JavaScript,
+        (false, false, false) => TraceLanguage::Unknown,
+        // A panic with V8-looking `at` lines is still a Rust backtrace
+        (true, _, _) => TraceLanguage::Rust,
+        _ => TraceLanguage::Unknown,
+    };
+    ParsedTrace {
+        language,
+        message: if language == TraceLanguage::Python {
+            python_message.or(message)
+        } else {
+            message
+        },
+        frames,
+    }
+}
+
+/// Files of toolchains and installed dependencies, never the project's own
+fn is_library_path(file: &str) -> bool {
+    let file = file.replace('\\', "/");
+    [
+        "/rustc/",
+        "/.cargo/registry/",
+        "/.cargo/git/",
+        "/.rustup/",
+        "site-packages/",
+        "dist-packages/",
+        "/lib/python",
+        "node_modules/",
+        "node:",
+        "<frozen ",
+        "<anonymous>",
+    ]
+    .iter()
+    .any(|marker| file.contains(marker) || file.starts_with(marker.trim_start_matches('/')))
+}
+
+/// Resolve the frames of a trace onto the project's files and functions.
+pub async fn resolve_frames(
+    neo4j: &dyn GraphStore,
+    project: &ProjectNode,
+    frames: &mut [StackFrame],
+) -> Result<()> {
+    let synced_paths = neo4j.get_project_file_paths(project.id).await?;
+    let synced: HashSet<&str> = synced_paths.iter().map(String::as_str).collect();
+    let index = SuffixIndex::build(&synced_paths);
+    let roots = super::project_roots(&project.root_path);
+
+    let spans = neo4j.get_project_function_spans(project.id).await?;
+    let mut spans_by_file: HashMap<&str, Vec<&FunctionSpanRow>> = HashMap::new();
+    for span in &spans {
+        spans_by_file
+            .entry(span.file_path.as_str())
+            .or_default()
+            .push(span);
+    }
+
+    for frame in frames.iter_mut() {
+        if is_library_path(&frame.file) {
+            continue;
+        }
+        let Some(path) = resolve_path(&frame.file, &roots, &synced, &index) else {
+            continue;
+        };
+        frame.function_id = spans_by_file.get(path.as_str()).and_then(|spans| {
+            spans
+                .iter()
+                .filter(|s| {
+                    s.line_start <= frame.line && frame.line <= s.line_end.max(s.line_start)
+                })
+                .min_by_key(|s| s.line_end.saturating_sub(s.line_start))
+                .map(|s| s.id.clone())
+        });
+        frame.file_path = Some(path);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::{FunctionNode, Visibility};
+
+    const RUST: &str = "thread 'main' panicked at src/config.rs:12:9:
+called `Option::unwrap()` on a `None` value
+stack backtrace:
+   0: rust_begin_unwind
+             at /rustc/90b35a6239c3d8bdabc530a6a0816f7ff89a0aaf/library/std/src/panicking.rs:665:5
+   1: core::option::unwrap_failed
+             at /rustc/90b35a6239c3d8bdabc530a6a0816f7ff89a0aaf/library/core/src/option.rs:2015:5
+   2: demo::config::load::h2f8e7c3b1a9d0e4f
+             at ./src/config.rs:12:9
+   3: demo::main
+             at ./src/main.rs:3:5
+note: Some details are omitted, run with `RUST_BACKTRACE=full` for a verbose backtrace.";
+
+    const PYTHON: &str = r#"Traceback (most recent call last):
+  File "/srv/app/venv/lib/python3.12/site-packages/flask/app.py", line 880, in full_dispatch_request
+    rv = self.dispatch_request()
+  File "/srv/app/api/views.py", line 42, in get_user
+    return serialize(user)
+  File "/srv/app/api/serializers.py", line 7, in serialize
+    return {"name": user.name}
+AttributeError: 'NoneType' object has no attribute 'name'"#;
+
+    const JAVASCRIPT: &str = "TypeError: Cannot read properties of undefined (reading 'id')
+    at renderUser (webpack://app/./src/components/User.js:18:23)
+    at async loadPage (/home/ci/app/src/pages/index.js:5:3)
+    at processTicksAndRejections (node:internal/process/task_queues:95:5)
+    at http://localhost:3000/static/js/main.js:1:2048
+handleClick@http://localhost:3000/src/components/User.js:30:7";
+
+    #[test]
+    fn test_parse_rust_panic() {
+        let trace = parse_stack_trace(RUST);
+        assert_eq!(trace.language, TraceLanguage::Rust);
+        assert_eq!(
+            trace.message.as_deref(),
+            Some("called `Option::unwrap()` on a `None` value")
+        );
+        assert_eq!(trace.frames.len(), 5);
+        assert_eq!(trace.frames[0].file, "src/config.rs");
+        assert_eq!(trace.frames[0].function, None);
+        assert_eq!(
+            trace.frames[3].function.as_deref(),
+            Some("demo::config::load")
+        );
+        assert_eq!(trace.frames[3].file, "./src/config.rs");
+        assert_eq!(
+            (trace.frames[3].line, trace.frames[3].column),
+            (12, Some(9))
+        );
+        assert_eq!(trace.frames[4].function.as_deref(), Some("demo::main"));
+
+        let old = parse_stack_trace("thread 'main' panicked at 'boom', src/lib.rs:4:5");
+        assert_eq!(old.message.as_deref(), Some("boom"));
+        assert_eq!(old.frames[0].line, 4);
+    }
+
+    #[test]
+    fn test_parse_python_and_javascript() {
+        let trace = parse_stack_trace(PYTHON);
+        assert_eq!(trace.language, TraceLanguage::Python);
+        assert_eq!(
+            trace.message.as_deref(),
+            Some("AttributeError: 'NoneType' object has no attribute 'name'")
+        );
+        assert_eq!(trace.frames.len(), 3);
+        assert_eq!(trace.frames[1].file, "/srv/app/api/views.py");
+        assert_eq!(trace.frames[1].line, 42);
+        assert_eq!(trace.frames[1].function.as_deref(), Some("get_user"));
+
+        let trace = parse_stack_trace(JAVASCRIPT);
+        assert_eq!(trace.language, TraceLanguage::JavaScript);
+        assert_eq!(
+            trace.message.as_deref(),
+            Some("TypeError: Cannot read properties of undefined (reading 'id')")
+        );
+        assert_eq!(trace.frames.len(), 5);
+        assert_eq!(trace.frames[0].function.as_deref(), Some("renderUser"));
+        assert_eq!(trace.frames[0].file, "/src/components/User.js");
+        assert_eq!(trace.frames[1].function.as_deref(), Some("loadPage"));
+        assert_eq!(trace.frames[2].file, "node:internal/process/task_queues");
+        assert_eq!(trace.frames[3].function, None);
+        assert_eq!(trace.frames[3].file, "/static/js/main.js");
+        assert_eq!(trace.frames[4].function.as_deref(), Some("handleClick"));
+        assert_eq!(trace.frames[4].file, "/src/components/User.js");
+        assert_eq!(trace.frames[4].line, 30);
+
+        assert_eq!(
+            parse_stack_trace("just some text").language,
+            TraceLanguage::Unknown
+        );
+        assert!(parse_stack_trace("just some text").frames.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_frames() {
+        let graph = MockGraphStore::new();
+        let mut project = crate::test_helpers::test_project_named("traced");
+        project.root_path = "/repo".to_string();
+        graph.create_project(&project).await.unwrap();
+        for path in ["/repo/src/config.rs", "/repo/src/main.rs"] {
+            graph.link_file_to_project(path, project.id).await.unwrap();
+        }
+        for (name, path, line_start, line_end) in [
+            ("load", "/repo/src/config.rs", 5, 30),
+            ("parse_port", "/repo/src/config.rs", 10, 14),
+            ("main", "/repo/src/main.rs", 1, 8),
+        ] {
+            graph
+                .upsert_function(&FunctionNode {
+                    name: name.to_string(),
+                    visibility: Visibility::Public,
+                    params: vec![],
+                    return_type: None,
+                    generics: vec![],
+                    is_async: false,
+                    is_unsafe: false,
+                    complexity: 1,
+                    file_path: path.to_string(),
+                    line_start,
+                    line_end,
+                    docstring: None,
+                })
+                .await
+                .unwrap();
+        }
+
+        let mut trace = parse_stack_trace(RUST);
+        resolve_frames(&graph, &project, &mut trace.frames)
+            .await
+            .unwrap();
+        // std frames stay unresolved
+        assert_eq!(trace.frames[1].file_path, None);
+        assert_eq!(trace.frames[2].file_path, None);
+        // The innermost function containing the line
+        assert_eq!(
+            trace.frames[3].function_id.as_deref(),
+            Some("/repo/src/config.rs:parse_port:10")
+        );
+        assert_eq!(
+            trace.frames[4].function_id.as_deref(),
+            Some("/repo/src/main.rs:main:1")
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//! Sync limits.
+//!
+//! Lockfiles, bundled JavaScript and minified assets are large, generated
+//! and useless in the graph, yet parsing them can take longer than the rest
+//! of the project. A project's [`SyncLimits`] name the files a sync leaves
+//! unparsed: the ones over a size, the binary ones and the ones with an
+//! excluded extension. They are reported in the sync result as
+//! [`SkippedFile`]s instead of being parsed.
+
+use crate::neo4j::models::SyncLimits;
+use serde::Serialize;
+
+/// Bytes sniffed for a NUL byte by [`looks_binary`]
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+/// Why a sync left a file unparsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// Larger than `max_file_bytes`
+    TooLarge,
+    /// Contains a NUL byte
+    Binary,
+    /// Ends with one of `excluded_extensions`
+    ExcludedExtension,
+}
+
+/// A file a sync left unparsed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkippedFile {
+    pub path: String,
+    pub reason: SkipReason,
+    /// File size in bytes
+    pub size: u64,
+}
+
+/// Whether the content has a NUL byte in its first 8 KiB
+pub fn looks_binary(content: &[u8]) -> bool {
+    content[..content.len().min(BINARY_SNIFF_BYTES)].contains(&0)
+}
+
+/// Why a file must not be parsed, judged from its path and size alone
+/// (binary files are only detected once read).
+pub fn skip_reason(limits: &SyncLimits, path: &str, size: u64) -> Option<SkipReason> {
+    let name = path
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(path)
+        .to_lowercase();
+    let excluded = limits.excluded_extensions.iter().any(|ext| {
+        let ext = ext.trim_start_matches('.').to_lowercase();
+        !ext.is_empty()
+            && name
+                .strip_suffix(&ext)
+                .is_some_and(|stem| stem.ends_with('.'))
+    });
+    if excluded {
+        Some(SkipReason::ExcludedExtension)
+    } else if size > limits.max_file_bytes {
+        Some(SkipReason::TooLarge)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skip_reason() {
+        let limits = SyncLimits {
+            max_file_bytes: 100,
+            excluded_extensions: vec!["min.js".to_string(), ".PB.GO".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(skip_reason(&limits, "/repo/src/app.js", 100), None);
+        assert_eq!(
+            skip_reason(&limits, "/repo/src/app.js", 101),
+            Some(SkipReason::TooLarge)
+        );
+        assert_eq!(
+            skip_reason(&limits, "/repo/dist/App.MIN.js", 10),
+            Some(SkipReason::ExcludedExtension)
+        );
+        assert_eq!(
+            skip_reason(&limits, "/repo/api/user.pb.go", 10),
+            Some(SkipReason::ExcludedExtension)
+        );
+        // Suffixes only match whole extensions
+        assert_eq!(skip_reason(&limits, "/repo/src/admin.js", 10), None);
+        assert_eq!(skip_reason(&limits, "/repo/min.js/index.js", 10), None);
+    }
+
+    #[test]
+    fn test_looks_binary() {
+        assert!(!looks_binary(b"fn main() {}\n"));
+        assert!(looks_binary(b"\x7fELF\x02\x01\x01\x00"));
+        let mut late_nul = vec![b'a'; BINARY_SNIFF_BYTES];
+        late_nul.push(0);
+        assert!(!looks_binary(&late_nul));
+    }
+}
@@ -0,0 +1,774 @@
+//! CI test result ingestion and flaky test tracking.
+//!
+//! JUnit XML reports (cargo-nextest, pytest, Jest, Maven Surefire, Gradle, …)
+//! are uploaded after each CI run. Every test case keeps the outcomes of its
+//! last [`HISTORY_LEN`] runs and is linked to the test function it runs when
+//! one can be found by name, narrowed down by the report's `file` attribute or
+//! by the module path in its class name.
+//!
+//! A test is flaky when its recent history shows a flake event: a pass on a
+//! retry (Surefire `flakyFailure`, the same case reported twice, or a rerun of
+//! a commit it failed on) or a single failure between two passing runs. A
+//! regression that fails until it is fixed does not count. Chronically flaky
+//! tests come with a suggested gotcha note.
+
+use super::coverage::resolve_path;
+use crate::neo4j::models::{FunctionSpanRow, ProjectNode, TestCaseNode, TestOutcome, TestRunNode};
+use crate::neo4j::traits::GraphStore;
+use crate::notes::{
+    CreateAnchorRequest, CreateNoteRequest, EntityType, NoteImportance, NoteScope, NoteType,
+};
+use crate::resolver::SuffixIndex;
+use crate::utils::{floor_char_boundary, xml};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use uuid::Uuid;
+
+/// Runs kept in the history of a test case
+pub const HISTORY_LEN: usize = 30;
+/// Test runs kept per project
+pub const MAX_TEST_RUNS: usize = 200;
+/// Flake events in the history of a chronically flaky test
+pub const CHRONIC_FLAKE_EVENTS: usize = 3;
+/// Failed tests listed in an ingestion summary
+const MAX_FAILURES_LISTED: usize = 50;
+/// Bytes of a failure message kept
+const MAX_MESSAGE_LEN: usize = 500;
+
+/// A test case of a JUnit report
+#[derive(Debug, Clone, PartialEq)]
+pub struct JUnitCase {
+    pub classname: String,
+    pub name: String,
+    /// Source file, when the tool reports it
+    pub file: Option<String>,
+    pub outcome: TestOutcome,
+    pub duration_ms: u64,
+    /// First line of the failure message
+    pub message: Option<String>,
+    /// Failed at least once before passing within the run
+    pub retried: bool,
+}
+
+impl JUnitCase {
+    /// `classname::name`, identifying the case across runs
+    pub fn key(&self) -> String {
+        if self.classname.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}::{}", self.classname, self.name)
+        }
+    }
+}
+
+fn first_line(message: &str) -> Option<String> {
+    let line = message.lines().map(str::trim).find(|l| !l.is_empty())?;
+    Some(line[..floor_char_boundary(line, MAX_MESSAGE_LEN)].to_string())
+}
+
+fn duration_ms(seconds: Option<String>) -> u64 {
+    seconds
+        .and_then(|s| s.replace(',', "").parse::<f64>().ok())
+        .map_or(0, |s| (s * 1000.0).round().max(0.0) as u64)
+}
+
+/// Parse a JUnit XML report (`<testsuite>`/`<testcase>` with `<failure>`,
+/// `<error>`, `<skipped>` and Surefire's `flakyFailure`/`flakyError`).
+pub fn parse_junit(report: &str) -> Vec<JUnitCase> {
+    let mut cases = Vec::new();
+    let mut suite_files: Vec<Option<String>> = Vec::new();
+    let mut current: Option<JUnitCase> = None;
+    for tag in xml::tags(report) {
+        match (tag.name, tag.closing) {
+            ("testsuite", false) if !tag.self_closing => {
+                let inherited = suite_files.last().cloned().flatten();
+                suite_files.push(tag.attr("file").or(inherited));
+            }
+            ("testsuite", true) => {
+                suite_files.pop();
+            }
+            ("testcase", false) => {
+                let case = JUnitCase {
+                    classname: tag.attr("classname").unwrap_or_default(),
+                    name: tag.attr("name").unwrap_or_default(),
+                    file: tag
+                        .attr("file")
+                        .or_else(|| suite_files.last().cloned().flatten()),
+                    outcome: TestOutcome::Passed,
+                    duration_ms: duration_ms(tag.attr("time")),
+                    message: None,
+                    retried: false,
+                };
+                if tag.self_closing {
+                    cases.push(case);
+                } else {
+                    current = Some(case);
+                }
+            }
+            ("testcase", true) => cases.extend(current.take()),
+            ("failure" | "error", false) => {
+                if let Some(case) = current.as_mut() {
+                    case.outcome = TestOutcome::Failed;
+                    case.message = tag
+                        .attr("message")
+                        .as_deref()
+                        .and_then(first_line)
+                        .or_else(|| first_line(&tag.text()));
+                }
+            }
+            ("skipped", false) => {
+                if let Some(case) = current
+                    .as_mut()
+                    .filter(|c| c.outcome == TestOutcome::Passed)
+                {
+                    case.outcome = TestOutcome::Skipped;
+                }
+            }
+            ("flakyFailure" | "flakyError", false) => {
+                if let Some(case) = current.as_mut() {
+                    case.retried = true;
+                }
+            }
+            _ => {}
+        }
+    }
+    cases.retain(|c| !c.name.is_empty());
+    cases
+}
+
+/// Merge the cases of a run reported several times (retries, parameterized
+/// cases): failed if any failed, retried if some also passed.
+fn merge_cases(cases: &[JUnitCase]) -> BTreeMap<String, JUnitCase> {
+    let mut merged: BTreeMap<String, JUnitCase> = BTreeMap::new();
+    for case in cases {
+        let Some(existing) = merged.get_mut(&case.key()) else {
+            merged.insert(case.key(), case.clone());
+            continue;
+        };
+        let outcomes = [existing.outcome, case.outcome];
+        existing.retried |= case.retried
+            || (outcomes.contains(&TestOutcome::Passed) && outcomes.contains(&TestOutcome::Failed));
+        if case.outcome == TestOutcome::Failed {
+            existing.message = existing.message.take().or_else(|| case.message.clone());
+        }
+        existing.outcome = if outcomes.contains(&TestOutcome::Failed) {
+            TestOutcome::Failed
+        } else if outcomes.contains(&TestOutcome::Passed) {
+            TestOutcome::Passed
+        } else {
+            TestOutcome::Skipped
+        };
+        existing.duration_ms += case.duration_ms;
+        existing.file = existing.file.take().or_else(|| case.file.clone());
+    }
+    merged
+}
+
+/// Name of the test function a case runs: the last `::` or `.` segment of
+/// its name, without parameters (`test_x[1]`, `testX()`). None for sentence
+/// names (Jest, JUnit 5 display names).
+fn test_function_name(name: &str) -> Option<&str> {
+    let base = name.split(['(', '[']).next().unwrap_or(name).trim();
+    if base.is_empty() || base.contains(char::is_whitespace) {
+        return None;
+    }
+    let base = base.rsplit("::").next().unwrap_or(base);
+    base.rsplit('.').next().filter(|s| !s.is_empty())
+}
+
+/// The test function a case runs, among the project functions named like it.
+fn link_function<'a>(
+    case: &JUnitCase,
+    file: Option<&str>,
+    by_name: &HashMap<&str, Vec<&'a FunctionSpanRow>>,
+) -> Option<&'a FunctionSpanRow> {
+    let candidates = by_name.get(test_function_name(&case.name)?)?;
+    if let Some(found) = file.and_then(|file| candidates.iter().find(|f| f.file_path == file)) {
+        return Some(*found);
+    }
+    if let [only] = candidates.as_slice() {
+        return Some(*only);
+    }
+
+    // Module path segments (`crate::parser::tests`, `tests.test_api.TestApi`)
+    // matched against the directories and stem of each candidate's file
+    let hints: HashSet<String> = case
+        .classname
+        .split([':', '.', '/', '\\'])
+        .chain(case.name.split("::"))
+        .filter(|s| !s.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let score = |f: &FunctionSpanRow| {
+        f.file_path
+            .split('/')
+            .map(|segment| segment.split('.').next().unwrap_or(segment).to_lowercase())
+            .filter(|segment| hints.contains(segment))
+            .count()
+    };
+    let best = candidates.iter().map(|f| score(f)).max()?;
+    let mut top = candidates.iter().filter(|f| score(f) == best);
+    match (best, top.next(), top.next()) {
+        (1.., Some(found), None) => Some(*found),
+        _ => None,
+    }
+}
+
+/// Add the outcome of a run to a test case.
+pub fn record_outcome(
+    case: &mut TestCaseNode,
+    result: &JUnitCase,
+    commit: Option<&str>,
+    at: DateTime<Utc>,
+) {
+    case.last_run_at = at;
+    if result.outcome == TestOutcome::Skipped {
+        return;
+    }
+    let same_commit = commit.is_some() && case.last_commit.as_deref() == commit;
+    let rerun_recovery = same_commit && case.last_outcome == TestOutcome::Failed;
+    let mark = match result.outcome {
+        TestOutcome::Failed => 'F',
+        _ if result.retried || rerun_recovery => 'R',
+        _ => 'P',
+    };
+    if result.outcome == TestOutcome::Failed {
+        case.failures += 1;
+        case.last_failure_message = result.message.clone();
+        case.last_failed_at = Some(at);
+    }
+    case.runs += 1;
+    case.last_outcome = result.outcome;
+    case.last_commit = commit.map(String::from);
+    case.history.push(mark);
+    let excess = case.history.len().saturating_sub(HISTORY_LEN);
+    case.history.drain(..excess);
+}
+
+/// Flake events of a history: passes on retry, and failures between two
+/// passing runs.
+pub fn flake_events(history: &str) -> usize {
+    let marks: Vec<char> = history.chars().collect();
+    let retries = marks.iter().filter(|m| **m == 'R').count();
+    let isolated = marks
+        .windows(3)
+        .filter(|w| w[0] != 'F' && w[1] == 'F' && w[2] == 'P')
+        .count();
+    retries + isolated
+}
+
+/// A failed test of an ingested run
+#[derive(Debug, Serialize)]
+pub struct FailedTest {
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Flaky before this run
+    pub known_flaky: bool,
+}
+
+/// Outcome of a test result ingestion
+#[derive(Debug, Serialize)]
+pub struct TestRunIngestion {
+    pub run: TestRunNode,
+    /// Test cases linked to a test function
+    pub linked_tests: usize,
+    /// Failed tests, the first 50
+    pub failed_tests: Vec<FailedTest>,
+    /// Tests this run made flaky
+    pub newly_flaky: Vec<String>,
+}
+
+/// Record a parsed JUnit report as a test run of the project.
+pub async fn ingest_test_results(
+    neo4j: &dyn GraphStore,
+    project: &ProjectNode,
+    cases: &[JUnitCase],
+    commit: Option<&str>,
+    branch: Option<&str>,
+) -> Result<TestRunIngestion> {
+    let now = Utc::now();
+    let merged = merge_cases(cases);
+
+    let synced_paths = neo4j.get_project_file_paths(project.id).await?;
+    let synced: HashSet<&str> = synced_paths.iter().map(String::as_str).collect();
+    let index = SuffixIndex::build(&synced_paths);
+    let roots = super::project_roots(&project.root_path);
+    let spans = neo4j.get_project_function_spans(project.id).await?;
+    let mut by_name: HashMap<&str, Vec<&FunctionSpanRow>> = HashMap::new();
+    for span in &spans {
+        by_name.entry(span.name.as_str()).or_default().push(span);
+    }
+
+    let mut known: HashMap<String, TestCaseNode> = neo4j
+        .get_test_cases(project.id)
+        .await?
+        .into_iter()
+        .map(|c| (c.key.clone(), c))
+        .collect();
+
+    let mut run = TestRunNode {
+        id: Uuid::new_v4(),
+        project_id: project.id,
+        commit: commit.map(String::from),
+        branch: branch.map(String::from),
+        total: merged.len() as u32,
+        passed: 0,
+        failed: 0,
+        skipped: 0,
+        duration_ms: 0,
+        created_at: now,
+    };
+    let mut updated = Vec::with_capacity(merged.len());
+    let mut failed_tests = Vec::new();
+    let mut newly_flaky = Vec::new();
+    for (key, result) in &merged {
+        run.duration_ms += result.duration_ms;
+        match result.outcome {
+            TestOutcome::Passed => run.passed += 1,
+            TestOutcome::Failed => run.failed += 1,
+            TestOutcome::Skipped => run.skipped += 1,
+        }
+
+        let file = result
+            .file
+            .as_deref()
+            .and_then(|f| resolve_path(f, &roots, &synced, &index));
+        let function = link_function(result, file.as_deref(), &by_name);
+        let mut case = known.remove(key).unwrap_or_else(|| TestCaseNode {
+            project_id: project.id,
+            key: key.clone(),
+            classname: result.classname.clone(),
+            name: result.name.clone(),
+            function_id: None,
+            file_path: None,
+            history: String::new(),
+            runs: 0,
+            failures: 0,
+            last_outcome: TestOutcome::Passed,
+            last_commit: None,
+            last_failure_message: None,
+            last_failed_at: None,
+            last_run_at: now,
+        });
+        if let Some(function) = function {
+            case.function_id = Some(function.id.clone());
+            case.file_path = Some(function.file_path.clone());
+        } else if file.is_some() {
+            case.file_path = file;
+        }
+
+        let was_flaky = flake_events(&case.history) > 0;
+        record_outcome(&mut case, result, commit, now);
+        if !was_flaky && flake_events(&case.history) > 0 {
+            newly_flaky.push(key.clone());
+        }
+        if result.outcome == TestOutcome::Failed && failed_tests.len() < MAX_FAILURES_LISTED {
+            failed_tests.push(FailedTest {
+                key: key.clone(),
+                function_id: case.function_id.clone(),
+                message: result.message.clone(),
+                known_flaky: was_flaky,
+            });
+        }
+        updated.push(case);
+    }
+
+    neo4j.upsert_test_cases(&updated).await?;
+    neo4j.create_test_run(&run, MAX_TEST_RUNS).await?;
+    Ok(TestRunIngestion {
+        run,
+        linked_tests: updated.iter().filter(|c| c.function_id.is_some()).count(),
+        failed_tests,
+        newly_flaky,
+    })
+}
+
+/// A flaky test of a project
+#[derive(Debug, Serialize)]
+pub struct FlakyTest {
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<String>,
+    /// Runs in the history (at most [`HISTORY_LEN`])
+    pub recent_runs: usize,
+    pub recent_failures: usize,
+    /// See [`flake_events`]
+    pub flake_events: usize,
+    /// `flake_events / recent_runs`
+    pub flakiness: f64,
+    /// At least [`CHRONIC_FLAKE_EVENTS`] flake events in the history
+    pub chronic: bool,
+    pub history: String,
+    pub last_outcome: TestOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_failure_message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_failed_at: Option<DateTime<Utc>>,
+    /// Gotcha note to create for a chronically flaky test
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_note: Option<CreateNoteRequest>,
+}
+
+/// The test case as a flaky test, None when it is not flaky.
+pub fn flaky_test(case: &TestCaseNode) -> Option<FlakyTest> {
+    let events = flake_events(&case.history);
+    if events == 0 {
+        return None;
+    }
+    let recent_runs = case.history.chars().count();
+    let chronic = events >= CHRONIC_FLAKE_EVENTS;
+    let mut flaky = FlakyTest {
+        key: case.key.clone(),
+        function_id: case.function_id.clone(),
+        file_path: case.file_path.clone(),
+        recent_runs,
+        recent_failures: case.history.chars().filter(|c| *c == 'F').count(),
+        flake_events: events,
+        flakiness: (events as f64 * 100.0 / recent_runs as f64).round() / 100.0,
+        chronic,
+        history: case.history.clone(),
+        last_outcome: case.last_outcome,
+        last_failure_message: case.last_failure_message.clone(),
+        last_failed_at: case.last_failed_at,
+        suggested_note: None,
+    };
+    if chronic {
+        flaky.suggested_note = Some(suggested_note(case, &flaky));
+    }
+    Some(flaky)
+}
+
+fn suggested_note(case: &TestCaseNode, flaky: &FlakyTest) -> CreateNoteRequest {
+    let mut content = format!(
+        "Test `{}` is flaky: it failed {} of its last {} CI runs with {} intermittent failure(s) or pass(es) on retry. Re-run it before treating a failure as a regression, and fix or quarantine it.",
+        case.key, flaky.recent_failures, flaky.recent_runs, flaky.flake_events
+    );
+    if let Some(message) = &case.last_failure_message {
+        content.push_str(&format!("\n\nLast failure: {}", message));
+    }
+    let (scope, anchors) = match (&case.function_id, &case.file_path) {
+        (Some(id), _) => (
+            NoteScope::Function(case.name.clone()),
+            Some(vec![CreateAnchorRequest {
+                entity_type: EntityType::Function,
+                entity_id: id.clone(),
+                signature_hash: None,
+                body_hash: None,
+            }]),
+        ),
+        (None, Some(path)) => (NoteScope::File(path.clone()), None),
+        (None, None) => (NoteScope::Project, None),
+    };
+    CreateNoteRequest {
+        project_id: Some(case.project_id),
+        note_type: NoteType::Gotcha,
+        content,
+        importance: Some(NoteImportance::High),
+        scope: Some(scope),
+        tags: Some(vec!["flaky-test".to_string(), "ci".to_string()]),
+        anchors,
+        assertion_rule: None,
+        run_id: None,
+    }
+}
+
+/// Flaky tests of a project
+#[derive(Debug, Serialize)]
+pub struct FlakyTestReport {
+    /// Test cases with a history
+    pub tests_tracked: usize,
+    /// Most flaky first
+    pub flaky_tests: Vec<FlakyTest>,
+    /// Most recent test runs
+    pub recent_runs: Vec<TestRunNode>,
+}
+
+/// Flaky tests of a project, the `limit` most flaky.
+pub async fn flaky_test_report(
+    neo4j: &dyn GraphStore,
+    project_id: Uuid,
+    limit: usize,
+) -> Result<FlakyTestReport> {
+    let cases = neo4j.get_test_cases(project_id).await?;
+    let mut flaky_tests: Vec<FlakyTest> = cases.iter().filter_map(flaky_test).collect();
+    flaky_tests.sort_by(|a, b| {
+        b.flakiness
+            .total_cmp(&a.flakiness)
+            .then(b.recent_failures.cmp(&a.recent_failures))
+            .then_with(|| a.key.cmp(&b.key))
+    });
+    flaky_tests.truncate(limit);
+    Ok(FlakyTestReport {
+        tests_tracked: cases.len(),
+        flaky_tests,
+        recent_runs: neo4j.list_test_runs(project_id, 10).await?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::{FunctionNode, Visibility};
+
+    const NEXTEST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="nextest-run" tests="4" failures="1" errors="0">
+  <testsuite name="demo" tests="4">
+    <testcase name="parser::tests::test_parse" classname="demo" time="0.012"/>
+    <testcase name="net::tests::test_retry" classname="demo" time="1.5">
+      <failure message="assertion failed: ok&#10;at src/net.rs:10" type="test failure"><![CDATA[thread panicked]]></failure>
+    </testcase>
+    <testcase name="net::tests::test_slow" classname="demo"><skipped/></testcase>
+    <testcase name="net::tests::test_flaky" classname="demo">
+      <flakyFailure message="timed out"/>
+    </testcase>
+  </testsuite>
+</testsuites>"#;
+
+    const PYTEST: &str = r#"<testsuites><testsuite name="pytest">
+<testcase classname="tests.test_api.TestApi" name="test_get[a-1]" file="tests/test_api.py" time="0.1"/>
+<testcase classname="tests.test_api.TestApi" name="test_get[b-2]" file="tests/test_api.py" time="0.2"><error message="ConnectionError"/></testcase>
+<testcase classname="ui" name="renders the header" time="0.1"/>
+</testsuite></testsuites>"#;
+
+    fn case(classname: &str, name: &str, outcome: TestOutcome) -> JUnitCase {
+        JUnitCase {
+            classname: classname.to_string(),
+            name: name.to_string(),
+            file: None,
+            outcome,
+            duration_ms: 0,
+            message: None,
+            retried: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_junit() {
+        let cases = parse_junit(NEXTEST);
+        assert_eq!(cases.len(), 4);
+        assert_eq!(cases[0].key(), "demo::parser::tests::test_parse");
+        assert_eq!(cases[0].duration_ms, 12);
+        assert_eq!(cases[1].outcome, TestOutcome::Failed);
+        assert_eq!(cases[1].message.as_deref(), Some("assertion failed: ok"));
+        assert_eq!(cases[2].outcome, TestOutcome::Skipped);
+        assert_eq!(cases[3].outcome, TestOutcome::Passed);
+        assert!(cases[3].retried);
+
+        let merged = merge_cases(&parse_junit(PYTEST));
+        assert_eq!(merged.len(), 3);
+        let get = &merged["tests.test_api.TestApi::test_get[a-1]"];
+        assert_eq!(get.file.as_deref(), Some("tests/test_api.py"));
+        let failed = &merged["tests.test_api.TestApi::test_get[b-2]"];
+        assert_eq!(failed.outcome, TestOutcome::Failed);
+        assert_eq!(failed.message.as_deref(), Some("ConnectionError"));
+
+        let twice = merge_cases(&[
+            case("demo", "test_x", TestOutcome::Failed),
+            case("demo", "test_x", TestOutcome::Passed),
+        ]);
+        assert_eq!(twice["demo::test_x"].outcome, TestOutcome::Failed);
+        assert!(twice["demo::test_x"].retried);
+    }
+
+    #[test]
+    fn test_function_names_and_linking() {
+        assert_eq!(
+            test_function_name("net::tests::test_retry"),
+            Some("test_retry")
+        );
+        assert_eq!(test_function_name("test_get[a-1]"), Some("test_get"));
+        assert_eq!(
+            test_function_name("com.acme.FooTest.testBar()"),
+            Some("testBar")
+        );
+        assert_eq!(test_function_name("renders the header"), None);
+
+        let span = |file: &str, name: &str| FunctionSpanRow {
+            id: format!("{}:{}:1", file, name),
+            name: name.to_string(),
+            file_path: file.to_string(),
+            line_start: 1,
+            line_end: 5,
+        };
+        let spans = [
+            span("/repo/src/net.rs", "test_retry"),
+            span("/repo/src/parser.rs", "test_parse"),
+            span("/repo/src/lexer.rs", "test_parse"),
+            span("/repo/tests/test_api.py", "test_get"),
+        ];
+        let mut by_name: HashMap<&str, Vec<&FunctionSpanRow>> = HashMap::new();
+        for s in &spans {
+            by_name.entry(s.name.as_str()).or_default().push(s);
+        }
+        let link = |c: &JUnitCase, file: Option<&str>| {
+            link_function(c, file, &by_name).map(|f| f.id.clone())
+        };
+
+        let retry = case("demo", "net::tests::test_retry", TestOutcome::Failed);
+        assert_eq!(
+            link(&retry, None).as_deref(),
+            Some("/repo/src/net.rs:test_retry:1")
+        );
+        // Two candidates, told apart by the module path
+        let parse = case("demo", "parser::tests::test_parse", TestOutcome::Passed);
+        assert_eq!(
+            link(&parse, None).as_deref(),
+            Some("/repo/src/parser.rs:test_parse:1")
+        );
+        let ambiguous = case("demo", "test_parse", TestOutcome::Passed);
+        assert_eq!(link(&ambiguous, None), None);
+        let get = case(
+            "tests.test_api.TestApi",
+            "test_get[a-1]",
+            TestOutcome::Passed,
+        );
+        assert_eq!(
+            link(&get, Some("/repo/tests/test_api.py")).as_deref(),
+            Some("/repo/tests/test_api.py:test_get:1")
+        );
+        assert_eq!(
+            link(&case("demo", "test_missing", TestOutcome::Passed), None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_history_and_flakiness() {
+        let now = Utc::now();
+        let mut tc = TestCaseNode {
+            project_id: Uuid::new_v4(),
+            key: "demo::net::tests::test_retry".to_string(),
+            classname: "demo".to_string(),
+            name: "net::tests::test_retry".to_string(),
+            function_id: None,
+            file_path: None,
+            history: String::new(),
+            runs: 0,
+            failures: 0,
+            last_outcome: TestOutcome::Passed,
+            last_commit: None,
+            last_failure_message: None,
+            last_failed_at: None,
+            last_run_at: now,
+        };
+        let passed = case("demo", "net::tests::test_retry", TestOutcome::Passed);
+        let mut failed = case("demo", "net::tests::test_retry", TestOutcome::Failed);
+        failed.message = Some("timed out".to_string());
+        let skipped = case("demo", "net::tests::test_retry", TestOutcome::Skipped);
+
+        record_outcome(&mut tc, &passed, Some("aaa"), now);
+        record_outcome(&mut tc, &skipped, Some("bbb"), now);
+        record_outcome(&mut tc, &failed, Some("bbb"), now);
+        assert_eq!(tc.history, "PF");
+        assert!(flaky_test(&tc).is_none(), "a single failure is not flaky");
+
+        // Rerun of the failed commit passes
+        record_outcome(&mut tc, &passed, Some("bbb"), now);
+        assert_eq!(tc.history, "PFR");
+        assert_eq!(flake_events(&tc.history), 1);
+        assert_eq!(flake_events("PFFFP"), 0, "a fixed regression is not flaky");
+        let flaky = flaky_test(&tc).unwrap();
+        assert!(!flaky.chronic);
+        assert!(flaky.suggested_note.is_none());
+        assert_eq!(tc.runs, 3);
+        assert_eq!(tc.failures, 1);
+        assert_eq!(tc.last_failure_message.as_deref(), Some("timed out"));
+
+        for _ in 0..2 {
+            record_outcome(&mut tc, &failed, None, now);
+            record_outcome(&mut tc, &passed, None, now);
+        }
+        let flaky = flaky_test(&tc).unwrap();
+        assert!(flaky.chronic);
+        let note = flaky.suggested_note.unwrap();
+        assert_eq!(note.note_type, NoteType::Gotcha);
+        assert!(note.content.contains("demo::net::tests::test_retry"));
+
+        for _ in 0..HISTORY_LEN {
+            record_outcome(&mut tc, &passed, None, now);
+        }
+        assert_eq!(tc.history.len(), HISTORY_LEN);
+        assert!(
+            flaky_test(&tc).is_none(),
+            "flakiness ages out of the history"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ingest_and_report() {
+        let store = MockGraphStore::new();
+        let project = crate::test_helpers::test_project_named("flaky");
+        store.create_project(&project).await.unwrap();
+        store
+            .link_file_to_project("/tmp/flaky/src/net.rs", project.id)
+            .await
+            .unwrap();
+        store
+            .upsert_function(&FunctionNode {
+                name: "test_retry".to_string(),
+                visibility: Visibility::Private,
+                params: vec![],
+                return_type: None,
+                generics: vec![],
+                is_async: false,
+                is_unsafe: false,
+                complexity: 1,
+                file_path: "/tmp/flaky/src/net.rs".to_string(),
+                line_start: 10,
+                line_end: 20,
+                docstring: None,
+            })
+            .await
+            .unwrap();
+
+        let report = |outcome: &str| {
+            format!(
+                r#"<testsuite><testcase classname="demo" name="net::tests::test_retry">{}</testcase></testsuite>"#,
+                outcome
+            )
+        };
+        let mut last = None;
+        for (i, outcome) in ["", "<failure/>", "", "<failure/>", "", "<failure/>", ""]
+            .iter()
+            .enumerate()
+        {
+            let commit = format!("c{}", i);
+            let cases = parse_junit(&report(outcome));
+            last = Some(
+                ingest_test_results(&store, &project, &cases, Some(&commit), Some("main"))
+                    .await
+                    .unwrap(),
+            );
+            if i == 2 {
+                assert_eq!(last.as_ref().unwrap().newly_flaky.len(), 1);
+            }
+        }
+        let last = last.unwrap();
+        assert_eq!(last.linked_tests, 1);
+        assert_eq!(last.run.passed, 1);
+
+        let report = flaky_test_report(&store, project.id, 10).await.unwrap();
+        assert_eq!(report.tests_tracked, 1);
+        assert_eq!(report.recent_runs.len(), 7);
+        let flaky = &report.flaky_tests[0];
+        assert_eq!(
+            flaky.function_id.as_deref(),
+            Some("/tmp/flaky/src/net.rs:test_retry:10")
+        );
+        assert_eq!(flaky.history, "PFPFPFP");
+        assert!(flaky.chronic);
+        let note = flaky.suggested_note.as_ref().unwrap();
+        assert_eq!(
+            note.anchors.as_ref().unwrap()[0].entity_type,
+            EntityType::Function
+        );
+    }
+}
@@ -0,0 +1,493 @@
+//! Workspace topology drift detection.
+//!
+//! Compares the declared `DEPENDS_ON_COMPONENT` edges of a workspace with the
+//! dependencies observed in code:
+//! - **resource imports** — a file of component A's project imports a
+//!   resource owned or implemented by component B's project
+//!   (USES_RESOURCE_SYMBOL edges, see [`super::resource_link_hook`]);
+//! - **HTTP base URLs** — a source file of A's project references
+//!   `://<host>` where `<host>` is B's configured url/host or its service
+//!   name (docker-compose / k8s DNS style).
+//!
+//! Declared edges that can't be checked (the depending component has no
+//! synced project, or the target exposes nothing observable — typically
+//! databases and queues) are reported as `unverified` rather than `stale`.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::neo4j::models::{ComponentDependency, ComponentNode};
+use crate::neo4j::GraphStore;
+
+/// Evidence items kept per edge (the total is still counted)
+const MAX_EVIDENCE_PER_EDGE: usize = 5;
+
+/// Larger files (bundles, fixtures) are not scanned for URLs
+const MAX_SCANNED_FILE_BYTES: u64 = 512 * 1024;
+
+/// Hosts that only identify a component together with a port
+const LOCAL_HOSTS: &[&str] = &["localhost", "127.0.0.1", "0.0.0.0", "[::1]"];
+
+/// How an observed dependency was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvidenceKind {
+    ResourceImport,
+    HttpUrl,
+}
+
+/// One code location showing a component depends on another
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct DependencyEvidence {
+    pub kind: EvidenceKind,
+    pub file_path: String,
+    pub line: Option<u32>,
+    pub detail: String,
+}
+
+/// A component → component edge in the drift report
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DriftEdge {
+    pub from_id: Uuid,
+    pub from_name: String,
+    pub to_id: Uuid,
+    pub to_name: String,
+    /// Declared protocol (`None` for undeclared edges)
+    pub protocol: Option<String>,
+    pub evidence_count: usize,
+    pub evidence: Vec<DependencyEvidence>,
+}
+
+/// Declared vs observed component dependencies of a workspace
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct TopologyDriftReport {
+    /// Declared and observed
+    pub confirmed: Vec<DriftEdge>,
+    /// Observed in code but not declared
+    pub undeclared: Vec<DriftEdge>,
+    /// Declared, checkable, but not observed
+    pub stale: Vec<DriftEdge>,
+    /// Declared but not checkable from code
+    pub unverified: Vec<DriftEdge>,
+    /// Source files scanned for URLs
+    pub files_scanned: usize,
+}
+
+type ObservedEdges = BTreeMap<(Uuid, Uuid), Vec<DependencyEvidence>>;
+
+fn observe(observed: &mut ObservedEdges, from: Uuid, to: Uuid, evidence: DependencyEvidence) {
+    if from != to {
+        observed.entry((from, to)).or_default().push(evidence);
+    }
+}
+
+/// Build the drift report of a workspace.
+pub async fn compute_topology_drift(
+    neo4j: &dyn GraphStore,
+    workspace_id: Uuid,
+) -> anyhow::Result<TopologyDriftReport> {
+    let topology = neo4j.get_workspace_topology(workspace_id).await?;
+    let mappings = neo4j.list_component_project_mappings(workspace_id).await?;
+
+    let mut components_by_project: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for (component_id, project_id) in &mappings {
+        components_by_project
+            .entry(*project_id)
+            .or_default()
+            .push(*component_id);
+    }
+
+    let mut observed = ObservedEdges::new();
+    // Components that can be observed as a dependency target
+    let mut observable: HashSet<Uuid> = HashSet::new();
+
+    // 1. Imports of resources provided by another component's project
+    for resource in neo4j.list_workspace_resources(workspace_id).await? {
+        let mut providers: Vec<Uuid> = resource.project_id.into_iter().collect();
+        for project in neo4j.get_resource_implementers(resource.id).await? {
+            providers.push(project.id);
+        }
+        let provider_components: Vec<Uuid> = providers
+            .iter()
+            .filter_map(|p| components_by_project.get(p))
+            .flatten()
+            .copied()
+            .collect();
+        if provider_components.is_empty() {
+            continue;
+        }
+        observable.extend(&provider_components);
+
+        for link in neo4j.get_resource_symbol_links(resource.id).await? {
+            for from in components_by_project
+                .get(&link.project_id)
+                .into_iter()
+                .flatten()
+            {
+                for to in &provider_components {
+                    observe(
+                        &mut observed,
+                        *from,
+                        *to,
+                        DependencyEvidence {
+                            kind: EvidenceKind::ResourceImport,
+                            file_path: link.file_path.clone(),
+                            line: Some(link.line),
+                            detail: format!("imports {} ({})", link.import_path, resource.name),
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    // 2. References to another component's base URL / service name
+    let hosts: Vec<(Uuid, Vec<String>)> = topology
+        .iter()
+        .map(|(c, _, _)| (c.id, component_hosts(c)))
+        .filter(|(_, h)| !h.is_empty())
+        .collect();
+    observable.extend(hosts.iter().map(|(id, _)| *id));
+
+    let mut files_scanned = 0;
+    // Components whose project has synced files
+    let mut verifiable: HashSet<Uuid> = HashSet::new();
+    for (project_id, from_components) in &components_by_project {
+        let paths = neo4j.get_project_file_paths(*project_id).await?;
+        if !paths.is_empty() {
+            verifiable.extend(from_components);
+        }
+        for path in paths {
+            let Ok(meta) = tokio::fs::metadata(&path).await else {
+                continue;
+            };
+            if !meta.is_file() || meta.len() > MAX_SCANNED_FILE_BYTES {
+                continue;
+            }
+            let Ok(content) = tokio::fs::read_to_string(&path).await else {
+                continue;
+            };
+            files_scanned += 1;
+            let content = content.to_lowercase();
+            for (to, tokens) in &hosts {
+                if from_components.contains(to) {
+                    continue;
+                }
+                if let Some((token, line)) = find_host_reference(&content, tokens) {
+                    for from in from_components {
+                        observe(
+                            &mut observed,
+                            *from,
+                            *to,
+                            DependencyEvidence {
+                                kind: EvidenceKind::HttpUrl,
+                                file_path: path.clone(),
+                                line: Some(line),
+                                detail: format!("references ://{}", token),
+                            },
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    let mut report = classify(&topology, observed, &verifiable, &observable);
+    report.files_scanned = files_scanned;
+    Ok(report)
+}
+
+/// Split declared and observed edges into confirmed / undeclared / stale /
+/// unverified.
+fn classify(
+    topology: &[(ComponentNode, Option<String>, Vec<ComponentDependency>)],
+    mut observed: ObservedEdges,
+    verifiable: &HashSet<Uuid>,
+    observable: &HashSet<Uuid>,
+) -> TopologyDriftReport {
+    let names: HashMap<Uuid, &str> = topology
+        .iter()
+        .map(|(c, _, _)| (c.id, c.name.as_str()))
+        .collect();
+    let edge =
+        |from: Uuid, to: Uuid, protocol: Option<String>, evidence: Vec<DependencyEvidence>| {
+            DriftEdge {
+                from_id: from,
+                from_name: names.get(&from).copied().unwrap_or_default().to_string(),
+                to_id: to,
+                to_name: names.get(&to).copied().unwrap_or_default().to_string(),
+                protocol,
+                evidence_count: evidence.len(),
+                evidence: evidence.into_iter().take(MAX_EVIDENCE_PER_EDGE).collect(),
+            }
+        };
+
+    let mut report = TopologyDriftReport::default();
+    for (_, _, deps) in topology {
+        for dep in deps {
+            let key = (dep.from_id, dep.to_id);
+            let protocol = dep.protocol.clone();
+            if let Some(evidence) = observed.remove(&key) {
+                report
+                    .confirmed
+                    .push(edge(key.0, key.1, protocol, evidence));
+            } else if verifiable.contains(&key.0) && observable.contains(&key.1) {
+                report.stale.push(edge(key.0, key.1, protocol, Vec::new()));
+            } else {
+                report
+                    .unverified
+                    .push(edge(key.0, key.1, protocol, Vec::new()));
+            }
+        }
+    }
+    for ((from, to), evidence) in observed {
+        if names.contains_key(&from) && names.contains_key(&to) {
+            report.undeclared.push(edge(from, to, None, evidence));
+        }
+    }
+
+    for edges in [
+        &mut report.confirmed,
+        &mut report.undeclared,
+        &mut report.stale,
+        &mut report.unverified,
+    ] {
+        edges.sort_by(|a, b| (&a.from_name, &a.to_name).cmp(&(&b.from_name, &b.to_name)));
+    }
+    report
+}
+
+/// Hosts other components may use to reach `component`, lowercased:
+/// `config.url` / `base_url` / `endpoint` (host[:port] part), `config.host`
+/// / `hostname`, and the component name as a service DNS name
+/// (`Billing Service` → `billing-service`). Local hosts are kept only with
+/// a port.
+pub fn component_hosts(component: &ComponentNode) -> Vec<String> {
+    let config = &component.config;
+    let port = config.get("port").and_then(|p| {
+        p.as_u64()
+            .map(|n| n.to_string())
+            .or_else(|| p.as_str().map(String::from))
+    });
+
+    let mut candidates = Vec::new();
+    for key in ["url", "base_url", "endpoint"] {
+        if let Some(host) = config.get(key).and_then(|v| v.as_str()).and_then(host_of) {
+            candidates.push(host);
+        }
+    }
+    for key in ["host", "hostname"] {
+        if let Some(host) = config.get(key).and_then(|v| v.as_str()).and_then(host_of) {
+            if let Some(port) = port.as_ref().filter(|_| !host.contains(':')) {
+                candidates.push(format!("{}:{}", host, port));
+            }
+            candidates.push(host);
+        }
+    }
+    candidates.push(
+        component
+            .name
+            .trim()
+            .to_lowercase()
+            .replace([' ', '_'], "-"),
+    );
+
+    let mut hosts: Vec<String> = Vec::new();
+    for host in candidates {
+        let bare = host.split(':').next().unwrap_or_default();
+        let usable = host.len() >= 3 && (host.contains(':') || !LOCAL_HOSTS.contains(&bare));
+        if usable && !hosts.contains(&host) {
+            hosts.push(host);
+        }
+    }
+    hosts
+}
+
+/// `https://user@billing:8443/v1?x` → `billing:8443`
+fn host_of(value: &str) -> Option<String> {
+    let rest = value.split_once("://").map_or(value, |(_, r)| r);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default().trim();
+    (!host.is_empty()).then(|| host.to_lowercase())
+}
+
+/// First `://<token>` reference in (lowercased) `content` that isn't the
+/// prefix of a longer host name. Returns the token and its 1-based line.
+fn find_host_reference(content: &str, tokens: &[String]) -> Option<(String, u32)> {
+    tokens.iter().find_map(|token| {
+        let needle = format!("://{}", token);
+        content.match_indices(&needle).find_map(|(idx, _)| {
+            let next = content[idx + needle.len()..].chars().next();
+            let continues = next.is_some_and(|c| c.is_alphanumeric() || "-_.".contains(c));
+            (!continues).then(|| {
+                let line = content[..idx].matches('\n').count() as u32 + 1;
+                (token.clone(), line)
+            })
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::{ComponentType, ResourceNode, ResourceSymbolLink, ResourceType};
+    use crate::test_helpers::{test_project_named, test_workspace};
+
+    fn component(workspace_id: Uuid, name: &str, config: serde_json::Value) -> ComponentNode {
+        ComponentNode {
+            id: Uuid::new_v4(),
+            workspace_id,
+            name: name.to_string(),
+            component_type: ComponentType::Service,
+            description: None,
+            runtime: None,
+            config,
+            created_at: chrono::Utc::now(),
+            tags: vec![],
+        }
+    }
+
+    #[test]
+    fn test_component_hosts() {
+        let ws = Uuid::new_v4();
+        let c = component(
+            ws,
+            "Billing Service",
+            serde_json::json!({"url": "https://billing.internal:8443/v1", "host": "localhost", "port": 9000}),
+        );
+        assert_eq!(
+            component_hosts(&c),
+            vec!["billing.internal:8443", "localhost:9000", "billing-service"]
+        );
+
+        let db = component(ws, "db", serde_json::json!({"host": "127.0.0.1"}));
+        assert!(component_hosts(&db).is_empty());
+    }
+
+    #[test]
+    fn test_find_host_reference() {
+        let tokens = vec!["billing-service".to_string()];
+        let src = "const a = \"http://billing-service-v2/x\";\nconst b = `http://billing-service:8080/charge`;\n";
+        assert_eq!(
+            find_host_reference(src, &tokens),
+            Some(("billing-service".to_string(), 2))
+        );
+        assert_eq!(
+            find_host_reference("http://billing-services", &tokens),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compute_topology_drift() {
+        let graph = MockGraphStore::new();
+        let ws = test_workspace();
+        graph.create_workspace(&ws).await.unwrap();
+        let tmp = tempfile::tempdir().unwrap();
+
+        let api = test_project_named("api");
+        let web = test_project_named("web");
+        for p in [&api, &web] {
+            graph.create_project(p).await.unwrap();
+            graph.add_project_to_workspace(ws.id, p.id).await.unwrap();
+        }
+        let api_svc = component(ws.id, "api", serde_json::json!({}));
+        let web_app = component(ws.id, "web", serde_json::json!({}));
+        let payments = component(ws.id, "payments", serde_json::json!({}));
+        let db = component(ws.id, "db", serde_json::json!({}));
+        for c in [&api_svc, &web_app, &payments, &db] {
+            graph.create_component(c).await.unwrap();
+        }
+        graph
+            .map_component_to_project(api_svc.id, api.id)
+            .await
+            .unwrap();
+        graph
+            .map_component_to_project(web_app.id, web.id)
+            .await
+            .unwrap();
+
+        // Declared: web → payments (never referenced), api → db (unobservable)
+        graph
+            .add_component_dependency(web_app.id, payments.id, Some("http".into()), true)
+            .await
+            .unwrap();
+        graph
+            .add_component_dependency(api_svc.id, db.id, None, true)
+            .await
+            .unwrap();
+        // Declared and observed through a resource import
+        graph
+            .add_component_dependency(web_app.id, api_svc.id, Some("grpc".into()), true)
+            .await
+            .unwrap();
+
+        let billing = ResourceNode {
+            id: Uuid::new_v4(),
+            workspace_id: Some(ws.id),
+            project_id: Some(api.id),
+            name: "Billing".to_string(),
+            resource_type: ResourceType::Protobuf,
+            file_path: "billing.proto".to_string(),
+            url: None,
+            format: None,
+            version: None,
+            description: None,
+            created_at: chrono::Utc::now(),
+            updated_at: None,
+            metadata: serde_json::Value::Null,
+        };
+        graph.create_resource(&billing).await.unwrap();
+        graph
+            .replace_resource_symbol_links(
+                web.id,
+                &[ResourceSymbolLink {
+                    resource_id: billing.id,
+                    project_id: web.id,
+                    file_path: "/web/src/pay.ts".to_string(),
+                    import_path: "gen/billing_pb".to_string(),
+                    symbol: "billing_pb".to_string(),
+                    line: 4,
+                }],
+            )
+            .await
+            .unwrap();
+
+        // Undeclared: the api calls back into the web app by service name
+        let client = tmp.path().join("client.rs");
+        std::fs::write(&client, "\nlet url = \"http://web:3000/hooks\";\n").unwrap();
+        let client = client.to_string_lossy().to_string();
+        graph.link_file_to_project(&client, api.id).await.unwrap();
+        graph
+            .link_file_to_project("/web/src/pay.ts", web.id)
+            .await
+            .unwrap();
+
+        let report = compute_topology_drift(&graph, ws.id).await.unwrap();
+        let pairs = |edges: &[DriftEdge]| -> Vec<(String, String)> {
+            edges
+                .iter()
+                .map(|e| (e.from_name.clone(), e.to_name.clone()))
+                .collect()
+        };
+
+        assert_eq!(pairs(&report.confirmed), vec![("web".into(), "api".into())]);
+        assert_eq!(report.confirmed[0].protocol.as_deref(), Some("grpc"));
+        assert_eq!(
+            report.confirmed[0].evidence[0].kind,
+            EvidenceKind::ResourceImport
+        );
+        assert_eq!(
+            pairs(&report.undeclared),
+            vec![("api".into(), "web".into())]
+        );
+        assert_eq!(report.undeclared[0].evidence[0].line, Some(2));
+        assert_eq!(
+            pairs(&report.stale),
+            vec![("web".into(), "payments".into())]
+        );
+        assert_eq!(pairs(&report.unverified), vec![("api".into(), "db".into())]);
+        assert_eq!(report.files_scanned, 1);
+    }
+}
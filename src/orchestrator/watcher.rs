@@ -10,6 +10,7 @@
 //! detected, the watcher finds the longest matching root_path prefix to
 //! determine the correct project context for syncing.
 
+use crate::events::Leadership;
 use anyhow::{Context, Result};
 use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
 use std::collections::{HashMap, HashSet};
@@ -70,6 +71,8 @@ pub struct FileWatcher {
     /// Channel to dynamically add new paths to the running notify watcher.
     /// None if the watcher background task hasn't been started yet.
     add_path_tx: Option<mpsc::Sender<PathBuf>>,
+    /// Changes are only synced while this instance leads
+    leadership: Leadership,
 }
 
 impl FileWatcher {
@@ -81,9 +84,16 @@ impl FileWatcher {
             project_map: Arc::new(RwLock::new(HashMap::new())),
             stop_tx: None,
             add_path_tx: None,
+            leadership: Leadership::always(),
         }
     }
 
+    /// Only sync changes while `leadership` holds. Followers keep their
+    /// projects registered so they can take over without re-registering.
+    pub fn set_leadership(&mut self, leadership: Leadership) {
+        self.leadership = leadership;
+    }
+
     /// Set project context for a single project (legacy compatibility).
     ///
     /// This is equivalent to calling `register_project` with the first
@@ -269,6 +279,7 @@ impl FileWatcher {
         let watched_paths = self.watched_paths.clone();
         let project_map = self.project_map.clone();
         let orchestrator = self.orchestrator.clone();
+        let leadership = self.leadership.clone();
 
         // Spawn the file system watcher
         tokio::spawn(async move {
@@ -369,7 +380,11 @@ impl FileWatcher {
 
                     // ── Collect file events into pending map ─────────────
                     Some((path, event_kind)) = event_rx.recv() => {
-                        if !should_sync_file(&path) {
+                        // The leader syncs; followers drop events
+                        if !leadership.is_leader() {
+                            continue;
+                        }
+                        if !should_sync_file(&path) && !should_index_document(&path) {
                             continue;
                         }
 
@@ -643,6 +658,12 @@ async fn flush_pending_files(
         // debounced by the AnalyticsDebouncer's own quiet period)
         orchestrator.analytics_debouncer().trigger(pid);
 
+        // Summarize again the directories whose files changed
+        orchestrator.spawn_refresh_module_summaries(pid);
+
+        // Re-check the project budgets against the changed code
+        orchestrator.spawn_check_budgets(pid);
+
         // Spawn knowledge link reconstruction in background: link notes + decisions
         // to newly synced files (cross-project notes + decision AFFECTS)
         let neo4j = orchestrator.neo4j_arc();
@@ -683,6 +704,18 @@ async fn flush_pending_files(
         for path in &files {
             let path_str = super::runner::normalize_path(&path.to_string_lossy());
 
+            // Markdown documentation: Document + Section nodes and docs index
+            if crate::parser::markdown::is_markdown_path(path) {
+                match orchestrator.delete_document(&path_str).await {
+                    Ok(()) => deleted += 1,
+                    Err(e) => {
+                        tracing::warn!("Failed to delete document {}: {}", path_str, e);
+                        errors += 1;
+                    }
+                }
+                continue;
+            }
+
             // Remove from Neo4j (File node + all children symbols + relationships)
             if let Err(e) = orchestrator.neo4j().delete_file(&path_str).await {
                 tracing::warn!("Failed to delete {} from Neo4j: {}", path_str, e);
@@ -739,6 +772,12 @@ async fn flush_pending_files(
         // Trigger analytics debouncer (graph changed)
         orchestrator.analytics_debouncer().trigger(pid);
 
+        // Summarize again (or drop) the directories that lost files
+        orchestrator.spawn_refresh_module_summaries(pid);
+
+        // Re-check the project budgets without the deleted files
+        orchestrator.spawn_check_budgets(pid);
+
         // Spawn knowledge link reconstruction in background after deletions too:
         // notes/decisions referencing deleted files won't match, but those
         // referencing remaining files may now need re-anchoring.
@@ -768,6 +807,10 @@ async fn flush_pending_files(
     if !orphans.is_empty() {
         tracing::debug!("Syncing {} orphan file(s) (no project)", orphans.len());
         for path in &orphans {
+            // Documentation is only indexed within a project
+            if crate::parser::markdown::is_markdown_path(path) {
+                continue;
+            }
             if let Err(e) = orchestrator.sync_file_for_project(path, None, None).await {
                 tracing::warn!("Failed to sync orphan {}: {}", path.display(), e);
             }
@@ -781,6 +824,9 @@ async fn flush_pending_files(
             orphan_deletions.len()
         );
         for path in &orphan_deletions {
+            if crate::parser::markdown::is_markdown_path(path) {
+                continue;
+            }
             let path_str = super::runner::normalize_path(&path.to_string_lossy());
             if let Err(e) = orchestrator.neo4j().delete_file(&path_str).await {
                 tracing::warn!("Failed to delete orphan {} from Neo4j: {}", path_str, e);
@@ -815,6 +861,15 @@ async fn resolve_project(
         .map(|(_, ctx)| ctx.clone())
 }
 
+/// Check if a file is Markdown documentation that should be (re)indexed.
+///
+/// Kept separate from [`should_sync_file`]: documents go through
+/// `Orchestrator::sync_document` rather than the tree-sitter pipeline.
+fn should_index_document(path: &Path) -> bool {
+    crate::parser::markdown::is_markdown_path(path)
+        && !super::should_ignore_path(&path.to_string_lossy())
+}
+
 /// Check if a file should be synced based on extension and path
 ///
 /// Supports all 21 extensions matching the main sync engine in runner.rs.
@@ -840,7 +895,9 @@ fn should_sync_file(path: &Path) -> bool {
         "sh", "bash", // Bash
     ];
 
-    if !supported_extensions.contains(&ext) {
+    if !supported_extensions.contains(&ext)
+        && crate::parser::plugins::grammar_for_extension(ext).is_none()
+    {
         return false;
     }
 
@@ -1002,6 +1059,18 @@ async fn handle_project_created(
                         orch.spawn_analyze_project(project_id);
                         // Refresh auto-built feature graphs
                         orch.spawn_refresh_feature_graphs(project_id);
+                        // Link imports to foreign workspace resources
+                        orch.spawn_link_resource_symbols(project_id);
+                        // Record locked packages for the vulnerability audit
+                        orch.spawn_ingest_packages(project_id);
+                        // Link the files to their CODEOWNERS owners
+                        orch.spawn_ingest_code_owners(project_id);
+                        // Summarize the directories whose files changed
+                        orch.spawn_refresh_module_summaries(project_id);
+                        // Check the complexity and size budgets
+                        orch.spawn_check_budgets(project_id);
+                        // Snapshot the public API surface
+                        orch.spawn_record_api_surface(project_id);
                     }
                     Err(e) => {
                         tracing::warn!(
@@ -1044,23 +1113,35 @@ async fn handle_project_deleted(watcher: &Arc<RwLock<FileWatcher>>, event: &Crud
     );
 }
 
-/// Handle a Project::Updated event — re-register if root_path changed.
+/// Handle a Project::Updated event — re-register if root_path changed,
+/// unregister on archive, register again on unarchive.
 async fn handle_project_updated(
     watcher: &Arc<RwLock<FileWatcher>>,
     event: &CrudEvent,
     orchestrator: &Arc<super::Orchestrator>,
 ) {
-    // Only react if root_path was part of the update
-    let new_root_path = match event.payload.get("root_path").and_then(|v| v.as_str()) {
-        Some(rp) => rp.to_string(),
-        None => return, // root_path not changed, nothing to do
-    };
+    // Only react if root_path or the archived flag was part of the update
+    let archived = event.payload.get("archived").and_then(|v| v.as_bool());
+    let new_root_path = event.payload.get("root_path").and_then(|v| v.as_str());
+    if archived.is_none() && new_root_path.is_none() {
+        return; // nothing to do
+    }
 
     let project_id = match event.entity_id.parse::<Uuid>() {
         Ok(id) => id,
         Err(_) => return,
     };
 
+    if archived == Some(true) {
+        let w = watcher.read().await;
+        w.unregister_project(project_id).await;
+        tracing::info!(
+            "Watcher bridge: unregistered project {} on archive",
+            project_id
+        );
+        return;
+    }
+
     // Look up the project to get current slug
     let neo4j = orchestrator.neo4j_arc();
     let project = match neo4j.get_project(project_id).await {
@@ -1082,6 +1163,13 @@ async fn handle_project_updated(
         }
     };
 
+    // Archived projects stay unwatched; unarchived ones only come back if
+    // watching was enabled
+    if project.archived || (archived == Some(false) && !project.watch_enabled) {
+        return;
+    }
+    let new_root_path = new_root_path.unwrap_or(&project.root_path).to_string();
+
     // Unregister old path, register new one
     {
         let w = watcher.read().await;
@@ -1301,6 +1389,16 @@ mod tests {
         assert!(!should_sync_file(Path::new("/project/Dockerfile")));
     }
 
+    #[test]
+    fn test_should_index_markdown_documents() {
+        assert!(should_index_document(Path::new("/project/README.md")));
+        assert!(should_index_document(Path::new("/project/docs/guide.mdx")));
+        assert!(!should_index_document(Path::new("/project/src/main.rs")));
+        assert!(!should_index_document(Path::new(
+            "/project/node_modules/pkg/README.md"
+        )));
+    }
+
     // ── resolve_project tests ─────────────────────────────────────────
 
     #[tokio::test]
@@ -0,0 +1,392 @@
+//! Architecture Decision Record (ADR) files.
+//!
+//! Decisions of a project are mirrored as `docs/adr/NNN-title.md` files in
+//! its repository. [`render_adr`] writes the layout below; [`parse_adr`]
+//! reads it back, along with the common hand-written variants (Nygard's
+//! `## Status` / `## Context` / `## Decision` / `## Consequences` sections,
+//! MADR's `## Considered Options` / `## Decision Outcome`).
+//!
+//! ```markdown
+//! # 7. Use Neo4j for the knowledge graph
+//!
+//! - Status: accepted
+//! - Date: 2026-03-02
+//! - Deciders: alice
+//! - Decision-Id: 9b1c…
+//!
+//! ## Decision
+//! ## Rationale
+//! ## Alternatives considered
+//! ## Chosen option
+//! ```
+//!
+//! The parser is forgiving: unknown sections are ignored and a file without
+//! a `# ` title still yields a decision described by its file name.
+
+use crate::neo4j::models::{DecisionNode, DecisionStatus};
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+/// Directory of the ADR files, relative to the project root
+pub const ADR_DIR: &str = "docs/adr";
+
+/// Length limit of an ADR title (and of the slug in its file name)
+const MAX_TITLE_CHARS: usize = 80;
+const MAX_SLUG_CHARS: usize = 50;
+
+/// Decision read from an ADR file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedAdr {
+    /// Number from the file name (`007-…`) or the title (`# 7. …`)
+    pub number: Option<u32>,
+    pub title: String,
+    pub status: DecisionStatus,
+    pub date: Option<NaiveDate>,
+    pub deciders: Option<String>,
+    /// Id of the graph decision the file was exported from
+    pub decision_id: Option<Uuid>,
+    pub description: String,
+    pub rationale: String,
+    pub alternatives: Vec<String>,
+    pub chosen_option: Option<String>,
+}
+
+/// Whether a file of the ADR directory is a record (not its README, index
+/// or template).
+pub fn is_adr_file(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    lower.ends_with(".md")
+        && lower != "readme.md"
+        && lower != "index.md"
+        && !lower.starts_with("template")
+}
+
+/// Leading number of a name (`0007-use-neo4j.md` → 7)
+fn leading_number(s: &str) -> Option<u32> {
+    let digits: String = s.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+/// Split `ADR-007: Title`, `7. Title` or `0007 - Title` into number and title.
+fn split_title(raw: &str) -> (Option<u32>, String) {
+    let raw = raw.trim();
+    let rest = match raw.get(..3) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("adr") => {
+            raw[3..].trim_start_matches(['-', ' ', '#'])
+        }
+        _ => raw,
+    };
+    if let Some(number) = leading_number(rest) {
+        let title = rest
+            .trim_start_matches(|c: char| c.is_ascii_digit())
+            .trim_start_matches(['.', ':', '-', '—', ' '])
+            .trim();
+        if !title.is_empty() {
+            return (Some(number), title.to_string());
+        }
+    }
+    (None, raw.to_string())
+}
+
+fn parse_status(value: &str) -> DecisionStatus {
+    let word = value
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase();
+    match word.as_str() {
+        "proposed" | "draft" => DecisionStatus::Proposed,
+        "deprecated" | "rejected" => DecisionStatus::Deprecated,
+        "superseded" => DecisionStatus::Superseded,
+        _ => DecisionStatus::Accepted,
+    }
+}
+
+/// `key: value` of a metadata line (`- Status: accepted`, `**Date:** …`)
+fn metadata(line: &str) -> Option<(String, String)> {
+    let line = line
+        .trim()
+        .trim_start_matches(['-', '*', ' '])
+        .replace("**", "");
+    let (key, value) = line.split_once(':')?;
+    let key = key.trim().to_lowercase();
+    if key.is_empty() || (key.contains(char::is_whitespace) && key != "decided by") {
+        return None;
+    }
+    Some((key, value.trim().to_string()))
+}
+
+/// Items of a markdown list (`- a`, `* b`, `1. c`)
+fn list_items(body: &str) -> Vec<String> {
+    body.lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let item = line
+                .strip_prefix("- ")
+                .or_else(|| line.strip_prefix("* "))
+                .or_else(|| {
+                    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+                    (digits > 0).then(|| line[digits..].strip_prefix(". "))?
+                })?;
+            let item = item.trim();
+            (!item.is_empty()).then(|| item.to_string())
+        })
+        .collect()
+}
+
+/// Parse an ADR file. `file_name` provides the number (and a title when the
+/// file has no `# ` heading).
+pub fn parse_adr(file_name: &str, content: &str) -> ParsedAdr {
+    let mut title_line: Option<String> = None;
+    let mut header = String::new();
+    let mut sections: Vec<(String, String)> = Vec::new();
+    let mut in_fence = false;
+
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        if !in_fence {
+            if let Some(heading) = line.strip_prefix("## ") {
+                sections.push((heading.trim().to_lowercase(), String::new()));
+                continue;
+            }
+            if title_line.is_none() && sections.is_empty() {
+                if let Some(heading) = line.strip_prefix("# ") {
+                    title_line = Some(heading.to_string());
+                    continue;
+                }
+            }
+        }
+        match sections.last_mut() {
+            Some((_, body)) => {
+                body.push_str(line);
+                body.push('\n');
+            }
+            None => {
+                header.push_str(line);
+                header.push('\n');
+            }
+        }
+    }
+
+    let stem = file_name.trim_end_matches(".md");
+    let (title_number, title) = match title_line {
+        Some(line) => split_title(&line),
+        None => split_title(&stem.replace(['-', '_'], " ")),
+    };
+
+    let mut adr = ParsedAdr {
+        number: leading_number(file_name).or(title_number),
+        title,
+        status: DecisionStatus::Accepted,
+        date: None,
+        deciders: None,
+        decision_id: None,
+        description: String::new(),
+        rationale: String::new(),
+        alternatives: Vec::new(),
+        chosen_option: None,
+    };
+
+    for (key, value) in header.lines().filter_map(metadata) {
+        match key.as_str() {
+            "status" => adr.status = parse_status(&value),
+            "date" => adr.date = NaiveDate::parse_from_str(&value, "%Y-%m-%d").ok(),
+            "deciders" | "decided by" | "decided-by" | "author" | "authors" => {
+                adr.deciders = Some(value).filter(|v| !v.is_empty())
+            }
+            "decision-id" | "decision_id" => adr.decision_id = value.parse().ok(),
+            _ => {}
+        }
+    }
+
+    let mut rationale = Vec::new();
+    for (name, body) in &sections {
+        let text = body.trim();
+        match name.as_str() {
+            "status" => adr.status = parse_status(text),
+            "decision" | "decision outcome" => adr.description = text.to_string(),
+            "context" | "context and problem statement" | "rationale" | "consequences" => {
+                if !text.is_empty() {
+                    rationale.push(text.to_string());
+                }
+            }
+            "alternatives" | "alternatives considered" | "considered options" | "options" => {
+                adr.alternatives = list_items(text)
+            }
+            "chosen option" => adr.chosen_option = Some(text.to_string()).filter(|t| !t.is_empty()),
+            _ => {}
+        }
+    }
+    adr.rationale = rationale.join("\n\n");
+    if adr.description.is_empty() {
+        adr.description = adr.title.clone();
+    }
+    adr
+}
+
+/// Title of the ADR of a decision: the first line of its description.
+pub fn adr_title(decision: &DecisionNode) -> String {
+    let first = decision
+        .description
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or("Decision")
+        .trim_end_matches('.');
+    if first.chars().count() > MAX_TITLE_CHARS {
+        let cut: String = first.chars().take(MAX_TITLE_CHARS - 1).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        first.to_string()
+    }
+}
+
+/// File name of the ADR of a decision (`007-use-neo4j.md`)
+pub fn adr_file_name(number: u32, decision: &DecisionNode) -> String {
+    let mut slug = String::new();
+    for word in adr_title(decision)
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        if slug.len() + word.len() + 1 > MAX_SLUG_CHARS {
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(word);
+    }
+    if slug.is_empty() {
+        slug.push_str("decision");
+    }
+    format!("{:03}-{}.md", number, slug)
+}
+
+/// Render a decision as an ADR file.
+pub fn render_adr(number: u32, decision: &DecisionNode) -> String {
+    let mut md = format!("# {}. {}\n\n", number, adr_title(decision));
+    md.push_str(&format!("- Status: {}\n", decision.status));
+    md.push_str(&format!(
+        "- Date: {}\n",
+        decision.decided_at.format("%Y-%m-%d")
+    ));
+    if !decision.decided_by.is_empty() {
+        md.push_str(&format!("- Deciders: {}\n", decision.decided_by));
+    }
+    md.push_str(&format!("- Decision-Id: {}\n", decision.id));
+
+    md.push_str(&format!(
+        "\n## Decision\n\n{}\n",
+        decision.description.trim()
+    ));
+    if !decision.rationale.trim().is_empty() {
+        md.push_str(&format!(
+            "\n## Rationale\n\n{}\n",
+            decision.rationale.trim()
+        ));
+    }
+    if !decision.alternatives.is_empty() {
+        md.push_str("\n## Alternatives considered\n\n");
+        for alternative in &decision.alternatives {
+            md.push_str(&format!("- {}\n", alternative));
+        }
+    }
+    if let Some(chosen) = decision.chosen_option.as_deref().filter(|c| !c.is_empty()) {
+        md.push_str(&format!("\n## Chosen option\n\n{}\n", chosen));
+    }
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn decision() -> DecisionNode {
+        DecisionNode {
+            id: Uuid::new_v4(),
+            description: "Use Neo4j for the knowledge graph.\n\nAll code entities live there."
+                .to_string(),
+            rationale: "Relationships are first-class.".to_string(),
+            alternatives: vec![
+                "PostgreSQL".to_string(),
+                "SQLite + recursive CTEs".to_string(),
+            ],
+            chosen_option: Some("Neo4j".to_string()),
+            decided_by: "alice".to_string(),
+            decided_at: chrono::Utc.with_ymd_and_hms(2026, 3, 2, 10, 0, 0).unwrap(),
+            status: DecisionStatus::Accepted,
+            embedding: None,
+            embedding_model: None,
+            scar_intensity: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_file_names() {
+        assert!(is_adr_file("0001-record-decisions.md"));
+        assert!(!is_adr_file("README.md"));
+        assert!(!is_adr_file("template.md"));
+        assert!(!is_adr_file("diagram.png"));
+        assert_eq!(
+            adr_file_name(7, &decision()),
+            "007-use-neo4j-for-the-knowledge-graph.md"
+        );
+    }
+
+    #[test]
+    fn test_render_parse_roundtrip() {
+        let d = decision();
+        let md = render_adr(7, &d);
+        assert!(md.starts_with("# 7. Use Neo4j for the knowledge graph\n\n- Status: accepted\n"));
+
+        let adr = parse_adr("007-use-neo4j-for-the-knowledge-graph.md", &md);
+        assert_eq!(adr.number, Some(7));
+        assert_eq!(adr.title, "Use Neo4j for the knowledge graph");
+        assert_eq!(adr.decision_id, Some(d.id));
+        assert_eq!(adr.status, DecisionStatus::Accepted);
+        assert_eq!(adr.date, NaiveDate::from_ymd_opt(2026, 3, 2));
+        assert_eq!(adr.deciders.as_deref(), Some("alice"));
+        assert_eq!(adr.description, d.description);
+        assert_eq!(adr.rationale, d.rationale);
+        assert_eq!(adr.alternatives, d.alternatives);
+        assert_eq!(adr.chosen_option, d.chosen_option);
+    }
+
+    #[test]
+    fn test_parse_nygard_adr() {
+        let md = "# ADR-012: Record architecture decisions\n\n\
+                  Date: 2024-05-01\n\n\
+                  ## Status\n\nSuperseded by ADR-013\n\n\
+                  ## Context\n\nWe need to remember why.\n\n\
+                  ## Decision\n\nWe will use ADRs.\n\n\
+                  ```markdown\n## Not a section\n```\n\n\
+                  ## Consequences\n\nMore files to maintain.\n";
+        let adr = parse_adr("record-architecture-decisions.md", md);
+        assert_eq!(adr.number, Some(12));
+        assert_eq!(adr.title, "Record architecture decisions");
+        assert_eq!(adr.status, DecisionStatus::Superseded);
+        assert_eq!(adr.decision_id, None);
+        assert!(adr.description.starts_with("We will use ADRs."));
+        assert!(adr.description.contains("## Not a section"));
+        assert_eq!(
+            adr.rationale,
+            "We need to remember why.\n\nMore files to maintain."
+        );
+    }
+
+    #[test]
+    fn test_parse_madr_and_untitled() {
+        let md = "* **Status:** proposed\n\n## Considered Options\n\n1. Kafka\n2. NATS\n";
+        let adr = parse_adr("0003-message_bus.md", md);
+        assert_eq!(adr.number, Some(3));
+        assert_eq!(adr.title, "message bus");
+        assert_eq!(adr.description, "message bus");
+        assert_eq!(adr.status, DecisionStatus::Proposed);
+        assert_eq!(adr.alternatives, vec!["Kafka", "NATS"]);
+    }
+}
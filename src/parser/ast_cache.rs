@@ -137,6 +137,7 @@ mod tests {
             path: path.to_string(),
             language: "rust".to_string(),
             hash: hash.to_string(),
+            line_count: 0,
             functions: vec![],
             structs: vec![],
             traits: vec![],
@@ -145,6 +146,8 @@ mod tests {
             impl_blocks: vec![],
             function_calls: vec![],
             symbols: vec![],
+            function_fingerprints: vec![],
+            encoding: None,
         }
     }
 
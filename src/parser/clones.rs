@@ -0,0 +1,399 @@
+//! Near-duplicate function detection.
+//!
+//! Each function body is reduced to a token stream — comments dropped, string
+//! and number literals collapsed to placeholders — and its 5-token shingles
+//! are summarized into a 64-value MinHash signature at parse time. The share
+//! of equal values between two signatures estimates the Jaccard similarity of
+//! the bodies' shingle sets, so clones are found without keeping source text:
+//! locality-sensitive hashing over signature bands yields candidate pairs,
+//! which are linked when the estimate reaches the threshold and merged into
+//! clone groups.
+
+use crate::neo4j::models::{FunctionFingerprint, FunctionFingerprintRow, FunctionNode};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// Number of MinHash values per signature
+pub const SIGNATURE_SIZE: usize = 64;
+
+/// Tokens per shingle
+const SHINGLE_SIZE: usize = 5;
+
+/// Bodies with fewer tokens are not fingerprinted (accessors, one-liners)
+pub const MIN_CLONE_TOKENS: usize = 40;
+
+/// Signature values per LSH band (16 bands of 4)
+const LSH_ROWS: usize = 4;
+
+/// Estimated similarity from which two functions count as clones
+pub const DEFAULT_CLONE_THRESHOLD: f64 = 0.85;
+
+// ============================================================================
+// Fingerprinting
+// ============================================================================
+
+/// Split source into tokens: identifiers and keywords verbatim, numbers as
+/// `0`, string/char literals as `""`, every other symbol on its own.
+/// `//` and `/* */` comments are skipped.
+fn tokenize(source: &str) -> Vec<&str> {
+    let bytes = source.as_bytes();
+    let is_word = |b: u8| b.is_ascii_alphanumeric() || b == b'_' || b >= 0x80;
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        if c.is_ascii_whitespace() {
+            i += 1;
+        } else if c == b'/' && bytes.get(i + 1) == Some(&b'/') {
+            while i < bytes.len() && bytes[i] != b'\n' {
+                i += 1;
+            }
+        } else if c == b'/' && bytes.get(i + 1) == Some(&b'*') {
+            i = source[i + 2..]
+                .find("*/")
+                .map_or(bytes.len(), |end| i + 2 + end + 2);
+        } else if c.is_ascii_digit() {
+            while i < bytes.len() && (is_word(bytes[i]) || bytes[i] == b'.') {
+                i += 1;
+            }
+            tokens.push("0");
+        } else if is_word(c) {
+            let start = i;
+            while i < bytes.len() && is_word(bytes[i]) {
+                i += 1;
+            }
+            tokens.push(&source[start..i]);
+        } else if c == b'"' || c == b'\'' || c == b'`' {
+            i += 1;
+            while i < bytes.len() && bytes[i] != c && bytes[i] != b'\n' {
+                i += if bytes[i] == b'\\' { 2 } else { 1 };
+            }
+            i += 1;
+            tokens.push("\"\"");
+        } else {
+            tokens.push(&source[i..i + 1]);
+            i += 1;
+        }
+    }
+    tokens
+}
+
+/// FNV-1a over a shingle, with a separator byte between tokens
+fn hash_shingle(tokens: &[&str]) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325u64;
+    for token in tokens {
+        for &b in token.as_bytes().iter().chain(std::iter::once(&0xff)) {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+/// splitmix64 finalizer, used to derive one hash function per signature slot
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// Token count and MinHash signature of a body, `None` below
+/// [`MIN_CLONE_TOKENS`].
+pub fn body_signature(body: &str) -> Option<(u32, Vec<u64>)> {
+    let tokens = tokenize(body);
+    if tokens.len() < MIN_CLONE_TOKENS {
+        return None;
+    }
+    let seeds: [u64; SIGNATURE_SIZE] = std::array::from_fn(|i| mix(i as u64 + 1));
+    let mut signature = vec![u64::MAX; SIGNATURE_SIZE];
+    for shingle in tokens.windows(SHINGLE_SIZE) {
+        let hash = hash_shingle(shingle);
+        for (slot, seed) in signature.iter_mut().zip(seeds) {
+            *slot = (*slot).min(mix(hash ^ seed));
+        }
+    }
+    Some((tokens.len() as u32, signature))
+}
+
+/// Fingerprint every function of a parsed file large enough to matter.
+pub fn fingerprint_functions(
+    functions: &[FunctionNode],
+    content: &str,
+) -> Vec<FunctionFingerprint> {
+    let lines: Vec<&str> = content.lines().collect();
+    functions
+        .iter()
+        .filter_map(|func| {
+            let start = func.line_start.saturating_sub(1) as usize;
+            let end = (func.line_end as usize).min(lines.len());
+            if start >= end {
+                return None;
+            }
+            let (token_count, minhash) = body_signature(&lines[start..end].join("\n"))?;
+            Some(FunctionFingerprint {
+                function_id: format!("{}:{}:{}", func.file_path, func.name, func.line_start),
+                token_count,
+                minhash,
+            })
+        })
+        .collect()
+}
+
+/// Share of equal values between two signatures (0.0 when sizes differ)
+pub fn estimated_similarity(a: &[u64], b: &[u64]) -> f64 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    a.iter().zip(b).filter(|(x, y)| x == y).count() as f64 / a.len() as f64
+}
+
+// ============================================================================
+// Clone groups
+// ============================================================================
+
+/// A function belonging to a clone group
+#[derive(Debug, Clone, Serialize)]
+pub struct CloneMember {
+    pub id: String,
+    pub name: String,
+    pub file_path: String,
+    pub line_start: u32,
+    pub line_end: u32,
+    pub token_count: u32,
+}
+
+/// Functions whose bodies are near-duplicates of each other
+#[derive(Debug, Clone, Serialize)]
+pub struct CloneGroup {
+    /// Lowest estimated similarity among the pairs linking the group
+    pub similarity: f64,
+    /// Tokens saved if the group were folded into its largest member
+    pub duplicated_tokens: u32,
+    pub members: Vec<CloneMember>,
+}
+
+fn find_root(parent: &mut [usize], mut x: usize) -> usize {
+    while parent[x] != x {
+        parent[x] = parent[parent[x]];
+        x = parent[x];
+    }
+    x
+}
+
+/// Two functions of the same file whose line ranges overlap (nested
+/// closures, inner functions) are never clones of each other.
+fn overlaps(a: &FunctionFingerprintRow, b: &FunctionFingerprintRow) -> bool {
+    a.file_path == b.file_path && a.line_start <= b.line_end && b.line_start <= a.line_end
+}
+
+/// Group fingerprinted functions whose estimated similarity reaches
+/// `threshold`, largest duplication first.
+pub fn find_clone_groups(rows: &[FunctionFingerprintRow], threshold: f64) -> Vec<CloneGroup> {
+    let rows: Vec<&FunctionFingerprintRow> = rows
+        .iter()
+        .filter(|r| r.minhash.len() == SIGNATURE_SIZE)
+        .collect();
+
+    // Functions sharing a whole band are candidates; BTreeMap keeps the
+    // linking order (and so the reported similarity) deterministic.
+    let mut buckets: BTreeMap<(usize, &[u64]), Vec<usize>> = BTreeMap::new();
+    for (idx, row) in rows.iter().enumerate() {
+        for (band, values) in row.minhash.chunks(LSH_ROWS).enumerate() {
+            buckets.entry((band, values)).or_default().push(idx);
+        }
+    }
+
+    let mut parent: Vec<usize> = (0..rows.len()).collect();
+    let mut links: Vec<(usize, f64)> = Vec::new();
+    for members in buckets.values().filter(|m| m.len() > 1) {
+        for (k, &a) in members.iter().enumerate() {
+            for &b in &members[k + 1..] {
+                let (root_a, root_b) = (find_root(&mut parent, a), find_root(&mut parent, b));
+                if root_a == root_b || overlaps(rows[a], rows[b]) {
+                    continue;
+                }
+                let similarity = estimated_similarity(&rows[a].minhash, &rows[b].minhash);
+                if similarity >= threshold {
+                    parent[root_a] = root_b;
+                    links.push((a, similarity));
+                }
+            }
+        }
+    }
+
+    let mut grouped: HashMap<usize, (f64, Vec<usize>)> = HashMap::new();
+    for idx in 0..rows.len() {
+        let root = find_root(&mut parent, idx);
+        grouped.entry(root).or_insert((1.0, Vec::new())).1.push(idx);
+    }
+    for (idx, similarity) in links {
+        let root = find_root(&mut parent, idx);
+        if let Some(group) = grouped.get_mut(&root) {
+            group.0 = group.0.min(similarity);
+        }
+    }
+
+    let mut groups: Vec<CloneGroup> = grouped
+        .into_values()
+        .filter(|(_, members)| members.len() > 1)
+        .map(|(similarity, members)| {
+            let mut members: Vec<CloneMember> = members
+                .into_iter()
+                .map(|idx| CloneMember {
+                    id: rows[idx].id.clone(),
+                    name: rows[idx].name.clone(),
+                    file_path: rows[idx].file_path.clone(),
+                    line_start: rows[idx].line_start,
+                    line_end: rows[idx].line_end,
+                    token_count: rows[idx].token_count,
+                })
+                .collect();
+            members.sort_by(|a, b| {
+                a.file_path
+                    .cmp(&b.file_path)
+                    .then(a.line_start.cmp(&b.line_start))
+            });
+            let total: u32 = members.iter().map(|m| m.token_count).sum();
+            let largest = members.iter().map(|m| m.token_count).max().unwrap_or(0);
+            CloneGroup {
+                similarity,
+                duplicated_tokens: total - largest,
+                members,
+            }
+        })
+        .collect();
+    groups.sort_by(|a, b| {
+        b.duplicated_tokens
+            .cmp(&a.duplicated_tokens)
+            .then_with(|| a.members[0].id.cmp(&b.members[0].id))
+    });
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::models::Visibility;
+
+    const BODY: &str = r#"fn apply_discount(order: &mut Order, rate: f64) -> Result<()> {
+    // Orders below the minimum keep their price
+    if order.total < 100.0 {
+        return Ok(());
+    }
+    let discount = order.total * rate;
+    order.total -= discount;
+    order.notes.push(format!("discount {}", discount));
+    tracing::info!("applied discount to {}", order.id);
+    Ok(())
+}"#;
+
+    fn row(id: &str, file_path: &str, line_start: u32, body: &str) -> FunctionFingerprintRow {
+        let (token_count, minhash) = body_signature(body).unwrap();
+        FunctionFingerprintRow {
+            id: id.to_string(),
+            name: id.to_string(),
+            file_path: file_path.to_string(),
+            line_start,
+            line_end: line_start + 10,
+            token_count,
+            minhash,
+        }
+    }
+
+    #[test]
+    fn test_tokenize_normalizes_literals_and_drops_comments() {
+        let tokens = tokenize("let x = 42; // answer\nlet s = \"hi \\\" there\"; /* c */ y");
+        assert_eq!(
+            tokens,
+            vec!["let", "x", "=", "0", ";", "let", "s", "=", "\"\"", ";", "y"]
+        );
+    }
+
+    #[test]
+    fn test_body_signature_skips_short_bodies() {
+        assert!(body_signature("fn id(&self) -> u64 { self.id }").is_none());
+        let (tokens, signature) = body_signature(BODY).unwrap();
+        assert!(tokens as usize >= MIN_CLONE_TOKENS);
+        assert_eq!(signature.len(), SIGNATURE_SIZE);
+    }
+
+    const UNRELATED: &str = r#"fn render(rows: &[Row]) -> String {
+    let mut out = String::new();
+    for row in rows {
+        out.push_str(&row.label);
+        out.push('\t');
+        out.push_str(&row.value.to_string());
+        out.push('\n');
+    }
+    out
+}"#;
+
+    #[test]
+    fn test_literal_changes_keep_signature() {
+        let edited = BODY
+            .replace("100.0", "250.0")
+            .replace("discount {}", "rebate {}")
+            .replace("// Orders below", "// Small orders");
+        let a = body_signature(BODY).unwrap().1;
+        let b = body_signature(&edited).unwrap().1;
+        assert_eq!(estimated_similarity(&a, &b), 1.0);
+
+        let c = body_signature(UNRELATED).unwrap().1;
+        assert!(estimated_similarity(&a, &c) < 0.3);
+    }
+
+    #[test]
+    fn test_fingerprint_functions_uses_line_ranges() {
+        let content = format!("use std::fmt;\n\n{}\n", BODY);
+        let func = |name: &str, line_start, line_end| FunctionNode {
+            name: name.to_string(),
+            visibility: Visibility::Public,
+            params: vec![],
+            return_type: None,
+            generics: vec![],
+            is_async: false,
+            is_unsafe: false,
+            complexity: 1,
+            file_path: "src/a.rs".to_string(),
+            line_start,
+            line_end,
+            docstring: None,
+        };
+        let fps = fingerprint_functions(
+            &[func("apply_discount", 3, 13), func("tiny", 1, 1)],
+            &content,
+        );
+        assert_eq!(fps.len(), 1);
+        assert_eq!(fps[0].function_id, "src/a.rs:apply_discount:3");
+        assert_eq!(fps[0].minhash, body_signature(BODY).unwrap().1);
+    }
+
+    #[test]
+    fn test_find_clone_groups() {
+        let renamed = BODY.replace("rate", "pct");
+        let rows = vec![
+            row("b", "src/b.rs", 1, BODY),
+            row("a", "src/a.rs", 20, BODY),
+            row("c", "src/c.rs", 1, &renamed),
+            row("d", "src/d.rs", 1, UNRELATED),
+        ];
+
+        let groups = find_clone_groups(&rows, 0.5);
+        assert_eq!(groups.len(), 1);
+        let ids: Vec<&str> = groups[0].members.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+        assert!(groups[0].similarity >= 0.5);
+        assert!(groups[0].duplicated_tokens >= 2 * rows[0].token_count - 1);
+
+        assert!(find_clone_groups(&rows, 1.01).is_empty());
+    }
+
+    #[test]
+    fn test_find_clone_groups_ignores_overlapping_functions() {
+        let rows = vec![
+            row("outer", "src/a.rs", 1, BODY),
+            row("inner", "src/a.rs", 3, BODY),
+        ];
+        assert!(find_clone_groups(&rows, DEFAULT_CLONE_THRESHOLD).is_empty());
+    }
+}
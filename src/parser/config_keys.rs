@@ -0,0 +1,794 @@
+//! Configuration key extraction and cross-referencing.
+//!
+//! Reads the keys a project's config files define — `.env` variables, YAML,
+//! TOML and JSON leaves as dotted paths (`server.port`) — and finds the lines
+//! of code naming them, so "where is `MEILISEARCH_URL` consumed" becomes a
+//! graph lookup.
+//!
+//! A reference is a string literal whose content is exactly a known key
+//! (`env::var("MEILISEARCH_URL")`, `config.get("server.port")`), a
+//! `process.env.NAME`-style access or a `$NAME` / `${NAME}` interpolation.
+//! Values are never kept: `.env` files hold secrets.
+//!
+//! All parsers are line-based and forgiving; a malformed file yields the
+//! keys read before the error instead of failing the sync.
+
+use crate::neo4j::models::{ConfigKeyKind, ConfigKeyLocation, ConfigKeyNode};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::Path;
+use uuid::Uuid;
+
+/// References kept per key; a key read from hundreds of places is a
+/// framework constant more than a config lookup.
+pub const MAX_REFERENCES_PER_KEY: usize = 50;
+
+/// Format of a configuration file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Dotenv,
+    Yaml,
+    Toml,
+    Json,
+}
+
+/// Lockfiles and manifests that look like config but define no settings
+const NON_CONFIG_FILES: &[&str] = &[
+    "package-lock.json",
+    "pnpm-lock.yaml",
+    "composer.lock",
+    "tsconfig.json",
+    "jsconfig.json",
+];
+
+/// Format of a config file from its name (`.env`, `.env.production`,
+/// `prod.env`, `*.yml`, `*.yaml`, `*.toml`, `*.json`), `None` otherwise.
+pub fn config_format(path: &Path) -> Option<ConfigFormat> {
+    let name = path.file_name()?.to_str()?.to_lowercase();
+    if NON_CONFIG_FILES.contains(&name.as_str()) {
+        return None;
+    }
+    if name == ".env" || name.starts_with(".env.") || name.ends_with(".env") {
+        return Some(ConfigFormat::Dotenv);
+    }
+    match name.rsplit_once('.')?.1 {
+        "yml" | "yaml" => Some(ConfigFormat::Yaml),
+        "toml" => Some(ConfigFormat::Toml),
+        "json" => Some(ConfigFormat::Json),
+        _ => None,
+    }
+}
+
+/// A key defined by a config file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigDefinition {
+    pub key: String,
+    pub kind: ConfigKeyKind,
+    /// 1-based
+    pub line: u32,
+}
+
+impl ConfigDefinition {
+    fn new(key: impl Into<String>, kind: ConfigKeyKind, line: usize) -> Self {
+        Self {
+            key: key.into(),
+            kind,
+            line: line as u32,
+        }
+    }
+}
+
+/// Whether `name` looks like an environment variable (`PORT`, `DATABASE_URL`)
+pub fn is_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_uppercase())
+        && name.len() >= 2
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn unquote(key: &str) -> &str {
+    let key = key.trim();
+    key.strip_prefix('"')
+        .and_then(|k| k.strip_suffix('"'))
+        .or_else(|| key.strip_prefix('\'').and_then(|k| k.strip_suffix('\'')))
+        .unwrap_or(key)
+}
+
+/// Add a leaf: its dotted path, and an env definition if the leaf name looks
+/// like an environment variable.
+fn push_leaf(out: &mut Vec<ConfigDefinition>, path: &[String], line: usize) {
+    let Some(leaf) = path.last() else {
+        return;
+    };
+    if is_env_name(leaf) {
+        out.push(ConfigDefinition::new(
+            leaf.clone(),
+            ConfigKeyKind::Env,
+            line,
+        ));
+    }
+    if path.len() > 1 || !is_env_name(leaf) {
+        out.push(ConfigDefinition::new(
+            path.join("."),
+            ConfigKeyKind::Path,
+            line,
+        ));
+    }
+}
+
+/// Parse the keys a config file defines.
+pub fn parse_config(format: ConfigFormat, content: &str) -> Vec<ConfigDefinition> {
+    match format {
+        ConfigFormat::Dotenv => parse_dotenv(content),
+        ConfigFormat::Yaml => parse_yaml(content),
+        ConfigFormat::Toml => parse_toml(content),
+        ConfigFormat::Json => parse_json(content),
+    }
+}
+
+/// `KEY=value` and `export KEY=value` lines.
+fn parse_dotenv(content: &str) -> Vec<ConfigDefinition> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let line = line.trim();
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, _) = line.split_once('=')?;
+            let key = key.trim();
+            (!key.is_empty() && !key.starts_with('#') && key.chars().all(is_ident_char))
+                .then(|| ConfigDefinition::new(key, ConfigKeyKind::Env, i + 1))
+        })
+        .collect()
+}
+
+/// `key:` at the start of a YAML line, if any, with the rest of the line
+fn yaml_key(line: &str) -> Option<(&str, &str)> {
+    let (key, rest) = if let Some(quoted) = line.strip_prefix('"') {
+        let end = quoted.find('"')?;
+        (&quoted[..end], quoted[end + 1..].strip_prefix(':')?)
+    } else if let Some(quoted) = line.strip_prefix('\'') {
+        let end = quoted.find('\'')?;
+        (&quoted[..end], quoted[end + 1..].strip_prefix(':')?)
+    } else {
+        let colon = line
+            .find(": ")
+            .or_else(|| line.strip_suffix(':').map(|k| k.len()))?;
+        (&line[..colon], &line[colon + 1..])
+    };
+    let valid = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| is_ident_char(c) || matches!(c, '-' | '.' | '/'));
+    valid.then_some((key, rest.trim()))
+}
+
+/// Mappings as dotted paths, by indentation. Sequences add no path segment;
+/// `- KEY=value` items (compose `environment:` lists) define env keys.
+fn parse_yaml(content: &str) -> Vec<ConfigDefinition> {
+    let mut out = Vec::new();
+    // (indent, key) of the enclosing mappings
+    let mut stack: Vec<(usize, String)> = Vec::new();
+    // Indent of the key owning a `|` / `>` block scalar being skipped
+    let mut block_indent: Option<usize> = None;
+
+    for (i, raw) in content.lines().enumerate() {
+        let trimmed = raw.trim_start();
+        let mut indent = raw.len() - trimmed.len();
+        if let Some(owner) = block_indent {
+            if trimmed.is_empty() || indent > owner {
+                continue;
+            }
+            block_indent = None;
+        }
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("---") {
+            continue;
+        }
+
+        let mut item = trimmed;
+        if let Some(rest) = item
+            .strip_prefix("- ")
+            .or_else(|| (item == "-").then_some(""))
+        {
+            indent += 2;
+            item = rest.trim_start();
+            if let Some((key, _)) = item.split_once('=') {
+                if is_env_name(key.trim()) && !item.contains(": ") {
+                    out.push(ConfigDefinition::new(key.trim(), ConfigKeyKind::Env, i + 1));
+                    continue;
+                }
+            }
+        }
+
+        let Some((key, value)) = yaml_key(item) else {
+            continue;
+        };
+        while stack.last().is_some_and(|(d, _)| *d >= indent) {
+            stack.pop();
+        }
+        stack.push((indent, key.to_string()));
+
+        let value = value.split(" #").next().unwrap_or("").trim();
+        if value.starts_with('|') || value.starts_with('>') {
+            block_indent = Some(indent);
+        }
+        if !value.is_empty() {
+            let path: Vec<String> = stack.iter().map(|(_, k)| k.clone()).collect();
+            push_leaf(&mut out, &path, i + 1);
+        }
+    }
+    out
+}
+
+/// Net `[` minus `]` outside strings
+fn bracket_balance(s: &str) -> i32 {
+    let mut balance = 0;
+    let mut quote = None;
+    for c in s.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' => quote = Some(c),
+                '[' => balance += 1,
+                ']' => balance -= 1,
+                '#' => break,
+                _ => {}
+            },
+        }
+    }
+    balance
+}
+
+/// `[table]`, `[[array.of.tables]]` and `key = value` lines, skipping the
+/// continuation lines of multi-line strings and arrays.
+fn parse_toml(content: &str) -> Vec<ConfigDefinition> {
+    let mut out = Vec::new();
+    let mut table: Vec<String> = Vec::new();
+    // Closing delimiter of the multi-line string being skipped
+    let mut in_string: Option<&str> = None;
+    let mut open_brackets = 0;
+
+    for (i, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if let Some(delim) = in_string {
+            if line.contains(delim) {
+                in_string = None;
+            }
+            continue;
+        }
+        if open_brackets > 0 {
+            open_brackets += bracket_balance(line);
+            continue;
+        }
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with('[') {
+            let header = line.split('#').next().unwrap_or("").trim();
+            let name = header.trim_start_matches('[').trim_end_matches(']');
+            table = name.split('.').map(|k| unquote(k).to_string()).collect();
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        let mut path = table.clone();
+        path.extend(key.split('.').map(|k| unquote(k).to_string()));
+        if path.iter().any(|k| k.is_empty()) {
+            continue;
+        }
+        push_leaf(&mut out, &path, i + 1);
+
+        for delim in ["\"\"\"", "'''"] {
+            if let Some(rest) = value.strip_prefix(delim) {
+                if !rest.contains(delim) {
+                    in_string = Some(delim);
+                }
+            }
+        }
+        if value.starts_with('[') {
+            open_brackets = bracket_balance(value).max(0);
+        }
+    }
+    out
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum JsonFrame {
+    Object,
+    Array,
+}
+
+/// Object leaves as dotted paths, from a character scan that tolerates
+/// comments and trailing commas (`tsconfig`-style JSON). Arrays are leaves;
+/// keys of the objects they hold extend the array's path.
+fn parse_json(content: &str) -> Vec<ConfigDefinition> {
+    let mut out = Vec::new();
+    let chars: Vec<char> = content.chars().collect();
+    // Key of each enclosing object; `None` for arrays and the root
+    let mut frames: Vec<(JsonFrame, Option<String>)> = Vec::new();
+    let mut pending_key: Option<(String, usize)> = None;
+    let mut line = 1;
+    let mut pos = 0;
+
+    let path_of = |frames: &[(JsonFrame, Option<String>)], key: &str| -> Vec<String> {
+        frames
+            .iter()
+            .filter_map(|(_, k)| k.clone())
+            .chain(std::iter::once(key.to_string()))
+            .collect()
+    };
+
+    while pos < chars.len() {
+        let c = chars[pos];
+        match c {
+            '\n' => line += 1,
+            '/' if chars.get(pos + 1) == Some(&'/') => {
+                while pos < chars.len() && chars[pos] != '\n' {
+                    pos += 1;
+                }
+                continue;
+            }
+            '/' if chars.get(pos + 1) == Some(&'*') => {
+                pos += 2;
+                while pos < chars.len() && !(chars[pos] == '*' && chars.get(pos + 1) == Some(&'/'))
+                {
+                    if chars[pos] == '\n' {
+                        line += 1;
+                    }
+                    pos += 1;
+                }
+                pos += 2;
+                continue;
+            }
+            '"' => {
+                let start_line = line;
+                let mut s = String::new();
+                pos += 1;
+                while pos < chars.len() && chars[pos] != '"' {
+                    if chars[pos] == '\\' {
+                        pos += 1;
+                    }
+                    if let Some(&ch) = chars.get(pos) {
+                        if ch == '\n' {
+                            line += 1;
+                        }
+                        s.push(ch);
+                    }
+                    pos += 1;
+                }
+                let in_object = frames.last().is_some_and(|(f, _)| *f == JsonFrame::Object);
+                let next = chars[(pos + 1).min(chars.len())..]
+                    .iter()
+                    .find(|ch| !ch.is_whitespace());
+                if in_object && next == Some(&':') {
+                    pending_key = Some((s, start_line));
+                } else if let Some((key, key_line)) = pending_key.take() {
+                    push_leaf(&mut out, &path_of(&frames, &key), key_line);
+                }
+            }
+            '{' | '[' => {
+                if let (Some((key, key_line)), '[') = (&pending_key, c) {
+                    push_leaf(&mut out, &path_of(&frames, key), *key_line);
+                }
+                let frame = if c == '{' {
+                    JsonFrame::Object
+                } else {
+                    JsonFrame::Array
+                };
+                frames.push((frame, pending_key.take().map(|(k, _)| k)));
+            }
+            '}' | ']' => {
+                if let Some((key, key_line)) = pending_key.take() {
+                    push_leaf(&mut out, &path_of(&frames, &key), key_line);
+                }
+                frames.pop();
+            }
+            ',' => {
+                if let Some((key, key_line)) = pending_key.take() {
+                    push_leaf(&mut out, &path_of(&frames, &key), key_line);
+                }
+            }
+            _ => {}
+        }
+        pos += 1;
+    }
+    out
+}
+
+/// Identifier starting at byte `start` of `line`, if any
+fn ident_at(line: &str, start: usize) -> Option<&str> {
+    let rest = line.get(start..)?;
+    let len = rest.find(|c: char| !is_ident_char(c)).unwrap_or(rest.len());
+    (len > 0).then(|| &rest[..len])
+}
+
+/// Names interpolated on one line: `$NAME`, `${NAME}`, `${NAME:-default}`.
+fn interpolations(line: &str) -> Vec<&str> {
+    line.match_indices('$')
+        .filter_map(|(i, _)| ident_at(line, i + 1 + usize::from(line[i + 1..].starts_with('{'))))
+        .collect()
+}
+
+/// Names read on one line through interpolation or `env.NAME`
+/// (`process.env.NAME`, `import.meta.env.NAME`).
+fn env_accesses(line: &str) -> Vec<&str> {
+    let dotted = line
+        .match_indices("env.")
+        .filter(|(i, _)| !matches!(line[..*i].chars().next_back(), Some(c) if is_ident_char(c)))
+        .filter_map(|(i, _)| ident_at(line, i + 4));
+    interpolations(line).into_iter().chain(dotted).collect()
+}
+
+/// Contents of the single-line string literals of a line (`"…"`, `'…'`,
+/// `` `…` ``).
+fn string_literals(line: &str) -> Vec<String> {
+    let mut literals = Vec::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if !matches!(c, '"' | '\'' | '`') {
+            continue;
+        }
+        let mut literal = String::new();
+        let mut closed = false;
+        while let Some(ch) = chars.next() {
+            if ch == '\\' {
+                chars.next();
+                literal.push('\\');
+            } else if ch == c {
+                closed = true;
+                break;
+            } else {
+                literal.push(ch);
+            }
+        }
+        if closed {
+            literals.push(literal);
+        }
+    }
+    literals
+}
+
+/// Lines of `content` naming one of `keys`, as `(key, line)`.
+pub fn find_references(content: &str, keys: &HashSet<&str>) -> Vec<(String, u32)> {
+    let mut refs = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let mut found: BTreeSet<String> = BTreeSet::new();
+        for literal in string_literals(line) {
+            if keys.contains(literal.as_str()) {
+                found.insert(literal);
+            }
+        }
+        for name in env_accesses(line) {
+            if keys.contains(name) {
+                found.insert(name.to_string());
+            }
+        }
+        refs.extend(found.into_iter().map(|key| (key, (i + 1) as u32)));
+    }
+    refs
+}
+
+/// Node of `key`, created if missing; an env definition wins over a path
+/// of the same name.
+fn key_entry<'a>(
+    keys: &'a mut BTreeMap<String, ConfigKeyNode>,
+    project_id: Uuid,
+    key: &str,
+    kind: ConfigKeyKind,
+) -> &'a mut ConfigKeyNode {
+    let node = keys
+        .entry(key.to_string())
+        .or_insert_with(|| ConfigKeyNode {
+            key: key.to_string(),
+            project_id,
+            kind,
+            defined_in: Vec::new(),
+            referenced_by: Vec::new(),
+        });
+    if kind == ConfigKeyKind::Env {
+        node.kind = ConfigKeyKind::Env;
+    }
+    node
+}
+
+/// Cross-reference the keys defined by `configs` with the files naming
+/// them. Both are `(path, content)`; `configs` are the files
+/// [`config_format`] recognises.
+///
+/// Environment variables are kept even when nothing reads them (an unused
+/// setting is worth knowing about), and so are variables only interpolated
+/// by config files (`${DATABASE_URL}` in a compose file). Dotted paths are
+/// kept only when referenced: every leaf of every JSON file is noise
+/// otherwise. Keys are sorted.
+pub fn build_config_keys(
+    project_id: Uuid,
+    configs: &[(String, String)],
+    sources: &[(String, String)],
+) -> Vec<ConfigKeyNode> {
+    let mut keys: BTreeMap<String, ConfigKeyNode> = BTreeMap::new();
+
+    for (path, content) in configs {
+        let Some(format) = config_format(Path::new(path)) else {
+            continue;
+        };
+        for def in parse_config(format, content) {
+            key_entry(&mut keys, project_id, &def.key, def.kind)
+                .defined_in
+                .push(ConfigKeyLocation {
+                    file_path: path.clone(),
+                    line: def.line,
+                });
+        }
+        for (i, line) in content.lines().enumerate() {
+            for name in interpolations(line).into_iter().filter(|n| is_env_name(n)) {
+                key_entry(&mut keys, project_id, name, ConfigKeyKind::Env)
+                    .referenced_by
+                    .push(ConfigKeyLocation {
+                        file_path: path.clone(),
+                        line: (i + 1) as u32,
+                    });
+            }
+        }
+    }
+
+    let known: HashSet<String> = keys.keys().cloned().collect();
+    let known: HashSet<&str> = known.iter().map(String::as_str).collect();
+    for (path, content) in sources {
+        for (key, line) in find_references(content, &known) {
+            if let Some(node) = keys.get_mut(&key) {
+                node.referenced_by.push(ConfigKeyLocation {
+                    file_path: path.clone(),
+                    line,
+                });
+            }
+        }
+    }
+
+    keys.into_values()
+        .filter(|node| node.kind == ConfigKeyKind::Env || !node.referenced_by.is_empty())
+        .map(|mut node| {
+            node.defined_in.sort();
+            node.defined_in.dedup();
+            node.referenced_by.sort();
+            node.referenced_by.dedup();
+            node.referenced_by.truncate(MAX_REFERENCES_PER_KEY);
+            node
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys_of(defs: &[ConfigDefinition]) -> Vec<(&str, ConfigKeyKind, u32)> {
+        defs.iter()
+            .map(|d| (d.key.as_str(), d.kind, d.line))
+            .collect()
+    }
+
+    #[test]
+    fn test_config_format() {
+        assert_eq!(config_format(Path::new(".env")), Some(ConfigFormat::Dotenv));
+        assert_eq!(
+            config_format(Path::new("deploy/.env.production")),
+            Some(ConfigFormat::Dotenv)
+        );
+        assert_eq!(
+            config_format(Path::new("prod.env")),
+            Some(ConfigFormat::Dotenv)
+        );
+        assert_eq!(
+            config_format(Path::new("config/app.yaml")),
+            Some(ConfigFormat::Yaml)
+        );
+        assert_eq!(
+            config_format(Path::new("Cargo.toml")),
+            Some(ConfigFormat::Toml)
+        );
+        assert_eq!(
+            config_format(Path::new("settings.json")),
+            Some(ConfigFormat::Json)
+        );
+        assert_eq!(config_format(Path::new("package-lock.json")), None);
+        assert_eq!(config_format(Path::new("src/main.rs")), None);
+    }
+
+    #[test]
+    fn test_parse_dotenv() {
+        let defs = parse_config(
+            ConfigFormat::Dotenv,
+            "# comment\nMEILISEARCH_URL=http://localhost:7700\nexport NEO4J_PASSWORD=secret\n\nnot a key\n",
+        );
+        assert_eq!(
+            keys_of(&defs),
+            vec![
+                ("MEILISEARCH_URL", ConfigKeyKind::Env, 2),
+                ("NEO4J_PASSWORD", ConfigKeyKind::Env, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_yaml() {
+        let content = r#"server:
+  port: 8080  # default
+  host: "0.0.0.0"
+description: |
+  not: a key
+services:
+  api:
+    environment:
+      - MEILISEARCH_URL=${MEILISEARCH_URL:-http://meili:7700}
+      - RUST_LOG=info
+  worker:
+    environment:
+      NEO4J_URI: bolt://neo4j:7687
+"#;
+        let defs = parse_config(ConfigFormat::Yaml, content);
+        assert_eq!(
+            keys_of(&defs),
+            vec![
+                ("server.port", ConfigKeyKind::Path, 2),
+                ("server.host", ConfigKeyKind::Path, 3),
+                ("description", ConfigKeyKind::Path, 4),
+                ("MEILISEARCH_URL", ConfigKeyKind::Env, 9),
+                ("RUST_LOG", ConfigKeyKind::Env, 10),
+                ("NEO4J_URI", ConfigKeyKind::Env, 13),
+                (
+                    "services.worker.environment.NEO4J_URI",
+                    ConfigKeyKind::Path,
+                    13
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_toml() {
+        let content = r#"title = "app"
+[server]
+port = 8080
+description = """
+fake = 1
+"""
+[database.pool]
+max_size = 10
+hosts = [
+  "a",
+]
+[env]
+DATABASE_URL = "postgres://localhost/app"
+"#;
+        let defs = parse_config(ConfigFormat::Toml, content);
+        assert_eq!(
+            keys_of(&defs),
+            vec![
+                ("title", ConfigKeyKind::Path, 1),
+                ("server.port", ConfigKeyKind::Path, 3),
+                ("server.description", ConfigKeyKind::Path, 4),
+                ("database.pool.max_size", ConfigKeyKind::Path, 8),
+                ("database.pool.hosts", ConfigKeyKind::Path, 9),
+                ("DATABASE_URL", ConfigKeyKind::Env, 13),
+                ("env.DATABASE_URL", ConfigKeyKind::Path, 13),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let content = r#"{
+  // comment
+  "server": {
+    "port": 8080,
+    "tags": ["a", "b"]
+  },
+  "LOG_LEVEL": "debug",
+  "nested": { "deep": { "flag": true } }
+}"#;
+        let defs = parse_config(ConfigFormat::Json, content);
+        assert_eq!(
+            keys_of(&defs),
+            vec![
+                ("server.port", ConfigKeyKind::Path, 4),
+                ("server.tags", ConfigKeyKind::Path, 5),
+                ("LOG_LEVEL", ConfigKeyKind::Env, 7),
+                ("nested.deep.flag", ConfigKeyKind::Path, 8),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_references() {
+        let keys: HashSet<&str> = ["MEILISEARCH_URL", "server.port", "PORT"]
+            .into_iter()
+            .collect();
+        let content = r#"let url = std::env::var("MEILISEARCH_URL")?;
+const port = process.env.PORT;
+let p = cfg.get("server.port");
+let s = "MEILISEARCH_URL is required"; // not exact
+"#;
+        assert_eq!(
+            find_references(content, &keys),
+            vec![
+                ("MEILISEARCH_URL".to_string(), 1),
+                ("PORT".to_string(), 2),
+                ("server.port".to_string(), 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_config_keys() {
+        let pid = Uuid::new_v4();
+        let configs = vec![
+            (
+                "/repo/.env".to_string(),
+                "MEILISEARCH_URL=http://localhost:7700\nUNUSED_FLAG=1\n".to_string(),
+            ),
+            (
+                "/repo/docker-compose.yml".to_string(),
+                "services:\n  api:\n    image: app\n    environment:\n      DATABASE_URL: ${DATABASE_URL}\n      SEARCH_URL: ${MEILISEARCH_URL}\n".to_string(),
+            ),
+            (
+                "/repo/config.json".to_string(),
+                "{\"server\": {\"port\": 8080}, \"ignored\": 1}".to_string(),
+            ),
+        ];
+        let sources = vec![(
+            "/repo/src/main.rs".to_string(),
+            "let url = env::var(\"MEILISEARCH_URL\");\nlet port = cfg.get(\"server.port\");\n"
+                .to_string(),
+        )];
+
+        let keys = build_config_keys(pid, &configs, &sources);
+        let names: Vec<&str> = keys.iter().map(|k| k.key.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "DATABASE_URL",
+                "MEILISEARCH_URL",
+                "SEARCH_URL",
+                "UNUSED_FLAG",
+                "server.port"
+            ]
+        );
+
+        let meili = &keys[1];
+        assert_eq!(meili.kind, ConfigKeyKind::Env);
+        assert_eq!(
+            meili.defined_in,
+            vec![ConfigKeyLocation {
+                file_path: "/repo/.env".to_string(),
+                line: 1
+            }]
+        );
+        assert_eq!(
+            meili.referenced_by,
+            vec![
+                ConfigKeyLocation {
+                    file_path: "/repo/docker-compose.yml".to_string(),
+                    line: 6
+                },
+                ConfigKeyLocation {
+                    file_path: "/repo/src/main.rs".to_string(),
+                    line: 1
+                },
+            ]
+        );
+
+        assert_eq!(keys[0].defined_in.len(), 1);
+        assert_eq!(keys[0].referenced_by.len(), 1);
+        assert!(keys[3].referenced_by.is_empty());
+        assert_eq!(keys[4].kind, ConfigKeyKind::Path);
+        assert_eq!(keys[4].referenced_by[0].line, 2);
+    }
+}
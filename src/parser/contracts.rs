@@ -0,0 +1,1031 @@
+//! Contract parsers for workspace resources.
+//!
+//! Extracts the elements a shared contract defines — OpenAPI operations and
+//! component schemas, protobuf services, rpcs, messages and enums — as
+//! [`ResourceElementNode`]s. Each element carries a normalized `signature`
+//! so two versions of a contract can be diffed element by element
+//! ([`diff_elements`]).
+//!
+//! Both parsers are deliberately shallow: they read the structure agents ask
+//! about ("which endpoints does this contract define", "which fields does
+//! this message have") and ignore the rest (examples, options, extensions).
+
+use crate::neo4j::models::{
+    ResourceElementChange, ResourceElementDiff, ResourceElementKind, ResourceElementNode,
+    ResourceType,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use serde_yaml::Value;
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+/// Spec formats the contract parsers understand
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContractFormat {
+    /// OpenAPI 3.x or Swagger 2.0, YAML or JSON
+    OpenApi,
+    /// Protocol buffers IDL (proto2/proto3)
+    Protobuf,
+}
+
+impl ContractFormat {
+    /// Detect the format from the resource type, its declared format and the
+    /// spec file extension. `None` when the resource isn't a parseable spec.
+    pub fn detect(
+        resource_type: &ResourceType,
+        format: Option<&str>,
+        file_path: &str,
+    ) -> Option<Self> {
+        let ext = std::path::Path::new(file_path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default()
+            .to_lowercase();
+        let format = format.unwrap_or_default().to_lowercase();
+
+        if *resource_type == ResourceType::Protobuf || ext == "proto" || format == "protobuf" {
+            return Some(Self::Protobuf);
+        }
+        let spec_ext = matches!(ext.as_str(), "yaml" | "yml" | "json");
+        if spec_ext
+            && (*resource_type == ResourceType::ApiContract
+                || format.contains("openapi")
+                || format.contains("swagger"))
+        {
+            return Some(Self::OpenApi);
+        }
+        None
+    }
+}
+
+/// Parse a spec file into the elements it defines, sorted by (kind, name).
+pub fn parse_contract(
+    resource_id: Uuid,
+    format: ContractFormat,
+    content: &str,
+) -> Result<Vec<ResourceElementNode>> {
+    let mut elements = match format {
+        ContractFormat::OpenApi => parse_openapi(resource_id, content)?,
+        ContractFormat::Protobuf => parse_proto(resource_id, content)?,
+    };
+    elements.sort_by(|a, b| (a.kind, &a.name).cmp(&(b.kind, &b.name)));
+    elements.dedup_by(|a, b| a.kind == b.kind && a.name == b.name);
+    Ok(elements)
+}
+
+/// Element-level diff from `old` to `new`, keyed by (kind, name).
+pub fn diff_elements(
+    old: &[ResourceElementNode],
+    new: &[ResourceElementNode],
+) -> ResourceElementDiff {
+    let old_by_key: BTreeMap<_, _> = old.iter().map(|e| ((e.kind, e.name.as_str()), e)).collect();
+    let new_by_key: BTreeMap<_, _> = new.iter().map(|e| ((e.kind, e.name.as_str()), e)).collect();
+
+    let mut diff = ResourceElementDiff::default();
+    for (key, after) in &new_by_key {
+        match old_by_key.get(key) {
+            None => diff.added.push((*after).clone()),
+            Some(before) if before.signature != after.signature => {
+                diff.changed.push(ResourceElementChange {
+                    kind: after.kind,
+                    name: after.name.clone(),
+                    before: before.signature.clone(),
+                    after: after.signature.clone(),
+                });
+            }
+            Some(_) => {}
+        }
+    }
+    for (key, before) in &old_by_key {
+        if !new_by_key.contains_key(key) {
+            diff.removed.push((*before).clone());
+        }
+    }
+    diff
+}
+
+// ============================================================================
+// OpenAPI / Swagger
+// ============================================================================
+
+const HTTP_METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+fn parse_openapi(resource_id: Uuid, content: &str) -> Result<Vec<ResourceElementNode>> {
+    let doc: Value = serde_yaml::from_str(content).context("Invalid OpenAPI document")?;
+    if doc.get("openapi").is_none() && doc.get("swagger").is_none() {
+        bail!("Not an OpenAPI document: missing `openapi` or `swagger` version");
+    }
+    let lines: Vec<&str> = content.lines().collect();
+    let mut elements = Vec::new();
+
+    if let Some(paths) = doc.get("paths").and_then(Value::as_mapping) {
+        let paths_line = find_key_line(&lines, "paths", 0);
+        for (path, item) in paths {
+            let Some(path) = key_str(path) else {
+                continue;
+            };
+            let path_line = paths_line.and_then(|l| find_key_line(&lines, &path, l));
+            let shared_params = item.get("parameters");
+            for method in HTTP_METHODS {
+                let Some(op) = item.get(*method) else {
+                    continue;
+                };
+                let summary = op
+                    .get("summary")
+                    .or_else(|| op.get("operationId"))
+                    .and_then(Value::as_str)
+                    .map(String::from);
+                elements.push(ResourceElementNode {
+                    resource_id,
+                    kind: ResourceElementKind::Operation,
+                    name: format!("{} {}", method.to_uppercase(), path),
+                    summary,
+                    signature: operation_signature(op, shared_params),
+                    line: path_line
+                        .and_then(|l| find_key_line(&lines, method, l))
+                        .map(|l| l as u32 + 1),
+                });
+            }
+        }
+    }
+
+    // OpenAPI 3: components.schemas — Swagger 2: definitions
+    let (schemas, section) = match doc.get("components").and_then(|c| c.get("schemas")) {
+        Some(s) => (Some(s), "schemas"),
+        None => (doc.get("definitions"), "definitions"),
+    };
+    if let Some(schemas) = schemas.and_then(Value::as_mapping) {
+        let section_line = find_key_line(&lines, section, 0);
+        for (name, schema) in schemas {
+            let Some(name) = key_str(name) else {
+                continue;
+            };
+            elements.push(ResourceElementNode {
+                resource_id,
+                kind: ResourceElementKind::Schema,
+                summary: schema
+                    .get("description")
+                    .or_else(|| schema.get("title"))
+                    .and_then(Value::as_str)
+                    .map(|s| s.trim().to_string()),
+                signature: schema_signature(schema),
+                line: section_line
+                    .and_then(|l| find_key_line(&lines, &name, l))
+                    .map(|l| l as u32 + 1),
+                name,
+            });
+        }
+    }
+
+    Ok(elements)
+}
+
+/// Mapping keys may be numbers (`200:`) or strings.
+fn key_str(key: &Value) -> Option<String> {
+    match key {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// First line at or after `from` that declares `key` (YAML `key:`, quoted,
+/// or JSON `"key":`).
+fn find_key_line(lines: &[&str], key: &str, from: usize) -> Option<usize> {
+    let candidates = [
+        format!("{}:", key),
+        format!("\"{}\":", key),
+        format!("'{}':", key),
+    ];
+    lines
+        .iter()
+        .enumerate()
+        .skip(from)
+        .find(|(_, line)| {
+            let trimmed = line.trim_start().trim_start_matches("- ");
+            candidates.iter().any(|c| trimmed.starts_with(c.as_str()))
+        })
+        .map(|(i, _)| i)
+}
+
+/// `params(path:id!, query:limit) body(application/json: User) responses(200: User, 404)`
+fn operation_signature(op: &Value, shared_params: Option<&Value>) -> String {
+    let mut params = Vec::new();
+    let mut body = Vec::new();
+    for list in [shared_params, op.get("parameters")].into_iter().flatten() {
+        for param in list.as_sequence().into_iter().flatten() {
+            if let Some(reference) = param.get("$ref").and_then(Value::as_str) {
+                params.push(format!("ref:{}", ref_name(reference)));
+                continue;
+            }
+            let location = param.get("in").and_then(Value::as_str).unwrap_or("?");
+            // Swagger 2 request bodies are `in: body` parameters
+            if location == "body" {
+                body.push(schema_repr(param.get("schema")));
+                continue;
+            }
+            let name = param.get("name").and_then(Value::as_str).unwrap_or("?");
+            let required = param
+                .get("required")
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            params.push(format!(
+                "{}:{}{}",
+                location,
+                name,
+                if required { "!" } else { "" }
+            ));
+        }
+    }
+    params.sort();
+    params.dedup();
+
+    if let Some(content) = op
+        .get("requestBody")
+        .and_then(|b| b.get("content"))
+        .and_then(Value::as_mapping)
+    {
+        for (media, spec) in content {
+            body.push(format!(
+                "{}: {}",
+                key_str(media).unwrap_or_default(),
+                schema_repr(spec.get("schema"))
+            ));
+        }
+    } else if let Some(reference) = op
+        .get("requestBody")
+        .and_then(|b| b.get("$ref"))
+        .and_then(Value::as_str)
+    {
+        body.push(ref_name(reference).to_string());
+    }
+    body.sort();
+
+    let mut responses = Vec::new();
+    for (code, response) in op
+        .get("responses")
+        .and_then(Value::as_mapping)
+        .into_iter()
+        .flatten()
+    {
+        let code = key_str(code).unwrap_or_default();
+        let schema = response
+            .get("content")
+            .and_then(|c| {
+                c.get("application/json")
+                    .or_else(|| c.as_mapping().and_then(|m| m.values().next()))
+            })
+            .and_then(|m| m.get("schema"))
+            .or_else(|| response.get("schema"));
+        responses.push(
+            match (schema, response.get("$ref").and_then(Value::as_str)) {
+                (Some(_), _) => format!("{}: {}", code, schema_repr(schema)),
+                (None, Some(reference)) => format!("{}: {}", code, ref_name(reference)),
+                (None, None) => code,
+            },
+        );
+    }
+    responses.sort();
+
+    format!(
+        "params({}) body({}) responses({})",
+        params.join(", "),
+        body.join(", "),
+        responses.join(", ")
+    )
+}
+
+/// `{id!: string(uuid), tags: [string], owner: User}` or the plain type
+fn schema_signature(schema: &Value) -> String {
+    if let Some(values) = schema.get("enum").and_then(Value::as_sequence) {
+        let values: Vec<String> = values.iter().filter_map(key_str).collect();
+        return format!("enum({})", values.join(", "));
+    }
+    let Some(properties) = schema.get("properties").and_then(Value::as_mapping) else {
+        return schema_repr(Some(schema));
+    };
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_sequence)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+    let mut fields: Vec<String> = properties
+        .iter()
+        .filter_map(|(name, prop)| {
+            let name = key_str(name)?;
+            let bang = if required.contains(&name.as_str()) {
+                "!"
+            } else {
+                ""
+            };
+            Some(format!("{}{}: {}", name, bang, schema_repr(Some(prop))))
+        })
+        .collect();
+    fields.sort();
+    format!("{{{}}}", fields.join(", "))
+}
+
+/// Short type of a schema: `User`, `[string]`, `integer(int64)`, `oneOf(A|B)`
+fn schema_repr(schema: Option<&Value>) -> String {
+    let Some(schema) = schema else {
+        return "-".to_string();
+    };
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        return ref_name(reference).to_string();
+    }
+    for combinator in ["oneOf", "anyOf", "allOf"] {
+        if let Some(variants) = schema.get(combinator).and_then(Value::as_sequence) {
+            let variants: Vec<String> = variants.iter().map(|v| schema_repr(Some(v))).collect();
+            return format!("{}({})", combinator, variants.join("|"));
+        }
+    }
+    match schema.get("type").and_then(Value::as_str) {
+        Some("array") => format!("[{}]", schema_repr(schema.get("items"))),
+        Some(t) => match schema.get("format").and_then(Value::as_str) {
+            Some(f) => format!("{}({})", t, f),
+            None => t.to_string(),
+        },
+        None if schema.get("properties").is_some() => "object".to_string(),
+        None => "any".to_string(),
+    }
+}
+
+/// `#/components/schemas/User` → `User`
+fn ref_name(reference: &str) -> &str {
+    reference.rsplit('/').next().unwrap_or(reference)
+}
+
+// ============================================================================
+// Protocol buffers
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    text: String,
+    line: u32,
+    /// `//` comment lines directly above the token
+    comment: Option<String>,
+}
+
+/// Split a .proto file into identifiers, punctuation and string literals.
+/// Comments are dropped, except that a run of `//` lines is attached to the
+/// token that follows it.
+fn tokenize_proto(content: &str) -> Vec<Token> {
+    let chars: Vec<char> = content.chars().collect();
+    let mut tokens = Vec::new();
+    let mut comment: Vec<String> = Vec::new();
+    let mut line = 1u32;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            line += 1;
+            i += 1;
+        } else if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            let start = i + 2;
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            comment.push(
+                chars[start..i]
+                    .iter()
+                    .collect::<String>()
+                    .trim()
+                    .to_string(),
+            );
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                i += 1;
+            }
+            i += 2;
+        } else if c == '"' || c == '\'' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i += 1;
+            let end = i.min(chars.len());
+            tokens.push(Token {
+                text: chars[start..end].iter().collect(),
+                line,
+                comment: None,
+            });
+            comment.clear();
+        } else if c.is_alphanumeric() || c == '_' || c == '.' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+            {
+                i += 1;
+            }
+            tokens.push(Token {
+                text: chars[start..i].iter().collect(),
+                line,
+                comment: (!comment.is_empty()).then(|| comment.join(" ")),
+            });
+            comment.clear();
+        } else {
+            tokens.push(Token {
+                text: c.to_string(),
+                line,
+                comment: None,
+            });
+            comment.clear();
+            i += 1;
+        }
+    }
+    tokens
+}
+
+struct ProtoParser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    resource_id: Uuid,
+    elements: Vec<ResourceElementNode>,
+}
+
+impl ProtoParser<'_> {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(|t| t.text.as_str())
+    }
+
+    fn next(&mut self) -> Result<&Token> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .ok_or_else(|| anyhow!("Unexpected end of proto file"))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, text: &str) -> Result<()> {
+        let token = self.next()?;
+        if token.text != text {
+            bail!(
+                "line {}: expected `{}`, found `{}`",
+                token.line,
+                text,
+                token.text
+            );
+        }
+        Ok(())
+    }
+
+    /// Skip to the end of the current statement: a `;` or a balanced `{…}`.
+    fn skip_statement(&mut self) -> Result<()> {
+        let mut depth = 0usize;
+        loop {
+            let text = self.next()?.text.as_str();
+            match text {
+                "{" => depth += 1,
+                "}" => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        return Ok(());
+                    }
+                }
+                ";" if depth == 0 => return Ok(()),
+                _ => {}
+            }
+        }
+    }
+
+    /// Consume a declaration keyword (`message`, `rpc`, …), returning the
+    /// comment attached to it.
+    fn keyword(&mut self, keyword: &str) -> Result<Option<String>> {
+        let comment = self.tokens.get(self.pos).and_then(|t| t.comment.clone());
+        self.expect(keyword)?;
+        Ok(comment)
+    }
+
+    fn push(
+        &mut self,
+        kind: ResourceElementKind,
+        name: String,
+        line: u32,
+        summary: Option<String>,
+        signature: String,
+    ) {
+        self.elements.push(ResourceElementNode {
+            resource_id: self.resource_id,
+            kind,
+            name,
+            summary,
+            signature,
+            line: Some(line),
+        });
+    }
+
+    /// Top-level or message-body declarations until `}` (nested) or EOF.
+    fn parse_file(&mut self) -> Result<()> {
+        let mut package = String::new();
+        while let Some(text) = self.peek() {
+            match text {
+                "package" => {
+                    self.pos += 1;
+                    package = self.next()?.text.clone();
+                    self.expect(";")?;
+                }
+                "message" => self.parse_message(&package)?,
+                "enum" => self.parse_enum(&package)?,
+                "service" => self.parse_service(&package)?,
+                ";" => self.pos += 1,
+                // syntax, edition, import, option, extend
+                _ => self.skip_statement()?,
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_message(&mut self, scope: &str) -> Result<()> {
+        let comment = self.keyword("message")?;
+        let token = self.next()?.clone();
+        let name = qualify(scope, &token.text);
+        self.expect("{")?;
+
+        let mut fields: Vec<(i64, String)> = Vec::new();
+        loop {
+            match self.peek() {
+                None => bail!("line {}: unterminated message `{}`", token.line, name),
+                Some("}") => {
+                    self.pos += 1;
+                    break;
+                }
+                Some("message") => self.parse_message(&name)?,
+                Some("enum") => self.parse_enum(&name)?,
+                Some("oneof") => {
+                    self.pos += 1;
+                    let oneof = self.next()?.text.clone();
+                    self.expect("{")?;
+                    while self.peek() != Some("}") {
+                        if self.peek() == Some("option") {
+                            self.skip_statement()?;
+                            continue;
+                        }
+                        let (number, field) = self.parse_field()?;
+                        fields.push((number, format!("{} (oneof {})", field, oneof)));
+                    }
+                    self.pos += 1;
+                }
+                Some("option" | "reserved" | "extensions" | "extend" | ";") => {
+                    self.skip_statement()?
+                }
+                Some(_) => fields.push(self.parse_field()?),
+            }
+        }
+
+        fields.sort_by_key(|(number, _)| *number);
+        let signature = fields
+            .iter()
+            .map(|(number, field)| format!("{} {}", number, field))
+            .collect::<Vec<_>>()
+            .join("; ");
+        self.push(
+            ResourceElementKind::Message,
+            name,
+            token.line,
+            comment,
+            signature,
+        );
+        Ok(())
+    }
+
+    /// `[repeated|optional|required] type name = N [options];` or
+    /// `map<K, V> name = N;` → (N, "repeated string tags")
+    fn parse_field(&mut self) -> Result<(i64, String)> {
+        let mut parts = Vec::new();
+        let mut ty = self.next()?.text.clone();
+        if matches!(ty.as_str(), "repeated" | "optional" | "required") {
+            parts.push(ty);
+            ty = self.next()?.text.clone();
+        }
+        if ty == "map" {
+            self.expect("<")?;
+            let key = self.next()?.text.clone();
+            self.expect(",")?;
+            let value = self.next()?.text.clone();
+            self.expect(">")?;
+            ty = format!("map<{}, {}>", key, value);
+        }
+        parts.push(ty);
+        let field_token = self.next()?.clone();
+        parts.push(field_token.text);
+        self.expect("=")?;
+        let number_token = self.next()?;
+        let number: i64 = number_token.text.parse().map_err(|_| {
+            anyhow!(
+                "line {}: invalid field number `{}`",
+                number_token.line,
+                number_token.text
+            )
+        })?;
+        // Field options: `[deprecated = true]`
+        while self.peek() != Some(";") {
+            self.next()?;
+        }
+        self.pos += 1;
+        Ok((number, parts.join(" ")))
+    }
+
+    fn parse_enum(&mut self, scope: &str) -> Result<()> {
+        let comment = self.keyword("enum")?;
+        let token = self.next()?.clone();
+        let name = qualify(scope, &token.text);
+        self.expect("{")?;
+
+        let mut values = Vec::new();
+        loop {
+            match self.peek() {
+                None => bail!("line {}: unterminated enum `{}`", token.line, name),
+                Some("}") => {
+                    self.pos += 1;
+                    break;
+                }
+                Some("option" | "reserved" | ";") => self.skip_statement()?,
+                Some(_) => {
+                    let value = self.next()?.text.clone();
+                    self.expect("=")?;
+                    let mut number = self.next()?.text.clone();
+                    if number == "-" {
+                        number = format!("-{}", self.next()?.text);
+                    }
+                    values.push(format!("{}={}", value, number));
+                    self.skip_statement()?;
+                }
+            }
+        }
+
+        self.push(
+            ResourceElementKind::Enum,
+            name,
+            token.line,
+            comment,
+            values.join(", "),
+        );
+        Ok(())
+    }
+
+    fn parse_service(&mut self, scope: &str) -> Result<()> {
+        let comment = self.keyword("service")?;
+        let token = self.next()?.clone();
+        let name = qualify(scope, &token.text);
+        self.expect("{")?;
+
+        let mut rpcs = Vec::new();
+        loop {
+            match self.peek() {
+                None => bail!("line {}: unterminated service `{}`", token.line, name),
+                Some("}") => {
+                    self.pos += 1;
+                    break;
+                }
+                Some("rpc") => {
+                    let rpc_comment = self.keyword("rpc")?;
+                    let rpc_token = self.next()?.clone();
+                    let request = self.parse_rpc_type()?;
+                    self.expect("returns")?;
+                    let response = self.parse_rpc_type()?;
+                    // `;` or an options block `{ option … }`
+                    self.skip_statement()?;
+                    rpcs.push(rpc_token.text.clone());
+                    self.push(
+                        ResourceElementKind::Rpc,
+                        format!("{}/{}", name, rpc_token.text),
+                        rpc_token.line,
+                        rpc_comment,
+                        format!("({}) returns ({})", request, response),
+                    );
+                }
+                Some(_) => self.skip_statement()?,
+            }
+        }
+
+        rpcs.sort();
+        self.push(
+            ResourceElementKind::Service,
+            name,
+            token.line,
+            comment,
+            rpcs.join(", "),
+        );
+        Ok(())
+    }
+
+    /// `(stream pkg.Request)` → `stream pkg.Request`
+    fn parse_rpc_type(&mut self) -> Result<String> {
+        self.expect("(")?;
+        let mut ty = self.next()?.text.clone();
+        if ty == "stream" {
+            ty = format!("stream {}", self.next()?.text);
+        }
+        self.expect(")")?;
+        Ok(ty)
+    }
+}
+
+fn qualify(scope: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", scope, name)
+    }
+}
+
+fn parse_proto(resource_id: Uuid, content: &str) -> Result<Vec<ResourceElementNode>> {
+    let tokens = tokenize_proto(content);
+    let mut parser = ProtoParser {
+        tokens: &tokens,
+        pos: 0,
+        resource_id,
+        elements: Vec::new(),
+    };
+    parser.parse_file().context("Invalid proto file")?;
+    Ok(parser.elements)
+}
+
+/// Count elements per kind, e.g. for sync/parse summaries.
+pub fn count_by_kind(elements: &[ResourceElementNode]) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for element in elements {
+        *counts.entry(element.kind.to_string()).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPENAPI: &str = r#"
+openapi: 3.0.3
+info:
+  title: Orders
+  version: 1.0.0
+paths:
+  /orders/{id}:
+    parameters:
+      - name: id
+        in: path
+        required: true
+        schema:
+          type: string
+    get:
+      operationId: getOrder
+      responses:
+        200:
+          description: ok
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Order'
+        404:
+          description: missing
+    delete:
+      summary: Cancel an order
+      responses:
+        '204':
+          description: gone
+  /orders:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/Order'
+      responses:
+        '201':
+          description: created
+components:
+  schemas:
+    Order:
+      type: object
+      required: [id]
+      properties:
+        id:
+          type: string
+          format: uuid
+        items:
+          type: array
+          items:
+            $ref: '#/components/schemas/Item'
+    Status:
+      type: string
+      enum: [open, closed]
+"#;
+
+    const PROTO: &str = r#"
+syntax = "proto3";
+package billing.v1;
+
+import "google/protobuf/timestamp.proto";
+option go_package = "example.com/billing";
+
+// Charges customers.
+service Billing {
+  rpc Charge (ChargeRequest) returns (Invoice);
+  rpc Watch (WatchRequest) returns (stream Invoice) {
+    option deprecated = true;
+  }
+}
+
+/* An issued invoice */
+message Invoice {
+  string id = 1;
+  repeated LineItem items = 3;
+  map<string, string> labels = 4 [deprecated = true];
+  oneof payer {
+    string customer_id = 5;
+    string org_id = 6;
+  }
+  reserved 2;
+
+  message LineItem {
+    string sku = 1;
+    int64 cents = 2;
+  }
+
+  enum State {
+    STATE_UNSPECIFIED = 0;
+    PAID = 1;
+  }
+}
+
+message ChargeRequest { string customer_id = 1; }
+message WatchRequest {}
+"#;
+
+    fn names(elements: &[ResourceElementNode], kind: ResourceElementKind) -> Vec<&str> {
+        elements
+            .iter()
+            .filter(|e| e.kind == kind)
+            .map(|e| e.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(
+            ContractFormat::detect(&ResourceType::Other, None, "api/billing.proto"),
+            Some(ContractFormat::Protobuf)
+        );
+        assert_eq!(
+            ContractFormat::detect(&ResourceType::ApiContract, None, "specs/users.yaml"),
+            Some(ContractFormat::OpenApi)
+        );
+        assert_eq!(
+            ContractFormat::detect(&ResourceType::Other, Some("OpenAPI"), "users.json"),
+            Some(ContractFormat::OpenApi)
+        );
+        assert_eq!(
+            ContractFormat::detect(&ResourceType::Config, None, "config.yaml"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_openapi() {
+        let rid = Uuid::new_v4();
+        let elements = parse_contract(rid, ContractFormat::OpenApi, OPENAPI).unwrap();
+
+        assert_eq!(
+            names(&elements, ResourceElementKind::Operation),
+            vec!["DELETE /orders/{id}", "GET /orders/{id}", "POST /orders"]
+        );
+        assert_eq!(
+            names(&elements, ResourceElementKind::Schema),
+            vec!["Order", "Status"]
+        );
+
+        let get = elements
+            .iter()
+            .find(|e| e.name == "GET /orders/{id}")
+            .unwrap();
+        assert_eq!(get.summary.as_deref(), Some("getOrder"));
+        assert_eq!(
+            get.signature,
+            "params(path:id!) body() responses(200: Order, 404)"
+        );
+        assert_eq!(get.line, Some(14));
+
+        let order = elements.iter().find(|e| e.name == "Order").unwrap();
+        assert_eq!(order.signature, "{id!: string(uuid), items: [Item]}");
+        let status = elements.iter().find(|e| e.name == "Status").unwrap();
+        assert_eq!(status.signature, "enum(open, closed)");
+        assert!(elements.iter().all(|e| e.resource_id == rid));
+    }
+
+    #[test]
+    fn test_parse_openapi_rejects_other_yaml() {
+        let err = parse_contract(Uuid::new_v4(), ContractFormat::OpenApi, "name: x\n").unwrap_err();
+        assert!(err.to_string().contains("Not an OpenAPI document"));
+    }
+
+    #[test]
+    fn test_parse_proto() {
+        let elements = parse_contract(Uuid::new_v4(), ContractFormat::Protobuf, PROTO).unwrap();
+
+        assert_eq!(
+            names(&elements, ResourceElementKind::Service),
+            vec!["billing.v1.Billing"]
+        );
+        assert_eq!(
+            names(&elements, ResourceElementKind::Rpc),
+            vec!["billing.v1.Billing/Charge", "billing.v1.Billing/Watch"]
+        );
+        assert_eq!(
+            names(&elements, ResourceElementKind::Message),
+            vec![
+                "billing.v1.ChargeRequest",
+                "billing.v1.Invoice",
+                "billing.v1.Invoice.LineItem",
+                "billing.v1.WatchRequest",
+            ]
+        );
+        assert_eq!(
+            names(&elements, ResourceElementKind::Enum),
+            vec!["billing.v1.Invoice.State"]
+        );
+
+        let service = elements
+            .iter()
+            .find(|e| e.kind == ResourceElementKind::Service)
+            .unwrap();
+        assert_eq!(service.summary.as_deref(), Some("Charges customers."));
+        assert_eq!(service.signature, "Charge, Watch");
+
+        let watch = elements
+            .iter()
+            .find(|e| e.name == "billing.v1.Billing/Watch")
+            .unwrap();
+        assert_eq!(watch.signature, "(WatchRequest) returns (stream Invoice)");
+
+        let invoice = elements
+            .iter()
+            .find(|e| e.name == "billing.v1.Invoice")
+            .unwrap();
+        assert_eq!(
+            invoice.signature,
+            "1 string id; 3 repeated LineItem items; 4 map<string, string> labels; \
+             5 string customer_id (oneof payer); 6 string org_id (oneof payer)"
+        );
+        let state = elements
+            .iter()
+            .find(|e| e.kind == ResourceElementKind::Enum)
+            .unwrap();
+        assert_eq!(state.signature, "STATE_UNSPECIFIED=0, PAID=1");
+    }
+
+    #[test]
+    fn test_parse_proto_reports_line_on_error() {
+        let err = parse_contract(
+            Uuid::new_v4(),
+            ContractFormat::Protobuf,
+            "message A {\n  string id = one;\n}\n",
+        )
+        .unwrap_err();
+        assert!(format!("{:#}", err).contains("line 2"), "{:#}", err);
+    }
+
+    #[test]
+    fn test_diff_elements() {
+        let rid = Uuid::new_v4();
+        let v1 = parse_contract(rid, ContractFormat::Protobuf, PROTO).unwrap();
+        let v2_src = PROTO
+            .replace("  rpc Watch (WatchRequest) returns (stream Invoice) {\n    option deprecated = true;\n  }\n", "  rpc Refund (ChargeRequest) returns (Invoice);\n")
+            .replace("string sku = 1;", "string sku = 1;\n    string currency = 3;");
+        let v2 = parse_contract(rid, ContractFormat::Protobuf, &v2_src).unwrap();
+
+        let diff = diff_elements(&v1, &v2);
+        assert_eq!(
+            names(&diff.added, ResourceElementKind::Rpc),
+            vec!["billing.v1.Billing/Refund"]
+        );
+        assert_eq!(
+            names(&diff.removed, ResourceElementKind::Rpc),
+            vec!["billing.v1.Billing/Watch"]
+        );
+        let changed: Vec<&str> = diff.changed.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            changed,
+            vec!["billing.v1.Billing", "billing.v1.Invoice.LineItem"]
+        );
+        assert!(diff_elements(&v1, &v1).is_empty());
+    }
+}
@@ -0,0 +1,658 @@
+//! Dockerfile and docker-compose parsers.
+//!
+//! Extracts the deployment topology a project declares for itself: every
+//! compose service becomes a [`DiscoveredComponent`] (image, published ports,
+//! `depends_on`), enriched with the base image and `EXPOSE`d ports of the
+//! Dockerfile it builds. Dockerfiles no compose file builds become components
+//! of their own, named after their directory.
+//!
+//! Both parsers only read what topology needs; build steps, environment,
+//! volumes and networks are ignored.
+
+use crate::neo4j::models::ComponentType;
+use anyhow::{Context, Result};
+use serde_yaml::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Whether a path is a Dockerfile (`Dockerfile`, `Dockerfile.worker`,
+/// `api.Dockerfile`, `Containerfile`)
+pub fn is_dockerfile_path(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    let lower = name.to_lowercase();
+    lower == "dockerfile"
+        || lower == "containerfile"
+        || lower.starts_with("dockerfile.")
+        || lower.ends_with(".dockerfile")
+}
+
+/// Whether a path is a compose file (`docker-compose.yml`, `compose.yaml`,
+/// `docker-compose.prod.yml`)
+pub fn is_compose_path(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    let lower = name.to_lowercase();
+    (lower.starts_with("docker-compose") || lower.starts_with("compose."))
+        && (lower.ends_with(".yml") || lower.ends_with(".yaml"))
+}
+
+// ============================================================================
+// Dockerfile
+// ============================================================================
+
+/// What a Dockerfile tells about the container it builds
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DockerfileInfo {
+    /// Image of the final stage, following stage aliases (`FROM build`)
+    pub base_image: Option<String>,
+    /// Stage names (`FROM rust:1.80 AS build` → `build`)
+    pub stages: Vec<String>,
+    /// `EXPOSE`d ports of the final stage (`8080`, `53/udp`)
+    pub exposed_ports: Vec<String>,
+}
+
+/// Parse a Dockerfile, joining `\` continuations and skipping comments.
+pub fn parse_dockerfile(content: &str) -> DockerfileInfo {
+    let mut info = DockerfileInfo::default();
+    // (stage alias, image) per FROM, in order
+    let mut stage_images: Vec<(Option<String>, String)> = Vec::new();
+
+    let mut logical = String::new();
+    let mut instructions = Vec::new();
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if logical.is_empty() && (trimmed.starts_with('#') || trimmed.is_empty()) {
+            continue;
+        }
+        match trimmed.strip_suffix('\\') {
+            Some(head) => {
+                logical.push_str(head);
+                logical.push(' ');
+            }
+            None => {
+                logical.push_str(trimmed);
+                instructions.push(std::mem::take(&mut logical));
+            }
+        }
+    }
+    if !logical.is_empty() {
+        instructions.push(logical);
+    }
+
+    for instruction in instructions {
+        let mut words = instruction.split_whitespace();
+        let Some(keyword) = words.next() else {
+            continue;
+        };
+        let args: Vec<&str> = words.collect();
+        match keyword.to_uppercase().as_str() {
+            "FROM" => {
+                let args: Vec<&str> = args.into_iter().filter(|a| !a.starts_with("--")).collect();
+                let Some(image) = args.first() else {
+                    continue;
+                };
+                let alias = match args.as_slice() {
+                    [_, as_kw, alias, ..] if as_kw.eq_ignore_ascii_case("as") => {
+                        Some(alias.to_string())
+                    }
+                    _ => None,
+                };
+                if let Some(alias) = &alias {
+                    info.stages.push(alias.clone());
+                }
+                stage_images.push((alias, image.to_string()));
+                // EXPOSE applies to the stage it appears in
+                info.exposed_ports.clear();
+            }
+            "EXPOSE" => {
+                for port in args {
+                    let port = port.strip_suffix("/tcp").unwrap_or(port);
+                    if !info.exposed_ports.iter().any(|p| p == port) {
+                        info.exposed_ports.push(port.to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Resolve `FROM <stage>` chains to the external image
+    let mut image = stage_images.last().map(|(_, image)| image.clone());
+    for _ in 0..stage_images.len() {
+        let Some(current) = image.clone() else {
+            break;
+        };
+        match stage_images
+            .iter()
+            .find(|(alias, _)| alias.as_deref() == Some(current.as_str()))
+        {
+            Some((_, parent)) if *parent != current => image = Some(parent.clone()),
+            _ => break,
+        }
+    }
+    info.base_image = image;
+    info
+}
+
+// ============================================================================
+// docker-compose
+// ============================================================================
+
+/// A service of a compose file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ComposeService {
+    pub name: String,
+    pub image: Option<String>,
+    /// Build context, as written (relative to the compose file)
+    pub build_context: Option<String>,
+    /// Dockerfile, as written (relative to the build context)
+    pub dockerfile: Option<String>,
+    /// Published ports (`8080:80`, `443`)
+    pub ports: Vec<String>,
+    /// Ports exposed to other services only
+    pub expose: Vec<String>,
+    /// `depends_on` and `links` targets
+    pub depends_on: Vec<String>,
+}
+
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn scalar_list(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_sequence())
+        .map(|seq| seq.iter().filter_map(scalar).collect())
+        .unwrap_or_default()
+}
+
+/// Short (`"8080:80/tcp"`) or long (`{target, published}`) port syntax
+fn port_spec(value: &Value) -> Option<String> {
+    if let Some(port) = scalar(value) {
+        return Some(port.strip_suffix("/tcp").unwrap_or(&port).to_string());
+    }
+    let target = value.get("target").and_then(scalar)?;
+    Some(match value.get("published").and_then(scalar) {
+        Some(published) => format!("{}:{}", published, target),
+        None => target,
+    })
+}
+
+/// Parse the services of a compose file, in file order.
+pub fn parse_compose(content: &str) -> Result<Vec<ComposeService>> {
+    let doc: Value = serde_yaml::from_str(content).context("Invalid compose file")?;
+    let Some(services) = doc.get("services").and_then(|s| s.as_mapping()) else {
+        return Ok(vec![]);
+    };
+
+    let mut result = Vec::new();
+    for (name, service) in services {
+        let Some(name) = name.as_str() else {
+            continue;
+        };
+        let (build_context, dockerfile) = match service.get("build") {
+            Some(Value::String(context)) => (Some(context.clone()), None),
+            Some(build) => (
+                build
+                    .get("context")
+                    .and_then(scalar)
+                    .or_else(|| Some(".".to_string())),
+                build.get("dockerfile").and_then(scalar),
+            ),
+            None => (None, None),
+        };
+
+        let mut depends_on = match service.get("depends_on") {
+            Some(Value::Mapping(m)) => m.keys().filter_map(scalar).collect(),
+            other => scalar_list(other),
+        };
+        for link in scalar_list(service.get("links")) {
+            let target = link.split(':').next().unwrap_or_default().to_string();
+            if !target.is_empty() && !depends_on.contains(&target) {
+                depends_on.push(target);
+            }
+        }
+
+        result.push(ComposeService {
+            name: name.to_string(),
+            image: service.get("image").and_then(scalar),
+            build_context,
+            dockerfile,
+            ports: service
+                .get("ports")
+                .and_then(|p| p.as_sequence())
+                .map(|seq| seq.iter().filter_map(port_spec).collect())
+                .unwrap_or_default(),
+            expose: scalar_list(service.get("expose")),
+            depends_on,
+        });
+    }
+    Ok(result)
+}
+
+// ============================================================================
+// Topology
+// ============================================================================
+
+/// A component found in a project's Dockerfiles and compose files
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiscoveredComponent {
+    /// Compose service name, or Dockerfile directory name
+    pub name: String,
+    pub image: Option<String>,
+    /// Final-stage image of the Dockerfile the component is built from
+    pub base_image: Option<String>,
+    /// Absolute path of the Dockerfile built, if any
+    pub dockerfile: Option<String>,
+    pub ports: Vec<String>,
+    pub exposed_ports: Vec<String>,
+    pub depends_on: Vec<String>,
+    /// File the component was found in
+    pub source: String,
+}
+
+impl DiscoveredComponent {
+    /// Whether the component is built from the project's sources (and so
+    /// belongs to it) rather than pulled as an image
+    pub fn is_built(&self) -> bool {
+        self.dockerfile.is_some()
+    }
+
+    /// Container port other components connect to: the target of the first
+    /// published port, else the first exposed one
+    pub fn primary_port(&self) -> Option<u16> {
+        self.ports
+            .iter()
+            .map(|p| p.rsplit(':').next().unwrap_or(p))
+            .chain(self.exposed_ports.iter().map(String::as_str))
+            .find_map(|p| p.split('/').next()?.parse().ok())
+    }
+
+    /// Guess the component type from its name and image.
+    pub fn component_type(&self) -> ComponentType {
+        infer_component_type(
+            &self.name,
+            self.image.as_deref().or(self.base_image.as_deref()),
+        )
+    }
+
+    /// Component `config` entries describing the container.
+    pub fn to_config(&self) -> serde_json::Map<String, serde_json::Value> {
+        let mut config = serde_json::Map::new();
+        let mut set = |key: &str, value: serde_json::Value| {
+            config.insert(key.to_string(), value);
+        };
+        if let Some(image) = &self.image {
+            set("image", image.clone().into());
+        }
+        if let Some(base_image) = &self.base_image {
+            set("base_image", base_image.clone().into());
+        }
+        if let Some(dockerfile) = &self.dockerfile {
+            set("dockerfile", dockerfile.clone().into());
+        }
+        if !self.ports.is_empty() {
+            set("ports", self.ports.clone().into());
+        }
+        if !self.exposed_ports.is_empty() {
+            set("exposed_ports", self.exposed_ports.clone().into());
+        }
+        set("source", self.source.clone().into());
+        config
+    }
+}
+
+/// Guess a component type from a service name and image.
+pub fn infer_component_type(name: &str, image: Option<&str>) -> ComponentType {
+    // `docker.io/library/postgres:16-alpine` → `postgres`
+    let image = image
+        .map(|i| {
+            let repo = i.split(['@', ':']).next().unwrap_or(i);
+            repo.rsplit('/').next().unwrap_or(repo).to_lowercase()
+        })
+        .unwrap_or_default();
+    let name = name.to_lowercase();
+    let matches = |markers: &[&str]| {
+        markers
+            .iter()
+            .any(|m| image.contains(m) || name.contains(m))
+    };
+
+    if matches(&[
+        "postgres",
+        "mysql",
+        "mariadb",
+        "mongo",
+        "cockroach",
+        "clickhouse",
+        "cassandra",
+        "neo4j",
+        "timescale",
+        "mssql",
+        "elasticsearch",
+        "opensearch",
+        "meilisearch",
+    ]) {
+        ComponentType::Database
+    } else if matches(&["redis", "memcached", "valkey", "dragonfly", "keydb"]) {
+        ComponentType::Cache
+    } else if matches(&[
+        "rabbitmq",
+        "kafka",
+        "nats",
+        "activemq",
+        "pulsar",
+        "mosquitto",
+    ]) {
+        ComponentType::MessageQueue
+    } else if matches(&[
+        "nginx", "traefik", "envoy", "haproxy", "caddy", "gateway", "proxy",
+    ]) {
+        ComponentType::Gateway
+    } else if matches(&[
+        "worker",
+        "consumer",
+        "scheduler",
+        "cron",
+        "celery",
+        "sidekiq",
+    ]) {
+        ComponentType::Worker
+    } else if ["frontend", "web", "ui"].iter().any(|m| {
+        name == *m || name.starts_with(&format!("{}-", m)) || name.ends_with(&format!("-{}", m))
+    }) {
+        ComponentType::Frontend
+    } else {
+        ComponentType::Service
+    }
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::ParentDir => {
+                out.pop();
+            }
+            std::path::Component::CurDir => {}
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Name for a Dockerfile no compose service builds: `Dockerfile.worker` →
+/// `worker`, `api.Dockerfile` → `api`, `services/billing/Dockerfile` →
+/// `billing`, `<root>/Dockerfile` → `root_name`.
+fn dockerfile_component_name(path: &Path, root: &Path, root_name: &str) -> String {
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    let lower = file_name.to_lowercase();
+    if let Some(suffix) = lower.strip_prefix("dockerfile.") {
+        return suffix.to_string();
+    }
+    if let Some(prefix) = lower.strip_suffix(".dockerfile") {
+        return prefix.to_string();
+    }
+    match path.parent() {
+        Some(dir) if dir != root => dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(root_name)
+            .to_string(),
+        _ => root_name.to_string(),
+    }
+}
+
+/// Build the components declared by a project from `(path, content)` pairs
+/// of its Dockerfiles and compose files (absolute paths, under `root`).
+///
+/// Services with the same name across compose files (`docker-compose.yml` +
+/// `docker-compose.override.yml`) are merged. Components are ordered by name.
+pub fn discover_components(
+    root: &Path,
+    root_name: &str,
+    files: &[(String, String)],
+) -> Vec<DiscoveredComponent> {
+    let dockerfiles: BTreeMap<PathBuf, DockerfileInfo> = files
+        .iter()
+        .filter(|(path, _)| is_dockerfile_path(Path::new(path)))
+        .map(|(path, content)| (normalize(Path::new(path)), parse_dockerfile(content)))
+        .collect();
+
+    let mut compose_files: Vec<&(String, String)> = files
+        .iter()
+        .filter(|(path, _)| is_compose_path(Path::new(path)))
+        .collect();
+    // Base files before overrides
+    compose_files.sort_by_key(|(path, _)| (path.contains("override"), path.clone()));
+
+    let mut components: BTreeMap<String, DiscoveredComponent> = BTreeMap::new();
+    let mut built = Vec::new();
+    for (path, content) in compose_files {
+        let services = match parse_compose(content) {
+            Ok(services) => services,
+            Err(e) => {
+                tracing::debug!("Skipping compose file {}: {}", path, e);
+                continue;
+            }
+        };
+        let compose_dir = Path::new(path).parent().unwrap_or(root);
+        for service in services {
+            let dockerfile = service.build_context.as_ref().map(|context| {
+                let context = compose_dir.join(context);
+                normalize(&context.join(service.dockerfile.as_deref().unwrap_or("Dockerfile")))
+            });
+            let info = dockerfile.as_ref().and_then(|p| dockerfiles.get(p));
+            if let Some(dockerfile) = &dockerfile {
+                built.push(dockerfile.clone());
+            }
+
+            let entry =
+                components
+                    .entry(service.name.clone())
+                    .or_insert_with(|| DiscoveredComponent {
+                        name: service.name.clone(),
+                        source: path.clone(),
+                        ..Default::default()
+                    });
+            if service.image.is_some() {
+                entry.image = service.image.clone();
+            }
+            if let Some(dockerfile) = &dockerfile {
+                entry.dockerfile = Some(dockerfile.to_string_lossy().to_string());
+            }
+            if let Some(info) = info {
+                entry.base_image = info.base_image.clone();
+                extend_unique(&mut entry.exposed_ports, &info.exposed_ports);
+            }
+            extend_unique(&mut entry.ports, &service.ports);
+            extend_unique(&mut entry.exposed_ports, &service.expose);
+            extend_unique(&mut entry.depends_on, &service.depends_on);
+        }
+    }
+
+    for (path, info) in &dockerfiles {
+        if built.contains(path) {
+            continue;
+        }
+        let name = dockerfile_component_name(path, root, root_name);
+        components
+            .entry(name.clone())
+            .or_insert_with(|| DiscoveredComponent {
+                name,
+                base_image: info.base_image.clone(),
+                dockerfile: Some(path.to_string_lossy().to_string()),
+                exposed_ports: info.exposed_ports.clone(),
+                source: path.to_string_lossy().to_string(),
+                ..Default::default()
+            });
+    }
+
+    // Drop dependencies on services the files don't define
+    let names: Vec<String> = components.keys().cloned().collect();
+    for component in components.values_mut() {
+        component.depends_on.retain(|d| names.contains(d));
+    }
+    components.into_values().collect()
+}
+
+fn extend_unique(target: &mut Vec<String>, items: &[String]) {
+    for item in items {
+        if !target.contains(item) {
+            target.push(item.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_docker_paths() {
+        for path in [
+            "/r/Dockerfile",
+            "/r/Dockerfile.worker",
+            "/r/api.Dockerfile",
+            "/r/Containerfile",
+        ] {
+            assert!(is_dockerfile_path(Path::new(path)), "{}", path);
+        }
+        assert!(!is_dockerfile_path(Path::new("/r/dockerfile.rs.bak/x.rs")));
+        for path in [
+            "/r/docker-compose.yml",
+            "/r/docker-compose.override.yaml",
+            "/r/compose.yaml",
+        ] {
+            assert!(is_compose_path(Path::new(path)), "{}", path);
+        }
+        assert!(!is_compose_path(Path::new("/r/composer.json")));
+    }
+
+    #[test]
+    fn test_parse_dockerfile_multi_stage() {
+        let info = parse_dockerfile(
+            "# syntax=docker/dockerfile:1\n\
+             FROM --platform=$BUILDPLATFORM rust:1.80 AS build\n\
+             EXPOSE 9999\n\
+             RUN cargo build --release \\\n    --locked\n\
+             FROM debian:bookworm-slim AS runtime\n\
+             FROM runtime\n\
+             EXPOSE 8080/tcp 53/udp\n\
+             CMD [\"/app\"]\n",
+        );
+        assert_eq!(info.base_image.as_deref(), Some("debian:bookworm-slim"));
+        assert_eq!(info.stages, ["build", "runtime"]);
+        assert_eq!(info.exposed_ports, ["8080", "53/udp"]);
+    }
+
+    #[test]
+    fn test_parse_compose_short_and_long_syntax() {
+        let services = parse_compose(
+            r#"
+services:
+  api:
+    build:
+      context: ./api
+      dockerfile: Dockerfile.prod
+    ports:
+      - "8080:80"
+      - target: 9090
+        published: 19090
+    depends_on:
+      db:
+        condition: service_healthy
+    links:
+      - cache:redis
+  db:
+    image: postgres:16
+    expose: [5432]
+  cache:
+    image: redis:7
+"#,
+        )
+        .unwrap();
+        assert_eq!(services.len(), 3);
+        let api = &services[0];
+        assert_eq!(api.build_context.as_deref(), Some("./api"));
+        assert_eq!(api.dockerfile.as_deref(), Some("Dockerfile.prod"));
+        assert_eq!(api.ports, ["8080:80", "19090:9090"]);
+        assert_eq!(api.depends_on, ["db", "cache"]);
+        assert_eq!(services[1].expose, ["5432"]);
+        assert!(parse_compose("services: [").is_err());
+    }
+
+    #[test]
+    fn test_infer_component_type() {
+        let cases = [
+            (
+                "db",
+                Some("docker.io/library/postgres:16-alpine"),
+                ComponentType::Database,
+            ),
+            ("cache", Some("redis:7"), ComponentType::Cache),
+            ("broker", Some("bitnami/kafka"), ComponentType::MessageQueue),
+            ("edge", Some("traefik:v3"), ComponentType::Gateway),
+            ("email-worker", None, ComponentType::Worker),
+            ("web", Some("node:20"), ComponentType::Frontend),
+            ("billing", Some("rust:1.80"), ComponentType::Service),
+        ];
+        for (name, image, expected) in cases {
+            assert_eq!(infer_component_type(name, image), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_discover_components() {
+        let files = vec![
+            (
+                "/repo/docker-compose.yml".to_string(),
+                "services:\n  api:\n    build: ./api\n    ports: [\"8080:3000\"]\n    depends_on: [db, ghost]\n  db:\n    image: postgres:16\n".to_string(),
+            ),
+            (
+                "/repo/docker-compose.override.yml".to_string(),
+                "services:\n  api:\n    ports: [\"9229:9229\"]\n".to_string(),
+            ),
+            (
+                "/repo/api/Dockerfile".to_string(),
+                "FROM node:20-alpine\nEXPOSE 3000\n".to_string(),
+            ),
+            (
+                "/repo/tools/Dockerfile.migrate".to_string(),
+                "FROM alpine\n".to_string(),
+            ),
+            ("/repo/Dockerfile".to_string(), "FROM scratch\n".to_string()),
+        ];
+        let components = discover_components(Path::new("/repo"), "demo", &files);
+        let names: Vec<&str> = components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["api", "db", "demo", "migrate"]);
+
+        let api = &components[0];
+        assert_eq!(api.dockerfile.as_deref(), Some("/repo/api/Dockerfile"));
+        assert_eq!(api.base_image.as_deref(), Some("node:20-alpine"));
+        assert_eq!(api.ports, ["8080:3000", "9229:9229"]);
+        assert_eq!(api.exposed_ports, ["3000"]);
+        assert_eq!(api.depends_on, ["db"], "undefined services are dropped");
+        assert_eq!(api.primary_port(), Some(3000));
+        assert!(api.is_built());
+
+        let db = &components[1];
+        assert!(!db.is_built());
+        assert_eq!(db.component_type(), ComponentType::Database);
+        assert_eq!(db.to_config()["image"], "postgres:16");
+        assert_eq!(
+            components[2].dockerfile.as_deref(),
+            Some("/repo/Dockerfile")
+        );
+    }
+}
@@ -0,0 +1,87 @@
+//! Source encoding detection.
+//!
+//! Legacy codebases hold Latin-1, Shift_JIS or UTF-16 files that
+//! `read_to_string` rejects. [`decode_source`] keeps UTF-8 as is and
+//! converts anything else to UTF-8, naming the encoding it detected so the
+//! File node can record it.
+
+use encoding_rs::Encoding;
+
+/// A file's content converted to UTF-8
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedSource {
+    pub content: String,
+    /// Detected encoding, `None` for UTF-8
+    pub encoding: Option<&'static str>,
+    /// Whether bytes invalid in the detected encoding were replaced by U+FFFD
+    pub lossy: bool,
+}
+
+/// Convert a file's bytes to UTF-8: the encoding comes from the byte order
+/// mark when there is one, and is guessed by chardetng otherwise.
+pub fn decode_source(bytes: Vec<u8>) -> DecodedSource {
+    let bytes = match String::from_utf8(bytes) {
+        Ok(content) => {
+            return DecodedSource {
+                content,
+                encoding: None,
+                lossy: false,
+            }
+        }
+        Err(e) => e.into_bytes(),
+    };
+
+    let (encoding, bom_len) = Encoding::for_bom(&bytes).unwrap_or_else(|| {
+        let mut detector = chardetng::EncodingDetector::new();
+        detector.feed(&bytes, true);
+        (detector.guess(None, true), 0)
+    });
+    let (content, lossy) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+    DecodedSource {
+        content: content.into_owned(),
+        encoding: Some(encoding.name()),
+        lossy,
+    }
+}
+
+/// Read a source file, converting it to UTF-8 with [`decode_source`] and
+/// warning when it was not UTF-8.
+pub async fn read_source(path: impl AsRef<std::path::Path>) -> std::io::Result<DecodedSource> {
+    let path = path.as_ref();
+    let decoded = decode_source(tokio::fs::read(path).await?);
+    if let Some(encoding) = decoded.encoding {
+        tracing::warn!(
+            "{} is not UTF-8: decoded as {}{}",
+            path.display(),
+            encoding,
+            if decoded.lossy {
+                ", replacing invalid bytes"
+            } else {
+                ""
+            }
+        );
+    }
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_source() {
+        let utf8 = decode_source("fn café() {}".as_bytes().to_vec());
+        assert_eq!(utf8.content, "fn café() {}");
+        assert_eq!(utf8.encoding, None);
+
+        // "// café" in Latin-1
+        let latin1 = decode_source(b"// caf\xe9 cr\xe8me br\xfbl\xe9e\n".to_vec());
+        assert_eq!(latin1.content, "// café crème brûlée\n");
+        assert_eq!(latin1.encoding, Some("windows-1252"));
+        assert!(!latin1.lossy);
+
+        let utf16 = decode_source(vec![0xff, 0xfe, b'f', 0, b'n', 0]);
+        assert_eq!(utf16.content, "fn");
+        assert_eq!(utf16.encoding, Some("UTF-16LE"));
+    }
+}
@@ -196,6 +196,7 @@ fn extract_calls_recursive(
                             line: node.start_position().row as u32 + 1,
                             confidence: 0.50,
                             reason: "unscored".to_string(),
+                            receiver_type: None,
                         });
                     }
                 }
@@ -119,6 +119,7 @@ fn extract_variable(node: &tree_sitter::Node, source: &str, file_path: &str) ->
         docstring,
         parent_class: None,
         interfaces: vec![],
+        fields: vec![],
     })
 }
 
@@ -152,6 +153,7 @@ fn extract_declaration(
                     docstring: get_bash_doc(node, source),
                     parent_class: None,
                     interfaces: vec![],
+                    fields: vec![],
                 });
             }
         }
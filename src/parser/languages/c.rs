@@ -217,6 +217,7 @@ fn extract_struct(node: &tree_sitter::Node, source: &str, file_path: &str) -> Op
         docstring,
         parent_class: None,
         interfaces: vec![],
+        fields: vec![],
     })
 }
 
@@ -242,6 +243,7 @@ fn extract_enum(node: &tree_sitter::Node, source: &str, file_path: &str) -> Opti
         line_start: node.start_position().row as u32 + 1,
         line_end: node.end_position().row as u32 + 1,
         docstring,
+        variant_payloads: vec![],
     })
 }
 
@@ -281,6 +283,7 @@ fn extract_typedef(
                         docstring: get_c_doc(node, source),
                         parent_class: None,
                         interfaces: vec![],
+                        fields: vec![],
                     });
                 }
             }
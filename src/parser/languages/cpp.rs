@@ -276,6 +276,7 @@ fn extract_class(node: &tree_sitter::Node, source: &str, file_path: &str) -> Opt
         docstring,
         parent_class,
         interfaces,
+        fields: vec![],
     })
 }
 
@@ -301,6 +302,7 @@ fn extract_enum(node: &tree_sitter::Node, source: &str, file_path: &str) -> Opti
         line_start: node.start_position().row as u32 + 1,
         line_end: node.end_position().row as u32 + 1,
         docstring,
+        variant_payloads: vec![],
     })
 }
 
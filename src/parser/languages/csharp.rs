@@ -118,6 +118,7 @@ fn extract_class(node: &tree_sitter::Node, source: &str, file_path: &str) -> Opt
         docstring,
         parent_class,
         interfaces,
+        fields: vec![],
     })
 }
 
@@ -138,6 +139,7 @@ fn extract_struct(node: &tree_sitter::Node, source: &str, file_path: &str) -> Op
         docstring,
         parent_class: None, // C# structs can't inherit
         interfaces,
+        fields: vec![],
     })
 }
 
@@ -183,6 +185,7 @@ fn extract_enum(node: &tree_sitter::Node, source: &str, file_path: &str) -> Opti
         line_start: node.start_position().row as u32 + 1,
         line_end: node.end_position().row as u32 + 1,
         docstring,
+        variant_payloads: vec![],
     })
 }
 
@@ -89,6 +89,7 @@ fn extract_toplevel(
                         docstring: None,
                         parent_class: None,
                         interfaces: vec![],
+                        fields: vec![],
                     });
                 }
             }
@@ -325,6 +326,7 @@ fn extract_class(node: &tree_sitter::Node, source: &str, file_path: &str) -> Opt
         docstring,
         parent_class,
         interfaces: ifaces,
+        fields: vec![],
     })
 }
 
@@ -386,6 +388,7 @@ fn extract_enum(node: &tree_sitter::Node, source: &str, file_path: &str) -> Opti
         line_start: node.start_position().row as u32 + 1,
         line_end: node.end_position().row as u32 + 1,
         docstring,
+        variant_payloads: vec![],
     })
 }
 
@@ -410,6 +413,7 @@ fn extract_extension(
             file_path: file_path.to_string(),
             line_start: node.start_position().row as u32 + 1,
             line_end: node.end_position().row as u32 + 1,
+            derived: false,
         });
     }
 
@@ -192,6 +192,7 @@ fn extract_type_declaration(
                                 docstring,
                                 parent_class: None,
                                 interfaces: vec![],
+                                fields: vec![],
                             });
                         }
                         "interface_type" => {
@@ -221,6 +222,7 @@ fn extract_type_declaration(
                                 docstring,
                                 parent_class: None,
                                 interfaces: vec![],
+                                fields: vec![],
                             });
                         }
                     }
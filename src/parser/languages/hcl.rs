@@ -171,6 +171,7 @@ fn extract_resource(
             docstring: None,
             parent_class: None,
             interfaces: vec![],
+            fields: vec![],
         });
 
         // Extract inter-resource references from the block body
@@ -211,6 +212,7 @@ fn extract_data_source(
             docstring: None,
             parent_class: None,
             interfaces: vec![],
+            fields: vec![],
         });
     }
 }
@@ -409,6 +411,7 @@ fn extract_references_recursive(
                                         line: node.start_position().row as u32 + 1,
                                         confidence: 0.70,
                                         reason: "hcl-reference".to_string(),
+                                        receiver_type: None,
                                     });
                                 }
                             }
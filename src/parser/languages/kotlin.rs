@@ -171,6 +171,7 @@ fn extract_class(node: &tree_sitter::Node, source: &str, file_path: &str) -> Opt
         docstring,
         parent_class,
         interfaces,
+        fields: vec![],
     })
 }
 
@@ -208,6 +209,7 @@ fn extract_object(node: &tree_sitter::Node, source: &str, file_path: &str) -> Op
         docstring,
         parent_class: None,
         interfaces: vec![],
+        fields: vec![],
     })
 }
 
@@ -234,6 +236,7 @@ fn extract_enum(node: &tree_sitter::Node, source: &str, file_path: &str) -> Opti
         line_start: node.start_position().row as u32 + 1,
         line_end: node.end_position().row as u32 + 1,
         docstring,
+        variant_payloads: vec![],
     })
 }
 
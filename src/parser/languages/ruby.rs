@@ -187,6 +187,7 @@ fn extract_class(node: &tree_sitter::Node, source: &str, file_path: &str) -> Opt
         docstring,
         parent_class,
         interfaces: vec![],
+        fields: vec![],
     })
 }
 
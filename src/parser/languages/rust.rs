@@ -5,7 +5,7 @@
 //! - Structs, Traits, Enums
 //! - Impl blocks
 //! - Generics and lifetimes
-//! - Derive macros
+//! - Derive macros, and items declared in macro bodies
 
 use crate::neo4j::models::*;
 use crate::parser::helpers::*;
@@ -22,91 +22,164 @@ pub fn extract(
     let mut cursor = root.walk();
 
     for node in root.children(&mut cursor) {
-        match node.kind() {
-            "function_item" => {
-                if let Some(func) = extract_function(&node, source, file_path) {
-                    let func_id = format!("{}:{}:{}", file_path, func.name, func.line_start);
-                    let calls = extract_function_calls(&node, source, &func_id);
-                    parsed.function_calls.extend(calls);
-                    parsed.symbols.push(func.name.clone());
-                    parsed.functions.push(func);
-                }
+        extract_item(&node, source, file_path, parsed)?;
+    }
+
+    Ok(())
+}
+
+/// Extract one item of a file or of a macro body
+fn extract_item(
+    node: &tree_sitter::Node,
+    source: &str,
+    file_path: &str,
+    parsed: &mut ParsedFile,
+) -> Result<()> {
+    match node.kind() {
+        "function_item" => {
+            if let Some(func) = extract_function(node, source, file_path) {
+                let func_id = format!("{}:{}:{}", file_path, func.name, func.line_start);
+                let calls = extract_function_calls(node, source, &func_id, None);
+                parsed.function_calls.extend(calls);
+                parsed.symbols.push(func.name.clone());
+                parsed.functions.push(func);
             }
-            "struct_item" => {
-                if let Some(s) = extract_struct(&node, source, file_path) {
-                    // Extract derive traits
-                    let derives = extract_derive_traits(&node, source);
-                    for trait_name in derives {
-                        parsed.impl_blocks.push(ImplNode {
-                            for_type: s.name.clone(),
-                            trait_name: Some(trait_name),
-                            generics: s.generics.clone(),
-                            where_clause: None,
-                            file_path: file_path.to_string(),
-                            line_start: s.line_start,
-                            line_end: s.line_start,
-                        });
-                    }
-                    parsed.symbols.push(s.name.clone());
-                    parsed.structs.push(s);
+        }
+        "struct_item" => {
+            if let Some(s) = extract_struct(node, source, file_path) {
+                // Extract derive traits
+                let derives = extract_derive_traits(node, source);
+                for trait_name in derives {
+                    parsed.impl_blocks.push(ImplNode {
+                        for_type: s.name.clone(),
+                        trait_name: Some(trait_name),
+                        generics: s.generics.clone(),
+                        where_clause: None,
+                        file_path: file_path.to_string(),
+                        line_start: s.line_start,
+                        line_end: s.line_start,
+                        derived: true,
+                    });
                 }
+                parsed.symbols.push(s.name.clone());
+                parsed.structs.push(s);
             }
-            "trait_item" => {
-                if let Some(t) = extract_trait(&node, source, file_path) {
-                    parsed.symbols.push(t.name.clone());
-                    parsed.traits.push(t);
-                }
+        }
+        "trait_item" => {
+            if let Some(t) = extract_trait(node, source, file_path) {
+                parsed.symbols.push(t.name.clone());
+                parsed.traits.push(t);
             }
-            "enum_item" => {
-                if let Some(e) = extract_enum(&node, source, file_path) {
-                    let derives = extract_derive_traits(&node, source);
-                    for trait_name in derives {
-                        parsed.impl_blocks.push(ImplNode {
-                            for_type: e.name.clone(),
-                            trait_name: Some(trait_name),
-                            generics: vec![],
-                            where_clause: None,
-                            file_path: file_path.to_string(),
-                            line_start: e.line_start,
-                            line_end: e.line_start,
-                        });
-                    }
-                    parsed.symbols.push(e.name.clone());
-                    parsed.enums.push(e);
+        }
+        "enum_item" => {
+            if let Some(e) = extract_enum(node, source, file_path) {
+                let derives = extract_derive_traits(node, source);
+                for trait_name in derives {
+                    parsed.impl_blocks.push(ImplNode {
+                        for_type: e.name.clone(),
+                        trait_name: Some(trait_name),
+                        generics: vec![],
+                        where_clause: None,
+                        file_path: file_path.to_string(),
+                        line_start: e.line_start,
+                        line_end: e.line_start,
+                        derived: true,
+                    });
                 }
+                parsed.symbols.push(e.name.clone());
+                parsed.enums.push(e);
             }
-            "use_declaration" => {
-                if let Some(import) = extract_import(&node, source, file_path) {
-                    parsed.imports.push(import);
-                }
+        }
+        "use_declaration" => {
+            if let Some(import) = extract_import(node, source, file_path) {
+                parsed.imports.push(import);
             }
-            "mod_item" => {
-                // Extract `mod foo;` declarations as imports.
-                // Only file-level declarations (no body) — inline modules
-                // (`mod foo { ... }`) are not file imports.
-                #[allow(clippy::collapsible_match)]
-                if node.child_by_field_name("body").is_none() {
-                    if let Some(name) = get_field_text(&node, "name", source) {
-                        parsed.imports.push(ImportNode {
-                            path: format!("self::{}", name),
-                            alias: None,
-                            items: vec![],
-                            file_path: file_path.to_string(),
-                            line: node.start_position().row as u32 + 1,
-                        });
-                    }
+        }
+        "mod_item" => {
+            // Extract `mod foo;` declarations as imports.
+            // Only file-level declarations (no body) — inline modules
+            // (`mod foo { ... }`) are not file imports.
+            #[allow(clippy::collapsible_match)]
+            if node.child_by_field_name("body").is_none() {
+                if let Some(name) = get_field_text(&node, "name", source) {
+                    parsed.imports.push(ImportNode {
+                        path: format!("self::{}", name),
+                        alias: None,
+                        items: vec![],
+                        file_path: file_path.to_string(),
+                        line: node.start_position().row as u32 + 1,
+                    });
                 }
             }
-            "impl_item" => {
-                extract_impl(&node, source, file_path, parsed)?;
+        }
+        "impl_item" => {
+            extract_impl(node, source, file_path, parsed)?;
+        }
+        "macro_invocation" => {
+            extract_macro_items(&node, source, file_path, parsed)?;
+        }
+        "expression_statement" => {
+            if let Some(invocation) = node
+                .named_child(0)
+                .filter(|n| n.kind() == "macro_invocation")
+            {
+                extract_macro_items(&invocation, source, file_path, parsed)?;
             }
-            _ => {}
         }
+        _ => {}
     }
 
     Ok(())
 }
 
+/// Extract the items declared in the body of an item-level macro invocation
+/// (`pin_project! { struct Foo { .. } }`, `my_macro! { impl Bar { .. } }`).
+///
+/// The body is parsed as Rust on its own, from a copy of the file blanked
+/// outside of it so positions stay the file's. Macros whose input is not
+/// Rust items (`lazy_static!`, `bitflags!`) leave only syntax errors, and
+/// items with errors are skipped.
+fn extract_macro_items(
+    node: &tree_sitter::Node,
+    source: &str,
+    file_path: &str,
+    parsed: &mut ParsedFile,
+) -> Result<()> {
+    let Some(body) = find_child_by_kind(node, "token_tree") else {
+        return Ok(());
+    };
+    // Inside the delimiters, which are ASCII: the blanked copy stays UTF-8
+    let (start, end) = (body.start_byte() + 1, body.end_byte().saturating_sub(1));
+    if start >= end {
+        return Ok(());
+    }
+    let masked: Vec<u8> = source
+        .bytes()
+        .enumerate()
+        .map(|(i, b)| {
+            if (start..end).contains(&i) || b == b'\n' {
+                b
+            } else {
+                b' '
+            }
+        })
+        .collect();
+    let masked = String::from_utf8(masked)?;
+
+    let mut parser = tree_sitter::Parser::new();
+    parser.set_language(&tree_sitter_rust::LANGUAGE.into())?;
+    let Some(tree) = parser.parse(&masked, None) else {
+        return Ok(());
+    };
+    let root = tree.root_node();
+    for item in root.children(&mut root.walk()) {
+        if !item.has_error() {
+            extract_item(&item, &masked, file_path, parsed)?;
+        }
+    }
+    Ok(())
+}
+
 fn extract_function(
     node: &tree_sitter::Node,
     source: &str,
@@ -151,6 +224,10 @@ fn extract_struct(node: &tree_sitter::Node, source: &str, file_path: &str) -> Op
     let visibility = get_visibility(node, source);
     let docstring = get_rust_docstring(node, source);
     let generics = extract_rust_type_parameters(node, source);
+    let fields = node
+        .child_by_field_name("body")
+        .map(|body| extract_fields(&body, source))
+        .unwrap_or_default();
 
     Some(StructNode {
         name,
@@ -162,6 +239,7 @@ fn extract_struct(node: &tree_sitter::Node, source: &str, file_path: &str) -> Op
         docstring,
         parent_class: None,
         interfaces: vec![],
+        fields,
     })
 }
 
@@ -189,15 +267,25 @@ fn extract_enum(node: &tree_sitter::Node, source: &str, file_path: &str) -> Opti
     let visibility = get_visibility(node, source);
     let docstring = get_rust_docstring(node, source);
 
-    let variants: Vec<String> = node
-        .child_by_field_name("body")
-        .map(|body| {
-            body.children(&mut body.walk())
-                .filter(|c| c.kind() == "enum_variant")
-                .filter_map(|v| get_field_text(&v, "name", source))
-                .collect()
-        })
-        .unwrap_or_default();
+    let mut variants = Vec::new();
+    let mut variant_payloads = Vec::new();
+    if let Some(body) = node.child_by_field_name("body") {
+        for variant in body
+            .children(&mut body.walk())
+            .filter(|c| c.kind() == "enum_variant")
+        {
+            let Some(variant_name) = get_field_text(&variant, "name", source) else {
+                continue;
+            };
+            if let Some(payload) = variant.child_by_field_name("body") {
+                variant_payloads.push(VariantNode {
+                    name: variant_name.clone(),
+                    fields: extract_fields(&payload, source),
+                });
+            }
+            variants.push(variant_name);
+        }
+    }
 
     Some(EnumNode {
         name,
@@ -207,9 +295,53 @@ fn extract_enum(node: &tree_sitter::Node, source: &str, file_path: &str) -> Opti
         line_start: node.start_position().row as u32 + 1,
         line_end: node.end_position().row as u32 + 1,
         docstring,
+        variant_payloads,
     })
 }
 
+/// Fields of a struct or enum variant body: named (`{ a: T }`) or tuple
+/// (`(pub T, U)`), the latter named by position.
+fn extract_fields(body: &tree_sitter::Node, source: &str) -> Vec<FieldNode> {
+    let mut fields = Vec::new();
+    match body.kind() {
+        "field_declaration_list" => {
+            for decl in body
+                .children(&mut body.walk())
+                .filter(|c| c.kind() == "field_declaration")
+            {
+                if let Some(name) = get_field_text(&decl, "name", source) {
+                    fields.push(FieldNode {
+                        name,
+                        type_name: get_field_text(&decl, "type", source).unwrap_or_default(),
+                        visibility: get_visibility(&decl, source),
+                        default_value: None,
+                    });
+                }
+            }
+        }
+        "ordered_field_declaration_list" => {
+            // A visibility modifier applies to the type following it
+            let mut visibility = Visibility::Private;
+            for child in body.named_children(&mut body.walk()) {
+                match child.kind() {
+                    "visibility_modifier" => {
+                        visibility = parse_visibility(get_text(&child, source).unwrap_or_default());
+                    }
+                    "attribute_item" | "line_comment" | "block_comment" => {}
+                    _ => fields.push(FieldNode {
+                        name: fields.len().to_string(),
+                        type_name: get_text(&child, source).unwrap_or_default().to_string(),
+                        visibility: std::mem::take(&mut visibility),
+                        default_value: None,
+                    }),
+                }
+            }
+        }
+        _ => {}
+    }
+    fields
+}
+
 fn extract_import(node: &tree_sitter::Node, source: &str, file_path: &str) -> Option<ImportNode> {
     let path = get_text(node, source)?;
     let path = path
@@ -235,7 +367,7 @@ fn extract_impl(
     let for_type = get_impl_type(node, source);
     let trait_name = get_impl_trait(node, source);
 
-    if let Some(for_type) = for_type {
+    if let Some(ref for_type) = for_type {
         let generics = extract_rust_type_parameters(node, source);
 
         parsed.impl_blocks.push(ImplNode {
@@ -246,6 +378,7 @@ fn extract_impl(
             file_path: file_path.to_string(),
             line_start: node.start_position().row as u32 + 1,
             line_end: node.end_position().row as u32 + 1,
+            derived: false,
         });
     }
 
@@ -255,7 +388,8 @@ fn extract_impl(
             if child.kind() == "function_item" {
                 if let Some(func) = extract_function(&child, source, file_path) {
                     let func_id = format!("{}:{}:{}", file_path, func.name, func.line_start);
-                    let calls = extract_function_calls(&child, source, &func_id);
+                    let calls =
+                        extract_function_calls(&child, source, &func_id, for_type.as_deref());
                     parsed.function_calls.extend(calls);
                     parsed.symbols.push(func.name.clone());
                     parsed.functions.push(func);
@@ -328,21 +462,237 @@ fn get_impl_trait(node: &tree_sitter::Node, source: &str) -> Option<String> {
     None
 }
 
+/// Extract the calls made by a function. `self_type` is the type of the
+/// enclosing impl block, if any.
 fn extract_function_calls(
     node: &tree_sitter::Node,
     source: &str,
     caller_id: &str,
+    self_type: Option<&str>,
 ) -> Vec<FunctionCall> {
+    let mut bindings = Bindings::default();
+    if let Some(params) = node.child_by_field_name("parameters") {
+        for param in params.children(&mut params.walk()) {
+            if param.kind() != "parameter" {
+                continue;
+            }
+            if let Some(name) = param
+                .child_by_field_name("pattern")
+                .filter(|p| p.kind() == "identifier")
+                .and_then(|p| get_text(&p, source))
+            {
+                let type_name = param
+                    .child_by_field_name("type")
+                    .and_then(|t| receiver_type_name(&t, source));
+                bindings.bind(name, type_name);
+            }
+        }
+    }
+    if let Some(body) = node.child_by_field_name("body") {
+        collect_let_bindings(&body, source, self_type, &mut bindings);
+    }
+
+    let context = CallContext {
+        source,
+        caller_id,
+        self_type,
+        bindings: &bindings,
+    };
     let mut calls = Vec::new();
     let mut cursor = node.walk();
-    extract_calls_recursive(&mut cursor, source, caller_id, &mut calls);
+    extract_calls_recursive(&mut cursor, &context, &mut calls);
     calls
 }
 
+/// What the calls of a function are resolved against.
+struct CallContext<'a> {
+    source: &'a str,
+    caller_id: &'a str,
+    self_type: Option<&'a str>,
+    bindings: &'a Bindings,
+}
+
+/// Types of a function's local names, from parameter annotations and `let`
+/// statements. A name bound more than once to different (or unknown) types
+/// is ambiguous and has none: the bindings ignore scopes.
+#[derive(Default)]
+struct Bindings(std::collections::HashMap<String, Option<String>>);
+
+impl Bindings {
+    fn bind(&mut self, name: &str, type_name: Option<String>) {
+        match self.0.get_mut(name) {
+            Some(existing) if *existing != type_name => *existing = None,
+            Some(_) => {}
+            None => {
+                self.0.insert(name.to_string(), type_name);
+            }
+        }
+    }
+
+    fn type_of(&self, name: &str) -> Option<&str> {
+        self.0.get(name).and_then(|t| t.as_deref())
+    }
+}
+
+fn collect_let_bindings(
+    node: &tree_sitter::Node,
+    source: &str,
+    self_type: Option<&str>,
+    bindings: &mut Bindings,
+) {
+    match node.kind() {
+        "let_declaration" => {
+            if let Some(name) = node
+                .child_by_field_name("pattern")
+                .filter(|p| p.kind() == "identifier")
+                .and_then(|p| get_text(&p, source))
+            {
+                let type_name = match node.child_by_field_name("type") {
+                    Some(t) => receiver_type_name(&t, source),
+                    None => node
+                        .child_by_field_name("value")
+                        .and_then(|v| value_type_name(&v, source, self_type)),
+                };
+                bindings.bind(name, type_name);
+            }
+        }
+        "closure_parameters" => {
+            // Closure parameters shadow the function's names
+            for param in node.named_children(&mut node.walk()) {
+                let pattern = match param.kind() {
+                    "identifier" => Some(param),
+                    "parameter" => param.child_by_field_name("pattern"),
+                    _ => None,
+                };
+                if let Some(name) = pattern.and_then(|p| get_text(&p, source)) {
+                    bindings.bind(name, None);
+                }
+            }
+        }
+        _ => {}
+    }
+    for child in node.named_children(&mut node.walk()) {
+        collect_let_bindings(&child, source, self_type, bindings);
+    }
+}
+
+/// Name of the type whose methods a value of the annotated type exposes:
+/// references and `Box`/`Rc`/`Arc` are looked through, paths and generic
+/// arguments dropped (`&mut crate::db::Pool<T>` → `Pool`).
+fn receiver_type_name(node: &tree_sitter::Node, source: &str) -> Option<String> {
+    match node.kind() {
+        "type_identifier" | "primitive_type" => get_text(node, source).map(|s| s.to_string()),
+        "scoped_type_identifier" => get_field_text(node, "name", source),
+        "reference_type" => node
+            .child_by_field_name("type")
+            .and_then(|t| receiver_type_name(&t, source)),
+        "generic_type" => {
+            let outer = node
+                .child_by_field_name("type")
+                .and_then(|t| receiver_type_name(&t, source))?;
+            if matches!(outer.as_str(), "Box" | "Rc" | "Arc") {
+                node.child_by_field_name("type_arguments")
+                    .and_then(|args| args.named_child(0))
+                    .and_then(|inner| receiver_type_name(&inner, source))
+            } else {
+                Some(outer)
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Type of a `let` value when it is obvious from the expression: a struct
+/// literal, or a constructor named after the Rust conventions (`new`,
+/// `new_*`, `with_*`, `from*`, `default`) — `?` and `.await` included.
+fn value_type_name(
+    node: &tree_sitter::Node,
+    source: &str,
+    self_type: Option<&str>,
+) -> Option<String> {
+    match node.kind() {
+        "struct_expression" => {
+            let name = node.child_by_field_name("name")?;
+            match get_text(&name, source)? {
+                "Self" => self_type.map(|t| t.to_string()),
+                _ => receiver_type_name(&name, source),
+            }
+        }
+        "call_expression" => {
+            let (type_name, function) =
+                associated_call(&node.child_by_field_name("function")?, source, self_type)?;
+            let constructor = function == "new"
+                || function == "default"
+                || function.starts_with("new_")
+                || function.starts_with("with_")
+                || function.starts_with("from");
+            constructor.then_some(type_name)
+        }
+        "try_expression" | "await_expression" | "parenthesized_expression" => node
+            .named_child(0)
+            .and_then(|inner| value_type_name(&inner, source, self_type)),
+        _ => None,
+    }
+}
+
+/// `(type, function)` of a `Type::function` path whose prefix is a single
+/// type name (`Self` being the enclosing impl type).
+fn associated_call(
+    function: &tree_sitter::Node,
+    source: &str,
+    self_type: Option<&str>,
+) -> Option<(String, String)> {
+    if function.kind() != "scoped_identifier" {
+        return None;
+    }
+    let path = function.child_by_field_name("path")?;
+    let name = get_field_text(function, "name", source)?;
+    let type_name = match (path.kind(), get_text(&path, source)?) {
+        (_, "Self") => self_type?.to_string(),
+        ("identifier", text) if text.starts_with(|c: char| c.is_ascii_uppercase()) => {
+            text.to_string()
+        }
+        _ => return None,
+    };
+    Some((type_name, name))
+}
+
+/// Callee name and, when it can be inferred, receiver type of a call.
+///
+/// Method calls (`x.save()`) are typed from `self` or from the bindings of
+/// the receiver; associated function calls (`Type::new()`, `Self::load()`)
+/// by their path. Other paths (`fs::read`) keep their full text.
+fn resolve_callee(
+    function: &tree_sitter::Node,
+    context: &CallContext,
+) -> Option<(String, Option<String>)> {
+    let source = context.source;
+    match function.kind() {
+        "identifier" => get_text(function, source).map(|s| (s.to_string(), None)),
+        "field_expression" => {
+            let name = get_field_text(function, "field", source)?;
+            let receiver = function.child_by_field_name("value");
+            let receiver_type = match receiver.as_ref().map(|r| r.kind()) {
+                Some("self") => context.self_type.map(|t| t.to_string()),
+                Some("identifier") => receiver
+                    .and_then(|r| get_text(&r, source))
+                    .and_then(|r| context.bindings.type_of(r))
+                    .map(|t| t.to_string()),
+                _ => None,
+            };
+            Some((name, receiver_type))
+        }
+        "scoped_identifier" => match associated_call(function, source, context.self_type) {
+            Some((type_name, name)) => Some((name, Some(type_name))),
+            None => get_text(function, source).map(|s| (s.to_string(), None)),
+        },
+        _ => None,
+    }
+}
+
 fn extract_calls_recursive(
     cursor: &mut tree_sitter::TreeCursor,
-    source: &str,
-    caller_id: &str,
+    context: &CallContext,
     calls: &mut Vec<FunctionCall>,
 ) {
     loop {
@@ -350,24 +700,15 @@ fn extract_calls_recursive(
 
         if node.kind() == "call_expression" {
             if let Some(func) = node.child_by_field_name("function") {
-                let callee_name = match func.kind() {
-                    "identifier" => get_text(&func, source).map(|s| s.to_string()),
-                    "field_expression" => func
-                        .child_by_field_name("field")
-                        .and_then(|f| get_text(&f, source))
-                        .map(|s| s.to_string()),
-                    "scoped_identifier" => get_text(&func, source).map(|s| s.to_string()),
-                    _ => None,
-                };
-
-                if let Some(callee) = callee_name {
+                if let Some((callee, receiver_type)) = resolve_callee(&func, context) {
                     if !crate::parser::noise_filter::is_builtin_call(&callee) {
                         calls.push(FunctionCall {
-                            caller_id: caller_id.to_string(),
+                            caller_id: context.caller_id.to_string(),
                             callee_name: callee,
                             line: node.start_position().row as u32 + 1,
                             confidence: 0.50,
                             reason: "unscored".to_string(),
+                            receiver_type,
                         });
                     }
                 }
@@ -375,7 +716,7 @@ fn extract_calls_recursive(
         }
 
         if cursor.goto_first_child() {
-            extract_calls_recursive(cursor, source, caller_id, calls);
+            extract_calls_recursive(cursor, context, calls);
             cursor.goto_parent();
         }
 
@@ -411,23 +752,26 @@ fn extract_params(node: &tree_sitter::Node, source: &str) -> Vec<Parameter> {
 fn get_visibility(node: &tree_sitter::Node, source: &str) -> Visibility {
     for child in node.children(&mut node.walk()) {
         if child.kind() == "visibility_modifier" {
-            let text = get_text(&child, source).unwrap_or_default();
-            return match text {
-                "pub" => Visibility::Public,
-                s if s.starts_with("pub(crate)") => Visibility::Crate,
-                s if s.starts_with("pub(super)") => Visibility::Super,
-                s if s.starts_with("pub(in") => Visibility::InPath(
-                    s.trim_start_matches("pub(in ")
-                        .trim_end_matches(')')
-                        .to_string(),
-                ),
-                _ => Visibility::Private,
-            };
+            return parse_visibility(get_text(&child, source).unwrap_or_default());
         }
     }
     Visibility::Private
 }
 
+fn parse_visibility(text: &str) -> Visibility {
+    match text {
+        "pub" => Visibility::Public,
+        s if s.starts_with("pub(crate)") => Visibility::Crate,
+        s if s.starts_with("pub(super)") => Visibility::Super,
+        s if s.starts_with("pub(in") => Visibility::InPath(
+            s.trim_start_matches("pub(in ")
+                .trim_end_matches(')')
+                .to_string(),
+        ),
+        _ => Visibility::Private,
+    }
+}
+
 fn has_modifier(node: &tree_sitter::Node, source: &str, modifier: &str) -> bool {
     for child in node.children(&mut node.walk()) {
         if child.kind() == "function_modifiers" {
@@ -494,6 +838,9 @@ fn get_rust_docstring(node: &tree_sitter::Node, source: &str) -> Option<String>
     }
 }
 
+/// Traits derived by the attributes preceding an item, from `#[derive(..)]`
+/// and `#[cfg_attr(<predicate>, derive(..))]`, paths kept as written
+/// (`serde::Serialize`).
 fn extract_derive_traits(node: &tree_sitter::Node, source: &str) -> Vec<String> {
     let mut traits = Vec::new();
     let mut prev = node.prev_sibling();
@@ -501,19 +848,7 @@ fn extract_derive_traits(node: &tree_sitter::Node, source: &str) -> Vec<String>
     while let Some(sibling) = prev {
         if sibling.kind() == "attribute_item" {
             if let Some(attr_text) = get_text(&sibling, source) {
-                if attr_text.starts_with("#[derive(") {
-                    if let Some(start) = attr_text.find('(') {
-                        if let Some(end) = attr_text.rfind(')') {
-                            let traits_str = &attr_text[start + 1..end];
-                            for trait_name in traits_str.split(',') {
-                                let name = trait_name.trim();
-                                if !name.is_empty() {
-                                    traits.push(name.to_string());
-                                }
-                            }
-                        }
-                    }
-                }
+                traits.extend(derived_traits(attr_text));
             }
         } else if sibling.kind() != "line_comment" && sibling.kind() != "block_comment" {
             break;
@@ -524,6 +859,43 @@ fn extract_derive_traits(node: &tree_sitter::Node, source: &str) -> Vec<String>
     traits
 }
 
+/// Trait names listed by the `derive(..)` of an attribute's text.
+fn derived_traits(attr_text: &str) -> Vec<String> {
+    let compact: String = attr_text.chars().filter(|c| !c.is_whitespace()).collect();
+    let Some(inner) = compact
+        .strip_prefix("#[")
+        .and_then(|rest| rest.strip_suffix(']'))
+    else {
+        return vec![];
+    };
+
+    let lists: Vec<&str> = if let Some(list) = inner.strip_prefix("derive(") {
+        vec![list]
+    } else if inner.starts_with("cfg_attr(") {
+        // derive(..) is one of the attributes after the predicate
+        inner
+            .match_indices(",derive(")
+            .map(|(i, m)| &inner[i + m.len()..])
+            .collect()
+    } else {
+        vec![]
+    };
+
+    let mut traits = Vec::new();
+    for list in lists {
+        let Some(end) = list.find(')') else {
+            continue;
+        };
+        traits.extend(
+            list[..end]
+                .split(',')
+                .filter(|name| !name.is_empty())
+                .map(|name| name.to_string()),
+        );
+    }
+    traits
+}
+
 fn extract_rust_type_parameters(node: &tree_sitter::Node, source: &str) -> Vec<String> {
     let mut generics = Vec::new();
 
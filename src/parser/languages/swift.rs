@@ -162,6 +162,7 @@ fn extract_class(node: &tree_sitter::Node, source: &str, file_path: &str) -> Opt
         docstring,
         parent_class,
         interfaces,
+        fields: vec![],
     })
 }
 
@@ -182,6 +183,7 @@ fn extract_struct(node: &tree_sitter::Node, source: &str, file_path: &str) -> Op
         docstring,
         parent_class,
         interfaces,
+        fields: vec![],
     })
 }
 
@@ -232,6 +234,7 @@ fn extract_enum(node: &tree_sitter::Node, source: &str, file_path: &str) -> Opti
         line_start: node.start_position().row as u32 + 1,
         line_end: node.end_position().row as u32 + 1,
         docstring,
+        variant_payloads: vec![],
     })
 }
 
@@ -254,6 +257,7 @@ fn extract_extension(
             file_path: file_path.to_string(),
             line_start: node.start_position().row as u32 + 1,
             line_end: node.end_position().row as u32 + 1,
+            derived: false,
         });
     }
 
@@ -210,6 +210,7 @@ fn extract_type_decl(
         docstring: get_zig_doc(node, source),
         parent_class: None,
         interfaces: vec![],
+        fields: vec![],
     })
 }
 
@@ -243,6 +244,7 @@ fn extract_enum_decl(node: &tree_sitter::Node, source: &str, file_path: &str) ->
         line_start: node.start_position().row as u32 + 1,
         line_end: node.end_position().row as u32 + 1,
         docstring: get_zig_doc(node, source),
+        variant_payloads: vec![],
     })
 }
 
@@ -0,0 +1,717 @@
+//! Markdown documentation parser.
+//!
+//! Splits README and documentation files into heading sections so agents can
+//! cite the project's own docs: each section keeps its heading hierarchy,
+//! GitHub-style anchor, line range, fenced code blocks and links. Text before
+//! the first heading becomes a level-0 preamble section.
+//!
+//! The parser is line-based and deliberately shallow — it understands ATX and
+//! setext headings, fenced code blocks, YAML front matter, inline/reference
+//! links and autolinks, and ignores the rest of CommonMark (tables, HTML,
+//! indented code).
+
+use crate::meilisearch::indexes::DocSectionDocument;
+use crate::neo4j::models::DocSectionNode;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+/// File extensions indexed as documentation
+pub const MARKDOWN_EXTENSIONS: &[&str] = &["md", "markdown", "mdx"];
+
+/// Characters of body text kept as a section summary in the graph
+const SUMMARY_CHARS: usize = 300;
+
+/// Whether a path is a Markdown document
+pub fn is_markdown_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| MARKDOWN_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// A parsed Markdown document
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedDocument {
+    /// Front matter `title`, else the first level-1 heading
+    pub title: Option<String>,
+    pub sections: Vec<ParsedSection>,
+}
+
+/// A heading and the content up to the next heading
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedSection {
+    /// Heading text (empty for the preamble)
+    pub heading: String,
+    /// 1-6, or 0 for the preamble
+    pub level: u8,
+    /// GitHub-style anchor, unique within the document
+    pub anchor: String,
+    /// Anchor of the closest enclosing heading
+    pub parent_anchor: Option<String>,
+    /// 1-based, inclusive
+    pub line_start: usize,
+    pub line_end: usize,
+    /// Body text without the heading line
+    pub text: String,
+    pub code_blocks: Vec<CodeBlock>,
+    pub links: Vec<DocLink>,
+}
+
+/// A fenced code block
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodeBlock {
+    /// First word of the info string (```rust)
+    pub language: Option<String>,
+    pub content: String,
+    pub line: usize,
+}
+
+/// A link found in a section
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocLink {
+    pub text: String,
+    pub target: String,
+    pub line: usize,
+}
+
+impl DocLink {
+    /// URL with a scheme (`https:`, `mailto:`) rather than a path
+    pub fn is_external(&self) -> bool {
+        let target = self.target.as_str();
+        target.contains("://") || target.starts_with("mailto:") || target.starts_with("tel:")
+    }
+
+    /// Resolve the target against the document path: URLs are kept, relative
+    /// paths become absolute (fragment dropped), in-page anchors give `None`.
+    pub fn resolve(&self, document_path: &str) -> Option<String> {
+        if self.is_external() {
+            return Some(self.target.clone());
+        }
+        let path = self.target.split(['#', '?']).next().unwrap_or_default();
+        if path.is_empty() {
+            return None;
+        }
+        let base = if path.starts_with('/') {
+            PathBuf::from(path)
+        } else {
+            Path::new(document_path)
+                .parent()
+                .unwrap_or(Path::new(""))
+                .join(path)
+        };
+        Some(normalize_lexically(&base).to_string_lossy().to_string())
+    }
+}
+
+/// Resolve `.` and `..` without touching the filesystem
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// GitHub heading anchor: lowercase, punctuation dropped, spaces to hyphens.
+pub fn heading_anchor(heading: &str) -> String {
+    plain_text(heading)
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Strip inline markup: links keep their text, code/emphasis markers go.
+fn plain_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '`' | '*' | '~' => i += 1,
+            '!' if chars.get(i + 1) == Some(&'[') => i += 1,
+            '[' => {
+                if let Some((label, _, next)) = inline_link_at(&chars, i) {
+                    out.push_str(&label);
+                    i = next;
+                } else {
+                    out.push('[');
+                    i += 1;
+                }
+            }
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    out.trim().to_string()
+}
+
+/// `[label](target "title")` starting at `start`: (label, target, index after).
+fn inline_link_at(chars: &[char], start: usize) -> Option<(String, String, usize)> {
+    let mut depth = 0;
+    let mut close = None;
+    for (j, c) in chars.iter().enumerate().skip(start) {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(j);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let close = close?;
+    if chars.get(close + 1) != Some(&'(') {
+        return None;
+    }
+    let end = chars[close + 2..].iter().position(|c| *c == ')')? + close + 2;
+    let label: String = chars[start + 1..close].iter().collect();
+    let inside: String = chars[close + 2..end].iter().collect();
+    let target = inside
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches('<')
+        .trim_end_matches('>')
+        .to_string();
+    Some((label, target, end + 1))
+}
+
+/// Links of one line (inline code spans ignored).
+fn extract_links(line: &str, line_no: usize, links: &mut Vec<DocLink>) {
+    // Reference definition: `[label]: target`
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('[') {
+        if let Some((label, rest)) = trimmed[1..].split_once("]:") {
+            if let Some(target) = rest.split_whitespace().next() {
+                links.push(DocLink {
+                    text: label.to_string(),
+                    target: target
+                        .trim_start_matches('<')
+                        .trim_end_matches('>')
+                        .to_string(),
+                    line: line_no,
+                });
+                return;
+            }
+        }
+    }
+
+    let chars: Vec<char> = line.chars().collect();
+    let mut i = 0;
+    let mut in_code = false;
+    while i < chars.len() {
+        match chars[i] {
+            '`' => {
+                in_code = !in_code;
+                i += 1;
+            }
+            _ if in_code => i += 1,
+            '!' if chars.get(i + 1) == Some(&'[') => {
+                // Image: skip its source
+                i = inline_link_at(&chars, i + 1).map_or(i + 2, |(_, _, next)| next);
+            }
+            '[' => match inline_link_at(&chars, i) {
+                Some((label, target, next)) => {
+                    if !target.is_empty() {
+                        links.push(DocLink {
+                            text: plain_text(&label),
+                            target,
+                            line: line_no,
+                        });
+                    }
+                    i = next;
+                }
+                None => i += 1,
+            },
+            '<' => {
+                let rest: String = chars[i + 1..].iter().collect();
+                match rest.split_once('>') {
+                    Some((url, _)) if url.starts_with("http://") || url.starts_with("https://") => {
+                        links.push(DocLink {
+                            text: url.to_string(),
+                            target: url.to_string(),
+                            line: line_no,
+                        });
+                        i += url.chars().count() + 2;
+                    }
+                    _ => i += 1,
+                }
+            }
+            _ => i += 1,
+        }
+    }
+}
+
+/// `# Heading` → (level, text)
+fn atx_heading(line: &str) -> Option<(u8, String)> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+    let rest = &line[indent..];
+    let level = rest.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let text = &rest[level..];
+    if !text.is_empty() && !text.starts_with([' ', '\t']) {
+        return None;
+    }
+    // Optional closing sequence: `## Title ##`
+    let text = text.trim();
+    let text = match text.trim_end_matches('#') {
+        stripped if stripped.is_empty() || stripped.ends_with(' ') => stripped.trim_end(),
+        _ => text,
+    };
+    Some((level as u8, text.to_string()))
+}
+
+/// Setext underline level (`===` → 1, `---` → 2)
+fn setext_level(line: &str) -> Option<u8> {
+    let t = line.trim();
+    if t.is_empty() || line.len() - line.trim_start().len() > 3 {
+        return None;
+    }
+    if t.chars().all(|c| c == '=') {
+        Some(1)
+    } else if t.chars().all(|c| c == '-') {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+/// Opening/closing code fence: (fence char, length, info string)
+fn code_fence(line: &str) -> Option<(char, usize, &str)> {
+    let t = line.trim_start();
+    if line.len() - t.len() > 3 {
+        return None;
+    }
+    let c = t.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = t.chars().take_while(|x| *x == c).count();
+    (len >= 3).then(|| (c, len, t[len..].trim()))
+}
+
+/// Parse a Markdown document into sections.
+pub fn parse_markdown(content: &str) -> ParsedDocument {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut doc = ParsedDocument::default();
+    let mut start = 0;
+
+    // YAML front matter
+    if lines.first().map(|l| l.trim()) == Some("---") {
+        if let Some(end) = lines.iter().skip(1).position(|l| l.trim() == "---") {
+            for line in &lines[1..=end] {
+                if let Some(title) = line.strip_prefix("title:") {
+                    let title = title.trim().trim_matches(['"', '\'']).trim();
+                    if !title.is_empty() {
+                        doc.title = Some(title.to_string());
+                    }
+                }
+            }
+            start = end + 2;
+        }
+    }
+
+    let mut anchors_seen: HashMap<String, usize> = HashMap::new();
+    let mut stack: Vec<(u8, String)> = Vec::new();
+    let mut current = ParsedSection {
+        line_start: start + 1,
+        ..Default::default()
+    };
+    let mut body: Vec<&str> = Vec::new();
+    let mut fence: Option<(char, usize, Option<String>, usize, Vec<&str>)> = None;
+
+    let mut i = start;
+    while i < lines.len() {
+        let line = lines[i];
+        let line_no = i + 1;
+
+        // Inside a fenced code block: headings and links are literal
+        if let Some((c, len, lang, fence_line, mut code)) = fence.take() {
+            match code_fence(line) {
+                Some((cc, cl, info)) if cc == c && cl >= len && info.is_empty() => {
+                    current.code_blocks.push(CodeBlock {
+                        language: lang,
+                        content: code.join("\n"),
+                        line: fence_line,
+                    });
+                }
+                _ => {
+                    code.push(line);
+                    fence = Some((c, len, lang, fence_line, code));
+                }
+            }
+            body.push(line);
+            i += 1;
+            continue;
+        }
+        if let Some((c, len, info)) = code_fence(line) {
+            let lang = info.split_whitespace().next().map(str::to_string);
+            fence = Some((c, len, lang, line_no, Vec::new()));
+            body.push(line);
+            i += 1;
+            continue;
+        }
+
+        let heading = atx_heading(line).map(|(l, t)| (l, t, 1)).or_else(|| {
+            let next = lines.get(i + 1)?;
+            let level = setext_level(next)?;
+            let text = line.trim();
+            let is_paragraph = !text.is_empty()
+                && body.last().is_none_or(|prev| prev.trim().is_empty())
+                && !text.starts_with(['-', '*', '+', '>', '|', '<']);
+            is_paragraph.then(|| (level, text.to_string(), 2))
+        });
+
+        let Some((level, text, consumed)) = heading else {
+            extract_links(line, line_no, &mut current.links);
+            body.push(line);
+            i += 1;
+            continue;
+        };
+
+        // Close the current section
+        finish_section(&mut current, &body, line_no - 1, &mut doc);
+        body.clear();
+
+        let base = heading_anchor(&text);
+        let seen = anchors_seen.entry(base.clone()).or_insert(0);
+        let anchor = if *seen == 0 {
+            base
+        } else {
+            format!("{}-{}", base, seen)
+        };
+        *seen += 1;
+
+        while stack.last().is_some_and(|(l, _)| *l >= level) {
+            stack.pop();
+        }
+        if level == 1 && doc.title.is_none() {
+            doc.title = Some(plain_text(&text));
+        }
+
+        current = ParsedSection {
+            heading: plain_text(&text),
+            level,
+            anchor: anchor.clone(),
+            parent_anchor: stack.last().map(|(_, a)| a.clone()),
+            line_start: line_no,
+            ..Default::default()
+        };
+        extract_links(line, line_no, &mut current.links);
+        stack.push((level, anchor));
+        i += consumed;
+    }
+
+    // Unterminated fence runs to the end of the document
+    if let Some((_, _, lang, fence_line, code)) = fence {
+        current.code_blocks.push(CodeBlock {
+            language: lang,
+            content: code.join("\n"),
+            line: fence_line,
+        });
+    }
+    finish_section(&mut current, &body, lines.len(), &mut doc);
+    doc
+}
+
+fn finish_section(
+    section: &mut ParsedSection,
+    body: &[&str],
+    line_end: usize,
+    doc: &mut ParsedDocument,
+) {
+    let text = body.join("\n").trim().to_string();
+    // Empty preamble (document starts with a heading) is dropped
+    if section.level == 0 && text.is_empty() {
+        return;
+    }
+    section.text = text;
+    // Trailing blank lines belong to no section
+    let trailing_blank = body
+        .iter()
+        .rev()
+        .take_while(|l| l.trim().is_empty())
+        .count();
+    section.line_end = line_end
+        .saturating_sub(trailing_blank)
+        .max(section.line_start);
+    doc.sections.push(std::mem::take(section));
+}
+
+/// First paragraph of prose (code blocks skipped), truncated.
+fn summarize(section: &ParsedSection) -> String {
+    let mut in_code = false;
+    let mut summary = String::new();
+    for line in section.text.lines() {
+        if code_fence(line).is_some() {
+            in_code = !in_code;
+            continue;
+        }
+        let line = line.trim();
+        if in_code || (line.is_empty() && !summary.is_empty()) {
+            if !summary.is_empty() {
+                break;
+            }
+            continue;
+        }
+        if !line.is_empty() {
+            if !summary.is_empty() {
+                summary.push(' ');
+            }
+            summary.push_str(line);
+        }
+    }
+    let summary = plain_text(&summary);
+    if summary.chars().count() > SUMMARY_CHARS {
+        let cut: String = summary.chars().take(SUMMARY_CHARS).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        summary
+    }
+}
+
+fn code_languages(section: &ParsedSection) -> Vec<String> {
+    let mut langs: Vec<String> = section
+        .code_blocks
+        .iter()
+        .filter_map(|b| b.language.as_ref().map(|l| l.to_lowercase()))
+        .collect();
+    langs.sort();
+    langs.dedup();
+    langs
+}
+
+fn resolved_links(section: &ParsedSection, document_path: &str) -> Vec<String> {
+    let mut links: Vec<String> = section
+        .links
+        .iter()
+        .filter_map(|l| l.resolve(document_path))
+        .collect();
+    links.sort();
+    links.dedup();
+    links
+}
+
+/// Graph nodes for the sections of a document.
+pub fn to_section_nodes(document_path: &str, doc: &ParsedDocument) -> Vec<DocSectionNode> {
+    doc.sections
+        .iter()
+        .enumerate()
+        .map(|(ordinal, s)| DocSectionNode {
+            id: format!("{}#{}", document_path, s.anchor),
+            document_path: document_path.to_string(),
+            ordinal,
+            heading: s.heading.clone(),
+            level: s.level,
+            anchor: s.anchor.clone(),
+            parent_anchor: s.parent_anchor.clone(),
+            line_start: s.line_start,
+            line_end: s.line_end,
+            summary: summarize(s),
+            code_languages: code_languages(s),
+            links: resolved_links(s, document_path),
+        })
+        .collect()
+}
+
+/// Search documents for the sections of a document (one per section).
+pub fn to_search_documents(
+    document_path: &str,
+    doc: &ParsedDocument,
+    project_id: &str,
+    project_slug: &str,
+) -> Vec<DocSectionDocument> {
+    doc.sections
+        .iter()
+        .map(|s| {
+            let id = format!("{}#{}", document_path, s.anchor);
+            DocSectionDocument {
+                id: crate::meilisearch::client::MeiliClient::path_to_id(&id),
+                path: document_path.to_string(),
+                title: doc.title.clone().unwrap_or_default(),
+                heading: s.heading.clone(),
+                anchor: s.anchor.clone(),
+                level: s.level,
+                line_start: s.line_start,
+                content: s.text.clone(),
+                code_languages: code_languages(s),
+                project_id: project_id.to_string(),
+                project_slug: project_slug.to_string(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const README: &str = r#"---
+layout: page
+---
+Intro paragraph before any heading.
+
+# Project Orchestrator
+
+Coordinates agents. See [the guide](docs/guide.md#setup) and <https://example.com>.
+
+## Install
+
+```bash
+# not a heading
+cargo install po
+```
+
+Usage
+-----
+
+Run `[not](a-link)` then read [API](../api/README.md "API docs").
+
+![logo](assets/logo.png)
+
+## Install
+
+Duplicate heading.
+
+### Notes ###
+
+[ref]: https://docs.rs/po
+"#;
+
+    #[test]
+    fn test_parse_markdown_sections() {
+        let doc = parse_markdown(README);
+        assert_eq!(doc.title.as_deref(), Some("Project Orchestrator"));
+
+        let headings: Vec<(&str, u8, &str, Option<&str>)> = doc
+            .sections
+            .iter()
+            .map(|s| {
+                (
+                    s.heading.as_str(),
+                    s.level,
+                    s.anchor.as_str(),
+                    s.parent_anchor.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            headings,
+            vec![
+                ("", 0, "", None),
+                ("Project Orchestrator", 1, "project-orchestrator", None),
+                ("Install", 2, "install", Some("project-orchestrator")),
+                ("Usage", 2, "usage", Some("project-orchestrator")),
+                ("Install", 2, "install-1", Some("project-orchestrator")),
+                ("Notes", 3, "notes", Some("install-1")),
+            ]
+        );
+
+        let preamble = &doc.sections[0];
+        assert_eq!(preamble.text, "Intro paragraph before any heading.");
+        assert_eq!(preamble.line_start, 4);
+
+        let install = &doc.sections[2];
+        assert_eq!(install.code_blocks.len(), 1);
+        assert_eq!(install.code_blocks[0].language.as_deref(), Some("bash"));
+        assert!(install.code_blocks[0].content.contains("# not a heading"));
+        assert_eq!(install.line_start, 10);
+        assert_eq!(install.line_end, 15);
+    }
+
+    #[test]
+    fn test_parse_markdown_links() {
+        let doc = parse_markdown(README);
+        let targets = |i: usize| -> Vec<String> {
+            doc.sections[i]
+                .links
+                .iter()
+                .map(|l| l.target.clone())
+                .collect()
+        };
+        assert_eq!(
+            targets(1),
+            vec!["docs/guide.md#setup", "https://example.com"]
+        );
+        // Inline code and images are not links
+        assert_eq!(targets(3), vec!["../api/README.md"]);
+        assert_eq!(doc.sections[3].links[0].text, "API");
+        assert_eq!(targets(5), vec!["https://docs.rs/po"]);
+    }
+
+    #[test]
+    fn test_section_nodes_resolve_links() {
+        let doc = parse_markdown(README);
+        let nodes = to_section_nodes("/repo/docs/README.md", &doc);
+        assert_eq!(nodes[1].id, "/repo/docs/README.md#project-orchestrator");
+        assert_eq!(
+            nodes[1].links,
+            vec!["/repo/docs/docs/guide.md", "https://example.com"]
+        );
+        assert_eq!(nodes[3].links, vec!["/repo/api/README.md"]);
+        assert_eq!(nodes[2].code_languages, vec!["bash"]);
+        assert_eq!(
+            nodes[1].summary,
+            "Coordinates agents. See the guide and <https://example.com>."
+        );
+
+        let search = to_search_documents("/repo/docs/README.md", &doc, "pid", "po");
+        assert_eq!(search.len(), nodes.len());
+        assert_eq!(search[2].title, "Project Orchestrator");
+        assert_ne!(search[2].id, search[4].id);
+    }
+
+    #[test]
+    fn test_heading_anchor() {
+        assert_eq!(heading_anchor("Getting Started"), "getting-started");
+        assert_eq!(heading_anchor("`cargo` & Rust: v1.2!"), "cargo--rust-v12");
+        assert_eq!(heading_anchor("See [docs](x.md)"), "see-docs");
+        assert_eq!(heading_anchor("snake_case API"), "snake_case-api");
+    }
+
+    #[test]
+    fn test_parse_markdown_edge_cases() {
+        // Thematic break after a blank line is not a setext heading
+        let doc = parse_markdown("# A\n\ntext\n\n---\n\nmore\n");
+        assert_eq!(doc.sections.len(), 1);
+        assert!(doc.sections[0].text.contains("more"));
+
+        // Not headings
+        let doc = parse_markdown("#hashtag\n    # indented code\n");
+        assert_eq!(doc.sections.len(), 1);
+        assert_eq!(doc.sections[0].level, 0);
+        assert!(doc.title.is_none());
+
+        // Front matter title wins, unterminated fence keeps its code
+        let doc = parse_markdown("---\ntitle: \"Guide\"\n---\n# Other\n~~~\ncode");
+        assert_eq!(doc.title.as_deref(), Some("Guide"));
+        assert_eq!(doc.sections[0].code_blocks[0].content, "code");
+
+        assert!(parse_markdown("").sections.is_empty());
+    }
+
+    #[test]
+    fn test_is_markdown_path() {
+        assert!(is_markdown_path(Path::new("/repo/README.md")));
+        assert!(is_markdown_path(Path::new("/repo/docs/guide.MDX")));
+        assert!(!is_markdown_path(Path::new("/repo/src/main.rs")));
+        assert!(!is_markdown_path(Path::new("/repo/Makefile")));
+    }
+}
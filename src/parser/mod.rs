@@ -2,10 +2,19 @@
 //!
 //! Supports multiple programming languages with full AST extraction.
 
+pub mod adr;
 pub mod ast_cache;
+pub mod clones;
+pub mod config_keys;
+pub mod contracts;
+pub mod docker;
+pub mod encoding;
 pub mod helpers;
 pub mod languages;
+pub mod markdown;
 pub mod noise_filter;
+pub mod plugins;
+pub mod sql;
 
 use crate::meilisearch::indexes::CodeDocument;
 use crate::neo4j::models::*;
@@ -35,11 +44,19 @@ pub enum SupportedLanguage {
     Zig,
     Hcl,
     Dart,
+    /// Grammar loaded at runtime (see [`plugins`])
+    Plugin(&'static plugins::PluginGrammar),
 }
 
 impl SupportedLanguage {
     /// Detect language from file extension
     pub fn from_extension(ext: &str) -> Option<Self> {
+        Self::builtin_from_extension(ext)
+            .or_else(|| plugins::grammar_for_extension(ext).map(Self::Plugin))
+    }
+
+    /// Detect a built-in language from file extension
+    pub fn builtin_from_extension(ext: &str) -> Option<Self> {
         match ext.to_lowercase().as_str() {
             "rs" => Some(Self::Rust),
             "ts" | "tsx" => Some(Self::TypeScript),
@@ -83,6 +100,7 @@ impl SupportedLanguage {
             Self::Zig => tree_sitter_zig::LANGUAGE.into(),
             Self::Hcl => tree_sitter_hcl::LANGUAGE.into(),
             Self::Dart => tree_sitter_dart::LANGUAGE.into(),
+            Self::Plugin(grammar) => grammar.language(),
         }
     }
 
@@ -106,10 +124,11 @@ impl SupportedLanguage {
             Self::Zig => "zig",
             Self::Hcl => "hcl",
             Self::Dart => "dart",
+            Self::Plugin(grammar) => grammar.name.as_str(),
         }
     }
 
-    /// Get all supported languages
+    /// Get all built-in languages
     pub fn all() -> &'static [Self] {
         &[
             Self::Rust,
@@ -140,6 +159,7 @@ pub struct CodeParser {
 
 impl CodeParser {
     /// Create a new code parser
+    #[allow(clippy::mutable_key_type)] // plugin grammars hash by name
     pub fn new() -> Result<Self> {
         let mut parsers = HashMap::new();
 
@@ -150,6 +170,9 @@ impl CodeParser {
                 .context(format!("Failed to set language for {:?}", lang))?;
             parsers.insert(*lang, parser);
         }
+        for grammar in plugins::grammars() {
+            parsers.insert(SupportedLanguage::Plugin(grammar), grammar.parser()?);
+        }
 
         Ok(Self { parsers })
     }
@@ -185,6 +208,7 @@ impl CodeParser {
             path: path_str.clone(),
             language: language.as_str().to_string(),
             hash,
+            line_count: content.lines().count() as u32,
             functions: Vec::new(),
             structs: Vec::new(),
             traits: Vec::new(),
@@ -193,6 +217,8 @@ impl CodeParser {
             impl_blocks: Vec::new(),
             function_calls: Vec::new(),
             symbols: Vec::new(),
+            function_fingerprints: Vec::new(),
+            encoding: None,
         };
 
         // Extract based on language
@@ -248,8 +274,13 @@ impl CodeParser {
             SupportedLanguage::Dart => {
                 languages::dart::extract(&root, content, &path_str, &mut parsed)?;
             }
+            SupportedLanguage::Plugin(grammar) => {
+                plugins::extract(grammar, &root, content, &path_str, &mut parsed)?;
+            }
         }
 
+        parsed.function_fingerprints = clones::fingerprint_functions(&parsed.functions, content);
+
         Ok(parsed)
     }
 
@@ -335,6 +366,8 @@ pub struct ParsedFile {
     pub path: String,
     pub language: String,
     pub hash: String,
+    /// Lines of the file
+    pub line_count: u32,
     pub functions: Vec<FunctionNode>,
     pub structs: Vec<StructNode>,
     pub traits: Vec<TraitNode>,
@@ -343,6 +376,11 @@ pub struct ParsedFile {
     pub impl_blocks: Vec<ImplNode>,
     pub function_calls: Vec<FunctionCall>,
     pub symbols: Vec<String>,
+    /// MinHash fingerprints of the function bodies (see [`clones`])
+    pub function_fingerprints: Vec<FunctionFingerprint>,
+    /// Encoding the file was converted from, `None` for UTF-8
+    /// (see [`encoding`])
+    pub encoding: Option<String>,
 }
 
 /// Represents a function call found in code
@@ -355,10 +393,14 @@ pub struct FunctionCall {
     /// Line where the call occurs
     pub line: u32,
     /// Confidence score (0.0-1.0) for the call relationship.
-    /// Set during import resolution: import-resolved=0.90, same-file=0.85, fuzzy-global=0.30-0.50
+    /// Set during import resolution: receiver-typed=0.90, import-resolved=0.90, same-file=0.85, same-module=0.70, fuzzy-global=0.30-0.50
     pub confidence: f64,
-    /// Reason for the confidence level (e.g., "import-resolved", "same-file", "fuzzy-unique", "fuzzy-ambiguous")
+    /// Reason for the confidence level (e.g., "import-resolved", "same-file", "same-module", "fuzzy-unique", "fuzzy-ambiguous")
     pub reason: String,
+    /// Type the callee is a method of, when the parser could infer it from the
+    /// receiver (`self.save()` in `impl Repo`, `Repo::open()`). Such calls only
+    /// link to methods of impl blocks for that type.
+    pub receiver_type: Option<String>,
 }
 
 #[cfg(test)]
@@ -498,6 +540,33 @@ mod tests {
         assert_ne!(a.hash, c.hash, "different content must hash differently");
     }
 
+    #[test]
+    fn test_parse_file_counts_lines() {
+        let mut parser = CodeParser::new().unwrap();
+        let parsed = parser
+            .parse_file(&PathBuf::from("a.rs"), "fn x() {\n    1\n}\n")
+            .unwrap();
+        assert_eq!(parsed.line_count, 3);
+        let empty = parser.parse_file(&PathBuf::from("b.rs"), "").unwrap();
+        assert_eq!(empty.line_count, 0);
+    }
+
+    /// Functions large enough get a clone fingerprint keyed by their node id;
+    /// identical bodies in two files get the same signature.
+    #[test]
+    fn test_parse_file_fingerprints_functions() {
+        let src = "fn short() {}\n\nfn total(items: &[Item]) -> u64 {\n    let mut sum = 0;\n    for item in items {\n        if item.active && item.price > 0 {\n            sum += item.price * item.quantity;\n        }\n    }\n    sum\n}\n";
+        let mut parser = CodeParser::new().unwrap();
+        let a = parser.parse_file(&PathBuf::from("a.rs"), src).unwrap();
+        let b = parser.parse_file(&PathBuf::from("b.rs"), src).unwrap();
+        assert_eq!(a.function_fingerprints.len(), 1);
+        assert_eq!(a.function_fingerprints[0].function_id, "a.rs:total:3");
+        assert_eq!(
+            a.function_fingerprints[0].minhash,
+            b.function_fingerprints[0].minhash
+        );
+    }
+
     /// SupportedLanguage::all() and as_str() round-trip + are non-empty/unique.
     #[test]
     fn test_supported_language_all_and_as_str() {
@@ -834,6 +903,193 @@ pub struct Config {
         assert!(!parsed.structs.is_empty());
     }
 
+    #[test]
+    fn test_parse_rust_method_call_receivers() {
+        let mut parser = CodeParser::new().unwrap();
+        let content = r#"
+struct Repo;
+
+impl Repo {
+    fn sync(&self, store: &mut Store, cache: Arc<Cache>) {
+        self.persist();
+        Self::validate();
+        store.save();
+        cache.warm();
+        let index = Index::new();
+        index.rebuild();
+        let pending = load();
+        pending.flush_all();
+        crate::util::checksum();
+    }
+}
+
+fn main() {
+    let repo = Repo { };
+    repo.sync_all();
+}
+"#;
+        let path = PathBuf::from("src/repo.rs");
+        let parsed = parser.parse_file(&path, content).unwrap();
+
+        let receivers: std::collections::HashMap<&str, Option<&str>> = parsed
+            .function_calls
+            .iter()
+            .map(|c| (c.callee_name.as_str(), c.receiver_type.as_deref()))
+            .collect();
+        assert_eq!(receivers["persist"], Some("Repo"));
+        assert_eq!(receivers["validate"], Some("Repo"));
+        assert_eq!(receivers["save"], Some("Store"));
+        assert_eq!(receivers["warm"], Some("Cache"));
+        assert_eq!(receivers["new"], Some("Index"));
+        assert_eq!(receivers["rebuild"], Some("Index"));
+        assert_eq!(receivers["load"], None);
+        assert_eq!(receivers["flush_all"], None);
+        assert_eq!(receivers["crate::util::checksum"], None);
+        assert_eq!(receivers["sync_all"], Some("Repo"));
+    }
+
+    #[test]
+    fn test_parse_rust_fields_and_variant_payloads() {
+        let mut parser = CodeParser::new().unwrap();
+        let content = r#"
+pub struct Config {
+    pub name: String,
+    pub(crate) port: u16,
+    retries: Option<u32>,
+}
+
+struct Pair(pub i64, String);
+
+enum Event {
+    Started,
+    Moved { x: i32, y: i32 },
+    Failed(String),
+}
+"#;
+        let path = PathBuf::from("src/config.rs");
+        let parsed = parser.parse_file(&path, content).unwrap();
+
+        let field = |name: &str, type_name: &str, visibility: Visibility| FieldNode {
+            name: name.to_string(),
+            type_name: type_name.to_string(),
+            visibility,
+            default_value: None,
+        };
+        let config = parsed.structs.iter().find(|s| s.name == "Config").unwrap();
+        assert_eq!(
+            config.fields,
+            vec![
+                field("name", "String", Visibility::Public),
+                field("port", "u16", Visibility::Crate),
+                field("retries", "Option<u32>", Visibility::Private),
+            ]
+        );
+        let pair = parsed.structs.iter().find(|s| s.name == "Pair").unwrap();
+        assert_eq!(
+            pair.fields,
+            vec![
+                field("0", "i64", Visibility::Public),
+                field("1", "String", Visibility::Private),
+            ]
+        );
+
+        let event = &parsed.enums[0];
+        assert_eq!(event.variants, vec!["Started", "Moved", "Failed"]);
+        assert_eq!(
+            event.variant_payloads,
+            vec![
+                VariantNode {
+                    name: "Moved".to_string(),
+                    fields: vec![
+                        field("x", "i32", Visibility::Private),
+                        field("y", "i32", Visibility::Private),
+                    ],
+                },
+                VariantNode {
+                    name: "Failed".to_string(),
+                    fields: vec![field("0", "String", Visibility::Private)],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_rust_derived_traits() {
+        let mut parser = CodeParser::new().unwrap();
+        let content = r#"
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Config {
+    pub name: String,
+}
+
+impl Config {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+"#;
+        let path = PathBuf::from("src/config.rs");
+        let parsed = parser.parse_file(&path, content).unwrap();
+
+        let mut derived: Vec<&str> = parsed
+            .impl_blocks
+            .iter()
+            .filter(|i| i.derived)
+            .filter_map(|i| i.trait_name.as_deref())
+            .collect();
+        derived.sort();
+        assert_eq!(
+            derived,
+            vec!["Clone", "Debug", "serde::Deserialize", "serde::Serialize"]
+        );
+        assert!(parsed
+            .impl_blocks
+            .iter()
+            .any(|i| !i.derived && i.trait_name.is_none()));
+    }
+
+    #[test]
+    fn test_parse_rust_macro_body_items() {
+        let mut parser = CodeParser::new().unwrap();
+        let content = r#"
+pin_project! {
+    #[derive(Clone)]
+    pub struct Stream {
+        inner: Vec<u8>,
+    }
+}
+
+generate! {
+    impl Stream {
+        fn poll(&self) {
+            self.wake();
+        }
+    }
+}
+
+lazy_static! {
+    static ref CACHE: Cache = Cache::new();
+}
+"#;
+        let path = PathBuf::from("src/stream.rs");
+        let parsed = parser.parse_file(&path, content).unwrap();
+
+        let stream = parsed.structs.iter().find(|s| s.name == "Stream").unwrap();
+        assert_eq!(stream.line_start, 4);
+        assert!(parsed
+            .impl_blocks
+            .iter()
+            .any(|i| i.derived && i.trait_name.as_deref() == Some("Clone")));
+
+        let poll = parsed.functions.iter().find(|f| f.name == "poll").unwrap();
+        assert_eq!(poll.line_start, 11);
+        assert!(parsed
+            .function_calls
+            .iter()
+            .any(|c| c.callee_name == "wake" && c.receiver_type.as_deref() == Some("Stream")));
+    }
+
     #[test]
     fn test_parse_python_file() {
         let mut parser = CodeParser::new().unwrap();
@@ -1762,6 +2018,7 @@ struct Bar {}
             line: 42,
             confidence: 0.85,
             reason: "same-file".to_string(),
+            receiver_type: None,
         };
 
         assert_eq!(call.caller_id, "main");
@@ -1781,6 +2038,7 @@ struct Bar {}
             path: "test.rs".to_string(),
             language: "rust".to_string(),
             hash: "abc123".to_string(),
+            line_count: 0,
             functions: vec![],
             structs: vec![],
             traits: vec![],
@@ -1789,6 +2047,8 @@ struct Bar {}
             impl_blocks: vec![],
             function_calls: vec![],
             symbols: vec![],
+            function_fingerprints: vec![],
+            encoding: None,
         };
 
         assert!(parsed.functions.is_empty());
@@ -0,0 +1,689 @@
+//! Grammar plugins — tree-sitter grammars compiled to WASM, loaded at runtime
+//!
+//! Built-in languages are compiled into the binary. Other languages can be
+//! added from config.yaml, without rebuilding:
+//!
+//! ```yaml
+//! grammars:
+//!   dir: /opt/orchestrator/grammars
+//!   languages:
+//!     - name: elixir
+//!       wasm: tree-sitter-elixir.wasm
+//!       tags_query: elixir-tags.scm
+//!       extensions: [ex, exs]
+//! ```
+//!
+//! `wasm` is the grammar built with `tree-sitter build --wasm`; `name` must be
+//! the grammar's own name (the module exports `tree_sitter_<name>`). Relative
+//! paths are resolved against `dir`. Loading needs the `wasm-grammars` cargo
+//! feature (tree-sitter's `wasm` feature, which embeds the wasmtime runtime);
+//! without it a configured grammar is a startup error.
+//!
+//! Symbols are extracted with the grammar's tags query (`queries/tags.scm` in
+//! most grammar repositories), following the tree-sitter tags conventions:
+//!
+//! - `@definition.function`, `@definition.method`, `@definition.macro` —
+//!   functions
+//! - `@definition.class`, `@definition.struct`, `@definition.module`,
+//!   `@definition.type` — structs
+//! - `@definition.interface`, `@definition.trait` — traits
+//! - `@definition.enum` — enums
+//! - `@reference.call` — calls, attributed to the innermost function around
+//!   them
+//! - `@name` — the name of the definition or callee, `@doc` — its doc comment
+//!
+//! Other definitions (`@definition.constant`…) are only added to the file's
+//! symbols. An `@import` capture, not part of the conventions, adds the text
+//! it captures as an import.
+//!
+//! Grammars are registered once per process ([`install`], from `AppState`
+//! startup) and picked up by every [`CodeParser`](super::CodeParser) created
+//! afterwards: their files are scanned, parsed and watched like the built-in
+//! languages, under [`SupportedLanguage::Plugin`](super::SupportedLanguage).
+
+use super::helpers::{calculate_complexity, get_text, visibility_from_name};
+use super::{noise_filter, FunctionCall, ParsedFile, SupportedLanguage};
+use crate::neo4j::models::*;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tree_sitter::{Language, Parser, Query, QueryCursor, StreamingIterator};
+
+/// Grammars registered by [`install`]
+static REGISTRY: OnceLock<Vec<PluginGrammar>> = OnceLock::new();
+
+/// A grammar plugin (`grammars.languages[]` in config.yaml)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GrammarPluginConfig {
+    /// Name of the grammar, also the `language` of its files
+    pub name: String,
+    /// Grammar compiled to WASM
+    pub wasm: PathBuf,
+    /// Tags query of the grammar
+    pub tags_query: PathBuf,
+    /// File extensions, without the dot
+    pub extensions: Vec<String>,
+}
+
+/// `grammars` section of config.yaml.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GrammarsConfig {
+    /// Directory relative `wasm` and `tags_query` paths are resolved against
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dir: Option<PathBuf>,
+    pub languages: Vec<GrammarPluginConfig>,
+}
+
+impl GrammarsConfig {
+    /// Validate names and extensions. Files are checked when loaded.
+    pub fn check(&self) -> Result<(), String> {
+        let mut names = HashSet::new();
+        let mut extensions = HashSet::new();
+        for plugin in &self.languages {
+            if plugin.name.is_empty() {
+                return Err("a grammar has no name".to_string());
+            }
+            if SupportedLanguage::all()
+                .iter()
+                .any(|l| l.as_str() == plugin.name)
+            {
+                return Err(format!("`{}` is a built-in language", plugin.name));
+            }
+            if !names.insert(plugin.name.as_str()) {
+                return Err(format!("grammar `{}` is configured twice", plugin.name));
+            }
+            if plugin.extensions.is_empty() {
+                return Err(format!("grammar `{}` has no extensions", plugin.name));
+            }
+            for ext in &plugin.extensions {
+                let ext = ext.to_lowercase();
+                if ext.is_empty() || ext.starts_with('.') {
+                    return Err(format!(
+                        "grammar `{}`: extension `{}` must be given without the dot",
+                        plugin.name, ext
+                    ));
+                }
+                if let Some(language) = SupportedLanguage::builtin_from_extension(&ext) {
+                    return Err(format!(
+                        "grammar `{}`: .{} files are parsed as {}",
+                        plugin.name,
+                        ext,
+                        language.as_str()
+                    ));
+                }
+                if !extensions.insert(ext.clone()) {
+                    return Err(format!("extension `{}` is mapped to several grammars", ext));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn resolve(&self, path: &Path) -> PathBuf {
+        match &self.dir {
+            Some(dir) if path.is_relative() => dir.join(path),
+            _ => path.to_path_buf(),
+        }
+    }
+}
+
+/// A loaded grammar with its tags query
+pub struct PluginGrammar {
+    pub name: String,
+    /// Lowercase file extensions
+    pub extensions: Vec<String>,
+    language: Language,
+    tags: Query,
+    /// Runtime of a WASM grammar; each parser gets its own store on it
+    #[cfg(feature = "wasm-grammars")]
+    engine: Option<tree_sitter::wasmtime::Engine>,
+}
+
+impl PluginGrammar {
+    /// Grammar of an already loaded `language`.
+    pub(crate) fn new(
+        name: &str,
+        extensions: &[String],
+        language: Language,
+        tags_query: &str,
+    ) -> Result<Self> {
+        let tags = Query::new(&language, tags_query)
+            .with_context(|| format!("tags query of grammar `{}`", name))?;
+        Ok(Self {
+            name: name.to_string(),
+            extensions: extensions.iter().map(|e| e.to_lowercase()).collect(),
+            language,
+            tags,
+            #[cfg(feature = "wasm-grammars")]
+            engine: None,
+        })
+    }
+
+    pub fn language(&self) -> Language {
+        self.language.clone()
+    }
+
+    /// A parser for this grammar.
+    pub fn parser(&self) -> Result<Parser> {
+        let mut parser = Parser::new();
+        #[cfg(feature = "wasm-grammars")]
+        if let Some(ref engine) = self.engine {
+            let store = tree_sitter::WasmStore::new(engine)
+                .with_context(|| format!("WASM store for grammar `{}`", self.name))?;
+            parser.set_wasm_store(store)?;
+        }
+        parser
+            .set_language(&self.language)
+            .with_context(|| format!("Failed to set language for grammar `{}`", self.name))?;
+        Ok(parser)
+    }
+}
+
+impl std::fmt::Debug for PluginGrammar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PluginGrammar")
+            .field("name", &self.name)
+            .field("extensions", &self.extensions)
+            .finish()
+    }
+}
+
+impl PartialEq for PluginGrammar {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for PluginGrammar {}
+
+impl std::hash::Hash for PluginGrammar {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
+}
+
+/// Load the configured grammars.
+fn load(config: &GrammarsConfig) -> Result<Vec<PluginGrammar>> {
+    #[cfg(feature = "wasm-grammars")]
+    let engine = tree_sitter::wasmtime::Engine::default();
+    #[cfg(not(feature = "wasm-grammars"))]
+    let engine = ();
+    config
+        .languages
+        .iter()
+        .map(|plugin| {
+            let wasm_path = config.resolve(&plugin.wasm);
+            let bytes = std::fs::read(&wasm_path)
+                .with_context(|| format!("Failed to read {}", wasm_path.display()))?;
+            let language = load_wasm(&engine, &plugin.name, &bytes)
+                .with_context(|| format!("Failed to load {}", wasm_path.display()))?;
+            let query_path = config.resolve(&plugin.tags_query);
+            let query = std::fs::read_to_string(&query_path)
+                .with_context(|| format!("Failed to read {}", query_path.display()))?;
+            let grammar = PluginGrammar::new(&plugin.name, &plugin.extensions, language, &query)?;
+            #[cfg(feature = "wasm-grammars")]
+            let grammar = PluginGrammar {
+                engine: Some(engine.clone()),
+                ..grammar
+            };
+            Ok(grammar)
+        })
+        .collect()
+}
+
+#[cfg(feature = "wasm-grammars")]
+fn load_wasm(engine: &tree_sitter::wasmtime::Engine, name: &str, bytes: &[u8]) -> Result<Language> {
+    let mut store = tree_sitter::WasmStore::new(engine)?;
+    Ok(store.load_language(name, bytes)?)
+}
+
+#[cfg(not(feature = "wasm-grammars"))]
+fn load_wasm(_engine: &(), _name: &str, _bytes: &[u8]) -> Result<Language> {
+    anyhow::bail!("this build has no WASM runtime (built without the `wasm-grammars` feature)")
+}
+
+/// Load the configured grammars and register them for every `CodeParser`
+/// created afterwards. Only the first call with grammars registers them.
+pub fn install(config: &GrammarsConfig) -> Result<()> {
+    if config.languages.is_empty() || REGISTRY.get().is_some() {
+        return Ok(());
+    }
+    config
+        .check()
+        .map_err(|e| anyhow::anyhow!("grammars: {}", e))?;
+    let grammars = load(config).context("grammars")?;
+    for grammar in &grammars {
+        tracing::info!(
+            "Loaded grammar plugin {} (.{})",
+            grammar.name,
+            grammar.extensions.join(", .")
+        );
+    }
+    let _ = REGISTRY.set(grammars);
+    Ok(())
+}
+
+/// Registered grammars
+pub fn grammars() -> &'static [PluginGrammar] {
+    REGISTRY.get().map(Vec::as_slice).unwrap_or_default()
+}
+
+/// Registered grammar of a file extension
+pub fn grammar_for_extension(ext: &str) -> Option<&'static PluginGrammar> {
+    let ext = ext.to_lowercase();
+    grammars().iter().find(|g| g.extensions.contains(&ext))
+}
+
+// ============================================================================
+// Tags query extraction
+// ============================================================================
+
+/// Kind of a definition or reference captured by a tags query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tag {
+    Function,
+    Struct,
+    Trait,
+    Enum,
+    /// Other definitions: symbol only
+    Symbol,
+    Call,
+    Import,
+}
+
+impl Tag {
+    fn from_capture(name: &str) -> Option<Self> {
+        Some(match name {
+            "definition.function" | "definition.method" | "definition.macro" => Self::Function,
+            "definition.class" | "definition.struct" | "definition.module" | "definition.type" => {
+                Self::Struct
+            }
+            "definition.interface" | "definition.trait" => Self::Trait,
+            "definition.enum" => Self::Enum,
+            "reference.call" => Self::Call,
+            "import" => Self::Import,
+            _ if name.starts_with("definition.") => Self::Symbol,
+            _ => return None,
+        })
+    }
+}
+
+/// Strip comment markers and string quotes from a `@doc` capture.
+fn clean_doc(text: &str) -> String {
+    const MARKERS: &[&str] = &["///", "//!", "//", "/**", "/*", "*/", "*", "#", "--", ";;"];
+    text.trim()
+        .trim_matches(|c| c == '"' || c == '\'')
+        .lines()
+        .map(|line| {
+            let mut line = line.trim();
+            if let Some(marker) = MARKERS.iter().find(|m| line.starts_with(**m)) {
+                line = &line[marker.len()..];
+            }
+            line.trim_end_matches("*/").trim()
+        })
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Extract the code structure of a file with the grammar's tags query
+pub fn extract(
+    grammar: &PluginGrammar,
+    root: &tree_sitter::Node,
+    source: &str,
+    file_path: &str,
+    parsed: &mut ParsedFile,
+) -> Result<()> {
+    let capture_names = grammar.tags.capture_names();
+    let mut cursor = QueryCursor::new();
+    let mut matches = cursor.matches(&grammar.tags, *root, source.as_bytes());
+
+    let mut seen = HashSet::new();
+    // Byte range and id of each function, to attribute calls
+    let mut function_ranges: Vec<(std::ops::Range<usize>, String)> = Vec::new();
+    let mut calls: Vec<(usize, String, u32)> = Vec::new();
+
+    while let Some(m) = matches.next() {
+        let mut tag = None;
+        let mut name = None;
+        let mut docs = Vec::new();
+        for capture in m.captures {
+            match capture_names[capture.index as usize] {
+                "name" => name = get_text(&capture.node, source),
+                "doc" => docs.extend(get_text(&capture.node, source).map(clean_doc)),
+                other => {
+                    if let Some(t) = Tag::from_capture(other) {
+                        tag = Some((t, capture.node));
+                    }
+                }
+            }
+        }
+        let Some((tag, node)) = tag else {
+            continue;
+        };
+        let line_start = node.start_position().row as u32 + 1;
+        let line_end = node.end_position().row as u32 + 1;
+
+        if tag == Tag::Import {
+            if let Some(path) = get_text(&node, source) {
+                parsed.imports.push(ImportNode {
+                    path: path.trim_matches(|c| c == '"' || c == '\'').to_string(),
+                    alias: None,
+                    items: vec![],
+                    file_path: file_path.to_string(),
+                    line: line_start,
+                });
+            }
+            continue;
+        }
+        let Some(name) = name.map(str::to_string) else {
+            continue;
+        };
+        if tag == Tag::Call {
+            if !noise_filter::is_builtin_call(&name) {
+                calls.push((node.start_byte(), name, line_start));
+            }
+            continue;
+        }
+        // Several patterns may capture the same definition
+        if !seen.insert((node.id(), name.clone())) {
+            continue;
+        }
+
+        let docstring = (!docs.is_empty()).then(|| docs.join("\n"));
+        let visibility = visibility_from_name(&name);
+        parsed.symbols.push(name.clone());
+        match tag {
+            Tag::Function => {
+                function_ranges.push((
+                    node.byte_range(),
+                    format!("{}:{}:{}", file_path, name, line_start),
+                ));
+                parsed.functions.push(FunctionNode {
+                    name,
+                    visibility,
+                    params: vec![],
+                    return_type: None,
+                    generics: vec![],
+                    is_async: false,
+                    is_unsafe: false,
+                    complexity: calculate_complexity(&node),
+                    file_path: file_path.to_string(),
+                    line_start,
+                    line_end,
+                    docstring,
+                });
+            }
+            Tag::Struct => parsed.structs.push(StructNode {
+                name,
+                visibility,
+                generics: vec![],
+                file_path: file_path.to_string(),
+                line_start,
+                line_end,
+                docstring,
+                parent_class: None,
+                interfaces: vec![],
+                fields: vec![],
+            }),
+            Tag::Trait => parsed.traits.push(TraitNode {
+                name,
+                visibility,
+                generics: vec![],
+                file_path: file_path.to_string(),
+                line_start,
+                line_end,
+                docstring,
+                is_external: false,
+                source: None,
+            }),
+            Tag::Enum => parsed.enums.push(EnumNode {
+                name,
+                visibility,
+                variants: vec![],
+                file_path: file_path.to_string(),
+                line_start,
+                line_end,
+                docstring,
+                variant_payloads: vec![],
+            }),
+            Tag::Symbol | Tag::Call | Tag::Import => {}
+        }
+    }
+
+    // A call belongs to the innermost function around it; calls outside any
+    // function have no caller
+    for (offset, callee_name, line) in calls {
+        let caller = function_ranges
+            .iter()
+            .filter(|(range, _)| range.contains(&offset))
+            .min_by_key(|(range, _)| range.len());
+        if let Some((_, caller_id)) = caller {
+            parsed.function_calls.push(FunctionCall {
+                caller_id: caller_id.clone(),
+                callee_name,
+                line,
+                confidence: 0.50,
+                reason: "unscored".to_string(),
+                receiver_type: None,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::CodeParser;
+
+    /// Python stands in for a WASM grammar: extraction only sees a
+    /// `Language` and its tags query.
+    const PYTHON_TAGS: &str = r#"
+(module (expression_statement (assignment left: (identifier) @name) @definition.constant))
+
+(class_definition
+  name: (identifier) @name
+  body: (block . (expression_statement (string) @doc)?)) @definition.class
+
+(function_definition
+  name: (identifier) @name
+  body: (block . (expression_statement (string) @doc)?)) @definition.function
+
+(call
+  function: [
+      (identifier) @name
+      (attribute attribute: (identifier) @name)
+  ]) @reference.call
+
+(import_statement name: (dotted_name) @import)
+"#;
+
+    const SOURCE: &str = r#"import os.path
+
+LIMIT = 10
+
+class Repo:
+    """Stores things."""
+
+    def save(self, item):
+        """Persist an item."""
+        self.validate(item)
+        write_all(item)
+
+def write_all(item):
+    def inner():
+        flush()
+    inner()
+
+print("top level")
+"#;
+
+    fn python_grammar(extensions: &[&str]) -> PluginGrammar {
+        let extensions: Vec<String> = extensions.iter().map(|e| e.to_string()).collect();
+        PluginGrammar::new(
+            "pyplugin",
+            &extensions,
+            tree_sitter_python::LANGUAGE.into(),
+            PYTHON_TAGS,
+        )
+        .unwrap()
+    }
+
+    fn plugin(name: &str, extensions: &[&str]) -> GrammarPluginConfig {
+        GrammarPluginConfig {
+            name: name.to_string(),
+            wasm: PathBuf::from(format!("tree-sitter-{}.wasm", name)),
+            tags_query: PathBuf::from(format!("{}-tags.scm", name)),
+            extensions: extensions.iter().map(|e| e.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_extract_with_tags_query() {
+        let grammar = python_grammar(&["pyplugin"]);
+        let mut parser = grammar.parser().unwrap();
+        let tree = parser.parse(SOURCE, None).unwrap();
+        let mut parsed = ParsedFile {
+            path: "repo.pyplugin".to_string(),
+            language: "pyplugin".to_string(),
+            hash: String::new(),
+            line_count: 0,
+            functions: Vec::new(),
+            structs: Vec::new(),
+            traits: Vec::new(),
+            enums: Vec::new(),
+            imports: Vec::new(),
+            impl_blocks: Vec::new(),
+            function_calls: Vec::new(),
+            symbols: Vec::new(),
+            function_fingerprints: Vec::new(),
+            encoding: None,
+        };
+        extract(
+            &grammar,
+            &tree.root_node(),
+            SOURCE,
+            "repo.pyplugin",
+            &mut parsed,
+        )
+        .unwrap();
+
+        let functions: Vec<(&str, u32)> = parsed
+            .functions
+            .iter()
+            .map(|f| (f.name.as_str(), f.line_start))
+            .collect();
+        assert_eq!(
+            functions,
+            vec![("save", 8), ("write_all", 13), ("inner", 14)]
+        );
+        assert_eq!(
+            parsed.functions[0].docstring.as_deref(),
+            Some("Persist an item.")
+        );
+        assert_eq!(parsed.structs.len(), 1);
+        assert_eq!(parsed.structs[0].name, "Repo");
+        assert_eq!(
+            parsed.structs[0].docstring.as_deref(),
+            Some("Stores things.")
+        );
+        assert!(parsed.symbols.contains(&"LIMIT".to_string()));
+        assert_eq!(parsed.imports[0].path, "os.path");
+
+        // Innermost function wins; the top-level print has no caller
+        let calls: Vec<(&str, &str)> = parsed
+            .function_calls
+            .iter()
+            .map(|c| (c.caller_id.as_str(), c.callee_name.as_str()))
+            .collect();
+        assert_eq!(
+            calls,
+            vec![
+                ("repo.pyplugin:save:8", "validate"),
+                ("repo.pyplugin:save:8", "write_all"),
+                ("repo.pyplugin:inner:14", "flush"),
+                ("repo.pyplugin:write_all:13", "inner"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_registered_grammar_is_parsed_by_extension() {
+        REGISTRY.get_or_init(|| vec![python_grammar(&["pyplugin", "PYP2"])]);
+
+        let language = SupportedLanguage::from_extension("pyp2").unwrap();
+        assert_eq!(language.as_str(), "pyplugin");
+        assert_eq!(
+            SupportedLanguage::from_extension("py"),
+            Some(SupportedLanguage::Python)
+        );
+
+        let mut parser = CodeParser::new().unwrap();
+        let parsed = parser
+            .parse_file(Path::new("src/repo.pyplugin"), SOURCE)
+            .unwrap();
+        assert_eq!(parsed.language, "pyplugin");
+        assert_eq!(parsed.functions.len(), 3);
+        assert_eq!(parsed.structs[0].name, "Repo");
+    }
+
+    #[test]
+    fn test_check_config() {
+        let config = |languages| GrammarsConfig {
+            dir: Some(PathBuf::from("/opt/grammars")),
+            languages,
+        };
+        assert!(config(vec![plugin("elixir", &["ex", "exs"])])
+            .check()
+            .is_ok());
+
+        for (languages, error) in [
+            (vec![plugin("rust", &["rsx"])], "built-in"),
+            (vec![plugin("elixir", &["rs"])], "are parsed as rust"),
+            (vec![plugin("elixir", &[".ex"])], "without the dot"),
+            (vec![plugin("elixir", &[])], "no extensions"),
+            (
+                vec![plugin("elixir", &["ex"]), plugin("heex", &["EX"])],
+                "several grammars",
+            ),
+            (
+                vec![plugin("elixir", &["ex"]), plugin("elixir", &["exs"])],
+                "twice",
+            ),
+        ] {
+            let e = config(languages).check().unwrap_err();
+            assert!(e.contains(error), "{}", e);
+        }
+
+        let grammars = config(vec![]);
+        assert_eq!(
+            grammars.resolve(Path::new("tree-sitter-elixir.wasm")),
+            PathBuf::from("/opt/grammars/tree-sitter-elixir.wasm")
+        );
+        assert_eq!(
+            grammars.resolve(Path::new("/srv/elixir.wasm")),
+            PathBuf::from("/srv/elixir.wasm")
+        );
+    }
+
+    #[test]
+    fn test_grammars_yaml() {
+        let yaml = r#"
+dir: /opt/grammars
+languages:
+  - name: elixir
+    wasm: tree-sitter-elixir.wasm
+    tags_query: elixir-tags.scm
+    extensions: [ex, exs]
+"#;
+        let config: GrammarsConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.languages, vec![plugin("elixir", &["ex", "exs"])]);
+        // Nothing configured: nothing to load, whatever the build
+        assert!(install(&GrammarsConfig::default()).is_ok());
+    }
+}
@@ -0,0 +1,1599 @@
+//! SQL schema and migration parser.
+//!
+//! Reconstructs the tables of a project from its `.sql` files: schema dumps
+//! (`schema.sql`, `structure.sql`) are applied first, then migrations in
+//! version order — plain `migrations/0003_add_email.sql`, sqlx
+//! `20240101120000_init.up.sql`, diesel `<version>_<name>/up.sql` and Flyway
+//! `V3__add_email.sql`. Down migrations are ignored.
+//!
+//! Only DDL is understood: `CREATE TABLE`, `ALTER TABLE`, `DROP TABLE`,
+//! `RENAME TABLE` and `CREATE INDEX` in the PostgreSQL, MySQL and SQLite
+//! dialects. Everything else (DML, views, functions, triggers) is skipped.
+
+use crate::neo4j::models::{ColumnNode, MigrationAlter, MigrationNode, TableNode};
+use std::collections::BTreeMap;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Directory names whose `.sql` files are migrations even without a version
+const MIGRATION_DIRS: &[&str] = &["migrations", "migration", "migrate"];
+
+/// Schemas dropped from qualified table names (`public.users` → `users`)
+const DEFAULT_SCHEMAS: &[&str] = &["public", "dbo", "main"];
+
+/// Keywords ending the data type of a column definition
+const COLUMN_CONSTRAINT_KEYWORDS: &[&str] = &[
+    "NOT",
+    "NULL",
+    "DEFAULT",
+    "PRIMARY",
+    "REFERENCES",
+    "UNIQUE",
+    "CHECK",
+    "CONSTRAINT",
+    "GENERATED",
+    "COLLATE",
+    "AUTO_INCREMENT",
+    "AUTOINCREMENT",
+    "COMMENT",
+    "ON",
+    "AS",
+];
+
+/// Whether a path is a SQL file
+pub fn is_sql_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("sql"))
+}
+
+// ============================================================================
+// Model
+// ============================================================================
+
+/// A column as declared in `CREATE TABLE` or `ALTER TABLE ... ADD COLUMN`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ColumnDef {
+    pub name: String,
+    /// Keywords uppercased (`VARCHAR(255)`), empty for SQLite typeless columns
+    pub data_type: String,
+    pub nullable: bool,
+    pub primary_key: bool,
+    pub default_value: Option<String>,
+    /// `table.column` (or `table` when the column is implicit)
+    pub references: Option<String>,
+}
+
+/// A change to a single column (`ALTER COLUMN`, MySQL `MODIFY`/`CHANGE`)
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnChange {
+    Type(String),
+    SetNotNull,
+    DropNotNull,
+    SetDefault(String),
+    DropDefault,
+    Redefine(ColumnDef),
+}
+
+/// A DDL operation
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaOp {
+    CreateTable {
+        table: String,
+        columns: Vec<ColumnDef>,
+        if_not_exists: bool,
+    },
+    DropTable {
+        table: String,
+    },
+    RenameTable {
+        table: String,
+        to: String,
+    },
+    AddColumn {
+        table: String,
+        column: ColumnDef,
+    },
+    DropColumn {
+        table: String,
+        column: String,
+    },
+    RenameColumn {
+        table: String,
+        from: String,
+        to: String,
+    },
+    AlterColumn {
+        table: String,
+        column: String,
+        change: ColumnChange,
+    },
+    AddPrimaryKey {
+        table: String,
+        columns: Vec<String>,
+    },
+    AddForeignKey {
+        table: String,
+        columns: Vec<String>,
+        references_table: String,
+        references_columns: Vec<String>,
+    },
+    CreateIndex {
+        table: String,
+        name: Option<String>,
+        columns: Vec<String>,
+        unique: bool,
+    },
+}
+
+impl SchemaOp {
+    /// Table the operation applies to (the new name for a rename)
+    pub fn table(&self) -> &str {
+        match self {
+            SchemaOp::RenameTable { to, .. } => to,
+            SchemaOp::CreateTable { table, .. }
+            | SchemaOp::DropTable { table }
+            | SchemaOp::AddColumn { table, .. }
+            | SchemaOp::DropColumn { table, .. }
+            | SchemaOp::RenameColumn { table, .. }
+            | SchemaOp::AlterColumn { table, .. }
+            | SchemaOp::AddPrimaryKey { table, .. }
+            | SchemaOp::AddForeignKey { table, .. }
+            | SchemaOp::CreateIndex { table, .. } => table,
+        }
+    }
+
+    /// Operation name recorded on `ALTERS` relationships
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SchemaOp::CreateTable { .. } => "create_table",
+            SchemaOp::DropTable { .. } => "drop_table",
+            SchemaOp::RenameTable { .. } => "rename_table",
+            SchemaOp::AddColumn { .. } => "add_column",
+            SchemaOp::DropColumn { .. } => "drop_column",
+            SchemaOp::RenameColumn { .. } => "rename_column",
+            SchemaOp::AlterColumn { .. } => "alter_column",
+            SchemaOp::AddPrimaryKey { .. } => "add_primary_key",
+            SchemaOp::AddForeignKey { .. } => "add_foreign_key",
+            SchemaOp::CreateIndex { .. } => "create_index",
+        }
+    }
+}
+
+/// A table after all schema files and migrations have been applied
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableSchema {
+    pub name: String,
+    pub columns: Vec<ColumnDef>,
+    /// File with the `CREATE TABLE` statement
+    pub defined_in: Option<String>,
+    /// Files that changed the table afterwards, in apply order
+    pub altered_by: Vec<String>,
+}
+
+/// A migration file with its DDL operations
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlMigration {
+    pub path: String,
+    pub version: String,
+    pub ops: Vec<SchemaOp>,
+}
+
+/// Result of [`build_schema`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedSchema {
+    /// Ordered by name
+    pub tables: Vec<TableSchema>,
+    /// In apply order; migrations without DDL are omitted
+    pub migrations: Vec<SqlMigration>,
+}
+
+// ============================================================================
+// File classification
+// ============================================================================
+
+/// Role of a `.sql` file, derived from its path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SqlFileKind {
+    /// Schema dump or standalone DDL
+    Schema,
+    Migration {
+        version: String,
+    },
+    /// Rollback script, ignored
+    Down,
+}
+
+/// Classify a `.sql` file by its name and directory.
+pub fn classify_sql_path(path: &Path) -> SqlFileKind {
+    let file_stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    let lower = file_stem.to_lowercase();
+
+    let flyway_undo = file_stem.starts_with('U')
+        && file_stem.contains("__")
+        && version_prefix(&file_stem[1..]).is_some();
+    if lower == "down" || lower.ends_with(".down") || lower.ends_with("_down") || flyway_undo {
+        return SqlFileKind::Down;
+    }
+
+    let stem = lower
+        .strip_suffix(".up")
+        .or_else(|| lower.strip_suffix("_up"))
+        .unwrap_or(&lower);
+    let flyway = stem.strip_prefix('v').filter(|s| s.contains("__"));
+    if let Some(version) = version_prefix(flyway.unwrap_or(stem)) {
+        return SqlFileKind::Migration { version };
+    }
+
+    let in_migrations_dir = path.components().any(|c| {
+        c.as_os_str()
+            .to_str()
+            .is_some_and(|s| MIGRATION_DIRS.contains(&s.to_lowercase().as_str()))
+    });
+
+    // diesel: migrations/<version>_<name>/up.sql
+    let parent_version = path
+        .parent()
+        .and_then(|p| p.file_name())
+        .and_then(|n| n.to_str())
+        .and_then(version_prefix);
+    if let Some(version) = parent_version.filter(|_| stem == "up" || in_migrations_dir) {
+        return SqlFileKind::Migration { version };
+    }
+
+    if in_migrations_dir {
+        return SqlFileKind::Migration {
+            version: stem.to_string(),
+        };
+    }
+    SqlFileKind::Schema
+}
+
+/// Leading version of a file or directory name (`20240101120000`, `0003`,
+/// `2020-01-01-000000`).
+fn version_prefix(name: &str) -> Option<String> {
+    let prefix: String = name
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '-' || *c == '.')
+        .collect();
+    let prefix = prefix.trim_end_matches(['-', '.']);
+    prefix
+        .starts_with(|c: char| c.is_ascii_digit())
+        .then(|| prefix.to_string())
+}
+
+/// Numeric sort key of a version (`V10` after `V2`)
+fn version_key(version: &str) -> (u128, String) {
+    let digits: String = version.chars().filter(|c| c.is_ascii_digit()).collect();
+    (digits.parse().unwrap_or(u128::MAX), version.to_string())
+}
+
+// ============================================================================
+// Tokenizer
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    /// Keyword, unquoted identifier or number
+    Word(String),
+    /// Quoted identifier (`"x"`, `` `x` ``), case preserved
+    Ident(String),
+    /// String literal (quotes included) or dollar-quoted body
+    Str(String),
+    Punct(char),
+}
+
+/// Split SQL into statements of tokens, dropping comments.
+fn tokenize(sql: &str) -> Vec<Vec<Tok>> {
+    let chars: Vec<char> = sql.chars().collect();
+    let len = chars.len();
+    let mut statements = Vec::new();
+    let mut current = Vec::new();
+    let mut i = 0;
+
+    while i < len {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '-' if chars.get(i + 1) == Some(&'-') => {
+                while i < len && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < len && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+            }
+            '\'' => {
+                let start = i;
+                i += 1;
+                while i < len {
+                    match chars[i] {
+                        '\\' => i += 2,
+                        '\'' if chars.get(i + 1) == Some(&'\'') => i += 2,
+                        '\'' => {
+                            i += 1;
+                            break;
+                        }
+                        _ => i += 1,
+                    }
+                }
+                current.push(Tok::Str(chars[start..i.min(len)].iter().collect()));
+            }
+            '"' | '`' => {
+                let quote = c;
+                let mut ident = String::new();
+                i += 1;
+                while i < len {
+                    if chars[i] == quote {
+                        if chars.get(i + 1) == Some(&quote) {
+                            ident.push(quote);
+                            i += 2;
+                            continue;
+                        }
+                        i += 1;
+                        break;
+                    }
+                    ident.push(chars[i]);
+                    i += 1;
+                }
+                current.push(Tok::Ident(ident));
+            }
+            '$' if dollar_tag(&chars, i).is_some() => {
+                let tag: Vec<char> = dollar_tag(&chars, i).unwrap_or_default().chars().collect();
+                let tag_len = tag.len();
+                let start = i;
+                i += tag_len;
+                while i < len && !chars[i..].starts_with(&tag) {
+                    i += 1;
+                }
+                i = (i + tag_len).min(len);
+                current.push(Tok::Str(chars[start..i].iter().collect()));
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '$' => {
+                let start = i;
+                while i < len && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$')
+                {
+                    i += 1;
+                }
+                current.push(Tok::Word(chars[start..i].iter().collect()));
+            }
+            ';' => {
+                if !current.is_empty() {
+                    statements.push(std::mem::take(&mut current));
+                }
+                i += 1;
+            }
+            _ => {
+                current.push(Tok::Punct(c));
+                i += 1;
+            }
+        }
+    }
+    if !current.is_empty() {
+        statements.push(current);
+    }
+    statements
+}
+
+/// `$$` or `$tag$` starting at `i`
+fn dollar_tag(chars: &[char], i: usize) -> Option<String> {
+    let mut j = i + 1;
+    while j < chars.len() && (chars[j].is_alphanumeric() || chars[j] == '_') {
+        j += 1;
+    }
+    let valid = chars.get(j) == Some(&'$')
+        && chars
+            .get(i + 1)
+            .is_some_and(|c| *c == '$' || c.is_alphabetic() || *c == '_');
+    valid.then(|| chars[i..=j].iter().collect())
+}
+
+/// Render tokens back to compact SQL text.
+fn render(toks: &[Tok], uppercase_words: bool) -> String {
+    let mut out = String::new();
+    let mut prev: Option<&Tok> = None;
+    for tok in toks {
+        let text = match tok {
+            Tok::Word(w) if uppercase_words => w.to_uppercase(),
+            Tok::Word(w) | Tok::Ident(w) | Tok::Str(w) => w.clone(),
+            Tok::Punct(c) => c.to_string(),
+        };
+        let glue = match (prev, tok) {
+            (None, _) => true,
+            (_, Tok::Punct(')' | ',' | '.' | ':' | '[' | ']')) => true,
+            (Some(Tok::Punct('(' | '.' | ':' | '[')), _) => true,
+            (Some(Tok::Word(_) | Tok::Ident(_)), Tok::Punct('(')) => true,
+            (Some(Tok::Punct('-' | '+')), _) => out.len() == 1,
+            _ => false,
+        };
+        if !glue {
+            out.push(' ');
+        }
+        out.push_str(&text);
+        prev = Some(tok);
+    }
+    out
+}
+
+fn is_kw(tok: Option<&Tok>, kw: &str) -> bool {
+    matches!(tok, Some(Tok::Word(w)) if w.eq_ignore_ascii_case(kw))
+}
+
+fn is_constraint_kw(tok: &Tok) -> bool {
+    matches!(tok, Tok::Word(w) if COLUMN_CONSTRAINT_KEYWORDS.iter().any(|k| w.eq_ignore_ascii_case(k)))
+}
+
+/// Split on commas outside parentheses
+fn split_top_level(toks: &[Tok]) -> Vec<&[Tok]> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, tok) in toks.iter().enumerate() {
+        match tok {
+            Tok::Punct('(') => depth += 1,
+            Tok::Punct(')') => depth -= 1,
+            Tok::Punct(',') if depth == 0 => {
+                parts.push(&toks[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&toks[start..]);
+    parts.retain(|p| !p.is_empty());
+    parts
+}
+
+struct Cursor<'a> {
+    toks: &'a [Tok],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(toks: &'a [Tok]) -> Self {
+        Self { toks, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<&'a Tok> {
+        self.toks.get(self.pos)
+    }
+
+    fn rest(&self) -> &'a [Tok] {
+        &self.toks[self.pos.min(self.toks.len())..]
+    }
+
+    fn kw(&mut self, kw: &str) -> bool {
+        let matched = is_kw(self.peek(), kw);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    /// Consume a keyword sequence, all or nothing
+    fn kws(&mut self, kws: &[&str]) -> bool {
+        let matched = kws
+            .iter()
+            .enumerate()
+            .all(|(n, kw)| is_kw(self.toks.get(self.pos + n), kw));
+        if matched {
+            self.pos += kws.len();
+        }
+        matched
+    }
+
+    fn ident(&mut self) -> Option<String> {
+        let ident = match self.peek()? {
+            Tok::Word(w) => w.to_lowercase(),
+            Tok::Ident(s) => s.clone(),
+            _ => return None,
+        };
+        self.pos += 1;
+        Some(ident)
+    }
+
+    /// Possibly schema-qualified name, default schema dropped
+    fn name(&mut self) -> Option<String> {
+        let mut parts = vec![self.ident()?];
+        while self.peek() == Some(&Tok::Punct('.'))
+            && matches!(
+                self.toks.get(self.pos + 1),
+                Some(Tok::Word(_) | Tok::Ident(_))
+            )
+        {
+            self.pos += 1;
+            parts.push(self.ident()?);
+        }
+        if parts.len() > 1 && DEFAULT_SCHEMAS.contains(&parts[0].as_str()) {
+            parts.remove(0);
+        }
+        Some(parts.join("."))
+    }
+
+    /// Tokens inside the parenthesized group at the cursor
+    fn group(&mut self) -> Option<&'a [Tok]> {
+        if self.peek() != Some(&Tok::Punct('(')) {
+            return None;
+        }
+        let start = self.pos + 1;
+        let mut depth = 0;
+        while let Some(tok) = self.peek() {
+            match tok {
+                Tok::Punct('(') => depth += 1,
+                Tok::Punct(')') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.pos += 1;
+                        return Some(&self.toks[start..self.pos - 1]);
+                    }
+                }
+                _ => {}
+            }
+            self.pos += 1;
+        }
+        Some(&self.toks[start..])
+    }
+
+    /// `(a, b DESC, lower(c))` → first identifier of each element
+    fn paren_list(&mut self) -> Option<Vec<String>> {
+        let group = self.group()?;
+        Some(
+            split_top_level(group)
+                .into_iter()
+                .filter_map(|part| Cursor::new(part).ident())
+                .collect(),
+        )
+    }
+
+    /// Skip an expression up to the next column constraint keyword
+    fn skip_expr(&mut self) {
+        let mut first = true;
+        while let Some(tok) = self.peek() {
+            if !first && is_constraint_kw(tok) {
+                break;
+            }
+            if tok == &Tok::Punct('(') {
+                self.group();
+            } else {
+                self.pos += 1;
+            }
+            first = false;
+        }
+    }
+}
+
+// ============================================================================
+// Statement parsing
+// ============================================================================
+
+/// Parse the DDL operations of a SQL file, in order.
+pub fn parse_sql(sql: &str) -> Vec<SchemaOp> {
+    tokenize(sql)
+        .iter()
+        .flat_map(|statement| parse_statement(statement))
+        .collect()
+}
+
+fn parse_statement(toks: &[Tok]) -> Vec<SchemaOp> {
+    let mut c = Cursor::new(toks);
+
+    if c.kw("CREATE") {
+        c.kws(&["OR", "REPLACE"]);
+        let mut temporary = false;
+        while c.kw("TEMP") || c.kw("TEMPORARY") || c.kw("GLOBAL") || c.kw("LOCAL") {
+            temporary = true;
+        }
+        c.kw("UNLOGGED");
+        if c.kw("TABLE") {
+            return if temporary {
+                vec![]
+            } else {
+                parse_create_table(&mut c)
+            };
+        }
+        let unique = c.kw("UNIQUE");
+        if c.kw("INDEX") {
+            return parse_create_index(&mut c, unique);
+        }
+        return vec![];
+    }
+
+    if c.kws(&["ALTER", "TABLE"]) {
+        c.kws(&["IF", "EXISTS"]);
+        c.kw("ONLY");
+        let Some(table) = c.name() else {
+            return vec![];
+        };
+        return split_top_level(c.rest())
+            .into_iter()
+            .flat_map(|action| parse_alter_action(&table, action))
+            .collect();
+    }
+
+    if c.kws(&["DROP", "TABLE"]) {
+        c.kws(&["IF", "EXISTS"]);
+        let mut ops = Vec::new();
+        while let Some(table) = c.name() {
+            ops.push(SchemaOp::DropTable { table });
+            if c.peek() != Some(&Tok::Punct(',')) {
+                break;
+            }
+            c.pos += 1;
+        }
+        return ops;
+    }
+
+    // MySQL: RENAME TABLE a TO b, c TO d
+    if c.kws(&["RENAME", "TABLE"]) {
+        return split_top_level(c.rest())
+            .into_iter()
+            .filter_map(|part| {
+                let mut p = Cursor::new(part);
+                let table = p.name()?;
+                p.kw("TO");
+                Some(SchemaOp::RenameTable {
+                    table,
+                    to: p.name()?,
+                })
+            })
+            .collect();
+    }
+
+    vec![]
+}
+
+fn parse_create_table(c: &mut Cursor) -> Vec<SchemaOp> {
+    let if_not_exists = c.kws(&["IF", "NOT", "EXISTS"]);
+    let Some(table) = c.name() else {
+        return vec![];
+    };
+
+    let mut columns: Vec<ColumnDef> = Vec::new();
+    let mut primary_key = Vec::new();
+    let mut foreign_keys = Vec::new();
+    if let Some(body) = c.group() {
+        for element in split_top_level(body) {
+            match parse_table_constraint(element) {
+                Some(TableConstraint::PrimaryKey(cols)) => primary_key.extend(cols),
+                Some(fk @ TableConstraint::ForeignKey { .. }) => foreign_keys.push(fk),
+                Some(_) => {}
+                None => columns.extend(parse_column_def(element)),
+            }
+        }
+    }
+
+    for column in columns.iter_mut() {
+        if primary_key.contains(&column.name) {
+            column.primary_key = true;
+            column.nullable = false;
+        }
+    }
+    for fk in foreign_keys {
+        if let TableConstraint::ForeignKey {
+            columns: fk_columns,
+            table: ref_table,
+            ref_columns,
+        } = fk
+        {
+            for (i, name) in fk_columns.iter().enumerate() {
+                if let Some(column) = columns.iter_mut().find(|col| &col.name == name) {
+                    column.references = Some(reference(&ref_table, ref_columns.get(i)));
+                }
+            }
+        }
+    }
+
+    vec![SchemaOp::CreateTable {
+        table,
+        columns,
+        if_not_exists,
+    }]
+}
+
+fn parse_create_index(c: &mut Cursor, unique: bool) -> Vec<SchemaOp> {
+    c.kw("CONCURRENTLY");
+    c.kws(&["IF", "NOT", "EXISTS"]);
+    let name = if is_kw(c.peek(), "ON") {
+        None
+    } else {
+        c.name()
+    };
+    if !c.kw("ON") {
+        return vec![];
+    }
+    c.kw("ONLY");
+    let Some(table) = c.name() else {
+        return vec![];
+    };
+    if c.kw("USING") {
+        c.ident();
+    }
+    vec![SchemaOp::CreateIndex {
+        table,
+        name,
+        columns: c.paren_list().unwrap_or_default(),
+        unique,
+    }]
+}
+
+fn parse_alter_action(table: &str, toks: &[Tok]) -> Vec<SchemaOp> {
+    let mut c = Cursor::new(toks);
+    let table = table.to_string();
+
+    if c.kw("ADD") {
+        if let Some(constraint) = parse_table_constraint(c.rest()) {
+            return match constraint {
+                TableConstraint::PrimaryKey(columns) => {
+                    vec![SchemaOp::AddPrimaryKey { table, columns }]
+                }
+                TableConstraint::ForeignKey {
+                    columns,
+                    table: references_table,
+                    ref_columns,
+                } => vec![SchemaOp::AddForeignKey {
+                    table,
+                    columns,
+                    references_table,
+                    references_columns: ref_columns,
+                }],
+                TableConstraint::Index {
+                    name,
+                    columns,
+                    unique,
+                } => vec![SchemaOp::CreateIndex {
+                    table,
+                    name,
+                    columns,
+                    unique,
+                }],
+                TableConstraint::Other => vec![],
+            };
+        }
+        c.kw("COLUMN");
+        c.kws(&["IF", "NOT", "EXISTS"]);
+        return parse_column_def(c.rest())
+            .map(|column| SchemaOp::AddColumn { table, column })
+            .into_iter()
+            .collect();
+    }
+
+    if c.kw("DROP") {
+        let is_constraint = c.kw("CONSTRAINT")
+            || c.kw("INDEX")
+            || c.kw("KEY")
+            || c.kw("CHECK")
+            || c.kws(&["PRIMARY", "KEY"])
+            || c.kws(&["FOREIGN", "KEY"]);
+        if is_constraint {
+            return vec![];
+        }
+        c.kw("COLUMN");
+        c.kws(&["IF", "EXISTS"]);
+        return c
+            .ident()
+            .map(|column| SchemaOp::DropColumn { table, column })
+            .into_iter()
+            .collect();
+    }
+
+    if c.kw("RENAME") {
+        if c.kw("TO") || c.kw("AS") {
+            return c
+                .name()
+                .map(|to| SchemaOp::RenameTable { table, to })
+                .into_iter()
+                .collect();
+        }
+        if c.kw("CONSTRAINT") || c.kw("INDEX") || c.kw("KEY") {
+            return vec![];
+        }
+        c.kw("COLUMN");
+        let Some(from) = c.ident() else {
+            return vec![];
+        };
+        c.kw("TO");
+        return c
+            .ident()
+            .map(|to| SchemaOp::RenameColumn { table, from, to })
+            .into_iter()
+            .collect();
+    }
+
+    if c.kw("ALTER") {
+        c.kw("COLUMN");
+        let Some(column) = c.ident() else {
+            return vec![];
+        };
+        let change = if c.kw("TYPE") || c.kws(&["SET", "DATA", "TYPE"]) {
+            let start = c.pos;
+            while c.peek().is_some() && !is_kw(c.peek(), "USING") && !is_kw(c.peek(), "COLLATE") {
+                if c.group().is_none() {
+                    c.pos += 1;
+                }
+            }
+            ColumnChange::Type(render(&c.toks[start..c.pos], true))
+        } else if c.kws(&["SET", "NOT", "NULL"]) {
+            ColumnChange::SetNotNull
+        } else if c.kws(&["DROP", "NOT", "NULL"]) {
+            ColumnChange::DropNotNull
+        } else if c.kws(&["SET", "DEFAULT"]) {
+            ColumnChange::SetDefault(render(c.rest(), false))
+        } else if c.kws(&["DROP", "DEFAULT"]) {
+            ColumnChange::DropDefault
+        } else {
+            return vec![];
+        };
+        return vec![SchemaOp::AlterColumn {
+            table,
+            column,
+            change,
+        }];
+    }
+
+    // MySQL: MODIFY [COLUMN] <definition>
+    if c.kw("MODIFY") {
+        c.kw("COLUMN");
+        return parse_column_def(c.rest())
+            .map(|column| SchemaOp::AlterColumn {
+                table,
+                column: column.name.clone(),
+                change: ColumnChange::Redefine(column),
+            })
+            .into_iter()
+            .collect();
+    }
+
+    // MySQL: CHANGE [COLUMN] <old name> <definition>
+    if c.kw("CHANGE") {
+        c.kw("COLUMN");
+        let Some(from) = c.ident() else {
+            return vec![];
+        };
+        let Some(column) = parse_column_def(c.rest()) else {
+            return vec![];
+        };
+        let mut ops = Vec::new();
+        if column.name != from {
+            ops.push(SchemaOp::RenameColumn {
+                table: table.clone(),
+                from,
+                to: column.name.clone(),
+            });
+        }
+        ops.push(SchemaOp::AlterColumn {
+            table,
+            column: column.name.clone(),
+            change: ColumnChange::Redefine(column),
+        });
+        return ops;
+    }
+
+    vec![]
+}
+
+enum TableConstraint {
+    PrimaryKey(Vec<String>),
+    ForeignKey {
+        columns: Vec<String>,
+        table: String,
+        ref_columns: Vec<String>,
+    },
+    Index {
+        name: Option<String>,
+        columns: Vec<String>,
+        unique: bool,
+    },
+    Other,
+}
+
+/// Parse a table-level constraint; `None` when the element is a column.
+fn parse_table_constraint(toks: &[Tok]) -> Option<TableConstraint> {
+    let mut c = Cursor::new(toks);
+    let mut name = None;
+    let named = c.kw("CONSTRAINT");
+    if named {
+        name = c.ident();
+    }
+
+    if c.kws(&["PRIMARY", "KEY"]) {
+        return Some(TableConstraint::PrimaryKey(
+            c.paren_list().unwrap_or_default(),
+        ));
+    }
+    if c.kws(&["FOREIGN", "KEY"]) {
+        let columns = c.paren_list().unwrap_or_default();
+        if !c.kw("REFERENCES") {
+            return Some(TableConstraint::Other);
+        }
+        let Some(table) = c.name() else {
+            return Some(TableConstraint::Other);
+        };
+        return Some(TableConstraint::ForeignKey {
+            columns,
+            table,
+            ref_columns: c.paren_list().unwrap_or_default(),
+        });
+    }
+    if c.kw("UNIQUE") {
+        let has_name = c.kw("KEY") || c.kw("INDEX") || name.is_none();
+        if has_name && c.peek() != Some(&Tok::Punct('(')) {
+            name = c.name().or(name);
+        }
+        return Some(TableConstraint::Index {
+            name,
+            columns: c.paren_list().unwrap_or_default(),
+            unique: true,
+        });
+    }
+    // MySQL `KEY idx (col)` / `INDEX idx (col)` — but not a column named `key`
+    if is_kw(c.peek(), "KEY") || is_kw(c.peek(), "INDEX") {
+        let after = &c.rest()[1..];
+        let is_index = match after {
+            [Tok::Punct('('), ..] => true,
+            [Tok::Word(_) | Tok::Ident(_), Tok::Punct('('), next, ..] => {
+                !matches!(next, Tok::Word(w) if w.chars().all(|ch| ch.is_ascii_digit()))
+            }
+            _ => false,
+        };
+        if is_index {
+            c.pos += 1;
+            if c.peek() != Some(&Tok::Punct('(')) {
+                name = c.name();
+            }
+            return Some(TableConstraint::Index {
+                name,
+                columns: c.paren_list().unwrap_or_default(),
+                unique: false,
+            });
+        }
+    }
+    let other = ["CHECK", "EXCLUDE", "LIKE", "FULLTEXT", "SPATIAL", "PERIOD"]
+        .iter()
+        .any(|kw| is_kw(c.peek(), kw));
+    (other || named).then_some(TableConstraint::Other)
+}
+
+fn parse_column_def(toks: &[Tok]) -> Option<ColumnDef> {
+    let mut c = Cursor::new(toks);
+    let name = c.ident()?;
+
+    let type_start = c.pos;
+    while let Some(tok) = c.peek() {
+        if is_constraint_kw(tok) {
+            break;
+        }
+        if c.group().is_none() {
+            c.pos += 1;
+        }
+    }
+    let mut column = ColumnDef {
+        name,
+        data_type: render(&toks[type_start..c.pos], true),
+        nullable: true,
+        ..Default::default()
+    };
+
+    while let Some(tok) = c.peek() {
+        if c.kws(&["NOT", "NULL"]) {
+            column.nullable = false;
+        } else if c.kw("NULL") {
+            column.nullable = true;
+        } else if c.kws(&["PRIMARY", "KEY"]) {
+            column.primary_key = true;
+            column.nullable = false;
+        } else if c.kw("DEFAULT") {
+            let start = c.pos;
+            c.skip_expr();
+            column.default_value = Some(render(&toks[start..c.pos], false));
+        } else if c.kw("REFERENCES") {
+            if let Some(table) = c.name() {
+                let ref_columns = c.paren_list().unwrap_or_default();
+                column.references = Some(reference(&table, ref_columns.first()));
+            }
+        } else if c.kw("GENERATED") {
+            while [
+                "ALWAYS", "BY", "DEFAULT", "AS", "IDENTITY", "STORED", "VIRTUAL",
+            ]
+            .iter()
+            .any(|kw| is_kw(c.peek(), kw))
+                || c.peek() == Some(&Tok::Punct('('))
+            {
+                if c.group().is_none() {
+                    c.pos += 1;
+                }
+            }
+        } else if tok == &Tok::Punct('(') {
+            c.group();
+        } else {
+            c.pos += 1;
+        }
+    }
+    Some(column)
+}
+
+fn reference(table: &str, column: Option<&String>) -> String {
+    match column {
+        Some(column) => format!("{}.{}", table, column),
+        None => table.to_string(),
+    }
+}
+
+// ============================================================================
+// Schema reconstruction
+// ============================================================================
+
+#[derive(Default)]
+struct SchemaState {
+    tables: BTreeMap<String, TableSchema>,
+}
+
+impl SchemaState {
+    fn apply(&mut self, op: &SchemaOp, source: &str) {
+        match op {
+            SchemaOp::CreateTable {
+                table,
+                columns,
+                if_not_exists,
+            } => {
+                if *if_not_exists && self.tables.contains_key(table) {
+                    return;
+                }
+                self.tables.insert(
+                    table.clone(),
+                    TableSchema {
+                        name: table.clone(),
+                        columns: columns.clone(),
+                        defined_in: Some(source.to_string()),
+                        altered_by: Vec::new(),
+                    },
+                );
+            }
+            SchemaOp::DropTable { table } => {
+                self.tables.remove(table);
+            }
+            SchemaOp::RenameTable { table, to } => {
+                let Some(mut schema) = self.tables.remove(table) else {
+                    return;
+                };
+                schema.name = to.clone();
+                touch(&mut schema, source);
+                self.tables.insert(to.clone(), schema);
+                self.rename_references(table, None, to, None);
+            }
+            SchemaOp::AddColumn { table, column } => {
+                self.with_table(table, source, |t| {
+                    match t.columns.iter_mut().find(|c| c.name == column.name) {
+                        Some(existing) => *existing = column.clone(),
+                        None => t.columns.push(column.clone()),
+                    }
+                });
+            }
+            SchemaOp::DropColumn { table, column } => {
+                self.with_table(table, source, |t| t.columns.retain(|c| &c.name != column));
+            }
+            SchemaOp::RenameColumn { table, from, to } => {
+                self.with_table(table, source, |t| {
+                    if let Some(c) = t.columns.iter_mut().find(|c| &c.name == from) {
+                        c.name = to.clone();
+                    }
+                });
+                self.rename_references(table, Some(from), table, Some(to));
+            }
+            SchemaOp::AlterColumn {
+                table,
+                column,
+                change,
+            } => {
+                self.with_table(table, source, |t| {
+                    let Some(c) = t.columns.iter_mut().find(|c| &c.name == column) else {
+                        return;
+                    };
+                    match change {
+                        ColumnChange::Type(data_type) => c.data_type = data_type.clone(),
+                        ColumnChange::SetNotNull => c.nullable = false,
+                        ColumnChange::DropNotNull => c.nullable = true,
+                        ColumnChange::SetDefault(value) => c.default_value = Some(value.clone()),
+                        ColumnChange::DropDefault => c.default_value = None,
+                        ColumnChange::Redefine(def) => *c = def.clone(),
+                    }
+                });
+            }
+            SchemaOp::AddPrimaryKey { table, columns } => {
+                self.with_table(table, source, |t| {
+                    for c in t.columns.iter_mut().filter(|c| columns.contains(&c.name)) {
+                        c.primary_key = true;
+                        c.nullable = false;
+                    }
+                });
+            }
+            SchemaOp::AddForeignKey {
+                table,
+                columns,
+                references_table,
+                references_columns,
+            } => {
+                self.with_table(table, source, |t| {
+                    for (i, name) in columns.iter().enumerate() {
+                        if let Some(c) = t.columns.iter_mut().find(|c| &c.name == name) {
+                            c.references =
+                                Some(reference(references_table, references_columns.get(i)));
+                        }
+                    }
+                });
+            }
+            SchemaOp::CreateIndex { table, .. } => {
+                self.with_table(table, source, |_| {});
+            }
+        }
+    }
+
+    /// Apply a change to an existing table; changes to unknown tables
+    /// (created outside the SQL files) are ignored.
+    fn with_table(&mut self, table: &str, source: &str, f: impl FnOnce(&mut TableSchema)) {
+        if let Some(schema) = self.tables.get_mut(table) {
+            f(schema);
+            touch(schema, source);
+        }
+    }
+
+    /// Keep foreign keys pointing at renamed tables and columns
+    fn rename_references(
+        &mut self,
+        table: &str,
+        column: Option<&str>,
+        new_table: &str,
+        new_column: Option<&str>,
+    ) {
+        for schema in self.tables.values_mut() {
+            for c in schema.columns.iter_mut() {
+                let Some(target) = c.references.as_deref() else {
+                    continue;
+                };
+                let renamed = if target == table {
+                    match column {
+                        Some(_) => continue,
+                        None => new_table.to_string(),
+                    }
+                } else if let Some((_, col)) = target.rsplit_once('.').filter(|(t, _)| *t == table)
+                {
+                    match column {
+                        Some(from) if from != col => continue,
+                        Some(_) => format!("{}.{}", new_table, new_column.unwrap_or(col)),
+                        None => format!("{}.{}", new_table, col),
+                    }
+                } else {
+                    continue;
+                };
+                c.references = Some(renamed);
+            }
+        }
+    }
+}
+
+fn touch(table: &mut TableSchema, source: &str) {
+    if table.defined_in.as_deref() != Some(source)
+        && table.altered_by.last().map(String::as_str) != Some(source)
+    {
+        table.altered_by.push(source.to_string());
+    }
+}
+
+/// Reconstruct the schema from `(path, content)` pairs of `.sql` files.
+///
+/// Schema files are applied first (by path), then migrations by version.
+pub fn build_schema(files: &[(String, String)]) -> ParsedSchema {
+    let mut schema_files = Vec::new();
+    let mut migrations = Vec::new();
+    for (path, content) in files {
+        match classify_sql_path(Path::new(path)) {
+            SqlFileKind::Schema => schema_files.push((path, content)),
+            SqlFileKind::Migration { version } => migrations.push(SqlMigration {
+                path: path.clone(),
+                version,
+                ops: parse_sql(content),
+            }),
+            SqlFileKind::Down => {}
+        }
+    }
+    schema_files.sort();
+    migrations.sort_by(|a, b| {
+        version_key(&a.version)
+            .cmp(&version_key(&b.version))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    migrations.retain(|m| !m.ops.is_empty());
+
+    let mut state = SchemaState::default();
+    for (path, content) in schema_files {
+        for op in parse_sql(content) {
+            state.apply(&op, path);
+        }
+    }
+    for migration in &migrations {
+        for op in &migration.ops {
+            state.apply(op, &migration.path);
+        }
+    }
+
+    ParsedSchema {
+        tables: state.tables.into_values().collect(),
+        migrations,
+    }
+}
+
+/// Graph nodes for the reconstructed tables.
+pub fn to_table_nodes(project_id: Uuid, schema: &ParsedSchema) -> Vec<TableNode> {
+    schema
+        .tables
+        .iter()
+        .map(|t| TableNode {
+            name: t.name.clone(),
+            project_id,
+            defined_in: t.defined_in.clone(),
+            altered_by: t.altered_by.clone(),
+            columns: t
+                .columns
+                .iter()
+                .map(|c| ColumnNode {
+                    name: c.name.clone(),
+                    data_type: c.data_type.clone(),
+                    nullable: c.nullable,
+                    primary_key: c.primary_key,
+                    default_value: c.default_value.clone(),
+                    references: c.references.clone(),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Graph nodes for the migrations, with the operations grouped per table.
+pub fn to_migration_nodes(project_id: Uuid, schema: &ParsedSchema) -> Vec<MigrationNode> {
+    schema
+        .migrations
+        .iter()
+        .enumerate()
+        .map(|(ordinal, m)| {
+            let mut alters: Vec<MigrationAlter> = Vec::new();
+            for op in &m.ops {
+                let kind = op.kind().to_string();
+                match alters.iter_mut().find(|a| a.table == op.table()) {
+                    Some(alter) => {
+                        if !alter.operations.contains(&kind) {
+                            alter.operations.push(kind);
+                        }
+                    }
+                    None => alters.push(MigrationAlter {
+                        table: op.table().to_string(),
+                        operations: vec![kind],
+                    }),
+                }
+            }
+            MigrationNode {
+                path: m.path.clone(),
+                version: m.version.clone(),
+                ordinal,
+                project_id,
+                alters,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table<'a>(schema: &'a ParsedSchema, name: &str) -> &'a TableSchema {
+        schema.tables.iter().find(|t| t.name == name).unwrap()
+    }
+
+    fn column<'a>(table: &'a TableSchema, name: &str) -> &'a ColumnDef {
+        table.columns.iter().find(|c| c.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_create_table_postgres() {
+        let ops = parse_sql(
+            r#"
+            -- users of the app
+            CREATE TABLE IF NOT EXISTS public."Users" (
+                id BIGSERIAL PRIMARY KEY,
+                email varchar(255) NOT NULL UNIQUE,
+                org_id bigint REFERENCES orgs(id) ON DELETE CASCADE,
+                created_at timestamp with time zone NOT NULL DEFAULT now(),
+                score numeric(10, 2) DEFAULT -1,
+                tags text[] DEFAULT '{}'::text[],
+                seq integer GENERATED BY DEFAULT AS IDENTITY,
+                /* inline; comment */ note text CHECK (note IS NOT NULL),
+                CONSTRAINT users_email_key UNIQUE (email)
+            );
+            "#,
+        );
+        assert_eq!(ops.len(), 1);
+        let SchemaOp::CreateTable {
+            table,
+            columns,
+            if_not_exists,
+        } = &ops[0]
+        else {
+            panic!("expected CREATE TABLE");
+        };
+        assert_eq!(table, "Users");
+        assert!(*if_not_exists);
+        let names: Vec<&str> = columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "id",
+                "email",
+                "org_id",
+                "created_at",
+                "score",
+                "tags",
+                "seq",
+                "note"
+            ]
+        );
+
+        assert!(columns[0].primary_key && !columns[0].nullable);
+        assert_eq!(columns[1].data_type, "VARCHAR(255)");
+        assert!(!columns[1].nullable);
+        assert_eq!(columns[2].references.as_deref(), Some("orgs.id"));
+        assert_eq!(columns[3].data_type, "TIMESTAMP WITH TIME ZONE");
+        assert_eq!(columns[3].default_value.as_deref(), Some("now()"));
+        assert_eq!(columns[4].data_type, "NUMERIC(10, 2)");
+        assert_eq!(columns[4].default_value.as_deref(), Some("-1"));
+        assert_eq!(columns[5].data_type, "TEXT[]");
+        assert_eq!(columns[5].default_value.as_deref(), Some("'{}'::text[]"));
+        assert_eq!(columns[6].default_value, None);
+        assert!(columns[7].nullable, "CHECK contents must not set NOT NULL");
+    }
+
+    #[test]
+    fn test_create_table_mysql_and_table_constraints() {
+        let ops = parse_sql(
+            "CREATE TABLE `settings` (\n\
+               `key` varchar(64) NOT NULL,\n\
+               `value` text,\n\
+               `user_id` int unsigned NOT NULL,\n\
+               PRIMARY KEY (`key`, `user_id`),\n\
+               KEY `idx_user` (`user_id`),\n\
+               CONSTRAINT `fk_user` FOREIGN KEY (`user_id`) REFERENCES `users` (`id`)\n\
+             ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;\n\
+             INSERT INTO settings VALUES ('a;b', 'it\\'s', 1);",
+        );
+        assert_eq!(ops.len(), 1);
+        let SchemaOp::CreateTable { columns, .. } = &ops[0] else {
+            panic!("expected CREATE TABLE");
+        };
+        assert_eq!(columns.len(), 3, "KEY idx must not become a column");
+        assert_eq!(columns[0].name, "key");
+        assert!(columns[0].primary_key);
+        assert!(columns[2].primary_key);
+        assert_eq!(columns[2].data_type, "INT UNSIGNED");
+        assert_eq!(columns[2].references.as_deref(), Some("users.id"));
+    }
+
+    #[test]
+    fn test_alter_statements() {
+        let ops = parse_sql(
+            r#"
+            ALTER TABLE ONLY users
+                ADD COLUMN IF NOT EXISTS name text,
+                DROP COLUMN legacy,
+                ALTER COLUMN email TYPE citext USING email::citext,
+                ALTER COLUMN name SET NOT NULL,
+                ALTER name SET DEFAULT 'anon';
+            ALTER TABLE users RENAME COLUMN name TO display_name;
+            ALTER TABLE users ADD CONSTRAINT users_org_fk FOREIGN KEY (org_id) REFERENCES orgs (id);
+            ALTER TABLE accounts MODIFY COLUMN balance decimal(12,2) NOT NULL;
+            ALTER TABLE accounts CHANGE `bal` `balance` int;
+            ALTER TABLE users RENAME TO members;
+            RENAME TABLE a TO b;
+            CREATE UNIQUE INDEX CONCURRENTLY idx_email ON members USING btree (lower(email));
+            DROP TABLE IF EXISTS old_a, old_b CASCADE;
+            "#,
+        );
+        let kinds: Vec<(&str, &str)> = ops.iter().map(|o| (o.kind(), o.table())).collect();
+        assert_eq!(
+            kinds,
+            [
+                ("add_column", "users"),
+                ("drop_column", "users"),
+                ("alter_column", "users"),
+                ("alter_column", "users"),
+                ("alter_column", "users"),
+                ("rename_column", "users"),
+                ("add_foreign_key", "users"),
+                ("alter_column", "accounts"),
+                ("rename_column", "accounts"),
+                ("alter_column", "accounts"),
+                ("rename_table", "members"),
+                ("rename_table", "b"),
+                ("create_index", "members"),
+                ("drop_table", "old_a"),
+                ("drop_table", "old_b"),
+            ]
+        );
+        assert_eq!(
+            ops[2],
+            SchemaOp::AlterColumn {
+                table: "users".to_string(),
+                column: "email".to_string(),
+                change: ColumnChange::Type("CITEXT".to_string()),
+            }
+        );
+        assert_eq!(
+            ops[4],
+            SchemaOp::AlterColumn {
+                table: "users".to_string(),
+                column: "name".to_string(),
+                change: ColumnChange::SetDefault("'anon'".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_function_bodies_and_dml_are_skipped() {
+        let ops = parse_sql(
+            r#"
+            CREATE FUNCTION touch() RETURNS trigger AS $$
+            BEGIN
+                NEW.updated_at = now(); -- ; inside body
+                RETURN NEW;
+            END;
+            $$ LANGUAGE plpgsql;
+            UPDATE users SET name = 'x; y';
+            CREATE TEMP TABLE scratch (id int);
+            CREATE TABLE t (id int);
+            "#,
+        );
+        assert_eq!(ops.len(), 1);
+        assert_eq!(ops[0].table(), "t");
+    }
+
+    #[test]
+    fn test_classify_sql_path() {
+        let migration = |v: &str| SqlFileKind::Migration {
+            version: v.to_string(),
+        };
+        let cases = [
+            ("/repo/db/schema.sql", SqlFileKind::Schema),
+            ("/repo/structure.sql", SqlFileKind::Schema),
+            ("/repo/migrations/0003_add_email.sql", migration("0003")),
+            (
+                "/repo/migrations/20240101120000_init.up.sql",
+                migration("20240101120000"),
+            ),
+            (
+                "/repo/migrations/20240101120000_init.down.sql",
+                SqlFileKind::Down,
+            ),
+            (
+                "/repo/migrations/2020-01-01-000000_create_users/up.sql",
+                migration("2020-01-01-000000"),
+            ),
+            (
+                "/repo/migrations/2020-01-01-000000_create_users/down.sql",
+                SqlFileKind::Down,
+            ),
+            ("/repo/sql/V10__add_index.sql", migration("10")),
+            ("/repo/sql/U10__add_index.sql", SqlFileKind::Down),
+            ("/repo/db/migrate/init.sql", migration("init")),
+        ];
+        for (path, expected) in cases {
+            assert_eq!(classify_sql_path(Path::new(path)), expected, "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_build_schema_applies_migrations_in_version_order() {
+        let files = vec![
+            (
+                "/repo/migrations/V10__rename.sql".to_string(),
+                "ALTER TABLE users RENAME COLUMN mail TO email;".to_string(),
+            ),
+            (
+                "/repo/migrations/V2__mail.sql".to_string(),
+                "ALTER TABLE users ADD COLUMN mail text NOT NULL;\n\
+                 CREATE TABLE posts (id int PRIMARY KEY, author text REFERENCES users(mail));"
+                    .to_string(),
+            ),
+            (
+                "/repo/migrations/V1__init.sql".to_string(),
+                "CREATE TABLE users (id serial PRIMARY KEY);\nCREATE TABLE tmp (x int);"
+                    .to_string(),
+            ),
+            (
+                "/repo/migrations/V3__drop_tmp.sql".to_string(),
+                "DROP TABLE tmp; INSERT INTO users DEFAULT VALUES;".to_string(),
+            ),
+            (
+                "/repo/migrations/V4__data.sql".to_string(),
+                "UPDATE users SET id = id;".to_string(),
+            ),
+            (
+                "/repo/migrations/U2__mail.sql".to_string(),
+                "DROP TABLE users;".to_string(),
+            ),
+        ];
+        let schema = build_schema(&files);
+
+        let versions: Vec<&str> = schema
+            .migrations
+            .iter()
+            .map(|m| m.version.as_str())
+            .collect();
+        assert_eq!(versions, ["1", "2", "3", "10"], "data-only V4 is omitted");
+
+        let names: Vec<&str> = schema.tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["posts", "users"]);
+
+        let users = table(&schema, "users");
+        assert_eq!(
+            users.defined_in.as_deref(),
+            Some("/repo/migrations/V1__init.sql")
+        );
+        assert_eq!(
+            users.altered_by,
+            [
+                "/repo/migrations/V2__mail.sql",
+                "/repo/migrations/V10__rename.sql"
+            ]
+        );
+        assert!(!column(users, "email").nullable);
+        assert_eq!(
+            column(table(&schema, "posts"), "author")
+                .references
+                .as_deref(),
+            Some("users.email"),
+            "foreign keys follow column renames"
+        );
+
+        let project_id = Uuid::new_v4();
+        let migrations = to_migration_nodes(project_id, &schema);
+        assert_eq!(migrations[1].ordinal, 1);
+        assert_eq!(
+            migrations[1].alters,
+            vec![
+                MigrationAlter {
+                    table: "users".to_string(),
+                    operations: vec!["add_column".to_string()],
+                },
+                MigrationAlter {
+                    table: "posts".to_string(),
+                    operations: vec!["create_table".to_string()],
+                },
+            ]
+        );
+        let tables = to_table_nodes(project_id, &schema);
+        assert_eq!(tables[1].columns.len(), 2);
+    }
+
+    #[test]
+    fn test_schema_file_applied_before_migrations() {
+        let files = vec![
+            (
+                "/repo/migrations/0002_add_bio.sql".to_string(),
+                "ALTER TABLE profiles ADD COLUMN bio text;".to_string(),
+            ),
+            (
+                "/repo/db/schema.sql".to_string(),
+                "CREATE TABLE profiles (id int NOT NULL);".to_string(),
+            ),
+        ];
+        let schema = build_schema(&files);
+        let profiles = table(&schema, "profiles");
+        assert_eq!(profiles.defined_in.as_deref(), Some("/repo/db/schema.sql"));
+        assert_eq!(profiles.columns.len(), 2);
+        assert_eq!(profiles.altered_by, ["/repo/migrations/0002_add_bio.sql"]);
+    }
+}
@@ -0,0 +1,94 @@
+//! Board ordering of tasks.
+//!
+//! Each task may carry a `board_position`: a rank string ordering it within
+//! its status column. Ranks compare lexicographically and are made of the
+//! digits `0-9a-z`; a rank can always be generated between two others, so
+//! moving a card rewrites the position of that card only. Tasks without a
+//! position sort after the ranked ones.
+
+const DIGITS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+const BASE: usize = DIGITS.len();
+
+fn digit(c: u8) -> usize {
+    DIGITS.iter().position(|&d| d == c).unwrap_or(0)
+}
+
+/// A rank sorting strictly after `lower` and strictly before `upper` (either
+/// bound may be missing). An `upper` not above `lower` is ignored.
+pub fn rank_between(lower: Option<&str>, upper: Option<&str>) -> String {
+    let lo = lower.unwrap_or_default().as_bytes();
+    let hi = upper.map(str::as_bytes).filter(|hi| *hi > lo);
+
+    let mut rank = Vec::new();
+    let mut lo_bound = true;
+    let mut hi_bound = hi.is_some();
+    for i in 0.. {
+        let l = if lo_bound {
+            lo.get(i).map_or(0, |&c| digit(c))
+        } else {
+            0
+        };
+        let h = match hi {
+            Some(hi) if hi_bound => hi.get(i).map_or(BASE, |&c| digit(c)),
+            _ => BASE,
+        };
+        if h > l + 1 {
+            rank.push(DIGITS[(l + h) / 2]);
+            break;
+        }
+        // No room at this digit: keep the lower digit and look further
+        rank.push(DIGITS[l]);
+        if h > l {
+            hi_bound = false;
+        }
+        if lo_bound && i >= lo.len() {
+            lo_bound = false;
+        }
+    }
+    String::from_utf8(rank).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn between(lower: Option<&str>, upper: Option<&str>) -> String {
+        let rank = rank_between(lower, upper);
+        if let Some(lower) = lower {
+            assert!(rank.as_str() > lower, "{rank} <= {lower}");
+        }
+        if let Some(upper) = upper {
+            assert!(rank.as_str() < upper, "{rank} >= {upper}");
+        }
+        rank
+    }
+
+    #[test]
+    fn test_rank_between() {
+        assert_eq!(between(None, None), "i");
+        assert_eq!(between(Some("i"), None), "r");
+        assert_eq!(between(None, Some("i")), "9");
+        assert_eq!(between(Some("a"), Some("b")), "ai");
+        assert_eq!(between(Some("z"), None), "zi");
+        assert_eq!(between(None, Some("01")), "00i");
+        assert_eq!(between(Some("a"), Some("a5")), "a2");
+        between(Some("ai"), Some("b"));
+        // Inverted bounds fall back to "after lower"
+        assert_eq!(rank_between(Some("r"), Some("i")), "v");
+    }
+
+    #[test]
+    fn test_rank_between_repeated_inserts() {
+        // Always inserting right after the same card keeps the order
+        let first = rank_between(None, None);
+        let mut upper = rank_between(Some(&first), None);
+        for _ in 0..200 {
+            upper = between(Some(&first), Some(&upper));
+        }
+        // ...as does always inserting at the top
+        let mut top = first;
+        for _ in 0..200 {
+            top = between(None, Some(&top));
+        }
+    }
+}
@@ -11,6 +11,59 @@ use anyhow::Result;
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// Plan and constraints to create for `req`
+fn plan_from_request(req: CreatePlanRequest, created_by: &str) -> (PlanNode, Vec<ConstraintNode>) {
+    let plan = if let Some(project_id) = req.project_id {
+        PlanNode::new_for_project(
+            req.title,
+            req.description,
+            created_by.to_string(),
+            req.priority.unwrap_or(0),
+            project_id,
+        )
+    } else {
+        PlanNode::new(
+            req.title,
+            req.description,
+            created_by.to_string(),
+            req.priority.unwrap_or(0),
+        )
+    };
+    let constraints = req
+        .constraints
+        .unwrap_or_default()
+        .into_iter()
+        .map(|c| ConstraintNode::new(c.constraint_type, c.description, c.enforced_by))
+        .collect();
+    (plan, constraints)
+}
+
+/// Task, dependencies and steps to create for `req`. The id of the task is
+/// known before it is created, so later tasks of a batch can depend on it.
+pub fn new_plan_task(req: CreateTaskRequest) -> NewPlanTask {
+    let task = TaskNode::new_full(
+        req.title,
+        req.description,
+        req.priority,
+        req.tags.unwrap_or_default(),
+        req.acceptance_criteria.unwrap_or_default(),
+        req.affected_files.unwrap_or_default(),
+        req.estimated_complexity,
+    );
+    let steps = req
+        .steps
+        .unwrap_or_default()
+        .into_iter()
+        .enumerate()
+        .map(|(i, s)| StepNode::new(i as u32, s.description, s.verification))
+        .collect();
+    NewPlanTask {
+        task,
+        depends_on: req.depends_on.unwrap_or_default(),
+        steps,
+    }
+}
+
 /// Manager for plan operations
 pub struct PlanManager {
     neo4j: Arc<dyn GraphStore>,
@@ -63,48 +116,46 @@ impl PlanManager {
 
     /// Create a new plan
     pub async fn create_plan(&self, req: CreatePlanRequest, created_by: &str) -> Result<PlanNode> {
-        let plan = if let Some(project_id) = req.project_id {
-            PlanNode::new_for_project(
-                req.title,
-                req.description,
-                created_by.to_string(),
-                req.priority.unwrap_or(0),
-                project_id,
-            )
-        } else {
-            PlanNode::new(
-                req.title,
-                req.description,
-                created_by.to_string(),
-                req.priority.unwrap_or(0),
-            )
-        };
-
-        self.neo4j.create_plan(&plan).await?;
+        let (plan, constraints) = plan_from_request(req, created_by);
+        self.neo4j
+            .create_plan_with_constraints(&plan, &constraints)
+            .await?;
+        self.emit_plan_created(&plan, &constraints);
+        Ok(plan)
+    }
 
-        // Create constraints if provided
-        if let Some(constraints) = req.constraints {
-            for constraint_req in constraints {
-                let constraint = ConstraintNode::new(
-                    constraint_req.constraint_type,
-                    constraint_req.description,
-                    constraint_req.enforced_by,
-                );
-                self.add_constraint(plan.id, &constraint).await?;
+    /// Create a plan with its tasks (see [`new_plan_task`]) in one
+    /// transaction, so a failure leaves no plan with only some of them
+    pub async fn create_plan_with_tasks(
+        &self,
+        req: CreatePlanRequest,
+        tasks: Vec<NewPlanTask>,
+        created_by: &str,
+    ) -> Result<PlanNode> {
+        let (plan, constraints) = plan_from_request(req, created_by);
+        self.neo4j
+            .create_plan_with_tasks(&plan, &constraints, &tasks)
+            .await?;
+        self.emit_plan_created(&plan, &constraints);
+        for new_task in &tasks {
+            self.emit_task_created(plan.id, &new_task.task);
+            for step in &new_task.steps {
+                self.emit_step_created(new_task.task.id, step);
             }
         }
+        Ok(plan)
+    }
 
-        {
-            let mut event =
-                CrudEvent::new(EntityType::Plan, CrudAction::Created, plan.id.to_string())
-                    .with_payload(serde_json::json!({"title": &plan.title}));
-            if let Some(pid) = plan.project_id {
-                event = event.with_project_id(pid.to_string());
-            }
-            self.emit(event);
+    fn emit_plan_created(&self, plan: &PlanNode, constraints: &[ConstraintNode]) {
+        let mut event = CrudEvent::new(EntityType::Plan, CrudAction::Created, plan.id.to_string())
+            .with_payload(serde_json::json!({"title": &plan.title}));
+        if let Some(pid) = plan.project_id {
+            event = event.with_project_id(pid.to_string());
+        }
+        self.emit(event);
+        for constraint in constraints {
+            self.emit_constraint_created(plan.id, constraint);
         }
-
-        Ok(plan)
     }
 
     /// Get a plan by ID
@@ -183,41 +234,30 @@ impl PlanManager {
 
     /// Add a task to a plan
     pub async fn add_task(&self, plan_id: Uuid, req: CreateTaskRequest) -> Result<TaskNode> {
-        let task = TaskNode::new_full(
-            req.title,
-            req.description,
-            req.priority,
-            req.tags.unwrap_or_default(),
-            req.acceptance_criteria.unwrap_or_default(),
-            req.affected_files.unwrap_or_default(),
-            req.estimated_complexity,
-        );
-
-        self.neo4j.create_task(plan_id, &task).await?;
+        let NewPlanTask {
+            task,
+            depends_on,
+            steps,
+        } = new_plan_task(req);
+        self.neo4j
+            .create_task_with_steps(plan_id, &task, &depends_on, &steps)
+            .await?;
 
-        // Add dependencies
-        if let Some(deps) = req.depends_on {
-            for dep_id in deps {
-                self.neo4j.add_task_dependency(task.id, dep_id).await?;
-            }
+        self.emit_task_created(plan_id, &task);
+        for step in &steps {
+            self.emit_step_created(task.id, step);
         }
 
-        // Add steps
-        if let Some(steps) = req.steps {
-            for (i, step_req) in steps.into_iter().enumerate() {
-                let step = StepNode::new(i as u32, step_req.description, step_req.verification);
-                self.add_step(task.id, &step).await?;
-            }
-        }
+        Ok(task)
+    }
 
+    fn emit_task_created(&self, plan_id: Uuid, task: &TaskNode) {
         self.emit(
             CrudEvent::new(EntityType::Task, CrudAction::Created, task.id.to_string())
                 .with_payload(
                     serde_json::json!({"title": &task.title, "plan_id": plan_id.to_string()}),
                 ),
         );
-
-        Ok(task)
     }
 
     /// Get task details
@@ -271,13 +311,17 @@ impl PlanManager {
     /// Add a step to a task
     pub async fn add_step(&self, task_id: Uuid, step: &StepNode) -> Result<()> {
         self.neo4j.create_step(task_id, step).await?;
+        self.emit_step_created(task_id, step);
+        Ok(())
+    }
+
+    fn emit_step_created(&self, task_id: Uuid, step: &StepNode) {
         self.emit(
             CrudEvent::new(EntityType::Step, CrudAction::Created, step.id.to_string())
                 .with_payload(
                     serde_json::json!({"task_id": task_id.to_string(), "order": step.order}),
                 ),
         );
-        Ok(())
     }
 
     /// Update step fields (description, verification)
@@ -702,11 +746,15 @@ impl PlanManager {
     /// Add a constraint to a plan
     pub async fn add_constraint(&self, plan_id: Uuid, constraint: &ConstraintNode) -> Result<()> {
         self.neo4j.create_constraint(plan_id, constraint).await?;
+        self.emit_constraint_created(plan_id, constraint);
+        Ok(())
+    }
+
+    fn emit_constraint_created(&self, plan_id: Uuid, constraint: &ConstraintNode) {
         self.emit(
             CrudEvent::new(EntityType::Constraint, CrudAction::Created, constraint.id.to_string())
                 .with_payload(serde_json::json!({"plan_id": plan_id.to_string(), "type": constraint.constraint_type})),
         );
-        Ok(())
     }
 
     // ========================================================================
@@ -810,6 +858,30 @@ mod tests {
         assert!(types.contains(&ConstraintType::Security));
     }
 
+    #[tokio::test]
+    async fn test_create_plan_rolls_back_when_a_constraint_fails() {
+        let (state, graph, _meili) = mock_app_state_with_stores();
+        let pm = PlanManager::new(state.neo4j.clone(), state.meili.clone());
+        graph
+            .mock_fail_create_constraint
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+
+        let req = CreatePlanRequest {
+            title: "Doomed Plan".to_string(),
+            description: "Its constraint can't be stored".to_string(),
+            project_id: None,
+            priority: None,
+            constraints: Some(vec![CreateConstraintRequest {
+                constraint_type: ConstraintType::Security,
+                description: "Sanitize all user input".to_string(),
+                enforced_by: None,
+            }]),
+        };
+        assert!(pm.create_plan(req, "agent").await.is_err());
+        assert!(pm.list_active_plans().await.unwrap().is_empty());
+        assert!(graph.constraints.read().await.is_empty());
+    }
+
     #[tokio::test]
     async fn test_create_plan_for_project() {
         let pm = create_plan_manager();
@@ -929,6 +1001,71 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_delete_plan_cascades() {
+        let (state, graph, _meili) = mock_app_state_with_stores();
+        let pm = PlanManager::new(state.neo4j.clone(), state.meili.clone());
+        let plan = pm
+            .create_plan(
+                CreatePlanRequest {
+                    title: "Plan".to_string(),
+                    description: "Desc".to_string(),
+                    project_id: None,
+                    priority: None,
+                    constraints: Some(vec![CreateConstraintRequest {
+                        constraint_type: ConstraintType::Performance,
+                        description: "Fast".to_string(),
+                        enforced_by: None,
+                    }]),
+                },
+                "agent",
+            )
+            .await
+            .unwrap();
+        let task = pm
+            .add_task(
+                plan.id,
+                CreateTaskRequest {
+                    title: Some("Task".to_string()),
+                    description: "With a step".to_string(),
+                    priority: None,
+                    tags: None,
+                    acceptance_criteria: None,
+                    affected_files: None,
+                    depends_on: None,
+                    steps: Some(vec![CreateStepRequest {
+                        description: "Do it".to_string(),
+                        verification: None,
+                    }]),
+                    estimated_complexity: None,
+                },
+            )
+            .await
+            .unwrap();
+        let decision = pm
+            .add_decision(
+                task.id,
+                CreateDecisionRequest {
+                    description: "Use a queue".to_string(),
+                    rationale: "Bursty load".to_string(),
+                    alternatives: None,
+                    chosen_option: None,
+                    run_id: None,
+                },
+                "agent",
+            )
+            .await
+            .unwrap();
+
+        pm.delete_plan(plan.id).await.unwrap();
+
+        assert!(pm.get_plan(plan.id).await.unwrap().is_none());
+        assert!(graph.get_task(task.id).await.unwrap().is_none());
+        assert!(graph.steps.read().await.is_empty());
+        assert!(graph.constraints.read().await.is_empty());
+        assert!(graph.get_decision(decision.id).await.unwrap().is_none());
+    }
+
     // =========================================================================
     // Task CRUD
     // =========================================================================
@@ -1046,6 +1183,121 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_add_task_rolls_back_when_a_step_fails() {
+        let (state, graph, _meili) = mock_app_state_with_stores();
+        let pm = PlanManager::new(state.neo4j.clone(), state.meili.clone());
+        let plan = pm
+            .create_plan(
+                CreatePlanRequest {
+                    title: "Plan".to_string(),
+                    description: "Desc".to_string(),
+                    project_id: None,
+                    priority: Some(1),
+                    constraints: None,
+                },
+                "agent",
+            )
+            .await
+            .unwrap();
+        // The second of the three steps can't be stored
+        *graph.mock_fail_step_order.lock().unwrap() = Some(1);
+
+        let step = |description: &str| CreateStepRequest {
+            description: description.to_string(),
+            verification: None,
+        };
+        let task_req = CreateTaskRequest {
+            title: Some("Half-stored task".to_string()),
+            description: "Task with steps".to_string(),
+            priority: None,
+            tags: None,
+            acceptance_criteria: None,
+            affected_files: None,
+            depends_on: None,
+            steps: Some(vec![step("First"), step("Second"), step("Third")]),
+            estimated_complexity: None,
+        };
+        assert!(pm.add_task(plan.id, task_req).await.is_err());
+
+        let details = pm.get_plan_details(plan.id).await.unwrap().unwrap();
+        assert!(details.tasks.is_empty());
+        assert!(graph.tasks.read().await.is_empty());
+        assert!(graph.steps.read().await.is_empty());
+    }
+
+    fn chained_tasks(titles: &[&str]) -> Vec<NewPlanTask> {
+        let mut tasks: Vec<NewPlanTask> = Vec::new();
+        for title in titles {
+            tasks.push(new_plan_task(CreateTaskRequest {
+                title: Some(title.to_string()),
+                description: format!("{} task", title),
+                priority: None,
+                tags: None,
+                acceptance_criteria: None,
+                affected_files: None,
+                depends_on: tasks.last().map(|prev| vec![prev.task.id]),
+                steps: Some(vec![CreateStepRequest {
+                    description: format!("Do {}", title),
+                    verification: None,
+                }]),
+                estimated_complexity: None,
+            }));
+        }
+        tasks
+    }
+
+    fn plan_request(title: &str) -> CreatePlanRequest {
+        CreatePlanRequest {
+            title: title.to_string(),
+            description: "Desc".to_string(),
+            project_id: None,
+            priority: Some(1),
+            constraints: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_plan_with_tasks() {
+        let pm = create_plan_manager();
+        let tasks = chained_tasks(&["First", "Second"]);
+        let first_id = tasks[0].task.id;
+        let plan = pm
+            .create_plan_with_tasks(plan_request("Plan"), tasks, "agent")
+            .await
+            .unwrap();
+
+        let details = pm.get_plan_details(plan.id).await.unwrap().unwrap();
+        assert_eq!(details.tasks.len(), 2);
+        let second = details
+            .tasks
+            .iter()
+            .find(|t| t.task.title.as_deref() == Some("Second"))
+            .unwrap();
+        assert_eq!(second.depends_on, vec![first_id]);
+        assert_eq!(second.steps.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_create_plan_with_tasks_rolls_back_when_the_last_task_fails() {
+        let (state, graph, _meili) = mock_app_state_with_stores();
+        let pm = PlanManager::new(state.neo4j.clone(), state.meili.clone());
+        *graph.mock_fail_task_title.lock().unwrap() = Some("Third".to_string());
+
+        let result = pm
+            .create_plan_with_tasks(
+                plan_request("Half-stored plan"),
+                chained_tasks(&["First", "Second", "Third"]),
+                "agent",
+            )
+            .await;
+        assert!(result.is_err());
+
+        assert!(graph.plans.read().await.is_empty());
+        assert!(graph.tasks.read().await.is_empty());
+        assert!(graph.steps.read().await.is_empty());
+    }
+
     #[tokio::test]
     async fn test_add_task_with_dependencies() {
         let pm = create_plan_manager();
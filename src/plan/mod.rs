@@ -1,5 +1,6 @@
 //! Plan management module
 
+pub mod board;
 pub mod manager;
 pub mod models;
 
@@ -1,8 +1,8 @@
 //! Plan-related models and DTOs
 
 use crate::neo4j::models::{
-    ConstraintNode, ConstraintType, DecisionNode, DecisionStatus, PlanNode, PlanStatus, StepNode,
-    StepStatus, TaskNode, TaskStatus,
+    CommentNode, CommitNode, ConstraintNode, ConstraintType, DecisionNode, DecisionStatus,
+    PlanNode, PlanStatus, StepNode, StepStatus, TaskNode, TaskStatus,
 };
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
@@ -148,6 +148,9 @@ pub struct TaskDetails {
     pub decisions: Vec<DecisionNode>,
     pub depends_on: Vec<Uuid>,
     pub modifies_files: Vec<String>,
+    /// Commits produced by the sessions working on the task (IMPLEMENTED_BY)
+    #[serde(default)]
+    pub implemented_by: Vec<CommitNode>,
 }
 
 /// Agent context for executing a task
@@ -182,6 +185,10 @@ pub struct AgentContext {
     /// Biomimicry: Frustration-Catharsis feedback loop.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub frustration_signals: Option<FrustrationSignals>,
+
+    /// Discussion on the task and its plan, oldest first
+    #[serde(default)]
+    pub comments: Vec<CommentNode>,
 }
 
 /// Frustration-Catharsis signals injected into context when a task is struggling.
@@ -277,6 +284,18 @@ pub struct FileContext {
     /// Notes directly attached to this file or its entities
     #[serde(default)]
     pub notes: Vec<ContextNote>,
+    /// Test line coverage (0–100), once a coverage report was ingested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coverage_pct: Option<f64>,
+    /// Poorly tested functions of this file, most central (PageRank) first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub untested_functions: Vec<String>,
+    /// Open Sentry issues going through this file, most events first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub production_errors: Vec<String>,
+    /// CODEOWNERS owners of this file, who should review the change
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub owners: Vec<String>,
 }
 
 /// A note surfaced in context for an agent
@@ -327,6 +346,7 @@ impl PlanNode {
             project_id: None,
             execution_context: None,
             persona: None,
+            version: 0,
         }
     }
 
@@ -349,6 +369,7 @@ impl PlanNode {
             project_id: Some(project_id),
             execution_context: None,
             persona: None,
+            version: 0,
         }
     }
 }
@@ -377,6 +398,8 @@ impl TaskNode {
             execution_context: None,
             persona: None,
             prompt_cache: None,
+            board_position: None,
+            version: 0,
         }
     }
 
@@ -411,6 +434,8 @@ impl TaskNode {
             execution_context: None,
             persona: None,
             prompt_cache: None,
+            board_position: None,
+            version: 0,
         }
     }
 
@@ -435,6 +460,7 @@ impl StepNode {
             completed_at: None,
             execution_context: None,
             persona: None,
+            version: 0,
         }
     }
 }
@@ -845,6 +871,7 @@ mod tests {
             related_decisions: vec![],
             notes: vec![],
             frustration_signals: None,
+            comments: vec![],
         };
 
         let json = serde_json::to_string(&context).unwrap();
@@ -863,6 +890,10 @@ mod tests {
             dependent_files: vec!["src/other.rs".to_string()],
             dependencies: vec!["src/lib.rs".to_string()],
             notes: vec![],
+            coverage_pct: None,
+            untested_functions: vec![],
+            production_errors: vec![],
+            owners: vec![],
         };
 
         let json = serde_json::to_string(&context).unwrap();
@@ -407,6 +407,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         mock.create_project(&project).await.unwrap();
 
@@ -596,6 +596,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         store.create_project(&project).await.unwrap();
 
@@ -948,6 +949,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         store.create_project(&project).await.unwrap();
 
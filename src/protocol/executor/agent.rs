@@ -311,6 +311,9 @@ async fn execute_via_agent(
         permission_mode: Some("bypassPermissions".to_string()),
         add_dirs: None,
         workspace_slug: None,
+        sandbox: None,
+        limits: None,
+        template: None,
         user_claims: Some(crate::auth::jwt::Claims::service_account(&format!(
             "protocol-agent:{}",
             run.id
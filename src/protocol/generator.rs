@@ -164,6 +164,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         store.create_project(&project).await.unwrap();
 
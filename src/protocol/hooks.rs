@@ -15,7 +15,8 @@
 //! Spawned at server startup via [`spawn_protocol_scheduler`]. Evaluates every hour
 //! which protocols have `trigger_mode = Scheduled | Auto` and a `schedule` field
 //! in their `trigger_config`. If the schedule interval has elapsed since
-//! `last_triggered_at`, a new run is started automatically.
+//! `last_triggered_at`, a new run is started automatically. With several
+//! instances, only the elected leader evaluates schedules.
 //!
 //! ## Debounce
 //!
@@ -442,6 +443,7 @@ fn schedule_interval_secs(schedule: &str) -> Option<i64> {
 pub fn spawn_protocol_scheduler(
     store: Arc<dyn GraphStore>,
     emitter: Option<Arc<dyn EventEmitter>>,
+    leadership: crate::events::Leadership,
 ) {
     tokio::spawn(async move {
         let mut interval =
@@ -453,6 +455,9 @@ pub fn spawn_protocol_scheduler(
 
         loop {
             interval.tick().await;
+            if !leadership.is_leader() {
+                continue;
+            }
 
             tracing::debug!("Protocol scheduler: tick — orphan recovery + timeout + scheduling");
 
@@ -605,6 +610,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         store.create_project(&project).await.unwrap();
 
@@ -823,6 +829,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         store.create_project(&project).await.unwrap();
 
@@ -878,6 +885,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         store.create_project(&project).await.unwrap();
 
@@ -1169,6 +1177,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         store.create_project(&project).await.unwrap();
 
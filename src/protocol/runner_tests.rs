@@ -81,6 +81,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         store.create_project(&project).await.unwrap();
 
@@ -175,6 +176,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         store.create_project(&project).await.unwrap();
 
@@ -725,6 +727,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         store.create_project(&project).await.unwrap();
 
@@ -237,6 +237,7 @@ mod tests {
             path: path.to_string(),
             language: "rust".to_string(),
             hash: "abc123".to_string(),
+            line_count: 0,
             functions: functions
                 .iter()
                 .map(|(name, line)| FunctionNode {
@@ -266,6 +267,7 @@ mod tests {
                     docstring: None,
                     parent_class: None,
                     interfaces: vec![],
+                    fields: vec![],
                 })
                 .collect(),
             traits: vec![],
@@ -274,6 +276,8 @@ mod tests {
             impl_blocks: vec![],
             function_calls: vec![],
             symbols: vec![],
+            function_fingerprints: vec![],
+            encoding: None,
         }
     }
 
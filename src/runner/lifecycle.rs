@@ -186,6 +186,7 @@ mod tests {
             project_id: Some(project.id),
             execution_context: None,
             persona: None,
+            version: 0,
         };
         mock.create_plan(&plan).await.unwrap();
         (plan_id, project.id)
@@ -207,6 +208,7 @@ mod tests {
             project_id: None,
             execution_context: None,
             persona: None,
+            version: 0,
         };
         mock.create_plan(&plan).await.unwrap();
 
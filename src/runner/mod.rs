@@ -12,6 +12,7 @@
 //! ├── guard.rs     — AgentGuard: drift detection, hint injection
 //! ├── verifier.rs  — post-task verification: build, steps, git
 //! ├── enricher.rs  — post-task knowledge capture (V1: git-based)
+//! ├── report.rs    — RunReport: structured outcome of a finished run
 //! ├── trigger.rs   — TriggerEngine: evaluation + firing
 //! └── providers/   — trigger providers (schedule, webhook, event)
 //! ```
@@ -26,6 +27,7 @@ pub mod models;
 pub mod persona;
 pub mod prompt;
 pub mod providers;
+pub mod report;
 #[allow(clippy::module_inception)]
 pub mod runner;
 pub mod state;
@@ -60,6 +62,7 @@ pub use prompt::{
     build_runner_constraints, PromptBuilder, PromptSection, RunnerPromptContext, StructuredPrompt,
 };
 pub use providers::TriggerProvider;
+pub use report::{RunReport, RunReportTask};
 pub use runner::{PlanRunner, RunStatus, RUNNER_CANCEL, RUNNER_STATE};
 pub use state::RunnerState;
 pub use trigger::TriggerEngine;
@@ -465,6 +465,19 @@ pub enum RunnerEvent {
         to_state: String,
         trigger: String,
     },
+    /// The report of a finished run was recorded (`GET /api/runs/{run_id}`)
+    RunReported {
+        run_id: Uuid,
+        plan_id: Uuid,
+        plan_title: String,
+        status: PlanRunStatus,
+        tasks_completed: usize,
+        tasks_failed: usize,
+        files_changed: usize,
+        notes_created: u32,
+        cost_usd: f64,
+        duration_secs: f64,
+    },
 }
 
 // ============================================================================
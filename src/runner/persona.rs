@@ -987,6 +987,8 @@ mod tests {
             execution_context: None,
             persona: None,
             prompt_cache: None,
+            board_position: None,
+            version: 0,
         }
     }
 
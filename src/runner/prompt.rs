@@ -36,6 +36,8 @@ pub enum PromptSection {
     Enrichment(String),
     /// Propagated notes from Knowledge Fabric
     PropagatedNotes(String),
+    /// Comments on the task and its plan
+    Discussion(String),
     /// Custom section (lowest priority)
     Custom(String),
 }
@@ -57,7 +59,8 @@ impl PromptSection {
             Self::PersonaContext(_) => 7,
             Self::FileContext(_) => 8,
             Self::RunnerConstraints(_) => 9,
-            Self::Custom(_) => 10,
+            Self::Discussion(_) => 10,
+            Self::Custom(_) => 11,
         }
     }
 
@@ -74,6 +77,7 @@ impl PromptSection {
             Self::FileContext(_) => "Files to Modify",
             Self::Enrichment(_) => "Enrichment Context",
             Self::RunnerConstraints(_) => "Runner Constraints",
+            Self::Discussion(_) => "Discussion",
             Self::Custom(_) => "Additional Context",
         }
     }
@@ -91,6 +95,7 @@ impl PromptSection {
             | Self::FileContext(s)
             | Self::Enrichment(s)
             | Self::RunnerConstraints(s)
+            | Self::Discussion(s)
             | Self::Custom(s) => s,
         }
     }
@@ -176,6 +181,11 @@ impl PromptBuilder {
         self.add_section(PromptSection::PropagatedNotes(notes.into()))
     }
 
+    /// Add the discussion section.
+    pub fn with_discussion(self, discussion: impl Into<String>) -> Self {
+        self.add_section(PromptSection::Discussion(discussion.into()))
+    }
+
     /// Add a custom section.
     pub fn with_custom(self, content: impl Into<String>) -> Self {
         self.add_section(PromptSection::Custom(content.into()))
@@ -946,7 +956,8 @@ mod tests {
         // Simulate a full runner prompt with persona context included.
         // New priority order: Task(0), Steps(1), Constraints(2), Enrichment(3),
         // SkillContext(4), KnowledgeNotes(5), PropagatedNotes(6),
-        // PersonaContext(7), FileContext(8), RunnerConstraints(9), Custom(10).
+        // PersonaContext(7), FileContext(8), RunnerConstraints(9), Discussion(10),
+        // Custom(11).
         let prompt = PromptBuilder::new()
             .with_task("Implement cache layer")
             .with_steps("1. Add Redis\n2. Wire up")
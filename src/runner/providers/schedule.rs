@@ -5,6 +5,7 @@
 //! and checked against the current time at each tick.
 
 use super::TriggerProvider;
+use crate::events::Leadership;
 use crate::neo4j::traits::GraphStore;
 use crate::runner::models::TriggerType;
 use crate::runner::trigger::TriggerEngine;
@@ -22,6 +23,8 @@ pub struct ScheduleProvider {
     graph: Arc<dyn GraphStore>,
     engine: Arc<TriggerEngine>,
     tick_interval_secs: u64,
+    /// Triggers only fire while this instance leads
+    leadership: Leadership,
     shutdown_tx: watch::Sender<bool>,
     shutdown_rx: watch::Receiver<bool>,
 }
@@ -45,10 +48,18 @@ impl ScheduleProvider {
             graph,
             engine,
             tick_interval_secs: tick_interval_secs.unwrap_or(60),
+            leadership: Leadership::always(),
             shutdown_tx,
             shutdown_rx,
         }
     }
+
+    /// Only evaluate triggers while `leadership` holds, so replicas don't
+    /// fire the same schedule twice.
+    pub fn with_leadership(mut self, leadership: Leadership) -> Self {
+        self.leadership = leadership;
+        self
+    }
 }
 
 #[async_trait]
@@ -57,6 +68,7 @@ impl TriggerProvider for ScheduleProvider {
         let graph = self.graph.clone();
         let engine = self.engine.clone();
         let tick = self.tick_interval_secs;
+        let leadership = self.leadership.clone();
         let mut shutdown_rx = self.shutdown_rx.clone();
 
         tokio::spawn(async move {
@@ -66,6 +78,9 @@ impl TriggerProvider for ScheduleProvider {
             loop {
                 tokio::select! {
                     _ = interval.tick() => {
+                        if !leadership.is_leader() {
+                            continue;
+                        }
                         if let Err(e) = evaluate_schedule_triggers(&graph, &engine).await {
                             error!("ScheduleProvider tick error: {}", e);
                         }
@@ -0,0 +1,290 @@
+//! Run reports — the structured outcome of a finished plan run
+//!
+//! When a run ends (headless or started from a chat), [`record_run_report`]
+//! gathers what it did — tasks touched, files changed, commits, notes
+//! created, cost, duration and the agent transcripts — into a [`RunReport`],
+//! persisted as a `(:PlanRun)-[:HAS_REPORT]->(:RunReport)` node and returned
+//! by `GET /api/runs/{run_id}`. The runner then emits
+//! `RunnerEvent::RunReported`, which notification rules can match.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use uuid::Uuid;
+
+use super::models::{PlanRunStatus, TriggerSource};
+use super::state::RunnerState;
+use super::vector::KnowledgeDim;
+use crate::neo4j::agent_execution::{AgentExecutionNode, AgentExecutionStatus};
+use crate::neo4j::GraphStore;
+
+/// Structured summary of a finished plan run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunReport {
+    pub run_id: Uuid,
+    pub plan_id: Uuid,
+    pub plan_title: String,
+    pub project_id: Option<Uuid>,
+    pub status: PlanRunStatus,
+    pub triggered_by: TriggerSource,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub duration_secs: f64,
+    pub cost_usd: f64,
+    /// Tasks an agent worked on, in execution order
+    pub tasks: Vec<RunReportTask>,
+    /// Files modified by the agents, sorted
+    pub files_changed: Vec<String>,
+    /// Commits produced by the agents
+    pub commits: Vec<String>,
+    pub notes_created: u32,
+    pub decisions_created: u32,
+    /// Transcript of the chat session that started the run, for chat-driven runs
+    pub transcript_url: Option<String>,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// One task of a [`RunReport`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunReportTask {
+    pub task_id: Uuid,
+    pub title: String,
+    pub status: AgentExecutionStatus,
+    /// Agent executions of the task (retries and gate retries included)
+    pub attempts: u32,
+    pub cost_usd: f64,
+    pub duration_secs: f64,
+    pub files_modified: Vec<String>,
+    pub commits: Vec<String>,
+    /// Chat session of the last agent
+    pub session_id: Option<Uuid>,
+    /// Transcript of the last agent
+    pub transcript_url: Option<String>,
+}
+
+/// API path of the messages of a chat session.
+fn transcript_url(session_id: impl std::fmt::Display) -> String {
+    format!("/api/chat/sessions/{}/messages", session_id)
+}
+
+impl RunReport {
+    /// Build the report of a finalized run from its agent executions.
+    /// `task_titles` are looked up by task id; `knowledge` is the knowledge
+    /// dimension of the run's execution vector.
+    pub fn build(
+        state: &RunnerState,
+        plan_title: String,
+        executions: &[AgentExecutionNode],
+        task_titles: &std::collections::HashMap<Uuid, String>,
+        knowledge: &KnowledgeDim,
+    ) -> Self {
+        let mut executions: Vec<&AgentExecutionNode> = executions.iter().collect();
+        executions.sort_by_key(|ae| ae.started_at);
+
+        let mut tasks: Vec<RunReportTask> = Vec::new();
+        for ae in executions.iter().copied() {
+            let task = match tasks.iter_mut().find(|t| t.task_id == ae.task_id) {
+                Some(task) => task,
+                None => {
+                    tasks.push(RunReportTask {
+                        task_id: ae.task_id,
+                        title: task_titles
+                            .get(&ae.task_id)
+                            .cloned()
+                            .unwrap_or_else(|| ae.task_id.to_string()),
+                        status: ae.status,
+                        attempts: 0,
+                        cost_usd: 0.0,
+                        duration_secs: 0.0,
+                        files_modified: Vec::new(),
+                        commits: Vec::new(),
+                        session_id: None,
+                        transcript_url: None,
+                    });
+                    tasks.last_mut().expect("task just pushed")
+                }
+            };
+            task.status = ae.status;
+            task.attempts += 1;
+            task.cost_usd += ae.cost_usd;
+            task.duration_secs += ae.duration_secs;
+            for file in &ae.files_modified {
+                if !task.files_modified.contains(file) {
+                    task.files_modified.push(file.clone());
+                }
+            }
+            for commit in &ae.commits {
+                if !task.commits.contains(commit) {
+                    task.commits.push(commit.clone());
+                }
+            }
+            if let Some(session_id) = ae.session_id {
+                task.session_id = Some(session_id);
+                task.transcript_url = Some(transcript_url(session_id));
+            }
+        }
+        // The run state is authoritative on the task outcome
+        for task in &mut tasks {
+            if state.completed_tasks.contains(&task.task_id) {
+                task.status = AgentExecutionStatus::Completed;
+            } else if state.failed_tasks.contains(&task.task_id) {
+                task.status = AgentExecutionStatus::Failed;
+            }
+        }
+
+        let files_changed: BTreeSet<&String> =
+            tasks.iter().flat_map(|t| &t.files_modified).collect();
+        let commits = tasks.iter().flat_map(|t| t.commits.clone()).collect();
+        let transcript_url = match &state.triggered_by {
+            TriggerSource::Chat {
+                session_id: Some(session_id),
+            } => Some(transcript_url(session_id)),
+            _ => None,
+        };
+
+        Self {
+            run_id: state.run_id,
+            plan_id: state.plan_id,
+            plan_title,
+            project_id: state.project_id,
+            status: state.status,
+            triggered_by: state.triggered_by.clone(),
+            started_at: state.started_at,
+            completed_at: state.completed_at,
+            duration_secs: state.elapsed_secs(),
+            cost_usd: state.cost_usd,
+            files_changed: files_changed.into_iter().cloned().collect(),
+            commits,
+            tasks,
+            notes_created: knowledge.notes_created,
+            decisions_created: knowledge.decisions_created,
+            transcript_url,
+            generated_at: Utc::now(),
+        }
+    }
+}
+
+/// Build the report of a finalized run and persist it.
+pub async fn record_run_report(
+    graph: &dyn GraphStore,
+    state: &RunnerState,
+    knowledge: &KnowledgeDim,
+) -> anyhow::Result<RunReport> {
+    let plan_title = graph
+        .get_plan(state.plan_id)
+        .await?
+        .map(|p| p.title)
+        .unwrap_or_default();
+    let executions = graph.get_agent_executions_for_run(state.run_id).await?;
+
+    let mut task_titles = std::collections::HashMap::new();
+    for task_id in executions.iter().map(|ae| ae.task_id) {
+        if task_titles.contains_key(&task_id) {
+            continue;
+        }
+        if let Some(title) = graph.get_task(task_id).await?.and_then(|t| t.title) {
+            task_titles.insert(task_id, title);
+        }
+    }
+
+    let report = RunReport::build(state, plan_title, &executions, &task_titles, knowledge);
+    graph.save_run_report(&report).await?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn execution(
+        run_id: Uuid,
+        task_id: Uuid,
+        status: AgentExecutionStatus,
+        files: &[&str],
+    ) -> AgentExecutionNode {
+        AgentExecutionNode {
+            id: Uuid::new_v4(),
+            run_id,
+            task_id,
+            session_id: Some(Uuid::new_v4()),
+            started_at: Utc::now(),
+            completed_at: Some(Utc::now()),
+            cost_usd: 0.5,
+            duration_secs: 30.0,
+            status,
+            tools_used: "{}".to_string(),
+            files_modified: files.iter().map(|f| f.to_string()).collect(),
+            commits: vec![format!("sha-{}", files.len())],
+            persona_profile: String::new(),
+            vector_json: None,
+            report_json: None,
+            execution_type: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_build_report() {
+        let run_id = Uuid::new_v4();
+        let (login, signup) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut state = RunnerState::new(
+            run_id,
+            Uuid::new_v4(),
+            2,
+            TriggerSource::Chat {
+                session_id: Some("chat-1".to_string()),
+            },
+        );
+        state.cost_usd = 1.5;
+        state.completed_tasks.push(login);
+        state.failed_tasks.push(signup);
+        state.finalize(PlanRunStatus::CompletedWithErrors);
+
+        let first_try = execution(run_id, login, AgentExecutionStatus::Failed, &["src/a.rs"]);
+        let mut retry = execution(
+            run_id,
+            login,
+            AgentExecutionStatus::Completed,
+            &["src/a.rs", "src/b.rs"],
+        );
+        retry.started_at = first_try.started_at + chrono::Duration::seconds(60);
+        let failed = execution(run_id, signup, AgentExecutionStatus::Timeout, &[]);
+        let titles = HashMap::from([(login, "Add login".to_string())]);
+        let knowledge = KnowledgeDim {
+            notes_created: 3,
+            ..Default::default()
+        };
+
+        let report = RunReport::build(
+            &state,
+            "Auth".to_string(),
+            &[retry.clone(), failed.clone(), first_try],
+            &titles,
+            &knowledge,
+        );
+        assert_eq!(report.status, PlanRunStatus::CompletedWithErrors);
+        assert_eq!(report.tasks.len(), 2);
+        let task = &report.tasks[0];
+        assert_eq!(task.title, "Add login");
+        assert_eq!(task.status, AgentExecutionStatus::Completed);
+        assert_eq!(task.attempts, 2);
+        assert_eq!(task.cost_usd, 1.0);
+        assert_eq!(task.files_modified, vec!["src/a.rs", "src/b.rs"]);
+        assert_eq!(
+            task.transcript_url,
+            Some(format!(
+                "/api/chat/sessions/{}/messages",
+                retry.session_id.unwrap()
+            ))
+        );
+        // No title found: the id stands in; the run state marks it failed
+        assert_eq!(report.tasks[1].title, signup.to_string());
+        assert_eq!(report.tasks[1].status, AgentExecutionStatus::Failed);
+        assert_eq!(report.files_changed, vec!["src/a.rs", "src/b.rs"]);
+        assert_eq!(report.notes_created, 3);
+        assert_eq!(
+            report.transcript_url.as_deref(),
+            Some("/api/chat/sessions/chat-1/messages")
+        );
+    }
+}
@@ -525,6 +525,9 @@ impl PlanRunner {
                 RunnerEvent::LifecycleTransition { run_id, .. } => {
                     (run_id.to_string(), CrudAction::StatusChanged)
                 }
+                RunnerEvent::RunReported { run_id, .. } => {
+                    (run_id.to_string(), CrudAction::Created)
+                }
             };
 
             let payload = serde_json::to_value(&event).unwrap_or_default();
@@ -2365,6 +2368,8 @@ impl PlanRunner {
                         execution_context: None,
                         persona: None,
                         prompt_cache: None,
+                        board_position: None,
+                        version: 0,
                     },
                     0,
                 )
@@ -2632,6 +2637,9 @@ impl PlanRunner {
             permission_mode: Some("bypassPermissions".to_string()),
             add_dirs: None,
             workspace_slug: None,
+            sandbox: None,
+            limits: None,
+            template: None,
             user_claims: Some(self.user_claims.clone().unwrap_or_else(|| {
                 crate::auth::jwt::Claims::service_account(&format!("runner-agent:{}", run_id))
             })),
@@ -3707,6 +3715,8 @@ impl PlanRunner {
             completed_tasks: Vec<Uuid>,
             failed_tasks: Vec<Uuid>,
             started_at: chrono::DateTime<chrono::Utc>,
+            run_state: RunnerState,
+            knowledge: crate::runner::vector::KnowledgeDim,
         }
 
         let snapshot = {
@@ -3717,6 +3727,7 @@ impl PlanRunner {
             }
 
             // Finalize the execution vector from the collector
+            let mut knowledge = Default::default();
             if let Some(ref state) = *global {
                 let collector = VECTOR_COLLECTOR.read().await;
                 let vector = collector.finalize(state);
@@ -3727,6 +3738,7 @@ impl PlanRunner {
                     vector.velocity(),
                     vector.stability()
                 );
+                knowledge = vector.knowledge;
             }
 
             // Extract everything we need, then drop the lock
@@ -3739,6 +3751,8 @@ impl PlanRunner {
                 completed_tasks: s.completed_tasks.clone(),
                 failed_tasks: s.failed_tasks.clone(),
                 started_at: s.started_at,
+                run_state: s.clone(),
+                knowledge,
             })
         };
         // ============================================================
@@ -3959,9 +3973,36 @@ impl PlanRunner {
             _ => {}
         }
 
+        // Run report — after the post-run sweep so its commits are in
+        self.record_report(&snap.run_state, &snap.knowledge).await;
+
         Ok(())
     }
 
+    /// Record the report of a finished run and announce it. Non-fatal.
+    async fn record_report(
+        &self,
+        state: &RunnerState,
+        knowledge: &crate::runner::vector::KnowledgeDim,
+    ) {
+        match crate::runner::report::record_run_report(self.graph.as_ref(), state, knowledge).await
+        {
+            Ok(report) => self.emit_event(RunnerEvent::RunReported {
+                run_id: report.run_id,
+                plan_id: report.plan_id,
+                plan_title: report.plan_title.clone(),
+                status: report.status,
+                tasks_completed: state.completed_tasks.len(),
+                tasks_failed: state.failed_tasks.len(),
+                files_changed: report.files_changed.len(),
+                notes_created: report.notes_created,
+                cost_usd: report.cost_usd,
+                duration_secs: report.duration_secs,
+            }),
+            Err(e) => warn!("Failed to record report of run {}: {}", state.run_id, e),
+        }
+    }
+
     /// Fire a lifecycle protocol transition and emit a WebSocket event.
     ///
     /// Non-fatal: logs warnings on failure but never propagates errors.
@@ -4666,6 +4707,9 @@ mod tests {
             jwt_secret: None,
             server_port: 0,
             session_token_expiry_secs: 3600,
+            sandbox: Default::default(),
+            limits: Default::default(),
+            model_routing: Default::default(),
         };
         let chat_manager = Arc::new(ChatManager::new_without_memory(
             graph.clone(),
@@ -5227,6 +5271,9 @@ mod tests {
             jwt_secret: None,
             server_port: 0,
             session_token_expiry_secs: 3600,
+            sandbox: Default::default(),
+            limits: Default::default(),
+            model_routing: Default::default(),
         };
         let chat_manager = Arc::new(ChatManager::new_without_memory(
             graph.clone(),
@@ -6435,6 +6482,10 @@ mod tests {
             "Completed should map to PlanStatus::Completed"
         );
 
+        let report = graph.get_run_report(run_id).await.unwrap().unwrap();
+        assert_eq!(report.status, PlanRunStatus::Completed);
+        assert_eq!(report.plan_title, plan.title);
+
         // Cleanup
         {
             let mut global = RUNNER_STATE.write().await;
@@ -0,0 +1,239 @@
+//! Sentry REST API calls and the stack frames of Sentry events.
+
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+use super::SentryConfig;
+use crate::orchestrator::stack_trace::{parse_stack_trace, StackFrame, MAX_FRAMES};
+
+/// Open issues fetched per Sentry project (one page, most recently seen first)
+pub const MAX_ISSUES: usize = 100;
+
+/// An issue as listed by `GET /api/0/projects/{org}/{project}/issues/`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SentryIssue {
+    pub id: String,
+    pub short_id: String,
+    pub title: String,
+    #[serde(default)]
+    pub culprit: Option<String>,
+    #[serde(default)]
+    pub level: String,
+    /// Sent as a string
+    #[serde(default, deserialize_with = "count")]
+    pub count: u64,
+    #[serde(default)]
+    pub user_count: u64,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    #[serde(default)]
+    pub permalink: Option<String>,
+}
+
+fn count<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    Ok(match Value::deserialize(deserializer)? {
+        Value::String(s) => s.parse().unwrap_or(0),
+        value => value.as_u64().unwrap_or(0),
+    })
+}
+
+/// Client of one Sentry organization
+pub struct SentryClient {
+    http: reqwest::Client,
+    base_url: String,
+    token: String,
+    organization: String,
+}
+
+impl SentryClient {
+    pub fn new(base_url: &str, token: &str, organization: &str) -> Self {
+        Self {
+            http: reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .unwrap_or_else(|_| reqwest::Client::new()),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token: token.to_string(),
+            organization: organization.to_string(),
+        }
+    }
+
+    /// Client of the configured organization, `None` without a token.
+    pub fn from_config(config: &SentryConfig) -> Option<Self> {
+        let token = config.token.as_deref().filter(|t| !t.is_empty())?;
+        Some(Self::new(&config.url, token, &config.organization))
+    }
+
+    async fn get(&self, url: &str, query: &[(&str, String)]) -> Result<Value> {
+        let response = self
+            .http
+            .get(url)
+            .query(query)
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach {}", url))?;
+        if !response.status().is_success() {
+            bail!("{} returned {}", url, response.status());
+        }
+        response
+            .json()
+            .await
+            .with_context(|| format!("Invalid response from {}", url))
+    }
+
+    /// Unresolved issues of a Sentry project, most recently seen first.
+    pub async fn open_issues(&self, sentry_project: &str) -> Result<Vec<SentryIssue>> {
+        let url = format!(
+            "{}/api/0/projects/{}/{}/issues/",
+            self.base_url, self.organization, sentry_project
+        );
+        let body = self
+            .get(
+                &url,
+                &[
+                    ("query", "is:unresolved".to_string()),
+                    ("sort", "date".to_string()),
+                    ("limit", MAX_ISSUES.to_string()),
+                ],
+            )
+            .await?;
+        serde_json::from_value(body).with_context(|| format!("Invalid issue list from {}", url))
+    }
+
+    /// Latest event of an issue, with its exception stacks.
+    pub async fn latest_event(&self, issue_id: &str) -> Result<Value> {
+        let url = format!(
+            "{}/api/0/organizations/{}/issues/{}/events/latest/",
+            self.base_url, self.organization, issue_id
+        );
+        self.get(&url, &[]).await
+    }
+}
+
+/// Frames of the exception stacks of an event, innermost first. Frames
+/// Sentry flags as not in-app are dropped. An event without stack (an error
+/// logged with its trace in the message) falls back to parsing the message.
+pub fn event_frames(event: &Value) -> Vec<StackFrame> {
+    let mut frames = Vec::new();
+    let entries = event["entries"].as_array().into_iter().flatten();
+    for entry in entries.filter(|e| e["type"] == "exception") {
+        for exception in entry["data"]["values"].as_array().into_iter().flatten() {
+            // Sentry lists the outermost call first
+            let stack = exception["stacktrace"]["frames"].as_array();
+            for frame in stack.into_iter().flatten().rev() {
+                if frame["inApp"] == false {
+                    continue;
+                }
+                let file = frame["filename"]
+                    .as_str()
+                    .filter(|f| !f.is_empty())
+                    .or_else(|| frame["absPath"].as_str());
+                let (Some(file), Some(line)) = (file, frame["lineNo"].as_u64()) else {
+                    continue;
+                };
+                frames.push(StackFrame {
+                    function: frame["function"].as_str().map(str::to_string),
+                    file: file.to_string(),
+                    line: line as u32,
+                    column: frame["colNo"].as_u64().map(|c| c as u32),
+                    file_path: None,
+                    function_id: None,
+                });
+            }
+        }
+    }
+    if frames.is_empty() {
+        let message = event["message"]
+            .as_str()
+            .filter(|m| !m.is_empty())
+            .or_else(|| {
+                event["entries"]
+                    .as_array()?
+                    .iter()
+                    .find(|e| e["type"] == "message")?["data"]["formatted"]
+                    .as_str()
+            });
+        if let Some(message) = message {
+            frames = parse_stack_trace(message).frames;
+        }
+    }
+    frames.truncate(MAX_FRAMES);
+    frames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_event_frames() {
+        let event = json!({
+            "entries": [{
+                "type": "exception",
+                "data": {"values": [{
+                    "type": "TypeError",
+                    "stacktrace": {"frames": [
+                        {"filename": "node_modules/express/lib/router.js", "lineNo": 10, "inApp": false},
+                        {"filename": "./src/routes.js", "function": "handle", "lineNo": 20, "colNo": 3, "inApp": true},
+                        {"filename": "", "absPath": "/srv/app/src/user.js", "function": "renderUser", "lineNo": 7},
+                    ]}
+                }]}
+            }]
+        });
+        let frames = event_frames(&event);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].file, "/srv/app/src/user.js");
+        assert_eq!(frames[0].function.as_deref(), Some("renderUser"));
+        assert_eq!((frames[1].line, frames[1].column), (20, Some(3)));
+
+        let logged = json!({
+            "message": "Traceback (most recent call last):\n  File \"/srv/app/api/views.py\", line 42, in get_user\n    return user.name\nAttributeError: boom",
+            "entries": []
+        });
+        let frames = event_frames(&logged);
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].line, 42);
+        assert!(event_frames(&json!({})).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_open_issues() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/0/projects/acme/backend/issues/"))
+            .and(query_param("query", "is:unresolved"))
+            .and(header("authorization", "Bearer secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+                "id": "42",
+                "shortId": "BACKEND-1A",
+                "title": "TypeError: boom",
+                "culprit": "renderUser",
+                "level": "error",
+                "count": "1234",
+                "userCount": 56,
+                "firstSeen": "2026-10-01T08:00:00Z",
+                "lastSeen": "2026-10-13T09:30:00.123Z",
+                "permalink": "https://sentry.io/organizations/acme/issues/42/"
+            }])))
+            .mount(&server)
+            .await;
+
+        let client = SentryClient::new(&server.uri(), "secret", "acme");
+        let issues = client.open_issues("backend").await.unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].short_id, "BACKEND-1A");
+        assert_eq!((issues[0].count, issues[0].user_count), (1234, 56));
+
+        let err = client.open_issues("frontend").await.unwrap_err();
+        assert!(err.to_string().contains("404"));
+    }
+}
@@ -0,0 +1,319 @@
+//! Sentry integration.
+//!
+//! The `sentry` heartbeat check polls the unresolved issues of every Sentry
+//! project listed in the `sentry` config section. The stack of each issue's
+//! latest event is resolved onto the linked orchestrator project by the stack
+//! trace resolver ([`crate::orchestrator::stack_trace`]) and the issues are
+//! stored as `(:SentryIssue)-[:AFFECTS]->(:Function)`. Agent context lists
+//! the open issues going through the functions of each target file.
+//!
+//! - [`client`] — Sentry REST calls and the frames of Sentry events
+
+pub mod client;
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use self::client::{event_frames, SentryClient, SentryIssue};
+use crate::neo4j::models::SentryIssueNode;
+use crate::neo4j::traits::GraphStore;
+use crate::orchestrator::stack_trace::{resolve_frames, StackFrame};
+
+/// Latest events fetched per sync of a Sentry project. New issues and issues
+/// seen again since the last sync are refreshed first come, first served;
+/// the others keep their links until a later sync.
+const MAX_EVENT_FETCHES: usize = 25;
+
+/// Functions linked to an issue at most
+const MAX_ISSUE_FUNCTIONS: usize = 10;
+
+/// `sentry` section of config.yaml.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SentryConfig {
+    /// Base URL of the Sentry instance (default: https://sentry.io).
+    pub url: String,
+    /// Auth token with the `project:read` and `event:read` scopes (supports
+    /// `${VAR}` and `token_file`).
+    pub token: Option<String>,
+    /// Organization slug.
+    pub organization: String,
+    /// Sentry projects to poll, and the project each one reports for.
+    pub projects: Vec<SentryProjectLink>,
+    /// Seconds between two polls (default: 900).
+    pub interval_secs: u64,
+}
+
+impl Default for SentryConfig {
+    fn default() -> Self {
+        Self {
+            url: "https://sentry.io".to_string(),
+            token: None,
+            organization: String::new(),
+            projects: Vec::new(),
+            interval_secs: 900,
+        }
+    }
+}
+
+impl SentryConfig {
+    /// Reject a polling setup missing its token or organization.
+    pub fn check(&self) -> Result<(), String> {
+        if !self.url.starts_with("http://") && !self.url.starts_with("https://") {
+            return Err(format!(
+                "url `{}` must start with http:// or https://",
+                self.url
+            ));
+        }
+        if self.projects.is_empty() {
+            return Ok(());
+        }
+        if self.token.as_deref().unwrap_or_default().is_empty() {
+            return Err("token is required to poll projects".to_string());
+        }
+        if self.organization.is_empty() {
+            return Err("organization is required to poll projects".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// A Sentry project reporting the errors of an orchestrator project.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SentryProjectLink {
+    /// Sentry project slug.
+    pub sentry_project: String,
+    /// Orchestrator project slug.
+    pub project_slug: String,
+}
+
+/// Outcome of polling one Sentry project.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SentrySyncReport {
+    pub sentry_project: String,
+    /// Open issues stored
+    pub issues: usize,
+    pub new_issues: usize,
+    /// Issues linked to at least one function
+    pub linked_issues: usize,
+    pub events_fetched: usize,
+}
+
+/// Distinct function ids of resolved frames, innermost first.
+fn frame_functions(frames: &[StackFrame]) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for id in frames.iter().filter_map(|f| f.function_id.as_ref()) {
+        if !ids.contains(id) && ids.len() < MAX_ISSUE_FUNCTIONS {
+            ids.push(id.clone());
+        }
+    }
+    ids
+}
+
+fn to_node(
+    project_id: uuid::Uuid,
+    issue: SentryIssue,
+    function_ids: Vec<String>,
+) -> SentryIssueNode {
+    SentryIssueNode {
+        project_id,
+        id: issue.id,
+        short_id: issue.short_id,
+        title: issue.title,
+        culprit: issue.culprit.filter(|c| !c.is_empty()),
+        level: issue.level,
+        events: issue.count,
+        users: issue.user_count,
+        first_seen: issue.first_seen,
+        last_seen: issue.last_seen,
+        permalink: issue.permalink,
+        function_ids,
+    }
+}
+
+/// Poll the open issues of a Sentry project and replace the issues stored
+/// for its orchestrator project.
+pub async fn sync_project(
+    graph: &dyn GraphStore,
+    client: &SentryClient,
+    link: &SentryProjectLink,
+) -> Result<SentrySyncReport> {
+    let project = graph
+        .get_project_by_slug(&link.project_slug)
+        .await?
+        .ok_or_else(|| anyhow!("unknown project `{}`", link.project_slug))?;
+    let issues = client.open_issues(&link.sentry_project).await?;
+    let previous: HashMap<String, SentryIssueNode> = graph
+        .list_sentry_issues(project.id)
+        .await?
+        .into_iter()
+        .map(|i| (i.id.clone(), i))
+        .collect();
+
+    let mut report = SentrySyncReport {
+        sentry_project: link.sentry_project.clone(),
+        ..Default::default()
+    };
+    let mut nodes = Vec::with_capacity(issues.len());
+    // Frames of all the fetched events, resolved in one pass
+    let mut frames: Vec<StackFrame> = Vec::new();
+    let mut frame_ranges = Vec::new();
+    for issue in issues {
+        let known = previous.get(&issue.id);
+        let changed = match known {
+            Some(known) => known.last_seen != issue.last_seen,
+            None => {
+                report.new_issues += 1;
+                true
+            }
+        };
+        if changed && report.events_fetched < MAX_EVENT_FETCHES {
+            report.events_fetched += 1;
+            match client.latest_event(&issue.id).await {
+                Ok(event) => {
+                    let start = frames.len();
+                    frames.extend(event_frames(&event));
+                    frame_ranges.push((nodes.len(), start..frames.len()));
+                }
+                Err(e) => warn!(
+                    "Sentry: latest event of issue {} failed: {:#}",
+                    issue.short_id, e
+                ),
+            }
+        }
+        let function_ids = known.map(|k| k.function_ids.clone()).unwrap_or_default();
+        nodes.push(to_node(project.id, issue, function_ids));
+    }
+
+    resolve_frames(graph, &project, &mut frames).await?;
+    for (index, range) in frame_ranges {
+        nodes[index].function_ids = frame_functions(&frames[range]);
+    }
+
+    graph.replace_sentry_issues(project.id, &nodes).await?;
+    report.issues = nodes.len();
+    report.linked_issues = nodes.iter().filter(|n| !n.function_ids.is_empty()).count();
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neo4j::mock::MockGraphStore;
+    use crate::neo4j::models::{FunctionNode, Visibility};
+    use crate::test_helpers::test_project_named;
+    use serde_json::json;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_config_check() {
+        let mut config = SentryConfig::default();
+        assert!(config.check().is_ok());
+        config.projects.push(SentryProjectLink {
+            sentry_project: "backend".to_string(),
+            project_slug: "shop".to_string(),
+        });
+        assert!(config.check().unwrap_err().contains("token"));
+        config.token = Some("secret".to_string());
+        assert!(config.check().unwrap_err().contains("organization"));
+        config.organization = "acme".to_string();
+        assert!(config.check().is_ok());
+        config.url = "sentry.example.com".to_string();
+        assert!(config.check().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sync_project() {
+        let graph = MockGraphStore::new();
+        let project = test_project_named("shop");
+        graph.create_project(&project).await.unwrap();
+        let file = "/tmp/shop/src/user.js".to_string();
+        graph.link_file_to_project(&file, project.id).await.unwrap();
+        graph
+            .upsert_function(&FunctionNode {
+                name: "renderUser".to_string(),
+                visibility: Visibility::Public,
+                params: vec![],
+                return_type: None,
+                generics: vec![],
+                is_async: false,
+                is_unsafe: false,
+                complexity: 1,
+                file_path: file.clone(),
+                line_start: 5,
+                line_end: 9,
+                docstring: None,
+            })
+            .await
+            .unwrap();
+
+        let server = MockServer::start().await;
+        let issue = |id: &str, last_seen: &str| {
+            json!({
+                "id": id, "shortId": format!("SHOP-{}", id), "title": "TypeError: boom",
+                "level": "error", "count": "12", "userCount": 3,
+                "firstSeen": "2026-10-01T08:00:00Z", "lastSeen": last_seen
+            })
+        };
+        Mock::given(method("GET"))
+            .and(path("/api/0/projects/acme/web/issues/"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                issue("1", "2026-10-13T09:00:00Z"),
+                issue("2", "2026-10-12T09:00:00Z"),
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/0/organizations/acme/issues/1/events/latest/"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "entries": [{"type": "exception", "data": {"values": [{"stacktrace": {"frames": [
+                    {"filename": "node_modules/react-dom/index.js", "lineNo": 1},
+                    {"filename": "./src/user.js", "function": "renderUser", "lineNo": 7},
+                ]}}]}}]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/0/organizations/acme/issues/2/events/latest/"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let client = SentryClient::new(&server.uri(), "secret", "acme");
+        let link = SentryProjectLink {
+            sentry_project: "web".to_string(),
+            project_slug: "shop".to_string(),
+        };
+        let report = sync_project(&graph, &client, &link).await.unwrap();
+        assert_eq!(
+            (report.issues, report.new_issues, report.linked_issues),
+            (2, 2, 1)
+        );
+        let stored = graph.list_sentry_issues(project.id).await.unwrap();
+        assert_eq!(
+            stored[0].function_ids,
+            vec![format!("{}:renderUser:5", file)]
+        );
+        assert_eq!(stored[0].events, 12);
+        assert!(stored[1].function_ids.is_empty());
+        let affecting = graph.get_file_sentry_issues(&file).await.unwrap();
+        assert_eq!(affecting.len(), 1);
+        assert_eq!(affecting[0].short_id, "SHOP-1");
+
+        // Unchanged issues keep their links without refetching their event
+        let report = sync_project(&graph, &client, &link).await.unwrap();
+        assert_eq!((report.new_issues, report.events_fetched), (0, 0));
+        assert_eq!(report.linked_issues, 1);
+
+        let unknown = SentryProjectLink {
+            project_slug: "nope".to_string(),
+            ..link
+        };
+        assert!(sync_project(&graph, &client, &unknown).await.is_err());
+    }
+}
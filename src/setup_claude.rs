@@ -82,7 +82,6 @@ const MCP_ALLOWED_TOOL_PATTERN: &str = "mcp__project-orchestrator__*";
 /// - `PO_SERVER_URL=http://127.0.0.1:{port}` — points to this instance
 /// - `PO_JWT_SECRET={secret}` — auto-generates auth tokens at startup
 pub fn setup_claude_code(config: &SetupConfig) -> Result<SetupResult> {
-    let server_url = format!("http://127.0.0.1:{}", config.server_port);
     let mcp_path = config.mcp_server_path.to_string_lossy().to_string();
 
     // Always try to configure allowed tools (idempotent — safe to call multiple times)
@@ -97,15 +96,11 @@ pub fn setup_claude_code(config: &SetupConfig) -> Result<SetupResult> {
         }
     };
 
-    // Build the expected env vars
-    let mut env_vars = std::collections::HashMap::new();
-    env_vars.insert("PO_SERVER_URL".to_string(), server_url.clone());
-    if let Some(ref secret) = config.jwt_secret {
-        env_vars.insert("PO_JWT_SECRET".to_string(), secret.clone());
-    }
+    let env_vars = expected_env(config);
 
     // Check if already configured and up-to-date
-    match check_existing_config(&mcp_path, &env_vars)? {
+    let claude_cli = detect_claude_cli();
+    match check_existing_config(claude_cli.as_deref(), &mcp_path, &env_vars)? {
         ConfigStatus::UpToDate => {
             tracing::info!(
                 "Project Orchestrator MCP server is already correctly configured in Claude Code"
@@ -114,6 +109,12 @@ pub fn setup_claude_code(config: &SetupConfig) -> Result<SetupResult> {
                 allowed_tools_configured: allowed_tools_ok,
             });
         }
+        ConfigStatus::Stale if claude_cli.is_some() => {
+            // Registered through the CLI, maybe outside mcp.json: register again
+            tracing::info!(
+                "Project Orchestrator MCP config is stale — registering it again via the CLI"
+            );
+        }
         ConfigStatus::Stale => {
             tracing::info!(
                 "Project Orchestrator MCP config is stale — updating to stdio mode with current settings"
@@ -131,7 +132,7 @@ pub fn setup_claude_code(config: &SetupConfig) -> Result<SetupResult> {
     }
 
     // Try CLI first (for new installs)
-    if let Some(claude_path) = detect_claude_cli() {
+    if let Some(claude_path) = claude_cli {
         tracing::info!("Claude Code CLI found at: {}", claude_path);
         match configure_via_cli(&claude_path, &mcp_path, &env_vars) {
             Ok(()) => {
@@ -256,7 +257,8 @@ pub fn detect_claude_cli() -> Option<String> {
 // Configuration status check
 // ============================================================================
 
-enum ConfigStatus {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigStatus {
     /// Config exists with correct command, PO_SERVER_URL, and PO_JWT_SECRET
     UpToDate,
     /// Config exists but is stale (wrong mode, missing env vars, wrong URL)
@@ -265,40 +267,129 @@ enum ConfigStatus {
     Missing,
 }
 
+/// Env vars the `mcp_server` binary is registered with
+fn expected_env(config: &SetupConfig) -> std::collections::HashMap<String, String> {
+    let mut env_vars = std::collections::HashMap::new();
+    env_vars.insert(
+        "PO_SERVER_URL".to_string(),
+        format!("http://127.0.0.1:{}", config.server_port),
+    );
+    if let Some(ref secret) = config.jwt_secret {
+        env_vars.insert("PO_JWT_SECRET".to_string(), secret.clone());
+    }
+    env_vars
+}
+
+/// Status of the MCP registration `setup_claude_code` would make for
+/// `config`, without changing anything.
+///
+/// Read-only counterpart of [`setup_claude_code`], used by `orchestrator doctor`.
+pub fn registration_status(config: &SetupConfig) -> Result<ConfigStatus> {
+    check_existing_config(
+        detect_claude_cli().as_deref(),
+        &config.mcp_server_path.to_string_lossy(),
+        &expected_env(config),
+    )
+}
+
 /// Check if the existing MCP config is up-to-date.
 ///
+/// Asks `claude mcp get` when the CLI is installed — it sees every scope,
+/// `claude mcp add` registrations included — and reads `~/.claude/mcp.json`
+/// otherwise. See [`server_status`] for what is checked.
+fn check_existing_config(
+    claude_path: Option<&str>,
+    expected_command: &str,
+    expected_env: &std::collections::HashMap<String, String>,
+) -> Result<ConfigStatus> {
+    let server = match claude_path {
+        Some(claude_path) => {
+            let output = std::process::Command::new(claude_path)
+                .args(["mcp", "get", MCP_SERVER_NAME])
+                .output()
+                .context("Failed to execute claude mcp get")?;
+            if !output.status.success() {
+                return Ok(ConfigStatus::Missing);
+            }
+            parse_mcp_get(&String::from_utf8_lossy(&output.stdout))
+        }
+        None => {
+            let path = mcp_json_path()?;
+            if !path.exists() {
+                return Ok(ConfigStatus::Missing);
+            }
+            let content = std::fs::read_to_string(&path).context("Failed to read mcp.json")?;
+            let json: Value =
+                serde_json::from_str(&content).unwrap_or(Value::Object(Default::default()));
+            match json.get("mcpServers").and_then(|s| s.get(MCP_SERVER_NAME)) {
+                Some(s) => s.clone(),
+                None => return Ok(ConfigStatus::Missing),
+            }
+        }
+    };
+    Ok(server_status(&server, expected_command, expected_env))
+}
+
+/// Parse the output of `claude mcp get` into an `mcpServers` entry of
+/// mcp.json (`type`, `command`, `url`, `env`).
+fn parse_mcp_get(output: &str) -> Value {
+    let mut server = serde_json::Map::new();
+    let mut env = serde_json::Map::new();
+    let mut in_env = false;
+    for line in output.lines() {
+        let line = line.trim();
+        if in_env {
+            let var = line.split_once('=').filter(|(key, _)| {
+                !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            });
+            if let Some((key, value)) = var {
+                env.insert(key.to_string(), Value::String(value.to_string()));
+                continue;
+            }
+            in_env = false;
+        }
+        let Some((field, value)) = line.split_once(':') else {
+            continue;
+        };
+        let key = match field {
+            "Type" => "type",
+            "Command" => "command",
+            "URL" => "url",
+            "Environment" => {
+                in_env = true;
+                continue;
+            }
+            _ => continue,
+        };
+        server.insert(key.to_string(), Value::String(value.trim().to_string()));
+    }
+    if !env.is_empty() {
+        server.insert("env".to_string(), Value::Object(env));
+    }
+    Value::Object(server)
+}
+
+/// Check a registered server entry against the expected one.
+///
 /// Validates:
-/// - Entry exists in mcpServers
 /// - Uses stdio mode (has "command", no "type": "sse")
 /// - Command matches the expected mcp_server binary path
 /// - PO_SERVER_URL matches the expected URL
 /// - PO_JWT_SECRET is set (if we have one)
-fn check_existing_config(
+fn server_status(
+    server: &Value,
     expected_command: &str,
     expected_env: &std::collections::HashMap<String, String>,
-) -> Result<ConfigStatus> {
-    let path = mcp_json_path()?;
-    if !path.exists() {
-        return Ok(ConfigStatus::Missing);
-    }
-
-    let content = std::fs::read_to_string(&path).context("Failed to read mcp.json")?;
-    let json: Value = serde_json::from_str(&content).unwrap_or(Value::Object(Default::default()));
-
-    let server = match json.get("mcpServers").and_then(|s| s.get(MCP_SERVER_NAME)) {
-        Some(s) => s,
-        None => return Ok(ConfigStatus::Missing),
-    };
-
+) -> ConfigStatus {
     // Check if it's SSE mode (stale — SSE endpoint doesn't exist)
     if server.get("type").and_then(|t| t.as_str()) == Some("sse") {
-        return Ok(ConfigStatus::Stale);
+        return ConfigStatus::Stale;
     }
 
     // Check command matches
     let current_command = server.get("command").and_then(|c| c.as_str()).unwrap_or("");
     if current_command != expected_command {
-        return Ok(ConfigStatus::Stale);
+        return ConfigStatus::Stale;
     }
 
     // Check env vars match
@@ -306,20 +397,20 @@ fn check_existing_config(
     match env_obj {
         None => {
             if !expected_env.is_empty() {
-                return Ok(ConfigStatus::Stale);
+                return ConfigStatus::Stale;
             }
         }
         Some(env) => {
             for (key, expected_val) in expected_env {
                 match env.get(key).and_then(|v| v.as_str()) {
                     Some(val) if val == expected_val => {}
-                    _ => return Ok(ConfigStatus::Stale),
+                    _ => return ConfigStatus::Stale,
                 }
             }
         }
     }
 
-    Ok(ConfigStatus::UpToDate)
+    ConfigStatus::UpToDate
 }
 
 // ============================================================================
@@ -631,7 +722,7 @@ mod tests {
         // but we test the logic with a direct call
         let env = std::collections::HashMap::new();
         // The function reads from the real mcp.json path, so just verify it doesn't panic
-        let _result = check_existing_config("/fake/path", &env);
+        let _result = check_existing_config(None, "/fake/path", &env);
     }
 
     #[test]
@@ -644,6 +735,53 @@ mod tests {
 
         // Check that SSE type is correctly identified as stale
         assert_eq!(sse_config.get("type").and_then(|t| t.as_str()), Some("sse"));
+        assert_eq!(
+            server_status(&sse_config, "/path/to/mcp_server", &Default::default()),
+            ConfigStatus::Stale
+        );
+    }
+
+    #[test]
+    fn test_server_status_from_claude_mcp_get() {
+        let output = "project-orchestrator:
+  Scope: Local config (private to you in this project)
+  Status: ✓ Connected
+  Type: stdio
+  Command: /usr/local/bin/mcp_server
+  Args: 
+  Environment:
+    PO_SERVER_URL=http://127.0.0.1:8080
+    PO_JWT_SECRET=test-secret
+
+To remove this server, run: claude mcp remove \"project-orchestrator\" -s local
+";
+        let server = parse_mcp_get(output);
+        assert_eq!(server["type"], "stdio");
+        assert_eq!(server["command"], "/usr/local/bin/mcp_server");
+        assert_eq!(server["env"].as_object().unwrap().len(), 2);
+
+        let config = SetupConfig {
+            mcp_server_path: PathBuf::from("/usr/local/bin/mcp_server"),
+            server_port: 8080,
+            jwt_secret: Some("test-secret".to_string()),
+        };
+        let env = expected_env(&config);
+        assert_eq!(
+            server_status(&server, "/usr/local/bin/mcp_server", &env),
+            ConfigStatus::UpToDate
+        );
+        assert_eq!(
+            server_status(&server, "/opt/po/mcp_server", &env),
+            ConfigStatus::Stale
+        );
+        let other_port = expected_env(&SetupConfig {
+            server_port: 9090,
+            ..config
+        });
+        assert_eq!(
+            server_status(&server, "/usr/local/bin/mcp_server", &other_port),
+            ConfigStatus::Stale
+        );
     }
 
     // ========================================================================
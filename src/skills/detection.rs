@@ -1022,6 +1022,7 @@ mod tests {
             memory_horizon: crate::notes::MemoryHorizon::Operational,
             scar_intensity: 0.0,
             sharing_consent: Default::default(),
+            version: 0,
         }
     }
 
@@ -1259,6 +1260,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         store.create_project(&project).await.unwrap();
 
@@ -1267,8 +1269,10 @@ mod tests {
             path: "/tmp/anchor-test/src/main.rs".to_string(),
             language: "rust".to_string(),
             hash: "abc123".to_string(),
+            line_count: 0,
             last_parsed: Utc::now(),
             project_id: Some(project_id),
+            encoding: None,
         };
         store.upsert_file(&file).await.unwrap();
 
@@ -1302,6 +1306,7 @@ mod tests {
             memory_horizon: crate::notes::MemoryHorizon::Operational,
             scar_intensity: 0.0,
             sharing_consent: Default::default(),
+            version: 0,
         };
         store.create_note(&note).await.unwrap();
 
@@ -388,6 +388,7 @@ mod tests {
             memory_horizon: crate::notes::MemoryHorizon::Operational,
             scar_intensity: 0.0,
             sharing_consent: Default::default(),
+            version: 0,
         };
         store
             .add_skill_member(skill_id, "note", note1.id)
@@ -480,6 +480,7 @@ fn portable_note_to_note(
         assertion_rule: None,
         last_assertion_result: None,
         sharing_consent: Default::default(),
+        version: 0,
     })
 }
 
@@ -541,6 +542,7 @@ fn decision_to_note(
         assertion_rule: None,
         last_assertion_result: None,
         sharing_consent: Default::default(),
+        version: 0,
     })
 }
 
@@ -629,6 +631,7 @@ fn episode_to_note(
         assertion_rule: None,
         last_assertion_result: None,
         sharing_consent: Default::default(),
+        version: 0,
     })
 }
 
@@ -1323,6 +1326,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         store.create_project(&source_project).await.unwrap();
 
@@ -1341,6 +1345,7 @@ mod tests {
             scaffolding_override: None,
             sharing_policy: None,
             watch_enabled: true,
+            archived: false,
         };
         store.create_project(&target_project).await.unwrap();
 
@@ -1383,6 +1388,7 @@ mod tests {
             memory_horizon: MemoryHorizon::Operational,
             scar_intensity: 0.0,
             sharing_consent: Default::default(),
+            version: 0,
         };
         let note_id = note.id;
         store.create_note(&note).await.unwrap();
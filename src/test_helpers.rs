@@ -13,77 +13,7 @@ use crate::{AppState, AuthConfig};
 use std::sync::Arc;
 use uuid::Uuid;
 
-/// A no-op trajectory store for tests.
-pub struct NoopStore;
-#[async_trait::async_trait]
-impl neural_routing_runtime::TrajectoryStore for NoopStore {
-    async fn store_trajectory(
-        &self,
-        _t: &neural_routing_runtime::Trajectory,
-    ) -> neural_routing_runtime::routing_error::Result<()> {
-        Ok(())
-    }
-    async fn get_trajectory(
-        &self,
-        _id: &uuid::Uuid,
-    ) -> neural_routing_runtime::routing_error::Result<Option<neural_routing_runtime::Trajectory>>
-    {
-        Ok(None)
-    }
-    async fn list_trajectories(
-        &self,
-        _filter: &neural_routing_runtime::TrajectoryFilter,
-    ) -> neural_routing_runtime::routing_error::Result<Vec<neural_routing_runtime::Trajectory>>
-    {
-        Ok(vec![])
-    }
-    async fn search_similar(
-        &self,
-        _embedding: &[f32],
-        _top_k: usize,
-        _min_sim: f32,
-    ) -> neural_routing_runtime::routing_error::Result<Vec<(neural_routing_runtime::Trajectory, f64)>>
-    {
-        Ok(vec![])
-    }
-    async fn get_stats(
-        &self,
-    ) -> neural_routing_runtime::routing_error::Result<neural_routing_runtime::TrajectoryStats>
-    {
-        Ok(neural_routing_runtime::TrajectoryStats {
-            total_count: 0,
-            avg_reward: 0.0,
-            avg_step_count: 0.0,
-            avg_duration_ms: 0.0,
-            reward_distribution: Default::default(),
-        })
-    }
-    async fn count(&self) -> neural_routing_runtime::routing_error::Result<usize> {
-        Ok(0)
-    }
-    async fn delete_trajectory(
-        &self,
-        _id: &uuid::Uuid,
-    ) -> neural_routing_runtime::routing_error::Result<bool> {
-        Ok(false)
-    }
-}
-
-/// Create a mock trajectory store for tests.
-pub fn mock_trajectory_store() -> Arc<dyn neural_routing_runtime::TrajectoryStore> {
-    Arc::new(NoopStore)
-}
-
-/// Create a mock neural router for tests (disabled by default, no real store).
-pub fn mock_neural_router() -> Arc<tokio::sync::RwLock<neural_routing_runtime::DualTrackRouter>> {
-    let store: Arc<dyn neural_routing_runtime::TrajectoryStore> = Arc::new(NoopStore);
-    let config = neural_routing_runtime::NeuralRoutingConfig {
-        enabled: false, // disabled in tests by default
-        ..Default::default()
-    };
-    let router = neural_routing_runtime::DualTrackRouter::new(store, config);
-    Arc::new(tokio::sync::RwLock::new(router))
-}
+pub use crate::testkit::{mock_neural_router, mock_trajectory_store, NoopStore};
 
 // ============================================================================
 // Mock state builders
@@ -91,149 +21,17 @@ pub fn mock_neural_router() -> Arc<tokio::sync::RwLock<neural_routing_runtime::D
 
 /// Create a mock AppState with empty in-memory backends
 pub fn mock_app_state() -> AppState {
-    AppState {
-        neo4j: Arc::new(MockGraphStore::new()),
-        meili: Arc::new(MockSearchStore::new()),
-        parser: Arc::new(crate::parser::CodeParser::new().expect("parser init")),
-        config: Arc::new(crate::Config {
-            setup_completed: true,
-            neo4j_uri: "bolt://mock:7687".to_string(),
-            neo4j_user: "neo4j".to_string(),
-            neo4j_password: "mock".to_string(),
-            meilisearch_url: "http://mock:7700".to_string(),
-            meilisearch_key: "mock-key".to_string(),
-            nats_url: None,
-            workspace_path: ".".to_string(),
-            server_port: 0,
-            auth_config: None,
-            serve_frontend: false,
-            frontend_path: "./dist".to_string(),
-            public_url: None,
-            chat_permissions: None,
-            chat_default_model: None,
-            chat_max_sessions: None,
-            chat_max_turns: None,
-            chat_session_timeout_secs: None,
-            chat_process_path: None,
-            chat_claude_cli_path: None,
-            chat_auto_update_cli: None,
-            chat_auto_update_app: None,
-            embedding_provider: None,
-            embedding_fastembed_model: None,
-            embedding_fastembed_cache_dir: None,
-            embedding_url: None,
-            embedding_model: None,
-            embedding_api_key: None,
-            embedding_dimensions: None,
-            anthropic_api_key: None,
-            registry_remote_url: None,
-            neural_routing: Default::default(),
-            config_yaml_path: None,
-        }),
-        neural_router: mock_neural_router(),
-        trajectory_collector: Arc::new(std::sync::RwLock::new(None)),
-        trajectory_store_neo4j: None,
-        trajectory_store: mock_trajectory_store(),
-        mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
-    }
+    mock_app_state_with_stores().0
 }
 
 /// Create a mock AppState with pre-seeded backends
 pub fn mock_app_state_with(graph: MockGraphStore, search: MockSearchStore) -> AppState {
-    AppState {
-        neo4j: Arc::new(graph),
-        meili: Arc::new(search),
-        parser: Arc::new(crate::parser::CodeParser::new().expect("parser init")),
-        config: Arc::new(crate::Config {
-            setup_completed: true,
-            neo4j_uri: "bolt://mock:7687".to_string(),
-            neo4j_user: "neo4j".to_string(),
-            neo4j_password: "mock".to_string(),
-            meilisearch_url: "http://mock:7700".to_string(),
-            meilisearch_key: "mock-key".to_string(),
-            nats_url: None,
-            workspace_path: ".".to_string(),
-            server_port: 0,
-            auth_config: None,
-            serve_frontend: false,
-            frontend_path: "./dist".to_string(),
-            public_url: None,
-            chat_permissions: None,
-            chat_default_model: None,
-            chat_max_sessions: None,
-            chat_max_turns: None,
-            chat_session_timeout_secs: None,
-            chat_process_path: None,
-            chat_claude_cli_path: None,
-            chat_auto_update_cli: None,
-            chat_auto_update_app: None,
-            embedding_provider: None,
-            embedding_fastembed_model: None,
-            embedding_fastembed_cache_dir: None,
-            embedding_url: None,
-            embedding_model: None,
-            embedding_api_key: None,
-            embedding_dimensions: None,
-            anthropic_api_key: None,
-            registry_remote_url: None,
-            neural_routing: Default::default(),
-            config_yaml_path: None,
-        }),
-        neural_router: mock_neural_router(),
-        trajectory_collector: Arc::new(std::sync::RwLock::new(None)),
-        trajectory_store_neo4j: None,
-        trajectory_store: mock_trajectory_store(),
-        mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
-    }
+    crate::testkit::app_state(Arc::new(graph), Arc::new(search))
 }
 
 /// Create a mock AppState with a shared Arc<MockGraphStore> (allows direct access in tests)
 pub fn mock_app_state_with_graph(graph: Arc<MockGraphStore>) -> AppState {
-    AppState {
-        neo4j: graph,
-        meili: Arc::new(MockSearchStore::new()),
-        parser: Arc::new(crate::parser::CodeParser::new().expect("parser init")),
-        config: Arc::new(crate::Config {
-            setup_completed: true,
-            neo4j_uri: "bolt://mock:7687".to_string(),
-            neo4j_user: "neo4j".to_string(),
-            neo4j_password: "mock".to_string(),
-            meilisearch_url: "http://mock:7700".to_string(),
-            meilisearch_key: "mock-key".to_string(),
-            nats_url: None,
-            workspace_path: ".".to_string(),
-            server_port: 0,
-            auth_config: None,
-            serve_frontend: false,
-            frontend_path: "./dist".to_string(),
-            public_url: None,
-            chat_permissions: None,
-            chat_default_model: None,
-            chat_max_sessions: None,
-            chat_max_turns: None,
-            chat_session_timeout_secs: None,
-            chat_process_path: None,
-            chat_claude_cli_path: None,
-            chat_auto_update_cli: None,
-            chat_auto_update_app: None,
-            embedding_provider: None,
-            embedding_fastembed_model: None,
-            embedding_fastembed_cache_dir: None,
-            embedding_url: None,
-            embedding_model: None,
-            embedding_api_key: None,
-            embedding_dimensions: None,
-            anthropic_api_key: None,
-            registry_remote_url: None,
-            neural_routing: Default::default(),
-            config_yaml_path: None,
-        }),
-        neural_router: mock_neural_router(),
-        trajectory_collector: Arc::new(std::sync::RwLock::new(None)),
-        trajectory_store_neo4j: None,
-        trajectory_store: mock_trajectory_store(),
-        mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
-    }
+    crate::testkit::app_state(graph, Arc::new(MockSearchStore::new()))
 }
 
 /// Create a mock AppState with shared access to BOTH graph and search stores.
@@ -243,51 +41,7 @@ pub fn mock_app_state_with_graph(graph: Arc<MockGraphStore>) -> AppState {
 pub fn mock_app_state_with_stores() -> (AppState, Arc<MockGraphStore>, Arc<MockSearchStore>) {
     let graph = Arc::new(MockGraphStore::new());
     let meili = Arc::new(MockSearchStore::new());
-    let state = AppState {
-        neo4j: graph.clone(),
-        meili: meili.clone(),
-        parser: Arc::new(crate::parser::CodeParser::new().expect("parser init")),
-        config: Arc::new(crate::Config {
-            setup_completed: true,
-            neo4j_uri: "bolt://mock:7687".to_string(),
-            neo4j_user: "neo4j".to_string(),
-            neo4j_password: "mock".to_string(),
-            meilisearch_url: "http://mock:7700".to_string(),
-            meilisearch_key: "mock-key".to_string(),
-            nats_url: None,
-            workspace_path: ".".to_string(),
-            server_port: 0,
-            auth_config: None,
-            serve_frontend: false,
-            frontend_path: "./dist".to_string(),
-            public_url: None,
-            chat_permissions: None,
-            chat_default_model: None,
-            chat_max_sessions: None,
-            chat_max_turns: None,
-            chat_session_timeout_secs: None,
-            chat_process_path: None,
-            chat_claude_cli_path: None,
-            chat_auto_update_cli: None,
-            chat_auto_update_app: None,
-            embedding_provider: None,
-            embedding_fastembed_model: None,
-            embedding_fastembed_cache_dir: None,
-            embedding_url: None,
-            embedding_model: None,
-            embedding_api_key: None,
-            embedding_dimensions: None,
-            anthropic_api_key: None,
-            registry_remote_url: None,
-            neural_routing: Default::default(),
-            config_yaml_path: None,
-        }),
-        neural_router: mock_neural_router(),
-        trajectory_collector: Arc::new(std::sync::RwLock::new(None)),
-        trajectory_store_neo4j: None,
-        trajectory_store: mock_trajectory_store(),
-        mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
-    };
+    let state = crate::testkit::app_state(graph.clone(), meili.clone());
     (state, graph, meili)
 }
 
@@ -297,37 +51,15 @@ pub fn mock_app_state_with_stores() -> (AppState, Arc<MockGraphStore>, Arc<MockS
 /// Tests that use `create_router()` should pass `Some(test_auth_config())`
 /// as `auth_config` to avoid deny-by-default 403 rejections.
 pub fn test_auth_config() -> AuthConfig {
-    AuthConfig {
-        jwt_secret: "test-secret-key-minimum-32-chars!!".to_string(),
-        access_token_expiry_secs: 900,
-        refresh_token_expiry_secs: 604800,
-        allowed_email_domain: None,
-        allowed_emails: None,
-        frontend_url: None,
-        additional_origins: vec![],
-        allow_registration: false,
-        root_account: None,
-        oidc: None,
-        google_client_id: Some("test-client-id".to_string()),
-        google_client_secret: Some("test-client-secret".to_string()),
-        google_redirect_uri: Some("http://localhost:3000/auth/callback".to_string()),
-    }
+    crate::testkit::auth_config()
 }
 
 /// Generate a valid Bearer token string for test requests.
 ///
-/// Returns the full header value: `"Bearer eyJ..."`.
-/// Uses the same secret as `test_auth_config()`.
+/// Returns the full header value: `"Bearer eyJ..."`, for a new user on
+/// every call. Uses the same secret as `test_auth_config()`.
 pub fn test_bearer_token() -> String {
-    let token = crate::auth::jwt::encode_jwt(
-        Uuid::new_v4(),
-        "test@ffs.holdings",
-        "Test User",
-        "test-secret-key-minimum-32-chars!!",
-        28800,
-    )
-    .expect("test token encoding should succeed");
-    format!("Bearer {}", token)
+    crate::testkit::bearer_token(Uuid::new_v4(), "test@ffs.holdings", "Test User")
 }
 
 // ============================================================================
@@ -350,6 +82,7 @@ pub fn test_project() -> ProjectNode {
         scaffolding_override: None,
         sharing_policy: None,
         watch_enabled: true,
+        archived: false,
     }
 }
 
@@ -368,6 +101,7 @@ pub fn test_project_named(name: &str) -> ProjectNode {
         default_note_energy: None,
         scaffolding_override: None,
         watch_enabled: true,
+        archived: false,
         sharing_policy: None,
     }
 }
@@ -415,6 +149,8 @@ pub fn test_task() -> TaskNode {
         execution_context: None,
         persona: None,
         prompt_cache: None,
+        board_position: None,
+        version: 0,
     }
 }
 
@@ -527,6 +263,8 @@ pub fn test_chat_session(project_slug: Option<&str>) -> ChatSessionNode {
         preview: None,
         permission_mode: None,
         add_dirs: None,
+        sandbox: None,
+        limits: None,
         spawned_by: None,
     }
 }
@@ -0,0 +1,236 @@
+//! [`TestApp`]: the whole HTTP API over in-memory stores.
+
+use super::{app_state, auth_config, bearer_token, MockGraphStore, MockSearchStore};
+use crate::api::handlers::{OrchestratorState, ServerState};
+use crate::api::routes::create_router;
+use crate::events::{EventBus, HybridEmitter};
+use crate::orchestrator::{FileWatcher, Orchestrator};
+use axum::body::Body;
+use axum::http::{HeaderMap, Method, Request, StatusCode};
+use axum::Router;
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+/// Builds a [`TestApp`], over fresh stores unless given pre-seeded ones.
+#[derive(Default)]
+pub struct TestAppBuilder {
+    graph: Option<Arc<MockGraphStore>>,
+    search: Option<Arc<MockSearchStore>>,
+    read_only: bool,
+}
+
+impl TestAppBuilder {
+    /// Serve this graph store, e.g. one seeded with fixtures.
+    pub fn with_graph(mut self, graph: Arc<MockGraphStore>) -> Self {
+        self.graph = Some(graph);
+        self
+    }
+
+    /// Serve this search store.
+    pub fn with_search(mut self, search: Arc<MockSearchStore>) -> Self {
+        self.search = Some(search);
+        self
+    }
+
+    /// Reject mutating routes with 423 Locked, as a read-only server does.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    pub async fn build(self) -> TestApp {
+        let graph = self
+            .graph
+            .unwrap_or_else(|| Arc::new(MockGraphStore::new()));
+        let search = self
+            .search
+            .unwrap_or_else(|| Arc::new(MockSearchStore::new()));
+        let event_bus = Arc::new(HybridEmitter::new(Arc::new(EventBus::default())));
+        let orchestrator = Arc::new(
+            Orchestrator::with_event_bus(
+                app_state(graph.clone(), search.clone()),
+                event_bus.clone(),
+            )
+            .await
+            .expect("in-memory orchestrator"),
+        );
+        let watcher = Arc::new(tokio::sync::RwLock::new(FileWatcher::new(
+            orchestrator.clone(),
+        )));
+        let state = Arc::new(ServerState {
+            orchestrator,
+            watcher,
+            chat_manager: None,
+            event_bus,
+            nats_emitter: None,
+            auth_config: Some(auth_config()),
+            serve_frontend: false,
+            frontend_path: "./dist".to_string(),
+            setup_completed: true,
+            server_port: 6600,
+            public_url: None,
+            read_only: self.read_only,
+            ws_ticket_store: Arc::new(crate::api::ws_auth::WsTicketStore::new()),
+            presence: Arc::new(crate::api::presence::PresenceTracker::new()),
+            idempotency: Arc::new(crate::api::idempotency::IdempotencyStore::new()),
+            registry_remote_url: None,
+            oidc_client: None,
+            neural_router: super::mock_neural_router(),
+            trajectory_collector: std::sync::RwLock::new(None),
+            trajectory_store_neo4j: None,
+            trajectory_store: None,
+            identity: None,
+            reactor_counters: std::sync::OnceLock::new(),
+            confidence_tracker: Arc::new(crate::graph::confidence::ConfidenceTracker::default()),
+            mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
+            model_catalog: crate::chat::model_catalog::ModelCatalogCache::new(None),
+        });
+        let user_id = Uuid::new_v4();
+        TestApp {
+            graph,
+            search,
+            router: create_router(state.clone()),
+            state,
+            user_id,
+            token: bearer_token(user_id, "test@ffs.holdings", "Test User"),
+        }
+    }
+}
+
+/// A response of the [`TestApp`], with its body parsed as JSON (`null` when
+/// empty, a string when not JSON).
+#[derive(Debug)]
+pub struct TestResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: serde_json::Value,
+}
+
+/// The HTTP API over in-memory stores, sending requests as one user.
+#[derive(Clone)]
+pub struct TestApp {
+    pub graph: Arc<MockGraphStore>,
+    pub search: Arc<MockSearchStore>,
+    pub state: OrchestratorState,
+    /// User the requests are authenticated as
+    pub user_id: Uuid,
+    router: Router,
+    token: String,
+}
+
+impl TestApp {
+    pub fn builder() -> TestAppBuilder {
+        TestAppBuilder::default()
+    }
+
+    /// The same app, sending requests as another user.
+    pub fn as_user(&self, email: &str, name: &str) -> TestApp {
+        let user_id = Uuid::new_v4();
+        TestApp {
+            user_id,
+            token: bearer_token(user_id, email, name),
+            ..self.clone()
+        }
+    }
+
+    pub async fn request(
+        &self,
+        method: Method,
+        uri: &str,
+        body: Option<serde_json::Value>,
+    ) -> TestResponse {
+        let builder = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("authorization", &self.token);
+        let request = match body {
+            Some(body) => builder
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string())),
+            None => builder.body(Body::empty()),
+        }
+        .expect("valid request");
+
+        let response = self
+            .router
+            .clone()
+            .oneshot(request)
+            .await
+            .expect("the router is infallible");
+        let status = response.status();
+        let headers = response.headers().clone();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("readable body");
+        let body = if bytes.is_empty() {
+            serde_json::Value::Null
+        } else {
+            serde_json::from_slice(&bytes).unwrap_or_else(|_| {
+                serde_json::Value::String(String::from_utf8_lossy(&bytes).into_owned())
+            })
+        };
+        TestResponse {
+            status,
+            headers,
+            body,
+        }
+    }
+
+    pub async fn get(&self, uri: &str) -> TestResponse {
+        self.request(Method::GET, uri, None).await
+    }
+
+    pub async fn post(&self, uri: &str, body: serde_json::Value) -> TestResponse {
+        self.request(Method::POST, uri, Some(body)).await
+    }
+
+    pub async fn put(&self, uri: &str, body: serde_json::Value) -> TestResponse {
+        self.request(Method::PUT, uri, Some(body)).await
+    }
+
+    pub async fn patch(&self, uri: &str, body: serde_json::Value) -> TestResponse {
+        self.request(Method::PATCH, uri, Some(body)).await
+    }
+
+    pub async fn delete(&self, uri: &str) -> TestResponse {
+        self.request(Method::DELETE, uri, None).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_app_round_trip() {
+        let app = TestApp::builder().build().await;
+        let created = app
+            .post(
+                "/api/notes",
+                serde_json::json!({"note_type": "tip", "content": "Prefer small PRs"}),
+            )
+            .await;
+        assert_eq!(created.status, StatusCode::CREATED);
+
+        let id = created.body["id"].as_str().unwrap();
+        let fetched = app.get(&format!("/api/notes/{}", id)).await;
+        assert_eq!(fetched.status, StatusCode::OK);
+        assert_eq!(fetched.body["content"], "Prefer small PRs");
+        assert!(app
+            .graph
+            .notes
+            .read()
+            .await
+            .contains_key(&id.parse().unwrap()));
+
+        let locked = TestApp::builder().read_only(true).build().await;
+        let resp = locked
+            .post(
+                "/api/notes",
+                serde_json::json!({"note_type": "tip", "content": "x"}),
+            )
+            .await;
+        assert_eq!(resp.status, StatusCode::LOCKED);
+    }
+}
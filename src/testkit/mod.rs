@@ -0,0 +1,223 @@
+//! In-memory test kit for integration tests without Docker.
+//!
+//! Exposes the in-memory graph and search stores the crate's own tests run
+//! against, a builder for a complete [`AppState`] and HTTP router on top of
+//! them ([`TestApp`]), and golden-file assertions for API responses
+//! ([`snapshot`]).
+//!
+//! Compiled for the crate's unit tests and, for integration tests of this
+//! crate or downstream ones, behind the `testkit` feature:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! project-orchestrator = { path = "...", features = ["testkit"] }
+//! ```
+//!
+//! ```ignore
+//! use project_orchestrator::testkit::{assert_json_snapshot, TestApp};
+//!
+//! #[tokio::test]
+//! async fn plan_listing() {
+//!     let app = TestApp::builder().build().await;
+//!     let resp = app.get("/api/plans").await;
+//!     assert_eq!(resp.status, 200);
+//!     assert_json_snapshot!("plan_listing", resp.body);
+//! }
+//! ```
+
+mod app;
+pub mod snapshot;
+
+pub use crate::assert_json_snapshot;
+pub use crate::meilisearch::mock::MockSearchStore;
+pub use crate::neo4j::mock::MockGraphStore;
+pub use app::{TestApp, TestAppBuilder, TestResponse};
+pub use snapshot::{assert_snapshot, redact};
+
+use crate::{AppState, AuthConfig, Config};
+use std::sync::Arc;
+
+/// JWT secret of [`auth_config`], used to sign the tokens of [`bearer_token`]
+pub const TEST_JWT_SECRET: &str = "test-secret-key-minimum-32-chars!!";
+
+/// A no-op trajectory store for in-memory states.
+pub struct NoopStore;
+#[async_trait::async_trait]
+impl neural_routing_runtime::TrajectoryStore for NoopStore {
+    async fn store_trajectory(
+        &self,
+        _t: &neural_routing_runtime::Trajectory,
+    ) -> neural_routing_runtime::routing_error::Result<()> {
+        Ok(())
+    }
+    async fn get_trajectory(
+        &self,
+        _id: &uuid::Uuid,
+    ) -> neural_routing_runtime::routing_error::Result<Option<neural_routing_runtime::Trajectory>>
+    {
+        Ok(None)
+    }
+    async fn list_trajectories(
+        &self,
+        _filter: &neural_routing_runtime::TrajectoryFilter,
+    ) -> neural_routing_runtime::routing_error::Result<Vec<neural_routing_runtime::Trajectory>>
+    {
+        Ok(vec![])
+    }
+    async fn search_similar(
+        &self,
+        _embedding: &[f32],
+        _top_k: usize,
+        _min_sim: f32,
+    ) -> neural_routing_runtime::routing_error::Result<Vec<(neural_routing_runtime::Trajectory, f64)>>
+    {
+        Ok(vec![])
+    }
+    async fn get_stats(
+        &self,
+    ) -> neural_routing_runtime::routing_error::Result<neural_routing_runtime::TrajectoryStats>
+    {
+        Ok(neural_routing_runtime::TrajectoryStats {
+            total_count: 0,
+            avg_reward: 0.0,
+            avg_step_count: 0.0,
+            avg_duration_ms: 0.0,
+            reward_distribution: Default::default(),
+        })
+    }
+    async fn count(&self) -> neural_routing_runtime::routing_error::Result<usize> {
+        Ok(0)
+    }
+    async fn delete_trajectory(
+        &self,
+        _id: &uuid::Uuid,
+    ) -> neural_routing_runtime::routing_error::Result<bool> {
+        Ok(false)
+    }
+}
+
+/// A trajectory store that keeps nothing.
+pub fn mock_trajectory_store() -> Arc<dyn neural_routing_runtime::TrajectoryStore> {
+    Arc::new(NoopStore)
+}
+
+/// A disabled neural router backed by [`NoopStore`].
+pub fn mock_neural_router() -> Arc<tokio::sync::RwLock<neural_routing_runtime::DualTrackRouter>> {
+    let store: Arc<dyn neural_routing_runtime::TrajectoryStore> = Arc::new(NoopStore);
+    let config = neural_routing_runtime::NeuralRoutingConfig {
+        enabled: false, // disabled in tests by default
+        ..Default::default()
+    };
+    let router = neural_routing_runtime::DualTrackRouter::new(store, config);
+    Arc::new(tokio::sync::RwLock::new(router))
+}
+
+// ============================================================================
+// State builders
+// ============================================================================
+
+/// Configuration of an in-memory instance (setup completed, no auth, no
+/// chat, no embeddings). The backend URLs point nowhere.
+pub fn mock_config() -> Config {
+    Config {
+        setup_completed: true,
+        neo4j_uri: "bolt://mock:7687".to_string(),
+        neo4j_user: "neo4j".to_string(),
+        neo4j_password: "mock".to_string(),
+        neo4j_query_timeout_ms: 60_000,
+        neo4j_slow_query_ms: 100,
+        meilisearch_url: "http://mock:7700".to_string(),
+        meilisearch_key: "mock-key".to_string(),
+        meilisearch_index_queue_path: None,
+        nats_url: None,
+        nats_jetstream: false,
+        nats_consumer: None,
+        ipc_enabled: false,
+        ipc_path: None,
+        workspace_path: ".".to_string(),
+        server_host: "0.0.0.0".into(),
+        server_port: 0,
+        auth_config: None,
+        serve_frontend: false,
+        frontend_path: "./dist".to_string(),
+        public_url: None,
+        read_only: false,
+        chat_permissions: None,
+        chat_default_model: None,
+        chat_max_sessions: None,
+        chat_max_turns: None,
+        chat_session_timeout_secs: None,
+        chat_process_path: None,
+        chat_claude_cli_path: None,
+        chat_auto_update_cli: None,
+        chat_auto_update_app: None,
+        chat_sandbox: None,
+        chat_limits: None,
+        chat_model_routing: None,
+        embedding_provider: None,
+        embedding_fastembed_model: None,
+        embedding_fastembed_cache_dir: None,
+        embedding_url: None,
+        embedding_model: None,
+        embedding_api_key: None,
+        embedding_dimensions: None,
+        anthropic_api_key: None,
+        registry_remote_url: None,
+        neural_routing: Default::default(),
+        config_yaml_path: None,
+        headless: false,
+        update_channel: Default::default(),
+        update_public_key: None,
+        note_federation: Default::default(),
+        telemetry_enabled: false,
+        analytics_min_call_confidence: 0.0,
+        sentry: Default::default(),
+        notifications: Default::default(),
+        lsp: Default::default(),
+        grammars: Default::default(),
+        log_format: Default::default(),
+        log_filter: String::new(),
+    }
+}
+
+/// An [`AppState`] over the given in-memory stores.
+pub fn app_state(graph: Arc<MockGraphStore>, search: Arc<MockSearchStore>) -> AppState {
+    AppState {
+        neo4j: graph,
+        meili: search,
+        parser: Arc::new(crate::parser::CodeParser::new().expect("parser init")),
+        config: Arc::new(mock_config()),
+        neural_router: mock_neural_router(),
+        trajectory_collector: Arc::new(std::sync::RwLock::new(None)),
+        trajectory_store_neo4j: None,
+        trajectory_store: mock_trajectory_store(),
+        mcp_registry: crate::mcp_federation::registry::new_shared_registry(),
+    }
+}
+
+/// Auth configuration accepting the tokens of [`bearer_token`], with no
+/// domain restriction.
+pub fn auth_config() -> AuthConfig {
+    AuthConfig {
+        jwt_secret: TEST_JWT_SECRET.to_string(),
+        access_token_expiry_secs: 900,
+        refresh_token_expiry_secs: 604800,
+        allowed_email_domain: None,
+        allowed_emails: None,
+        frontend_url: None,
+        additional_origins: vec![],
+        allow_registration: false,
+        root_account: None,
+        oidc: None,
+        google_client_id: Some("test-client-id".to_string()),
+        google_client_secret: Some("test-client-secret".to_string()),
+        google_redirect_uri: Some("http://localhost:3000/auth/callback".to_string()),
+    }
+}
+
+/// `Authorization` header value for a user, signed for [`auth_config`].
+pub fn bearer_token(user_id: uuid::Uuid, email: &str, name: &str) -> String {
+    let token = crate::auth::jwt::encode_jwt(user_id, email, name, TEST_JWT_SECRET, 28800)
+        .expect("test token encoding should succeed");
+    format!("Bearer {}", token)
+}
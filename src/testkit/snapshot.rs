@@ -0,0 +1,154 @@
+//! Golden-file assertions for JSON responses.
+//!
+//! A snapshot is the pretty-printed JSON of a value stored as
+//! `<dir>/<name>.json`. Before comparison, values that change from run to
+//! run are redacted — UUIDs become `"[uuid]"` and RFC 3339 timestamps
+//! `"[timestamp]"` — and object keys are sorted.
+//!
+//! Run the tests with `UPDATE_SNAPSHOTS=1` to record new snapshots and
+//! accept changed ones instead of failing.
+
+use serde_json::{Map, Value};
+use std::path::Path;
+
+/// Environment variable that makes assertions write snapshots
+pub const UPDATE_ENV: &str = "UPDATE_SNAPSHOTS";
+
+/// Assert that a value matches the snapshot `name` under the calling
+/// crate's `tests/snapshots/` directory.
+///
+/// ```ignore
+/// assert_json_snapshot!("create_note", resp.body);
+/// ```
+#[macro_export]
+macro_rules! assert_json_snapshot {
+    ($name:expr, $value:expr $(,)?) => {
+        $crate::testkit::snapshot::assert_snapshot(
+            ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots"),
+            $name,
+            &$value,
+        )
+    };
+}
+
+/// The value with UUIDs and timestamps redacted and object keys sorted.
+pub fn redact(value: &Value) -> Value {
+    match value {
+        Value::String(s) if s.parse::<uuid::Uuid>().is_ok() => Value::from("[uuid]"),
+        Value::String(s) if chrono::DateTime::parse_from_rfc3339(s).is_ok() => {
+            Value::from("[timestamp]")
+        }
+        Value::Array(items) => items.iter().map(redact).collect(),
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            keys.into_iter()
+                .map(|key| (key.clone(), redact(&map[key])))
+                .collect::<Map<String, Value>>()
+                .into()
+        }
+        other => other.clone(),
+    }
+}
+
+/// Assert that a value matches the snapshot `<dir>/<name>.json`, or record
+/// it when `UPDATE_SNAPSHOTS=1`.
+///
+/// # Panics
+///
+/// When the snapshot is missing or differs, showing both versions.
+pub fn assert_snapshot(dir: impl AsRef<Path>, name: &str, value: &Value) {
+    let path = dir.as_ref().join(format!("{}.json", name));
+    let actual = format!(
+        "{}\n",
+        serde_json::to_string_pretty(&redact(value)).expect("JSON values serialize")
+    );
+    let expected = std::fs::read_to_string(&path)
+        .ok()
+        .map(|s| s.replace("\r\n", "\n"));
+    if expected.as_deref() == Some(actual.as_str()) {
+        return;
+    }
+    if std::env::var(UPDATE_ENV).is_ok_and(|v| v == "1") {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("create the snapshot directory");
+        }
+        std::fs::write(&path, &actual).expect("write the snapshot");
+        return;
+    }
+    match expected {
+        Some(expected) => panic!(
+            "Snapshot `{}` does not match {}\n--- expected\n{}+++ actual\n{}\
+             Run with {}=1 to accept the new output.",
+            name,
+            path.display(),
+            expected,
+            actual,
+            UPDATE_ENV
+        ),
+        None => panic!(
+            "Snapshot `{}` is missing ({}); run with {}=1 to record it:\n{}",
+            name,
+            path.display(),
+            UPDATE_ENV,
+            actual
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_redact() {
+        let value = json!({
+            "zeta": 1,
+            "id": "6f1c2a5e-8a0b-4c3d-9e2f-1a2b3c4d5e6f",
+            "created_at": "2026-10-14T12:34:56.789Z",
+            "items": [{"owner": "alice", "at": "2026-10-14T12:34:56+02:00"}],
+            "day": "2026-10-14",
+        });
+        let redacted = redact(&value);
+        assert_eq!(
+            redacted,
+            json!({
+                "created_at": "[timestamp]",
+                "day": "2026-10-14",
+                "id": "[uuid]",
+                "items": [{"at": "[timestamp]", "owner": "alice"}],
+                "zeta": 1,
+            })
+        );
+        let keys: Vec<&String> = redacted.as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["created_at", "day", "id", "items", "zeta"]);
+    }
+
+    #[test]
+    fn test_matching_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("note.json"),
+            "{\n  \"content\": \"hello\",\n  \"id\": \"[uuid]\"\n}\n",
+        )
+        .unwrap();
+        assert_snapshot(
+            dir.path(),
+            "note",
+            &json!({"id": uuid::Uuid::new_v4().to_string(), "content": "hello"}),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match")]
+    fn test_differing_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("note.json"),
+            "{\n  \"content\": \"hello\"\n}\n",
+        )
+        .unwrap();
+        assert_snapshot(dir.path(), "note", &json!({"content": "bye"}));
+    }
+}
@@ -0,0 +1,1046 @@
+//! `orchestrator tui` — live terminal dashboard.
+//!
+//! Meant for servers where the web UI is not deployed. The dashboard is a
+//! client of the running server, so it shows what the server sees:
+//!
+//! - **Projects** — `GET /api/projects`, with their last sync
+//! - **Chat sessions** — first the sessions running on the server's chat
+//!   manager (`GET /api/chat/sessions/active`: streaming or idle, tool
+//!   calls, spend), then the most recent stored ones
+//!   (`GET /api/chat/sessions`)
+//! - **Events** — the CRUD event stream of `/ws/events` (syncs, watcher
+//!   re-syncs, chat sessions, plan/task changes), reconnected when it drops
+//!
+//! Lists are re-read every [`REFRESH_INTERVAL`]; events update them in
+//! between. The screen is drawn with `ratatui` and follows the terminal's
+//! size. Keys: `Tab`/`Shift+Tab` switch pane, `↑`/`↓` (or `k`/`j`) move in
+//! it, `r` refreshes, `q` or `Ctrl+C` quits.
+
+use crate::auth::jwt::{encode_jwt, ANONYMOUS_USER_ID};
+use crate::chat::manager::ActiveSessionInfo;
+use crate::chat::types::ChatSession;
+use crate::events::{CrudAction, CrudEvent, EntityType};
+use crate::{logging, Config};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use futures::{SinkExt, StreamExt};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Cell, List, ListItem, ListState, Paragraph, Row, Table, TableState};
+use ratatui::Frame;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+
+/// How often projects and sessions are re-read from the server.
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+/// Delay before reconnecting a dropped event stream.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+/// How long the input thread waits for a key before checking it should stop.
+const INPUT_POLL: Duration = Duration::from_millis(250);
+/// Number of events kept in the activity feed.
+const MAX_EVENTS: usize = 200;
+/// Number of stored chat sessions listed after the running ones.
+const MAX_SESSIONS: usize = 20;
+/// Lifetime of the token the dashboard authenticates with.
+const TOKEN_EXPIRY_SECS: u64 = 7 * 86400;
+
+/// A project as listed by `GET /api/projects`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProjectRow {
+    pub id: String,
+    pub slug: String,
+    pub root_path: String,
+    pub last_synced: Option<String>,
+    #[serde(default)]
+    pub file_count: usize,
+}
+
+/// The items of a paginated API response.
+#[derive(Deserialize)]
+struct Page<T> {
+    items: Vec<T>,
+}
+
+/// A pane of the dashboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    Projects,
+    Sessions,
+    Events,
+}
+
+impl Pane {
+    fn next(self) -> Self {
+        match self {
+            Pane::Projects => Pane::Sessions,
+            Pane::Sessions => Pane::Events,
+            Pane::Events => Pane::Projects,
+        }
+    }
+
+    fn previous(self) -> Self {
+        self.next().next()
+    }
+}
+
+/// State of the `/ws/events` connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeedStatus {
+    Connecting,
+    Live,
+    /// Disconnected, with the reason; retried after [`RECONNECT_DELAY`].
+    Down(String),
+}
+
+/// What a key asks the main loop to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    None,
+    Refresh,
+    Quit,
+}
+
+/// A row of the sessions pane: a running session, a stored one, or both.
+struct SessionRow<'a> {
+    id: &'a str,
+    stored: Option<&'a ChatSession>,
+    active: Option<&'a ActiveSessionInfo>,
+}
+
+/// Everything the dashboard shows, independent of the terminal.
+#[derive(Debug)]
+pub struct Dashboard {
+    pub server_url: String,
+    pub projects: Vec<ProjectRow>,
+    /// Sessions running on the server, most recently active first.
+    pub active: Vec<ActiveSessionInfo>,
+    /// Most recently updated stored sessions.
+    pub recent: Vec<ChatSession>,
+    /// Most recent first.
+    pub events: VecDeque<CrudEvent>,
+    pub feed: FeedStatus,
+    pub last_refresh: Option<DateTime<Utc>>,
+    pub refresh_error: Option<String>,
+    pub focus: Pane,
+    projects_state: TableState,
+    sessions_state: TableState,
+    events_state: ListState,
+}
+
+impl Dashboard {
+    pub fn new(server_url: impl Into<String>) -> Self {
+        Self {
+            server_url: server_url.into(),
+            projects: Vec::new(),
+            active: Vec::new(),
+            recent: Vec::new(),
+            events: VecDeque::new(),
+            feed: FeedStatus::Connecting,
+            last_refresh: None,
+            refresh_error: None,
+            focus: Pane::Projects,
+            projects_state: TableState::default(),
+            sessions_state: TableState::default(),
+            events_state: ListState::default(),
+        }
+    }
+
+    /// Replace projects and sessions with a fresh read from the server.
+    pub fn set_snapshot(
+        &mut self,
+        mut projects: Vec<ProjectRow>,
+        active: Vec<ActiveSessionInfo>,
+        recent: Vec<ChatSession>,
+        now: DateTime<Utc>,
+    ) {
+        projects.sort_by(|a, b| a.slug.cmp(&b.slug));
+        self.projects = projects;
+        self.active = active;
+        self.recent = recent;
+        self.last_refresh = Some(now);
+        self.refresh_error = None;
+        self.clamp_selections();
+    }
+
+    /// Record a live event, updating the affected rows without waiting for
+    /// the next refresh.
+    pub fn apply_event(&mut self, event: CrudEvent, now: DateTime<Utc>) {
+        match (&event.entity_type, &event.action) {
+            (EntityType::Project, CrudAction::Synced) => {
+                if let Some(p) = self.projects.iter_mut().find(|p| p.id == event.entity_id) {
+                    p.last_synced = Some(now.to_rfc3339());
+                }
+            }
+            (EntityType::ChatSession, _) => {
+                if let Some(s) = self.recent.iter_mut().find(|s| s.id == event.entity_id) {
+                    s.updated_at = now.to_rfc3339();
+                }
+            }
+            _ => {}
+        }
+        self.events.push_front(event);
+        self.events.truncate(MAX_EVENTS);
+        // Keep a scrolled-back selection on the same event; the newest one
+        // stays followed
+        if let Some(i) = self.events_state.selected().filter(|&i| i > 0) {
+            self.events_state
+                .select(Some((i + 1).min(self.events.len() - 1)));
+        }
+        self.clamp_selections();
+    }
+
+    /// Handle a key press.
+    pub fn handle_key(&mut self, key: KeyEvent) -> Action {
+        if key.kind != KeyEventKind::Press {
+            return Action::None;
+        }
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+            KeyCode::Char('q') | KeyCode::Esc => Action::Quit,
+            KeyCode::Char('r') => Action::Refresh,
+            KeyCode::Tab => {
+                self.focus = self.focus.next();
+                Action::None
+            }
+            KeyCode::BackTab => {
+                self.focus = self.focus.previous();
+                Action::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::PageDown => self.move_selection(10),
+            KeyCode::PageUp => self.move_selection(-10),
+            KeyCode::Home | KeyCode::Char('g') => self.move_selection(isize::MIN),
+            KeyCode::End | KeyCode::Char('G') => self.move_selection(isize::MAX),
+            _ => Action::None,
+        }
+    }
+
+    /// Index of the selected row of a pane.
+    pub fn selected(&self, pane: Pane) -> Option<usize> {
+        match pane {
+            Pane::Projects => self.projects_state.selected(),
+            Pane::Sessions => self.sessions_state.selected(),
+            Pane::Events => self.events_state.selected(),
+        }
+    }
+
+    fn pane_len(&self, pane: Pane) -> usize {
+        match pane {
+            Pane::Projects => self.projects.len(),
+            Pane::Sessions => self.session_rows().len(),
+            Pane::Events => self.events.len(),
+        }
+    }
+
+    fn select(&mut self, pane: Pane, index: Option<usize>) {
+        match pane {
+            Pane::Projects => self.projects_state.select(index),
+            Pane::Sessions => self.sessions_state.select(index),
+            Pane::Events => self.events_state.select(index),
+        }
+    }
+
+    fn move_selection(&mut self, delta: isize) -> Action {
+        let len = self.pane_len(self.focus);
+        if len > 0 {
+            let current = self.selected(self.focus).unwrap_or(0) as isize;
+            let index = current.saturating_add(delta).clamp(0, len as isize - 1);
+            self.select(self.focus, Some(index as usize));
+        }
+        Action::None
+    }
+
+    /// Keep every selection on a row, selecting the first one of a pane
+    /// that just got rows.
+    fn clamp_selections(&mut self) {
+        for pane in [Pane::Projects, Pane::Sessions, Pane::Events] {
+            let len = self.pane_len(pane);
+            let index = match (len, self.selected(pane)) {
+                (0, _) => None,
+                (_, None) => Some(0),
+                (len, Some(i)) => Some(i.min(len - 1)),
+            };
+            self.select(pane, index);
+        }
+    }
+
+    /// Running sessions first (with their stored title and project), then
+    /// the stored ones that are not running.
+    fn session_rows(&self) -> Vec<SessionRow<'_>> {
+        let stored = |id: &str| self.recent.iter().find(|s| s.id == id);
+        let mut rows: Vec<SessionRow<'_>> = self
+            .active
+            .iter()
+            .map(|a| SessionRow {
+                id: &a.session_id,
+                stored: stored(&a.session_id),
+                active: Some(a),
+            })
+            .collect();
+        rows.extend(
+            self.recent
+                .iter()
+                .filter(|s| !self.active.iter().any(|a| a.session_id == s.id))
+                .map(|s| SessionRow {
+                    id: &s.id,
+                    stored: Some(s),
+                    active: None,
+                }),
+        );
+        rows
+    }
+
+    /// Draw one frame over the whole terminal.
+    pub fn render(&mut self, frame: &mut Frame, now: DateTime<Utc>) {
+        let [header, top, events, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Percentage(50),
+            Constraint::Min(5),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [projects, sessions] =
+            Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(top);
+
+        frame.render_widget(Paragraph::new(self.header_line(now)), header);
+        self.render_projects(frame, projects, now);
+        self.render_sessions(frame, sessions, now);
+        self.render_events(frame, events);
+        frame.render_widget(
+            Paragraph::new(format!(
+                "Tab pane  ↑↓ select  r refresh  q quit │ {}",
+                self.detail()
+            ))
+            .style(Style::new().fg(Color::DarkGray)),
+            footer,
+        );
+    }
+
+    fn header_line(&self, now: DateTime<Utc>) -> Line<'static> {
+        let feed = match &self.feed {
+            FeedStatus::Connecting => "events: connecting…".to_string(),
+            FeedStatus::Live => "events: live".to_string(),
+            FeedStatus::Down(reason) => format!("events: down ({})", reason),
+        };
+        let refresh = match (&self.refresh_error, self.last_refresh) {
+            (Some(e), _) => format!("! refresh failed: {}", e),
+            (None, Some(at)) => format!("refreshed {}", ago(now, at)),
+            (None, None) => "loading…".to_string(),
+        };
+        Line::from(format!(
+            "Project Orchestrator — {}   {}   {}",
+            self.server_url, feed, refresh
+        ))
+        .style(Style::new().add_modifier(Modifier::BOLD))
+    }
+
+    fn block(&self, pane: Pane, title: String) -> Block<'static> {
+        let block = Block::bordered().title(title);
+        if self.focus == pane {
+            block.border_style(Style::new().fg(Color::Cyan))
+        } else {
+            block
+        }
+    }
+
+    fn render_projects(
+        &mut self,
+        frame: &mut Frame,
+        area: ratatui::layout::Rect,
+        now: DateTime<Utc>,
+    ) {
+        let rows = self.projects.iter().map(|p| {
+            Row::new(vec![
+                Cell::from(p.slug.clone()),
+                Cell::from(
+                    p.last_synced
+                        .as_deref()
+                        .and_then(parse_time)
+                        .map(|t| ago(now, t))
+                        .unwrap_or_else(|| "never".to_string()),
+                ),
+                Cell::from(p.file_count.to_string()),
+                Cell::from(p.root_path.clone()),
+            ])
+        });
+        let slug_width = self
+            .projects
+            .iter()
+            .map(|p| p.slug.len())
+            .max()
+            .unwrap_or(0)
+            .max(4);
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(slug_width as u16),
+                Constraint::Length(9),
+                Constraint::Length(6),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(Row::new(["SLUG", "LAST SYNC", "FILES", "ROOT"]).style(header_style()))
+        .block(self.block(
+            Pane::Projects,
+            format!(" Projects ({}) ", self.projects.len()),
+        ))
+        .row_highlight_style(highlight_style(self.focus == Pane::Projects));
+        frame.render_stateful_widget(table, area, &mut self.projects_state);
+    }
+
+    fn render_sessions(
+        &mut self,
+        frame: &mut Frame,
+        area: ratatui::layout::Rect,
+        now: DateTime<Utc>,
+    ) {
+        let rows: Vec<Row> = self
+            .session_rows()
+            .iter()
+            .map(|row| {
+                let (marker, state, tools, cost) = match row.active {
+                    Some(a) => (
+                        Cell::from("●").style(Style::new().fg(Color::Green)),
+                        if a.is_streaming {
+                            "streaming".to_string()
+                        } else {
+                            format!("idle {}", duration(a.idle_secs))
+                        },
+                        a.tool_calls.to_string(),
+                        Some(a.cost_usd),
+                    ),
+                    None => (
+                        Cell::from("○"),
+                        row.stored
+                            .and_then(|s| parse_time(&s.updated_at))
+                            .map(|t| ago(now, t))
+                            .unwrap_or_default(),
+                        "-".to_string(),
+                        row.stored.and_then(|s| s.total_cost_usd),
+                    ),
+                };
+                let project = row
+                    .stored
+                    .and_then(|s| s.project_slug.as_deref().or(s.workspace_slug.as_deref()))
+                    .unwrap_or("-");
+                let title = row
+                    .stored
+                    .and_then(|s| s.title.as_deref().or(s.preview.as_deref()))
+                    .unwrap_or("");
+                Row::new(vec![
+                    marker,
+                    Cell::from(short_id(row.id).to_string()),
+                    Cell::from(project.to_string()),
+                    Cell::from(state),
+                    Cell::from(tools),
+                    Cell::from(cost.map(|c| format!("${:.2}", c)).unwrap_or_default()),
+                    Cell::from(title.to_string()),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(1),
+                Constraint::Length(8),
+                Constraint::Length(14),
+                Constraint::Length(10),
+                Constraint::Length(5),
+                Constraint::Length(7),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(
+            Row::new(["", "ID", "PROJECT", "STATE", "TOOLS", "COST", "TITLE"])
+                .style(header_style()),
+        )
+        .block(self.block(
+            Pane::Sessions,
+            format!(" Chat sessions ({} running) ", self.active.len()),
+        ))
+        .row_highlight_style(highlight_style(self.focus == Pane::Sessions));
+        frame.render_stateful_widget(table, area, &mut self.sessions_state);
+    }
+
+    fn render_events(&mut self, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let items: Vec<ListItem> = if self.events.is_empty() {
+            vec![ListItem::new("(none yet)")]
+        } else {
+            self.events
+                .iter()
+                .map(|e| ListItem::new(self.event_line(e)))
+                .collect()
+        };
+        let list = List::new(items)
+            .block(self.block(Pane::Events, " Events ".to_string()))
+            .highlight_style(highlight_style(self.focus == Pane::Events));
+        frame.render_stateful_widget(list, area, &mut self.events_state);
+    }
+
+    /// Details of the selected row of the focused pane.
+    fn detail(&self) -> String {
+        let selected = self.selected(self.focus);
+        match self.focus {
+            Pane::Projects => selected
+                .and_then(|i| self.projects.get(i))
+                .map(|p| format!("{}  {}", p.id, p.root_path)),
+            Pane::Sessions => selected.and_then(|i| {
+                self.session_rows().get(i).map(|row| {
+                    let mut detail = row.id.to_string();
+                    if let Some(model) = row
+                        .active
+                        .and_then(|a| a.model.as_deref())
+                        .or(row.stored.map(|s| s.model.as_str()))
+                    {
+                        detail.push_str(&format!("  {}", model));
+                    }
+                    if let Some(a) = row.active {
+                        detail.push_str(&format!(
+                            "  {} file writes, up {}",
+                            a.file_writes,
+                            duration(a.elapsed_secs)
+                        ));
+                    }
+                    if let Some(s) = row.stored {
+                        detail.push_str(&format!("  {}", s.cwd));
+                    }
+                    detail
+                })
+            }),
+            Pane::Events => selected.and_then(|i| self.events.get(i)).map(|e| {
+                if e.payload.is_null() {
+                    e.entity_id.clone()
+                } else {
+                    format!("{}  {}", e.entity_id, e.payload)
+                }
+            }),
+        }
+        .unwrap_or_default()
+    }
+
+    fn event_line(&self, e: &CrudEvent) -> String {
+        let time = parse_time(&e.timestamp)
+            .map(|t| t.format("%H:%M:%S").to_string())
+            .unwrap_or_else(|| "--:--:--".to_string());
+        let label = match e.entity_type {
+            EntityType::Project => self
+                .projects
+                .iter()
+                .find(|p| p.id == e.entity_id)
+                .map(|p| p.slug.clone()),
+            _ => None,
+        }
+        .or_else(|| {
+            ["title", "name", "slug"]
+                .iter()
+                .find_map(|k| e.payload.get(*k).and_then(|v| v.as_str()))
+                .map(String::from)
+        })
+        .unwrap_or_else(|| short_id(&e.entity_id).to_string());
+
+        let mut line = format!(
+            "{}  {:<14} {:<14} {}",
+            time,
+            snake_case(&e.entity_type),
+            snake_case(&e.action),
+            label
+        );
+        if e.action == CrudAction::Synced {
+            let files = e.payload.get("files_parsed").and_then(|v| v.as_u64());
+            let ms = e.payload.get("duration_ms").and_then(|v| v.as_u64());
+            if let (Some(files), Some(ms)) = (files, ms) {
+                line.push_str(&format!("  ({} files, {} ms)", files, ms));
+            }
+        }
+        if let Some(status) = e.payload.get("new_status").and_then(|v| v.as_str()) {
+            line.push_str(&format!("  → {}", status));
+        }
+        line
+    }
+}
+
+fn header_style() -> Style {
+    Style::new().add_modifier(Modifier::BOLD)
+}
+
+fn highlight_style(focused: bool) -> Style {
+    if focused {
+        Style::new().add_modifier(Modifier::REVERSED)
+    } else {
+        Style::new().add_modifier(Modifier::BOLD)
+    }
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// `serde` name of an enum variant (`chat_session`, `status_changed`…).
+fn snake_case(value: &impl serde::Serialize) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(String::from))
+        .unwrap_or_default()
+}
+
+fn short_id(id: &str) -> &str {
+    id.get(..8).unwrap_or(id)
+}
+
+/// Human-readable duration: `12s`, `5m`, `3h`, `2d`.
+fn duration(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86_399 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86_400),
+    }
+}
+
+/// Human-readable age: `12s ago`, `5m ago`, `3h ago`, `2d ago`.
+fn ago(now: DateTime<Utc>, then: DateTime<Utc>) -> String {
+    format!("{} ago", duration((now - then).num_seconds().max(0) as u64))
+}
+
+/// `ws://` / `wss://` URL of the server at `base_url`.
+fn ws_base(base_url: &str) -> String {
+    if let Some(rest) = base_url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = base_url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        base_url.to_string()
+    }
+}
+
+// ============================================================================
+// Server client
+// ============================================================================
+
+/// Client of the server's HTTP API.
+struct ApiClient {
+    http: reqwest::Client,
+    base_url: String,
+    token: Option<String>,
+}
+
+impl ApiClient {
+    fn new(base_url: &str, config: &Config) -> Result<Self> {
+        // Same machine identity as the `mcp_server` auto-auth, which the
+        // server's email policy doesn't apply to
+        let token = config
+            .auth_config
+            .as_ref()
+            .map(|auth| {
+                encode_jwt(
+                    ANONYMOUS_USER_ID,
+                    "tui@local",
+                    "Terminal Dashboard",
+                    &auth.jwt_secret,
+                    TOKEN_EXPIRY_SECS,
+                )
+            })
+            .transpose()?;
+        Ok(Self {
+            http: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()?,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token,
+        })
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let req = self
+            .http
+            .request(method, format!("{}{}", self.base_url, path));
+        match &self.token {
+            Some(token) => req.bearer_auth(token),
+            None => req,
+        }
+    }
+
+    /// GET `path`, or None when the server answers 404.
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>> {
+        let resp = self.request(reqwest::Method::GET, path).send().await?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        Ok(Some(resp.error_for_status()?.json().await?))
+    }
+
+    /// URL of `/ws/events` with a fresh single-use ticket.
+    async fn events_url(&self) -> Result<String> {
+        let body: serde_json::Value = self
+            .request(reqwest::Method::POST, "/auth/ws-ticket")
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let ticket = body["ticket"].as_str().context("no ticket in response")?;
+        Ok(format!(
+            "{}/ws/events?ticket={}",
+            ws_base(&self.base_url),
+            ticket
+        ))
+    }
+}
+
+/// What the main loop waits on.
+enum Update {
+    Input(Event),
+    Event(CrudEvent),
+    Feed(FeedStatus),
+}
+
+async fn refresh(client: &ApiClient, dashboard: &mut Dashboard) {
+    let snapshot = async {
+        let projects = client
+            .get::<Page<ProjectRow>>("/api/projects?limit=100")
+            .await?
+            .map(|p| p.items)
+            .unwrap_or_default();
+        // 404 when the server runs without chat
+        let active = client
+            .get::<Vec<ActiveSessionInfo>>("/api/chat/sessions/active")
+            .await?
+            .unwrap_or_default();
+        let recent = client
+            .get::<Page<ChatSession>>(&format!("/api/chat/sessions?limit={}", MAX_SESSIONS))
+            .await?
+            .map(|p| p.items)
+            .unwrap_or_default();
+        anyhow::Ok((projects, active, recent))
+    };
+    match snapshot.await {
+        Ok((projects, active, recent)) => {
+            dashboard.set_snapshot(projects, active, recent, Utc::now())
+        }
+        Err(e) => dashboard.refresh_error = Some(e.to_string()),
+    }
+}
+
+/// Forward `/ws/events` to the main loop, reconnecting when it drops.
+async fn event_feed(client: Arc<ApiClient>, tx: mpsc::UnboundedSender<Update>) {
+    loop {
+        let reason = match stream_events(&client, &tx).await {
+            Ok(()) => "connection closed".to_string(),
+            Err(e) => e.to_string(),
+        };
+        if tx.send(Update::Feed(FeedStatus::Down(reason))).is_err() {
+            return;
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+async fn stream_events(client: &ApiClient, tx: &mpsc::UnboundedSender<Update>) -> Result<()> {
+    let (mut ws, _) = tokio_tungstenite::connect_async(client.events_url().await?).await?;
+    // The server authenticates the socket once the client is listening
+    ws.send(Message::text("ready")).await?;
+    let _ = tx.send(Update::Feed(FeedStatus::Live));
+    while let Some(msg) = ws.next().await {
+        // `auth_ok` and graph batches are not CRUD events
+        if let Message::Text(text) = msg? {
+            if let Ok(event) = serde_json::from_str::<CrudEvent>(&text) {
+                if tx.send(Update::Event(event)).is_err() {
+                    return Ok(());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Forward terminal input to the main loop until it goes away. Runs on its
+/// own thread: crossterm's reads block.
+fn read_input(tx: mpsc::UnboundedSender<Update>) {
+    while !tx.is_closed() {
+        match event::poll(INPUT_POLL) {
+            Ok(true) => match event::read() {
+                Ok(input) => {
+                    if tx.send(Update::Input(input)).is_err() {
+                        return;
+                    }
+                }
+                Err(_) => return,
+            },
+            Ok(false) => {}
+            Err(_) => return,
+        }
+    }
+}
+
+/// Restores the terminal and the log filter however the dashboard exits.
+struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        ratatui::restore();
+        let _ = logging::reset_filter();
+    }
+}
+
+/// Run the dashboard against the server at `url` (default: this host's
+/// `server_port`) until the user quits.
+pub async fn run(config: &Config, url: Option<String>) -> Result<()> {
+    let server_url = url.unwrap_or_else(|| format!("http://127.0.0.1:{}", config.server_port));
+    let client = Arc::new(ApiClient::new(&server_url, config)?);
+    let mut dashboard = Dashboard::new(server_url);
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    tokio::spawn(event_feed(client.clone(), tx.clone()));
+    std::thread::spawn(move || read_input(tx));
+
+    // Log lines would tear the frame: silence them while the screen is up
+    let _ = logging::set_filter("off");
+    let mut terminal = ratatui::init();
+    let _guard = TerminalGuard;
+    let mut ticker = tokio::time::interval(REFRESH_INTERVAL);
+
+    loop {
+        terminal.draw(|frame| dashboard.render(frame, Utc::now()))?;
+        tokio::select! {
+            _ = ticker.tick() => refresh(&client, &mut dashboard).await,
+            update = rx.recv() => match update {
+                Some(Update::Input(Event::Key(key))) => match dashboard.handle_key(key) {
+                    Action::Quit => break,
+                    Action::Refresh => refresh(&client, &mut dashboard).await,
+                    Action::None => {}
+                },
+                // Resizes: the next draw uses the new size
+                Some(Update::Input(_)) => {}
+                Some(Update::Event(event)) => dashboard.apply_event(event, Utc::now()),
+                Some(Update::Feed(status)) => dashboard.feed = status,
+                None => break,
+            },
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn project(id: &str, slug: &str) -> ProjectRow {
+        ProjectRow {
+            id: id.to_string(),
+            slug: slug.to_string(),
+            root_path: format!("/srv/{}", slug),
+            last_synced: None,
+            file_count: 12,
+        }
+    }
+
+    fn session(id: &str, project: &str, title: &str, updated_at: DateTime<Utc>) -> ChatSession {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "project_slug": project,
+            "cwd": format!("/srv/{}", project),
+            "title": title,
+            "model": "claude-sonnet-4-6",
+            "created_at": updated_at.to_rfc3339(),
+            "updated_at": updated_at.to_rfc3339(),
+        }))
+        .unwrap()
+    }
+
+    fn active(id: &str, is_streaming: bool) -> ActiveSessionInfo {
+        ActiveSessionInfo {
+            session_id: id.to_string(),
+            is_streaming,
+            model: None,
+            idle_secs: 90,
+            elapsed_secs: 600,
+            tool_calls: 17,
+            file_writes: 3,
+            cost_usd: 0.42,
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    /// Render into a `width` x `height` terminal, one string per line.
+    fn draw(dash: &mut Dashboard, now: DateTime<Utc>, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| dash.render(frame, now)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| (0..width).map(|x| buffer[(x, y)].symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_ago() {
+        let now = Utc::now();
+        assert_eq!(ago(now, now - chrono::Duration::seconds(5)), "5s ago");
+        assert_eq!(ago(now, now - chrono::Duration::minutes(3)), "3m ago");
+        assert_eq!(ago(now, now - chrono::Duration::hours(2)), "2h ago");
+        assert_eq!(ago(now, now - chrono::Duration::days(4)), "4d ago");
+        assert_eq!(ago(now, now + chrono::Duration::seconds(5)), "0s ago");
+        assert_eq!(ws_base("https://po.example.com"), "wss://po.example.com");
+        assert_eq!(ws_base("http://127.0.0.1:8080"), "ws://127.0.0.1:8080");
+    }
+
+    #[test]
+    fn test_running_sessions_come_from_the_chat_manager() {
+        let now = Utc::now();
+        let mut dash = Dashboard::new("http://127.0.0.1:8080");
+        // Updated a minute ago but not running; running but not in the
+        // stored page
+        dash.set_snapshot(
+            vec![],
+            vec![active("aaaaaaaa-1", true), active("cccccccc-3", false)],
+            vec![
+                session("aaaaaaaa-1", "api", "Fix login", now),
+                session(
+                    "bbbbbbbb-2",
+                    "web",
+                    "Recent but idle",
+                    now - chrono::Duration::minutes(1),
+                ),
+            ],
+            now,
+        );
+
+        let screen = draw(&mut dash, now, 140, 30).join("\n");
+        assert!(screen.contains("Chat sessions (2 running)"), "{}", screen);
+        let line = |id: &str| {
+            screen
+                .lines()
+                .find(|l| l.contains(id))
+                .unwrap_or_else(|| panic!("{} not shown:\n{}", id, screen))
+                .to_string()
+        };
+        assert!(
+            line("aaaaaaaa").contains("● aaaaaaaa api"),
+            "{}",
+            line("aaaaaaaa")
+        );
+        assert!(line("aaaaaaaa").contains("streaming"));
+        assert!(line("aaaaaaaa").contains("$0.42"));
+        assert!(line("cccccccc").contains("idle 1m"));
+        assert!(
+            line("bbbbbbbb").contains("○ bbbbbbbb web"),
+            "{}",
+            line("bbbbbbbb")
+        );
+        assert!(line("bbbbbbbb").contains("1m ago"));
+    }
+
+    #[test]
+    fn test_sync_event_updates_project_and_feed() {
+        let now = Utc::now();
+        let mut dash = Dashboard::new("http://127.0.0.1:8080");
+        dash.set_snapshot(vec![project("p-1", "api")], vec![], vec![], now);
+
+        let event = CrudEvent::new(EntityType::Project, CrudAction::Synced, "p-1")
+            .with_payload(serde_json::json!({"files_parsed": 42, "duration_ms": 1200}));
+        dash.apply_event(event, now);
+
+        assert_eq!(dash.projects[0].last_synced, Some(now.to_rfc3339()));
+        let screen = draw(&mut dash, now, 120, 24);
+        assert!(screen.iter().any(|l| l.contains("Projects (1)")));
+        let line = screen.iter().find(|l| l.contains(" synced ")).unwrap();
+        assert!(line.contains("api  (42 files, 1200 ms)"), "{}", line);
+    }
+
+    #[test]
+    fn test_navigation() {
+        let now = Utc::now();
+        let mut dash = Dashboard::new("http://127.0.0.1:8080");
+        dash.set_snapshot(
+            vec![
+                project("p-1", "api"),
+                project("p-2", "web"),
+                project("p-3", "cli"),
+            ],
+            vec![],
+            vec![],
+            now,
+        );
+        assert_eq!(dash.focus, Pane::Projects);
+        assert_eq!(dash.selected(Pane::Projects), Some(0));
+
+        dash.handle_key(key(KeyCode::Down));
+        dash.handle_key(key(KeyCode::Char('j')));
+        dash.handle_key(key(KeyCode::Down));
+        assert_eq!(dash.selected(Pane::Projects), Some(2));
+        dash.handle_key(key(KeyCode::Home));
+        assert_eq!(dash.selected(Pane::Projects), Some(0));
+        // Sorted by slug: the selection detail names the project
+        assert!(dash.detail().ends_with("/srv/api"), "{}", dash.detail());
+
+        dash.handle_key(key(KeyCode::Tab));
+        dash.handle_key(key(KeyCode::Tab));
+        assert_eq!(dash.focus, Pane::Events);
+        dash.handle_key(key(KeyCode::BackTab));
+        assert_eq!(dash.focus, Pane::Sessions);
+        assert_eq!(dash.selected(Pane::Sessions), None);
+
+        assert_eq!(dash.handle_key(key(KeyCode::Char('r'))), Action::Refresh);
+        assert_eq!(dash.handle_key(key(KeyCode::Char('q'))), Action::Quit);
+        assert_eq!(
+            dash.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Action::Quit
+        );
+
+        // Fewer projects after a refresh: the selection stays on a row
+        dash.projects_state.select(Some(2));
+        dash.set_snapshot(vec![project("p-1", "api")], vec![], vec![], now);
+        assert_eq!(dash.selected(Pane::Projects), Some(0));
+    }
+
+    #[test]
+    fn test_event_feed_is_bounded_and_keeps_selection() {
+        let now = Utc::now();
+        let mut dash = Dashboard::new("http://127.0.0.1:8080");
+        for i in 0..(MAX_EVENTS + 5) {
+            dash.apply_event(
+                CrudEvent::new(EntityType::Task, CrudAction::Created, format!("task-{}", i)),
+                now,
+            );
+        }
+        assert_eq!(dash.events.len(), MAX_EVENTS);
+        assert_eq!(
+            dash.events.front().unwrap().entity_id,
+            format!("task-{}", MAX_EVENTS + 4)
+        );
+
+        dash.focus = Pane::Events;
+        dash.handle_key(key(KeyCode::Down));
+        let selected = dash.detail();
+        dash.apply_event(
+            CrudEvent::new(EntityType::Task, CrudAction::Created, "task-new"),
+            now,
+        );
+        assert_eq!(dash.detail(), selected);
+    }
+
+    #[test]
+    fn test_render_fits_small_terminals() {
+        let now = Utc::now();
+        let mut dash = Dashboard::new("http://127.0.0.1:8080");
+        dash.feed = FeedStatus::Down("connection refused".to_string());
+        let screen = draw(&mut dash, now, 60, 12);
+        assert_eq!(screen.len(), 12);
+        assert!(screen[0].contains("events: down"));
+        assert!(screen.iter().any(|l| l.contains("(none yet)")));
+    }
+}
@@ -0,0 +1,1014 @@
+//! Self-update mechanism for the standalone binary.
+//!
+//! Checks GitHub Releases for newer versions, downloads the appropriate
+//! binary for the current OS/arch, verifies its SHA-256 checksum, and
+//! performs an atomic binary replacement.
+//!
+//! The checksum is only trusted once the release's
+//! `checksums-sha256.txt.minisig` verifies against the release public key
+//! (compiled in from `ORCHESTRATOR_RELEASE_PUBKEY`, or `update.public_key` /
+//! `UPDATE_PUBLIC_KEY`). Unsigned or badly signed releases are refused
+//! unless `orchestrator update --insecure` is used; a checksum mismatch is
+//! always fatal.
+//!
+//! Two release channels are available (`update.channel` in config.yaml,
+//! `UPDATE_CHANNEL` env, or `orchestrator update --channel`): `stable`
+//! follows the latest GitHub release, `beta` also considers pre-releases,
+//! ordered by semver precedence (`1.4.0-beta.2 < 1.4.0-rc.1 < 1.4.0`).
+//!
+//! The replaced binary is kept next to the new one as `<binary>.previous`
+//! (with its version in `<binary>.previous.version`), so
+//! `orchestrator update --rollback` can swap back without a download.
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+mod signature;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+const GITHUB_REPO_OWNER: &str = "this-rs";
+const GITHUB_REPO_NAME: &str = "project-orchestrator";
+const BINARY_NAME: &str = "orchestrator";
+const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Minisign public key the release workflow signs with, set at build time.
+const RELEASE_PUBLIC_KEY: Option<&str> = option_env!("ORCHESTRATOR_RELEASE_PUBKEY");
+const CHECKSUMS_ASSET: &str = "checksums-sha256.txt";
+const CHECKSUMS_SIGNATURE_ASSET: &str = "checksums-sha256.txt.minisig";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Release channel followed by `orchestrator update`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    /// Latest stable GitHub release.
+    #[default]
+    Stable,
+    /// Newest release, pre-releases included.
+    Beta,
+}
+
+impl UpdateChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Stable => "stable",
+            Self::Beta => "beta",
+        }
+    }
+}
+
+impl std::fmt::Display for UpdateChannel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for UpdateChannel {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "stable" => Ok(Self::Stable),
+            "beta" => Ok(Self::Beta),
+            other => Err(format!("unknown update channel `{}` (stable, beta)", other)),
+        }
+    }
+}
+
+/// `update` section of config.yaml.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct UpdateYamlConfig {
+    /// Release channel (default: stable).
+    pub channel: Option<UpdateChannel>,
+    /// Minisign public key trusted for release signatures, replacing the
+    /// one compiled into the binary.
+    pub public_key: Option<String>,
+}
+
+/// Information about an available update.
+#[derive(Debug, Clone)]
+pub struct UpdateInfo {
+    pub channel: UpdateChannel,
+    pub current_version: String,
+    pub latest_version: String,
+    pub release_notes: Option<String>,
+    pub download_url: String,
+    /// File name of the release asset behind `download_url`.
+    pub archive_name: String,
+    /// Contents of `checksums-sha256.txt`, if the release has one.
+    pub checksums: Option<String>,
+    /// Contents of `checksums-sha256.txt.minisig`, if the release has one.
+    pub checksums_signature: Option<String>,
+    pub html_url: String,
+}
+
+/// Status returned after performing an update.
+#[derive(Debug)]
+pub enum UpdateStatus {
+    Updated { from: String, to: String },
+    RolledBack { from: String, to: String },
+    AlreadyUpToDate,
+}
+
+/// GitHub API release response (subset).
+#[derive(Debug, Deserialize)]
+struct GitHubRelease {
+    tag_name: String,
+    body: Option<String>,
+    html_url: String,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+    assets: Vec<GitHubAsset>,
+}
+
+/// GitHub API asset response (subset).
+#[derive(Debug, Deserialize)]
+struct GitHubAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+// ============================================================================
+// Platform detection
+// ============================================================================
+
+/// Get the archive filename suffix for the current platform.
+fn platform_archive_suffix() -> Result<(&'static str, &'static str)> {
+    let os = if cfg!(target_os = "macos") {
+        "macos"
+    } else if cfg!(target_os = "linux") {
+        "linux"
+    } else if cfg!(target_os = "windows") {
+        "windows"
+    } else {
+        bail!("Unsupported operating system for self-update");
+    };
+
+    let arch = if cfg!(target_arch = "aarch64") {
+        "arm64"
+    } else if cfg!(target_arch = "x86_64") {
+        "x86_64"
+    } else {
+        bail!("Unsupported architecture for self-update");
+    };
+
+    Ok((os, arch))
+}
+
+// ============================================================================
+// Version comparison
+// ============================================================================
+
+/// Parse a semver string into (major, minor, patch) tuple.
+fn parse_semver(version: &str) -> Result<(u64, u64, u64)> {
+    let v = version.strip_prefix('v').unwrap_or(version);
+    // pre-release and build metadata may themselves contain dots
+    let core = v.split(['-', '+']).next().unwrap_or(v);
+    let parts: Vec<&str> = core.split('.').collect();
+    if parts.len() != 3 {
+        bail!("Invalid semver: {}", version);
+    }
+    Ok((
+        parts[0].parse().context("Invalid major version")?,
+        parts[1].parse().context("Invalid minor version")?,
+        parts[2].parse().context("Invalid patch version")?,
+    ))
+}
+
+/// One dot-separated pre-release identifier. Numeric identifiers have
+/// lower precedence than alphanumeric ones (semver §11), which the derived
+/// ordering gives by variant order.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum PreRelease {
+    Numeric(u64),
+    Alpha(String),
+}
+
+/// A semver version with pre-release identifiers (build metadata ignored).
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Version {
+    core: (u64, u64, u64),
+    pre: Vec<PreRelease>,
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.core.cmp(&other.core).then_with(|| {
+            // a release ranks above any of its pre-releases; otherwise
+            // identifiers compare left to right, a longer list winning ties
+            match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => self.pre.cmp(&other.pre),
+            }
+        })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn parse_version(version: &str) -> Result<Version> {
+    let core = parse_semver(version)?;
+    let v = version.split('+').next().unwrap_or(version);
+    let pre = match v.split_once('-') {
+        Some((_, pre)) => pre
+            .split('.')
+            .map(|id| {
+                if id.is_empty() {
+                    bail!("Invalid pre-release in {}", version);
+                }
+                Ok(match id.parse() {
+                    Ok(n) => PreRelease::Numeric(n),
+                    Err(_) => PreRelease::Alpha(id.to_string()),
+                })
+            })
+            .collect::<Result<_>>()?,
+        None => Vec::new(),
+    };
+    Ok(Version { core, pre })
+}
+
+/// Check if `latest` is newer than `current` (semver precedence, so
+/// `1.0.0-beta.2` is newer than `1.0.0-beta.1` but older than `1.0.0`).
+fn is_newer(current: &str, latest: &str) -> Result<bool> {
+    let current = parse_version(current)?;
+    let latest = parse_version(latest)?;
+    Ok(latest > current)
+}
+
+/// Pick the newest release a channel may install. Drafts and tags that are
+/// not valid semver are skipped; `stable` also skips pre-releases.
+fn pick_release(releases: Vec<GitHubRelease>, channel: UpdateChannel) -> Option<GitHubRelease> {
+    releases
+        .into_iter()
+        .filter(|r| !r.draft)
+        .filter_map(|r| parse_version(&r.tag_name).ok().map(|v| (v, r)))
+        .filter(|(v, r)| channel == UpdateChannel::Beta || (!r.prerelease && v.pre.is_empty()))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, r)| r)
+}
+
+// ============================================================================
+// Update check
+// ============================================================================
+
+/// Check GitHub Releases for a newer version on `channel`.
+pub async fn check_for_update(channel: UpdateChannel) -> Result<Option<UpdateInfo>> {
+    // Stable uses the release GitHub marks as latest; beta needs the list
+    // to see pre-releases
+    let url = match channel {
+        UpdateChannel::Stable => format!(
+            "https://api.github.com/repos/{}/{}/releases/latest",
+            GITHUB_REPO_OWNER, GITHUB_REPO_NAME
+        ),
+        UpdateChannel::Beta => format!(
+            "https://api.github.com/repos/{}/{}/releases?per_page=30",
+            GITHUB_REPO_OWNER, GITHUB_REPO_NAME
+        ),
+    };
+
+    let client = reqwest::Client::builder()
+        .user_agent(format!("{}/{}", BINARY_NAME, CURRENT_VERSION))
+        .build()?;
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .context("Failed to reach GitHub API")?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        // No releases yet
+        return Ok(None);
+    }
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        bail!("GitHub API returned {}: {}", status, body);
+    }
+
+    let release = match channel {
+        UpdateChannel::Stable => response
+            .json::<GitHubRelease>()
+            .await
+            .context("Failed to parse GitHub release")?,
+        UpdateChannel::Beta => {
+            let releases: Vec<GitHubRelease> = response
+                .json()
+                .await
+                .context("Failed to parse GitHub releases")?;
+            match pick_release(releases, channel) {
+                Some(release) => release,
+                None => return Ok(None),
+            }
+        }
+    };
+
+    let latest_version = release
+        .tag_name
+        .strip_prefix('v')
+        .unwrap_or(&release.tag_name);
+
+    if !is_newer(CURRENT_VERSION, latest_version)? {
+        return Ok(None);
+    }
+
+    // Find the download URL for our platform
+    let (os, arch) = platform_archive_suffix()?;
+    let extension = if cfg!(target_os = "windows") {
+        "zip"
+    } else {
+        "tar.gz"
+    };
+    // Look for the "full" variant (with embedded frontend) first, then fall back to light
+    let archive_pattern_full = format!(
+        "{}-full-{}-{}-{}.{}",
+        BINARY_NAME, latest_version, os, arch, extension
+    );
+    let archive_pattern_light = format!(
+        "{}-{}-{}-{}.{}",
+        BINARY_NAME, latest_version, os, arch, extension
+    );
+
+    let download_asset = release
+        .assets
+        .iter()
+        .find(|a| a.name == archive_pattern_full)
+        .or_else(|| {
+            release
+                .assets
+                .iter()
+                .find(|a| a.name == archive_pattern_light)
+        });
+
+    let download_url = match download_asset {
+        Some(asset) => asset.browser_download_url.clone(),
+        None => {
+            tracing::warn!(
+                "No binary found for {}-{} in release {} (looked for {} and {})",
+                os,
+                arch,
+                latest_version,
+                archive_pattern_full,
+                archive_pattern_light,
+            );
+            bail!(
+                "No binary available for your platform ({}-{}) in release {}",
+                os,
+                arch,
+                latest_version
+            );
+        }
+    };
+
+    let archive_name = download_asset.map(|a| a.name.clone()).unwrap();
+    let checksums = fetch_asset_text(&client, &release.assets, CHECKSUMS_ASSET).await?;
+    let checksums_signature =
+        fetch_asset_text(&client, &release.assets, CHECKSUMS_SIGNATURE_ASSET).await?;
+
+    Ok(Some(UpdateInfo {
+        channel,
+        current_version: CURRENT_VERSION.to_string(),
+        latest_version: latest_version.to_string(),
+        release_notes: release.body,
+        download_url,
+        archive_name,
+        checksums,
+        checksums_signature,
+        html_url: release.html_url,
+    }))
+}
+
+/// Download a small text asset (checksums, signature) if the release has it.
+async fn fetch_asset_text(
+    client: &reqwest::Client,
+    assets: &[GitHubAsset],
+    name: &str,
+) -> Result<Option<String>> {
+    let Some(asset) = assets.iter().find(|a| a.name == name) else {
+        return Ok(None);
+    };
+    let response = client
+        .get(&asset.browser_download_url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to download {}", name))?;
+    if !response.status().is_success() {
+        bail!(
+            "Downloading {} failed with status: {}",
+            name,
+            response.status()
+        );
+    }
+    Ok(Some(response.text().await?))
+}
+
+/// Find the checksum of `archive_name` in `sha256sum` output. Names must
+/// match exactly (`*name` is binary mode).
+fn checksum_for(checksums: &str, archive_name: &str) -> Option<String> {
+    checksums.lines().find_map(|line| {
+        let (sum, name) = line.split_once(char::is_whitespace)?;
+        let name = name.trim_start();
+        let name = name.strip_prefix('*').unwrap_or(name);
+        (name == archive_name).then(|| sum.to_ascii_lowercase())
+    })
+}
+
+// ============================================================================
+// Verification
+// ============================================================================
+
+/// The archive checksum from a correctly signed `checksums-sha256.txt`.
+fn signed_checksum(info: &UpdateInfo, public_key: Option<&str>) -> Result<String> {
+    let key = public_key
+        .or(RELEASE_PUBLIC_KEY)
+        .filter(|k| !k.trim().is_empty())
+        .ok_or_else(|| {
+            anyhow!("No release public key available (set update.public_key in config.yaml)")
+        })?;
+    let key = signature::parse_public_key(key).context("Release public key")?;
+    let checksums = info.checksums.as_deref().ok_or_else(|| {
+        anyhow!(
+            "Release v{} does not publish {}",
+            info.latest_version,
+            CHECKSUMS_ASSET
+        )
+    })?;
+    let sig = info.checksums_signature.as_deref().ok_or_else(|| {
+        anyhow!(
+            "Release v{} is not signed (no {})",
+            info.latest_version,
+            CHECKSUMS_SIGNATURE_ASSET
+        )
+    })?;
+    let trusted_comment = signature::verify(&key, checksums.as_bytes(), sig)
+        .with_context(|| format!("Invalid signature on {}", CHECKSUMS_ASSET))?;
+    tracing::info!("Release signature verified: {}", trusted_comment);
+    checksum_for(checksums, &info.archive_name).ok_or_else(|| {
+        anyhow!(
+            "{} is not listed in the signed {}",
+            info.archive_name,
+            CHECKSUMS_ASSET
+        )
+    })
+}
+
+/// Check the downloaded archive against the signed checksums.
+///
+/// With `insecure`, signature problems are downgraded to warnings and the
+/// unsigned checksum (if any) is used instead.
+fn verify_archive(
+    info: &UpdateInfo,
+    archive_bytes: &[u8],
+    public_key: Option<&str>,
+    insecure: bool,
+) -> Result<()> {
+    let expected = match signed_checksum(info, public_key) {
+        Ok(sum) => Some(sum),
+        Err(e) if insecure => {
+            tracing::warn!("{:#} — continuing because --insecure was passed", e);
+            info.checksums
+                .as_deref()
+                .and_then(|c| checksum_for(c, &info.archive_name))
+        }
+        Err(e) => {
+            return Err(
+                e.context("Refusing to install an unverified update (pass --insecure to override)")
+            )
+        }
+    };
+
+    use sha2::{Digest, Sha256};
+    let actual = hex::encode(Sha256::digest(archive_bytes));
+    match expected {
+        Some(expected) if actual != expected => bail!(
+            "Checksum mismatch!\n  Expected: {}\n  Got:      {}",
+            expected,
+            actual
+        ),
+        Some(_) => tracing::info!("Checksum verified: {}", actual),
+        None => tracing::warn!("No checksum available — skipping verification"),
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Perform update
+// ============================================================================
+
+/// Download and install the update, replacing the current binary.
+///
+/// `public_key` overrides the compiled-in release key; `insecure` allows
+/// unsigned releases (see [`verify_archive`]).
+pub async fn perform_update(
+    info: &UpdateInfo,
+    public_key: Option<&str>,
+    insecure: bool,
+) -> Result<UpdateStatus> {
+    let client = reqwest::Client::builder()
+        .user_agent(format!("{}/{}", BINARY_NAME, CURRENT_VERSION))
+        .build()?;
+
+    // Download the archive
+    tracing::info!("Downloading {}...", info.download_url);
+    let response = client
+        .get(&info.download_url)
+        .send()
+        .await
+        .context("Failed to download update")?;
+
+    if !response.status().is_success() {
+        bail!("Download failed with status: {}", response.status());
+    }
+
+    let archive_bytes = response.bytes().await.context("Failed to read download")?;
+
+    // Verify the signed checksum before touching the installed binary
+    verify_archive(info, &archive_bytes, public_key, insecure)?;
+
+    // Extract the binary from the archive
+    let binary_bytes = extract_binary_from_archive(&archive_bytes)?;
+
+    // Perform atomic replacement of the current binary, keeping the old one
+    // for `--rollback`
+    let current_exe = std::env::current_exe().context("Failed to get current executable path")?;
+    atomic_replace(&current_exe, &binary_bytes)?;
+    if let Err(e) = std::fs::write(previous_version_path(&current_exe), &info.current_version) {
+        tracing::warn!("Failed to record previous version: {}", e);
+    }
+
+    tracing::info!(
+        "Updated from v{} to v{}",
+        info.current_version,
+        info.latest_version
+    );
+
+    Ok(UpdateStatus::Updated {
+        from: info.current_version.clone(),
+        to: info.latest_version.clone(),
+    })
+}
+
+/// Extract the orchestrator binary from a tar.gz or zip archive.
+fn extract_binary_from_archive(archive_bytes: &[u8]) -> Result<Vec<u8>> {
+    #[cfg(not(target_os = "windows"))]
+    {
+        extract_from_tar_gz(archive_bytes)
+    }
+    #[cfg(target_os = "windows")]
+    {
+        extract_from_zip(archive_bytes)
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn extract_from_tar_gz(archive_bytes: &[u8]) -> Result<Vec<u8>> {
+    use std::io::Read;
+
+    let decoder = flate2::read::GzDecoder::new(archive_bytes);
+    let mut archive = tar::Archive::new(decoder);
+
+    let binary_name = BINARY_NAME;
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?;
+        let file_name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+
+        if file_name == binary_name {
+            let mut buf = Vec::new();
+            entry.read_to_end(&mut buf)?;
+            return Ok(buf);
+        }
+    }
+
+    bail!("Binary '{}' not found in archive", binary_name);
+}
+
+#[cfg(target_os = "windows")]
+fn extract_from_zip(archive_bytes: &[u8]) -> Result<Vec<u8>> {
+    use std::io::Read;
+
+    let cursor = std::io::Cursor::new(archive_bytes);
+    let mut archive = zip::ZipArchive::new(cursor)?;
+
+    let binary_name = format!("{}.exe", BINARY_NAME);
+
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        let name = file.name().to_string();
+        let file_name = std::path::Path::new(&name)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default()
+            .to_string();
+
+        if file_name == binary_name {
+            let mut buf = Vec::new();
+            file.read_to_end(&mut buf)?;
+            return Ok(buf);
+        }
+    }
+
+    bail!("Binary '{}' not found in archive", binary_name);
+}
+
+/// Path of the binary kept by the last update (`orchestrator.previous`).
+fn previous_binary_path(target: &Path) -> PathBuf {
+    let name = target
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| BINARY_NAME.to_string());
+    target.with_file_name(format!("{}.previous", name))
+}
+
+/// Version of the binary at [`previous_binary_path`].
+fn previous_version_path(target: &Path) -> PathBuf {
+    let mut path = previous_binary_path(target).into_os_string();
+    path.push(".version");
+    PathBuf::from(path)
+}
+
+/// Atomically replace a binary file.
+///
+/// Strategy:
+/// 1. Write new binary to a temporary file next to the target
+/// 2. Set executable permissions
+/// 3. Rename old binary to `<binary>.previous` (replacing an older one)
+/// 4. Rename new binary into place
+///
+/// The previous binary is kept for [`rollback`].
+fn atomic_replace(target: &Path, new_bytes: &[u8]) -> Result<()> {
+    let parent = target
+        .parent()
+        .ok_or_else(|| anyhow!("Cannot determine parent directory of {}", target.display()))?;
+
+    let temp_path = parent.join(format!(".{}.new", BINARY_NAME));
+    let backup_path = previous_binary_path(target);
+
+    // Write new binary
+    {
+        let mut file = std::fs::File::create(&temp_path)
+            .context("Failed to create temporary file for update")?;
+        file.write_all(new_bytes)?;
+        file.flush()?;
+    }
+
+    // Set executable permissions on Unix
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&temp_path, std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    // Move current binary to backup
+    if target.exists() {
+        // Remove old backup if it exists
+        let _ = std::fs::remove_file(&backup_path);
+        std::fs::rename(target, &backup_path).context("Failed to backup current binary")?;
+    }
+
+    // Move new binary into place
+    match std::fs::rename(&temp_path, target) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            // Rollback: restore backup
+            tracing::error!("Failed to place new binary, rolling back: {}", e);
+            if backup_path.exists() {
+                let _ = std::fs::rename(&backup_path, target);
+            }
+            let _ = std::fs::remove_file(&temp_path);
+            Err(e).context("Failed to replace binary with update")
+        }
+    }
+}
+
+// ============================================================================
+// Rollback
+// ============================================================================
+
+/// Swap the current binary with the one kept by the last update.
+///
+/// The binary being replaced becomes the new `.previous`, so running the
+/// rollback twice returns to the updated version.
+pub fn rollback() -> Result<UpdateStatus> {
+    let current_exe = std::env::current_exe().context("Failed to get current executable path")?;
+    let restored = swap_with_previous(&current_exe, CURRENT_VERSION)?;
+    tracing::info!("Rolled back from v{} to v{}", CURRENT_VERSION, restored);
+    Ok(UpdateStatus::RolledBack {
+        from: CURRENT_VERSION.to_string(),
+        to: restored,
+    })
+}
+
+/// Returns the version that was restored (`unknown` when not recorded).
+fn swap_with_previous(target: &Path, current_version: &str) -> Result<String> {
+    let previous = previous_binary_path(target);
+    if !previous.exists() {
+        bail!(
+            "No previous binary to roll back to ({} not found)",
+            previous.display()
+        );
+    }
+    let version_path = previous_version_path(target);
+    let restored = std::fs::read_to_string(&version_path)
+        .map(|v| v.trim().to_string())
+        .unwrap_or_else(|_| "unknown".to_string());
+
+    let parent = target
+        .parent()
+        .ok_or_else(|| anyhow!("Cannot determine parent directory of {}", target.display()))?;
+    let swap_path = parent.join(format!(".{}.rollback", BINARY_NAME));
+
+    std::fs::rename(target, &swap_path).context("Failed to move current binary aside")?;
+    if let Err(e) = std::fs::rename(&previous, target) {
+        let _ = std::fs::rename(&swap_path, target);
+        return Err(e).context("Failed to restore previous binary");
+    }
+    if let Err(e) = std::fs::rename(&swap_path, &previous) {
+        // the rollback itself succeeded; only the way forward is lost
+        tracing::warn!("Failed to keep the replaced binary: {}", e);
+        let _ = std::fs::remove_file(&swap_path);
+        let _ = std::fs::remove_file(&version_path);
+    } else {
+        std::fs::write(&version_path, current_version)
+            .context("Failed to record previous version")?;
+    }
+    Ok(restored)
+}
+
+// ============================================================================
+// Config persistence
+// ============================================================================
+
+/// Record `channel` as `update.channel` in config.yaml, leaving every other
+/// key untouched (read as a YAML tree, written back via tmp + rename).
+pub fn persist_channel(yaml_path: &Path, channel: UpdateChannel) -> Result<()> {
+    let mut doc: serde_yaml::Value = if yaml_path.exists() {
+        let contents = std::fs::read_to_string(yaml_path)
+            .with_context(|| format!("Reading {}", yaml_path.display()))?;
+        serde_yaml::from_str(&contents)
+            .with_context(|| format!("Parsing {}", yaml_path.display()))?
+    } else {
+        serde_yaml::Value::Mapping(serde_yaml::Mapping::new())
+    };
+    if doc.is_null() {
+        doc = serde_yaml::Value::Mapping(serde_yaml::Mapping::new());
+    }
+
+    let root = doc
+        .as_mapping_mut()
+        .ok_or_else(|| anyhow!("config.yaml root is not a YAML mapping"))?;
+    let section = root
+        .entry(serde_yaml::Value::String("update".into()))
+        .or_insert_with(|| serde_yaml::Value::Mapping(serde_yaml::Mapping::new()));
+    if !section.is_mapping() {
+        *section = serde_yaml::Value::Mapping(serde_yaml::Mapping::new());
+    }
+    section.as_mapping_mut().unwrap().insert(
+        serde_yaml::Value::String("channel".into()),
+        serde_yaml::Value::String(channel.as_str().into()),
+    );
+
+    let tmp = yaml_path.with_extension("yaml.tmp");
+    std::fs::write(&tmp, serde_yaml::to_string(&doc)?)
+        .with_context(|| format!("Writing {}", tmp.display()))?;
+    std::fs::rename(&tmp, yaml_path)
+        .with_context(|| format!("Renaming {} to {}", tmp.display(), yaml_path.display()))?;
+    Ok(())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_semver() {
+        assert_eq!(parse_semver("0.1.0").unwrap(), (0, 1, 0));
+        assert_eq!(parse_semver("v1.2.3").unwrap(), (1, 2, 3));
+        assert_eq!(parse_semver("1.0.0-beta").unwrap(), (1, 0, 0));
+    }
+
+    #[test]
+    fn test_is_newer() {
+        assert!(is_newer("0.1.0", "0.2.0").unwrap());
+        assert!(is_newer("0.1.0", "1.0.0").unwrap());
+        assert!(is_newer("1.0.0", "1.0.1").unwrap());
+        assert!(!is_newer("1.0.0", "1.0.0").unwrap());
+        assert!(!is_newer("2.0.0", "1.0.0").unwrap());
+    }
+
+    #[test]
+    fn test_prerelease_ordering() {
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1-beta.1",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(is_newer(pair[0], pair[1]).unwrap(), "{:?}", pair);
+            assert!(!is_newer(pair[1], pair[0]).unwrap(), "{:?}", pair);
+        }
+        assert!(!is_newer("1.0.0+build.1", "1.0.0+build.2").unwrap());
+        assert!(parse_version("1.0.0-").is_err());
+        assert_eq!(parse_semver("2.1.0-rc.1+sha.5114f85").unwrap(), (2, 1, 0));
+    }
+
+    fn release(tag: &str, prerelease: bool, draft: bool) -> GitHubRelease {
+        GitHubRelease {
+            tag_name: tag.to_string(),
+            body: None,
+            html_url: String::new(),
+            draft,
+            prerelease,
+            assets: vec![],
+        }
+    }
+
+    #[test]
+    fn test_pick_release_by_channel() {
+        let releases = || {
+            vec![
+                release("v1.2.0", false, false),
+                release("v1.3.0-beta.1", true, false),
+                release("v1.3.0-beta.2", true, true),
+                release("nightly", true, false),
+            ]
+        };
+        assert_eq!(
+            pick_release(releases(), UpdateChannel::Stable)
+                .unwrap()
+                .tag_name,
+            "v1.2.0"
+        );
+        assert_eq!(
+            pick_release(releases(), UpdateChannel::Beta)
+                .unwrap()
+                .tag_name,
+            "v1.3.0-beta.1"
+        );
+    }
+
+    #[test]
+    fn test_channel_parse() {
+        assert_eq!(
+            "BETA".parse::<UpdateChannel>().unwrap(),
+            UpdateChannel::Beta
+        );
+        assert!("nightly".parse::<UpdateChannel>().is_err());
+        let yaml: UpdateYamlConfig = serde_yaml::from_str("channel: beta").unwrap();
+        assert_eq!(yaml.channel, Some(UpdateChannel::Beta));
+    }
+
+    #[test]
+    fn test_replace_keeps_previous_and_rollback_swaps() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe = dir.path().join(BINARY_NAME);
+        std::fs::write(&exe, b"v1").unwrap();
+
+        atomic_replace(&exe, b"v2").unwrap();
+        std::fs::write(previous_version_path(&exe), "1.0.0").unwrap();
+        assert_eq!(std::fs::read(&exe).unwrap(), b"v2");
+        assert_eq!(std::fs::read(previous_binary_path(&exe)).unwrap(), b"v1");
+
+        assert_eq!(swap_with_previous(&exe, "2.0.0").unwrap(), "1.0.0");
+        assert_eq!(std::fs::read(&exe).unwrap(), b"v1");
+        assert_eq!(std::fs::read(previous_binary_path(&exe)).unwrap(), b"v2");
+        assert_eq!(
+            std::fs::read_to_string(previous_version_path(&exe)).unwrap(),
+            "2.0.0"
+        );
+    }
+
+    #[test]
+    fn test_rollback_without_previous_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe = dir.path().join(BINARY_NAME);
+        std::fs::write(&exe, b"v1").unwrap();
+        let err = swap_with_previous(&exe, "1.0.0").unwrap_err();
+        assert!(err.to_string().contains("No previous binary"));
+    }
+
+    #[test]
+    fn test_persist_channel_keeps_other_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        std::fs::write(&path, "server:\n  port: 9000\nupdate:\n  channel: stable\n").unwrap();
+
+        persist_channel(&path, UpdateChannel::Beta).unwrap();
+        let doc: serde_yaml::Value =
+            serde_yaml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(doc["server"]["port"].as_u64(), Some(9000));
+        assert_eq!(doc["update"]["channel"].as_str(), Some("beta"));
+    }
+
+    const ARCHIVE: &[u8] = b"fake archive";
+    const ARCHIVE_NAME: &str = "orchestrator-9.0.0-linux-x86_64.tar.gz";
+
+    fn signed_info(checksums: &str, signature: Option<String>) -> UpdateInfo {
+        UpdateInfo {
+            channel: UpdateChannel::Stable,
+            current_version: "1.0.0".to_string(),
+            latest_version: "9.0.0".to_string(),
+            release_notes: None,
+            download_url: String::new(),
+            archive_name: ARCHIVE_NAME.to_string(),
+            checksums: Some(checksums.to_string()),
+            checksums_signature: signature,
+            html_url: String::new(),
+        }
+    }
+
+    fn archive_checksums() -> String {
+        use sha2::{Digest, Sha256};
+        format!(
+            "{}  {}\n{}  {}.sig\n",
+            hex::encode(Sha256::digest(ARCHIVE)),
+            ARCHIVE_NAME,
+            "0".repeat(64),
+            ARCHIVE_NAME
+        )
+    }
+
+    #[test]
+    fn test_checksum_for_matches_exact_name() {
+        let text = "aaa  orchestrator-1.0.0-linux-x86_64.tar.gz.sig\nBBB *orchestrator-1.0.0-linux-x86_64.tar.gz\n";
+        assert_eq!(
+            checksum_for(text, "orchestrator-1.0.0-linux-x86_64.tar.gz").as_deref(),
+            Some("bbb")
+        );
+        assert_eq!(checksum_for(text, "orchestrator-1.0.0-linux"), None);
+    }
+
+    #[test]
+    fn test_verify_archive_with_signed_checksums() {
+        use signature::tests::{public_key_line, sign, signing_key};
+        let key = signing_key();
+        let public_key = public_key_line(&key);
+        let checksums = archive_checksums();
+        let info = signed_info(&checksums, Some(sign(&key, checksums.as_bytes(), "v9.0.0")));
+
+        verify_archive(&info, ARCHIVE, Some(&public_key), false).unwrap();
+        let err = verify_archive(&info, b"tampered", Some(&public_key), true).unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch"), "{:#}", err);
+
+        // Checksums edited after signing
+        let forged = signed_info(
+            &checksums.replace(ARCHIVE_NAME, "other.tar.gz"),
+            info.checksums_signature.clone(),
+        );
+        assert!(verify_archive(&forged, ARCHIVE, Some(&public_key), false).is_err());
+    }
+
+    #[test]
+    fn test_verify_archive_refuses_unsigned_unless_insecure() {
+        let public_key = signature::tests::public_key_line(&signature::tests::signing_key());
+        let info = signed_info(&archive_checksums(), None);
+
+        let err = verify_archive(&info, ARCHIVE, Some(&public_key), false).unwrap_err();
+        assert!(format!("{:#}", err).contains("--insecure"), "{:#}", err);
+
+        verify_archive(&info, ARCHIVE, Some(&public_key), true).unwrap();
+        // --insecure still checks the unsigned checksum
+        assert!(verify_archive(&info, b"tampered", Some(&public_key), true).is_err());
+    }
+
+    #[test]
+    fn test_platform_detection() {
+        let result = platform_archive_suffix();
+        assert!(result.is_ok());
+        let (os, arch) = result.unwrap();
+        assert!(!os.is_empty());
+        assert!(!arch.is_empty());
+    }
+}
@@ -0,0 +1,136 @@
+//! Minisign signature verification for release artifacts.
+//!
+//! Releases publish `checksums-sha256.txt` together with a minisign
+//! signature (`checksums-sha256.txt.minisig`). Verification is done by the
+//! `minisign-verify` crate; both minisign algorithms are accepted: the
+//! default `ED` (Ed25519 over the BLAKE2b-512 hash of the file) and legacy
+//! `Ed` (Ed25519 over the file itself). The trusted comment is covered by
+//! the global signature, so it is returned only once that signature checks
+//! out.
+
+use minisign_verify::{Error, PublicKey, Signature};
+
+/// Parse a release public key: the base64 key line (`RWQ…`), or the
+/// contents of a `minisign.pub` file.
+pub fn parse_public_key(input: &str) -> Result<PublicKey, Error> {
+    let input = input.trim();
+    if input.starts_with("untrusted comment:") {
+        PublicKey::decode(input)
+    } else {
+        PublicKey::from_base64(input)
+    }
+}
+
+/// Verify `signature_file` (the `.minisig` contents) over `message` and
+/// return its trusted comment.
+pub fn verify(
+    public_key: &PublicKey,
+    message: &[u8],
+    signature_file: &str,
+) -> Result<String, Error> {
+    let signature = Signature::decode(signature_file)?;
+    public_key.verify(message, &signature, true)?;
+    Ok(signature.trusted_comment().to_string())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use multibase::Base;
+
+    const KEY_ID: [u8; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+
+    /// `ED` signature of [`SIGNED_MESSAGE`] by [`signing_key`], made the way
+    /// `minisign -S` does (the test helpers can only make legacy ones).
+    const PREHASHED_SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RUQBAgMEBQYHCK6+tjbkgnXJtH++1Pfi+IynIEP/cFqU61o6MZybhJWUe8k9AoLsCxSB/lKI12jIG0EMekluyCA+JQ+FXsEw3QE=
+trusted comment: timestamp:1 file:checksums-sha256.txt
+Cc+kGVCXEceMtUj0231oPKvzHRdTfVLWRalMsF7+j4cOAyVj5cqophrYD5YhRjuEDkjOnMi/Rryx4RY812UlDQ==
+";
+    const SIGNED_MESSAGE: &[u8] = b"abc123  orchestrator-1.0.0-linux-x86_64.tar.gz\n";
+
+    fn b64(bytes: &[u8]) -> String {
+        multibase::encode(Base::Base64Pad, bytes)[1..].to_string()
+    }
+
+    pub(crate) fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    pub(crate) fn public_key_line(key: &SigningKey) -> String {
+        let mut bytes = b"Ed".to_vec();
+        bytes.extend_from_slice(&KEY_ID);
+        bytes.extend_from_slice(key.verifying_key().as_bytes());
+        b64(&bytes)
+    }
+
+    /// Build a legacy (`Ed`) `.minisig` file the way `minisign -S -l` does.
+    pub(crate) fn sign(key: &SigningKey, message: &[u8], trusted: &str) -> String {
+        let signature = key.sign(message).to_bytes();
+        let mut sig = b"Ed".to_vec();
+        sig.extend_from_slice(&KEY_ID);
+        sig.extend_from_slice(&signature);
+        let mut global = signature.to_vec();
+        global.extend_from_slice(trusted.as_bytes());
+        format!(
+            "untrusted comment: signature from minisign secret key\n{}\ntrusted comment: {}\n{}\n",
+            b64(&sig),
+            trusted,
+            b64(&key.sign(&global).to_bytes())
+        )
+    }
+
+    #[test]
+    fn test_verify_prehashed_and_legacy() {
+        let key = signing_key();
+        let public = parse_public_key(&format!(
+            "untrusted comment: minisign public key\n{}\n",
+            public_key_line(&key)
+        ))
+        .unwrap();
+
+        assert_eq!(
+            verify(&public, SIGNED_MESSAGE, PREHASHED_SIGNATURE).unwrap(),
+            "timestamp:1 file:checksums-sha256.txt"
+        );
+        let legacy = sign(&key, SIGNED_MESSAGE, "timestamp:2");
+        assert_eq!(
+            verify(&public, SIGNED_MESSAGE, &legacy).unwrap(),
+            "timestamp:2"
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_tampering() {
+        let key = signing_key();
+        let public = parse_public_key(&public_key_line(&key)).unwrap();
+
+        assert!(matches!(
+            verify(&public, b"tampered", PREHASHED_SIGNATURE),
+            Err(Error::InvalidSignature)
+        ));
+
+        let forged = PREHASHED_SIGNATURE.replace("timestamp:1", "timestamp:9");
+        assert!(matches!(
+            verify(&public, SIGNED_MESSAGE, &forged),
+            Err(Error::InvalidSignature)
+        ));
+
+        let other = SigningKey::from_bytes(&[9u8; 32]);
+        let mut other_line = b"Ed".to_vec();
+        other_line.extend_from_slice(&[0xff; 8]);
+        other_line.extend_from_slice(other.verifying_key().as_bytes());
+        let other_public = parse_public_key(&b64(&other_line)).unwrap();
+        assert!(matches!(
+            verify(&other_public, SIGNED_MESSAGE, PREHASHED_SIGNATURE),
+            Err(Error::UnexpectedKeyId)
+        ));
+
+        assert!(matches!(
+            verify(&public, SIGNED_MESSAGE, "not a signature"),
+            Err(Error::InvalidEncoding)
+        ));
+        assert!(parse_public_key("bm90IGEga2V5").is_err());
+    }
+}
@@ -2,6 +2,7 @@
 
 pub mod file_path_extractor;
 pub mod paths;
+pub mod xml;
 
 /// Stable equivalent of the nightly-only `str::floor_char_boundary`.
 /// Returns the largest byte index `<= index` that is a valid UTF-8 char boundary.
@@ -0,0 +1,192 @@
+//! Tag scanner for the machine-written XML reports we ingest (Cobertura
+//! coverage, JUnit test results).
+//!
+//! This is not a parser: tags are yielded one by one in document order with
+//! their raw attributes and the text that follows them. Comments, processing
+//! instructions and doctypes are skipped; entities are limited to the
+//! predefined ones and character references, which is all these reports use.
+
+/// A start, end or empty-element tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tag<'a> {
+    /// Element name, without the `/` of an end tag
+    pub name: &'a str,
+    /// `</name>`
+    pub closing: bool,
+    /// `<name/>`
+    pub self_closing: bool,
+    /// Everything between `<` and `>`
+    raw: &'a str,
+    /// Document after the tag
+    rest: &'a str,
+}
+
+impl Tag<'_> {
+    /// Value of attribute `key`, unescaped.
+    pub fn attr(&self, key: &str) -> Option<String> {
+        attribute(self.raw, key)
+    }
+
+    /// Text up to the next tag (or the content of a CDATA section right after
+    /// this tag), unescaped and trimmed.
+    pub fn text(&self) -> String {
+        if let Some(cdata) = self.rest.trim_start().strip_prefix("<![CDATA[") {
+            let end = cdata.find("]]>").unwrap_or(cdata.len());
+            return cdata[..end].trim().to_string();
+        }
+        let end = self.rest.find('<').unwrap_or(self.rest.len());
+        unescape(self.rest[..end].trim())
+    }
+}
+
+/// Iterator over the tags of a document
+pub struct Tags<'a> {
+    rest: &'a str,
+}
+
+/// Tags of `xml` in document order.
+pub fn tags(xml: &str) -> Tags<'_> {
+    Tags { rest: xml }
+}
+
+impl<'a> Iterator for Tags<'a> {
+    type Item = Tag<'a>;
+
+    fn next(&mut self) -> Option<Tag<'a>> {
+        loop {
+            let start = self.rest.find('<')?;
+            let rest = &self.rest[start + 1..];
+            let skipped = [("!--", "-->"), ("![CDATA[", "]]>"), ("?", "?>")]
+                .iter()
+                .find_map(|(open, close)| {
+                    rest.strip_prefix(open).map(|body| {
+                        body.find(close)
+                            .map_or("", |end| &body[end + close.len()..])
+                    })
+                });
+            if let Some(after) = skipped {
+                self.rest = after;
+                continue;
+            }
+
+            let end = rest.find('>')?;
+            let raw = &rest[..end];
+            self.rest = &rest[end + 1..];
+            if raw.starts_with('!') {
+                continue;
+            }
+
+            let closing = raw.starts_with('/');
+            let self_closing = raw.ends_with('/');
+            let name = raw
+                .trim_start_matches('/')
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or("");
+            return Some(Tag {
+                name,
+                closing,
+                self_closing,
+                raw,
+                rest: self.rest,
+            });
+        }
+    }
+}
+
+/// Value of attribute `key` of a start tag (`name a="1" b='2'`).
+fn attribute(tag: &str, key: &str) -> Option<String> {
+    let mut rest = tag.split_once(char::is_whitespace)?.1;
+    loop {
+        let (name, after) = rest.split_once('=')?;
+        let after = after.trim_start();
+        let quote = after.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let len = after[1..].find(quote)?;
+        if name.trim() == key {
+            return Some(unescape(&after[1..1 + len]));
+        }
+        rest = &after[len + 2..];
+    }
+}
+
+/// Decode the predefined entities and character references (`&#10;`,
+/// `&#x27;`). Unknown entities are kept as is.
+pub fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').and_then(|end| {
+            let c = match &rest[1..end] {
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                "amp" => '&',
+                entity => {
+                    let number = entity.strip_prefix('#')?;
+                    let code = match number.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => number.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, end + 1))
+        });
+        match decoded {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tags() {
+        let xml = r#"<?xml version="1.0"?>
+<!DOCTYPE report>
+<!-- <skipped name="x"/> -->
+<suite name='a &amp; b'>
+  <case name="one" time="0.5"/>
+  <case name="two"><failure message="boom">trace &lt;here&gt;</failure></case>
+  <out><![CDATA[<not a tag>]]></out>
+</suite>"#;
+        let found: Vec<_> = tags(xml).collect();
+        let names: Vec<_> = found
+            .iter()
+            .map(|t| format!("{}{}", if t.closing { "/" } else { "" }, t.name))
+            .collect();
+        assert_eq!(
+            names,
+            ["suite", "case", "case", "failure", "/failure", "/case", "out", "/out", "/suite"]
+        );
+        assert_eq!(found[0].attr("name").as_deref(), Some("a & b"));
+        assert!(found[1].self_closing);
+        assert_eq!(found[1].attr("time").as_deref(), Some("0.5"));
+        assert_eq!(found[1].attr("missing"), None);
+        assert!(!found[2].self_closing);
+        assert_eq!(found[3].attr("message").as_deref(), Some("boom"));
+        assert_eq!(found[3].text(), "trace <here>");
+        assert_eq!(found[6].text(), "<not a tag>");
+    }
+
+    #[test]
+    fn test_unescape() {
+        assert_eq!(unescape("a &lt;b&gt; &amp;lt;"), "a <b> &lt;");
+        assert_eq!(unescape("line&#10;next&#x21;"), "line\nnext!");
+        assert_eq!(unescape("R&D &unknown; &#xZZ;"), "R&D &unknown; &#xZZ;");
+    }
+}
@@ -16,17 +16,27 @@ fn test_config() -> Config {
         neo4j_user: std::env::var("NEO4J_USER").unwrap_or_else(|_| "neo4j".into()),
         neo4j_password: std::env::var("NEO4J_PASSWORD")
             .unwrap_or_else(|_| "orchestrator123".into()),
+        neo4j_query_timeout_ms: 60_000,
+        neo4j_slow_query_ms: 100,
         meilisearch_url: std::env::var("MEILISEARCH_URL")
             .unwrap_or_else(|_| "http://localhost:7700".into()),
         meilisearch_key: std::env::var("MEILISEARCH_KEY")
             .unwrap_or_else(|_| "orchestrator-meili-key-change-me".into()),
+        // Index synchronously: tests search right after syncing
+        meilisearch_index_queue_path: Some(String::new()),
         nats_url: None,
+        nats_jetstream: false,
+        nats_consumer: None,
+        ipc_enabled: false,
+        ipc_path: None,
         workspace_path: ".".into(),
+        server_host: "0.0.0.0".into(),
         server_port: 8080,
         auth_config: None,
         serve_frontend: false,
         frontend_path: "./dist".to_string(),
         public_url: None,
+        read_only: false,
         chat_permissions: None,
         chat_default_model: None,
         chat_max_sessions: None,
@@ -47,6 +57,11 @@ fn test_config() -> Config {
         registry_remote_url: None,
         neural_routing: Default::default(),
         config_yaml_path: None,
+        headless: false,
+        update_channel: Default::default(),
+        update_public_key: None,
+        log_format: Default::default(),
+        log_filter: String::new(),
     }
 }
 
@@ -110,8 +125,10 @@ async fn test_neo4j_file_operations() {
         path: format!("/test/file_{}.rs", Uuid::new_v4()),
         language: "rust".to_string(),
         hash: "abc123".to_string(),
+        line_count: 0,
         last_parsed: chrono::Utc::now(),
         project_id: None,
+        encoding: None,
     };
 
     // Upsert file
@@ -399,8 +416,10 @@ async fn test_neo4j_stale_file_cleanup() {
             path: path.clone(),
             language: "rust".to_string(),
             hash: "test-hash".to_string(),
+            line_count: 0,
             last_parsed: chrono::Utc::now(),
             project_id: Some(project_id),
+            encoding: None,
         };
         state.neo4j.upsert_file(&file).await.unwrap();
         state
@@ -482,6 +501,8 @@ fn make_task(title: &str, status: TaskStatus) -> TaskNode {
         execution_context: None,
         persona: None,
         prompt_cache: None,
+        board_position: None,
+        version: 0,
     }
 }
 
@@ -697,3 +718,180 @@ async fn test_neo4j_task_plan_and_next_available() {
     state.neo4j.delete_task(task.id).await.ok();
     state.neo4j.delete_plan(plan_id).await.ok();
 }
+
+#[tokio::test]
+async fn test_neo4j_run_in_txn_rolls_back() {
+    if !backends_available().await {
+        return;
+    }
+    let config = test_config();
+    let client = project_orchestrator::neo4j::Neo4jClient::new(
+        &config.neo4j_uri,
+        &config.neo4j_user,
+        &config.neo4j_password,
+    )
+    .await
+    .unwrap();
+    let marker = Uuid::new_v4().to_string();
+
+    let result = client
+        .run_in_txn(vec![
+            neo4rs::query("CREATE (:TxnProbe {id: $id})").param("id", marker.clone()),
+            neo4rs::query("UNWIND [0] AS zero RETURN 1 / zero"),
+        ])
+        .await;
+    assert!(result.is_err());
+
+    let graph = neo4rs::Graph::new(
+        &config.neo4j_uri,
+        &config.neo4j_user,
+        &config.neo4j_password,
+    )
+    .await
+    .unwrap();
+    let mut rows = graph
+        .execute(
+            neo4rs::query("MATCH (n:TxnProbe {id: $id}) RETURN count(n) AS count")
+                .param("id", marker),
+        )
+        .await
+        .unwrap();
+    let row = rows.next().await.unwrap().unwrap();
+    assert_eq!(row.get::<i64>("count").unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_neo4j_create_task_with_steps_is_atomic() {
+    if !backends_available().await {
+        return;
+    }
+    let state = AppState::new(test_config()).await.unwrap();
+    let plan_id = setup_plan(&state).await;
+    let task = make_task("atomic", TaskStatus::Pending);
+    // The second step breaks the step_id uniqueness constraint
+    let first = make_step(0, "first");
+    let mut second = make_step(1, "second");
+    second.id = first.id;
+
+    let result = state
+        .neo4j
+        .create_task_with_steps(plan_id, &task, &[], &[first.clone(), second])
+        .await;
+    assert!(result.is_err());
+    assert!(state.neo4j.get_task(task.id).await.unwrap().is_none());
+    assert!(state.neo4j.get_step(first.id).await.unwrap().is_none());
+    assert!(state
+        .neo4j
+        .get_plan_tasks(plan_id)
+        .await
+        .unwrap()
+        .is_empty());
+
+    state.neo4j.delete_plan(plan_id).await.ok();
+}
+
+#[tokio::test]
+async fn test_neo4j_create_plan_with_constraints_is_atomic() {
+    if !backends_available().await {
+        return;
+    }
+    let state = AppState::new(test_config()).await.unwrap();
+    let plan = PlanNode::new(
+        format!("Plan {}", Uuid::new_v4()),
+        "atomic plan".to_string(),
+        "test-agent".to_string(),
+        5,
+    );
+    // The second constraint breaks the constraint_id uniqueness constraint
+    let first = ConstraintNode::new(ConstraintType::Security, "first".to_string(), None);
+    let mut second = ConstraintNode::new(ConstraintType::Style, "second".to_string(), None);
+    second.id = first.id;
+
+    let result = state
+        .neo4j
+        .create_plan_with_constraints(&plan, &[first, second])
+        .await;
+    assert!(result.is_err());
+    assert!(state.neo4j.get_plan(plan.id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_neo4j_create_plan_with_tasks_is_atomic() {
+    if !backends_available().await {
+        return;
+    }
+    let state = AppState::new(test_config()).await.unwrap();
+    let plan = PlanNode::new(
+        format!("Plan {}", Uuid::new_v4()),
+        "atomic plan with tasks".to_string(),
+        "test-agent".to_string(),
+        5,
+    );
+    let first = make_task("first", TaskStatus::Pending);
+    let step = make_step(0, "only");
+    // The last task breaks the task_id uniqueness constraint
+    let mut last = make_task("last", TaskStatus::Pending);
+    last.id = first.id;
+    let tasks = vec![
+        NewPlanTask {
+            task: first.clone(),
+            depends_on: vec![],
+            steps: vec![step.clone()],
+        },
+        NewPlanTask {
+            task: last,
+            depends_on: vec![first.id],
+            steps: vec![],
+        },
+    ];
+
+    let result = state.neo4j.create_plan_with_tasks(&plan, &[], &tasks).await;
+    assert!(result.is_err());
+    assert!(state.neo4j.get_plan(plan.id).await.unwrap().is_none());
+    assert!(state.neo4j.get_task(first.id).await.unwrap().is_none());
+    assert!(state
+        .neo4j
+        .get_task_steps(first.id)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_neo4j_delete_plan_cascades() {
+    if !backends_available().await {
+        return;
+    }
+    let state = AppState::new(test_config()).await.unwrap();
+    let plan = PlanNode::new(
+        format!("Plan {}", Uuid::new_v4()),
+        "cascade plan".to_string(),
+        "test-agent".to_string(),
+        5,
+    );
+    let constraint = ConstraintNode::new(ConstraintType::Performance, "fast".to_string(), None);
+    state
+        .neo4j
+        .create_plan_with_constraints(&plan, std::slice::from_ref(&constraint))
+        .await
+        .unwrap();
+    let task = make_task("cascade", TaskStatus::Pending);
+    let step = make_step(0, "only");
+    state
+        .neo4j
+        .create_task_with_steps(plan.id, &task, &[], std::slice::from_ref(&step))
+        .await
+        .unwrap();
+
+    state.neo4j.delete_plan(plan.id).await.unwrap();
+
+    assert!(state.neo4j.get_plan(plan.id).await.unwrap().is_none());
+    assert!(state.neo4j.get_task(task.id).await.unwrap().is_none());
+    assert!(state.neo4j.get_step(step.id).await.unwrap().is_none());
+    assert!(state
+        .neo4j
+        .get_plan_constraints(plan.id)
+        .await
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,57 @@
+//! API snapshot tests over the in-memory test kit
+//!
+//! These tests don't require external services.
+//! Run with: cargo test --features testkit --test testkit_snapshots
+//! Record changed snapshots with UPDATE_SNAPSHOTS=1.
+
+use project_orchestrator::testkit::{assert_json_snapshot, TestApp};
+use serde_json::json;
+
+async fn create_note(app: &TestApp) -> String {
+    let resp = app
+        .post(
+            "/api/notes",
+            json!({
+                "note_type": "gotcha",
+                "content": "The sync job skips symlinked directories.",
+                "importance": "low"
+            }),
+        )
+        .await;
+    assert_eq!(resp.status, 201);
+    resp.body["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_comment_on_note() {
+    let app = TestApp::builder().build().await;
+    let note_id = create_note(&app).await;
+
+    let resp = app
+        .post(
+            "/api/comments",
+            json!({
+                "entity_type": "note",
+                "entity_id": note_id,
+                "content": "Ping @alice about the flaky test"
+            }),
+        )
+        .await;
+    assert_eq!(resp.status, 201);
+    assert_json_snapshot!("comment_on_note", resp.body);
+}
+
+#[tokio::test]
+async fn test_endorse_note() {
+    let app = TestApp::builder().build().await;
+    let note_id = create_note(&app).await;
+
+    let resp = app
+        .post(
+            &format!("/api/notes/{}/endorsements", note_id),
+            json!({"kind": "ack"}),
+        )
+        .await;
+    assert_eq!(resp.status, 200);
+    assert_json_snapshot!("endorse_note", resp.body);
+}
@@ -15,17 +15,27 @@ fn test_config() -> Config {
         neo4j_user: std::env::var("NEO4J_USER").unwrap_or_else(|_| "neo4j".into()),
         neo4j_password: std::env::var("NEO4J_PASSWORD")
             .unwrap_or_else(|_| "orchestrator123".into()),
+        neo4j_query_timeout_ms: 60_000,
+        neo4j_slow_query_ms: 100,
         meilisearch_url: std::env::var("MEILISEARCH_URL")
             .unwrap_or_else(|_| "http://localhost:7700".into()),
         meilisearch_key: std::env::var("MEILISEARCH_KEY")
             .unwrap_or_else(|_| "orchestrator-meili-key-change-me".into()),
+        // Index synchronously: tests search right after syncing
+        meilisearch_index_queue_path: Some(String::new()),
         nats_url: None,
+        nats_jetstream: false,
+        nats_consumer: None,
+        ipc_enabled: false,
+        ipc_path: None,
         workspace_path: ".".into(),
+        server_host: "0.0.0.0".into(),
         server_port: 8080,
         auth_config: None,
         serve_frontend: false,
         frontend_path: "./dist".to_string(),
         public_url: None,
+        read_only: false,
         chat_permissions: None,
         chat_default_model: None,
         chat_max_sessions: None,
@@ -46,6 +56,11 @@ fn test_config() -> Config {
         registry_remote_url: None,
         neural_routing: Default::default(),
         config_yaml_path: None,
+        headless: false,
+        update_channel: Default::default(),
+        update_public_key: None,
+        log_format: Default::default(),
+        log_filter: String::new(),
     }
 }
 
@@ -798,6 +813,7 @@ async fn test_workspace_milestone_task_association() {
         project_id: None,
         execution_context: None,
         persona: None,
+        version: 0,
     };
     state.neo4j.create_plan(&plan).await.unwrap();
 
@@ -821,6 +837,8 @@ async fn test_workspace_milestone_task_association() {
         execution_context: None,
         persona: None,
         prompt_cache: None,
+        board_position: None,
+        version: 0,
     };
     state.neo4j.create_task(plan.id, &task).await.unwrap();
 